@@ -0,0 +1,45 @@
+//! Screen-reader events for the browser, built from egui's widget output events.
+
+use eframe::egui;
+use egui::output::OutputEvent;
+use shared::{AccessibilityEvent, AccessibilityEventKind};
+
+/// Focus, click and value-change events emitted by widgets during the current frame.
+pub fn collect_events(ctx: &egui::Context) -> Vec<AccessibilityEvent> {
+    ctx.output(|o| o.events.iter().filter_map(to_event).collect())
+}
+
+fn to_event(event: &OutputEvent) -> Option<AccessibilityEvent> {
+    let (kind, info) = match event {
+        OutputEvent::FocusGained(info) => (AccessibilityEventKind::Focus, info),
+        OutputEvent::Clicked(info) | OutputEvent::DoubleClicked(info) => (AccessibilityEventKind::Click, info),
+        OutputEvent::ValueChanged(info) => (AccessibilityEventKind::ValueChanged, info),
+        _ => return None,
+    };
+    let value = info
+        .current_text_value
+        .clone()
+        .or_else(|| info.selected.map(|s| if s { "selected" } else { "not selected" }.to_string()));
+    Some(AccessibilityEvent {
+        kind,
+        role: role(info.typ).to_string(),
+        label: info.label.clone(),
+        value,
+    })
+}
+
+fn role(typ: egui::WidgetType) -> &'static str {
+    use egui::WidgetType;
+    match typ {
+        WidgetType::Label => "label",
+        WidgetType::Link => "link",
+        WidgetType::TextEdit => "text-edit",
+        WidgetType::Button => "button",
+        WidgetType::Checkbox => "checkbox",
+        WidgetType::RadioButton => "radio",
+        WidgetType::SelectableLabel => "option",
+        WidgetType::ComboBox => "combo-box",
+        WidgetType::Slider | WidgetType::DragValue => "slider",
+        _ => "other",
+    }
+}
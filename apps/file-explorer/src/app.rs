@@ -1,76 +1,214 @@
 use eframe::egui;
+use shared::archive::{self, ExtractLimits};
+use shared::i18n::{tr, Locale};
+use shared::listing::{self, DirectoryPages, FileEntry, FileOwner, SortKey};
+use shared::transfer::Chunks;
+use shared::{AppMessage, ArchiveFormat, CrashRecorder, DirectoryWatcher, FrameScheduler, IpcClient, PlatformMessage};
+
+use crate::accessibility;
+use std::collections::{HashMap, HashSet};
+use std::ffi::OsStr;
 use std::fs;
-use std::path::PathBuf;
-
-#[derive(Default)]
-pub struct FileItem {
-    pub name: String,
-    pub path: PathBuf,
-    pub is_dir: bool,
-    pub size: u64,
-}
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Component, Path, PathBuf};
+use std::sync::mpsc;
+use std::time::{Duration, Instant, SystemTime};
 
 pub struct FileExplorerApp {
     pub search_query: String,
     pub root_path: PathBuf,
     pub current_path: PathBuf,
-    pub items: Vec<FileItem>,
+    pub items: Vec<FileEntry>,
     pub selected_index: Option<usize>,
+    /// Columns with modification time, type and owner instead of names only
+    pub details_view: bool,
+    pub sort_key: SortKey,
+    pub sort_ascending: bool,
+    /// Folders listed whose size the platform measured; the others show none yet
+    pub measured_folders: HashSet<PathBuf>,
+    /// Id of the last folder size query sent
+    pub size_requests: u64,
     pub error_message: Option<String>,
     pub allowed_paths: Vec<PathBuf>,
+    pub locale: Locale,
+    /// Connection to the platform, if the app runs inside a session
+    pub ipc: Option<IpcClient>,
+    /// Messages from the platform, read on a background thread
+    pub platform_rx: Option<mpsc::Receiver<PlatformMessage>>,
+    /// The platform forbids file transfers for this session
+    pub view_only: bool,
+    /// Pages of the folder being listed in the background; closed once it is all listed
+    pub listing_task: Option<mpsc::Receiver<Result<Vec<FileEntry>, String>>>,
+    /// Notices entries of the current folder changing, such as uploads from another session
+    pub watcher: Option<DirectoryWatcher>,
+    /// When the changed folder is listed again, once changes stop arriving in a burst
+    pub refresh_due: Option<Instant>,
+    /// Listing of the changed folder, kept apart until complete so the view does not empty
+    pub refresh_task: Option<mpsc::Receiver<Result<Vec<FileEntry>, String>>>,
+    pub fresh_items: Vec<FileEntry>,
+    /// When a change last showed up, for the "updated" hint
+    pub updated_at: Option<Instant>,
+    /// Result of the archive operation running in the background, if any
+    pub archive_task: Option<mpsc::Receiver<Result<(), String>>>,
+    /// `Ready` went to the platform, after the first frame was laid out
+    pub ready_sent: bool,
+    /// Keeps passes to input, animations and idle polling, and times them
+    pub frames: FrameScheduler,
+    /// Recent input and frame count, for the crash report if the app panics
+    pub crashes: CrashRecorder,
 }
 
-impl Default for FileExplorerApp {
-    fn default() -> Self {
+/// Key of the saved state holding the folder being browsed, reopened on the next launch
+pub const LAST_PATH_KEY: &str = "last_path";
+
+/// Entries read in detail at a time while listing a folder. The first page shows while the
+/// rest of a huge folder is still being read.
+const LISTING_PAGE: usize = 500;
+
+/// Width of each details column, the same for the header and the rows so they line up
+const COLUMN_WIDTH: f32 = 150.0;
+
+/// Wait after a change before listing the folder again, so a burst of changes (a copy of many
+/// files) costs one listing
+const REFRESH_DELAY: Duration = Duration::from_millis(500);
+
+/// How long the "updated" hint stays after a refresh
+const UPDATED_HINT: Duration = Duration::from_secs(3);
+
+/// Columns of the details view, in order, with their header's catalog key
+const COLUMNS: [(SortKey, &str); 5] = [
+    (SortKey::Name, "explorer.column_name"),
+    (SortKey::Size, "explorer.column_size"),
+    (SortKey::Modified, "explorer.column_modified"),
+    (SortKey::Type, "explorer.column_type"),
+    (SortKey::Owner, "explorer.column_owner"),
+];
+
+/// Archive actions offered in an item's context menu.
+enum ArchiveAction {
+    Compress(PathBuf),
+    Extract(PathBuf),
+}
+
+impl FileExplorerApp {
+    pub fn new(
+        locale: Locale,
+        mut ipc: Option<IpcClient>,
+        view_only: bool,
+        allowed_paths: Vec<String>,
+        last_path: Option<PathBuf>,
+        crashes: CrashRecorder,
+    ) -> Self {
         let root_path = std::env::var("ROOT_PATH")
             .map(PathBuf::from)
             .unwrap_or_else(|_| PathBuf::from("/"));
 
-        let allowed_paths: Vec<PathBuf> = std::env::var("ALLOWED_PATHS")
-            .map(|s| s.split(':').map(PathBuf::from).collect())
-            .unwrap_or_default();
+        // The platform names them in `Init`; without a session, only the environment may
+        let allowed_paths: Vec<PathBuf> = if allowed_paths.is_empty() {
+            std::env::var("ALLOWED_PATHS")
+                .map(|s| s.split(':').map(PathBuf::from).collect())
+                .unwrap_or_default()
+        } else {
+            allowed_paths.into_iter().map(PathBuf::from).collect()
+        };
+
+        let platform_rx = ipc.as_mut().and_then(|client| match client.listen() {
+            Ok(rx) => Some(rx),
+            Err(e) => {
+                eprintln!("Cannot listen for platform messages: {}", e);
+                None
+            }
+        });
+
+        let watcher = match DirectoryWatcher::new() {
+            Ok(watcher) => Some(watcher),
+            Err(e) => {
+                eprintln!("Cannot watch folders for changes: {}", e);
+                None
+            }
+        };
 
         let current_path = root_path.clone();
-        let (items, error_message) = load_directory(&current_path);
-        Self {
+        let mut app = Self {
             search_query: String::new(),
             root_path,
             current_path,
-            items,
+            items: Vec::new(),
             selected_index: None,
-            error_message,
+            details_view: false,
+            sort_key: SortKey::Name,
+            sort_ascending: true,
+            measured_folders: HashSet::new(),
+            size_requests: 0,
+            error_message: None,
             allowed_paths,
+            locale,
+            ipc,
+            platform_rx,
+            view_only,
+            listing_task: None,
+            watcher,
+            refresh_due: None,
+            refresh_task: None,
+            fresh_items: Vec::new(),
+            updated_at: None,
+            archive_task: None,
+            ready_sent: false,
+            frames: FrameScheduler::default(),
+            crashes,
+        };
+        // The folder may be gone, or outside what this session may see
+        match last_path.filter(|path| path.is_dir() && app.is_accessible(path)) {
+            Some(path) => app.navigate(path),
+            None => app.start_listing(),
         }
+        app
     }
 }
 
-fn load_directory(path: &PathBuf) -> (Vec<FileItem>, Option<String>) {
-    match fs::read_dir(path) {
-        Ok(entries) => {
-            let mut items: Vec<FileItem> = entries
-                .filter_map(|entry| entry.ok())
-                .map(|entry| {
-                    let metadata = entry.metadata().ok();
-                    let is_dir = metadata.as_ref().map(|m| m.is_dir()).unwrap_or(false);
-                    let size = metadata.as_ref().map(|m| m.len()).unwrap_or(0);
-                    FileItem {
-                        name: entry.file_name().to_string_lossy().into_owned(),
-                        path: entry.path(),
-                        is_dir,
-                        size,
-                    }
-                })
-                .collect();
-            // Directories first, then files, both alphabetically
-            items.sort_by(|a, b| {
-                b.is_dir
-                    .cmp(&a.is_dir)
-                    .then(a.name.to_lowercase().cmp(&b.name.to_lowercase()))
-            });
-            (items, None)
+/// List `path` a page at a time on a worker thread, which stops once nobody reads the pages.
+fn list_in_background(path: PathBuf, locale: Locale) -> mpsc::Receiver<Result<Vec<FileEntry>, String>> {
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut pages = match DirectoryPages::open(&path) {
+            Ok(pages) => pages,
+            Err(e) => {
+                let _ = tx.send(Err(format!("{} {}: {}", tr(locale, "explorer.read_error"), path.display(), e)));
+                return;
+            }
+        };
+        loop {
+            let page = pages.next_page(LISTING_PAGE);
+            let done = pages.cursor().is_none();
+            // Nobody is waiting any more: the user went elsewhere
+            if !page.is_empty() && tx.send(Ok(page)).is_err() {
+                return;
+            }
+            if done {
+                return;
+            }
         }
-        Err(e) => (Vec::new(), Some(format!("Error reading {}: {}", path.display(), e))),
+    });
+    rx
+}
+
+/// A modification time in the session's time zone, which the platform sets as `TZ`, written
+/// the way the user's locale does.
+fn format_modified(modified: SystemTime, locale: Locale) -> String {
+    let local: chrono::DateTime<chrono::Local> = modified.into();
+    let pattern = match locale {
+        Locale::En => "%m/%d/%Y %-I:%M %p",
+        Locale::Fr => "%d/%m/%Y %H:%M",
+    };
+    local.format(pattern).to_string()
+}
+
+/// Size of an item as shown, empty for a folder not measured yet
+fn size_text(item: &FileEntry, measured_folders: &HashSet<PathBuf>, locale: Locale) -> String {
+    if item.is_dir && !measured_folders.contains(&item.path) {
+        return String::new();
     }
+    format!("{} {}", item.size, tr(locale, "explorer.bytes"))
 }
 
 impl FileExplorerApp {
@@ -98,37 +236,448 @@ impl FileExplorerApp {
 
     fn navigate(&mut self, path: PathBuf) {
         if !self.is_accessible(&path) {
-            self.error_message = Some(format!("Access denied: {}", path.display()));
+            self.error_message = Some(format!(
+                "{}: {}",
+                tr(self.locale, "explorer.access_denied"),
+                path.display()
+            ));
             return;
         }
-        let (items, err) = load_directory(&path);
         self.current_path = path;
+        self.search_query.clear();
+        self.save_last_path();
+        self.start_listing();
+    }
+
+    /// List the current folder again, a page at a time on a worker thread, so a folder with
+    /// tens of thousands of entries neither freezes the window nor waits to show its first
+    /// entries. A listing still running for a folder left is dropped.
+    fn start_listing(&mut self) {
+        self.items.clear();
+        self.selected_index = None;
+        self.measured_folders.clear();
+        self.error_message = None;
+        self.refresh_due = None;
+        self.refresh_task = None;
+        self.fresh_items.clear();
+        self.updated_at = None;
+        if let Some(watcher) = self.watcher.as_mut() {
+            if let Err(e) = watcher.watch(&self.current_path) {
+                eprintln!("Cannot watch {}: {}", self.current_path.display(), e);
+            }
+        }
+        self.listing_task = Some(list_in_background(self.current_path.clone(), self.locale));
+    }
+
+    /// Add the pages listed since the last pass, in the chosen order.
+    fn poll_listing(&mut self) {
+        let Some(rx) = &self.listing_task else {
+            return;
+        };
+        let mut pages = Vec::new();
+        let finished = loop {
+            match rx.try_recv() {
+                Ok(page) => pages.push(page),
+                Err(mpsc::TryRecvError::Empty) => break false,
+                Err(mpsc::TryRecvError::Disconnected) => break true,
+            }
+        };
+        if finished {
+            self.listing_task = None;
+        }
+        for page in pages {
+            match page {
+                Ok(entries) => {
+                    self.request_folder_sizes(&entries);
+                    self.items.extend(entries);
+                }
+                Err(e) => self.error_message = Some(e),
+            }
+        }
+        // Pages arrive in name order already
+        if self.sort_key != SortKey::Name || !self.sort_ascending {
+            self.sort_items();
+        }
+    }
+
+    /// Schedule a new listing of the current folder when it changed, and start it once the
+    /// changes have settled and no other listing is running.
+    fn poll_watcher(&mut self) {
+        let Some(watcher) = self.watcher.as_mut() else {
+            return;
+        };
+        if watcher.changed() && self.refresh_due.is_none() {
+            self.refresh_due = Some(Instant::now() + REFRESH_DELAY);
+        }
+        let due = self.refresh_due.is_some_and(|due| due <= Instant::now());
+        if due && self.listing_task.is_none() && self.refresh_task.is_none() {
+            self.refresh_due = None;
+            self.fresh_items.clear();
+            self.refresh_task = Some(list_in_background(self.current_path.clone(), self.locale));
+        }
+    }
+
+    /// Swap in the new listing of a changed folder once it is complete. The selection and the
+    /// folder sizes already measured carry over; only new folders are measured.
+    fn poll_refresh(&mut self) {
+        let Some(rx) = &self.refresh_task else {
+            return;
+        };
+        loop {
+            match rx.try_recv() {
+                Ok(Ok(entries)) => self.fresh_items.extend(entries),
+                Ok(Err(e)) => {
+                    // The folder itself went away; say so, as a navigation would
+                    self.error_message = Some(e);
+                    self.refresh_task = None;
+                    return;
+                }
+                Err(mpsc::TryRecvError::Empty) => return,
+                Err(mpsc::TryRecvError::Disconnected) => break,
+            }
+        }
+        self.refresh_task = None;
+
+        let measured: HashMap<PathBuf, u64> = self
+            .items
+            .iter()
+            .filter(|item| self.measured_folders.contains(&item.path))
+            .map(|item| (item.path.clone(), item.size))
+            .collect();
+        let mut items = std::mem::take(&mut self.fresh_items);
+        for item in items.iter_mut().filter(|item| item.is_dir) {
+            if let Some(&size) = measured.get(&item.path) {
+                item.size = size;
+            }
+        }
+        let unmeasured: Vec<FileEntry> = items.iter().filter(|item| item.is_dir && !measured.contains_key(&item.path)).cloned().collect();
+        self.measured_folders.retain(|path| items.iter().any(|item| &item.path == path));
+
+        let selected = self.selected_index.and_then(|idx| self.items.get(idx)).map(|item| item.path.clone());
         self.items = items;
-        self.error_message = err;
         self.selected_index = None;
-        self.search_query.clear();
+        self.request_folder_sizes(&unmeasured);
+        listing::sort(&mut self.items, self.sort_key, self.sort_ascending);
+        self.selected_index = selected.and_then(|path| self.items.iter().position(|item| item.path == path));
+        self.updated_at = Some(Instant::now());
+    }
+
+    /// Ask the platform to measure each newly listed folder. Walking a folder can take a while,
+    /// so the answers arrive in the background and fill in sizes as they come.
+    fn request_folder_sizes(&mut self, entries: &[FileEntry]) {
+        let Some(ipc) = self.ipc.as_mut() else {
+            return;
+        };
+        for item in entries.iter().filter(|item| item.is_dir) {
+            let Ok(relative) = item.path.strip_prefix(&self.root_path) else {
+                continue;
+            };
+            self.size_requests += 1;
+            let query = AppMessage::FolderSize { request_id: self.size_requests, path: relative.to_string_lossy().into_owned() };
+            if let Err(e) = ipc.send(&query) {
+                eprintln!("IPC send failed, disabling: {}", e);
+                self.ipc = None;
+                return;
+            }
+        }
+    }
+
+    /// Order the items by the chosen column, keeping the selected one selected.
+    fn sort_items(&mut self) {
+        let selected = self.selected_index.and_then(|idx| self.items.get(idx)).map(|item| item.path.clone());
+        listing::sort(&mut self.items, self.sort_key, self.sort_ascending);
+        self.selected_index = selected.and_then(|path| self.items.iter().position(|item| item.path == path));
+    }
+
+    /// Remember the current folder for the next launch.
+    fn save_last_path(&mut self) {
+        let Some(ipc) = self.ipc.as_mut() else {
+            return;
+        };
+        let value = serde_json::Value::String(self.current_path.to_string_lossy().into_owned());
+        if let Err(e) = ipc.send(&AppMessage::SaveState { key: LAST_PATH_KEY.to_string(), value }) {
+            eprintln!("IPC send failed, disabling: {}", e);
+            self.ipc = None;
+        }
+    }
+}
+
+impl FileExplorerApp {
+    /// Download and upload are only offered inside a session that allows them.
+    fn transfers_enabled(&self) -> bool {
+        self.ipc.is_some() && !self.view_only
+    }
+
+    /// Send the file to the platform in chunks, which it hands to the browser. `resume` is the
+    /// offset and file version of an interrupted transfer to continue.
+    fn download(&mut self, path: PathBuf, resume: Option<(u64, String)>) {
+        if !self.transfers_enabled() {
+            return;
+        }
+        let Some(ipc) = self.ipc.as_mut() else {
+            return;
+        };
+        let (offset, etag) = resume.map_or((0, None), |(offset, etag)| (offset, Some(etag)));
+        let result = Chunks::open(&self.root_path, &path, offset, etag.as_deref())
+            .map_err(|e| e.to_string())
+            .and_then(|mut chunks| {
+                chunks.try_for_each(|chunk| {
+                    let chunk = chunk.map_err(|e| e.to_string())?;
+                    ipc.send(&chunk).map_err(|e| e.to_string())
+                })
+            });
+        if let Err(e) = result {
+            self.error_message = Some(format!("{}: {}", tr(self.locale, "explorer.download_failed"), e));
+        }
+    }
+
+    /// Act on requests the platform relayed from the browser.
+    fn handle_platform_messages(&mut self) {
+        let Some(rx) = &self.platform_rx else {
+            return;
+        };
+        let messages: Vec<PlatformMessage> = rx.try_iter().collect();
+        for msg in messages {
+            match msg {
+                // The path is the vault-relative one the transfer was announced with
+                PlatformMessage::ResumeDownload { path, offset, etag } => {
+                    let relative = Path::new(&path);
+                    if !relative.components().all(|c| matches!(c, Component::Normal(_))) {
+                        continue;
+                    }
+                    let path = self.root_path.join(relative);
+                    if self.is_accessible(&path) {
+                        self.download(path, Some((offset, etag)));
+                    }
+                }
+                // The folder being browsed is all there is to restore
+                PlatformMessage::Suspend => {
+                    let state = self.current_path.as_os_str().as_bytes().to_vec();
+                    if let Some(ipc) = self.ipc.as_mut() {
+                        if let Err(e) = ipc.send(&AppMessage::SuspendState { state }) {
+                            eprintln!("IPC send failed, disabling: {}", e);
+                            self.ipc = None;
+                        }
+                    }
+                }
+                PlatformMessage::Resume { state } => {
+                    let path = PathBuf::from(OsStr::from_bytes(&state));
+                    if path.is_dir() {
+                        self.navigate(path);
+                    }
+                }
+                // Answers for a folder left since are for entries no longer listed
+                PlatformMessage::ScopeChanged { allowed_paths } => {
+                    self.allowed_paths = allowed_paths.into_iter().map(PathBuf::from).collect();
+                    // A folder no longer shared is left for the root, which lists what still is
+                    if !self.is_accessible(&self.current_path) {
+                        self.navigate(self.root_path.clone());
+                    } else if self.selected_index.and_then(|idx| self.items.get(idx)).is_some_and(|item| !self.is_accessible(&item.path)) {
+                        self.selected_index = None;
+                    }
+                }
+                PlatformMessage::FolderSize { path, size: Some(size), .. } => {
+                    let path = self.root_path.join(path);
+                    if let Some(item) = self.items.iter_mut().find(|item| item.is_dir && item.path == path) {
+                        item.size = size;
+                        self.measured_folders.insert(path);
+                        if self.sort_key == SortKey::Size {
+                            self.sort_items();
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Compress or extract on a worker thread, so large archives do not freeze the window.
+    fn start_archive(&mut self, action: ArchiveAction) {
+        if self.archive_task.is_some() {
+            return;
+        }
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let result = match action {
+                ArchiveAction::Compress(path) => compress(&path),
+                ArchiveAction::Extract(path) => extract(&path),
+            };
+            let _ = tx.send(result);
+        });
+        self.archive_task = Some(rx);
+    }
+
+    /// Pick up a finished archive operation and show its outcome.
+    fn poll_archive(&mut self) {
+        let Some(rx) = &self.archive_task else {
+            return;
+        };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => Err(tr(self.locale, "explorer.archive_failed").to_string()),
+        };
+        self.archive_task = None;
+        self.start_listing();
+        if let Err(e) = result {
+            self.error_message = Some(format!("{}: {}", tr(self.locale, "explorer.archive_failed"), e));
+        }
+    }
+
+    /// Tell the platform the first screen is drawn, so the client drops its loading state.
+    fn send_ready_once(&mut self) {
+        if self.ready_sent {
+            return;
+        }
+        self.ready_sent = true;
+        if let Some(ipc) = self.ipc.as_mut() {
+            if let Err(e) = ipc.send(&AppMessage::Ready) {
+                eprintln!("IPC send failed, disabling: {}", e);
+                self.ipc = None;
+            }
+        }
+    }
+
+    /// Ask for another pass only when background work needs polling; input and animations
+    /// (which leave egui needing a repaint) wake the app on their own. Reports render times
+    /// to the platform every few seconds.
+    fn schedule_next_frame(&mut self, ctx: &egui::Context, frame: &eframe::Frame, had_input: bool) {
+        let active = had_input || ctx.has_requested_repaint();
+        let polling = self.platform_rx.is_some()
+            || self.listing_task.is_some()
+            || self.watcher.is_some()
+            || self.archive_task.is_some();
+        let cpu = frame.info().cpu_usage.map(Duration::from_secs_f32);
+        if let Some(delay) = self.frames.frame(cpu, active, polling) {
+            ctx.request_repaint_after(delay);
+        }
+        let Some(stats) = self.frames.report(Instant::now()) else {
+            return;
+        };
+        if let Some(ipc) = self.ipc.as_mut() {
+            if let Err(e) = ipc.send(&AppMessage::RenderStats { stats }) {
+                eprintln!("IPC send failed, disabling: {}", e);
+                self.ipc = None;
+            }
+        }
+    }
+
+    /// Note this pass and its input for a crash report.
+    fn record_activity(&self, ctx: &egui::Context) {
+        ctx.input(|i| {
+            for description in i.events.iter().filter_map(describe_input) {
+                self.crashes.input(description);
+            }
+        });
+        self.crashes.frame();
+    }
+
+    /// Forward this frame's widget events so the browser can announce them.
+    fn send_accessibility_events(&mut self, ctx: &egui::Context) {
+        let Some(ipc) = self.ipc.as_mut() else {
+            return;
+        };
+        let events = accessibility::collect_events(ctx);
+        if events.is_empty() {
+            return;
+        }
+        if let Err(e) = ipc.send(&AppMessage::Accessibility { events }) {
+            eprintln!("IPC send failed, disabling: {}", e);
+            self.ipc = None;
+        }
+    }
+}
+
+/// First name next to `path` built from `stem` and `extension` that is not taken yet.
+fn free_sibling(path: &Path, stem: &str, extension: Option<&str>) -> PathBuf {
+    let parent = path.parent().unwrap_or(Path::new("/"));
+    let name = |n: usize| {
+        let base = if n == 0 { stem.to_string() } else { format!("{stem} ({n})") };
+        match extension {
+            Some(ext) => format!("{base}.{ext}"),
+            None => base,
+        }
+    };
+    (0..)
+        .map(|n| parent.join(name(n)))
+        .find(|candidate| fs::symlink_metadata(candidate).is_err())
+        .unwrap_or_default()
+}
+
+fn file_stem(path: &Path) -> String {
+    path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default()
+}
+
+fn compress(path: &Path) -> Result<(), String> {
+    let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    let target = free_sibling(path, &name, Some(ArchiveFormat::Zip.extension()));
+    let result = fs::File::create(&target)
+        .map(std::io::BufWriter::new)
+        .and_then(|file| archive::create(ArchiveFormat::Zip, file, &[path.to_path_buf()]))
+        .and_then(|mut file| std::io::Write::flush(&mut file));
+    if result.is_err() {
+        let _ = fs::remove_file(&target);
+    }
+    result.map_err(|e| e.to_string())
+}
+
+fn extract(path: &Path) -> Result<(), String> {
+    let target = free_sibling(path, &file_stem(path), None);
+    archive::extract(path, &target, ExtractLimits::default())
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// A crash report line for an input event. Typed text and character keys are left out, since
+/// they may spell out a password.
+fn describe_input(event: &egui::Event) -> Option<String> {
+    let state = |pressed: bool| if pressed { "down" } else { "up" };
+    match event {
+        egui::Event::Key { key, pressed, .. } => {
+            let name = if key.name().chars().count() > 1 { key.name() } else { "character" };
+            Some(format!("key {} {}", name, state(*pressed)))
+        }
+        egui::Event::PointerButton { pos, button, pressed, .. } => {
+            Some(format!("{:?} button {} at ({:.0}, {:.0})", button, state(*pressed), pos.x, pos.y))
+        }
+        egui::Event::MouseWheel { delta, .. } => Some(format!("wheel ({:.1}, {:.1})", delta.x, delta.y)),
+        egui::Event::Text(_) => Some("text input".to_string()),
+        egui::Event::Paste(_) => Some("paste".to_string()),
+        egui::Event::Copy => Some("copy".to_string()),
+        egui::Event::Cut => Some("cut".to_string()),
+        _ => None,
     }
 }
 
 impl eframe::App for FileExplorerApp {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        // Hide the mouse cursor
-        ctx.set_cursor_icon(egui::CursorIcon::None);
+    fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
+        let locale = self.locale;
+        let had_input = ctx.input(|i| !i.events.is_empty() || i.pointer.is_moving());
+        self.record_activity(ctx);
+        self.handle_platform_messages();
+        self.poll_listing();
+        self.poll_watcher();
+        self.poll_refresh();
+        self.poll_archive();
         egui::CentralPanel::default().show(ctx, |ui| {
-            ui.heading("File Explorer");
+            ui.heading(tr(locale, "explorer.title"));
+            if self.view_only {
+                ui.label(tr(locale, "explorer.view_only"));
+            }
             ui.separator();
             ui.horizontal(|ui| {
-                ui.label("Search:");
+                ui.label(tr(locale, "explorer.search"));
                 ui.text_edit_singleline(&mut self.search_query);
+                ui.checkbox(&mut self.details_view, tr(locale, "explorer.details"));
             });
             ui.separator();
 
             // Breadcrumb showing path relative to root
             ui.horizontal(|ui| {
-                ui.label("Path:");
+                ui.label(tr(locale, "explorer.path"));
                 ui.label(self.display_path());
                 if self.current_path != self.root_path {
-                    if ui.button("↑ Up").clicked() {
+                    if ui.button(tr(locale, "explorer.up")).clicked() {
                         if let Some(parent) = self.current_path.parent().map(PathBuf::from) {
                             let parent_clone = parent.clone();
                             self.navigate(parent_clone);
@@ -138,52 +687,163 @@ impl eframe::App for FileExplorerApp {
             });
             ui.separator();
 
-            egui::ScrollArea::vertical().show(ui, |ui| {
-                let mut navigate_to: Option<PathBuf> = None;
+            let mut navigate_to: Option<PathBuf> = None;
+            let mut download: Option<PathBuf> = None;
+            let mut archive_action: Option<ArchiveAction> = None;
+            let mut sort_by: Option<SortKey> = None;
+            let transfers_enabled = self.transfers_enabled();
+            let archive_idle = self.archive_task.is_none();
+            // Both archive actions write into the session's folders
+            let view_only = self.view_only;
+            let (details_view, sort_key, sort_ascending) = (self.details_view, self.sort_key, self.sort_ascending);
 
-                for (idx, item) in self.items.iter().enumerate() {
-                    if !self.search_query.is_empty()
-                        && !item
-                            .name
-                            .to_lowercase()
-                            .contains(&self.search_query.to_lowercase())
-                    {
-                        continue;
-                    }
-                    // Hide items outside allowed_paths when ALLOWED_PATHS is set
-                    if !self.allowed_paths.is_empty() && !self.is_accessible(&item.path) {
-                        continue;
-                    }
+            let query = self.search_query.to_lowercase();
+            let visible: Vec<usize> = self
+                .items
+                .iter()
+                .enumerate()
+                .filter(|(_, item)| query.is_empty() || item.name.to_lowercase().contains(&query))
+                // Hide items outside allowed_paths when ALLOWED_PATHS is set
+                .filter(|(_, item)| self.allowed_paths.is_empty() || self.is_accessible(&item.path))
+                .map(|(idx, _)| idx)
+                .collect();
+
+            if details_view {
+                egui::Grid::new("file_details_header")
+                    .num_columns(5)
+                    .min_col_width(COLUMN_WIDTH)
+                    .max_col_width(COLUMN_WIDTH)
+                    .show(ui, |ui| {
+                        for (key, label) in COLUMNS {
+                            let arrow = match (key == sort_key, sort_ascending) {
+                                (false, _) => "",
+                                (true, true) => " ▲",
+                                (true, false) => " ▼",
+                            };
+                            if ui.button(format!("{}{}", tr(locale, label), arrow)).clicked() {
+                                sort_by = Some(key);
+                            }
+                        }
+                        ui.end_row();
+                    });
+            }
+
+            // Only the rows scrolled into view are laid out, however many the folder holds
+            let row_height = ui.spacing().interact_size.y;
+            egui::ScrollArea::vertical().show_rows(ui, row_height, visible.len(), |ui, range| {
+                let mut rows = |ui: &mut egui::Ui| for &idx in &visible[range.clone()] {
+                    let item = &self.items[idx];
                     let is_selected = self.selected_index == Some(idx);
                     let icon = if item.is_dir { "[D]" } else { "[F]" };
                     let label = format!("{} {}", icon, item.name);
-                    let response = ui.selectable_label(is_selected, &label);
+                    let mut response = ui.selectable_label(is_selected, &label);
+                    if let Some(modified) = item.modified {
+                        response = response.on_hover_text(format_modified(modified, locale));
+                    }
                     if response.clicked() {
                         self.selected_index = Some(idx);
                     }
                     if response.double_clicked() && item.is_dir {
                         navigate_to = Some(item.path.clone());
                     }
-                }
+                    response.context_menu(|ui| {
+                        if transfers_enabled && !item.is_dir && ui.button(tr(locale, "explorer.download")).clicked() {
+                            download = Some(item.path.clone());
+                        }
+                        if view_only {
+                            return;
+                        }
+                        ui.add_enabled_ui(archive_idle, |ui| {
+                            if ui.button(tr(locale, "explorer.compress")).clicked() {
+                                archive_action = Some(ArchiveAction::Compress(item.path.clone()));
+                            }
+                            if !item.is_dir
+                                && ArchiveFormat::from_path(&item.path).is_some()
+                                && ui.button(tr(locale, "explorer.extract")).clicked()
+                            {
+                                archive_action = Some(ArchiveAction::Extract(item.path.clone()));
+                            }
+                        });
+                    });
+                    if details_view {
+                        // Truncated rather than wrapped, so every row keeps the height scrolling assumes
+                        let owner = match item.owner {
+                            FileOwner::Session => tr(locale, "explorer.owner_session").to_string(),
+                            FileOwner::Other(uid) => format!("uid {}", uid),
+                        };
+                        let modified = item.modified.map(|modified| format_modified(modified, locale)).unwrap_or_default();
+                        for text in [size_text(item, &self.measured_folders, locale), modified, item.mime_type.to_string(), owner] {
+                            ui.add(egui::Label::new(text).truncate());
+                        }
+                        ui.end_row();
+                    }
+                };
 
-                if let Some(path) = navigate_to {
-                    self.navigate(path);
+                if details_view {
+                    egui::Grid::new("file_details")
+                        .striped(true)
+                        .num_columns(5)
+                        .min_col_width(COLUMN_WIDTH)
+                        .max_col_width(COLUMN_WIDTH)
+                        .show(ui, |ui| rows(ui));
+                } else {
+                    rows(ui);
                 }
             });
 
+            if let Some(key) = sort_by {
+                self.sort_ascending = key != self.sort_key || !self.sort_ascending;
+                self.sort_key = key;
+                self.sort_items();
+            }
+            if let Some(path) = navigate_to {
+                self.navigate(path);
+            }
+            if let Some(path) = download {
+                self.download(path, None);
+            }
+            if let Some(action) = archive_action {
+                self.start_archive(action);
+            }
+
             ui.separator();
             if let Some(idx) = self.selected_index {
                 if let Some(item) = self.items.get(idx) {
-                    let kind = if item.is_dir { "directory" } else { "file" };
+                    let kind = if item.is_dir { "explorer.directory" } else { "explorer.file" };
+                    let size = match size_text(item, &self.measured_folders, locale) {
+                        size if size.is_empty() => size,
+                        size => format!(", {}", size),
+                    };
+                    let modified = item
+                        .modified
+                        .map(|modified| format!(", {} {}", tr(locale, "explorer.modified"), format_modified(modified, locale)))
+                        .unwrap_or_default();
                     ui.label(format!(
-                        "Selected: {} ({}, {} bytes)",
-                        item.name, kind, item.size
+                        "{}: {} ({}{}{})",
+                        tr(locale, "explorer.selected"),
+                        item.name,
+                        tr(locale, kind),
+                        size,
+                        modified
                     ));
                 }
             }
+            if self.listing_task.is_some() {
+                ui.label(tr(locale, "explorer.listing"));
+            }
+            if self.updated_at.is_some_and(|at| at.elapsed() < UPDATED_HINT) {
+                ui.weak(tr(locale, "explorer.updated"));
+            }
+            if self.archive_task.is_some() {
+                ui.label(tr(locale, "explorer.archive_working"));
+            }
             if let Some(ref err) = self.error_message {
                 ui.colored_label(egui::Color32::RED, err);
             }
         });
+
+        self.send_accessibility_events(ctx);
+        self.send_ready_once();
+        self.schedule_next_frame(ctx, frame, had_input);
     }
 }
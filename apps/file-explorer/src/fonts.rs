@@ -0,0 +1,99 @@
+//! Fallback fonts for scripts the built-in egui fonts don't cover (CJK, emoji).
+//!
+//! Fonts are not embedded: they are read from `SANDBOX_FONTS_DIR` at startup, in the order
+//! given by `SANDBOX_FONT_FALLBACKS` (comma-separated file name prefixes). Loading happens on
+//! a background thread so the first frame isn't delayed; glyphs show up once a font is in.
+//! Ship subsetted fonts in that directory to keep memory use down — files larger than
+//! `SANDBOX_FONT_MAX_BYTES` are skipped.
+
+use eframe::egui;
+use egui::epaint::text::{FontInsert, FontPriority, InsertFontFamily};
+use std::path::{Path, PathBuf};
+
+const DEFAULT_FALLBACKS: &str = "NotoSansCJK,NotoSansSC,NotoSansJP,NotoSansKR,NotoEmoji";
+const DEFAULT_MAX_BYTES: u64 = 32 * 1024 * 1024;
+
+pub fn setup_custom_fonts(ctx: &egui::Context) {
+    let Ok(dir) = std::env::var("SANDBOX_FONTS_DIR") else {
+        return;
+    };
+    let prefixes: Vec<String> = std::env::var("SANDBOX_FONT_FALLBACKS")
+        .unwrap_or_else(|_| DEFAULT_FALLBACKS.to_string())
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
+    let max_bytes = std::env::var("SANDBOX_FONT_MAX_BYTES")
+        .ok()
+        .and_then(|s| s.parse::<u64>().ok())
+        .unwrap_or(DEFAULT_MAX_BYTES);
+
+    let ctx = ctx.clone();
+    std::thread::spawn(move || {
+        for path in fallback_chain(Path::new(&dir), &prefixes, max_bytes) {
+            let data = match std::fs::read(&path) {
+                Ok(data) => data,
+                Err(e) => {
+                    eprintln!("Failed to read font {}: {}", path.display(), e);
+                    continue;
+                }
+            };
+            let name = path
+                .file_stem()
+                .map(|s| s.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            // Lowest priority: only used for glyphs missing from the fonts already installed
+            ctx.add_font(FontInsert::new(
+                &name,
+                egui::FontData::from_owned(data),
+                vec![
+                    InsertFontFamily {
+                        family: egui::FontFamily::Proportional,
+                        priority: FontPriority::Lowest,
+                    },
+                    InsertFontFamily {
+                        family: egui::FontFamily::Monospace,
+                        priority: FontPriority::Lowest,
+                    },
+                ],
+            ));
+            ctx.request_repaint();
+        }
+    });
+}
+
+/// Font files in `dir` matching the configured prefixes, in fallback order (one per prefix).
+fn fallback_chain(dir: &Path, prefixes: &[String], max_bytes: u64) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut candidates: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| {
+            let is_font = p
+                .extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| matches!(e.to_ascii_lowercase().as_str(), "ttf" | "otf" | "ttc"));
+            let small_enough = std::fs::metadata(p).map(|m| m.len() <= max_bytes).unwrap_or(false);
+            is_font && small_enough
+        })
+        .collect();
+    // Deterministic pick when several files share a prefix (e.g. Regular before Bold)
+    candidates.sort();
+
+    prefixes
+        .iter()
+        .filter_map(|prefix| {
+            candidates
+                .iter()
+                .filter(|p| {
+                    p.file_name()
+                        .and_then(|n| n.to_str())
+                        .is_some_and(|n| n.starts_with(prefix.as_str()))
+                })
+                .min_by_key(|p| !p.to_string_lossy().contains("Regular"))
+                .cloned()
+        })
+        .collect()
+}
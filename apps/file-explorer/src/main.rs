@@ -1,8 +1,35 @@
+mod accessibility;
 mod app;
+mod fonts;
 
 use eframe::egui;
+use shared::i18n::tr;
+use shared::{CrashRecorder, IpcClient, SessionInit, Theme};
+use std::path::PathBuf;
 
 fn main() -> eframe::Result {
+    let (ipc, init) = match IpcClient::connect_from_env() {
+        Ok((client, init)) => (Some(client), init),
+        Err(e) => {
+            eprintln!("IPC unavailable, using defaults: {}", e);
+            (None, SessionInit::default())
+        }
+    };
+    // Report panics to the platform before the process dies
+    let crashes = CrashRecorder::new();
+    shared::crash::install(crashes.clone(), ipc.as_ref());
+    let locale = init.locale;
+    let scale_factor = init.scale_factor;
+    let view_only = init.view_only;
+    let allowed_paths = init.allowed_paths;
+    // The folder the user was in when the explorer last closed
+    let last_path = init.saved_state.get(app::LAST_PATH_KEY).and_then(|v| v.as_str()).map(PathBuf::from);
+    let theme = match init.theme {
+        Theme::Light => egui::ThemePreference::Light,
+        Theme::Dark => egui::ThemePreference::Dark,
+        Theme::System => egui::ThemePreference::System,
+    };
+
     let width = std::env::var("SANDBOX_WIDTH")
         .ok()
         .and_then(|w| w.parse::<f32>().ok())
@@ -12,7 +39,7 @@ fn main() -> eframe::Result {
         .and_then(|h| h.parse::<f32>().ok())
         .unwrap_or(600.0);
     let viewport = egui::ViewportBuilder::default()
-        .with_title("File Explorer")
+        .with_title(tr(locale, "explorer.title"))
         .with_inner_size([width, height]);
     let options = eframe::NativeOptions {
         viewport,
@@ -22,6 +49,12 @@ fn main() -> eframe::Result {
     eframe::run_native(
         "File Explorer",
         options,
-        Box::new(|_cc| Ok(Box::new(app::FileExplorerApp::default()))),
+        Box::new(move |cc| {
+            cc.egui_ctx.set_theme(theme);
+            // The window is sized in device pixels; render the UI at the client's DPI
+            cc.egui_ctx.set_zoom_factor(scale_factor);
+            fonts::setup_custom_fonts(&cc.egui_ctx);
+            Ok(Box::new(app::FileExplorerApp::new(locale, ipc, view_only, allowed_paths, last_path, crashes)))
+        }),
     )
 }
@@ -0,0 +1,9 @@
+CREATE TABLE quality_preferences (
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    app_id TEXT NOT NULL,
+    framerate INTEGER NOT NULL,
+    max_bitrate INTEGER NOT NULL,
+    resolution_scale REAL NOT NULL,
+    updated_at TEXT NOT NULL,
+    PRIMARY KEY (user_id, app_id)
+);
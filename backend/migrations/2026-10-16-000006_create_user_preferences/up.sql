@@ -0,0 +1,12 @@
+CREATE TABLE user_preferences (
+    user_id TEXT PRIMARY KEY NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    theme TEXT NOT NULL DEFAULT 'system'
+        CHECK(theme IN ('system', 'light', 'dark')),
+    default_width INTEGER NOT NULL DEFAULT 1280,
+    default_height INTEGER NOT NULL DEFAULT 720,
+    default_framerate INTEGER NOT NULL DEFAULT 60,
+    keyboard_layout TEXT NOT NULL DEFAULT 'us',
+    notify_email INTEGER NOT NULL DEFAULT 1,
+    notify_in_app INTEGER NOT NULL DEFAULT 1,
+    updated_at TEXT NOT NULL
+);
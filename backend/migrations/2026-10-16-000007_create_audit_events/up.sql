@@ -0,0 +1,13 @@
+CREATE TABLE audit_events (
+    id TEXT PRIMARY KEY NOT NULL,
+    session_id TEXT,
+    user_id TEXT,
+    owner_id TEXT,
+    event_type TEXT NOT NULL,
+    payload TEXT NOT NULL DEFAULT '{}',
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_audit_events_created_at ON audit_events(created_at);
+CREATE INDEX idx_audit_events_user_id ON audit_events(user_id);
+CREATE INDEX idx_audit_events_owner_id ON audit_events(owner_id);
@@ -0,0 +1,8 @@
+CREATE TABLE access_policies (
+    owner_id TEXT PRIMARY KEY NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    allowed_cidrs TEXT NOT NULL DEFAULT '[]',
+    denied_cidrs TEXT NOT NULL DEFAULT '[]',
+    allowed_countries TEXT NOT NULL DEFAULT '[]',
+    denied_countries TEXT NOT NULL DEFAULT '[]',
+    updated_at TEXT NOT NULL
+);
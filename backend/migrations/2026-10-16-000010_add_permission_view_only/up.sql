@@ -0,0 +1 @@
+ALTER TABLE file_permissions ADD COLUMN view_only INTEGER NOT NULL DEFAULT 0;
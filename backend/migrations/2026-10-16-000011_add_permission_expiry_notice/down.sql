@@ -0,0 +1 @@
+ALTER TABLE file_permissions DROP COLUMN expiry_notified_at;
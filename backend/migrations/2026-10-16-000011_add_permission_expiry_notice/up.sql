@@ -0,0 +1 @@
+ALTER TABLE file_permissions ADD COLUMN expiry_notified_at TEXT;
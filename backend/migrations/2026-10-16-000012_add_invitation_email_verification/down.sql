@@ -0,0 +1 @@
+ALTER TABLE invitations DROP COLUMN require_email_verification;
@@ -0,0 +1 @@
+ALTER TABLE invitations ADD COLUMN require_email_verification INTEGER NOT NULL DEFAULT 0;
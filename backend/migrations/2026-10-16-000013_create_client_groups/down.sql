@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS idx_file_permissions_group;
+ALTER TABLE file_permissions DROP COLUMN group_id;
+DROP TABLE IF EXISTS client_group_members;
+DROP TABLE IF EXISTS client_groups;
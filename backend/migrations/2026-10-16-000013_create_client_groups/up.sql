@@ -0,0 +1,20 @@
+CREATE TABLE client_groups (
+    id TEXT PRIMARY KEY NOT NULL,
+    owner_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    grants TEXT NOT NULL DEFAULT '[]',
+    created_at TEXT NOT NULL,
+    UNIQUE (owner_id, name)
+);
+
+CREATE TABLE client_group_members (
+    group_id TEXT NOT NULL REFERENCES client_groups(id) ON DELETE CASCADE,
+    client_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    added_at TEXT NOT NULL,
+    PRIMARY KEY (group_id, client_id)
+);
+
+-- Kept on revoked permissions for history, so no foreign key to client_groups
+ALTER TABLE file_permissions ADD COLUMN group_id TEXT;
+
+CREATE INDEX idx_file_permissions_group ON file_permissions (group_id, client_id);
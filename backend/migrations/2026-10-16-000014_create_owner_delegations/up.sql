@@ -0,0 +1,13 @@
+CREATE TABLE owner_delegations (
+    id TEXT PRIMARY KEY NOT NULL,
+    owner_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    delegate_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    path TEXT NOT NULL,
+    created_by TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    revoked_at TEXT,
+    grants_owner_role INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE INDEX idx_owner_delegations_delegate ON owner_delegations (delegate_id, revoked_at);
+CREATE INDEX idx_owner_delegations_owner ON owner_delegations (owner_id);
@@ -0,0 +1,6 @@
+DROP INDEX IF EXISTS idx_audit_events_owner_created;
+DROP INDEX IF EXISTS idx_sessions_owner_created;
+DROP INDEX IF EXISTS idx_sessions_user_created;
+DROP INDEX IF EXISTS idx_invitations_owner_created;
+DROP INDEX IF EXISTS idx_file_permissions_client_granted;
+DROP INDEX IF EXISTS idx_file_permissions_owner_granted;
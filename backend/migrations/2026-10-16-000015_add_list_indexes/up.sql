@@ -0,0 +1,7 @@
+-- Keyset pagination orders by (sort column, id) within an owner or user
+CREATE INDEX idx_file_permissions_owner_granted ON file_permissions (owner_id, granted_at, id);
+CREATE INDEX idx_file_permissions_client_granted ON file_permissions (client_id, granted_at, id);
+CREATE INDEX idx_invitations_owner_created ON invitations (owner_id, created_at, id);
+CREATE INDEX idx_sessions_user_created ON sessions (user_id, created_at, id);
+CREATE INDEX idx_sessions_owner_created ON sessions (acting_as_owner_id, created_at, id);
+CREATE INDEX idx_audit_events_owner_created ON audit_events (owner_id, created_at, id);
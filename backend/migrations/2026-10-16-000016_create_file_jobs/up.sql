@@ -0,0 +1,17 @@
+CREATE TABLE file_jobs (
+    id TEXT PRIMARY KEY NOT NULL,
+    owner_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_by TEXT NOT NULL,
+    -- JSON array of operations
+    operations TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'queued',
+    completed INTEGER NOT NULL DEFAULT 0,
+    total INTEGER NOT NULL,
+    error TEXT,
+    cancel_requested BOOLEAN NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_file_jobs_status ON file_jobs (status, created_at);
+CREATE INDEX idx_file_jobs_owner ON file_jobs (owner_id, created_at);
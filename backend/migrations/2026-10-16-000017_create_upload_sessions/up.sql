@@ -0,0 +1,17 @@
+CREATE TABLE upload_sessions (
+    id TEXT PRIMARY KEY NOT NULL,
+    owner_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_by TEXT NOT NULL,
+    -- Destination, relative to the owner's vault
+    path TEXT NOT NULL,
+    size BIGINT NOT NULL,
+    -- Bytes received and staged so far
+    received BIGINT NOT NULL DEFAULT 0,
+    status TEXT NOT NULL DEFAULT 'active',
+    error TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    expires_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_upload_sessions_expiry ON upload_sessions (status, expires_at);
@@ -0,0 +1,6 @@
+CREATE TABLE session_timelines (
+    session_id TEXT PRIMARY KEY NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+    -- JSON array of timeline events, oldest first
+    events TEXT NOT NULL,
+    recorded_at TEXT NOT NULL
+);
@@ -0,0 +1,10 @@
+CREATE TABLE session_snapshots (
+    session_id TEXT PRIMARY KEY NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    app_id TEXT NOT NULL,
+    -- Opaque state saved by the app; NULL when it saved none
+    state BLOB,
+    suspended_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_session_snapshots_suspended_at ON session_snapshots (suspended_at);
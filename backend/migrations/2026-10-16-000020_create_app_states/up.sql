@@ -0,0 +1,9 @@
+CREATE TABLE app_states (
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    app_id TEXT NOT NULL,
+    key TEXT NOT NULL,
+    -- JSON text saved by the app
+    value TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    PRIMARY KEY (user_id, app_id, key)
+);
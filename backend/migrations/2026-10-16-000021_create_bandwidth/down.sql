@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS bandwidth_usage;
+DROP TABLE IF EXISTS bandwidth_caps;
@@ -0,0 +1,21 @@
+-- Limits an owner sets for a client's sessions in their vault
+CREATE TABLE bandwidth_caps (
+    owner_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    -- bits per second
+    session_max_bitrate BIGINT,
+    monthly_bytes BIGINT,
+    throttle_bitrate BIGINT,
+    updated_at TEXT NOT NULL,
+    PRIMARY KEY (owner_id, user_id)
+);
+
+-- Video bytes sent to a user's sessions in an owner's vault, per calendar month (UTC)
+CREATE TABLE bandwidth_usage (
+    owner_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    -- YYYY-MM
+    month TEXT NOT NULL,
+    bytes_sent BIGINT NOT NULL DEFAULT 0,
+    PRIMARY KEY (owner_id, user_id, month)
+);
@@ -0,0 +1,17 @@
+-- Panics reported by SDK apps just before they exited
+CREATE TABLE app_crashes (
+    id TEXT PRIMARY KEY NOT NULL,
+    session_id TEXT NOT NULL,
+    -- NULL when the app crashed before its session was identified
+    user_id TEXT REFERENCES users(id) ON DELETE SET NULL,
+    app_id TEXT,
+    message TEXT NOT NULL,
+    location TEXT,
+    backtrace TEXT NOT NULL,
+    -- JSON array of the last input events, oldest first
+    recent_input TEXT NOT NULL,
+    frames BIGINT NOT NULL,
+    crashed_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_app_crashes_crashed_at ON app_crashes (crashed_at);
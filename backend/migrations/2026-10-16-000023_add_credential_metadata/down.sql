@@ -0,0 +1,6 @@
+ALTER TABLE webauthn_credentials DROP COLUMN sign_count_anomalies;
+ALTER TABLE webauthn_credentials DROP COLUMN last_used_at;
+ALTER TABLE webauthn_credentials DROP COLUMN backup_state;
+ALTER TABLE webauthn_credentials DROP COLUMN backup_eligible;
+ALTER TABLE webauthn_credentials DROP COLUMN transports;
+ALTER TABLE webauthn_credentials DROP COLUMN aaguid;
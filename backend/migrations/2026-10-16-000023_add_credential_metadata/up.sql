@@ -0,0 +1,9 @@
+-- What the authenticator reported at registration, refreshed on each login
+ALTER TABLE webauthn_credentials ADD COLUMN aaguid TEXT;
+-- JSON array of transports, e.g. ["usb","nfc"]
+ALTER TABLE webauthn_credentials ADD COLUMN transports TEXT NOT NULL DEFAULT '[]';
+ALTER TABLE webauthn_credentials ADD COLUMN backup_eligible INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE webauthn_credentials ADD COLUMN backup_state INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE webauthn_credentials ADD COLUMN last_used_at TEXT;
+-- Logins whose signature counter did not move forward (possible cloned authenticator)
+ALTER TABLE webauthn_credentials ADD COLUMN sign_count_anomalies INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,14 @@
+-- One row per login, so users can see and sign out the devices holding their tokens
+CREATE TABLE auth_sessions (
+    id TEXT PRIMARY KEY NOT NULL,
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    user_agent TEXT,
+    ip TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    last_seen_at TEXT NOT NULL,
+    -- When the session's token expires
+    expires_at TEXT NOT NULL,
+    revoked_at TEXT
+);
+
+CREATE INDEX idx_auth_sessions_user_id ON auth_sessions (user_id);
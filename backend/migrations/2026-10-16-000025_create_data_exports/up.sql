@@ -0,0 +1,19 @@
+-- Archives of a user's data, assembled in the background and downloaded once ready
+CREATE TABLE data_exports (
+    id TEXT PRIMARY KEY NOT NULL,
+    -- The user whose data is exported
+    subject_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    requested_by TEXT NOT NULL,
+    -- Set when an owner exports a client: only what the client shares with that owner
+    owner_id TEXT,
+    status TEXT NOT NULL DEFAULT 'queued',
+    completed INTEGER NOT NULL DEFAULT 0,
+    total INTEGER NOT NULL,
+    error TEXT,
+    size_bytes BIGINT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_data_exports_status ON data_exports (status, created_at);
+CREATE INDEX idx_data_exports_requested_by ON data_exports (requested_by, created_at);
@@ -0,0 +1,18 @@
+DROP TABLE IF EXISTS account_deletions;
+
+UPDATE users SET status = 'active' WHERE status = 'pending_deletion';
+
+CREATE TABLE users_old (
+    id TEXT PRIMARY KEY NOT NULL,
+    email TEXT NOT NULL UNIQUE,
+    display_name TEXT NOT NULL,
+    roles TEXT NOT NULL DEFAULT '[]',
+    status TEXT NOT NULL DEFAULT 'active'
+        CHECK(status IN ('active', 'suspended', 'deleted')),
+    locale TEXT NOT NULL DEFAULT 'en'
+);
+
+INSERT INTO users_old (id, email, display_name, roles, status, locale)
+    SELECT id, email, display_name, roles, status, locale FROM users;
+DROP TABLE users;
+ALTER TABLE users_old RENAME TO users;
@@ -0,0 +1,26 @@
+-- SQLite cannot alter a CHECK constraint: rebuild users to allow 'pending_deletion'
+CREATE TABLE users_new (
+    id TEXT PRIMARY KEY NOT NULL,
+    email TEXT NOT NULL UNIQUE,
+    display_name TEXT NOT NULL,
+    roles TEXT NOT NULL DEFAULT '[]',
+    status TEXT NOT NULL DEFAULT 'active'
+        CHECK(status IN ('active', 'suspended', 'pending_deletion', 'deleted')),
+    locale TEXT NOT NULL DEFAULT 'en'
+);
+
+INSERT INTO users_new (id, email, display_name, roles, status, locale)
+    SELECT id, email, display_name, roles, status, locale FROM users;
+DROP TABLE users;
+ALTER TABLE users_new RENAME TO users;
+
+-- Accounts waiting out their grace period before their data is purged
+CREATE TABLE account_deletions (
+    user_id TEXT PRIMARY KEY NOT NULL,
+    requested_by TEXT NOT NULL,
+    requested_at TEXT NOT NULL,
+    purge_after TEXT NOT NULL,
+    purged_at TEXT
+);
+
+CREATE INDEX idx_account_deletions_purge_after ON account_deletions (purge_after);
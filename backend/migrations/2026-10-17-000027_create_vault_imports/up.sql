@@ -0,0 +1,24 @@
+-- Host directory trees adopted into an owner's vault in the background
+CREATE TABLE vault_imports (
+    id TEXT PRIMARY KEY NOT NULL,
+    owner_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    requested_by TEXT NOT NULL,
+    source TEXT NOT NULL,
+    destination TEXT NOT NULL,
+    -- 'copy', 'hardlink' or 'move'
+    mode TEXT NOT NULL,
+    -- 'skip', 'overwrite' or 'rename'
+    on_conflict TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'queued',
+    -- Unknown until the source has been scanned
+    files_total BIGINT,
+    files_done BIGINT NOT NULL DEFAULT 0,
+    bytes_done BIGINT NOT NULL DEFAULT 0,
+    skipped BIGINT NOT NULL DEFAULT 0,
+    error TEXT,
+    cancel_requested INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_vault_imports_status ON vault_imports (status, created_at);
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS provisioning_requests;
+DROP TABLE IF EXISTS provisioning_tokens;
@@ -0,0 +1,24 @@
+-- Long-lived tokens owners' scripts use to provision clients
+CREATE TABLE provisioning_tokens (
+    id TEXT PRIMARY KEY NOT NULL,
+    owner_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    token_hash TEXT NOT NULL UNIQUE,
+    created_at TEXT NOT NULL,
+    last_used_at TEXT,
+    revoked_at TEXT
+);
+
+CREATE INDEX idx_provisioning_tokens_owner_id ON provisioning_tokens (owner_id);
+
+-- Responses to provisioning requests sent with an Idempotency-Key, replayed on retries. The
+-- row is inserted before the request is applied; status and response stay NULL until then.
+CREATE TABLE provisioning_requests (
+    owner_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    idempotency_key TEXT NOT NULL,
+    request_hash TEXT NOT NULL,
+    status INTEGER,
+    response TEXT,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (owner_id, idempotency_key)
+);
@@ -0,0 +1,16 @@
+-- Personal access tokens for scripts and backup tools, limited to their scopes
+CREATE TABLE access_tokens (
+    id TEXT PRIMARY KEY NOT NULL,
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    display_prefix TEXT NOT NULL,
+    token_hash TEXT NOT NULL UNIQUE,
+    -- Comma-separated, e.g. files:read,metrics:read
+    scopes TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    expires_at TEXT NOT NULL,
+    last_used_at TEXT,
+    revoked_at TEXT
+);
+
+CREATE INDEX idx_access_tokens_user_id ON access_tokens (user_id);
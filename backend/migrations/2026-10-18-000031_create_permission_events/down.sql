@@ -0,0 +1,2 @@
+DROP TRIGGER IF EXISTS permission_events_immutable;
+DROP TABLE IF EXISTS permission_events;
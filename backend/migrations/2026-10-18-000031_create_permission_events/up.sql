@@ -0,0 +1,38 @@
+-- Every grant, revocation and change of expiry of a file permission, with the permission as
+-- it stood afterwards. Rows are only ever added, so past access can be replayed.
+CREATE TABLE permission_events (
+    seq INTEGER PRIMARY KEY AUTOINCREMENT,
+    permission_id TEXT NOT NULL,
+    owner_id TEXT NOT NULL,
+    client_id TEXT NOT NULL,
+    -- granted, revoked or expiry_changed
+    kind TEXT NOT NULL,
+    path TEXT NOT NULL,
+    access TEXT NOT NULL,
+    view_only INTEGER NOT NULL DEFAULT 0,
+    group_id TEXT,
+    expires_at TEXT,
+    occurred_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_permission_events_owner ON permission_events (owner_id, seq);
+CREATE INDEX idx_permission_events_client ON permission_events (client_id);
+
+CREATE TRIGGER permission_events_immutable BEFORE UPDATE ON permission_events
+BEGIN
+    SELECT RAISE(ABORT, 'permission events cannot be changed');
+END;
+
+-- Start the history from the permissions that already exist. Earlier renewals were not
+-- recorded, so those grants carry their current expiry.
+INSERT INTO permission_events (permission_id, owner_id, client_id, kind, path, access, view_only, group_id, expires_at, occurred_at)
+SELECT id, owner_id, client_id, 'granted', path, access, view_only, group_id, expires_at, granted_at
+FROM file_permissions
+ORDER BY granted_at;
+
+INSERT INTO permission_events (permission_id, owner_id, client_id, kind, path, access, view_only, group_id, expires_at, occurred_at)
+SELECT id, owner_id, client_id, 'revoked', path, access, view_only, group_id, expires_at,
+       strftime('%Y-%m-%dT%H:%M:%S+00:00', revoked_at)
+FROM file_permissions
+WHERE revoked_at IS NOT NULL
+ORDER BY revoked_at;
@@ -0,0 +1,13 @@
+-- Vault paths that may not be deleted or changed until released or expired
+CREATE TABLE legal_holds (
+    id TEXT PRIMARY KEY NOT NULL,
+    owner_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    path TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    created_by TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    expires_at TEXT,
+    released_at TEXT
+);
+
+CREATE INDEX idx_legal_holds_owner_id ON legal_holds (owner_id);
@@ -0,0 +1,10 @@
+-- How each owner lets installed apps be used in their vault; apps without a row are enabled for everyone
+CREATE TABLE app_settings (
+    owner_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    app_id TEXT NOT NULL,
+    enabled INTEGER NOT NULL DEFAULT 1,
+    allowed_roles TEXT NOT NULL DEFAULT '[]',
+    allowed_users TEXT NOT NULL DEFAULT '[]',
+    updated_at TEXT NOT NULL,
+    PRIMARY KEY (owner_id, app_id)
+);
@@ -0,0 +1,6 @@
+DROP INDEX IF EXISTS idx_sessions_tenant;
+DROP INDEX IF EXISTS idx_users_tenant;
+ALTER TABLE sessions DROP COLUMN tenant_id;
+ALTER TABLE file_permissions DROP COLUMN tenant_id;
+ALTER TABLE users DROP COLUMN tenant_id;
+DROP TABLE IF EXISTS tenants;
@@ -0,0 +1,17 @@
+-- Isolated groups of users sharing the instance; everything before them is in the default tenant
+CREATE TABLE tenants (
+    id TEXT PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL UNIQUE,
+    setup_token_hash TEXT UNIQUE,
+    created_at TEXT NOT NULL
+);
+
+INSERT INTO tenants (id, name, created_at)
+VALUES ('00000000-0000-0000-0000-000000000000', 'Default', strftime('%Y-%m-%dT%H:%M:%SZ', 'now'));
+
+ALTER TABLE users ADD COLUMN tenant_id TEXT NOT NULL DEFAULT '00000000-0000-0000-0000-000000000000';
+ALTER TABLE file_permissions ADD COLUMN tenant_id TEXT NOT NULL DEFAULT '00000000-0000-0000-0000-000000000000';
+ALTER TABLE sessions ADD COLUMN tenant_id TEXT NOT NULL DEFAULT '00000000-0000-0000-0000-000000000000';
+
+CREATE INDEX idx_users_tenant ON users(tenant_id);
+CREATE INDEX idx_sessions_tenant ON sessions(tenant_id);
@@ -0,0 +1,7 @@
+CREATE TABLE owner_branding (
+    owner_id TEXT PRIMARY KEY NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    display_name TEXT,
+    logo TEXT,
+    accent_color TEXT,
+    updated_at TEXT NOT NULL
+);
@@ -0,0 +1,3 @@
+ALTER TABLE sessions DROP COLUMN interaction;
+ALTER TABLE permission_events DROP COLUMN interaction;
+ALTER TABLE file_permissions DROP COLUMN interaction;
@@ -0,0 +1,3 @@
+ALTER TABLE file_permissions ADD COLUMN interaction TEXT NOT NULL DEFAULT 'interactive';
+ALTER TABLE permission_events ADD COLUMN interaction TEXT NOT NULL DEFAULT 'interactive';
+ALTER TABLE sessions ADD COLUMN interaction TEXT NOT NULL DEFAULT 'interactive';
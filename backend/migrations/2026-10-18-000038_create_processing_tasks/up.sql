@@ -0,0 +1,17 @@
+CREATE TABLE processing_tasks (
+    id TEXT PRIMARY KEY NOT NULL,
+    owner_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    path TEXT NOT NULL,
+    size BIGINT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending',
+    -- JSON array of the stages that succeeded
+    done TEXT NOT NULL DEFAULT '[]',
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    next_attempt_at TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_processing_tasks_due ON processing_tasks (status, next_attempt_at);
+CREATE INDEX idx_processing_tasks_owner ON processing_tasks (owner_id, status, updated_at);
@@ -0,0 +1,17 @@
+CREATE TABLE media_metadata (
+    owner_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    path TEXT NOT NULL,
+    -- Camera local time, ISO 8601 without offset
+    taken_at TEXT,
+    camera TEXT,
+    latitude REAL,
+    longitude REAL,
+    width INTEGER,
+    height INTEGER,
+    duration_secs REAL,
+    extracted_at TEXT NOT NULL,
+    PRIMARY KEY (owner_id, path)
+);
+
+CREATE INDEX idx_media_metadata_taken ON media_metadata (owner_id, taken_at);
+CREATE INDEX idx_media_metadata_camera ON media_metadata (owner_id, camera);
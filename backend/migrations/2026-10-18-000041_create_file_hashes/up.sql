@@ -0,0 +1,11 @@
+CREATE TABLE file_hashes (
+    owner_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    path TEXT NOT NULL,
+    size BIGINT NOT NULL,
+    -- SHA-256 of the contents, hex
+    sha256 TEXT NOT NULL,
+    hashed_at TEXT NOT NULL,
+    PRIMARY KEY (owner_id, path)
+);
+
+CREATE INDEX idx_file_hashes_sha256 ON file_hashes (owner_id, sha256);
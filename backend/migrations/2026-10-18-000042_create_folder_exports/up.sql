@@ -0,0 +1,21 @@
+CREATE TABLE folder_exports (
+    id TEXT PRIMARY KEY NOT NULL,
+    owner_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    client_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    path TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    -- requested, approved, ready, denied, failed or expired
+    status TEXT NOT NULL,
+    error TEXT,
+    size_bytes BIGINT,
+    -- Archive password, cleared once the export expires
+    password TEXT,
+    decided_at TEXT,
+    expires_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_folder_exports_owner ON folder_exports (owner_id, created_at);
+CREATE INDEX idx_folder_exports_client ON folder_exports (client_id, created_at);
+CREATE INDEX idx_folder_exports_status ON folder_exports (status);
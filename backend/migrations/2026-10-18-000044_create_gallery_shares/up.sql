@@ -0,0 +1,16 @@
+CREATE TABLE gallery_shares (
+    id TEXT PRIMARY KEY NOT NULL,
+    owner_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    path TEXT NOT NULL,
+    title TEXT NOT NULL,
+    token_hash TEXT NOT NULL UNIQUE,
+    -- salt$hash of the visitors' password, NULL for an open gallery
+    password_hash TEXT,
+    expires_at TEXT,
+    view_count BIGINT NOT NULL DEFAULT 0,
+    last_viewed_at TEXT,
+    created_at TEXT NOT NULL,
+    revoked_at TEXT
+);
+
+CREATE INDEX idx_gallery_shares_owner ON gallery_shares (owner_id, created_at);
@@ -0,0 +1,37 @@
+CREATE TABLE file_requests (
+    id TEXT PRIMARY KEY NOT NULL,
+    owner_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    -- Where approved uploads are placed, vault-relative
+    folder TEXT NOT NULL,
+    title TEXT NOT NULL,
+    token_hash TEXT NOT NULL UNIQUE,
+    max_file_bytes BIGINT NOT NULL,
+    -- Comma-separated lowercase extensions, empty for any
+    allowed_extensions TEXT NOT NULL DEFAULT '',
+    expires_at TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    revoked_at TEXT
+);
+
+CREATE INDEX idx_file_requests_owner ON file_requests (owner_id, created_at);
+
+CREATE TABLE inbox_uploads (
+    id TEXT PRIMARY KEY NOT NULL,
+    request_id TEXT NOT NULL REFERENCES file_requests(id) ON DELETE CASCADE,
+    owner_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    file_name TEXT NOT NULL,
+    size BIGINT NOT NULL,
+    media_type TEXT NOT NULL,
+    -- Name the uploader gave, if any
+    sender TEXT,
+    sender_ip TEXT NOT NULL,
+    -- receiving, pending, approved or rejected
+    status TEXT NOT NULL,
+    -- Where an approved upload was placed
+    path TEXT,
+    received_at TEXT NOT NULL,
+    decided_at TEXT
+);
+
+CREATE INDEX idx_inbox_uploads_owner ON inbox_uploads (owner_id, status, received_at);
+CREATE INDEX idx_inbox_uploads_request ON inbox_uploads (request_id, status);
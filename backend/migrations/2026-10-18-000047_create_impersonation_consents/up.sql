@@ -0,0 +1,11 @@
+CREATE TABLE impersonation_consents (
+    id TEXT PRIMARY KEY NOT NULL,
+    client_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    owner_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    granted_at TEXT NOT NULL,
+    expires_at TEXT NOT NULL,
+    revoked_at TEXT
+);
+
+CREATE INDEX idx_impersonation_consents_client ON impersonation_consents (client_id, granted_at);
+CREATE INDEX idx_impersonation_consents_owner ON impersonation_consents (owner_id, granted_at);
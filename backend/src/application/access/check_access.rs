@@ -0,0 +1,75 @@
+use std::net::IpAddr;
+use axum::http::StatusCode;
+use crate::application::ports::{AccessPolicyRepository, AuditRepository, GeoIpResolver};
+use crate::domain::entities::access_policy::admitted_by_all;
+use crate::domain::entities::audit_event::AuditEvent;
+use crate::domain::{User, UserId, UserRole};
+use crate::infrastructure::AppState;
+
+/// Whether a request from `ip` may reach the vaults of `owner_ids`: every one of their policies
+/// has to admit it, so one owner's loose policy never opens another's vault. Blocked requests
+/// are audited.
+pub async fn execute(
+    policy_repo: &dyn AccessPolicyRepository,
+    audit_repo: &dyn AuditRepository,
+    geoip: Option<&dyn GeoIpResolver>,
+    owner_ids: &[UserId],
+    ip: IpAddr,
+    path: &str,
+) -> Result<bool, String> {
+    let mut policies = Vec::new();
+    for owner_id in owner_ids {
+        policies.extend(policy_repo.find(owner_id).await?);
+    }
+    if policies.is_empty() {
+        return Ok(true);
+    }
+
+    let country = geoip.and_then(|g| g.country(ip));
+    if admitted_by_all(&policies, ip, country.as_deref()) {
+        return Ok(true);
+    }
+
+    tracing::warn!("Access policy blocked {} ({:?}) on {}", ip, country, path);
+    let event = AuditEvent::new(
+        "access_blocked",
+        serde_json::json!({ "ip": ip.to_string(), "country": country, "path": path }),
+    );
+    audit_repo.record(&event).await?;
+    Ok(false)
+}
+
+/// The owners whose vaults `user` reaches: their own, and those sharing files with them.
+pub async fn vault_owners(state: &AppState, user: &User) -> Result<Vec<UserId>, String> {
+    let mut owners = Vec::new();
+    if user.has_role(UserRole::Owner) {
+        owners.push(user.id().clone());
+    }
+    for permission in state.file_permission_repo.find_active_for_client(user.id()).await? {
+        if !owners.contains(&permission.owner_id) {
+            owners.push(permission.owner_id);
+        }
+    }
+    Ok(owners)
+}
+
+/// Refuse `user` at `ip` if the policy of any vault they reach does not admit the address.
+pub async fn ensure_admitted(state: &AppState, user: &User, ip: IpAddr, path: &str) -> Result<(), (StatusCode, String)> {
+    let internal = |e: String| (StatusCode::INTERNAL_SERVER_ERROR, e);
+    let owners = vault_owners(state, user).await.map_err(internal)?;
+    let admitted = execute(
+        &*state.access_policy_repo,
+        &*state.audit_repo,
+        state.geoip.as_deref(),
+        &owners,
+        ip,
+        path,
+    )
+    .await
+    .map_err(internal)?;
+    if admitted {
+        Ok(())
+    } else {
+        Err((StatusCode::FORBIDDEN, "Access from this location is not allowed".to_string()))
+    }
+}
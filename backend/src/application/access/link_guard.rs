@@ -0,0 +1,66 @@
+use axum::http::StatusCode;
+use crate::domain::entities::audit_event::AuditEvent;
+use crate::domain::value_objects::lockout_policy::LoginPenalty;
+use crate::infrastructure::AppState;
+
+/// Throttling for links that grant access without an account, like galleries and file
+/// requests. Invalid tokens and passwords count against a subject, usually the caller's IP,
+/// with the login lockout policy, so links cannot be guessed by brute force.
+pub struct LinkGuard {
+    /// Prefix of the subjects failures count against
+    pub kind: &'static str,
+    /// What callers are told they got wrong too often, e.g. "file request links"
+    pub label: &'static str,
+    /// Audit event recorded when a subject is locked out
+    pub lockout_event: &'static str,
+}
+
+impl LinkGuard {
+    pub fn ip_subject(&self, ip: &str) -> String {
+        format!("{}-ip:{}", self.kind, ip)
+    }
+
+    /// Reject the request while `subject` is backing off or locked out.
+    pub async fn ensure_allowed(&self, state: &AppState, subject: &str) -> Result<(), (StatusCode, String)> {
+        let blocked = state.login_attempt_repo
+            .blocked_for(subject)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+        match blocked {
+            Some(seconds) => Err((
+                StatusCode::TOO_MANY_REQUESTS,
+                format!("Too many invalid {}, retry in {} seconds", self.label, seconds),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Count a failure from `ip` against `subject`. Best effort: storage errors are logged so
+    /// the caller still gets the original failure.
+    pub async fn penalize(&self, state: &AppState, subject: &str, ip: &str) {
+        if let Err(e) = self.record_failure(state, subject, ip).await {
+            tracing::warn!("Failed to record invalid {} from {}: {}", self.label, ip, e);
+        }
+    }
+
+    async fn record_failure(&self, state: &AppState, subject: &str, ip: &str) -> Result<(), String> {
+        let policy = state.lockout_policy;
+        let failures = state.login_attempt_repo
+            .record_failure(subject, policy.window_secs)
+            .await?;
+
+        match policy.penalty(failures) {
+            None => Ok(()),
+            Some(LoginPenalty::Backoff(seconds)) => state.login_attempt_repo.block(subject, seconds).await,
+            Some(LoginPenalty::Lockout(seconds)) => {
+                state.login_attempt_repo.block(subject, seconds).await?;
+                tracing::warn!("Locked out {} after {} invalid {} from {}", subject, failures, self.label, ip);
+                let event = AuditEvent::new(
+                    self.lockout_event,
+                    serde_json::json!({ "subject": subject, "ip": ip, "failures": failures, "locked_for_secs": seconds }),
+                );
+                state.audit_repo.record(&event).await
+            }
+        }
+    }
+}
@@ -0,0 +1,4 @@
+// Access checks against the IP and country policies of the vaults a user reaches
+pub mod check_access;
+// Throttling of links that grant access without an account
+pub mod link_guard;
@@ -0,0 +1,201 @@
+// Account deletion - requested by the user or by the owner of a client, purged after a grace period
+use std::collections::HashSet;
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use crate::application::ports::pagination::{Cursor, PageRequest, SortDirection, MAX_PAGE_SIZE};
+use crate::application::ports::session_repository::SessionFilter;
+use crate::domain::entities::account_deletion::AccountDeletion;
+use crate::domain::entities::audit_event::AuditEvent;
+use crate::domain::entities::file_job::JobStatus;
+use crate::domain::entities::notification::Notification;
+use crate::domain::entities::session::Session;
+use crate::domain::value_objects::user_role::UserRole;
+use crate::domain::value_objects::{UserId, UserStatus};
+use crate::domain::User;
+use crate::infrastructure::AppState;
+
+/// Stop `subject_id`'s account now and schedule its purge after the grace period: the account
+/// is marked pending deletion, its logins, running sessions and permissions (held and granted)
+/// are revoked, and the owners or clients it shared with are notified.
+///
+/// Users may delete themselves, except the last super-admin. Owners may delete a client that
+/// only they share with.
+pub async fn request(state: &AppState, subject_id: &UserId, requested_by: &UserId) -> Result<AccountDeletion, String> {
+    let user = state
+        .user_repo
+        .find_by_id(subject_id)
+        .await?
+        .ok_or_else(|| "User not found".to_string())?;
+    if user.status().is_deleting() {
+        return Err("Account is already being deleted".to_string());
+    }
+    if subject_id == requested_by {
+        if user.has_role(UserRole::SuperAdmin) && state.user_repo.count_super_admins().await? <= 1 {
+            return Err("The last super-admin cannot delete their account".to_string());
+        }
+    } else {
+        check_owner_may_delete(state, &user, requested_by).await?;
+    }
+
+    let now = Utc::now();
+    let deletion = AccountDeletion::new(subject_id.clone(), requested_by.clone(), state.account_deletion_grace, now);
+    state.user_repo.update_status(subject_id, UserStatus::PendingDeletion).await?;
+    state.account_deletion_repo.save(&deletion).await?;
+
+    state.auth_sessions.revoke_all(subject_id).await?;
+    end_sessions(state, &user).await?;
+    let notified = revoke_permissions(state, &user).await?;
+
+    for recipient in notified {
+        let kind = if user.has_role(UserRole::Owner) { "owner_account_deleted" } else { "client_account_deleted" };
+        let payload = json!({ "user_id": subject_id, "display_name": user.display_name().as_str() });
+        if let Err(e) = state.notification_repo.create(&Notification::new(recipient, kind, payload)).await {
+            tracing::warn!("Failed to notify about the deletion of account {}: {}", subject_id, e);
+        }
+    }
+
+    let mut event = AuditEvent::new(
+        "account_deletion_requested",
+        json!({
+            "subject_id": subject_id,
+            "purge_after": deletion.purge_after,
+            "self_service": deletion.is_self_service(),
+        }),
+    );
+    event.user_id = Some(requested_by.clone());
+    if !deletion.is_self_service() {
+        event.owner_id = Some(requested_by.clone());
+    }
+    state.audit_repo.record(&event).await?;
+    Ok(deletion)
+}
+
+/// Restore an account still in its grace period. Revoked permissions and logins stay revoked.
+pub async fn cancel(state: &AppState, user_id: &UserId, acting_id: &UserId) -> Result<(), String> {
+    let deletion = state
+        .account_deletion_repo
+        .find(user_id)
+        .await?
+        .filter(|d| d.purged_at.is_none())
+        .ok_or_else(|| "Account deletion not found".to_string())?;
+    state.account_deletion_repo.delete(user_id).await?;
+    state.user_repo.update_status(user_id, UserStatus::Active).await?;
+
+    let mut event = AuditEvent::new(
+        "account_deletion_cancelled",
+        json!({ "subject_id": user_id, "requested_at": deletion.requested_at }),
+    );
+    event.user_id = Some(acting_id.clone());
+    state.audit_repo.record(&event).await
+}
+
+/// Purge every account whose grace period is over. Returns how many were purged. A vault
+/// with an active legal hold is kept, and its owner's purge retried once the holds lapse.
+pub async fn purge_due(state: &AppState, now: DateTime<Utc>) -> Result<usize, String> {
+    let due = state.account_deletion_repo.find_due(now).await?;
+    let mut purged = 0;
+    for deletion in &due {
+        let holds = state.legal_hold_repo.list_for_owner(&deletion.user_id).await?;
+        if holds.iter().any(|hold| hold.is_active(now)) {
+            tracing::info!("Purge of account {} postponed: its vault is under legal hold", deletion.user_id);
+            continue;
+        }
+        purge(state, deletion, now).await?;
+        purged += 1;
+    }
+    Ok(purged)
+}
+
+async fn purge(state: &AppState, deletion: &AccountDeletion, now: DateTime<Utc>) -> Result<(), String> {
+    let Some(user) = state.user_repo.find_by_id(&deletion.user_id).await? else {
+        return state.account_deletion_repo.delete(&deletion.user_id).await;
+    };
+    // Files first: the rows pointing at them go with the purge
+    state.vault_storage.delete_vault(user.id()).await?;
+    for status in [JobStatus::Queued, JobStatus::Running, JobStatus::Completed, JobStatus::Failed] {
+        for export in state.data_export_repo.find_by_status(status).await? {
+            if &export.subject_id == user.id() || &export.requested_by == user.id() {
+                state.vault_storage.discard_export(&export.id).await?;
+            }
+        }
+    }
+    state.account_deletion_repo.purge(user.id(), user.email().as_str(), now).await?;
+
+    // Recorded after the purge so the scrub does not catch it; it names no one
+    let event = AuditEvent::new(
+        "account_purged",
+        json!({ "requested_at": deletion.requested_at, "self_service": deletion.is_self_service() }),
+    );
+    state.audit_repo.record(&event).await
+}
+
+/// Owners may only delete clients: accounts without roles of their own that hold no active
+/// permission from another owner and have been granted something by this one.
+async fn check_owner_may_delete(state: &AppState, client: &User, owner_id: &UserId) -> Result<(), String> {
+    let granted = state.file_permission_repo.find_by_owner_client(owner_id, client.id()).await?;
+    if granted.is_empty() {
+        return Err("Client not found".to_string());
+    }
+    if client.has_role(UserRole::Owner) || client.has_role(UserRole::SuperAdmin) {
+        return Err("Only client accounts can be deleted by an owner".to_string());
+    }
+    let shared_elsewhere = state
+        .file_permission_repo
+        .find_active_for_client(client.id())
+        .await?
+        .iter()
+        .any(|p| &p.owner_id != owner_id);
+    if shared_elsewhere {
+        return Err("Client also has access from other owners".to_string());
+    }
+    Ok(())
+}
+
+/// Revoke what the user holds and, for owners, what they granted. Returns who lost access to
+/// or from the user, deduplicated.
+async fn revoke_permissions(state: &AppState, user: &User) -> Result<HashSet<UserId>, String> {
+    let mut counterparts = HashSet::new();
+    let mut permissions = state.file_permission_repo.find_active_for_client(user.id()).await?;
+    permissions.extend(state.file_permission_repo.find_active_by_owner(user.id()).await?);
+    for permission in permissions {
+        state.file_permission_repo.revoke(&permission.id).await?;
+        let counterpart = if &permission.owner_id == user.id() { permission.client_id } else { permission.owner_id };
+        counterparts.insert(counterpart);
+    }
+    counterparts.remove(user.id());
+    Ok(counterparts)
+}
+
+/// End the user's running sessions and, for owners, every session running in their vault.
+async fn end_sessions(state: &AppState, user: &User) -> Result<(), String> {
+    let mut sessions = state.session_repo.find_active_by_user(user.id()).await?;
+    if user.has_role(UserRole::Owner) {
+        let filter = SessionFilter { acting_as_owner_id: Some(user.id().clone()), ..Default::default() };
+        let mut page = PageRequest { limit: MAX_PAGE_SIZE, after: None, direction: SortDirection::Asc };
+        loop {
+            let batch = state.session_repo.list(&filter, &page).await?;
+            sessions.extend(batch.items.into_iter().filter(|s| s.terminated_at.is_none()));
+            match batch.next_cursor {
+                Some(cursor) => page.after = Some(Cursor::decode(&cursor)?),
+                None => break,
+            }
+        }
+    }
+    let mut ended = HashSet::new();
+    for session in sessions {
+        if ended.insert(session.id) {
+            end_session(state, &session).await;
+        }
+    }
+    Ok(())
+}
+
+async fn end_session(state: &AppState, session: &Session) {
+    let sid = session.id.to_string();
+    let _ = state.xvfb_manager.cleanup_session(&sid).await;
+    let _ = state.session_repo.terminate(&session.id).await;
+    if let Err(e) = state.session_timelines.finish(&sid).await {
+        tracing::warn!("Failed to save timeline of session {}: {}", sid, e);
+    }
+    let _ = state.session_affinity.release(&sid).await;
+}
@@ -0,0 +1,61 @@
+use crate::application::ports::{AppSettingRepository, FilePermissionRepository};
+use crate::domain::entities::app_setting::{normalize_app_id, AppSetting};
+use crate::domain::value_objects::{UserId, UserRole};
+
+/// Someone asking for apps, in the vault they would use them in
+pub struct AppUser {
+    pub owner_id: UserId,
+    /// Their role in that vault: `Owner` in their own, `Client` in one shared with them
+    pub role: UserRole,
+    pub user_id: UserId,
+}
+
+/// The vault `user_id` works in: their own for owners, else the one their active permissions
+/// are in. `None` for clients without any.
+pub async fn resolve<P: FilePermissionRepository + ?Sized>(
+    permissions: &P,
+    user_id: &UserId,
+    roles: &[UserRole],
+) -> Result<Option<AppUser>, String> {
+    if roles.contains(&UserRole::Owner) || roles.contains(&UserRole::SuperAdmin) {
+        return Ok(Some(AppUser { owner_id: user_id.clone(), role: UserRole::Owner, user_id: user_id.clone() }));
+    }
+    let active = permissions.find_active_for_client(user_id).await?;
+    Ok(active.into_iter().next().map(|permission| AppUser {
+        owner_id: permission.owner_id,
+        role: UserRole::Client,
+        user_id: user_id.clone(),
+    }))
+}
+
+fn permits(settings: &[AppSetting], user: &AppUser, app_id: &str) -> bool {
+    let Ok(app_id) = normalize_app_id(app_id) else {
+        return false;
+    };
+    settings
+        .iter()
+        .find(|setting| setting.app_id == app_id)
+        .map_or(true, |setting| setting.permits(user.role, &user.user_id))
+}
+
+/// Whether `user` may launch `app_id`. Apps the owner set nothing for are available to everyone.
+pub async fn is_available<R: AppSettingRepository + ?Sized>(
+    repo: &R,
+    user: &AppUser,
+    app_id: &str,
+) -> Result<bool, String> {
+    let Ok(app_id) = normalize_app_id(app_id) else {
+        return Ok(false);
+    };
+    let setting = repo.find(&user.owner_id, &app_id).await?;
+    Ok(setting.map_or(true, |setting| setting.permits(user.role, &user.user_id)))
+}
+
+/// The `apps` available to `user`, in order.
+pub async fn filter<R, T>(repo: &R, user: &AppUser, apps: Vec<T>, app_id: impl Fn(&T) -> &str) -> Result<Vec<T>, String>
+where
+    R: AppSettingRepository + ?Sized,
+{
+    let settings = repo.list_for_owner(&user.owner_id).await?;
+    Ok(apps.into_iter().filter(|app| permits(&settings, user, app_id(app))).collect())
+}
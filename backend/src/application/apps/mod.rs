@@ -0,0 +1,2 @@
+// Installed apps - which ones each vault offers, and to whom
+pub mod availability;
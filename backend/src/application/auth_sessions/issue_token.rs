@@ -0,0 +1,40 @@
+use crate::domain::User;
+use crate::domain::entities::auth_session::AuthSession;
+use crate::infrastructure::AppState;
+
+/// How long a login token stays valid
+const TOKEN_LIFETIME_HOURS: i64 = 24;
+
+#[derive(serde::Serialize)]
+struct Claims {
+    sub: String,
+    email: String,
+    roles: Vec<String>,
+    exp: usize,
+    /// The auth session the token belongs to, checked for revocation on every request
+    sid: String,
+    /// The tenant the user belongs to, scoping what they see
+    tid: String,
+}
+
+/// Start an auth session for `user` on the requesting device and sign its token.
+pub async fn execute(state: &AppState, user: &User, ip: &str, user_agent: Option<&str>) -> Result<String, String> {
+    let expires_at = chrono::Utc::now()
+        .checked_add_signed(chrono::Duration::hours(TOKEN_LIFETIME_HOURS))
+        .expect("valid timestamp");
+    let session = AuthSession::new(user.id().clone(), ip.to_string(), user_agent.map(str::to_string), expires_at);
+    state.auth_sessions.start(&session).await?;
+
+    let claims = Claims {
+        sub: user.id().to_string(),
+        email: user.email().as_str().to_string(),
+        roles: user.roles().iter().map(|r| r.as_db_str().to_string()).collect(),
+        exp: expires_at.timestamp() as usize,
+        sid: session.id.to_string(),
+        tid: user.tenant_id().to_string(),
+    };
+    state
+        .jwt_keys
+        .encode(&claims)
+        .map_err(|e| format!("Failed to generate JWT: {e}"))
+}
@@ -0,0 +1,98 @@
+// Auth sessions - the devices a user is logged in on, and revoking them
+pub mod issue_token;
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+use crate::application::ports::{AccessTokenRepository, AuthSessionRepository};
+use crate::domain::entities::auth_session::AuthSession;
+use crate::domain::value_objects::UserId;
+
+/// How often a session's `last_seen_at` is written while it keeps making requests
+const TOUCH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Auth sessions, with the revoked ones kept in memory so token validation stays synchronous.
+/// Each instance reloads the revoked set with [`AuthSessions::refresh`], so a revocation made on
+/// another instance applies here at the next refresh.
+pub struct AuthSessions {
+    repo: Arc<dyn AuthSessionRepository>,
+    access_tokens: Arc<dyn AccessTokenRepository>,
+    revoked: RwLock<HashSet<Uuid>>,
+    last_touched: Mutex<HashMap<Uuid, Instant>>,
+}
+
+impl AuthSessions {
+    pub fn new(repo: Arc<dyn AuthSessionRepository>, access_tokens: Arc<dyn AccessTokenRepository>) -> Self {
+        Self {
+            repo,
+            access_tokens,
+            revoked: RwLock::new(HashSet::new()),
+            last_touched: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub async fn start(&self, session: &AuthSession) -> Result<(), String> {
+        self.repo.create(session).await
+    }
+
+    pub fn is_revoked(&self, id: &Uuid) -> bool {
+        self.revoked.read().unwrap_or_else(|e| e.into_inner()).contains(id)
+    }
+
+    /// Note an authenticated request made with session `id`. Must be called from the runtime.
+    pub fn seen(&self, id: Uuid) {
+        let now = Instant::now();
+        {
+            let mut last_touched = self.last_touched.lock().unwrap_or_else(|e| e.into_inner());
+            if last_touched.get(&id).is_some_and(|at| now.duration_since(*at) < TOUCH_INTERVAL) {
+                return;
+            }
+            last_touched.insert(id, now);
+        }
+        let repo = self.repo.clone();
+        tokio::spawn(async move {
+            if let Err(e) = repo.touch(&id, chrono::Utc::now()).await {
+                tracing::warn!("Failed to update last use of auth session {}: {}", id, e);
+            }
+        });
+    }
+
+    pub async fn list(&self, user_id: &UserId) -> Result<Vec<AuthSession>, String> {
+        self.repo.list_active(user_id).await
+    }
+
+    /// Sign out one of the user's devices. False when the session is not theirs or was
+    /// already revoked.
+    pub async fn revoke(&self, user_id: &UserId, id: &Uuid) -> Result<bool, String> {
+        let revoked = self.repo.revoke(user_id, id, chrono::Utc::now()).await?;
+        if revoked {
+            self.revoked.write().unwrap_or_else(|e| e.into_inner()).insert(*id);
+        }
+        Ok(revoked)
+    }
+
+    /// Sign the user out everywhere, personal access tokens included. Returns how many
+    /// sessions and tokens were revoked.
+    pub async fn revoke_all(&self, user_id: &UserId) -> Result<usize, String> {
+        let mut revoked = self.access_tokens.revoke_all(user_id).await?;
+        for session in self.repo.list_active(user_id).await? {
+            if self.revoke(user_id, &session.id).await? {
+                revoked += 1;
+            }
+        }
+        Ok(revoked)
+    }
+
+    /// Reload the revoked sessions (dropping those whose tokens expired) and forget idle ones.
+    pub async fn refresh(&self) -> Result<(), String> {
+        let revoked: HashSet<Uuid> = self.repo.revoked_unexpired(chrono::Utc::now()).await?.into_iter().collect();
+        *self.revoked.write().unwrap_or_else(|e| e.into_inner()) = revoked;
+        let now = Instant::now();
+        self.last_touched
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .retain(|_, at| now.duration_since(*at) < TOUCH_INTERVAL);
+        Ok(())
+    }
+}
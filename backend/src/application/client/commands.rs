@@ -1,3 +1,4 @@
 // Client commands
 pub mod launch_application;
 pub mod list_my_permissions;
+pub mod set_stream_quality;
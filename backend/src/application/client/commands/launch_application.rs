@@ -1,8 +1,57 @@
+use std::path::Path;
 use axum::http::StatusCode;
 use crate::infrastructure::AppState;
 use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
 use crate::domain::value_objects::user_role::UserRole;
+use crate::domain::aggregates::application_session::{VideoCodec, VideoConfig, MAX_DISPLAY_SIZE, MIN_DISPLAY_SIZE};
+use crate::domain::entities::file_permission::FilePermission;
+use crate::domain::entities::impersonation_consent::{ImpersonationConsent, IMPERSONATION_ROLE};
 use crate::domain::entities::session::Session;
+use crate::domain::entities::session_timeline::TimelineStage;
+use crate::domain::entities::user_preferences::is_timezone_name;
+use crate::domain::services::permission_evaluator::{Authority, PermissionEvaluator};
+use crate::application::apps::availability::{self, AppUser};
+use crate::application::profile::commands::get_my_preferences;
+use crate::application::sessions::app_state::AppStateScope;
+use crate::application::sessions::scope;
+use crate::infrastructure::driven::storage::vault_dir;
+use crate::infrastructure::driven::sandbox::pipeline_template::STREAM_CODEC;
+use crate::infrastructure::driven::sandbox::xvfb::AppLaunch;
+use crate::infrastructure::driving::webrtc::quality_limits;
+use shared::i18n::tr;
+use shared::PlatformMessage;
+use tracing::Instrument;
+
+/// What the user asked of a launch; anything unset falls back to their preferences
+#[derive(Debug, Clone, Default)]
+pub struct LaunchParameters {
+    /// Display size in CSS pixels
+    pub width: Option<u16>,
+    pub height: Option<u16>,
+    /// Browser `devicePixelRatio`
+    pub scale_factor: Option<f32>,
+    pub framerate: Option<u8>,
+    pub codec: Option<VideoCodec>,
+    /// Browser's IANA time zone, used unless the user chose one
+    pub timezone: Option<String>,
+}
+
+/// Where a launch comes from, beyond what the user asked for
+#[derive(Default)]
+pub struct LaunchOrigin<'a> {
+    /// Instance the scheduler placed this launch on, when it was redirected here
+    pub placed_on: Option<&'a str>,
+    /// State saved by the app of the suspended session this launch resumes
+    pub resume_state: Option<Vec<u8>>,
+    /// Set when an owner opens the session as one of their clients, with the client's consent
+    pub impersonating: Option<Impersonation<'a>>,
+}
+
+/// The client an owner acts as, and the consent that allows it
+pub struct Impersonation<'a> {
+    pub consent: &'a ImpersonationConsent,
+    pub client_email: &'a str,
+}
 
 pub struct LaunchResult {
     pub session_id: String,
@@ -13,22 +62,87 @@ pub async fn execute(
     state: &AppState,
     user: &AuthenticatedUser,
     app_id: &str,
-    width: u16,
-    height: u16,
+    params: LaunchParameters,
+    origin: LaunchOrigin<'_>,
 ) -> Result<LaunchResult, (StatusCode, String)> {
-    let ws_base = std::env::var("WEBSOCKET_BASE_URL")
-        .unwrap_or_else(|_| "ws://localhost:8080".to_string());
+    let session_timeout = state.config.current().parse::<u64>("SESSION_TIMEOUT_SECS").unwrap_or(3600);
+    let impersonating = origin.impersonating.as_ref();
+    // A session opened as a client is the client's: their settings, their apps, their files
+    let subject = impersonating.map_or(&user.id, |i| &i.consent.client_id);
+    let session_timeout = impersonating.map_or(session_timeout, |i| i.consent.session_secs(chrono::Utc::now(), session_timeout));
+
+    let (locale, preferences) =
+        get_my_preferences::execute(&*state.user_repo, &*state.user_preferences_repo, subject)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    // What the user asked for is checked against what the app and the server allow; their
+    // preferences are brought within those limits instead
+    let limits = quality_limits(&state.config.current());
+    let app_limits = state
+        .xvfb_manager
+        .video_limits(app_id)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("{e:#}")))?;
+    let (max_width, max_height) = app_limits.max_size();
+    let max_framerate = app_limits.max_framerate(&limits);
+    let saved_framerate = match state.quality_preference_repo.find(subject, app_id).await {
+        Ok(Some(pref)) => pref.quality.framerate,
+        _ => preferences.default_framerate,
+    };
+    let requested = VideoConfig {
+        width: params.width.unwrap_or(preferences.default_width.clamp(MIN_DISPLAY_SIZE.0, max_width)),
+        height: params.height.unwrap_or(preferences.default_height.clamp(MIN_DISPLAY_SIZE.1, max_height)),
+        framerate: params.framerate.unwrap_or(saved_framerate.clamp(limits.min_framerate, max_framerate)),
+        codec: params.codec.unwrap_or(STREAM_CODEC),
+    };
+    app_limits.check(&requested, &limits).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    // Templates were built at startup; the built-in pipeline needs the plugins probed then.
+    // The WebRTC track only carries the stream codec so far.
+    let codec = requested.codec;
+    if codec != STREAM_CODEC
+        || (!state.xvfb_manager.uses_pipeline_template(app_id) && !state.codec_support.is_available(codec))
+    {
+        let message = tr(locale, "errors.codec_unavailable").replace("{codec}", &format!("{:?}", codec));
+        return Err((StatusCode::NOT_IMPLEMENTED, message));
+    }
 
-    let session_timeout = std::env::var("SESSION_TIMEOUT_SECS")
-        .ok()
-        .and_then(|s| s.parse::<u64>().ok())
-        .unwrap_or(3600);
+    // The display and capture run at device resolution so text stays sharp on high-DPI clients
+    let scale_factor = clamp_scale_factor(params.scale_factor.unwrap_or(1.0));
+    // A zone the browser made up is ignored rather than failing the launch
+    let timezone = preferences
+        .timezone
+        .clone()
+        .or(params.timezone.filter(|tz| is_timezone_name(tz)))
+        .unwrap_or_else(shared::protocol::default_timezone);
+    let (width, height) = device_size(requested.width, requested.height, scale_factor, (max_width, max_height));
+    let video = VideoConfig { width, height, ..requested };
 
     // Determine root_path and role context
-    let (root_path, acting_as_owner_id, active_role, allowed_paths) =
-        if user.roles.contains(&UserRole::Owner) || user.roles.contains(&UserRole::SuperAdmin) {
-            let path = format!("{}/{}", state.storage_path, user.id);
-            (path, None, "owner".to_string(), vec![])
+    let (root_path, acting_as_owner_id, active_role, allowed_paths, authority) =
+        if let Some(impersonation) = impersonating {
+            let owner_id = impersonation.consent.owner_id.clone();
+            let mut permissions: Vec<FilePermission> = state
+                .file_permission_repo
+                .find_active_for_client(subject)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+                .into_iter()
+                .filter(|p| p.owner_id == owner_id)
+                .collect();
+            if permissions.is_empty() {
+                return Err((StatusCode::FORBIDDEN, tr(locale, "errors.no_active_permissions").to_string()));
+            }
+            // The owner sees what the client sees, but never changes files in their name
+            for permission in &mut permissions {
+                permission.view_only = true;
+            }
+            let root = vault_dir(Path::new(&state.storage_path), user.tenant_id, &owner_id.to_string()).display().to_string();
+            let allowed = scope::allowed_paths(&root, &permissions);
+
+            (root, Some(owner_id), IMPERSONATION_ROLE.to_string(), allowed, Authority::Client(permissions))
+        } else if user.roles.contains(&UserRole::Owner) || user.roles.contains(&UserRole::SuperAdmin) {
+            let path = vault_dir(Path::new(&state.storage_path), user.tenant_id, &user.id.to_string()).display().to_string();
+            (path, None, "owner".to_string(), vec![], Authority::Owner)
         } else {
             let permissions = state
                 .file_permission_repo
@@ -37,57 +151,208 @@ pub async fn execute(
                 .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
 
             if permissions.is_empty() {
-                return Err((StatusCode::FORBIDDEN, "No active permissions for this client".to_string()));
+                return Err((StatusCode::FORBIDDEN, tr(locale, "errors.no_active_permissions").to_string()));
             }
 
             let owner_id = permissions[0].owner_id.clone();
-            let root = format!("{}/{}", state.storage_path, owner_id);
-            let allowed = permissions
-                .iter()
-                .map(|p| format!("{}/{}", root, p.path))
-                .collect::<Vec<_>>();
+            // Permissions never cross tenants, so the vault is in the user's
+            let root = vault_dir(Path::new(&state.storage_path), user.tenant_id, &owner_id.to_string()).display().to_string();
+            let allowed = scope::allowed_paths(&root, &permissions);
 
-            (root, Some(owner_id), "client".to_string(), allowed)
+            (root, Some(owner_id), "client".to_string(), allowed, Authority::Client(permissions))
         };
+    let vault_owner_id = acting_as_owner_id.clone().unwrap_or_else(|| user.id.clone());
+    // The vault's owner decides which apps are offered, and to whom
+    let app_user = AppUser {
+        owner_id: vault_owner_id.clone(),
+        role: if acting_as_owner_id.is_some() { UserRole::Client } else { UserRole::Owner },
+        user_id: subject.clone(),
+    };
+    let available = availability::is_available(&*state.app_setting_repo, &app_user, app_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    if !available {
+        return Err((StatusCode::FORBIDDEN, tr(locale, "errors.app_unavailable").to_string()));
+    }
+    // Files under legal hold are not deleted through the session, nor changed by the app
+    let holds = state
+        .legal_hold_repo
+        .list_for_owner(&vault_owner_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    let held_paths = scope::held_paths(&root_path, &holds, &allowed_paths, chrono::Utc::now());
+    let permissions = PermissionEvaluator::new(authority, holds);
+    let view_only = permissions.is_view_only(chrono::Utc::now());
+    let interaction = permissions.interaction(chrono::Utc::now());
+
+    // Another instance may have more room; a launch it sent here was already placed
+    let local = state.host_metrics.status(&state.xvfb_manager, state.maintenance.is_active()).await;
+    if origin.placed_on != Some(local.instance_id.as_str()) {
+        let class = state.xvfb_manager.resource_class(app_id);
+        let (decision, hosts) = state.scheduler.place(app_id, class, &local).await;
+        match decision.chosen_host(&hosts) {
+            None if local.draining => return Err((StatusCode::SERVICE_UNAVAILABLE, tr(locale, "errors.maintenance").to_string())),
+            None => {
+                return Err((StatusCode::SERVICE_UNAVAILABLE, format!("No host has room for a {} app", class.as_str())));
+            }
+            Some(host) if host.instance_id != local.instance_id => {
+                let target = serde_json::json!({ "launch_url": host.launch_url, "instance_id": host.instance_id });
+                return Err((StatusCode::MISDIRECTED_REQUEST, target.to_string()));
+            }
+            Some(_) => {}
+        }
+    } else if local.draining {
+        // Placed here before maintenance started
+        return Err((StatusCode::SERVICE_UNAVAILABLE, tr(locale, "errors.maintenance").to_string()));
+    }
 
     // Create session record to get the session_id
-    let session = Session::new(
-        user.id.clone(),
+    let mut session = Session::new(
+        subject.clone(),
         acting_as_owner_id,
         active_role,
         app_id.to_string(),
         None, // display_number set after xvfb starts
         session_timeout,
     );
+    // Read back by the signaling socket for the stream's starting framerate
+    session.video = Some(video);
+    session.tenant_id = user.tenant_id;
+    // Read back by the signaling socket, which drops the input of watch-only sessions
+    session.interaction = interaction;
     let session_id = session.id.to_string();
+    // Everything done for the session from here, spawned tasks included, logs under its span
+    let span = state.session_logs.open(&session_id, &user.id, app_id);
+    async {
+        // Display, app and peer live in this instance's memory, so signaling must come back here
+        state
+            .session_affinity
+            .claim(&session_id)
+            .await
+            .map_err(|e| (StatusCode::SERVICE_UNAVAILABLE, e))?;
+        state
+            .session_repo
+            .save(&session)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+        let timeline = state.session_timelines.recorder(&session_id);
+        timeline.record(
+            TimelineStage::Launched,
+            Some(format!("{app_id} at {width}x{height}, {} fps, {:?}", video.framerate, codec)),
+        );
 
-    state
-        .session_repo
-        .save(&session)
-        .await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+        // Take a pooled display when one waits for this app at this size, else start Xvfb
+        if state.xvfb_manager.bind_warm(&session_id, app_id, width, height).await.is_some() {
+            timeline.record(TimelineStage::XvfbStarted, Some("from pool".to_string()));
+            let pool = state.session_pool.clone();
+            tokio::spawn(async move { pool.refill().await });
+        } else {
+            let start_result = state.xvfb_manager.start_xvfb(&session_id, width, height).await;
+            if let Err(e) = start_result {
+                let _ = state.session_repo.terminate(&session.id).await;
+                timeline.record(TimelineStage::LaunchFailed, Some(format!("Xvfb: {e}")));
+                let _ = state.session_timelines.finish(&session_id).await;
+                let _ = state.session_affinity.release(&session_id).await;
+                return Err((StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to start Xvfb: {e}")));
+            }
+            timeline.record(TimelineStage::XvfbStarted, None);
+        }
 
-    // Start Xvfb using the session_id
-    let start_result = state.xvfb_manager.start_xvfb(&session_id, width, height).await;
-    if let Err(e) = start_result {
-        let _ = state.session_repo.terminate(&session.id).await;
-        return Err((StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to start Xvfb: {e}")));
-    }
+        // Session context handed to the app once it connects over IPC, with what it saved for
+        // this user before; a store failure only costs the app its restore. An owner acting as a
+        // client keeps their own, so the client's saved state is left as they left it.
+        let scope = AppStateScope { user_id: user.id.clone(), app_id: app_id.to_string() };
+        let saved_state = state.app_states.load_all(&scope).await.unwrap_or_else(|e| {
+            tracing::warn!("Failed to load saved state of {} for session {}: {}", app_id, session_id, e);
+            Default::default()
+        });
+        let ipc_token = state
+            .ipc_server
+            .prepare_session(
+                &session_id,
+                scope,
+                PlatformMessage::Init {
+                    locale,
+                    theme: preferences.theme,
+                    keyboard_layout: preferences.keyboard_layout.clone(),
+                    timezone: timezone.clone(),
+                    scale_factor,
+                    view_only,
+                    saved_state,
+                    allowed_paths: allowed_paths.clone(),
+                },
+            )
+            .await;
+        state.ipc_server.set_permissions(&session_id, permissions).await;
+        // Permissions changing mid-session are pushed to the app within these folders
+        if !allowed_paths.is_empty() {
+            state.ipc_server.set_scope(&session_id, allowed_paths.clone()).await;
+        }
+        state.ipc_server.set_vault_root(&session_id, Path::new(&root_path).to_path_buf()).await;
+        // A resumed session's app gets back the state it saved when suspended
+        if let Some(saved) = origin.resume_state {
+            state.ipc_server.prepare_resume(&session_id, saved).await;
+        }
 
-    // Launch app
-    let launch_result = state
-        .xvfb_manager
-        .launch_app(&session_id, app_id, width, height, &root_path, &allowed_paths)
-        .await;
-    if let Err(e) = launch_result {
-        let _ = state.xvfb_manager.cleanup_session(&session_id).await;
-        let _ = state.session_repo.terminate(&session.id).await;
-        return Err((StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to launch app: {e}")));
+        // Frames of view-only sessions carry who is watching, so leaked captures can be traced.
+        // Sessions opened as a client also say whose view it is.
+        if let Some(impersonation) = impersonating {
+            state
+                .xvfb_manager
+                .set_watermark(&session_id, format!("{} as {} · {}", user.email, impersonation.client_email, session_id))
+                .await;
+        } else if view_only {
+            state
+                .xvfb_manager
+                .set_watermark(&session_id, format!("{} · {}", user.email, session_id))
+                .await;
+        }
+
+        // Launch app
+        let user_id = user.id.to_string();
+        let launch_result = state
+            .xvfb_manager
+            .launch_app(AppLaunch {
+                session_id: &session_id,
+                app_name: app_id,
+                user_id: &user_id,
+                width,
+                height,
+                root_path: &root_path,
+                allowed_paths: &allowed_paths,
+                held_paths: &held_paths,
+                ipc_token: &ipc_token,
+                locale,
+                timezone: &timezone,
+            })
+            .await;
+        if let Err(e) = launch_result {
+            let _ = state.xvfb_manager.cleanup_session(&session_id).await;
+            let _ = state.session_repo.terminate(&session.id).await;
+            timeline.record(TimelineStage::LaunchFailed, Some(format!("App: {e}")));
+            let _ = state.session_timelines.finish(&session_id).await;
+            let _ = state.session_affinity.release(&session_id).await;
+            return Err((StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to launch app: {e}")));
+        }
+        timeline.record(TimelineStage::AppSpawned, None);
+
+        // Mark session ready
+        let _ = state.session_repo.update_state(&session.id, "ready").await;
+
+        let websocket_url = format!("{}/ws?session={}", state.session_affinity.instance().websocket_base_url, session_id);
+        Ok(LaunchResult { session_id, websocket_url })
     }
+    .instrument(span)
+    .await
+}
 
-    // Mark session ready
-    let _ = state.session_repo.update_state(&session.id, "ready").await;
+fn clamp_scale_factor(scale: f32) -> f32 {
+    if scale.is_finite() { scale.clamp(1.0, 4.0) } else { 1.0 }
+}
 
-    let websocket_url = format!("{}/ws?session={}", ws_base, session_id);
-    Ok(LaunchResult { session_id, websocket_url })
+/// Logical size scaled to device pixels, rounded to even values for the encoder
+/// and capped at `max` (at most 8K).
+fn device_size(width: u16, height: u16, scale_factor: f32, max: (u16, u16)) -> (u16, u16) {
+    let scale = |v: u16, max: u16| (((v as f32 * scale_factor).min(max as f32) as u16) / 2) * 2;
+    (scale(width, max.0.min(MAX_DISPLAY_SIZE.0)), scale(height, max.1.min(MAX_DISPLAY_SIZE.1)))
 }
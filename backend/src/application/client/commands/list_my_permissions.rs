@@ -1,9 +1,14 @@
-use crate::application::ports::file_permission_repository::FilePermissionRepository;
+use crate::application::ports::file_permission_repository::{FilePermissionRepository, PermissionFilter};
+use crate::application::ports::pagination::{Page, PageRequest};
+use crate::domain::entities::file_permission::FilePermission;
 use crate::domain::value_objects::UserId;
 
 pub async fn execute<R: FilePermissionRepository + ?Sized>(
     repo: &R,
     client_id: &UserId,
-) -> Result<Vec<crate::domain::entities::file_permission::FilePermission>, String> {
-    repo.find_active_for_client(client_id).await
+    mut filter: PermissionFilter,
+    page: &PageRequest,
+) -> Result<Page<FilePermission>, String> {
+    filter.client_id = Some(client_id.clone());
+    repo.list(&filter, page).await
 }
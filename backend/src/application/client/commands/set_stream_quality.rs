@@ -0,0 +1,26 @@
+use crate::application::ports::QualityPreferenceRepository;
+use crate::domain::aggregates::application_session::{QualityLimits, StreamQuality};
+use crate::domain::entities::quality_preference::QualityPreference;
+use crate::domain::value_objects::UserId;
+
+/// Validate a requested stream quality against the server limits and remember it
+/// as the user's preference for the given application.
+pub async fn execute<R: QualityPreferenceRepository + ?Sized>(
+    repo: &R,
+    user_id: &UserId,
+    app_id: &str,
+    quality: StreamQuality,
+    limits: &QualityLimits,
+) -> Result<StreamQuality, String> {
+    quality.validate(limits)?;
+
+    let preference = QualityPreference {
+        user_id: user_id.clone(),
+        app_id: app_id.to_string(),
+        quality,
+        updated_at: chrono::Utc::now(),
+    };
+    repo.save(&preference).await?;
+
+    Ok(quality)
+}
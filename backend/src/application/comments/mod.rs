@@ -0,0 +1,166 @@
+// Comments - owners and the clients they share files with discussing a file
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use uuid::Uuid;
+use crate::application::owner::scope;
+use crate::application::ports::FileCommentRepository;
+use crate::domain::entities::file_comment::FileComment;
+use crate::domain::entities::notification::Notification;
+use crate::domain::services::markdown;
+use crate::domain::services::permission_evaluator::{Authority, Operation, PermissionEvaluator};
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::AppState;
+
+/// A comment as shown in a thread
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CommentView {
+    pub id: Uuid,
+    pub path: String,
+    pub author_id: UserId,
+    /// Written by the vault's owner or a co-owner, rather than a client
+    pub by_owner: bool,
+    pub body: String,
+    pub body_html: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Which side of the share the acting user is on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Side {
+    Owner,
+    Client,
+}
+
+/// Which side the acting user reads the file from, as owner, co-owner or client; `None` when
+/// they may not read it.
+async fn side(state: &AppState, acting_id: &UserId, owner_id: &UserId, path: &str) -> Result<Option<Side>, String> {
+    if let Ok(scope) = scope::resolve(&*state.delegation_repo, acting_id, owner_id).await {
+        if scope.covers(path) {
+            return Ok(Some(Side::Owner));
+        }
+    }
+    let grants = state.file_permission_repo.find_by_owner_client(owner_id, acting_id).await?;
+    let reads = PermissionEvaluator::new(Authority::Client(grants), Vec::new()).check(path, Operation::Read, Utc::now()).is_ok();
+    Ok(reads.then_some(Side::Client))
+}
+
+/// Who may comment on a file: whoever may read it. Anyone else is told it does not exist.
+async fn authorize(state: &AppState, acting_id: &UserId, owner_id: &UserId, path: &str) -> Result<Side, String> {
+    let not_found = || format!("File not found: {path}");
+    let side = side(state, acting_id, owner_id, path).await?.ok_or_else(not_found)?;
+    state.vault_storage.stat(owner_id, path).await.map_err(|_| not_found())?;
+    Ok(side)
+}
+
+fn view(comment: FileComment, owner_side: &[UserId]) -> CommentView {
+    CommentView {
+        by_owner: owner_side.contains(&comment.author_id),
+        body_html: markdown::to_html(&comment.body),
+        id: comment.id,
+        path: comment.path,
+        author_id: comment.author_id,
+        body: comment.body,
+        created_at: comment.created_at,
+    }
+}
+
+/// The vault owner and everyone who was their co-owner, who comment as the owner side.
+async fn owner_side(state: &AppState, owner_id: &UserId) -> Result<Vec<UserId>, String> {
+    let mut ids = vec![owner_id.clone()];
+    ids.extend(state.delegation_repo.find_by_owner(owner_id).await?.into_iter().map(|d| d.delegate_id));
+    Ok(ids)
+}
+
+/// The thread on a file, oldest first.
+pub async fn list(state: &AppState, acting_id: &UserId, owner_id: &UserId, path: &str) -> Result<Vec<CommentView>, String> {
+    let path = path.trim_matches('/');
+    authorize(state, acting_id, owner_id, path).await?;
+    let owner_side = owner_side(state, owner_id).await?;
+    let comments = state.file_comment_repo.find_for_file(owner_id, path).await?;
+    Ok(comments.into_iter().map(|comment| view(comment, &owner_side)).collect())
+}
+
+/// Add to a file's thread. A client's comment notifies the owner; the owner side's notifies
+/// the clients currently allowed to read the file.
+pub async fn add(state: &AppState, acting_id: &UserId, owner_id: &UserId, path: &str, body: &str) -> Result<CommentView, String> {
+    let comment = FileComment::new(owner_id.clone(), path, acting_id.clone(), body)?;
+    let side = authorize(state, acting_id, owner_id, &comment.path).await?;
+    state.file_comment_repo.save(&comment).await?;
+
+    let mut recipients = Vec::new();
+    if side == Side::Client || acting_id != owner_id {
+        recipients.push(owner_id.clone());
+    }
+    if side == Side::Owner {
+        let now = Utc::now();
+        for grant in state.file_permission_repo.find_active_by_owner(owner_id).await? {
+            let reads = PermissionEvaluator::new(Authority::Client(vec![grant.clone()]), Vec::new())
+                .check(&comment.path, Operation::Read, now)
+                .is_ok();
+            if reads && !recipients.contains(&grant.client_id) {
+                recipients.push(grant.client_id);
+            }
+        }
+    }
+    let payload = json!({ "comment_id": comment.id, "owner_id": owner_id, "path": comment.path, "author_id": acting_id });
+    for recipient in recipients.into_iter().filter(|id| id != acting_id) {
+        if let Err(e) = state.notification_repo.create(&Notification::new(recipient, "file_comment_added", payload.clone())).await {
+            tracing::warn!("Failed to notify of comment {}: {}", comment.id, e);
+        }
+    }
+
+    let owner_side = owner_side(state, owner_id).await?;
+    Ok(view(comment, &owner_side))
+}
+
+/// Remove a comment. Authors may remove their own, and the owner side anything on files they
+/// manage.
+pub async fn delete(state: &AppState, acting_id: &UserId, id: &Uuid) -> Result<(), String> {
+    let comment = state
+        .file_comment_repo
+        .find_by_id(id)
+        .await?
+        .ok_or_else(|| "Comment not found".to_string())?;
+    if &comment.author_id != acting_id {
+        match side(state, acting_id, &comment.owner_id, &comment.path).await? {
+            Some(Side::Owner) => {}
+            Some(Side::Client) => return Err("Only the author or the vault owner can delete a comment".to_string()),
+            None => return Err("Comment not found".to_string()),
+        }
+    }
+    state.file_comment_repo.delete(id).await
+}
+
+/// Comments on each file directly in the folder `path`, for listings. Files without any are
+/// left out.
+pub async fn counts<C>(comments: &C, owner_id: &UserId, path: &str) -> Result<HashMap<String, u64>, String>
+where
+    C: FileCommentRepository + ?Sized,
+{
+    let folder = path.trim_matches('/');
+    Ok(comments
+        .count_under(owner_id, folder)
+        .await?
+        .into_iter()
+        .filter(|(file, _)| {
+            let name = if folder.is_empty() { Some(file.as_str()) } else { file.strip_prefix(folder).and_then(|rest| rest.strip_prefix('/')) };
+            name.is_some_and(|name| !name.contains('/'))
+        })
+        .collect())
+}
+
+/// Like [`counts`], limited to the files the acting user may read.
+pub async fn readable_counts(state: &AppState, acting_id: &UserId, owner_id: &UserId, path: &str) -> Result<HashMap<String, u64>, String> {
+    let mut per_file = counts(&*state.file_comment_repo, owner_id, path).await?;
+    let evaluator = match scope::resolve(&*state.delegation_repo, acting_id, owner_id).await {
+        Ok(scope) => scope.evaluator(Vec::new()),
+        Err(_) => {
+            let grants = state.file_permission_repo.find_by_owner_client(owner_id, acting_id).await?;
+            PermissionEvaluator::new(Authority::Client(grants), Vec::new())
+        }
+    };
+    let now = Utc::now();
+    per_file.retain(|file, _| evaluator.check(file, Operation::Read, now).is_ok());
+    Ok(per_file)
+}
@@ -0,0 +1,64 @@
+// Data exports - archives of everything held about a user, assembled in the background
+pub mod run;
+
+use crate::application::ports::{AuditRepository, ByteStream, DataExportRepository, VaultStorage};
+use crate::domain::entities::audit_event::AuditEvent;
+use crate::domain::entities::data_export::DataExport;
+use crate::domain::entities::file_job::JobStatus;
+use crate::domain::value_objects::UserId;
+use uuid::Uuid;
+
+/// Queue `export` for the background worker. A requester runs one export at a time.
+pub async fn queue<E, A>(exports: &E, audit: &A, export: DataExport) -> Result<DataExport, String>
+where
+    E: DataExportRepository + ?Sized,
+    A: AuditRepository + ?Sized,
+{
+    for status in [JobStatus::Queued, JobStatus::Running] {
+        if exports.find_by_status(status).await?.iter().any(|e| e.requested_by == export.requested_by) {
+            return Err("An export is already in progress".to_string());
+        }
+    }
+    exports.save(&export).await?;
+
+    let mut event = AuditEvent::new(
+        "data_export_requested",
+        serde_json::json!({ "export_id": export.id, "subject_id": export.subject_id }),
+    );
+    event.owner_id = export.owner_id.clone();
+    event.user_id = Some(export.requested_by.clone());
+    audit.record(&event).await?;
+    Ok(export)
+}
+
+/// An export is visible only to whoever requested it.
+pub async fn find<E: DataExportRepository + ?Sized>(
+    exports: &E,
+    acting_id: &UserId,
+    export_id: &Uuid,
+) -> Result<DataExport, String> {
+    exports
+        .find_by_id(export_id)
+        .await?
+        .filter(|export| export.is_visible_to(acting_id))
+        .ok_or_else(|| "Export not found".to_string())
+}
+
+/// The archive of a finished export.
+pub async fn download<E, S>(
+    exports: &E,
+    storage: &S,
+    acting_id: &UserId,
+    export_id: &Uuid,
+) -> Result<(DataExport, ByteStream), String>
+where
+    E: DataExportRepository + ?Sized,
+    S: VaultStorage + ?Sized,
+{
+    let export = find(exports, acting_id, export_id).await?;
+    if !export.is_ready() {
+        return Err("Export is not ready".to_string());
+    }
+    let stream = storage.read_export(&export.id).await?;
+    Ok((export, stream))
+}
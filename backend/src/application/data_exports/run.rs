@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+use std::future::Future;
+use serde_json::json;
+use crate::application::ports::audit_repository::AuditFilter;
+use crate::application::ports::file_permission_repository::{PermissionFilter, PermissionStatus};
+use crate::application::ports::pagination::{Cursor, Page, PageRequest, SortDirection, MAX_PAGE_SIZE};
+use crate::application::ports::{DataExportRepository, VaultStorage};
+use crate::domain::entities::audit_event::AuditEvent;
+use crate::domain::entities::data_export::{DataExport, ExportStep};
+use crate::domain::entities::file_job::JobStatus;
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::AppState;
+
+/// Run every queued export, oldest first. Returns how many were run.
+pub async fn run_pending(state: &AppState) -> Result<usize, String> {
+    let queued = state.data_export_repo.find_by_status(JobStatus::Queued).await?;
+    for export in &queued {
+        run_export(state, export).await?;
+    }
+    Ok(queued.len())
+}
+
+/// Exports left running by a restart start over: their staging area is dropped and they go
+/// back to the queue.
+pub async fn requeue_interrupted<E, S>(exports: &E, storage: &S) -> Result<usize, String>
+where
+    E: DataExportRepository + ?Sized,
+    S: VaultStorage + ?Sized,
+{
+    let running = exports.find_by_status(JobStatus::Running).await?;
+    for export in &running {
+        storage.discard_export(&export.id).await?;
+        exports.update_progress(&export.id, JobStatus::Queued, 0, None, None).await?;
+    }
+    Ok(running.len())
+}
+
+/// Delete finished exports, and their archives, last updated before `before`.
+pub async fn expire<E, S>(exports: &E, storage: &S, before: chrono::DateTime<chrono::Utc>) -> Result<usize, String>
+where
+    E: DataExportRepository + ?Sized,
+    S: VaultStorage + ?Sized,
+{
+    let mut expired = 0;
+    for status in [JobStatus::Completed, JobStatus::Failed] {
+        for export in exports.find_by_status(status).await? {
+            if export.updated_at < before {
+                storage.discard_export(&export.id).await?;
+                exports.delete(&export.id).await?;
+                expired += 1;
+            }
+        }
+    }
+    Ok(expired)
+}
+
+async fn run_export(state: &AppState, export: &DataExport) -> Result<(), String> {
+    let exports = &*state.data_export_repo;
+    exports.update_progress(&export.id, JobStatus::Running, 0, None, None).await?;
+
+    let mut completed = 0;
+    let mut size_bytes = None;
+    let mut error = None;
+    for step in ExportStep::ALL {
+        let result = match step {
+            ExportStep::Profile => stage_profile(state, export).await,
+            ExportStep::Records => stage_records(state, export).await,
+            ExportStep::Files => stage_files(state, export).await,
+            ExportStep::Archive => state.vault_storage.finish_export(&export.id).await.map(|size| size_bytes = Some(size)),
+        };
+        if let Err(e) = result {
+            error = Some(format!("{step:?} step failed: {e}"));
+            break;
+        }
+        completed += 1;
+        exports.update_progress(&export.id, JobStatus::Running, completed, None, None).await?;
+    }
+
+    let status = if error.is_none() { JobStatus::Completed } else { JobStatus::Failed };
+    if error.is_some() {
+        state.vault_storage.discard_export(&export.id).await?;
+    }
+    exports.update_progress(&export.id, status, completed, error.as_deref(), size_bytes).await?;
+
+    let mut event = AuditEvent::new(
+        "data_export_finished",
+        json!({
+            "export_id": export.id,
+            "subject_id": export.subject_id,
+            "status": status,
+            "size_bytes": size_bytes,
+            "error": error,
+        }),
+    );
+    event.owner_id = export.owner_id.clone();
+    event.user_id = Some(export.requested_by.clone());
+    state.audit_repo.record(&event).await
+}
+
+fn to_document(value: serde_json::Value) -> Result<Vec<u8>, String> {
+    serde_json::to_vec_pretty(&value).map_err(|e| e.to_string())
+}
+
+/// Account details; a user's own export adds preferences, passkeys and logged-in devices,
+/// which an owner exporting a client does not get to see.
+async fn stage_profile(state: &AppState, export: &DataExport) -> Result<(), String> {
+    let user = state
+        .user_repo
+        .find_by_id(&export.subject_id)
+        .await?
+        .ok_or_else(|| "User not found".to_string())?;
+    let mut profile = json!({
+        "account": {
+            "id": user.id(),
+            "email": user.email().as_str(),
+            "display_name": user.display_name().as_str(),
+        },
+    });
+    if export.owner_id.is_none() {
+        profile["account"]["roles"] = json!(user.roles());
+        profile["account"]["status"] = json!(user.status());
+        profile["account"]["locale"] = json!(user.locale());
+        profile["preferences"] = match state.user_preferences_repo.find(user.id()).await? {
+            Some(p) => json!({
+                "theme": p.theme,
+                "default_width": p.default_width,
+                "default_height": p.default_height,
+                "default_framerate": p.default_framerate,
+                "keyboard_layout": p.keyboard_layout,
+                "timezone": p.timezone,
+                "notify_email": p.notify_email,
+                "notify_in_app": p.notify_in_app,
+                "updated_at": p.updated_at,
+            }),
+            None => serde_json::Value::Null,
+        };
+        let credentials = state.credential_repo.find_by_user_id(user.id()).await?;
+        profile["passkeys"] = credentials
+            .iter()
+            .map(|credential| {
+                let usage = credential.usage();
+                json!({
+                    "id": credential.passkey().cred_id(),
+                    "metadata": credential.metadata(),
+                    "created_at": usage.created_at,
+                    "last_used_at": usage.last_used_at,
+                })
+            })
+            .collect();
+        profile["devices"] = json!(state.auth_sessions.list(user.id()).await?);
+    }
+    state.vault_storage.stage_export_document(&export.id, "profile.json", to_document(profile)?).await
+}
+
+/// Permissions the subject holds (and, in their own export, has granted), and the audit
+/// entries about them.
+async fn stage_records(state: &AppState, export: &DataExport) -> Result<(), String> {
+    let received = PermissionFilter {
+        owner_id: export.owner_id.clone(),
+        client_id: Some(export.subject_id.clone()),
+        status: PermissionStatus::All,
+        ..Default::default()
+    };
+    let received = &received;
+    let received = collect_pages(|page| async move { state.file_permission_repo.list(received, &page).await }).await?;
+    let mut permissions = json!({ "received": received });
+    let performed = AuditFilter {
+        owner_id: export.owner_id.clone(),
+        user_id: Some(export.subject_id.clone()),
+        ..Default::default()
+    };
+    let performed = &performed;
+    let performed = collect_pages(|page| async move { state.audit_repo.list(performed, &page).await }).await?;
+    let mut audit = json!({ "performed": performed });
+
+    if export.owner_id.is_none() {
+        let granted = PermissionFilter {
+            owner_id: Some(export.subject_id.clone()),
+            status: PermissionStatus::All,
+            ..Default::default()
+        };
+        let granted = &granted;
+        let granted = collect_pages(|page| async move { state.file_permission_repo.list(granted, &page).await }).await?;
+        permissions["granted"] = json!(granted);
+        let in_vault = &AuditFilter { owner_id: Some(export.subject_id.clone()), ..Default::default() };
+        let events: Vec<AuditEvent> = collect_pages(|page| async move { state.audit_repo.list(in_vault, &page).await }).await?;
+        audit["in_vault"] = json!(events);
+    }
+
+    state.vault_storage.stage_export_document(&export.id, "permissions.json", to_document(permissions)?).await?;
+    state.vault_storage.stage_export_document(&export.id, "audit.json", to_document(audit)?).await
+}
+
+/// The subject's own vault and the files shared with them for download. An owner's export of
+/// a client lists the shared paths in `permissions.json` but leaves the contents out, since
+/// they are the owner's own files. With `STRIP_SHARED_GPS`, shared photos lose their
+/// location.
+async fn stage_files(state: &AppState, export: &DataExport) -> Result<(), String> {
+    if export.owner_id.is_some() {
+        return Ok(());
+    }
+    state.vault_storage.stage_export_files(&export.id, &export.subject_id, None).await?;
+
+    let mut shared: HashMap<UserId, Vec<String>> = HashMap::new();
+    for permission in state.file_permission_repo.find_active_for_client(&export.subject_id).await? {
+        if !permission.view_only {
+            shared.entry(permission.owner_id).or_default().push(permission.path);
+        }
+    }
+    let strip_gps = state.config.current().parse::<bool>("STRIP_SHARED_GPS").unwrap_or(false);
+    for (owner_id, paths) in &shared {
+        state.vault_storage.stage_export_files(&export.id, owner_id, Some(paths)).await?;
+        if strip_gps {
+            state.vault_storage.strip_export_gps(&export.id, owner_id).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Every item of a listing, following its cursors from the oldest item.
+async fn collect_pages<T, F, Fut>(mut fetch: F) -> Result<Vec<T>, String>
+where
+    F: FnMut(PageRequest) -> Fut,
+    Fut: Future<Output = Result<Page<T>, String>>,
+{
+    let mut items = Vec::new();
+    let mut page = PageRequest { limit: MAX_PAGE_SIZE, after: None, direction: SortDirection::Asc };
+    loop {
+        let batch = fetch(page.clone()).await?;
+        items.extend(batch.items);
+        match batch.next_cursor {
+            Some(cursor) => page.after = Some(Cursor::decode(&cursor)?),
+            None => return Ok(items),
+        }
+    }
+}
@@ -0,0 +1,193 @@
+// File requests - upload-only links that fill a quarantined inbox the owner sorts through
+use chrono::Utc;
+use serde_json::json;
+use uuid::Uuid;
+use crate::application::ports::{AuditRepository, ByteStream, FileRequestRepository, VaultStorage};
+use crate::application::processing;
+use crate::domain::entities::audit_event::AuditEvent;
+use crate::domain::entities::file_request::{FileRequest, MAX_PENDING_UPLOADS, MAX_RECEIVING_UPLOADS};
+use crate::domain::entities::inbox_upload::{InboxStatus, InboxUpload};
+use crate::domain::entities::notification::Notification;
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::AppState;
+
+pub mod token_guard;
+
+async fn record<A: AuditRepository + ?Sized>(audit: &A, kind: &str, owner_id: &UserId, details: serde_json::Value) -> Result<(), String> {
+    let mut event = AuditEvent::new(kind, details);
+    event.owner_id = Some(owner_id.clone());
+    event.user_id = Some(owner_id.clone());
+    audit.record(&event).await
+}
+
+/// Open a new request, provided its folder exists.
+pub async fn create<R, S, A>(requests: &R, storage: &S, audit: &A, request: FileRequest) -> Result<FileRequest, String>
+where
+    R: FileRequestRepository + ?Sized,
+    S: VaultStorage + ?Sized,
+    A: AuditRepository + ?Sized,
+{
+    storage.list(&request.owner_id, &request.folder).await?;
+    requests.save(&request).await?;
+    let details = json!({ "request_id": request.id, "folder": request.folder, "expires_at": request.expires_at });
+    record(audit, "file_request_created", &request.owner_id, details).await?;
+    Ok(request)
+}
+
+/// Close one of the owner's requests. Its link stops taking files at once; what already
+/// arrived stays in the inbox.
+pub async fn revoke<R, A>(requests: &R, audit: &A, owner_id: &UserId, id: &Uuid) -> Result<FileRequest, String>
+where
+    R: FileRequestRepository + ?Sized,
+    A: AuditRepository + ?Sized,
+{
+    let mut request = requests
+        .find_by_id(id)
+        .await?
+        .filter(|request| &request.owner_id == owner_id)
+        .ok_or_else(|| "File request not found".to_string())?;
+    if request.revoked_at.is_some() {
+        return Err("File request was already revoked".to_string());
+    }
+    request.revoked_at = Some(Utc::now());
+    requests.save(&request).await?;
+    record(audit, "file_request_revoked", owner_id, json!({ "request_id": request.id, "folder": request.folder })).await?;
+    Ok(request)
+}
+
+/// Take a file sent through an open request into the owner's inbox. `size` is what the sender
+/// declared; a body that ends early or runs past it is dropped. Its inbox row and bytes are
+/// reserved before any arrive, so concurrent senders stay within the link's limits and the
+/// owner's quota. The owner is notified.
+pub async fn receive(
+    state: &AppState,
+    request: &FileRequest,
+    file_name: &str,
+    size: u64,
+    sender: Option<&str>,
+    ip: &str,
+    body: ByteStream,
+) -> Result<InboxUpload, String> {
+    let file_name = request.accept(file_name, size)?;
+    let media_type = shared::listing::mime_type(std::path::Path::new(&file_name));
+    let mut upload = InboxUpload::new(request.id, request.owner_id.clone(), file_name, size, media_type, sender, ip.to_string());
+    let reserved = state
+        .file_request_repo
+        .reserve_upload(&upload, MAX_PENDING_UPLOADS, MAX_RECEIVING_UPLOADS)
+        .await?;
+    if !reserved {
+        return Err("Too many files are being sent or waiting for the owner; try again later".to_string());
+    }
+
+    let received = match fits_quota(state, &request.owner_id).await {
+        Ok(()) => state.vault_storage.append_upload(&upload.id, 0, size, body).await,
+        Err(e) => Err(e),
+    };
+    let received = match received {
+        Ok(received) if received == size => Ok(received),
+        Ok(_) => Err("Upload was interrupted before the whole file arrived".to_string()),
+        Err(e) => Err(e),
+    };
+    let saved = match received {
+        Ok(received) => {
+            upload.size = received;
+            upload.status = InboxStatus::Pending;
+            state.file_request_repo.save_upload(&upload).await
+        }
+        Err(e) => Err(e),
+    };
+    if let Err(e) = saved {
+        state.vault_storage.discard_upload(&upload.id).await?;
+        state.file_request_repo.delete_upload(&upload.id).await?;
+        return Err(e);
+    }
+
+    let details = json!({
+        "request_id": request.id,
+        "upload_id": upload.id,
+        "file_name": upload.file_name,
+        "size": upload.size,
+        "sender": upload.sender,
+        "ip": ip,
+    });
+    let mut event = AuditEvent::new("file_request_upload_received", details.clone());
+    event.owner_id = Some(request.owner_id.clone());
+    state.audit_repo.record(&event).await?;
+    let notification = Notification::new(request.owner_id.clone(), "file_request_upload", details);
+    if let Err(e) = state.notification_repo.create(&notification).await {
+        tracing::warn!("Failed to notify of inbox upload {}: {}", upload.id, e);
+    }
+    Ok(upload)
+}
+
+/// Whether the owner's quota has room for everything in their inbox, this upload included.
+/// The sender has no account, so they are not told how full the vault is.
+async fn fits_quota(state: &AppState, owner_id: &UserId) -> Result<(), String> {
+    let reserved = state.file_request_repo.reserved_bytes(owner_id).await?;
+    state.vault_storage.check_quota(owner_id, reserved).await.map_err(|e| {
+        tracing::info!("Refused a file request upload for {}: {}", owner_id, e);
+        "The owner has no room for this file (quota)".to_string()
+    })
+}
+
+/// Drop the uploads still being received after `max_age`, with their staged bytes. Returns how
+/// many were dropped.
+pub async fn expire_receiving(state: &AppState, max_age: chrono::Duration) -> Result<usize, String> {
+    let expired = state.file_request_repo.expire_receiving(Utc::now() - max_age).await?;
+    for id in &expired {
+        state.vault_storage.discard_upload(id).await?;
+    }
+    Ok(expired.len())
+}
+
+async fn pending(state: &AppState, owner_id: &UserId, id: &Uuid) -> Result<InboxUpload, String> {
+    let upload = state
+        .file_request_repo
+        .find_upload(id)
+        .await?
+        .filter(|upload| &upload.owner_id == owner_id)
+        .ok_or_else(|| "Inbox upload not found".to_string())?;
+    if upload.status != InboxStatus::Pending {
+        return Err("Inbox upload was already decided".to_string());
+    }
+    Ok(upload)
+}
+
+/// Move an inbox upload into the request's folder, under `name` when given, else the name it
+/// was sent with. It then goes through processing like any other upload.
+pub async fn approve(state: &AppState, owner_id: &UserId, id: &Uuid, name: Option<&str>) -> Result<InboxUpload, String> {
+    let mut upload = pending(state, owner_id, id).await?;
+    let request = state
+        .file_request_repo
+        .find_by_id(&upload.request_id)
+        .await?
+        .ok_or_else(|| "File request not found".to_string())?;
+    let name = request.accept(name.unwrap_or(&upload.file_name), upload.size)?;
+    let path = if request.folder.is_empty() { name } else { format!("{}/{name}", request.folder) };
+
+    state.vault_storage.check_quota(owner_id, upload.size).await?;
+    state.vault_storage.commit_upload(&upload.id, owner_id, &path).await?;
+    upload.status = InboxStatus::Approved;
+    upload.path = Some(path.clone());
+    upload.decided_at = Some(Utc::now());
+    state.file_request_repo.save_upload(&upload).await?;
+    let details = json!({ "request_id": upload.request_id, "upload_id": upload.id, "path": path, "size": upload.size });
+    record(&*state.audit_repo, "file_request_upload_approved", owner_id, details).await?;
+
+    if let Err(e) = processing::enqueue(&*state.processing_repo, &state.processors, owner_id, &path, upload.size).await {
+        tracing::warn!("Failed to queue {} for processing: {}", path, e);
+    }
+    Ok(upload)
+}
+
+/// Delete an inbox upload without it ever reaching the vault.
+pub async fn reject(state: &AppState, owner_id: &UserId, id: &Uuid) -> Result<InboxUpload, String> {
+    let mut upload = pending(state, owner_id, id).await?;
+    state.vault_storage.discard_upload(&upload.id).await?;
+    upload.status = InboxStatus::Rejected;
+    upload.decided_at = Some(Utc::now());
+    state.file_request_repo.save_upload(&upload).await?;
+    let details = json!({ "request_id": upload.request_id, "upload_id": upload.id, "file_name": upload.file_name });
+    record(&*state.audit_repo, "file_request_upload_rejected", owner_id, details).await?;
+    Ok(upload)
+}
@@ -0,0 +1,32 @@
+use axum::http::StatusCode;
+use chrono::Utc;
+use crate::application::access::link_guard::LinkGuard;
+use crate::domain::entities::file_request::FileRequest;
+use crate::domain::services::secrets::hash_secret;
+use crate::infrastructure::AppState;
+
+const GUARD: LinkGuard = LinkGuard {
+    kind: "file-request",
+    label: "file request links",
+    lockout_event: "file_request_token_lockout",
+};
+
+/// Look up an open file request by its link token. Unknown tokens count against the caller's
+/// IP with the login lockout policy, so links cannot be guessed by brute force.
+pub async fn open(state: &AppState, token: &str, ip: &str) -> Result<FileRequest, (StatusCode, String)> {
+    let subject = GUARD.ip_subject(ip);
+    GUARD.ensure_allowed(state, &subject).await?;
+
+    let request = state.file_request_repo
+        .find_by_token_hash(&hash_secret(token))
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    let Some(request) = request else {
+        GUARD.penalize(state, &subject, ip).await;
+        return Err((StatusCode::NOT_FOUND, "File request not found".to_string()));
+    };
+    if !request.is_active_at(Utc::now()) {
+        return Err((StatusCode::GONE, "File request is expired or was closed".to_string()));
+    }
+    Ok(request)
+}
@@ -0,0 +1,126 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use crate::application::ports::file_hash_repository::DuplicateGroup;
+use crate::application::ports::{FileHashRepository, FileProcessor, VaultStorage};
+use crate::domain::entities::file_job::FileOperation;
+use crate::domain::entities::processing_task::ProcessingTask;
+use crate::domain::value_objects::UserId;
+
+/// One set of identical files, with the copy a cleanup keeps unless told otherwise
+#[derive(Debug, Clone, Serialize)]
+pub struct DuplicateSet {
+    #[serde(flatten)]
+    pub group: DuplicateGroup,
+    pub keep: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DuplicateReport {
+    pub groups: Vec<DuplicateSet>,
+    /// Freed by keeping one copy of each
+    pub reclaimable_bytes: u64,
+}
+
+/// The copy to keep: one the owner picked, otherwise the one with the shortest path.
+fn keeper<'a>(group: &'a DuplicateGroup, picked: &[String]) -> &'a str {
+    group
+        .paths
+        .iter()
+        .find(|path| picked.iter().any(|p| p.trim_matches('/') == path.as_str()))
+        .or_else(|| group.paths.iter().min_by_key(|path| (path.len(), path.as_str())))
+        .map(String::as_str)
+        .unwrap_or_default()
+}
+
+pub async fn report<H>(hashes: &H, owner_id: &UserId) -> Result<DuplicateReport, String>
+where
+    H: FileHashRepository + ?Sized,
+{
+    let groups: Vec<DuplicateSet> = hashes
+        .find_duplicates(owner_id)
+        .await?
+        .into_iter()
+        .map(|group| DuplicateSet { keep: keeper(&group, &[]).to_string(), group })
+        .collect();
+    let reclaimable_bytes = groups.iter().map(|set| set.group.size * (set.group.paths.len() as u64 - 1)).sum();
+    Ok(DuplicateReport { groups, reclaimable_bytes })
+}
+
+/// Deletions leaving one copy of each group, for a file job. `only` limits the cleanup to
+/// some groups by hash; `keep` names copies to keep over the suggested ones.
+pub fn cleanup_operations(groups: &[DuplicateGroup], only: Option<&[String]>, keep: &[String]) -> Vec<FileOperation> {
+    groups
+        .iter()
+        .filter(|group| only.map_or(true, |only| only.contains(&group.sha256)))
+        .flat_map(|group| {
+            let kept = keeper(group, keep);
+            group
+                .paths
+                .iter()
+                .filter(move |path| path.as_str() != kept)
+                .map(|path| FileOperation::Delete { path: path.clone() })
+        })
+        .collect()
+}
+
+/// Processing stage recording the SHA-256 of every file, to find duplicates by.
+pub struct HashStage {
+    storage: Arc<dyn VaultStorage>,
+    hashes: Arc<dyn FileHashRepository>,
+}
+
+impl HashStage {
+    pub fn new(storage: Arc<dyn VaultStorage>, hashes: Arc<dyn FileHashRepository>) -> Self {
+        Self { storage, hashes }
+    }
+}
+
+#[async_trait]
+impl FileProcessor for HashStage {
+    fn name(&self) -> &'static str {
+        "hash"
+    }
+
+    async fn process(&self, task: &ProcessingTask) -> Result<(), String> {
+        let mut stream = match self.storage.read_range(&task.owner_id, &task.path, 0, task.size).await {
+            Ok(stream) => stream,
+            // Moved or deleted since it was queued
+            Err(e) if e.contains("not found") => return Ok(()),
+            Err(e) => return Err(e),
+        };
+        let mut hasher = Sha256::new();
+        while let Some(chunk) = stream.next().await {
+            hasher.update(chunk?);
+        }
+        self.hashes.save(&task.owner_id, &task.path, task.size, &format!("{:x}", hasher.finalize())).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cleanup_keeps_one_copy() {
+        let groups = vec![
+            DuplicateGroup { sha256: "a".into(), size: 10, paths: vec!["photos/x.jpg".into(), "x.jpg".into(), "old/x copy.jpg".into()] },
+            DuplicateGroup { sha256: "b".into(), size: 5, paths: vec!["b1.txt".into(), "b2.txt".into()] },
+        ];
+        let paths = |ops: Vec<FileOperation>| -> Vec<String> {
+            ops.into_iter()
+                .map(|op| match op {
+                    FileOperation::Delete { path } => path,
+                    other => panic!("unexpected {other:?}"),
+                })
+                .collect()
+        };
+        assert_eq!(paths(cleanup_operations(&groups, None, &[])), ["photos/x.jpg", "old/x copy.jpg", "b2.txt"]);
+        assert_eq!(
+            paths(cleanup_operations(&groups, Some(&["a".into()]), &["/photos/x.jpg".into()])),
+            ["x.jpg", "old/x copy.jpg"]
+        );
+    }
+}
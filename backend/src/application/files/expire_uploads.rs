@@ -0,0 +1,16 @@
+use crate::application::ports::{UploadSessionRepository, VaultStorage};
+use crate::domain::entities::upload_session::UploadStatus;
+
+/// Discard uploads left idle past their expiry. Returns how many were expired.
+pub async fn execute<U, S>(uploads: &U, storage: &S) -> Result<usize, String>
+where
+    U: UploadSessionRepository + ?Sized,
+    S: VaultStorage + ?Sized,
+{
+    let expired = uploads.find_expired(chrono::Utc::now()).await?;
+    for upload in &expired {
+        storage.discard_upload(&upload.id).await?;
+        uploads.finish(&upload.id, UploadStatus::Expired, None).await?;
+    }
+    Ok(expired.len())
+}
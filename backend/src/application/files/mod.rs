@@ -0,0 +1,6 @@
+// Vault file work run by the platform in the background (bulk jobs, upload expiry, hashing)
+pub mod run_jobs;
+pub mod expire_uploads;
+pub mod upload_hooks;
+pub mod duplicates;
+pub mod usage;
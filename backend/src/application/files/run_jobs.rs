@@ -0,0 +1,214 @@
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+use crate::application::ports::{AuditRepository, FileHashRepository, FileJobRepository, LegalHoldRepository, MediaMetadataRepository, VaultStorage};
+use crate::domain::entities::audit_event::AuditEvent;
+use crate::domain::entities::file_job::{FileJob, FileOperation, JobStatus};
+use crate::domain::services::permission_evaluator::{Authority, DenialCode, PermissionEvaluator};
+use crate::domain::value_objects::UserId;
+
+/// What the runner keeps between passes. Each vault's jobs run one at a time and in order,
+/// different vaults' side by side, so a large job holds up only its own vault.
+#[derive(Clone, Default)]
+pub struct JobRunner {
+    running: Arc<Mutex<HashSet<UserId>>>,
+    /// Jobs a restart interrupted, whose next step may already have been done
+    interrupted: Arc<Mutex<HashSet<Uuid>>>,
+}
+
+/// An owner's queue taken by one runner; it is free again once the claim is dropped.
+pub struct OwnerClaim {
+    running: Arc<Mutex<HashSet<UserId>>>,
+    interrupted: Arc<Mutex<HashSet<Uuid>>>,
+    owner_id: UserId,
+}
+
+/// A job about to run, and whether a restart interrupted it
+struct QueuedJob {
+    job: FileJob,
+    resumed: bool,
+}
+
+impl JobRunner {
+    /// Take `owner_id`'s queue, unless their jobs are already running.
+    pub fn claim(&self, owner_id: &UserId) -> Option<OwnerClaim> {
+        let mut running = self.running.lock().unwrap_or_else(|e| e.into_inner());
+        running.insert(owner_id.clone()).then(|| OwnerClaim {
+            running: Arc::clone(&self.running),
+            interrupted: Arc::clone(&self.interrupted),
+            owner_id: owner_id.clone(),
+        })
+    }
+}
+
+impl OwnerClaim {
+    fn take_interrupted(&self, job_id: &Uuid) -> bool {
+        self.interrupted.lock().unwrap_or_else(|e| e.into_inner()).remove(job_id)
+    }
+}
+
+impl Drop for OwnerClaim {
+    fn drop(&mut self) {
+        self.running.lock().unwrap_or_else(|e| e.into_inner()).remove(&self.owner_id);
+    }
+}
+
+/// Owners with queued jobs, the one waiting longest first.
+pub async fn queued_owners<J: FileJobRepository + ?Sized>(jobs: &J) -> Result<Vec<UserId>, String> {
+    let mut owners: Vec<UserId> = Vec::new();
+    for job in jobs.find_by_status(JobStatus::Queued).await? {
+        if !owners.contains(&job.owner_id) {
+            owners.push(job.owner_id);
+        }
+    }
+    Ok(owners)
+}
+
+/// Run the queued jobs of `claim`'s owner, oldest first. Returns how many jobs were run.
+pub async fn run_pending<J, S, H, M, F, A>(
+    jobs: &J,
+    storage: &S,
+    holds: &H,
+    metadata: &M,
+    hashes: &F,
+    audit: &A,
+    claim: &OwnerClaim,
+) -> Result<usize, String>
+where
+    J: FileJobRepository + ?Sized,
+    S: VaultStorage + ?Sized,
+    H: LegalHoldRepository + ?Sized,
+    M: MediaMetadataRepository + ?Sized,
+    F: FileHashRepository + ?Sized,
+    A: AuditRepository + ?Sized,
+{
+    let queued = jobs.find_by_status(JobStatus::Queued).await?;
+    let mut ran = 0;
+    for job in queued.into_iter().filter(|job| job.owner_id == claim.owner_id) {
+        // Cancelled since the queue was read
+        let Some(job) = jobs.find_by_id(&job.id).await?.filter(|j| j.status == JobStatus::Queued) else {
+            continue;
+        };
+        let resumed = claim.take_interrupted(&job.id);
+        run_job(jobs, storage, holds, metadata, hashes, audit, QueuedJob { job, resumed }).await?;
+        ran += 1;
+    }
+    Ok(ran)
+}
+
+/// Jobs left running by a restart go back to the queue and resume after their last
+/// completed operation. The runner is told, since that operation may have gone through.
+pub async fn requeue_interrupted<J: FileJobRepository + ?Sized>(jobs: &J, runner: &JobRunner) -> Result<usize, String> {
+    let running = jobs.find_by_status(JobStatus::Running).await?;
+    for job in &running {
+        jobs.update_progress(&job.id, JobStatus::Queued, job.completed, None).await?;
+        runner.interrupted.lock().unwrap_or_else(|e| e.into_inner()).insert(job.id);
+    }
+    Ok(running.len())
+}
+
+async fn run_job<J, S, H, M, F, A>(
+    jobs: &J,
+    storage: &S,
+    holds: &H,
+    metadata: &M,
+    hashes: &F,
+    audit: &A,
+    queued: QueuedJob,
+) -> Result<(), String>
+where
+    J: FileJobRepository + ?Sized,
+    S: VaultStorage + ?Sized,
+    H: LegalHoldRepository + ?Sized,
+    M: MediaMetadataRepository + ?Sized,
+    F: FileHashRepository + ?Sized,
+    A: AuditRepository + ?Sized,
+{
+    let QueuedJob { job, resumed } = queued;
+    jobs.update_progress(&job.id, JobStatus::Running, job.completed, None).await?;
+    // Authority was checked when the job was queued; holds placed since then apply too
+    let evaluator = PermissionEvaluator::new(Authority::Owner, holds.list_for_owner(&job.owner_id).await?);
+    let mut completed = job.completed;
+    let mut outcome = (JobStatus::Completed, None);
+
+    for (index, operation) in job.operations.iter().enumerate().skip(job.completed as usize) {
+        let cancelled = jobs.find_by_id(&job.id).await?.map_or(true, |j| j.cancel_requested);
+        if cancelled {
+            outcome = (JobStatus::Cancelled, None);
+            break;
+        }
+        let now = chrono::Utc::now();
+        if let Some(denial) = operation
+            .accesses()
+            .into_iter()
+            .find_map(|(path, access)| evaluator.check(path, access, now).err())
+        {
+            if denial.code == DenialCode::LegalHold {
+                let mut event = AuditEvent::new(
+                    "legal_hold_blocked",
+                    serde_json::json!({ "hold_id": denial.hold_id, "path": denial.path, "job_id": job.id }),
+                );
+                event.owner_id = Some(job.owner_id.clone());
+                event.user_id = Some(job.created_by.clone());
+                audit.record(&event).await?;
+            }
+            outcome = (JobStatus::Failed, Some(format!("Operation {} failed: {denial}", index + 1)));
+            break;
+        }
+        let repeated = resumed && index == job.completed as usize;
+        if let Err(e) = storage.apply(&job.owner_id, operation, repeated).await {
+            outcome = (JobStatus::Failed, Some(format!("Operation {} failed: {e}", index + 1)));
+            break;
+        }
+        // What was read from the files follows them
+        let followed = match operation {
+            FileOperation::Move { from, to } => metadata
+                .move_path(&job.owner_id, from, to)
+                .await
+                .and(hashes.move_path(&job.owner_id, from, to).await),
+            FileOperation::Delete { path } => metadata
+                .delete_path(&job.owner_id, path)
+                .await
+                .and(hashes.delete_path(&job.owner_id, path).await),
+            _ => Ok(()),
+        };
+        if let Err(e) = followed {
+            tracing::warn!("Failed to update file metadata after job {}: {}", job.id, e);
+        }
+        completed = index as u32 + 1;
+        jobs.update_progress(&job.id, JobStatus::Running, completed, None).await?;
+    }
+
+    let (status, error) = outcome;
+    jobs.update_progress(&job.id, status, completed, error.as_deref()).await?;
+
+    let mut event = AuditEvent::new(
+        "file_job_finished",
+        serde_json::json!({
+            "job_id": job.id,
+            "status": status,
+            "completed": completed,
+            "total": job.total,
+            "error": error,
+        }),
+    );
+    event.owner_id = Some(job.owner_id.clone());
+    event.user_id = Some(job.created_by.clone());
+    audit.record(&event).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_an_owner_queue_is_claimed_once_until_released() {
+        let runner = JobRunner::default();
+        let (alice, bob) = (UserId::new(), UserId::new());
+        let claim = runner.claim(&alice).unwrap();
+        assert!(runner.claim(&alice).is_none());
+        assert!(runner.claim(&bob).is_some());
+        drop(claim);
+        assert!(runner.claim(&alice).is_some());
+    }
+}
@@ -0,0 +1,27 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use crate::application::ports::{UploadHook, VaultStorage};
+use crate::domain::entities::upload_session::UploadSession;
+
+/// Refuses an upload that would take the vault over its quota. Checked again at completion
+/// because other uploads may have landed since the session was opened.
+pub struct QuotaHook {
+    storage: Arc<dyn VaultStorage>,
+}
+
+impl QuotaHook {
+    pub fn new(storage: Arc<dyn VaultStorage>) -> Self {
+        Self { storage }
+    }
+}
+
+#[async_trait]
+impl UploadHook for QuotaHook {
+    fn name(&self) -> &'static str {
+        "quota"
+    }
+
+    async fn on_complete(&self, upload: &UploadSession) -> Result<(), String> {
+        self.storage.check_quota(&upload.owner_id, upload.size).await
+    }
+}
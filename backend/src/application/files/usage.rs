@@ -0,0 +1,154 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use serde::Serialize;
+use crate::application::ports::file_hash_repository::IndexedFile;
+use crate::application::ports::FileHashRepository;
+use crate::domain::value_objects::UserId;
+
+/// Children kept per folder, largest first; the rest are summed into `other_bytes`
+const MAX_CHILDREN: usize = 50;
+
+/// Deepest a breakdown goes below the folder it starts from
+pub const MAX_DEPTH: u32 = 10;
+
+/// What is using the space under a folder, for a treemap
+#[derive(Debug, Clone, Serialize)]
+pub struct UsageBreakdown {
+    pub tree: UsageNode,
+    /// Bytes per media type, largest first
+    pub by_type: Vec<TypeUsage>,
+}
+
+/// A folder or file with the bytes under it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UsageNode {
+    pub name: String,
+    pub path: String,
+    pub is_dir: bool,
+    pub bytes: u64,
+    pub files: u64,
+    /// Largest first; empty below the requested depth
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<UsageNode>,
+    /// Bytes of the children left out past the first [`MAX_CHILDREN`]
+    #[serde(skip_serializing_if = "is_zero")]
+    pub other_bytes: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TypeUsage {
+    pub media_type: &'static str,
+    pub bytes: u64,
+    pub files: u64,
+}
+
+fn is_zero(bytes: &u64) -> bool {
+    *bytes == 0
+}
+
+/// Break down the indexed files under `path`, the whole vault for `""`, `depth` levels deep.
+/// The hash stage indexes each file as it lands and the index follows moves and deletions, so
+/// the breakdown stays current without walking the vault. Files from before that stage
+/// existed are not counted.
+pub async fn breakdown<H>(hashes: &H, owner_id: &UserId, path: &str, depth: u32) -> Result<UsageBreakdown, String>
+where
+    H: FileHashRepository + ?Sized,
+{
+    let path = path.trim_matches('/');
+    let files = hashes.find_under(owner_id, path).await?;
+    Ok(UsageBreakdown { tree: tree(&files, path, depth.min(MAX_DEPTH)), by_type: by_type(&files) })
+}
+
+#[derive(Default)]
+struct Tally {
+    bytes: u64,
+    files: u64,
+    is_dir: bool,
+    children: BTreeMap<String, Tally>,
+}
+
+impl Tally {
+    fn add(&mut self, parts: &[&str], size: u64) {
+        self.bytes += size;
+        self.files += 1;
+        if let Some((first, rest)) = parts.split_first() {
+            let child = self.children.entry(first.to_string()).or_default();
+            child.is_dir |= !rest.is_empty();
+            child.add(rest, size);
+        }
+    }
+
+    fn into_node(self, name: String, path: String, depth: u32) -> UsageNode {
+        let mut children = Vec::new();
+        let mut other_bytes = 0;
+        if depth > 0 {
+            let mut sorted: Vec<(String, Tally)> = self.children.into_iter().collect();
+            sorted.sort_by(|a, b| b.1.bytes.cmp(&a.1.bytes).then_with(|| a.0.cmp(&b.0)));
+            for (index, (child_name, tally)) in sorted.into_iter().enumerate() {
+                if index >= MAX_CHILDREN {
+                    other_bytes += tally.bytes;
+                    continue;
+                }
+                let child_path = if path.is_empty() { child_name.clone() } else { format!("{path}/{child_name}") };
+                children.push(tally.into_node(child_name, child_path, depth - 1));
+            }
+        }
+        UsageNode { name, path, is_dir: self.is_dir, bytes: self.bytes, files: self.files, children, other_bytes }
+    }
+}
+
+fn tree(files: &[IndexedFile], root: &str, depth: u32) -> UsageNode {
+    let mut tally = Tally { is_dir: true, ..Tally::default() };
+    for file in files {
+        let relative = file.path.strip_prefix(root).unwrap_or(&file.path).trim_start_matches('/');
+        let parts: Vec<&str> = relative.split('/').filter(|part| !part.is_empty()).collect();
+        tally.add(&parts, file.size);
+    }
+    let name = root.rsplit('/').next().unwrap_or_default().to_string();
+    tally.into_node(name, root.to_string(), depth)
+}
+
+fn by_type(files: &[IndexedFile]) -> Vec<TypeUsage> {
+    let mut totals: HashMap<&'static str, (u64, u64)> = HashMap::new();
+    for file in files {
+        let total = totals.entry(shared::listing::mime_type(Path::new(&file.path))).or_default();
+        total.0 += file.size;
+        total.1 += 1;
+    }
+    let mut usage: Vec<TypeUsage> =
+        totals.into_iter().map(|(media_type, (bytes, files))| TypeUsage { media_type, bytes, files }).collect();
+    usage.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.media_type.cmp(b.media_type)));
+    usage
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tree_sums_folders_and_stops_at_depth() {
+        let file = |path: &str, size| IndexedFile { path: path.to_string(), size };
+        let files = [
+            file("Photos/2025/a.jpg", 300),
+            file("Photos/2026/b.jpg", 100),
+            file("Photos/c.png", 50),
+            file("notes.txt", 10),
+        ];
+
+        let root = tree(&files, "", 1);
+        assert_eq!((root.bytes, root.files), (460, 4));
+        let names: Vec<&str> = root.children.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["Photos", "notes.txt"]);
+        assert!(root.children[0].is_dir && root.children[0].children.is_empty());
+        assert!(!root.children[1].is_dir);
+
+        let photos = tree(&files[..3], "Photos", 2);
+        assert_eq!(photos.name, "Photos");
+        assert_eq!(photos.children[0].path, "Photos/2025");
+        assert_eq!(photos.children[0].children[0].path, "Photos/2025/a.jpg");
+
+        let types = by_type(&files);
+        assert_eq!(types[0], TypeUsage { media_type: "image/jpeg", bytes: 400, files: 2 });
+        assert_eq!(types.len(), 3);
+    }
+}
@@ -0,0 +1,181 @@
+// Folder exports - clients taking a shared folder offline, with the owner's approval
+use chrono::{DateTime, Duration, Utc};
+use serde_json::json;
+use uuid::Uuid;
+use crate::application::ports::{AuditRepository, ByteStream, FilePermissionRepository, FolderExportRepository, NotificationRepository, VaultStorage};
+use crate::domain::entities::audit_event::AuditEvent;
+use crate::domain::entities::folder_export::{FolderExport, FolderExportStatus};
+use crate::domain::entities::notification::Notification;
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::AppState;
+
+async fn record<A: AuditRepository + ?Sized>(audit: &A, kind: &str, export: &FolderExport, acting_id: Option<&UserId>) -> Result<(), String> {
+    let mut event = AuditEvent::new(
+        kind,
+        json!({ "export_id": export.id, "client_id": export.client_id, "path": export.path }),
+    );
+    event.owner_id = Some(export.owner_id.clone());
+    event.user_id = acting_id.cloned();
+    audit.record(&event).await
+}
+
+/// File a client's request, provided one of their grants from the owner lets them download
+/// the folder. The owner is notified.
+pub async fn request<E, P, N, A>(exports: &E, permissions: &P, notifications: &N, audit: &A, export: FolderExport) -> Result<FolderExport, String>
+where
+    E: FolderExportRepository + ?Sized,
+    P: FilePermissionRepository + ?Sized,
+    N: NotificationRepository + ?Sized,
+    A: AuditRepository + ?Sized,
+{
+    let granted = permissions.find_active_for_client(&export.client_id).await?;
+    if !granted.iter().any(|permission| export.is_covered_by(permission)) {
+        return Err("Folder is not shared with you for download".to_string());
+    }
+    exports.save(&export).await?;
+    record(audit, "folder_export_requested", &export, Some(&export.client_id)).await?;
+
+    let payload = json!({ "export_id": export.id, "client_id": export.client_id, "path": export.path });
+    if let Err(e) = notifications.create(&Notification::new(export.owner_id.clone(), "folder_export_requested", payload)).await {
+        tracing::warn!("Failed to notify owner of folder export {}: {}", export.id, e);
+    }
+    Ok(export)
+}
+
+/// Approve or deny one of the owner's pending requests. Approved ones are built by
+/// [`run_pending`].
+pub async fn decide<E, N, A>(exports: &E, notifications: &N, audit: &A, owner_id: &UserId, id: &Uuid, approve: bool) -> Result<FolderExport, String>
+where
+    E: FolderExportRepository + ?Sized,
+    N: NotificationRepository + ?Sized,
+    A: AuditRepository + ?Sized,
+{
+    let mut export = exports
+        .find_by_id(id)
+        .await?
+        .filter(|export| &export.owner_id == owner_id)
+        .ok_or_else(|| "Export request not found".to_string())?;
+    export.decide(approve)?;
+    exports.save(&export).await?;
+    let kind = if approve { "folder_export_approved" } else { "folder_export_denied" };
+    record(audit, kind, &export, Some(owner_id)).await?;
+
+    if !approve {
+        let payload = json!({ "export_id": export.id, "path": export.path });
+        if let Err(e) = notifications.create(&Notification::new(export.client_id.clone(), kind, payload)).await {
+            tracing::warn!("Failed to notify client of folder export {}: {}", export.id, e);
+        }
+    }
+    Ok(export)
+}
+
+/// An export is visible to the client who requested it.
+pub async fn find_for_client<E: FolderExportRepository + ?Sized>(exports: &E, client_id: &UserId, id: &Uuid) -> Result<FolderExport, String> {
+    exports
+        .find_by_id(id)
+        .await?
+        .filter(|export| &export.client_id == client_id)
+        .ok_or_else(|| "Export not found".to_string())
+}
+
+/// The encrypted archive of a ready export.
+pub async fn download<E, S, A>(exports: &E, storage: &S, audit: &A, client_id: &UserId, id: &Uuid) -> Result<(FolderExport, ByteStream), String>
+where
+    E: FolderExportRepository + ?Sized,
+    S: VaultStorage + ?Sized,
+    A: AuditRepository + ?Sized,
+{
+    let export = find_for_client(exports, client_id, id).await?;
+    if !export.is_ready_at(Utc::now()) {
+        return Err("Export is not ready or has expired".to_string());
+    }
+    let stream = storage.read_export(&export.id).await?;
+    record(audit, "folder_export_downloaded", &export, Some(client_id)).await?;
+    Ok((export, stream))
+}
+
+/// The archive's password and until when it is handed out.
+pub async fn key<E, A>(exports: &E, audit: &A, client_id: &UserId, id: &Uuid) -> Result<(String, DateTime<Utc>), String>
+where
+    E: FolderExportRepository + ?Sized,
+    A: AuditRepository + ?Sized,
+{
+    let export = find_for_client(exports, client_id, id).await?;
+    let (Some(password), Some(expires_at)) = (export.password.clone(), export.expires_at) else {
+        return Err("Export is not ready or has expired".to_string());
+    };
+    if !export.is_ready_at(Utc::now()) {
+        return Err("Export is not ready or has expired".to_string());
+    }
+    record(audit, "folder_export_key_fetched", &export, Some(client_id)).await?;
+    Ok((password, expires_at))
+}
+
+/// Build every approved export, oldest first. Returns how many were built.
+pub async fn run_pending(state: &AppState) -> Result<usize, String> {
+    let approved = state.folder_export_repo.find_by_status(FolderExportStatus::Approved).await?;
+    let valid_for = Duration::hours(state.config.current().parse::<i64>("FOLDER_EXPORT_VALID_HOURS").unwrap_or(72));
+    for mut export in approved.iter().cloned() {
+        let password = format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+        match build(state, &export, &password).await {
+            Ok(size_bytes) => export.mark_ready(password, size_bytes, valid_for),
+            Err(e) => {
+                state.vault_storage.discard_export(&export.id).await?;
+                export.mark_failed(e);
+            }
+        }
+        state.folder_export_repo.save(&export).await?;
+
+        let kind = if export.status == FolderExportStatus::Ready { "folder_export_ready" } else { "folder_export_failed" };
+        record(&*state.audit_repo, kind, &export, None).await?;
+        let payload = json!({
+            "export_id": export.id,
+            "path": export.path,
+            "expires_at": export.expires_at,
+            "key_url": format!("/api/my-folder-exports/{}/key", export.id),
+        });
+        if let Err(e) = state.notification_repo.create(&Notification::new(export.client_id.clone(), kind, payload)).await {
+            tracing::warn!("Failed to notify client of folder export {}: {}", export.id, e);
+        }
+    }
+    Ok(approved.len())
+}
+
+/// Stage the folder and seal it under `password`. The grant is checked again, since it may
+/// have been revoked while the request waited. With `STRIP_SHARED_GPS`, photos lose their
+/// location, as in clients' data exports.
+async fn build(state: &AppState, export: &FolderExport, password: &str) -> Result<u64, String> {
+    let granted = state.file_permission_repo.find_active_for_client(&export.client_id).await?;
+    if !granted.iter().any(|permission| export.is_covered_by(permission)) {
+        return Err("The folder is no longer shared with the client".to_string());
+    }
+    let storage = &*state.vault_storage;
+    storage.discard_export(&export.id).await?;
+    storage.stage_export_files(&export.id, &export.owner_id, Some(&[export.path.clone()])).await?;
+    if state.config.current().parse::<bool>("STRIP_SHARED_GPS").unwrap_or(false) {
+        storage.strip_export_gps(&export.id, &export.owner_id).await?;
+    }
+    storage.seal_export(&export.id, password).await
+}
+
+/// Delete the archives of ready exports past their expiry and forget their keys. Returns how
+/// many expired.
+pub async fn expire<E, S, A>(exports: &E, storage: &S, audit: &A, now: DateTime<Utc>) -> Result<usize, String>
+where
+    E: FolderExportRepository + ?Sized,
+    S: VaultStorage + ?Sized,
+    A: AuditRepository + ?Sized,
+{
+    let mut expired = 0;
+    for mut export in exports.find_by_status(FolderExportStatus::Ready).await? {
+        if export.is_ready_at(now) {
+            continue;
+        }
+        storage.discard_export(&export.id).await?;
+        export.expire();
+        exports.save(&export).await?;
+        record(audit, "folder_export_expired", &export, None).await?;
+        expired += 1;
+    }
+    Ok(expired)
+}
@@ -0,0 +1,134 @@
+// Galleries - folders of photos owners publish, read-only, to anyone holding the link
+use chrono::{DateTime, Utc};
+use futures_util::{stream, StreamExt};
+use serde_json::json;
+use uuid::Uuid;
+use crate::application::ports::pagination::Cursor;
+use crate::application::ports::{AuditRepository, ByteStream, GalleryShareRepository, Page, PageRequest, VaultStorage};
+use crate::domain::entities::audit_event::AuditEvent;
+use crate::domain::entities::gallery_share::GalleryShare;
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::driven::media;
+
+pub mod token_guard;
+
+/// One photo of a gallery, as visitors see it
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct GalleryPhoto {
+    pub name: String,
+    pub size: u64,
+    pub media_type: &'static str,
+    pub modified: Option<DateTime<Utc>>,
+}
+
+/// Raster images only: an SVG served from the vault's origin could run script.
+fn is_gallery_image(media_type: &str) -> bool {
+    media_type.starts_with("image/") && media_type != "image/svg+xml"
+}
+
+async fn record<A: AuditRepository + ?Sized>(audit: &A, kind: &str, share: &GalleryShare) -> Result<(), String> {
+    let mut event = AuditEvent::new(kind, json!({ "gallery_id": share.id, "path": share.path }));
+    event.owner_id = Some(share.owner_id.clone());
+    event.user_id = Some(share.owner_id.clone());
+    audit.record(&event).await
+}
+
+/// Publish a new gallery, provided its folder exists.
+pub async fn create<G, S, A>(galleries: &G, storage: &S, audit: &A, share: GalleryShare) -> Result<GalleryShare, String>
+where
+    G: GalleryShareRepository + ?Sized,
+    S: VaultStorage + ?Sized,
+    A: AuditRepository + ?Sized,
+{
+    storage.list(&share.owner_id, &share.path).await?;
+    galleries.save(&share).await?;
+    record(audit, "gallery_share_created", &share).await?;
+    Ok(share)
+}
+
+/// Close one of the owner's galleries; its link stops working at once.
+pub async fn revoke<G, A>(galleries: &G, audit: &A, owner_id: &UserId, id: &Uuid) -> Result<GalleryShare, String>
+where
+    G: GalleryShareRepository + ?Sized,
+    A: AuditRepository + ?Sized,
+{
+    let mut share = galleries
+        .find_by_id(id)
+        .await?
+        .filter(|share| &share.owner_id == owner_id)
+        .ok_or_else(|| "Gallery not found".to_string())?;
+    if share.revoked_at.is_some() {
+        return Err("Gallery was already revoked".to_string());
+    }
+    share.revoked_at = Some(Utc::now());
+    galleries.save(&share).await?;
+    record(audit, "gallery_share_revoked", &share).await?;
+    Ok(share)
+}
+
+/// One page of the gallery's photos, by name. Subfolders and other files are not shown.
+/// Fetching the first page counts as a visit.
+pub async fn photos<G, S>(galleries: &G, storage: &S, share: &GalleryShare, page: &PageRequest) -> Result<Page<GalleryPhoto>, String>
+where
+    G: GalleryShareRepository + ?Sized,
+    S: VaultStorage + ?Sized,
+{
+    let mut photos: Vec<GalleryPhoto> = storage
+        .list(&share.owner_id, &share.path)
+        .await?
+        .into_iter()
+        .filter(|entry| !entry.is_dir && is_gallery_image(entry.mime_type))
+        .map(|entry| GalleryPhoto { size: entry.size.unwrap_or(0), media_type: entry.mime_type, modified: entry.modified, name: entry.name })
+        .collect();
+    let sort_key = |name: &str| (name.to_lowercase(), name.to_string());
+    photos.sort_by_cached_key(|photo| sort_key(&photo.name));
+
+    let total = photos.len() as u64;
+    let rows: Vec<GalleryPhoto> = match &page.after {
+        Some(after) => {
+            let after = sort_key(&after.key);
+            photos.into_iter().filter(|photo| sort_key(&photo.name) > after).take(page.limit as usize + 1).collect()
+        }
+        None => {
+            if let Err(e) = galleries.record_view(&share.id, Utc::now()).await {
+                tracing::warn!("Failed to count a view of gallery {}: {}", share.id, e);
+            }
+            photos.into_iter().take(page.limit as usize + 1).collect()
+        }
+    };
+    Ok(Page::from_rows(rows, page.limit, total, |photo| Cursor::new(photo.name.clone(), "")))
+}
+
+/// A photo of the gallery with its location blanked, or with `thumbnail` the small preview
+/// the camera embedded in it when there is one. Returns its media type, length and bytes.
+pub async fn photo<S>(storage: &S, share: &GalleryShare, name: &str, thumbnail: bool) -> Result<(&'static str, u64, ByteStream), String>
+where
+    S: VaultStorage + ?Sized,
+{
+    let media_type = shared::listing::mime_type(std::path::Path::new(name));
+    if name.is_empty() || name.contains('/') || name == "." || name == ".." || !is_gallery_image(media_type) {
+        return Err("Photo not found".to_string());
+    }
+    let path = if share.path.is_empty() { name.to_string() } else { format!("{}/{name}", share.path) };
+    let size = storage.stat(&share.owner_id, &path).await?.size;
+
+    let head_len = size.min(media::EXIF_HEAD_LEN);
+    let mut head = Vec::with_capacity(head_len as usize);
+    let mut chunks = storage.read_range(&share.owner_id, &path, 0, head_len).await?;
+    while let Some(chunk) = chunks.next().await {
+        head.extend(chunk?);
+    }
+    if thumbnail {
+        if let Some(range) = media::embedded_thumbnail(&head) {
+            let jpeg = head[range].to_vec();
+            return Ok(("image/jpeg", jpeg.len() as u64, stream::once(async move { Ok(jpeg) }).boxed()));
+        }
+    }
+    media::blank_gps(&mut head);
+    let rest = if size > head_len {
+        storage.read_range(&share.owner_id, &path, head_len, size - head_len).await?
+    } else {
+        stream::empty().boxed()
+    };
+    Ok((media_type, size, stream::once(async move { Ok(head) }).chain(rest).boxed()))
+}
@@ -0,0 +1,69 @@
+use axum::http::StatusCode;
+use chrono::Utc;
+use crate::application::access::link_guard::LinkGuard;
+use crate::domain::entities::gallery_share::GalleryShare;
+use crate::domain::services::secrets::hash_secret;
+use crate::infrastructure::AppState;
+
+const GUARD: LinkGuard = LinkGuard {
+    kind: "gallery",
+    label: "gallery links or passwords",
+    lockout_event: "gallery_token_lockout",
+};
+
+/// Wrong passwords for one gallery from any IP, so spreading guesses over many addresses
+/// does not get around the lockout
+fn share_subject(share: &GalleryShare) -> String {
+    format!("gallery-share:{}", share.id)
+}
+
+/// Look up an open gallery by its link token. Unknown tokens count against the caller's IP
+/// with the login lockout policy, so links cannot be guessed by brute force.
+async fn find_active(state: &AppState, token: &str, ip: &str) -> Result<GalleryShare, (StatusCode, String)> {
+    let subject = GUARD.ip_subject(ip);
+    GUARD.ensure_allowed(state, &subject).await?;
+
+    let share = state.gallery_share_repo
+        .find_by_token_hash(&hash_secret(token))
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    let Some(share) = share else {
+        GUARD.penalize(state, &subject, ip).await;
+        return Err((StatusCode::NOT_FOUND, "Gallery not found".to_string()));
+    };
+    if !share.is_active_at(Utc::now()) {
+        return Err((StatusCode::GONE, "Gallery is expired or was closed".to_string()));
+    }
+    Ok(share)
+}
+
+/// The gallery behind a link, for a visitor carrying its view key when it has a password.
+/// A wrong key counts against the caller's IP like an unknown token.
+pub async fn open(state: &AppState, token: &str, key: Option<&str>, ip: &str) -> Result<GalleryShare, (StatusCode, String)> {
+    let share = find_active(state, token, ip).await?;
+    if !share.admits(key) {
+        if key.is_some() {
+            GUARD.penalize(state, &GUARD.ip_subject(ip), ip).await;
+        }
+        return Err((StatusCode::UNAUTHORIZED, "Gallery password required".to_string()));
+    }
+    Ok(share)
+}
+
+/// Check a visitor's password and hand out the view key their later requests carry. A wrong
+/// password counts against the caller's IP and against the gallery itself.
+pub async fn unlock(state: &AppState, token: &str, password: &str, ip: &str) -> Result<Option<String>, (StatusCode, String)> {
+    let share = find_active(state, token, ip).await?;
+    GUARD.ensure_allowed(state, &share_subject(&share)).await?;
+    // Hashing the password takes a while, so it stays off the async workers
+    let (candidate, password) = (share.clone(), password.to_string());
+    let matches = tokio::task::spawn_blocking(move || candidate.check_password(&password))
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    if !matches {
+        GUARD.penalize(state, &GUARD.ip_subject(ip), ip).await;
+        GUARD.penalize(state, &share_subject(&share), ip).await;
+        return Err((StatusCode::UNAUTHORIZED, "Wrong gallery password".to_string()));
+    }
+    Ok(share.view_key())
+}
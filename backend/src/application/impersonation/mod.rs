@@ -0,0 +1,128 @@
+// Impersonation - owners opening app sessions as a client who consented, to see what they see
+use axum::http::StatusCode;
+use chrono::Utc;
+use serde_json::json;
+use crate::application::client::commands::launch_application::{self, Impersonation, LaunchOrigin, LaunchParameters, LaunchResult};
+use crate::domain::entities::audit_event::AuditEvent;
+use crate::domain::entities::impersonation_consent::{ImpersonationConsent, IMPERSONATION_ROLE};
+use crate::domain::entities::notification::Notification;
+use crate::domain::entities::session_timeline::TimelineStage;
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+use crate::infrastructure::AppState;
+
+async fn record(state: &AppState, kind: &str, consent: &ImpersonationConsent, user_id: &UserId, details: serde_json::Value) -> Result<(), String> {
+    let mut event = AuditEvent::new(kind, details);
+    event.owner_id = Some(consent.owner_id.clone());
+    event.user_id = Some(user_id.clone());
+    state.audit_repo.record(&event).await
+}
+
+async fn notify(state: &AppState, user_id: &UserId, kind: &str, payload: serde_json::Value) {
+    if let Err(e) = state.notification_repo.create(&Notification::new(user_id.clone(), kind, payload)).await {
+        tracing::warn!("Failed to send {} notification: {}", kind, e);
+    }
+}
+
+/// The consent `client_id` currently gives `owner_id`, if any.
+pub async fn active_consent(state: &AppState, client_id: &UserId, owner_id: &UserId) -> Result<Option<ImpersonationConsent>, String> {
+    let now = Utc::now();
+    Ok(state
+        .impersonation_consent_repo
+        .find_by_client(client_id)
+        .await?
+        .into_iter()
+        .find(|consent| &consent.owner_id == owner_id && consent.is_active_at(now)))
+}
+
+/// Let `owner_id`, one of the owners sharing files with the client, act as them for `hours`.
+/// A consent given before to the same owner is replaced.
+pub async fn grant(state: &AppState, client_id: &UserId, owner_id: &UserId, hours: i64) -> Result<ImpersonationConsent, String> {
+    let shares = state.file_permission_repo.find_active_for_client(client_id).await?;
+    if !shares.iter().any(|p| &p.owner_id == owner_id) {
+        return Err("This owner shares no files with you".to_string());
+    }
+    let consent = ImpersonationConsent::grant(client_id.clone(), owner_id.clone(), hours)?;
+    if let Some(mut previous) = active_consent(state, client_id, owner_id).await? {
+        previous.revoked_at = Some(consent.granted_at);
+        state.impersonation_consent_repo.save(&previous).await?;
+    }
+    state.impersonation_consent_repo.save(&consent).await?;
+
+    let details = json!({ "consent_id": consent.id, "client_id": client_id, "expires_at": consent.expires_at });
+    record(state, "impersonation_consent_granted", &consent, client_id, details.clone()).await?;
+    notify(state, owner_id, "impersonation_consent_granted", details).await;
+    Ok(consent)
+}
+
+/// Take back the client's consent to `owner_id`. Sessions the owner is running as them end now.
+pub async fn withdraw(state: &AppState, client_id: &UserId, owner_id: &UserId) -> Result<ImpersonationConsent, String> {
+    let mut consent = active_consent(state, client_id, owner_id)
+        .await?
+        .ok_or_else(|| "No consent to withdraw".to_string())?;
+    consent.revoked_at = Some(Utc::now());
+    state.impersonation_consent_repo.save(&consent).await?;
+    let details = json!({ "consent_id": consent.id, "client_id": client_id });
+    record(state, "impersonation_consent_withdrawn", &consent, client_id, details.clone()).await?;
+    notify(state, owner_id, "impersonation_consent_withdrawn", details).await;
+
+    for session in state.session_repo.find_active_by_user(client_id).await? {
+        if session.active_role != IMPERSONATION_ROLE || session.acting_as_owner_id.as_ref() != Some(owner_id) {
+            continue;
+        }
+        let session_id = session.id.to_string();
+        tracing::info!("Ending session {}: consent withdrawn", session_id);
+        state.session_timelines.record(&session_id, TimelineStage::Disconnected, Some("Consent withdrawn".to_string()));
+        let _ = state.xvfb_manager.cleanup_session(&session_id).await;
+        state.session_repo.terminate(&session.id).await?;
+        state.session_timelines.finish(&session_id).await?;
+        state.session_affinity.release(&session_id).await?;
+        let details = json!({ "consent_id": consent.id, "client_id": client_id, "reason": "consent_withdrawn" });
+        let mut event = AuditEvent::new("impersonation_ended", details);
+        event.owner_id = Some(owner_id.clone());
+        event.user_id = Some(client_id.clone());
+        event.session_id = Some(session_id);
+        state.audit_repo.record(&event).await?;
+    }
+    Ok(consent)
+}
+
+/// Open `app_id` as `client_id` for the owner, read-only, within what the consent leaves. The
+/// client is told, and the stream is marked with both names.
+pub async fn start(
+    state: &AppState,
+    owner: &AuthenticatedUser,
+    client_id: &UserId,
+    app_id: &str,
+    params: LaunchParameters,
+    placed_on: Option<&str>,
+) -> Result<LaunchResult, (StatusCode, String)> {
+    let internal = |e: String| (StatusCode::INTERNAL_SERVER_ERROR, e);
+    let consent = active_consent(state, client_id, &owner.id)
+        .await
+        .map_err(internal)?
+        .ok_or_else(|| (StatusCode::FORBIDDEN, "This client has not agreed to you acting as them".to_string()))?;
+    let client = state
+        .user_repo
+        .find_by_id(client_id)
+        .await
+        .map_err(internal)?
+        .ok_or_else(|| (StatusCode::NOT_FOUND, "Client not found".to_string()))?;
+    let client_email = client.email().as_str().to_string();
+
+    let origin = LaunchOrigin {
+        placed_on,
+        resume_state: None,
+        impersonating: Some(Impersonation { consent: &consent, client_email: &client_email }),
+    };
+    let launched = launch_application::execute(state, owner, app_id, params, origin).await?;
+
+    let details = json!({ "consent_id": consent.id, "client_id": client_id, "app_id": app_id, "owner_email": owner.email });
+    let mut event = AuditEvent::new("impersonation_started", details.clone());
+    event.owner_id = Some(owner.id.clone());
+    event.user_id = Some(owner.id.clone());
+    event.session_id = Some(launched.session_id.clone());
+    state.audit_repo.record(&event).await.map_err(internal)?;
+    notify(state, client_id, "impersonation_started", details).await;
+    Ok(launched)
+}
@@ -0,0 +1,211 @@
+// Use cases - adopt existing host directory trees into owners' vaults
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use serde_json::json;
+use crate::application::ports::{AuditRepository, FileProcessor, ImportOutcome, ProcessingRepository, VaultImportRepository, VaultStorage};
+use crate::application::processing;
+use crate::domain::entities::audit_event::AuditEvent;
+use crate::domain::entities::file_job::JobStatus;
+use crate::domain::entities::vault_import::{ImportMode, VaultImport};
+
+/// How often a running import saves its progress and checks for cancellation
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(2);
+
+/// The directories imports may read from, from the colon-separated `IMPORT_ROOTS`.
+pub fn import_roots(setting: Option<&str>) -> Vec<String> {
+    setting
+        .unwrap_or_default()
+        .split(':')
+        .map(|root| root.trim().trim_end_matches('/'))
+        .filter(|root| !root.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Queue an import for the background worker. The source is checked against `roots` again,
+/// with links resolved, when it is scanned.
+pub async fn queue<I, A>(imports: &I, audit: &A, import: VaultImport, roots: &[String]) -> Result<VaultImport, String>
+where
+    I: VaultImportRepository + ?Sized,
+    A: AuditRepository + ?Sized,
+{
+    if roots.is_empty() {
+        return Err("Imports are disabled: IMPORT_ROOTS is not set".to_string());
+    }
+    if !roots.iter().any(|root| Path::new(&import.source).starts_with(root)) {
+        return Err(format!("Source {} is not inside IMPORT_ROOTS", import.source));
+    }
+    imports.save(&import).await?;
+
+    let mut event = AuditEvent::new(
+        "vault_import_started",
+        json!({
+            "import_id": import.id,
+            "source": import.source,
+            "destination": import.destination,
+            "mode": import.mode,
+            "on_conflict": import.on_conflict,
+        }),
+    );
+    event.owner_id = Some(import.owner_id.clone());
+    event.user_id = Some(import.requested_by.clone());
+    audit.record(&event).await?;
+    Ok(import)
+}
+
+/// A queued import is cancelled at once; a running one stops before its next file. Files
+/// already imported stay in the vault.
+pub async fn cancel<I: VaultImportRepository + ?Sized>(imports: &I, import_id: &uuid::Uuid) -> Result<VaultImport, String> {
+    let mut import = imports
+        .find_by_id(import_id)
+        .await?
+        .ok_or_else(|| "Import not found".to_string())?;
+    if import.status.is_finished() {
+        return Err("Import already finished".to_string());
+    }
+    imports.request_cancel(&import.id).await?;
+    import.cancel_requested = true;
+    if import.status == JobStatus::Queued {
+        imports.update_progress(&import.id, JobStatus::Cancelled, &import.progress, None).await?;
+        import.status = JobStatus::Cancelled;
+    }
+    Ok(import)
+}
+
+/// Run every queued import, oldest first, queueing each imported file for processing.
+/// Returns how many were run.
+pub async fn run_pending<I, S, P, A>(
+    imports: &I,
+    storage: &S,
+    processing: &P,
+    processors: &[Arc<dyn FileProcessor>],
+    audit: &A,
+    roots: &[String],
+) -> Result<usize, String>
+where
+    I: VaultImportRepository + ?Sized,
+    S: VaultStorage + ?Sized,
+    P: ProcessingRepository + ?Sized,
+    A: AuditRepository + ?Sized,
+{
+    let queued = imports.find_by_status(JobStatus::Queued).await?;
+    let mut ran = 0;
+    for import in queued {
+        // Cancelled since the queue was read
+        let Some(import) = imports.find_by_id(&import.id).await?.filter(|i| i.status == JobStatus::Queued) else {
+            continue;
+        };
+        run_import(imports, storage, processing, processors, audit, roots, import).await?;
+        ran += 1;
+    }
+    Ok(ran)
+}
+
+/// Imports left running by a restart go back to the queue. They scan their source again and
+/// resume after the last file they saved progress for.
+pub async fn requeue_interrupted<I: VaultImportRepository + ?Sized>(imports: &I) -> Result<usize, String> {
+    let running = imports.find_by_status(JobStatus::Running).await?;
+    for import in &running {
+        imports.update_progress(&import.id, JobStatus::Queued, &import.progress, None).await?;
+    }
+    Ok(running.len())
+}
+
+async fn run_import<I, S, P, A>(
+    imports: &I,
+    storage: &S,
+    processing: &P,
+    processors: &[Arc<dyn FileProcessor>],
+    audit: &A,
+    roots: &[String],
+    import: VaultImport,
+) -> Result<(), String>
+where
+    I: VaultImportRepository + ?Sized,
+    S: VaultStorage + ?Sized,
+    P: ProcessingRepository + ?Sized,
+    A: AuditRepository + ?Sized,
+{
+    let mut progress = import.progress;
+    imports.update_progress(&import.id, JobStatus::Running, &progress, None).await?;
+    let mut outcome = (JobStatus::Completed, None);
+
+    match storage.scan_import(&import.source, roots).await {
+        Err(e) => outcome = (JobStatus::Failed, Some(format!("Scan failed: {e}"))),
+        Ok(entries) => {
+            // Moved files are gone from the source, so a resumed move starts from the top
+            let resume_at = match import.mode {
+                ImportMode::Move => 0,
+                _ => progress.files_done as usize,
+            };
+            let mut remaining = entries.get(resume_at..).unwrap_or_default();
+            progress.files_total = Some(match import.mode {
+                ImportMode::Move => progress.files_done + remaining.len() as u64,
+                _ => entries.len() as u64,
+            });
+            let incoming: u64 = remaining.iter().map(|entry| entry.size).sum();
+            if let Err(e) = storage.check_quota(&import.owner_id, incoming).await {
+                outcome = (JobStatus::Failed, Some(e));
+                remaining = &[];
+            }
+            imports.update_progress(&import.id, JobStatus::Running, &progress, None).await?;
+
+            let mut last_report = Instant::now();
+            for entry in remaining {
+                if last_report.elapsed() >= PROGRESS_INTERVAL {
+                    let cancelled = imports.find_by_id(&import.id).await?.map_or(true, |i| i.cancel_requested);
+                    if cancelled {
+                        outcome = (JobStatus::Cancelled, None);
+                        break;
+                    }
+                    imports.update_progress(&import.id, JobStatus::Running, &progress, None).await?;
+                    last_report = Instant::now();
+                }
+                let path = format!("{}/{}", import.destination, entry.path);
+                match storage.import_file(&import.owner_id, entry, &path, import.mode, import.on_conflict).await {
+                    Ok(ImportOutcome::Imported(path)) => {
+                        progress.bytes_done += entry.size;
+                        if let Err(e) = processing::enqueue(processing, processors, &import.owner_id, &path, entry.size).await {
+                            tracing::warn!("Failed to queue {} for processing: {}", path, e);
+                        }
+                    }
+                    Ok(ImportOutcome::Skipped) => progress.skipped += 1,
+                    Err(e) => {
+                        outcome = (JobStatus::Failed, Some(e));
+                        break;
+                    }
+                }
+                progress.files_done += 1;
+            }
+        }
+    }
+
+    let (status, error) = outcome;
+    imports.update_progress(&import.id, status, &progress, error.as_deref()).await?;
+
+    let mut event = AuditEvent::new(
+        "vault_import_finished",
+        json!({
+            "import_id": import.id,
+            "status": status,
+            "progress": progress,
+            "error": error,
+        }),
+    );
+    event.owner_id = Some(import.owner_id.clone());
+    event.user_id = Some(import.requested_by.clone());
+    audit.record(&event).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_import_roots() {
+        assert_eq!(import_roots(Some("/mnt/nas/:/srv/old-vault")), vec!["/mnt/nas", "/srv/old-vault"]);
+        assert_eq!(import_roots(Some(" : ")), Vec::<String>::new());
+        assert!(import_roots(None).is_empty());
+    }
+}
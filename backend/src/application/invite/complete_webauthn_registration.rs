@@ -1,9 +1,15 @@
+use axum::http::StatusCode;
 use webauthn_rs::prelude::*;
-use jsonwebtoken::{encode, Header, EncodingKey};
 use crate::infrastructure::AppState;
+use crate::domain::entities::invitation::Invitation;
 use crate::domain::{User, Credential, Email, DisplayName};
+use crate::domain::entities::credential::CredentialMetadata;
 use crate::domain::value_objects::user_role::UserRole;
 use crate::domain::entities::file_permission::FilePermission;
+use super::initiate_webauthn_registration::PendingRegistration;
+use super::token_guard::find_valid_invitation;
+use crate::application::auth_sessions::issue_token;
+use shared::i18n::{tr, Locale};
 
 pub struct InviteCompleteResult {
     pub token: String,
@@ -15,20 +21,30 @@ pub struct InviteCompleteResult {
 pub async fn execute(
     state: &AppState,
     token: &str,
+    ip: &str,
+    user_agent: Option<&str>,
     challenge_id: &str,
     credential: RegisterPublicKeyCredential,
-) -> Result<InviteCompleteResult, String> {
-    // 1. Look up and validate invitation
-    let invitation = state
-        .invitation_repo
-        .find_by_token(token)
-        .await?
-        .ok_or_else(|| "Invitation not found".to_string())?;
-
-    if !invitation.is_valid() {
-        return Err("Invitation is expired or revoked".to_string());
+) -> Result<InviteCompleteResult, (StatusCode, String)> {
+    // Invitations only create owners and clients, who cannot log in while logins are blocked
+    if state.maintenance.current().is_some_and(|window| window.block_logins) {
+        return Err((StatusCode::SERVICE_UNAVAILABLE, tr(Locale::default(), "errors.maintenance").to_string()));
     }
+    // 1. Look up and validate invitation
+    let invitation = find_valid_invitation(state, token, ip).await?;
+    register(state, &invitation, challenge_id, credential, ip, user_agent)
+        .await
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))
+}
 
+async fn register(
+    state: &AppState,
+    invitation: &Invitation,
+    challenge_id: &str,
+    credential: RegisterPublicKeyCredential,
+    ip: &str,
+    user_agent: Option<&str>,
+) -> Result<InviteCompleteResult, String> {
     // 2. Get registration challenge and finish WebAuthn registration
     let state_json = state
         .challenge_repo
@@ -36,32 +52,47 @@ pub async fn execute(
         .await
         .map_err(|e| format!("Challenge not found or expired: {e}"))?;
 
-    let reg_state: PasskeyRegistration = serde_json::from_str(&state_json)
+    let pending: PendingRegistration = serde_json::from_str(&state_json)
         .map_err(|e| format!("Failed to deserialize registration state: {e}"))?;
+    // The account email is the one the invitation was issued to, fixed when the challenge was created
+    if pending.invitation_id != invitation.id || pending.email != invitation.invitee_email.as_str() {
+        return Err("Registration challenge does not belong to this invitation".to_string());
+    }
 
+    let metadata = CredentialMetadata::from_registration(&credential);
     let passkey = state
         .webauthn
-        .finish_passkey_registration(&credential, &reg_state)
+        .finish_passkey_registration(&credential, &pending.registration)
         .map_err(|e| format!("WebAuthn registration failed: {e}"))?;
 
     // 3. Find or create user
-    let email_str = invitation.invitee_email.as_str().to_string();
+    let email_str = pending.email;
     let user_email = Email::new(email_str.clone())
         .map_err(|e| format!("Invalid email: {e}"))?;
 
+    // Clients join the tenant of the owner inviting them, and an account cannot span tenants
+    let tenant_id = state
+        .user_repo
+        .find_by_id(&invitation.owner_id)
+        .await?
+        .ok_or_else(|| "The inviting owner no longer exists".to_string())?
+        .tenant_id();
     let user = match state.user_repo.find_by_email(&user_email).await? {
+        Some(existing) if existing.tenant_id() != tenant_id => {
+            return Err("This email is already used by an account of another tenant".to_string());
+        }
         Some(existing) => existing,
         None => {
             let display_name = DisplayName::new(email_str.clone())
                 .map_err(|e| format!("Invalid display name: {e}"))?;
-            let new_user = User::new(user_email, display_name, vec![UserRole::Client]);
+            let new_user = User::new(user_email, display_name, vec![UserRole::Client]).in_tenant(tenant_id);
             state.user_repo.save(&new_user).await?;
             new_user
         }
     };
 
     // 4. Save credential
-    let cred = Credential::new(user.id().clone(), passkey);
+    let cred = Credential::new(user.id().clone(), passkey, metadata);
     state.credential_repo.save(&cred).await?;
 
     // 5. Create FilePermission rows for each granted path
@@ -75,6 +106,9 @@ pub async fn execute(
             granted_at: chrono::Utc::now(),
             expires_at: invitation.expires_at,
             revoked_at: None,
+            view_only: granted_path.view_only,
+            interaction: granted_path.interaction,
+            group_id: None,
         };
         state.file_permission_repo.save(&permission).await?;
     }
@@ -85,40 +119,14 @@ pub async fn execute(
         .update_status(&invitation.id, "Accepted")
         .await?;
 
-    // 7. Generate JWT
-    #[derive(serde::Serialize)]
-    struct Claims {
-        sub: String,
-        email: String,
-        roles: Vec<String>,
-        exp: usize,
-    }
-
-    let expiration = chrono::Utc::now()
-        .checked_add_signed(chrono::Duration::hours(24))
-        .expect("valid timestamp")
-        .timestamp() as usize;
-
+    // 7. Start an auth session on this device and issue its JWT
+    let jwt = issue_token::execute(state, &user, ip, user_agent).await?;
     let role_strings: Vec<String> = user
         .roles()
         .iter()
         .map(|r| r.as_db_str().to_string())
         .collect();
 
-    let claims = Claims {
-        sub: user.id().to_string(),
-        email: email_str.clone(),
-        roles: role_strings.clone(),
-        exp: expiration,
-    };
-
-    let jwt = encode(
-        &Header::default(),
-        &claims,
-        &EncodingKey::from_secret(state.jwt_secret.as_bytes()),
-    )
-    .map_err(|e| format!("Failed to generate JWT: {e}"))?;
-
     Ok(InviteCompleteResult {
         token: jwt,
         user_id: user.id().to_string(),
@@ -0,0 +1,102 @@
+use axum::http::StatusCode;
+use shared::i18n::{tr, Locale};
+use crate::domain::entities::invitation::Invitation;
+use crate::infrastructure::AppState;
+use super::token_guard::find_valid_invitation;
+
+const CODE_TTL_SECS: u64 = 10 * 60;
+/// Codes mailed per invitation within one code lifetime
+const MAX_SENDS: u32 = 5;
+/// Wrong guesses before the pending code is discarded
+const MAX_CODE_ATTEMPTS: u32 = 5;
+
+fn code_key(invitation: &Invitation) -> String {
+    format!("invite:{}", invitation.id)
+}
+
+fn sends_subject(invitation: &Invitation) -> String {
+    format!("invite-send:{}", invitation.id)
+}
+
+fn attempts_subject(invitation: &Invitation) -> String {
+    format!("invite-code:{}", invitation.id)
+}
+
+/// Six random digits.
+fn generate_code() -> String {
+    format!("{:06}", uuid::Uuid::new_v4().as_u128() % 1_000_000)
+}
+
+/// Mail a fresh verification code to the invitee, replacing any pending one.
+pub async fn send_code(
+    state: &AppState,
+    token: &str,
+    ip: &str,
+    locale: Locale,
+) -> Result<(), (StatusCode, String)> {
+    let invitation = find_valid_invitation(state, token, ip).await?;
+    if !invitation.require_email_verification {
+        return Err((StatusCode::BAD_REQUEST, "This invitation does not require email verification".to_string()));
+    }
+    let email = state.email_sender.as_deref().ok_or((
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Email delivery is not configured".to_string(),
+    ))?;
+
+    let sends = state.login_attempt_repo
+        .record_failure(&sends_subject(&invitation), CODE_TTL_SECS)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    if sends > MAX_SENDS {
+        return Err((StatusCode::TOO_MANY_REQUESTS, "Too many verification codes requested".to_string()));
+    }
+
+    let code = generate_code();
+    state.verification_code_repo
+        .save(&code_key(&invitation), &code, CODE_TTL_SECS)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    let body = tr(locale, "email.invitation_code.body").replace("{code}", &code);
+    email
+        .send(invitation.invitee_email.as_str(), tr(locale, "email.invitation_code.subject"), &body)
+        .await
+        .map_err(|e| (StatusCode::BAD_GATEWAY, e))
+}
+
+/// Check the code supplied when accepting. Invitations without verification accept any input.
+/// A correct code is consumed; too many wrong ones discard it.
+pub async fn verify_code(
+    state: &AppState,
+    invitation: &Invitation,
+    code: Option<&str>,
+) -> Result<(), (StatusCode, String)> {
+    if !invitation.require_email_verification {
+        return Ok(());
+    }
+    let internal = |e: String| (StatusCode::INTERNAL_SERVER_ERROR, e);
+    let code = code.ok_or((StatusCode::BAD_REQUEST, "Email verification code required".to_string()))?;
+
+    let key = code_key(invitation);
+    let pending = state.verification_code_repo.find(&key).await.map_err(internal)?;
+    let Some(pending) = pending else {
+        return Err((StatusCode::BAD_REQUEST, "No pending verification code, request a new one".to_string()));
+    };
+
+    let attempts = attempts_subject(invitation);
+    if pending != code.trim() {
+        let failures = state.login_attempt_repo
+            .record_failure(&attempts, CODE_TTL_SECS)
+            .await
+            .map_err(internal)?;
+        if failures >= MAX_CODE_ATTEMPTS {
+            state.verification_code_repo.delete(&key).await.map_err(internal)?;
+            state.login_attempt_repo.clear(&attempts).await.map_err(internal)?;
+            return Err((StatusCode::TOO_MANY_REQUESTS, "Too many wrong codes, request a new one".to_string()));
+        }
+        return Err((StatusCode::BAD_REQUEST, "Invalid verification code".to_string()));
+    }
+
+    state.verification_code_repo.delete(&key).await.map_err(internal)?;
+    state.login_attempt_repo.clear(&attempts).await.map_err(internal)
+}
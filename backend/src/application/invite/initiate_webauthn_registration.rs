@@ -1,21 +1,31 @@
+use axum::http::StatusCode;
 use webauthn_rs::prelude::*;
 use crate::infrastructure::AppState;
+use super::email_verification;
+use super::token_guard::find_valid_invitation;
 
 pub struct InitiateInviteResult {
     pub challenge_id: String,
     pub challenge: CreationChallengeResponse,
 }
 
-pub async fn execute(state: &AppState, token: &str) -> Result<InitiateInviteResult, String> {
-    let invitation = state
-        .invitation_repo
-        .find_by_token(token)
-        .await?
-        .ok_or_else(|| "Invitation not found".to_string())?;
+/// Registration state kept between initiate and complete. It pins the invitation and the
+/// email the account will be created with, so a challenge cannot be replayed elsewhere.
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct PendingRegistration {
+    pub invitation_id: uuid::Uuid,
+    pub email: String,
+    pub registration: PasskeyRegistration,
+}
 
-    if !invitation.is_valid() {
-        return Err("Invitation is expired or revoked".to_string());
-    }
+pub async fn execute(
+    state: &AppState,
+    token: &str,
+    ip: &str,
+    code: Option<&str>,
+) -> Result<InitiateInviteResult, (StatusCode, String)> {
+    let invitation = find_valid_invitation(state, token, ip).await?;
+    email_verification::verify_code(state, &invitation, code).await?;
 
     let user_unique_id = uuid::Uuid::new_v4();
     let email = invitation.invitee_email.as_str();
@@ -23,17 +33,22 @@ pub async fn execute(state: &AppState, token: &str) -> Result<InitiateInviteResu
     let (challenge, reg_state) = state
         .webauthn
         .start_passkey_registration(user_unique_id, email, email, None)
-        .map_err(|e| format!("Failed to start passkey registration: {e}"))?;
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to start passkey registration: {e}")))?;
 
+    let pending = PendingRegistration {
+        invitation_id: invitation.id,
+        email: email.to_string(),
+        registration: reg_state,
+    };
     let challenge_id = uuid::Uuid::new_v4().to_string();
-    let state_json = serde_json::to_string(&reg_state)
-        .map_err(|e| format!("Failed to serialize registration state: {e}"))?;
+    let state_json = serde_json::to_string(&pending)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to serialize registration state: {e}")))?;
 
     state
         .challenge_repo
         .save_registration_challenge(&challenge_id, &state_json, 300)
         .await
-        .map_err(|e| format!("Failed to save challenge: {e}"))?;
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to save challenge: {e}")))?;
 
     Ok(InitiateInviteResult {
         challenge_id,
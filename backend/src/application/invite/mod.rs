@@ -1,3 +1,5 @@
 pub mod view_invitation;
 pub mod initiate_webauthn_registration;
 pub mod complete_webauthn_registration;
+pub mod email_verification;
+pub mod token_guard;
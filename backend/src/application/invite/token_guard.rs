@@ -0,0 +1,35 @@
+use axum::http::StatusCode;
+use crate::application::access::link_guard::LinkGuard;
+use crate::domain::entities::invitation::Invitation;
+use crate::infrastructure::AppState;
+
+const GUARD: LinkGuard = LinkGuard {
+    kind: "invite",
+    label: "invitation links",
+    lockout_event: "invite_token_lockout",
+};
+
+/// Look up a pending invitation by token. Unknown tokens count against the caller's IP, so
+/// tokens cannot be guessed by brute force.
+pub async fn find_valid_invitation(
+    state: &AppState,
+    token: &str,
+    ip: &str,
+) -> Result<Invitation, (StatusCode, String)> {
+    let subject = GUARD.ip_subject(ip);
+    GUARD.ensure_allowed(state, &subject).await?;
+
+    let invitation = state.invitation_repo
+        .find_by_token(token)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    let Some(invitation) = invitation else {
+        GUARD.penalize(state, &subject, ip).await;
+        return Err((StatusCode::NOT_FOUND, "Invitation not found".to_string()));
+    };
+
+    if !invitation.is_valid() {
+        return Err((StatusCode::BAD_REQUEST, "Invitation is expired or revoked".to_string()));
+    }
+    Ok(invitation)
+}
@@ -1,29 +1,51 @@
-use crate::application::ports::invitation_repository::InvitationRepository;
+use axum::http::StatusCode;
 use crate::domain::entities::invitation::GrantedPath;
+use crate::domain::entities::owner_branding::OwnerBranding;
+use crate::infrastructure::AppState;
+use super::token_guard::find_valid_invitation;
 
 #[derive(Debug, serde::Serialize)]
 pub struct InvitationView {
     pub owner_id: String,
     pub granted_paths: Vec<GrantedPath>,
     pub expires_at: Option<String>,
+    pub require_email_verification: bool,
+    /// How the inviting owner presents their vault; `null` for the defaults
+    pub branding: Option<Branding>,
 }
 
-pub async fn execute(
-    invitation_repo: &dyn InvitationRepository,
-    token: &str,
-) -> Result<InvitationView, String> {
-    let invitation = invitation_repo
-        .find_by_token(token)
-        .await?
-        .ok_or_else(|| "Invitation not found".to_string())?;
+/// Branding shown on pages reached without an account
+#[derive(Debug, serde::Serialize)]
+pub struct Branding {
+    pub display_name: Option<String>,
+    pub logo: Option<String>,
+    pub accent_color: Option<String>,
+}
 
-    if !invitation.is_valid() {
-        return Err("Invitation is expired or revoked".to_string());
+impl From<OwnerBranding> for Branding {
+    fn from(branding: OwnerBranding) -> Self {
+        Self { display_name: branding.display_name, logo: branding.logo, accent_color: branding.accent_color }
     }
+}
+
+pub async fn execute(
+    state: &AppState,
+    token: &str,
+    ip: &str,
+) -> Result<InvitationView, (StatusCode, String)> {
+    let invitation = find_valid_invitation(state, token, ip).await?;
+    let branding = state
+        .branding_repo
+        .find(&invitation.owner_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+        .map(Branding::from);
 
     Ok(InvitationView {
         owner_id: invitation.owner_id.to_string(),
         granted_paths: invitation.granted_paths,
         expires_at: invitation.expires_at.map(|dt| dt.to_rfc3339()),
+        require_email_verification: invitation.require_email_verification,
+        branding,
     })
 }
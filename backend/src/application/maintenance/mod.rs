@@ -0,0 +1,70 @@
+// Maintenance mode - drain an instance before upgrading it
+use std::sync::RwLock;
+use serde_json::json;
+use crate::domain::entities::audit_event::AuditEvent;
+use crate::domain::entities::maintenance::MaintenanceWindow;
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::webrtc::SignalingMessage;
+
+/// This instance's maintenance state. It lives in memory, so each instance of a multi-host
+/// deployment is put in maintenance on its own, and a restart ends it.
+#[derive(Default)]
+pub struct Maintenance {
+    window: RwLock<Option<MaintenanceWindow>>,
+}
+
+impl Maintenance {
+    pub fn current(&self) -> Option<MaintenanceWindow> {
+        self.window.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.window.read().unwrap_or_else(|e| e.into_inner()).is_some()
+    }
+
+    fn replace(&self, window: Option<MaintenanceWindow>) -> Option<MaintenanceWindow> {
+        std::mem::replace(&mut *self.window.write().unwrap_or_else(|e| e.into_inner()), window)
+    }
+}
+
+/// Put the instance in maintenance, or change the message and login policy of the current
+/// window. Connected users are warned either way.
+pub async fn start(
+    state: &AppState,
+    acting_id: &UserId,
+    message: Option<String>,
+    block_logins: bool,
+) -> Result<MaintenanceWindow, String> {
+    let started_at = state.maintenance.current().map_or_else(chrono::Utc::now, |w| w.started_at);
+    let window = MaintenanceWindow { started_at, started_by: acting_id.clone(), message, block_logins };
+    let previous = state.maintenance.replace(Some(window.clone()));
+    state
+        .webrtc
+        .broadcast(&SignalingMessage::Maintenance { active: true, message: window.message.clone() })
+        .await;
+
+    let mut event = AuditEvent::new(
+        if previous.is_some() { "maintenance_updated" } else { "maintenance_started" },
+        json!({ "message": window.message, "block_logins": window.block_logins }),
+    );
+    event.user_id = Some(acting_id.clone());
+    state.audit_repo.record(&event).await?;
+    Ok(window)
+}
+
+/// Leave maintenance. False when the instance was not in it.
+pub async fn end(state: &AppState, acting_id: &UserId) -> Result<bool, String> {
+    let Some(window) = state.maintenance.replace(None) else {
+        return Ok(false);
+    };
+    state
+        .webrtc
+        .broadcast(&SignalingMessage::Maintenance { active: false, message: None })
+        .await;
+
+    let mut event = AuditEvent::new("maintenance_ended", json!({ "started_at": window.started_at }));
+    event.user_id = Some(acting_id.clone());
+    state.audit_repo.record(&event).await?;
+    Ok(true)
+}
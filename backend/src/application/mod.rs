@@ -5,4 +5,25 @@ pub mod super_admin;
 pub mod owner;
 pub mod client;
 pub mod invite;
+pub mod profile;
+pub mod access;
+pub mod permissions;
+pub mod files;
+pub mod sessions;
+pub mod auth_sessions;
+pub mod data_exports;
+pub mod folder_exports;
+pub mod galleries;
+pub mod comments;
+pub mod file_requests;
+pub mod impersonation;
+pub mod account_deletion;
+pub mod maintenance;
+pub mod storage_scheduler;
+pub mod processing;
+pub mod organization;
+pub mod tenants;
+pub mod imports;
+pub mod provisioning;
+pub mod apps;
 pub mod ports;
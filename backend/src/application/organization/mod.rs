@@ -0,0 +1,177 @@
+// Organization rules - sort the media landing in a folder into others, named after the date
+// they were taken or their camera
+use std::sync::Arc;
+use async_trait::async_trait;
+use chrono::Utc;
+use serde::Serialize;
+use serde_json::json;
+use uuid::Uuid;
+use crate::application::ports::{AuditRepository, FileHashRepository, FileProcessor, MediaMetadataRepository, OrganizationRuleRepository, VaultStorage};
+use crate::domain::entities::audit_event::AuditEvent;
+use crate::domain::entities::file_job::FileOperation;
+use crate::domain::entities::media_metadata::MediaMetadata;
+use crate::domain::entities::organization_rule::OrganizationRule;
+use crate::domain::entities::processing_task::ProcessingTask;
+use crate::domain::entities::vault_import::numbered_name;
+use crate::domain::value_objects::UserId;
+
+/// Audit event recorded for every file a rule moved; the rules' activity log
+pub const RULE_APPLIED: &str = "organization_rule_applied";
+
+/// Names tried when a file already has the destination's path
+const MAX_RENAMES: u32 = 100;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PlannedMove {
+    pub from: String,
+    pub to: String,
+}
+
+/// Save a new rule, built and checked with [`OrganizationRule::new`].
+pub async fn create<R, A>(rules: &R, audit: &A, rule: OrganizationRule) -> Result<OrganizationRule, String>
+where
+    R: OrganizationRuleRepository + ?Sized,
+    A: AuditRepository + ?Sized,
+{
+    rules.save(&rule).await?;
+    record(audit, &rule.owner_id, "organization_rule_created", json!({ "rule_id": rule.id, "name": rule.name })).await?;
+    Ok(rule)
+}
+
+/// Replace the name, folders and state of the owner's rule `id` with those of `changes`.
+pub async fn update<R, A>(rules: &R, audit: &A, id: &Uuid, changes: OrganizationRule) -> Result<OrganizationRule, String>
+where
+    R: OrganizationRuleRepository + ?Sized,
+    A: AuditRepository + ?Sized,
+{
+    let mut rule = find_owned(rules, &changes.owner_id, id).await?;
+    rule.name = changes.name;
+    rule.source = changes.source;
+    rule.destination = changes.destination;
+    rule.enabled = changes.enabled;
+    rule.updated_at = Utc::now();
+    rules.save(&rule).await?;
+    record(
+        audit,
+        &rule.owner_id,
+        "organization_rule_updated",
+        json!({ "rule_id": rule.id, "name": rule.name, "enabled": rule.enabled }),
+    )
+    .await?;
+    Ok(rule)
+}
+
+pub async fn delete<R, A>(rules: &R, audit: &A, owner_id: &UserId, id: &Uuid) -> Result<(), String>
+where
+    R: OrganizationRuleRepository + ?Sized,
+    A: AuditRepository + ?Sized,
+{
+    let rule = find_owned(rules, owner_id, id).await?;
+    rules.delete(&rule.id).await?;
+    record(audit, owner_id, "organization_rule_deleted", json!({ "rule_id": rule.id, "name": rule.name })).await
+}
+
+/// What a rule would do to the media already in its source folder, without moving anything.
+/// Files whose destination is taken would get a numbered name instead.
+pub async fn preview<M>(metadata: &M, rule: &OrganizationRule) -> Result<Vec<PlannedMove>, String>
+where
+    M: MediaMetadataRepository + ?Sized,
+{
+    Ok(metadata
+        .list_under(&rule.owner_id, &rule.source)
+        .await?
+        .iter()
+        .filter_map(|media| Some(PlannedMove { from: media.path.clone(), to: rule.destination_for(media)? }))
+        .collect())
+}
+
+async fn find_owned<R>(rules: &R, owner_id: &UserId, id: &Uuid) -> Result<OrganizationRule, String>
+where
+    R: OrganizationRuleRepository + ?Sized,
+{
+    rules
+        .find_by_id(id)
+        .await?
+        .filter(|r| &r.owner_id == owner_id)
+        .ok_or_else(|| "Rule not found".to_string())
+}
+
+async fn record<A: AuditRepository + ?Sized>(audit: &A, owner_id: &UserId, event_type: &str, details: serde_json::Value) -> Result<(), String> {
+    let mut event = AuditEvent::new(event_type, details);
+    event.owner_id = Some(owner_id.clone());
+    event.user_id = Some(owner_id.clone());
+    audit.record(&event).await
+}
+
+/// Processing stage moving each new photo or video as the owner's first matching rule says.
+/// Runs after the metadata stage, and last, since the file is no longer at the task's path.
+pub struct OrganizeStage {
+    rules: Arc<dyn OrganizationRuleRepository>,
+    metadata: Arc<dyn MediaMetadataRepository>,
+    hashes: Arc<dyn FileHashRepository>,
+    storage: Arc<dyn VaultStorage>,
+    audit: Arc<dyn AuditRepository>,
+}
+
+impl OrganizeStage {
+    pub fn new(
+        rules: Arc<dyn OrganizationRuleRepository>,
+        metadata: Arc<dyn MediaMetadataRepository>,
+        hashes: Arc<dyn FileHashRepository>,
+        storage: Arc<dyn VaultStorage>,
+        audit: Arc<dyn AuditRepository>,
+    ) -> Self {
+        Self { rules, metadata, hashes, storage, audit }
+    }
+
+    /// The destination, or the first numbered name next to it that is free.
+    async fn free_path(&self, owner_id: &UserId, destination: &str) -> Result<String, String> {
+        let (folder, name) = destination.rsplit_once('/').unwrap_or(("", destination));
+        for n in 0..=MAX_RENAMES {
+            let candidate = match n {
+                0 => destination.to_string(),
+                n => format!("{folder}/{}", numbered_name(name, n)),
+            };
+            match self.storage.stat(owner_id, &candidate).await {
+                Ok(_) => continue,
+                Err(e) if e.contains("not found") => return Ok(candidate),
+                Err(e) => return Err(e),
+            }
+        }
+        Err(format!("{destination} and its numbered names are all taken"))
+    }
+
+    async fn apply(&self, rule: &OrganizationRule, media: &MediaMetadata, destination: &str) -> Result<(), String> {
+        let to = self.free_path(&media.owner_id, destination).await?;
+        let operation = FileOperation::Move { from: media.path.clone(), to: to.clone() };
+        self.storage.apply(&media.owner_id, &operation, false).await?;
+        self.metadata.move_path(&media.owner_id, &media.path, &to).await?;
+        self.hashes.move_path(&media.owner_id, &media.path, &to).await?;
+
+        let mut event = AuditEvent::new(
+            RULE_APPLIED,
+            json!({ "rule_id": rule.id, "rule": rule.name, "from": media.path, "to": to }),
+        );
+        event.owner_id = Some(media.owner_id.clone());
+        self.audit.record(&event).await
+    }
+}
+
+#[async_trait]
+impl FileProcessor for OrganizeStage {
+    fn name(&self) -> &'static str {
+        "organize"
+    }
+
+    async fn process(&self, task: &ProcessingTask) -> Result<(), String> {
+        // Only media with metadata can be sorted
+        let Some(media) = self.metadata.find(&task.owner_id, &task.path).await? else {
+            return Ok(());
+        };
+        let rules = self.rules.find_by_owner(&task.owner_id).await?;
+        let Some((rule, destination)) = rules.iter().find_map(|rule| Some((rule, rule.destination_for(&media)?))) else {
+            return Ok(());
+        };
+        self.apply(rule, &media, &destination).await
+    }
+}
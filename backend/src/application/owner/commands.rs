@@ -1,4 +1,39 @@
 // Owner commands
 pub mod create_invitation;
 pub mod list_permissions;
+pub mod get_permission_history;
+pub mod list_invitations;
+pub mod list_audit_events;
+pub mod list_vault_sessions;
 pub mod revoke_permission;
+pub mod renew_permission;
+pub mod unlock_account;
+pub mod set_bandwidth_caps;
+pub mod list_bandwidth_usage;
+pub mod get_access_policy;
+pub mod update_access_policy;
+pub mod watch_session;
+pub mod create_group;
+pub mod list_groups;
+pub mod update_group;
+pub mod delete_group;
+pub mod add_group_member;
+pub mod remove_group_member;
+pub mod submit_file_job;
+pub mod get_file_job;
+pub mod cancel_file_job;
+pub mod download_archive;
+pub mod download_file;
+pub mod list_files;
+pub mod create_upload;
+pub mod get_upload;
+pub mod append_upload;
+pub mod complete_upload;
+pub mod cancel_upload;
+pub mod export_client_data;
+pub mod place_legal_hold;
+pub mod list_legal_holds;
+pub mod release_legal_hold;
+pub mod list_app_settings;
+pub mod update_app_setting;
+pub mod update_branding;
@@ -0,0 +1,46 @@
+use uuid::Uuid;
+use crate::application::ports::{AuditRepository, ClientGroupRepository, FilePermissionRepository};
+use crate::domain::entities::audit_event::AuditEvent;
+use crate::domain::entities::client_group::ClientGroup;
+use crate::domain::value_objects::UserId;
+
+/// Add one of the owner's clients to a group and grant them the group's paths.
+/// Adding an existing member changes nothing.
+pub async fn execute<G, P, A>(
+    groups: &G,
+    permissions: &P,
+    audit: &A,
+    owner_id: &UserId,
+    group_id: &Uuid,
+    client_id: &UserId,
+) -> Result<ClientGroup, String>
+where
+    G: ClientGroupRepository + ?Sized,
+    P: FilePermissionRepository + ?Sized,
+    A: AuditRepository + ?Sized,
+{
+    let mut group = groups
+        .find_by_id(group_id)
+        .await?
+        .filter(|g| &g.owner_id == owner_id)
+        .ok_or("Group not found")?;
+    if group.has_member(client_id) {
+        return Ok(group);
+    }
+    // Clients join through an invitation first; groups only organize existing ones
+    if permissions.find_by_owner_client(owner_id, client_id).await?.is_empty() {
+        return Err("Not one of your clients".to_string());
+    }
+
+    groups.add_member(&group.id, client_id).await?;
+    for permission in group.permissions_for(client_id) {
+        permissions.save(&permission).await?;
+    }
+    group.members.push(client_id.clone());
+
+    let mut event = AuditEvent::new("group_member_added", serde_json::json!({ "group_id": group.id }));
+    event.user_id = Some(client_id.clone());
+    event.owner_id = Some(owner_id.clone());
+    audit.record(&event).await?;
+    Ok(group)
+}
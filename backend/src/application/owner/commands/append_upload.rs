@@ -0,0 +1,79 @@
+use crate::application::owner::commands::get_upload;
+use crate::application::ports::{ByteStream, UploadSessionRepository, VaultStorage};
+use crate::domain::entities::upload_session::UploadSession;
+use crate::domain::value_objects::UserId;
+use chrono::Utc;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// Uploads receiving a chunk right now. Chunks of one upload are taken one at a time, so two
+/// requests claiming the same offset cannot both pass the offset check and write.
+#[derive(Clone, Default)]
+pub struct AppendingUploads(Arc<Mutex<HashSet<Uuid>>>);
+
+struct Appending {
+    uploads: AppendingUploads,
+    upload_id: Uuid,
+}
+
+impl AppendingUploads {
+    fn start(&self, upload_id: &Uuid) -> Result<Appending, String> {
+        let mut appending = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        if !appending.insert(*upload_id) {
+            return Err("Another chunk of this upload is being received".to_string());
+        }
+        Ok(Appending { uploads: self.clone(), upload_id: *upload_id })
+    }
+}
+
+impl Drop for Appending {
+    fn drop(&mut self) {
+        self.uploads.0.lock().unwrap_or_else(|e| e.into_inner()).remove(&self.upload_id);
+    }
+}
+
+/// Stage a chunk that claims to start at `offset`. Whatever arrived is recorded even if the
+/// connection dropped, so the client resumes from the returned session's offset.
+pub async fn execute<U, S>(
+    uploads: &U,
+    storage: &S,
+    appending: &AppendingUploads,
+    acting_id: &UserId,
+    upload_id: &Uuid,
+    offset: u64,
+    chunk: ByteStream,
+) -> Result<UploadSession, String>
+where
+    U: UploadSessionRepository + ?Sized,
+    S: VaultStorage + ?Sized,
+{
+    let _appending = appending.start(upload_id)?;
+    let mut upload = get_upload::execute(uploads, acting_id, upload_id).await?;
+    upload.check_offset(offset)?;
+    let received = storage
+        .append_upload(&upload.id, offset, upload.size - offset, chunk)
+        .await?;
+    if received != upload.offset {
+        upload.offset = received;
+        upload.expires_at = Utc::now() + UploadSession::idle_ttl();
+        uploads.update_offset(&upload.id, upload.offset, upload.expires_at).await?;
+    }
+    Ok(upload)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_one_chunk_per_upload_at_a_time() {
+        let appending = AppendingUploads::default();
+        let upload_id = Uuid::new_v4();
+        let first = appending.start(&upload_id).unwrap();
+        assert!(appending.start(&upload_id).is_err());
+        assert!(appending.start(&Uuid::new_v4()).is_ok());
+        drop(first);
+        assert!(appending.start(&upload_id).is_ok());
+    }
+}
@@ -0,0 +1,24 @@
+use crate::application::owner::commands::get_file_job;
+use crate::application::ports::FileJobRepository;
+use crate::domain::entities::file_job::{FileJob, JobStatus};
+use crate::domain::value_objects::UserId;
+use uuid::Uuid;
+
+/// A queued job is cancelled at once; a running one stops before its next operation.
+pub async fn execute<J: FileJobRepository + ?Sized>(
+    jobs: &J,
+    acting_id: &UserId,
+    job_id: &Uuid,
+) -> Result<FileJob, String> {
+    let mut job = get_file_job::execute(jobs, acting_id, job_id).await?;
+    if job.status.is_finished() {
+        return Err("Job already finished".to_string());
+    }
+    jobs.request_cancel(&job.id).await?;
+    job.cancel_requested = true;
+    if job.status == JobStatus::Queued {
+        jobs.update_progress(&job.id, JobStatus::Cancelled, job.completed, None).await?;
+        job.status = JobStatus::Cancelled;
+    }
+    Ok(job)
+}
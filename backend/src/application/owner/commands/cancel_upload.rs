@@ -0,0 +1,26 @@
+use crate::application::owner::commands::get_upload;
+use crate::application::ports::{UploadSessionRepository, VaultStorage};
+use crate::domain::entities::upload_session::{UploadSession, UploadStatus};
+use crate::domain::value_objects::UserId;
+use uuid::Uuid;
+
+/// Abandon an active upload and free its staged bytes.
+pub async fn execute<U, S>(
+    uploads: &U,
+    storage: &S,
+    acting_id: &UserId,
+    upload_id: &Uuid,
+) -> Result<UploadSession, String>
+where
+    U: UploadSessionRepository + ?Sized,
+    S: VaultStorage + ?Sized,
+{
+    let mut upload = get_upload::execute(uploads, acting_id, upload_id).await?;
+    if upload.status != UploadStatus::Active {
+        return Err("Upload already finished".to_string());
+    }
+    storage.discard_upload(&upload.id).await?;
+    uploads.finish(&upload.id, UploadStatus::Cancelled, None).await?;
+    upload.status = UploadStatus::Cancelled;
+    Ok(upload)
+}
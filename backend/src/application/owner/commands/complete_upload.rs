@@ -0,0 +1,59 @@
+use std::sync::Arc;
+use crate::application::ports::{AuditRepository, FileProcessor, ProcessingRepository, UploadHook, UploadSessionRepository, VaultStorage};
+use crate::application::processing;
+use crate::domain::entities::audit_event::AuditEvent;
+use crate::domain::entities::upload_session::{UploadSession, UploadStatus};
+
+/// Run the completion hooks on a fully received upload and move it into the vault. A rejected
+/// upload is marked failed and its staged bytes discarded; an accepted one is queued for the
+/// processing stages.
+pub async fn execute<U, S, P, A>(
+    uploads: &U,
+    storage: &S,
+    hooks: &[Arc<dyn UploadHook>],
+    processing: &P,
+    processors: &[Arc<dyn FileProcessor>],
+    audit: &A,
+    mut upload: UploadSession,
+) -> Result<UploadSession, String>
+where
+    U: UploadSessionRepository + ?Sized,
+    S: VaultStorage + ?Sized,
+    P: ProcessingRepository + ?Sized,
+    A: AuditRepository + ?Sized,
+{
+    if !upload.is_complete() || upload.status != UploadStatus::Active {
+        return Err("Upload is not ready to complete".to_string());
+    }
+    let mut result = Ok(());
+    for hook in hooks {
+        result = hook.on_complete(&upload).await.map_err(|e| format!("{}: {e}", hook.name()));
+        if result.is_err() {
+            break;
+        }
+    }
+    if result.is_ok() {
+        result = storage.commit_upload(&upload.id, &upload.owner_id, &upload.path).await;
+    }
+    if let Err(e) = result {
+        storage.discard_upload(&upload.id).await?;
+        uploads.finish(&upload.id, UploadStatus::Failed, Some(&e)).await?;
+        return Err(e);
+    }
+    uploads.finish(&upload.id, UploadStatus::Completed, None).await?;
+    upload.status = UploadStatus::Completed;
+
+    let mut event = AuditEvent::new(
+        "file_uploaded",
+        serde_json::json!({ "upload_id": upload.id, "path": upload.path, "size": upload.size }),
+    );
+    event.owner_id = Some(upload.owner_id.clone());
+    event.user_id = Some(upload.created_by.clone());
+    audit.record(&event).await?;
+
+    // The file is in the vault either way; a failing queue only costs its processing
+    if let Err(e) = processing::enqueue(processing, processors, &upload.owner_id, &upload.path, upload.size).await {
+        tracing::warn!("Failed to queue {} for processing: {}", upload.path, e);
+    }
+    Ok(upload)
+}
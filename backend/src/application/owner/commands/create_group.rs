@@ -0,0 +1,32 @@
+use crate::application::ports::{AuditRepository, ClientGroupRepository};
+use crate::domain::entities::audit_event::AuditEvent;
+use crate::domain::entities::client_group::ClientGroup;
+use crate::domain::entities::invitation::GrantedPath;
+use crate::domain::value_objects::UserId;
+
+/// Create an empty group whose members will all receive `grants`.
+pub async fn execute<G, A>(
+    groups: &G,
+    audit: &A,
+    owner_id: &UserId,
+    name: String,
+    grants: Vec<GrantedPath>,
+) -> Result<ClientGroup, String>
+where
+    G: ClientGroupRepository + ?Sized,
+    A: AuditRepository + ?Sized,
+{
+    if groups.find_by_owner(owner_id).await?.iter().any(|g| g.name == name.trim()) {
+        return Err("A group with this name already exists".to_string());
+    }
+    let group = ClientGroup::new(owner_id.clone(), name, grants)?;
+    groups.save(&group).await?;
+
+    let mut event = AuditEvent::new(
+        "group_created",
+        serde_json::json!({ "group_id": group.id, "name": group.name }),
+    );
+    event.owner_id = Some(owner_id.clone());
+    audit.record(&event).await?;
+    Ok(group)
+}
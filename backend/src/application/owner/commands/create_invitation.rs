@@ -1,14 +1,17 @@
 use crate::domain::entities::invitation::{Invitation, InvitationStatus, GrantedPath, AccessLevel};
 use crate::domain::value_objects::{Email, UserId};
 use crate::application::ports::invitation_repository::InvitationRepository;
+use crate::application::owner::scope::OwnerScope;
 use chrono::{Utc, Duration};
 use uuid::Uuid;
 
 pub struct CreateInvitationCommand {
+    /// The vault the client is invited into
     pub owner_id: UserId,
     pub invitee_email: String,
     pub granted_paths: Vec<GrantedPath>,
     pub expires_in_hours: Option<i64>,
+    pub require_email_verification: bool,
 }
 
 pub struct CreateInvitationResult {
@@ -20,6 +23,7 @@ pub struct CreateInvitationResult {
 pub async fn execute<R: InvitationRepository + ?Sized>(
     repo: &R,
     cmd: CreateInvitationCommand,
+    scope: &OwnerScope,
     base_url: &str,
 ) -> Result<CreateInvitationResult, String> {
     // Validate email
@@ -29,9 +33,19 @@ pub async fn execute<R: InvitationRepository + ?Sized>(
         if gp.path.contains("..") || gp.path.starts_with('/') {
             return Err("Invalid path: must be a relative path without '..'".to_string());
         }
+        if !scope.covers(&gp.path) {
+            return Err(format!("Path outside your delegated area: {}", gp.path));
+        }
     }
     let token = uuid::Uuid::new_v4().simple().to_string();
-    let expires_at = cmd.expires_in_hours.map(|h| Utc::now() + Duration::hours(h));
+    let expires_at = match cmd.expires_in_hours {
+        Some(hours) => Some(
+            Duration::try_hours(hours)
+                .and_then(|d| Utc::now().checked_add_signed(d))
+                .ok_or_else(|| "Expiry is too far in the future".to_string())?,
+        ),
+        None => None,
+    };
     let invitation = Invitation {
         id: Uuid::new_v4(),
         owner_id: cmd.owner_id,
@@ -41,6 +55,7 @@ pub async fn execute<R: InvitationRepository + ?Sized>(
         status: InvitationStatus::Pending,
         expires_at,
         created_at: Utc::now(),
+        require_email_verification: cmd.require_email_verification,
     };
     repo.save(&invitation).await?;
     let invite_url = format!("{}/invite/{}", base_url.trim_end_matches('/'), token);
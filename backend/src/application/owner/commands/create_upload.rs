@@ -0,0 +1,33 @@
+use crate::application::owner::scope::OwnerScope;
+use crate::application::ports::{UploadSessionRepository, VaultStorage};
+use crate::domain::entities::upload_session::UploadSession;
+use crate::domain::services::permission_evaluator::Operation;
+use crate::domain::value_objects::UserId;
+
+/// Open a resumable upload of `size` bytes to `path` in `owner_id`'s vault. The quota and the
+/// destination are checked up front, so a phone does not send gigabytes only to be refused at
+/// the end.
+pub async fn execute<U, S>(
+    uploads: &U,
+    storage: &S,
+    acting_id: &UserId,
+    owner_id: UserId,
+    path: String,
+    size: u64,
+    scope: &OwnerScope,
+) -> Result<UploadSession, String>
+where
+    U: UploadSessionRepository + ?Sized,
+    S: VaultStorage + ?Sized,
+{
+    scope.evaluator(Vec::new()).check(&path, Operation::Upload, chrono::Utc::now())?;
+    storage.check_free(&owner_id, &path).await?;
+    storage.check_quota(&owner_id, size).await?;
+    let upload = UploadSession::new(owner_id, acting_id.clone(), path, size)?;
+    // Staging an empty file up front lets zero-byte uploads complete without a chunk
+    storage
+        .append_upload(&upload.id, 0, 0, Box::pin(futures_util::stream::empty()))
+        .await?;
+    uploads.save(&upload).await?;
+    Ok(upload)
+}
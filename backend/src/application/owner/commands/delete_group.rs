@@ -0,0 +1,34 @@
+use uuid::Uuid;
+use crate::application::ports::{AuditRepository, ClientGroupRepository, FilePermissionRepository};
+use crate::domain::entities::audit_event::AuditEvent;
+use crate::domain::value_objects::UserId;
+
+/// Delete a group, revoking every permission its members derived from it.
+pub async fn execute<G, P, A>(
+    groups: &G,
+    permissions: &P,
+    audit: &A,
+    owner_id: &UserId,
+    group_id: &Uuid,
+) -> Result<(), String>
+where
+    G: ClientGroupRepository + ?Sized,
+    P: FilePermissionRepository + ?Sized,
+    A: AuditRepository + ?Sized,
+{
+    let group = groups
+        .find_by_id(group_id)
+        .await?
+        .filter(|g| &g.owner_id == owner_id)
+        .ok_or("Group not found")?;
+
+    permissions.revoke_for_group(&group.id, None).await?;
+    groups.delete(&group.id).await?;
+
+    let mut event = AuditEvent::new(
+        "group_deleted",
+        serde_json::json!({ "group_id": group.id, "name": group.name, "members": group.members.len() }),
+    );
+    event.owner_id = Some(owner_id.clone());
+    audit.record(&event).await
+}
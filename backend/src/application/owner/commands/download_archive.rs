@@ -0,0 +1,40 @@
+use shared::ArchiveFormat;
+use crate::application::owner::scope::OwnerScope;
+use crate::application::ports::{AuditRepository, ByteStream, VaultStorage};
+use crate::domain::entities::audit_event::AuditEvent;
+use crate::domain::services::permission_evaluator::Operation;
+use crate::domain::value_objects::UserId;
+
+/// Stream an archive of vault paths. Recorded in the audit log, since it takes files out of the vault.
+pub async fn execute<S, A>(
+    storage: &S,
+    audit: &A,
+    acting_id: &UserId,
+    owner_id: &UserId,
+    paths: Vec<String>,
+    format: ArchiveFormat,
+    scope: &OwnerScope,
+) -> Result<ByteStream, String>
+where
+    S: VaultStorage + ?Sized,
+    A: AuditRepository + ?Sized,
+{
+    if paths.is_empty() {
+        return Err("An archive needs at least one path".to_string());
+    }
+    let evaluator = scope.evaluator(Vec::new());
+    let now = chrono::Utc::now();
+    for path in &paths {
+        evaluator.check(path, Operation::Download, now)?;
+    }
+    let stream = storage.archive(owner_id, &paths, format).await?;
+
+    let mut event = AuditEvent::new(
+        "archive_downloaded",
+        serde_json::json!({ "paths": paths, "format": format }),
+    );
+    event.owner_id = Some(owner_id.clone());
+    event.user_id = Some(acting_id.clone());
+    audit.record(&event).await?;
+    Ok(stream)
+}
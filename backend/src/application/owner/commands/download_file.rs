@@ -0,0 +1,42 @@
+use std::ops::Range;
+use crate::application::owner::scope::OwnerScope;
+use crate::application::ports::{AuditRepository, ByteStream, FileStat, VaultStorage};
+use crate::domain::entities::audit_event::AuditEvent;
+use crate::domain::services::permission_evaluator::Operation;
+use crate::domain::value_objects::UserId;
+
+/// Look up a vault file before serving it, so conditional and range headers can be checked.
+pub async fn stat<S>(storage: &S, owner_id: &UserId, path: &str, scope: &OwnerScope) -> Result<FileStat, String>
+where
+    S: VaultStorage + ?Sized,
+{
+    scope.evaluator(Vec::new()).check(path, Operation::Download, chrono::Utc::now())?;
+    storage.stat(owner_id, path).await
+}
+
+/// Stream the `bytes` of a vault file. Only reads starting at the first byte are audited, so resumed
+/// and seeking requests for the same download are not recorded again.
+pub async fn execute<S, A>(
+    storage: &S,
+    audit: &A,
+    acting_id: &UserId,
+    owner_id: &UserId,
+    path: &str,
+    bytes: Range<u64>,
+    scope: &OwnerScope,
+) -> Result<ByteStream, String>
+where
+    S: VaultStorage + ?Sized,
+    A: AuditRepository + ?Sized,
+{
+    scope.evaluator(Vec::new()).check(path, Operation::Download, chrono::Utc::now())?;
+    let stream = storage.read_range(owner_id, path, bytes.start, bytes.end - bytes.start).await?;
+
+    if bytes.start == 0 {
+        let mut event = AuditEvent::new("file_downloaded", serde_json::json!({ "path": path }));
+        event.owner_id = Some(owner_id.clone());
+        event.user_id = Some(acting_id.clone());
+        audit.record(&event).await?;
+    }
+    Ok(stream)
+}
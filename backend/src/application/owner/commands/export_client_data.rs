@@ -0,0 +1,25 @@
+use crate::application::data_exports;
+use crate::application::ports::{AuditRepository, DataExportRepository, FilePermissionRepository};
+use crate::domain::entities::data_export::DataExport;
+use crate::domain::value_objects::UserId;
+
+/// Queue an archive of what `client_id` shares with the owner: their account details, the
+/// permissions between them and the client's actions in the owner's vault.
+pub async fn execute<E, P, A>(
+    exports: &E,
+    permissions: &P,
+    audit: &A,
+    owner_id: &UserId,
+    client_id: &UserId,
+) -> Result<DataExport, String>
+where
+    E: DataExportRepository + ?Sized,
+    P: FilePermissionRepository + ?Sized,
+    A: AuditRepository + ?Sized,
+{
+    // Only clients this owner has granted something to, revoked or not
+    if permissions.find_by_owner_client(owner_id, client_id).await?.is_empty() {
+        return Err("Client not found".to_string());
+    }
+    data_exports::queue(exports, audit, DataExport::for_client(owner_id.clone(), client_id.clone())).await
+}
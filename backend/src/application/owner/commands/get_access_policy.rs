@@ -0,0 +1,18 @@
+use crate::application::ports::AccessPolicyRepository;
+use crate::domain::entities::access_policy::AccessPolicy;
+use crate::domain::value_objects::UserId;
+
+/// The owner's stored policy, or an empty one that admits everyone.
+pub async fn execute<R: AccessPolicyRepository + ?Sized>(
+    repo: &R,
+    owner_id: &UserId,
+) -> Result<AccessPolicy, String> {
+    Ok(repo.find(owner_id).await?.unwrap_or_else(|| AccessPolicy {
+        owner_id: owner_id.clone(),
+        allowed_ranges: Vec::new(),
+        denied_ranges: Vec::new(),
+        allowed_countries: Vec::new(),
+        denied_countries: Vec::new(),
+        updated_at: chrono::Utc::now(),
+    }))
+}
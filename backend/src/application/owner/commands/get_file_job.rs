@@ -0,0 +1,16 @@
+use crate::application::ports::FileJobRepository;
+use crate::domain::entities::file_job::FileJob;
+use crate::domain::value_objects::UserId;
+use uuid::Uuid;
+
+/// A job is visible to the vault owner and to whoever submitted it.
+pub async fn execute<J: FileJobRepository + ?Sized>(
+    jobs: &J,
+    acting_id: &UserId,
+    job_id: &Uuid,
+) -> Result<FileJob, String> {
+    jobs.find_by_id(job_id)
+        .await?
+        .filter(|job| &job.owner_id == acting_id || &job.created_by == acting_id)
+        .ok_or_else(|| "Job not found".to_string())
+}
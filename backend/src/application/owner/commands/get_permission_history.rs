@@ -0,0 +1,57 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use crate::application::owner::scope::OwnerScope;
+use crate::application::ports::FilePermissionRepository;
+use crate::domain::entities::file_permission::FilePermission;
+use crate::domain::entities::permission_event::{self, PathCoverage, PermissionEvent};
+use crate::domain::value_objects::UserId;
+
+#[derive(Debug, Serialize)]
+pub struct HistoryHolder {
+    #[serde(flatten)]
+    pub permission: FilePermission,
+    pub coverage: PathCoverage,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PermissionHistory {
+    pub path: String,
+    pub at: DateTime<Utc>,
+    /// Permissions that reached the folder at `at`
+    pub holders: Vec<HistoryHolder>,
+    /// What happened to permissions on the folder up to `at`, oldest first
+    pub events: Vec<PermissionEvent>,
+}
+
+/// Who could access `path` in the owner's vault at `at`, rebuilt from the recorded grants,
+/// revocations and expiry changes. Co-owners only see what was granted inside their subtrees.
+pub async fn execute<R: FilePermissionRepository + ?Sized>(
+    repo: &R,
+    owner_id: &UserId,
+    path: &str,
+    at: DateTime<Utc>,
+    scope: &OwnerScope,
+) -> Result<PermissionHistory, String> {
+    let path = path.trim().trim_matches('/');
+    if path.split('/').any(|part| part == "..") {
+        return Err("Invalid path: must be a relative path without '..'".to_string());
+    }
+    if !scope.covers(path) {
+        return Err("No delegated authority over this path".to_string());
+    }
+
+    let events = repo.events_for_owner(owner_id).await?;
+    let (in_force, timeline) = permission_event::replay(&events, at);
+    let holders = in_force
+        .into_iter()
+        .filter(|p| scope.covers(&p.path))
+        .filter_map(|permission| {
+            permission_event::coverage(&permission.path, path).map(|coverage| HistoryHolder { permission, coverage })
+        })
+        .collect();
+    let events = timeline
+        .into_iter()
+        .filter(|e| scope.covers(&e.path) && permission_event::coverage(&e.path, path).is_some())
+        .collect();
+    Ok(PermissionHistory { path: path.to_string(), at, holders, events })
+}
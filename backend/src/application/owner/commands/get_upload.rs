@@ -0,0 +1,17 @@
+use crate::application::ports::UploadSessionRepository;
+use crate::domain::entities::upload_session::UploadSession;
+use crate::domain::value_objects::UserId;
+use uuid::Uuid;
+
+/// An upload is visible to the vault owner and to whoever started it.
+pub async fn execute<U: UploadSessionRepository + ?Sized>(
+    uploads: &U,
+    acting_id: &UserId,
+    upload_id: &Uuid,
+) -> Result<UploadSession, String> {
+    uploads
+        .find_by_id(upload_id)
+        .await?
+        .filter(|upload| &upload.owner_id == acting_id || &upload.created_by == acting_id)
+        .ok_or_else(|| "Upload not found".to_string())
+}
@@ -0,0 +1,22 @@
+use crate::application::ports::AppSettingRepository;
+use crate::domain::entities::app_setting::{normalize_app_id, AppSetting};
+use crate::domain::value_objects::UserId;
+
+/// The owner's setting for each of `app_ids`, defaults included for apps never configured.
+pub async fn execute<R: AppSettingRepository + ?Sized>(
+    repo: &R,
+    owner_id: &UserId,
+    app_ids: &[&str],
+) -> Result<Vec<AppSetting>, String> {
+    let stored = repo.list_for_owner(owner_id).await?;
+    app_ids
+        .iter()
+        .map(|app_id| {
+            let app_id = normalize_app_id(app_id)?;
+            match stored.iter().find(|setting| setting.app_id == app_id) {
+                Some(setting) => Ok(setting.clone()),
+                None => AppSetting::new(owner_id.clone(), &app_id),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,17 @@
+use crate::application::ports::audit_repository::{AuditFilter, AuditRepository};
+use crate::application::ports::pagination::{Page, PageRequest};
+use crate::domain::entities::audit_event::AuditEvent;
+
+/// The owner's audit trail: actions taken in their vault and on their clients.
+pub async fn execute<A: AuditRepository + ?Sized>(
+    audit: &A,
+    filter: &AuditFilter,
+    page: &PageRequest,
+) -> Result<Page<AuditEvent>, String> {
+    if let (Some(since), Some(until)) = (filter.since, filter.until) {
+        if since >= until {
+            return Err("'since' must be before 'until'".to_string());
+        }
+    }
+    audit.list(filter, page).await
+}
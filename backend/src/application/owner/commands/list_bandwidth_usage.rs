@@ -0,0 +1,22 @@
+use crate::application::ports::{BandwidthRepository, BandwidthUsage};
+use crate::application::sessions::bandwidth::month_of;
+use crate::domain::value_objects::UserId;
+
+/// What each client streamed from the owner's vault in `month` (`YYYY-MM`, the current month
+/// by default), against the caps the owner set.
+pub async fn execute<B: BandwidthRepository + ?Sized>(
+    bandwidth: &B,
+    owner_id: &UserId,
+    month: Option<&str>,
+) -> Result<(String, Vec<BandwidthUsage>), String> {
+    let month = match month {
+        Some(month) => {
+            chrono::NaiveDate::parse_from_str(&format!("{month}-01"), "%Y-%m-%d")
+                .map_err(|_| format!("Invalid month {month:?}, expected YYYY-MM"))?;
+            month.to_string()
+        }
+        None => month_of(chrono::Utc::now()),
+    };
+    let usage = bandwidth.list_usage(owner_id, &month).await?;
+    Ok((month, usage))
+}
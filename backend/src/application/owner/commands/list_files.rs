@@ -0,0 +1,57 @@
+use futures_util::future::join_all;
+use crate::application::owner::scope::OwnerScope;
+use crate::application::ports::{Page, PageRequest, VaultEntry, VaultStorage};
+use crate::domain::services::permission_evaluator::Operation;
+use crate::domain::value_objects::UserId;
+
+/// List a vault folder. With `include_sizes`, subfolders carry the size of their contents,
+/// measured concurrently and cached by the storage; a folder that cannot be measured, e.g.
+/// because it was removed meanwhile, keeps no size.
+pub async fn execute<S>(
+    storage: &S,
+    owner_id: &UserId,
+    path: &str,
+    include_sizes: bool,
+    scope: &OwnerScope,
+) -> Result<Vec<VaultEntry>, String>
+where
+    S: VaultStorage + ?Sized,
+{
+    scope.evaluator(Vec::new()).check(path, Operation::Browse, chrono::Utc::now())?;
+    let mut entries = storage.list(owner_id, path).await?;
+    if include_sizes {
+        measure_folders(storage, owner_id, &mut entries).await;
+    }
+    Ok(entries)
+}
+
+/// Like [`execute`], one page at a time, for folders too large to list whole. Only the
+/// page's subfolders are measured.
+pub async fn page<S>(
+    storage: &S,
+    owner_id: &UserId,
+    path: &str,
+    include_sizes: bool,
+    scope: &OwnerScope,
+    request: &PageRequest,
+) -> Result<Page<VaultEntry>, String>
+where
+    S: VaultStorage + ?Sized,
+{
+    scope.evaluator(Vec::new()).check(path, Operation::Browse, chrono::Utc::now())?;
+    let mut listed = storage.list_page(owner_id, path, request).await?;
+    if include_sizes {
+        measure_folders(storage, owner_id, &mut listed.items).await;
+    }
+    Ok(listed)
+}
+
+async fn measure_folders<S>(storage: &S, owner_id: &UserId, entries: &mut [VaultEntry])
+where
+    S: VaultStorage + ?Sized,
+{
+    let sizes = join_all(entries.iter().filter(|e| e.is_dir).map(|e| storage.folder_size(owner_id, &e.path))).await;
+    for (entry, size) in entries.iter_mut().filter(|e| e.is_dir).zip(sizes) {
+        entry.size = size.ok();
+    }
+}
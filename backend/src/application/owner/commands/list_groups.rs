@@ -0,0 +1,10 @@
+use crate::application::ports::ClientGroupRepository;
+use crate::domain::entities::client_group::ClientGroup;
+use crate::domain::value_objects::UserId;
+
+pub async fn execute<G: ClientGroupRepository + ?Sized>(
+    groups: &G,
+    owner_id: &UserId,
+) -> Result<Vec<ClientGroup>, String> {
+    groups.find_by_owner(owner_id).await
+}
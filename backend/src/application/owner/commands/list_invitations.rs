@@ -0,0 +1,11 @@
+use crate::application::ports::invitation_repository::{InvitationFilter, InvitationRepository};
+use crate::application::ports::pagination::{Page, PageRequest};
+use crate::domain::entities::invitation::Invitation;
+
+pub async fn execute<R: InvitationRepository + ?Sized>(
+    repo: &R,
+    filter: &InvitationFilter,
+    page: &PageRequest,
+) -> Result<Page<Invitation>, String> {
+    repo.list(filter, page).await
+}
@@ -0,0 +1,14 @@
+use crate::application::owner::scope::OwnerScope;
+use crate::application::ports::LegalHoldRepository;
+use crate::domain::entities::legal_hold::LegalHold;
+use crate::domain::value_objects::UserId;
+
+/// The vault's holds, newest first. Co-owners only see those inside their subtrees.
+pub async fn execute<H: LegalHoldRepository + ?Sized>(
+    holds: &H,
+    owner_id: &UserId,
+    scope: &OwnerScope,
+) -> Result<Vec<LegalHold>, String> {
+    let holds = holds.list_for_owner(owner_id).await?;
+    Ok(holds.into_iter().filter(|hold| scope.covers(&hold.path)).collect())
+}
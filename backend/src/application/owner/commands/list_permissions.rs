@@ -1,14 +1,20 @@
-use crate::application::ports::file_permission_repository::FilePermissionRepository;
+use crate::application::ports::file_permission_repository::{FilePermissionRepository, PermissionFilter};
+use crate::application::ports::pagination::{Page, PageRequest};
+use crate::application::owner::scope::OwnerScope;
+use crate::domain::entities::file_permission::FilePermission;
 use crate::domain::value_objects::UserId;
 
 pub async fn execute<R: FilePermissionRepository + ?Sized>(
     repo: &R,
     owner_id: &UserId,
-    client_id: Option<&UserId>,
-) -> Result<Vec<crate::domain::entities::file_permission::FilePermission>, String> {
-    if let Some(cid) = client_id {
-        repo.find_by_owner_client(owner_id, cid).await
-    } else {
-        repo.find_active_by_owner(owner_id).await
+    mut filter: PermissionFilter,
+    page: &PageRequest,
+    scope: &OwnerScope,
+) -> Result<Page<FilePermission>, String> {
+    filter.owner_id = Some(owner_id.clone());
+    // Co-owners only see what was granted inside their subtrees
+    if let OwnerScope::Subtrees(delegations) = scope {
+        filter.subtrees = Some(delegations.iter().map(|d| d.path.clone()).collect());
     }
+    repo.list(&filter, page).await
 }
@@ -0,0 +1,15 @@
+use crate::application::ports::pagination::{Page, PageRequest};
+use crate::application::ports::session_repository::{SessionFilter, SessionRepository};
+use crate::domain::entities::session::Session;
+use crate::domain::value_objects::UserId;
+
+/// Sessions clients ran inside the owner's vault.
+pub async fn execute<R: SessionRepository + ?Sized>(
+    sessions: &R,
+    owner_id: &UserId,
+    mut filter: SessionFilter,
+    page: &PageRequest,
+) -> Result<Page<Session>, String> {
+    filter.acting_as_owner_id = Some(owner_id.clone());
+    sessions.list(&filter, page).await
+}
@@ -0,0 +1,42 @@
+use chrono::{DateTime, Utc};
+use crate::application::owner::scope::OwnerScope;
+use crate::application::ports::{AuditRepository, LegalHoldRepository};
+use crate::domain::entities::audit_event::AuditEvent;
+use crate::domain::entities::legal_hold::LegalHold;
+use crate::domain::value_objects::UserId;
+
+pub struct PlaceLegalHoldCommand {
+    pub owner_id: UserId,
+    pub path: String,
+    pub reason: String,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Protect a path of the owner's vault from deletion and change until the hold expires, or
+/// until released. Co-owners may place holds within their subtrees.
+pub async fn execute<H, A>(
+    holds: &H,
+    audit: &A,
+    acting_id: &UserId,
+    cmd: PlaceLegalHoldCommand,
+    scope: &OwnerScope,
+) -> Result<LegalHold, String>
+where
+    H: LegalHoldRepository + ?Sized,
+    A: AuditRepository + ?Sized,
+{
+    let hold = LegalHold::new(cmd.owner_id, &cmd.path, &cmd.reason, acting_id.clone(), cmd.expires_at)?;
+    if !scope.covers(&hold.path) {
+        return Err(format!("Path outside your delegated area: {}", hold.path));
+    }
+    holds.save(&hold).await?;
+
+    let mut event = AuditEvent::new(
+        "legal_hold_placed",
+        serde_json::json!({ "hold_id": hold.id, "path": hold.path, "reason": hold.reason, "expires_at": hold.expires_at }),
+    );
+    event.owner_id = Some(hold.owner_id.clone());
+    event.user_id = Some(acting_id.clone());
+    audit.record(&event).await?;
+    Ok(hold)
+}
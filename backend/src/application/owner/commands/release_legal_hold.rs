@@ -0,0 +1,35 @@
+use uuid::Uuid;
+use crate::application::owner::scope;
+use crate::application::ports::{AuditRepository, DelegationRepository, LegalHoldRepository};
+use crate::domain::entities::audit_event::AuditEvent;
+use crate::domain::value_objects::UserId;
+
+/// Lift a hold of the acting owner's vault, or one inside a subtree delegated to them.
+pub async fn execute<H, D, A>(
+    holds: &H,
+    delegations: &D,
+    audit: &A,
+    acting_id: &UserId,
+    hold_id: &Uuid,
+) -> Result<(), String>
+where
+    H: LegalHoldRepository + ?Sized,
+    D: DelegationRepository + ?Sized,
+    A: AuditRepository + ?Sized,
+{
+    let hold = holds.find_by_id(hold_id).await?.ok_or("Legal hold not found")?;
+    let allowed = scope::resolve(delegations, acting_id, &hold.owner_id)
+        .await
+        .is_ok_and(|s| s.covers(&hold.path));
+    if !allowed {
+        return Err("Legal hold not found".to_string());
+    }
+    if !holds.release(hold_id, chrono::Utc::now()).await? {
+        return Err("Legal hold was already released".to_string());
+    }
+
+    let mut event = AuditEvent::new("legal_hold_released", serde_json::json!({ "hold_id": hold.id, "path": hold.path }));
+    event.owner_id = Some(hold.owner_id.clone());
+    event.user_id = Some(acting_id.clone());
+    audit.record(&event).await
+}
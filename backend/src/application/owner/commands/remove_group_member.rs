@@ -0,0 +1,40 @@
+use uuid::Uuid;
+use crate::application::ports::{AuditRepository, ClientGroupRepository, FilePermissionRepository};
+use crate::domain::entities::audit_event::AuditEvent;
+use crate::domain::entities::client_group::ClientGroup;
+use crate::domain::value_objects::UserId;
+
+/// Remove a client from a group, revoking the permissions they derived from it.
+/// Permissions granted to the client directly are kept.
+pub async fn execute<G, P, A>(
+    groups: &G,
+    permissions: &P,
+    audit: &A,
+    owner_id: &UserId,
+    group_id: &Uuid,
+    client_id: &UserId,
+) -> Result<ClientGroup, String>
+where
+    G: ClientGroupRepository + ?Sized,
+    P: FilePermissionRepository + ?Sized,
+    A: AuditRepository + ?Sized,
+{
+    let mut group = groups
+        .find_by_id(group_id)
+        .await?
+        .filter(|g| &g.owner_id == owner_id)
+        .ok_or("Group not found")?;
+    if !group.has_member(client_id) {
+        return Err("Client is not a member of this group".to_string());
+    }
+
+    groups.remove_member(&group.id, client_id).await?;
+    permissions.revoke_for_group(&group.id, Some(client_id)).await?;
+    group.members.retain(|m| m != client_id);
+
+    let mut event = AuditEvent::new("group_member_removed", serde_json::json!({ "group_id": group.id }));
+    event.user_id = Some(client_id.clone());
+    event.owner_id = Some(owner_id.clone());
+    audit.record(&event).await?;
+    Ok(group)
+}
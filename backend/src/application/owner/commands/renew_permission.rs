@@ -0,0 +1,61 @@
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+use crate::application::owner::scope;
+use crate::application::ports::{AuditRepository, DelegationRepository, FilePermissionRepository, NotificationRepository};
+use crate::domain::entities::audit_event::AuditEvent;
+use crate::domain::entities::file_permission::FilePermission;
+use crate::domain::entities::notification::Notification;
+use crate::domain::value_objects::UserId;
+
+/// Extend one of the owner's expiring permissions by `extend_hours`, or by its original
+/// grant length. Co-owners may renew within their subtrees. The renewal is audited and the
+/// client is told.
+pub async fn execute<P, D, N, A>(
+    permissions: &P,
+    delegations: &D,
+    notifications: &N,
+    audit: &A,
+    acting_id: &UserId,
+    permission_id: &Uuid,
+    extend_hours: Option<i64>,
+) -> Result<FilePermission, String>
+where
+    P: FilePermissionRepository + ?Sized,
+    D: DelegationRepository + ?Sized,
+    N: NotificationRepository + ?Sized,
+    A: AuditRepository + ?Sized,
+{
+    let mut permission = permissions.find_by_id(permission_id).await?.ok_or("Permission not found")?;
+    let allowed = scope::resolve(delegations, acting_id, &permission.owner_id)
+        .await
+        .is_ok_and(|s| s.covers(&permission.path));
+    if !allowed {
+        return Err("Permission not found".to_string());
+    }
+
+    let previous = permission.expires_at;
+    let extension = match extend_hours {
+        Some(hours) => Some(Duration::try_hours(hours).ok_or("Renewal is too long")?),
+        None => None,
+    };
+    let expires_at = permission.renewed_until(extension, Utc::now())?;
+    permissions.update_expiry(&permission.id, expires_at).await?;
+    permission.expires_at = Some(expires_at);
+
+    let payload = serde_json::json!({
+        "permission_id": permission.id,
+        "path": permission.path,
+        "previous_expires_at": previous.map(|e| e.to_rfc3339()),
+        "expires_at": expires_at.to_rfc3339(),
+        "renewed_by": acting_id,
+    });
+    let mut event = AuditEvent::new("permission_renewed", payload.clone());
+    event.user_id = Some(permission.client_id.clone());
+    event.owner_id = Some(permission.owner_id.clone());
+    audit.record(&event).await?;
+
+    notifications
+        .create(&Notification::new(permission.client_id.clone(), "permission_renewed", payload))
+        .await?;
+    Ok(permission)
+}
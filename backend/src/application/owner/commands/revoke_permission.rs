@@ -1,9 +1,28 @@
-use crate::application::ports::file_permission_repository::FilePermissionRepository;
+use crate::application::owner::scope;
+use crate::application::ports::{DelegationRepository, FilePermissionRepository};
+use crate::domain::entities::file_permission::FilePermission;
+use crate::domain::value_objects::UserId;
 use uuid::Uuid;
 
-pub async fn execute<R: FilePermissionRepository + ?Sized>(
+/// Revoke a permission of the acting owner's vault, or one inside a subtree delegated to them.
+/// Returns the permission as it was, for its client.
+pub async fn execute<R, D>(
     repo: &R,
+    delegations: &D,
+    acting_id: &UserId,
     permission_id: &Uuid,
-) -> Result<(), String> {
-    repo.revoke(permission_id).await
+) -> Result<FilePermission, String>
+where
+    R: FilePermissionRepository + ?Sized,
+    D: DelegationRepository + ?Sized,
+{
+    let permission = repo.find_by_id(permission_id).await?.ok_or("Permission not found")?;
+    let allowed = scope::resolve(delegations, acting_id, &permission.owner_id)
+        .await
+        .is_ok_and(|s| s.covers(&permission.path));
+    if !allowed {
+        return Err("Permission not found".to_string());
+    }
+    repo.revoke(permission_id).await?;
+    Ok(permission)
 }
@@ -0,0 +1,36 @@
+use crate::application::ports::{AuditRepository, BandwidthRepository, FilePermissionRepository};
+use crate::domain::entities::audit_event::AuditEvent;
+use crate::domain::value_objects::{BandwidthCaps, UserId};
+
+/// Cap one of the owner's clients' bandwidth in their vault, or lift the caps with `None`.
+/// Running sessions pick the new caps up at their next usage check.
+pub async fn execute<B, P, A>(
+    bandwidth: &B,
+    permissions: &P,
+    audit: &A,
+    owner_id: &UserId,
+    client_id: &UserId,
+    caps: Option<BandwidthCaps>,
+) -> Result<(), String>
+where
+    B: BandwidthRepository + ?Sized,
+    P: FilePermissionRepository + ?Sized,
+    A: AuditRepository + ?Sized,
+{
+    if permissions.find_by_owner_client(owner_id, client_id).await?.is_empty() {
+        return Err("Not one of your clients".to_string());
+    }
+
+    match &caps {
+        Some(caps) => {
+            caps.validate()?;
+            bandwidth.save_caps(owner_id, client_id, caps).await?;
+        }
+        None => bandwidth.delete_caps(owner_id, client_id).await?,
+    }
+
+    let mut event = AuditEvent::new("bandwidth_caps_updated", serde_json::json!({ "caps": caps }));
+    event.user_id = Some(client_id.clone());
+    event.owner_id = Some(owner_id.clone());
+    audit.record(&event).await
+}
@@ -0,0 +1,53 @@
+use crate::application::owner::scope::OwnerScope;
+use crate::application::ports::{AuditRepository, FileJobRepository, LegalHoldRepository};
+use crate::domain::entities::audit_event::AuditEvent;
+use crate::domain::entities::file_job::{FileJob, FileOperation};
+use crate::domain::services::permission_evaluator::DenialCode;
+use crate::domain::value_objects::UserId;
+
+/// Queue a batch of operations on `owner_id`'s vault for the background worker. A batch that
+/// would delete or move anything under legal hold is refused whole, and the attempt audited.
+pub async fn execute<J, H, A>(
+    jobs: &J,
+    holds: &H,
+    audit: &A,
+    acting_id: &UserId,
+    owner_id: UserId,
+    operations: Vec<FileOperation>,
+    scope: &OwnerScope,
+) -> Result<FileJob, String>
+where
+    J: FileJobRepository + ?Sized,
+    H: LegalHoldRepository + ?Sized,
+    A: AuditRepository + ?Sized,
+{
+    let evaluator = scope.evaluator(holds.list_for_owner(&owner_id).await?);
+    let now = chrono::Utc::now();
+    if let Some(denial) = operations
+        .iter()
+        .flat_map(FileOperation::accesses)
+        .find_map(|(path, operation)| evaluator.check(path, operation, now).err())
+    {
+        if denial.code == DenialCode::LegalHold {
+            let mut event = AuditEvent::new(
+                "legal_hold_blocked",
+                serde_json::json!({ "hold_id": denial.hold_id, "path": denial.path }),
+            );
+            event.owner_id = Some(owner_id.clone());
+            event.user_id = Some(acting_id.clone());
+            audit.record(&event).await?;
+        }
+        return Err(denial.into());
+    }
+    let job = FileJob::new(owner_id, acting_id.clone(), operations)?;
+    jobs.save(&job).await?;
+
+    let mut event = AuditEvent::new(
+        "file_job_submitted",
+        serde_json::json!({ "job_id": job.id, "operations": job.total }),
+    );
+    event.owner_id = Some(job.owner_id.clone());
+    event.user_id = Some(acting_id.clone());
+    audit.record(&event).await?;
+    Ok(job)
+}
@@ -0,0 +1,29 @@
+use crate::application::ports::{AuditRepository, FilePermissionRepository, LoginAttemptRepository};
+use crate::application::super_admin::commands::login_throttle::user_subject;
+use crate::domain::entities::audit_event::AuditEvent;
+use crate::domain::value_objects::UserId;
+
+/// Lift a login lockout on one of the owner's clients before it expires.
+pub async fn execute<L, P, A>(
+    attempts: &L,
+    permissions: &P,
+    audit: &A,
+    owner_id: &UserId,
+    client_id: &UserId,
+) -> Result<(), String>
+where
+    L: LoginAttemptRepository + ?Sized,
+    P: FilePermissionRepository + ?Sized,
+    A: AuditRepository + ?Sized,
+{
+    if permissions.find_by_owner_client(owner_id, client_id).await?.is_empty() {
+        return Err("Not one of your clients".to_string());
+    }
+
+    attempts.clear(&user_subject(client_id)).await?;
+
+    let mut event = AuditEvent::new("account_unlocked", serde_json::json!({}));
+    event.user_id = Some(client_id.clone());
+    event.owner_id = Some(owner_id.clone());
+    audit.record(&event).await
+}
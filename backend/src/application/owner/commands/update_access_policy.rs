@@ -0,0 +1,30 @@
+use crate::application::ports::{AccessPolicyRepository, AuditRepository};
+use crate::domain::entities::access_policy::AccessPolicy;
+use crate::domain::entities::audit_event::AuditEvent;
+
+pub async fn execute<R, A>(
+    repo: &R,
+    audit: &A,
+    mut policy: AccessPolicy,
+) -> Result<AccessPolicy, String>
+where
+    R: AccessPolicyRepository + ?Sized,
+    A: AuditRepository + ?Sized,
+{
+    policy.validate()?;
+    policy.updated_at = chrono::Utc::now();
+    repo.save(&policy).await?;
+
+    let mut event = AuditEvent::new(
+        "access_policy_updated",
+        serde_json::json!({
+            "allowed_cidrs": policy.allowed_ranges.iter().map(|r| r.to_string()).collect::<Vec<_>>(),
+            "denied_cidrs": policy.denied_ranges.iter().map(|r| r.to_string()).collect::<Vec<_>>(),
+            "allowed_countries": &policy.allowed_countries,
+            "denied_countries": &policy.denied_countries,
+        }),
+    );
+    event.owner_id = Some(policy.owner_id.clone());
+    audit.record(&event).await?;
+    Ok(policy)
+}
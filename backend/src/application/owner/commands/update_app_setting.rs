@@ -0,0 +1,31 @@
+use crate::application::ports::{AppSettingRepository, AuditRepository};
+use crate::domain::entities::app_setting::AppSetting;
+use crate::domain::entities::audit_event::AuditEvent;
+
+/// Store who may use an app in the owner's vault. Sessions already running are not affected.
+pub async fn execute<R, A>(
+    repo: &R,
+    audit: &A,
+    mut setting: AppSetting,
+) -> Result<AppSetting, String>
+where
+    R: AppSettingRepository + ?Sized,
+    A: AuditRepository + ?Sized,
+{
+    setting.validate()?;
+    setting.updated_at = chrono::Utc::now();
+    repo.save(&setting).await?;
+
+    let mut event = AuditEvent::new(
+        "app_setting_updated",
+        serde_json::json!({
+            "app_id": &setting.app_id,
+            "enabled": setting.enabled,
+            "allowed_roles": &setting.allowed_roles,
+            "allowed_users": &setting.allowed_users,
+        }),
+    );
+    event.owner_id = Some(setting.owner_id.clone());
+    audit.record(&event).await?;
+    Ok(setting)
+}
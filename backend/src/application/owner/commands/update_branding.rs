@@ -0,0 +1,35 @@
+use crate::application::ports::{AuditRepository, BrandingRepository};
+use crate::domain::entities::audit_event::AuditEvent;
+use crate::domain::entities::owner_branding::OwnerBranding;
+
+/// Store the owner's branding; with every field unset it goes back to the defaults.
+pub async fn execute<R, A>(
+    repo: &R,
+    audit: &A,
+    branding: OwnerBranding,
+) -> Result<Option<OwnerBranding>, String>
+where
+    R: BrandingRepository + ?Sized,
+    A: AuditRepository + ?Sized,
+{
+    branding.validate()?;
+    let saved = if branding.is_empty() {
+        repo.delete(&branding.owner_id).await?;
+        None
+    } else {
+        repo.save(&branding).await?;
+        Some(branding.clone())
+    };
+
+    let mut event = AuditEvent::new(
+        "branding_updated",
+        serde_json::json!({
+            "display_name": &branding.display_name,
+            "accent_color": &branding.accent_color,
+            "logo": branding.logo.is_some(),
+        }),
+    );
+    event.owner_id = Some(branding.owner_id.clone());
+    audit.record(&event).await?;
+    Ok(saved)
+}
@@ -0,0 +1,53 @@
+use uuid::Uuid;
+use crate::application::ports::{AuditRepository, ClientGroupRepository, FilePermissionRepository};
+use crate::domain::entities::audit_event::AuditEvent;
+use crate::domain::entities::client_group::ClientGroup;
+use crate::domain::entities::invitation::GrantedPath;
+use crate::domain::value_objects::UserId;
+
+/// Rename a group and replace its grants. Members' derived permissions are re-issued
+/// so they match the new grants.
+pub async fn execute<G, P, A>(
+    groups: &G,
+    permissions: &P,
+    audit: &A,
+    owner_id: &UserId,
+    group_id: &Uuid,
+    name: String,
+    grants: Vec<GrantedPath>,
+) -> Result<ClientGroup, String>
+where
+    G: ClientGroupRepository + ?Sized,
+    P: FilePermissionRepository + ?Sized,
+    A: AuditRepository + ?Sized,
+{
+    let mut group = groups
+        .find_by_id(group_id)
+        .await?
+        .filter(|g| &g.owner_id == owner_id)
+        .ok_or("Group not found")?;
+    let name = name.trim().to_string();
+    if name != group.name && groups.find_by_owner(owner_id).await?.iter().any(|g| g.name == name) {
+        return Err("A group with this name already exists".to_string());
+    }
+
+    group.name = name;
+    group.grants = grants;
+    group.validate()?;
+    groups.save(&group).await?;
+
+    permissions.revoke_for_group(&group.id, None).await?;
+    for member in &group.members {
+        for permission in group.permissions_for(member) {
+            permissions.save(&permission).await?;
+        }
+    }
+
+    let mut event = AuditEvent::new(
+        "group_updated",
+        serde_json::json!({ "group_id": group.id, "name": group.name, "grants": group.grants }),
+    );
+    event.owner_id = Some(owner_id.clone());
+    audit.record(&event).await?;
+    Ok(group)
+}
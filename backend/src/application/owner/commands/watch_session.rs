@@ -0,0 +1,55 @@
+use crate::application::ports::{AuditRepository, FilePermissionRepository, SessionRepository};
+use crate::domain::entities::audit_event::AuditEvent;
+use crate::domain::entities::session::Session;
+use crate::domain::value_objects::UserId;
+
+/// Authorize an owner to watch a client session read-only: the session must be active and
+/// the owner must have granted its user access. The start of the watch is audited.
+pub async fn execute<S, P, A>(
+    sessions: &S,
+    permissions: &P,
+    audit: &A,
+    owner_id: &UserId,
+    session_id: &uuid::Uuid,
+) -> Result<Session, String>
+where
+    S: SessionRepository + ?Sized,
+    P: FilePermissionRepository + ?Sized,
+    A: AuditRepository + ?Sized,
+{
+    let session = sessions
+        .find_by_id(session_id)
+        .await?
+        .filter(Session::is_active)
+        .ok_or_else(|| "Session not found or not active".to_string())?;
+
+    if !is_granted(permissions, owner_id, &session).await? {
+        return Err("Not a session of one of your clients".to_string());
+    }
+
+    audit.record(&watch_event("session_watch_started", owner_id, &session)).await?;
+    Ok(session)
+}
+
+/// Whether `owner_id` may watch `session`: they act as its user, or grant its user access.
+pub async fn is_granted<P: FilePermissionRepository + ?Sized>(permissions: &P, owner_id: &UserId, session: &Session) -> Result<bool, String> {
+    Ok(session.acting_as_owner_id.as_ref() == Some(owner_id)
+        || permissions
+            .find_by_owner_client(owner_id, &session.user_id)
+            .await?
+            .iter()
+            .any(|p| p.is_active()))
+}
+
+/// Audit the end of a watch.
+pub async fn finish<A: AuditRepository + ?Sized>(audit: &A, owner_id: &UserId, session: &Session) -> Result<(), String> {
+    audit.record(&watch_event("session_watch_ended", owner_id, session)).await
+}
+
+fn watch_event(event_type: &str, owner_id: &UserId, session: &Session) -> AuditEvent {
+    let mut event = AuditEvent::new(event_type, serde_json::json!({ "app_id": session.app_id }));
+    event.session_id = Some(session.id.to_string());
+    event.user_id = Some(session.user_id.clone());
+    event.owner_id = Some(owner_id.clone());
+    event
+}
@@ -2,4 +2,5 @@
 // Manages files, permissions, monitors client activity
 
 pub mod commands;
-pub mod queries;
\ No newline at end of file
+pub mod queries;
+pub mod scope;
@@ -0,0 +1,51 @@
+use crate::application::ports::DelegationRepository;
+use crate::domain::entities::legal_hold::LegalHold;
+use crate::domain::entities::owner_delegation::OwnerDelegation;
+use crate::domain::services::permission_evaluator::{Authority, PermissionEvaluator};
+use crate::domain::value_objects::UserId;
+
+/// What the acting user may manage in a vault: all of it when it is theirs, otherwise the
+/// subtrees a super-admin delegated to them.
+#[derive(Debug, Clone)]
+pub enum OwnerScope {
+    Full,
+    Subtrees(Vec<OwnerDelegation>),
+}
+
+impl OwnerScope {
+    pub fn covers(&self, path: &str) -> bool {
+        match self {
+            OwnerScope::Full => true,
+            OwnerScope::Subtrees(delegations) => delegations.iter().any(|d| d.covers(path)),
+        }
+    }
+
+    /// What the scope allows on the vault's files, given the vault's legal holds.
+    pub fn evaluator(&self, holds: Vec<LegalHold>) -> PermissionEvaluator {
+        let authority = match self {
+            OwnerScope::Full => Authority::Owner,
+            OwnerScope::Subtrees(delegations) => Authority::Delegate(delegations.clone()),
+        };
+        PermissionEvaluator::new(authority, holds)
+    }
+}
+
+pub async fn resolve<D: DelegationRepository + ?Sized>(
+    delegations: &D,
+    acting_id: &UserId,
+    vault_owner_id: &UserId,
+) -> Result<OwnerScope, String> {
+    if acting_id == vault_owner_id {
+        return Ok(OwnerScope::Full);
+    }
+    let subtrees: Vec<OwnerDelegation> = delegations
+        .find_active_for_delegate(acting_id)
+        .await?
+        .into_iter()
+        .filter(|d| &d.owner_id == vault_owner_id)
+        .collect();
+    if subtrees.is_empty() {
+        return Err("No delegated authority over this vault".to_string());
+    }
+    Ok(OwnerScope::Subtrees(subtrees))
+}
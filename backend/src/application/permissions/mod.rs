@@ -0,0 +1,2 @@
+// Permission lifecycle jobs run by the platform (expiry warnings)
+pub mod notify_expiring;
@@ -0,0 +1,83 @@
+use chrono::{Duration, Utc};
+use shared::i18n::tr;
+use crate::application::ports::{EmailSender, FilePermissionRepository, NotificationRepository};
+use crate::application::ports::user_repository::UserRepository;
+use crate::domain::entities::file_permission::FilePermission;
+use crate::domain::entities::notification::Notification;
+use crate::domain::User;
+
+/// Warn owner and client about every permission expiring within `notice`, once per expiry.
+/// Email is sent in addition to the in-app notification when a sender is configured.
+pub async fn execute(
+    permissions: &dyn FilePermissionRepository,
+    users: &dyn UserRepository,
+    notifications: &dyn NotificationRepository,
+    email: Option<&dyn EmailSender>,
+    notice: Duration,
+) -> Result<usize, String> {
+    let expiring = permissions.find_expiring(Utc::now() + notice).await?;
+    for permission in &expiring {
+        notify(permission, users, notifications, email).await?;
+        permissions.mark_expiry_notified(&permission.id).await?;
+    }
+    Ok(expiring.len())
+}
+
+async fn notify(
+    permission: &FilePermission,
+    users: &dyn UserRepository,
+    notifications: &dyn NotificationRepository,
+    email: Option<&dyn EmailSender>,
+) -> Result<(), String> {
+    let expires_at = permission.expires_at.map(|e| e.to_rfc3339()).unwrap_or_default();
+    let client = users.find_by_id(&permission.client_id).await?;
+    let client_email = client.as_ref().map(|c| c.email().as_str().to_string()).unwrap_or_default();
+
+    let payload = serde_json::json!({
+        "permission_id": permission.id,
+        "path": permission.path,
+        "expires_at": expires_at,
+    });
+    notifications
+        .create(&Notification::new(permission.client_id.clone(), "permission_expiring", payload.clone()))
+        .await?;
+
+    let mut owner_payload = payload;
+    owner_payload["client_id"] = serde_json::json!(permission.client_id.to_string());
+    owner_payload["email"] = serde_json::json!(client_email);
+    notifications
+        .create(&Notification::new(permission.owner_id.clone(), "client_permission_expiring", owner_payload))
+        .await?;
+
+    let Some(email) = email else {
+        return Ok(());
+    };
+    if let Some(client) = &client {
+        send(email, client, "email.permission_expiring", permission, &expires_at, &client_email).await;
+    }
+    if let Some(owner) = users.find_by_id(&permission.owner_id).await? {
+        send(email, &owner, "email.client_permission_expiring", permission, &expires_at, &client_email).await;
+    }
+    Ok(())
+}
+
+/// Delivery failures are logged only: the in-app notification already went out.
+async fn send(
+    email: &dyn EmailSender,
+    recipient: &User,
+    template: &str,
+    permission: &FilePermission,
+    expires_at: &str,
+    client_email: &str,
+) {
+    let locale = recipient.locale();
+    let (subject_key, body_key) = (format!("{}.subject", template), format!("{}.body", template));
+    let subject = tr(locale, &subject_key);
+    let body = tr(locale, &body_key)
+        .replace("{path}", &permission.path)
+        .replace("{expires_at}", expires_at)
+        .replace("{client}", client_email);
+    if let Err(e) = email.send(recipient.email().as_str(), subject, &body).await {
+        tracing::warn!("Expiry email for permission {} not sent: {}", permission.id, e);
+    }
+}
@@ -0,0 +1,10 @@
+use async_trait::async_trait;
+use crate::domain::entities::access_policy::AccessPolicy;
+use crate::domain::value_objects::UserId;
+
+#[async_trait]
+pub trait AccessPolicyRepository: Send + Sync {
+    async fn save(&self, policy: &AccessPolicy) -> Result<(), String>;
+    async fn find(&self, owner_id: &UserId) -> Result<Option<AccessPolicy>, String>;
+    async fn find_all(&self) -> Result<Vec<AccessPolicy>, String>;
+}
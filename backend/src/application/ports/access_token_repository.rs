@@ -0,0 +1,16 @@
+use async_trait::async_trait;
+use crate::domain::entities::access_token::AccessToken;
+use crate::domain::value_objects::UserId;
+
+#[async_trait]
+pub trait AccessTokenRepository: Send + Sync {
+    async fn save(&self, token: &AccessToken) -> Result<(), String>;
+    async fn find_by_hash(&self, token_hash: &str) -> Result<Option<AccessToken>, String>;
+    /// The user's tokens, newest first, revoked and expired ones included
+    async fn list_for_user(&self, user_id: &UserId) -> Result<Vec<AccessToken>, String>;
+    /// Revoke one of the user's tokens; `false` when they have no such active token.
+    async fn revoke(&self, user_id: &UserId, id: &uuid::Uuid) -> Result<bool, String>;
+    /// Revoke all of the user's active tokens; returns how many there were.
+    async fn revoke_all(&self, user_id: &UserId) -> Result<usize, String>;
+    async fn record_use(&self, id: &uuid::Uuid, at: chrono::DateTime<chrono::Utc>) -> Result<(), String>;
+}
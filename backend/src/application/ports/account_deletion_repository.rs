@@ -0,0 +1,20 @@
+// Driven port - Account deletion repository (output port)
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use crate::domain::entities::account_deletion::AccountDeletion;
+use crate::domain::value_objects::UserId;
+
+#[async_trait]
+pub trait AccountDeletionRepository: Send + Sync {
+    async fn save(&self, deletion: &AccountDeletion) -> Result<(), String>;
+    async fn find(&self, user_id: &UserId) -> Result<Option<AccountDeletion>, String>;
+    /// Drop a pending deletion, e.g. when a super-admin restores the account.
+    async fn delete(&self, user_id: &UserId) -> Result<(), String>;
+    /// Unpurged deletions whose grace period ended before `now`, oldest first.
+    async fn find_due(&self, now: DateTime<Utc>) -> Result<Vec<AccountDeletion>, String>;
+    /// In one transaction: delete the user's credentials, preferences, sessions, permissions
+    /// and other records, anonymize the user row, scrub their id and `email` from audit
+    /// entries, and mark the deletion purged.
+    async fn purge(&self, user_id: &UserId, email: &str, at: DateTime<Utc>) -> Result<(), String>;
+}
@@ -0,0 +1,12 @@
+// Driven port - App crash report repository (output port)
+
+use async_trait::async_trait;
+use crate::domain::entities::app_crash::AppCrash;
+use super::pagination::{Page, PageRequest};
+
+#[async_trait]
+pub trait AppCrashRepository: Send + Sync {
+    async fn record(&self, crash: &AppCrash) -> Result<(), String>;
+    /// Ordered by `crashed_at`, optionally only one app's crashes.
+    async fn list(&self, app_id: Option<&str>, page: &PageRequest) -> Result<Page<AppCrash>, String>;
+}
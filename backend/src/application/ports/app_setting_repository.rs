@@ -0,0 +1,10 @@
+use async_trait::async_trait;
+use crate::domain::entities::app_setting::AppSetting;
+use crate::domain::value_objects::UserId;
+
+#[async_trait]
+pub trait AppSettingRepository: Send + Sync {
+    async fn save(&self, setting: &AppSetting) -> Result<(), String>;
+    async fn find(&self, owner_id: &UserId, app_id: &str) -> Result<Option<AppSetting>, String>;
+    async fn list_for_owner(&self, owner_id: &UserId) -> Result<Vec<AppSetting>, String>;
+}
@@ -0,0 +1,17 @@
+// Driven port - Per-user app state repository (output port)
+
+use async_trait::async_trait;
+use crate::domain::value_objects::UserId;
+
+/// Values are JSON text, stored as the app sent them
+#[async_trait]
+pub trait AppStateRepository: Send + Sync {
+    /// Every entry the user has for the app, as key and value
+    async fn find_all(&self, user_id: &UserId, app_id: &str) -> Result<Vec<(String, String)>, String>;
+    async fn find(&self, user_id: &UserId, app_id: &str, key: &str) -> Result<Option<String>, String>;
+    /// Insert or replace the entry under `key`
+    async fn save(&self, user_id: &UserId, app_id: &str, key: &str, value: &str) -> Result<(), String>;
+    async fn delete(&self, user_id: &UserId, app_id: &str, key: &str) -> Result<(), String>;
+    /// Bytes of keys and values of the user's entries for the app, leaving out `key`
+    async fn size_without(&self, user_id: &UserId, app_id: &str, key: &str) -> Result<usize, String>;
+}
@@ -0,0 +1,23 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use crate::domain::entities::audit_event::AuditEvent;
+use crate::domain::value_objects::UserId;
+use super::pagination::{Page, PageRequest};
+
+/// Criteria for an audit listing; unset fields do not filter.
+#[derive(Debug, Clone, Default)]
+pub struct AuditFilter {
+    /// The vault the events happened in; owner-facing listings always set it
+    pub owner_id: Option<UserId>,
+    pub user_id: Option<UserId>,
+    pub event_type: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+#[async_trait]
+pub trait AuditRepository: Send + Sync {
+    async fn record(&self, event: &AuditEvent) -> Result<(), String>;
+    /// Ordered by `created_at`.
+    async fn list(&self, filter: &AuditFilter, page: &PageRequest) -> Result<Page<AuditEvent>, String>;
+}
@@ -0,0 +1,19 @@
+// Driven port - Auth session repository (output port)
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use crate::domain::entities::auth_session::AuthSession;
+use crate::domain::value_objects::UserId;
+
+#[async_trait]
+pub trait AuthSessionRepository: Send + Sync {
+    async fn create(&self, session: &AuthSession) -> Result<(), String>;
+    async fn touch(&self, id: &Uuid, at: DateTime<Utc>) -> Result<(), String>;
+    /// The user's sessions that are neither expired nor revoked, most recently seen first.
+    async fn list_active(&self, user_id: &UserId) -> Result<Vec<AuthSession>, String>;
+    /// Revoke one of the user's sessions; false when it is not theirs or already revoked.
+    async fn revoke(&self, user_id: &UserId, id: &Uuid, at: DateTime<Utc>) -> Result<bool, String>;
+    /// Ids of revoked sessions whose tokens have not expired yet
+    async fn revoked_unexpired(&self, now: DateTime<Utc>) -> Result<Vec<Uuid>, String>;
+}
@@ -0,0 +1,28 @@
+// Driven port - Bandwidth caps and usage repository (output port)
+
+use async_trait::async_trait;
+use serde::Serialize;
+use crate::domain::value_objects::{BandwidthCaps, UserId};
+
+/// One client's use of an owner's vault in a month, for the owner's overview
+#[derive(Debug, Clone, Serialize)]
+pub struct BandwidthUsage {
+    pub user_id: UserId,
+    pub bytes_sent: u64,
+    pub caps: BandwidthCaps,
+    pub over_cap: bool,
+}
+
+/// Months are `YYYY-MM`, in UTC
+#[async_trait]
+pub trait BandwidthRepository: Send + Sync {
+    async fn find_caps(&self, owner_id: &UserId, user_id: &UserId) -> Result<Option<BandwidthCaps>, String>;
+    /// Insert or replace the client's caps
+    async fn save_caps(&self, owner_id: &UserId, user_id: &UserId, caps: &BandwidthCaps) -> Result<(), String>;
+    async fn delete_caps(&self, owner_id: &UserId, user_id: &UserId) -> Result<(), String>;
+    /// Add `bytes` to the client's total for `month`
+    async fn add_usage(&self, owner_id: &UserId, user_id: &UserId, month: &str, bytes: u64) -> Result<(), String>;
+    async fn usage(&self, owner_id: &UserId, user_id: &UserId, month: &str) -> Result<u64, String>;
+    /// Clients with caps or usage in the owner's vault, heaviest users first
+    async fn list_usage(&self, owner_id: &UserId, month: &str) -> Result<Vec<BandwidthUsage>, String>;
+}
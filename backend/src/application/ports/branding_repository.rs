@@ -0,0 +1,10 @@
+use async_trait::async_trait;
+use crate::domain::entities::owner_branding::OwnerBranding;
+use crate::domain::value_objects::UserId;
+
+#[async_trait]
+pub trait BrandingRepository: Send + Sync {
+    async fn save(&self, branding: &OwnerBranding) -> Result<(), String>;
+    async fn find(&self, owner_id: &UserId) -> Result<Option<OwnerBranding>, String>;
+    async fn delete(&self, owner_id: &UserId) -> Result<(), String>;
+}
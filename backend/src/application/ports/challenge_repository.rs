@@ -8,4 +8,17 @@ pub trait ChallengeRepository: Send + Sync {
     async fn get_and_delete_registration_challenge(&self, challenge_id: &str) -> Result<String, String>;
     async fn save_auth_challenge(&self, challenge_id: &str, state: &str, ttl_seconds: u64) -> Result<(), String>;
     async fn get_and_delete_auth_challenge(&self, challenge_id: &str) -> Result<String, String>;
+    /// Username-less (conditional UI) login challenges: the user is only known once the
+    /// browser answers with a credential, so these are kept apart from per-user ones.
+    async fn save_discoverable_challenge(&self, challenge_id: &str, state: &str, ttl_seconds: u64) -> Result<(), String>;
+    async fn get_and_delete_discoverable_challenge(&self, challenge_id: &str) -> Result<String, String>;
+    /// One-time token that `sandbox-server admin create-super-admin` hands out in its setup
+    /// link. While one is pending, initial setup only proceeds with it.
+    async fn save_setup_token(&self, token: &str, ttl_seconds: u64) -> Result<(), String>;
+    async fn setup_token(&self) -> Result<Option<String>, String>;
+    async fn delete_setup_token(&self) -> Result<(), String>;
+    /// An OpenID Connect login in progress, keyed by its `state` parameter, and the steps
+    /// after the provider's callback, keyed by a one-time ticket.
+    async fn save_oidc_login(&self, key: &str, state: &str, ttl_seconds: u64) -> Result<(), String>;
+    async fn get_and_delete_oidc_login(&self, key: &str) -> Result<String, String>;
 }
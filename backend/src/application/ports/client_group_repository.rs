@@ -0,0 +1,16 @@
+// Driven port - Client group repository (output port)
+
+use async_trait::async_trait;
+use crate::domain::entities::client_group::ClientGroup;
+use crate::domain::value_objects::UserId;
+
+#[async_trait]
+pub trait ClientGroupRepository: Send + Sync {
+    /// Insert or update the group's name and grants; members are managed separately.
+    async fn save(&self, group: &ClientGroup) -> Result<(), String>;
+    async fn find_by_id(&self, id: &uuid::Uuid) -> Result<Option<ClientGroup>, String>;
+    async fn find_by_owner(&self, owner_id: &UserId) -> Result<Vec<ClientGroup>, String>;
+    async fn delete(&self, id: &uuid::Uuid) -> Result<(), String>;
+    async fn add_member(&self, group_id: &uuid::Uuid, client_id: &UserId) -> Result<(), String>;
+    async fn remove_member(&self, group_id: &uuid::Uuid, client_id: &UserId) -> Result<(), String>;
+}
@@ -6,5 +6,7 @@ use crate::domain::{Credential, UserId};
 #[async_trait]
 pub trait CredentialRepository: Send + Sync {
     async fn find_by_user_id(&self, user_id: &UserId) -> Result<Vec<Credential>, String>;
+    /// The credential with this raw id, whichever user registered it
+    async fn find_by_credential_id(&self, credential_id: &[u8]) -> Result<Option<Credential>, String>;
     async fn save(&self, credential: &Credential) -> Result<(), String>;
 }
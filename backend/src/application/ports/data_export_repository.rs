@@ -0,0 +1,22 @@
+// Driven port - Data export repository (output port)
+
+use async_trait::async_trait;
+use crate::domain::entities::data_export::DataExport;
+use crate::domain::entities::file_job::JobStatus;
+
+#[async_trait]
+pub trait DataExportRepository: Send + Sync {
+    async fn save(&self, export: &DataExport) -> Result<(), String>;
+    async fn find_by_id(&self, id: &uuid::Uuid) -> Result<Option<DataExport>, String>;
+    /// Exports in `status`, oldest first.
+    async fn find_by_status(&self, status: JobStatus) -> Result<Vec<DataExport>, String>;
+    async fn update_progress(
+        &self,
+        id: &uuid::Uuid,
+        status: JobStatus,
+        completed: u32,
+        error: Option<&str>,
+        size_bytes: Option<u64>,
+    ) -> Result<(), String>;
+    async fn delete(&self, id: &uuid::Uuid) -> Result<(), String>;
+}
@@ -0,0 +1,16 @@
+// Driven port - Owner delegation repository (output port)
+
+use async_trait::async_trait;
+use crate::domain::entities::owner_delegation::OwnerDelegation;
+use crate::domain::value_objects::UserId;
+
+#[async_trait]
+pub trait DelegationRepository: Send + Sync {
+    async fn save(&self, delegation: &OwnerDelegation) -> Result<(), String>;
+    async fn find_by_id(&self, id: &uuid::Uuid) -> Result<Option<OwnerDelegation>, String>;
+    /// Active delegations held by `delegate_id`, across all vaults.
+    async fn find_active_for_delegate(&self, delegate_id: &UserId) -> Result<Vec<OwnerDelegation>, String>;
+    /// All delegations of the owner's vault, revoked ones included.
+    async fn find_by_owner(&self, owner_id: &UserId) -> Result<Vec<OwnerDelegation>, String>;
+    async fn revoke(&self, id: &uuid::Uuid) -> Result<(), String>;
+}
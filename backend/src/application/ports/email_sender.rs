@@ -0,0 +1,9 @@
+// Driven port - outgoing email (output port)
+
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait EmailSender: Send + Sync {
+    /// Send a plain-text message to `to`.
+    async fn send(&self, to: &str, subject: &str, body: &str) -> Result<(), String>;
+}
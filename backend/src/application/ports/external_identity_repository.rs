@@ -0,0 +1,11 @@
+// Driven port - External identity repository (output port)
+
+use async_trait::async_trait;
+use crate::domain::entities::external_identity::ExternalIdentity;
+
+#[async_trait]
+pub trait ExternalIdentityRepository: Send + Sync {
+    async fn find(&self, issuer: &str, subject: &str) -> Result<Option<ExternalIdentity>, String>;
+    /// Insert the link, or record a new login on an existing one.
+    async fn save(&self, identity: &ExternalIdentity) -> Result<(), String>;
+}
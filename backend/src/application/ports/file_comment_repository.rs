@@ -0,0 +1,17 @@
+// Driven port - Comments on vault files (output port)
+
+use async_trait::async_trait;
+use crate::domain::entities::file_comment::FileComment;
+use crate::domain::value_objects::UserId;
+
+#[async_trait]
+pub trait FileCommentRepository: Send + Sync {
+    async fn save(&self, comment: &FileComment) -> Result<(), String>;
+    async fn find_by_id(&self, id: &uuid::Uuid) -> Result<Option<FileComment>, String>;
+    /// Oldest first.
+    async fn find_for_file(&self, owner_id: &UserId, path: &str) -> Result<Vec<FileComment>, String>;
+    async fn delete(&self, id: &uuid::Uuid) -> Result<(), String>;
+    /// How many comments each commented file under the folder `path` has, `""` for the whole
+    /// vault.
+    async fn count_under(&self, owner_id: &UserId, path: &str) -> Result<Vec<(String, u64)>, String>;
+}
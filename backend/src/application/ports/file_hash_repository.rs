@@ -0,0 +1,37 @@
+// Driven port - Content hashes of vault files (output port)
+
+use async_trait::async_trait;
+use serde::Serialize;
+use crate::domain::value_objects::UserId;
+
+/// Files of one vault with identical contents
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DuplicateGroup {
+    /// SHA-256 of the contents, hex
+    pub sha256: String,
+    /// Size of each copy
+    pub size: u64,
+    /// Sorted
+    pub paths: Vec<String>,
+}
+
+/// A file the hash stage indexed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexedFile {
+    pub path: String,
+    pub size: u64,
+}
+
+#[async_trait]
+pub trait FileHashRepository: Send + Sync {
+    /// Insert or replace the hash of a file.
+    async fn save(&self, owner_id: &UserId, path: &str, size: u64, sha256: &str) -> Result<(), String>;
+    /// Contents found more than once in the owner's vault, largest first.
+    async fn find_duplicates(&self, owner_id: &UserId) -> Result<Vec<DuplicateGroup>, String>;
+    /// Indexed files at or under `path`, the whole vault for `""`.
+    async fn find_under(&self, owner_id: &UserId, path: &str) -> Result<Vec<IndexedFile>, String>;
+    /// Follow a file or folder moved from `from` to `to`.
+    async fn move_path(&self, owner_id: &UserId, from: &str, to: &str) -> Result<(), String>;
+    /// Forget a deleted file or folder.
+    async fn delete_path(&self, owner_id: &UserId, path: &str) -> Result<(), String>;
+}
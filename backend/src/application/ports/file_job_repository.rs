@@ -0,0 +1,15 @@
+// Driven port - Bulk file job repository (output port)
+
+use async_trait::async_trait;
+use crate::domain::entities::file_job::{FileJob, JobStatus};
+
+#[async_trait]
+pub trait FileJobRepository: Send + Sync {
+    async fn save(&self, job: &FileJob) -> Result<(), String>;
+    async fn find_by_id(&self, id: &uuid::Uuid) -> Result<Option<FileJob>, String>;
+    /// Jobs in `status`, oldest first.
+    async fn find_by_status(&self, status: JobStatus) -> Result<Vec<FileJob>, String>;
+    async fn update_progress(&self, id: &uuid::Uuid, status: JobStatus, completed: u32, error: Option<&str>) -> Result<(), String>;
+    /// Flag a job to stop before its next operation.
+    async fn request_cancel(&self, id: &uuid::Uuid) -> Result<(), String>;
+}
@@ -1,5 +1,38 @@
 use async_trait::async_trait;
 use crate::domain::entities::file_permission::FilePermission;
+use crate::domain::entities::permission_event::PermissionEvent;
+use crate::domain::value_objects::UserId;
+use super::pagination::{Page, PageRequest};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PermissionStatus {
+    #[default]
+    Active,
+    Expired,
+    Revoked,
+    All,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PermissionSort {
+    #[default]
+    GrantedAt,
+    Path,
+}
+
+/// Criteria for a permission listing; unset fields do not filter.
+#[derive(Debug, Clone, Default)]
+pub struct PermissionFilter {
+    pub owner_id: Option<UserId>,
+    pub client_id: Option<UserId>,
+    pub status: PermissionStatus,
+    pub path_prefix: Option<String>,
+    /// Only permissions inside these subtrees, for co-owners
+    pub subtrees: Option<Vec<String>>,
+    pub sort: PermissionSort,
+}
 
 #[async_trait]
 pub trait FilePermissionRepository: Send + Sync {
@@ -8,4 +41,15 @@ pub trait FilePermissionRepository: Send + Sync {
     async fn find_by_owner_client(&self, owner_id: &crate::domain::value_objects::UserId, client_id: &crate::domain::value_objects::UserId) -> Result<Vec<FilePermission>, String>;
     async fn find_active_by_owner(&self, owner_id: &crate::domain::value_objects::UserId) -> Result<Vec<FilePermission>, String>;
     async fn revoke(&self, id: &uuid::Uuid) -> Result<(), String>;
+    async fn find_by_id(&self, id: &uuid::Uuid) -> Result<Option<FilePermission>, String>;
+    /// Active permissions expiring before `before` whose holders have not been warned yet.
+    async fn find_expiring(&self, before: chrono::DateTime<chrono::Utc>) -> Result<Vec<FilePermission>, String>;
+    async fn mark_expiry_notified(&self, id: &uuid::Uuid) -> Result<(), String>;
+    /// Revoke the active permissions derived from a group, for one member or all of them.
+    async fn revoke_for_group(&self, group_id: &uuid::Uuid, client_id: Option<&crate::domain::value_objects::UserId>) -> Result<(), String>;
+    async fn list(&self, filter: &PermissionFilter, page: &PageRequest) -> Result<Page<FilePermission>, String>;
+    /// Move the expiry and re-arm the expiry warning.
+    async fn update_expiry(&self, id: &uuid::Uuid, expires_at: chrono::DateTime<chrono::Utc>) -> Result<(), String>;
+    /// Every grant, revocation and expiry change in the owner's vault, in the order recorded.
+    async fn events_for_owner(&self, owner_id: &crate::domain::value_objects::UserId) -> Result<Vec<PermissionEvent>, String>;
 }
@@ -0,0 +1,19 @@
+// Driven port - Processing stages run on files after they land in a vault (output port)
+
+use async_trait::async_trait;
+use crate::domain::entities::processing_task::ProcessingTask;
+
+/// One stage of the post-upload pipeline: a scanner, thumbnailer, indexer, metadata
+/// extractor... Runs after the file is in the vault, in the background; an error is retried
+/// with backoff and, once retries are used up, dead-letters the file. Unlike an
+/// [`super::UploadHook`], it never rejects the upload.
+#[async_trait]
+pub trait FileProcessor: Send + Sync {
+    /// Recorded in the task once the stage succeeded; keep it stable across releases
+    fn name(&self) -> &'static str;
+    /// Whether the file needs this stage, e.g. judging by its extension
+    fn accepts(&self, _path: &str) -> bool {
+        true
+    }
+    async fn process(&self, task: &ProcessingTask) -> Result<(), String>;
+}
@@ -0,0 +1,35 @@
+// Driven port - Upload-only links and the inbox they fill (output port)
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use crate::domain::entities::file_request::FileRequest;
+use crate::domain::entities::inbox_upload::{InboxStatus, InboxUpload};
+use crate::domain::value_objects::UserId;
+
+#[async_trait]
+pub trait FileRequestRepository: Send + Sync {
+    /// Insert the request, or store its revocation.
+    async fn save(&self, request: &FileRequest) -> Result<(), String>;
+    async fn find_by_id(&self, id: &uuid::Uuid) -> Result<Option<FileRequest>, String>;
+    async fn find_by_token_hash(&self, token_hash: &str) -> Result<Option<FileRequest>, String>;
+    /// Newest first.
+    async fn find_by_owner(&self, owner_id: &UserId) -> Result<Vec<FileRequest>, String>;
+
+    /// Insert the upload, or store the owner's decision on it.
+    async fn save_upload(&self, upload: &InboxUpload) -> Result<(), String>;
+    async fn find_upload(&self, id: &uuid::Uuid) -> Result<Option<InboxUpload>, String>;
+    /// The owner's inbox, newest first, optionally only in one status.
+    async fn find_uploads(&self, owner_id: &UserId, status: Option<InboxStatus>) -> Result<Vec<InboxUpload>, String>;
+    /// Insert `upload`, still being received, unless its request already has `max_waiting`
+    /// uploads waiting or being received, or `max_receiving` being received. Returns whether
+    /// it was inserted; checking and inserting are one statement, so concurrent senders cannot
+    /// both take the last place.
+    async fn reserve_upload(&self, upload: &InboxUpload, max_waiting: u64, max_receiving: u64) -> Result<bool, String>;
+    /// Bytes of the owner's uploads waiting or being received, which are not in the vault yet.
+    async fn reserved_bytes(&self, owner_id: &UserId) -> Result<u64, String>;
+    /// Remove an upload whose bytes never all arrived.
+    async fn delete_upload(&self, id: &uuid::Uuid) -> Result<(), String>;
+    /// Remove the uploads still being received since before `before`, left by a sender or
+    /// server that stopped. Returns them, so their staged bytes can go too.
+    async fn expire_receiving(&self, before: DateTime<Utc>) -> Result<Vec<uuid::Uuid>, String>;
+}
@@ -0,0 +1,18 @@
+// Driven port - Clients' requests to take shared folders offline (output port)
+
+use async_trait::async_trait;
+use crate::domain::entities::folder_export::{FolderExport, FolderExportStatus};
+use crate::domain::value_objects::UserId;
+
+#[async_trait]
+pub trait FolderExportRepository: Send + Sync {
+    /// Insert the export, or store its changes.
+    async fn save(&self, export: &FolderExport) -> Result<(), String>;
+    async fn find_by_id(&self, id: &uuid::Uuid) -> Result<Option<FolderExport>, String>;
+    /// Newest first.
+    async fn find_by_owner(&self, owner_id: &UserId) -> Result<Vec<FolderExport>, String>;
+    /// Newest first.
+    async fn find_by_client(&self, client_id: &UserId) -> Result<Vec<FolderExport>, String>;
+    /// Oldest first.
+    async fn find_by_status(&self, status: FolderExportStatus) -> Result<Vec<FolderExport>, String>;
+}
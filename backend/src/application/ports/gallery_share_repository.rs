@@ -0,0 +1,18 @@
+// Driven port - Owners' public photo galleries (output port)
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use crate::domain::entities::gallery_share::GalleryShare;
+use crate::domain::value_objects::UserId;
+
+#[async_trait]
+pub trait GalleryShareRepository: Send + Sync {
+    /// Insert the gallery, or store its revocation.
+    async fn save(&self, share: &GalleryShare) -> Result<(), String>;
+    async fn find_by_id(&self, id: &uuid::Uuid) -> Result<Option<GalleryShare>, String>;
+    async fn find_by_token_hash(&self, token_hash: &str) -> Result<Option<GalleryShare>, String>;
+    /// Newest first.
+    async fn find_by_owner(&self, owner_id: &UserId) -> Result<Vec<GalleryShare>, String>;
+    /// Count one more visit, made at `at`.
+    async fn record_view(&self, id: &uuid::Uuid, at: DateTime<Utc>) -> Result<(), String>;
+}
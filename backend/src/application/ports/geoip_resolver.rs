@@ -0,0 +1,8 @@
+// Driven port - IP geolocation (output port)
+
+use std::net::IpAddr;
+
+pub trait GeoIpResolver: Send + Sync {
+    /// ISO 3166-1 alpha-2 country code for `ip`, if known.
+    fn country(&self, ip: IpAddr) -> Option<String>;
+}
@@ -0,0 +1,35 @@
+// Driven port - external OpenID Connect provider (output port)
+
+use async_trait::async_trait;
+
+/// Where to send the browser to log in at the provider, and what to check when it comes back
+#[derive(Debug, Clone)]
+pub struct AuthorizationRequest {
+    pub url: String,
+    /// Returned by the provider with the code; ties the callback to this request
+    pub state: String,
+    /// Echoed in the ID token, so a token issued for another login is refused
+    pub nonce: String,
+    /// PKCE secret whose hash `url` carries; sent with the code
+    pub pkce_verifier: String,
+}
+
+/// What a verified ID token says about the user
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternalClaims {
+    pub issuer: String,
+    pub subject: String,
+    pub email: Option<String>,
+    pub email_verified: bool,
+    pub name: Option<String>,
+    /// From the claim named by `OIDC_GROUPS_CLAIM`
+    pub groups: Vec<String>,
+}
+
+#[async_trait]
+pub trait IdentityProvider: Send + Sync {
+    /// Start a login with a fresh state, nonce and PKCE verifier.
+    async fn authorization_request(&self) -> Result<AuthorizationRequest, String>;
+    /// Redeem the authorization code and verify the ID token it is exchanged for.
+    async fn exchange(&self, code: &str, pkce_verifier: &str, nonce: &str) -> Result<ExternalClaims, String>;
+}
@@ -0,0 +1,15 @@
+// Driven port - Clients' consents to owners acting as them (output port)
+
+use async_trait::async_trait;
+use crate::domain::entities::impersonation_consent::ImpersonationConsent;
+use crate::domain::value_objects::UserId;
+
+#[async_trait]
+pub trait ImpersonationConsentRepository: Send + Sync {
+    /// Insert the consent, or store its changes.
+    async fn save(&self, consent: &ImpersonationConsent) -> Result<(), String>;
+    /// Newest first.
+    async fn find_by_client(&self, client_id: &UserId) -> Result<Vec<ImpersonationConsent>, String>;
+    /// Newest first.
+    async fn find_by_owner(&self, owner_id: &UserId) -> Result<Vec<ImpersonationConsent>, String>;
+}
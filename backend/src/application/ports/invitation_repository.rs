@@ -1,10 +1,30 @@
 use async_trait::async_trait;
-use crate::domain::entities::invitation::Invitation;
+use crate::domain::entities::invitation::{Invitation, InvitationStatus};
+use crate::domain::value_objects::UserId;
+use super::pagination::{Page, PageRequest};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InvitationSort {
+    #[default]
+    CreatedAt,
+    InviteeEmail,
+}
+
+#[derive(Debug, Clone)]
+pub struct InvitationFilter {
+    pub owner_id: UserId,
+    pub status: Option<InvitationStatus>,
+    /// Case-insensitive substring of the invitee's email
+    pub email: Option<String>,
+    pub sort: InvitationSort,
+}
 
 #[async_trait]
 pub trait InvitationRepository: Send + Sync {
     async fn save(&self, invitation: &Invitation) -> Result<(), String>;
     async fn find_by_token(&self, token: &str) -> Result<Option<Invitation>, String>;
     async fn find_by_owner(&self, owner_id: &crate::domain::value_objects::UserId) -> Result<Vec<Invitation>, String>;
+    async fn list(&self, filter: &InvitationFilter, page: &PageRequest) -> Result<Page<Invitation>, String>;
     async fn update_status(&self, id: &uuid::Uuid, status: &str) -> Result<(), String>;
 }
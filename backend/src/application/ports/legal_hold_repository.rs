@@ -0,0 +1,13 @@
+use async_trait::async_trait;
+use crate::domain::entities::legal_hold::LegalHold;
+use crate::domain::value_objects::UserId;
+
+#[async_trait]
+pub trait LegalHoldRepository: Send + Sync {
+    async fn save(&self, hold: &LegalHold) -> Result<(), String>;
+    async fn find_by_id(&self, id: &uuid::Uuid) -> Result<Option<LegalHold>, String>;
+    /// The vault's holds, newest first, released and expired ones included
+    async fn list_for_owner(&self, owner_id: &UserId) -> Result<Vec<LegalHold>, String>;
+    /// Release a hold; `false` when it was already released.
+    async fn release(&self, id: &uuid::Uuid, at: chrono::DateTime<chrono::Utc>) -> Result<bool, String>;
+}
@@ -0,0 +1,15 @@
+// Driven port - failed login tracking (output port)
+
+use async_trait::async_trait;
+
+/// Failed-attempt counters and temporary blocks, keyed by subject (`user:<id>`, `ip:<addr>`).
+#[async_trait]
+pub trait LoginAttemptRepository: Send + Sync {
+    /// Count a failure and return the number of failures within the current window.
+    async fn record_failure(&self, subject: &str, window_seconds: u64) -> Result<u32, String>;
+    async fn block(&self, subject: &str, seconds: u64) -> Result<(), String>;
+    /// Seconds left on an active block, if any.
+    async fn blocked_for(&self, subject: &str) -> Result<Option<u64>, String>;
+    /// Forget failures and lift any block.
+    async fn clear(&self, subject: &str) -> Result<(), String>;
+}
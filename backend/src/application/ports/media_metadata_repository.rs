@@ -0,0 +1,41 @@
+// Driven port - Metadata read from media files (output port)
+
+use async_trait::async_trait;
+use serde::Serialize;
+use crate::domain::entities::media_metadata::MediaMetadata;
+use crate::domain::value_objects::UserId;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct YearFacet {
+    pub year: i32,
+    pub count: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CameraFacet {
+    pub camera: String,
+    pub count: u64,
+}
+
+/// How an owner's media spreads over years and cameras, to narrow a search by
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct MediaFacets {
+    pub years: Vec<YearFacet>,
+    pub cameras: Vec<CameraFacet>,
+}
+
+#[async_trait]
+pub trait MediaMetadataRepository: Send + Sync {
+    /// Insert or replace the metadata of a file.
+    async fn save(&self, metadata: &MediaMetadata) -> Result<(), String>;
+    async fn find(&self, owner_id: &UserId, path: &str) -> Result<Option<MediaMetadata>, String>;
+    /// The owner's media taken in `year` and/or with `camera`, most recent first.
+    async fn search(&self, owner_id: &UserId, year: Option<i32>, camera: Option<&str>, limit: u32) -> Result<Vec<MediaMetadata>, String>;
+    /// The owner's media in `folder` and its subfolders.
+    async fn list_under(&self, owner_id: &UserId, folder: &str) -> Result<Vec<MediaMetadata>, String>;
+    async fn facets(&self, owner_id: &UserId) -> Result<MediaFacets, String>;
+    /// Follow a file or folder moved from `from` to `to`.
+    async fn move_path(&self, owner_id: &UserId, from: &str, to: &str) -> Result<(), String>;
+    /// Forget a deleted file or folder.
+    async fn delete_path(&self, owner_id: &UserId, path: &str) -> Result<(), String>;
+}
@@ -7,6 +7,51 @@ pub mod application_ports;
 pub mod invitation_repository;
 pub mod file_permission_repository;
 pub mod session_repository;
+pub mod quality_preference_repository;
+pub mod user_preferences_repository;
+pub mod audit_repository;
+pub mod notification_repository;
+pub mod login_attempt_repository;
+pub mod access_policy_repository;
+pub mod geoip_resolver;
+pub mod email_sender;
+pub mod verification_code_repository;
+pub mod client_group_repository;
+pub mod delegation_repository;
+pub mod pagination;
+pub mod file_job_repository;
+pub mod vault_storage;
+pub mod upload_session_repository;
+pub mod upload_hook;
+pub mod session_timeline_repository;
+pub mod session_snapshot_repository;
+pub mod session_ownership_repository;
+pub mod scheduler_repository;
+pub mod app_state_repository;
+pub mod bandwidth_repository;
+pub mod app_crash_repository;
+pub mod auth_session_repository;
+pub mod data_export_repository;
+pub mod account_deletion_repository;
+pub mod vault_import_repository;
+pub mod identity_provider;
+pub mod external_identity_repository;
+pub mod provisioning_repository;
+pub mod access_token_repository;
+pub mod legal_hold_repository;
+pub mod app_setting_repository;
+pub mod tenant_repository;
+pub mod branding_repository;
+pub mod file_processor;
+pub mod processing_repository;
+pub mod media_metadata_repository;
+pub mod organization_rule_repository;
+pub mod file_hash_repository;
+pub mod folder_export_repository;
+pub mod gallery_share_repository;
+pub mod file_comment_repository;
+pub mod file_request_repository;
+pub mod impersonation_consent_repository;
 
 // Removed pub use for UserRepository
 pub use credential_repository::CredentialRepository;
@@ -14,3 +59,48 @@ pub use challenge_repository::ChallengeRepository;
 pub use invitation_repository::InvitationRepository;
 pub use file_permission_repository::FilePermissionRepository;
 pub use session_repository::SessionRepository;
+pub use quality_preference_repository::QualityPreferenceRepository;
+pub use user_preferences_repository::UserPreferencesRepository;
+pub use audit_repository::AuditRepository;
+pub use notification_repository::NotificationRepository;
+pub use login_attempt_repository::LoginAttemptRepository;
+pub use access_policy_repository::AccessPolicyRepository;
+pub use geoip_resolver::GeoIpResolver;
+pub use email_sender::EmailSender;
+pub use verification_code_repository::VerificationCodeRepository;
+pub use client_group_repository::ClientGroupRepository;
+pub use delegation_repository::DelegationRepository;
+pub use pagination::{Page, PageRequest, SortDirection};
+pub use file_job_repository::FileJobRepository;
+pub use vault_storage::{ByteStream, FileStat, ImportEntry, ImportOutcome, VaultEntry, VaultStorage};
+pub use upload_session_repository::UploadSessionRepository;
+pub use upload_hook::UploadHook;
+pub use session_timeline_repository::SessionTimelineRepository;
+pub use session_snapshot_repository::SessionSnapshotRepository;
+pub use session_ownership_repository::{InstanceInfo, SessionOwnershipRepository};
+pub use scheduler_repository::SchedulerRepository;
+pub use app_state_repository::AppStateRepository;
+pub use bandwidth_repository::{BandwidthRepository, BandwidthUsage};
+pub use app_crash_repository::AppCrashRepository;
+pub use auth_session_repository::AuthSessionRepository;
+pub use data_export_repository::DataExportRepository;
+pub use account_deletion_repository::AccountDeletionRepository;
+pub use vault_import_repository::VaultImportRepository;
+pub use identity_provider::{AuthorizationRequest, ExternalClaims, IdentityProvider};
+pub use external_identity_repository::ExternalIdentityRepository;
+pub use provisioning_repository::{IdempotencyClaim, ProvisioningRepository, StoredResponse};
+pub use access_token_repository::AccessTokenRepository;
+pub use legal_hold_repository::LegalHoldRepository;
+pub use app_setting_repository::AppSettingRepository;
+pub use tenant_repository::TenantRepository;
+pub use branding_repository::BrandingRepository;
+pub use file_processor::FileProcessor;
+pub use processing_repository::ProcessingRepository;
+pub use media_metadata_repository::MediaMetadataRepository;
+pub use organization_rule_repository::OrganizationRuleRepository;
+pub use file_hash_repository::FileHashRepository;
+pub use folder_export_repository::FolderExportRepository;
+pub use gallery_share_repository::GalleryShareRepository;
+pub use file_comment_repository::FileCommentRepository;
+pub use file_request_repository::FileRequestRepository;
+pub use impersonation_consent_repository::ImpersonationConsentRepository;
@@ -0,0 +1,7 @@
+use async_trait::async_trait;
+use crate::domain::entities::notification::Notification;
+
+#[async_trait]
+pub trait NotificationRepository: Send + Sync {
+    async fn create(&self, notification: &Notification) -> Result<(), String>;
+}
@@ -0,0 +1,15 @@
+// Driven port - Owners' rules sorting new media into folders (output port)
+
+use async_trait::async_trait;
+use crate::domain::entities::organization_rule::OrganizationRule;
+use crate::domain::value_objects::UserId;
+
+#[async_trait]
+pub trait OrganizationRuleRepository: Send + Sync {
+    /// Insert the rule, or store its changes.
+    async fn save(&self, rule: &OrganizationRule) -> Result<(), String>;
+    async fn find_by_id(&self, id: &uuid::Uuid) -> Result<Option<OrganizationRule>, String>;
+    /// Oldest first, the order they are tried in.
+    async fn find_by_owner(&self, owner_id: &UserId) -> Result<Vec<OrganizationRule>, String>;
+    async fn delete(&self, id: &uuid::Uuid) -> Result<(), String>;
+}
@@ -0,0 +1,148 @@
+// Shared list conventions: keyset cursors, sort direction and the response envelope
+
+pub const DEFAULT_PAGE_SIZE: u32 = 50;
+pub const MAX_PAGE_SIZE: u32 = 200;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    #[default]
+    Desc,
+}
+
+impl SortDirection {
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            SortDirection::Asc => "ASC",
+            SortDirection::Desc => "DESC",
+        }
+    }
+}
+
+/// Position just past the last item of a page: its sort key, and its id as tiebreaker.
+/// Clients treat the encoded form as opaque.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cursor {
+    pub key: String,
+    pub id: String,
+}
+
+const CURSOR_SEPARATOR: char = '\u{1f}';
+
+impl Cursor {
+    pub fn new(key: impl Into<String>, id: impl Into<String>) -> Self {
+        Self { key: key.into(), id: id.into() }
+    }
+
+    pub fn encode(&self) -> String {
+        format!("{}{}{}", self.key, CURSOR_SEPARATOR, self.id)
+            .bytes()
+            .map(|b| format!("{b:02x}"))
+            .collect()
+    }
+
+    pub fn decode(encoded: &str) -> Result<Self, String> {
+        let invalid = || "Invalid cursor".to_string();
+        if encoded.len() % 2 != 0 {
+            return Err(invalid());
+        }
+        let bytes = (0..encoded.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(encoded.get(i..i + 2).ok_or_else(invalid)?, 16).map_err(|_| invalid()))
+            .collect::<Result<Vec<u8>, String>>()?;
+        let decoded = String::from_utf8(bytes).map_err(|_| invalid())?;
+        let (key, id) = decoded.split_once(CURSOR_SEPARATOR).ok_or_else(invalid)?;
+        Ok(Self::new(key, id))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageRequest {
+    pub limit: u32,
+    pub after: Option<Cursor>,
+    pub direction: SortDirection,
+}
+
+impl PageRequest {
+    /// Build from raw query parameters; the limit is clamped to `1..=MAX_PAGE_SIZE`.
+    pub fn new(limit: Option<u32>, cursor: Option<&str>, direction: Option<SortDirection>) -> Result<Self, String> {
+        Ok(Self {
+            limit: limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE),
+            after: cursor.filter(|c| !c.is_empty()).map(Cursor::decode).transpose()?,
+            direction: direction.unwrap_or_default(),
+        })
+    }
+}
+
+impl Default for PageRequest {
+    fn default() -> Self {
+        Self { limit: DEFAULT_PAGE_SIZE, after: None, direction: SortDirection::default() }
+    }
+}
+
+/// Response envelope shared by every list endpoint.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>,
+    /// Matching items across all pages, ignoring the cursor
+    pub total_estimate: u64,
+}
+
+impl<T> Page<T> {
+    /// Repositories fetch `limit + 1` rows; the extra row only tells that another page exists.
+    pub fn from_rows(mut rows: Vec<T>, limit: u32, total_estimate: u64, cursor_of: impl Fn(&T) -> Cursor) -> Self {
+        let has_more = rows.len() > limit as usize;
+        rows.truncate(limit as usize);
+        let next_cursor = if has_more { rows.last().map(|row| cursor_of(row).encode()) } else { None };
+        Self { items: rows, next_cursor, total_estimate }
+    }
+
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page {
+            items: self.items.into_iter().map(f).collect(),
+            next_cursor: self.next_cursor,
+            total_estimate: self.total_estimate,
+        }
+    }
+
+    pub fn try_map<U, E>(self, f: impl FnMut(T) -> Result<U, E>) -> Result<Page<U>, E> {
+        Ok(Page {
+            items: self.items.into_iter().map(f).collect::<Result<_, _>>()?,
+            next_cursor: self.next_cursor,
+            total_estimate: self.total_estimate,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_round_trips() {
+        let cursor = Cursor::new("2026-10-16T08:00:00+00:00", "b1c2");
+        assert_eq!(Cursor::decode(&cursor.encode()).unwrap(), cursor);
+        assert!(Cursor::decode("zz").is_err());
+        assert!(Cursor::decode("616263").is_err());
+    }
+
+    #[test]
+    fn test_page_request_clamps_limit() {
+        assert_eq!(PageRequest::new(Some(0), None, None).unwrap().limit, 1);
+        assert_eq!(PageRequest::new(Some(10_000), None, None).unwrap().limit, MAX_PAGE_SIZE);
+        assert_eq!(PageRequest::new(None, Some(""), None).unwrap(), PageRequest::default());
+    }
+
+    #[test]
+    fn test_from_rows_sets_cursor_only_when_more_rows_exist() {
+        let cursor_of = |n: &u32| Cursor::new(n.to_string(), n.to_string());
+        let page = Page::from_rows(vec![1, 2, 3], 2, 3, cursor_of);
+        assert_eq!(page.items, vec![1, 2]);
+        assert_eq!(page.next_cursor, Some(Cursor::new("2", "2").encode()));
+
+        let last = Page::from_rows(vec![3], 2, 3, cursor_of);
+        assert_eq!(last.next_cursor, None);
+    }
+}
@@ -0,0 +1,17 @@
+// Driven port - Post-upload processing queue (output port)
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use crate::domain::entities::processing_task::ProcessingTask;
+use crate::domain::value_objects::UserId;
+
+#[async_trait]
+pub trait ProcessingRepository: Send + Sync {
+    /// Insert the task, or store its progress.
+    async fn save(&self, task: &ProcessingTask) -> Result<(), String>;
+    async fn find_by_id(&self, id: &uuid::Uuid) -> Result<Option<ProcessingTask>, String>;
+    /// Pending tasks whose next attempt is due at `now`, oldest first.
+    async fn find_due(&self, now: DateTime<Utc>, limit: u32) -> Result<Vec<ProcessingTask>, String>;
+    /// The owner's dead-lettered files, most recent failure first.
+    async fn list_dead_letters(&self, owner_id: &UserId) -> Result<Vec<ProcessingTask>, String>;
+}
@@ -0,0 +1,40 @@
+use async_trait::async_trait;
+use crate::domain::entities::provisioning_token::ProvisioningToken;
+use crate::domain::value_objects::UserId;
+
+/// The response a provisioning request got, kept under its `Idempotency-Key`
+#[derive(Debug, Clone)]
+pub struct StoredResponse {
+    /// Hash of the request body, so a key reused for a different request is refused
+    pub request_hash: String,
+    pub status: u16,
+    pub body: String,
+}
+
+/// Where an `Idempotency-Key` stands when a request arrives with it
+#[derive(Debug, Clone)]
+pub enum IdempotencyClaim {
+    /// The key was free and is now held for this request, until its response is saved or the
+    /// claim released
+    Claimed,
+    /// A request with the key is still being applied
+    InProgress { request_hash: String },
+    Done(StoredResponse),
+}
+
+#[async_trait]
+pub trait ProvisioningRepository: Send + Sync {
+    async fn save_token(&self, token: &ProvisioningToken) -> Result<(), String>;
+    async fn find_token_by_hash(&self, token_hash: &str) -> Result<Option<ProvisioningToken>, String>;
+    async fn list_tokens(&self, owner_id: &UserId) -> Result<Vec<ProvisioningToken>, String>;
+    /// Revoke one of the owner's tokens; `false` when they have no such active token.
+    async fn revoke_token(&self, owner_id: &UserId, id: &uuid::Uuid) -> Result<bool, String>;
+    async fn touch_token(&self, id: &uuid::Uuid) -> Result<(), String>;
+    /// Hold the key for a request with `request_hash`, unless a request younger than `max_age`
+    /// already holds it. Keys older than that are dropped first.
+    async fn claim_key(&self, owner_id: &UserId, key: &str, request_hash: &str, max_age: chrono::Duration) -> Result<IdempotencyClaim, String>;
+    /// Store the response of the request holding the key.
+    async fn save_response(&self, owner_id: &UserId, key: &str, response: &StoredResponse) -> Result<(), String>;
+    /// Free a key whose request stored no response, so a retry is applied.
+    async fn release_key(&self, owner_id: &UserId, key: &str) -> Result<(), String>;
+}
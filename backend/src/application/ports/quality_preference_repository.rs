@@ -0,0 +1,9 @@
+use async_trait::async_trait;
+use crate::domain::entities::quality_preference::QualityPreference;
+use crate::domain::value_objects::UserId;
+
+#[async_trait]
+pub trait QualityPreferenceRepository: Send + Sync {
+    async fn save(&self, preference: &QualityPreference) -> Result<(), String>;
+    async fn find(&self, user_id: &UserId, app_id: &str) -> Result<Option<QualityPreference>, String>;
+}
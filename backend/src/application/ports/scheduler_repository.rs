@@ -0,0 +1,15 @@
+// Driven port - Host capacity shared between backend instances (output port)
+
+use async_trait::async_trait;
+use crate::domain::entities::placement::{HostStatus, PlacementDecision};
+
+/// The hosts sessions can be placed on, and a log of recent placements for operators.
+#[async_trait]
+pub trait SchedulerRepository: Send + Sync {
+    /// Publish a host's status for `ttl_seconds`; hosts that stop publishing drop out.
+    async fn publish_host(&self, status: &HostStatus, ttl_seconds: u64) -> Result<(), String>;
+    async fn hosts(&self) -> Result<Vec<HostStatus>, String>;
+    async fn record_decision(&self, decision: &PlacementDecision) -> Result<(), String>;
+    /// Most recent first
+    async fn recent_decisions(&self, limit: usize) -> Result<Vec<PlacementDecision>, String>;
+}
@@ -0,0 +1,24 @@
+// Driven port - Session ownership across backend instances (output port)
+
+use async_trait::async_trait;
+
+/// A backend instance and the base URL its signaling WebSocket is reached at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstanceInfo {
+    pub id: String,
+    pub websocket_base_url: String,
+}
+
+/// Which backend instance runs each streaming session. Claims expire unless renewed, so the
+/// sessions of an instance that stops become free for another one to take over.
+#[async_trait]
+pub trait SessionOwnershipRepository: Send + Sync {
+    /// Publish the instance's address for `ttl_seconds`.
+    async fn announce(&self, instance: &InstanceInfo, ttl_seconds: u64) -> Result<(), String>;
+    /// Claim the session, or extend the instance's own claim. False when another instance holds it.
+    async fn claim(&self, session_id: &str, instance_id: &str, ttl_seconds: u64) -> Result<bool, String>;
+    /// The instance holding a live claim on the session.
+    async fn owner(&self, session_id: &str) -> Result<Option<InstanceInfo>, String>;
+    /// Drop the claim if `instance_id` still holds it.
+    async fn release(&self, session_id: &str, instance_id: &str) -> Result<(), String>;
+}
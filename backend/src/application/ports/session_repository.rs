@@ -1,6 +1,26 @@
 use async_trait::async_trait;
 use crate::domain::entities::session::Session;
 use crate::domain::value_objects::UserId;
+use super::pagination::{Page, PageRequest};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionSort {
+    #[default]
+    CreatedAt,
+    ExpiresAt,
+}
+
+/// Criteria for a session listing; unset fields do not filter.
+#[derive(Debug, Clone, Default)]
+pub struct SessionFilter {
+    pub user_id: Option<UserId>,
+    /// Sessions run inside this owner's vault
+    pub acting_as_owner_id: Option<UserId>,
+    pub state: Option<String>,
+    pub app_id: Option<String>,
+    pub sort: SessionSort,
+}
 
 #[async_trait]
 pub trait SessionRepository: Send + Sync {
@@ -9,5 +29,8 @@ pub trait SessionRepository: Send + Sync {
     async fn find_active_by_user(&self, user_id: &UserId) -> Result<Vec<Session>, String>;
     async fn update_state(&self, id: &uuid::Uuid, state: &str) -> Result<(), String>;
     async fn terminate(&self, id: &uuid::Uuid) -> Result<(), String>;
+    /// Move the expiry to `at`; each instance's expiry sweep then ends the session wherever it runs.
+    async fn expire(&self, id: &uuid::Uuid, at: chrono::DateTime<chrono::Utc>) -> Result<(), String>;
     async fn find_expired(&self) -> Result<Vec<Session>, String>;
+    async fn list(&self, filter: &SessionFilter, page: &PageRequest) -> Result<Page<Session>, String>;
 }
@@ -0,0 +1,14 @@
+// Driven port - Suspended session repository (output port)
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use crate::domain::entities::session_snapshot::SessionSnapshot;
+
+#[async_trait]
+pub trait SessionSnapshotRepository: Send + Sync {
+    async fn save(&self, snapshot: &SessionSnapshot) -> Result<(), String>;
+    async fn find(&self, session_id: &uuid::Uuid) -> Result<Option<SessionSnapshot>, String>;
+    async fn delete(&self, session_id: &uuid::Uuid) -> Result<(), String>;
+    /// Sessions suspended before `cutoff`
+    async fn find_suspended_before(&self, cutoff: DateTime<Utc>) -> Result<Vec<uuid::Uuid>, String>;
+}
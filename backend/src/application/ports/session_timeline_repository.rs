@@ -0,0 +1,11 @@
+// Driven port - Session timeline repository (output port)
+
+use async_trait::async_trait;
+use crate::domain::entities::session_timeline::SessionTimeline;
+
+#[async_trait]
+pub trait SessionTimelineRepository: Send + Sync {
+    /// Store the timeline of a finished session, replacing any earlier one.
+    async fn save(&self, timeline: &SessionTimeline) -> Result<(), String>;
+    async fn find(&self, session_id: &uuid::Uuid) -> Result<Option<SessionTimeline>, String>;
+}
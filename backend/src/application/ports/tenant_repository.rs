@@ -0,0 +1,12 @@
+use async_trait::async_trait;
+use crate::domain::entities::tenant::Tenant;
+
+#[async_trait]
+pub trait TenantRepository: Send + Sync {
+    async fn save(&self, tenant: &Tenant) -> Result<(), String>;
+    async fn find(&self, id: &uuid::Uuid) -> Result<Option<Tenant>, String>;
+    async fn find_by_setup_token_hash(&self, hash: &str) -> Result<Option<Tenant>, String>;
+    async fn list(&self) -> Result<Vec<Tenant>, String>;
+    /// Retire the setup token once the tenant's first admin registered.
+    async fn complete_setup(&self, id: &uuid::Uuid) -> Result<(), String>;
+}
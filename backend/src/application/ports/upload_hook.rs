@@ -0,0 +1,12 @@
+// Driven port - Checks run on finished uploads (output port)
+
+use async_trait::async_trait;
+use crate::domain::entities::upload_session::UploadSession;
+
+/// Runs once every byte of an upload arrived, before the file is moved into the vault.
+/// Scanning, quota and versioning plug in here; an error rejects the upload.
+#[async_trait]
+pub trait UploadHook: Send + Sync {
+    fn name(&self) -> &'static str;
+    async fn on_complete(&self, upload: &UploadSession) -> Result<(), String>;
+}
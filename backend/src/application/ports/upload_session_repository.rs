@@ -0,0 +1,16 @@
+// Driven port - Resumable upload repository (output port)
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use crate::domain::entities::upload_session::{UploadSession, UploadStatus};
+
+#[async_trait]
+pub trait UploadSessionRepository: Send + Sync {
+    async fn save(&self, upload: &UploadSession) -> Result<(), String>;
+    async fn find_by_id(&self, id: &uuid::Uuid) -> Result<Option<UploadSession>, String>;
+    /// Record received bytes and push the expiry back.
+    async fn update_offset(&self, id: &uuid::Uuid, offset: u64, expires_at: DateTime<Utc>) -> Result<(), String>;
+    async fn finish(&self, id: &uuid::Uuid, status: UploadStatus, error: Option<&str>) -> Result<(), String>;
+    /// Active uploads idle past their expiry.
+    async fn find_expired(&self, now: DateTime<Utc>) -> Result<Vec<UploadSession>, String>;
+}
@@ -0,0 +1,11 @@
+use async_trait::async_trait;
+use crate::domain::entities::user_preferences::UserPreferences;
+use crate::domain::value_objects::UserId;
+use shared::Locale;
+
+#[async_trait]
+pub trait UserPreferencesRepository: Send + Sync {
+    /// Store the preferences and the user's locale, which lives on their profile, together.
+    async fn save(&self, preferences: &UserPreferences, locale: Locale) -> Result<(), String>;
+    async fn find(&self, user_id: &UserId) -> Result<Option<UserPreferences>, String>;
+}
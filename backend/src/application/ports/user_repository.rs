@@ -8,4 +8,9 @@ pub trait UserRepository: Send + Sync {
         async fn count_super_admins(&self) -> Result<u64, String>;
     async fn save(&self, user: &crate::domain::User) -> Result<(), String>;
     async fn find_by_email(&self, email: &crate::domain::Email) -> Result<Option<crate::domain::User>, String>;
+    async fn find_by_id(&self, id: &crate::domain::UserId) -> Result<Option<crate::domain::User>, String>;
+    /// Users of one tenant, by email
+    async fn list_by_tenant(&self, tenant_id: &uuid::Uuid) -> Result<Vec<crate::domain::User>, String>;
+    async fn update_roles(&self, id: &crate::domain::UserId, roles: &[crate::domain::value_objects::user_role::UserRole]) -> Result<(), String>;
+    async fn update_status(&self, id: &crate::domain::UserId, status: crate::domain::UserStatus) -> Result<(), String>;
 }
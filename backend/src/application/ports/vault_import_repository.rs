@@ -0,0 +1,22 @@
+// Driven port - Vault import repository (output port)
+
+use async_trait::async_trait;
+use crate::domain::entities::file_job::JobStatus;
+use crate::domain::entities::vault_import::{ImportProgress, VaultImport};
+
+#[async_trait]
+pub trait VaultImportRepository: Send + Sync {
+    async fn save(&self, import: &VaultImport) -> Result<(), String>;
+    async fn find_by_id(&self, id: &uuid::Uuid) -> Result<Option<VaultImport>, String>;
+    /// Imports in `status`, oldest first.
+    async fn find_by_status(&self, status: JobStatus) -> Result<Vec<VaultImport>, String>;
+    async fn update_progress(
+        &self,
+        id: &uuid::Uuid,
+        status: JobStatus,
+        progress: &ImportProgress,
+        error: Option<&str>,
+    ) -> Result<(), String>;
+    /// Flag an import to stop before its next batch of files.
+    async fn request_cancel(&self, id: &uuid::Uuid) -> Result<(), String>;
+}
@@ -0,0 +1,117 @@
+// Driven port - Owner vault filesystem (output port)
+
+use async_trait::async_trait;
+use futures_util::stream::BoxStream;
+use shared::ArchiveFormat;
+use crate::application::ports::pagination::{Page, PageRequest};
+use crate::domain::entities::file_job::FileOperation;
+use crate::domain::entities::vault_import::{ConflictPolicy, ImportMode};
+use crate::domain::value_objects::UserId;
+use uuid::Uuid;
+
+/// File or archive bytes, produced while the consumer reads them
+pub type ByteStream = BoxStream<'static, Result<Vec<u8>, String>>;
+
+/// Size and content version of a vault file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileStat {
+    pub size: u64,
+    /// Changes whenever the content may have changed; used as the HTTP `ETag`
+    pub etag: String,
+}
+
+/// One entry of a listed vault folder
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VaultEntry {
+    pub name: String,
+    /// Relative to the vault root, `/`-separated
+    pub path: String,
+    pub is_dir: bool,
+    /// File size; for directories the size of their contents when asked for, else `None`
+    pub size: Option<u64>,
+    pub modified: Option<chrono::DateTime<chrono::Utc>>,
+    pub mime_type: &'static str,
+}
+
+/// A regular file found under a host directory being imported
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportEntry {
+    /// Absolute host path
+    pub source: String,
+    /// Relative to the imported directory, `/`-separated
+    pub path: String,
+    pub size: u64,
+}
+
+/// What became of one imported file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportOutcome {
+    /// Now in the vault at this path, which differs from the requested one after a rename
+    Imported(String),
+    /// Left out: a vault file already had its path
+    Skipped,
+}
+
+#[async_trait]
+pub trait VaultStorage: Send + Sync {
+    /// Apply one operation inside `owner_id`'s vault. `resumed` marks the step a restart
+    /// interrupted, whose result may already be in place.
+    async fn apply(&self, owner_id: &UserId, operation: &FileOperation, resumed: bool) -> Result<(), String>;
+    /// Keep `owner_id`'s vault under `tenant_id`'s storage root from now on.
+    fn place_in_tenant(&self, owner_id: &UserId, tenant_id: Uuid);
+    /// Delete `owner_id`'s whole vault, when their account is purged.
+    async fn delete_vault(&self, owner_id: &UserId) -> Result<(), String>;
+    /// Stream an archive of vault `paths` as it is built, without holding it in memory.
+    async fn archive(&self, owner_id: &UserId, paths: &[String], format: ArchiveFormat) -> Result<ByteStream, String>;
+    /// Describe a regular file; directories and links are reported as not found.
+    async fn stat(&self, owner_id: &UserId, path: &str) -> Result<FileStat, String>;
+    /// Entries of the folder at `path`, `""` for the vault root, directories first. Directory
+    /// sizes are left out; see `folder_size`.
+    async fn list(&self, owner_id: &UserId, path: &str) -> Result<Vec<VaultEntry>, String>;
+    /// One page of the folder at `path`, in `list`'s order. Only the page's entries are read
+    /// in detail, so folders with tens of thousands of entries list without stalling.
+    async fn list_page(&self, owner_id: &UserId, path: &str, page: &PageRequest) -> Result<Page<VaultEntry>, String>;
+    /// Total bytes under the folder at `path`, from a cache that changes made here invalidate.
+    async fn folder_size(&self, owner_id: &UserId, path: &str) -> Result<u64, String>;
+    /// Stream `len` bytes of a file starting at `offset`.
+    async fn read_range(&self, owner_id: &UserId, path: &str, offset: u64, len: u64) -> Result<ByteStream, String>;
+    /// Refuse `path` if a file, folder or link is already there.
+    async fn check_free(&self, owner_id: &UserId, path: &str) -> Result<(), String>;
+    /// Refuse `incoming` more bytes if they would take the vault over its quota.
+    async fn check_quota(&self, owner_id: &UserId, incoming: u64) -> Result<(), String>;
+    /// Stage an upload's bytes from `offset` on, accepting at most `max_len`. Bytes that arrived
+    /// before the client stream broke are kept; returns the new offset.
+    async fn append_upload(&self, upload_id: &Uuid, offset: u64, max_len: u64, chunk: ByteStream) -> Result<u64, String>;
+    /// Move a fully staged upload to `path` in the owner's vault.
+    async fn commit_upload(&self, upload_id: &Uuid, owner_id: &UserId, path: &str) -> Result<(), String>;
+    async fn discard_upload(&self, upload_id: &Uuid) -> Result<(), String>;
+    /// Write one document, e.g. `profile.json`, into a data export's staging area.
+    async fn stage_export_document(&self, export_id: &Uuid, name: &str, contents: Vec<u8>) -> Result<(), String>;
+    /// Copy vault files into a data export under `files/{owner_id}/`: the whole vault when
+    /// `paths` is `None`. Missing paths are skipped, since grants can outlive their files.
+    async fn stage_export_files(&self, export_id: &Uuid, owner_id: &UserId, paths: Option<&[String]>) -> Result<(), String>;
+    /// Blank the location in the photos staged from `owner_id`'s vault. Returns how many
+    /// carried one.
+    async fn strip_export_gps(&self, export_id: &Uuid, owner_id: &UserId) -> Result<u64, String>;
+    /// Zip a staged export into its downloadable archive and drop the staging area. Returns the
+    /// archive size.
+    async fn finish_export(&self, export_id: &Uuid) -> Result<u64, String>;
+    /// Like `finish_export`, with every file in the archive AES-256 encrypted under `password`.
+    async fn seal_export(&self, export_id: &Uuid, password: &str) -> Result<u64, String>;
+    async fn read_export(&self, export_id: &Uuid) -> Result<ByteStream, String>;
+    /// Remove an export's staging area and archive, whichever exist.
+    async fn discard_export(&self, export_id: &Uuid) -> Result<(), String>;
+    /// Regular files under the host directory `source`, sorted by path. Symbolic links are
+    /// skipped. `source` must resolve inside one of `roots` and outside the storage root.
+    async fn scan_import(&self, source: &str, roots: &[String]) -> Result<Vec<ImportEntry>, String>;
+    /// Bring one scanned file into `owner_id`'s vault at `path`. A file only appears at its
+    /// vault path once complete.
+    async fn import_file(
+        &self,
+        owner_id: &UserId,
+        entry: &ImportEntry,
+        path: &str,
+        mode: ImportMode,
+        on_conflict: ConflictPolicy,
+    ) -> Result<ImportOutcome, String>;
+}
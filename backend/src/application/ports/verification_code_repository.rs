@@ -0,0 +1,11 @@
+// Driven port - short-lived verification codes (output port)
+
+use async_trait::async_trait;
+
+/// One pending code per key; saving again replaces it.
+#[async_trait]
+pub trait VerificationCodeRepository: Send + Sync {
+    async fn save(&self, key: &str, code: &str, ttl_seconds: u64) -> Result<(), String>;
+    async fn find(&self, key: &str) -> Result<Option<String>, String>;
+    async fn delete(&self, key: &str) -> Result<(), String>;
+}
@@ -0,0 +1,104 @@
+// Processing pipeline - stages (scanning, thumbnails, indexing, ...) run on files once they
+// landed in a vault, retried with backoff and dead-lettered when they keep failing
+use std::sync::Arc;
+use chrono::{DateTime, Duration, Utc};
+use serde_json::json;
+use uuid::Uuid;
+use crate::application::ports::{AuditRepository, FileProcessor, ProcessingRepository};
+use crate::domain::entities::audit_event::AuditEvent;
+use crate::domain::entities::processing_task::{ProcessingStatus, ProcessingTask, RetryPolicy};
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::driven::config::Config;
+
+/// Tasks taken from the queue per run
+const BATCH_SIZE: u32 = 20;
+
+/// `PROCESSING_MAX_ATTEMPTS`, `PROCESSING_RETRY_BASE_SECS`
+pub fn retry_policy(config: &Config) -> RetryPolicy {
+    let default = RetryPolicy::default();
+    RetryPolicy {
+        max_attempts: config.parse::<u32>("PROCESSING_MAX_ATTEMPTS").filter(|n| *n > 0).unwrap_or(default.max_attempts),
+        base_delay: config.parse::<i64>("PROCESSING_RETRY_BASE_SECS").map(Duration::seconds).unwrap_or(default.base_delay),
+    }
+}
+
+/// Queue a file that just landed in a vault. Nothing is queued when no stage wants it.
+pub async fn enqueue<R>(
+    repo: &R,
+    processors: &[Arc<dyn FileProcessor>],
+    owner_id: &UserId,
+    path: &str,
+    size: u64,
+) -> Result<Option<ProcessingTask>, String>
+where
+    R: ProcessingRepository + ?Sized,
+{
+    if !processors.iter().any(|p| p.accepts(path)) {
+        return Ok(None);
+    }
+    let task = ProcessingTask::new(owner_id.clone(), path, size);
+    repo.save(&task).await?;
+    Ok(Some(task))
+}
+
+/// Run the stages each due task has left, in order, stopping at the first failure.
+/// Returns how many tasks were worked on.
+pub async fn run_due<R, A>(
+    repo: &R,
+    processors: &[Arc<dyn FileProcessor>],
+    audit: &A,
+    policy: &RetryPolicy,
+    now: DateTime<Utc>,
+) -> Result<usize, String>
+where
+    R: ProcessingRepository + ?Sized,
+    A: AuditRepository + ?Sized,
+{
+    let tasks = repo.find_due(now, BATCH_SIZE).await?;
+    let count = tasks.len();
+    for mut task in tasks {
+        let mut failed = false;
+        for processor in processors {
+            let stage = processor.name();
+            if task.has_done(stage) || !processor.accepts(&task.path) {
+                continue;
+            }
+            match processor.process(&task).await {
+                Ok(()) => task.stage_succeeded(stage, Utc::now()),
+                Err(e) => {
+                    tracing::warn!("Processing stage {} failed for {}: {}", stage, task.path, e);
+                    task.stage_failed(stage, &e, policy, Utc::now());
+                    failed = true;
+                    break;
+                }
+            }
+        }
+        if !failed {
+            task.complete(Utc::now());
+        } else if task.status == ProcessingStatus::DeadLettered {
+            let mut event = AuditEvent::new(
+                "file_processing_dead_lettered",
+                json!({ "task_id": task.id, "path": task.path, "error": task.last_error }),
+            );
+            event.owner_id = Some(task.owner_id.clone());
+            audit.record(&event).await?;
+        }
+        repo.save(&task).await?;
+    }
+    Ok(count)
+}
+
+/// Put one of the owner's dead-lettered files back in the queue.
+pub async fn retry<R>(repo: &R, owner_id: &UserId, id: &Uuid) -> Result<ProcessingTask, String>
+where
+    R: ProcessingRepository + ?Sized,
+{
+    let mut task = repo
+        .find_by_id(id)
+        .await?
+        .filter(|t| &t.owner_id == owner_id)
+        .ok_or_else(|| "Processing task not found".to_string())?;
+    task.retry(Utc::now())?;
+    repo.save(&task).await?;
+    Ok(task)
+}
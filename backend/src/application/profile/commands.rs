@@ -0,0 +1,13 @@
+// Profile commands
+pub mod get_my_preferences;
+pub mod update_my_preferences;
+pub mod list_my_sessions;
+pub mod get_session_timeline;
+pub mod get_session_signaling;
+pub mod list_my_credentials;
+pub mod list_my_auth_sessions;
+pub mod revoke_my_auth_session;
+pub mod request_my_data_export;
+pub mod create_my_access_token;
+pub mod list_my_access_tokens;
+pub mod revoke_my_access_token;
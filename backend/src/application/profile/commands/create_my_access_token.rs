@@ -0,0 +1,43 @@
+use serde_json::json;
+use crate::application::ports::{AccessTokenRepository, AuditRepository};
+use crate::domain::entities::access_token::{AccessToken, TokenScope};
+use crate::domain::entities::audit_event::AuditEvent;
+use crate::domain::value_objects::UserRole;
+use crate::domain::value_objects::UserId;
+
+/// Create a token acting as the user within `scopes`. Returns it with its secret, which is not
+/// stored and cannot be shown again.
+pub async fn execute<R, A>(
+    tokens: &R,
+    audit: &A,
+    user_id: &UserId,
+    roles: &[UserRole],
+    name: &str,
+    scopes: Vec<TokenScope>,
+    lifetime_days: i64,
+) -> Result<(AccessToken, String), String>
+where
+    R: AccessTokenRepository + ?Sized,
+    A: AuditRepository + ?Sized,
+{
+    // The file and metrics APIs are for owners and admins; refuse scopes the user could never use
+    for scope in &scopes {
+        let usable = match scope {
+            TokenScope::FilesRead | TokenScope::FilesWrite => roles.contains(&UserRole::Owner),
+            TokenScope::MetricsRead => roles.contains(&UserRole::Owner) || roles.contains(&UserRole::SuperAdmin),
+        };
+        if !usable {
+            return Err(format!("Scope {} is not available to your account", scope.as_db_str()));
+        }
+    }
+    let (token, secret) = AccessToken::issue(user_id.clone(), name, scopes, lifetime_days)?;
+    tokens.save(&token).await?;
+
+    let mut event = AuditEvent::new(
+        "access_token_created",
+        json!({ "token_id": token.id, "name": token.name, "scopes": token.scopes, "expires_at": token.expires_at }),
+    );
+    event.user_id = Some(user_id.clone());
+    audit.record(&event).await?;
+    Ok((token, secret))
+}
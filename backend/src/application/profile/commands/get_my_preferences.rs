@@ -0,0 +1,27 @@
+use crate::application::ports::user_repository::UserRepository;
+use crate::application::ports::UserPreferencesRepository;
+use crate::domain::entities::user_preferences::UserPreferences;
+use crate::domain::value_objects::UserId;
+use shared::Locale;
+
+/// Stored preferences of the user, or the defaults when none were saved yet.
+pub async fn execute<U, P>(
+    user_repo: &U,
+    preferences_repo: &P,
+    user_id: &UserId,
+) -> Result<(Locale, UserPreferences), String>
+where
+    U: UserRepository + ?Sized,
+    P: UserPreferencesRepository + ?Sized,
+{
+    let locale = user_repo
+        .find_by_id(user_id)
+        .await?
+        .map(|u| u.locale())
+        .ok_or_else(|| "User not found".to_string())?;
+    let preferences = preferences_repo
+        .find(user_id)
+        .await?
+        .unwrap_or_else(|| UserPreferences::defaults(user_id.clone()));
+    Ok((locale, preferences))
+}
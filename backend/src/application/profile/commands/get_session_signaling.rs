@@ -0,0 +1,26 @@
+use crate::application::ports::session_repository::SessionRepository;
+use crate::application::sessions::affinity::{SessionAffinity, SessionLocation};
+use crate::domain::value_objects::UserId;
+use uuid::Uuid;
+
+/// The signaling WebSocket URL of a running session, on the instance that runs it. Visible to
+/// the same users as the session's timeline.
+pub async fn execute<R: SessionRepository + ?Sized>(
+    sessions: &R,
+    affinity: &SessionAffinity,
+    acting_id: &UserId,
+    administers: impl Fn(Uuid) -> bool,
+    session_id: &Uuid,
+) -> Result<String, String> {
+    let session = sessions
+        .find_by_id(session_id)
+        .await?
+        .filter(|s| administers(s.tenant_id) || &s.user_id == acting_id || s.acting_as_owner_id.as_ref() == Some(acting_id))
+        .ok_or_else(|| "Session not found".to_string())?;
+    let base_url = match affinity.locate(&session.id.to_string()).await? {
+        SessionLocation::Here => affinity.instance().websocket_base_url.clone(),
+        SessionLocation::Elsewhere(owner) => owner.websocket_base_url,
+        SessionLocation::Unowned => return Err("Session not found or no longer running".to_string()),
+    };
+    Ok(format!("{}/ws?session={}", base_url, session.id))
+}
@@ -0,0 +1,25 @@
+use crate::application::ports::session_repository::SessionRepository;
+use crate::application::sessions::timeline::SessionTimelines;
+use crate::domain::entities::session_timeline::SessionTimeline;
+use crate::domain::value_objects::UserId;
+use uuid::Uuid;
+
+/// A session's timeline is visible to whoever ran it, the owner whose vault it opened, and
+/// the admins of its tenant. Sessions that ended before timelines were recorded have an empty one.
+pub async fn execute<R: SessionRepository + ?Sized>(
+    sessions: &R,
+    timelines: &SessionTimelines,
+    acting_id: &UserId,
+    administers: impl Fn(Uuid) -> bool,
+    session_id: &Uuid,
+) -> Result<SessionTimeline, String> {
+    let session = sessions
+        .find_by_id(session_id)
+        .await?
+        .filter(|s| administers(s.tenant_id) || &s.user_id == acting_id || s.acting_as_owner_id.as_ref() == Some(acting_id))
+        .ok_or_else(|| "Session not found".to_string())?;
+    Ok(timelines
+        .find(&session.id)
+        .await?
+        .unwrap_or_else(|| SessionTimeline::new(session.id)))
+}
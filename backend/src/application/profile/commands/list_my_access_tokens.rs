@@ -0,0 +1,7 @@
+use crate::application::ports::AccessTokenRepository;
+use crate::domain::entities::access_token::AccessToken;
+use crate::domain::value_objects::UserId;
+
+pub async fn execute<R: AccessTokenRepository + ?Sized>(tokens: &R, user_id: &UserId) -> Result<Vec<AccessToken>, String> {
+    tokens.list_for_user(user_id).await
+}
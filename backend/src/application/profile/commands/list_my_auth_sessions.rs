@@ -0,0 +1,7 @@
+use crate::application::auth_sessions::AuthSessions;
+use crate::domain::entities::auth_session::AuthSession;
+use crate::domain::value_objects::UserId;
+
+pub async fn execute(auth_sessions: &AuthSessions, user_id: &UserId) -> Result<Vec<AuthSession>, String> {
+    auth_sessions.list(user_id).await
+}
@@ -0,0 +1,11 @@
+use crate::application::ports::CredentialRepository;
+use crate::domain::{Credential, UserId};
+
+pub async fn execute<R: CredentialRepository + ?Sized>(
+    credentials: &R,
+    user_id: &UserId,
+) -> Result<Vec<Credential>, String> {
+    let mut credentials = credentials.find_by_user_id(user_id).await?;
+    credentials.sort_by_key(|c| c.usage().created_at);
+    Ok(credentials)
+}
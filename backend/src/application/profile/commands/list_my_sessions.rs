@@ -0,0 +1,14 @@
+use crate::application::ports::pagination::{Page, PageRequest};
+use crate::application::ports::session_repository::{SessionFilter, SessionRepository};
+use crate::domain::entities::session::Session;
+use crate::domain::value_objects::UserId;
+
+pub async fn execute<R: SessionRepository + ?Sized>(
+    sessions: &R,
+    user_id: &UserId,
+    mut filter: SessionFilter,
+    page: &PageRequest,
+) -> Result<Page<Session>, String> {
+    filter.user_id = Some(user_id.clone());
+    sessions.list(&filter, page).await
+}
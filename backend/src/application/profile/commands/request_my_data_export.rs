@@ -0,0 +1,13 @@
+use crate::application::data_exports;
+use crate::application::ports::{AuditRepository, DataExportRepository};
+use crate::domain::entities::data_export::DataExport;
+use crate::domain::value_objects::UserId;
+
+/// Queue an archive of everything held about the caller.
+pub async fn execute<E, A>(exports: &E, audit: &A, user_id: &UserId) -> Result<DataExport, String>
+where
+    E: DataExportRepository + ?Sized,
+    A: AuditRepository + ?Sized,
+{
+    data_exports::queue(exports, audit, DataExport::for_self(user_id.clone())).await
+}
@@ -0,0 +1,19 @@
+use serde_json::json;
+use uuid::Uuid;
+use crate::application::ports::{AccessTokenRepository, AuditRepository};
+use crate::domain::entities::audit_event::AuditEvent;
+use crate::domain::value_objects::UserId;
+
+/// Revoke one of the user's tokens; it is refused from its next request on.
+pub async fn execute<R, A>(tokens: &R, audit: &A, user_id: &UserId, token_id: &Uuid) -> Result<(), String>
+where
+    R: AccessTokenRepository + ?Sized,
+    A: AuditRepository + ?Sized,
+{
+    if !tokens.revoke(user_id, token_id).await? {
+        return Err("Access token not found".to_string());
+    }
+    let mut event = AuditEvent::new("access_token_revoked", json!({ "token_id": token_id }));
+    event.user_id = Some(user_id.clone());
+    audit.record(&event).await
+}
@@ -0,0 +1,12 @@
+use uuid::Uuid;
+use crate::application::auth_sessions::AuthSessions;
+use crate::domain::value_objects::UserId;
+
+/// Sign out one of the user's devices; its token is refused from then on.
+pub async fn execute(auth_sessions: &AuthSessions, user_id: &UserId, session_id: &Uuid) -> Result<(), String> {
+    if auth_sessions.revoke(user_id, session_id).await? {
+        Ok(())
+    } else {
+        Err("Auth session not found".to_string())
+    }
+}
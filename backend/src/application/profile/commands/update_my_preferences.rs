@@ -0,0 +1,19 @@
+use crate::application::ports::UserPreferencesRepository;
+use crate::domain::entities::user_preferences::UserPreferences;
+use shared::Locale;
+
+/// Validate and store the user's preferences; the locale lives on the user profile.
+pub async fn execute<P>(
+    preferences_repo: &P,
+    locale: Locale,
+    mut preferences: UserPreferences,
+) -> Result<UserPreferences, String>
+where
+    P: UserPreferencesRepository + ?Sized,
+{
+    preferences.validate()?;
+    preferences.updated_at = chrono::Utc::now();
+
+    preferences_repo.save(&preferences, locale).await?;
+    Ok(preferences)
+}
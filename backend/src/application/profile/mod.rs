@@ -0,0 +1,3 @@
+// Profile - settings any authenticated user manages for themselves
+
+pub mod commands;
@@ -0,0 +1,350 @@
+// Use cases - owners' scripts creating and deactivating clients with a provisioning token
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+use crate::application::owner::commands::create_invitation::{self, CreateInvitationCommand};
+use crate::application::owner::scope::OwnerScope;
+use crate::domain::entities::audit_event::AuditEvent;
+use crate::domain::entities::file_permission::FilePermission;
+use crate::domain::entities::invitation::{GrantedPath, Invitation};
+use crate::domain::entities::provisioning_token::ProvisioningToken;
+use crate::domain::value_objects::{Email, UserId};
+use crate::infrastructure::AppState;
+
+/// Most clients one request may provision
+pub const MAX_CLIENTS_PER_REQUEST: usize = 500;
+
+/// How long a response is replayed for a retried `Idempotency-Key`
+pub fn idempotency_window() -> chrono::Duration {
+    chrono::Duration::hours(24)
+}
+
+fn active_by_default() -> bool {
+    true
+}
+
+/// The state a client should be in. Applying the same spec again changes nothing.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ClientSpec {
+    pub email: String,
+    /// `false` revokes the client's access to the vault
+    #[serde(default = "active_by_default")]
+    pub active: bool,
+    #[serde(default)]
+    pub granted_paths: Vec<GrantedPath>,
+    pub expires_in_hours: Option<i64>,
+    #[serde(default)]
+    pub require_email_verification: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProvisioningRequest {
+    pub users: Vec<ClientSpec>,
+    /// Report what would change without changing anything
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClientAction {
+    /// No account yet: invited, to accept with the link like any invitation
+    Invited,
+    /// An invitation is already pending
+    AlreadyInvited,
+    /// An existing account was given the paths it lacked
+    Granted,
+    Deactivated,
+    Unchanged,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ClientOutcome {
+    pub email: String,
+    pub action: ClientAction,
+    /// Only handed out once, like the invitation's own link
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invite_url: Option<String>,
+    pub permissions_granted: usize,
+    pub permissions_revoked: usize,
+    pub invitations_revoked: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ClientOutcome {
+    fn new(email: &str, action: ClientAction) -> Self {
+        Self {
+            email: email.to_string(),
+            action,
+            invite_url: None,
+            permissions_granted: 0,
+            permissions_revoked: 0,
+            invitations_revoked: 0,
+            error: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProvisioningReport {
+    pub dry_run: bool,
+    pub results: Vec<ClientOutcome>,
+}
+
+/// Identifies a request body, so an `Idempotency-Key` reused for another request is refused.
+pub fn request_hash(body: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(body))
+}
+
+/// Issue a token for the owner's scripts; the secret is returned once.
+pub async fn issue_token(state: &AppState, owner_id: &UserId, name: &str) -> Result<(ProvisioningToken, String), String> {
+    let (token, secret) = ProvisioningToken::issue(owner_id.clone(), name)?;
+    state.provisioning_repo.save_token(&token).await?;
+    let mut event = AuditEvent::new("provisioning_token_created", json!({ "token_id": token.id, "name": token.name }));
+    event.owner_id = Some(owner_id.clone());
+    event.user_id = Some(owner_id.clone());
+    state.audit_repo.record(&event).await?;
+    Ok((token, secret))
+}
+
+pub async fn revoke_token(state: &AppState, owner_id: &UserId, token_id: &Uuid) -> Result<(), String> {
+    if !state.provisioning_repo.revoke_token(owner_id, token_id).await? {
+        return Err("Token not found".to_string());
+    }
+    let mut event = AuditEvent::new("provisioning_token_revoked", json!({ "token_id": token_id }));
+    event.owner_id = Some(owner_id.clone());
+    event.user_id = Some(owner_id.clone());
+    state.audit_repo.record(&event).await
+}
+
+/// Bring each client to the state its spec describes, in order. A client that fails is
+/// reported and the rest are still applied.
+pub async fn apply(
+    state: &AppState,
+    owner_id: &UserId,
+    token_id: &Uuid,
+    request: ProvisioningRequest,
+    base_url: &str,
+) -> Result<ProvisioningReport, (StatusCode, String)> {
+    if request.users.len() > MAX_CLIENTS_PER_REQUEST {
+        return Err((StatusCode::BAD_REQUEST, format!("At most {MAX_CLIENTS_PER_REQUEST} users per request")));
+    }
+    let mut results = Vec::with_capacity(request.users.len());
+    for spec in &request.users {
+        let outcome = match apply_one(state, owner_id, spec, request.dry_run, base_url).await {
+            Ok(outcome) => outcome,
+            Err(e) => ClientOutcome { error: Some(e), ..ClientOutcome::new(&spec.email, ClientAction::Failed) },
+        };
+        results.push(outcome);
+    }
+
+    if !request.dry_run {
+        let count = |action: ClientAction| results.iter().filter(|r| r.action == action).count();
+        let mut event = AuditEvent::new(
+            "clients_provisioned",
+            json!({
+                "token_id": token_id,
+                "invited": count(ClientAction::Invited),
+                "granted": count(ClientAction::Granted),
+                "deactivated": count(ClientAction::Deactivated),
+                "failed": count(ClientAction::Failed),
+            }),
+        );
+        event.owner_id = Some(owner_id.clone());
+        state.audit_repo.record(&event).await.map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    }
+    Ok(ProvisioningReport { dry_run: request.dry_run, results })
+}
+
+async fn apply_one(
+    state: &AppState,
+    owner_id: &UserId,
+    spec: &ClientSpec,
+    dry_run: bool,
+    base_url: &str,
+) -> Result<ClientOutcome, String> {
+    let email = Email::new(spec.email.clone())?;
+    let pending: Vec<Invitation> = state
+        .invitation_repo
+        .find_by_owner(owner_id)
+        .await?
+        .into_iter()
+        .filter(|i| i.is_valid() && i.invitee_email.as_str() == email.as_str())
+        .collect();
+    let user = state.user_repo.find_by_email(&email).await?;
+    if user.as_ref().is_some_and(|u| u.id() == owner_id) {
+        return Err("An owner cannot provision themselves".to_string());
+    }
+
+    if !spec.active {
+        let mut outcome = ClientOutcome::new(email.as_str(), ClientAction::Unchanged);
+        for invitation in &pending {
+            if !dry_run {
+                state.invitation_repo.update_status(&invitation.id, "Revoked").await?;
+            }
+            outcome.invitations_revoked += 1;
+        }
+        if let Some(user) = &user {
+            for permission in state.file_permission_repo.find_by_owner_client(owner_id, user.id()).await? {
+                if !permission.is_active() {
+                    continue;
+                }
+                if !dry_run {
+                    state.file_permission_repo.revoke(&permission.id).await?;
+                }
+                outcome.permissions_revoked += 1;
+            }
+        }
+        if outcome.invitations_revoked + outcome.permissions_revoked > 0 {
+            outcome.action = ClientAction::Deactivated;
+        }
+        return Ok(outcome);
+    }
+
+    check_paths(&spec.granted_paths)?;
+    let now = chrono::Utc::now();
+    let expires_at = expiry(spec.expires_in_hours, now)?;
+
+    let Some(user) = user else {
+        if !pending.is_empty() {
+            return Ok(ClientOutcome::new(email.as_str(), ClientAction::AlreadyInvited));
+        }
+        // The invitee could never receive a code
+        if spec.require_email_verification && state.email_sender.is_none() {
+            return Err("Email verification requires email delivery to be configured".to_string());
+        }
+        let mut outcome = ClientOutcome::new(email.as_str(), ClientAction::Invited);
+        if !dry_run {
+            let cmd = CreateInvitationCommand {
+                owner_id: owner_id.clone(),
+                invitee_email: email.as_str().to_string(),
+                granted_paths: spec.granted_paths.clone(),
+                expires_in_hours: spec.expires_in_hours,
+                require_email_verification: spec.require_email_verification,
+            };
+            let created = create_invitation::execute(&*state.invitation_repo, cmd, &OwnerScope::Full, base_url).await?;
+            outcome.invite_url = Some(created.invite_url);
+        }
+        return Ok(outcome);
+    };
+
+    if user.status().is_deleting() {
+        return Err("Account is being deleted".to_string());
+    }
+    let held: Vec<FilePermission> = state
+        .file_permission_repo
+        .find_by_owner_client(owner_id, user.id())
+        .await?
+        .into_iter()
+        .filter(FilePermission::is_active)
+        .collect();
+    let mut outcome = ClientOutcome::new(email.as_str(), ClientAction::Unchanged);
+    for granted in &spec.granted_paths {
+        if held.iter().any(|p| grants(p, granted)) {
+            continue;
+        }
+        if !dry_run {
+            let permission = FilePermission {
+                id: Uuid::new_v4(),
+                owner_id: owner_id.clone(),
+                client_id: user.id().clone(),
+                path: granted.path.clone(),
+                access: granted.access.clone(),
+                granted_at: now,
+                expires_at,
+                revoked_at: None,
+                view_only: granted.view_only,
+                interaction: granted.interaction,
+                group_id: None,
+            };
+            state.file_permission_repo.save(&permission).await?;
+        }
+        outcome.permissions_granted += 1;
+    }
+    if outcome.permissions_granted > 0 {
+        outcome.action = ClientAction::Granted;
+    }
+    Ok(outcome)
+}
+
+/// When grants made now for `expires_in_hours` end
+fn expiry(
+    expires_in_hours: Option<i64>,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Result<Option<chrono::DateTime<chrono::Utc>>, String> {
+    let Some(hours) = expires_in_hours else {
+        return Ok(None);
+    };
+    if hours <= 0 {
+        return Err("expires_in_hours must be positive".to_string());
+    }
+    chrono::Duration::try_hours(hours)
+        .and_then(|d| now.checked_add_signed(d))
+        .map(Some)
+        .ok_or_else(|| "expires_in_hours is too large".to_string())
+}
+
+/// The same rule invitations apply: relative paths without `..`
+fn check_paths(paths: &[GrantedPath]) -> Result<(), String> {
+    for granted in paths {
+        if granted.path.contains("..") || granted.path.starts_with('/') {
+            return Err(format!("Invalid path {}: must be a relative path without '..'", granted.path));
+        }
+        if granted.access.is_empty() {
+            return Err(format!("No access level given for {}", granted.path));
+        }
+    }
+    Ok(())
+}
+
+/// Whether an active permission already gives exactly what the spec asks for
+fn grants(permission: &FilePermission, granted: &GrantedPath) -> bool {
+    permission.path == granted.path
+        && permission.view_only == granted.view_only
+        && permission.interaction == granted.interaction
+        && permission.access.len() == granted.access.len()
+        && granted.access.iter().all(|level| permission.access.contains(level))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::invitation::AccessLevel;
+
+    #[test]
+    fn test_grants() {
+        let permission = FilePermission {
+            id: Uuid::new_v4(),
+            owner_id: UserId::new(),
+            client_id: UserId::new(),
+            path: "photos".to_string(),
+            access: vec![AccessLevel::Read, AccessLevel::Write],
+            granted_at: chrono::Utc::now(),
+            expires_at: None,
+            revoked_at: None,
+            view_only: false,
+            interaction: Default::default(),
+            group_id: None,
+        };
+        let granted = |path: &str, access: Vec<AccessLevel>| GrantedPath { path: path.to_string(), access, view_only: false, interaction: Default::default() };
+        assert!(grants(&permission, &granted("photos", vec![AccessLevel::Write, AccessLevel::Read])));
+        assert!(!grants(&permission, &granted("photos", vec![AccessLevel::Read])));
+        assert!(!grants(&permission, &granted("docs", vec![AccessLevel::Read, AccessLevel::Write])));
+        assert!(check_paths(&[granted("../etc", vec![AccessLevel::Read])]).is_err());
+        assert!(check_paths(&[granted("photos", vec![])]).is_err());
+    }
+
+    #[test]
+    fn test_expiry_rejects_out_of_range_hours() {
+        let now = chrono::Utc::now();
+        assert_eq!(expiry(None, now), Ok(None));
+        assert_eq!(expiry(Some(2), now), Ok(Some(now + chrono::Duration::hours(2))));
+        assert!(expiry(Some(0), now).is_err());
+        assert!(expiry(Some(i64::MAX), now).is_err());
+        assert!(expiry(Some(i64::MAX / 3600), now).is_err());
+    }
+}
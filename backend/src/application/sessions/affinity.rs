@@ -0,0 +1,80 @@
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use crate::application::ports::{InstanceInfo, SessionOwnershipRepository};
+
+/// Where a session's display, app and peer connection live
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionLocation {
+    Here,
+    Elsewhere(InstanceInfo),
+    /// No live instance holds the session: it never existed, ended, or its instance stopped
+    Unowned,
+}
+
+/// Session state is held in the memory of the instance that launched it, so each session is
+/// claimed by that instance and signaling must reach it there.
+pub struct SessionAffinity {
+    instance: InstanceInfo,
+    claim_ttl_seconds: u64,
+    repo: Arc<dyn SessionOwnershipRepository>,
+    claimed: Mutex<HashSet<String>>,
+}
+
+impl SessionAffinity {
+    pub fn new(instance: InstanceInfo, repo: Arc<dyn SessionOwnershipRepository>, claim_ttl_seconds: u64) -> Self {
+        Self { instance, claim_ttl_seconds: claim_ttl_seconds.max(3), repo, claimed: Mutex::new(HashSet::new()) }
+    }
+
+    pub fn instance(&self) -> &InstanceInfo {
+        &self.instance
+    }
+
+    /// Claims are renewed three times per TTL, so one missed renewal does not lose them.
+    pub fn renew_interval(&self) -> Duration {
+        Duration::from_secs(self.claim_ttl_seconds / 3)
+    }
+
+    pub async fn claim(&self, session_id: &str) -> Result<(), String> {
+        if !self.repo.claim(session_id, &self.instance.id, self.claim_ttl_seconds).await? {
+            return Err("Session is running on another instance".to_string());
+        }
+        self.lock().insert(session_id.to_string());
+        Ok(())
+    }
+
+    pub async fn locate(&self, session_id: &str) -> Result<SessionLocation, String> {
+        Ok(match self.repo.owner(session_id).await? {
+            Some(owner) if owner.id == self.instance.id => SessionLocation::Here,
+            Some(owner) => SessionLocation::Elsewhere(owner),
+            None => SessionLocation::Unowned,
+        })
+    }
+
+    pub async fn release(&self, session_id: &str) -> Result<(), String> {
+        self.lock().remove(session_id);
+        self.repo.release(session_id, &self.instance.id).await
+    }
+
+    /// Announce this instance again and extend its claims. Returns the sessions another
+    /// instance took over meanwhile, whose local resources are now orphaned.
+    pub async fn renew(&self) -> Result<Vec<String>, String> {
+        self.repo.announce(&self.instance, self.claim_ttl_seconds).await?;
+        let claimed: Vec<String> = self.lock().iter().cloned().collect();
+        let mut lost = Vec::new();
+        for session_id in claimed {
+            if !self.repo.claim(&session_id, &self.instance.id, self.claim_ttl_seconds).await? {
+                lost.push(session_id);
+            }
+        }
+        let mut current = self.lock();
+        for session_id in &lost {
+            current.remove(session_id);
+        }
+        Ok(lost)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashSet<String>> {
+        self.claimed.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
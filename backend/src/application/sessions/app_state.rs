@@ -0,0 +1,65 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use crate::application::ports::AppStateRepository;
+use crate::domain::value_objects::{AppStateLimits, UserId};
+
+/// Whose state a session's app reads and writes: the launching user's, for that app
+#[derive(Debug, Clone)]
+pub struct AppStateScope {
+    pub user_id: UserId,
+    pub app_id: String,
+}
+
+/// Key-value state SDK apps keep per user across sessions, such as the last folder opened.
+pub struct AppStateStore {
+    repo: Arc<dyn AppStateRepository>,
+    limits: AppStateLimits,
+}
+
+impl AppStateStore {
+    pub fn new(repo: Arc<dyn AppStateRepository>, limits: AppStateLimits) -> Self {
+        Self { repo, limits }
+    }
+
+    /// Limits from `APP_STATE_MAX_VALUE_BYTES` and `APP_STATE_MAX_TOTAL_BYTES`, else the defaults
+    pub fn from_env(repo: Arc<dyn AppStateRepository>) -> Self {
+        let defaults = AppStateLimits::default();
+        let bytes = |name: &str, default: usize| {
+            std::env::var(name).ok().and_then(|v| v.parse::<usize>().ok()).unwrap_or(default)
+        };
+        let limits = AppStateLimits {
+            max_value_bytes: bytes("APP_STATE_MAX_VALUE_BYTES", defaults.max_value_bytes),
+            max_total_bytes: bytes("APP_STATE_MAX_TOTAL_BYTES", defaults.max_total_bytes),
+            ..defaults
+        };
+        Self::new(repo, limits)
+    }
+
+    /// Everything saved for the scope, handed to the app in its `Init`. Entries that are no
+    /// longer valid JSON are left out.
+    pub async fn load_all(&self, scope: &AppStateScope) -> Result<BTreeMap<String, serde_json::Value>, String> {
+        let entries = self.repo.find_all(&scope.user_id, &scope.app_id).await?;
+        Ok(entries
+            .into_iter()
+            .filter_map(|(key, value)| serde_json::from_str(&value).ok().map(|value| (key, value)))
+            .collect())
+    }
+
+    /// The value under `key`, null when there is none
+    pub async fn load(&self, scope: &AppStateScope, key: &str) -> Result<serde_json::Value, String> {
+        let value = self.repo.find(&scope.user_id, &scope.app_id, key).await?;
+        Ok(value.and_then(|value| serde_json::from_str(&value).ok()).unwrap_or_default())
+    }
+
+    /// Store `value` under `key`, or forget the key when `value` is null. Fails with the reason
+    /// when the entry does not fit the limits.
+    pub async fn save(&self, scope: &AppStateScope, key: &str, value: &serde_json::Value) -> Result<(), String> {
+        if value.is_null() {
+            return self.repo.delete(&scope.user_id, &scope.app_id, key).await;
+        }
+        let value = value.to_string();
+        let others = self.repo.size_without(&scope.user_id, &scope.app_id, key).await?;
+        self.limits.check(key, value.len(), others)?;
+        self.repo.save(&scope.user_id, &scope.app_id, key, &value).await
+    }
+}
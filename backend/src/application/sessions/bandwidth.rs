@@ -0,0 +1,56 @@
+use std::sync::Arc;
+use chrono::{DateTime, Utc};
+use crate::application::ports::BandwidthRepository;
+use crate::domain::entities::session::Session;
+use crate::domain::value_objects::UserId;
+
+/// Whose bandwidth a session spends: the user's, counted against the vault it runs in
+#[derive(Debug, Clone)]
+pub struct BandwidthScope {
+    pub owner_id: UserId,
+    pub user_id: UserId,
+}
+
+impl BandwidthScope {
+    /// An owner's own sessions count against their own vault
+    pub fn for_session(session: &Session) -> Self {
+        Self {
+            owner_id: session.acting_as_owner_id.clone().unwrap_or_else(|| session.user_id.clone()),
+            user_id: session.user_id.clone(),
+        }
+    }
+}
+
+/// The month usage is counted under, e.g. `2026-10`
+pub fn month_of(at: DateTime<Utc>) -> String {
+    at.format("%Y-%m").to_string()
+}
+
+/// Counts the video bytes sent to each user and works out the bitrate their sessions may use
+/// under the caps their vault's owner set.
+pub struct BandwidthAccounting {
+    repo: Arc<dyn BandwidthRepository>,
+}
+
+impl BandwidthAccounting {
+    pub fn new(repo: Arc<dyn BandwidthRepository>) -> Self {
+        Self { repo }
+    }
+
+    /// Highest bitrate the scope's sessions may stream at right now, if capped
+    pub async fn ceiling(&self, scope: &BandwidthScope) -> Result<Option<u32>, String> {
+        let Some(caps) = self.repo.find_caps(&scope.owner_id, &scope.user_id).await? else {
+            return Ok(None);
+        };
+        let used = self.repo.usage(&scope.owner_id, &scope.user_id, &month_of(Utc::now())).await?;
+        Ok(caps.bitrate_ceiling(used))
+    }
+
+    /// Add bytes sent to the scope's sessions to this month's total
+    pub async fn record(&self, scope: &BandwidthScope, bytes: u64) -> Result<(), String> {
+        if bytes == 0 {
+            return Ok(());
+        }
+        self.repo.add_usage(&scope.owner_id, &scope.user_id, &month_of(Utc::now()), bytes).await
+    }
+}
@@ -0,0 +1,41 @@
+use crate::application::ports::pagination::{PageRequest, MAX_PAGE_SIZE};
+use crate::application::ports::session_repository::{SessionFilter, SessionRepository};
+use crate::application::sessions::affinity::{SessionAffinity, SessionLocation};
+use crate::application::sessions::timeline::SessionTimelines;
+use crate::domain::entities::session_timeline::TimelineStage;
+
+/// Take over the running sessions of instances that stopped renewing their claims. Their app
+/// and display died with the instance, so each is terminated for its user to relaunch; the
+/// claim keeps other instances from ending it at the same time. Returns how many were ended.
+pub async fn execute<R: SessionRepository + ?Sized>(
+    sessions: &R,
+    affinity: &SessionAffinity,
+    timelines: &SessionTimelines,
+) -> Result<usize, String> {
+    let filter = SessionFilter { state: Some("ready".to_string()), ..Default::default() };
+    let mut running = Vec::new();
+    let mut cursor: Option<String> = None;
+    loop {
+        let page = PageRequest::new(Some(MAX_PAGE_SIZE), cursor.as_deref(), None)?;
+        let found = sessions.list(&filter, &page).await?;
+        running.extend(found.items.into_iter().map(|session| session.id));
+        cursor = found.next_cursor;
+        if cursor.is_none() {
+            break;
+        }
+    }
+
+    let mut ended = 0;
+    for id in running {
+        let session_id = id.to_string();
+        if affinity.locate(&session_id).await? != SessionLocation::Unowned || affinity.claim(&session_id).await.is_err() {
+            continue;
+        }
+        timelines.record(&session_id, TimelineStage::Disconnected, Some("Instance running the session stopped".to_string()));
+        sessions.terminate(&id).await?;
+        timelines.finish(&session_id).await?;
+        affinity.release(&session_id).await?;
+        ended += 1;
+    }
+    Ok(ended)
+}
@@ -0,0 +1,18 @@
+use chrono::{Duration, Utc};
+use crate::application::ports::session_repository::SessionRepository;
+use crate::application::ports::session_snapshot_repository::SessionSnapshotRepository;
+
+/// End sessions suspended longer than `retention`, dropping the state their app saved.
+/// Returns how many were ended.
+pub async fn execute<S, R>(snapshots: &S, sessions: &R, retention: Duration) -> Result<usize, String>
+where
+    S: SessionSnapshotRepository + ?Sized,
+    R: SessionRepository + ?Sized,
+{
+    let expired = snapshots.find_suspended_before(Utc::now() - retention).await?;
+    for id in &expired {
+        snapshots.delete(id).await?;
+        sessions.terminate(id).await?;
+    }
+    Ok(expired.len())
+}
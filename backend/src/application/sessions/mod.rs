@@ -0,0 +1,12 @@
+// Streaming sessions - lifecycle tracking shared by the launch, signaling and cleanup paths
+pub mod affinity;
+pub mod app_state;
+pub mod bandwidth;
+pub mod end_orphaned;
+pub mod expire_suspended;
+pub mod resume;
+pub mod scheduler;
+pub mod scope;
+pub mod stream_budget;
+pub mod suspend;
+pub mod timeline;
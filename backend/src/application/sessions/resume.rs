@@ -0,0 +1,56 @@
+use axum::http::StatusCode;
+use uuid::Uuid;
+use crate::application::client::commands::launch_application::{self, LaunchOrigin, LaunchParameters, LaunchResult};
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+
+/// Relaunch a suspended session's app with the state it saved, as a new session sized for the
+/// device resuming it. Vault access is worked out again, so permissions revoked while the
+/// session was suspended no longer apply. The suspended session ends once the new one runs.
+pub async fn execute(
+    state: &AppState,
+    user: &AuthenticatedUser,
+    session_id: &Uuid,
+    width: Option<u16>,
+    height: Option<u16>,
+    scale_factor: Option<f32>,
+    placed_on: Option<&str>,
+) -> Result<LaunchResult, (StatusCode, String)> {
+    let snapshot = state
+        .session_snapshot_repo
+        .find(session_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+        .filter(|s| s.user_id == user.id)
+        .ok_or_else(|| (StatusCode::NOT_FOUND, "No suspended session found".to_string()))?;
+
+    // Taken first, so a second resume of the same session finds nothing
+    state
+        .session_snapshot_repo
+        .delete(session_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    let launched = launch_application::execute(
+        state,
+        user,
+        &snapshot.app_id,
+        LaunchParameters { width, height, scale_factor, ..Default::default() },
+        LaunchOrigin { placed_on, resume_state: snapshot.state.clone(), impersonating: None },
+    )
+    .await;
+    let launched = match launched {
+        Ok(launched) => launched,
+        Err(e) => {
+            // Still resumable, e.g. from the instance the scheduler redirected to
+            let _ = state.session_snapshot_repo.save(&snapshot).await;
+            return Err(e);
+        }
+    };
+
+    state
+        .session_repo
+        .terminate(session_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    Ok(launched)
+}
@@ -0,0 +1,55 @@
+use std::sync::Arc;
+use crate::application::ports::SchedulerRepository;
+use crate::domain::entities::placement::{self, HostStatus, OvercommitPolicy, PlacementDecision};
+use crate::domain::value_objects::ResourceClass;
+
+/// Places new sessions on the host with room for them. Every instance publishes its own
+/// status; an instance that cannot reach the others still places sessions on itself.
+pub struct Scheduler {
+    repo: Arc<dyn SchedulerRepository>,
+    policy: OvercommitPolicy,
+    host_ttl_seconds: u64,
+}
+
+impl Scheduler {
+    pub fn new(repo: Arc<dyn SchedulerRepository>, policy: OvercommitPolicy, host_ttl_seconds: u64) -> Self {
+        Self { repo, policy, host_ttl_seconds }
+    }
+
+    pub fn policy(&self) -> &OvercommitPolicy {
+        &self.policy
+    }
+
+    pub async fn publish(&self, local: &HostStatus) -> Result<(), String> {
+        self.repo.publish_host(local, self.host_ttl_seconds).await
+    }
+
+    /// The hosts to choose from. `local` is fresher than what this instance last published,
+    /// so it replaces that entry.
+    pub async fn hosts(&self, local: &HostStatus) -> Vec<HostStatus> {
+        let mut hosts = match self.repo.hosts().await {
+            Ok(hosts) => hosts,
+            Err(e) => {
+                tracing::warn!("Scheduling on this host only, other hosts unknown: {}", e);
+                Vec::new()
+            }
+        };
+        hosts.retain(|host| host.instance_id != local.instance_id);
+        hosts.push(local.clone());
+        hosts
+    }
+
+    /// Choose the host for a session of `app_id`; the decision is logged for the admin view.
+    pub async fn place(&self, app_id: &str, class: ResourceClass, local: &HostStatus) -> (PlacementDecision, Vec<HostStatus>) {
+        let hosts = self.hosts(local).await;
+        let decision = placement::place(app_id, class, &hosts, &self.policy, chrono::Utc::now());
+        if let Err(e) = self.repo.record_decision(&decision).await {
+            tracing::warn!("Failed to record placement of {}: {}", app_id, e);
+        }
+        (decision, hosts)
+    }
+
+    pub async fn recent_decisions(&self, limit: usize) -> Result<Vec<PlacementDecision>, String> {
+        self.repo.recent_decisions(limit).await
+    }
+}
@@ -0,0 +1,206 @@
+use std::path::Path;
+use chrono::{DateTime, Utc};
+use crate::application::owner::commands::watch_session;
+use crate::domain::entities::file_permission::FilePermission;
+use crate::domain::entities::impersonation_consent::IMPERSONATION_ROLE;
+use crate::domain::entities::legal_hold::LegalHold;
+use crate::domain::entities::session::Session;
+use crate::domain::entities::session_timeline::TimelineStage;
+use crate::domain::services::permission_evaluator::{Authority, PermissionEvaluator};
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::driven::sandbox::xvfb::LaunchedSandbox;
+use crate::infrastructure::driven::storage::vault_dir;
+use crate::infrastructure::AppState;
+
+/// The folders `permissions` open in the vault at `root`, as paths inside a session's sandbox.
+pub fn allowed_paths(root: &str, permissions: &[FilePermission]) -> Vec<String> {
+    permissions.iter().map(|p| format!("{}/{}", root, p.path)).collect()
+}
+
+/// The part of `allowed` inside `sandbox`, the folders a running app was launched with. Its
+/// sandbox cannot widen while it runs, so a grant above a launched folder opens only that
+/// folder, and a grant beside them waits for the next launch.
+pub fn narrow(allowed: &[String], sandbox: &[String]) -> Vec<String> {
+    let mut scope: Vec<String> = Vec::new();
+    for path in allowed {
+        for launched in sandbox {
+            let inner = if Path::new(path).starts_with(launched) {
+                path
+            } else if Path::new(launched).starts_with(path) {
+                launched
+            } else {
+                continue;
+            };
+            if !scope.contains(inner) {
+                scope.push(inner.clone());
+            }
+        }
+    }
+    scope
+}
+
+/// The paths under an active hold in the part of the vault at `root` a sandbox reaches:
+/// `allowed`, or the whole vault when empty. A hold above an allowed folder holds all of it.
+pub fn held_paths(root: &str, holds: &[LegalHold], allowed: &[String], now: DateTime<Utc>) -> Vec<String> {
+    let sandbox = if allowed.is_empty() { vec![root.to_string()] } else { allowed.to_vec() };
+    let held: Vec<String> = holds
+        .iter()
+        .filter(|hold| hold.is_active(now))
+        .map(|hold| format!("{}/{}", root, hold.path))
+        .collect();
+    narrow(&held, &sandbox)
+}
+
+/// Bring the sessions running here in line with their current permissions and holds, those
+/// of `client_id` only when given. A session whose app could change files placed under hold
+/// since its launch is ended, its sandbox being fixed. So is a client session left with less
+/// than the folders it was launched with, and owners' watches no longer granted; other client
+/// apps are told their current permissions. Returns how many sessions changed.
+pub async fn refresh(state: &AppState, client_id: Option<&UserId>) -> Result<usize, String> {
+    let mut changed = 0;
+    let mut ended = Vec::new();
+    for (session_id, sandbox) in state.xvfb_manager.launched_sandboxes().await {
+        let Some(session) = find_session(state, &session_id, client_id).await? else {
+            continue;
+        };
+        if !holds_covered(state, &session, &sandbox).await? {
+            end_session(state, &session, "Files placed under legal hold").await?;
+            ended.push(session_id);
+            changed += 1;
+        }
+    }
+    for (session_id, sandbox) in state.ipc_server.scoped_sessions().await {
+        if ended.contains(&session_id) {
+            continue;
+        }
+        let Some(session) = find_session(state, &session_id, client_id).await? else {
+            continue;
+        };
+        if refresh_session(state, &session, &sandbox).await? {
+            changed += 1;
+        }
+    }
+    end_ungranted_watches(state, client_id).await?;
+    Ok(changed)
+}
+
+/// The session running as `session_id`, unless it belongs to someone other than `client_id`.
+async fn find_session(state: &AppState, session_id: &str, client_id: Option<&UserId>) -> Result<Option<Session>, String> {
+    let Ok(id) = uuid::Uuid::parse_str(session_id) else {
+        return Ok(None);
+    };
+    let session = state.session_repo.find_by_id(&id).await?;
+    Ok(session.filter(|session| client_id.is_none_or(|client_id| &session.user_id == client_id)))
+}
+
+/// Whether every path now under hold in the session's sandbox was already read-only there.
+async fn holds_covered(state: &AppState, session: &Session, sandbox: &LaunchedSandbox) -> Result<bool, String> {
+    let owner_id = session.acting_as_owner_id.clone().unwrap_or_else(|| session.user_id.clone());
+    let root = vault_dir(Path::new(&state.storage_path), session.tenant_id, &owner_id.to_string()).display().to_string();
+    let holds = state.legal_hold_repo.list_for_owner(&owner_id).await?;
+    let held = held_paths(&root, &holds, &sandbox.data_paths, Utc::now());
+    Ok(held
+        .iter()
+        .all(|path| sandbox.held_paths.iter().any(|read_only| Path::new(path).starts_with(read_only))))
+}
+
+async fn end_session(state: &AppState, session: &Session, reason: &str) -> Result<(), String> {
+    let session_id = session.id.to_string();
+    tracing::info!("Ending session {}: {}", session_id, reason);
+    state.session_timelines.record(&session_id, TimelineStage::Disconnected, Some(reason.to_string()));
+    let _ = state.xvfb_manager.cleanup_session(&session_id).await;
+    state.session_repo.terminate(&session.id).await?;
+    state.session_timelines.finish(&session_id).await?;
+    state.session_affinity.release(&session_id).await
+}
+
+/// End the watches of owners who no longer grant the watched session's user anything.
+async fn end_ungranted_watches(state: &AppState, client_id: Option<&UserId>) -> Result<(), String> {
+    for (key, watch) in state.webrtc.active_watches().await {
+        if client_id.is_some_and(|client_id| &watch.session.user_id != client_id) {
+            continue;
+        }
+        if !watch_session::is_granted(&*state.file_permission_repo, &watch.owner_id, &watch.session).await? {
+            state.webrtc.end_watch(&key).await;
+        }
+    }
+    Ok(())
+}
+
+/// [`refresh`] right after an owner changed permissions or holds, so running apps see the change at
+/// once. Failures are only logged: the change itself went through, and the periodic refresh
+/// tries again.
+pub async fn refresh_after_change(state: &AppState, client_id: Option<&UserId>) {
+    if let Err(e) = refresh(state, client_id).await {
+        tracing::warn!("Failed to update running sessions after a permission change: {}", e);
+    }
+}
+
+async fn refresh_session(state: &AppState, session: &Session, sandbox: &[String]) -> Result<bool, String> {
+    let Some(owner_id) = session.acting_as_owner_id.clone() else {
+        return Ok(false);
+    };
+    let mut permissions: Vec<FilePermission> = state
+        .file_permission_repo
+        .find_active_for_client(&session.user_id)
+        .await?
+        .into_iter()
+        .filter(|p| p.owner_id == owner_id)
+        .collect();
+    // An owner acting as the client stays read-only, as at launch
+    if session.active_role == IMPERSONATION_ROLE {
+        for permission in &mut permissions {
+            permission.view_only = true;
+        }
+    }
+    let root = vault_dir(Path::new(&state.storage_path), session.tenant_id, &owner_id.to_string()).display().to_string();
+    let allowed = narrow(&allowed_paths(&root, &permissions), sandbox);
+    let session_id = session.id.to_string();
+
+    // The app's sandbox still reaches every launched folder, so telling it over IPC that a
+    // folder is gone would not keep it out: a session losing any of them ends
+    if sandbox.iter().any(|launched| !allowed.contains(launched)) {
+        end_session(state, session, "Permissions revoked").await?;
+        return Ok(true);
+    }
+
+    let holds = state.legal_hold_repo.list_for_owner(&owner_id).await?;
+    let permissions = PermissionEvaluator::new(Authority::Client(permissions), holds);
+    state
+        .ipc_server
+        .update_scope(&session_id, permissions, allowed)
+        .await
+        .map_err(|e| format!("Failed to update session {}: {}", session_id, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_held_paths_within_the_sandbox() {
+        let hold = |path: &str| LegalHold::new(UserId::new(), path, "tax records", UserId::new(), None).unwrap();
+        let mut released = hold("photos");
+        released.released_at = Some(Utc::now());
+        let holds = vec![hold("docs/taxes"), hold("music"), released];
+        let root = "/vault/o1";
+        assert_eq!(held_paths(root, &holds, &[], Utc::now()), ["/vault/o1/docs/taxes", "/vault/o1/music"]);
+        // A client sees a hold inside its folders, or all of a folder inside a hold
+        let allowed = vec!["/vault/o1/docs".to_string(), "/vault/o1/music/live".to_string(), "/vault/o1/photos".to_string()];
+        assert_eq!(held_paths(root, &holds, &allowed, Utc::now()), ["/vault/o1/docs/taxes", "/vault/o1/music/live"]);
+    }
+
+    #[test]
+    fn test_narrow_keeps_scope_inside_the_launched_folders() {
+        let sandbox = vec!["/vault/o1/docs".to_string(), "/vault/o1/photos/2024".to_string()];
+        let allowed = vec![
+            "/vault/o1/docs/tax".to_string(),
+            "/vault/o1/photos".to_string(),
+            "/vault/o1/music".to_string(),
+            "/vault/o1/docs".to_string(),
+        ];
+        assert_eq!(narrow(&allowed, &sandbox), ["/vault/o1/docs/tax", "/vault/o1/photos/2024", "/vault/o1/docs"]);
+        // Sibling names sharing a prefix are not inside each other
+        assert!(narrow(&["/vault/o1/docs-old".to_string()], &sandbox).is_empty());
+    }
+}
@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, RwLock};
+use tokio::sync::watch;
+use crate::domain::entities::stream_budget::{StreamBudget, StreamDemand, StreamShare};
+
+/// Shares the host's encoding budget between the sessions streaming at the moment, so a few
+/// large streams cannot starve the others of bandwidth or encoder CPU. Shares are worked out
+/// again whenever a stream joins, leaves or changes what it asks for, and each stream is told
+/// its new share through its receiver.
+pub struct StreamBudgetController {
+    budget: RwLock<StreamBudget>,
+    streams: Mutex<HashMap<String, (StreamDemand, watch::Sender<StreamShare>)>>,
+}
+
+impl StreamBudgetController {
+    pub fn new(budget: StreamBudget) -> Self {
+        Self { budget: RwLock::new(budget), streams: Mutex::new(HashMap::new()) }
+    }
+
+    /// Totals from `STREAM_TOTAL_BITRATE` (bits/s) and `STREAM_TOTAL_PIXEL_RATE` (pixels/s), as
+    /// read by `setting`; without them streams are not limited by each other.
+    pub fn configured_budget(setting: impl Fn(&str) -> Option<u64>) -> StreamBudget {
+        let total = |name: &str| setting(name).filter(|v| *v > 0);
+        StreamBudget {
+            total_bitrate: total("STREAM_TOTAL_BITRATE"),
+            total_pixel_rate: total("STREAM_TOTAL_PIXEL_RATE"),
+        }
+    }
+
+    /// Share a new budget between the running streams.
+    pub fn set_budget(&self, budget: StreamBudget) {
+        let streams = self.lock();
+        *self.budget.write().unwrap_or_else(|e| e.into_inner()) = budget;
+        self.rebalance(&streams);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, (StreamDemand, watch::Sender<StreamShare>)>> {
+        self.streams.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Start sharing the budget with `session_id`'s stream, replacing an earlier entry of the
+    /// session. The receiver holds the stream's current share.
+    pub fn join(&self, session_id: &str, demand: StreamDemand) -> watch::Receiver<StreamShare> {
+        let (sender, receiver) = watch::channel(StreamShare::default());
+        let mut streams = self.lock();
+        streams.insert(session_id.to_string(), (demand, sender));
+        self.rebalance(&streams);
+        receiver
+    }
+
+    /// Take a new demand of the stream into account, e.g. after a quality or size change.
+    pub fn update(&self, session_id: &str, demand: StreamDemand) {
+        let mut streams = self.lock();
+        let Some(entry) = streams.get_mut(session_id) else { return };
+        if entry.0 == demand {
+            return;
+        }
+        entry.0 = demand;
+        self.rebalance(&streams);
+    }
+
+    /// Give the stream's share back to the others.
+    pub fn leave(&self, session_id: &str) {
+        let mut streams = self.lock();
+        if streams.remove(session_id).is_some() {
+            self.rebalance(&streams);
+        }
+    }
+
+    fn rebalance(&self, streams: &HashMap<String, (StreamDemand, watch::Sender<StreamShare>)>) {
+        let budget = *self.budget.read().unwrap_or_else(|e| e.into_inner());
+        let entries: Vec<_> = streams.values().collect();
+        // Without a budget every stream keeps the default, unlimited share
+        let shares = if budget == StreamBudget::default() {
+            vec![StreamShare::default(); entries.len()]
+        } else {
+            let demands: Vec<StreamDemand> = entries.iter().map(|(demand, _)| *demand).collect();
+            budget.allocate(&demands)
+        };
+        for ((_, sender), share) in entries.into_iter().zip(shares) {
+            sender.send_if_modified(|current| {
+                let changed = *current != share;
+                *current = share;
+                changed
+            });
+        }
+    }
+}
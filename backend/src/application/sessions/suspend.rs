@@ -0,0 +1,103 @@
+use std::time::Duration;
+use axum::http::StatusCode;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use crate::application::sessions::affinity::SessionLocation;
+use crate::domain::entities::session_snapshot::SessionSnapshot;
+use crate::domain::entities::session_timeline::TimelineStage;
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+
+#[derive(Debug, serde::Serialize)]
+pub struct SuspendResult {
+    pub session_id: String,
+    /// False when the app saved nothing, so it starts fresh on resume
+    pub state_saved: bool,
+    pub suspended_at: DateTime<Utc>,
+}
+
+/// How long the app may take to save its state (`SESSION_SUSPEND_TIMEOUT_SECS`)
+fn suspend_timeout() -> Duration {
+    let secs = std::env::var("SESSION_SUSPEND_TIMEOUT_SECS")
+        .ok()
+        .and_then(|s| s.parse::<u64>().ok())
+        .unwrap_or(5);
+    Duration::from_secs(secs)
+}
+
+/// Pause a running session: the app saves its state, which is stored with the session, then
+/// the app, display and stream are stopped. The session stays `suspended` until resumed or
+/// past the retention period. Only runs on the instance that holds the session.
+pub async fn execute(
+    state: &AppState,
+    user: &AuthenticatedUser,
+    session_id: &Uuid,
+) -> Result<SuspendResult, (StatusCode, String)> {
+    let session = state
+        .session_repo
+        .find_by_id(session_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+        .filter(|s| s.user_id == user.id || user.administers(s.tenant_id))
+        .ok_or_else(|| (StatusCode::NOT_FOUND, "Session not found".to_string()))?;
+    if !session.is_active() || session.state == "suspended" {
+        return Err((StatusCode::CONFLICT, "Session is not running".to_string()));
+    }
+
+    let sid = session.id.to_string();
+    let location = state
+        .session_affinity
+        .locate(&sid)
+        .await
+        .map_err(|e| (StatusCode::SERVICE_UNAVAILABLE, e))?;
+    match location {
+        SessionLocation::Here => {}
+        SessionLocation::Elsewhere(owner) => {
+            return Err((StatusCode::MISDIRECTED_REQUEST, format!("Session runs on instance {}", owner.id)));
+        }
+        SessionLocation::Unowned => return Err((StatusCode::CONFLICT, "Session is not running".to_string())),
+    }
+
+    // Apps that predate suspend do not answer; they start fresh on resume
+    let saved = match state.ipc_server.request_suspend(&sid, suspend_timeout()).await {
+        Ok(saved) => Some(saved),
+        Err(e) => {
+            tracing::warn!("Session {} suspends without app state: {:#}", sid, e);
+            None
+        }
+    };
+    let snapshot = SessionSnapshot {
+        session_id: session.id,
+        user_id: session.user_id.clone(),
+        app_id: session.app_id.clone(),
+        state: saved,
+        suspended_at: Utc::now(),
+    };
+    state
+        .session_snapshot_repo
+        .save(&snapshot)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    state
+        .session_repo
+        .update_state(&session.id, "suspended")
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    let detail = match &snapshot.state {
+        Some(saved) => format!("{} bytes of app state", saved.len()),
+        None => "no app state".to_string(),
+    };
+    state.session_timelines.record(&sid, TimelineStage::Suspended, Some(detail));
+    if let Err(e) = state.webrtc.suspend(&sid).await {
+        tracing::warn!("Failed to stop streaming suspended session {}: {:#}", sid, e);
+    }
+    if let Err(e) = state.session_timelines.finish(&sid).await {
+        tracing::warn!("Failed to save timeline of session {}: {}", sid, e);
+    }
+    if let Err(e) = state.session_affinity.release(&sid).await {
+        tracing::warn!("Failed to release session {}: {}", sid, e);
+    }
+
+    Ok(SuspendResult { session_id: sid, state_saved: snapshot.state.is_some(), suspended_at: snapshot.suspended_at })
+}
@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use chrono::Utc;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+use crate::application::ports::SessionTimelineRepository;
+use crate::domain::entities::session_timeline::{SessionTimeline, TimelineEvent, TimelineStage};
+
+/// Events of all sessions that may wait for a slow listener
+const LISTENER_BACKLOG: usize = 256;
+
+/// Timelines of running sessions, kept in memory and persisted once the session ends.
+/// Recording is synchronous so GStreamer threads and WebRTC callbacks can call it directly.
+pub struct SessionTimelines {
+    live: Mutex<HashMap<Uuid, SessionTimeline>>,
+    repo: Arc<dyn SessionTimelineRepository>,
+    /// Every recorded event, for following a session's launch as it happens
+    events: broadcast::Sender<(Uuid, TimelineEvent)>,
+}
+
+impl SessionTimelines {
+    pub fn new(repo: Arc<dyn SessionTimelineRepository>) -> Self {
+        let (events, _) = broadcast::channel(LISTENER_BACKLOG);
+        Self { live: Mutex::new(HashMap::new()), repo, events }
+    }
+
+    pub fn record(&self, session_id: &str, stage: TimelineStage, detail: Option<String>) {
+        let Ok(id) = Uuid::parse_str(session_id) else {
+            return;
+        };
+        let mut live = self.live.lock().unwrap_or_else(|e| e.into_inner());
+        let timeline = live.entry(id).or_insert_with(|| SessionTimeline::new(id));
+        let before = timeline.events.len();
+        timeline.record(stage, detail, Utc::now());
+        if let Some(event) = timeline.events.get(before) {
+            // Nobody listening is the usual case
+            let _ = self.events.send((id, event.clone()));
+        }
+    }
+
+    /// The session's events so far, and a receiver of the events recorded from now on for
+    /// every session. Taken together, so no event falls between the two.
+    pub fn follow(&self, session_id: &Uuid) -> (Vec<TimelineEvent>, broadcast::Receiver<(Uuid, TimelineEvent)>) {
+        let live = self.live.lock().unwrap_or_else(|e| e.into_inner());
+        let past = live.get(session_id).map(|timeline| timeline.events.clone()).unwrap_or_default();
+        (past, self.events.subscribe())
+    }
+
+    /// Milliseconds from the session's first event to `stage`, while the session runs.
+    pub fn elapsed_at(&self, session_id: &str, stage: TimelineStage) -> Option<u64> {
+        let id = Uuid::parse_str(session_id).ok()?;
+        let live = self.live.lock().unwrap_or_else(|e| e.into_inner());
+        live.get(&id)?.elapsed_at(stage)
+    }
+
+    /// A recorder bound to one session, for tasks that outlive the caller.
+    pub fn recorder(self: &Arc<Self>, session_id: &str) -> TimelineRecorder {
+        TimelineRecorder { timelines: Arc::clone(self), session_id: session_id.to_string() }
+    }
+
+    /// Record the cleanup and move the timeline from memory to the database.
+    pub async fn finish(&self, session_id: &str) -> Result<(), String> {
+        self.record(session_id, TimelineStage::CleanedUp, None);
+        let Ok(id) = Uuid::parse_str(session_id) else {
+            return Ok(());
+        };
+        let timeline = self.live.lock().unwrap_or_else(|e| e.into_inner()).remove(&id);
+        match timeline {
+            Some(timeline) => self.repo.save(&timeline).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Drop events recorded after the session's timeline was finished, e.g. the disconnect of a
+    /// suspended session's client, so they do not replace the stored timeline.
+    pub fn discard(&self, session_id: &str) {
+        if let Ok(id) = Uuid::parse_str(session_id) {
+            self.live.lock().unwrap_or_else(|e| e.into_inner()).remove(&id);
+        }
+    }
+
+    /// The live timeline of a running session, else the stored one.
+    pub async fn find(&self, session_id: &Uuid) -> Result<Option<SessionTimeline>, String> {
+        let live = self.live.lock().unwrap_or_else(|e| e.into_inner()).get(session_id).cloned();
+        match live {
+            Some(timeline) => Ok(Some(timeline)),
+            None => self.repo.find(session_id).await,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct TimelineRecorder {
+    timelines: Arc<SessionTimelines>,
+    session_id: String,
+}
+
+impl TimelineRecorder {
+    pub fn record(&self, stage: TimelineStage, detail: Option<String>) {
+        self.timelines.record(&self.session_id, stage, detail);
+    }
+}
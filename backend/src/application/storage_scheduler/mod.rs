@@ -0,0 +1,131 @@
+// Storage access scheduler - hold deferrable disk work until the disk is awake anyway, so
+// the disks of NAS hosts can spin down
+use std::collections::BTreeMap;
+use std::sync::RwLock;
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use crate::infrastructure::driven::config::Config;
+
+/// Background jobs that can wait for the disk to be awake, named in logs and
+/// `GET /api/admin/storage`
+pub const EXPIRE_DATA_EXPORTS: &str = "expire_data_exports";
+pub const EXPIRE_UPLOADS: &str = "expire_uploads";
+pub const PURGE_DELETED_ACCOUNTS: &str = "purge_deleted_accounts";
+
+/// When deferrable work may run (`DEFER_BACKGROUND_JOBS`, `STORAGE_ACTIVE_WINDOW_SECS`,
+/// `STORAGE_MAX_DEFER_SECS`)
+#[derive(Debug, Clone)]
+pub struct DeferPolicy {
+    pub enabled: bool,
+    /// The disk counts as awake this long after its last activity
+    pub active_window: Duration,
+    /// Work held back this long runs anyway, waking the disk
+    pub max_defer: Duration,
+}
+
+impl DeferPolicy {
+    pub fn from_config(config: &Config) -> Self {
+        let default = Self::default();
+        let secs = |name: &str, default: Duration| config.parse::<i64>(name).map(Duration::seconds).unwrap_or(default);
+        Self {
+            enabled: config.parse::<bool>("DEFER_BACKGROUND_JOBS").unwrap_or(default.enabled),
+            active_window: secs("STORAGE_ACTIVE_WINDOW_SECS", default.active_window),
+            max_defer: secs("STORAGE_MAX_DEFER_SECS", default.max_defer),
+        }
+    }
+}
+
+impl Default for DeferPolicy {
+    fn default() -> Self {
+        Self { enabled: false, active_window: Duration::minutes(5), max_defer: Duration::hours(6) }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DeferredJob {
+    pub job: String,
+    pub since: DateTime<Utc>,
+}
+
+/// What `GET /api/admin/storage` reports
+#[derive(Debug, Clone, Serialize)]
+pub struct StorageActivity {
+    /// Last time the storage disk read or wrote anything; `None` when unknown
+    pub last_disk_activity: Option<DateTime<Utc>>,
+    pub defer_enabled: bool,
+    /// Jobs held back since their last run
+    pub deferred: Vec<DeferredJob>,
+}
+
+/// This instance's view of its storage disk, and the deferrable jobs it holds back.
+#[derive(Default)]
+pub struct StorageScheduler {
+    last_disk_activity: RwLock<Option<DateTime<Utc>>>,
+    /// When each deferrable job last ran, or was first held back
+    waiting_since: RwLock<BTreeMap<&'static str, DateTime<Utc>>>,
+    /// Jobs currently held back
+    held: RwLock<BTreeMap<&'static str, DateTime<Utc>>>,
+}
+
+impl StorageScheduler {
+    /// Note that the disk read or wrote something at `at`.
+    pub fn record_disk_activity(&self, at: DateTime<Utc>) {
+        *self.last_disk_activity.write().unwrap_or_else(|e| e.into_inner()) = Some(at);
+    }
+
+    pub fn last_disk_activity(&self) -> Option<DateTime<Utc>> {
+        *self.last_disk_activity.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Whether `job` may run now. Always without deferral. Otherwise only while the disk is
+    /// awake anyway: a session is running, or the disk was active within the window. A job
+    /// held back past `max_defer` runs regardless. A job that may run counts as run.
+    pub fn may_run(&self, job: &'static str, policy: &DeferPolicy, sessions_running: bool, now: DateTime<Utc>) -> bool {
+        let mut waiting_since = self.waiting_since.write().unwrap_or_else(|e| e.into_inner());
+        let mut held = self.held.write().unwrap_or_else(|e| e.into_inner());
+        let since = *waiting_since.entry(job).or_insert(now);
+        let disk_awake = self.last_disk_activity().is_some_and(|at| now - at <= policy.active_window);
+        if !policy.enabled || sessions_running || disk_awake || now - since >= policy.max_defer {
+            waiting_since.insert(job, now);
+            held.remove(job);
+            return true;
+        }
+        held.entry(job).or_insert(now);
+        false
+    }
+
+    pub fn activity(&self, policy: &DeferPolicy) -> StorageActivity {
+        let held = self.held.read().unwrap_or_else(|e| e.into_inner());
+        StorageActivity {
+            last_disk_activity: self.last_disk_activity(),
+            defer_enabled: policy.enabled,
+            deferred: held.iter().map(|(job, since)| DeferredJob { job: job.to_string(), since: *since }).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_defers_until_the_disk_is_awake() {
+        let policy = DeferPolicy { enabled: true, ..DeferPolicy::default() };
+        let scheduler = StorageScheduler::default();
+        let start = Utc.with_ymd_and_hms(2026, 10, 17, 3, 0, 0).unwrap();
+
+        assert!(!scheduler.may_run(EXPIRE_UPLOADS, &policy, false, start));
+        assert_eq!(scheduler.activity(&policy).deferred.len(), 1);
+        assert!(scheduler.may_run(EXPIRE_UPLOADS, &policy, true, start + Duration::minutes(1)));
+        assert!(scheduler.activity(&policy).deferred.is_empty());
+
+        scheduler.record_disk_activity(start + Duration::hours(1));
+        assert!(scheduler.may_run(EXPIRE_UPLOADS, &policy, false, start + Duration::hours(1) + Duration::minutes(4)));
+        assert!(!scheduler.may_run(EXPIRE_UPLOADS, &policy, false, start + Duration::hours(2)));
+        // Held back past the limit, it wakes the disk
+        assert!(scheduler.may_run(EXPIRE_UPLOADS, &policy, false, start + Duration::hours(8)));
+
+        assert!(StorageScheduler::default().may_run(EXPIRE_UPLOADS, &DeferPolicy::default(), false, start));
+    }
+}
@@ -4,6 +4,13 @@ pub mod initiate_webauthn_registration;
 pub mod complete_webauthn_registration;
 pub mod initiate_webauthn_login;
 pub mod complete_webauthn_login;
+pub mod initiate_discoverable_login;
+pub mod complete_discoverable_login;
+pub mod oidc_login;
+pub mod login_throttle;
+pub mod delegate_subtree;
+pub mod revoke_delegation;
+pub mod debug_session_dump;
 
 // Re-export for convenience
 // Re-exports for convenience if needed
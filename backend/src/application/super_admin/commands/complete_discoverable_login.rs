@@ -0,0 +1,57 @@
+use axum::http::StatusCode;
+use webauthn_rs::prelude::*;
+use crate::infrastructure::AppState;
+use super::complete_webauthn_login::{self, LoginCompleteResult};
+use super::login_throttle;
+
+/// Finish a username-less login started by `initiate_discoverable_login`: look the user up from
+/// the credential id the browser answered with, then verify as for a regular login.
+pub async fn execute(
+    state: &AppState,
+    challenge_id: &str,
+    credential: PublicKeyCredential,
+    ip: &str,
+    user_agent: Option<&str>,
+) -> Result<LoginCompleteResult, (StatusCode, String)> {
+    login_throttle::ensure_allowed(state, None, ip).await?;
+
+    let state_json = state.challenge_repo
+        .get_and_delete_discoverable_challenge(challenge_id)
+        .await
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    let auth_state: DiscoverableAuthentication = serde_json::from_str(&state_json)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    // Our user handles are random, so the credential id is what identifies the account
+    let (_, credential_id) = state.webauthn
+        .identify_discoverable_authentication(&credential)
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+    let stored = match state.credential_repo
+        .find_by_credential_id(credential_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+    {
+        Some(stored) => stored,
+        None => {
+            login_throttle::record_failure(state, None, ip).await;
+            return Err((StatusCode::UNAUTHORIZED, "Unknown credential".to_string()));
+        }
+    };
+    let user = state.user_repo
+        .find_by_id(stored.user_id())
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+        .ok_or((StatusCode::UNAUTHORIZED, "User not found".to_string()))?;
+    login_throttle::ensure_allowed(state, Some(user.id()), ip).await?;
+
+    let key = DiscoverableKey::from(stored.passkey());
+    let result = match state.webauthn.finish_discoverable_authentication(&credential, auth_state, &[key]) {
+        Ok(result) => result,
+        Err(e) => {
+            login_throttle::record_failure(state, Some(&user), ip).await;
+            return Err((StatusCode::FORBIDDEN, format!("WebAuthn verification failed: {e}")));
+        }
+    };
+
+    complete_webauthn_login::finish(state, &user, vec![stored], &result, ip, user_agent).await
+}
@@ -1,7 +1,12 @@
 use axum::http::StatusCode;
 use webauthn_rs::prelude::*;
-use jsonwebtoken::{encode, Header, EncodingKey};
 use crate::infrastructure::AppState;
+use crate::application::access::check_access;
+use crate::application::auth_sessions::issue_token;
+use crate::domain::{Credential, User};
+use super::login_throttle;
+use shared::i18n::tr;
+use tracing::warn;
 // use crate::domain::Email; // removed unused import
 
 pub struct LoginCompleteResult {
@@ -17,78 +22,98 @@ pub async fn execute(
     challenge_id: &str,
     credential: PublicKeyCredential,
     email: &str,
+    ip: &str,
+    user_agent: Option<&str>,
 ) -> Result<LoginCompleteResult, (StatusCode, String)> {
+    login_throttle::ensure_allowed(state, None, ip).await?;
+
     // Get and delete challenge from repository
     let state_json = state.challenge_repo
         .get_and_delete_auth_challenge(challenge_id)
         .await
         .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
-    
-    let auth_state: PasskeyAuthentication = serde_json::from_str(&state_json)
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
-    // Find user by email
+    // Find user by email. An unknown address and an account without passkeys were handed a
+    // decoy challenge, and fail here the same way.
     let user_email = crate::domain::value_objects::Email::new(email.to_string())
         .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
     let user = state.user_repo
         .find_by_email(&user_email)
         .await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
-        .ok_or((StatusCode::UNAUTHORIZED, "User not found".to_string()))?;
-
-    // Find credentials for user
-    let credentials = state.credential_repo
-        .find_by_user_id(user.id())
-        .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
-    if credentials.is_empty() {
-        return Err((StatusCode::UNAUTHORIZED, "No credentials found for user".to_string()));
-    }
+    let credentials = match &user {
+        Some(user) => {
+            login_throttle::ensure_allowed(state, Some(user.id()), ip).await?;
+            state.credential_repo
+                .find_by_user_id(user.id())
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+        }
+        None => Vec::new(),
+    };
+    let Some(user) = user.filter(|_| !credentials.is_empty()) else {
+        login_throttle::record_failure(state, None, ip).await;
+        return Err((StatusCode::FORBIDDEN, "WebAuthn verification failed".to_string()));
+    };
+
+    let auth_state: PasskeyAuthentication = serde_json::from_str(&state_json)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
     // Validate credential with WebAuthn
-    let result = state.webauthn
-        .finish_passkey_authentication(&credential, &auth_state)
-        .map_err(|e| (StatusCode::FORBIDDEN, format!("WebAuthn verification failed: {e}")))?;
+    let result = match state.webauthn.finish_passkey_authentication(&credential, &auth_state) {
+        Ok(result) => result,
+        Err(e) => {
+            tracing::info!("WebAuthn verification failed for {}: {}", user.id(), e);
+            login_throttle::record_failure(state, Some(&user), ip).await;
+            return Err((StatusCode::FORBIDDEN, "WebAuthn verification failed".to_string()));
+        }
+    };
+
+    finish(state, &user, credentials, &result, ip, user_agent).await
+}
 
-    // Update sign count using webauthn_rs passkey update
-    let mut updated_passkey = credentials[0].passkey().clone();
-    let _ = updated_passkey.update_credential(&result);
-    let updated_cred = crate::domain::Credential::from_persistence(
-        credentials[0].user_id().clone(),
-        credentials[0].credential_id().to_vec(),
-        updated_passkey,
-        result.counter(),
-    );
-    state.credential_repo.save(&updated_cred)
+/// Record a verified assertion on the credential that signed it, not just the user's first
+/// one, and issue the session token. Shared with the username-less login.
+pub(super) async fn finish(
+    state: &AppState,
+    user: &User,
+    credentials: Vec<Credential>,
+    result: &AuthenticationResult,
+    ip: &str,
+    user_agent: Option<&str>,
+) -> Result<LoginCompleteResult, (StatusCode, String)> {
+    if user.status().is_deleting() {
+        return Err((StatusCode::FORBIDDEN, "Account is being deleted".to_string()));
+    }
+    if state.maintenance.current().is_some_and(|window| !window.allows_login(user.roles())) {
+        return Err((StatusCode::SERVICE_UNAVAILABLE, tr(user.locale(), "errors.maintenance").to_string()));
+    }
+    let Some(mut used) = credentials
+        .into_iter()
+        .find(|c| c.credential_id() == result.cred_id().as_ref())
+    else {
+        return Err((StatusCode::UNAUTHORIZED, "Credential not registered for user".to_string()));
+    };
+    let cloned = used.record_use(result, chrono::Utc::now());
+    state.credential_repo.save(&used)
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
-
-    #[derive(serde::Serialize, serde::Deserialize)]
-    struct Claims {
-        sub: String,
-        email: String,
-        roles: Vec<String>,
-        exp: usize,
+    if cloned {
+        warn!(
+            "Signature counter of a credential of user {} did not increase ({} anomalies); rejecting login",
+            user.id(),
+            used.usage().sign_count_anomalies
+        );
+        login_throttle::record_failure(state, Some(user), ip).await;
+        return Err((StatusCode::FORBIDDEN, "Credential counter did not increase; the authenticator may have been cloned".to_string()));
     }
+    let addr = ip.parse().map_err(|_| (StatusCode::BAD_REQUEST, "Invalid client address".to_string()))?;
+    check_access::ensure_admitted(state, user, addr, "login").await?;
+    login_throttle::record_success(state, user.id()).await;
 
-    let expiration = chrono::Utc::now()
-        .checked_add_signed(chrono::Duration::hours(24))
-        .expect("valid timestamp")
-        .timestamp() as usize;
-
-    let claims = Claims {
-        sub: user.id().to_string(),
-        email: user.email().as_str().to_string(),
-        roles: user.roles().iter().map(|r| r.as_db_str().to_string()).collect(),
-        exp: expiration,
-    };
-    
-    let token = encode(
-        &Header::default(),
-        &claims,
-        &EncodingKey::from_secret(state.jwt_secret.as_bytes()),
-    )
-    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let token = issue_token::execute(state, user, ip, user_agent)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
 
     Ok(LoginCompleteResult {
         token,
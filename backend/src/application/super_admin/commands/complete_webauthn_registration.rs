@@ -2,7 +2,10 @@ use axum::http::StatusCode;
 use webauthn_rs::prelude::*;
 use crate::infrastructure::AppState;
 use crate::domain::{User, Credential, Email, DisplayName, UserRole};
+use crate::domain::entities::credential::CredentialMetadata;
 use crate::infrastructure::driven::storage::create_owner_storage;
+use crate::domain::entities::tenant::DEFAULT_TENANT;
+use uuid::Uuid;
 
 pub async fn execute(
     state: &AppState,
@@ -10,10 +13,11 @@ pub async fn execute(
     credential: RegisterPublicKeyCredential,
     email: &str,
     display_name: &str,
-) -> Result<(), (StatusCode, String)> {
-    // Check if a SuperAdmin already exists
+    tenant_id: Uuid,
+) -> Result<User, (StatusCode, String)> {
+    // Check if a SuperAdmin already exists; a new tenant's first admin is checked by its setup token
     let super_admin_count = state.user_repo.count_super_admins().await.map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
-    if super_admin_count > 0 {
+    if tenant_id == DEFAULT_TENANT && super_admin_count > 0 {
         return Err((StatusCode::CONFLICT, "A SuperAdmin already exists. Initial setup can only be performed once.".to_string()));
     }
 
@@ -27,6 +31,7 @@ pub async fn execute(
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
     
     // Validate credential with WebAuthn
+    let metadata = CredentialMetadata::from_registration(&credential);
     let passkey = state.webauthn
         .finish_passkey_registration(&credential, &reg_state)
         .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
@@ -41,9 +46,10 @@ pub async fn execute(
         user_email,
         user_display_name,
         vec![UserRole::SuperAdmin, UserRole::Owner],
-    );
+    )
+    .in_tenant(tenant_id);
     
-    println!("Creating user with id: {}", user.id());
+    tracing::debug!(user_id = %user.id(), "Creating super admin");
     
     // Persist user before credential
     state.user_repo.save(&user)
@@ -51,22 +57,22 @@ pub async fn execute(
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
 
     // Create owner storage directory
-    if let Err(e) = create_owner_storage(&user.id().to_string()) {
-        eprintln!("[WARN] Failed to create owner storage directory: {}", e);
+    if let Err(e) = create_owner_storage(&user.id().to_string(), user.tenant_id()) {
+        tracing::warn!(user_id = %user.id(), "Failed to create owner storage directory: {}", e);
     }
 
-    println!("User created, now creating credential");
+    tracing::debug!(user_id = %user.id(), "Super admin saved, creating credential");
     
     // Create credential entity
-    let credential = Credential::new(user.id().clone(), passkey);
+    let credential = Credential::new(user.id().clone(), passkey, metadata);
     
     // Persist credential through repository
     state.credential_repo.save(&credential)
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
     
-    println!("Super admin created: {} ({})", user.email(), user.id());
-    Ok(())
+    tracing::info!(user_id = %user.id(), email = %user.email(), tenant_id = %tenant_id, "Super admin created");
+    Ok(user)
 }
 
 #[cfg(test)]
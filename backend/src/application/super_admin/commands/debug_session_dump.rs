@@ -0,0 +1,83 @@
+use crate::application::ports::{AuditRepository, NotificationRepository, SessionRepository};
+use crate::domain::entities::audit_event::AuditEvent;
+use crate::domain::entities::notification::Notification;
+use crate::domain::entities::session::Session;
+use crate::domain::value_objects::UserId;
+use uuid::Uuid;
+
+/// Allow a super admin to start dumping a session's video stream. A dump captures what the
+/// client sees, so starting, stopping and downloading it are all audited, and the session's
+/// user is told when recording starts and stops.
+pub async fn start<S, A, N>(
+    sessions: &S,
+    audit: &A,
+    notifications: &N,
+    super_admin_id: &UserId,
+    session_id: &Uuid,
+) -> Result<Session, String>
+where
+    S: SessionRepository + ?Sized,
+    A: AuditRepository + ?Sized,
+    N: NotificationRepository + ?Sized,
+{
+    let session = sessions
+        .find_by_id(session_id)
+        .await?
+        .filter(Session::is_active)
+        .ok_or_else(|| "Session not found or not active".to_string())?;
+    audit.record(&dump_event("session_debug_dump_started", super_admin_id, &session)).await?;
+    notifications.create(&dump_notice("session_debug_dump_started", &session)).await?;
+    Ok(session)
+}
+
+pub async fn stop<S, A, N>(
+    sessions: &S,
+    audit: &A,
+    notifications: &N,
+    super_admin_id: &UserId,
+    session_id: &Uuid,
+) -> Result<Session, String>
+where
+    S: SessionRepository + ?Sized,
+    A: AuditRepository + ?Sized,
+    N: NotificationRepository + ?Sized,
+{
+    let session = find(sessions, session_id).await?;
+    audit.record(&dump_event("session_debug_dump_stopped", super_admin_id, &session)).await?;
+    notifications.create(&dump_notice("session_debug_dump_stopped", &session)).await?;
+    Ok(session)
+}
+
+/// Dumps outlive their session, so ended sessions can still be downloaded.
+pub async fn download<S, A>(sessions: &S, audit: &A, super_admin_id: &UserId, session_id: &Uuid) -> Result<Session, String>
+where
+    S: SessionRepository + ?Sized,
+    A: AuditRepository + ?Sized,
+{
+    let session = find(sessions, session_id).await?;
+    audit.record(&dump_event("session_debug_dump_downloaded", super_admin_id, &session)).await?;
+    Ok(session)
+}
+
+async fn find<S: SessionRepository + ?Sized>(sessions: &S, session_id: &Uuid) -> Result<Session, String> {
+    sessions
+        .find_by_id(session_id)
+        .await?
+        .ok_or_else(|| "Session not found".to_string())
+}
+
+fn dump_event(event_type: &str, super_admin_id: &UserId, session: &Session) -> AuditEvent {
+    let mut event = AuditEvent::new(
+        event_type,
+        serde_json::json!({ "app_id": session.app_id, "by": super_admin_id }),
+    );
+    event.session_id = Some(session.id.to_string());
+    event.user_id = Some(session.user_id.clone());
+    event.owner_id = session.acting_as_owner_id.clone();
+    event
+}
+
+fn dump_notice(kind: &str, session: &Session) -> Notification {
+    let payload = serde_json::json!({ "session_id": session.id, "app_id": session.app_id });
+    Notification::new(session.user_id.clone(), kind, payload)
+}
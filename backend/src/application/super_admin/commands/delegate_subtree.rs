@@ -0,0 +1,54 @@
+use crate::application::ports::{AuditRepository, DelegationRepository, UserRepository};
+use crate::domain::entities::audit_event::AuditEvent;
+use crate::domain::entities::owner_delegation::OwnerDelegation;
+use crate::domain::value_objects::user_role::UserRole;
+use crate::domain::value_objects::UserId;
+
+/// Make `delegate_id` a co-owner of `path` in `owner_id`'s vault. The delegate gains the
+/// Owner role if they lack it, until their last delegation is revoked; it takes effect on
+/// their next login.
+pub async fn execute<U, D, A>(
+    users: &U,
+    delegations: &D,
+    audit: &A,
+    super_admin_id: &UserId,
+    owner_id: UserId,
+    delegate_id: UserId,
+    path: &str,
+) -> Result<OwnerDelegation, String>
+where
+    U: UserRepository + ?Sized,
+    D: DelegationRepository + ?Sized,
+    A: AuditRepository + ?Sized,
+{
+    let owner = users.find_by_id(&owner_id).await?.ok_or("Owner not found")?;
+    if !owner.has_role(UserRole::Owner) {
+        return Err("Only an owner's vault can be delegated".to_string());
+    }
+    let mut delegate = users.find_by_id(&delegate_id).await?.ok_or("Delegate not found")?;
+
+    let mut delegation = OwnerDelegation::new(owner_id, delegate_id, path, super_admin_id.clone())?;
+    let existing = delegations.find_active_for_delegate(&delegation.delegate_id).await?;
+    if existing.iter().any(|d| d.owner_id == delegation.owner_id && d.covers(&delegation.path)) {
+        return Err("The delegate already administers this subtree".to_string());
+    }
+    delegation.grants_owner_role = delegate.grant_role(UserRole::Owner);
+    delegations.save(&delegation).await?;
+    if delegation.grants_owner_role {
+        users.update_roles(&delegation.delegate_id, delegate.roles()).await?;
+    }
+
+    let mut event = AuditEvent::new(
+        "ownership_delegated",
+        serde_json::json!({
+            "delegation_id": delegation.id,
+            "delegate_id": delegation.delegate_id,
+            "path": delegation.path,
+            "granted_by": super_admin_id,
+        }),
+    );
+    event.owner_id = Some(delegation.owner_id.clone());
+    event.user_id = Some(delegation.delegate_id.clone());
+    audit.record(&event).await?;
+    Ok(delegation)
+}
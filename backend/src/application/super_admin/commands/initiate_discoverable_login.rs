@@ -0,0 +1,29 @@
+use axum::http::StatusCode;
+use webauthn_rs::prelude::*;
+use crate::infrastructure::AppState;
+use super::initiate_webauthn_login::LoginInitiateResult;
+use super::login_throttle;
+
+/// Start a username-less login for passkey autofill (conditional mediation). The challenge
+/// allows any discoverable credential; the user is resolved from the one the browser returns.
+pub async fn execute(state: &AppState, ip: &str) -> Result<LoginInitiateResult, (StatusCode, String)> {
+    login_throttle::ensure_allowed(state, None, ip).await?;
+
+    let (challenge, auth_state) = state.webauthn
+        .start_discoverable_authentication()
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let challenge_id = uuid::Uuid::new_v4().to_string();
+    let state_json = serde_json::to_string(&auth_state)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    state.challenge_repo
+        .save_discoverable_challenge(&challenge_id, &state_json, 300)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    Ok(LoginInitiateResult {
+        options: challenge,
+        challenge_id,
+    })
+}
@@ -1,64 +1,91 @@
 use axum::http::StatusCode;
+use webauthn_rs::fake::{FakePasskeyDistribution, WebauthnFakeCredentialGenerator};
 use webauthn_rs::prelude::*;
+use webauthn_rs_proto::AllowCredentials;
 use crate::infrastructure::AppState;
 use crate::domain::Email;
+use super::login_throttle;
 
 pub struct LoginInitiateResult {
     pub options: RequestChallengeResponse,
     pub challenge_id: String,
 }
 
+/// Start a passkey login for `email`. An unknown address, or an account without passkeys, gets
+/// a challenge listing made-up credentials that stay the same for that address, so the answer
+/// never tells whether an account exists.
 pub async fn execute(
     state: &AppState,
     email: &str,
+    ip: &str,
 ) -> Result<LoginInitiateResult, (StatusCode, String)> {
+    login_throttle::ensure_allowed(state, None, ip).await?;
+
     // Parse email
     let email = Email::new(email.to_string())
         .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
-    
-    // Find user by email
-        let user = state.user_repo
-            .find_by_email(&email)
-            .await
-            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
-            .ok_or((StatusCode::NOT_FOUND, "User not found".to_string()))?;
 
-        // Get user's credentials
-        let credentials = state.credential_repo
-            .find_by_user_id(user.id())
-            .await
-            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
-
-        println!("Found {} credentials for user {}", credentials.len(), email);
-
-        if credentials.is_empty() {
-            return Err((StatusCode::NOT_FOUND, "No credentials found for user".to_string()));
+    // Find user by email
+    let user = state.user_repo
+        .find_by_email(&email)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    let credentials = match &user {
+        Some(user) => {
+            // A locked-out user gets no new challenge
+            login_throttle::ensure_allowed(state, Some(user.id()), ip).await?;
+            state.credential_repo
+                .find_by_user_id(user.id())
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
         }
+        None => Vec::new(),
+    };
+    if credentials.is_empty() {
+        return decoy(state, &email).await;
+    }
+    tracing::debug!(credentials = credentials.len(), "Starting passkey login");
+
+    let passkeys: Vec<Passkey> = credentials
+        .iter()
+        .map(|cred| cred.passkey().clone())
+        .collect();
 
-        let passkeys: Vec<Passkey> = credentials
-            .iter()
-            .map(|cred| cred.passkey().clone())
-            .collect();
-    
     // Generate WebAuthn challenge
     let (challenge, auth_state) = state.webauthn
         .start_passkey_authentication(&passkeys)
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
-    
-    let challenge_id = uuid::Uuid::new_v4().to_string();
     let state_json = serde_json::to_string(&auth_state)
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
-    
-    // Store challenge using repository
+    save(state, challenge, &state_json).await
+}
+
+/// A challenge no passkey can answer, shaped like a real one. Its credential ids come from a
+/// key only the server knows, so they cannot be told from genuine ones.
+async fn decoy(state: &AppState, email: &Email) -> Result<LoginInitiateResult, (StatusCode, String)> {
+    let internal = |e: WebauthnError| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string());
+    let generator: WebauthnFakeCredentialGenerator<FakePasskeyDistribution> =
+        WebauthnFakeCredentialGenerator::new(&state.jwt_keys.derive("webauthn-decoy")).map_err(internal)?;
+    let ids = generator.generate(email.as_str().as_bytes()).map_err(internal)?;
+
+    // Nothing can complete it, but the state is kept like any other so completing fails alike
+    let (mut challenge, auth_state) = state.webauthn.start_discoverable_authentication().map_err(internal)?;
+    challenge.public_key.allow_credentials = ids
+        .into_iter()
+        .map(|id| AllowCredentials { type_: "public-key".to_string(), id, transports: None })
+        .collect();
+    let state_json = serde_json::to_string(&auth_state)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    save(state, challenge, &state_json).await
+}
+
+async fn save(state: &AppState, options: RequestChallengeResponse, state_json: &str) -> Result<LoginInitiateResult, (StatusCode, String)> {
+    let challenge_id = uuid::Uuid::new_v4().to_string();
     state.challenge_repo
-        .save_auth_challenge(&challenge_id, &state_json, 300)
+        .save_auth_challenge(&challenge_id, state_json, 300)
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
-    
-    Ok(LoginInitiateResult {
-        options: challenge,
-        challenge_id,
-    })
+    Ok(LoginInitiateResult { options, challenge_id })
 }
 
 #[cfg(test)]
@@ -0,0 +1,114 @@
+use axum::http::StatusCode;
+use crate::domain::entities::audit_event::AuditEvent;
+use crate::domain::entities::notification::Notification;
+use crate::domain::value_objects::lockout_policy::LoginPenalty;
+use crate::domain::User;
+use crate::domain::UserId;
+use crate::infrastructure::AppState;
+
+pub fn user_subject(user_id: &UserId) -> String {
+    format!("user:{}", user_id)
+}
+
+pub fn ip_subject(ip: &str) -> String {
+    format!("ip:{}", ip)
+}
+
+/// Reject the attempt while the IP (and the user, once known) is backing off or locked out.
+pub async fn ensure_allowed(
+    state: &AppState,
+    user_id: Option<&UserId>,
+    ip: &str,
+) -> Result<(), (StatusCode, String)> {
+    let subjects = std::iter::once(ip_subject(ip)).chain(user_id.map(user_subject));
+    for subject in subjects {
+        let blocked = state.login_attempt_repo
+            .blocked_for(&subject)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+        if let Some(seconds) = blocked {
+            return Err((
+                StatusCode::TOO_MANY_REQUESTS,
+                format!("Too many failed login attempts, retry in {} seconds", seconds),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Count a failed attempt against the IP and the user, applying backoff or lockout.
+/// Throttling is best effort: storage errors are logged so the original failure is still returned.
+pub async fn record_failure(state: &AppState, user: Option<&User>, ip: &str) {
+    if let Err(e) = penalize(state, &ip_subject(ip), None, ip).await {
+        tracing::warn!("Failed to record login failure for {}: {}", ip, e);
+    }
+    if let Some(user) = user {
+        if let Err(e) = penalize(state, &user_subject(user.id()), Some(user), ip).await {
+            tracing::warn!("Failed to record login failure for user {}: {}", user.id(), e);
+        }
+    }
+}
+
+/// A successful login resets the user's counter; the IP counter keeps running.
+pub async fn record_success(state: &AppState, user_id: &UserId) {
+    if let Err(e) = state.login_attempt_repo.clear(&user_subject(user_id)).await {
+        tracing::warn!("Failed to reset login failures for user {}: {}", user_id, e);
+    }
+}
+
+async fn penalize(state: &AppState, subject: &str, user: Option<&User>, ip: &str) -> Result<(), String> {
+    let policy = state.lockout_policy;
+    let failures = state.login_attempt_repo
+        .record_failure(subject, policy.window_secs)
+        .await?;
+
+    match policy.penalty(failures) {
+        None => Ok(()),
+        Some(LoginPenalty::Backoff(seconds)) => state.login_attempt_repo.block(subject, seconds).await,
+        Some(LoginPenalty::Lockout(seconds)) => {
+            state.login_attempt_repo.block(subject, seconds).await?;
+            tracing::warn!("Login locked out for {} after {} failures", subject, failures);
+
+            let payload = serde_json::json!({
+                "subject": subject,
+                "ip": ip,
+                "failures": failures,
+                "locked_for_secs": seconds,
+            });
+            let mut event = AuditEvent::new("login_lockout", payload.clone());
+            event.user_id = user.map(|u| u.id().clone());
+            state.audit_repo.record(&event).await?;
+
+            if let Some(user) = user {
+                notify_lockout(state, user, payload).await?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Tell the locked user and every owner sharing files with them, so an owner can unlock early.
+async fn notify_lockout(state: &AppState, user: &User, payload: serde_json::Value) -> Result<(), String> {
+    state.notification_repo
+        .create(&Notification::new(user.id().clone(), "account_locked", payload.clone()))
+        .await?;
+
+    let mut owners: Vec<UserId> = state.file_permission_repo
+        .find_active_for_client(user.id())
+        .await?
+        .into_iter()
+        .map(|p| p.owner_id)
+        .collect();
+    owners.sort_by_key(UserId::as_uuid);
+    owners.dedup();
+
+    let mut owner_payload = payload;
+    owner_payload["user_id"] = serde_json::json!(user.id().to_string());
+    owner_payload["email"] = serde_json::json!(user.email().as_str());
+    for owner_id in owners {
+        state.notification_repo
+            .create(&Notification::new(owner_id, "client_account_locked", owner_payload.clone()))
+            .await?;
+    }
+    Ok(())
+}
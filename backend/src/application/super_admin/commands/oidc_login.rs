@@ -0,0 +1,364 @@
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use webauthn_rs::prelude::*;
+use crate::infrastructure::AppState;
+use crate::application::access::check_access;
+use crate::application::auth_sessions::issue_token;
+use crate::application::ports::ExternalClaims;
+use crate::domain::entities::audit_event::AuditEvent;
+use crate::domain::entities::credential::CredentialMetadata;
+use crate::domain::entities::external_identity::{ExternalIdentity, PasskeyRequirement};
+use crate::domain::services::secrets;
+use crate::domain::{Credential, DisplayName, Email, User, UserId, UserRole};
+use crate::infrastructure::driven::storage::create_owner_storage;
+use super::complete_webauthn_login::{self, LoginCompleteResult};
+use super::initiate_webauthn_login::LoginInitiateResult;
+use super::login_throttle;
+use shared::i18n::tr;
+
+/// How long the user has at the provider before the login must be started again
+const AUTHORIZE_TTL: u64 = 600;
+/// How long the login page has to redeem the ticket it was redirected with
+const TICKET_TTL: u64 = 60;
+const PASSKEY_TTL: u64 = 300;
+
+/// Where a provider login stands, kept in the challenge store between requests
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "step", rename_all = "snake_case")]
+enum OidcStep {
+    /// Sent to the provider; keyed by the `state` parameter. `browser` is the value of the
+    /// cookie set on the browser that started the login.
+    Authorizing { nonce: String, pkce_verifier: String, browser: String },
+    /// Back from the provider; keyed by the ticket handed to the login page
+    Verified { user_id: UserId, link: Option<PendingLink> },
+    /// Waiting for one of the user's passkeys
+    Passkey { user_id: UserId, auth_state: PasskeyAuthentication, link: Option<PendingLink> },
+    /// Waiting for the user's first passkey
+    PasskeySetup { user_id: UserId, reg_state: PasskeyRegistration },
+}
+
+/// A provider account matched to a local one by email only. It is linked once the user proves
+/// they own the local account with one of its passkeys.
+#[derive(Serialize, Deserialize)]
+struct PendingLink {
+    issuer: String,
+    subject: String,
+}
+
+/// A login started with [`start`]: where to send the browser, and the value of the cookie
+/// the callback must come back with
+pub struct LoginStart {
+    pub url: String,
+    pub browser: String,
+}
+
+pub enum RedeemResult {
+    LoggedIn(LoginCompleteResult),
+    /// The provider login must be confirmed with a passkey
+    PasskeyRequired(LoginInitiateResult),
+    /// As above, for a user with no passkey yet: they register one now
+    PasskeySetupRequired { options: CreationChallengeResponse, challenge_id: String },
+}
+
+fn provider(state: &AppState) -> Result<&dyn crate::application::ports::IdentityProvider, (StatusCode, String)> {
+    state.identity_provider
+        .as_deref()
+        .ok_or((StatusCode::NOT_FOUND, "OpenID Connect login is not configured".to_string()))
+}
+
+async fn save_step(state: &AppState, key: &str, step: &OidcStep, ttl: u64) -> Result<(), (StatusCode, String)> {
+    let json = serde_json::to_string(step).map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    state.challenge_repo
+        .save_oidc_login(key, &json, ttl)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))
+}
+
+async fn take_step(state: &AppState, key: &str) -> Result<OidcStep, (StatusCode, String)> {
+    let json = state.challenge_repo
+        .get_and_delete_oidc_login(key)
+        .await
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    serde_json::from_str(&json).map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+async fn find_user(state: &AppState, user_id: &UserId) -> Result<User, (StatusCode, String)> {
+    state.user_repo
+        .find_by_id(user_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+        .ok_or((StatusCode::UNAUTHORIZED, "User not found".to_string()))
+}
+
+/// The provider's authorization URL to send the browser to. The login is bound to the
+/// browser, so a callback URL planted in someone else's browser logs nobody in.
+pub async fn start(state: &AppState, ip: &str) -> Result<LoginStart, (StatusCode, String)> {
+    login_throttle::ensure_allowed(state, None, ip).await?;
+    let request = provider(state)?
+        .authorization_request()
+        .await
+        .map_err(|e| (StatusCode::BAD_GATEWAY, e))?;
+    let browser = secrets::generate();
+    let step = OidcStep::Authorizing { nonce: request.nonce, pkce_verifier: request.pkce_verifier, browser: browser.clone() };
+    save_step(state, &request.state, &step, AUTHORIZE_TTL).await?;
+    Ok(LoginStart { url: request.url, browser })
+}
+
+/// Exchange the code the provider redirected back with and resolve the local user. `browser`
+/// is the cookie [`start`] set. Returns a one-time ticket: the session token is only issued
+/// when the login page redeems it, so it never appears in a URL.
+pub async fn callback(
+    state: &AppState,
+    oidc_state: &str,
+    code: &str,
+    browser: Option<&str>,
+    ip: &str,
+) -> Result<String, (StatusCode, String)> {
+    login_throttle::ensure_allowed(state, None, ip).await?;
+    let OidcStep::Authorizing { nonce, pkce_verifier, browser: expected } = take_step(state, oidc_state).await? else {
+        return Err((StatusCode::BAD_REQUEST, "Invalid or expired login".to_string()));
+    };
+    if !browser.is_some_and(|browser| secrets::constant_time_eq(browser, &expected)) {
+        login_throttle::record_failure(state, None, ip).await;
+        return Err((StatusCode::BAD_REQUEST, "This login was started in another browser".to_string()));
+    }
+    let claims = match provider(state)?.exchange(code, &pkce_verifier, &nonce).await {
+        Ok(claims) => claims,
+        Err(e) => {
+            login_throttle::record_failure(state, None, ip).await;
+            return Err((StatusCode::UNAUTHORIZED, e));
+        }
+    };
+
+    let (user, link) = match resolve_user(state, &claims).await {
+        Ok(resolved) => resolved,
+        Err(e) => {
+            login_throttle::record_failure(state, None, ip).await;
+            return Err(e);
+        }
+    };
+    login_throttle::ensure_allowed(state, Some(user.id()), ip).await?;
+    if link.is_none() {
+        save_identity(state, &claims.issuer, &claims.subject, &user).await?;
+    }
+
+    let ticket = uuid::Uuid::new_v4().to_string();
+    save_step(state, &ticket, &OidcStep::Verified { user_id: user.id().clone(), link }, TICKET_TTL).await?;
+    Ok(ticket)
+}
+
+/// Link the provider's subject to `user`, or note the login on the existing link.
+async fn save_identity(state: &AppState, issuer: &str, subject: &str, user: &User) -> Result<(), (StatusCode, String)> {
+    let mut identity = match state.external_identity_repo.find(issuer, subject).await {
+        Ok(Some(identity)) => identity,
+        Ok(None) => ExternalIdentity::new(issuer.to_string(), subject.to_string(), user.id().clone()),
+        Err(e) => return Err((StatusCode::INTERNAL_SERVER_ERROR, e)),
+    };
+    identity.last_login_at = chrono::Utc::now();
+    state.external_identity_repo
+        .save(&identity)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))
+}
+
+/// The account linked to the provider's subject; else a new account with the roles
+/// `OIDC_ROLE_MAPPING` gives the user's groups. An existing account with the provider's
+/// verified email is returned with a [`PendingLink`]: whoever controls an address at the
+/// provider does not get the local account until they use its passkey. Admin and owner
+/// accounts are never matched by email.
+async fn resolve_user(state: &AppState, claims: &ExternalClaims) -> Result<(User, Option<PendingLink>), (StatusCode, String)> {
+    let linked = state.external_identity_repo
+        .find(&claims.issuer, &claims.subject)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    if let Some(identity) = linked {
+        return Ok((find_user(state, &identity.user_id).await?, None));
+    }
+
+    // An unverified address could be anyone's, so it neither links nor creates an account
+    let email = match &claims.email {
+        Some(email) if claims.email_verified => Email::new(email.clone()).map_err(|e| (StatusCode::FORBIDDEN, e))?,
+        _ => return Err((StatusCode::FORBIDDEN, "The provider did not report a verified email".to_string())),
+    };
+    let existing = state.user_repo
+        .find_by_email(&email)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    if let Some(user) = existing {
+        if user.has_role(UserRole::SuperAdmin) || user.has_role(UserRole::Owner) {
+            return Err((
+                StatusCode::FORBIDDEN,
+                "An administrator or owner account cannot be linked to a provider by email".to_string(),
+            ));
+        }
+        let link = PendingLink { issuer: claims.issuer.clone(), subject: claims.subject.clone() };
+        return Ok((user, Some(link)));
+    }
+
+    let roles = state.oidc_policy.role_mapping.roles_for(&claims.groups);
+    if roles.is_empty() {
+        return Err((StatusCode::FORBIDDEN, "No account for this user, and none of their groups grants one".to_string()));
+    }
+    let name = claims.name.clone().unwrap_or_else(|| email.as_str().split('@').next().unwrap_or_default().to_string());
+    let display_name = DisplayName::new(name).map_err(|e| (StatusCode::FORBIDDEN, e))?;
+    let user = User::new(email, display_name, roles);
+    state.user_repo.save(&user).await.map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    if user.has_role(UserRole::Owner) {
+        if let Err(e) = create_owner_storage(&user.id().to_string(), user.tenant_id()) {
+            tracing::warn!("Failed to create owner storage directory: {}", e);
+        }
+    }
+
+    let roles: Vec<&str> = user.roles().iter().map(|r| r.as_db_str()).collect();
+    let mut event = AuditEvent::new(
+        "oidc_user_provisioned",
+        json!({ "issuer": claims.issuer, "subject": claims.subject, "groups": claims.groups, "roles": roles }),
+    );
+    event.user_id = Some(user.id().clone());
+    state.audit_repo.record(&event).await.map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    Ok((user, None))
+}
+
+/// Trade the ticket for a session, or for a passkey challenge when `OIDC_PASSKEY=second_factor`
+/// or the provider account is not linked yet.
+pub async fn redeem(
+    state: &AppState,
+    ticket: &str,
+    ip: &str,
+    user_agent: Option<&str>,
+) -> Result<RedeemResult, (StatusCode, String)> {
+    login_throttle::ensure_allowed(state, None, ip).await?;
+    let OidcStep::Verified { user_id, link } = take_step(state, ticket).await? else {
+        return Err((StatusCode::BAD_REQUEST, "Invalid or expired login".to_string()));
+    };
+    let user = find_user(state, &user_id).await?;
+    login_throttle::ensure_allowed(state, Some(user.id()), ip).await?;
+
+    if link.is_none() && state.oidc_policy.passkey == PasskeyRequirement::Primary {
+        return log_in(state, &user, ip, user_agent).await.map(RedeemResult::LoggedIn);
+    }
+
+    let credentials = state.credential_repo
+        .find_by_user_id(user.id())
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    let challenge_id = uuid::Uuid::new_v4().to_string();
+    if credentials.is_empty() {
+        // Registering a passkey now would prove nothing about owning the account
+        if link.is_some() {
+            return Err((
+                StatusCode::FORBIDDEN,
+                "This account has no passkey to confirm the provider link with".to_string(),
+            ));
+        }
+        let (options, reg_state) = state.webauthn
+            .start_passkey_registration(user.id().as_uuid(), user.email().as_str(), user.display_name().as_str(), None)
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        save_step(state, &challenge_id, &OidcStep::PasskeySetup { user_id, reg_state }, PASSKEY_TTL).await?;
+        return Ok(RedeemResult::PasskeySetupRequired { options, challenge_id });
+    }
+
+    let passkeys: Vec<Passkey> = credentials.iter().map(|c| c.passkey().clone()).collect();
+    let (options, auth_state) = state.webauthn
+        .start_passkey_authentication(&passkeys)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    save_step(state, &challenge_id, &OidcStep::Passkey { user_id, auth_state, link }, PASSKEY_TTL).await?;
+    Ok(RedeemResult::PasskeyRequired(LoginInitiateResult { options, challenge_id }))
+}
+
+/// Finish a second-factor login with one of the user's passkeys, linking the provider account
+/// if that was pending.
+pub async fn confirm_passkey(
+    state: &AppState,
+    challenge_id: &str,
+    credential: PublicKeyCredential,
+    ip: &str,
+    user_agent: Option<&str>,
+) -> Result<LoginCompleteResult, (StatusCode, String)> {
+    login_throttle::ensure_allowed(state, None, ip).await?;
+    let OidcStep::Passkey { user_id, auth_state, link } = take_step(state, challenge_id).await? else {
+        return Err((StatusCode::BAD_REQUEST, "Invalid or expired login".to_string()));
+    };
+    let user = find_user(state, &user_id).await?;
+    login_throttle::ensure_allowed(state, Some(user.id()), ip).await?;
+
+    let credentials = state.credential_repo
+        .find_by_user_id(user.id())
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    let result = match state.webauthn.finish_passkey_authentication(&credential, &auth_state) {
+        Ok(result) => result,
+        Err(e) => {
+            login_throttle::record_failure(state, Some(&user), ip).await;
+            return Err((StatusCode::FORBIDDEN, format!("WebAuthn verification failed: {e}")));
+        }
+    };
+    let logged_in = complete_webauthn_login::finish(state, &user, credentials, &result, ip, user_agent).await?;
+    if let Some(link) = link {
+        save_identity(state, &link.issuer, &link.subject, &user).await?;
+        let mut event = AuditEvent::new("oidc_identity_linked", json!({ "issuer": link.issuer, "subject": link.subject }));
+        event.user_id = Some(user.id().clone());
+        state.audit_repo.record(&event).await.map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    }
+    Ok(logged_in)
+}
+
+/// Finish a second-factor login by registering the user's first passkey.
+pub async fn set_up_passkey(
+    state: &AppState,
+    challenge_id: &str,
+    credential: RegisterPublicKeyCredential,
+    ip: &str,
+    user_agent: Option<&str>,
+) -> Result<LoginCompleteResult, (StatusCode, String)> {
+    login_throttle::ensure_allowed(state, None, ip).await?;
+    let OidcStep::PasskeySetup { user_id, reg_state } = take_step(state, challenge_id).await? else {
+        return Err((StatusCode::BAD_REQUEST, "Invalid or expired login".to_string()));
+    };
+    let user = find_user(state, &user_id).await?;
+    login_throttle::ensure_allowed(state, Some(user.id()), ip).await?;
+    let metadata = CredentialMetadata::from_registration(&credential);
+    let passkey = match state.webauthn.finish_passkey_registration(&credential, &reg_state) {
+        Ok(passkey) => passkey,
+        Err(e) => {
+            login_throttle::record_failure(state, Some(&user), ip).await;
+            return Err((StatusCode::BAD_REQUEST, e.to_string()));
+        }
+    };
+    state.credential_repo
+        .save(&Credential::new(user.id().clone(), passkey, metadata))
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    log_in(state, &user, ip, user_agent).await
+}
+
+/// The checks `complete_webauthn_login::finish` makes, for logins without an assertion.
+async fn log_in(
+    state: &AppState,
+    user: &User,
+    ip: &str,
+    user_agent: Option<&str>,
+) -> Result<LoginCompleteResult, (StatusCode, String)> {
+    if user.status().is_deleting() {
+        return Err((StatusCode::FORBIDDEN, "Account is being deleted".to_string()));
+    }
+    if state.maintenance.current().is_some_and(|window| !window.allows_login(user.roles())) {
+        return Err((StatusCode::SERVICE_UNAVAILABLE, tr(user.locale(), "errors.maintenance").to_string()));
+    }
+    let addr = ip.parse().map_err(|_| (StatusCode::BAD_REQUEST, "Invalid client address".to_string()))?;
+    check_access::ensure_admitted(state, user, addr, "login").await?;
+    login_throttle::record_success(state, user.id()).await;
+
+    let token = issue_token::execute(state, user, ip, user_agent)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    Ok(LoginCompleteResult {
+        token,
+        user_id: user.id().to_string(),
+        email: user.email().as_str().to_string(),
+        display_name: user.display_name().as_str().to_string(),
+        roles: user.roles().iter().map(|r| r.as_db_str().to_string()).collect(),
+    })
+}
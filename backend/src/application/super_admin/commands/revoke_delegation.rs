@@ -0,0 +1,63 @@
+use crate::application::ports::{AuditRepository, DelegationRepository, UserRepository};
+use crate::domain::entities::audit_event::AuditEvent;
+use crate::domain::value_objects::user_role::UserRole;
+use crate::domain::value_objects::UserId;
+use uuid::Uuid;
+
+/// End a co-owner's authority over a subtree. Permissions they granted stay in place;
+/// they belong to the vault owner. A delegate who held the Owner role only through
+/// delegations loses it with the last one; returns whether they did.
+pub async fn execute<U, D, A>(
+    users: &U,
+    delegations: &D,
+    audit: &A,
+    super_admin_id: &UserId,
+    delegation_id: &Uuid,
+) -> Result<bool, String>
+where
+    U: UserRepository + ?Sized,
+    D: DelegationRepository + ?Sized,
+    A: AuditRepository + ?Sized,
+{
+    let delegation = delegations
+        .find_by_id(delegation_id)
+        .await?
+        .filter(|d| d.is_active())
+        .ok_or("Delegation not found")?;
+    delegations.revoke(delegation_id).await?;
+
+    let mut role_removed = false;
+    if delegation.grants_owner_role {
+        let remaining = delegations.find_active_for_delegate(&delegation.delegate_id).await?;
+        match remaining.into_iter().next() {
+            // The role now hangs on a delegation still in force
+            Some(mut next) => {
+                next.grants_owner_role = true;
+                delegations.save(&next).await?;
+            }
+            None => {
+                if let Some(mut delegate) = users.find_by_id(&delegation.delegate_id).await? {
+                    if delegate.remove_role(UserRole::Owner) {
+                        users.update_roles(&delegation.delegate_id, delegate.roles()).await?;
+                        role_removed = true;
+                    }
+                }
+            }
+        }
+    }
+
+    let mut event = AuditEvent::new(
+        "delegation_revoked",
+        serde_json::json!({
+            "delegation_id": delegation.id,
+            "delegate_id": delegation.delegate_id,
+            "path": delegation.path,
+            "revoked_by": super_admin_id,
+            "owner_role_removed": role_removed,
+        }),
+    );
+    event.owner_id = Some(delegation.owner_id);
+    event.user_id = Some(delegation.delegate_id);
+    audit.record(&event).await?;
+    Ok(role_removed)
+}
@@ -0,0 +1,48 @@
+// Tenants - isolated groups of users sharing the instance, each with their own admins and vaults
+use serde_json::json;
+use uuid::Uuid;
+use crate::domain::entities::audit_event::AuditEvent;
+use crate::domain::services::secrets::hash_secret;
+use crate::domain::entities::tenant::Tenant;
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::AppState;
+
+/// Create a tenant. Returns it with the setup token its first admin registers with, which is
+/// not shown again.
+pub async fn create(state: &AppState, name: &str, created_by: &UserId) -> Result<(Tenant, String), String> {
+    let (tenant, token) = Tenant::create(name)?;
+    state.tenant_repo.save(&tenant).await?;
+    let mut event = AuditEvent::new("tenant_created", json!({ "tenant_id": tenant.id, "name": tenant.name }));
+    event.user_id = Some(created_by.clone());
+    state.audit_repo.record(&event).await?;
+    Ok((tenant, token))
+}
+
+/// The tenant a setup token was issued for, while its first admin has yet to register.
+pub async fn awaiting_setup(state: &AppState, token: &str) -> Result<Option<Tenant>, String> {
+    Ok(state
+        .tenant_repo
+        .find_by_setup_token_hash(&hash_secret(token))
+        .await?
+        .filter(Tenant::awaits_setup))
+}
+
+/// Close a tenant's setup once its first admin registered, and keep their vault in the
+/// tenant's storage root.
+pub async fn complete_setup(state: &AppState, tenant_id: &Uuid, admin_id: &UserId) -> Result<(), String> {
+    state.tenant_repo.complete_setup(tenant_id).await?;
+    state.vault_storage.place_in_tenant(admin_id, *tenant_id);
+    let mut event = AuditEvent::new("tenant_admin_registered", json!({ "tenant_id": tenant_id }));
+    event.user_id = Some(admin_id.clone());
+    state.audit_repo.record(&event).await
+}
+
+/// Point the vault storage at every tenant's vaults, at startup.
+pub async fn place_vaults(state: &AppState) -> Result<(), String> {
+    for tenant in state.tenant_repo.list().await?.into_iter().filter(|t| !t.is_default()) {
+        for user in state.user_repo.list_by_tenant(&tenant.id).await? {
+            state.vault_storage.place_in_tenant(user.id(), tenant.id);
+        }
+    }
+    Ok(())
+}
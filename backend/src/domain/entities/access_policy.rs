@@ -0,0 +1,83 @@
+use crate::domain::value_objects::{IpRange, UserId};
+use chrono::{DateTime, Utc};
+use std::net::IpAddr;
+
+/// Where an owner allows logins and streaming connections from. Deny entries win over allow
+/// entries; empty allow lists admit everyone not denied. Countries are ISO 3166-1 alpha-2 codes.
+#[derive(Debug, Clone)]
+pub struct AccessPolicy {
+    pub owner_id: UserId,
+    pub allowed_ranges: Vec<IpRange>,
+    pub denied_ranges: Vec<IpRange>,
+    pub allowed_countries: Vec<String>,
+    pub denied_countries: Vec<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl AccessPolicy {
+    pub fn permits(&self, ip: IpAddr, country: Option<&str>) -> bool {
+        let in_country = |list: &[String]| country.is_some_and(|c| list.iter().any(|l| l.eq_ignore_ascii_case(c)));
+        if self.denied_ranges.iter().any(|r| r.contains(ip)) || in_country(&self.denied_countries) {
+            return false;
+        }
+        if self.allowed_ranges.is_empty() && self.allowed_countries.is_empty() {
+            return true;
+        }
+        self.allowed_ranges.iter().any(|r| r.contains(ip)) || in_country(&self.allowed_countries)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        for code in self.allowed_countries.iter().chain(&self.denied_countries) {
+            if code.len() != 2 || !code.chars().all(|c| c.is_ascii_uppercase()) {
+                return Err(format!("Invalid country code: {}", code));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A request reaching several owners' vaults passes only if each of their policies admits it.
+pub fn admitted_by_all(policies: &[AccessPolicy], ip: IpAddr, country: Option<&str>) -> bool {
+    policies.iter().all(|p| p.permits(ip, country))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(allowed: &[&str], denied: &[&str], allowed_countries: &[&str]) -> AccessPolicy {
+        AccessPolicy {
+            owner_id: UserId::new(),
+            allowed_ranges: allowed.iter().map(|r| IpRange::parse(r).unwrap()).collect(),
+            denied_ranges: denied.iter().map(|r| IpRange::parse(r).unwrap()).collect(),
+            allowed_countries: allowed_countries.iter().map(|c| c.to_string()).collect(),
+            denied_countries: Vec::new(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_deny_wins_over_allow() {
+        let p = policy(&["10.0.0.0/8"], &["10.1.0.0/16"], &[]);
+        assert!(p.permits("10.2.3.4".parse().unwrap(), None));
+        assert!(!p.permits("10.1.3.4".parse().unwrap(), None));
+        assert!(!p.permits("192.168.0.1".parse().unwrap(), None));
+    }
+
+    #[test]
+    fn test_country_allowlist_requires_known_country() {
+        let p = policy(&[], &[], &["FR"]);
+        assert!(p.permits("203.0.113.1".parse().unwrap(), Some("FR")));
+        assert!(!p.permits("203.0.113.1".parse().unwrap(), Some("US")));
+        assert!(!p.permits("203.0.113.1".parse().unwrap(), None));
+    }
+
+    #[test]
+    fn test_admitted_by_all_policies() {
+        let ip = "192.168.0.1".parse().unwrap();
+        assert!(admitted_by_all(&[], ip, None));
+        assert!(!admitted_by_all(&[policy(&["10.0.0.0/8"], &[], &[])], ip, None));
+        assert!(!admitted_by_all(&[policy(&[], &["172.16.0.0/12"], &[]), policy(&["10.0.0.0/8"], &[], &[])], ip, None));
+        assert!(admitted_by_all(&[policy(&[], &["172.16.0.0/12"], &[]), policy(&["192.168.0.0/16"], &[], &[])], ip, None));
+    }
+}
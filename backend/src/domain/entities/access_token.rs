@@ -0,0 +1,151 @@
+use crate::domain::value_objects::UserId;
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+use crate::domain::services::secrets::hash_secret;
+
+/// Prefix of every personal access token, so leaked ones are easy to spot in logs and scanners
+pub const TOKEN_PREFIX: &str = "pvt_";
+
+/// Longest lifetime a token may be given
+pub const MAX_LIFETIME_DAYS: i64 = 365;
+pub const DEFAULT_LIFETIME_DAYS: i64 = 90;
+
+/// What a personal access token may be used for. Tokens never reach the rest of the API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum TokenScope {
+    /// Download files and archives, and follow uploads and file jobs
+    #[serde(rename = "files:read")]
+    FilesRead,
+    /// Everything `files:read` allows, plus uploads and file jobs
+    #[serde(rename = "files:write")]
+    FilesWrite,
+    /// Read-only usage figures, e.g. bandwidth and render statistics
+    #[serde(rename = "metrics:read")]
+    MetricsRead,
+}
+
+impl TokenScope {
+    pub fn as_db_str(&self) -> &'static str {
+        match self {
+            TokenScope::FilesRead => "files:read",
+            TokenScope::FilesWrite => "files:write",
+            TokenScope::MetricsRead => "metrics:read",
+        }
+    }
+
+    pub fn from_db_str(s: &str) -> Result<Self, String> {
+        match s {
+            "files:read" => Ok(TokenScope::FilesRead),
+            "files:write" => Ok(TokenScope::FilesWrite),
+            "metrics:read" => Ok(TokenScope::MetricsRead),
+            other => Err(format!("Unknown token scope '{other}'")),
+        }
+    }
+}
+
+/// A token a user creates for scripts and backup tools, acting as them within its scopes.
+/// Only its hash is stored; the token itself is shown once, when it is created.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct AccessToken {
+    pub id: Uuid,
+    pub user_id: UserId,
+    pub name: String,
+    /// The start of the token, to tell tokens apart without revealing them
+    pub display_prefix: String,
+    #[serde(skip_serializing)]
+    pub token_hash: String,
+    pub scopes: Vec<TokenScope>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl AccessToken {
+    /// A new token and its secret, valid for `lifetime_days`.
+    pub fn issue(user_id: UserId, name: &str, scopes: Vec<TokenScope>, lifetime_days: i64) -> Result<(Self, String), String> {
+        let name = name.trim();
+        if name.is_empty() || name.len() > 100 {
+            return Err("Token name must be 1 to 100 characters".to_string());
+        }
+        if scopes.is_empty() {
+            return Err("A token needs at least one scope".to_string());
+        }
+        if !(1..=MAX_LIFETIME_DAYS).contains(&lifetime_days) {
+            return Err(format!("Token lifetime must be 1 to {MAX_LIFETIME_DAYS} days"));
+        }
+        let mut unique = Vec::new();
+        for scope in scopes {
+            if !unique.contains(&scope) {
+                unique.push(scope);
+            }
+        }
+        let secret = format!("{TOKEN_PREFIX}{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+        let now = Utc::now();
+        let token = Self {
+            id: Uuid::new_v4(),
+            user_id,
+            name: name.to_string(),
+            display_prefix: secret[..TOKEN_PREFIX.len() + 8].to_string(),
+            token_hash: hash_secret(&secret),
+            scopes: unique,
+            created_at: now,
+            expires_at: now + Duration::days(lifetime_days),
+            last_used_at: None,
+            revoked_at: None,
+        };
+        Ok((token, secret))
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at <= now
+    }
+
+    pub fn has_scope(&self, scope: TokenScope) -> bool {
+        self.scopes.contains(&scope) || (scope == TokenScope::FilesRead && self.scopes.contains(&TokenScope::FilesWrite))
+    }
+
+    /// Whether the last use is stale enough to record again; bounds writes for busy scripts.
+    pub fn use_worth_recording(&self, now: DateTime<Utc>) -> bool {
+        self.last_used_at.map_or(true, |at| now - at >= Duration::minutes(1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_issue() {
+        let (token, secret) = AccessToken::issue(
+            UserId::new(),
+            "backup",
+            vec![TokenScope::FilesWrite, TokenScope::FilesWrite],
+            DEFAULT_LIFETIME_DAYS,
+        )
+        .unwrap();
+        assert!(secret.starts_with(TOKEN_PREFIX));
+        assert!(secret.starts_with(&token.display_prefix));
+        assert_eq!(token.token_hash, hash_secret(&secret));
+        assert_eq!(token.scopes, vec![TokenScope::FilesWrite]);
+        assert!(token.has_scope(TokenScope::FilesRead));
+        assert!(!token.has_scope(TokenScope::MetricsRead));
+        assert!(!token.is_expired(Utc::now()));
+        assert!(token.is_expired(Utc::now() + Duration::days(DEFAULT_LIFETIME_DAYS + 1)));
+
+        assert!(AccessToken::issue(UserId::new(), "backup", vec![], 30).is_err());
+        assert!(AccessToken::issue(UserId::new(), "backup", vec![TokenScope::MetricsRead], 0).is_err());
+        assert!(AccessToken::issue(UserId::new(), "backup", vec![TokenScope::MetricsRead], MAX_LIFETIME_DAYS + 1).is_err());
+    }
+
+    #[test]
+    fn test_use_worth_recording() {
+        let (mut token, _) = AccessToken::issue(UserId::new(), "metrics", vec![TokenScope::MetricsRead], 30).unwrap();
+        let now = Utc::now();
+        assert!(token.use_worth_recording(now));
+        token.last_used_at = Some(now - Duration::seconds(10));
+        assert!(!token.use_worth_recording(now));
+        token.last_used_at = Some(now - Duration::minutes(2));
+        assert!(token.use_worth_recording(now));
+    }
+}
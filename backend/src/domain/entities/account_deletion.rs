@@ -0,0 +1,59 @@
+use crate::domain::value_objects::UserId;
+use chrono::{DateTime, Duration, Utc};
+
+/// Grace period between a deletion request and the purge, unless configured otherwise
+pub const DEFAULT_GRACE_DAYS: i64 = 30;
+
+/// A pending or completed account deletion.
+///
+/// Retention rules: on request the account stops working at once (permissions and logins are
+/// revoked). After `purge_after`, credentials, preferences, sessions, permissions and the vault
+/// are deleted; the user row is kept, anonymized, and audit entries are kept with every
+/// reference to the user scrubbed, so owners' trails stay complete.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct AccountDeletion {
+    pub user_id: UserId,
+    /// The user themselves, or the owner who deleted their client
+    pub requested_by: UserId,
+    pub requested_at: DateTime<Utc>,
+    pub purge_after: DateTime<Utc>,
+    pub purged_at: Option<DateTime<Utc>>,
+}
+
+impl AccountDeletion {
+    pub fn new(user_id: UserId, requested_by: UserId, grace: Duration, now: DateTime<Utc>) -> Self {
+        Self {
+            user_id,
+            requested_by,
+            requested_at: now,
+            purge_after: now + grace,
+            purged_at: None,
+        }
+    }
+
+    pub fn is_self_service(&self) -> bool {
+        self.user_id == self.requested_by
+    }
+
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.purged_at.is_none() && self.purge_after <= now
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deletion_is_due_once_after_grace_period() {
+        let now = Utc::now();
+        let user = UserId::new();
+        let mut deletion = AccountDeletion::new(user.clone(), user, Duration::days(DEFAULT_GRACE_DAYS), now);
+        assert!(deletion.is_self_service());
+        assert!(!deletion.is_due(now + Duration::days(29)));
+        assert!(deletion.is_due(now + Duration::days(30)));
+
+        deletion.purged_at = Some(now + Duration::days(30));
+        assert!(!deletion.is_due(now + Duration::days(31)));
+    }
+}
@@ -0,0 +1,21 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use crate::domain::value_objects::UserId;
+
+/// A panic an SDK app reported just before it exited, kept for super admins.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct AppCrash {
+    pub id: Uuid,
+    pub session_id: String,
+    /// Who launched the session; unknown when the app crashed before identifying it
+    pub user_id: Option<UserId>,
+    pub app_id: Option<String>,
+    pub message: String,
+    pub location: Option<String>,
+    pub backtrace: String,
+    /// The last input events the app handled, oldest first
+    pub recent_input: Vec<String>,
+    /// Frames the app drew before crashing
+    pub frames: u64,
+    pub crashed_at: DateTime<Utc>,
+}
@@ -0,0 +1,94 @@
+use crate::domain::value_objects::{UserId, UserRole};
+use chrono::{DateTime, Utc};
+
+/// How an owner lets an app installed under `APPS_ROOT` be used in their vault. Apps without
+/// a setting are enabled for everyone.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct AppSetting {
+    pub owner_id: UserId,
+    /// See [`normalize_app_id`]
+    pub app_id: String,
+    pub enabled: bool,
+    /// Roles in the vault that may use the app: the owner, or clients
+    pub allowed_roles: Vec<UserRole>,
+    /// Users who may use the app whatever their role
+    pub allowed_users: Vec<UserId>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl AppSetting {
+    pub fn new(owner_id: UserId, app_id: &str) -> Result<Self, String> {
+        Ok(Self {
+            owner_id,
+            app_id: normalize_app_id(app_id)?,
+            enabled: true,
+            allowed_roles: Vec::new(),
+            allowed_users: Vec::new(),
+            updated_at: Utc::now(),
+        })
+    }
+
+    /// Whether `user_id`, acting as `role` in the vault, may see and launch the app. With both
+    /// lists empty everyone may; otherwise the role or the user must be listed.
+    pub fn permits(&self, role: UserRole, user_id: &UserId) -> bool {
+        if !self.enabled {
+            return false;
+        }
+        if self.allowed_roles.is_empty() && self.allowed_users.is_empty() {
+            return true;
+        }
+        self.allowed_roles.contains(&role) || self.allowed_users.contains(user_id)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.allowed_roles.contains(&UserRole::SuperAdmin) {
+            return Err("Allowed roles are the vault's roles: Owner or Client".to_string());
+        }
+        if self.allowed_users.len() > 1000 {
+            return Err("At most 1000 allowed users".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// App ids as the launcher resolves them: `file-explorer` and `file_explorer` are one app.
+pub fn normalize_app_id(app_id: &str) -> Result<String, String> {
+    let id = app_id.trim().replace('-', "_");
+    if id.is_empty() || id.len() > 64 || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(format!("Invalid app id: {app_id}"));
+    }
+    Ok(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_permits_by_role_or_user() {
+        let (client, other) = (UserId::new(), UserId::new());
+        let mut setting = AppSetting::new(UserId::new(), "file-explorer").unwrap();
+        assert_eq!(setting.app_id, "file_explorer");
+        assert!(setting.permits(UserRole::Client, &client));
+
+        setting.allowed_roles = vec![UserRole::Owner];
+        assert!(setting.permits(UserRole::Owner, &other));
+        assert!(!setting.permits(UserRole::Client, &client));
+        setting.allowed_users = vec![client.clone()];
+        assert!(setting.permits(UserRole::Client, &client));
+        assert!(!setting.permits(UserRole::Client, &other));
+
+        setting.enabled = false;
+        assert!(!setting.permits(UserRole::Owner, &other));
+    }
+
+    #[test]
+    fn test_rejects_invalid_ids_and_roles() {
+        assert!(normalize_app_id("../bin").is_err());
+        assert!(normalize_app_id("").is_err());
+        assert!(normalize_app_id("file explorer").is_err());
+        let mut setting = AppSetting::new(UserId::new(), "notes").unwrap();
+        setting.allowed_roles = vec![UserRole::SuperAdmin];
+        assert!(setting.validate().is_err());
+    }
+}
@@ -0,0 +1,29 @@
+use crate::domain::value_objects::UserId;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Immutable record of a security-relevant action, kept for owners and auditors.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct AuditEvent {
+    pub id: Uuid,
+    pub session_id: Option<String>,
+    pub user_id: Option<UserId>,
+    pub owner_id: Option<UserId>,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+impl AuditEvent {
+    pub fn new(event_type: &str, payload: serde_json::Value) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            session_id: None,
+            user_id: None,
+            owner_id: None,
+            event_type: event_type.to_string(),
+            payload,
+            created_at: Utc::now(),
+        }
+    }
+}
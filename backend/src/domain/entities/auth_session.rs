@@ -0,0 +1,34 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use crate::domain::value_objects::UserId;
+
+/// A login on one device: every token issued at login carries its id, so revoking the session
+/// signs that device out.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct AuthSession {
+    pub id: Uuid,
+    pub user_id: UserId,
+    pub user_agent: Option<String>,
+    pub ip: String,
+    pub created_at: DateTime<Utc>,
+    /// Last authenticated request, updated at most once a minute
+    pub last_seen_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl AuthSession {
+    pub fn new(user_id: UserId, ip: String, user_agent: Option<String>, expires_at: DateTime<Utc>) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            user_agent,
+            ip,
+            created_at: now,
+            last_seen_at: now,
+            expires_at,
+            revoked_at: None,
+        }
+    }
+}
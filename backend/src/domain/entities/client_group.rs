@@ -0,0 +1,96 @@
+use crate::domain::value_objects::UserId;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use super::file_permission::FilePermission;
+use super::invitation::GrantedPath;
+
+const MAX_NAME_LEN: usize = 64;
+
+/// Clients an owner manages together (e.g. "family", "accountant"). Every member holds a
+/// [`FilePermission`] per grant, tagged with the group so membership changes can undo it.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ClientGroup {
+    pub id: Uuid,
+    pub owner_id: UserId,
+    pub name: String,
+    pub grants: Vec<GrantedPath>,
+    pub members: Vec<UserId>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ClientGroup {
+    pub fn new(owner_id: UserId, name: String, grants: Vec<GrantedPath>) -> Result<Self, String> {
+        let group = Self {
+            id: Uuid::new_v4(),
+            owner_id,
+            name: name.trim().to_string(),
+            grants,
+            members: Vec::new(),
+            created_at: Utc::now(),
+        };
+        group.validate()?;
+        Ok(group)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.is_empty() || self.name.chars().count() > MAX_NAME_LEN {
+            return Err(format!("Group name must be 1 to {} characters", MAX_NAME_LEN));
+        }
+        if self.grants.iter().any(|g| g.path.contains("..") || g.path.starts_with('/')) {
+            return Err("Invalid path: must be a relative path without '..'".to_string());
+        }
+        Ok(())
+    }
+
+    pub fn has_member(&self, client_id: &UserId) -> bool {
+        self.members.contains(client_id)
+    }
+
+    /// The permissions a member derives from the group's grants.
+    pub fn permissions_for(&self, client_id: &UserId) -> Vec<FilePermission> {
+        self.grants
+            .iter()
+            .map(|grant| FilePermission {
+                id: Uuid::new_v4(),
+                owner_id: self.owner_id.clone(),
+                client_id: client_id.clone(),
+                path: grant.path.clone(),
+                access: grant.access.clone(),
+                granted_at: Utc::now(),
+                expires_at: None,
+                revoked_at: None,
+                view_only: grant.view_only,
+                interaction: grant.interaction,
+                group_id: Some(self.id),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::invitation::AccessLevel;
+
+    fn grant(path: &str) -> GrantedPath {
+        GrantedPath { path: path.to_string(), access: vec![AccessLevel::Read], view_only: false, interaction: Default::default() }
+    }
+
+    #[test]
+    fn test_new_rejects_blank_names_and_escaping_paths() {
+        assert!(ClientGroup::new(UserId::new(), "  ".to_string(), vec![]).is_err());
+        assert!(ClientGroup::new(UserId::new(), "family".to_string(), vec![grant("../etc")]).is_err());
+        assert!(ClientGroup::new(UserId::new(), "family".to_string(), vec![grant("/abs")]).is_err());
+        assert_eq!(ClientGroup::new(UserId::new(), " family ".to_string(), vec![]).unwrap().name, "family");
+    }
+
+    #[test]
+    fn test_derived_permissions_are_tagged_with_group() {
+        let group = ClientGroup::new(UserId::new(), "accountant".to_string(), vec![grant("taxes"), grant("bank")]).unwrap();
+        let client = UserId::new();
+        let permissions = group.permissions_for(&client);
+        assert_eq!(permissions.len(), 2);
+        assert!(permissions.iter().all(|p| p.group_id == Some(group.id) && p.client_id == client));
+        assert_eq!(permissions[1].path, "bank");
+    }
+}
@@ -1,53 +1,275 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
 use crate::domain::value_objects::*;
-use webauthn_rs::prelude::Passkey;
+use webauthn_rs::prelude::{AuthenticationResult, Passkey, RegisterPublicKeyCredential};
+
+/// Authenticator data flag: the credential may be synced to other devices
+const FLAG_BACKUP_ELIGIBLE: u8 = 0x08;
+/// Authenticator data flag: the credential is currently synced
+const FLAG_BACKUP_STATE: u8 = 0x10;
+/// Authenticator data flag: attested credential data (AAGUID onwards) follows
+const FLAG_ATTESTED_DATA: u8 = 0x40;
+
+/// What the authenticator told us about a credential
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct CredentialMetadata {
+    /// Authenticator model, when it disclosed one (privacy-preserving authenticators send zeros)
+    pub aaguid: Option<Uuid>,
+    /// How the browser can reach the authenticator: "usb", "nfc", "ble", "internal", "hybrid"
+    pub transports: Vec<String>,
+    pub backup_eligible: bool,
+    pub backup_state: bool,
+}
+
+impl CredentialMetadata {
+    /// Read from a registration response: AAGUID and backup flags from the authenticator data in
+    /// the attestation object, transports from the response itself.
+    pub fn from_registration(credential: &RegisterPublicKeyCredential) -> Self {
+        let transports = credential
+            .response
+            .transports
+            .as_ref()
+            .and_then(|t| serde_json::to_value(t).ok())
+            .and_then(|v| serde_json::from_value(v).ok())
+            .unwrap_or_default();
+        let mut metadata = Self { transports, ..Default::default() };
+        if let Some(auth_data) = authenticator_data(credential.response.attestation_object.as_ref()) {
+            metadata.apply_authenticator_data(auth_data);
+        }
+        metadata
+    }
+
+    fn apply_authenticator_data(&mut self, auth_data: &[u8]) {
+        let Some(&flags) = auth_data.get(32) else { return };
+        self.backup_eligible = flags & FLAG_BACKUP_ELIGIBLE != 0;
+        self.backup_state = flags & FLAG_BACKUP_STATE != 0;
+        if flags & FLAG_ATTESTED_DATA != 0 {
+            self.aaguid = auth_data
+                .get(37..53)
+                .and_then(|bytes| Uuid::from_slice(bytes).ok())
+                .filter(|aaguid| !aaguid.is_nil());
+        }
+    }
+}
+
+/// When a credential was registered and how it has been used since
+#[derive(Debug, Clone)]
+pub struct CredentialUsage {
+    pub sign_count: u32,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    /// Logins whose signature counter did not move forward
+    pub sign_count_anomalies: u32,
+}
 
 #[derive(Debug, Clone)]
 pub struct Credential {
     user_id: UserId,
     credential_id: Vec<u8>,
     passkey: Passkey,
-    sign_count: u32,
+    metadata: CredentialMetadata,
+    usage: CredentialUsage,
 }
 
 impl Credential {
-    pub fn new(user_id: UserId, passkey: Passkey) -> Self {
+    pub fn new(user_id: UserId, passkey: Passkey, metadata: CredentialMetadata) -> Self {
         Self {
             user_id,
             credential_id: passkey.cred_id().as_ref().to_vec(),
             passkey,
-            sign_count: 0,
+            metadata,
+            usage: CredentialUsage {
+                sign_count: 0,
+                created_at: Utc::now(),
+                last_used_at: None,
+                sign_count_anomalies: 0,
+            },
         }
     }
-    
+
     pub fn from_persistence(
         user_id: UserId,
         credential_id: Vec<u8>,
         passkey: Passkey,
-        sign_count: u32,
+        metadata: CredentialMetadata,
+        usage: CredentialUsage,
     ) -> Self {
         Self {
             user_id,
             credential_id,
             passkey,
-            sign_count,
+            metadata,
+            usage,
         }
     }
-    
+
     pub fn user_id(&self) -> &UserId {
         &self.user_id
     }
-    
+
     pub fn credential_id(&self) -> &[u8] {
         &self.credential_id
     }
-    
+
     pub fn passkey(&self) -> &Passkey {
         &self.passkey
     }
-    
+
     pub fn sign_count(&self) -> u32 {
-        self.sign_count
+        self.usage.sign_count
+    }
+
+    pub fn metadata(&self) -> &CredentialMetadata {
+        &self.metadata
+    }
+
+    pub fn usage(&self) -> &CredentialUsage {
+        &self.usage
+    }
+
+    /// Apply a successful authentication with this credential at `at`. Returns true when the
+    /// signature counter did not move forward, which may mean the authenticator was cloned; the
+    /// stored counter is then left as it was.
+    pub fn record_use(&mut self, result: &AuthenticationResult, at: DateTime<Utc>) -> bool {
+        let counter = result.counter();
+        let anomaly = (counter > 0 || self.usage.sign_count > 0) && counter <= self.usage.sign_count;
+        if anomaly {
+            self.usage.sign_count_anomalies += 1;
+        } else {
+            self.usage.sign_count = counter;
+        }
+        self.passkey.update_credential(result);
+        self.metadata.backup_eligible = result.backup_eligible();
+        self.metadata.backup_state = result.backup_state();
+        self.usage.last_used_at = Some(at);
+        anomaly
+    }
+}
+
+/// The `authData` byte string of a CBOR attestation object (`{fmt, attStmt, authData}`).
+fn authenticator_data(attestation_object: &[u8]) -> Option<&[u8]> {
+    let mut pos = 0;
+    let (major, entries) = cbor_head(attestation_object, &mut pos)?;
+    if major != 5 {
+        return None;
+    }
+    for _ in 0..entries {
+        let (key_major, key_len) = cbor_head(attestation_object, &mut pos)?;
+        if key_major != 3 {
+            return None;
+        }
+        let key = cbor_bytes(attestation_object, &mut pos, key_len)?;
+        if key == b"authData" {
+            let (value_major, len) = cbor_head(attestation_object, &mut pos)?;
+            return if value_major == 2 { cbor_bytes(attestation_object, &mut pos, len) } else { None };
+        }
+        cbor_skip(attestation_object, &mut pos, 0)?;
+    }
+    None
+}
+
+/// Major type and argument of the CBOR item at `pos`. Indefinite lengths are not used in
+/// attestation objects.
+fn cbor_head(data: &[u8], pos: &mut usize) -> Option<(u8, u64)> {
+    let initial = *data.get(*pos)?;
+    *pos += 1;
+    let info = initial & 0x1f;
+    let argument = match info {
+        0..=23 => info as u64,
+        24..=27 => {
+            let bytes = cbor_bytes(data, pos, 1 << (info - 24))?;
+            bytes.iter().fold(0u64, |acc, &b| (acc << 8) | b as u64)
+        }
+        _ => return None,
+    };
+    Some((initial >> 5, argument))
+}
+
+fn cbor_bytes<'a>(data: &'a [u8], pos: &mut usize, len: u64) -> Option<&'a [u8]> {
+    let end = pos.checked_add(usize::try_from(len).ok()?)?;
+    let bytes = data.get(*pos..end)?;
+    *pos = end;
+    Some(bytes)
+}
+
+fn cbor_skip(data: &[u8], pos: &mut usize, depth: u8) -> Option<()> {
+    if depth > 8 {
+        return None;
+    }
+    let (major, argument) = cbor_head(data, pos)?;
+    match major {
+        0 | 1 | 7 => {}
+        2 | 3 => {
+            cbor_bytes(data, pos, argument)?;
+        }
+        4 | 5 => {
+            let items = if major == 5 { argument.checked_mul(2)? } else { argument };
+            for _ in 0..items {
+                cbor_skip(data, pos, depth + 1)?;
+            }
+        }
+        6 => cbor_skip(data, pos, depth + 1)?,
+        _ => return None,
+    }
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AAGUID: [u8; 16] = [0xad, 0xce, 0x00, 0x02, 0x35, 0xbc, 0xc6, 0x0a, 0x64, 0x8b, 0x0b, 0x25, 0xf1, 0xf0, 0x55, 0x03];
+
+    fn auth_data(flags: u8, aaguid: [u8; 16]) -> Vec<u8> {
+        let mut data = vec![0u8; 32];
+        data.push(flags);
+        data.extend_from_slice(&[0, 0, 0, 7]);
+        data.extend_from_slice(&aaguid);
+        data.extend_from_slice(&[0, 2, 0xaa, 0xbb]);
+        data
+    }
+
+    /// `{"fmt": "packed", "attStmt": {"alg": -7, "sig": h'0102'}, "authData": <auth_data>}`
+    fn attestation_object(auth_data: &[u8]) -> Vec<u8> {
+        let mut object = vec![0xa3, 0x63];
+        object.extend_from_slice(b"fmt");
+        object.push(0x66);
+        object.extend_from_slice(b"packed");
+        object.push(0x67);
+        object.extend_from_slice(b"attStmt");
+        object.extend_from_slice(&[0xa2, 0x63]);
+        object.extend_from_slice(b"alg");
+        object.extend_from_slice(&[0x26, 0x63]);
+        object.extend_from_slice(b"sig");
+        object.extend_from_slice(&[0x42, 0x01, 0x02, 0x68]);
+        object.extend_from_slice(b"authData");
+        object.extend_from_slice(&[0x58, auth_data.len() as u8]);
+        object.extend_from_slice(auth_data);
+        object
+    }
+
+    #[test]
+    fn test_reads_aaguid_and_backup_flags_from_attestation() {
+        let data = auth_data(0x45 | FLAG_BACKUP_ELIGIBLE | FLAG_BACKUP_STATE, AAGUID);
+        let object = attestation_object(&data);
+        assert_eq!(authenticator_data(&object), Some(data.as_slice()));
+
+        let mut metadata = CredentialMetadata::default();
+        metadata.apply_authenticator_data(&data);
+        assert_eq!(metadata.aaguid, Some(Uuid::from_bytes(AAGUID)));
+        assert!(metadata.backup_eligible);
+        assert!(metadata.backup_state);
+    }
+
+    #[test]
+    fn test_zero_aaguid_and_truncated_objects_yield_nothing() {
+        let mut metadata = CredentialMetadata::default();
+        metadata.apply_authenticator_data(&auth_data(0x45, [0; 16]));
+        assert_eq!(metadata, CredentialMetadata::default());
+
+        let object = attestation_object(&auth_data(0x45, AAGUID));
+        assert_eq!(authenticator_data(&object[..object.len() - 1]), None);
+        assert_eq!(authenticator_data(&[0x80]), None);
     }
-    
-    // Removed unused method update_sign_count
 }
@@ -0,0 +1,101 @@
+use crate::domain::entities::file_job::JobStatus;
+use crate::domain::value_objects::UserId;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Steps of an export, reported as its progress
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportStep {
+    /// Account, preferences, passkeys and devices
+    Profile,
+    /// Permissions and audit entries
+    Records,
+    /// Vault files the user owns or was granted
+    Files,
+    /// Packing everything into the downloadable zip
+    Archive,
+}
+
+impl ExportStep {
+    pub const ALL: [ExportStep; 4] = [ExportStep::Profile, ExportStep::Records, ExportStep::Files, ExportStep::Archive];
+}
+
+/// A downloadable archive of everything the platform holds about one user, assembled in the
+/// background.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct DataExport {
+    pub id: Uuid,
+    /// Whose data is exported
+    pub subject_id: UserId,
+    pub requested_by: UserId,
+    /// Set when an owner exports a client's footprint: only the permissions and audit entries
+    /// between that client and this owner, without file contents
+    pub owner_id: Option<UserId>,
+    pub status: JobStatus,
+    /// Steps finished so far, out of `total`
+    pub completed: u32,
+    pub total: u32,
+    pub error: Option<String>,
+    /// Size of the finished archive
+    pub size_bytes: Option<u64>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl DataExport {
+    /// A user's export of their own data.
+    pub fn for_self(user_id: UserId) -> Self {
+        Self::new(user_id.clone(), user_id, None)
+    }
+
+    /// An owner's export of what `client_id` shares with them.
+    pub fn for_client(owner_id: UserId, client_id: UserId) -> Self {
+        Self::new(client_id, owner_id.clone(), Some(owner_id))
+    }
+
+    fn new(subject_id: UserId, requested_by: UserId, owner_id: Option<UserId>) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            subject_id,
+            requested_by,
+            owner_id,
+            status: JobStatus::Queued,
+            completed: 0,
+            total: ExportStep::ALL.len() as u32,
+            error: None,
+            size_bytes: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Whether `user_id` may follow and download this export.
+    pub fn is_visible_to(&self, user_id: &UserId) -> bool {
+        &self.requested_by == user_id
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.status == JobStatus::Completed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_export_is_scoped_to_the_requesting_owner() {
+        let (owner, client) = (UserId::new(), UserId::new());
+        let export = DataExport::for_client(owner.clone(), client.clone());
+        assert_eq!(export.subject_id, client);
+        assert_eq!(export.owner_id, Some(owner.clone()));
+        assert!(export.is_visible_to(&owner));
+        assert!(!export.is_visible_to(&client));
+        assert_eq!(export.total, 4);
+
+        let own = DataExport::for_self(client.clone());
+        assert!(own.owner_id.is_none() && own.is_visible_to(&client));
+        assert!(!own.is_ready());
+    }
+}
@@ -0,0 +1,125 @@
+use crate::domain::value_objects::{UserId, UserRole};
+use chrono::{DateTime, Utc};
+
+/// A local account's link to an account at an external OpenID Connect provider, e.g. the
+/// household's Authelia or Keycloak.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ExternalIdentity {
+    /// The provider's `iss`
+    pub issuer: String,
+    /// The provider's stable `sub` for the account, which unlike the email never changes
+    pub subject: String,
+    pub user_id: UserId,
+    pub created_at: DateTime<Utc>,
+    pub last_login_at: DateTime<Utc>,
+}
+
+impl ExternalIdentity {
+    pub fn new(issuer: String, subject: String, user_id: UserId) -> Self {
+        let now = Utc::now();
+        Self { issuer, subject, user_id, created_at: now, last_login_at: now }
+    }
+}
+
+/// Whether a passkey is still needed once the provider has vouched for a user
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PasskeyRequirement {
+    /// Provider and passkey logins are alternatives; either alone is enough
+    #[default]
+    Primary,
+    /// Provider logins must be confirmed with one of the user's passkeys
+    SecondFactor,
+}
+
+impl std::str::FromStr for PasskeyRequirement {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "primary" => Ok(PasskeyRequirement::Primary),
+            "second_factor" => Ok(PasskeyRequirement::SecondFactor),
+            other => Err(format!("Unknown passkey requirement '{other}': expected primary or second_factor")),
+        }
+    }
+}
+
+/// Rules giving accounts created on a first provider login their roles, from the groups the
+/// provider reports, e.g. `vault-admins=super_admin,family=owner,guests=client`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RoleMapping {
+    rules: Vec<(String, UserRole)>,
+}
+
+impl RoleMapping {
+    pub fn parse(rules: &str) -> Result<Self, String> {
+        let rules = rules
+            .split(',')
+            .map(str::trim)
+            .filter(|rule| !rule.is_empty())
+            .map(|rule| {
+                let (group, role) = rule
+                    .split_once('=')
+                    .ok_or_else(|| format!("Invalid role mapping '{rule}': expected group=role"))?;
+                let role = match role.trim() {
+                    "super_admin" => UserRole::SuperAdmin,
+                    "owner" => UserRole::Owner,
+                    "client" => UserRole::Client,
+                    other => return Err(format!("Unknown role '{other}' in role mapping")),
+                };
+                Ok((group.trim().to_string(), role))
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(Self { rules })
+    }
+
+    /// Roles of every rule whose group is in `groups`. A super admin also gets `Owner`, as
+    /// accounts created through setup do.
+    pub fn roles_for(&self, groups: &[String]) -> Vec<UserRole> {
+        let mut roles = Vec::new();
+        for (group, role) in &self.rules {
+            if groups.contains(group) && !roles.contains(role) {
+                roles.push(*role);
+            }
+        }
+        if roles.contains(&UserRole::SuperAdmin) && !roles.contains(&UserRole::Owner) {
+            roles.push(UserRole::Owner);
+        }
+        roles
+    }
+}
+
+/// How provider logins are turned into local sessions, from `OIDC_ROLE_MAPPING` and
+/// `OIDC_PASSKEY`.
+#[derive(Debug, Clone, Default)]
+pub struct OidcPolicy {
+    pub role_mapping: RoleMapping,
+    pub passkey: PasskeyRequirement,
+}
+
+impl OidcPolicy {
+    pub fn from_env() -> Result<Self, String> {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+        Ok(Self {
+            role_mapping: RoleMapping::parse(&var("OIDC_ROLE_MAPPING").unwrap_or_default())?,
+            passkey: var("OIDC_PASSKEY").map(|v| v.parse::<PasskeyRequirement>()).transpose()?.unwrap_or_default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_role_mapping() {
+        let mapping = RoleMapping::parse("vault-admins=super_admin, family=owner,guests=client,friends=client").unwrap();
+        let groups = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+        assert_eq!(mapping.roles_for(&groups(&["family", "staff"])), vec![UserRole::Owner]);
+        assert_eq!(mapping.roles_for(&groups(&["guests", "friends"])), vec![UserRole::Client]);
+        assert_eq!(mapping.roles_for(&groups(&["vault-admins"])), vec![UserRole::SuperAdmin, UserRole::Owner]);
+        assert!(mapping.roles_for(&groups(&["staff"])).is_empty());
+        assert!(RoleMapping::parse("family").is_err());
+        assert!(RoleMapping::parse("family=admin").is_err());
+        assert_eq!(RoleMapping::parse("").unwrap(), RoleMapping::default());
+    }
+}
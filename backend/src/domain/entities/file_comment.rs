@@ -0,0 +1,34 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use crate::domain::value_objects::UserId;
+
+/// Longest comment, in characters
+pub const MAX_BODY_CHARS: usize = 4000;
+
+/// A note left on a vault file by its owner or a client it is shared with. The body is the
+/// Markdown the author typed; see [`crate::domain::services::markdown`].
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct FileComment {
+    pub id: Uuid,
+    /// Vault the file is in
+    pub owner_id: UserId,
+    /// Vault-relative
+    pub path: String,
+    pub author_id: UserId,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl FileComment {
+    pub fn new(owner_id: UserId, path: &str, author_id: UserId, body: &str) -> Result<Self, String> {
+        let path = path.trim().trim_matches('/').to_string();
+        if path.is_empty() || path.split('/').any(|segment| segment == "." || segment == "..") {
+            return Err(format!("Invalid file: {path}"));
+        }
+        let body = body.trim();
+        if body.is_empty() || body.chars().count() > MAX_BODY_CHARS {
+            return Err(format!("Comment must be 1 to {MAX_BODY_CHARS} characters"));
+        }
+        Ok(Self { id: Uuid::new_v4(), owner_id, path, author_id, body: body.to_string(), created_at: Utc::now() })
+    }
+}
@@ -0,0 +1,210 @@
+use crate::domain::services::permission_evaluator::Operation;
+use crate::domain::value_objects::UserId;
+use chrono::{DateTime, Utc};
+use shared::ArchiveFormat;
+use std::path::Path;
+use uuid::Uuid;
+
+/// One step of a bulk job. Paths are relative to the owner's vault.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum FileOperation {
+    Move { from: String, to: String },
+    Copy { from: String, to: String },
+    Delete { path: String },
+    /// Pack `paths` into a new archive at `destination`
+    Zip { paths: Vec<String>, destination: String },
+    /// Unpack a zip or tar archive into the new folder `destination`
+    Extract { archive: String, destination: String },
+}
+
+impl FileOperation {
+    /// Every vault path the operation reads or writes.
+    pub fn paths(&self) -> Vec<&str> {
+        match self {
+            FileOperation::Move { from, to } | FileOperation::Copy { from, to } => vec![from.as_str(), to.as_str()],
+            FileOperation::Delete { path } => vec![path.as_str()],
+            FileOperation::Extract { archive, destination } => vec![archive.as_str(), destination.as_str()],
+            FileOperation::Zip { paths, destination } => {
+                paths.iter().map(String::as_str).chain([destination.as_str()]).collect()
+            }
+        }
+    }
+
+    /// Each vault path with what the operation does to it. Destinations only gain new entries,
+    /// since operations never overwrite.
+    pub fn accesses(&self) -> Vec<(&str, Operation)> {
+        match self {
+            FileOperation::Move { from, to } => vec![(from.as_str(), Operation::Delete), (to.as_str(), Operation::Write)],
+            FileOperation::Copy { from, to } => vec![(from.as_str(), Operation::Read), (to.as_str(), Operation::Write)],
+            FileOperation::Delete { path } => vec![(path.as_str(), Operation::Delete)],
+            FileOperation::Extract { archive, destination } => {
+                vec![(archive.as_str(), Operation::Read), (destination.as_str(), Operation::Write)]
+            }
+            FileOperation::Zip { paths, destination } => paths
+                .iter()
+                .map(|path| (path.as_str(), Operation::Read))
+                .chain([(destination.as_str(), Operation::Write)])
+                .collect(),
+        }
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        match self {
+            FileOperation::Zip { paths, .. } if paths.is_empty() => {
+                return Err("A zip operation needs at least one path".to_string());
+            }
+            FileOperation::Extract { archive, .. } if ArchiveFormat::from_path(Path::new(archive)).is_none() => {
+                return Err(format!("Not a zip or tar archive: {archive}"));
+            }
+            _ => {}
+        }
+        self.paths().into_iter().try_for_each(validate_vault_path)
+    }
+}
+
+/// Vault paths are non-empty, relative, and never climb out with `..`.
+pub fn validate_vault_path(path: &str) -> Result<(), String> {
+    let trimmed = path.trim_matches('/');
+    if path.starts_with('/') || trimmed.is_empty() || trimmed.split('/').any(|part| part == "..") {
+        return Err(format!("Invalid path '{path}': must be a non-empty relative path without '..'"));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+impl JobStatus {
+    pub fn as_db_str(&self) -> &'static str {
+        match self {
+            JobStatus::Queued => "queued",
+            JobStatus::Running => "running",
+            JobStatus::Completed => "completed",
+            JobStatus::Failed => "failed",
+            JobStatus::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn from_db_str(s: &str) -> Result<Self, String> {
+        match s {
+            "queued" => Ok(JobStatus::Queued),
+            "running" => Ok(JobStatus::Running),
+            "completed" => Ok(JobStatus::Completed),
+            "failed" => Ok(JobStatus::Failed),
+            "cancelled" => Ok(JobStatus::Cancelled),
+            other => Err(format!("Unknown job status: {other}")),
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        matches!(self, JobStatus::Completed | JobStatus::Failed | JobStatus::Cancelled)
+    }
+}
+
+/// A batch of file operations run in the background, in order, stopping at the first failure.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct FileJob {
+    pub id: Uuid,
+    /// The vault the operations apply to
+    pub owner_id: UserId,
+    pub created_by: UserId,
+    pub operations: Vec<FileOperation>,
+    pub status: JobStatus,
+    /// Operations finished so far; a resumed job continues from here
+    pub completed: u32,
+    pub total: u32,
+    pub error: Option<String>,
+    pub cancel_requested: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl FileJob {
+    pub const MAX_OPERATIONS: usize = 10_000;
+
+    pub fn new(owner_id: UserId, created_by: UserId, operations: Vec<FileOperation>) -> Result<Self, String> {
+        if operations.is_empty() {
+            return Err("A job needs at least one operation".to_string());
+        }
+        if operations.len() > Self::MAX_OPERATIONS {
+            return Err(format!("A job holds at most {} operations", Self::MAX_OPERATIONS));
+        }
+        operations.iter().try_for_each(FileOperation::validate)?;
+        let now = Utc::now();
+        Ok(Self {
+            id: Uuid::new_v4(),
+            owner_id,
+            created_by,
+            total: operations.len() as u32,
+            operations,
+            status: JobStatus::Queued,
+            completed: 0,
+            error: None,
+            cancel_requested: false,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(operations: Vec<FileOperation>) -> Result<FileJob, String> {
+        FileJob::new(UserId::new(), UserId::new(), operations)
+    }
+
+    #[test]
+    fn test_new_job_is_queued_with_total() {
+        let job = job(vec![
+            FileOperation::Delete { path: "tmp/a.txt".to_string() },
+            FileOperation::Move { from: "inbox/b.txt".to_string(), to: "archive/b.txt".to_string() },
+        ])
+        .unwrap();
+        assert_eq!(job.status, JobStatus::Queued);
+        assert_eq!(job.total, 2);
+        assert_eq!(job.completed, 0);
+    }
+
+    #[test]
+    fn test_rejects_empty_jobs_and_escaping_paths() {
+        assert!(job(vec![]).is_err());
+        assert!(job(vec![FileOperation::Delete { path: "../etc".to_string() }]).is_err());
+        assert!(job(vec![FileOperation::Delete { path: "/etc".to_string() }]).is_err());
+        assert!(job(vec![FileOperation::Copy { from: "a".to_string(), to: "a/../../b".to_string() }]).is_err());
+        assert!(job(vec![FileOperation::Zip { paths: vec![], destination: "out.zip".to_string() }]).is_err());
+        assert!(job(vec![FileOperation::Extract { archive: "a.rar".to_string(), destination: "a".to_string() }]).is_err());
+    }
+
+    #[test]
+    fn test_operations_deserialize_from_tagged_json() {
+        let op: FileOperation = serde_json::from_str(r#"{"op":"zip","paths":["a","b"],"destination":"ab.zip"}"#).unwrap();
+        assert_eq!(op.paths(), vec!["a", "b", "ab.zip"]);
+    }
+
+    #[test]
+    fn test_accesses_remove_only_sources_that_go_away() {
+        let moved = FileOperation::Move { from: "inbox/b.txt".to_string(), to: "archive".to_string() };
+        assert_eq!(moved.accesses(), vec![("inbox/b.txt", Operation::Delete), ("archive", Operation::Write)]);
+        let copied = FileOperation::Copy { from: "inbox/b.txt".to_string(), to: "archive".to_string() };
+        assert_eq!(copied.accesses(), vec![("inbox/b.txt", Operation::Read), ("archive", Operation::Write)]);
+    }
+
+    #[test]
+    fn test_status_round_trips_through_db_strings() {
+        for status in [JobStatus::Queued, JobStatus::Running, JobStatus::Completed, JobStatus::Failed, JobStatus::Cancelled] {
+            assert_eq!(JobStatus::from_db_str(status.as_db_str()).unwrap(), status);
+        }
+        assert!(JobStatus::Cancelled.is_finished());
+        assert!(!JobStatus::Running.is_finished());
+    }
+}
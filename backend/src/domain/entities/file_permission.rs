@@ -1,8 +1,11 @@
 use crate::domain::value_objects::*;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use uuid::Uuid;
 use super::invitation::AccessLevel;
 
+/// Longest extension a single renewal grants
+pub const MAX_RENEWAL_DAYS: i64 = 3650;
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct FilePermission {
     pub id: Uuid,
@@ -13,11 +16,23 @@ pub struct FilePermission {
     pub granted_at: DateTime<Utc>,
     pub expires_at: Option<DateTime<Utc>>,
     pub revoked_at: Option<DateTime<Utc>>,
+    /// Streaming-only access: no downloads or uploads, and the video stream is watermarked
+    #[serde(default)]
+    pub view_only: bool,
+    /// Whether apps launched on the share take the client's input
+    #[serde(default)]
+    pub interaction: Interaction,
+    /// Set when the permission is derived from a [`super::client_group::ClientGroup`] membership
+    #[serde(default)]
+    pub group_id: Option<Uuid>,
 }
 
 impl FilePermission {
     pub fn is_active(&self) -> bool {
-        self.revoked_at.is_none() && self.expires_at.map(|e| e > Utc::now()).unwrap_or(true)
+        self.is_active_at(Utc::now())
+    }
+    pub fn is_active_at(&self, now: DateTime<Utc>) -> bool {
+        self.revoked_at.is_none() && self.expires_at.map(|e| e > now).unwrap_or(true)
     }
     pub fn allows(&self, level: AccessLevel) -> bool {
         self.access.contains(&level)
@@ -25,4 +40,73 @@ impl FilePermission {
     pub fn revoke(&mut self) {
         self.revoked_at = Some(Utc::now());
     }
+
+    /// New expiry after renewing by `extension`, or by the original grant length when `None`.
+    /// Time left on the permission is kept; an expired permission restarts from `now`. One
+    /// renewal extends by at most [`MAX_RENEWAL_DAYS`].
+    pub fn renewed_until(&self, extension: Option<Duration>, now: DateTime<Utc>) -> Result<DateTime<Utc>, String> {
+        if self.revoked_at.is_some() {
+            return Err("Permission has been revoked".to_string());
+        }
+        let expires_at = self.expires_at.ok_or("Permission does not expire")?;
+        let extension = extension.unwrap_or(expires_at - self.granted_at);
+        if extension <= Duration::zero() {
+            return Err("Renewal must extend the permission".to_string());
+        }
+        if extension > Duration::days(MAX_RENEWAL_DAYS) {
+            return Err(format!("A renewal extends by at most {} days", MAX_RENEWAL_DAYS));
+        }
+        expires_at
+            .max(now)
+            .checked_add_signed(extension)
+            .ok_or_else(|| "Renewal is too long".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn permission(granted_at: DateTime<Utc>, expires_at: Option<DateTime<Utc>>) -> FilePermission {
+        FilePermission {
+            id: Uuid::new_v4(),
+            owner_id: UserId::new(),
+            client_id: UserId::new(),
+            path: "docs".to_string(),
+            access: vec![AccessLevel::Read],
+            granted_at,
+            expires_at,
+            revoked_at: None,
+            view_only: false,
+            interaction: Interaction::Interactive,
+            group_id: None,
+        }
+    }
+
+    #[test]
+    fn test_renewal_defaults_to_original_grant_length() {
+        let now = Utc::now();
+        let p = permission(now - Duration::days(28), Some(now + Duration::days(2)));
+        assert_eq!(p.renewed_until(None, now).unwrap(), now + Duration::days(32));
+        assert_eq!(p.renewed_until(Some(Duration::days(7)), now).unwrap(), now + Duration::days(9));
+    }
+
+    #[test]
+    fn test_expired_permission_renews_from_now() {
+        let now = Utc::now();
+        let p = permission(now - Duration::days(10), Some(now - Duration::days(3)));
+        assert_eq!(p.renewed_until(Some(Duration::days(7)), now).unwrap(), now + Duration::days(7));
+    }
+
+    #[test]
+    fn test_renewal_rejects_permanent_and_revoked_permissions() {
+        let now = Utc::now();
+        assert!(permission(now, None).renewed_until(None, now).is_err());
+        let mut p = permission(now, Some(now + Duration::days(1)));
+        p.revoke();
+        assert!(p.renewed_until(None, now).is_err());
+        let p = permission(now, Some(now + Duration::days(1)));
+        assert!(p.renewed_until(Some(Duration::zero()), now).is_err());
+        assert!(p.renewed_until(Some(Duration::days(MAX_RENEWAL_DAYS + 1)), now).is_err());
+    }
 }
@@ -0,0 +1,155 @@
+use crate::domain::value_objects::UserId;
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+use crate::domain::services::secrets::hash_secret;
+
+/// Prefix of every file request link token
+pub const TOKEN_PREFIX: &str = "pvr_";
+
+/// Longest a file request may stay open
+pub const MAX_LIFETIME_DAYS: i64 = 90;
+
+/// Largest file a request accepts when the owner sets no limit
+pub const DEFAULT_MAX_FILE_BYTES: u64 = 100 * 1024 * 1024;
+
+/// Largest limit an owner may set
+pub const MAX_FILE_BYTES: u64 = 4 * 1024 * 1024 * 1024;
+
+/// Uploads a link may leave waiting for a decision or still arriving, so it cannot fill the disk
+pub const MAX_PENDING_UPLOADS: u64 = 100;
+
+/// Uploads a link may take at once
+pub const MAX_RECEIVING_UPLOADS: u64 = 4;
+
+/// An upload still arriving after this long is given up, its sender or the server having stopped
+pub const RECEIVING_EXPIRY_HOURS: i64 = 24;
+
+/// An upload-only link to one of an owner's folders, for people without an account. What
+/// arrives waits in the owner's inbox until approved. Only the token's hash is stored; the
+/// link is shown once, when it is created.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct FileRequest {
+    pub id: Uuid,
+    pub owner_id: UserId,
+    /// Where approved uploads are placed, vault-relative
+    pub folder: String,
+    /// Shown to uploaders instead of the folder's path
+    pub title: String,
+    #[serde(skip_serializing)]
+    pub token_hash: String,
+    pub max_file_bytes: u64,
+    /// Lowercase, without the dot; empty accepts any type
+    pub allowed_extensions: Vec<String>,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl FileRequest {
+    /// A new request and its link token.
+    pub fn create(
+        owner_id: UserId,
+        folder: &str,
+        title: &str,
+        max_file_bytes: Option<u64>,
+        allowed_extensions: &[String],
+        expires_in_days: i64,
+    ) -> Result<(Self, String), String> {
+        let folder = folder.trim().trim_matches('/').to_string();
+        if folder.split('/').any(|segment| segment == "." || segment == "..") {
+            return Err(format!("Invalid folder: {folder}"));
+        }
+        let title = title.trim();
+        let title = if title.is_empty() { folder.rsplit('/').next().unwrap_or_default() } else { title }.to_string();
+        if title.is_empty() || title.chars().count() > 100 {
+            return Err("File request title must be 1 to 100 characters".to_string());
+        }
+        let max_file_bytes = max_file_bytes.unwrap_or(DEFAULT_MAX_FILE_BYTES);
+        if !(1..=MAX_FILE_BYTES).contains(&max_file_bytes) {
+            return Err(format!("File size limit must be 1 to {MAX_FILE_BYTES} bytes"));
+        }
+        let mut extensions = Vec::new();
+        for extension in allowed_extensions {
+            let extension = extension.trim().trim_start_matches('.').to_lowercase();
+            if extension.is_empty() || !extension.chars().all(|c| c.is_ascii_alphanumeric()) {
+                return Err(format!("Invalid file type: {extension}"));
+            }
+            if !extensions.contains(&extension) {
+                extensions.push(extension);
+            }
+        }
+        if !(1..=MAX_LIFETIME_DAYS).contains(&expires_in_days) {
+            return Err(format!("File request expiry must be 1 to {MAX_LIFETIME_DAYS} days"));
+        }
+        let secret = format!("{TOKEN_PREFIX}{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+        let now = Utc::now();
+        let request = Self {
+            id: Uuid::new_v4(),
+            owner_id,
+            folder,
+            title,
+            token_hash: hash_secret(&secret),
+            max_file_bytes,
+            allowed_extensions: extensions,
+            expires_at: now + Duration::days(expires_in_days),
+            created_at: now,
+            revoked_at: None,
+        };
+        Ok((request, secret))
+    }
+
+    pub fn is_active_at(&self, now: DateTime<Utc>) -> bool {
+        self.revoked_at.is_none() && self.expires_at > now
+    }
+
+    /// The name an upload is kept under, if the request takes a file of that name and size.
+    /// Uploaders name a file, never a place: anything before the last separator is dropped.
+    pub fn accept(&self, file_name: &str, size: u64) -> Result<String, String> {
+        let name = file_name.rsplit(['/', '\\']).next().unwrap_or_default().trim();
+        if name.is_empty() || name == "." || name == ".." || name.chars().count() > 255 || name.chars().any(char::is_control) {
+            return Err(format!("Invalid file name: {file_name}"));
+        }
+        if size == 0 {
+            return Err("File is empty".to_string());
+        }
+        if size > self.max_file_bytes {
+            return Err(format!("File is too large: the limit is {} bytes", self.max_file_bytes));
+        }
+        if !self.allowed_extensions.is_empty() {
+            let extension = name.rsplit_once('.').map(|(_, ext)| ext.to_lowercase()).unwrap_or_default();
+            if !self.allowed_extensions.contains(&extension) {
+                return Err(format!("File type not accepted: only {} files", self.allowed_extensions.join(", ")));
+            }
+        }
+        Ok(name.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_and_accept() {
+        let types = vec![".PDF".to_string(), "jpg".to_string()];
+        let (request, secret) = FileRequest::create(UserId::new(), "/Taxes/2026/", "", Some(1000), &types, 14).unwrap();
+        assert!(secret.starts_with(TOKEN_PREFIX));
+        assert_eq!(request.token_hash, hash_secret(&secret));
+        assert_eq!((request.folder.as_str(), request.title.as_str()), ("Taxes/2026", "2026"));
+        assert_eq!(request.allowed_extensions, vec!["pdf", "jpg"]);
+        assert!(request.is_active_at(Utc::now()));
+        assert!(!request.is_active_at(Utc::now() + Duration::days(15)));
+
+        assert_eq!(request.accept("C:\\Scans\\receipt.Pdf", 10).unwrap(), "receipt.Pdf");
+        assert_eq!(request.accept("../../etc/photo.jpg", 10).unwrap(), "photo.jpg");
+        assert!(request.accept("notes.txt", 10).is_err());
+        assert!(request.accept("receipt.pdf", 1001).is_err());
+        assert!(request.accept("receipt.pdf", 0).is_err());
+        assert!(request.accept("scans/..", 10).is_err());
+
+        assert!(FileRequest::create(UserId::new(), "a/../b", "", None, &[], 7).is_err());
+        assert!(FileRequest::create(UserId::new(), "a", "", None, &[], 0).is_err());
+        assert!(FileRequest::create(UserId::new(), "a", "", Some(MAX_FILE_BYTES + 1), &[], 7).is_err());
+        assert!(FileRequest::create(UserId::new(), "a", "", None, &["p d f".to_string()], 7).is_err());
+    }
+}
@@ -0,0 +1,182 @@
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+use super::file_permission::FilePermission;
+use crate::domain::value_objects::UserId;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FolderExportStatus {
+    /// Waiting for the owner's decision
+    Requested,
+    /// Approved, the archive is being built
+    Approved,
+    /// The archive can be downloaded and its key fetched until `expires_at`
+    Ready,
+    Denied,
+    Failed,
+    /// Past `expires_at`: the archive and its key are gone
+    Expired,
+}
+
+impl FolderExportStatus {
+    pub fn as_db_str(&self) -> &'static str {
+        match self {
+            FolderExportStatus::Requested => "requested",
+            FolderExportStatus::Approved => "approved",
+            FolderExportStatus::Ready => "ready",
+            FolderExportStatus::Denied => "denied",
+            FolderExportStatus::Failed => "failed",
+            FolderExportStatus::Expired => "expired",
+        }
+    }
+
+    pub fn from_db_str(s: &str) -> Result<Self, String> {
+        match s {
+            "requested" => Ok(FolderExportStatus::Requested),
+            "approved" => Ok(FolderExportStatus::Approved),
+            "ready" => Ok(FolderExportStatus::Ready),
+            "denied" => Ok(FolderExportStatus::Denied),
+            "failed" => Ok(FolderExportStatus::Failed),
+            "expired" => Ok(FolderExportStatus::Expired),
+            other => Err(format!("Unknown folder export status: {other}")),
+        }
+    }
+}
+
+/// A client's request to take a shared folder offline. Once the owner approves it, the folder
+/// is packed into an encrypted zip whose password the client fetches from a separate link;
+/// both stop working at `expires_at`.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct FolderExport {
+    pub id: Uuid,
+    pub owner_id: UserId,
+    pub client_id: UserId,
+    /// Shared folder, vault-relative
+    pub path: String,
+    /// Why the client needs it offline, shown to the owner
+    pub reason: String,
+    pub status: FolderExportStatus,
+    pub error: Option<String>,
+    /// Size of the finished archive
+    pub size_bytes: Option<u64>,
+    /// Decrypts the archive; only ever handed out by the key link
+    #[serde(skip_serializing)]
+    pub password: Option<String>,
+    pub decided_at: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl FolderExport {
+    pub fn request(owner_id: UserId, client_id: UserId, path: &str, reason: &str) -> Result<Self, String> {
+        let path = path.trim().trim_matches('/').to_string();
+        if path.split('/').any(|segment| segment == "." || segment == "..") {
+            return Err(format!("Invalid folder: {path}"));
+        }
+        let reason = reason.trim().to_string();
+        if reason.is_empty() || reason.chars().count() > 500 {
+            return Err("A reason of 1 to 500 characters is required".to_string());
+        }
+        let now = Utc::now();
+        Ok(Self {
+            id: Uuid::new_v4(),
+            owner_id,
+            client_id,
+            path,
+            reason,
+            status: FolderExportStatus::Requested,
+            error: None,
+            size_bytes: None,
+            password: None,
+            decided_at: None,
+            expires_at: None,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// Whether `permission` lets the client take this folder offline: an active grant from the
+    /// same owner on the folder or one of its parents, not limited to viewing.
+    pub fn is_covered_by(&self, permission: &FilePermission) -> bool {
+        let granted = permission.path.trim_matches('/');
+        permission.owner_id == self.owner_id
+            && permission.is_active()
+            && !permission.view_only
+            && (granted.is_empty()
+                || self.path == granted
+                || self.path.strip_prefix(granted).is_some_and(|rest| rest.starts_with('/')))
+    }
+
+    pub fn decide(&mut self, approve: bool) -> Result<(), String> {
+        if self.status != FolderExportStatus::Requested {
+            return Err("Export request was already decided".to_string());
+        }
+        let now = Utc::now();
+        self.status = if approve { FolderExportStatus::Approved } else { FolderExportStatus::Denied };
+        self.decided_at = Some(now);
+        self.updated_at = now;
+        Ok(())
+    }
+
+    pub fn mark_ready(&mut self, password: String, size_bytes: u64, valid_for: Duration) {
+        let now = Utc::now();
+        self.status = FolderExportStatus::Ready;
+        self.password = Some(password);
+        self.size_bytes = Some(size_bytes);
+        self.expires_at = Some(now + valid_for);
+        self.updated_at = now;
+    }
+
+    pub fn mark_failed(&mut self, error: String) {
+        self.status = FolderExportStatus::Failed;
+        self.error = Some(error);
+        self.updated_at = Utc::now();
+    }
+
+    pub fn is_ready_at(&self, now: DateTime<Utc>) -> bool {
+        self.status == FolderExportStatus::Ready && self.expires_at.is_some_and(|e| e > now)
+    }
+
+    /// Forget the key; the archive is deleted alongside.
+    pub fn expire(&mut self) {
+        self.status = FolderExportStatus::Expired;
+        self.password = None;
+        self.updated_at = Utc::now();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::invitation::AccessLevel;
+
+    #[test]
+    fn test_covered_by_parent_grant_only() {
+        let owner = UserId::new();
+        let export = FolderExport::request(owner.clone(), UserId::new(), "/clients/acme/", "Site visit").unwrap();
+        assert_eq!(export.path, "clients/acme");
+        let mut permission = FilePermission {
+            id: Uuid::new_v4(),
+            owner_id: owner,
+            client_id: export.client_id.clone(),
+            path: "clients".to_string(),
+            access: vec![AccessLevel::Read],
+            granted_at: Utc::now(),
+            expires_at: None,
+            revoked_at: None,
+            view_only: false,
+            interaction: Default::default(),
+            group_id: None,
+        };
+        assert!(export.is_covered_by(&permission));
+        permission.view_only = true;
+        assert!(!export.is_covered_by(&permission));
+        permission.view_only = false;
+        permission.path = "clients/acme-old".to_string();
+        assert!(!export.is_covered_by(&permission));
+
+        assert!(FolderExport::request(UserId::new(), UserId::new(), "a/../b", "x").is_err());
+        assert!(FolderExport::request(UserId::new(), UserId::new(), "a", " ").is_err());
+    }
+}
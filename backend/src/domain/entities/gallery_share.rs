@@ -0,0 +1,148 @@
+use crate::domain::value_objects::UserId;
+use chrono::{DateTime, Duration, Utc};
+use sha2::Sha256;
+use uuid::Uuid;
+use crate::domain::services::secrets::{constant_time_eq, hash_secret};
+
+/// Prefix of every gallery link token
+pub const TOKEN_PREFIX: &str = "pvg_";
+
+/// Longest a gallery may stay open when given an expiry
+pub const MAX_LIFETIME_DAYS: i64 = 365;
+
+/// PBKDF2-HMAC-SHA256 rounds for gallery passwords, stored with each hash
+const PASSWORD_ROUNDS: u32 = 600_000;
+
+/// A folder of photos an owner publishes to anyone holding the link, read-only and without an
+/// account. Only the token's hash is stored; the link is shown once, when it is created.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct GalleryShare {
+    pub id: Uuid,
+    pub owner_id: UserId,
+    /// Published folder, vault-relative
+    pub path: String,
+    /// Shown to visitors instead of the folder's path
+    pub title: String,
+    #[serde(skip_serializing)]
+    pub token_hash: String,
+    /// `pbkdf2-sha256$rounds$salt$hash` of the password visitors must give, if any
+    #[serde(rename = "password_protected", serialize_with = "serialize_is_some")]
+    pub password_hash: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Times the gallery was opened
+    pub view_count: u64,
+    pub last_viewed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+fn serialize_is_some<S: serde::Serializer>(value: &Option<String>, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_bool(value.is_some())
+}
+
+/// Slow on purpose, to resist guessing: async callers run it on a blocking task.
+fn hash_password(rounds: u32, salt: &str, password: &str) -> String {
+    let mut hash = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<Sha256>(password.as_bytes(), salt.as_bytes(), rounds, &mut hash);
+    let hex: String = hash.iter().map(|byte| format!("{byte:02x}")).collect();
+    format!("pbkdf2-sha256${rounds}${salt}${hex}")
+}
+
+impl GalleryShare {
+    /// A new gallery and its link token. Without `expires_in_days` it stays open until revoked.
+    pub fn create(
+        owner_id: UserId,
+        path: &str,
+        title: &str,
+        password: Option<&str>,
+        expires_in_days: Option<i64>,
+    ) -> Result<(Self, String), String> {
+        let path = path.trim().trim_matches('/').to_string();
+        if path.split('/').any(|segment| segment == "." || segment == "..") {
+            return Err(format!("Invalid folder: {path}"));
+        }
+        let title = title.trim();
+        let title = if title.is_empty() { path.rsplit('/').next().unwrap_or_default() } else { title }.to_string();
+        if title.is_empty() || title.chars().count() > 100 {
+            return Err("Gallery title must be 1 to 100 characters".to_string());
+        }
+        let password = password.filter(|p| !p.is_empty());
+        if password.is_some_and(|p| p.chars().count() < 6) {
+            return Err("Gallery password must be at least 6 characters".to_string());
+        }
+        if expires_in_days.is_some_and(|days| !(1..=MAX_LIFETIME_DAYS).contains(&days)) {
+            return Err(format!("Gallery expiry must be 1 to {MAX_LIFETIME_DAYS} days"));
+        }
+        let secret = format!("{TOKEN_PREFIX}{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+        let now = Utc::now();
+        let share = Self {
+            id: Uuid::new_v4(),
+            owner_id,
+            path,
+            title,
+            token_hash: hash_secret(&secret),
+            password_hash: password.map(|p| hash_password(PASSWORD_ROUNDS, &Uuid::new_v4().simple().to_string(), p)),
+            expires_at: expires_in_days.map(|days| now + Duration::days(days)),
+            view_count: 0,
+            last_viewed_at: None,
+            created_at: now,
+            revoked_at: None,
+        };
+        Ok((share, secret))
+    }
+
+    pub fn is_active_at(&self, now: DateTime<Utc>) -> bool {
+        self.revoked_at.is_none() && self.expires_at.map_or(true, |e| e > now)
+    }
+
+    pub fn check_password(&self, password: &str) -> bool {
+        let Some(stored) = &self.password_hash else {
+            return true;
+        };
+        let mut parts = stored.split('$');
+        match (parts.next(), parts.next().and_then(|r| r.parse().ok()), parts.next()) {
+            (Some("pbkdf2-sha256"), Some(rounds), Some(salt)) => constant_time_eq(&hash_password(rounds, salt, password), stored),
+            _ => false,
+        }
+    }
+
+    /// What visitors of a password-protected gallery send with each request once they gave the
+    /// password, so photos load as plain `<img>` links. Changes with the link and the password.
+    pub fn view_key(&self) -> Option<String> {
+        self.password_hash.as_ref().map(|hash| hash_secret(&format!("{}:{hash}", self.token_hash)))
+    }
+
+    /// Whether a request may see the photos: any request for an open gallery, else one
+    /// carrying the view key.
+    pub fn admits(&self, key: Option<&str>) -> bool {
+        self.view_key().map_or(true, |expected| key.is_some_and(|key| constant_time_eq(key, &expected)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_and_unlock() {
+        let (open, secret) = GalleryShare::create(UserId::new(), "/Photos/Wedding/", "", None, Some(30)).unwrap();
+        assert!(secret.starts_with(TOKEN_PREFIX));
+        assert_eq!(open.token_hash, hash_secret(&secret));
+        assert_eq!((open.path.as_str(), open.title.as_str()), ("Photos/Wedding", "Wedding"));
+        assert!(open.admits(None));
+        assert!(open.is_active_at(Utc::now()));
+        assert!(!open.is_active_at(Utc::now() + Duration::days(31)));
+
+        let (locked, _) = GalleryShare::create(UserId::new(), "Photos", "Summer", Some("sunshine"), None).unwrap();
+        assert!(locked.check_password("sunshine"));
+        assert!(!locked.check_password("rain"));
+        assert!(locked.password_hash.as_deref().unwrap().starts_with("pbkdf2-sha256$600000$"));
+        assert!(!locked.admits(None));
+        assert!(locked.admits(locked.view_key().as_deref()));
+        assert_eq!(serde_json::to_value(&locked).unwrap()["password_protected"], true);
+
+        assert!(GalleryShare::create(UserId::new(), "a/../b", "", None, None).is_err());
+        assert!(GalleryShare::create(UserId::new(), "a", "", Some("abc"), None).is_err());
+        assert!(GalleryShare::create(UserId::new(), "a", "", None, Some(0)).is_err());
+    }
+}
@@ -0,0 +1,77 @@
+use crate::domain::value_objects::UserId;
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+/// Longest a client's consent may last
+pub const MAX_CONSENT_HOURS: i64 = 7 * 24;
+
+/// Longest a session opened as a client runs, whatever the session timeout
+pub const MAX_IMPERSONATION_SECS: u64 = 30 * 60;
+
+/// `active_role` of a session an owner runs as one of their clients. The session is the
+/// client's, with the owner as `acting_as_owner_id`, so it sees exactly what the client would.
+pub const IMPERSONATION_ROLE: &str = "impersonation";
+
+/// A client's agreement that one of their owners may open app sessions as them, to see what
+/// they see, until `expires_at` or until they withdraw it.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ImpersonationConsent {
+    pub id: Uuid,
+    pub client_id: UserId,
+    pub owner_id: UserId,
+    pub granted_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl ImpersonationConsent {
+    pub fn grant(client_id: UserId, owner_id: UserId, hours: i64) -> Result<Self, String> {
+        if !(1..=MAX_CONSENT_HOURS).contains(&hours) {
+            return Err(format!("Consent must last 1 to {MAX_CONSENT_HOURS} hours"));
+        }
+        if client_id == owner_id {
+            return Err("An owner cannot act as themselves".to_string());
+        }
+        let now = Utc::now();
+        Ok(Self {
+            id: Uuid::new_v4(),
+            client_id,
+            owner_id,
+            granted_at: now,
+            expires_at: now + Duration::hours(hours),
+            revoked_at: None,
+        })
+    }
+
+    pub fn is_active_at(&self, now: DateTime<Utc>) -> bool {
+        self.revoked_at.is_none() && self.expires_at > now
+    }
+
+    /// How long a session started at `now` may run: never past the consent, nor longer than
+    /// [`MAX_IMPERSONATION_SECS`] or the usual `timeout_secs`.
+    pub fn session_secs(&self, now: DateTime<Utc>, timeout_secs: u64) -> u64 {
+        let left = (self.expires_at - now).num_seconds().max(0) as u64;
+        left.min(MAX_IMPERSONATION_SECS).min(timeout_secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_consent_limits_session_length() {
+        let (client, owner) = (UserId::new(), UserId::new());
+        let consent = ImpersonationConsent::grant(client.clone(), owner.clone(), 2).unwrap();
+        let now = Utc::now();
+        assert!(consent.is_active_at(now));
+        assert!(!consent.is_active_at(now + Duration::hours(3)));
+        assert_eq!(consent.session_secs(now, 3600), MAX_IMPERSONATION_SECS);
+        assert_eq!(consent.session_secs(now, 600), 600);
+        let late = consent.expires_at - Duration::seconds(90);
+        assert_eq!(consent.session_secs(late, 3600), 90);
+
+        assert!(ImpersonationConsent::grant(client.clone(), owner, MAX_CONSENT_HOURS + 1).is_err());
+        assert!(ImpersonationConsent::grant(client.clone(), client, 2).is_err());
+    }
+}
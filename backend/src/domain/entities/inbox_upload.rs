@@ -0,0 +1,80 @@
+use crate::domain::value_objects::UserId;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InboxStatus {
+    /// Its bytes are still arriving; counts against the link's limits and the owner's quota
+    Receiving,
+    /// Quarantined, waiting for the owner
+    Pending,
+    /// Moved into the vault
+    Approved,
+    /// Deleted unseen by the vault
+    Rejected,
+}
+
+impl InboxStatus {
+    pub fn as_db_str(&self) -> &'static str {
+        match self {
+            InboxStatus::Receiving => "receiving",
+            InboxStatus::Pending => "pending",
+            InboxStatus::Approved => "approved",
+            InboxStatus::Rejected => "rejected",
+        }
+    }
+
+    pub fn from_db_str(s: &str) -> Result<Self, String> {
+        match s {
+            "receiving" => Ok(InboxStatus::Receiving),
+            "pending" => Ok(InboxStatus::Pending),
+            "approved" => Ok(InboxStatus::Approved),
+            "rejected" => Ok(InboxStatus::Rejected),
+            other => Err(format!("Unknown inbox status: {other}")),
+        }
+    }
+}
+
+/// A file someone sent through a file request. Its bytes are staged outside the vault, like an
+/// unfinished upload, until the owner approves or rejects it.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct InboxUpload {
+    pub id: Uuid,
+    pub request_id: Uuid,
+    pub owner_id: UserId,
+    pub file_name: String,
+    pub size: u64,
+    pub media_type: String,
+    /// Name the uploader gave, unverified
+    pub sender: Option<String>,
+    pub sender_ip: String,
+    pub status: InboxStatus,
+    /// Where the file was placed once approved, vault-relative
+    pub path: Option<String>,
+    pub received_at: DateTime<Utc>,
+    pub decided_at: Option<DateTime<Utc>>,
+}
+
+impl InboxUpload {
+    /// A file of `size` bytes about to be received, pending once all of them arrived.
+    pub fn new(request_id: Uuid, owner_id: UserId, file_name: String, size: u64, media_type: &str, sender: Option<&str>, sender_ip: String) -> Self {
+        let sender = sender
+            .map(|s| s.trim().chars().filter(|c| !c.is_control()).take(100).collect::<String>())
+            .filter(|s| !s.is_empty());
+        Self {
+            id: Uuid::new_v4(),
+            request_id,
+            owner_id,
+            file_name,
+            size,
+            media_type: media_type.to_string(),
+            sender,
+            sender_ip,
+            status: InboxStatus::Receiving,
+            path: None,
+            received_at: Utc::now(),
+            decided_at: None,
+        }
+    }
+}
@@ -2,16 +2,20 @@ use crate::domain::value_objects::*;
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct Invitation {
     pub id: Uuid,
     pub owner_id: UserId,
     pub invitee_email: Email,
+    /// Only handed out once, when the invitation is created
+    #[serde(skip_serializing)]
     pub token: String,
     pub granted_paths: Vec<GrantedPath>,
     pub status: InvitationStatus,
     pub expires_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
+    /// Accepting requires a code mailed to `invitee_email`, not just the token
+    pub require_email_verification: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -26,6 +30,12 @@ pub enum InvitationStatus {
 pub struct GrantedPath {
     pub path: String,
     pub access: Vec<AccessLevel>,
+    /// Grant the path for viewing in a session only (see [`super::file_permission::FilePermission::view_only`])
+    #[serde(default)]
+    pub view_only: bool,
+    /// See [`super::file_permission::FilePermission::interaction`]
+    #[serde(default)]
+    pub interaction: Interaction,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -0,0 +1,104 @@
+use crate::domain::value_objects::UserId;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use super::owner_delegation::normalize_path;
+
+/// A path of an owner's vault that may not be deleted or changed, e.g. tax records that must
+/// be kept for a number of years. Lapses at `expires_at`, if set, or when released.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct LegalHold {
+    pub id: Uuid,
+    pub owner_id: UserId,
+    /// Vault-relative, without leading or trailing slashes; covers everything beneath it
+    pub path: String,
+    pub reason: String,
+    pub created_by: UserId,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub released_at: Option<DateTime<Utc>>,
+}
+
+impl LegalHold {
+    pub fn new(
+        owner_id: UserId,
+        path: &str,
+        reason: &str,
+        created_by: UserId,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<Self, String> {
+        let reason = reason.trim();
+        if reason.is_empty() || reason.len() > 500 {
+            return Err("Hold reason must be 1 to 500 characters".to_string());
+        }
+        let now = Utc::now();
+        if expires_at.is_some_and(|at| at <= now) {
+            return Err("Hold expiry must be in the future".to_string());
+        }
+        Ok(Self {
+            id: Uuid::new_v4(),
+            owner_id,
+            path: normalize_path(path)?,
+            reason: reason.to_string(),
+            created_by,
+            created_at: now,
+            expires_at,
+            released_at: None,
+        })
+    }
+
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.released_at.is_none() && self.expires_at.map_or(true, |at| at > now)
+    }
+
+    /// Whether removing or changing `path` would touch what the hold protects: the path is
+    /// inside the held one, or holds it.
+    pub fn protects(&self, path: &str) -> bool {
+        let path = path.trim_matches('/');
+        let within = |inner: &str, outer: &str| {
+            outer.is_empty() || inner == outer || inner.strip_prefix(outer).is_some_and(|rest| rest.starts_with('/'))
+        };
+        within(path, self.path.as_str()) || within(self.path.as_str(), path)
+    }
+}
+
+/// The first of `holds` in force at `now` that protects `path`.
+pub fn blocking<'a>(holds: &'a [LegalHold], path: &str, now: DateTime<Utc>) -> Option<&'a LegalHold> {
+    holds.iter().find(|hold| hold.is_active(now) && hold.protects(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn hold(path: &str, expires_at: Option<DateTime<Utc>>) -> LegalHold {
+        LegalHold::new(UserId::new(), path, "tax records", UserId::new(), expires_at).unwrap()
+    }
+
+    #[test]
+    fn test_protects() {
+        let held = hold("/taxes/2024/", None);
+        assert_eq!(held.path, "taxes/2024");
+        assert!(held.protects("taxes/2024"));
+        assert!(held.protects("taxes/2024/receipts.pdf"));
+        // Removing a parent folder would remove the held one with it
+        assert!(held.protects("taxes"));
+        assert!(!held.protects("taxes/2023"));
+        assert!(!held.protects("taxes/2024-draft.pdf"));
+    }
+
+    #[test]
+    fn test_blocking() {
+        let now = Utc::now();
+        let mut holds = vec![hold("taxes", Some(now + Duration::days(1))), hold("contracts", None)];
+        assert!(blocking(&holds, "taxes/2024", now).is_some());
+        assert!(blocking(&holds, "taxes/2024", now + Duration::days(2)).is_none());
+        holds[1].released_at = Some(now);
+        assert!(blocking(&holds, "contracts/lease.pdf", now).is_none());
+        assert!(blocking(&holds, "photos", now).is_none());
+
+        assert!(LegalHold::new(UserId::new(), "taxes", " ", UserId::new(), None).is_err());
+        assert!(LegalHold::new(UserId::new(), "../etc", "audit", UserId::new(), None).is_err());
+        assert!(LegalHold::new(UserId::new(), "taxes", "audit", UserId::new(), Some(now - Duration::days(1))).is_err());
+    }
+}
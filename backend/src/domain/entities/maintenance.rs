@@ -0,0 +1,41 @@
+use chrono::{DateTime, Utc};
+use crate::domain::value_objects::user_role::UserRole;
+use crate::domain::value_objects::UserId;
+
+/// Maintenance of an instance ahead of an upgrade: no new sessions start on it, the ones
+/// running there carry on and their users are warned. Logins can be limited to super-admins.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct MaintenanceWindow {
+    pub started_at: DateTime<Utc>,
+    pub started_by: UserId,
+    /// Shown to connected users along with the warning
+    pub message: Option<String>,
+    pub block_logins: bool,
+}
+
+impl MaintenanceWindow {
+    pub fn allows_login(&self, roles: &[UserRole]) -> bool {
+        !self.block_logins || roles.contains(&UserRole::SuperAdmin)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blocked_logins_still_admit_super_admins() {
+        let mut window = MaintenanceWindow {
+            started_at: Utc::now(),
+            started_by: UserId::new(),
+            message: None,
+            block_logins: false,
+        };
+        assert!(window.allows_login(&[UserRole::Client]));
+
+        window.block_logins = true;
+        assert!(!window.allows_login(&[UserRole::Client]));
+        assert!(!window.allows_login(&[UserRole::Owner]));
+        assert!(window.allows_login(&[UserRole::Owner, UserRole::SuperAdmin]));
+    }
+}
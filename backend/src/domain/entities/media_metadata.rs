@@ -0,0 +1,82 @@
+use chrono::{DateTime, Datelike, NaiveDateTime, Utc};
+use crate::domain::value_objects::UserId;
+
+/// What was read from a photo's EXIF block or a video's header. Every field is optional,
+/// since cameras, phones and editors each write a different subset.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct MediaMetadata {
+    #[serde(skip_serializing)]
+    pub owner_id: UserId,
+    /// Vault-relative path of the file
+    pub path: String,
+    /// When the picture or video was taken, in the camera's local time
+    pub taken_at: Option<NaiveDateTime>,
+    /// Make and model, e.g. "Canon EOS R6"
+    pub camera: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    /// Videos only
+    pub duration_secs: Option<f64>,
+    pub extracted_at: DateTime<Utc>,
+}
+
+impl MediaMetadata {
+    pub fn new(owner_id: UserId, path: &str) -> Self {
+        Self {
+            owner_id,
+            path: path.trim_matches('/').to_string(),
+            taken_at: None,
+            camera: None,
+            latitude: None,
+            longitude: None,
+            width: None,
+            height: None,
+            duration_secs: None,
+            extracted_at: Utc::now(),
+        }
+    }
+
+    /// Joins make and model, leaving out the make when the model already starts with it
+    /// ("Canon" + "Canon EOS R6").
+    pub fn set_camera(&mut self, make: Option<&str>, model: Option<&str>) {
+        let make = make.map(str::trim).filter(|m| !m.is_empty());
+        let model = model.map(str::trim).filter(|m| !m.is_empty());
+        self.camera = match (make, model) {
+            (Some(make), Some(model)) if model.to_lowercase().starts_with(&make.to_lowercase()) => Some(model.to_string()),
+            (Some(make), Some(model)) => Some(format!("{make} {model}")),
+            (make, model) => make.or(model).map(str::to_string),
+        };
+    }
+
+    pub fn year(&self) -> Option<i32> {
+        self.taken_at.map(|t| t.year())
+    }
+
+    /// Whether nothing was found, in which case nothing is stored.
+    pub fn is_empty(&self) -> bool {
+        self.taken_at.is_none()
+            && self.camera.is_none()
+            && self.latitude.is_none()
+            && self.width.is_none()
+            && self.duration_secs.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_camera() {
+        let mut metadata = MediaMetadata::new(UserId::new(), "/a.jpg");
+        assert!(metadata.is_empty());
+        metadata.set_camera(Some("Canon"), Some("Canon EOS R6"));
+        assert_eq!(metadata.camera.as_deref(), Some("Canon EOS R6"));
+        metadata.set_camera(Some("Apple "), Some("iPhone 15"));
+        assert_eq!(metadata.camera.as_deref(), Some("Apple iPhone 15"));
+        metadata.set_camera(Some(""), None);
+        assert_eq!(metadata.camera, None);
+    }
+}
@@ -3,7 +3,43 @@ pub mod credential;
 pub mod invitation;
 pub mod file_permission;
 pub mod session;
+pub mod quality_preference;
+pub mod user_preferences;
+pub mod audit_event;
+pub mod notification;
+pub mod access_policy;
+pub mod client_group;
+pub mod owner_delegation;
+pub mod file_job;
+pub mod upload_session;
+pub mod session_timeline;
+pub mod session_snapshot;
+pub mod placement;
+pub mod stream_budget;
+pub mod app_crash;
+pub mod auth_session;
+pub mod data_export;
+pub mod account_deletion;
+pub mod maintenance;
+pub mod vault_import;
+pub mod external_identity;
+pub mod provisioning_token;
+pub mod access_token;
+pub mod permission_event;
+pub mod legal_hold;
+pub mod app_setting;
+pub mod tenant;
 
 pub use user::User;
 pub use credential::Credential;
 pub use session::Session;
+pub mod owner_branding;
+pub mod processing_task;
+pub mod media_metadata;
+pub mod organization_rule;
+pub mod folder_export;
+pub mod gallery_share;
+pub mod file_comment;
+pub mod file_request;
+pub mod inbox_upload;
+pub mod impersonation_consent;
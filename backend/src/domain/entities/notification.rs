@@ -0,0 +1,28 @@
+use crate::domain::value_objects::UserId;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// In-app notification. `kind` names the message (e.g. `account_locked`); the client renders
+/// it in the user's language from `kind` and `payload`.
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub id: Uuid,
+    pub user_id: UserId,
+    pub kind: String,
+    pub payload: serde_json::Value,
+    pub read_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Notification {
+    pub fn new(user_id: UserId, kind: &str, payload: serde_json::Value) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            kind: kind.to_string(),
+            payload,
+            read_at: None,
+            created_at: Utc::now(),
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use chrono::{DateTime, Datelike, Utc};
+use uuid::Uuid;
+use super::media_metadata::MediaMetadata;
+use crate::domain::value_objects::UserId;
+
+/// Placeholders a destination may use
+const PLACEHOLDERS: [&str; 4] = ["{YYYY}", "{MM}", "{DD}", "{camera}"];
+
+/// Sorts the photos and videos that land in a folder into another, e.g. `inbox` to
+/// `photos/{YYYY}/{MM}` by the date they were taken. Applied by the processing pipeline
+/// once their metadata was read.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct OrganizationRule {
+    pub id: Uuid,
+    #[serde(skip_serializing)]
+    pub owner_id: UserId,
+    pub name: String,
+    /// Folder whose new media the rule sorts, vault-relative; files in its subfolders too
+    pub source: String,
+    /// Folder they are moved to; `{YYYY}`, `{MM}` and `{DD}` stand for the date taken and
+    /// `{camera}` for the camera
+    pub destination: String,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl OrganizationRule {
+    pub fn new(owner_id: UserId, name: &str, source: &str, destination: &str) -> Result<Self, String> {
+        let now = Utc::now();
+        let rule = Self {
+            id: Uuid::new_v4(),
+            owner_id,
+            name: name.trim().to_string(),
+            source: source.trim().trim_matches('/').to_string(),
+            destination: destination.trim().trim_matches('/').to_string(),
+            enabled: true,
+            created_at: now,
+            updated_at: now,
+        };
+        rule.validate()?;
+        Ok(rule)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.is_empty() || self.name.chars().count() > 100 {
+            return Err("Rule name must be 1 to 100 characters".to_string());
+        }
+        if self.source.is_empty() || self.destination.is_empty() {
+            return Err("Rules need a source and a destination folder".to_string());
+        }
+        for path in [&self.source, &self.destination] {
+            if path.split('/').any(|segment| segment.is_empty() || segment == "." || segment == "..") {
+                return Err(format!("Invalid folder: {path}"));
+            }
+        }
+        let mut rest = self.destination.as_str();
+        while let Some(start) = rest.find('{') {
+            let placeholder = rest[start..].find('}').map(|end| &rest[start..start + end + 1]);
+            match placeholder {
+                Some(placeholder) if PLACEHOLDERS.contains(&placeholder) => rest = &rest[start + placeholder.len()..],
+                _ => return Err(format!("Unknown placeholder in {}; use {}", self.destination, PLACEHOLDERS.join(", "))),
+            }
+        }
+        if self.destination == self.source || self.destination.starts_with(&format!("{}/", self.source)) {
+            return Err("The destination cannot be inside the source folder".to_string());
+        }
+        Ok(())
+    }
+
+    /// Where the rule moves a file with `metadata`, or `None` when the file is not in its
+    /// source folder or lacks what the destination is named after.
+    pub fn destination_for(&self, metadata: &MediaMetadata) -> Option<String> {
+        let path = metadata.path.as_str();
+        if !self.enabled || !path.starts_with(&format!("{}/", self.source)) {
+            return None;
+        }
+        let name = path.rsplit('/').next()?;
+        let mut folder = self.destination.clone();
+        if PLACEHOLDERS[..3].iter().any(|p| folder.contains(p)) {
+            let taken_at = metadata.taken_at?;
+            folder = folder
+                .replace("{YYYY}", &format!("{:04}", taken_at.year()))
+                .replace("{MM}", &format!("{:02}", taken_at.month()))
+                .replace("{DD}", &format!("{:02}", taken_at.day()));
+        }
+        if folder.contains("{camera}") {
+            let camera = metadata.camera.as_deref()?.replace(['/', '\\'], "-");
+            folder = folder.replace("{camera}", camera.trim_matches('.'));
+        }
+        Some(format!("{folder}/{name}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_destination_for() {
+        let rule = OrganizationRule::new(UserId::new(), "By month", "/inbox/", "photos/{YYYY}/{MM}").unwrap();
+        let mut metadata = MediaMetadata::new(rule.owner_id.clone(), "inbox/phone/IMG_1.jpg");
+        assert_eq!(rule.destination_for(&metadata), None);
+        metadata.taken_at = NaiveDate::from_ymd_opt(2024, 3, 9).and_then(|d| d.and_hms_opt(10, 0, 0));
+        assert_eq!(rule.destination_for(&metadata).as_deref(), Some("photos/2024/03/IMG_1.jpg"));
+        metadata.path = "inboxes/IMG_1.jpg".to_string();
+        assert_eq!(rule.destination_for(&metadata), None);
+
+        assert!(OrganizationRule::new(UserId::new(), "x", "inbox", "photos/{year}").is_err());
+        assert!(OrganizationRule::new(UserId::new(), "x", "inbox", "inbox/sorted").is_err());
+        assert!(OrganizationRule::new(UserId::new(), "x", "inbox", "../photos").is_err());
+    }
+}
@@ -0,0 +1,87 @@
+use crate::domain::value_objects::UserId;
+use chrono::{DateTime, Utc};
+
+/// Image types a logo may have, embedded as a `data:` URL
+const LOGO_TYPES: [&str; 3] = ["image/png", "image/jpeg", "image/webp"];
+/// Longest logo data URL stored, about 200 KB of image
+pub const MAX_LOGO_LEN: usize = 280_000;
+
+/// How an owner presents their vault to the people they invite: the name, logo and accent
+/// color shown instead of the generic ones. Anything left unset falls back to the defaults.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct OwnerBranding {
+    pub owner_id: UserId,
+    pub display_name: Option<String>,
+    /// `data:image/...;base64,` URL
+    pub logo: Option<String>,
+    /// `#rrggbb`
+    pub accent_color: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl OwnerBranding {
+    /// Trims the fields, lowercases the color and treats empty values as unset.
+    pub fn new(
+        owner_id: UserId,
+        display_name: Option<&str>,
+        logo: Option<&str>,
+        accent_color: Option<&str>,
+    ) -> Result<Self, String> {
+        let set = |value: Option<&str>| value.map(str::trim).filter(|v| !v.is_empty()).map(str::to_string);
+        let branding = Self {
+            owner_id,
+            display_name: set(display_name),
+            logo: set(logo),
+            accent_color: set(accent_color).map(|c| c.to_ascii_lowercase()),
+            updated_at: Utc::now(),
+        };
+        branding.validate()?;
+        Ok(branding)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.display_name.as_ref().is_some_and(|name| name.chars().count() > 60) {
+            return Err("Display name must be at most 60 characters".to_string());
+        }
+        if let Some(color) = &self.accent_color {
+            let hex = color.strip_prefix('#').unwrap_or("");
+            if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(format!("Invalid accent color: {color}, expected #rrggbb"));
+            }
+        }
+        if let Some(logo) = &self.logo {
+            if logo.len() > MAX_LOGO_LEN {
+                return Err("Logo is too large".to_string());
+            }
+            let data = LOGO_TYPES
+                .iter()
+                .find_map(|mime| logo.strip_prefix(&format!("data:{mime};base64,")))
+                .ok_or_else(|| "Logo must be a base64 PNG, JPEG or WebP data URL".to_string())?;
+            if data.is_empty() || !data.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '/' | '=')) {
+                return Err("Logo is not valid base64".to_string());
+            }
+        }
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.display_name.is_none() && self.logo.is_none() && self.accent_color.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validation() {
+        let branding = OwnerBranding::new(UserId::new(), Some(" The Martins "), Some("data:image/png;base64,iVBORw0KGgo="), Some("#1A2B3C")).unwrap();
+        assert_eq!(branding.display_name.as_deref(), Some("The Martins"));
+        assert_eq!(branding.accent_color.as_deref(), Some("#1a2b3c"));
+
+        assert!(OwnerBranding::new(UserId::new(), None, None, Some("red")).is_err());
+        assert!(OwnerBranding::new(UserId::new(), None, Some("data:image/svg+xml;base64,PHN2Zz4="), None).is_err());
+        assert!(OwnerBranding::new(UserId::new(), None, Some("https://example.com/logo.png"), None).is_err());
+        assert!(OwnerBranding::new(UserId::new(), Some(" "), Some(""), None).unwrap().is_empty());
+    }
+}
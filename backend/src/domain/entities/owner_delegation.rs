@@ -0,0 +1,86 @@
+use crate::domain::value_objects::UserId;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// A co-owner's authority over one subtree of another owner's vault. Within `path` the
+/// delegate invites clients and grants or revokes permissions on the owner's behalf.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct OwnerDelegation {
+    pub id: Uuid,
+    pub owner_id: UserId,
+    pub delegate_id: UserId,
+    /// Vault-relative subtree, without leading or trailing slashes
+    pub path: String,
+    pub created_by: UserId,
+    pub created_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    /// Whether the delegate holds the Owner role only through delegation, so it is taken back
+    /// with their last one
+    pub grants_owner_role: bool,
+}
+
+impl OwnerDelegation {
+    pub fn new(owner_id: UserId, delegate_id: UserId, path: &str, created_by: UserId) -> Result<Self, String> {
+        if owner_id == delegate_id {
+            return Err("An owner cannot be delegated their own vault".to_string());
+        }
+        Ok(Self {
+            id: Uuid::new_v4(),
+            owner_id,
+            delegate_id,
+            path: normalize_path(path)?,
+            created_by,
+            created_at: Utc::now(),
+            revoked_at: None,
+            grants_owner_role: false,
+        })
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.revoked_at.is_none()
+    }
+
+    /// Whether the vault-relative `path` lies inside the delegated subtree.
+    pub fn covers(&self, path: &str) -> bool {
+        let path = path.trim_matches('/');
+        path == self.path || path.strip_prefix(&self.path).is_some_and(|rest| rest.starts_with('/'))
+    }
+}
+
+/// Strip surrounding slashes; the subtree must be a non-empty relative path without `..`.
+pub fn normalize_path(path: &str) -> Result<String, String> {
+    let path = path.trim().trim_matches('/');
+    if path.is_empty() || path.split('/').any(|part| part.is_empty() || part == "..") {
+        return Err("Invalid path: must be a non-empty relative path without '..'".to_string());
+    }
+    Ok(path.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn delegation(path: &str) -> OwnerDelegation {
+        OwnerDelegation::new(UserId::new(), UserId::new(), path, UserId::new()).unwrap()
+    }
+
+    #[test]
+    fn test_covers_subtree_but_not_siblings_with_same_prefix() {
+        let d = delegation("/family-photos/");
+        assert_eq!(d.path, "family-photos");
+        assert!(d.covers("family-photos"));
+        assert!(d.covers("family-photos/2024/beach.jpg"));
+        assert!(d.covers("/family-photos/2024"));
+        assert!(!d.covers("family-photos-old"));
+        assert!(!d.covers("taxes"));
+    }
+
+    #[test]
+    fn test_rejects_escaping_or_empty_paths() {
+        assert!(normalize_path("/").is_err());
+        assert!(normalize_path("photos/../taxes").is_err());
+        assert!(normalize_path("photos//2024").is_err());
+        let owner = UserId::new();
+        assert!(OwnerDelegation::new(owner.clone(), owner, "photos", UserId::new()).is_err());
+    }
+}
@@ -0,0 +1,210 @@
+use crate::domain::value_objects::{Interaction, UserId};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use uuid::Uuid;
+use super::file_permission::FilePermission;
+use super::invitation::AccessLevel;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PermissionEventKind {
+    Granted,
+    Revoked,
+    /// A renewal, or any other move of the expiry
+    ExpiryChanged,
+    /// Never stored: derived from the recorded expiry when a history is replayed
+    Expired,
+}
+
+impl PermissionEventKind {
+    pub fn as_db_str(&self) -> &'static str {
+        match self {
+            PermissionEventKind::Granted => "granted",
+            PermissionEventKind::Revoked => "revoked",
+            PermissionEventKind::ExpiryChanged => "expiry_changed",
+            PermissionEventKind::Expired => "expired",
+        }
+    }
+
+    pub fn from_db_str(s: &str) -> Result<Self, String> {
+        match s {
+            "granted" => Ok(PermissionEventKind::Granted),
+            "revoked" => Ok(PermissionEventKind::Revoked),
+            "expiry_changed" => Ok(PermissionEventKind::ExpiryChanged),
+            other => Err(format!("Unknown permission event '{other}'")),
+        }
+    }
+}
+
+/// Something that happened to a file permission, with the permission as it stood afterwards.
+/// Recorded alongside every change and never updated, so past access can be replayed.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct PermissionEvent {
+    pub permission_id: Uuid,
+    pub owner_id: UserId,
+    pub client_id: UserId,
+    pub kind: PermissionEventKind,
+    pub path: String,
+    pub access: Vec<AccessLevel>,
+    pub view_only: bool,
+    pub interaction: Interaction,
+    pub group_id: Option<Uuid>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// How a permission relates to the folder a history is asked for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PathCoverage {
+    /// The folder is inside what was granted
+    Whole,
+    /// Only something inside the folder was granted
+    Part,
+}
+
+/// How a permission granted on `granted` relates to `folder`, both vault-relative.
+/// An empty path is the whole vault.
+pub fn coverage(granted: &str, folder: &str) -> Option<PathCoverage> {
+    let granted = granted.trim_matches('/');
+    let folder = folder.trim_matches('/');
+    let within = |inner: &str, outer: &str| {
+        outer.is_empty() || inner == outer || inner.strip_prefix(outer).is_some_and(|rest| rest.starts_with('/'))
+    };
+    if within(folder, granted) {
+        Some(PathCoverage::Whole)
+    } else if within(granted, folder) {
+        Some(PathCoverage::Part)
+    } else {
+        None
+    }
+}
+
+/// Replay `events`, in the order they were recorded, up to `at`. Returns the permissions
+/// that were in force at that moment and the events that led there, with expiries filled in.
+pub fn replay(events: &[PermissionEvent], at: DateTime<Utc>) -> (Vec<FilePermission>, Vec<PermissionEvent>) {
+    let mut permissions: HashMap<Uuid, FilePermission> = HashMap::new();
+    let mut timeline = Vec::new();
+
+    // An expiry that passed before the next event, or before `at`, happened in between
+    let expired = |permission: &FilePermission, before: DateTime<Utc>| {
+        permission
+            .expires_at
+            .filter(|expires_at| permission.revoked_at.is_none() && *expires_at <= before)
+            .map(|expires_at| PermissionEvent {
+                permission_id: permission.id,
+                owner_id: permission.owner_id.clone(),
+                client_id: permission.client_id.clone(),
+                kind: PermissionEventKind::Expired,
+                path: permission.path.clone(),
+                access: permission.access.clone(),
+                view_only: permission.view_only,
+                interaction: permission.interaction,
+                group_id: permission.group_id,
+                expires_at: Some(expires_at),
+                occurred_at: expires_at,
+            })
+    };
+
+    for event in events.iter().filter(|e| e.occurred_at <= at) {
+        if let Some(permission) = permissions.get_mut(&event.permission_id) {
+            timeline.extend(expired(&*permission, event.occurred_at));
+            match event.kind {
+                PermissionEventKind::Revoked => permission.revoked_at = Some(event.occurred_at),
+                _ => permission.expires_at = event.expires_at,
+            }
+        } else if event.kind == PermissionEventKind::Granted {
+            permissions.insert(
+                event.permission_id,
+                FilePermission {
+                    id: event.permission_id,
+                    owner_id: event.owner_id.clone(),
+                    client_id: event.client_id.clone(),
+                    path: event.path.clone(),
+                    access: event.access.clone(),
+                    granted_at: event.occurred_at,
+                    expires_at: event.expires_at,
+                    revoked_at: None,
+                    view_only: event.view_only,
+                    interaction: event.interaction,
+                    group_id: event.group_id,
+                },
+            );
+        }
+        timeline.push(event.clone());
+    }
+    timeline.extend(permissions.values().filter_map(|p| expired(p, at)));
+    timeline.sort_by_key(|e| e.occurred_at);
+
+    let mut in_force: Vec<FilePermission> = permissions
+        .into_values()
+        .filter(|p| p.revoked_at.is_none() && p.expires_at.map_or(true, |e| e > at))
+        .collect();
+    in_force.sort_by_key(|p| p.granted_at);
+    (in_force, timeline)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn event(permission_id: Uuid, kind: PermissionEventKind, expires_at: Option<DateTime<Utc>>, occurred_at: DateTime<Utc>) -> PermissionEvent {
+        PermissionEvent {
+            permission_id,
+            owner_id: UserId::new(),
+            client_id: UserId::new(),
+            kind,
+            path: "photos".to_string(),
+            access: vec![AccessLevel::Read],
+            view_only: false,
+            interaction: Interaction::Interactive,
+            group_id: None,
+            expires_at,
+            occurred_at,
+        }
+    }
+
+    #[test]
+    fn test_replay() {
+        let start = Utc::now() - Duration::days(100);
+        let day = |n: i64| start + Duration::days(n);
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let events = vec![
+            event(a, PermissionEventKind::Granted, Some(day(10)), day(0)),
+            event(b, PermissionEventKind::Granted, None, day(1)),
+            event(a, PermissionEventKind::ExpiryChanged, Some(day(30)), day(15)),
+            event(b, PermissionEventKind::Revoked, None, day(20)),
+        ];
+
+        let (in_force, _) = replay(&events, day(5));
+        assert_eq!(in_force.len(), 2);
+        // Expired on day 10 and only renewed on day 15
+        let (in_force, timeline) = replay(&events, day(12));
+        assert_eq!(in_force.iter().map(|p| p.id).collect::<Vec<_>>(), vec![b]);
+        assert_eq!(timeline.last().unwrap().kind, PermissionEventKind::Expired);
+        let (in_force, _) = replay(&events, day(25));
+        assert_eq!(in_force.iter().map(|p| p.id).collect::<Vec<_>>(), vec![a]);
+
+        let (in_force, timeline) = replay(&events, day(40));
+        assert!(in_force.is_empty());
+        let kinds: Vec<_> = timeline.iter().map(|e| e.kind).collect();
+        assert_eq!(kinds, vec![
+            PermissionEventKind::Granted,
+            PermissionEventKind::Granted,
+            PermissionEventKind::Expired,
+            PermissionEventKind::ExpiryChanged,
+            PermissionEventKind::Revoked,
+            PermissionEventKind::Expired,
+        ]);
+    }
+
+    #[test]
+    fn test_coverage() {
+        assert_eq!(coverage("photos", "photos/2025"), Some(PathCoverage::Whole));
+        assert_eq!(coverage("", "photos"), Some(PathCoverage::Whole));
+        assert_eq!(coverage("photos/2025", "/photos/"), Some(PathCoverage::Part));
+        assert_eq!(coverage("photos", "photos"), Some(PathCoverage::Whole));
+        assert_eq!(coverage("photos-old", "photos"), None);
+    }
+}
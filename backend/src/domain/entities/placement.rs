@@ -0,0 +1,224 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::domain::value_objects::{ResourceClass, Resources};
+
+/// How far reservations may exceed a host's capacity. CPU is shared fairly under contention,
+/// so it is overcommitted by default; memory is not, since running out kills apps.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct OvercommitPolicy {
+    pub cpu_ratio: f32,
+    pub memory_ratio: f32,
+}
+
+impl Default for OvercommitPolicy {
+    fn default() -> Self {
+        Self { cpu_ratio: 2.0, memory_ratio: 1.0 }
+    }
+}
+
+impl OvercommitPolicy {
+    fn limit(&self, capacity: Resources) -> Resources {
+        Resources::new(
+            (capacity.cpu_millis as f32 * self.cpu_ratio) as u32,
+            (capacity.memory_mb as f32 * self.memory_ratio) as u32,
+        )
+    }
+}
+
+/// A host that can run sessions, as last published by it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HostStatus {
+    pub instance_id: String,
+    /// Where launches placed on this host are sent
+    pub launch_url: String,
+    pub capacity: Resources,
+    /// Sum of the classes of the sessions running there
+    pub reserved: Resources,
+    pub heavy_sessions: u32,
+    /// Measured on the host, including whatever else runs on it
+    pub used: Resources,
+    /// In maintenance: running sessions stay, new ones go elsewhere
+    #[serde(default)]
+    pub draining: bool,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkippedHost {
+    pub instance_id: String,
+    pub reason: String,
+}
+
+/// Where a new session went and why the other hosts did not get it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlacementDecision {
+    pub at: DateTime<Utc>,
+    pub app_id: String,
+    pub class: ResourceClass,
+    /// `None` when no host has room
+    pub chosen: Option<String>,
+    pub skipped: Vec<SkippedHost>,
+}
+
+impl PlacementDecision {
+    pub fn chosen_host<'a>(&self, hosts: &'a [HostStatus]) -> Option<&'a HostStatus> {
+        let chosen = self.chosen.as_deref()?;
+        hosts.iter().find(|host| host.instance_id == chosen)
+    }
+}
+
+/// Why `host` cannot take `request`, if it cannot.
+fn rejection(host: &HostStatus, request: Resources, policy: &OvercommitPolicy) -> Option<String> {
+    if host.draining {
+        return Some("in maintenance".to_string());
+    }
+    let limit = policy.limit(host.capacity);
+    let after = host.reserved.plus(request);
+    if after.cpu_millis > limit.cpu_millis {
+        return Some(format!("CPU reserved {}m of {}m", host.reserved.cpu_millis, limit.cpu_millis));
+    }
+    if after.memory_mb > limit.memory_mb {
+        return Some(format!("memory reserved {} MB of {} MB", host.reserved.memory_mb, limit.memory_mb));
+    }
+    // Reservations can look fine while the host is short of memory for other reasons
+    if host.used.memory_mb.saturating_add(request.memory_mb) > host.capacity.memory_mb {
+        return Some(format!("memory in use {} MB of {} MB", host.used.memory_mb, host.capacity.memory_mb));
+    }
+    None
+}
+
+/// Share of the host's limit still free after placing `request`; the tightest dimension counts.
+fn headroom(host: &HostStatus, request: Resources, policy: &OvercommitPolicy) -> f32 {
+    let limit = policy.limit(host.capacity);
+    let after = host.reserved.plus(request);
+    let free = |limit: u32, after: u32| (limit.saturating_sub(after)) as f32 / limit.max(1) as f32;
+    free(limit.cpu_millis, after.cpu_millis).min(free(limit.memory_mb, after.memory_mb))
+}
+
+/// Pick a host for a new session of `class`. Hosts that fit are packed best-fit, filling the
+/// fullest one first so whole hosts stay free for heavy apps; heavy apps avoid hosts already
+/// running one, unless every host that fits does.
+pub fn place(
+    app_id: &str,
+    class: ResourceClass,
+    hosts: &[HostStatus],
+    policy: &OvercommitPolicy,
+    at: DateTime<Utc>,
+) -> PlacementDecision {
+    let request = class.resources();
+    let mut skipped = Vec::new();
+    let mut fitting = Vec::new();
+    for host in hosts {
+        match rejection(host, request, policy) {
+            Some(reason) => skipped.push(SkippedHost { instance_id: host.instance_id.clone(), reason }),
+            None => fitting.push(host),
+        }
+    }
+
+    if class.is_heavy() && fitting.iter().any(|host| host.heavy_sessions == 0) {
+        fitting.retain(|host| {
+            if host.heavy_sessions == 0 {
+                return true;
+            }
+            skipped.push(SkippedHost {
+                instance_id: host.instance_id.clone(),
+                reason: format!("already runs {} heavy sessions", host.heavy_sessions),
+            });
+            false
+        });
+    }
+
+    let chosen = fitting
+        .iter()
+        .min_by(|a, b| {
+            headroom(a, request, policy)
+                .total_cmp(&headroom(b, request, policy))
+                .then_with(|| a.instance_id.cmp(&b.instance_id))
+        })
+        .map(|host| host.instance_id.clone());
+    for host in &fitting {
+        if Some(&host.instance_id) != chosen.as_ref() {
+            skipped.push(SkippedHost { instance_id: host.instance_id.clone(), reason: "less full".to_string() });
+        }
+    }
+
+    PlacementDecision { at, app_id: app_id.to_string(), class, chosen, skipped }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn host(id: &str, capacity: Resources, reserved: Resources, heavy_sessions: u32) -> HostStatus {
+        HostStatus {
+            instance_id: id.to_string(),
+            launch_url: format!("http://{}/api/applications/launch", id),
+            capacity,
+            reserved,
+            heavy_sessions,
+            used: Resources::default(),
+            draining: false,
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_best_fit_fills_fullest_host() {
+        let hosts = vec![
+            host("empty", Resources::new(4000, 8192), Resources::default(), 0),
+            host("busy", Resources::new(4000, 8192), Resources::new(1000, 4096), 0),
+        ];
+        let decision = place("editor", ResourceClass::Small, &hosts, &OvercommitPolicy::default(), Utc::now());
+        assert_eq!(decision.chosen.as_deref(), Some("busy"));
+        assert_eq!(decision.chosen_host(&hosts).unwrap().instance_id, "busy");
+    }
+
+    #[test]
+    fn test_overcommit_applies_to_cpu_only() {
+        let policy = OvercommitPolicy::default();
+        let cpu_bound = host("a", Resources::new(1000, 8192), Resources::new(1500, 0), 0);
+        assert!(rejection(&cpu_bound, ResourceClass::Small.resources(), &policy).is_none());
+        assert!(rejection(&cpu_bound, ResourceClass::Medium.resources(), &policy).is_some());
+
+        let memory_bound = host("b", Resources::new(8000, 1024), Resources::new(0, 768), 0);
+        assert!(rejection(&memory_bound, ResourceClass::Small.resources(), &policy).is_some());
+
+        let mut pressured = host("c", Resources::new(8000, 8192), Resources::default(), 0);
+        pressured.used.memory_mb = 8000;
+        assert!(rejection(&pressured, ResourceClass::Small.resources(), &policy).is_some());
+    }
+
+    #[test]
+    fn test_heavy_apps_prefer_hosts_without_heavy_sessions() {
+        let hosts = vec![
+            host("heavy", Resources::new(8000, 16384), Resources::new(4000, 8192), 1),
+            host("light", Resources::new(8000, 16384), Resources::new(500, 512), 0),
+        ];
+        let policy = OvercommitPolicy::default();
+        let decision = place("cad", ResourceClass::Large, &hosts, &policy, Utc::now());
+        assert_eq!(decision.chosen.as_deref(), Some("light"));
+        assert!(decision.skipped.iter().any(|s| s.instance_id == "heavy" && s.reason.contains("heavy")));
+
+        // Anti-affinity is a preference: with only heavy hosts left, one is still used
+        let decision = place("cad", ResourceClass::Large, &hosts[..1], &policy, Utc::now());
+        assert_eq!(decision.chosen.as_deref(), Some("heavy"));
+    }
+
+    #[test]
+    fn test_no_room_anywhere() {
+        let hosts = vec![host("full", Resources::new(1000, 1024), Resources::new(0, 1024), 0)];
+        let decision = place("editor", ResourceClass::Small, &hosts, &OvercommitPolicy::default(), Utc::now());
+        assert_eq!(decision.chosen, None);
+        assert_eq!(decision.skipped.len(), 1);
+    }
+
+    #[test]
+    fn test_draining_host_gets_no_new_sessions() {
+        let mut draining = host("upgrading", Resources::new(8000, 16384), Resources::default(), 0);
+        draining.draining = true;
+        let hosts = vec![draining, host("busy", Resources::new(4000, 8192), Resources::new(2000, 4096), 0)];
+        let decision = place("editor", ResourceClass::Small, &hosts, &OvercommitPolicy::default(), Utc::now());
+        assert_eq!(decision.chosen.as_deref(), Some("busy"));
+        assert_eq!(decision.skipped[0].reason, "in maintenance");
+    }
+}
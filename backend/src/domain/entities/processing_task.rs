@@ -0,0 +1,155 @@
+use crate::domain::value_objects::UserId;
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProcessingStatus {
+    Pending,
+    Completed,
+    /// A stage kept failing; the file waits for an owner to retry it
+    DeadLettered,
+}
+
+impl ProcessingStatus {
+    pub fn as_db_str(&self) -> &'static str {
+        match self {
+            ProcessingStatus::Pending => "pending",
+            ProcessingStatus::Completed => "completed",
+            ProcessingStatus::DeadLettered => "dead_lettered",
+        }
+    }
+
+    pub fn from_db_str(s: &str) -> Result<Self, String> {
+        match s {
+            "pending" => Ok(ProcessingStatus::Pending),
+            "completed" => Ok(ProcessingStatus::Completed),
+            "dead_lettered" => Ok(ProcessingStatus::DeadLettered),
+            other => Err(format!("Unknown processing status: {other}")),
+        }
+    }
+}
+
+/// How often a failing stage is tried again before its file is dead-lettered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    /// Wait after the first failure, doubled after each further one
+    pub base_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self { max_attempts: 5, base_delay: Duration::seconds(30) }
+    }
+}
+
+/// A file landed in a vault, on its way through the processing stages (scanning, thumbnails,
+/// indexing, ...). Stages run in their registered order; each is recorded once it succeeded,
+/// so a retried file resumes at the stage that failed.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ProcessingTask {
+    pub id: Uuid,
+    pub owner_id: UserId,
+    /// Vault-relative path of the file
+    pub path: String,
+    pub size: u64,
+    pub status: ProcessingStatus,
+    /// Names of the stages that succeeded
+    pub done: Vec<String>,
+    /// Failed attempts at the current stage
+    pub attempts: u32,
+    pub last_error: Option<String>,
+    pub next_attempt_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ProcessingTask {
+    pub fn new(owner_id: UserId, path: &str, size: u64) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            owner_id,
+            path: path.trim_matches('/').to_string(),
+            size,
+            status: ProcessingStatus::Pending,
+            done: Vec::new(),
+            attempts: 0,
+            last_error: None,
+            next_attempt_at: now,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn has_done(&self, stage: &str) -> bool {
+        self.done.iter().any(|s| s == stage)
+    }
+
+    pub fn stage_succeeded(&mut self, stage: &str, now: DateTime<Utc>) {
+        self.done.push(stage.to_string());
+        self.attempts = 0;
+        self.last_error = None;
+        self.updated_at = now;
+    }
+
+    /// Schedule the stage again after a growing delay, or dead-letter the file once the
+    /// policy's attempts are used up.
+    pub fn stage_failed(&mut self, stage: &str, error: &str, policy: &RetryPolicy, now: DateTime<Utc>) {
+        self.attempts += 1;
+        self.last_error = Some(format!("{stage}: {error}"));
+        self.updated_at = now;
+        if self.attempts >= policy.max_attempts {
+            self.status = ProcessingStatus::DeadLettered;
+        } else {
+            let backoff = 2_i32.saturating_pow(self.attempts.saturating_sub(1).min(16));
+            self.next_attempt_at = now + policy.base_delay * backoff;
+        }
+    }
+
+    pub fn complete(&mut self, now: DateTime<Utc>) {
+        self.status = ProcessingStatus::Completed;
+        self.updated_at = now;
+    }
+
+    /// Put a dead-lettered file back in the queue, starting over at the stage that failed.
+    pub fn retry(&mut self, now: DateTime<Utc>) -> Result<(), String> {
+        if self.status != ProcessingStatus::DeadLettered {
+            return Err("Only dead-lettered files can be retried".to_string());
+        }
+        self.status = ProcessingStatus::Pending;
+        self.attempts = 0;
+        self.next_attempt_at = now;
+        self.updated_at = now;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failures_back_off_then_dead_letter() {
+        let policy = RetryPolicy { max_attempts: 3, base_delay: Duration::seconds(10) };
+        let now = Utc::now();
+        let mut task = ProcessingTask::new(UserId::new(), "/photos/a.jpg", 42);
+        assert_eq!(task.path, "photos/a.jpg");
+
+        task.stage_succeeded("scanner", now);
+        task.stage_failed("thumbnailer", "decoder crashed", &policy, now);
+        assert_eq!(task.next_attempt_at, now + Duration::seconds(10));
+        task.stage_failed("thumbnailer", "decoder crashed", &policy, now);
+        assert_eq!(task.next_attempt_at, now + Duration::seconds(20));
+        assert_eq!(task.status, ProcessingStatus::Pending);
+        task.stage_failed("thumbnailer", "decoder crashed", &policy, now);
+        assert_eq!(task.status, ProcessingStatus::DeadLettered);
+        assert_eq!(task.last_error.as_deref(), Some("thumbnailer: decoder crashed"));
+
+        task.retry(now).unwrap();
+        assert_eq!((task.status, task.attempts), (ProcessingStatus::Pending, 0));
+        assert!(task.has_done("scanner") && !task.has_done("thumbnailer"));
+        assert!(task.retry(now).is_err());
+    }
+}
@@ -0,0 +1,63 @@
+use crate::domain::value_objects::UserId;
+use chrono::{DateTime, Utc};
+use crate::domain::services::secrets::hash_secret;
+use uuid::Uuid;
+
+/// Prefix of every provisioning token, so leaked ones are easy to spot in logs and scanners
+pub const TOKEN_PREFIX: &str = "pvp_";
+
+/// A long-lived token an owner's scripts use to provision clients into their vault. Only its
+/// hash is stored; the token itself is shown once, when it is issued.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ProvisioningToken {
+    pub id: Uuid,
+    pub owner_id: UserId,
+    /// What the owner calls it, e.g. the script using it
+    pub name: String,
+    #[serde(skip_serializing)]
+    pub token_hash: String,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl ProvisioningToken {
+    /// A new token and its secret.
+    pub fn issue(owner_id: UserId, name: &str) -> Result<(Self, String), String> {
+        let name = name.trim();
+        if name.is_empty() || name.len() > 100 {
+            return Err("Token name must be 1 to 100 characters".to_string());
+        }
+        let secret = format!("{TOKEN_PREFIX}{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+        let token = Self {
+            id: Uuid::new_v4(),
+            owner_id,
+            name: name.to_string(),
+            token_hash: hash_secret(&secret),
+            created_at: Utc::now(),
+            last_used_at: None,
+            revoked_at: None,
+        };
+        Ok((token, secret))
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.revoked_at.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_issue() {
+        let (token, secret) = ProvisioningToken::issue(UserId::new(), " nightly sync ").unwrap();
+        assert!(secret.starts_with(TOKEN_PREFIX));
+        assert_eq!(token.name, "nightly sync");
+        assert_eq!(token.token_hash, hash_secret(&secret));
+        assert_ne!(token.token_hash, hash_secret("pvp_other"));
+        assert!(token.is_active());
+        assert!(ProvisioningToken::issue(UserId::new(), "  ").is_err());
+    }
+}
@@ -0,0 +1,12 @@
+use crate::domain::aggregates::application_session::StreamQuality;
+use crate::domain::value_objects::UserId;
+use chrono::{DateTime, Utc};
+
+/// Last stream quality chosen by a user for an application, reused on the next launch.
+#[derive(Debug, Clone)]
+pub struct QualityPreference {
+    pub user_id: UserId,
+    pub app_id: String,
+    pub quality: StreamQuality,
+    pub updated_at: DateTime<Utc>,
+}
@@ -1,6 +1,8 @@
-use crate::domain::value_objects::UserId;
+use crate::domain::aggregates::application_session::VideoConfig;
+use crate::domain::value_objects::{Interaction, UserId};
+use super::tenant::DEFAULT_TENANT;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct Session {
     pub id: uuid::Uuid,
     pub user_id: UserId,
@@ -12,6 +14,13 @@ pub struct Session {
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub expires_at: chrono::DateTime<chrono::Utc>,
     pub terminated_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Display size (device pixels), framerate and codec the session was launched with;
+    /// `None` for sessions started before launches recorded them
+    pub video: Option<VideoConfig>,
+    /// Tenant of the user who launched it; a session never opens another tenant's vault
+    pub tenant_id: uuid::Uuid,
+    /// Whether the signaling socket passes the user's input to the app
+    pub interaction: Interaction,
 }
 
 impl Session {
@@ -35,6 +44,9 @@ impl Session {
             created_at: now,
             expires_at: now + chrono::Duration::seconds(session_timeout_secs as i64),
             terminated_at: None,
+            video: None,
+            tenant_id: DEFAULT_TENANT,
+            interaction: Interaction::Interactive,
         }
     }
 
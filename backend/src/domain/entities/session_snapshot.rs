@@ -0,0 +1,15 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use crate::domain::value_objects::UserId;
+
+/// What a suspended session needs to resume: its app, and the state the app saved. The vault
+/// paths and display size are worked out again on resume, from current permissions and device.
+#[derive(Debug, Clone)]
+pub struct SessionSnapshot {
+    pub session_id: Uuid,
+    pub user_id: UserId,
+    pub app_id: String,
+    /// `None` when the app did not answer the suspend request; it then starts fresh
+    pub state: Option<Vec<u8>>,
+    pub suspended_at: DateTime<Utc>,
+}
@@ -0,0 +1,173 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Steps of a session's life, from launch to cleanup, in the order they normally happen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimelineStage {
+    Launched,
+    XvfbStarted,
+    AppSpawned,
+    /// Launching stopped before the session could stream
+    LaunchFailed,
+    /// The encoder produced its first frame
+    FirstFrame,
+    /// The client was told the session is ready, by the app's `Ready` or the first frame
+    SessionReady,
+    PeerConnected,
+    /// ICE settled on a working candidate pair
+    IceCompleted,
+    /// The candidate pair carrying the media, recorded again on each switch
+    CandidatePairSelected,
+    FirstRtpSent,
+    Disconnected,
+    /// The client renegotiated ICE on the existing peer, usually after a network change
+    IceRestarted,
+    /// WebRTC could not connect, so video went over the signaling WebSocket instead
+    FallbackStarted,
+    /// The user suspended the session; the app's saved state is kept for a resume
+    Suspended,
+    CleanedUp,
+}
+
+impl TimelineStage {
+    /// How far a launch is once this stage is reached, in percent, for the client's progress
+    /// bar. `None` for stages that are not steps of a launch.
+    pub fn launch_percent(&self) -> Option<u8> {
+        match self {
+            TimelineStage::Launched => Some(5),
+            TimelineStage::XvfbStarted => Some(20),
+            TimelineStage::AppSpawned => Some(40),
+            TimelineStage::FirstFrame => Some(55),
+            TimelineStage::PeerConnected | TimelineStage::FallbackStarted => Some(70),
+            TimelineStage::IceCompleted => Some(80),
+            TimelineStage::FirstRtpSent => Some(90),
+            TimelineStage::SessionReady => Some(100),
+            _ => None,
+        }
+    }
+
+    /// Stages that can happen at most once; the others repeat on reconnects.
+    fn is_once(&self) -> bool {
+        matches!(
+            self,
+            TimelineStage::Launched
+                | TimelineStage::XvfbStarted
+                | TimelineStage::AppSpawned
+                | TimelineStage::LaunchFailed
+                | TimelineStage::FirstFrame
+                | TimelineStage::SessionReady
+                | TimelineStage::FirstRtpSent
+                | TimelineStage::Suspended
+                | TimelineStage::CleanedUp
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct TimelineEvent {
+    pub at: DateTime<Utc>,
+    pub stage: TimelineStage,
+    /// Milliseconds since the first event, so gaps stand out without date arithmetic
+    pub elapsed_ms: u64,
+    pub detail: Option<String>,
+}
+
+/// What happened to a session and when, for diagnosing sessions that never show video.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SessionTimeline {
+    pub session_id: Uuid,
+    pub events: Vec<TimelineEvent>,
+}
+
+impl SessionTimeline {
+    /// Reconnect loops stop being recorded past this, keeping the first events
+    pub const MAX_EVENTS: usize = 200;
+
+    pub fn new(session_id: Uuid) -> Self {
+        Self { session_id, events: Vec::new() }
+    }
+
+    pub fn has(&self, stage: TimelineStage) -> bool {
+        self.events.iter().any(|event| event.stage == stage)
+    }
+
+    /// Milliseconds from the first event to the first `stage`, if it happened
+    pub fn elapsed_at(&self, stage: TimelineStage) -> Option<u64> {
+        self.events.iter().find(|event| event.stage == stage).map(|event| event.elapsed_ms)
+    }
+
+    pub fn record(&mut self, stage: TimelineStage, detail: Option<String>, at: DateTime<Utc>) {
+        if self.events.len() >= Self::MAX_EVENTS || (stage.is_once() && self.has(stage)) {
+            return;
+        }
+        let elapsed_ms = self
+            .events
+            .first()
+            .map(|first| (at - first.at).num_milliseconds().max(0) as u64)
+            .unwrap_or(0);
+        self.events.push(TimelineEvent { at, stage, elapsed_ms, detail });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_once_stages_are_recorded_once() {
+        let start = Utc::now();
+        let mut timeline = SessionTimeline::new(Uuid::new_v4());
+        timeline.record(TimelineStage::Launched, None, start);
+        timeline.record(TimelineStage::FirstFrame, None, start);
+        timeline.record(TimelineStage::FirstFrame, None, start);
+        timeline.record(TimelineStage::PeerConnected, None, start);
+        timeline.record(TimelineStage::PeerConnected, None, start);
+        let stages: Vec<_> = timeline.events.iter().map(|e| e.stage).collect();
+        assert_eq!(
+            stages,
+            vec![TimelineStage::Launched, TimelineStage::FirstFrame, TimelineStage::PeerConnected, TimelineStage::PeerConnected]
+        );
+    }
+
+    #[test]
+    fn test_launch_percent_follows_stage_order() {
+        let percents: Vec<u8> = [
+            TimelineStage::Launched,
+            TimelineStage::XvfbStarted,
+            TimelineStage::AppSpawned,
+            TimelineStage::FirstFrame,
+            TimelineStage::PeerConnected,
+            TimelineStage::IceCompleted,
+            TimelineStage::FirstRtpSent,
+            TimelineStage::SessionReady,
+        ]
+        .iter()
+        .filter_map(|stage| stage.launch_percent())
+        .collect();
+        assert_eq!(percents.len(), 8);
+        assert!(percents.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(TimelineStage::LaunchFailed.launch_percent(), None);
+        assert_eq!(TimelineStage::Disconnected.launch_percent(), None);
+    }
+
+    #[test]
+    fn test_elapsed_is_relative_to_first_event_and_capped() {
+        let start = Utc::now();
+        let mut timeline = SessionTimeline::new(Uuid::new_v4());
+        timeline.record(TimelineStage::Launched, None, start);
+        timeline.record(TimelineStage::XvfbStarted, None, start + chrono::Duration::milliseconds(250));
+        assert_eq!(timeline.events[1].elapsed_ms, 250);
+        assert_eq!(timeline.elapsed_at(TimelineStage::XvfbStarted), Some(250));
+        assert_eq!(timeline.elapsed_at(TimelineStage::SessionReady), None);
+
+        for _ in 0..SessionTimeline::MAX_EVENTS {
+            timeline.record(TimelineStage::Disconnected, None, start);
+        }
+        assert_eq!(timeline.events.len(), SessionTimeline::MAX_EVENTS);
+        assert_eq!(
+            serde_json::to_value(timeline.events[1].stage).unwrap(),
+            serde_json::json!("xvfb_started")
+        );
+    }
+}
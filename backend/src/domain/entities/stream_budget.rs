@@ -0,0 +1,125 @@
+/// What one stream would encode on its own, and how much it counts when streams share the
+/// host's budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamDemand {
+    /// Encoded pixels per frame
+    pub pixels: u64,
+    /// Relative importance, from the app's manifest; 1 unless raised
+    pub priority: u8,
+    pub bitrate: u32,
+    pub framerate: u8,
+}
+
+impl StreamDemand {
+    /// Larger and more important streams get a larger part of a contended budget
+    fn weight(&self) -> u64 {
+        self.pixels.max(1) * self.priority.max(1) as u64
+    }
+}
+
+/// Ceilings the shared budget puts on one stream; `None` when it leaves that setting alone.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StreamShare {
+    pub max_bitrate: Option<u32>,
+    pub max_framerate: Option<u8>,
+}
+
+/// Totals every stream encoding on the host at once has to share. Unset totals are not shared.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StreamBudget {
+    /// Bits per second across all encoders
+    pub total_bitrate: Option<u64>,
+    /// Pixels encoded per second across all encoders, which is what their CPU use follows
+    pub total_pixel_rate: Option<u64>,
+}
+
+impl StreamBudget {
+    /// Each stream's share, in the order of `demands`.
+    pub fn allocate(&self, demands: &[StreamDemand]) -> Vec<StreamShare> {
+        let weights: Vec<u64> = demands.iter().map(StreamDemand::weight).collect();
+        let bitrates = self.total_bitrate.map(|total| {
+            let wants: Vec<u64> = demands.iter().map(|demand| demand.bitrate as u64).collect();
+            share_out(total, &weights, &wants)
+        });
+        let pixel_rates = self.total_pixel_rate.map(|total| {
+            let wants: Vec<u64> = demands.iter().map(|demand| demand.pixels.max(1) * demand.framerate as u64).collect();
+            share_out(total, &weights, &wants)
+        });
+        demands
+            .iter()
+            .enumerate()
+            .map(|(i, demand)| StreamShare {
+                max_bitrate: bitrates.as_ref().map(|shares| shares[i].min(u32::MAX as u64) as u32),
+                max_framerate: pixel_rates
+                    .as_ref()
+                    .map(|shares| (shares[i] / demand.pixels.max(1)).min(u8::MAX as u64) as u8),
+            })
+            .collect()
+    }
+}
+
+/// Weighted max-min fair split of `total`: nobody gets more than it wants, and what modest
+/// streams leave over goes to the others in proportion to their weights.
+fn share_out(total: u64, weights: &[u64], wants: &[u64]) -> Vec<u64> {
+    let mut shares = vec![0; wants.len()];
+    let mut open: Vec<usize> = (0..wants.len()).collect();
+    let mut left = total;
+    while !open.is_empty() {
+        let weight: u128 = open.iter().map(|&i| weights[i] as u128).sum();
+        let satisfied: Vec<usize> = open
+            .iter()
+            .copied()
+            .filter(|&i| wants[i] as u128 * weight <= left as u128 * weights[i] as u128)
+            .collect();
+        if satisfied.is_empty() {
+            for &i in &open {
+                shares[i] = (left as u128 * weights[i] as u128 / weight) as u64;
+            }
+            break;
+        }
+        for &i in &satisfied {
+            shares[i] = wants[i];
+            left -= wants[i];
+        }
+        open.retain(|i| !satisfied.contains(i));
+    }
+    shares
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn demand(pixels: u64, priority: u8, bitrate: u32) -> StreamDemand {
+        StreamDemand { pixels, priority, bitrate, framerate: 30 }
+    }
+
+    #[test]
+    fn test_modest_streams_keep_their_bitrate_and_the_rest_is_weighted() {
+        let budget = StreamBudget { total_bitrate: Some(10_000_000), total_pixel_rate: None };
+        let shares = budget.allocate(&[
+            demand(1_000_000, 1, 1_000_000),
+            demand(1_000_000, 1, 20_000_000),
+            demand(1_000_000, 2, 20_000_000),
+        ]);
+        let bitrates: Vec<_> = shares.iter().map(|share| share.max_bitrate.unwrap()).collect();
+        assert_eq!(bitrates, vec![1_000_000, 3_000_000, 6_000_000]);
+        assert!(shares.iter().all(|share| share.max_framerate.is_none()));
+    }
+
+    #[test]
+    fn test_uncontended_budget_covers_every_stream() {
+        let budget = StreamBudget { total_bitrate: Some(10_000_000), total_pixel_rate: Some(1_000_000 * 60) };
+        let shares = budget.allocate(&[demand(1_000_000, 1, 4_000_000)]);
+        assert_eq!(shares, vec![StreamShare { max_bitrate: Some(4_000_000), max_framerate: Some(30) }]);
+        assert!(StreamBudget::default().allocate(&[demand(1, 1, 1)]).iter().all(|share| *share == StreamShare::default()));
+    }
+
+    #[test]
+    fn test_pixel_rate_budget_lowers_framerates_by_resolution() {
+        let budget = StreamBudget { total_bitrate: None, total_pixel_rate: Some(3_000_000 * 30) };
+        let shares = budget.allocate(&[demand(2_000_000, 1, 1), demand(2_000_000, 1, 1)]);
+        assert_eq!(shares[0].max_framerate, Some(22));
+        assert_eq!(shares[1].max_framerate, Some(22));
+    }
+}
@@ -0,0 +1,65 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use crate::domain::services::secrets::hash_secret;
+
+/// The tenant everything belonged to before tenants existed. Its super admins run the
+/// instance; its vaults keep their place directly under `STORAGE_PATH`.
+pub const DEFAULT_TENANT: Uuid = Uuid::nil();
+
+/// Prefix of tenant setup tokens
+pub const SETUP_TOKEN_PREFIX: &str = "pvs_";
+
+/// An isolated group of users sharing the instance, e.g. one family or small organisation.
+/// Its users, permissions and sessions never see another tenant's, and its vaults live under
+/// `STORAGE_PATH/tenants/<id>`.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Tenant {
+    pub id: Uuid,
+    pub name: String,
+    /// Hash of the token its first admin registers with; cleared once they have
+    #[serde(skip_serializing)]
+    pub setup_token_hash: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Tenant {
+    /// A new tenant and the token its first admin sets up their account with, shown once.
+    pub fn create(name: &str) -> Result<(Self, String), String> {
+        let name = name.trim();
+        if name.is_empty() || name.len() > 100 {
+            return Err("Tenant name must be 1 to 100 characters".to_string());
+        }
+        let secret = format!("{SETUP_TOKEN_PREFIX}{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+        let tenant = Self {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            setup_token_hash: Some(hash_secret(&secret)),
+            created_at: Utc::now(),
+        };
+        Ok((tenant, secret))
+    }
+
+    pub fn is_default(&self) -> bool {
+        self.id == DEFAULT_TENANT
+    }
+
+    /// Whether its first admin has yet to register.
+    pub fn awaits_setup(&self) -> bool {
+        self.setup_token_hash.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create() {
+        let (tenant, secret) = Tenant::create(" The Martins ").unwrap();
+        assert_eq!(tenant.name, "The Martins");
+        assert!(secret.starts_with(SETUP_TOKEN_PREFIX));
+        assert_eq!(tenant.setup_token_hash, Some(hash_secret(&secret)));
+        assert!(tenant.awaits_setup() && !tenant.is_default());
+        assert!(Tenant::create("  ").is_err());
+    }
+}
@@ -0,0 +1,136 @@
+use crate::domain::entities::file_job::validate_vault_path;
+use crate::domain::value_objects::UserId;
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UploadStatus {
+    Active,
+    Completed,
+    /// Rejected by a completion hook, e.g. over quota
+    Failed,
+    Cancelled,
+    /// Left idle past its expiry
+    Expired,
+}
+
+impl UploadStatus {
+    pub fn as_db_str(&self) -> &'static str {
+        match self {
+            UploadStatus::Active => "active",
+            UploadStatus::Completed => "completed",
+            UploadStatus::Failed => "failed",
+            UploadStatus::Cancelled => "cancelled",
+            UploadStatus::Expired => "expired",
+        }
+    }
+
+    pub fn from_db_str(s: &str) -> Result<Self, String> {
+        match s {
+            "active" => Ok(UploadStatus::Active),
+            "completed" => Ok(UploadStatus::Completed),
+            "failed" => Ok(UploadStatus::Failed),
+            "cancelled" => Ok(UploadStatus::Cancelled),
+            "expired" => Ok(UploadStatus::Expired),
+            other => Err(format!("Unknown upload status: {other}")),
+        }
+    }
+}
+
+/// A file uploaded in chunks that may span several requests and connections. Bytes are staged
+/// outside the vault until all `size` of them arrived, then moved to `path`.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct UploadSession {
+    pub id: Uuid,
+    /// The vault the file lands in
+    pub owner_id: UserId,
+    pub created_by: UserId,
+    pub path: String,
+    pub size: u64,
+    /// Bytes received so far; the next chunk must start here
+    pub offset: u64,
+    pub status: UploadStatus,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Pushed back by every chunk received
+    pub expires_at: DateTime<Utc>,
+}
+
+impl UploadSession {
+    /// How long an unfinished upload may sit idle before it is discarded
+    pub fn idle_ttl() -> Duration {
+        Duration::hours(24)
+    }
+
+    pub fn new(owner_id: UserId, created_by: UserId, path: String, size: u64) -> Result<Self, String> {
+        validate_vault_path(&path)?;
+        let now = Utc::now();
+        Ok(Self {
+            id: Uuid::new_v4(),
+            owner_id,
+            created_by,
+            path,
+            size,
+            offset: 0,
+            status: UploadStatus::Active,
+            error: None,
+            created_at: now,
+            updated_at: now,
+            expires_at: now + Self::idle_ttl(),
+        })
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.offset >= self.size
+    }
+
+    /// A chunk is only accepted while the upload is active and if it starts exactly where the
+    /// received bytes end.
+    pub fn check_offset(&self, offset: u64) -> Result<(), String> {
+        if self.status != UploadStatus::Active {
+            return Err(format!("Upload is no longer active ({})", self.status.as_db_str()));
+        }
+        if offset != self.offset {
+            return Err(format!("Offset mismatch: upload is at {}", self.offset));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn upload(size: u64) -> UploadSession {
+        UploadSession::new(UserId::new(), UserId::new(), "videos/trip.mp4".to_string(), size).unwrap()
+    }
+
+    #[test]
+    fn test_new_upload_starts_at_zero() {
+        let session = upload(1024);
+        assert_eq!(session.offset, 0);
+        assert_eq!(session.status, UploadStatus::Active);
+        assert!(!session.is_complete());
+        assert!(upload(0).is_complete());
+        assert!(UploadSession::new(UserId::new(), UserId::new(), "../x".to_string(), 1).is_err());
+    }
+
+    #[test]
+    fn test_chunks_must_continue_at_offset() {
+        let mut session = upload(1024);
+        session.offset = 512;
+        assert!(session.check_offset(512).is_ok());
+        assert!(session.check_offset(0).is_err());
+        session.status = UploadStatus::Cancelled;
+        assert!(session.check_offset(512).is_err());
+    }
+
+    #[test]
+    fn test_status_round_trips_through_db_strings() {
+        for status in [UploadStatus::Active, UploadStatus::Completed, UploadStatus::Failed, UploadStatus::Cancelled, UploadStatus::Expired] {
+            assert_eq!(UploadStatus::from_db_str(status.as_db_str()).unwrap(), status);
+        }
+    }
+}
@@ -1,4 +1,7 @@
 use crate::domain::value_objects::*;
+use shared::Locale;
+use uuid::Uuid;
+use super::tenant::DEFAULT_TENANT;
 
 #[derive(Debug, Clone)]
 pub struct User {
@@ -7,6 +10,8 @@ pub struct User {
     display_name: DisplayName,
     roles: Vec<UserRole>,
     status: UserStatus,
+    locale: Locale,
+    tenant_id: Uuid,
 }
 
 impl User {
@@ -17,8 +22,16 @@ impl User {
             display_name,
             roles,
             status: UserStatus::Active,
+            locale: Locale::default(),
+            tenant_id: DEFAULT_TENANT,
         }
     }
+
+    /// The same new user, created in `tenant_id` rather than the default tenant.
+    pub fn in_tenant(mut self, tenant_id: Uuid) -> Self {
+        self.tenant_id = tenant_id;
+        self
+    }
     
     pub fn from_persistence(
         id: UserId,
@@ -26,6 +39,8 @@ impl User {
         display_name: DisplayName,
         roles: Vec<UserRole>,
         status: UserStatus,
+        locale: Locale,
+        tenant_id: Uuid,
     ) -> Self {
         Self {
             id,
@@ -33,6 +48,8 @@ impl User {
             display_name,
             roles,
             status,
+            locale,
+            tenant_id,
         }
     }
     
@@ -56,10 +73,34 @@ impl User {
     pub fn has_role(&self, role: UserRole) -> bool {
         self.roles.contains(&role)
     }
+
+    /// Add `role` if missing; returns whether the roles changed.
+    pub fn grant_role(&mut self, role: UserRole) -> bool {
+        if self.has_role(role) {
+            return false;
+        }
+        self.roles.push(role);
+        true
+    }
+
+    /// Drop `role` if held; returns whether the roles changed.
+    pub fn remove_role(&mut self, role: UserRole) -> bool {
+        let before = self.roles.len();
+        self.roles.retain(|r| *r != role);
+        self.roles.len() != before
+    }
     
     pub fn status(&self) -> UserStatus {
         self.status
     }
+
+    pub fn locale(&self) -> Locale {
+        self.locale
+    }
+
+    pub fn tenant_id(&self) -> Uuid {
+        self.tenant_id
+    }
     
     // Removed unused methods is_active, suspend, and activate
 }
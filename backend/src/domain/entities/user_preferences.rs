@@ -0,0 +1,87 @@
+use crate::domain::aggregates::application_session::VideoConfig;
+use crate::domain::value_objects::UserId;
+use chrono::{DateTime, Utc};
+use shared::Theme;
+
+/// Display and notification preferences of a user, applied to every launch.
+#[derive(Debug, Clone)]
+pub struct UserPreferences {
+    pub user_id: UserId,
+    pub theme: Theme,
+    pub default_width: u16,
+    pub default_height: u16,
+    pub default_framerate: u8,
+    pub keyboard_layout: String,
+    /// IANA time zone handed to apps; `None` follows the browser's
+    pub timezone: Option<String>,
+    pub notify_email: bool,
+    pub notify_in_app: bool,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl UserPreferences {
+    pub fn defaults(user_id: UserId) -> Self {
+        Self {
+            user_id,
+            theme: Theme::default(),
+            default_width: 1280,
+            default_height: 720,
+            default_framerate: VideoConfig::default().framerate,
+            keyboard_layout: shared::protocol::default_keyboard_layout(),
+            timezone: None,
+            notify_email: true,
+            notify_in_app: true,
+            updated_at: Utc::now(),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if !(320..=7680).contains(&self.default_width) || !(240..=4320).contains(&self.default_height) {
+            return Err(format!(
+                "Default resolution {}x{} is out of range",
+                self.default_width, self.default_height
+            ));
+        }
+        if !(5..=60).contains(&self.default_framerate) {
+            return Err(format!("Default framerate {} is out of range", self.default_framerate));
+        }
+        let layout_ok = !self.keyboard_layout.is_empty()
+            && self.keyboard_layout.len() <= 32
+            && self
+                .keyboard_layout
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '(' || c == ')');
+        if !layout_ok {
+            return Err(format!("Invalid keyboard layout: {}", self.keyboard_layout));
+        }
+        if let Some(timezone) = self.timezone.as_deref().filter(|tz| !is_timezone_name(tz)) {
+            return Err(format!("Invalid time zone: {}", timezone));
+        }
+        Ok(())
+    }
+}
+
+/// Whether `name` looks like an IANA time zone such as `Europe/Paris`. It ends up in `TZ`,
+/// where a leading `/` or `:`, or `..`, would point glibc at any file on the host.
+pub fn is_timezone_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 64
+        && !name.starts_with('/')
+        && !name.contains("..")
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '/' | '_' | '-' | '+'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timezone_names() {
+        for name in ["Europe/Paris", "America/Argentina/Buenos_Aires", "UTC", "Etc/GMT+3"] {
+            assert!(is_timezone_name(name), "{name}");
+        }
+        for name in ["", "/etc/shadow", "../../etc/shadow", ":Europe/Paris", "Europe/Paris\n"] {
+            assert!(!is_timezone_name(name), "{name:?}");
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use crate::domain::entities::file_job::{validate_vault_path, JobStatus};
+use crate::domain::value_objects::UserId;
+use chrono::{DateTime, Utc};
+use std::path::Path;
+use uuid::Uuid;
+
+/// How files get from the host directory into the vault
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportMode {
+    /// The source tree is left untouched
+    Copy,
+    /// No data is copied; the source must be on the vault's filesystem
+    Hardlink,
+    /// Renamed into the vault, or copied then deleted across filesystems
+    Move,
+}
+
+/// What happens when a vault file already exists at an imported file's path
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictPolicy {
+    /// Keep the vault file and leave the source file out
+    Skip,
+    Overwrite,
+    /// Import under a free name, `photo (1).jpg`
+    Rename,
+}
+
+impl ImportMode {
+    pub fn as_db_str(&self) -> &'static str {
+        match self {
+            ImportMode::Copy => "copy",
+            ImportMode::Hardlink => "hardlink",
+            ImportMode::Move => "move",
+        }
+    }
+
+    pub fn from_db_str(s: &str) -> Result<Self, String> {
+        match s {
+            "copy" => Ok(ImportMode::Copy),
+            "hardlink" => Ok(ImportMode::Hardlink),
+            "move" => Ok(ImportMode::Move),
+            other => Err(format!("Unknown import mode: {other}")),
+        }
+    }
+}
+
+impl ConflictPolicy {
+    pub fn as_db_str(&self) -> &'static str {
+        match self {
+            ConflictPolicy::Skip => "skip",
+            ConflictPolicy::Overwrite => "overwrite",
+            ConflictPolicy::Rename => "rename",
+        }
+    }
+
+    pub fn from_db_str(s: &str) -> Result<Self, String> {
+        match s {
+            "skip" => Ok(ConflictPolicy::Skip),
+            "overwrite" => Ok(ConflictPolicy::Overwrite),
+            "rename" => Ok(ConflictPolicy::Rename),
+            other => Err(format!("Unknown conflict policy: {other}")),
+        }
+    }
+}
+
+/// Counters of a running import, reported as its progress
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize)]
+pub struct ImportProgress {
+    /// Files found under the source; unknown until the scan finishes
+    pub files_total: Option<u64>,
+    /// Files handled so far, skipped ones included
+    pub files_done: u64,
+    pub bytes_done: u64,
+    pub skipped: u64,
+}
+
+/// A host directory tree adopted into an owner's vault in the background, e.g. when moving
+/// off a NAS.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct VaultImport {
+    pub id: Uuid,
+    pub owner_id: UserId,
+    pub requested_by: UserId,
+    /// Absolute host path, under one of the `IMPORT_ROOTS`
+    pub source: String,
+    /// Vault folder the tree lands in
+    pub destination: String,
+    pub mode: ImportMode,
+    pub on_conflict: ConflictPolicy,
+    pub status: JobStatus,
+    pub progress: ImportProgress,
+    pub error: Option<String>,
+    pub cancel_requested: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl VaultImport {
+    pub fn new(
+        owner_id: UserId,
+        requested_by: UserId,
+        source: &str,
+        destination: &str,
+        mode: ImportMode,
+        on_conflict: ConflictPolicy,
+    ) -> Result<Self, String> {
+        if !Path::new(source).is_absolute() || source.split('/').any(|part| part == "..") {
+            return Err(format!("Invalid source '{source}': must be an absolute path without '..'"));
+        }
+        validate_vault_path(destination)?;
+        let now = Utc::now();
+        Ok(Self {
+            id: Uuid::new_v4(),
+            owner_id,
+            requested_by,
+            source: source.trim_end_matches('/').to_string(),
+            destination: destination.trim_matches('/').to_string(),
+            mode,
+            on_conflict,
+            status: JobStatus::Queued,
+            progress: ImportProgress::default(),
+            error: None,
+            cancel_requested: false,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+}
+
+/// `name` with ` (n)` before its extension: `photo.jpg` becomes `photo (2).jpg`.
+pub fn numbered_name(name: &str, n: u32) -> String {
+    match name.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => format!("{stem} ({n}).{ext}"),
+        _ => format!("{name} ({n})"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn import(source: &str, destination: &str) -> Result<VaultImport, String> {
+        VaultImport::new(UserId::new(), UserId::new(), source, destination, ImportMode::Copy, ConflictPolicy::Skip)
+    }
+
+    #[test]
+    fn test_new_import_validates_both_paths() {
+        let job = import("/mnt/nas/photos/", "/nas/photos/").unwrap();
+        assert_eq!(job.source, "/mnt/nas/photos");
+        assert_eq!(job.destination, "nas/photos");
+        assert_eq!(job.status, JobStatus::Queued);
+        assert!(import("mnt/nas", "nas").is_err());
+        assert!(import("/mnt/nas/../../etc", "nas").is_err());
+        assert!(import("/mnt/nas", "../other").is_err());
+    }
+
+    #[test]
+    fn test_numbered_name_keeps_the_extension() {
+        assert_eq!(numbered_name("photo.jpg", 1), "photo (1).jpg");
+        assert_eq!(numbered_name("archive.tar.gz", 2), "archive.tar (2).gz");
+        assert_eq!(numbered_name("README", 1), "README (1)");
+        assert_eq!(numbered_name(".bashrc", 1), ".bashrc (1)");
+    }
+}
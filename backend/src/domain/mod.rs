@@ -6,6 +6,7 @@ pub mod aggregates;
 pub mod value_objects;
 pub mod events;
 pub mod apps;
+pub mod services;
 
 pub use entities::*;
 pub use value_objects::*;
@@ -0,0 +1,133 @@
+//! The small Markdown subset comments are written in, rendered to HTML that is safe to insert
+//! in a page: everything the author typed is escaped, and links only lead to `http`, `https`
+//! or `mailto` addresses.
+//!
+//! Supported: paragraphs separated by blank lines, line breaks, `-` or `*` bullet lists,
+//! `**bold**`, `*italic*`, `` `code` `` and `[text](url)`. Anything else shows as typed.
+
+/// Render `source` to HTML.
+pub fn to_html(source: &str) -> String {
+    let mut html = String::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut list: Vec<&str> = Vec::new();
+    for line in source.lines().map(str::trim) {
+        if let Some(item) = line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")) {
+            flush_paragraph(&mut html, &mut paragraph);
+            list.push(item.trim());
+        } else if line.is_empty() {
+            flush_paragraph(&mut html, &mut paragraph);
+            flush_list(&mut html, &mut list);
+        } else {
+            flush_list(&mut html, &mut list);
+            paragraph.push(line);
+        }
+    }
+    flush_paragraph(&mut html, &mut paragraph);
+    flush_list(&mut html, &mut list);
+    html
+}
+
+fn flush_paragraph(html: &mut String, lines: &mut Vec<&str>) {
+    if lines.is_empty() {
+        return;
+    }
+    let rendered: Vec<String> = lines.drain(..).map(inline).collect();
+    html.push_str(&format!("<p>{}</p>", rendered.join("<br>")));
+}
+
+fn flush_list(html: &mut String, items: &mut Vec<&str>) {
+    if items.is_empty() {
+        return;
+    }
+    html.push_str("<ul>");
+    for item in items.drain(..) {
+        html.push_str(&format!("<li>{}</li>", inline(item)));
+    }
+    html.push_str("</ul>");
+}
+
+/// Spans within a line. An opening marker without its closing one is kept as text.
+fn inline(text: &str) -> String {
+    let mut html = String::new();
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        if c == '`' {
+            if let Some(end) = rest[1..].find('`') {
+                html.push_str(&format!("<code>{}</code>", escape(&rest[1..1 + end])));
+                rest = &rest[end + 2..];
+                continue;
+            }
+        }
+        if let Some(inner) = rest.strip_prefix("**") {
+            if let Some(end) = inner.find("**").filter(|&end| end > 0) {
+                html.push_str(&format!("<strong>{}</strong>", inline(&inner[..end])));
+                rest = &inner[end + 2..];
+                continue;
+            }
+        }
+        if c == '*' {
+            if let Some(end) = rest[1..].find('*').filter(|&end| end > 0) {
+                html.push_str(&format!("<em>{}</em>", inline(&rest[1..1 + end])));
+                rest = &rest[end + 2..];
+                continue;
+            }
+        }
+        if c == '[' {
+            if let Some((label, url, len)) = link(rest) {
+                html.push_str(&format!(
+                    "<a href=\"{}\" rel=\"nofollow noopener noreferrer\" target=\"_blank\">{}</a>",
+                    escape(url),
+                    inline(label)
+                ));
+                rest = &rest[len..];
+                continue;
+            }
+        }
+        html.push_str(&escape(&rest[..c.len_utf8()]));
+        rest = &rest[c.len_utf8()..];
+    }
+    html
+}
+
+/// `[label](url)` at the start of `text`, with the length it spans, when the URL is allowed.
+fn link(text: &str) -> Option<(&str, &str, usize)> {
+    let label_end = text.find("](")?;
+    let url_end = label_end + 2 + text[label_end + 2..].find(')')?;
+    let (label, url) = (&text[1..label_end], text[label_end + 2..url_end].trim());
+    let allowed = ["http://", "https://", "mailto:"].iter().any(|scheme| url.to_ascii_lowercase().starts_with(scheme));
+    (allowed && !label.is_empty() && !url.contains(char::is_whitespace)).then_some((label, url, url_end + 1))
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_renders_subset_and_escapes_the_rest() {
+        assert_eq!(
+            to_html("Looks **good**, see *page 2*\nand `a<b`\n\n- one\n- two"),
+            "<p>Looks <strong>good</strong>, see <em>page 2</em><br>and <code>a&lt;b</code></p><ul><li>one</li><li>two</li></ul>"
+        );
+        assert_eq!(
+            to_html("[spec](https://example.com/a?b=1&c=2)"),
+            "<p><a href=\"https://example.com/a?b=1&amp;c=2\" rel=\"nofollow noopener noreferrer\" target=\"_blank\">spec</a></p>"
+        );
+        assert_eq!(to_html("[x](javascript:alert(1))"), "<p>[x](javascript:alert(1))</p>");
+        assert_eq!(to_html("<script>\"hi\"</script> 2 * 3"), "<p>&lt;script&gt;&quot;hi&quot;&lt;/script&gt; 2 * 3</p>");
+    }
+}
@@ -0,0 +1,4 @@
+// Domain services - rules spanning several entities
+pub mod permission_evaluator;
+pub mod markdown;
+pub mod secrets;
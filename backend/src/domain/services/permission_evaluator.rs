@@ -0,0 +1,467 @@
+//! Whether someone may do something to a path of a vault. Every adapter that touches vault
+//! files (the owner file API, background jobs, the IPC relay to apps) asks the same
+//! [`PermissionEvaluator`], so a grant, delegation or legal hold means the same everywhere, and a
+//! refusal comes back as a [`Denial`] with a code callers can match on.
+
+use crate::domain::entities::file_permission::FilePermission;
+use crate::domain::entities::invitation::AccessLevel;
+use crate::domain::entities::legal_hold::{self, LegalHold};
+use crate::domain::entities::owner_delegation::OwnerDelegation;
+use crate::domain::value_objects::Interaction;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::fmt;
+use uuid::Uuid;
+
+/// What is done to a path
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Operation {
+    /// List a folder on the way to what is granted, without reading anything in it
+    Browse,
+    Read,
+    /// Read, taking a copy out of the vault
+    Download,
+    /// Add new entries; vault operations never overwrite
+    Write,
+    /// Write, bringing a file into the vault
+    Upload,
+    /// Remove, including moving away
+    Delete,
+}
+
+impl Operation {
+    /// Level a client's grant must include
+    pub fn access_level(self) -> AccessLevel {
+        match self {
+            Operation::Browse | Operation::Read | Operation::Download => AccessLevel::Read,
+            Operation::Write | Operation::Upload => AccessLevel::Write,
+            Operation::Delete => AccessLevel::Delete,
+        }
+    }
+
+    fn is_transfer(self) -> bool {
+        matches!(self, Operation::Download | Operation::Upload)
+    }
+}
+
+/// Why an operation was refused, stable for API clients and apps
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DenialCode {
+    /// Not a vault-relative path, e.g. it climbs out with `..`
+    InvalidPath,
+    /// A co-owner acting outside the subtrees delegated to them
+    OutsideDelegation,
+    /// No grant of the client ever covered the path
+    NotGranted,
+    /// Grants covering the path expired or were revoked
+    GrantLapsed,
+    /// Grants covering the path lack the access level the operation needs
+    AccessMissing,
+    /// Downloads and uploads are off in view-only sessions
+    ViewOnly,
+    /// The path is, or holds, something under legal hold
+    LegalHold,
+}
+
+impl DenialCode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DenialCode::InvalidPath => "invalid_path",
+            DenialCode::OutsideDelegation => "outside_delegation",
+            DenialCode::NotGranted => "not_granted",
+            DenialCode::GrantLapsed => "grant_lapsed",
+            DenialCode::AccessMissing => "access_missing",
+            DenialCode::ViewOnly => "view_only",
+            DenialCode::LegalHold => "legal_hold",
+        }
+    }
+}
+
+/// A refused operation: the code to act on and a reason to show
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Denial {
+    pub code: DenialCode,
+    pub operation: Operation,
+    /// Path as asked for; `None` when the check was not tied to one
+    pub path: Option<String>,
+    /// Hold that refused it, for [`DenialCode::LegalHold`]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hold_id: Option<Uuid>,
+    pub reason: String,
+}
+
+impl Denial {
+    fn new(code: DenialCode, operation: Operation, path: Option<&str>) -> Self {
+        let level = format!("{:?}", operation.access_level()).to_lowercase();
+        let reason = match (code, path) {
+            (DenialCode::InvalidPath, _) => format!("Invalid path: {}", path.unwrap_or_default()),
+            (DenialCode::OutsideDelegation, _) => format!("Path outside your delegated area: {}", path.unwrap_or_default()),
+            (DenialCode::NotGranted, Some(path)) => format!("No permission covers {path}"),
+            (DenialCode::NotGranted, None) => "You have no permissions in this vault".to_string(),
+            (DenialCode::GrantLapsed, Some(path)) => format!("Your permission for {path} expired or was revoked"),
+            (DenialCode::GrantLapsed, None) => "Your permissions expired or were revoked".to_string(),
+            (DenialCode::AccessMissing, Some(path)) => format!("Your permission for {path} does not include {level} access"),
+            (DenialCode::AccessMissing, None) => format!("None of your permissions include {level} access"),
+            (DenialCode::ViewOnly, _) => "File transfers are disabled in view-only sessions".to_string(),
+            (DenialCode::LegalHold, Some(path)) => format!("{path} is under legal hold"),
+            (DenialCode::LegalHold, None) => "The vault has files under legal hold".to_string(),
+        };
+        Self { code, operation, path: path.map(str::to_string), hold_id: None, reason }
+    }
+}
+
+impl fmt::Display for Denial {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.reason)
+    }
+}
+
+impl std::error::Error for Denial {}
+
+impl From<Denial> for String {
+    fn from(denial: Denial) -> Self {
+        denial.reason
+    }
+}
+
+/// Where the acting user's rights over the vault come from
+#[derive(Debug, Clone)]
+pub enum Authority {
+    /// The vault is theirs
+    Owner,
+    /// Co-owner of the delegated subtrees
+    Delegate(Vec<OwnerDelegation>),
+    /// Client holding permissions on parts of the vault, lapsed ones included
+    Client(Vec<FilePermission>),
+}
+
+#[derive(Debug, Clone)]
+pub struct PermissionEvaluator {
+    authority: Authority,
+    /// Holds of the vault; lapsed ones are ignored
+    holds: Vec<LegalHold>,
+}
+
+impl PermissionEvaluator {
+    pub fn new(authority: Authority, holds: Vec<LegalHold>) -> Self {
+        Self { authority, holds }
+    }
+
+    /// Whether `operation` on the vault-relative `path` is allowed at `now`. Checked in order:
+    /// the path itself, the user's authority over it, view-only, then legal holds.
+    pub fn check(&self, path: &str, operation: Operation, now: DateTime<Utc>) -> Result<(), Denial> {
+        let deny = |code| Denial::new(code, operation, Some(path));
+        let relative = path.trim_matches('/');
+        if relative.split('/').any(|part| part == ".." || part == ".") || relative.contains('\0') {
+            return Err(deny(DenialCode::InvalidPath));
+        }
+        match &self.authority {
+            Authority::Owner => {}
+            Authority::Delegate(delegations) => {
+                if !delegations.iter().any(|d| d.is_active() && d.covers(relative)) {
+                    return Err(deny(DenialCode::OutsideDelegation));
+                }
+            }
+            Authority::Client(grants) => {
+                let level = operation.access_level();
+                let covering: Vec<_> = grants.iter().filter(|g| within(relative, &g.path)).collect();
+                let active: Vec<_> = covering.iter().filter(|g| g.is_active_at(now)).collect();
+                // Folders above a readable grant can be listed to reach it
+                let leads_to_grant = || {
+                    grants.iter().any(|g| g.is_active_at(now) && g.allows(AccessLevel::Read) && within(&g.path, relative))
+                };
+                if !active.iter().any(|g| g.allows(level.clone())) && !(operation == Operation::Browse && leads_to_grant()) {
+                    let code = if covering.is_empty() {
+                        DenialCode::NotGranted
+                    } else if active.is_empty() {
+                        DenialCode::GrantLapsed
+                    } else {
+                        DenialCode::AccessMissing
+                    };
+                    return Err(deny(code));
+                }
+            }
+        }
+        if operation.is_transfer() && self.is_view_only(now) {
+            return Err(deny(DenialCode::ViewOnly));
+        }
+        if operation == Operation::Delete {
+            if let Some(hold) = legal_hold::blocking(&self.holds, relative, now) {
+                return Err(Denial { hold_id: Some(hold.id), ..deny(DenialCode::LegalHold) });
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether `operation` may be allowed somewhere, for requests that do not say on which path,
+    /// like an app acting on its current selection. Refused when it could not be allowed on
+    /// any path. Holds are not checked: the app acts on files itself, so held paths are
+    /// mounted read-only in its sandbox instead.
+    pub fn check_anywhere(&self, operation: Operation, now: DateTime<Utc>) -> Result<(), Denial> {
+        let deny = |code| Denial::new(code, operation, None);
+        if let Authority::Client(grants) = &self.authority {
+            let active: Vec<_> = grants.iter().filter(|g| g.is_active_at(now)).collect();
+            if !active.iter().any(|g| g.allows(operation.access_level())) {
+                let code = if grants.is_empty() {
+                    DenialCode::NotGranted
+                } else if active.is_empty() {
+                    DenialCode::GrantLapsed
+                } else {
+                    DenialCode::AccessMissing
+                };
+                return Err(deny(code));
+            }
+        }
+        if operation.is_transfer() && self.is_view_only(now) {
+            return Err(deny(DenialCode::ViewOnly));
+        }
+        Ok(())
+    }
+
+    /// One active view-only grant restricts everything, since every granted path is reachable
+    /// in the same session.
+    pub fn is_view_only(&self, now: DateTime<Utc>) -> bool {
+        match &self.authority {
+            Authority::Client(grants) => grants.iter().any(|g| g.view_only && g.is_active_at(now)),
+            Authority::Owner | Authority::Delegate(_) => false,
+        }
+    }
+
+    /// Like view-only, one active grant without input makes the whole session watch-only.
+    pub fn interaction(&self, now: DateTime<Utc>) -> Interaction {
+        match &self.authority {
+            Authority::Client(grants)
+                if grants.iter().any(|g| !g.interaction.allows_input() && g.is_active_at(now)) =>
+            {
+                Interaction::ViewOnly
+            }
+            _ => Interaction::Interactive,
+        }
+    }
+}
+
+/// Whether `inner` is `outer` or beneath it; an empty `outer` is the whole vault.
+fn within(inner: &str, outer: &str) -> bool {
+    let (inner, outer) = (inner.trim_matches('/'), outer.trim_matches('/'));
+    outer.is_empty() || inner == outer || inner.strip_prefix(outer).is_some_and(|rest| rest.starts_with('/'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::UserId;
+    use chrono::Duration;
+
+    fn now() -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn grant(path: &str, access: &[AccessLevel]) -> FilePermission {
+        FilePermission {
+            id: Uuid::new_v4(),
+            owner_id: UserId::new(),
+            client_id: UserId::new(),
+            path: path.to_string(),
+            access: access.to_vec(),
+            granted_at: now() - Duration::days(1),
+            expires_at: None,
+            revoked_at: None,
+            view_only: false,
+            interaction: Interaction::Interactive,
+            group_id: None,
+        }
+    }
+
+    fn hold(path: &str) -> LegalHold {
+        LegalHold::new(UserId::new(), path, "tax records", UserId::new(), None).unwrap()
+    }
+
+    fn client(grants: Vec<FilePermission>) -> PermissionEvaluator {
+        PermissionEvaluator::new(Authority::Client(grants), Vec::new())
+    }
+
+    fn code(result: Result<(), Denial>) -> Option<DenialCode> {
+        result.err().map(|denial| denial.code)
+    }
+
+    const ALL: [Operation; 6] = [
+        Operation::Browse,
+        Operation::Read,
+        Operation::Download,
+        Operation::Write,
+        Operation::Upload,
+        Operation::Delete,
+    ];
+
+    #[test]
+    fn test_owner_may_do_anything_outside_holds() {
+        let evaluator = PermissionEvaluator::new(Authority::Owner, vec![hold("taxes/2024")]);
+        for operation in ALL {
+            assert_eq!(code(evaluator.check("photos/beach.jpg", operation, now())), None, "{operation:?}");
+        }
+        for operation in [Operation::Browse, Operation::Read, Operation::Write, Operation::Upload] {
+            assert_eq!(code(evaluator.check("", operation, now())), None, "{operation:?}");
+        }
+        assert_eq!(code(evaluator.check("taxes/2024/return.pdf", Operation::Read, now())), None);
+        assert_eq!(code(evaluator.check("taxes/2024/new.pdf", Operation::Write, now())), None);
+    }
+
+    #[test]
+    fn test_holds_refuse_removing_what_they_protect() {
+        let held = hold("taxes/2024");
+        let evaluator = PermissionEvaluator::new(Authority::Owner, vec![held.clone()]);
+        let denial = evaluator.check("/taxes/2024/return.pdf", Operation::Delete, now()).unwrap_err();
+        assert_eq!(denial.code, DenialCode::LegalHold);
+        assert_eq!(denial.hold_id, Some(held.id));
+        assert_eq!(denial.to_string(), "/taxes/2024/return.pdf is under legal hold");
+        // Removing a folder that holds it removes the held files too
+        assert_eq!(code(evaluator.check("taxes", Operation::Delete, now())), Some(DenialCode::LegalHold));
+        assert_eq!(code(evaluator.check("taxes/2023", Operation::Delete, now())), None);
+
+        let mut released = hold("taxes/2024");
+        released.released_at = Some(now());
+        let evaluator = PermissionEvaluator::new(Authority::Owner, vec![released]);
+        assert_eq!(code(evaluator.check("taxes/2024", Operation::Delete, now())), None);
+    }
+
+    #[test]
+    fn test_delegates_are_confined_to_their_subtrees() {
+        let delegation = OwnerDelegation::new(UserId::new(), UserId::new(), "family-photos", UserId::new()).unwrap();
+        let mut revoked = OwnerDelegation::new(UserId::new(), UserId::new(), "taxes", UserId::new()).unwrap();
+        revoked.revoked_at = Some(now());
+        let evaluator = PermissionEvaluator::new(Authority::Delegate(vec![delegation, revoked]), vec![hold("family-photos/2020")]);
+        for operation in ALL {
+            assert_eq!(code(evaluator.check("family-photos/2024/a.jpg", operation, now())), None);
+            let outside = evaluator.check("family-photos-old/a.jpg", operation, now()).unwrap_err();
+            assert_eq!(outside.code, DenialCode::OutsideDelegation);
+            assert_eq!(outside.reason, "Path outside your delegated area: family-photos-old/a.jpg");
+            assert_eq!(code(evaluator.check("taxes/2024", operation, now())), Some(DenialCode::OutsideDelegation));
+            assert_eq!(code(evaluator.check("", operation, now())), Some(DenialCode::OutsideDelegation));
+        }
+        assert_eq!(code(evaluator.check("family-photos/2020", Operation::Delete, now())), Some(DenialCode::LegalHold));
+        assert!(!evaluator.is_view_only(now()));
+    }
+
+    #[test]
+    fn test_client_operations_need_the_matching_access_level() {
+        let evaluator = client(vec![grant("docs", &[AccessLevel::Read]), grant("shared", &[AccessLevel::Read, AccessLevel::Write, AccessLevel::Delete])]);
+        for operation in [Operation::Browse, Operation::Read, Operation::Download] {
+            assert_eq!(code(evaluator.check("docs/a.pdf", operation, now())), None);
+        }
+        for operation in [Operation::Write, Operation::Upload, Operation::Delete] {
+            let denial = evaluator.check("docs/a.pdf", operation, now()).unwrap_err();
+            assert_eq!(denial.code, DenialCode::AccessMissing);
+            assert!(denial.reason.contains(&format!("{:?}", operation.access_level()).to_lowercase()));
+            assert_eq!(code(evaluator.check("shared/a.pdf", operation, now())), None);
+        }
+        for operation in ALL.into_iter().filter(|o| *o != Operation::Browse) {
+            assert_eq!(code(evaluator.check("docs-old/a.pdf", operation, now())), Some(DenialCode::NotGranted));
+            assert_eq!(code(evaluator.check("", operation, now())), Some(DenialCode::NotGranted));
+        }
+    }
+
+    #[test]
+    fn test_clients_browse_only_towards_readable_grants() {
+        let evaluator = client(vec![grant("projects/acme/specs", &[AccessLevel::Read]), grant("inbox", &[AccessLevel::Write])]);
+        assert_eq!(code(evaluator.check("", Operation::Browse, now())), None);
+        assert_eq!(code(evaluator.check("projects/acme", Operation::Browse, now())), None);
+        assert_eq!(code(evaluator.check("projects/acme", Operation::Read, now())), Some(DenialCode::NotGranted));
+        assert_eq!(code(evaluator.check("projects/other", Operation::Browse, now())), Some(DenialCode::NotGranted));
+        assert_eq!(code(evaluator.check("inbox", Operation::Browse, now())), Some(DenialCode::AccessMissing));
+    }
+
+    #[test]
+    fn test_lapsed_client_grants_are_reported_as_such() {
+        let mut expired = grant("docs", &[AccessLevel::Read]);
+        expired.expires_at = Some(now() - Duration::hours(1));
+        let mut revoked = grant("photos", &[AccessLevel::Read]);
+        revoked.revoked_at = Some(now());
+        let evaluator = client(vec![expired.clone(), revoked]);
+        assert_eq!(code(evaluator.check("docs/a.pdf", Operation::Read, now())), Some(DenialCode::GrantLapsed));
+        assert_eq!(code(evaluator.check("photos", Operation::Read, now())), Some(DenialCode::GrantLapsed));
+        assert_eq!(code(evaluator.check("", Operation::Browse, now())), Some(DenialCode::NotGranted));
+        // Checked at the given time, not when the evaluator was built
+        assert_eq!(code(evaluator.check("docs", Operation::Read, now() - Duration::hours(2))), None);
+
+        // An active grant wins over a lapsed one on the same path
+        let evaluator = client(vec![expired, grant("docs", &[AccessLevel::Read])]);
+        assert_eq!(code(evaluator.check("docs", Operation::Read, now())), None);
+    }
+
+    #[test]
+    fn test_view_only_grants_refuse_transfers_everywhere() {
+        let mut watched = grant("films", &[AccessLevel::Read]);
+        watched.view_only = true;
+        let evaluator = client(vec![watched, grant("docs", &[AccessLevel::Read, AccessLevel::Write])]);
+        assert!(evaluator.is_view_only(now()));
+        assert_eq!(code(evaluator.check("films/a.mkv", Operation::Read, now())), None);
+        assert_eq!(code(evaluator.check("docs/a.pdf", Operation::Write, now())), None);
+        for operation in [Operation::Download, Operation::Upload] {
+            assert_eq!(code(evaluator.check("docs/a.pdf", operation, now())), Some(DenialCode::ViewOnly));
+            assert_eq!(code(evaluator.check_anywhere(operation, now())), Some(DenialCode::ViewOnly));
+        }
+        // Missing access is reported before view-only
+        assert_eq!(code(evaluator.check("films/a.mkv", Operation::Upload, now())), Some(DenialCode::AccessMissing));
+    }
+
+    #[test]
+    fn test_one_grant_without_input_makes_the_session_watch_only() {
+        let mut watched = grant("films", &[AccessLevel::Read]);
+        watched.interaction = Interaction::ViewOnly;
+        let docs = grant("docs", &[AccessLevel::Read]);
+        assert_eq!(client(vec![docs.clone()]).interaction(now()), Interaction::Interactive);
+        assert_eq!(client(vec![watched.clone(), docs]).interaction(now()), Interaction::ViewOnly);
+        watched.revoked_at = Some(now());
+        assert_eq!(client(vec![watched]).interaction(now()), Interaction::Interactive);
+        assert_eq!(PermissionEvaluator::new(Authority::Owner, Vec::new()).interaction(now()), Interaction::Interactive);
+    }
+
+    #[test]
+    fn test_rejects_paths_escaping_the_vault() {
+        for authority in [Authority::Owner, Authority::Client(vec![grant("", &[AccessLevel::Read])])] {
+            let evaluator = PermissionEvaluator::new(authority, Vec::new());
+            for path in ["../other-vault", "docs/../../etc", "docs/./a", "a\0b"] {
+                assert_eq!(code(evaluator.check(path, Operation::Read, now())), Some(DenialCode::InvalidPath), "{path}");
+            }
+            assert_eq!(code(evaluator.check("docs/a..b", Operation::Read, now())), None);
+        }
+    }
+
+    #[test]
+    fn test_check_anywhere() {
+        let evaluator = PermissionEvaluator::new(Authority::Owner, Vec::new());
+        for operation in ALL {
+            assert_eq!(code(evaluator.check_anywhere(operation, now())), None);
+        }
+        let evaluator = PermissionEvaluator::new(Authority::Owner, vec![hold("taxes")]);
+        assert_eq!(code(evaluator.check_anywhere(Operation::Delete, now())), None);
+
+        let evaluator = client(vec![grant("docs", &[AccessLevel::Read])]);
+        assert_eq!(code(evaluator.check_anywhere(Operation::Download, now())), None);
+        assert_eq!(code(evaluator.check_anywhere(Operation::Delete, now())), Some(DenialCode::AccessMissing));
+        assert_eq!(code(client(Vec::new()).check_anywhere(Operation::Read, now())), Some(DenialCode::NotGranted));
+    }
+
+    #[test]
+    fn test_denial_codes_are_stable() {
+        let denial = Denial::new(DenialCode::GrantLapsed, Operation::Read, Some("docs"));
+        let json = serde_json::to_value(&denial).unwrap();
+        assert_eq!(json["code"], "grant_lapsed");
+        assert_eq!(json["operation"], "read");
+        assert_eq!(json["reason"], "Your permission for docs expired or was revoked");
+        assert!(json.get("hold_id").is_none());
+        for code in [
+            DenialCode::InvalidPath,
+            DenialCode::OutsideDelegation,
+            DenialCode::NotGranted,
+            DenialCode::GrantLapsed,
+            DenialCode::AccessMissing,
+            DenialCode::ViewOnly,
+            DenialCode::LegalHold,
+        ] {
+            assert_eq!(serde_json::to_value(code).unwrap(), code.as_str());
+        }
+    }
+}
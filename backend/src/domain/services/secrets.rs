@@ -0,0 +1,37 @@
+//! Handling of the random secrets the platform hands out: links, API tokens, session keys.
+
+use sha2::{Digest, Sha256};
+
+/// Random secret of 64 hex characters
+pub fn generate() -> String {
+    format!("{}{}", uuid::Uuid::new_v4().simple(), uuid::Uuid::new_v4().simple())
+}
+
+/// What a secret is stored and looked up by, so the database never holds one in the clear
+pub fn hash_secret(secret: &str) -> String {
+    format!("{:x}", Sha256::digest(secret.as_bytes()))
+}
+
+/// Whether `given` is `expected`, taking as long whatever bytes differ, so a caller cannot
+/// guess a secret one byte at a time from response times.
+pub fn constant_time_eq(given: &str, expected: &str) -> bool {
+    let (given, expected) = (given.as_bytes(), expected.as_bytes());
+    if given.len() != expected.len() {
+        return false;
+    }
+    given.iter().zip(expected).fold(0u8, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constant_time_eq() {
+        let secret = generate();
+        assert_eq!(secret.len(), 64);
+        assert!(constant_time_eq(&secret, &secret.clone()));
+        assert!(!constant_time_eq(&secret, &generate()));
+        assert!(!constant_time_eq(&secret[..63], &secret));
+    }
+}
@@ -0,0 +1,52 @@
+/// Bounds on what an app may save for one user, so the per-app store stays small.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AppStateLimits {
+    pub max_key_bytes: usize,
+    /// Size of one value, as JSON text
+    pub max_value_bytes: usize,
+    /// Keys and values of all entries of the user and app together
+    pub max_total_bytes: usize,
+}
+
+impl Default for AppStateLimits {
+    fn default() -> Self {
+        Self {
+            max_key_bytes: 128,
+            max_value_bytes: 64 * 1024,
+            max_total_bytes: 1024 * 1024,
+        }
+    }
+}
+
+impl AppStateLimits {
+    /// Whether `key` may hold a value of `value_bytes` when the user's other entries for the
+    /// app already take `others_bytes`.
+    pub fn check(&self, key: &str, value_bytes: usize, others_bytes: usize) -> Result<(), String> {
+        if key.is_empty() || key.len() > self.max_key_bytes {
+            return Err(format!("Key must be 1 to {} bytes", self.max_key_bytes));
+        }
+        if value_bytes > self.max_value_bytes {
+            return Err(format!("Value is larger than {} bytes", self.max_value_bytes));
+        }
+        if others_bytes + key.len() + value_bytes > self.max_total_bytes {
+            return Err(format!("Saved state would exceed {} bytes", self.max_total_bytes));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_enforces_each_limit() {
+        let limits = AppStateLimits { max_key_bytes: 8, max_value_bytes: 100, max_total_bytes: 150 };
+        assert!(limits.check("path", 100, 0).is_ok());
+        assert!(limits.check("", 1, 0).is_err());
+        assert!(limits.check("much-too-long", 1, 0).is_err());
+        assert!(limits.check("path", 101, 0).is_err());
+        assert!(limits.check("path", 100, 46).is_ok());
+        assert!(limits.check("path", 100, 47).is_err());
+    }
+}
@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+
+/// Bandwidth limits an owner sets for one client's sessions in their vault, for owners on
+/// metered connections. Unset fields do not limit anything.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BandwidthCaps {
+    /// Ceiling on the encoder bitrate of each session, in bits per second
+    pub session_max_bitrate: Option<u32>,
+    /// Bytes of video the client's sessions may be sent per calendar month (UTC)
+    pub monthly_bytes: Option<u64>,
+    /// Bitrate sessions drop to once the month's bytes are used up; without it the overrun is
+    /// only reported
+    pub throttle_bitrate: Option<u32>,
+}
+
+impl BandwidthCaps {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.session_max_bitrate == Some(0) || self.throttle_bitrate == Some(0) {
+            return Err("Bitrates must be above 0".to_string());
+        }
+        if self.monthly_bytes == Some(0) {
+            return Err("Monthly cap must be above 0 bytes".to_string());
+        }
+        if self.throttle_bitrate.is_some() && self.monthly_bytes.is_none() {
+            return Err("A throttle bitrate needs a monthly cap".to_string());
+        }
+        Ok(())
+    }
+
+    /// Whether `used_bytes` this month reached the monthly cap
+    pub fn is_over(&self, used_bytes: u64) -> bool {
+        self.monthly_bytes.is_some_and(|cap| used_bytes >= cap)
+    }
+
+    /// Highest bitrate a session may stream at after `used_bytes` this month, if limited
+    pub fn bitrate_ceiling(&self, used_bytes: u64) -> Option<u32> {
+        let throttle = self.throttle_bitrate.filter(|_| self.is_over(used_bytes));
+        match (self.session_max_bitrate, throttle) {
+            (Some(max), Some(throttle)) => Some(max.min(throttle)),
+            (max, throttle) => max.or(throttle),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ceiling_drops_to_throttle_once_over_cap() {
+        let caps = BandwidthCaps {
+            session_max_bitrate: Some(2_000_000),
+            monthly_bytes: Some(1_000),
+            throttle_bitrate: Some(300_000),
+        };
+        assert!(caps.validate().is_ok());
+        assert_eq!(caps.bitrate_ceiling(999), Some(2_000_000));
+        assert_eq!(caps.bitrate_ceiling(1_000), Some(300_000));
+
+        let report_only = BandwidthCaps { throttle_bitrate: None, ..caps };
+        assert!(report_only.is_over(5_000));
+        assert_eq!(report_only.bitrate_ceiling(5_000), Some(2_000_000));
+        assert_eq!(BandwidthCaps::default().bitrate_ceiling(u64::MAX), None);
+    }
+
+    #[test]
+    fn test_validate_rejects_zero_and_throttle_without_cap() {
+        assert!(BandwidthCaps { session_max_bitrate: Some(0), ..Default::default() }.validate().is_err());
+        assert!(BandwidthCaps { throttle_bitrate: Some(100_000), ..Default::default() }.validate().is_err());
+        assert!(BandwidthCaps::default().validate().is_ok());
+    }
+}
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+/// Whether a client may drive the apps they launch on a share, or only watch them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Interaction {
+    #[default]
+    Interactive,
+    /// The stream is shown but pointer and keyboard input is dropped
+    ViewOnly,
+}
+
+impl Interaction {
+    pub fn as_db_str(&self) -> &'static str {
+        match self {
+            Interaction::Interactive => "interactive",
+            Interaction::ViewOnly => "view_only",
+        }
+    }
+
+    pub fn from_db_str(s: &str) -> Result<Self, String> {
+        match s {
+            "interactive" => Ok(Interaction::Interactive),
+            "view_only" => Ok(Interaction::ViewOnly),
+            other => Err(format!("Unknown interaction level '{other}'")),
+        }
+    }
+
+    pub fn allows_input(&self) -> bool {
+        *self == Interaction::Interactive
+    }
+}
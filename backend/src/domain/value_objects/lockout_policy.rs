@@ -0,0 +1,68 @@
+/// Throttling applied after failed logins, counted per user and per IP within a window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LockoutPolicy {
+    /// Failures counted within this window (seconds) before the counter resets.
+    pub window_secs: u64,
+    /// Failures allowed before each further attempt is delayed.
+    pub backoff_after: u32,
+    pub max_backoff_secs: u64,
+    /// Failures after which the subject is locked out.
+    pub lockout_after: u32,
+    pub lockout_secs: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoginPenalty {
+    /// Delay before the next attempt is accepted.
+    Backoff(u64),
+    /// Temporary lockout; the account owner is notified.
+    Lockout(u64),
+}
+
+impl Default for LockoutPolicy {
+    fn default() -> Self {
+        Self {
+            window_secs: 15 * 60,
+            backoff_after: 3,
+            max_backoff_secs: 5 * 60,
+            lockout_after: 10,
+            lockout_secs: 15 * 60,
+        }
+    }
+}
+
+impl LockoutPolicy {
+    /// Penalty after `failures` consecutive failures: exponential backoff (1s, 2s, 4s, ...)
+    /// once past `backoff_after`, then a lockout at `lockout_after`.
+    pub fn penalty(&self, failures: u32) -> Option<LoginPenalty> {
+        if failures >= self.lockout_after {
+            return Some(LoginPenalty::Lockout(self.lockout_secs));
+        }
+        if failures <= self.backoff_after {
+            return None;
+        }
+        let exponent = (failures - self.backoff_after - 1).min(32);
+        Some(LoginPenalty::Backoff((1u64 << exponent).min(self.max_backoff_secs)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_doubles_then_locks_out() {
+        let policy = LockoutPolicy::default();
+        assert_eq!(policy.penalty(3), None);
+        assert_eq!(policy.penalty(4), Some(LoginPenalty::Backoff(1)));
+        assert_eq!(policy.penalty(5), Some(LoginPenalty::Backoff(2)));
+        assert_eq!(policy.penalty(9), Some(LoginPenalty::Backoff(32)));
+        assert_eq!(policy.penalty(10), Some(LoginPenalty::Lockout(900)));
+    }
+
+    #[test]
+    fn test_backoff_is_capped() {
+        let policy = LockoutPolicy { lockout_after: 100, max_backoff_secs: 60, ..Default::default() };
+        assert_eq!(policy.penalty(50), Some(LoginPenalty::Backoff(60)));
+    }
+}
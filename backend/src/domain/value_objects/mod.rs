@@ -3,9 +3,20 @@ pub mod email;
 pub mod display_name;
 pub mod user_role;
 pub mod user_status;
+pub mod lockout_policy;
+pub mod ip_range;
+pub mod resource_class;
+pub mod app_state_limits;
+pub mod bandwidth_caps;
+pub mod interaction;
 
 pub use user_id::UserId;
 pub use email::Email;
 pub use display_name::DisplayName;
 pub use user_role::UserRole;
 pub use user_status::UserStatus;
+pub use ip_range::IpRange;
+pub use resource_class::{ResourceClass, Resources};
+pub use app_state_limits::AppStateLimits;
+pub use bandwidth_caps::BandwidthCaps;
+pub use interaction::Interaction;
@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+
+/// CPU and memory, as reserved for sessions or offered by a host.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Resources {
+    /// Thousandths of a core
+    pub cpu_millis: u32,
+    pub memory_mb: u32,
+}
+
+impl Resources {
+    pub fn new(cpu_millis: u32, memory_mb: u32) -> Self {
+        Self { cpu_millis, memory_mb }
+    }
+
+    pub fn plus(&self, other: Resources) -> Resources {
+        Resources::new(
+            self.cpu_millis.saturating_add(other.cpu_millis),
+            self.memory_mb.saturating_add(other.memory_mb),
+        )
+    }
+}
+
+/// How heavy an app is, declared with `resource_class` in its manifest. The class sets the
+/// cgroup limits of the app's sessions and what the scheduler reserves for each of them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResourceClass {
+    #[default]
+    Small,
+    Medium,
+    Large,
+}
+
+impl ResourceClass {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ResourceClass::Small => "small",
+            ResourceClass::Medium => "medium",
+            ResourceClass::Large => "large",
+        }
+    }
+
+    pub fn resources(&self) -> Resources {
+        match self {
+            ResourceClass::Small => Resources::new(500, 512),
+            ResourceClass::Medium => Resources::new(1000, 1024),
+            ResourceClass::Large => Resources::new(2000, 4096),
+        }
+    }
+
+    /// Processes and threads an app of this class may run
+    pub fn max_pids(&self) -> u32 {
+        match self {
+            ResourceClass::Small => 100,
+            ResourceClass::Medium => 200,
+            ResourceClass::Large => 400,
+        }
+    }
+
+    /// Heavy apps are spread across hosts so they do not starve each other
+    pub fn is_heavy(&self) -> bool {
+        matches!(self, ResourceClass::Large)
+    }
+}
@@ -4,6 +4,9 @@ use serde::{Deserialize, Serialize};
 pub enum UserStatus {
     Active,
     Suspended,
+    /// Waiting out the grace period before the account's data is purged; cannot log in
+    PendingDeletion,
+    /// Purged: only the anonymized row is left
     Deleted,
 }
 
@@ -13,7 +16,13 @@ impl UserStatus {
         match self {
             UserStatus::Active => "active",
             UserStatus::Suspended => "suspended",
+            UserStatus::PendingDeletion => "pending_deletion",
             UserStatus::Deleted => "deleted",
         }
     }
+
+    /// Accounts being deleted, or already purged, can no longer log in.
+    pub fn is_deleting(&self) -> bool {
+        matches!(self, UserStatus::PendingDeletion | UserStatus::Deleted)
+    }
 }
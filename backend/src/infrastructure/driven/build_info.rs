@@ -0,0 +1,22 @@
+//! What this binary was built from, for the startup log and `/api/admin/info`.
+
+/// Crate version from Cargo.toml
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Commit the binary was built from, when the build set `GIT_HASH`
+pub const GIT_HASH: Option<&str> = option_env!("GIT_HASH");
+
+/// Optional Cargo features compiled in
+pub fn enabled_features() -> Vec<&'static str> {
+    let mut features = Vec::new();
+    if cfg!(feature = "geoip") {
+        features.push("geoip");
+    }
+    if cfg!(feature = "email") {
+        features.push("email");
+    }
+    if cfg!(feature = "oidc") {
+        features.push("oidc");
+    }
+    features
+}
@@ -0,0 +1,56 @@
+//! Reads and writes of the disk holding `STORAGE_PATH`, from the kernel's block device
+//! counters, so disk work can be deferred while the disk sleeps.
+
+use std::os::unix::fs::MetadataExt;
+
+/// Completed reads plus completed writes in a `/sys/dev/block/*/stat` line
+fn completed_io(stat: &str) -> Option<u64> {
+    let fields: Vec<u64> = stat.split_whitespace().map(|field| field.parse().ok()).collect::<Option<_>>()?;
+    Some(fields.first()? + fields.get(4)?)
+}
+
+pub struct DiskActivity {
+    /// The stat file of the device backing the storage path; `None` if it could not be found
+    stat_path: Option<String>,
+    last_count: Option<u64>,
+}
+
+impl DiskActivity {
+    pub fn for_path(storage_path: &str) -> Self {
+        let stat_path = std::fs::metadata(storage_path).ok().map(|metadata| {
+            let dev = metadata.dev();
+            let major = ((dev >> 8) & 0xfff) | ((dev >> 32) & !0xfff);
+            let minor = (dev & 0xff) | ((dev >> 12) & !0xff);
+            format!("/sys/dev/block/{major}:{minor}/stat")
+        });
+        let stat_path = stat_path.filter(|path| std::path::Path::new(path).exists());
+        if stat_path.is_none() {
+            tracing::warn!("No block device statistics for {}, disk activity is unknown", storage_path);
+        }
+        Self { stat_path, last_count: None }
+    }
+
+    /// Whether the disk read or wrote anything since the previous call. The first call only
+    /// takes the counters, so it reports none.
+    pub fn poll(&mut self) -> bool {
+        let Some(count) = self.stat_path.as_ref().and_then(|path| completed_io(&std::fs::read_to_string(path).ok()?)) else {
+            return false;
+        };
+        let changed = self.last_count.is_some_and(|last| last != count);
+        self.last_count = Some(count);
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_completed_io() {
+        let stat = "   12034      310   982344    5120     8871     2290   310456    19800        0    14020    24920";
+        assert_eq!(completed_io(stat), Some(12034 + 8871));
+        assert_eq!(completed_io("12 3"), None);
+        assert_eq!(completed_io(""), None);
+    }
+}
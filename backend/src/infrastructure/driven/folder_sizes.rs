@@ -0,0 +1,107 @@
+//! Total size of vault folders, for listings that show directories with their contents' size.
+//! Walking a large tree is slow, so results are cached per host directory. Every change made
+//! through [`super::storage::LocalVaultStorage`] drops the cached sizes it affects; files that
+//! apps write inside their sandbox are only caught up with once an entry goes stale.
+
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::infrastructure::driven::config::Config;
+use crate::infrastructure::driven::storage::disk_usage;
+
+/// How long a size is served from the cache when `FOLDER_SIZE_CACHE_SECS` is unset
+const DEFAULT_TTL: Duration = Duration::from_secs(300);
+
+#[derive(Default)]
+struct Sizes {
+    /// Size of each walked directory and when it was measured
+    measured: HashMap<PathBuf, (u64, Instant)>,
+    /// Bumped by every invalidation, so a walk that raced a change is not cached
+    generation: u64,
+}
+
+pub struct FolderSizeCache {
+    sizes: Mutex<Sizes>,
+    ttl: Duration,
+}
+
+impl Default for FolderSizeCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_TTL)
+    }
+}
+
+impl FolderSizeCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self { sizes: Mutex::default(), ttl }
+    }
+
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(config.parse("FOLDER_SIZE_CACHE_SECS").map(Duration::from_secs).unwrap_or(DEFAULT_TTL))
+    }
+
+    /// Bytes held under the host directory `dir`, links not followed. Walks the tree on a
+    /// blocking thread unless a fresh size is cached.
+    pub async fn size_of(&self, dir: &Path) -> io::Result<u64> {
+        let generation = {
+            let sizes = self.lock();
+            match sizes.measured.get(dir) {
+                Some(&(size, measured)) if measured.elapsed() < self.ttl => return Ok(size),
+                _ => sizes.generation,
+            }
+        };
+        let walked = dir.to_path_buf();
+        let size = tokio::task::spawn_blocking(move || disk_usage(&walked)).await.map_err(io::Error::other)??;
+        let mut sizes = self.lock();
+        if sizes.generation == generation {
+            let ttl = self.ttl;
+            sizes.measured.retain(|_, (_, measured)| measured.elapsed() < ttl);
+            sizes.measured.insert(dir.to_path_buf(), (size, Instant::now()));
+        }
+        Ok(size)
+    }
+
+    /// Forget sizes that a change at the host path `path` affects: the folders holding it and
+    /// everything under it.
+    pub fn invalidate(&self, path: &Path) {
+        let mut sizes = self.lock();
+        sizes.generation += 1;
+        sizes.measured.retain(|dir, _| !path.starts_with(dir) && !dir.starts_with(path));
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Sizes> {
+        self.sizes.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[tokio::test]
+    async fn test_caches_sizes_until_a_change_inside() {
+        let root = std::env::temp_dir().join(format!("folder-sizes-test-{}", std::process::id()));
+        fs::create_dir_all(root.join("photos/2026")).unwrap();
+        fs::create_dir_all(root.join("music")).unwrap();
+        fs::write(root.join("photos/2026/a.jpg"), [0u8; 100]).unwrap();
+        fs::write(root.join("music/b.flac"), [0u8; 40]).unwrap();
+
+        let cache = FolderSizeCache::default();
+        assert_eq!(cache.size_of(&root.join("photos")).await.unwrap(), 100);
+        assert_eq!(cache.size_of(&root.join("music")).await.unwrap(), 40);
+
+        fs::write(root.join("photos/2026/c.jpg"), [0u8; 20]).unwrap();
+        fs::write(root.join("music/d.flac"), [0u8; 20]).unwrap();
+        assert_eq!(cache.size_of(&root.join("photos")).await.unwrap(), 100);
+        cache.invalidate(&root.join("photos/2026/c.jpg"));
+        assert_eq!(cache.size_of(&root.join("photos")).await.unwrap(), 120);
+        // Sizes outside the changed path are kept
+        assert_eq!(cache.size_of(&root.join("music")).await.unwrap(), 40);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,49 @@
+use std::net::IpAddr;
+use std::sync::Arc;
+use crate::application::ports::GeoIpResolver;
+
+/// Country lookups from a MaxMind GeoLite2/GeoIP2 Country database.
+#[cfg(feature = "geoip")]
+pub struct MaxMindGeoIp {
+    reader: maxminddb::Reader<Vec<u8>>,
+}
+
+#[cfg(feature = "geoip")]
+impl MaxMindGeoIp {
+    pub fn open(path: &str) -> Result<Self, String> {
+        let reader = maxminddb::Reader::open_readfile(path)
+            .map_err(|e| format!("Failed to open GeoIP database {}: {}", path, e))?;
+        Ok(Self { reader })
+    }
+}
+
+#[cfg(feature = "geoip")]
+impl GeoIpResolver for MaxMindGeoIp {
+    fn country(&self, ip: IpAddr) -> Option<String> {
+        let record: maxminddb::geoip2::Country = self.reader.lookup(ip).ok()?;
+        record.country?.iso_code.map(str::to_string)
+    }
+}
+
+/// Resolver for the database at `GEOIP_DB_PATH`. Without one, country rules never match.
+pub fn from_env() -> Option<Arc<dyn GeoIpResolver>> {
+    let path = std::env::var("GEOIP_DB_PATH").ok()?;
+    open(&path)
+}
+
+#[cfg(feature = "geoip")]
+fn open(path: &str) -> Option<Arc<dyn GeoIpResolver>> {
+    match MaxMindGeoIp::open(path) {
+        Ok(resolver) => Some(Arc::new(resolver)),
+        Err(e) => {
+            tracing::warn!("{}; country rules are disabled", e);
+            None
+        }
+    }
+}
+
+#[cfg(not(feature = "geoip"))]
+fn open(path: &str) -> Option<Arc<dyn GeoIpResolver>> {
+    tracing::warn!("GEOIP_DB_PATH={} ignored: built without the `geoip` feature", path);
+    None
+}
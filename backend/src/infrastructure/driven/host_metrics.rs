@@ -0,0 +1,84 @@
+//! What this host offers to sessions and what it is using, for the scheduler. Capacity comes
+//! from `HOST_CPU_MILLIS`/`HOST_MEMORY_MB`, else from the machine; usage is read from /proc.
+
+use chrono::Utc;
+use crate::domain::entities::placement::HostStatus;
+use crate::domain::value_objects::Resources;
+use super::sandbox::XvfbManager;
+
+/// The value of a `/proc/meminfo` field in MB
+fn meminfo_mb(meminfo: &str, field: &str) -> Option<u32> {
+    let line = meminfo.lines().find(|line| line.starts_with(field) && line[field.len()..].starts_with(':'))?;
+    let kb: u64 = line[field.len() + 1..].trim().trim_end_matches("kB").trim().parse().ok()?;
+    Some((kb / 1024) as u32)
+}
+
+/// The one-minute load average, in thousandths of a core
+fn loadavg_millis(loadavg: &str) -> Option<u32> {
+    let load: f32 = loadavg.split_whitespace().next()?.parse().ok()?;
+    Some((load * 1000.0) as u32)
+}
+
+fn measure_used() -> Resources {
+    let meminfo = std::fs::read_to_string("/proc/meminfo").unwrap_or_default();
+    let memory_mb = match (meminfo_mb(&meminfo, "MemTotal"), meminfo_mb(&meminfo, "MemAvailable")) {
+        (Some(total), Some(available)) => total.saturating_sub(available),
+        _ => 0,
+    };
+    let loadavg = std::fs::read_to_string("/proc/loadavg").unwrap_or_default();
+    Resources::new(loadavg_millis(&loadavg).unwrap_or(0), memory_mb)
+}
+
+pub struct HostMetrics {
+    instance_id: String,
+    launch_url: String,
+    capacity: Resources,
+}
+
+impl HostMetrics {
+    pub fn from_env(instance_id: String, api_base_url: &str) -> Self {
+        let env_u32 = |name: &str| std::env::var(name).ok().and_then(|s| s.parse::<u32>().ok());
+        let cpu_millis = env_u32("HOST_CPU_MILLIS").unwrap_or_else(|| {
+            std::thread::available_parallelism().map(|n| n.get() as u32).unwrap_or(1) * 1000
+        });
+        let memory_mb = env_u32("HOST_MEMORY_MB").unwrap_or_else(|| {
+            let meminfo = std::fs::read_to_string("/proc/meminfo").unwrap_or_default();
+            meminfo_mb(&meminfo, "MemTotal").unwrap_or(1024)
+        });
+        Self {
+            instance_id,
+            launch_url: format!("{}/api/applications/launch", api_base_url.trim_end_matches('/')),
+            capacity: Resources::new(cpu_millis, memory_mb),
+        }
+    }
+
+    /// `draining` when the instance is in maintenance, so the scheduler sends launches elsewhere.
+    pub async fn status(&self, xvfb_manager: &XvfbManager, draining: bool) -> HostStatus {
+        let (reserved, heavy_sessions) = xvfb_manager.reserved().await;
+        HostStatus {
+            instance_id: self.instance_id.clone(),
+            launch_url: self.launch_url.clone(),
+            capacity: self.capacity,
+            reserved,
+            heavy_sessions,
+            used: measure_used(),
+            draining,
+            updated_at: Utc::now(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_proc_files() {
+        let meminfo = "MemTotal:       16384000 kB\nMemFree:         1024000 kB\nMemAvailable:    8192000 kB\n";
+        assert_eq!(meminfo_mb(meminfo, "MemTotal"), Some(16000));
+        assert_eq!(meminfo_mb(meminfo, "MemAvailable"), Some(8000));
+        assert_eq!(meminfo_mb(meminfo, "Mem"), None);
+        assert_eq!(loadavg_millis("1.50 0.80 0.40 2/345 6789\n"), Some(1500));
+        assert_eq!(loadavg_millis(""), None);
+    }
+}
@@ -0,0 +1,303 @@
+//! STUN/TURN servers handed to peer connections. Servers come from the JSON file named by
+//! `ICE_SERVERS`, are probed periodically, and each session gets the reachable ones by priority.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::Duration;
+use tokio::net::{TcpStream, UdpSocket};
+use webrtc::ice_transport::ice_server::RTCIceServer;
+
+const STUN_MAGIC_COOKIE: u32 = 0x2112_A442;
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct IceServerConfig {
+    pub urls: Vec<String>,
+    #[serde(default)]
+    pub username: String,
+    /// Defaults to `TURN_CREDENTIAL` for servers with a username
+    pub credential: Option<String>,
+    /// Higher is offered first
+    #[serde(default)]
+    pub priority: i32,
+}
+
+/// Outcome of the last probe of one server URL, as reported by `/health`
+#[derive(Debug, Clone, Serialize)]
+pub struct ServerHealth {
+    pub url: String,
+    pub healthy: bool,
+    pub checked_at: DateTime<Utc>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Transport {
+    Udp,
+    Tcp,
+    /// `turns:`; probed with a plain TCP connect
+    Tls,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct IceUrl {
+    host: String,
+    port: u16,
+    transport: Transport,
+}
+
+/// Parse `stun:host[:port]`, `turn:host[:port][?transport=udp|tcp]` or `turns:...`.
+fn parse_url(url: &str) -> Result<IceUrl> {
+    let (scheme, rest) = url.split_once(':').context("Missing scheme")?;
+    let (address, query) = rest.split_once('?').unwrap_or((rest, ""));
+    let (default_port, mut transport) = match scheme {
+        "stun" | "turn" => (3478, Transport::Udp),
+        "stuns" | "turns" => (5349, Transport::Tls),
+        other => anyhow::bail!("Unknown scheme '{}'", other),
+    };
+    match query {
+        "" | "transport=udp" => {}
+        "transport=tcp" if transport == Transport::Udp => transport = Transport::Tcp,
+        "transport=tcp" => {}
+        other => anyhow::bail!("Unsupported parameters '{}'", other),
+    }
+    // IPv6 literals are bracketed, so the port follows the closing bracket
+    let (host, port) = match address.rsplit_once(':') {
+        Some((host, port)) if !port.contains(']') => {
+            (host, port.parse().with_context(|| format!("Invalid port '{}'", port))?)
+        }
+        _ => (address, default_port),
+    };
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    if host.is_empty() {
+        anyhow::bail!("Missing host");
+    }
+    Ok(IceUrl { host: host.to_string(), port, transport })
+}
+
+fn binding_request(transaction_id: &[u8; 12]) -> [u8; 20] {
+    let mut request = [0u8; 20];
+    request[0..2].copy_from_slice(&0x0001u16.to_be_bytes());
+    request[4..8].copy_from_slice(&STUN_MAGIC_COOKIE.to_be_bytes());
+    request[8..20].copy_from_slice(transaction_id);
+    request
+}
+
+/// A binding success or error response to our request: either proves the server is up.
+fn is_binding_response(response: &[u8], transaction_id: &[u8; 12]) -> bool {
+    response.len() >= 20
+        && matches!(u16::from_be_bytes([response[0], response[1]]), 0x0101 | 0x0111)
+        && response[4..8] == STUN_MAGIC_COOKIE.to_be_bytes()
+        && &response[8..20] == transaction_id
+}
+
+/// UDP servers must answer a STUN binding request; TCP and TLS ones must accept a connection.
+async fn probe(url: &IceUrl) -> Result<()> {
+    let address = tokio::net::lookup_host((url.host.as_str(), url.port))
+        .await?
+        .next()
+        .context("Host has no address")?;
+    match url.transport {
+        Transport::Tcp | Transport::Tls => {
+            tokio::time::timeout(PROBE_TIMEOUT, TcpStream::connect(address))
+                .await
+                .context("Connection timed out")??;
+        }
+        Transport::Udp => {
+            let bind = if address.is_ipv4() { "0.0.0.0:0" } else { "[::]:0" };
+            let socket = UdpSocket::bind(bind).await?;
+            let mut transaction_id = [0u8; 12];
+            transaction_id.copy_from_slice(&uuid::Uuid::new_v4().as_bytes()[..12]);
+            socket.send_to(&binding_request(&transaction_id), address).await?;
+            let mut response = [0u8; 512];
+            let deadline = tokio::time::Instant::now() + PROBE_TIMEOUT;
+            loop {
+                let (len, from) = tokio::time::timeout_at(deadline, socket.recv_from(&mut response))
+                    .await
+                    .context("No STUN response")??;
+                if from == address && is_binding_response(&response[..len], &transaction_id) {
+                    break;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+pub struct IceServers {
+    /// Highest priority first; equal priorities keep their configured order
+    servers: Vec<IceServerConfig>,
+    default_credential: String,
+    per_session: usize,
+    health: RwLock<HashMap<String, ServerHealth>>,
+}
+
+impl IceServers {
+    /// Servers from the `ICE_SERVERS` file, else a public STUN server plus the TURN server
+    /// from `TURN_SERVER`/`TURN_USERNAME`.
+    pub fn from_env(turn_credential: String) -> Result<Self> {
+        let servers = match std::env::var("ICE_SERVERS") {
+            Ok(path) => {
+                let json = std::fs::read_to_string(&path).with_context(|| format!("Cannot read {}", path))?;
+                serde_json::from_str(&json).with_context(|| format!("Invalid JSON in {}", path))?
+            }
+            Err(_) => vec![
+                IceServerConfig {
+                    urls: vec!["stun:stun.l.google.com:19302".to_string()],
+                    username: String::new(),
+                    credential: None,
+                    priority: 0,
+                },
+                IceServerConfig {
+                    urls: vec![std::env::var("TURN_SERVER").unwrap_or_else(|_| "turn:localhost:3478".to_string())],
+                    username: std::env::var("TURN_USERNAME").unwrap_or_else(|_| "sandbox".to_string()),
+                    credential: None,
+                    priority: 0,
+                },
+            ],
+        };
+        let per_session = std::env::var("ICE_SERVERS_PER_SESSION")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(4);
+        Self::new(servers, turn_credential, per_session)
+    }
+
+    fn new(mut servers: Vec<IceServerConfig>, default_credential: String, per_session: usize) -> Result<Self> {
+        if servers.is_empty() {
+            anyhow::bail!("No ICE servers configured");
+        }
+        for url in servers.iter().flat_map(|server| &server.urls) {
+            parse_url(url).with_context(|| format!("ICE server '{}'", url))?;
+        }
+        servers.sort_by_key(|server| std::cmp::Reverse(server.priority));
+        Ok(Self { servers, default_credential, per_session: per_session.max(1), health: RwLock::new(HashMap::new()) })
+    }
+
+    /// A server is usable until a probe finds none of its URLs reachable.
+    fn is_usable(&self, server: &IceServerConfig) -> bool {
+        let health = self.health.read().unwrap_or_else(|e| e.into_inner());
+        server.urls.iter().any(|url| health.get(url).map_or(true, |h| h.healthy))
+    }
+
+    /// The servers for a new peer connection: usable ones by priority, or every server when
+    /// all probes fail, since a probe from here may fail where the client still gets through.
+    pub fn for_session(&self) -> Vec<RTCIceServer> {
+        let usable: Vec<&IceServerConfig> = self.servers.iter().filter(|server| self.is_usable(server)).collect();
+        let chosen = if usable.is_empty() { self.servers.iter().collect() } else { usable };
+        chosen
+            .into_iter()
+            .take(self.per_session)
+            .map(|server| RTCIceServer {
+                urls: server.urls.clone(),
+                username: server.username.clone(),
+                credential: match (&server.credential, server.username.is_empty()) {
+                    (Some(credential), _) => credential.clone(),
+                    (None, false) => self.default_credential.clone(),
+                    (None, true) => String::new(),
+                },
+            })
+            .collect()
+    }
+
+    /// Last probe result of every URL, in priority order; empty before the first check.
+    pub fn health(&self) -> Vec<ServerHealth> {
+        let health = self.health.read().unwrap_or_else(|e| e.into_inner());
+        self.servers
+            .iter()
+            .flat_map(|server| &server.urls)
+            .filter_map(|url| health.get(url).cloned())
+            .collect()
+    }
+
+    pub async fn check_all(&self) {
+        for url in self.servers.iter().flat_map(|server| &server.urls) {
+            let result = match parse_url(url) {
+                Ok(parsed) => probe(&parsed).await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = &result {
+                tracing::warn!("ICE server {} unreachable: {:#}", url, e);
+            }
+            self.health.write().unwrap_or_else(|e| e.into_inner()).insert(
+                url.clone(),
+                ServerHealth {
+                    url: url.clone(),
+                    healthy: result.is_ok(),
+                    checked_at: Utc::now(),
+                    error: result.err().map(|e| format!("{:#}", e)),
+                },
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server(url: &str, priority: i32) -> IceServerConfig {
+        IceServerConfig { urls: vec![url.to_string()], username: String::new(), credential: None, priority }
+    }
+
+    fn mark(servers: &IceServers, url: &str, healthy: bool) {
+        servers.health.write().unwrap().insert(
+            url.to_string(),
+            ServerHealth { url: url.to_string(), healthy, checked_at: Utc::now(), error: None },
+        );
+    }
+
+    #[test]
+    fn test_parse_url() {
+        let url = |host: &str, port, transport| IceUrl { host: host.to_string(), port, transport };
+        assert_eq!(parse_url("stun:stun.example.com").unwrap(), url("stun.example.com", 3478, Transport::Udp));
+        assert_eq!(parse_url("turn:10.0.0.1:3479?transport=tcp").unwrap(), url("10.0.0.1", 3479, Transport::Tcp));
+        assert_eq!(parse_url("turns:turn.example.com").unwrap(), url("turn.example.com", 5349, Transport::Tls));
+        assert_eq!(parse_url("stun:[::1]:3478").unwrap(), url("::1", 3478, Transport::Udp));
+        assert!(parse_url("http://example.com").is_err());
+        assert!(parse_url("turn:example.com:port").is_err());
+    }
+
+    #[test]
+    fn test_binding_response_must_match_transaction() {
+        let id = [7u8; 12];
+        let mut response = binding_request(&id);
+        assert!(!is_binding_response(&response, &id));
+        response[0..2].copy_from_slice(&0x0101u16.to_be_bytes());
+        assert!(is_binding_response(&response, &id));
+        assert!(!is_binding_response(&response, &[8u8; 12]));
+    }
+
+    #[test]
+    fn test_sessions_get_healthy_servers_by_priority() {
+        let servers = IceServers::new(
+            vec![server("stun:backup.example.com", 0), server("turn:main.example.com", 10), server("turn:spare.example.com", 5)],
+            "secret".to_string(),
+            2,
+        )
+        .unwrap();
+        let urls = |list: Vec<RTCIceServer>| list.into_iter().flat_map(|s| s.urls).collect::<Vec<_>>();
+        assert_eq!(urls(servers.for_session()), vec!["turn:main.example.com", "turn:spare.example.com"]);
+
+        mark(&servers, "turn:main.example.com", false);
+        assert_eq!(urls(servers.for_session()), vec!["turn:spare.example.com", "stun:backup.example.com"]);
+
+        for url in ["turn:spare.example.com", "stun:backup.example.com"] {
+            mark(&servers, url, false);
+        }
+        assert_eq!(servers.for_session().len(), 2);
+    }
+
+    #[test]
+    fn test_turn_servers_default_to_shared_credential() {
+        let mut turn = server("turn:turn.example.com", 0);
+        turn.username = "sandbox".to_string();
+        let servers = IceServers::new(vec![turn, server("stun:stun.example.com", 0)], "secret".to_string(), 4).unwrap();
+        let offered = servers.for_session();
+        assert_eq!(offered[0].credential, "secret");
+        assert_eq!(offered[1].credential, "");
+    }
+}
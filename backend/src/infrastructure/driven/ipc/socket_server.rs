@@ -1,24 +1,211 @@
 use anyhow::{Context, Result};
-use shared::{AppMessage, PlatformMessage};
-use std::path::PathBuf;
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use shared::wire::{self, MAX_IPC_MESSAGE_BYTES};
+use shared::listing::{self, ListingCursor};
+use shared::{AppMessage, ListedEntry, PlatformMessage, RenderStats};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
 use tokio::net::{UnixListener, UnixStream};
-use tokio::sync::mpsc;
-use tracing::{debug, error, info, warn};
+use tokio::sync::{mpsc, oneshot, RwLock};
+use tracing::{debug, error, info, warn, Instrument};
+use crate::application::ports::AppCrashRepository;
+use crate::application::sessions::app_state::{AppStateScope, AppStateStore};
+use crate::domain::entities::app_crash::AppCrash;
+use crate::domain::services::permission_evaluator::{Operation, PermissionEvaluator};
+use crate::domain::services::secrets;
+use crate::infrastructure::driven::folder_sizes::FolderSizeCache;
+use crate::infrastructure::driven::session_logs::session_span;
 
 /// Manages IPC socket server for app communication
 pub struct IpcSocketServer {
     socket_path: PathBuf,
-    // Removed connections field referencing missing Connection
+    registry: Registry,
+}
+
+/// Per-session state shared by the server and its connection handlers
+#[derive(Clone)]
+struct Registry {
+    // Secret the app launched for each session proves itself with on `Hello` and `OpenReader`;
+    // the socket is reachable from every sandbox, so a session id alone proves nothing
+    tokens: Arc<RwLock<HashMap<String, String>>>,
+    // Init (and Resume) messages waiting for the app of each session to say hello
+    pending_inits: Arc<RwLock<HashMap<String, Vec<PlatformMessage>>>>,
+    // Per-session listeners for messages coming from the app (e.g. the signaling socket)
+    subscribers: Arc<RwLock<HashMap<String, mpsc::UnboundedSender<AppMessage>>>>,
+    // Outgoing channel of each connected app, keyed by session
+    connections: Arc<RwLock<HashMap<String, mpsc::UnboundedSender<PlatformMessage>>>>,
+    // What each session's user may do to the vault; file transfers and deletions relayed
+    // in either direction are checked against it
+    permissions: Arc<RwLock<HashMap<String, PermissionEvaluator>>>,
+    // Host directory of the vault each session's app reads files from with `ReadFile`
+    vault_roots: Arc<RwLock<HashMap<String, PathBuf>>>,
+    // Sessions whose app sent `Ready`, possibly before any client subscribed
+    ready: Arc<RwLock<HashSet<String>>>,
+    // Suspends waiting for the app's `SuspendState`
+    suspending: Arc<RwLock<HashMap<String, oneshot::Sender<Vec<u8>>>>>,
+    // Whose saved state each session's app reads and writes
+    state_scopes: Arc<RwLock<HashMap<String, AppStateScope>>>,
+    // Latest render times each connected app reported
+    render_stats: Arc<RwLock<HashMap<String, RenderStats>>>,
+    // Folders each client session may browse, for telling its app when they change
+    scopes: Arc<RwLock<HashMap<String, SessionScope>>>,
+    app_state: Arc<AppStateStore>,
+    app_crashes: Arc<dyn AppCrashRepository>,
+    // Measured folder sizes, shared with the vault storage that invalidates them
+    folder_sizes: Arc<FolderSizeCache>,
 }
 
 
 impl IpcSocketServer {
-    pub fn new(socket_path: PathBuf) -> Self {
+    pub fn new(
+        socket_path: PathBuf,
+        app_state: Arc<AppStateStore>,
+        app_crashes: Arc<dyn AppCrashRepository>,
+        folder_sizes: Arc<FolderSizeCache>,
+    ) -> Self {
         Self {
             socket_path,
-            // Removed connections initialization referencing missing Connection
+            registry: Registry {
+                tokens: Arc::new(RwLock::new(HashMap::new())),
+                pending_inits: Arc::new(RwLock::new(HashMap::new())),
+                subscribers: Arc::new(RwLock::new(HashMap::new())),
+                connections: Arc::new(RwLock::new(HashMap::new())),
+                permissions: Arc::new(RwLock::new(HashMap::new())),
+                vault_roots: Arc::new(RwLock::new(HashMap::new())),
+                ready: Arc::new(RwLock::new(HashSet::new())),
+                suspending: Arc::new(RwLock::new(HashMap::new())),
+                state_scopes: Arc::new(RwLock::new(HashMap::new())),
+                render_stats: Arc::new(RwLock::new(HashMap::new())),
+                scopes: Arc::new(RwLock::new(HashMap::new())),
+                app_state,
+                app_crashes,
+                folder_sizes,
+            },
+        }
+    }
+
+    /// Receive the messages the session's app sends from now on. Replaces any
+    /// previous subscriber for that session.
+    pub async fn subscribe(&self, session_id: &str) -> mpsc::UnboundedReceiver<AppMessage> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.registry.subscribers.write().await.insert(session_id.to_string(), tx);
+        rx
+    }
+
+    pub async fn unsubscribe(&self, session_id: &str) {
+        self.registry.subscribers.write().await.remove(session_id);
+    }
+
+    /// Register the `Init` message to deliver when the session's app connects, and whose
+    /// saved state the app uses. Returns the token the app must connect with, to be handed to
+    /// it as `SANDBOX_IPC_TOKEN`.
+    pub async fn prepare_session(&self, session_id: &str, scope: AppStateScope, init: PlatformMessage) -> String {
+        let token = secrets::generate();
+        self.registry.tokens.write().await.insert(session_id.to_string(), token.clone());
+        self.registry.state_scopes.write().await.insert(session_id.to_string(), scope);
+        self.registry
+            .pending_inits
+            .write()
+            .await
+            .insert(session_id.to_string(), vec![init]);
+        token
+    }
+
+    /// What the session's user may do, for checking the transfers and deletions relayed to and
+    /// from its app. Both are refused for sessions without it.
+    pub async fn set_permissions(&self, session_id: &str, permissions: PermissionEvaluator) {
+        self.registry.permissions.write().await.insert(session_id.to_string(), permissions);
+    }
+
+    /// The folders a client's session was launched with. Its app's sandbox opens no others.
+    pub async fn set_scope(&self, session_id: &str, allowed_paths: Vec<String>) {
+        let scope = SessionScope { sandbox: allowed_paths.clone(), current: allowed_paths };
+        self.registry.scopes.write().await.insert(session_id.to_string(), scope);
+    }
+
+    /// Client sessions whose app is connected here, with the folders they were launched with.
+    pub async fn scoped_sessions(&self) -> Vec<(String, Vec<String>)> {
+        let scopes = self.registry.scopes.read().await;
+        scopes.iter().map(|(session_id, scope)| (session_id.clone(), scope.sandbox.clone())).collect()
+    }
+
+    /// Replace what a client's session may do, and tell its app when the folders it may browse
+    /// are no longer those it was last told. Returns whether the app was told; sessions without
+    /// a scope are left alone.
+    pub async fn update_scope(&self, session_id: &str, permissions: PermissionEvaluator, allowed_paths: Vec<String>) -> Result<bool> {
+        let mut scopes = self.registry.scopes.write().await;
+        let Some(scope) = scopes.get_mut(session_id) else {
+            return Ok(false);
+        };
+        self.registry.permissions.write().await.insert(session_id.to_string(), permissions);
+        if scope.current == allowed_paths {
+            return Ok(false);
+        }
+        scope.current = allowed_paths.clone();
+        drop(scopes);
+        self.send(session_id, PlatformMessage::ScopeChanged { allowed_paths }).await?;
+        Ok(true)
+    }
+
+    /// Where the vault the session's app reads with `ReadFile` is on the host. Reads are
+    /// refused for sessions without it.
+    pub async fn set_vault_root(&self, session_id: &str, root: PathBuf) {
+        self.registry.vault_roots.write().await.insert(session_id.to_string(), root);
+    }
+
+    /// Hand the app the state it saved when the session was suspended, right after its `Init`.
+    /// Must follow [`Self::prepare_session`].
+    pub async fn prepare_resume(&self, session_id: &str, state: Vec<u8>) {
+        if let Some(pending) = self.registry.pending_inits.write().await.get_mut(session_id) {
+            pending.push(PlatformMessage::Resume { state });
+        }
+    }
+
+    /// Ask the session's app for its state before the session is suspended. Fails when no app
+    /// is connected or it does not answer within `timeout`, e.g. because it predates suspend.
+    pub async fn request_suspend(&self, session_id: &str, timeout: Duration) -> Result<Vec<u8>> {
+        let (tx, rx) = oneshot::channel();
+        self.registry.suspending.write().await.insert(session_id.to_string(), tx);
+        let result = match self.send(session_id, PlatformMessage::Suspend).await {
+            Ok(()) => match tokio::time::timeout(timeout, rx).await {
+                Ok(Ok(state)) => Ok(state),
+                Ok(Err(_)) => Err(anyhow::anyhow!("App disconnected before saving its state")),
+                Err(_) => Err(anyhow::anyhow!("App did not save its state within {:?}", timeout)),
+            },
+            Err(e) => Err(e),
+        };
+        self.registry.suspending.write().await.remove(session_id);
+        result
+    }
+
+    /// Whether the session's app has sent `Ready` since it connected.
+    pub async fn is_ready(&self, session_id: &str) -> bool {
+        self.registry.ready.read().await.contains(session_id)
+    }
+
+    /// The render times each connected app last reported, keyed by session.
+    pub async fn render_stats(&self) -> HashMap<String, RenderStats> {
+        self.registry.render_stats.read().await.clone()
+    }
+
+    /// Send a message to the session's app. Downloads, uploads and deletions are refused
+    /// unless the session's permissions allow them; the error then holds the [`Denial`].
+    ///
+    /// [`Denial`]: crate::domain::services::permission_evaluator::Denial
+    pub async fn send(&self, session_id: &str, msg: PlatformMessage) -> Result<()> {
+        if let Some(operation) = file_operation(&msg) {
+            match self.registry.permissions.read().await.get(session_id) {
+                Some(permissions) => permissions.check_anywhere(operation, chrono::Utc::now())?,
+                None => anyhow::bail!("File operations are disabled for session {}: its permissions are unknown", session_id),
+            }
         }
+        let connections = self.registry.connections.read().await;
+        let tx = connections
+            .get(session_id)
+            .with_context(|| format!("No app connected for session {}", session_id))?;
+        tx.send(msg).context("App connection closed")
     }
 
     /// Start the IPC socket server
@@ -37,11 +224,18 @@ impl IpcSocketServer {
         loop {
             match listener.accept().await {
                 Ok((stream, _addr)) => {
-                    tokio::spawn(async move {
-                        if let Err(e) = Self::handle_connection(stream).await {
-                            error!("Connection error: {}", e);
+                    let registry = self.registry.clone();
+                    // Which session the connection serves is known once the app says hello
+                    let span = session_span(None, None, None);
+                    tokio::spawn(
+                        async move {
+                            let result = Self::handle_connection(stream, registry).await;
+                            if let Err(e) = result {
+                                error!("Connection error: {}", e);
+                            }
                         }
-                    });
+                        .instrument(span),
+                    );
                 }
                 Err(e) => {
                     error!("Failed to accept connection: {}", e);
@@ -50,51 +244,166 @@ impl IpcSocketServer {
         }
     }
 
-    async fn handle_connection(
-        stream: UnixStream,
-    ) -> Result<()> {
+    async fn handle_connection(stream: UnixStream, registry: Registry) -> Result<()> {
         info!("New IPC connection established");
+        let Registry {
+            tokens,
+            pending_inits,
+            subscribers,
+            connections,
+            permissions,
+            vault_roots,
+            ready,
+            suspending,
+            state_scopes,
+            render_stats,
+            scopes,
+            app_state,
+            app_crashes,
+            folder_sizes,
+        } = registry;
 
         let (reader, mut writer) = stream.into_split();
         let mut reader = BufReader::new(reader);
 
         // Create channels for bidirectional communication
-        let (_tx_to_app, mut rx_from_backend) = mpsc::unbounded_channel::<PlatformMessage>();
+        let (tx_to_app, mut rx_from_backend) = mpsc::unbounded_channel::<PlatformMessage>();
         let (_tx_to_backend, _rx_from_app) = mpsc::unbounded_channel::<AppMessage>();
 
         // Spawn task to send messages to app
-        tokio::spawn(async move {
-            while let Some(msg) = rx_from_backend.recv().await {
-                if let Ok(json) = serde_json::to_string(&msg) {
-                    if let Err(e) = writer.write_all(format!("{}\n", json).as_bytes()).await {
-                        error!("Failed to write to app: {}", e);
-                        break;
+        tokio::spawn(
+            async move {
+                while let Some(msg) = rx_from_backend.recv().await {
+                    if let Ok(json) = serde_json::to_string(&msg) {
+                        if let Err(e) = writer.write_all(format!("{}\n", json).as_bytes()).await {
+                            error!("Failed to write to app: {}", e);
+                            break;
+                        }
+                    } else {
+                        error!("Failed to serialize message to app");
                     }
-                } else {
-                    error!("Failed to serialize message to app");
                 }
+                debug!("App writer task ended");
             }
-            debug!("App writer task ended");
-        });
+            .in_current_span(),
+        );
 
         // Read messages from app
         let mut line = String::new();
-        let session_id: Option<String> = None;
+        let mut session_id: Option<String> = None;
+        // Extra connections opened with `OpenReader` only carry file reads
+        let mut reader_only = false;
 
         loop {
             line.clear();
-            match reader.read_line(&mut line).await {
+            // Never buffer more than one message: an app that keeps writing without a newline
+            // is cut off instead of growing the line without bound
+            match (&mut reader).take(MAX_IPC_MESSAGE_BYTES as u64 + 1).read_line(&mut line).await {
                 Ok(0) => {
                     info!("App disconnected");
                     break;
                 }
+                Ok(read) if read > MAX_IPC_MESSAGE_BYTES => {
+                    warn!("App sent a message over {} bytes, disconnecting", MAX_IPC_MESSAGE_BYTES);
+                    break;
+                }
                 Ok(_) => {
-                    match serde_json::from_str::<AppMessage>(&line) {
+                    match wire::decode::<AppMessage>(&line, MAX_IPC_MESSAGE_BYTES) {
                         Ok(msg) => {
                             debug!("Received from app: {:?}", msg);
+                            if reader_only && !matches!(msg, AppMessage::ReadFile { .. }) {
+                                warn!("Ignoring {:?} on a file reader connection", msg);
+                                continue;
+                            }
 
                             // Handle message based on type
                             match &msg {
+                                AppMessage::Hello { session_id: sid, token } => {
+                                    if !is_session_token(&tokens, sid, token).await {
+                                        warn!("Refused connection claiming session {}: wrong token", sid);
+                                        break;
+                                    }
+                                    info!("App connected for session: {}", sid);
+                                    match pending_inits.write().await.remove(sid) {
+                                        Some(pending) => {
+                                            for msg in pending {
+                                                let _ = tx_to_app.send(msg);
+                                            }
+                                        }
+                                        None => warn!("No pending init for session: {}", sid),
+                                    }
+                                    connections.write().await.insert(sid.clone(), tx_to_app.clone());
+                                    let span = tracing::Span::current();
+                                    span.record("session_id", sid.as_str());
+                                    if let Some(scope) = state_scopes.read().await.get(sid) {
+                                        span.record("user_id", scope.user_id.to_string().as_str());
+                                        span.record("app_id", scope.app_id.as_str());
+                                    }
+                                    session_id = Some(sid.clone());
+                                }
+                                AppMessage::OpenReader { session_id: sid, token } => {
+                                    if !is_session_token(&tokens, sid, token).await {
+                                        warn!("Refused file reader claiming session {}: wrong token", sid);
+                                        break;
+                                    }
+                                    // The session's main connection and state are left alone
+                                    debug!("File reader connected for session: {}", sid);
+                                    tracing::Span::current().record("session_id", sid.as_str());
+                                    session_id = Some(sid.clone());
+                                    reader_only = true;
+                                    continue;
+                                }
+                                AppMessage::ReadFile { request_id, path, offset, length } => {
+                                    let reply = match read_vault_file(&permissions, &vault_roots, session_id.as_deref(), path, *offset, *length).await {
+                                        Ok((size, data)) => PlatformMessage::FileData { request_id: *request_id, offset: *offset, size, data },
+                                        Err((reason, code)) => {
+                                            debug!("Refused read of {}: {}", path, reason);
+                                            PlatformMessage::FileReadFailed { request_id: *request_id, reason, code }
+                                        }
+                                    };
+                                    let _ = tx_to_app.send(reply);
+                                    continue;
+                                }
+                                AppMessage::FolderSize { request_id, path } => {
+                                    // Measured in the background so the app's other messages are not held up
+                                    let (request_id, path, tx) = (*request_id, path.clone(), tx_to_app.clone());
+                                    let (permissions, vault_roots, folder_sizes) = (permissions.clone(), vault_roots.clone(), folder_sizes.clone());
+                                    let sid = session_id.clone();
+                                    tokio::spawn(
+                                        async move {
+                                            let size = match folder_size(&permissions, &vault_roots, &folder_sizes, sid.as_deref(), &path).await {
+                                                Ok(size) => Some(size),
+                                                Err(reason) => {
+                                                    debug!("No size for folder {}: {}", path, reason);
+                                                    None
+                                                }
+                                            };
+                                            let _ = tx.send(PlatformMessage::FolderSize { request_id, path, size });
+                                        }
+                                        .in_current_span(),
+                                    );
+                                    continue;
+                                }
+                                AppMessage::ListFolder { request_id, path, after, limit } => {
+                                    // A huge folder takes a while to page through, so it is listed off the read loop
+                                    let (request_id, path, after, limit, tx) = (*request_id, path.clone(), after.clone(), *limit, tx_to_app.clone());
+                                    let (permissions, vault_roots) = (permissions.clone(), vault_roots.clone());
+                                    let sid = session_id.clone();
+                                    tokio::spawn(
+                                        async move {
+                                            let reply = match list_folder(&permissions, &vault_roots, sid.as_deref(), &path, after, limit).await {
+                                                Ok((entries, next, total)) => PlatformMessage::FolderPage { request_id, path, entries, next, total },
+                                                Err(reason) => {
+                                                    debug!("Refused listing of {}: {}", path, reason);
+                                                    PlatformMessage::FolderListFailed { request_id, reason }
+                                                }
+                                            };
+                                            let _ = tx.send(reply);
+                                        }
+                                        .in_current_span(),
+                                    );
+                                    continue;
+                                }
                                 AppMessage::State { path, selected, actions, metadata: _ } => {
                                     info!(
                                         "App state updated: path={}, selected={:?}, actions={:?}",
@@ -103,15 +412,113 @@ impl IpcSocketServer {
                                     // TODO: Update frontend with app state
                                 }
                                 AppMessage::DownloadData { filename, data: _ } => {
+                                    if let Err(reason) = may_download(&permissions, session_id.as_deref()).await {
+                                        warn!("Dropped download of {}: {}", filename, reason);
+                                        continue;
+                                    }
                                     info!("Received download data for: {}", filename);
                                     // TODO: Send file to frontend
                                 }
+                                AppMessage::DownloadChunk { transfer, offset, .. } => {
+                                    if let Err(reason) = may_download(&permissions, session_id.as_deref()).await {
+                                        warn!("Dropped download of {}: {}", transfer.filename, reason);
+                                        continue;
+                                    }
+                                    debug!("Download chunk of {} at {}/{}", transfer.filename, offset, transfer.size);
+                                }
                                 AppMessage::Success { operation, message } => {
                                     info!("Operation succeeded: {} - {:?}", operation, message);
                                 }
                                 AppMessage::Error { message, code } => {
                                     error!("App error: {} (code: {:?})", message, code);
                                 }
+                                AppMessage::Accessibility { events } => {
+                                    debug!("App accessibility events: {}", events.len());
+                                }
+                                AppMessage::Ready => {
+                                    if let Some(sid) = &session_id {
+                                        info!("App ready for session: {}", sid);
+                                        ready.write().await.insert(sid.clone());
+                                    }
+                                }
+                                AppMessage::SuspendState { state } => {
+                                    let waiter = match &session_id {
+                                        Some(sid) => suspending.write().await.remove(sid),
+                                        None => None,
+                                    };
+                                    match waiter {
+                                        Some(tx) => {
+                                            info!("App saved {} bytes of state for suspend", state.len());
+                                            let _ = tx.send(state.clone());
+                                        }
+                                        None => warn!("Ignoring suspend state nobody asked for"),
+                                    }
+                                    // Not for subscribers: the state may be large and is private
+                                    continue;
+                                }
+                                AppMessage::SaveState { key, value } => {
+                                    let scope = match &session_id {
+                                        Some(sid) => state_scopes.read().await.get(sid).cloned(),
+                                        None => None,
+                                    };
+                                    let result = match scope {
+                                        Some(scope) => app_state.save(&scope, key, value).await,
+                                        None => Err("Session is unidentified".to_string()),
+                                    };
+                                    if let Err(reason) = result {
+                                        warn!("Rejected app state {}: {}", key, reason);
+                                        let _ = tx_to_app.send(PlatformMessage::StateRejected { key: key.clone(), reason });
+                                    }
+                                    continue;
+                                }
+                                AppMessage::LoadState { key } => {
+                                    let scope = match &session_id {
+                                        Some(sid) => state_scopes.read().await.get(sid).cloned(),
+                                        None => None,
+                                    };
+                                    let value = match scope {
+                                        Some(scope) => app_state.load(&scope, key).await.unwrap_or_else(|e| {
+                                            warn!("Failed to load app state {}: {}", key, e);
+                                            serde_json::Value::Null
+                                        }),
+                                        None => serde_json::Value::Null,
+                                    };
+                                    let _ = tx_to_app.send(PlatformMessage::StateLoaded { key: key.clone(), value });
+                                    continue;
+                                }
+                                AppMessage::RenderStats { stats } => {
+                                    if let Some(sid) = &session_id {
+                                        render_stats.write().await.insert(sid.clone(), *stats);
+                                    }
+                                    continue;
+                                }
+                                AppMessage::Crash { report } => {
+                                    let scope = match &session_id {
+                                        Some(sid) => state_scopes.read().await.get(sid).cloned(),
+                                        None => None,
+                                    };
+                                    error!(
+                                        "App for session {:?} panicked: {} at {:?}",
+                                        session_id, report.message, report.location
+                                    );
+                                    let crash = AppCrash {
+                                        id: uuid::Uuid::new_v4(),
+                                        session_id: session_id.clone().unwrap_or_default(),
+                                        user_id: scope.as_ref().map(|s| s.user_id.clone()),
+                                        app_id: scope.map(|s| s.app_id),
+                                        message: report.message.clone(),
+                                        location: report.location.clone(),
+                                        backtrace: report.backtrace.clone(),
+                                        recent_input: report.recent_input.clone(),
+                                        frames: report.frames,
+                                        crashed_at: chrono::Utc::now(),
+                                    };
+                                    if let Err(e) = app_crashes.record(&crash).await {
+                                        error!("Failed to record app crash: {}", e);
+                                    }
+                                    // Not for subscribers: backtraces are for admins only
+                                    continue;
+                                }
                                 AppMessage::Log { level, message } => {
                                     match level {
                                         shared::LogLevel::Debug => debug!("App: {}", message),
@@ -121,9 +528,16 @@ impl IpcSocketServer {
                                     }
                                 }
                             }
+
+                            if let Some(sid) = &session_id {
+                                if let Some(tx) = subscribers.read().await.get(sid) {
+                                    let _ = tx.send(msg);
+                                }
+                            }
                         }
                         Err(e) => {
-                            error!("Failed to parse message from app: {} - Line: {}", e, line);
+                            // The line itself may be huge or hostile, so only its size is logged
+                            warn!("Rejected message from app ({} bytes): {}", line.len(), e);
                         }
                     }
                 }
@@ -135,8 +549,15 @@ impl IpcSocketServer {
         }
 
         // Clean up connection
-        if let Some(sid) = session_id {
-            // Removed connection cleanup referencing missing Connection
+        if let Some(sid) = session_id.filter(|_| !reader_only) {
+            connections.write().await.remove(&sid);
+            tokens.write().await.remove(&sid);
+            permissions.write().await.remove(&sid);
+            vault_roots.write().await.remove(&sid);
+            ready.write().await.remove(&sid);
+            state_scopes.write().await.remove(&sid);
+            render_stats.write().await.remove(&sid);
+            scopes.write().await.remove(&sid);
             info!("Removed connection for session: {}", sid);
         }
 
@@ -147,6 +568,204 @@ impl IpcSocketServer {
     // ...existing code...
 }
 
+/// Whether `token` is the one issued to the app of `session_id`.
+async fn is_session_token(tokens: &RwLock<HashMap<String, String>>, session_id: &str, token: &str) -> bool {
+    tokens.read().await.get(session_id).is_some_and(|expected| secrets::constant_time_eq(token, expected))
+}
+
+/// Folders a client's session may browse: those it was launched with, and those of them its
+/// app was last told it may still use
+struct SessionScope {
+    sandbox: Vec<String>,
+    current: Vec<String>,
+}
+
+/// What relaying `msg` would do to the vault, for messages that touch its files. The app acts
+/// on its own selection, so the path is not known here.
+fn file_operation(msg: &PlatformMessage) -> Option<Operation> {
+    match msg {
+        PlatformMessage::RequestDownload | PlatformMessage::ResumeDownload { .. } => Some(Operation::Download),
+        PlatformMessage::UploadFile { .. } => Some(Operation::Upload),
+        PlatformMessage::Delete => Some(Operation::Delete),
+        _ => None,
+    }
+}
+
+/// Whether file data the app sends may go on to the client: not for unidentified sessions or
+/// sessions whose permissions refuse downloads.
+async fn may_download(
+    permissions: &RwLock<HashMap<String, PermissionEvaluator>>,
+    session_id: Option<&str>,
+) -> std::result::Result<(), String> {
+    let Some(session_id) = session_id else {
+        return Err("session is unidentified".to_string());
+    };
+    match permissions.read().await.get(session_id) {
+        Some(permissions) => permissions.check_anywhere(Operation::Download, chrono::Utc::now()).map_err(|d| d.reason),
+        None => Err("session has no permissions".to_string()),
+    }
+}
+
+/// Up to `length` bytes of the vault file at `path` from `offset`, and the file's size, for a
+/// session allowed to read it. Streaming within the sandbox is reading, so view-only sessions
+/// may. Errors carry the denial code when permissions refused the read.
+async fn read_vault_file(
+    permissions: &RwLock<HashMap<String, PermissionEvaluator>>,
+    vault_roots: &RwLock<HashMap<String, PathBuf>>,
+    session_id: Option<&str>,
+    path: &str,
+    offset: u64,
+    length: u64,
+) -> std::result::Result<(u64, Vec<u8>), (String, Option<String>)> {
+    let Some(session_id) = session_id else {
+        return Err(("Session is unidentified".to_string(), None));
+    };
+    let file = permitted_in_vault(permissions, vault_roots, session_id, path, Operation::Read).await?;
+    tokio::task::spawn_blocking(move || read_range(&file.resolved, offset, length).map_err(|e| (e.to_string(), None)))
+        .await
+        .map_err(|e| (e.to_string(), None))?
+}
+
+/// Bytes under the vault folder at `path`, for a session allowed to read it.
+async fn folder_size(
+    permissions: &RwLock<HashMap<String, PermissionEvaluator>>,
+    vault_roots: &RwLock<HashMap<String, PathBuf>>,
+    folder_sizes: &FolderSizeCache,
+    session_id: Option<&str>,
+    path: &str,
+) -> std::result::Result<u64, String> {
+    let Some(session_id) = session_id else {
+        return Err("Session is unidentified".to_string());
+    };
+    let folder = permitted_in_vault(permissions, vault_roots, session_id, path, Operation::Read)
+        .await
+        .map_err(|(reason, _)| reason)?;
+    if !tokio::fs::metadata(&folder.resolved).await.map_err(|e| e.to_string())?.is_dir() {
+        return Err("Not a folder".to_string());
+    }
+    folder_sizes.size_of(&folder.named).await.map_err(|e| e.to_string())
+}
+
+/// A path in a session's vault with its links resolved
+struct VaultPath {
+    /// Where it is on disk
+    resolved: PathBuf,
+    /// The same path under the vault root as given, which is how the vault storage names it
+    named: PathBuf,
+    /// Vault-relative, what permissions apply to
+    relative: String,
+}
+
+/// `path` in the session's vault with links resolved, once permissions allow `operation` both
+/// on `path` as asked and on the vault path its links lead to, so a link cannot reach what the
+/// session may not. Errors carry the denial code when permissions refused.
+async fn permitted_in_vault(
+    permissions: &RwLock<HashMap<String, PermissionEvaluator>>,
+    vault_roots: &RwLock<HashMap<String, PathBuf>>,
+    session_id: &str,
+    path: &str,
+    operation: Operation,
+) -> std::result::Result<VaultPath, (String, Option<String>)> {
+    let Some(evaluator) = permissions.read().await.get(session_id).cloned() else {
+        return Err(("Session has no permissions".to_string(), None));
+    };
+    let check = |path: &str| {
+        evaluator
+            .check(path, operation, chrono::Utc::now())
+            .map_err(|d| (d.reason, Some(d.code.as_str().to_string())))
+    };
+    check(path)?;
+    let Some(root) = vault_roots.read().await.get(session_id).cloned() else {
+        return Err(("Session has no vault".to_string(), None));
+    };
+    let relative = path.trim_matches('/').to_string();
+    let vault = root.clone();
+    let (resolved, within) = tokio::task::spawn_blocking(move || {
+        let resolved = resolve_in_vault(&vault, &relative)?;
+        let within = resolved.strip_prefix(std::fs::canonicalize(&vault)?).map_err(std::io::Error::other)?.to_path_buf();
+        Ok::<_, std::io::Error>((resolved, within))
+    })
+    .await
+    .map_err(|e| (e.to_string(), None))?
+    .map_err(|e| (e.to_string(), None))?;
+    let relative = within.to_str().ok_or_else(|| ("Path is not valid UTF-8".to_string(), None))?.to_string();
+    check(&relative)?;
+    Ok(VaultPath { resolved, named: root.join(within), relative })
+}
+
+/// One page of the vault folder at `path`, for a session allowed to browse it where its links
+/// lead. Entries the session may not browse there are left out, so a page can come back
+/// shorter than `limit`.
+async fn list_folder(
+    permissions: &RwLock<HashMap<String, PermissionEvaluator>>,
+    vault_roots: &RwLock<HashMap<String, PathBuf>>,
+    session_id: Option<&str>,
+    path: &str,
+    after: Option<ListingCursor>,
+    limit: u32,
+) -> std::result::Result<(Vec<ListedEntry>, Option<ListingCursor>, u64), String> {
+    let Some(session_id) = session_id else {
+        return Err("Session is unidentified".to_string());
+    };
+    let evaluator = match permissions.read().await.get(session_id) {
+        Some(permissions) => permissions.clone(),
+        None => return Err("Session has no permissions".to_string()),
+    };
+    let folder = permitted_in_vault(permissions, vault_roots, session_id, path, Operation::Browse)
+        .await
+        .map_err(|(reason, _)| reason)?;
+    let dir = folder.resolved;
+    let (entries, next, total) = tokio::task::spawn_blocking(move || {
+        listing::list_directory_page(&dir, after.as_ref(), limit as usize)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())?;
+
+    let now = chrono::Utc::now();
+    let folder = folder.relative;
+    let entries = entries
+        .into_iter()
+        .filter(|entry| {
+            let path = if folder.is_empty() { entry.name.clone() } else { format!("{folder}/{}", entry.name) };
+            evaluator.check(&path, Operation::Browse, now).is_ok()
+        })
+        .map(|entry| ListedEntry {
+            modified: entry.modified.map(|t| chrono::DateTime::<chrono::Utc>::from(t).timestamp()),
+            mime_type: entry.mime_type.to_string(),
+            name: entry.name,
+            is_dir: entry.is_dir,
+            size: entry.size,
+        })
+        .collect();
+    Ok((entries, next, total as u64))
+}
+
+/// `path` inside the vault at `root` with links resolved, so one inside the vault cannot point
+/// elsewhere.
+fn resolve_in_vault(root: &Path, path: &str) -> std::io::Result<PathBuf> {
+    let root = std::fs::canonicalize(root)?;
+    let resolved = std::fs::canonicalize(root.join(path))?;
+    if !resolved.starts_with(&root) {
+        return Err(std::io::Error::new(std::io::ErrorKind::PermissionDenied, "Path leaves the vault"));
+    }
+    Ok(resolved)
+}
+
+fn read_range(resolved: &Path, offset: u64, length: u64) -> std::io::Result<(u64, Vec<u8>)> {
+    use std::io::{Read, Seek, SeekFrom};
+    let mut file = std::fs::File::open(resolved)?;
+    let meta = file.metadata()?;
+    if !meta.is_file() {
+        return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "Not a regular file"));
+    }
+    let length = length.min(shared::platform_file::MAX_READ_BYTES);
+    let mut data = Vec::with_capacity(length.min(meta.len().saturating_sub(offset)) as usize);
+    file.seek(SeekFrom::Start(offset))?;
+    file.take(length).read_to_end(&mut data)?;
+    Ok((meta.len(), data))
+}
+
 impl Drop for IpcSocketServer {
     fn drop(&mut self) {
         // Clean up socket file
@@ -0,0 +1,536 @@
+// Media metadata - reads the EXIF block of photos and the header of MP4/QuickTime videos,
+// and blanks the location a photo carries before it is shared
+use std::ops::Range;
+use std::sync::Arc;
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDateTime};
+use futures_util::StreamExt;
+use crate::application::ports::{FileProcessor, MediaMetadataRepository, VaultStorage};
+use crate::domain::entities::media_metadata::MediaMetadata;
+use crate::domain::entities::processing_task::ProcessingTask;
+
+/// EXIF sits at the start of a photo; this much of it is read
+pub const EXIF_HEAD_LEN: u64 = 256 * 1024;
+/// A video whose `moov` box is larger than this is left out
+const MAX_MOOV_LEN: u64 = 8 * 1024 * 1024;
+/// Seconds from 1904-01-01, where MP4 times count from, to the Unix epoch
+const MP4_EPOCH_OFFSET: u64 = 2_082_844_800;
+
+const TAG_MAKE: u16 = 0x010F;
+const TAG_MODEL: u16 = 0x0110;
+const TAG_DATE_TIME: u16 = 0x0132;
+const TAG_EXIF_IFD: u16 = 0x8769;
+const TAG_GPS_IFD: u16 = 0x8825;
+const TAG_THUMBNAIL_OFFSET: u16 = 0x0201;
+const TAG_THUMBNAIL_LENGTH: u16 = 0x0202;
+const TAG_DATE_TIME_ORIGINAL: u16 = 0x9003;
+const TAG_PIXEL_WIDTH: u16 = 0xA002;
+const TAG_PIXEL_HEIGHT: u16 = 0xA003;
+const TAG_GPS_LATITUDE_REF: u16 = 0x0001;
+const TAG_GPS_LATITUDE: u16 = 0x0002;
+const TAG_GPS_LONGITUDE_REF: u16 = 0x0003;
+const TAG_GPS_LONGITUDE: u16 = 0x0004;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MediaKind {
+    Photo,
+    Video,
+}
+
+fn media_kind(path: &str) -> Option<MediaKind> {
+    let extension = path.rsplit_once('.')?.1.to_ascii_lowercase();
+    match extension.as_str() {
+        "jpg" | "jpeg" | "tif" | "tiff" => Some(MediaKind::Photo),
+        "mp4" | "m4v" | "mov" => Some(MediaKind::Video),
+        _ => None,
+    }
+}
+
+/// Whether the file may carry a location to blank.
+pub fn is_photo(path: &str) -> bool {
+    media_kind(path) == Some(MediaKind::Photo)
+}
+
+/// Where the TIFF structure holding the EXIF entries starts: right after `Exif\0\0` in a
+/// JPEG, at 0 in a TIFF file.
+fn tiff_start(data: &[u8]) -> Option<usize> {
+    if data.starts_with(b"II*\0") || data.starts_with(b"MM\0*") {
+        return Some(0);
+    }
+    if !data.starts_with(&[0xFF, 0xD8]) {
+        return None;
+    }
+    let mut at = 2;
+    while at + 4 <= data.len() {
+        if data[at] != 0xFF {
+            return None;
+        }
+        let marker = data[at + 1];
+        if marker == 0xFF {
+            at += 1;
+            continue;
+        }
+        // Start of scan or end of image: no EXIF past this point
+        if marker == 0xDA || marker == 0xD9 {
+            return None;
+        }
+        if marker == 0xE1 && data.get(at + 4..at + 10) == Some(&b"Exif\0\0"[..]) {
+            return Some(at + 10);
+        }
+        at += 2 + u16::from_be_bytes([data[at + 2], data[at + 3]]) as usize;
+    }
+    None
+}
+
+/// One directory entry; its value is inline when it fits in 4 bytes
+struct Entry {
+    tag: u16,
+    kind: u16,
+    count: u32,
+    /// Offset of the value from the start of the TIFF structure
+    value_at: usize,
+}
+
+impl Entry {
+    fn value_range(&self) -> Range<usize> {
+        self.value_at..self.value_at + type_len(self.kind) * self.count as usize
+    }
+}
+
+fn type_len(kind: u16) -> usize {
+    match kind {
+        1 | 2 | 6 | 7 => 1,
+        3 | 8 => 2,
+        4 | 9 | 11 => 4,
+        5 | 10 | 12 => 8,
+        _ => 0,
+    }
+}
+
+fn find(entries: &[Entry], tag: u16) -> Option<&Entry> {
+    entries.iter().find(|e| e.tag == tag)
+}
+
+struct Tiff<'a> {
+    data: &'a [u8],
+    little_endian: bool,
+}
+
+impl<'a> Tiff<'a> {
+    fn parse(data: &'a [u8]) -> Option<Self> {
+        let little_endian = match data.get(..4)? {
+            b"II*\0" => true,
+            b"MM\0*" => false,
+            _ => return None,
+        };
+        Some(Self { data, little_endian })
+    }
+
+    fn u16_at(&self, at: usize) -> Option<u16> {
+        let bytes: [u8; 2] = self.data.get(at..at + 2)?.try_into().ok()?;
+        Some(if self.little_endian { u16::from_le_bytes(bytes) } else { u16::from_be_bytes(bytes) })
+    }
+
+    fn u32_at(&self, at: usize) -> Option<u32> {
+        let bytes: [u8; 4] = self.data.get(at..at + 4)?.try_into().ok()?;
+        Some(if self.little_endian { u32::from_le_bytes(bytes) } else { u32::from_be_bytes(bytes) })
+    }
+
+    fn first_ifd(&self) -> Option<usize> {
+        self.u32_at(4).map(|offset| offset as usize)
+    }
+
+    /// The directory after the one at `offset`, e.g. IFD1 holding the thumbnail after IFD0.
+    fn next_ifd(&self, offset: usize) -> Option<usize> {
+        let count = self.u16_at(offset)? as usize;
+        self.u32_at(offset + 2 + count * 12).filter(|&next| next != 0).map(|next| next as usize)
+    }
+
+    fn entries(&self, offset: usize) -> Vec<Entry> {
+        let count = self.u16_at(offset).unwrap_or(0) as usize;
+        (0..count)
+            .filter_map(|n| {
+                let at = offset + 2 + n * 12;
+                let kind = self.u16_at(at + 2)?;
+                let count = self.u32_at(at + 4)?;
+                let len = type_len(kind).checked_mul(count as usize)?;
+                let value_at = if len <= 4 { at + 8 } else { self.u32_at(at + 8)? as usize };
+                Some(Entry { tag: self.u16_at(at)?, kind, count, value_at })
+            })
+            .collect()
+    }
+
+    fn ascii(&self, entry: &Entry) -> Option<String> {
+        if entry.kind != 2 {
+            return None;
+        }
+        let text = String::from_utf8_lossy(self.data.get(entry.value_range())?);
+        let text = text.trim_matches(|c: char| c == '\0' || c.is_whitespace());
+        (!text.is_empty()).then(|| text.to_string())
+    }
+
+    fn uint(&self, entry: &Entry) -> Option<u32> {
+        match entry.kind {
+            3 => self.u16_at(entry.value_at).map(u32::from),
+            4 => self.u32_at(entry.value_at),
+            _ => None,
+        }
+    }
+
+    fn rationals(&self, entry: &Entry) -> Vec<f64> {
+        if entry.kind != 5 {
+            return Vec::new();
+        }
+        (0..entry.count as usize)
+            .filter_map(|n| {
+                let numerator = self.u32_at(entry.value_at + n * 8)?;
+                let denominator = self.u32_at(entry.value_at + n * 8 + 4)?;
+                (denominator != 0).then(|| numerator as f64 / denominator as f64)
+            })
+            .collect()
+    }
+
+    /// Degrees from degrees, minutes and seconds; negative south or west.
+    fn coordinate(&self, gps: &[Entry], reference: u16, tag: u16, negative: u8) -> Option<f64> {
+        let &[degrees, minutes, seconds] = self.rationals(find(gps, tag)?).as_slice() else {
+            return None;
+        };
+        let value = degrees + minutes / 60.0 + seconds / 3600.0;
+        let reference = find(gps, reference).and_then(|e| self.data.get(e.value_at)).copied();
+        Some(if reference == Some(negative) { -value } else { value })
+    }
+}
+
+/// Read what the EXIF block at the start of a photo tells.
+fn read_exif(data: &[u8], metadata: &mut MediaMetadata) {
+    let Some(tiff) = tiff_start(data).and_then(|start| Tiff::parse(&data[start..])) else {
+        return;
+    };
+    let Some(ifd0) = tiff.first_ifd() else {
+        return;
+    };
+    let entries = tiff.entries(ifd0);
+    metadata.set_camera(
+        find(&entries, TAG_MAKE).and_then(|e| tiff.ascii(e)).as_deref(),
+        find(&entries, TAG_MODEL).and_then(|e| tiff.ascii(e)).as_deref(),
+    );
+    let mut taken_at = find(&entries, TAG_DATE_TIME).and_then(|e| tiff.ascii(e));
+    if let Some(offset) = find(&entries, TAG_EXIF_IFD).and_then(|e| tiff.uint(e)) {
+        let exif = tiff.entries(offset as usize);
+        taken_at = find(&exif, TAG_DATE_TIME_ORIGINAL).and_then(|e| tiff.ascii(e)).or(taken_at);
+        metadata.width = find(&exif, TAG_PIXEL_WIDTH).and_then(|e| tiff.uint(e));
+        metadata.height = find(&exif, TAG_PIXEL_HEIGHT).and_then(|e| tiff.uint(e));
+    }
+    metadata.taken_at = taken_at.and_then(|t| NaiveDateTime::parse_from_str(&t, "%Y:%m:%d %H:%M:%S").ok());
+    if let Some(offset) = find(&entries, TAG_GPS_IFD).and_then(|e| tiff.uint(e)) {
+        let gps = tiff.entries(offset as usize);
+        let latitude = tiff.coordinate(&gps, TAG_GPS_LATITUDE_REF, TAG_GPS_LATITUDE, b'S');
+        let longitude = tiff.coordinate(&gps, TAG_GPS_LONGITUDE_REF, TAG_GPS_LONGITUDE, b'W');
+        if let (Some(latitude), Some(longitude)) = (latitude, longitude) {
+            metadata.latitude = Some(latitude);
+            metadata.longitude = Some(longitude);
+        }
+    }
+}
+
+/// Zero the GPS entries of the EXIF block at the start of a photo, in place, so the file
+/// keeps its size. Returns whether there was a location to blank.
+pub fn blank_gps(data: &mut [u8]) -> bool {
+    let Some(start) = tiff_start(data) else {
+        return false;
+    };
+    let (directory, values) = {
+        let Some(tiff) = Tiff::parse(&data[start..]) else {
+            return false;
+        };
+        let Some(directory) = tiff
+            .first_ifd()
+            .and_then(|ifd0| find(&tiff.entries(ifd0), TAG_GPS_IFD).and_then(|e| tiff.uint(e)))
+        else {
+            return false;
+        };
+        let values: Vec<Range<usize>> = tiff.entries(directory as usize).iter().map(Entry::value_range).collect();
+        (directory as usize, values)
+    };
+    if values.is_empty() {
+        return false;
+    }
+    let tiff = &mut data[start..];
+    for range in values {
+        if let Some(bytes) = tiff.get_mut(range) {
+            bytes.fill(0);
+        }
+    }
+    // An empty directory with no next one: readers find no location at all
+    if let Some(header) = tiff.get_mut(directory..directory + 6) {
+        header.fill(0);
+    }
+    true
+}
+
+/// Where the JPEG preview a camera embeds in the EXIF block at the start of a photo lies in
+/// `data`, when it is there in full.
+pub fn embedded_thumbnail(data: &[u8]) -> Option<Range<usize>> {
+    let start = tiff_start(data)?;
+    let tiff = Tiff::parse(&data[start..])?;
+    let ifd1 = tiff.next_ifd(tiff.first_ifd()?)?;
+    let entries = tiff.entries(ifd1);
+    let offset = find(&entries, TAG_THUMBNAIL_OFFSET).and_then(|e| tiff.uint(e))? as usize;
+    let length = find(&entries, TAG_THUMBNAIL_LENGTH).and_then(|e| tiff.uint(e))? as usize;
+    let range = start + offset..start + offset + length;
+    data.get(range.clone()).filter(|jpeg| jpeg.starts_with(&[0xFF, 0xD8]))?;
+    Some(range)
+}
+
+fn be_u32(data: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(data.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn be_u64(data: &[u8], at: usize) -> Option<u64> {
+    Some(u64::from_be_bytes(data.get(at..at + 8)?.try_into().ok()?))
+}
+
+/// Type, header length and total length of the MP4 box starting `data`, `remaining` bytes
+/// before the end of its parent.
+fn box_header(data: &[u8], remaining: u64) -> Option<([u8; 4], u64, u64)> {
+    let kind: [u8; 4] = data.get(4..8)?.try_into().ok()?;
+    let (header_len, len) = match be_u32(data, 0)? {
+        // Runs to the end of the file
+        0 => (8, remaining),
+        1 => (16, be_u64(data, 8)?),
+        len => (8, len as u64),
+    };
+    (len >= header_len && len <= remaining).then_some((kind, header_len, len))
+}
+
+/// The boxes directly inside a container box's body.
+fn children(data: &[u8]) -> Vec<([u8; 4], &[u8])> {
+    let mut boxes = Vec::new();
+    let mut at = 0;
+    while let Some((kind, header_len, len)) = box_header(&data[at..], (data.len() - at) as u64) {
+        boxes.push((kind, &data[at + header_len as usize..at + len as usize]));
+        at += len as usize;
+    }
+    boxes
+}
+
+/// Read creation time, duration and frame size from a video's `moov` box body.
+fn read_moov(moov: &[u8], metadata: &mut MediaMetadata) {
+    for (kind, body) in children(moov) {
+        match &kind {
+            b"mvhd" => {
+                let header = match body.first() {
+                    Some(1) => be_u64(body, 4).zip(be_u32(body, 20)).zip(be_u64(body, 24)),
+                    Some(_) => be_u32(body, 4).map(u64::from).zip(be_u32(body, 12)).zip(be_u32(body, 16).map(u64::from)),
+                    None => None,
+                };
+                let Some(((created, timescale), duration)) = header else {
+                    continue;
+                };
+                // Zero when the muxer left it unset; MP4 times are UTC
+                if created > MP4_EPOCH_OFFSET {
+                    metadata.taken_at = DateTime::from_timestamp((created - MP4_EPOCH_OFFSET) as i64, 0).map(|t| t.naive_utc());
+                }
+                if timescale > 0 && duration > 0 {
+                    metadata.duration_secs = Some(duration as f64 / timescale as f64);
+                }
+            }
+            b"trak" if metadata.width.is_none() => {
+                for (kind, tkhd) in children(body) {
+                    // Width and height close the box, as 16.16 fixed point; zero for sound
+                    if &kind != b"tkhd" || tkhd.len() < 8 {
+                        continue;
+                    }
+                    let width = be_u32(tkhd, tkhd.len() - 8).map(|w| w >> 16).unwrap_or(0);
+                    let height = be_u32(tkhd, tkhd.len() - 4).map(|h| h >> 16).unwrap_or(0);
+                    if width > 0 && height > 0 {
+                        metadata.width = Some(width);
+                        metadata.height = Some(height);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Processing stage storing the date, camera, location and size of photos and videos, for
+/// the metadata endpoints and search facets.
+pub struct MetadataExtractor {
+    storage: Arc<dyn VaultStorage>,
+    metadata: Arc<dyn MediaMetadataRepository>,
+}
+
+impl MetadataExtractor {
+    pub fn new(storage: Arc<dyn VaultStorage>, metadata: Arc<dyn MediaMetadataRepository>) -> Self {
+        Self { storage, metadata }
+    }
+
+    async fn read(&self, task: &ProcessingTask, offset: u64, len: u64) -> Result<Vec<u8>, String> {
+        let mut stream = self.storage.read_range(&task.owner_id, &task.path, offset, len).await?;
+        let mut bytes = Vec::with_capacity(len as usize);
+        while let Some(chunk) = stream.next().await {
+            bytes.extend(chunk?);
+        }
+        Ok(bytes)
+    }
+
+    /// Walk the top-level boxes to the `moov` one, which may come after the media data.
+    async fn read_video(&self, task: &ProcessingTask, metadata: &mut MediaMetadata) -> Result<(), String> {
+        let mut offset = 0;
+        while offset + 8 <= task.size {
+            let remaining = task.size - offset;
+            let header = self.read(task, offset, remaining.min(16)).await?;
+            let Some((kind, header_len, len)) = box_header(&header, remaining) else {
+                break;
+            };
+            if &kind == b"moov" {
+                if len - header_len <= MAX_MOOV_LEN {
+                    read_moov(&self.read(task, offset + header_len, len - header_len).await?, metadata);
+                }
+                break;
+            }
+            offset += len;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl FileProcessor for MetadataExtractor {
+    fn name(&self) -> &'static str {
+        "metadata"
+    }
+
+    fn accepts(&self, path: &str) -> bool {
+        media_kind(path).is_some()
+    }
+
+    async fn process(&self, task: &ProcessingTask) -> Result<(), String> {
+        let mut metadata = MediaMetadata::new(task.owner_id.clone(), &task.path);
+        let read = match media_kind(&task.path) {
+            Some(MediaKind::Photo) => self
+                .read(task, 0, task.size.min(EXIF_HEAD_LEN))
+                .await
+                .map(|head| read_exif(&head, &mut metadata)),
+            Some(MediaKind::Video) => self.read_video(task, &mut metadata).await,
+            None => return Ok(()),
+        };
+        match read {
+            // Moved or deleted since it was queued; nothing left to read
+            Err(e) if e.contains("not found") => return Ok(()),
+            result => result?,
+        }
+        if metadata.is_empty() {
+            return Ok(());
+        }
+        self.metadata.save(&metadata).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::UserId;
+
+    /// A little-endian TIFF with a make, and a GPS directory placing it in Paris' west.
+    fn tiff_with_gps() -> Vec<u8> {
+        fn entry(tiff: &mut Vec<u8>, tag: u16, kind: u16, count: u32, value: [u8; 4]) {
+            tiff.extend(tag.to_le_bytes());
+            tiff.extend(kind.to_le_bytes());
+            tiff.extend(count.to_le_bytes());
+            tiff.extend(value);
+        }
+        let mut tiff = b"II*\0".to_vec();
+        tiff.extend(8u32.to_le_bytes());
+        // IFD0, at 8 to 38
+        tiff.extend(2u16.to_le_bytes());
+        entry(&mut tiff, TAG_MAKE, 2, 4, *b"Sony");
+        entry(&mut tiff, TAG_GPS_IFD, 4, 1, 38u32.to_le_bytes());
+        tiff.extend(0u32.to_le_bytes());
+        // GPS directory, at 38 to 92, then its rationals
+        tiff.extend(4u16.to_le_bytes());
+        entry(&mut tiff, TAG_GPS_LATITUDE_REF, 2, 2, *b"N\0\0\0");
+        entry(&mut tiff, TAG_GPS_LATITUDE, 5, 3, 92u32.to_le_bytes());
+        entry(&mut tiff, TAG_GPS_LONGITUDE_REF, 2, 2, *b"W\0\0\0");
+        entry(&mut tiff, TAG_GPS_LONGITUDE, 5, 3, 116u32.to_le_bytes());
+        tiff.extend(0u32.to_le_bytes());
+        for (numerator, denominator) in [(48u32, 1u32), (51, 1), (2400, 100), (2, 1), (17, 1), (4000, 100)] {
+            tiff.extend(numerator.to_le_bytes());
+            tiff.extend(denominator.to_le_bytes());
+        }
+        tiff
+    }
+
+    fn jpeg(tiff: &[u8]) -> Vec<u8> {
+        let mut jpeg = vec![0xFF, 0xD8, 0xFF, 0xE1];
+        jpeg.extend(((tiff.len() + 8) as u16).to_be_bytes());
+        jpeg.extend(b"Exif\0\0");
+        jpeg.extend(tiff);
+        jpeg.extend([0xFF, 0xDA, 0x00, 0x02]);
+        jpeg
+    }
+
+    #[test]
+    fn test_read_exif_then_blank_gps() {
+        let mut photo = jpeg(&tiff_with_gps());
+        let mut metadata = MediaMetadata::new(UserId::new(), "a.jpg");
+        read_exif(&photo, &mut metadata);
+        assert_eq!(metadata.camera.as_deref(), Some("Sony"));
+        assert!((metadata.latitude.unwrap() - 48.85667).abs() < 1e-4);
+        assert!((metadata.longitude.unwrap() + 2.29444).abs() < 1e-4);
+
+        let len = photo.len();
+        assert!(blank_gps(&mut photo));
+        assert_eq!(photo.len(), len);
+        let mut metadata = MediaMetadata::new(UserId::new(), "a.jpg");
+        read_exif(&photo, &mut metadata);
+        assert_eq!(metadata.camera.as_deref(), Some("Sony"));
+        assert_eq!((metadata.latitude, metadata.longitude), (None, None));
+        assert!(!blank_gps(&mut photo));
+    }
+
+    #[test]
+    fn test_embedded_thumbnail() {
+        let mut tiff = b"II*\0".to_vec();
+        tiff.extend(8u32.to_le_bytes());
+        // IFD0, at 8 to 26, pointing on to IFD1
+        tiff.extend(1u16.to_le_bytes());
+        tiff.extend([0x0F, 0x01, 2, 0, 4, 0, 0, 0]);
+        tiff.extend(*b"Sony");
+        tiff.extend(26u32.to_le_bytes());
+        // IFD1, at 26 to 56, then the thumbnail
+        tiff.extend(2u16.to_le_bytes());
+        for (tag, value) in [(TAG_THUMBNAIL_OFFSET, 56u32), (TAG_THUMBNAIL_LENGTH, 6)] {
+            tiff.extend(tag.to_le_bytes());
+            tiff.extend(4u16.to_le_bytes());
+            tiff.extend(1u32.to_le_bytes());
+            tiff.extend(value.to_le_bytes());
+        }
+        tiff.extend(0u32.to_le_bytes());
+        tiff.extend([0xFF, 0xD8, 1, 2, 0xFF, 0xD9]);
+
+        let photo = jpeg(&tiff);
+        let range = embedded_thumbnail(&photo).unwrap();
+        assert_eq!(&photo[range], &[0xFF, 0xD8, 1, 2, 0xFF, 0xD9]);
+        assert_eq!(embedded_thumbnail(&jpeg(&tiff_with_gps())), None);
+    }
+
+    #[test]
+    fn test_read_moov() {
+        let mut mvhd = 28u32.to_be_bytes().to_vec();
+        mvhd.extend(b"mvhd");
+        mvhd.extend([0u8; 4]);
+        mvhd.extend(((MP4_EPOCH_OFFSET + 1_700_000_000) as u32).to_be_bytes());
+        mvhd.extend(0u32.to_be_bytes());
+        mvhd.extend(1000u32.to_be_bytes());
+        mvhd.extend(5000u32.to_be_bytes());
+
+        let mut metadata = MediaMetadata::new(UserId::new(), "a.mp4");
+        read_moov(&mvhd, &mut metadata);
+        assert_eq!(metadata.duration_secs, Some(5.0));
+        assert_eq!(metadata.taken_at.map(|t| t.to_string()).as_deref(), Some("2023-11-14 22:13:20"));
+        assert_eq!(media_kind("Clips/A.MOV"), Some(MediaKind::Video));
+        assert_eq!(media_kind("notes.txt"), None);
+    }
+}
@@ -3,7 +3,19 @@ pub mod sandbox;
 pub mod input;
 pub mod ipc;
 pub mod storage;
+pub mod folder_sizes;
+pub mod secrets;
+pub mod config;
+pub mod geoip;
+pub mod email;
+pub mod oidc;
+pub mod ice_servers;
+pub mod host_metrics;
+pub mod disk_activity;
+pub mod session_logs;
+pub mod build_info;
 
 pub use persistence::*;
 pub use sandbox::XvfbManager;
 pub use ipc::IpcSocketServer;
+pub mod media;
@@ -0,0 +1,272 @@
+use std::sync::Arc;
+use crate::application::ports::IdentityProvider;
+use crate::infrastructure::driven::secrets::{self, SecretsProvider};
+
+/// The client registered at the provider, from the `OIDC_*` settings.
+#[derive(Debug, Clone)]
+#[cfg_attr(not(feature = "oidc"), allow(dead_code))]
+pub struct OidcSettings {
+    pub issuer: String,
+    pub client_id: String,
+    /// Unset for public clients, which PKCE alone protects
+    pub client_secret: Option<String>,
+    /// `/api/auth/oidc/callback` as the provider's redirect reaches it
+    pub redirect_url: String,
+    pub scopes: String,
+    /// ID token claim listing the user's groups
+    pub groups_claim: String,
+}
+
+impl OidcSettings {
+    fn from_env(issuer: String) -> Result<Self, String> {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+        Ok(Self {
+            issuer: issuer.trim_end_matches('/').to_string(),
+            client_id: var("OIDC_CLIENT_ID").ok_or("OIDC_CLIENT_ID must be set with OIDC_ISSUER")?,
+            client_secret: secrets::default_provider().get("OIDC_CLIENT_SECRET"),
+            redirect_url: var("OIDC_REDIRECT_URL").ok_or("OIDC_REDIRECT_URL must be set with OIDC_ISSUER")?,
+            scopes: var("OIDC_SCOPES").unwrap_or_else(|| "openid email profile groups".to_string()),
+            groups_claim: var("OIDC_GROUPS_CLAIM").unwrap_or_else(|| "groups".to_string()),
+        })
+    }
+}
+
+#[cfg(feature = "oidc")]
+mod provider {
+    use async_trait::async_trait;
+    use base64::Engine;
+    use jsonwebtoken::jwk::JwkSet;
+    use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+    use serde_json::Value;
+    use sha2::{Digest, Sha256};
+    use tokio::sync::{OnceCell, RwLock};
+    use super::OidcSettings;
+    use crate::application::ports::{AuthorizationRequest, ExternalClaims, IdentityProvider};
+
+    /// The endpoints the provider publishes at `/.well-known/openid-configuration`
+    #[derive(Debug, serde::Deserialize)]
+    struct Metadata {
+        issuer: String,
+        authorization_endpoint: String,
+        token_endpoint: String,
+        jwks_uri: String,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct TokenResponse {
+        id_token: String,
+    }
+
+    /// Authorization code flow with PKCE against a standard provider such as Authelia or
+    /// Keycloak. Its endpoints are discovered on first use; its signing keys are fetched again
+    /// when a token names one not seen yet, as after a key rotation.
+    pub struct OidcProvider {
+        settings: OidcSettings,
+        http: reqwest::Client,
+        metadata: OnceCell<Metadata>,
+        keys: RwLock<JwkSet>,
+    }
+
+    impl OidcProvider {
+        pub fn new(settings: OidcSettings) -> Result<Self, String> {
+            let http = reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(10))
+                .build()
+                .map_err(|e| format!("Failed to build HTTP client: {e}"))?;
+            Ok(Self { settings, http, metadata: OnceCell::new(), keys: RwLock::new(JwkSet { keys: Vec::new() }) })
+        }
+
+        async fn metadata(&self) -> Result<&Metadata, String> {
+            self.metadata
+                .get_or_try_init(|| async {
+                    let url = format!("{}/.well-known/openid-configuration", self.settings.issuer);
+                    let metadata: Metadata = self.get_json(&url).await?;
+                    if metadata.issuer.trim_end_matches('/') != self.settings.issuer {
+                        return Err(format!("Provider reports issuer {}, expected {}", metadata.issuer, self.settings.issuer));
+                    }
+                    Ok(metadata)
+                })
+                .await
+        }
+
+        async fn get_json<T: serde::de::DeserializeOwned>(&self, url: &str) -> Result<T, String> {
+            let response = self.http.get(url).send().await.map_err(|e| format!("{url}: {e}"))?;
+            if !response.status().is_success() {
+                return Err(format!("{url}: {}", response.status()));
+            }
+            response.json().await.map_err(|e| format!("{url}: {e}"))
+        }
+
+        async fn decoding_key(&self, kid: Option<&str>) -> Result<DecodingKey, String> {
+            let find = |keys: &JwkSet| match kid {
+                Some(kid) => keys.find(kid).cloned(),
+                None => keys.keys.first().cloned(),
+            };
+            if let Some(jwk) = find(&*self.keys.read().await) {
+                return DecodingKey::from_jwk(&jwk).map_err(|e| e.to_string());
+            }
+            let fetched: JwkSet = self.get_json(&self.metadata().await?.jwks_uri).await?;
+            let jwk = find(&fetched).ok_or_else(|| format!("Provider has no signing key {}", kid.unwrap_or_default()))?;
+            *self.keys.write().await = fetched;
+            DecodingKey::from_jwk(&jwk).map_err(|e| e.to_string())
+        }
+
+        async fn verify_id_token(&self, id_token: &str, nonce: &str) -> Result<ExternalClaims, String> {
+            let header = jsonwebtoken::decode_header(id_token).map_err(|e| format!("Invalid ID token: {e}"))?;
+            // Only the provider's published keys may sign, never a shared secret
+            if !matches!(
+                header.alg,
+                Algorithm::RS256 | Algorithm::RS384 | Algorithm::RS512 | Algorithm::PS256 | Algorithm::PS384
+                    | Algorithm::PS512 | Algorithm::ES256 | Algorithm::ES384 | Algorithm::EdDSA
+            ) {
+                return Err(format!("ID token signed with unsupported algorithm {:?}", header.alg));
+            }
+            let key = self.decoding_key(header.kid.as_deref()).await?;
+            let mut validation = Validation::new(header.alg);
+            validation.set_issuer(&[&self.metadata().await?.issuer]);
+            validation.set_audience(&[&self.settings.client_id]);
+            let claims = jsonwebtoken::decode::<Value>(id_token, &key, &validation)
+                .map_err(|e| format!("ID token rejected: {e}"))?
+                .claims;
+            if claims["nonce"].as_str() != Some(nonce) {
+                return Err("ID token was issued for another login".to_string());
+            }
+            parse_claims(&claims, &self.settings)
+        }
+    }
+
+    fn parse_claims(claims: &Value, settings: &OidcSettings) -> Result<ExternalClaims, String> {
+        let string = |name: &str| claims[name].as_str().map(str::to_string);
+        let groups = match &claims[settings.groups_claim.as_str()] {
+            Value::Array(groups) => groups.iter().filter_map(|g| g.as_str().map(str::to_string)).collect(),
+            Value::String(group) => vec![group.clone()],
+            _ => Vec::new(),
+        };
+        Ok(ExternalClaims {
+            issuer: string("iss").ok_or("ID token has no issuer")?,
+            subject: string("sub").ok_or("ID token has no subject")?,
+            email: string("email"),
+            // Some providers send it as a string
+            email_verified: matches!(&claims["email_verified"], Value::Bool(true)) || claims["email_verified"] == "true",
+            name: string("name").or_else(|| string("preferred_username")),
+            groups,
+        })
+    }
+
+    fn random_token() -> String {
+        format!("{}{}", uuid::Uuid::new_v4().simple(), uuid::Uuid::new_v4().simple())
+    }
+
+    #[async_trait]
+    impl IdentityProvider for OidcProvider {
+        async fn authorization_request(&self) -> Result<AuthorizationRequest, String> {
+            let metadata = self.metadata().await?;
+            let (state, nonce, pkce_verifier) = (random_token(), random_token(), random_token());
+            let challenge = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(Sha256::digest(pkce_verifier.as_bytes()));
+            let mut url = url::Url::parse(&metadata.authorization_endpoint)
+                .map_err(|e| format!("Invalid authorization endpoint: {e}"))?;
+            url.query_pairs_mut()
+                .append_pair("response_type", "code")
+                .append_pair("client_id", &self.settings.client_id)
+                .append_pair("redirect_uri", &self.settings.redirect_url)
+                .append_pair("scope", &self.settings.scopes)
+                .append_pair("state", &state)
+                .append_pair("nonce", &nonce)
+                .append_pair("code_challenge", &challenge)
+                .append_pair("code_challenge_method", "S256");
+            Ok(AuthorizationRequest { url: url.to_string(), state, nonce, pkce_verifier })
+        }
+
+        async fn exchange(&self, code: &str, pkce_verifier: &str, nonce: &str) -> Result<ExternalClaims, String> {
+            let metadata = self.metadata().await?;
+            let mut form = vec![
+                ("grant_type", "authorization_code"),
+                ("code", code),
+                ("redirect_uri", self.settings.redirect_url.as_str()),
+                ("client_id", self.settings.client_id.as_str()),
+                ("code_verifier", pkce_verifier),
+            ];
+            if let Some(secret) = &self.settings.client_secret {
+                form.push(("client_secret", secret.as_str()));
+            }
+            let response = self.http
+                .post(&metadata.token_endpoint)
+                .form(&form)
+                .send()
+                .await
+                .map_err(|e| format!("Token request failed: {e}"))?;
+            if !response.status().is_success() {
+                // The body can carry provider details the browser must not see; it stays in the log
+                let status = response.status();
+                let body = response.text().await.unwrap_or_default();
+                tracing::warn!("Token endpoint refused the code ({}): {}", status, body);
+                return Err(format!("Provider refused the code ({status})"));
+            }
+            let tokens: TokenResponse = response.json().await.map_err(|e| format!("Invalid token response: {e}"))?;
+            self.verify_id_token(&tokens.id_token, nonce).await
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_parse_claims() {
+            let settings = OidcSettings {
+                issuer: "https://auth.example.com".into(),
+                client_id: "vault".into(),
+                client_secret: None,
+                redirect_url: "https://vault.example.com/api/auth/oidc/callback".into(),
+                scopes: "openid".into(),
+                groups_claim: "roles".into(),
+            };
+            let claims = serde_json::json!({
+                "iss": "https://auth.example.com",
+                "sub": "3f2a",
+                "email": "ana@example.com",
+                "email_verified": "true",
+                "preferred_username": "ana",
+                "roles": ["family", 7],
+            });
+            let parsed = parse_claims(&claims, &settings).unwrap();
+            assert_eq!(parsed.subject, "3f2a");
+            assert!(parsed.email_verified);
+            assert_eq!(parsed.name.as_deref(), Some("ana"));
+            assert_eq!(parsed.groups, vec!["family".to_string()]);
+            assert!(parse_claims(&serde_json::json!({ "iss": "x" }), &settings).is_err());
+        }
+    }
+}
+
+#[cfg(feature = "oidc")]
+pub use provider::OidcProvider;
+
+/// Provider at `OIDC_ISSUER`. Without one, users log in with passkeys only.
+pub fn from_env() -> Option<Arc<dyn IdentityProvider>> {
+    let issuer = std::env::var("OIDC_ISSUER").ok().filter(|v| !v.is_empty())?;
+    match OidcSettings::from_env(issuer) {
+        Ok(settings) => open(settings),
+        Err(e) => {
+            tracing::warn!("{}; OpenID Connect login is disabled", e);
+            None
+        }
+    }
+}
+
+#[cfg(feature = "oidc")]
+fn open(settings: OidcSettings) -> Option<Arc<dyn IdentityProvider>> {
+    match OidcProvider::new(settings) {
+        Ok(provider) => Some(Arc::new(provider)),
+        Err(e) => {
+            tracing::warn!("{}; OpenID Connect login is disabled", e);
+            None
+        }
+    }
+}
+
+#[cfg(not(feature = "oidc"))]
+fn open(settings: OidcSettings) -> Option<Arc<dyn IdentityProvider>> {
+    tracing::warn!("OIDC_ISSUER={} ignored: built without the `oidc` feature", settings.issuer);
+    None
+}
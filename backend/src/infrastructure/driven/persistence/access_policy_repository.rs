@@ -0,0 +1,115 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::SqliteConnection;
+use crate::application::ports::access_policy_repository::AccessPolicyRepository;
+use crate::domain::entities::access_policy::AccessPolicy;
+use crate::domain::value_objects::{IpRange, UserId};
+use crate::infrastructure::driven::persistence::db_types::DbAccessPolicy;
+
+pub struct SqliteAccessPolicyRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+}
+
+impl SqliteAccessPolicyRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>) -> Self {
+        Self { pool }
+    }
+}
+
+const SELECT_POLICY: &str =
+    "SELECT owner_id, allowed_cidrs, denied_cidrs, allowed_countries, denied_countries, updated_at FROM access_policies";
+
+fn parse_ranges(json: &str) -> Result<Vec<IpRange>, String> {
+    let ranges: Vec<String> = serde_json::from_str(json).map_err(|e| format!("Invalid CIDR list: {e}"))?;
+    ranges.iter().map(|r| IpRange::parse(r)).collect()
+}
+
+fn ranges_to_json(ranges: &[IpRange]) -> String {
+    serde_json::to_string(&ranges.iter().map(|r| r.to_string()).collect::<Vec<_>>()).unwrap_or_else(|_| "[]".to_string())
+}
+
+fn db_to_access_policy(row: DbAccessPolicy) -> Result<AccessPolicy, String> {
+    let owner_uuid = uuid::Uuid::parse_str(&row.owner_id).map_err(|e| format!("Invalid owner_id: {e}"))?;
+    let updated_at = row
+        .updated_at
+        .parse::<chrono::DateTime<chrono::Utc>>()
+        .unwrap_or_else(|_| chrono::Utc::now());
+
+    Ok(AccessPolicy {
+        owner_id: UserId::from_uuid(owner_uuid),
+        allowed_ranges: parse_ranges(&row.allowed_cidrs)?,
+        denied_ranges: parse_ranges(&row.denied_cidrs)?,
+        allowed_countries: serde_json::from_str(&row.allowed_countries).map_err(|e| format!("Invalid country list: {e}"))?,
+        denied_countries: serde_json::from_str(&row.denied_countries).map_err(|e| format!("Invalid country list: {e}"))?,
+        updated_at,
+    })
+}
+
+#[async_trait]
+impl AccessPolicyRepository for SqliteAccessPolicyRepository {
+    async fn save(&self, policy: &AccessPolicy) -> Result<(), String> {
+        let owner_id = policy.owner_id.to_string();
+        let allowed_cidrs = ranges_to_json(&policy.allowed_ranges);
+        let denied_cidrs = ranges_to_json(&policy.denied_ranges);
+        let allowed_countries = serde_json::to_string(&policy.allowed_countries).map_err(|e| e.to_string())?;
+        let denied_countries = serde_json::to_string(&policy.denied_countries).map_err(|e| e.to_string())?;
+        let updated_at = policy.updated_at.to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query(
+                "INSERT INTO access_policies (owner_id, allowed_cidrs, denied_cidrs, allowed_countries, denied_countries, updated_at) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6) \
+                 ON CONFLICT(owner_id) DO UPDATE SET allowed_cidrs=excluded.allowed_cidrs, denied_cidrs=excluded.denied_cidrs, \
+                 allowed_countries=excluded.allowed_countries, denied_countries=excluded.denied_countries, \
+                 updated_at=excluded.updated_at"
+            )
+            .bind::<diesel::sql_types::Text, _>(&owner_id)
+            .bind::<diesel::sql_types::Text, _>(&allowed_cidrs)
+            .bind::<diesel::sql_types::Text, _>(&denied_cidrs)
+            .bind::<diesel::sql_types::Text, _>(&allowed_countries)
+            .bind::<diesel::sql_types::Text, _>(&denied_countries)
+            .bind::<diesel::sql_types::Text, _>(&updated_at)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to save access policy: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find(&self, owner_id: &UserId) -> Result<Option<AccessPolicy>, String> {
+        let owner_id_str = owner_id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Option<AccessPolicy>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbAccessPolicy> = diesel::sql_query(format!("{SELECT_POLICY} WHERE owner_id = ?1"))
+                .bind::<diesel::sql_types::Text, _>(&owner_id_str)
+                .load(&mut conn)
+                .map_err(|e| format!("Database error: {e}"))?;
+
+            rows.into_iter().next().map(db_to_access_policy).transpose()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find_all(&self) -> Result<Vec<AccessPolicy>, String> {
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<AccessPolicy>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbAccessPolicy> = diesel::sql_query(SELECT_POLICY)
+                .load(&mut conn)
+                .map_err(|e| format!("Database error: {e}"))?;
+
+            rows.into_iter().map(db_to_access_policy).collect()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+}
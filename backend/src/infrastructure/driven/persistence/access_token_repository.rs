@@ -0,0 +1,176 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::SqliteConnection;
+use crate::application::ports::access_token_repository::AccessTokenRepository;
+use crate::domain::entities::access_token::{AccessToken, TokenScope};
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::driven::persistence::db_types::DbAccessToken;
+
+pub struct SqliteAccessTokenRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+}
+
+impl SqliteAccessTokenRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>) -> Self {
+        Self { pool }
+    }
+}
+
+const SELECT_TOKEN: &str = "SELECT id, user_id, name, display_prefix, token_hash, scopes, created_at, expires_at, \
+     last_used_at, revoked_at FROM access_tokens";
+
+fn db_to_access_token(row: DbAccessToken) -> Result<AccessToken, String> {
+    let parse_time = |s: &str| {
+        s.parse::<chrono::DateTime<chrono::Utc>>()
+            .unwrap_or_else(|_| chrono::Utc::now())
+    };
+
+    Ok(AccessToken {
+        id: uuid::Uuid::parse_str(&row.id).map_err(|e| format!("Invalid token id: {e}"))?,
+        user_id: uuid::Uuid::parse_str(&row.user_id)
+            .map(UserId::from_uuid)
+            .map_err(|e| format!("Invalid user_id: {e}"))?,
+        name: row.name,
+        display_prefix: row.display_prefix,
+        token_hash: row.token_hash,
+        scopes: row
+            .scopes
+            .split(',')
+            .filter(|s| !s.is_empty())
+            .map(TokenScope::from_db_str)
+            .collect::<Result<_, _>>()?,
+        created_at: parse_time(&row.created_at),
+        // An unreadable expiry must not make a token last forever
+        expires_at: row.expires_at.parse().unwrap_or(chrono::DateTime::<chrono::Utc>::MIN_UTC),
+        last_used_at: row.last_used_at.as_deref().map(parse_time),
+        revoked_at: row.revoked_at.as_deref().map(parse_time),
+    })
+}
+
+#[async_trait]
+impl AccessTokenRepository for SqliteAccessTokenRepository {
+    async fn save(&self, token: &AccessToken) -> Result<(), String> {
+        let id = token.id.to_string();
+        let user_id = token.user_id.to_string();
+        let name = token.name.clone();
+        let display_prefix = token.display_prefix.clone();
+        let token_hash = token.token_hash.clone();
+        let scopes = token.scopes.iter().map(|s| s.as_db_str()).collect::<Vec<_>>().join(",");
+        let created_at = token.created_at.to_rfc3339();
+        let expires_at = token.expires_at.to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query(
+                "INSERT INTO access_tokens (id, user_id, name, display_prefix, token_hash, scopes, created_at, expires_at) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)"
+            )
+            .bind::<diesel::sql_types::Text, _>(&id)
+            .bind::<diesel::sql_types::Text, _>(&user_id)
+            .bind::<diesel::sql_types::Text, _>(&name)
+            .bind::<diesel::sql_types::Text, _>(&display_prefix)
+            .bind::<diesel::sql_types::Text, _>(&token_hash)
+            .bind::<diesel::sql_types::Text, _>(&scopes)
+            .bind::<diesel::sql_types::Text, _>(&created_at)
+            .bind::<diesel::sql_types::Text, _>(&expires_at)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to save access token: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find_by_hash(&self, token_hash: &str) -> Result<Option<AccessToken>, String> {
+        let token_hash = token_hash.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Option<AccessToken>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbAccessToken> = diesel::sql_query(format!("{SELECT_TOKEN} WHERE token_hash = ?1"))
+                .bind::<diesel::sql_types::Text, _>(&token_hash)
+                .load(&mut conn)
+                .map_err(|e| format!("Database error: {e}"))?;
+            rows.into_iter().next().map(db_to_access_token).transpose()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn list_for_user(&self, user_id: &UserId) -> Result<Vec<AccessToken>, String> {
+        let user_id = user_id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<AccessToken>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbAccessToken> =
+                diesel::sql_query(format!("{SELECT_TOKEN} WHERE user_id = ?1 ORDER BY created_at DESC"))
+                    .bind::<diesel::sql_types::Text, _>(&user_id)
+                    .load(&mut conn)
+                    .map_err(|e| format!("Database error: {e}"))?;
+            rows.into_iter().map(db_to_access_token).collect()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn revoke(&self, user_id: &UserId, id: &uuid::Uuid) -> Result<bool, String> {
+        let user_id = user_id.to_string();
+        let id = id.to_string();
+        let now = chrono::Utc::now().to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<bool, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let updated = diesel::sql_query(
+                "UPDATE access_tokens SET revoked_at = ?1 WHERE id = ?2 AND user_id = ?3 AND revoked_at IS NULL"
+            )
+            .bind::<diesel::sql_types::Text, _>(&now)
+            .bind::<diesel::sql_types::Text, _>(&id)
+            .bind::<diesel::sql_types::Text, _>(&user_id)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to revoke access token: {e}"))?;
+            Ok(updated > 0)
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn revoke_all(&self, user_id: &UserId) -> Result<usize, String> {
+        let user_id = user_id.to_string();
+        let now = chrono::Utc::now().to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<usize, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query("UPDATE access_tokens SET revoked_at = ?1 WHERE user_id = ?2 AND revoked_at IS NULL")
+                .bind::<diesel::sql_types::Text, _>(&now)
+                .bind::<diesel::sql_types::Text, _>(&user_id)
+                .execute(&mut conn)
+                .map_err(|e| format!("Failed to revoke access tokens: {e}"))
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn record_use(&self, id: &uuid::Uuid, at: chrono::DateTime<chrono::Utc>) -> Result<(), String> {
+        let id = id.to_string();
+        let at = at.to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query("UPDATE access_tokens SET last_used_at = ?1 WHERE id = ?2")
+                .bind::<diesel::sql_types::Text, _>(&at)
+                .bind::<diesel::sql_types::Text, _>(&id)
+                .execute(&mut conn)
+                .map_err(|e| format!("Failed to update access token: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+}
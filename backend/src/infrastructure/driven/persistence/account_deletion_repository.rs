@@ -0,0 +1,210 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::SqliteConnection;
+use crate::application::ports::account_deletion_repository::AccountDeletionRepository;
+use crate::domain::entities::account_deletion::AccountDeletion;
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::driven::persistence::db_types::DbAccountDeletion;
+
+pub struct SqliteAccountDeletionRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+}
+
+impl SqliteAccountDeletionRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>) -> Self {
+        Self { pool }
+    }
+}
+
+const SELECT_DELETION: &str =
+    "SELECT user_id, requested_by, requested_at, purge_after, purged_at FROM account_deletions";
+
+/// What the purge deletes or detaches, each statement bound to the user id as `?1`. Foreign
+/// keys are not enforced, so nothing cascades on its own.
+const PURGE_STATEMENTS: [&str; 28] = [
+    "DELETE FROM webauthn_credentials WHERE user_id = ?1",
+    "DELETE FROM user_preferences WHERE user_id = ?1",
+    "DELETE FROM quality_preferences WHERE user_id = ?1",
+    "DELETE FROM notifications WHERE user_id = ?1",
+    "DELETE FROM auth_sessions WHERE user_id = ?1",
+    "DELETE FROM app_states WHERE user_id = ?1",
+    "DELETE FROM session_snapshots WHERE user_id = ?1 \
+     OR session_id IN (SELECT id FROM sessions WHERE acting_as_owner_id = ?1)",
+    "DELETE FROM session_timelines WHERE session_id IN \
+     (SELECT id FROM sessions WHERE user_id = ?1 OR acting_as_owner_id = ?1)",
+    "DELETE FROM sessions WHERE user_id = ?1 OR acting_as_owner_id = ?1",
+    "DELETE FROM file_permissions WHERE owner_id = ?1 OR client_id = ?1",
+    "DELETE FROM permission_events WHERE owner_id = ?1 OR client_id = ?1",
+    "DELETE FROM invitations WHERE owner_id = ?1",
+    "DELETE FROM access_policies WHERE owner_id = ?1",
+    "DELETE FROM client_group_members WHERE client_id = ?1 \
+     OR group_id IN (SELECT id FROM client_groups WHERE owner_id = ?1)",
+    "DELETE FROM client_groups WHERE owner_id = ?1",
+    "DELETE FROM owner_delegations WHERE owner_id = ?1 OR delegate_id = ?1",
+    "DELETE FROM file_jobs WHERE owner_id = ?1",
+    "DELETE FROM upload_sessions WHERE owner_id = ?1",
+    "DELETE FROM bandwidth_caps WHERE owner_id = ?1 OR user_id = ?1",
+    "DELETE FROM bandwidth_usage WHERE owner_id = ?1 OR user_id = ?1",
+    "DELETE FROM data_exports WHERE subject_id = ?1 OR requested_by = ?1",
+    "DELETE FROM vault_imports WHERE owner_id = ?1",
+    "DELETE FROM external_identities WHERE user_id = ?1",
+    "DELETE FROM provisioning_tokens WHERE owner_id = ?1",
+    "DELETE FROM provisioning_requests WHERE owner_id = ?1",
+    "DELETE FROM access_tokens WHERE user_id = ?1",
+    "DELETE FROM legal_holds WHERE owner_id = ?1",
+    "DELETE FROM app_settings WHERE owner_id = ?1",
+    "UPDATE app_crashes SET user_id = NULL WHERE user_id = ?1",
+];
+
+fn parse_user_id(s: &str, field: &str) -> Result<UserId, String> {
+    uuid::Uuid::parse_str(s)
+        .map(UserId::from_uuid)
+        .map_err(|e| format!("Invalid {field}: {e}"))
+}
+
+fn parse_time(s: &str, field: &str) -> Result<DateTime<Utc>, String> {
+    s.parse::<DateTime<Utc>>().map_err(|e| format!("Invalid {field}: {e}"))
+}
+
+fn db_to_account_deletion(row: DbAccountDeletion) -> Result<AccountDeletion, String> {
+    Ok(AccountDeletion {
+        user_id: parse_user_id(&row.user_id, "user_id")?,
+        requested_by: parse_user_id(&row.requested_by, "requested_by")?,
+        requested_at: parse_time(&row.requested_at, "requested_at")?,
+        purge_after: parse_time(&row.purge_after, "purge_after")?,
+        purged_at: row.purged_at.as_deref().map(|s| parse_time(s, "purged_at")).transpose()?,
+    })
+}
+
+#[async_trait]
+impl AccountDeletionRepository for SqliteAccountDeletionRepository {
+    async fn save(&self, deletion: &AccountDeletion) -> Result<(), String> {
+        let user_id = deletion.user_id.to_string();
+        let requested_by = deletion.requested_by.to_string();
+        let requested_at = deletion.requested_at.to_rfc3339();
+        let purge_after = deletion.purge_after.to_rfc3339();
+        let purged_at = deletion.purged_at.map(|at| at.to_rfc3339());
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query(
+                "INSERT INTO account_deletions (user_id, requested_by, requested_at, purge_after, purged_at) \
+                 VALUES (?1, ?2, ?3, ?4, ?5) \
+                 ON CONFLICT(user_id) DO UPDATE SET requested_by = excluded.requested_by, \
+                 requested_at = excluded.requested_at, purge_after = excluded.purge_after, purged_at = excluded.purged_at"
+            )
+            .bind::<diesel::sql_types::Text, _>(&user_id)
+            .bind::<diesel::sql_types::Text, _>(&requested_by)
+            .bind::<diesel::sql_types::Text, _>(&requested_at)
+            .bind::<diesel::sql_types::Text, _>(&purge_after)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&purged_at)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to save account deletion: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find(&self, user_id: &UserId) -> Result<Option<AccountDeletion>, String> {
+        let user_id = user_id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Option<AccountDeletion>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbAccountDeletion> = diesel::sql_query(format!("{SELECT_DELETION} WHERE user_id = ?1"))
+                .bind::<diesel::sql_types::Text, _>(&user_id)
+                .load(&mut conn)
+                .map_err(|e| format!("Database error: {e}"))?;
+
+            rows.into_iter().next().map(db_to_account_deletion).transpose()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn delete(&self, user_id: &UserId) -> Result<(), String> {
+        let user_id = user_id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query("DELETE FROM account_deletions WHERE user_id = ?1")
+                .bind::<diesel::sql_types::Text, _>(&user_id)
+                .execute(&mut conn)
+                .map_err(|e| format!("Failed to delete account deletion: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find_due(&self, now: DateTime<Utc>) -> Result<Vec<AccountDeletion>, String> {
+        let now = now.to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<AccountDeletion>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbAccountDeletion> = diesel::sql_query(format!(
+                "{SELECT_DELETION} WHERE purged_at IS NULL AND purge_after <= ?1 ORDER BY purge_after"
+            ))
+            .bind::<diesel::sql_types::Text, _>(&now)
+            .load(&mut conn)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+            rows.into_iter().map(db_to_account_deletion).collect()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn purge(&self, user_id: &UserId, email: &str, at: DateTime<Utc>) -> Result<(), String> {
+        let user_id = user_id.to_string();
+        let email = email.to_string();
+        let at = at.to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            conn.transaction(|conn| {
+                for statement in PURGE_STATEMENTS {
+                    diesel::sql_query(statement)
+                        .bind::<diesel::sql_types::Text, _>(&user_id)
+                        .execute(conn)?;
+                }
+                // Audit entries stay, without anything pointing back at the user
+                diesel::sql_query("UPDATE audit_events SET user_id = NULL WHERE user_id = ?1")
+                    .bind::<diesel::sql_types::Text, _>(&user_id)
+                    .execute(conn)?;
+                diesel::sql_query("UPDATE audit_events SET owner_id = NULL WHERE owner_id = ?1")
+                    .bind::<diesel::sql_types::Text, _>(&user_id)
+                    .execute(conn)?;
+                diesel::sql_query(
+                    "UPDATE audit_events SET payload = REPLACE(REPLACE(payload, ?1, '[deleted]'), ?2, '[deleted]') \
+                     WHERE instr(payload, ?1) > 0 OR instr(payload, ?2) > 0"
+                )
+                .bind::<diesel::sql_types::Text, _>(&user_id)
+                .bind::<diesel::sql_types::Text, _>(&email)
+                .execute(conn)?;
+                diesel::sql_query(
+                    "UPDATE users SET email = 'deleted-' || id || '@deleted.invalid', display_name = 'Deleted user', \
+                     roles = '[]', status = 'deleted' WHERE id = ?1"
+                )
+                .bind::<diesel::sql_types::Text, _>(&user_id)
+                .execute(conn)?;
+                diesel::sql_query("UPDATE account_deletions SET purged_at = ?1 WHERE user_id = ?2")
+                    .bind::<diesel::sql_types::Text, _>(&at)
+                    .bind::<diesel::sql_types::Text, _>(&user_id)
+                    .execute(conn)
+            })
+            .map_err(|e| format!("Failed to purge account: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+}
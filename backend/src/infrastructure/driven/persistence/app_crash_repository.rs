@@ -0,0 +1,109 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::SqliteConnection;
+use crate::application::ports::app_crash_repository::AppCrashRepository;
+use crate::application::ports::pagination::{Cursor, Page, PageRequest};
+use crate::domain::entities::app_crash::AppCrash;
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::driven::persistence::db_types::DbAppCrash;
+use crate::infrastructure::driven::persistence::paging::{self, Filters};
+
+pub struct SqliteAppCrashRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+}
+
+impl SqliteAppCrashRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>) -> Self {
+        Self { pool }
+    }
+}
+
+fn db_to_app_crash(row: DbAppCrash) -> Result<AppCrash, String> {
+    Ok(AppCrash {
+        id: uuid::Uuid::parse_str(&row.id).map_err(|e| format!("Invalid crash id: {e}"))?,
+        session_id: row.session_id,
+        user_id: row
+            .user_id
+            .as_deref()
+            .map(|s| uuid::Uuid::parse_str(s).map(UserId::from_uuid))
+            .transpose()
+            .map_err(|e| format!("Invalid user_id: {e}"))?,
+        app_id: row.app_id,
+        message: row.message,
+        location: row.location,
+        backtrace: row.backtrace,
+        recent_input: serde_json::from_str(&row.recent_input).unwrap_or_default(),
+        frames: row.frames.max(0) as u64,
+        crashed_at: row
+            .crashed_at
+            .parse::<chrono::DateTime<chrono::Utc>>()
+            .map_err(|e| format!("Invalid crashed_at: {e}"))?,
+    })
+}
+
+#[async_trait]
+impl AppCrashRepository for SqliteAppCrashRepository {
+    async fn record(&self, crash: &AppCrash) -> Result<(), String> {
+        let id = crash.id.to_string();
+        let session_id = crash.session_id.clone();
+        let user_id = crash.user_id.as_ref().map(|u| u.to_string());
+        let app_id = crash.app_id.clone();
+        let message = crash.message.clone();
+        let location = crash.location.clone();
+        let backtrace = crash.backtrace.clone();
+        let recent_input = serde_json::to_string(&crash.recent_input).map_err(|e| e.to_string())?;
+        let frames = crash.frames.min(i64::MAX as u64) as i64;
+        let crashed_at = crash.crashed_at.to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query(
+                "INSERT INTO app_crashes (id, session_id, user_id, app_id, message, location, backtrace, recent_input, frames, crashed_at) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)"
+            )
+            .bind::<diesel::sql_types::Text, _>(&id)
+            .bind::<diesel::sql_types::Text, _>(&session_id)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&user_id)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&app_id)
+            .bind::<diesel::sql_types::Text, _>(&message)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&location)
+            .bind::<diesel::sql_types::Text, _>(&backtrace)
+            .bind::<diesel::sql_types::Text, _>(&recent_input)
+            .bind::<diesel::sql_types::BigInt, _>(frames)
+            .bind::<diesel::sql_types::Text, _>(&crashed_at)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to record app crash: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn list(&self, app_id: Option<&str>, page: &PageRequest) -> Result<Page<AppCrash>, String> {
+        let mut filters = Filters::new();
+        if let Some(app_id) = app_id {
+            filters.add("app_id = ?", [app_id.to_string()]);
+        }
+        let page = page.clone();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Page<AppCrash>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let (rows, total) = paging::load_page::<DbAppCrash>(
+                &mut conn,
+                "app_crashes",
+                "id, session_id, user_id, app_id, message, location, backtrace, recent_input, frames, crashed_at",
+                &filters,
+                "crashed_at",
+                &page,
+            )?;
+            Page::from_rows(rows, page.limit, total, |row| Cursor::new(&row.crashed_at, &row.id))
+                .try_map(db_to_app_crash)
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+}
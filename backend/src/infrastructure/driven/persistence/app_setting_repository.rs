@@ -0,0 +1,108 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::SqliteConnection;
+use crate::application::ports::app_setting_repository::AppSettingRepository;
+use crate::domain::entities::app_setting::AppSetting;
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::driven::persistence::db_types::DbAppSetting;
+
+pub struct SqliteAppSettingRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+}
+
+impl SqliteAppSettingRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>) -> Self {
+        Self { pool }
+    }
+}
+
+const SELECT_SETTING: &str =
+    "SELECT owner_id, app_id, enabled, allowed_roles, allowed_users, updated_at FROM app_settings";
+
+fn db_to_app_setting(row: DbAppSetting) -> Result<AppSetting, String> {
+    let owner_uuid = uuid::Uuid::parse_str(&row.owner_id).map_err(|e| format!("Invalid owner_id: {e}"))?;
+    let updated_at = row
+        .updated_at
+        .parse::<chrono::DateTime<chrono::Utc>>()
+        .unwrap_or_else(|_| chrono::Utc::now());
+
+    Ok(AppSetting {
+        owner_id: UserId::from_uuid(owner_uuid),
+        app_id: row.app_id,
+        enabled: row.enabled,
+        allowed_roles: serde_json::from_str(&row.allowed_roles).map_err(|e| format!("Invalid role list: {e}"))?,
+        allowed_users: serde_json::from_str(&row.allowed_users).map_err(|e| format!("Invalid user list: {e}"))?,
+        updated_at,
+    })
+}
+
+#[async_trait]
+impl AppSettingRepository for SqliteAppSettingRepository {
+    async fn save(&self, setting: &AppSetting) -> Result<(), String> {
+        let owner_id = setting.owner_id.to_string();
+        let app_id = setting.app_id.clone();
+        let enabled = setting.enabled;
+        let allowed_roles = serde_json::to_string(&setting.allowed_roles).map_err(|e| e.to_string())?;
+        let allowed_users = serde_json::to_string(&setting.allowed_users).map_err(|e| e.to_string())?;
+        let updated_at = setting.updated_at.to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query(
+                "INSERT INTO app_settings (owner_id, app_id, enabled, allowed_roles, allowed_users, updated_at) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6) \
+                 ON CONFLICT(owner_id, app_id) DO UPDATE SET enabled=excluded.enabled, \
+                 allowed_roles=excluded.allowed_roles, allowed_users=excluded.allowed_users, \
+                 updated_at=excluded.updated_at"
+            )
+            .bind::<diesel::sql_types::Text, _>(&owner_id)
+            .bind::<diesel::sql_types::Text, _>(&app_id)
+            .bind::<diesel::sql_types::Bool, _>(enabled)
+            .bind::<diesel::sql_types::Text, _>(&allowed_roles)
+            .bind::<diesel::sql_types::Text, _>(&allowed_users)
+            .bind::<diesel::sql_types::Text, _>(&updated_at)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to save app setting: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find(&self, owner_id: &UserId, app_id: &str) -> Result<Option<AppSetting>, String> {
+        let owner_id = owner_id.to_string();
+        let app_id = app_id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Option<AppSetting>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbAppSetting> = diesel::sql_query(format!("{SELECT_SETTING} WHERE owner_id = ?1 AND app_id = ?2"))
+                .bind::<diesel::sql_types::Text, _>(&owner_id)
+                .bind::<diesel::sql_types::Text, _>(&app_id)
+                .load(&mut conn)
+                .map_err(|e| format!("Database error: {e}"))?;
+            rows.into_iter().next().map(db_to_app_setting).transpose()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn list_for_owner(&self, owner_id: &UserId) -> Result<Vec<AppSetting>, String> {
+        let owner_id = owner_id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<AppSetting>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbAppSetting> = diesel::sql_query(format!("{SELECT_SETTING} WHERE owner_id = ?1 ORDER BY app_id"))
+                .bind::<diesel::sql_types::Text, _>(&owner_id)
+                .load(&mut conn)
+                .map_err(|e| format!("Database error: {e}"))?;
+            rows.into_iter().map(db_to_app_setting).collect()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+}
@@ -0,0 +1,137 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::SqliteConnection;
+use crate::application::ports::app_state_repository::AppStateRepository;
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::driven::persistence::db_types::{DbAppStateEntry, DbCount};
+
+pub struct SqliteAppStateRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+}
+
+impl SqliteAppStateRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl AppStateRepository for SqliteAppStateRepository {
+    async fn find_all(&self, user_id: &UserId, app_id: &str) -> Result<Vec<(String, String)>, String> {
+        let user_id = user_id.to_string();
+        let app_id = app_id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<(String, String)>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbAppStateEntry> = diesel::sql_query(
+                "SELECT key, value FROM app_states WHERE user_id = ?1 AND app_id = ?2 ORDER BY key"
+            )
+            .bind::<diesel::sql_types::Text, _>(&user_id)
+            .bind::<diesel::sql_types::Text, _>(&app_id)
+            .load(&mut conn)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+            Ok(rows.into_iter().map(|row| (row.key, row.value)).collect())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find(&self, user_id: &UserId, app_id: &str, key: &str) -> Result<Option<String>, String> {
+        let user_id = user_id.to_string();
+        let app_id = app_id.to_string();
+        let key = key.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Option<String>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbAppStateEntry> = diesel::sql_query(
+                "SELECT key, value FROM app_states WHERE user_id = ?1 AND app_id = ?2 AND key = ?3"
+            )
+            .bind::<diesel::sql_types::Text, _>(&user_id)
+            .bind::<diesel::sql_types::Text, _>(&app_id)
+            .bind::<diesel::sql_types::Text, _>(&key)
+            .load(&mut conn)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+            Ok(rows.into_iter().next().map(|row| row.value))
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn save(&self, user_id: &UserId, app_id: &str, key: &str, value: &str) -> Result<(), String> {
+        let user_id = user_id.to_string();
+        let app_id = app_id.to_string();
+        let key = key.to_string();
+        let value = value.to_string();
+        let updated_at = Utc::now().to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query(
+                "INSERT INTO app_states (user_id, app_id, key, value, updated_at) VALUES (?1, ?2, ?3, ?4, ?5) \
+                 ON CONFLICT(user_id, app_id, key) DO UPDATE SET value=excluded.value, updated_at=excluded.updated_at"
+            )
+            .bind::<diesel::sql_types::Text, _>(&user_id)
+            .bind::<diesel::sql_types::Text, _>(&app_id)
+            .bind::<diesel::sql_types::Text, _>(&key)
+            .bind::<diesel::sql_types::Text, _>(&value)
+            .bind::<diesel::sql_types::Text, _>(&updated_at)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to save app state: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn delete(&self, user_id: &UserId, app_id: &str, key: &str) -> Result<(), String> {
+        let user_id = user_id.to_string();
+        let app_id = app_id.to_string();
+        let key = key.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query("DELETE FROM app_states WHERE user_id = ?1 AND app_id = ?2 AND key = ?3")
+                .bind::<diesel::sql_types::Text, _>(&user_id)
+                .bind::<diesel::sql_types::Text, _>(&app_id)
+                .bind::<diesel::sql_types::Text, _>(&key)
+                .execute(&mut conn)
+                .map_err(|e| format!("Failed to delete app state: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn size_without(&self, user_id: &UserId, app_id: &str, key: &str) -> Result<usize, String> {
+        let user_id = user_id.to_string();
+        let app_id = app_id.to_string();
+        let key = key.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<usize, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let size: DbCount = diesel::sql_query(
+                "SELECT COALESCE(SUM(LENGTH(CAST(key AS BLOB)) + LENGTH(CAST(value AS BLOB))), 0) AS count \
+                 FROM app_states WHERE user_id = ?1 AND app_id = ?2 AND key != ?3"
+            )
+            .bind::<diesel::sql_types::Text, _>(&user_id)
+            .bind::<diesel::sql_types::Text, _>(&app_id)
+            .bind::<diesel::sql_types::Text, _>(&key)
+            .get_result(&mut conn)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+            Ok(size.count.max(0) as usize)
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+}
@@ -0,0 +1,114 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::SqliteConnection;
+use crate::application::ports::audit_repository::{AuditFilter, AuditRepository};
+use crate::application::ports::pagination::{Cursor, Page, PageRequest};
+use crate::domain::entities::audit_event::AuditEvent;
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::driven::persistence::db_types::DbAuditEvent;
+use crate::infrastructure::driven::persistence::paging::{self, Filters};
+
+pub struct SqliteAuditRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+}
+
+impl SqliteAuditRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>) -> Self {
+        Self { pool }
+    }
+}
+
+fn db_to_audit_event(row: DbAuditEvent) -> Result<AuditEvent, String> {
+    let parse_user = |value: Option<String>, field: &str| {
+        value
+            .as_deref()
+            .map(|s| uuid::Uuid::parse_str(s).map(UserId::from_uuid))
+            .transpose()
+            .map_err(|e| format!("Invalid {field}: {e}"))
+    };
+    Ok(AuditEvent {
+        id: uuid::Uuid::parse_str(&row.id).map_err(|e| format!("Invalid audit event id: {e}"))?,
+        session_id: row.session_id,
+        user_id: parse_user(row.user_id, "user_id")?,
+        owner_id: parse_user(row.owner_id, "owner_id")?,
+        event_type: row.event_type,
+        payload: serde_json::from_str(&row.payload).unwrap_or(serde_json::Value::Null),
+        created_at: row
+            .created_at
+            .parse::<chrono::DateTime<chrono::Utc>>()
+            .map_err(|e| format!("Invalid created_at: {e}"))?,
+    })
+}
+
+#[async_trait]
+impl AuditRepository for SqliteAuditRepository {
+    async fn record(&self, event: &AuditEvent) -> Result<(), String> {
+        let id = event.id.to_string();
+        let session_id = event.session_id.clone();
+        let user_id = event.user_id.as_ref().map(|u| u.to_string());
+        let owner_id = event.owner_id.as_ref().map(|u| u.to_string());
+        let event_type = event.event_type.clone();
+        let payload = event.payload.to_string();
+        let created_at = event.created_at.to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query(
+                "INSERT INTO audit_events (id, session_id, user_id, owner_id, event_type, payload, created_at) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)"
+            )
+            .bind::<diesel::sql_types::Text, _>(&id)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&session_id)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&user_id)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&owner_id)
+            .bind::<diesel::sql_types::Text, _>(&event_type)
+            .bind::<diesel::sql_types::Text, _>(&payload)
+            .bind::<diesel::sql_types::Text, _>(&created_at)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to record audit event: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn list(&self, filter: &AuditFilter, page: &PageRequest) -> Result<Page<AuditEvent>, String> {
+        let mut filters = Filters::new();
+        if let Some(owner_id) = &filter.owner_id {
+            filters.add("owner_id = ?", [owner_id.to_string()]);
+        }
+        if let Some(user_id) = &filter.user_id {
+            filters.add("user_id = ?", [user_id.to_string()]);
+        }
+        if let Some(event_type) = &filter.event_type {
+            filters.add("event_type = ?", [event_type.clone()]);
+        }
+        if let Some(since) = filter.since {
+            filters.add("created_at >= ?", [since.to_rfc3339()]);
+        }
+        if let Some(until) = filter.until {
+            filters.add("created_at < ?", [until.to_rfc3339()]);
+        }
+        let page = page.clone();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Page<AuditEvent>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let (rows, total) = paging::load_page::<DbAuditEvent>(
+                &mut conn,
+                "audit_events",
+                "id, session_id, user_id, owner_id, event_type, payload, created_at",
+                &filters,
+                "created_at",
+                &page,
+            )?;
+            Page::from_rows(rows, page.limit, total, |row| Cursor::new(&row.created_at, &row.id))
+                .try_map(db_to_audit_event)
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+}
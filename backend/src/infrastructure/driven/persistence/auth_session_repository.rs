@@ -0,0 +1,154 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::SqliteConnection;
+use uuid::Uuid;
+use crate::application::ports::auth_session_repository::AuthSessionRepository;
+use crate::domain::entities::auth_session::AuthSession;
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::driven::persistence::db_types::DbAuthSession;
+
+const COLUMNS: &str = "id, user_id, user_agent, ip, created_at, last_seen_at, expires_at, revoked_at";
+
+pub struct SqliteAuthSessionRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+}
+
+impl SqliteAuthSessionRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>) -> Self {
+        Self { pool }
+    }
+}
+
+fn parse_time(value: &str, field: &str) -> Result<DateTime<Utc>, String> {
+    value.parse::<DateTime<Utc>>().map_err(|e| format!("Invalid {field}: {e}"))
+}
+
+fn db_to_auth_session(row: DbAuthSession) -> Result<AuthSession, String> {
+    Ok(AuthSession {
+        id: Uuid::parse_str(&row.id).map_err(|e| format!("Invalid auth session id: {e}"))?,
+        user_id: UserId::from_uuid(Uuid::parse_str(&row.user_id).map_err(|e| format!("Invalid user_id: {e}"))?),
+        user_agent: row.user_agent,
+        ip: row.ip,
+        created_at: parse_time(&row.created_at, "created_at")?,
+        last_seen_at: parse_time(&row.last_seen_at, "last_seen_at")?,
+        expires_at: parse_time(&row.expires_at, "expires_at")?,
+        revoked_at: row.revoked_at.as_deref().map(|s| parse_time(s, "revoked_at")).transpose()?,
+    })
+}
+
+#[async_trait]
+impl AuthSessionRepository for SqliteAuthSessionRepository {
+    async fn create(&self, session: &AuthSession) -> Result<(), String> {
+        let id = session.id.to_string();
+        let user_id = session.user_id.to_string();
+        let user_agent = session.user_agent.clone();
+        let ip = session.ip.clone();
+        let created_at = session.created_at.to_rfc3339();
+        let last_seen_at = session.last_seen_at.to_rfc3339();
+        let expires_at = session.expires_at.to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query(
+                "INSERT INTO auth_sessions (id, user_id, user_agent, ip, created_at, last_seen_at, expires_at) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)"
+            )
+            .bind::<diesel::sql_types::Text, _>(&id)
+            .bind::<diesel::sql_types::Text, _>(&user_id)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&user_agent)
+            .bind::<diesel::sql_types::Text, _>(&ip)
+            .bind::<diesel::sql_types::Text, _>(&created_at)
+            .bind::<diesel::sql_types::Text, _>(&last_seen_at)
+            .bind::<diesel::sql_types::Text, _>(&expires_at)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to create auth session: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn touch(&self, id: &Uuid, at: DateTime<Utc>) -> Result<(), String> {
+        let id = id.to_string();
+        let at = at.to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query("UPDATE auth_sessions SET last_seen_at = ?1 WHERE id = ?2")
+                .bind::<diesel::sql_types::Text, _>(&at)
+                .bind::<diesel::sql_types::Text, _>(&id)
+                .execute(&mut conn)
+                .map_err(|e| format!("Failed to touch auth session: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn list_active(&self, user_id: &UserId) -> Result<Vec<AuthSession>, String> {
+        let user_id = user_id.to_string();
+        let now = Utc::now().to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<AuthSession>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbAuthSession> = diesel::sql_query(format!(
+                "SELECT {COLUMNS} FROM auth_sessions \
+                 WHERE user_id = ?1 AND revoked_at IS NULL AND expires_at > ?2 \
+                 ORDER BY last_seen_at DESC"
+            ))
+            .bind::<diesel::sql_types::Text, _>(&user_id)
+            .bind::<diesel::sql_types::Text, _>(&now)
+            .load(&mut conn)
+            .map_err(|e| format!("Failed to list auth sessions: {e}"))?;
+            rows.into_iter().map(db_to_auth_session).collect()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn revoke(&self, user_id: &UserId, id: &Uuid, at: DateTime<Utc>) -> Result<bool, String> {
+        let user_id = user_id.to_string();
+        let id = id.to_string();
+        let at = at.to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<bool, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let updated = diesel::sql_query(
+                "UPDATE auth_sessions SET revoked_at = ?1 WHERE id = ?2 AND user_id = ?3 AND revoked_at IS NULL"
+            )
+            .bind::<diesel::sql_types::Text, _>(&at)
+            .bind::<diesel::sql_types::Text, _>(&id)
+            .bind::<diesel::sql_types::Text, _>(&user_id)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to revoke auth session: {e}"))?;
+            Ok(updated > 0)
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn revoked_unexpired(&self, now: DateTime<Utc>) -> Result<Vec<Uuid>, String> {
+        let now = now.to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<Uuid>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbAuthSession> = diesel::sql_query(format!(
+                "SELECT {COLUMNS} FROM auth_sessions WHERE revoked_at IS NOT NULL AND expires_at > ?1"
+            ))
+            .bind::<diesel::sql_types::Text, _>(&now)
+            .load(&mut conn)
+            .map_err(|e| format!("Failed to load revoked auth sessions: {e}"))?;
+            Ok(rows.iter().filter_map(|row| Uuid::parse_str(&row.id).ok()).collect())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+}
@@ -0,0 +1,195 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::SqliteConnection;
+use crate::application::ports::bandwidth_repository::{BandwidthRepository, BandwidthUsage};
+use crate::domain::value_objects::{BandwidthCaps, UserId};
+use crate::infrastructure::driven::persistence::db_types::{DbBandwidthCaps, DbBandwidthUsage, DbCount};
+
+pub struct SqliteBandwidthRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+}
+
+impl SqliteBandwidthRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>) -> Self {
+        Self { pool }
+    }
+}
+
+fn db_to_caps(session_max_bitrate: Option<i64>, monthly_bytes: Option<i64>, throttle_bitrate: Option<i64>) -> BandwidthCaps {
+    BandwidthCaps {
+        session_max_bitrate: session_max_bitrate.map(|v| v.clamp(0, u32::MAX as i64) as u32),
+        monthly_bytes: monthly_bytes.map(|v| v.max(0) as u64),
+        throttle_bitrate: throttle_bitrate.map(|v| v.clamp(0, u32::MAX as i64) as u32),
+    }
+}
+
+fn db_to_usage(row: DbBandwidthUsage) -> Result<BandwidthUsage, String> {
+    let caps = db_to_caps(row.session_max_bitrate, row.monthly_bytes, row.throttle_bitrate);
+    let bytes_sent = row.bytes_sent.max(0) as u64;
+    Ok(BandwidthUsage {
+        user_id: uuid::Uuid::parse_str(&row.user_id)
+            .map(UserId::from_uuid)
+            .map_err(|e| format!("Invalid user_id: {e}"))?,
+        bytes_sent,
+        caps,
+        over_cap: caps.is_over(bytes_sent),
+    })
+}
+
+#[async_trait]
+impl BandwidthRepository for SqliteBandwidthRepository {
+    async fn find_caps(&self, owner_id: &UserId, user_id: &UserId) -> Result<Option<BandwidthCaps>, String> {
+        let owner_id = owner_id.to_string();
+        let user_id = user_id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Option<BandwidthCaps>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbBandwidthCaps> = diesel::sql_query(
+                "SELECT session_max_bitrate, monthly_bytes, throttle_bitrate FROM bandwidth_caps WHERE owner_id = ?1 AND user_id = ?2"
+            )
+            .bind::<diesel::sql_types::Text, _>(&owner_id)
+            .bind::<diesel::sql_types::Text, _>(&user_id)
+            .load(&mut conn)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+            Ok(rows
+                .into_iter()
+                .next()
+                .map(|row| db_to_caps(row.session_max_bitrate, row.monthly_bytes, row.throttle_bitrate)))
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn save_caps(&self, owner_id: &UserId, user_id: &UserId, caps: &BandwidthCaps) -> Result<(), String> {
+        let owner_id = owner_id.to_string();
+        let user_id = user_id.to_string();
+        let session_max_bitrate = caps.session_max_bitrate.map(|v| v as i64);
+        let monthly_bytes = caps.monthly_bytes.map(|v| v.min(i64::MAX as u64) as i64);
+        let throttle_bitrate = caps.throttle_bitrate.map(|v| v as i64);
+        let updated_at = Utc::now().to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query(
+                "INSERT INTO bandwidth_caps (owner_id, user_id, session_max_bitrate, monthly_bytes, throttle_bitrate, updated_at) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6) \
+                 ON CONFLICT(owner_id, user_id) DO UPDATE SET session_max_bitrate=excluded.session_max_bitrate, \
+                 monthly_bytes=excluded.monthly_bytes, throttle_bitrate=excluded.throttle_bitrate, updated_at=excluded.updated_at"
+            )
+            .bind::<diesel::sql_types::Text, _>(&owner_id)
+            .bind::<diesel::sql_types::Text, _>(&user_id)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::BigInt>, _>(session_max_bitrate)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::BigInt>, _>(monthly_bytes)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::BigInt>, _>(throttle_bitrate)
+            .bind::<diesel::sql_types::Text, _>(&updated_at)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to save bandwidth caps: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn delete_caps(&self, owner_id: &UserId, user_id: &UserId) -> Result<(), String> {
+        let owner_id = owner_id.to_string();
+        let user_id = user_id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query("DELETE FROM bandwidth_caps WHERE owner_id = ?1 AND user_id = ?2")
+                .bind::<diesel::sql_types::Text, _>(&owner_id)
+                .bind::<diesel::sql_types::Text, _>(&user_id)
+                .execute(&mut conn)
+                .map_err(|e| format!("Failed to delete bandwidth caps: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn add_usage(&self, owner_id: &UserId, user_id: &UserId, month: &str, bytes: u64) -> Result<(), String> {
+        let owner_id = owner_id.to_string();
+        let user_id = user_id.to_string();
+        let month = month.to_string();
+        let bytes = bytes.min(i64::MAX as u64) as i64;
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query(
+                "INSERT INTO bandwidth_usage (owner_id, user_id, month, bytes_sent) VALUES (?1, ?2, ?3, ?4) \
+                 ON CONFLICT(owner_id, user_id, month) DO UPDATE SET bytes_sent = bytes_sent + excluded.bytes_sent"
+            )
+            .bind::<diesel::sql_types::Text, _>(&owner_id)
+            .bind::<diesel::sql_types::Text, _>(&user_id)
+            .bind::<diesel::sql_types::Text, _>(&month)
+            .bind::<diesel::sql_types::BigInt, _>(bytes)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to record bandwidth usage: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn usage(&self, owner_id: &UserId, user_id: &UserId, month: &str) -> Result<u64, String> {
+        let owner_id = owner_id.to_string();
+        let user_id = user_id.to_string();
+        let month = month.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<u64, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let used: DbCount = diesel::sql_query(
+                "SELECT COALESCE(SUM(bytes_sent), 0) AS count FROM bandwidth_usage WHERE owner_id = ?1 AND user_id = ?2 AND month = ?3"
+            )
+            .bind::<diesel::sql_types::Text, _>(&owner_id)
+            .bind::<diesel::sql_types::Text, _>(&user_id)
+            .bind::<diesel::sql_types::Text, _>(&month)
+            .get_result(&mut conn)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+            Ok(used.count.max(0) as u64)
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn list_usage(&self, owner_id: &UserId, month: &str) -> Result<Vec<BandwidthUsage>, String> {
+        let owner_id = owner_id.to_string();
+        let month = month.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<BandwidthUsage>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbBandwidthUsage> = diesel::sql_query(
+                "SELECT clients.user_id, COALESCE(u.bytes_sent, 0) AS bytes_sent, \
+                 c.session_max_bitrate, c.monthly_bytes, c.throttle_bitrate \
+                 FROM (SELECT user_id FROM bandwidth_caps WHERE owner_id = ?1 \
+                       UNION SELECT user_id FROM bandwidth_usage WHERE owner_id = ?2 AND month = ?3) clients \
+                 LEFT JOIN bandwidth_usage u ON u.owner_id = ?4 AND u.user_id = clients.user_id AND u.month = ?5 \
+                 LEFT JOIN bandwidth_caps c ON c.owner_id = ?6 AND c.user_id = clients.user_id \
+                 ORDER BY bytes_sent DESC, clients.user_id"
+            )
+            .bind::<diesel::sql_types::Text, _>(&owner_id)
+            .bind::<diesel::sql_types::Text, _>(&owner_id)
+            .bind::<diesel::sql_types::Text, _>(&month)
+            .bind::<diesel::sql_types::Text, _>(&owner_id)
+            .bind::<diesel::sql_types::Text, _>(&month)
+            .bind::<diesel::sql_types::Text, _>(&owner_id)
+            .load(&mut conn)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+            rows.into_iter().map(db_to_usage).collect()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+}
@@ -0,0 +1,102 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::SqliteConnection;
+use crate::application::ports::branding_repository::BrandingRepository;
+use crate::domain::entities::owner_branding::OwnerBranding;
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::driven::persistence::db_types::DbOwnerBranding;
+
+pub struct SqliteBrandingRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+}
+
+impl SqliteBrandingRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>) -> Self {
+        Self { pool }
+    }
+}
+
+fn db_to_branding(row: DbOwnerBranding) -> Result<OwnerBranding, String> {
+    let owner_uuid = uuid::Uuid::parse_str(&row.owner_id).map_err(|e| format!("Invalid owner_id: {e}"))?;
+    let updated_at = row
+        .updated_at
+        .parse::<chrono::DateTime<chrono::Utc>>()
+        .unwrap_or_else(|_| chrono::Utc::now());
+
+    Ok(OwnerBranding {
+        owner_id: UserId::from_uuid(owner_uuid),
+        display_name: row.display_name,
+        logo: row.logo,
+        accent_color: row.accent_color,
+        updated_at,
+    })
+}
+
+#[async_trait]
+impl BrandingRepository for SqliteBrandingRepository {
+    async fn save(&self, branding: &OwnerBranding) -> Result<(), String> {
+        let owner_id = branding.owner_id.to_string();
+        let display_name = branding.display_name.clone();
+        let logo = branding.logo.clone();
+        let accent_color = branding.accent_color.clone();
+        let updated_at = branding.updated_at.to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query(
+                "INSERT INTO owner_branding (owner_id, display_name, logo, accent_color, updated_at) \
+                 VALUES (?1, ?2, ?3, ?4, ?5) \
+                 ON CONFLICT(owner_id) DO UPDATE SET display_name=excluded.display_name, logo=excluded.logo, \
+                 accent_color=excluded.accent_color, updated_at=excluded.updated_at"
+            )
+            .bind::<diesel::sql_types::Text, _>(&owner_id)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&display_name)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&logo)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&accent_color)
+            .bind::<diesel::sql_types::Text, _>(&updated_at)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to save branding: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find(&self, owner_id: &UserId) -> Result<Option<OwnerBranding>, String> {
+        let owner_id_str = owner_id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Option<OwnerBranding>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbOwnerBranding> = diesel::sql_query(
+                "SELECT owner_id, display_name, logo, accent_color, updated_at FROM owner_branding WHERE owner_id = ?1"
+            )
+            .bind::<diesel::sql_types::Text, _>(&owner_id_str)
+            .load(&mut conn)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+            rows.into_iter().next().map(db_to_branding).transpose()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn delete(&self, owner_id: &UserId) -> Result<(), String> {
+        let owner_id_str = owner_id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query("DELETE FROM owner_branding WHERE owner_id = ?1")
+                .bind::<diesel::sql_types::Text, _>(&owner_id_str)
+                .execute(&mut conn)
+                .map_err(|e| format!("Failed to delete branding: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+}
@@ -61,4 +61,80 @@ impl ChallengeRepository for RedisChallengeRepository {
             .await
             .map_err(|_| "Invalid or expired challenge".to_string())
     }
+
+    async fn save_discoverable_challenge(&self, challenge_id: &str, state: &str, ttl_seconds: u64) -> Result<(), String> {
+        let mut conn = self.client.get_multiplexed_async_connection()
+            .await
+            .map_err(|e| format!("Redis connection error: {}", e))?;
+        
+        let key = format!("webauthn:discoverable:{}", challenge_id);
+        conn.set_ex::<_, _, ()>(&key, state, ttl_seconds)
+            .await
+            .map_err(|e| format!("Failed to save discoverable challenge: {}", e))?;
+        
+        Ok(())
+    }
+    
+    async fn get_and_delete_discoverable_challenge(&self, challenge_id: &str) -> Result<String, String> {
+        let mut conn = self.client.get_multiplexed_async_connection()
+            .await
+            .map_err(|e| format!("Redis connection error: {}", e))?;
+        
+        let key = format!("webauthn:discoverable:{}", challenge_id);
+        conn.get_del(&key)
+            .await
+            .map_err(|_| "Invalid or expired challenge".to_string())
+    }
+
+    async fn save_setup_token(&self, token: &str, ttl_seconds: u64) -> Result<(), String> {
+        let mut conn = self.client.get_multiplexed_async_connection()
+            .await
+            .map_err(|e| format!("Redis connection error: {}", e))?;
+
+        conn.set_ex::<_, _, ()>("setup:token", token, ttl_seconds)
+            .await
+            .map_err(|e| format!("Failed to save setup token: {}", e))
+    }
+
+    async fn setup_token(&self) -> Result<Option<String>, String> {
+        let mut conn = self.client.get_multiplexed_async_connection()
+            .await
+            .map_err(|e| format!("Redis connection error: {}", e))?;
+
+        conn.get("setup:token")
+            .await
+            .map_err(|e| format!("Failed to read setup token: {}", e))
+    }
+
+    async fn delete_setup_token(&self) -> Result<(), String> {
+        let mut conn = self.client.get_multiplexed_async_connection()
+            .await
+            .map_err(|e| format!("Redis connection error: {}", e))?;
+
+        conn.del::<_, ()>("setup:token")
+            .await
+            .map_err(|e| format!("Failed to delete setup token: {}", e))
+    }
+
+    async fn save_oidc_login(&self, key: &str, state: &str, ttl_seconds: u64) -> Result<(), String> {
+        let mut conn = self.client.get_multiplexed_async_connection()
+            .await
+            .map_err(|e| format!("Redis connection error: {}", e))?;
+
+        let key = format!("oidc:login:{}", key);
+        conn.set_ex::<_, _, ()>(&key, state, ttl_seconds)
+            .await
+            .map_err(|e| format!("Failed to save OIDC login: {}", e))
+    }
+
+    async fn get_and_delete_oidc_login(&self, key: &str) -> Result<String, String> {
+        let mut conn = self.client.get_multiplexed_async_connection()
+            .await
+            .map_err(|e| format!("Redis connection error: {}", e))?;
+
+        let key = format!("oidc:login:{}", key);
+        conn.get_del(&key)
+            .await
+            .map_err(|_| "Invalid or expired login".to_string())
+    }
 }
@@ -0,0 +1,196 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::SqliteConnection;
+use crate::application::ports::client_group_repository::ClientGroupRepository;
+use crate::domain::entities::client_group::ClientGroup;
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::driven::persistence::db_types::{DbClientGroup, DbClientGroupMember};
+
+pub struct SqliteClientGroupRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+}
+
+impl SqliteClientGroupRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>) -> Self {
+        Self { pool }
+    }
+}
+
+fn db_to_client_group(row: DbClientGroup, members: &[DbClientGroupMember]) -> Result<ClientGroup, String> {
+    let id = uuid::Uuid::parse_str(&row.id).map_err(|e| format!("Invalid group id: {e}"))?;
+    let owner_uuid = uuid::Uuid::parse_str(&row.owner_id).map_err(|e| format!("Invalid owner_id: {e}"))?;
+    let grants = serde_json::from_str(&row.grants).map_err(|e| format!("Failed to parse grants: {e}"))?;
+    let members = members
+        .iter()
+        .filter(|m| m.group_id == row.id)
+        .map(|m| {
+            uuid::Uuid::parse_str(&m.client_id)
+                .map(UserId::from_uuid)
+                .map_err(|e| format!("Invalid client_id: {e}"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let created_at = row
+        .created_at
+        .parse::<chrono::DateTime<chrono::Utc>>()
+        .unwrap_or_else(|_| chrono::Utc::now());
+
+    Ok(ClientGroup {
+        id,
+        owner_id: UserId::from_uuid(owner_uuid),
+        name: row.name,
+        grants,
+        members,
+        created_at,
+    })
+}
+
+#[async_trait]
+impl ClientGroupRepository for SqliteClientGroupRepository {
+    async fn save(&self, group: &ClientGroup) -> Result<(), String> {
+        let id = group.id.to_string();
+        let owner_id = group.owner_id.to_string();
+        let name = group.name.clone();
+        let grants = serde_json::to_string(&group.grants)
+            .map_err(|e| format!("Failed to serialize grants: {e}"))?;
+        let created_at = group.created_at.to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query(
+                "INSERT INTO client_groups (id, owner_id, name, grants, created_at) \
+                 VALUES (?1, ?2, ?3, ?4, ?5) \
+                 ON CONFLICT(id) DO UPDATE SET name=excluded.name, grants=excluded.grants"
+            )
+            .bind::<diesel::sql_types::Text, _>(&id)
+            .bind::<diesel::sql_types::Text, _>(&owner_id)
+            .bind::<diesel::sql_types::Text, _>(&name)
+            .bind::<diesel::sql_types::Text, _>(&grants)
+            .bind::<diesel::sql_types::Text, _>(&created_at)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to save group: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find_by_id(&self, id: &uuid::Uuid) -> Result<Option<ClientGroup>, String> {
+        let id_str = id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Option<ClientGroup>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbClientGroup> = diesel::sql_query(
+                "SELECT id, owner_id, name, grants, created_at FROM client_groups WHERE id = ?1"
+            )
+            .bind::<diesel::sql_types::Text, _>(&id_str)
+            .load(&mut conn)
+            .map_err(|e| format!("Database error: {e}"))?;
+            let members: Vec<DbClientGroupMember> = diesel::sql_query(
+                "SELECT group_id, client_id FROM client_group_members WHERE group_id = ?1 ORDER BY added_at"
+            )
+            .bind::<diesel::sql_types::Text, _>(&id_str)
+            .load(&mut conn)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+            rows.into_iter()
+                .next()
+                .map(|row| db_to_client_group(row, &members))
+                .transpose()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find_by_owner(&self, owner_id: &UserId) -> Result<Vec<ClientGroup>, String> {
+        let owner_id_str = owner_id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<ClientGroup>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbClientGroup> = diesel::sql_query(
+                "SELECT id, owner_id, name, grants, created_at FROM client_groups WHERE owner_id = ?1 ORDER BY name"
+            )
+            .bind::<diesel::sql_types::Text, _>(&owner_id_str)
+            .load(&mut conn)
+            .map_err(|e| format!("Database error: {e}"))?;
+            let members: Vec<DbClientGroupMember> = diesel::sql_query(
+                "SELECT m.group_id, m.client_id FROM client_group_members m \
+                 JOIN client_groups g ON g.id = m.group_id \
+                 WHERE g.owner_id = ?1 ORDER BY m.added_at"
+            )
+            .bind::<diesel::sql_types::Text, _>(&owner_id_str)
+            .load(&mut conn)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+            rows.into_iter().map(|row| db_to_client_group(row, &members)).collect()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn delete(&self, id: &uuid::Uuid) -> Result<(), String> {
+        let id_str = id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            conn.transaction(|conn| {
+                diesel::sql_query("DELETE FROM client_group_members WHERE group_id = ?1")
+                    .bind::<diesel::sql_types::Text, _>(&id_str)
+                    .execute(conn)?;
+                diesel::sql_query("DELETE FROM client_groups WHERE id = ?1")
+                    .bind::<diesel::sql_types::Text, _>(&id_str)
+                    .execute(conn)
+            })
+            .map_err(|e| format!("Failed to delete group: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn add_member(&self, group_id: &uuid::Uuid, client_id: &UserId) -> Result<(), String> {
+        let group_id_str = group_id.to_string();
+        let client_id_str = client_id.to_string();
+        let added_at = chrono::Utc::now().to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query(
+                "INSERT INTO client_group_members (group_id, client_id, added_at) VALUES (?1, ?2, ?3) \
+                 ON CONFLICT(group_id, client_id) DO NOTHING"
+            )
+            .bind::<diesel::sql_types::Text, _>(&group_id_str)
+            .bind::<diesel::sql_types::Text, _>(&client_id_str)
+            .bind::<diesel::sql_types::Text, _>(&added_at)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to add group member: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn remove_member(&self, group_id: &uuid::Uuid, client_id: &UserId) -> Result<(), String> {
+        let group_id_str = group_id.to_string();
+        let client_id_str = client_id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query("DELETE FROM client_group_members WHERE group_id = ?1 AND client_id = ?2")
+                .bind::<diesel::sql_types::Text, _>(&group_id_str)
+                .bind::<diesel::sql_types::Text, _>(&client_id_str)
+                .execute(&mut conn)
+                .map_err(|e| format!("Failed to remove group member: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+}
@@ -5,6 +5,7 @@ use diesel::SqliteConnection;
 use async_trait::async_trait;
 use crate::application::ports::CredentialRepository;
 use crate::domain::{Credential, UserId};
+use crate::domain::entities::credential::{CredentialMetadata, CredentialUsage};
 use crate::infrastructure::driven::persistence::schema::webauthn_credentials;
 use crate::infrastructure::driven::persistence::db_types::{DbCredential, NewDbCredential};
 
@@ -29,11 +30,39 @@ fn hex_to_bytes(hex: &str) -> Result<Vec<u8>, String> {
         .collect()
 }
 
+/// `created_at` comes from SQLite's `CURRENT_TIMESTAMP`; timestamps written here are RFC 3339.
+fn parse_timestamp(value: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    value.parse::<chrono::DateTime<chrono::Utc>>().ok().or_else(|| {
+        chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S")
+            .ok()
+            .map(|naive| naive.and_utc())
+    })
+}
+
+fn db_to_credential(row: DbCredential) -> Option<Credential> {
+    let user_id = UserId::from_uuid(uuid::Uuid::parse_str(&row.user_id).ok()?);
+    let cred_id_bytes = hex_to_bytes(&row.credential_id).ok()?;
+    let passkey = serde_json::from_str(&row.public_key).ok()?;
+    let metadata = CredentialMetadata {
+        aaguid: row.aaguid.as_deref().and_then(|s| uuid::Uuid::parse_str(s).ok()),
+        transports: serde_json::from_str(&row.transports).unwrap_or_default(),
+        backup_eligible: row.backup_eligible,
+        backup_state: row.backup_state,
+    };
+    let usage = CredentialUsage {
+        // Convert i64 from DB to u32 for domain
+        sign_count: u32::try_from(row.sign_count).ok()?,
+        created_at: parse_timestamp(&row.created_at)?,
+        last_used_at: row.last_used_at.as_deref().and_then(parse_timestamp),
+        sign_count_anomalies: u32::try_from(row.sign_count_anomalies).unwrap_or(u32::MAX),
+    };
+    Some(Credential::from_persistence(user_id, cred_id_bytes, passkey, metadata, usage))
+}
+
 #[async_trait]
 impl CredentialRepository for SqliteCredentialRepository {
     async fn find_by_user_id(&self, user_id: &UserId) -> Result<Vec<Credential>, String> {
         let user_id_str = user_id.to_string();
-        let user_id_clone = user_id.clone();
         let pool = self.pool.clone();
 
         tokio::task::spawn_blocking(move || {
@@ -45,18 +74,7 @@ impl CredentialRepository for SqliteCredentialRepository {
 
             let credentials = rows
                 .into_iter()
-                .filter_map(|row| {
-                    let cred_id_bytes = hex_to_bytes(&row.credential_id).ok()?;
-                    let passkey = serde_json::from_str(&row.public_key).ok()?;
-                    // Convert i64 from DB to u32 for domain
-                    let sign_count = u32::try_from(row.sign_count).ok()?;
-                    Some(Credential::from_persistence(
-                        user_id_clone.clone(),
-                        cred_id_bytes,
-                        passkey,
-                        sign_count,
-                    ))
-                })
+                .filter_map(db_to_credential)
                 .collect();
 
             Ok(credentials)
@@ -65,6 +83,24 @@ impl CredentialRepository for SqliteCredentialRepository {
         .map_err(|e| e.to_string())?
     }
 
+    async fn find_by_credential_id(&self, credential_id: &[u8]) -> Result<Option<Credential>, String> {
+        let credential_id_str = bytes_to_hex(credential_id);
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let row: Option<DbCredential> = webauthn_credentials::table
+                .filter(webauthn_credentials::credential_id.eq(&credential_id_str))
+                .first::<DbCredential>(&mut conn)
+                .optional()
+                .map_err(|e| format!("Database error: {}", e))?;
+
+            Ok(row.and_then(db_to_credential))
+        })
+        .await
+        .map_err(|e| e.to_string())?
+    }
+
     async fn save(&self, credential: &Credential) -> Result<(), String> {
         let id_val = uuid::Uuid::new_v4().to_string();
         let user_id_val = credential.user_id().to_string();
@@ -72,6 +108,11 @@ impl CredentialRepository for SqliteCredentialRepository {
         let public_key_val = serde_json::to_string(credential.passkey())
             .map_err(|e| format!("Failed to serialize passkey: {}", e))?;
         let sign_count_val = credential.sign_count() as i64;
+        let metadata = credential.metadata().clone();
+        let transports_val = serde_json::to_string(&metadata.transports)
+            .map_err(|e| format!("Failed to serialize transports: {}", e))?;
+        let last_used_at_val = credential.usage().last_used_at.map(|at| at.to_rfc3339());
+        let anomalies_val = credential.usage().sign_count_anomalies as i64;
         let pool = self.pool.clone();
 
         tokio::task::spawn_blocking(move || {
@@ -82,18 +123,36 @@ impl CredentialRepository for SqliteCredentialRepository {
                 credential_id: credential_id_val,
                 public_key: public_key_val,
                 sign_count: sign_count_val,
+                aaguid: metadata.aaguid.map(|aaguid| aaguid.to_string()),
+                transports: transports_val,
+                backup_eligible: metadata.backup_eligible,
+                backup_state: metadata.backup_state,
+                last_used_at: last_used_at_val,
+                sign_count_anomalies: anomalies_val,
             };
             use diesel::dsl::insert_into;
             use diesel::sqlite::Sqlite;
             use diesel::query_builder::InsertStatement;
             use diesel::query_dsl::RunQueryDsl;
             use crate::infrastructure::driven::persistence::schema::webauthn_credentials::dsl::*;
-            diesel::sql_query("INSERT INTO webauthn_credentials (id, user_id, credential_id, public_key, sign_count) VALUES (?1, ?2, ?3, ?4, ?5) ON CONFLICT(credential_id) DO UPDATE SET sign_count=excluded.sign_count, public_key=excluded.public_key")
+            diesel::sql_query(
+                "INSERT INTO webauthn_credentials (id, user_id, credential_id, public_key, sign_count, aaguid, transports, backup_eligible, backup_state, last_used_at, sign_count_anomalies) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11) \
+                 ON CONFLICT(credential_id) DO UPDATE SET sign_count=excluded.sign_count, public_key=excluded.public_key, \
+                 aaguid=excluded.aaguid, transports=excluded.transports, backup_eligible=excluded.backup_eligible, \
+                 backup_state=excluded.backup_state, last_used_at=excluded.last_used_at, sign_count_anomalies=excluded.sign_count_anomalies"
+            )
                 .bind::<diesel::sql_types::Text, _>(&new_cred.id)
                 .bind::<diesel::sql_types::Text, _>(&new_cred.user_id)
                 .bind::<diesel::sql_types::Text, _>(&new_cred.credential_id)
                 .bind::<diesel::sql_types::Text, _>(&new_cred.public_key)
                 .bind::<diesel::sql_types::BigInt, _>(new_cred.sign_count)
+                .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&new_cred.aaguid)
+                .bind::<diesel::sql_types::Text, _>(&new_cred.transports)
+                .bind::<diesel::sql_types::Bool, _>(new_cred.backup_eligible)
+                .bind::<diesel::sql_types::Bool, _>(new_cred.backup_state)
+                .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&new_cred.last_used_at)
+                .bind::<diesel::sql_types::BigInt, _>(new_cred.sign_count_anomalies)
                 .execute(&mut conn)
                 .map_err(|e| format!("Failed to upsert credential: {}", e))?;
             Ok(())
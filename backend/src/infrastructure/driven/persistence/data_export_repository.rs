@@ -0,0 +1,177 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::SqliteConnection;
+use crate::application::ports::data_export_repository::DataExportRepository;
+use crate::domain::entities::data_export::DataExport;
+use crate::domain::entities::file_job::JobStatus;
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::driven::persistence::db_types::DbDataExport;
+
+pub struct SqliteDataExportRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+}
+
+impl SqliteDataExportRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>) -> Self {
+        Self { pool }
+    }
+}
+
+const SELECT_EXPORT: &str =
+    "SELECT id, subject_id, requested_by, owner_id, status, completed, total, error, size_bytes, created_at, updated_at FROM data_exports";
+
+fn parse_user_id(s: &str, field: &str) -> Result<UserId, String> {
+    uuid::Uuid::parse_str(s)
+        .map(UserId::from_uuid)
+        .map_err(|e| format!("Invalid {field}: {e}"))
+}
+
+fn db_to_data_export(row: DbDataExport) -> Result<DataExport, String> {
+    let parse_time = |s: &str| {
+        s.parse::<chrono::DateTime<chrono::Utc>>()
+            .unwrap_or_else(|_| chrono::Utc::now())
+    };
+
+    Ok(DataExport {
+        id: uuid::Uuid::parse_str(&row.id).map_err(|e| format!("Invalid export id: {e}"))?,
+        subject_id: parse_user_id(&row.subject_id, "subject_id")?,
+        requested_by: parse_user_id(&row.requested_by, "requested_by")?,
+        owner_id: row.owner_id.as_deref().map(|s| parse_user_id(s, "owner_id")).transpose()?,
+        status: JobStatus::from_db_str(&row.status)?,
+        completed: row.completed.max(0) as u32,
+        total: row.total.max(0) as u32,
+        error: row.error,
+        size_bytes: row.size_bytes.map(|size| size.max(0) as u64),
+        created_at: parse_time(&row.created_at),
+        updated_at: parse_time(&row.updated_at),
+    })
+}
+
+#[async_trait]
+impl DataExportRepository for SqliteDataExportRepository {
+    async fn save(&self, export: &DataExport) -> Result<(), String> {
+        let id = export.id.to_string();
+        let subject_id = export.subject_id.to_string();
+        let requested_by = export.requested_by.to_string();
+        let owner_id = export.owner_id.as_ref().map(|u| u.to_string());
+        let status = export.status.as_db_str();
+        let completed = export.completed as i32;
+        let total = export.total as i32;
+        let error = export.error.clone();
+        let size_bytes = export.size_bytes.map(|size| size.min(i64::MAX as u64) as i64);
+        let created_at = export.created_at.to_rfc3339();
+        let updated_at = export.updated_at.to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query(
+                "INSERT INTO data_exports (id, subject_id, requested_by, owner_id, status, completed, total, error, size_bytes, created_at, updated_at) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)"
+            )
+            .bind::<diesel::sql_types::Text, _>(&id)
+            .bind::<diesel::sql_types::Text, _>(&subject_id)
+            .bind::<diesel::sql_types::Text, _>(&requested_by)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&owner_id)
+            .bind::<diesel::sql_types::Text, _>(status)
+            .bind::<diesel::sql_types::Integer, _>(completed)
+            .bind::<diesel::sql_types::Integer, _>(total)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&error)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::BigInt>, _>(size_bytes)
+            .bind::<diesel::sql_types::Text, _>(&created_at)
+            .bind::<diesel::sql_types::Text, _>(&updated_at)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to save data export: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find_by_id(&self, id: &uuid::Uuid) -> Result<Option<DataExport>, String> {
+        let id_str = id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Option<DataExport>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbDataExport> = diesel::sql_query(format!("{SELECT_EXPORT} WHERE id = ?1"))
+                .bind::<diesel::sql_types::Text, _>(&id_str)
+                .load(&mut conn)
+                .map_err(|e| format!("Database error: {e}"))?;
+
+            rows.into_iter().next().map(db_to_data_export).transpose()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find_by_status(&self, status: JobStatus) -> Result<Vec<DataExport>, String> {
+        let status = status.as_db_str();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<DataExport>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbDataExport> = diesel::sql_query(format!("{SELECT_EXPORT} WHERE status = ?1 ORDER BY created_at"))
+                .bind::<diesel::sql_types::Text, _>(status)
+                .load(&mut conn)
+                .map_err(|e| format!("Database error: {e}"))?;
+
+            rows.into_iter().map(db_to_data_export).collect()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn update_progress(
+        &self,
+        id: &uuid::Uuid,
+        status: JobStatus,
+        completed: u32,
+        error: Option<&str>,
+        size_bytes: Option<u64>,
+    ) -> Result<(), String> {
+        let id_str = id.to_string();
+        let status = status.as_db_str();
+        let completed = completed as i32;
+        let error = error.map(str::to_string);
+        let size_bytes = size_bytes.map(|size| size.min(i64::MAX as u64) as i64);
+        let updated_at = chrono::Utc::now().to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query(
+                "UPDATE data_exports SET status = ?1, completed = ?2, error = ?3, size_bytes = ?4, updated_at = ?5 WHERE id = ?6"
+            )
+            .bind::<diesel::sql_types::Text, _>(status)
+            .bind::<diesel::sql_types::Integer, _>(completed)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&error)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::BigInt>, _>(size_bytes)
+            .bind::<diesel::sql_types::Text, _>(&updated_at)
+            .bind::<diesel::sql_types::Text, _>(&id_str)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to update data export: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn delete(&self, id: &uuid::Uuid) -> Result<(), String> {
+        let id_str = id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query("DELETE FROM data_exports WHERE id = ?1")
+                .bind::<diesel::sql_types::Text, _>(&id_str)
+                .execute(&mut conn)
+                .map_err(|e| format!("Failed to delete data export: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+}
@@ -19,6 +19,8 @@ pub struct DbInvitation {
     pub expires_at: Option<String>,
     #[diesel(sql_type = diesel::sql_types::Text)]
     pub created_at: String,
+    #[diesel(sql_type = diesel::sql_types::Bool)]
+    pub require_email_verification: bool,
 }
 
 #[derive(diesel::QueryableByName, Debug)]
@@ -39,6 +41,38 @@ pub struct DbFilePermission {
     pub expires_at: Option<String>,
     #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
     pub revoked_at: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Bool)]
+    pub view_only: bool,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub interaction: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub group_id: Option<String>,
+}
+
+#[derive(diesel::QueryableByName, Debug)]
+pub struct DbPermissionEvent {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub permission_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub owner_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub client_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub kind: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub path: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub access: String,
+    #[diesel(sql_type = diesel::sql_types::Bool)]
+    pub view_only: bool,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub interaction: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub group_id: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub expires_at: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub occurred_at: String,
 }
 
 #[derive(diesel::QueryableByName, Debug)]
@@ -63,6 +97,300 @@ pub struct DbSession {
     pub expires_at: String,
     #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
     pub terminated_at: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub video: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub tenant_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub interaction: String,
+}
+
+#[derive(diesel::QueryableByName, Debug)]
+pub struct DbAuditEvent {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub session_id: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub user_id: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub owner_id: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub event_type: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub payload: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub created_at: String,
+}
+
+#[derive(diesel::QueryableByName, Debug)]
+pub struct DbUploadSession {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub owner_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub created_by: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub path: String,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub size: i64,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub received: i64,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub status: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub error: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub created_at: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub updated_at: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub expires_at: String,
+}
+
+#[derive(diesel::QueryableByName, Debug)]
+pub struct DbFileJob {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub owner_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub created_by: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub operations: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub status: String,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub completed: i32,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub total: i32,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub error: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Bool)]
+    pub cancel_requested: bool,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub created_at: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub updated_at: String,
+}
+
+#[derive(diesel::QueryableByName, Debug)]
+pub struct DbProcessingTask {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub owner_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub path: String,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub size: i64,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub status: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub done: String,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub attempts: i32,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub last_error: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub next_attempt_at: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub created_at: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub updated_at: String,
+}
+
+#[derive(diesel::QueryableByName, Debug)]
+pub struct DbMediaMetadata {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub owner_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub path: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub taken_at: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub camera: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Double>)]
+    pub latitude: Option<f64>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Double>)]
+    pub longitude: Option<f64>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
+    pub width: Option<i32>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
+    pub height: Option<i32>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Double>)]
+    pub duration_secs: Option<f64>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub extracted_at: String,
+}
+
+#[derive(diesel::QueryableByName, Debug)]
+pub struct DbOrganizationRule {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub owner_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub name: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub source: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub destination: String,
+    #[diesel(sql_type = diesel::sql_types::Bool)]
+    pub enabled: bool,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub created_at: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub updated_at: String,
+}
+
+#[derive(diesel::QueryableByName, Debug)]
+pub struct DbFileHash {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub path: String,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub size: i64,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub sha256: String,
+}
+
+#[derive(diesel::QueryableByName, Debug)]
+pub struct DbFolderExport {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub owner_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub client_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub path: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub reason: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub status: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub error: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::BigInt>)]
+    pub size_bytes: Option<i64>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub password: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub decided_at: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub expires_at: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub created_at: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub updated_at: String,
+}
+
+#[derive(diesel::QueryableByName, Debug)]
+pub struct DbGalleryShare {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub owner_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub path: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub title: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub token_hash: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub password_hash: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub expires_at: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub view_count: i64,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub last_viewed_at: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub created_at: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub revoked_at: Option<String>,
+}
+
+#[derive(diesel::QueryableByName, Debug)]
+pub struct DbFileComment {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub owner_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub path: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub author_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub body: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub created_at: String,
+}
+
+#[derive(diesel::QueryableByName, Debug)]
+pub struct DbFileRequest {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub owner_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub folder: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub title: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub token_hash: String,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub max_file_bytes: i64,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub allowed_extensions: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub expires_at: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub created_at: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub revoked_at: Option<String>,
+}
+
+#[derive(diesel::QueryableByName, Debug)]
+pub struct DbInboxUpload {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub request_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub owner_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub file_name: String,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub size: i64,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub media_type: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub sender: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub sender_ip: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub status: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub path: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub received_at: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub decided_at: Option<String>,
+}
+
+#[derive(diesel::QueryableByName, Debug)]
+pub struct DbFacet {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub value: String,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub count: i64,
+}
+
+#[derive(diesel::QueryableByName, Debug)]
+pub struct DbCount {
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub count: i64,
 }
 
 #[derive(Queryable, Selectable)]
@@ -73,6 +401,8 @@ pub struct DbUser {
     pub display_name: String,
     pub roles: String,
     pub status: String,
+    pub locale: String,
+    pub tenant_id: String,
 }
 
 #[derive(Insertable)]
@@ -83,6 +413,8 @@ pub struct NewDbUser {
     pub display_name: String,
     pub roles: String,
     pub status: String,
+    pub locale: String,
+    pub tenant_id: String,
 }
 
 #[derive(Queryable, Selectable)]
@@ -94,6 +426,12 @@ pub struct DbCredential {
     pub public_key: String,
     pub sign_count: i64,
     pub created_at: String,
+    pub aaguid: Option<String>,
+    pub transports: String,
+    pub backup_eligible: bool,
+    pub backup_state: bool,
+    pub last_used_at: Option<String>,
+    pub sign_count_anomalies: i64,
 }
 
 #[derive(Insertable)]
@@ -104,4 +442,426 @@ pub struct NewDbCredential {
     pub credential_id: String,
     pub public_key: String,
     pub sign_count: i64,
+    pub aaguid: Option<String>,
+    pub transports: String,
+    pub backup_eligible: bool,
+    pub backup_state: bool,
+    pub last_used_at: Option<String>,
+    pub sign_count_anomalies: i64,
+}
+
+#[derive(diesel::QueryableByName, Debug)]
+pub struct DbQualityPreference {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub user_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub app_id: String,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub framerate: i32,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub max_bitrate: i64,
+    #[diesel(sql_type = diesel::sql_types::Float)]
+    pub resolution_scale: f32,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub updated_at: String,
+}
+
+#[derive(diesel::QueryableByName, Debug)]
+pub struct DbUserPreferences {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub user_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub theme: String,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub default_width: i32,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub default_height: i32,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub default_framerate: i32,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub keyboard_layout: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub timezone: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Bool)]
+    pub notify_email: bool,
+    #[diesel(sql_type = diesel::sql_types::Bool)]
+    pub notify_in_app: bool,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub updated_at: String,
+}
+
+#[derive(diesel::QueryableByName, Debug)]
+pub struct DbAccessPolicy {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub owner_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub allowed_cidrs: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub denied_cidrs: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub allowed_countries: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub denied_countries: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub updated_at: String,
+}
+
+#[derive(diesel::QueryableByName, Debug)]
+pub struct DbClientGroup {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub owner_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub name: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub grants: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub created_at: String,
+}
+
+#[derive(diesel::QueryableByName, Debug)]
+pub struct DbClientGroupMember {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub group_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub client_id: String,
+}
+
+#[derive(diesel::QueryableByName, Debug)]
+pub struct DbOwnerDelegation {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub owner_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub delegate_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub path: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub created_by: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub created_at: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub revoked_at: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Bool)]
+    pub grants_owner_role: bool,
+}
+
+#[derive(diesel::QueryableByName, Debug)]
+pub struct DbSessionTimeline {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub session_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub events: String,
+}
+
+#[derive(diesel::QueryableByName, Debug)]
+pub struct DbSessionSnapshot {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub session_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub user_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub app_id: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Binary>)]
+    pub state: Option<Vec<u8>>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub suspended_at: String,
+}
+
+#[derive(diesel::QueryableByName, Debug)]
+pub struct DbAppStateEntry {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub key: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub value: String,
+}
+
+#[derive(diesel::QueryableByName, Debug)]
+pub struct DbBandwidthCaps {
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::BigInt>)]
+    pub session_max_bitrate: Option<i64>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::BigInt>)]
+    pub monthly_bytes: Option<i64>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::BigInt>)]
+    pub throttle_bitrate: Option<i64>,
+}
+
+#[derive(diesel::QueryableByName, Debug)]
+pub struct DbBandwidthUsage {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub user_id: String,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub bytes_sent: i64,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::BigInt>)]
+    pub session_max_bitrate: Option<i64>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::BigInt>)]
+    pub monthly_bytes: Option<i64>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::BigInt>)]
+    pub throttle_bitrate: Option<i64>,
+}
+
+#[derive(diesel::QueryableByName, Debug)]
+pub struct DbAppCrash {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub session_id: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub user_id: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub app_id: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub message: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub location: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub backtrace: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub recent_input: String,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub frames: i64,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub crashed_at: String,
+}
+
+#[derive(diesel::QueryableByName, Debug)]
+pub struct DbAuthSession {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub user_id: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub user_agent: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub ip: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub created_at: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub last_seen_at: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub expires_at: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub revoked_at: Option<String>,
+}
+
+#[derive(diesel::QueryableByName, Debug)]
+pub struct DbDataExport {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub subject_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub requested_by: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub owner_id: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub status: String,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub completed: i32,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub total: i32,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub error: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::BigInt>)]
+    pub size_bytes: Option<i64>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub created_at: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub updated_at: String,
+}
+
+#[derive(diesel::QueryableByName, Debug)]
+pub struct DbAccountDeletion {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub user_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub requested_by: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub requested_at: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub purge_after: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub purged_at: Option<String>,
+}
+
+#[derive(diesel::QueryableByName, Debug)]
+pub struct DbVaultImport {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub owner_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub requested_by: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub source: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub destination: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub mode: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub on_conflict: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub status: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::BigInt>)]
+    pub files_total: Option<i64>,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub files_done: i64,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub bytes_done: i64,
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub skipped: i64,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub error: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Bool)]
+    pub cancel_requested: bool,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub created_at: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub updated_at: String,
+}
+
+#[derive(diesel::QueryableByName, Debug)]
+pub struct DbExternalIdentity {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub issuer: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub subject: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub user_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub created_at: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub last_login_at: String,
+}
+
+#[derive(diesel::QueryableByName, Debug)]
+pub struct DbProvisioningToken {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub owner_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub name: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub token_hash: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub created_at: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub last_used_at: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub revoked_at: Option<String>,
+}
+
+#[derive(diesel::QueryableByName, Debug)]
+pub struct DbLegalHold {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub owner_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub path: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub reason: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub created_by: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub created_at: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub expires_at: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub released_at: Option<String>,
+}
+
+#[derive(diesel::QueryableByName, Debug)]
+pub struct DbAppSetting {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub owner_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub app_id: String,
+    #[diesel(sql_type = diesel::sql_types::Bool)]
+    pub enabled: bool,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub allowed_roles: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub allowed_users: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub updated_at: String,
+}
+
+#[derive(diesel::QueryableByName, Debug)]
+pub struct DbTenant {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub name: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub setup_token_hash: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub created_at: String,
+}
+
+#[derive(diesel::QueryableByName, Debug)]
+pub struct DbOwnerBranding {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub owner_id: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub display_name: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub logo: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub accent_color: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub updated_at: String,
+}
+
+#[derive(diesel::QueryableByName, Debug)]
+pub struct DbAccessToken {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub user_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub name: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub display_prefix: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub token_hash: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub scopes: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub created_at: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub expires_at: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub last_used_at: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub revoked_at: Option<String>,
+}
+
+#[derive(diesel::QueryableByName, Debug)]
+pub struct DbProvisioningRequest {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub request_hash: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
+    pub status: Option<i32>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub response: Option<String>,
+}
+
+#[derive(diesel::QueryableByName, Debug)]
+pub struct DbImpersonationConsent {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub client_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub owner_id: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub granted_at: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub expires_at: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub revoked_at: Option<String>,
 }
@@ -0,0 +1,163 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::SqliteConnection;
+use crate::application::ports::delegation_repository::DelegationRepository;
+use crate::domain::entities::owner_delegation::OwnerDelegation;
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::driven::persistence::db_types::DbOwnerDelegation;
+
+pub struct SqliteDelegationRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+}
+
+impl SqliteDelegationRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>) -> Self {
+        Self { pool }
+    }
+}
+
+const SELECT_DELEGATION: &str =
+    "SELECT id, owner_id, delegate_id, path, created_by, created_at, revoked_at, grants_owner_role FROM owner_delegations";
+
+fn parse_user_id(s: &str, field: &str) -> Result<UserId, String> {
+    uuid::Uuid::parse_str(s)
+        .map(UserId::from_uuid)
+        .map_err(|e| format!("Invalid {field}: {e}"))
+}
+
+fn db_to_delegation(row: DbOwnerDelegation) -> Result<OwnerDelegation, String> {
+    let id = uuid::Uuid::parse_str(&row.id).map_err(|e| format!("Invalid delegation id: {e}"))?;
+    let created_at = row
+        .created_at
+        .parse::<chrono::DateTime<chrono::Utc>>()
+        .unwrap_or_else(|_| chrono::Utc::now());
+    let revoked_at = row
+        .revoked_at
+        .as_deref()
+        .map(|s| s.parse::<chrono::DateTime<chrono::Utc>>())
+        .transpose()
+        .map_err(|e| format!("Invalid revoked_at: {e}"))?;
+
+    Ok(OwnerDelegation {
+        id,
+        owner_id: parse_user_id(&row.owner_id, "owner_id")?,
+        delegate_id: parse_user_id(&row.delegate_id, "delegate_id")?,
+        path: row.path,
+        created_by: parse_user_id(&row.created_by, "created_by")?,
+        created_at,
+        revoked_at,
+        grants_owner_role: row.grants_owner_role,
+    })
+}
+
+#[async_trait]
+impl DelegationRepository for SqliteDelegationRepository {
+    async fn save(&self, delegation: &OwnerDelegation) -> Result<(), String> {
+        let id = delegation.id.to_string();
+        let owner_id = delegation.owner_id.to_string();
+        let delegate_id = delegation.delegate_id.to_string();
+        let path = delegation.path.clone();
+        let created_by = delegation.created_by.to_string();
+        let created_at = delegation.created_at.to_rfc3339();
+        let revoked_at = delegation.revoked_at.map(|dt| dt.to_rfc3339());
+        let grants_owner_role = delegation.grants_owner_role;
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query(
+                "INSERT INTO owner_delegations (id, owner_id, delegate_id, path, created_by, created_at, revoked_at, grants_owner_role) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8) \
+                 ON CONFLICT(id) DO UPDATE SET revoked_at=excluded.revoked_at, grants_owner_role=excluded.grants_owner_role"
+            )
+            .bind::<diesel::sql_types::Text, _>(&id)
+            .bind::<diesel::sql_types::Text, _>(&owner_id)
+            .bind::<diesel::sql_types::Text, _>(&delegate_id)
+            .bind::<diesel::sql_types::Text, _>(&path)
+            .bind::<diesel::sql_types::Text, _>(&created_by)
+            .bind::<diesel::sql_types::Text, _>(&created_at)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&revoked_at)
+            .bind::<diesel::sql_types::Bool, _>(grants_owner_role)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to save delegation: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find_by_id(&self, id: &uuid::Uuid) -> Result<Option<OwnerDelegation>, String> {
+        let id_str = id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Option<OwnerDelegation>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbOwnerDelegation> = diesel::sql_query(format!("{SELECT_DELEGATION} WHERE id = ?1"))
+                .bind::<diesel::sql_types::Text, _>(&id_str)
+                .load(&mut conn)
+                .map_err(|e| format!("Database error: {e}"))?;
+
+            rows.into_iter().next().map(db_to_delegation).transpose()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find_active_for_delegate(&self, delegate_id: &UserId) -> Result<Vec<OwnerDelegation>, String> {
+        let delegate_id_str = delegate_id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<OwnerDelegation>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbOwnerDelegation> = diesel::sql_query(format!(
+                "{SELECT_DELEGATION} WHERE delegate_id = ?1 AND revoked_at IS NULL"
+            ))
+            .bind::<diesel::sql_types::Text, _>(&delegate_id_str)
+            .load(&mut conn)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+            rows.into_iter().map(db_to_delegation).collect()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find_by_owner(&self, owner_id: &UserId) -> Result<Vec<OwnerDelegation>, String> {
+        let owner_id_str = owner_id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<OwnerDelegation>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbOwnerDelegation> = diesel::sql_query(format!(
+                "{SELECT_DELEGATION} WHERE owner_id = ?1 ORDER BY created_at"
+            ))
+            .bind::<diesel::sql_types::Text, _>(&owner_id_str)
+            .load(&mut conn)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+            rows.into_iter().map(db_to_delegation).collect()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn revoke(&self, id: &uuid::Uuid) -> Result<(), String> {
+        let id_str = id.to_string();
+        let revoked_at = chrono::Utc::now().to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query("UPDATE owner_delegations SET revoked_at = ?1 WHERE id = ?2 AND revoked_at IS NULL")
+                .bind::<diesel::sql_types::Text, _>(&revoked_at)
+                .bind::<diesel::sql_types::Text, _>(&id_str)
+                .execute(&mut conn)
+                .map_err(|e| format!("Failed to revoke delegation: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+}
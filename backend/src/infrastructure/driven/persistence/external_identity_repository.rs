@@ -0,0 +1,89 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::SqliteConnection;
+use crate::application::ports::external_identity_repository::ExternalIdentityRepository;
+use crate::domain::entities::external_identity::ExternalIdentity;
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::driven::persistence::db_types::DbExternalIdentity;
+
+pub struct SqliteExternalIdentityRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+}
+
+impl SqliteExternalIdentityRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>) -> Self {
+        Self { pool }
+    }
+}
+
+fn db_to_external_identity(row: DbExternalIdentity) -> Result<ExternalIdentity, String> {
+    let parse_time = |s: &str| {
+        s.parse::<chrono::DateTime<chrono::Utc>>()
+            .unwrap_or_else(|_| chrono::Utc::now())
+    };
+
+    Ok(ExternalIdentity {
+        user_id: uuid::Uuid::parse_str(&row.user_id)
+            .map(UserId::from_uuid)
+            .map_err(|e| format!("Invalid user_id: {e}"))?,
+        issuer: row.issuer,
+        subject: row.subject,
+        created_at: parse_time(&row.created_at),
+        last_login_at: parse_time(&row.last_login_at),
+    })
+}
+
+#[async_trait]
+impl ExternalIdentityRepository for SqliteExternalIdentityRepository {
+    async fn find(&self, issuer: &str, subject: &str) -> Result<Option<ExternalIdentity>, String> {
+        let issuer = issuer.to_string();
+        let subject = subject.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Option<ExternalIdentity>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbExternalIdentity> = diesel::sql_query(
+                "SELECT issuer, subject, user_id, created_at, last_login_at FROM external_identities \
+                 WHERE issuer = ?1 AND subject = ?2"
+            )
+            .bind::<diesel::sql_types::Text, _>(&issuer)
+            .bind::<diesel::sql_types::Text, _>(&subject)
+            .load(&mut conn)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+            rows.into_iter().next().map(db_to_external_identity).transpose()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn save(&self, identity: &ExternalIdentity) -> Result<(), String> {
+        let issuer = identity.issuer.clone();
+        let subject = identity.subject.clone();
+        let user_id = identity.user_id.to_string();
+        let created_at = identity.created_at.to_rfc3339();
+        let last_login_at = identity.last_login_at.to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query(
+                "INSERT INTO external_identities (issuer, subject, user_id, created_at, last_login_at) \
+                 VALUES (?1, ?2, ?3, ?4, ?5) \
+                 ON CONFLICT(issuer, subject) DO UPDATE SET user_id = excluded.user_id, last_login_at = excluded.last_login_at"
+            )
+            .bind::<diesel::sql_types::Text, _>(&issuer)
+            .bind::<diesel::sql_types::Text, _>(&subject)
+            .bind::<diesel::sql_types::Text, _>(&user_id)
+            .bind::<diesel::sql_types::Text, _>(&created_at)
+            .bind::<diesel::sql_types::Text, _>(&last_login_at)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to save external identity: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+}
@@ -0,0 +1,146 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::SqliteConnection;
+use crate::application::ports::file_comment_repository::FileCommentRepository;
+use crate::domain::entities::file_comment::FileComment;
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::driven::persistence::db_types::{DbFacet, DbFileComment};
+
+const COLUMNS: &str = "id, owner_id, path, author_id, body, created_at";
+
+pub struct SqliteFileCommentRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+}
+
+impl SqliteFileCommentRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>) -> Self {
+        Self { pool }
+    }
+}
+
+fn parse_time(s: &str) -> chrono::DateTime<chrono::Utc> {
+    s.parse::<chrono::DateTime<chrono::Utc>>().unwrap_or_else(|_| chrono::Utc::now())
+}
+
+fn db_to_comment(row: DbFileComment) -> Result<FileComment, String> {
+    let id = uuid::Uuid::parse_str(&row.id).map_err(|e| format!("Invalid id: {e}"))?;
+    let owner_uuid = uuid::Uuid::parse_str(&row.owner_id).map_err(|e| format!("Invalid owner_id: {e}"))?;
+    let author_uuid = uuid::Uuid::parse_str(&row.author_id).map_err(|e| format!("Invalid author_id: {e}"))?;
+
+    Ok(FileComment {
+        id,
+        owner_id: UserId::from_uuid(owner_uuid),
+        path: row.path,
+        author_id: UserId::from_uuid(author_uuid),
+        body: row.body,
+        created_at: parse_time(&row.created_at),
+    })
+}
+
+#[async_trait]
+impl FileCommentRepository for SqliteFileCommentRepository {
+    async fn save(&self, comment: &FileComment) -> Result<(), String> {
+        let id = comment.id.to_string();
+        let owner_id = comment.owner_id.to_string();
+        let path = comment.path.clone();
+        let author_id = comment.author_id.to_string();
+        let body = comment.body.clone();
+        let created_at = comment.created_at.to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query(format!(
+                "INSERT INTO file_comments ({COLUMNS}) VALUES (?1, ?2, ?3, ?4, ?5, ?6) \
+                 ON CONFLICT(id) DO UPDATE SET body=excluded.body"
+            ))
+            .bind::<diesel::sql_types::Text, _>(&id)
+            .bind::<diesel::sql_types::Text, _>(&owner_id)
+            .bind::<diesel::sql_types::Text, _>(&path)
+            .bind::<diesel::sql_types::Text, _>(&author_id)
+            .bind::<diesel::sql_types::Text, _>(&body)
+            .bind::<diesel::sql_types::Text, _>(&created_at)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to save comment: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find_by_id(&self, id: &uuid::Uuid) -> Result<Option<FileComment>, String> {
+        let id = id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Option<FileComment>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbFileComment> = diesel::sql_query(format!("SELECT {COLUMNS} FROM file_comments WHERE id = ?1"))
+                .bind::<diesel::sql_types::Text, _>(&id)
+                .load(&mut conn)
+                .map_err(|e| format!("Database error: {e}"))?;
+            rows.into_iter().next().map(db_to_comment).transpose()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find_for_file(&self, owner_id: &UserId, path: &str) -> Result<Vec<FileComment>, String> {
+        let owner_id = owner_id.to_string();
+        let path = path.trim_matches('/').to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<FileComment>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbFileComment> = diesel::sql_query(format!(
+                "SELECT {COLUMNS} FROM file_comments WHERE owner_id = ?1 AND path = ?2 ORDER BY created_at ASC"
+            ))
+            .bind::<diesel::sql_types::Text, _>(&owner_id)
+            .bind::<diesel::sql_types::Text, _>(&path)
+            .load(&mut conn)
+            .map_err(|e| format!("Database error: {e}"))?;
+            rows.into_iter().map(db_to_comment).collect()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn delete(&self, id: &uuid::Uuid) -> Result<(), String> {
+        let id = id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query("DELETE FROM file_comments WHERE id = ?1")
+                .bind::<diesel::sql_types::Text, _>(&id)
+                .execute(&mut conn)
+                .map_err(|e| format!("Database error: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn count_under(&self, owner_id: &UserId, path: &str) -> Result<Vec<(String, u64)>, String> {
+        let owner_id = owner_id.to_string();
+        let path = path.trim_matches('/').to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<(String, u64)>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbFacet> = diesel::sql_query(
+                "SELECT path AS value, COUNT(*) AS count FROM file_comments \
+                 WHERE owner_id = ?1 AND (?2 = '' OR substr(path, 1, length(?2) + 1) = ?2 || '/') \
+                 GROUP BY path"
+            )
+            .bind::<diesel::sql_types::Text, _>(&owner_id)
+            .bind::<diesel::sql_types::Text, _>(&path)
+            .load(&mut conn)
+            .map_err(|e| format!("Database error: {e}"))?;
+            Ok(rows.into_iter().map(|row| (row.value, row.count.max(0) as u64)).collect())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+}
@@ -0,0 +1,141 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::SqliteConnection;
+use crate::application::ports::file_hash_repository::{DuplicateGroup, FileHashRepository, IndexedFile};
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::driven::persistence::db_types::DbFileHash;
+
+pub struct SqliteFileHashRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+}
+
+impl SqliteFileHashRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl FileHashRepository for SqliteFileHashRepository {
+    async fn save(&self, owner_id: &UserId, path: &str, size: u64, sha256: &str) -> Result<(), String> {
+        let owner_id_str = owner_id.to_string();
+        let path = path.trim_matches('/').to_string();
+        let size = size as i64;
+        let sha256 = sha256.to_string();
+        let hashed_at = chrono::Utc::now().to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query(
+                "INSERT OR REPLACE INTO file_hashes (owner_id, path, size, sha256, hashed_at) VALUES (?1, ?2, ?3, ?4, ?5)"
+            )
+            .bind::<diesel::sql_types::Text, _>(&owner_id_str)
+            .bind::<diesel::sql_types::Text, _>(&path)
+            .bind::<diesel::sql_types::BigInt, _>(size)
+            .bind::<diesel::sql_types::Text, _>(&sha256)
+            .bind::<diesel::sql_types::Text, _>(&hashed_at)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to save file hash: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find_duplicates(&self, owner_id: &UserId) -> Result<Vec<DuplicateGroup>, String> {
+        let owner_id_str = owner_id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<DuplicateGroup>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbFileHash> = diesel::sql_query(
+                "SELECT path, size, sha256 FROM file_hashes WHERE owner_id = ?1 AND sha256 IN \
+                 (SELECT sha256 FROM file_hashes WHERE owner_id = ?1 GROUP BY sha256 HAVING COUNT(*) > 1) \
+                 ORDER BY size DESC, sha256 ASC, path ASC"
+            )
+            .bind::<diesel::sql_types::Text, _>(&owner_id_str)
+            .load(&mut conn)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+            let mut groups: Vec<DuplicateGroup> = Vec::new();
+            for row in rows {
+                match groups.last_mut() {
+                    Some(group) if group.sha256 == row.sha256 => group.paths.push(row.path),
+                    _ => groups.push(DuplicateGroup { sha256: row.sha256, size: row.size.max(0) as u64, paths: vec![row.path] }),
+                }
+            }
+            Ok(groups)
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find_under(&self, owner_id: &UserId, path: &str) -> Result<Vec<IndexedFile>, String> {
+        let owner_id_str = owner_id.to_string();
+        let path = path.trim_matches('/').to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<IndexedFile>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbFileHash> = diesel::sql_query(
+                "SELECT path, size, sha256 FROM file_hashes \
+                 WHERE owner_id = ?1 AND (?2 = '' OR path = ?2 OR substr(path, 1, length(?2) + 1) = ?2 || '/')"
+            )
+            .bind::<diesel::sql_types::Text, _>(&owner_id_str)
+            .bind::<diesel::sql_types::Text, _>(&path)
+            .load(&mut conn)
+            .map_err(|e| format!("Database error: {e}"))?;
+            Ok(rows.into_iter().map(|row| IndexedFile { path: row.path, size: row.size.max(0) as u64 }).collect())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn move_path(&self, owner_id: &UserId, from: &str, to: &str) -> Result<(), String> {
+        let owner_id_str = owner_id.to_string();
+        let from = from.trim_matches('/').to_string();
+        let to = to.trim_matches('/').to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            // Matches the path itself and everything under it, without LIKE wildcards
+            diesel::sql_query(
+                "UPDATE file_hashes SET path = ?3 || substr(path, length(?2) + 1) \
+                 WHERE owner_id = ?1 AND (path = ?2 OR substr(path, 1, length(?2) + 1) = ?2 || '/')"
+            )
+            .bind::<diesel::sql_types::Text, _>(&owner_id_str)
+            .bind::<diesel::sql_types::Text, _>(&from)
+            .bind::<diesel::sql_types::Text, _>(&to)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to move file hashes: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn delete_path(&self, owner_id: &UserId, path: &str) -> Result<(), String> {
+        let owner_id_str = owner_id.to_string();
+        let path = path.trim_matches('/').to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query(
+                "DELETE FROM file_hashes \
+                 WHERE owner_id = ?1 AND (path = ?2 OR substr(path, 1, length(?2) + 1) = ?2 || '/')"
+            )
+            .bind::<diesel::sql_types::Text, _>(&owner_id_str)
+            .bind::<diesel::sql_types::Text, _>(&path)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to delete file hashes: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+}
@@ -0,0 +1,171 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::SqliteConnection;
+use crate::application::ports::file_job_repository::FileJobRepository;
+use crate::domain::entities::file_job::{FileJob, FileOperation, JobStatus};
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::driven::persistence::db_types::DbFileJob;
+
+pub struct SqliteFileJobRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+}
+
+impl SqliteFileJobRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>) -> Self {
+        Self { pool }
+    }
+}
+
+const SELECT_JOB: &str =
+    "SELECT id, owner_id, created_by, operations, status, completed, total, error, cancel_requested, created_at, updated_at FROM file_jobs";
+
+fn parse_user_id(s: &str, field: &str) -> Result<UserId, String> {
+    uuid::Uuid::parse_str(s)
+        .map(UserId::from_uuid)
+        .map_err(|e| format!("Invalid {field}: {e}"))
+}
+
+fn db_to_file_job(row: DbFileJob) -> Result<FileJob, String> {
+    let id = uuid::Uuid::parse_str(&row.id).map_err(|e| format!("Invalid job id: {e}"))?;
+    let operations: Vec<FileOperation> = serde_json::from_str(&row.operations)
+        .map_err(|e| format!("Failed to parse operations: {e}"))?;
+    let parse_time = |s: &str| {
+        s.parse::<chrono::DateTime<chrono::Utc>>()
+            .unwrap_or_else(|_| chrono::Utc::now())
+    };
+
+    Ok(FileJob {
+        id,
+        owner_id: parse_user_id(&row.owner_id, "owner_id")?,
+        created_by: parse_user_id(&row.created_by, "created_by")?,
+        operations,
+        status: JobStatus::from_db_str(&row.status)?,
+        completed: row.completed.max(0) as u32,
+        total: row.total.max(0) as u32,
+        error: row.error,
+        cancel_requested: row.cancel_requested,
+        created_at: parse_time(&row.created_at),
+        updated_at: parse_time(&row.updated_at),
+    })
+}
+
+#[async_trait]
+impl FileJobRepository for SqliteFileJobRepository {
+    async fn save(&self, job: &FileJob) -> Result<(), String> {
+        let id = job.id.to_string();
+        let owner_id = job.owner_id.to_string();
+        let created_by = job.created_by.to_string();
+        let operations = serde_json::to_string(&job.operations)
+            .map_err(|e| format!("Failed to serialize operations: {e}"))?;
+        let status = job.status.as_db_str();
+        let completed = job.completed as i32;
+        let total = job.total as i32;
+        let error = job.error.clone();
+        let cancel_requested = job.cancel_requested;
+        let created_at = job.created_at.to_rfc3339();
+        let updated_at = job.updated_at.to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query(
+                "INSERT INTO file_jobs (id, owner_id, created_by, operations, status, completed, total, error, cancel_requested, created_at, updated_at) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)"
+            )
+            .bind::<diesel::sql_types::Text, _>(&id)
+            .bind::<diesel::sql_types::Text, _>(&owner_id)
+            .bind::<diesel::sql_types::Text, _>(&created_by)
+            .bind::<diesel::sql_types::Text, _>(&operations)
+            .bind::<diesel::sql_types::Text, _>(status)
+            .bind::<diesel::sql_types::Integer, _>(completed)
+            .bind::<diesel::sql_types::Integer, _>(total)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&error)
+            .bind::<diesel::sql_types::Bool, _>(cancel_requested)
+            .bind::<diesel::sql_types::Text, _>(&created_at)
+            .bind::<diesel::sql_types::Text, _>(&updated_at)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to save file job: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find_by_id(&self, id: &uuid::Uuid) -> Result<Option<FileJob>, String> {
+        let id_str = id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Option<FileJob>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbFileJob> = diesel::sql_query(format!("{SELECT_JOB} WHERE id = ?1"))
+                .bind::<diesel::sql_types::Text, _>(&id_str)
+                .load(&mut conn)
+                .map_err(|e| format!("Database error: {e}"))?;
+
+            rows.into_iter().next().map(db_to_file_job).transpose()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find_by_status(&self, status: JobStatus) -> Result<Vec<FileJob>, String> {
+        let status = status.as_db_str();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<FileJob>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbFileJob> = diesel::sql_query(format!("{SELECT_JOB} WHERE status = ?1 ORDER BY created_at"))
+                .bind::<diesel::sql_types::Text, _>(status)
+                .load(&mut conn)
+                .map_err(|e| format!("Database error: {e}"))?;
+
+            rows.into_iter().map(db_to_file_job).collect()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn update_progress(&self, id: &uuid::Uuid, status: JobStatus, completed: u32, error: Option<&str>) -> Result<(), String> {
+        let id_str = id.to_string();
+        let status = status.as_db_str();
+        let completed = completed as i32;
+        let error = error.map(str::to_string);
+        let updated_at = chrono::Utc::now().to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query("UPDATE file_jobs SET status = ?1, completed = ?2, error = ?3, updated_at = ?4 WHERE id = ?5")
+                .bind::<diesel::sql_types::Text, _>(status)
+                .bind::<diesel::sql_types::Integer, _>(completed)
+                .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&error)
+                .bind::<diesel::sql_types::Text, _>(&updated_at)
+                .bind::<diesel::sql_types::Text, _>(&id_str)
+                .execute(&mut conn)
+                .map_err(|e| format!("Failed to update file job: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn request_cancel(&self, id: &uuid::Uuid) -> Result<(), String> {
+        let id_str = id.to_string();
+        let updated_at = chrono::Utc::now().to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query("UPDATE file_jobs SET cancel_requested = 1, updated_at = ?1 WHERE id = ?2")
+                .bind::<diesel::sql_types::Text, _>(&updated_at)
+                .bind::<diesel::sql_types::Text, _>(&id_str)
+                .execute(&mut conn)
+                .map_err(|e| format!("Failed to cancel file job: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+}
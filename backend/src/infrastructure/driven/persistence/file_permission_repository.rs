@@ -3,11 +3,14 @@ use async_trait::async_trait;
 use diesel::prelude::*;
 use diesel::r2d2::{ConnectionManager, Pool};
 use diesel::SqliteConnection;
-use crate::application::ports::file_permission_repository::FilePermissionRepository;
+use crate::application::ports::file_permission_repository::{FilePermissionRepository, PermissionFilter, PermissionSort, PermissionStatus};
+use crate::application::ports::pagination::{Cursor, Page, PageRequest};
 use crate::domain::entities::file_permission::FilePermission;
 use crate::domain::entities::invitation::AccessLevel;
-use crate::domain::value_objects::UserId;
-use crate::infrastructure::driven::persistence::db_types::DbFilePermission;
+use crate::domain::entities::permission_event::{PermissionEvent, PermissionEventKind};
+use crate::domain::value_objects::{Interaction, UserId};
+use crate::infrastructure::driven::persistence::db_types::{DbFilePermission, DbPermissionEvent};
+use crate::infrastructure::driven::persistence::paging::{self, Filters};
 
 pub struct SqliteFilePermissionRepository {
     pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
@@ -19,6 +22,36 @@ impl SqliteFilePermissionRepository {
     }
 }
 
+/// Append an event for each permission the caller's `WHERE` selects, as it stands. Binds the
+/// kind as `?1` and the time as `?2`; the condition's own parameters start at `?3`.
+const RECORD_EVENTS: &str = "INSERT INTO permission_events \
+     (permission_id, owner_id, client_id, kind, path, access, view_only, interaction, group_id, expires_at, occurred_at) \
+     SELECT id, owner_id, client_id, ?1, path, access, view_only, interaction, group_id, expires_at, ?2 FROM file_permissions";
+
+/// Keeps permissions inside their tenant: a row whose owner or client is in another tenant
+/// than the permission is never read, whatever put it there
+const SAME_TENANT: &str = "tenant_id = (SELECT tenant_id FROM users WHERE users.id = file_permissions.owner_id) \
+     AND tenant_id = (SELECT tenant_id FROM users WHERE users.id = file_permissions.client_id)";
+
+fn db_to_permission_event(row: DbPermissionEvent) -> Result<PermissionEvent, String> {
+    let parse_uuid = |s: &str, field: &str| uuid::Uuid::parse_str(s).map_err(|e| format!("Invalid {field}: {e}"));
+    let parse_time = |s: &str| s.parse::<chrono::DateTime<chrono::Utc>>().map_err(|e| format!("Invalid event time: {e}"));
+
+    Ok(PermissionEvent {
+        permission_id: parse_uuid(&row.permission_id, "permission_id")?,
+        owner_id: UserId::from_uuid(parse_uuid(&row.owner_id, "owner_id")?),
+        client_id: UserId::from_uuid(parse_uuid(&row.client_id, "client_id")?),
+        kind: PermissionEventKind::from_db_str(&row.kind)?,
+        path: row.path,
+        access: serde_json::from_str(&row.access).map_err(|e| format!("Failed to parse access: {e}"))?,
+        view_only: row.view_only,
+        interaction: Interaction::from_db_str(&row.interaction)?,
+        group_id: row.group_id.as_deref().map(|s| parse_uuid(s, "group_id")).transpose()?,
+        expires_at: row.expires_at.as_deref().map(parse_time).transpose()?,
+        occurred_at: parse_time(&row.occurred_at)?,
+    })
+}
+
 fn db_to_file_permission(row: DbFilePermission) -> Result<FilePermission, String> {
     let id = uuid::Uuid::parse_str(&row.id).map_err(|e| format!("Invalid id: {e}"))?;
     let owner_uuid = uuid::Uuid::parse_str(&row.owner_id).map_err(|e| format!("Invalid owner_id: {e}"))?;
@@ -42,6 +75,13 @@ fn db_to_file_permission(row: DbFilePermission) -> Result<FilePermission, String
         .transpose()
         .map_err(|e| format!("Invalid revoked_at: {e}"))?;
 
+    let group_id = row
+        .group_id
+        .as_deref()
+        .map(uuid::Uuid::parse_str)
+        .transpose()
+        .map_err(|e| format!("Invalid group_id: {e}"))?;
+
     Ok(FilePermission {
         id,
         owner_id: UserId::from_uuid(owner_uuid),
@@ -51,6 +91,9 @@ fn db_to_file_permission(row: DbFilePermission) -> Result<FilePermission, String
         granted_at,
         expires_at,
         revoked_at,
+        view_only: row.view_only,
+        interaction: Interaction::from_db_str(&row.interaction)?,
+        group_id,
     })
 }
 
@@ -70,25 +113,46 @@ impl FilePermissionRepository for SqliteFilePermissionRepository {
         let revoked_at = permission
             .revoked_at
             .map(|dt: chrono::DateTime<chrono::Utc>| dt.to_rfc3339());
+        let view_only = permission.view_only;
+        let interaction = permission.interaction.as_db_str();
+        let group_id = permission.group_id.map(|id| id.to_string());
         let pool = self.pool.clone();
 
         tokio::task::spawn_blocking(move || -> Result<(), String> {
             let mut conn = pool.get().map_err(|e| e.to_string())?;
-            diesel::sql_query(
-                "INSERT INTO file_permissions (id, owner_id, client_id, path, access, granted_at, expires_at, revoked_at) \
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)"
-            )
-            .bind::<diesel::sql_types::Text, _>(&id)
-            .bind::<diesel::sql_types::Text, _>(&owner_id)
-            .bind::<diesel::sql_types::Text, _>(&client_id)
-            .bind::<diesel::sql_types::Text, _>(&path)
-            .bind::<diesel::sql_types::Text, _>(&access)
-            .bind::<diesel::sql_types::Text, _>(&granted_at)
-            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&expires_at)
-            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&revoked_at)
-            .execute(&mut conn)
-            .map_err(|e| format!("Failed to save file permission: {e}"))?;
-            Ok(())
+            conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                // Only granted when owner and client share a tenant, which the row records
+                let inserted = diesel::sql_query(
+                    "INSERT INTO file_permissions (id, owner_id, client_id, path, access, granted_at, expires_at, revoked_at, view_only, group_id, interaction, tenant_id) \
+                     SELECT ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, owner.tenant_id \
+                     FROM users owner JOIN users client ON client.id = ?3 \
+                     WHERE owner.id = ?2 AND client.tenant_id = owner.tenant_id"
+                )
+                .bind::<diesel::sql_types::Text, _>(&id)
+                .bind::<diesel::sql_types::Text, _>(&owner_id)
+                .bind::<diesel::sql_types::Text, _>(&client_id)
+                .bind::<diesel::sql_types::Text, _>(&path)
+                .bind::<diesel::sql_types::Text, _>(&access)
+                .bind::<diesel::sql_types::Text, _>(&granted_at)
+                .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&expires_at)
+                .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&revoked_at)
+                .bind::<diesel::sql_types::Bool, _>(view_only)
+                .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&group_id)
+                .bind::<diesel::sql_types::Text, _>(interaction)
+                .execute(conn)?;
+                if inserted == 0 {
+                    return Err(diesel::result::Error::QueryBuilderError(
+                        "owner and client are not users of the same tenant".into(),
+                    ));
+                }
+                diesel::sql_query(format!("{RECORD_EVENTS} WHERE id = ?3"))
+                    .bind::<diesel::sql_types::Text, _>(PermissionEventKind::Granted.as_db_str())
+                    .bind::<diesel::sql_types::Text, _>(&granted_at)
+                    .bind::<diesel::sql_types::Text, _>(&id)
+                    .execute(conn)?;
+                Ok(())
+            })
+            .map_err(|e| format!("Failed to save file permission: {e}"))
         })
         .await
         .map_err(|e: tokio::task::JoinError| e.to_string())?
@@ -100,12 +164,13 @@ impl FilePermissionRepository for SqliteFilePermissionRepository {
 
         tokio::task::spawn_blocking(move || -> Result<Vec<FilePermission>, String> {
             let mut conn = pool.get().map_err(|e| e.to_string())?;
-            let rows: Vec<DbFilePermission> = diesel::sql_query(
-                "SELECT id, owner_id, client_id, path, access, granted_at, expires_at, revoked_at \
+            let rows: Vec<DbFilePermission> = diesel::sql_query(format!(
+                "SELECT id, owner_id, client_id, path, access, granted_at, expires_at, revoked_at, view_only, interaction, group_id \
                  FROM file_permissions \
                  WHERE client_id = ?1 AND revoked_at IS NULL \
-                 AND (expires_at IS NULL OR expires_at > datetime('now'))"
-            )
+                 AND (expires_at IS NULL OR expires_at > datetime('now')) \
+                 AND {SAME_TENANT}"
+            ))
             .bind::<diesel::sql_types::Text, _>(&client_id_str)
             .load(&mut conn)
             .map_err(|e| format!("Database error: {e}"))?;
@@ -122,11 +187,12 @@ impl FilePermissionRepository for SqliteFilePermissionRepository {
 
         tokio::task::spawn_blocking(move || -> Result<Vec<FilePermission>, String> {
             let mut conn = pool.get().map_err(|e| e.to_string())?;
-            let rows: Vec<DbFilePermission> = diesel::sql_query(
-                "SELECT id, owner_id, client_id, path, access, granted_at, expires_at, revoked_at \
+            let rows: Vec<DbFilePermission> = diesel::sql_query(format!(
+                "SELECT id, owner_id, client_id, path, access, granted_at, expires_at, revoked_at, view_only, interaction, group_id \
                  FROM file_permissions \
-                 WHERE owner_id = ?1 AND revoked_at IS NULL"
-            )
+                 WHERE owner_id = ?1 AND revoked_at IS NULL \
+                 AND {SAME_TENANT}"
+            ))
             .bind::<diesel::sql_types::Text, _>(&owner_id_str)
             .load(&mut conn)
             .map_err(|e| format!("Database error: {e}"))?;
@@ -148,11 +214,12 @@ impl FilePermissionRepository for SqliteFilePermissionRepository {
 
         tokio::task::spawn_blocking(move || -> Result<Vec<FilePermission>, String> {
             let mut conn = pool.get().map_err(|e| e.to_string())?;
-            let rows: Vec<DbFilePermission> = diesel::sql_query(
-                "SELECT id, owner_id, client_id, path, access, granted_at, expires_at, revoked_at \
+            let rows: Vec<DbFilePermission> = diesel::sql_query(format!(
+                "SELECT id, owner_id, client_id, path, access, granted_at, expires_at, revoked_at, view_only, interaction, group_id \
                  FROM file_permissions \
-                 WHERE owner_id = ?1 AND client_id = ?2"
-            )
+                 WHERE owner_id = ?1 AND client_id = ?2 \
+                 AND {SAME_TENANT}"
+            ))
             .bind::<diesel::sql_types::Text, _>(&owner_id_str)
             .bind::<diesel::sql_types::Text, _>(&client_id_str)
             .load(&mut conn)
@@ -166,19 +233,220 @@ impl FilePermissionRepository for SqliteFilePermissionRepository {
 
     async fn revoke(&self, id: &uuid::Uuid) -> Result<(), String> {
         let id_str = id.to_string();
+        let now = chrono::Utc::now().to_rfc3339();
         let pool = self.pool.clone();
 
         tokio::task::spawn_blocking(move || -> Result<(), String> {
             let mut conn = pool.get().map_err(|e| e.to_string())?;
-            diesel::sql_query(
-                "UPDATE file_permissions SET revoked_at = datetime('now') WHERE id = ?1"
-            )
+            conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                diesel::sql_query(format!("{RECORD_EVENTS} WHERE id = ?3 AND revoked_at IS NULL"))
+                    .bind::<diesel::sql_types::Text, _>(PermissionEventKind::Revoked.as_db_str())
+                    .bind::<diesel::sql_types::Text, _>(&now)
+                    .bind::<diesel::sql_types::Text, _>(&id_str)
+                    .execute(conn)?;
+                diesel::sql_query(
+                    "UPDATE file_permissions SET revoked_at = datetime('now') WHERE id = ?1"
+                )
+                .bind::<diesel::sql_types::Text, _>(&id_str)
+                .execute(conn)?;
+                Ok(())
+            })
+            .map_err(|e| format!("Failed to revoke permission: {e}"))
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find_by_id(&self, id: &uuid::Uuid) -> Result<Option<FilePermission>, String> {
+        let id_str = id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Option<FilePermission>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbFilePermission> = diesel::sql_query(format!(
+                "SELECT id, owner_id, client_id, path, access, granted_at, expires_at, revoked_at, view_only, interaction, group_id \
+                 FROM file_permissions WHERE id = ?1 AND {SAME_TENANT}"
+            ))
             .bind::<diesel::sql_types::Text, _>(&id_str)
-            .execute(&mut conn)
-            .map_err(|e| format!("Failed to revoke permission: {e}"))?;
+            .load(&mut conn)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+            rows.into_iter().next().map(db_to_file_permission).transpose()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find_expiring(&self, before: chrono::DateTime<chrono::Utc>) -> Result<Vec<FilePermission>, String> {
+        // expires_at is stored as RFC 3339, so bounds are compared in the same format
+        let now = chrono::Utc::now().to_rfc3339();
+        let before = before.to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<FilePermission>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbFilePermission> = diesel::sql_query(
+                "SELECT id, owner_id, client_id, path, access, granted_at, expires_at, revoked_at, view_only, interaction, group_id \
+                 FROM file_permissions \
+                 WHERE revoked_at IS NULL AND expiry_notified_at IS NULL \
+                 AND expires_at IS NOT NULL AND expires_at > ?1 AND expires_at <= ?2"
+            )
+            .bind::<diesel::sql_types::Text, _>(&now)
+            .bind::<diesel::sql_types::Text, _>(&before)
+            .load(&mut conn)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+            rows.into_iter().map(db_to_file_permission).collect()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn mark_expiry_notified(&self, id: &uuid::Uuid) -> Result<(), String> {
+        let id_str = id.to_string();
+        let notified_at = chrono::Utc::now().to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query("UPDATE file_permissions SET expiry_notified_at = ?1 WHERE id = ?2")
+                .bind::<diesel::sql_types::Text, _>(&notified_at)
+                .bind::<diesel::sql_types::Text, _>(&id_str)
+                .execute(&mut conn)
+                .map_err(|e| format!("Failed to mark expiry notice: {e}"))?;
             Ok(())
         })
         .await
         .map_err(|e: tokio::task::JoinError| e.to_string())?
     }
+
+    async fn list(&self, filter: &PermissionFilter, page: &PageRequest) -> Result<Page<FilePermission>, String> {
+        let mut filters = Filters::new();
+        filters.add(SAME_TENANT, []);
+        if let Some(owner_id) = &filter.owner_id {
+            filters.add("owner_id = ?", [owner_id.to_string()]);
+        }
+        if let Some(client_id) = &filter.client_id {
+            filters.add("client_id = ?", [client_id.to_string()]);
+        }
+        let now = chrono::Utc::now().to_rfc3339();
+        match filter.status {
+            PermissionStatus::Active => filters.add("revoked_at IS NULL AND (expires_at IS NULL OR expires_at > ?)", [now]),
+            PermissionStatus::Expired => filters.add("revoked_at IS NULL AND expires_at <= ?", [now]),
+            PermissionStatus::Revoked => filters.add("revoked_at IS NOT NULL", []),
+            PermissionStatus::All => &mut filters,
+        };
+        if let Some(prefix) = &filter.path_prefix {
+            filters.add_prefix("path", prefix);
+        }
+        if let Some(subtrees) = &filter.subtrees {
+            filters.add_subtrees("path", subtrees);
+        }
+        let sort = filter.sort;
+        let page = page.clone();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Page<FilePermission>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let sort_column = match sort {
+                PermissionSort::GrantedAt => "granted_at",
+                PermissionSort::Path => "path",
+            };
+            let (rows, total) = paging::load_page::<DbFilePermission>(
+                &mut conn,
+                "file_permissions",
+                "id, owner_id, client_id, path, access, granted_at, expires_at, revoked_at, view_only, interaction, group_id",
+                &filters,
+                sort_column,
+                &page,
+            )?;
+            Page::from_rows(rows, page.limit, total, |row| match sort {
+                PermissionSort::GrantedAt => Cursor::new(&row.granted_at, &row.id),
+                PermissionSort::Path => Cursor::new(&row.path, &row.id),
+            })
+            .try_map(db_to_file_permission)
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn update_expiry(&self, id: &uuid::Uuid, expires_at: chrono::DateTime<chrono::Utc>) -> Result<(), String> {
+        let id_str = id.to_string();
+        let expires_at = expires_at.to_rfc3339();
+        let now = chrono::Utc::now().to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                diesel::sql_query(
+                    "UPDATE file_permissions SET expires_at = ?1, expiry_notified_at = NULL WHERE id = ?2"
+                )
+                .bind::<diesel::sql_types::Text, _>(&expires_at)
+                .bind::<diesel::sql_types::Text, _>(&id_str)
+                .execute(conn)?;
+                diesel::sql_query(format!("{RECORD_EVENTS} WHERE id = ?3"))
+                    .bind::<diesel::sql_types::Text, _>(PermissionEventKind::ExpiryChanged.as_db_str())
+                    .bind::<diesel::sql_types::Text, _>(&now)
+                    .bind::<diesel::sql_types::Text, _>(&id_str)
+                    .execute(conn)?;
+                Ok(())
+            })
+            .map_err(|e| format!("Failed to update expiry: {e}"))
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn revoke_for_group(&self, group_id: &uuid::Uuid, client_id: Option<&UserId>) -> Result<(), String> {
+        let group_id_str = group_id.to_string();
+        let client_id_str = client_id.map(|c| c.to_string());
+        let now = chrono::Utc::now().to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                diesel::sql_query(format!(
+                    "{RECORD_EVENTS} WHERE group_id = ?3 AND (?4 IS NULL OR client_id = ?4) AND revoked_at IS NULL"
+                ))
+                .bind::<diesel::sql_types::Text, _>(PermissionEventKind::Revoked.as_db_str())
+                .bind::<diesel::sql_types::Text, _>(&now)
+                .bind::<diesel::sql_types::Text, _>(&group_id_str)
+                .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&client_id_str)
+                .execute(conn)?;
+                diesel::sql_query(
+                    "UPDATE file_permissions SET revoked_at = datetime('now') \
+                     WHERE group_id = ?1 AND (?2 IS NULL OR client_id = ?2) AND revoked_at IS NULL"
+                )
+                .bind::<diesel::sql_types::Text, _>(&group_id_str)
+                .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&client_id_str)
+                .execute(conn)?;
+                Ok(())
+            })
+            .map_err(|e| format!("Failed to revoke group permissions: {e}"))
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn events_for_owner(&self, owner_id: &UserId) -> Result<Vec<PermissionEvent>, String> {
+        let owner_id_str = owner_id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<PermissionEvent>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbPermissionEvent> = diesel::sql_query(
+                "SELECT permission_id, owner_id, client_id, kind, path, access, view_only, interaction, group_id, expires_at, occurred_at \
+                 FROM permission_events WHERE owner_id = ?1 ORDER BY seq"
+            )
+            .bind::<diesel::sql_types::Text, _>(&owner_id_str)
+            .load(&mut conn)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+            rows.into_iter().map(db_to_permission_event).collect()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
 }
@@ -0,0 +1,292 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::SqliteConnection;
+use crate::application::ports::file_request_repository::FileRequestRepository;
+use crate::domain::entities::file_request::FileRequest;
+use crate::domain::entities::inbox_upload::{InboxStatus, InboxUpload};
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::driven::persistence::db_types::{DbCount, DbFileRequest, DbInboxUpload};
+
+const COLUMNS: &str = "id, owner_id, folder, title, token_hash, max_file_bytes, allowed_extensions, expires_at, \
+                       created_at, revoked_at";
+
+const UPLOAD_COLUMNS: &str = "id, request_id, owner_id, file_name, size, media_type, sender, sender_ip, status, path, \
+                              received_at, decided_at";
+
+pub struct SqliteFileRequestRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+}
+
+impl SqliteFileRequestRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>) -> Self {
+        Self { pool }
+    }
+
+    /// Run `sql` with the upload's columns bound in order, returning the rows it changed.
+    async fn write_upload(&self, upload: &InboxUpload, sql: String) -> Result<usize, String> {
+        let id = upload.id.to_string();
+        let request_id = upload.request_id.to_string();
+        let owner_id = upload.owner_id.to_string();
+        let file_name = upload.file_name.clone();
+        let size = upload.size as i64;
+        let media_type = upload.media_type.clone();
+        let sender = upload.sender.clone();
+        let sender_ip = upload.sender_ip.clone();
+        let status = upload.status.as_db_str();
+        let path = upload.path.clone();
+        let received_at = upload.received_at.to_rfc3339();
+        let decided_at = upload.decided_at.map(|t| t.to_rfc3339());
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<usize, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query(sql)
+                .bind::<diesel::sql_types::Text, _>(&id)
+                .bind::<diesel::sql_types::Text, _>(&request_id)
+                .bind::<diesel::sql_types::Text, _>(&owner_id)
+                .bind::<diesel::sql_types::Text, _>(&file_name)
+                .bind::<diesel::sql_types::BigInt, _>(size)
+                .bind::<diesel::sql_types::Text, _>(&media_type)
+                .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&sender)
+                .bind::<diesel::sql_types::Text, _>(&sender_ip)
+                .bind::<diesel::sql_types::Text, _>(status)
+                .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&path)
+                .bind::<diesel::sql_types::Text, _>(&received_at)
+                .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&decided_at)
+                .execute(&mut conn)
+                .map_err(|e| format!("Failed to save inbox upload: {e}"))
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn load_where(&self, condition: &'static str, value: String) -> Result<Vec<FileRequest>, String> {
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<FileRequest>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbFileRequest> = diesel::sql_query(format!("SELECT {COLUMNS} FROM file_requests WHERE {condition}"))
+                .bind::<diesel::sql_types::Text, _>(&value)
+                .load(&mut conn)
+                .map_err(|e| format!("Database error: {e}"))?;
+
+            rows.into_iter().map(db_to_request).collect()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+}
+
+fn parse_time(s: &str) -> DateTime<Utc> {
+    s.parse::<DateTime<Utc>>().unwrap_or_else(|_| Utc::now())
+}
+
+fn db_to_request(row: DbFileRequest) -> Result<FileRequest, String> {
+    let id = uuid::Uuid::parse_str(&row.id).map_err(|e| format!("Invalid id: {e}"))?;
+    let owner_uuid = uuid::Uuid::parse_str(&row.owner_id).map_err(|e| format!("Invalid owner_id: {e}"))?;
+
+    Ok(FileRequest {
+        id,
+        owner_id: UserId::from_uuid(owner_uuid),
+        folder: row.folder,
+        title: row.title,
+        token_hash: row.token_hash,
+        max_file_bytes: row.max_file_bytes.max(0) as u64,
+        allowed_extensions: row.allowed_extensions.split(',').filter(|e| !e.is_empty()).map(str::to_string).collect(),
+        expires_at: parse_time(&row.expires_at),
+        created_at: parse_time(&row.created_at),
+        revoked_at: row.revoked_at.as_deref().map(parse_time),
+    })
+}
+
+fn db_to_upload(row: DbInboxUpload) -> Result<InboxUpload, String> {
+    let id = uuid::Uuid::parse_str(&row.id).map_err(|e| format!("Invalid id: {e}"))?;
+    let request_id = uuid::Uuid::parse_str(&row.request_id).map_err(|e| format!("Invalid request_id: {e}"))?;
+    let owner_uuid = uuid::Uuid::parse_str(&row.owner_id).map_err(|e| format!("Invalid owner_id: {e}"))?;
+
+    Ok(InboxUpload {
+        id,
+        request_id,
+        owner_id: UserId::from_uuid(owner_uuid),
+        file_name: row.file_name,
+        size: row.size.max(0) as u64,
+        media_type: row.media_type,
+        sender: row.sender,
+        sender_ip: row.sender_ip,
+        status: InboxStatus::from_db_str(&row.status)?,
+        path: row.path,
+        received_at: parse_time(&row.received_at),
+        decided_at: row.decided_at.as_deref().map(parse_time),
+    })
+}
+
+#[async_trait]
+impl FileRequestRepository for SqliteFileRequestRepository {
+    async fn save(&self, request: &FileRequest) -> Result<(), String> {
+        let id = request.id.to_string();
+        let owner_id = request.owner_id.to_string();
+        let folder = request.folder.clone();
+        let title = request.title.clone();
+        let token_hash = request.token_hash.clone();
+        let max_file_bytes = request.max_file_bytes as i64;
+        let allowed_extensions = request.allowed_extensions.join(",");
+        let expires_at = request.expires_at.to_rfc3339();
+        let created_at = request.created_at.to_rfc3339();
+        let revoked_at = request.revoked_at.map(|t| t.to_rfc3339());
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query(format!(
+                "INSERT INTO file_requests ({COLUMNS}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10) \
+                 ON CONFLICT(id) DO UPDATE SET title=excluded.title, revoked_at=excluded.revoked_at"
+            ))
+            .bind::<diesel::sql_types::Text, _>(&id)
+            .bind::<diesel::sql_types::Text, _>(&owner_id)
+            .bind::<diesel::sql_types::Text, _>(&folder)
+            .bind::<diesel::sql_types::Text, _>(&title)
+            .bind::<diesel::sql_types::Text, _>(&token_hash)
+            .bind::<diesel::sql_types::BigInt, _>(max_file_bytes)
+            .bind::<diesel::sql_types::Text, _>(&allowed_extensions)
+            .bind::<diesel::sql_types::Text, _>(&expires_at)
+            .bind::<diesel::sql_types::Text, _>(&created_at)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&revoked_at)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to save file request: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find_by_id(&self, id: &uuid::Uuid) -> Result<Option<FileRequest>, String> {
+        Ok(self.load_where("id = ?1", id.to_string()).await?.into_iter().next())
+    }
+
+    async fn find_by_token_hash(&self, token_hash: &str) -> Result<Option<FileRequest>, String> {
+        Ok(self.load_where("token_hash = ?1", token_hash.to_string()).await?.into_iter().next())
+    }
+
+    async fn find_by_owner(&self, owner_id: &UserId) -> Result<Vec<FileRequest>, String> {
+        self.load_where("owner_id = ?1 ORDER BY created_at DESC", owner_id.to_string()).await
+    }
+
+    async fn save_upload(&self, upload: &InboxUpload) -> Result<(), String> {
+        let sql = format!(
+            "INSERT INTO inbox_uploads ({UPLOAD_COLUMNS}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12) \
+             ON CONFLICT(id) DO UPDATE SET size=excluded.size, status=excluded.status, path=excluded.path, \
+             decided_at=excluded.decided_at"
+        );
+        self.write_upload(upload, sql).await.map(|_| ())
+    }
+
+    async fn reserve_upload(&self, upload: &InboxUpload, max_waiting: u64, max_receiving: u64) -> Result<bool, String> {
+        let sql = format!(
+            "INSERT INTO inbox_uploads ({UPLOAD_COLUMNS}) SELECT ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12 \
+             WHERE (SELECT COUNT(*) FROM inbox_uploads WHERE request_id = ?2 AND status IN ('receiving', 'pending')) < {max_waiting} \
+             AND (SELECT COUNT(*) FROM inbox_uploads WHERE request_id = ?2 AND status = 'receiving') < {max_receiving}"
+        );
+        Ok(self.write_upload(upload, sql).await? == 1)
+    }
+
+    async fn find_upload(&self, id: &uuid::Uuid) -> Result<Option<InboxUpload>, String> {
+        let id = id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Option<InboxUpload>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbInboxUpload> = diesel::sql_query(format!("SELECT {UPLOAD_COLUMNS} FROM inbox_uploads WHERE id = ?1"))
+                .bind::<diesel::sql_types::Text, _>(&id)
+                .load(&mut conn)
+                .map_err(|e| format!("Database error: {e}"))?;
+            rows.into_iter().next().map(db_to_upload).transpose()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find_uploads(&self, owner_id: &UserId, status: Option<InboxStatus>) -> Result<Vec<InboxUpload>, String> {
+        let owner_id = owner_id.to_string();
+        let status = status.map(|s| s.as_db_str());
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<InboxUpload>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbInboxUpload> = diesel::sql_query(format!(
+                "SELECT {UPLOAD_COLUMNS} FROM inbox_uploads WHERE owner_id = ?1 AND (status = ?2 OR (?2 IS NULL AND status != 'receiving')) \
+                 ORDER BY received_at DESC"
+            ))
+            .bind::<diesel::sql_types::Text, _>(&owner_id)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(status)
+            .load(&mut conn)
+            .map_err(|e| format!("Database error: {e}"))?;
+            rows.into_iter().map(db_to_upload).collect()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn reserved_bytes(&self, owner_id: &UserId) -> Result<u64, String> {
+        let owner_id = owner_id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<u64, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let reserved: DbCount = diesel::sql_query(
+                "SELECT COALESCE(SUM(size), 0) AS count FROM inbox_uploads \
+                 WHERE owner_id = ?1 AND status IN ('receiving', 'pending')"
+            )
+            .bind::<diesel::sql_types::Text, _>(&owner_id)
+            .get_result(&mut conn)
+            .map_err(|e| format!("Database error: {e}"))?;
+            Ok(reserved.count.max(0) as u64)
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn delete_upload(&self, id: &uuid::Uuid) -> Result<(), String> {
+        let id = id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query("DELETE FROM inbox_uploads WHERE id = ?1")
+                .bind::<diesel::sql_types::Text, _>(&id)
+                .execute(&mut conn)
+                .map_err(|e| format!("Failed to delete inbox upload: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn expire_receiving(&self, before: DateTime<Utc>) -> Result<Vec<uuid::Uuid>, String> {
+        let before = before.to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<uuid::Uuid>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                let rows: Vec<DbInboxUpload> = diesel::sql_query(format!(
+                    "SELECT {UPLOAD_COLUMNS} FROM inbox_uploads WHERE status = 'receiving' AND received_at < ?1"
+                ))
+                .bind::<diesel::sql_types::Text, _>(&before)
+                .load(conn)?;
+                diesel::sql_query("DELETE FROM inbox_uploads WHERE status = 'receiving' AND received_at < ?1")
+                    .bind::<diesel::sql_types::Text, _>(&before)
+                    .execute(conn)?;
+                Ok(rows)
+            })
+            .map_err(|e| format!("Failed to expire inbox uploads: {e}"))?
+            .into_iter()
+            .map(|row| uuid::Uuid::parse_str(&row.id).map_err(|e| format!("Invalid id: {e}")))
+            .collect()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+}
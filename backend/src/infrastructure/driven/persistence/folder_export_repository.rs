@@ -0,0 +1,128 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::SqliteConnection;
+use crate::application::ports::folder_export_repository::FolderExportRepository;
+use crate::domain::entities::folder_export::{FolderExport, FolderExportStatus};
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::driven::persistence::db_types::DbFolderExport;
+
+const COLUMNS: &str = "id, owner_id, client_id, path, reason, status, error, size_bytes, password, decided_at, \
+                       expires_at, created_at, updated_at";
+
+pub struct SqliteFolderExportRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+}
+
+impl SqliteFolderExportRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>) -> Self {
+        Self { pool }
+    }
+
+    async fn load_where(&self, condition: &'static str, value: String) -> Result<Vec<FolderExport>, String> {
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<FolderExport>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbFolderExport> = diesel::sql_query(format!("SELECT {COLUMNS} FROM folder_exports WHERE {condition}"))
+                .bind::<diesel::sql_types::Text, _>(&value)
+                .load(&mut conn)
+                .map_err(|e| format!("Database error: {e}"))?;
+
+            rows.into_iter().map(db_to_export).collect()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+}
+
+fn parse_time(s: &str) -> chrono::DateTime<chrono::Utc> {
+    s.parse::<chrono::DateTime<chrono::Utc>>().unwrap_or_else(|_| chrono::Utc::now())
+}
+
+fn db_to_export(row: DbFolderExport) -> Result<FolderExport, String> {
+    let id = uuid::Uuid::parse_str(&row.id).map_err(|e| format!("Invalid id: {e}"))?;
+    let owner_uuid = uuid::Uuid::parse_str(&row.owner_id).map_err(|e| format!("Invalid owner_id: {e}"))?;
+    let client_uuid = uuid::Uuid::parse_str(&row.client_id).map_err(|e| format!("Invalid client_id: {e}"))?;
+
+    Ok(FolderExport {
+        id,
+        owner_id: UserId::from_uuid(owner_uuid),
+        client_id: UserId::from_uuid(client_uuid),
+        path: row.path,
+        reason: row.reason,
+        status: FolderExportStatus::from_db_str(&row.status)?,
+        error: row.error,
+        size_bytes: row.size_bytes.map(|s| s as u64),
+        password: row.password,
+        decided_at: row.decided_at.as_deref().map(parse_time),
+        expires_at: row.expires_at.as_deref().map(parse_time),
+        created_at: parse_time(&row.created_at),
+        updated_at: parse_time(&row.updated_at),
+    })
+}
+
+#[async_trait]
+impl FolderExportRepository for SqliteFolderExportRepository {
+    async fn save(&self, export: &FolderExport) -> Result<(), String> {
+        let id = export.id.to_string();
+        let owner_id = export.owner_id.to_string();
+        let client_id = export.client_id.to_string();
+        let path = export.path.clone();
+        let reason = export.reason.clone();
+        let status = export.status.as_db_str();
+        let error = export.error.clone();
+        let size_bytes = export.size_bytes.map(|s| s as i64);
+        let password = export.password.clone();
+        let decided_at = export.decided_at.map(|t| t.to_rfc3339());
+        let expires_at = export.expires_at.map(|t| t.to_rfc3339());
+        let created_at = export.created_at.to_rfc3339();
+        let updated_at = export.updated_at.to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query(format!(
+                "INSERT INTO folder_exports ({COLUMNS}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13) \
+                 ON CONFLICT(id) DO UPDATE SET status=excluded.status, error=excluded.error, \
+                 size_bytes=excluded.size_bytes, password=excluded.password, decided_at=excluded.decided_at, \
+                 expires_at=excluded.expires_at, updated_at=excluded.updated_at"
+            ))
+            .bind::<diesel::sql_types::Text, _>(&id)
+            .bind::<diesel::sql_types::Text, _>(&owner_id)
+            .bind::<diesel::sql_types::Text, _>(&client_id)
+            .bind::<diesel::sql_types::Text, _>(&path)
+            .bind::<diesel::sql_types::Text, _>(&reason)
+            .bind::<diesel::sql_types::Text, _>(status)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&error)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::BigInt>, _>(size_bytes)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&password)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&decided_at)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&expires_at)
+            .bind::<diesel::sql_types::Text, _>(&created_at)
+            .bind::<diesel::sql_types::Text, _>(&updated_at)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to save folder export: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find_by_id(&self, id: &uuid::Uuid) -> Result<Option<FolderExport>, String> {
+        Ok(self.load_where("id = ?1", id.to_string()).await?.into_iter().next())
+    }
+
+    async fn find_by_owner(&self, owner_id: &UserId) -> Result<Vec<FolderExport>, String> {
+        self.load_where("owner_id = ?1 ORDER BY created_at DESC", owner_id.to_string()).await
+    }
+
+    async fn find_by_client(&self, client_id: &UserId) -> Result<Vec<FolderExport>, String> {
+        self.load_where("client_id = ?1 ORDER BY created_at DESC", client_id.to_string()).await
+    }
+
+    async fn find_by_status(&self, status: FolderExportStatus) -> Result<Vec<FolderExport>, String> {
+        self.load_where("status = ?1 ORDER BY created_at ASC", status.as_db_str().to_string()).await
+    }
+}
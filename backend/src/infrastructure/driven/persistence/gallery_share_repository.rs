@@ -0,0 +1,136 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::SqliteConnection;
+use crate::application::ports::gallery_share_repository::GalleryShareRepository;
+use crate::domain::entities::gallery_share::GalleryShare;
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::driven::persistence::db_types::DbGalleryShare;
+
+const COLUMNS: &str = "id, owner_id, path, title, token_hash, password_hash, expires_at, view_count, last_viewed_at, \
+                       created_at, revoked_at";
+
+pub struct SqliteGalleryShareRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+}
+
+impl SqliteGalleryShareRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>) -> Self {
+        Self { pool }
+    }
+
+    async fn load_where(&self, condition: &'static str, value: String) -> Result<Vec<GalleryShare>, String> {
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<GalleryShare>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbGalleryShare> = diesel::sql_query(format!("SELECT {COLUMNS} FROM gallery_shares WHERE {condition}"))
+                .bind::<diesel::sql_types::Text, _>(&value)
+                .load(&mut conn)
+                .map_err(|e| format!("Database error: {e}"))?;
+
+            rows.into_iter().map(db_to_share).collect()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+}
+
+fn parse_time(s: &str) -> DateTime<Utc> {
+    s.parse::<DateTime<Utc>>().unwrap_or_else(|_| Utc::now())
+}
+
+fn db_to_share(row: DbGalleryShare) -> Result<GalleryShare, String> {
+    let id = uuid::Uuid::parse_str(&row.id).map_err(|e| format!("Invalid id: {e}"))?;
+    let owner_uuid = uuid::Uuid::parse_str(&row.owner_id).map_err(|e| format!("Invalid owner_id: {e}"))?;
+
+    Ok(GalleryShare {
+        id,
+        owner_id: UserId::from_uuid(owner_uuid),
+        path: row.path,
+        title: row.title,
+        token_hash: row.token_hash,
+        password_hash: row.password_hash,
+        expires_at: row.expires_at.as_deref().map(parse_time),
+        view_count: row.view_count.max(0) as u64,
+        last_viewed_at: row.last_viewed_at.as_deref().map(parse_time),
+        created_at: parse_time(&row.created_at),
+        revoked_at: row.revoked_at.as_deref().map(parse_time),
+    })
+}
+
+#[async_trait]
+impl GalleryShareRepository for SqliteGalleryShareRepository {
+    async fn save(&self, share: &GalleryShare) -> Result<(), String> {
+        let id = share.id.to_string();
+        let owner_id = share.owner_id.to_string();
+        let path = share.path.clone();
+        let title = share.title.clone();
+        let token_hash = share.token_hash.clone();
+        let password_hash = share.password_hash.clone();
+        let expires_at = share.expires_at.map(|t| t.to_rfc3339());
+        let view_count = share.view_count as i64;
+        let last_viewed_at = share.last_viewed_at.map(|t| t.to_rfc3339());
+        let created_at = share.created_at.to_rfc3339();
+        let revoked_at = share.revoked_at.map(|t| t.to_rfc3339());
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            // Views are counted by `record_view` alone, so a save never loses one
+            diesel::sql_query(format!(
+                "INSERT INTO gallery_shares ({COLUMNS}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11) \
+                 ON CONFLICT(id) DO UPDATE SET title=excluded.title, expires_at=excluded.expires_at, \
+                 revoked_at=excluded.revoked_at"
+            ))
+            .bind::<diesel::sql_types::Text, _>(&id)
+            .bind::<diesel::sql_types::Text, _>(&owner_id)
+            .bind::<diesel::sql_types::Text, _>(&path)
+            .bind::<diesel::sql_types::Text, _>(&title)
+            .bind::<diesel::sql_types::Text, _>(&token_hash)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&password_hash)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&expires_at)
+            .bind::<diesel::sql_types::BigInt, _>(view_count)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&last_viewed_at)
+            .bind::<diesel::sql_types::Text, _>(&created_at)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&revoked_at)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to save gallery: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find_by_id(&self, id: &uuid::Uuid) -> Result<Option<GalleryShare>, String> {
+        Ok(self.load_where("id = ?1", id.to_string()).await?.into_iter().next())
+    }
+
+    async fn find_by_token_hash(&self, token_hash: &str) -> Result<Option<GalleryShare>, String> {
+        Ok(self.load_where("token_hash = ?1", token_hash.to_string()).await?.into_iter().next())
+    }
+
+    async fn find_by_owner(&self, owner_id: &UserId) -> Result<Vec<GalleryShare>, String> {
+        self.load_where("owner_id = ?1 ORDER BY created_at DESC", owner_id.to_string()).await
+    }
+
+    async fn record_view(&self, id: &uuid::Uuid, at: DateTime<Utc>) -> Result<(), String> {
+        let id = id.to_string();
+        let at = at.to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query("UPDATE gallery_shares SET view_count = view_count + 1, last_viewed_at = ?1 WHERE id = ?2")
+                .bind::<diesel::sql_types::Text, _>(&at)
+                .bind::<diesel::sql_types::Text, _>(&id)
+                .execute(&mut conn)
+                .map_err(|e| format!("Database error: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+}
@@ -0,0 +1,98 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::SqliteConnection;
+use crate::application::ports::impersonation_consent_repository::ImpersonationConsentRepository;
+use crate::domain::entities::impersonation_consent::ImpersonationConsent;
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::driven::persistence::db_types::DbImpersonationConsent;
+
+const COLUMNS: &str = "id, client_id, owner_id, granted_at, expires_at, revoked_at";
+
+pub struct SqliteImpersonationConsentRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+}
+
+impl SqliteImpersonationConsentRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>) -> Self {
+        Self { pool }
+    }
+
+    async fn load_where(&self, condition: &'static str, value: String) -> Result<Vec<ImpersonationConsent>, String> {
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<ImpersonationConsent>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbImpersonationConsent> =
+                diesel::sql_query(format!("SELECT {COLUMNS} FROM impersonation_consents WHERE {condition}"))
+                    .bind::<diesel::sql_types::Text, _>(&value)
+                    .load(&mut conn)
+                    .map_err(|e| format!("Database error: {e}"))?;
+
+            rows.into_iter().map(db_to_consent).collect()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+}
+
+fn parse_time(s: &str) -> DateTime<Utc> {
+    s.parse::<DateTime<Utc>>().unwrap_or_else(|_| Utc::now())
+}
+
+fn db_to_consent(row: DbImpersonationConsent) -> Result<ImpersonationConsent, String> {
+    let id = uuid::Uuid::parse_str(&row.id).map_err(|e| format!("Invalid id: {e}"))?;
+    let client_uuid = uuid::Uuid::parse_str(&row.client_id).map_err(|e| format!("Invalid client_id: {e}"))?;
+    let owner_uuid = uuid::Uuid::parse_str(&row.owner_id).map_err(|e| format!("Invalid owner_id: {e}"))?;
+
+    Ok(ImpersonationConsent {
+        id,
+        client_id: UserId::from_uuid(client_uuid),
+        owner_id: UserId::from_uuid(owner_uuid),
+        granted_at: parse_time(&row.granted_at),
+        expires_at: parse_time(&row.expires_at),
+        revoked_at: row.revoked_at.as_deref().map(parse_time),
+    })
+}
+
+#[async_trait]
+impl ImpersonationConsentRepository for SqliteImpersonationConsentRepository {
+    async fn save(&self, consent: &ImpersonationConsent) -> Result<(), String> {
+        let id = consent.id.to_string();
+        let client_id = consent.client_id.to_string();
+        let owner_id = consent.owner_id.to_string();
+        let granted_at = consent.granted_at.to_rfc3339();
+        let expires_at = consent.expires_at.to_rfc3339();
+        let revoked_at = consent.revoked_at.map(|t| t.to_rfc3339());
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query(format!(
+                "INSERT INTO impersonation_consents ({COLUMNS}) VALUES (?1, ?2, ?3, ?4, ?5, ?6) \
+                 ON CONFLICT(id) DO UPDATE SET revoked_at=excluded.revoked_at"
+            ))
+            .bind::<diesel::sql_types::Text, _>(&id)
+            .bind::<diesel::sql_types::Text, _>(&client_id)
+            .bind::<diesel::sql_types::Text, _>(&owner_id)
+            .bind::<diesel::sql_types::Text, _>(&granted_at)
+            .bind::<diesel::sql_types::Text, _>(&expires_at)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&revoked_at)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to save impersonation consent: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find_by_client(&self, client_id: &UserId) -> Result<Vec<ImpersonationConsent>, String> {
+        self.load_where("client_id = ?1 ORDER BY granted_at DESC", client_id.to_string()).await
+    }
+
+    async fn find_by_owner(&self, owner_id: &UserId) -> Result<Vec<ImpersonationConsent>, String> {
+        self.load_where("owner_id = ?1 ORDER BY granted_at DESC", owner_id.to_string()).await
+    }
+}
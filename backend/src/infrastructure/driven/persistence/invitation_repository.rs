@@ -3,10 +3,12 @@ use async_trait::async_trait;
 use diesel::prelude::*;
 use diesel::r2d2::{ConnectionManager, Pool};
 use diesel::SqliteConnection;
-use crate::application::ports::invitation_repository::InvitationRepository;
+use crate::application::ports::invitation_repository::{InvitationFilter, InvitationRepository, InvitationSort};
+use crate::application::ports::pagination::{Cursor, Page, PageRequest};
 use crate::domain::entities::invitation::{Invitation, InvitationStatus, GrantedPath};
 use crate::domain::value_objects::{Email, UserId};
 use crate::infrastructure::driven::persistence::db_types::DbInvitation;
+use crate::infrastructure::driven::persistence::paging::{self, Filters};
 
 pub struct SqliteInvitationRepository {
     pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
@@ -51,6 +53,7 @@ fn db_to_invitation(row: DbInvitation) -> Result<Invitation, String> {
         status,
         expires_at,
         created_at,
+        require_email_verification: row.require_email_verification,
     })
 }
 
@@ -66,13 +69,14 @@ impl InvitationRepository for SqliteInvitationRepository {
         let status = format!("{:?}", invitation.status);
         let expires_at = invitation.expires_at.map(|dt: chrono::DateTime<chrono::Utc>| dt.to_rfc3339());
         let created_at = invitation.created_at.to_rfc3339();
+        let require_email_verification = invitation.require_email_verification;
         let pool = self.pool.clone();
 
         tokio::task::spawn_blocking(move || {
             let mut conn = pool.get().map_err(|e| e.to_string())?;
             diesel::sql_query(
-                "INSERT INTO invitations (id, owner_id, invitee_email, token, granted_paths, status, expires_at, created_at) \
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8) \
+                "INSERT INTO invitations (id, owner_id, invitee_email, token, granted_paths, status, expires_at, created_at, require_email_verification) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9) \
                  ON CONFLICT(id) DO UPDATE SET status=excluded.status"
             )
             .bind::<diesel::sql_types::Text, _>(&id)
@@ -83,6 +87,7 @@ impl InvitationRepository for SqliteInvitationRepository {
             .bind::<diesel::sql_types::Text, _>(&status)
             .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&expires_at)
             .bind::<diesel::sql_types::Text, _>(&created_at)
+            .bind::<diesel::sql_types::Bool, _>(require_email_verification)
             .execute(&mut conn)
             .map_err(|e| format!("Failed to save invitation: {e}"))?;
             Ok(())
@@ -98,7 +103,7 @@ impl InvitationRepository for SqliteInvitationRepository {
         tokio::task::spawn_blocking(move || -> Result<Option<Invitation>, String> {
             let mut conn = pool.get().map_err(|e| e.to_string())?;
             let rows: Vec<DbInvitation> = diesel::sql_query(
-                "SELECT id, owner_id, invitee_email, token, granted_paths, status, expires_at, created_at \
+                "SELECT id, owner_id, invitee_email, token, granted_paths, status, expires_at, created_at, require_email_verification \
                  FROM invitations WHERE token = ?1"
             )
             .bind::<diesel::sql_types::Text, _>(&token)
@@ -121,7 +126,7 @@ impl InvitationRepository for SqliteInvitationRepository {
         tokio::task::spawn_blocking(move || -> Result<Vec<Invitation>, String> {
             let mut conn = pool.get().map_err(|e| e.to_string())?;
             let rows: Vec<DbInvitation> = diesel::sql_query(
-                "SELECT id, owner_id, invitee_email, token, granted_paths, status, expires_at, created_at \
+                "SELECT id, owner_id, invitee_email, token, granted_paths, status, expires_at, created_at, require_email_verification \
                  FROM invitations WHERE owner_id = ?1"
             )
             .bind::<diesel::sql_types::Text, _>(&owner_id_str)
@@ -134,6 +139,43 @@ impl InvitationRepository for SqliteInvitationRepository {
         .map_err(|e: tokio::task::JoinError| e.to_string())?
     }
 
+    async fn list(&self, filter: &InvitationFilter, page: &PageRequest) -> Result<Page<Invitation>, String> {
+        let mut filters = Filters::new();
+        filters.add("owner_id = ?", [filter.owner_id.to_string()]);
+        if let Some(status) = &filter.status {
+            filters.add("status = ?", [format!("{:?}", status)]);
+        }
+        if let Some(email) = &filter.email {
+            filters.add_contains("invitee_email", email);
+        }
+        let sort = filter.sort;
+        let page = page.clone();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Page<Invitation>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let sort_column = match sort {
+                InvitationSort::CreatedAt => "created_at",
+                InvitationSort::InviteeEmail => "invitee_email",
+            };
+            let (rows, total) = paging::load_page::<DbInvitation>(
+                &mut conn,
+                "invitations",
+                "id, owner_id, invitee_email, token, granted_paths, status, expires_at, created_at, require_email_verification",
+                &filters,
+                sort_column,
+                &page,
+            )?;
+            Page::from_rows(rows, page.limit, total, |row| match sort {
+                InvitationSort::CreatedAt => Cursor::new(&row.created_at, &row.id),
+                InvitationSort::InviteeEmail => Cursor::new(&row.invitee_email, &row.id),
+            })
+            .try_map(db_to_invitation)
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
     async fn update_status(&self, id: &uuid::Uuid, status: &str) -> Result<(), String> {
         let id_str = id.to_string();
         let status = status.to_string();
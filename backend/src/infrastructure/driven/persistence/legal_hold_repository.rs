@@ -0,0 +1,133 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::SqliteConnection;
+use crate::application::ports::legal_hold_repository::LegalHoldRepository;
+use crate::domain::entities::legal_hold::LegalHold;
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::driven::persistence::db_types::DbLegalHold;
+
+pub struct SqliteLegalHoldRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+}
+
+impl SqliteLegalHoldRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>) -> Self {
+        Self { pool }
+    }
+}
+
+const SELECT_HOLD: &str =
+    "SELECT id, owner_id, path, reason, created_by, created_at, expires_at, released_at FROM legal_holds";
+
+fn db_to_legal_hold(row: DbLegalHold) -> Result<LegalHold, String> {
+    let parse_user = |s: &str, field: &str| {
+        uuid::Uuid::parse_str(s)
+            .map(UserId::from_uuid)
+            .map_err(|e| format!("Invalid {field}: {e}"))
+    };
+    let parse_time = |s: &str| {
+        s.parse::<chrono::DateTime<chrono::Utc>>()
+            .map_err(|e| format!("Invalid hold time: {e}"))
+    };
+
+    Ok(LegalHold {
+        id: uuid::Uuid::parse_str(&row.id).map_err(|e| format!("Invalid hold id: {e}"))?,
+        owner_id: parse_user(&row.owner_id, "owner_id")?,
+        path: row.path,
+        reason: row.reason,
+        created_by: parse_user(&row.created_by, "created_by")?,
+        // A hold that cannot be read must keep protecting, so these fail loudly
+        created_at: parse_time(&row.created_at)?,
+        expires_at: row.expires_at.as_deref().map(parse_time).transpose()?,
+        released_at: row.released_at.as_deref().map(parse_time).transpose()?,
+    })
+}
+
+#[async_trait]
+impl LegalHoldRepository for SqliteLegalHoldRepository {
+    async fn save(&self, hold: &LegalHold) -> Result<(), String> {
+        let id = hold.id.to_string();
+        let owner_id = hold.owner_id.to_string();
+        let path = hold.path.clone();
+        let reason = hold.reason.clone();
+        let created_by = hold.created_by.to_string();
+        let created_at = hold.created_at.to_rfc3339();
+        let expires_at = hold.expires_at.map(|at| at.to_rfc3339());
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query(
+                "INSERT INTO legal_holds (id, owner_id, path, reason, created_by, created_at, expires_at) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)"
+            )
+            .bind::<diesel::sql_types::Text, _>(&id)
+            .bind::<diesel::sql_types::Text, _>(&owner_id)
+            .bind::<diesel::sql_types::Text, _>(&path)
+            .bind::<diesel::sql_types::Text, _>(&reason)
+            .bind::<diesel::sql_types::Text, _>(&created_by)
+            .bind::<diesel::sql_types::Text, _>(&created_at)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&expires_at)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to save legal hold: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find_by_id(&self, id: &uuid::Uuid) -> Result<Option<LegalHold>, String> {
+        let id = id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Option<LegalHold>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbLegalHold> = diesel::sql_query(format!("{SELECT_HOLD} WHERE id = ?1"))
+                .bind::<diesel::sql_types::Text, _>(&id)
+                .load(&mut conn)
+                .map_err(|e| format!("Database error: {e}"))?;
+            rows.into_iter().next().map(db_to_legal_hold).transpose()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn list_for_owner(&self, owner_id: &UserId) -> Result<Vec<LegalHold>, String> {
+        let owner_id = owner_id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<LegalHold>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbLegalHold> =
+                diesel::sql_query(format!("{SELECT_HOLD} WHERE owner_id = ?1 ORDER BY created_at DESC"))
+                    .bind::<diesel::sql_types::Text, _>(&owner_id)
+                    .load(&mut conn)
+                    .map_err(|e| format!("Database error: {e}"))?;
+            rows.into_iter().map(db_to_legal_hold).collect()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn release(&self, id: &uuid::Uuid, at: chrono::DateTime<chrono::Utc>) -> Result<bool, String> {
+        let id = id.to_string();
+        let at = at.to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<bool, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let updated = diesel::sql_query(
+                "UPDATE legal_holds SET released_at = ?1 WHERE id = ?2 AND released_at IS NULL"
+            )
+            .bind::<diesel::sql_types::Text, _>(&at)
+            .bind::<diesel::sql_types::Text, _>(&id)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to release legal hold: {e}"))?;
+            Ok(updated > 0)
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+}
@@ -0,0 +1,80 @@
+use async_trait::async_trait;
+use redis::AsyncCommands;
+use crate::application::ports::LoginAttemptRepository;
+
+pub struct RedisLoginAttemptRepository {
+    client: redis::Client,
+}
+
+impl RedisLoginAttemptRepository {
+    pub fn new(client: redis::Client) -> Self {
+        Self { client }
+    }
+}
+
+fn failures_key(subject: &str) -> String {
+    format!("login:failures:{}", subject)
+}
+
+fn blocked_key(subject: &str) -> String {
+    format!("login:blocked:{}", subject)
+}
+
+#[async_trait]
+impl LoginAttemptRepository for RedisLoginAttemptRepository {
+    async fn record_failure(&self, subject: &str, window_seconds: u64) -> Result<u32, String> {
+        let mut conn = self.client.get_multiplexed_async_connection()
+            .await
+            .map_err(|e| format!("Redis connection error: {}", e))?;
+
+        let key = failures_key(subject);
+        let failures: u32 = conn.incr(&key, 1)
+            .await
+            .map_err(|e| format!("Failed to record login failure: {}", e))?;
+        // The window starts at the first failure
+        if failures == 1 {
+            conn.expire::<_, ()>(&key, window_seconds as i64)
+                .await
+                .map_err(|e| format!("Failed to set failure window: {}", e))?;
+        }
+
+        Ok(failures)
+    }
+
+    async fn block(&self, subject: &str, seconds: u64) -> Result<(), String> {
+        let mut conn = self.client.get_multiplexed_async_connection()
+            .await
+            .map_err(|e| format!("Redis connection error: {}", e))?;
+
+        conn.set_ex::<_, _, ()>(blocked_key(subject), 1, seconds)
+            .await
+            .map_err(|e| format!("Failed to block login: {}", e))?;
+
+        Ok(())
+    }
+
+    async fn blocked_for(&self, subject: &str) -> Result<Option<u64>, String> {
+        let mut conn = self.client.get_multiplexed_async_connection()
+            .await
+            .map_err(|e| format!("Redis connection error: {}", e))?;
+
+        // TTL is -2 for a missing key and -1 for a key without expiry
+        let ttl: i64 = conn.ttl(blocked_key(subject))
+            .await
+            .map_err(|e| format!("Failed to read login block: {}", e))?;
+
+        Ok((ttl > 0).then_some(ttl as u64))
+    }
+
+    async fn clear(&self, subject: &str) -> Result<(), String> {
+        let mut conn = self.client.get_multiplexed_async_connection()
+            .await
+            .map_err(|e| format!("Redis connection error: {}", e))?;
+
+        conn.del::<_, ()>(vec![failures_key(subject), blocked_key(subject)])
+            .await
+            .map_err(|e| format!("Failed to clear login attempts: {}", e))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,230 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::SqliteConnection;
+use crate::application::ports::media_metadata_repository::{CameraFacet, MediaFacets, MediaMetadataRepository, YearFacet};
+use crate::domain::entities::media_metadata::MediaMetadata;
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::driven::persistence::db_types::{DbFacet, DbMediaMetadata};
+
+const COLUMNS: &str = "owner_id, path, taken_at, camera, latitude, longitude, width, height, duration_secs, extracted_at";
+const TAKEN_AT_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+pub struct SqliteMediaMetadataRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+}
+
+impl SqliteMediaMetadataRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>) -> Self {
+        Self { pool }
+    }
+}
+
+fn db_to_metadata(row: DbMediaMetadata) -> Result<MediaMetadata, String> {
+    let owner_uuid = uuid::Uuid::parse_str(&row.owner_id).map_err(|e| format!("Invalid owner_id: {e}"))?;
+    let extracted_at = row
+        .extracted_at
+        .parse::<chrono::DateTime<chrono::Utc>>()
+        .unwrap_or_else(|_| chrono::Utc::now());
+
+    Ok(MediaMetadata {
+        owner_id: UserId::from_uuid(owner_uuid),
+        path: row.path,
+        taken_at: row.taken_at.and_then(|t| NaiveDateTime::parse_from_str(&t, TAKEN_AT_FORMAT).ok()),
+        camera: row.camera,
+        latitude: row.latitude,
+        longitude: row.longitude,
+        width: row.width.map(|w| w.max(0) as u32),
+        height: row.height.map(|h| h.max(0) as u32),
+        duration_secs: row.duration_secs,
+        extracted_at,
+    })
+}
+
+#[async_trait]
+impl MediaMetadataRepository for SqliteMediaMetadataRepository {
+    async fn save(&self, metadata: &MediaMetadata) -> Result<(), String> {
+        let owner_id = metadata.owner_id.to_string();
+        let path = metadata.path.clone();
+        let taken_at = metadata.taken_at.map(|t| t.format(TAKEN_AT_FORMAT).to_string());
+        let camera = metadata.camera.clone();
+        let (latitude, longitude) = (metadata.latitude, metadata.longitude);
+        let width = metadata.width.map(|w| w as i32);
+        let height = metadata.height.map(|h| h as i32);
+        let duration_secs = metadata.duration_secs;
+        let extracted_at = metadata.extracted_at.to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query(format!("INSERT OR REPLACE INTO media_metadata ({COLUMNS}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)"))
+                .bind::<diesel::sql_types::Text, _>(&owner_id)
+                .bind::<diesel::sql_types::Text, _>(&path)
+                .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&taken_at)
+                .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&camera)
+                .bind::<diesel::sql_types::Nullable<diesel::sql_types::Double>, _>(latitude)
+                .bind::<diesel::sql_types::Nullable<diesel::sql_types::Double>, _>(longitude)
+                .bind::<diesel::sql_types::Nullable<diesel::sql_types::Integer>, _>(width)
+                .bind::<diesel::sql_types::Nullable<diesel::sql_types::Integer>, _>(height)
+                .bind::<diesel::sql_types::Nullable<diesel::sql_types::Double>, _>(duration_secs)
+                .bind::<diesel::sql_types::Text, _>(&extracted_at)
+                .execute(&mut conn)
+                .map_err(|e| format!("Failed to save media metadata: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find(&self, owner_id: &UserId, path: &str) -> Result<Option<MediaMetadata>, String> {
+        let owner_id_str = owner_id.to_string();
+        let path = path.trim_matches('/').to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Option<MediaMetadata>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbMediaMetadata> = diesel::sql_query(format!(
+                "SELECT {COLUMNS} FROM media_metadata WHERE owner_id = ?1 AND path = ?2"
+            ))
+            .bind::<diesel::sql_types::Text, _>(&owner_id_str)
+            .bind::<diesel::sql_types::Text, _>(&path)
+            .load(&mut conn)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+            rows.into_iter().next().map(db_to_metadata).transpose()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn search(&self, owner_id: &UserId, year: Option<i32>, camera: Option<&str>, limit: u32) -> Result<Vec<MediaMetadata>, String> {
+        let owner_id_str = owner_id.to_string();
+        let year = year.map(|y| format!("{y:04}"));
+        let camera = camera.map(str::to_string);
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<MediaMetadata>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbMediaMetadata> = diesel::sql_query(format!(
+                "SELECT {COLUMNS} FROM media_metadata WHERE owner_id = ?1 \
+                 AND (?2 IS NULL OR substr(taken_at, 1, 4) = ?2) AND (?3 IS NULL OR camera = ?3) \
+                 ORDER BY taken_at DESC, path ASC LIMIT ?4"
+            ))
+            .bind::<diesel::sql_types::Text, _>(&owner_id_str)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&year)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&camera)
+            .bind::<diesel::sql_types::Integer, _>(limit as i32)
+            .load(&mut conn)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+            rows.into_iter().map(db_to_metadata).collect()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn list_under(&self, owner_id: &UserId, folder: &str) -> Result<Vec<MediaMetadata>, String> {
+        let owner_id_str = owner_id.to_string();
+        let folder = folder.trim_matches('/').to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<MediaMetadata>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbMediaMetadata> = diesel::sql_query(format!(
+                "SELECT {COLUMNS} FROM media_metadata \
+                 WHERE owner_id = ?1 AND substr(path, 1, length(?2) + 1) = ?2 || '/' ORDER BY path ASC"
+            ))
+            .bind::<diesel::sql_types::Text, _>(&owner_id_str)
+            .bind::<diesel::sql_types::Text, _>(&folder)
+            .load(&mut conn)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+            rows.into_iter().map(db_to_metadata).collect()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn facets(&self, owner_id: &UserId) -> Result<MediaFacets, String> {
+        let owner_id_str = owner_id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<MediaFacets, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let years: Vec<DbFacet> = diesel::sql_query(
+                "SELECT substr(taken_at, 1, 4) AS value, COUNT(*) AS count FROM media_metadata \
+                 WHERE owner_id = ?1 AND taken_at IS NOT NULL GROUP BY value ORDER BY value DESC"
+            )
+            .bind::<diesel::sql_types::Text, _>(&owner_id_str)
+            .load(&mut conn)
+            .map_err(|e| format!("Database error: {e}"))?;
+            let cameras: Vec<DbFacet> = diesel::sql_query(
+                "SELECT camera AS value, COUNT(*) AS count FROM media_metadata \
+                 WHERE owner_id = ?1 AND camera IS NOT NULL GROUP BY camera ORDER BY count DESC, camera ASC"
+            )
+            .bind::<diesel::sql_types::Text, _>(&owner_id_str)
+            .load(&mut conn)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+            Ok(MediaFacets {
+                years: years
+                    .into_iter()
+                    .filter_map(|f| Some(YearFacet { year: f.value.parse().ok()?, count: f.count.max(0) as u64 }))
+                    .collect(),
+                cameras: cameras
+                    .into_iter()
+                    .map(|f| CameraFacet { camera: f.value, count: f.count.max(0) as u64 })
+                    .collect(),
+            })
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn move_path(&self, owner_id: &UserId, from: &str, to: &str) -> Result<(), String> {
+        let owner_id_str = owner_id.to_string();
+        let from = from.trim_matches('/').to_string();
+        let to = to.trim_matches('/').to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            // Matches the path itself and everything under it, without LIKE wildcards
+            diesel::sql_query(
+                "UPDATE media_metadata SET path = ?3 || substr(path, length(?2) + 1) \
+                 WHERE owner_id = ?1 AND (path = ?2 OR substr(path, 1, length(?2) + 1) = ?2 || '/')"
+            )
+            .bind::<diesel::sql_types::Text, _>(&owner_id_str)
+            .bind::<diesel::sql_types::Text, _>(&from)
+            .bind::<diesel::sql_types::Text, _>(&to)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to move media metadata: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn delete_path(&self, owner_id: &UserId, path: &str) -> Result<(), String> {
+        let owner_id_str = owner_id.to_string();
+        let path = path.trim_matches('/').to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query(
+                "DELETE FROM media_metadata \
+                 WHERE owner_id = ?1 AND (path = ?2 OR substr(path, 1, length(?2) + 1) = ?2 || '/')"
+            )
+            .bind::<diesel::sql_types::Text, _>(&owner_id_str)
+            .bind::<diesel::sql_types::Text, _>(&path)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to delete media metadata: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+}
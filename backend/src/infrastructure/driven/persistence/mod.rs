@@ -1,4 +1,5 @@
 mod db_types;
+mod paging;
 pub mod schema;
 pub mod user_repository;
 pub mod credential_repository;
@@ -6,6 +7,44 @@ pub mod challenge_repository;
 pub mod invitation_repository;
 pub mod file_permission_repository;
 pub mod session_repository;
+pub mod quality_preference_repository;
+pub mod user_preferences_repository;
+pub mod audit_repository;
+pub mod notification_repository;
+pub mod login_attempt_repository;
+pub mod access_policy_repository;
+pub mod verification_code_repository;
+pub mod client_group_repository;
+pub mod delegation_repository;
+pub mod file_job_repository;
+pub mod upload_session_repository;
+pub mod session_timeline_repository;
+pub mod session_snapshot_repository;
+pub mod session_ownership_repository;
+pub mod scheduler_repository;
+pub mod app_state_repository;
+pub mod bandwidth_repository;
+pub mod app_crash_repository;
+pub mod auth_session_repository;
+pub mod data_export_repository;
+pub mod account_deletion_repository;
+pub mod vault_import_repository;
+pub mod external_identity_repository;
+pub mod provisioning_repository;
+pub mod access_token_repository;
+pub mod legal_hold_repository;
+pub mod app_setting_repository;
+pub mod tenant_repository;
+pub mod branding_repository;
+pub mod processing_repository;
+pub mod media_metadata_repository;
+pub mod organization_rule_repository;
+pub mod file_hash_repository;
+pub mod folder_export_repository;
+pub mod gallery_share_repository;
+pub mod file_comment_repository;
+pub mod file_request_repository;
+pub mod impersonation_consent_repository;
 
 pub use user_repository::SqliteUserRepository;
 pub use credential_repository::SqliteCredentialRepository;
@@ -13,3 +52,41 @@ pub use challenge_repository::RedisChallengeRepository;
 pub use invitation_repository::SqliteInvitationRepository;
 pub use file_permission_repository::SqliteFilePermissionRepository;
 pub use session_repository::SqliteSessionRepository;
+pub use quality_preference_repository::SqliteQualityPreferenceRepository;
+pub use user_preferences_repository::SqliteUserPreferencesRepository;
+pub use audit_repository::SqliteAuditRepository;
+pub use notification_repository::SqliteNotificationRepository;
+pub use login_attempt_repository::RedisLoginAttemptRepository;
+pub use access_policy_repository::SqliteAccessPolicyRepository;
+pub use verification_code_repository::RedisVerificationCodeRepository;
+pub use client_group_repository::SqliteClientGroupRepository;
+pub use delegation_repository::SqliteDelegationRepository;
+pub use file_job_repository::SqliteFileJobRepository;
+pub use upload_session_repository::SqliteUploadSessionRepository;
+pub use session_timeline_repository::SqliteSessionTimelineRepository;
+pub use session_snapshot_repository::SqliteSessionSnapshotRepository;
+pub use session_ownership_repository::RedisSessionOwnershipRepository;
+pub use scheduler_repository::RedisSchedulerRepository;
+pub use app_state_repository::SqliteAppStateRepository;
+pub use bandwidth_repository::SqliteBandwidthRepository;
+pub use app_crash_repository::SqliteAppCrashRepository;
+pub use auth_session_repository::SqliteAuthSessionRepository;
+pub use data_export_repository::SqliteDataExportRepository;
+pub use account_deletion_repository::SqliteAccountDeletionRepository;
+pub use vault_import_repository::SqliteVaultImportRepository;
+pub use external_identity_repository::SqliteExternalIdentityRepository;
+pub use provisioning_repository::SqliteProvisioningRepository;
+pub use access_token_repository::SqliteAccessTokenRepository;
+pub use legal_hold_repository::SqliteLegalHoldRepository;
+pub use app_setting_repository::SqliteAppSettingRepository;
+pub use tenant_repository::SqliteTenantRepository;
+pub use branding_repository::SqliteBrandingRepository;
+pub use processing_repository::SqliteProcessingRepository;
+pub use media_metadata_repository::SqliteMediaMetadataRepository;
+pub use organization_rule_repository::SqliteOrganizationRuleRepository;
+pub use file_hash_repository::SqliteFileHashRepository;
+pub use folder_export_repository::SqliteFolderExportRepository;
+pub use gallery_share_repository::SqliteGalleryShareRepository;
+pub use file_comment_repository::SqliteFileCommentRepository;
+pub use file_request_repository::SqliteFileRequestRepository;
+pub use impersonation_consent_repository::SqliteImpersonationConsentRepository;
@@ -0,0 +1,49 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::SqliteConnection;
+use crate::application::ports::notification_repository::NotificationRepository;
+use crate::domain::entities::notification::Notification;
+
+pub struct SqliteNotificationRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+}
+
+impl SqliteNotificationRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl NotificationRepository for SqliteNotificationRepository {
+    async fn create(&self, notification: &Notification) -> Result<(), String> {
+        let id = notification.id.to_string();
+        let user_id = notification.user_id.to_string();
+        let kind = notification.kind.clone();
+        let payload = notification.payload.to_string();
+        let read_at = notification.read_at.map(|dt: chrono::DateTime<chrono::Utc>| dt.to_rfc3339());
+        let created_at = notification.created_at.to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query(
+                "INSERT INTO notifications (id, user_id, kind, payload, read_at, created_at) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)"
+            )
+            .bind::<diesel::sql_types::Text, _>(&id)
+            .bind::<diesel::sql_types::Text, _>(&user_id)
+            .bind::<diesel::sql_types::Text, _>(&kind)
+            .bind::<diesel::sql_types::Text, _>(&payload)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&read_at)
+            .bind::<diesel::sql_types::Text, _>(&created_at)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to create notification: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+}
@@ -0,0 +1,130 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::SqliteConnection;
+use crate::application::ports::organization_rule_repository::OrganizationRuleRepository;
+use crate::domain::entities::organization_rule::OrganizationRule;
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::driven::persistence::db_types::DbOrganizationRule;
+
+const COLUMNS: &str = "id, owner_id, name, source, destination, enabled, created_at, updated_at";
+
+pub struct SqliteOrganizationRuleRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+}
+
+impl SqliteOrganizationRuleRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>) -> Self {
+        Self { pool }
+    }
+}
+
+fn parse_time(s: &str) -> chrono::DateTime<chrono::Utc> {
+    s.parse::<chrono::DateTime<chrono::Utc>>().unwrap_or_else(|_| chrono::Utc::now())
+}
+
+fn db_to_rule(row: DbOrganizationRule) -> Result<OrganizationRule, String> {
+    let id = uuid::Uuid::parse_str(&row.id).map_err(|e| format!("Invalid id: {e}"))?;
+    let owner_uuid = uuid::Uuid::parse_str(&row.owner_id).map_err(|e| format!("Invalid owner_id: {e}"))?;
+
+    Ok(OrganizationRule {
+        id,
+        owner_id: UserId::from_uuid(owner_uuid),
+        name: row.name,
+        source: row.source,
+        destination: row.destination,
+        enabled: row.enabled,
+        created_at: parse_time(&row.created_at),
+        updated_at: parse_time(&row.updated_at),
+    })
+}
+
+#[async_trait]
+impl OrganizationRuleRepository for SqliteOrganizationRuleRepository {
+    async fn save(&self, rule: &OrganizationRule) -> Result<(), String> {
+        let id = rule.id.to_string();
+        let owner_id = rule.owner_id.to_string();
+        let name = rule.name.clone();
+        let source = rule.source.clone();
+        let destination = rule.destination.clone();
+        let enabled = rule.enabled;
+        let created_at = rule.created_at.to_rfc3339();
+        let updated_at = rule.updated_at.to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query(format!(
+                "INSERT INTO organization_rules ({COLUMNS}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8) \
+                 ON CONFLICT(id) DO UPDATE SET name=excluded.name, source=excluded.source, \
+                 destination=excluded.destination, enabled=excluded.enabled, updated_at=excluded.updated_at"
+            ))
+            .bind::<diesel::sql_types::Text, _>(&id)
+            .bind::<diesel::sql_types::Text, _>(&owner_id)
+            .bind::<diesel::sql_types::Text, _>(&name)
+            .bind::<diesel::sql_types::Text, _>(&source)
+            .bind::<diesel::sql_types::Text, _>(&destination)
+            .bind::<diesel::sql_types::Bool, _>(enabled)
+            .bind::<diesel::sql_types::Text, _>(&created_at)
+            .bind::<diesel::sql_types::Text, _>(&updated_at)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to save organization rule: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find_by_id(&self, id: &uuid::Uuid) -> Result<Option<OrganizationRule>, String> {
+        let id_str = id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Option<OrganizationRule>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbOrganizationRule> = diesel::sql_query(format!("SELECT {COLUMNS} FROM organization_rules WHERE id = ?1"))
+                .bind::<diesel::sql_types::Text, _>(&id_str)
+                .load(&mut conn)
+                .map_err(|e| format!("Database error: {e}"))?;
+
+            rows.into_iter().next().map(db_to_rule).transpose()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find_by_owner(&self, owner_id: &UserId) -> Result<Vec<OrganizationRule>, String> {
+        let owner_id_str = owner_id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<OrganizationRule>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbOrganizationRule> = diesel::sql_query(format!(
+                "SELECT {COLUMNS} FROM organization_rules WHERE owner_id = ?1 ORDER BY created_at ASC"
+            ))
+            .bind::<diesel::sql_types::Text, _>(&owner_id_str)
+            .load(&mut conn)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+            rows.into_iter().map(db_to_rule).collect()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn delete(&self, id: &uuid::Uuid) -> Result<(), String> {
+        let id_str = id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query("DELETE FROM organization_rules WHERE id = ?1")
+                .bind::<diesel::sql_types::Text, _>(&id_str)
+                .execute(&mut conn)
+                .map_err(|e| format!("Failed to delete organization rule: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+}
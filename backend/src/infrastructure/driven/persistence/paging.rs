@@ -0,0 +1,106 @@
+use diesel::prelude::*;
+use diesel::sql_types::Text;
+use diesel::sqlite::Sqlite;
+use diesel::SqliteConnection;
+use crate::application::ports::pagination::{PageRequest, SortDirection};
+use crate::infrastructure::driven::persistence::db_types::DbCount;
+
+/// WHERE clauses ANDed together; each uses `?` placeholders bound, in order, to text values.
+#[derive(Debug, Clone, Default)]
+pub struct Filters {
+    clauses: Vec<String>,
+    binds: Vec<String>,
+}
+
+impl Filters {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, clause: impl Into<String>, binds: impl IntoIterator<Item = String>) -> &mut Self {
+        self.clauses.push(clause.into());
+        self.binds.extend(binds);
+        self
+    }
+
+    /// Keep rows whose `column` starts with `prefix`.
+    pub fn add_prefix(&mut self, column: &str, prefix: &str) -> &mut Self {
+        self.add(format!("{column} LIKE ? ESCAPE '\\'"), [format!("{}%", escape_like(prefix))])
+    }
+
+    /// Keep rows whose `column` contains `needle`, ignoring ASCII case.
+    pub fn add_contains(&mut self, column: &str, needle: &str) -> &mut Self {
+        self.add(format!("LOWER({column}) LIKE ? ESCAPE '\\'"), [format!("%{}%", escape_like(&needle.to_lowercase()))])
+    }
+
+    /// Keep rows whose `column` is one of the `subtrees` or lies below one. No subtree
+    /// matches nothing.
+    pub fn add_subtrees(&mut self, column: &str, subtrees: &[String]) -> &mut Self {
+        if subtrees.is_empty() {
+            return self.add("0", []);
+        }
+        let clause = vec![format!("{column} = ? OR {column} LIKE ? ESCAPE '\\'"); subtrees.len()].join(" OR ");
+        let binds = subtrees.iter().flat_map(|s| [s.clone(), format!("{}/%", escape_like(s))]);
+        self.add(format!("({clause})"), binds.collect::<Vec<_>>())
+    }
+
+    fn where_sql(&self) -> String {
+        if self.clauses.is_empty() {
+            String::new()
+        } else {
+            format!(" WHERE {}", self.clauses.join(" AND "))
+        }
+    }
+}
+
+fn escape_like(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+}
+
+/// Keyset-paginate `SELECT columns FROM table`, ordered by `sort_column` then `id`.
+/// Loads up to `limit + 1` rows for `Page::from_rows`, and counts every match.
+pub fn load_page<Row>(
+    conn: &mut SqliteConnection,
+    table: &str,
+    columns: &str,
+    filters: &Filters,
+    sort_column: &str,
+    page: &PageRequest,
+) -> Result<(Vec<Row>, u64), String>
+where
+    Row: QueryableByName<Sqlite> + 'static,
+{
+    let mut count_query = diesel::sql_query(format!("SELECT COUNT(*) AS count FROM {table}{}", filters.where_sql()))
+        .into_boxed::<Sqlite>();
+    for value in &filters.binds {
+        count_query = count_query.bind::<Text, _>(value.clone());
+    }
+    let total = count_query
+        .get_result::<DbCount>(conn)
+        .map_err(|e| format!("Database error: {e}"))?
+        .count;
+
+    let mut filters = filters.clone();
+    if let Some(after) = &page.after {
+        let op = match page.direction {
+            SortDirection::Asc => ">",
+            SortDirection::Desc => "<",
+        };
+        filters.add(
+            format!("({sort_column} {op} ? OR ({sort_column} = ? AND id {op} ?))"),
+            [after.key.clone(), after.key.clone(), after.id.clone()],
+        );
+    }
+    let direction = page.direction.as_sql();
+    let mut query = diesel::sql_query(format!(
+        "SELECT {columns} FROM {table}{} ORDER BY {sort_column} {direction}, id {direction} LIMIT {}",
+        filters.where_sql(),
+        page.limit as u64 + 1
+    ))
+    .into_boxed::<Sqlite>();
+    for value in filters.binds {
+        query = query.bind::<Text, _>(value);
+    }
+    let rows = query.load::<Row>(conn).map_err(|e| format!("Database error: {e}"))?;
+    Ok((rows, total.max(0) as u64))
+}
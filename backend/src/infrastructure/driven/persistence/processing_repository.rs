@@ -0,0 +1,147 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::SqliteConnection;
+use crate::application::ports::processing_repository::ProcessingRepository;
+use crate::domain::entities::processing_task::{ProcessingStatus, ProcessingTask};
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::driven::persistence::db_types::DbProcessingTask;
+
+const COLUMNS: &str = "id, owner_id, path, size, status, done, attempts, last_error, next_attempt_at, created_at, updated_at";
+
+pub struct SqliteProcessingRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+}
+
+impl SqliteProcessingRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>) -> Self {
+        Self { pool }
+    }
+}
+
+fn parse_time(s: &str) -> DateTime<Utc> {
+    s.parse::<DateTime<Utc>>().unwrap_or_else(|_| Utc::now())
+}
+
+fn db_to_task(row: DbProcessingTask) -> Result<ProcessingTask, String> {
+    let id = uuid::Uuid::parse_str(&row.id).map_err(|e| format!("Invalid id: {e}"))?;
+    let owner_uuid = uuid::Uuid::parse_str(&row.owner_id).map_err(|e| format!("Invalid owner_id: {e}"))?;
+    let done: Vec<String> = serde_json::from_str(&row.done).map_err(|e| format!("Invalid done stages: {e}"))?;
+
+    Ok(ProcessingTask {
+        id,
+        owner_id: UserId::from_uuid(owner_uuid),
+        path: row.path,
+        size: row.size.max(0) as u64,
+        status: ProcessingStatus::from_db_str(&row.status)?,
+        done,
+        attempts: row.attempts.max(0) as u32,
+        last_error: row.last_error,
+        next_attempt_at: parse_time(&row.next_attempt_at),
+        created_at: parse_time(&row.created_at),
+        updated_at: parse_time(&row.updated_at),
+    })
+}
+
+#[async_trait]
+impl ProcessingRepository for SqliteProcessingRepository {
+    async fn save(&self, task: &ProcessingTask) -> Result<(), String> {
+        let id = task.id.to_string();
+        let owner_id = task.owner_id.to_string();
+        let path = task.path.clone();
+        let size = task.size as i64;
+        let status = task.status.as_db_str();
+        let done = serde_json::to_string(&task.done).map_err(|e| e.to_string())?;
+        let attempts = task.attempts as i32;
+        let last_error = task.last_error.clone();
+        let next_attempt_at = task.next_attempt_at.to_rfc3339();
+        let created_at = task.created_at.to_rfc3339();
+        let updated_at = task.updated_at.to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query(format!(
+                "INSERT INTO processing_tasks ({COLUMNS}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11) \
+                 ON CONFLICT(id) DO UPDATE SET status=excluded.status, done=excluded.done, attempts=excluded.attempts, \
+                 last_error=excluded.last_error, next_attempt_at=excluded.next_attempt_at, updated_at=excluded.updated_at"
+            ))
+            .bind::<diesel::sql_types::Text, _>(&id)
+            .bind::<diesel::sql_types::Text, _>(&owner_id)
+            .bind::<diesel::sql_types::Text, _>(&path)
+            .bind::<diesel::sql_types::BigInt, _>(size)
+            .bind::<diesel::sql_types::Text, _>(status)
+            .bind::<diesel::sql_types::Text, _>(&done)
+            .bind::<diesel::sql_types::Integer, _>(attempts)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&last_error)
+            .bind::<diesel::sql_types::Text, _>(&next_attempt_at)
+            .bind::<diesel::sql_types::Text, _>(&created_at)
+            .bind::<diesel::sql_types::Text, _>(&updated_at)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to save processing task: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find_by_id(&self, id: &uuid::Uuid) -> Result<Option<ProcessingTask>, String> {
+        let id_str = id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Option<ProcessingTask>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbProcessingTask> = diesel::sql_query(format!("SELECT {COLUMNS} FROM processing_tasks WHERE id = ?1"))
+                .bind::<diesel::sql_types::Text, _>(&id_str)
+                .load(&mut conn)
+                .map_err(|e| format!("Database error: {e}"))?;
+
+            rows.into_iter().next().map(db_to_task).transpose()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find_due(&self, now: DateTime<Utc>, limit: u32) -> Result<Vec<ProcessingTask>, String> {
+        let now_str = now.to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<ProcessingTask>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbProcessingTask> = diesel::sql_query(format!(
+                "SELECT {COLUMNS} FROM processing_tasks WHERE status = 'pending' AND next_attempt_at <= ?1 \
+                 ORDER BY created_at ASC LIMIT ?2"
+            ))
+            .bind::<diesel::sql_types::Text, _>(&now_str)
+            .bind::<diesel::sql_types::Integer, _>(limit as i32)
+            .load(&mut conn)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+            rows.into_iter().map(db_to_task).collect()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn list_dead_letters(&self, owner_id: &UserId) -> Result<Vec<ProcessingTask>, String> {
+        let owner_id_str = owner_id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<ProcessingTask>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbProcessingTask> = diesel::sql_query(format!(
+                "SELECT {COLUMNS} FROM processing_tasks WHERE owner_id = ?1 AND status = 'dead_lettered' \
+                 ORDER BY updated_at DESC"
+            ))
+            .bind::<diesel::sql_types::Text, _>(&owner_id_str)
+            .load(&mut conn)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+            rows.into_iter().map(db_to_task).collect()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+}
@@ -0,0 +1,239 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::SqliteConnection;
+use crate::application::ports::provisioning_repository::{IdempotencyClaim, ProvisioningRepository, StoredResponse};
+use crate::domain::entities::provisioning_token::ProvisioningToken;
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::driven::persistence::db_types::{DbProvisioningRequest, DbProvisioningToken};
+
+pub struct SqliteProvisioningRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+}
+
+impl SqliteProvisioningRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>) -> Self {
+        Self { pool }
+    }
+}
+
+const SELECT_TOKEN: &str =
+    "SELECT id, owner_id, name, token_hash, created_at, last_used_at, revoked_at FROM provisioning_tokens";
+
+fn db_to_token(row: DbProvisioningToken) -> Result<ProvisioningToken, String> {
+    let parse_time = |s: &str| {
+        s.parse::<chrono::DateTime<chrono::Utc>>()
+            .unwrap_or_else(|_| chrono::Utc::now())
+    };
+
+    Ok(ProvisioningToken {
+        id: uuid::Uuid::parse_str(&row.id).map_err(|e| format!("Invalid token id: {e}"))?,
+        owner_id: uuid::Uuid::parse_str(&row.owner_id)
+            .map(UserId::from_uuid)
+            .map_err(|e| format!("Invalid owner_id: {e}"))?,
+        name: row.name,
+        token_hash: row.token_hash,
+        created_at: parse_time(&row.created_at),
+        last_used_at: row.last_used_at.as_deref().map(parse_time),
+        revoked_at: row.revoked_at.as_deref().map(parse_time),
+    })
+}
+
+#[async_trait]
+impl ProvisioningRepository for SqliteProvisioningRepository {
+    async fn save_token(&self, token: &ProvisioningToken) -> Result<(), String> {
+        let id = token.id.to_string();
+        let owner_id = token.owner_id.to_string();
+        let name = token.name.clone();
+        let token_hash = token.token_hash.clone();
+        let created_at = token.created_at.to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query(
+                "INSERT INTO provisioning_tokens (id, owner_id, name, token_hash, created_at) VALUES (?1, ?2, ?3, ?4, ?5)"
+            )
+            .bind::<diesel::sql_types::Text, _>(&id)
+            .bind::<diesel::sql_types::Text, _>(&owner_id)
+            .bind::<diesel::sql_types::Text, _>(&name)
+            .bind::<diesel::sql_types::Text, _>(&token_hash)
+            .bind::<diesel::sql_types::Text, _>(&created_at)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to save provisioning token: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find_token_by_hash(&self, token_hash: &str) -> Result<Option<ProvisioningToken>, String> {
+        let token_hash = token_hash.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Option<ProvisioningToken>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbProvisioningToken> = diesel::sql_query(format!("{SELECT_TOKEN} WHERE token_hash = ?1"))
+                .bind::<diesel::sql_types::Text, _>(&token_hash)
+                .load(&mut conn)
+                .map_err(|e| format!("Database error: {e}"))?;
+            rows.into_iter().next().map(db_to_token).transpose()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn list_tokens(&self, owner_id: &UserId) -> Result<Vec<ProvisioningToken>, String> {
+        let owner_id = owner_id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<ProvisioningToken>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbProvisioningToken> =
+                diesel::sql_query(format!("{SELECT_TOKEN} WHERE owner_id = ?1 ORDER BY created_at"))
+                    .bind::<diesel::sql_types::Text, _>(&owner_id)
+                    .load(&mut conn)
+                    .map_err(|e| format!("Database error: {e}"))?;
+            rows.into_iter().map(db_to_token).collect()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn revoke_token(&self, owner_id: &UserId, id: &uuid::Uuid) -> Result<bool, String> {
+        let owner_id = owner_id.to_string();
+        let id = id.to_string();
+        let now = chrono::Utc::now().to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<bool, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let updated = diesel::sql_query(
+                "UPDATE provisioning_tokens SET revoked_at = ?1 WHERE id = ?2 AND owner_id = ?3 AND revoked_at IS NULL"
+            )
+            .bind::<diesel::sql_types::Text, _>(&now)
+            .bind::<diesel::sql_types::Text, _>(&id)
+            .bind::<diesel::sql_types::Text, _>(&owner_id)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to revoke provisioning token: {e}"))?;
+            Ok(updated > 0)
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn touch_token(&self, id: &uuid::Uuid) -> Result<(), String> {
+        let id = id.to_string();
+        let now = chrono::Utc::now().to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query("UPDATE provisioning_tokens SET last_used_at = ?1 WHERE id = ?2")
+                .bind::<diesel::sql_types::Text, _>(&now)
+                .bind::<diesel::sql_types::Text, _>(&id)
+                .execute(&mut conn)
+                .map_err(|e| format!("Failed to update provisioning token: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn claim_key(&self, owner_id: &UserId, key: &str, request_hash: &str, max_age: chrono::Duration) -> Result<IdempotencyClaim, String> {
+        let owner_id = owner_id.to_string();
+        let key = key.to_string();
+        let request_hash = request_hash.to_string();
+        let now = chrono::Utc::now();
+        let cutoff = (now - max_age).to_rfc3339();
+        let now = now.to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<IdempotencyClaim, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                diesel::sql_query("DELETE FROM provisioning_requests WHERE created_at < ?1")
+                    .bind::<diesel::sql_types::Text, _>(&cutoff)
+                    .execute(conn)?;
+                // The primary key lets only one of two concurrent requests insert the row
+                let inserted = diesel::sql_query(
+                    "INSERT OR IGNORE INTO provisioning_requests \
+                     (owner_id, idempotency_key, request_hash, created_at) VALUES (?1, ?2, ?3, ?4)"
+                )
+                .bind::<diesel::sql_types::Text, _>(&owner_id)
+                .bind::<diesel::sql_types::Text, _>(&key)
+                .bind::<diesel::sql_types::Text, _>(&request_hash)
+                .bind::<diesel::sql_types::Text, _>(&now)
+                .execute(conn)?;
+                if inserted == 1 {
+                    return Ok(IdempotencyClaim::Claimed);
+                }
+                let row: DbProvisioningRequest = diesel::sql_query(
+                    "SELECT request_hash, status, response FROM provisioning_requests \
+                     WHERE owner_id = ?1 AND idempotency_key = ?2"
+                )
+                .bind::<diesel::sql_types::Text, _>(&owner_id)
+                .bind::<diesel::sql_types::Text, _>(&key)
+                .get_result(conn)?;
+                Ok(match (row.status, row.response) {
+                    (Some(status), Some(body)) => IdempotencyClaim::Done(StoredResponse {
+                        request_hash: row.request_hash,
+                        status: status.clamp(100, 599) as u16,
+                        body,
+                    }),
+                    _ => IdempotencyClaim::InProgress { request_hash: row.request_hash },
+                })
+            })
+            .map_err(|e| format!("Failed to claim idempotency key: {e}"))
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn save_response(&self, owner_id: &UserId, key: &str, response: &StoredResponse) -> Result<(), String> {
+        let owner_id = owner_id.to_string();
+        let key = key.to_string();
+        let response = response.clone();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query(
+                "UPDATE provisioning_requests SET status = ?1, response = ?2 \
+                 WHERE owner_id = ?3 AND idempotency_key = ?4 AND request_hash = ?5"
+            )
+            .bind::<diesel::sql_types::Integer, _>(response.status as i32)
+            .bind::<diesel::sql_types::Text, _>(&response.body)
+            .bind::<diesel::sql_types::Text, _>(&owner_id)
+            .bind::<diesel::sql_types::Text, _>(&key)
+            .bind::<diesel::sql_types::Text, _>(&response.request_hash)
+            .execute(&mut conn)
+            .map(|_| ())
+            .map_err(|e| format!("Failed to save provisioning response: {e}"))
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn release_key(&self, owner_id: &UserId, key: &str) -> Result<(), String> {
+        let owner_id = owner_id.to_string();
+        let key = key.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query(
+                "DELETE FROM provisioning_requests \
+                 WHERE owner_id = ?1 AND idempotency_key = ?2 AND status IS NULL"
+            )
+            .bind::<diesel::sql_types::Text, _>(&owner_id)
+            .bind::<diesel::sql_types::Text, _>(&key)
+            .execute(&mut conn)
+            .map(|_| ())
+            .map_err(|e| format!("Failed to release idempotency key: {e}"))
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+}
@@ -0,0 +1,95 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::SqliteConnection;
+use crate::application::ports::quality_preference_repository::QualityPreferenceRepository;
+use crate::domain::aggregates::application_session::StreamQuality;
+use crate::domain::entities::quality_preference::QualityPreference;
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::driven::persistence::db_types::DbQualityPreference;
+
+pub struct SqliteQualityPreferenceRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+}
+
+impl SqliteQualityPreferenceRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>) -> Self {
+        Self { pool }
+    }
+}
+
+fn db_to_quality_preference(row: DbQualityPreference) -> Result<QualityPreference, String> {
+    let user_uuid = uuid::Uuid::parse_str(&row.user_id).map_err(|e| format!("Invalid user_id: {e}"))?;
+    let updated_at = row
+        .updated_at
+        .parse::<chrono::DateTime<chrono::Utc>>()
+        .unwrap_or_else(|_| chrono::Utc::now());
+
+    Ok(QualityPreference {
+        user_id: UserId::from_uuid(user_uuid),
+        app_id: row.app_id,
+        quality: StreamQuality {
+            framerate: u8::try_from(row.framerate).map_err(|e| format!("Invalid framerate: {e}"))?,
+            max_bitrate: u32::try_from(row.max_bitrate).map_err(|e| format!("Invalid max_bitrate: {e}"))?,
+            resolution_scale: row.resolution_scale,
+        },
+        updated_at,
+    })
+}
+
+#[async_trait]
+impl QualityPreferenceRepository for SqliteQualityPreferenceRepository {
+    async fn save(&self, preference: &QualityPreference) -> Result<(), String> {
+        let user_id = preference.user_id.to_string();
+        let app_id = preference.app_id.clone();
+        let framerate = preference.quality.framerate as i32;
+        let max_bitrate = preference.quality.max_bitrate as i64;
+        let resolution_scale = preference.quality.resolution_scale;
+        let updated_at = preference.updated_at.to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query(
+                "INSERT INTO quality_preferences (user_id, app_id, framerate, max_bitrate, resolution_scale, updated_at) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6) \
+                 ON CONFLICT(user_id, app_id) DO UPDATE SET framerate=excluded.framerate, \
+                 max_bitrate=excluded.max_bitrate, resolution_scale=excluded.resolution_scale, updated_at=excluded.updated_at"
+            )
+            .bind::<diesel::sql_types::Text, _>(&user_id)
+            .bind::<diesel::sql_types::Text, _>(&app_id)
+            .bind::<diesel::sql_types::Integer, _>(framerate)
+            .bind::<diesel::sql_types::BigInt, _>(max_bitrate)
+            .bind::<diesel::sql_types::Float, _>(resolution_scale)
+            .bind::<diesel::sql_types::Text, _>(&updated_at)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to save quality preference: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find(&self, user_id: &UserId, app_id: &str) -> Result<Option<QualityPreference>, String> {
+        let user_id_str = user_id.to_string();
+        let app_id = app_id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Option<QualityPreference>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbQualityPreference> = diesel::sql_query(
+                "SELECT user_id, app_id, framerate, max_bitrate, resolution_scale, updated_at \
+                 FROM quality_preferences WHERE user_id = ?1 AND app_id = ?2"
+            )
+            .bind::<diesel::sql_types::Text, _>(&user_id_str)
+            .bind::<diesel::sql_types::Text, _>(&app_id)
+            .load(&mut conn)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+            rows.into_iter().next().map(db_to_quality_preference).transpose()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+}
@@ -0,0 +1,101 @@
+use async_trait::async_trait;
+use redis::AsyncCommands;
+use crate::application::ports::SchedulerRepository;
+use crate::domain::entities::placement::{HostStatus, PlacementDecision};
+
+const HOSTS_KEY: &str = "scheduler:hosts";
+const DECISIONS_KEY: &str = "scheduler:decisions";
+/// Placements kept for the admin view
+const DECISIONS_KEPT: isize = 200;
+
+pub struct RedisSchedulerRepository {
+    client: redis::Client,
+}
+
+impl RedisSchedulerRepository {
+    pub fn new(client: redis::Client) -> Self {
+        Self { client }
+    }
+}
+
+fn host_key(instance_id: &str) -> String {
+    format!("scheduler:host:{}", instance_id)
+}
+
+#[async_trait]
+impl SchedulerRepository for RedisSchedulerRepository {
+    async fn publish_host(&self, status: &HostStatus, ttl_seconds: u64) -> Result<(), String> {
+        let mut conn = self.client.get_multiplexed_async_connection()
+            .await
+            .map_err(|e| format!("Redis connection error: {}", e))?;
+
+        let json = serde_json::to_string(status)
+            .map_err(|e| format!("Failed to serialize host status: {}", e))?;
+        redis::pipe()
+            .set_ex(host_key(&status.instance_id), json, ttl_seconds)
+            .sadd(HOSTS_KEY, &status.instance_id)
+            .query_async::<()>(&mut conn)
+            .await
+            .map_err(|e| format!("Failed to publish host status: {}", e))?;
+
+        Ok(())
+    }
+
+    async fn hosts(&self) -> Result<Vec<HostStatus>, String> {
+        let mut conn = self.client.get_multiplexed_async_connection()
+            .await
+            .map_err(|e| format!("Redis connection error: {}", e))?;
+
+        let ids: Vec<String> = conn.smembers(HOSTS_KEY)
+            .await
+            .map_err(|e| format!("Failed to list hosts: {}", e))?;
+        let mut hosts = Vec::with_capacity(ids.len());
+        for id in ids {
+            let json: Option<String> = conn.get(host_key(&id))
+                .await
+                .map_err(|e| format!("Failed to read host status: {}", e))?;
+            match json.and_then(|json| serde_json::from_str(&json).ok()) {
+                Some(status) => hosts.push(status),
+                // The host stopped publishing, or published a format this version cannot read
+                None => conn.srem::<_, _, ()>(HOSTS_KEY, &id)
+                    .await
+                    .map_err(|e| format!("Failed to forget host: {}", e))?,
+            }
+        }
+
+        Ok(hosts)
+    }
+
+    async fn record_decision(&self, decision: &PlacementDecision) -> Result<(), String> {
+        let mut conn = self.client.get_multiplexed_async_connection()
+            .await
+            .map_err(|e| format!("Redis connection error: {}", e))?;
+
+        let json = serde_json::to_string(decision)
+            .map_err(|e| format!("Failed to serialize placement: {}", e))?;
+        redis::pipe()
+            .lpush(DECISIONS_KEY, json)
+            .ltrim(DECISIONS_KEY, 0, DECISIONS_KEPT - 1)
+            .query_async::<()>(&mut conn)
+            .await
+            .map_err(|e| format!("Failed to record placement: {}", e))?;
+
+        Ok(())
+    }
+
+    async fn recent_decisions(&self, limit: usize) -> Result<Vec<PlacementDecision>, String> {
+        let mut conn = self.client.get_multiplexed_async_connection()
+            .await
+            .map_err(|e| format!("Redis connection error: {}", e))?;
+
+        let stop = limit.min(DECISIONS_KEPT as usize) as isize - 1;
+        if stop < 0 {
+            return Ok(Vec::new());
+        }
+        let entries: Vec<String> = conn.lrange(DECISIONS_KEY, 0, stop)
+            .await
+            .map_err(|e| format!("Failed to read placements: {}", e))?;
+
+        Ok(entries.iter().filter_map(|json| serde_json::from_str(json).ok()).collect())
+    }
+}
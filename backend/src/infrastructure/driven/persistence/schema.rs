@@ -5,6 +5,8 @@ diesel::table! {
         display_name -> Text,
         roles -> Text,
         status -> Text,
+        locale -> Text,
+        tenant_id -> Text,
     }
 }
 
@@ -16,5 +18,11 @@ diesel::table! {
         public_key -> Text,
         sign_count -> BigInt,
         created_at -> Text,
+        aaguid -> Nullable<Text>,
+        transports -> Text,
+        backup_eligible -> Bool,
+        backup_state -> Bool,
+        last_used_at -> Nullable<Text>,
+        sign_count_anomalies -> BigInt,
     }
 }
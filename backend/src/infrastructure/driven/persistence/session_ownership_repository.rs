@@ -0,0 +1,103 @@
+use async_trait::async_trait;
+use redis::AsyncCommands;
+use crate::application::ports::{InstanceInfo, SessionOwnershipRepository};
+
+/// Set the claim unless another instance holds it, so two instances never both win a session
+const CLAIM_SCRIPT: &str = r#"
+local holder = redis.call('GET', KEYS[1])
+if holder and holder ~= ARGV[1] then
+    return 0
+end
+redis.call('SET', KEYS[1], ARGV[1], 'EX', ARGV[2])
+return 1
+"#;
+
+const RELEASE_SCRIPT: &str = r#"
+if redis.call('GET', KEYS[1]) == ARGV[1] then
+    return redis.call('DEL', KEYS[1])
+end
+return 0
+"#;
+
+pub struct RedisSessionOwnershipRepository {
+    client: redis::Client,
+}
+
+impl RedisSessionOwnershipRepository {
+    pub fn new(client: redis::Client) -> Self {
+        Self { client }
+    }
+}
+
+fn owner_key(session_id: &str) -> String {
+    format!("session:owner:{}", session_id)
+}
+
+fn instance_key(instance_id: &str) -> String {
+    format!("instance:{}", instance_id)
+}
+
+#[async_trait]
+impl SessionOwnershipRepository for RedisSessionOwnershipRepository {
+    async fn announce(&self, instance: &InstanceInfo, ttl_seconds: u64) -> Result<(), String> {
+        let mut conn = self.client.get_multiplexed_async_connection()
+            .await
+            .map_err(|e| format!("Redis connection error: {}", e))?;
+
+        conn.set_ex::<_, _, ()>(instance_key(&instance.id), &instance.websocket_base_url, ttl_seconds)
+            .await
+            .map_err(|e| format!("Failed to announce instance: {}", e))?;
+
+        Ok(())
+    }
+
+    async fn claim(&self, session_id: &str, instance_id: &str, ttl_seconds: u64) -> Result<bool, String> {
+        let mut conn = self.client.get_multiplexed_async_connection()
+            .await
+            .map_err(|e| format!("Redis connection error: {}", e))?;
+
+        let claimed: i32 = redis::Script::new(CLAIM_SCRIPT)
+            .key(owner_key(session_id))
+            .arg(instance_id)
+            .arg(ttl_seconds)
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| format!("Failed to claim session: {}", e))?;
+
+        Ok(claimed == 1)
+    }
+
+    async fn owner(&self, session_id: &str) -> Result<Option<InstanceInfo>, String> {
+        let mut conn = self.client.get_multiplexed_async_connection()
+            .await
+            .map_err(|e| format!("Redis connection error: {}", e))?;
+
+        let instance_id: Option<String> = conn.get(owner_key(session_id))
+            .await
+            .map_err(|e| format!("Failed to read session owner: {}", e))?;
+        let Some(id) = instance_id else {
+            return Ok(None);
+        };
+        // An instance that stopped announcing itself is gone, whatever its claims say
+        let websocket_base_url: Option<String> = conn.get(instance_key(&id))
+            .await
+            .map_err(|e| format!("Failed to read instance: {}", e))?;
+
+        Ok(websocket_base_url.map(|websocket_base_url| InstanceInfo { id, websocket_base_url }))
+    }
+
+    async fn release(&self, session_id: &str, instance_id: &str) -> Result<(), String> {
+        let mut conn = self.client.get_multiplexed_async_connection()
+            .await
+            .map_err(|e| format!("Redis connection error: {}", e))?;
+
+        redis::Script::new(RELEASE_SCRIPT)
+            .key(owner_key(session_id))
+            .arg(instance_id)
+            .invoke_async::<i32>(&mut conn)
+            .await
+            .map_err(|e| format!("Failed to release session: {}", e))?;
+
+        Ok(())
+    }
+}
@@ -3,10 +3,12 @@ use async_trait::async_trait;
 use diesel::prelude::*;
 use diesel::r2d2::{ConnectionManager, Pool};
 use diesel::SqliteConnection;
-use crate::application::ports::session_repository::SessionRepository;
+use crate::application::ports::pagination::{Cursor, Page, PageRequest};
+use crate::application::ports::session_repository::{SessionFilter, SessionRepository, SessionSort};
 use crate::domain::entities::session::Session;
-use crate::domain::value_objects::UserId;
+use crate::domain::value_objects::{Interaction, UserId};
 use crate::infrastructure::driven::persistence::db_types::DbSession;
+use crate::infrastructure::driven::persistence::paging::{self, Filters};
 
 pub struct SqliteSessionRepository {
     pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
@@ -37,6 +39,12 @@ fn db_to_session(row: DbSession) -> Result<Session, String> {
         .map(|s| s.parse::<chrono::DateTime<chrono::Utc>>())
         .transpose()
         .map_err(|e| format!("Invalid terminated_at: {e}"))?;
+    let video = row.video
+        .as_deref()
+        .map(serde_json::from_str)
+        .transpose()
+        .map_err(|e| format!("Invalid video: {e}"))?;
+    let tenant_id = uuid::Uuid::parse_str(&row.tenant_id).map_err(|e| format!("Invalid tenant_id: {e}"))?;
 
     Ok(Session {
         id,
@@ -49,6 +57,9 @@ fn db_to_session(row: DbSession) -> Result<Session, String> {
         created_at,
         expires_at,
         terminated_at,
+        video,
+        tenant_id,
+        interaction: Interaction::from_db_str(&row.interaction)?,
     })
 }
 
@@ -65,13 +76,18 @@ impl SessionRepository for SqliteSessionRepository {
         let created_at = session.created_at.to_rfc3339();
         let expires_at = session.expires_at.to_rfc3339();
         let terminated_at = session.terminated_at.map(|dt: chrono::DateTime<chrono::Utc>| dt.to_rfc3339());
+        let video = session.video.as_ref().map(serde_json::to_string).transpose().map_err(|e| e.to_string())?;
+        let tenant_id = session.tenant_id.to_string();
+        let interaction = session.interaction.as_db_str();
         let pool = self.pool.clone();
 
         tokio::task::spawn_blocking(move || -> Result<(), String> {
             let mut conn = pool.get().map_err(|e| e.to_string())?;
-            diesel::sql_query(
-                "INSERT INTO sessions (id, user_id, acting_as_owner_id, active_role, app_id, display_number, state, created_at, expires_at, terminated_at) \
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10) \
+            let saved = diesel::sql_query(
+                "INSERT INTO sessions (id, user_id, acting_as_owner_id, active_role, app_id, display_number, state, created_at, expires_at, terminated_at, video, tenant_id, interaction) \
+                 SELECT ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13 \
+                 WHERE ?12 = (SELECT tenant_id FROM users WHERE id = ?2) \
+                 AND (?3 IS NULL OR ?12 = (SELECT tenant_id FROM users WHERE id = ?3)) \
                  ON CONFLICT(id) DO UPDATE SET state=excluded.state, terminated_at=excluded.terminated_at"
             )
             .bind::<diesel::sql_types::Text, _>(&id)
@@ -84,8 +100,15 @@ impl SessionRepository for SqliteSessionRepository {
             .bind::<diesel::sql_types::Text, _>(&created_at)
             .bind::<diesel::sql_types::Text, _>(&expires_at)
             .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&terminated_at)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&video)
+            .bind::<diesel::sql_types::Text, _>(&tenant_id)
+            .bind::<diesel::sql_types::Text, _>(interaction)
             .execute(&mut conn)
             .map_err(|e| format!("Failed to save session: {e}"))?;
+            // Nothing is written for a user or vault owner outside the session's tenant
+            if saved == 0 {
+                return Err("Failed to save session: the vault belongs to another tenant".to_string());
+            }
             Ok(())
         })
         .await
@@ -99,7 +122,7 @@ impl SessionRepository for SqliteSessionRepository {
         tokio::task::spawn_blocking(move || -> Result<Option<Session>, String> {
             let mut conn = pool.get().map_err(|e| e.to_string())?;
             let rows: Vec<DbSession> = diesel::sql_query(
-                "SELECT id, user_id, acting_as_owner_id, active_role, app_id, display_number, state, created_at, expires_at, terminated_at \
+                "SELECT id, user_id, acting_as_owner_id, active_role, app_id, display_number, state, created_at, expires_at, terminated_at, video, tenant_id, interaction \
                  FROM sessions WHERE id = ?1"
             )
             .bind::<diesel::sql_types::Text, _>(&id_str)
@@ -119,7 +142,7 @@ impl SessionRepository for SqliteSessionRepository {
         tokio::task::spawn_blocking(move || -> Result<Vec<Session>, String> {
             let mut conn = pool.get().map_err(|e| e.to_string())?;
             let rows: Vec<DbSession> = diesel::sql_query(
-                "SELECT id, user_id, acting_as_owner_id, active_role, app_id, display_number, state, created_at, expires_at, terminated_at \
+                "SELECT id, user_id, acting_as_owner_id, active_role, app_id, display_number, state, created_at, expires_at, terminated_at, video, tenant_id, interaction \
                  FROM sessions WHERE user_id = ?1 AND state != 'terminated' AND terminated_at IS NULL \
                  AND expires_at > datetime('now')"
             )
@@ -171,16 +194,37 @@ impl SessionRepository for SqliteSessionRepository {
         .map_err(|e: tokio::task::JoinError| e.to_string())?
     }
 
+    async fn expire(&self, id: &uuid::Uuid, at: chrono::DateTime<chrono::Utc>) -> Result<(), String> {
+        let id_str = id.to_string();
+        let at = at.to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query("UPDATE sessions SET expires_at = ?1 WHERE id = ?2")
+                .bind::<diesel::sql_types::Text, _>(&at)
+                .bind::<diesel::sql_types::Text, _>(&id_str)
+                .execute(&mut conn)
+                .map_err(|e| format!("Failed to expire session: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
     async fn find_expired(&self) -> Result<Vec<Session>, String> {
+        // Compared as RFC 3339 text, the format expiries are stored in
+        let now = chrono::Utc::now().to_rfc3339();
         let pool = self.pool.clone();
 
         tokio::task::spawn_blocking(move || -> Result<Vec<Session>, String> {
             let mut conn = pool.get().map_err(|e| e.to_string())?;
             let rows: Vec<DbSession> = diesel::sql_query(
-                "SELECT id, user_id, acting_as_owner_id, active_role, app_id, display_number, state, created_at, expires_at, terminated_at \
-                 FROM sessions WHERE state != 'terminated' AND terminated_at IS NULL \
-                 AND expires_at <= datetime('now')"
+                "SELECT id, user_id, acting_as_owner_id, active_role, app_id, display_number, state, created_at, expires_at, terminated_at, video, tenant_id, interaction \
+                 FROM sessions WHERE state NOT IN ('terminated', 'suspended') AND terminated_at IS NULL \
+                 AND expires_at <= ?1"
             )
+            .bind::<diesel::sql_types::Text, _>(&now)
             .load(&mut conn)
             .map_err(|e| format!("Database error: {e}"))?;
 
@@ -189,4 +233,46 @@ impl SessionRepository for SqliteSessionRepository {
         .await
         .map_err(|e: tokio::task::JoinError| e.to_string())?
     }
+
+    async fn list(&self, filter: &SessionFilter, page: &PageRequest) -> Result<Page<Session>, String> {
+        let mut filters = Filters::new();
+        if let Some(user_id) = &filter.user_id {
+            filters.add("user_id = ?", [user_id.to_string()]);
+        }
+        if let Some(owner_id) = &filter.acting_as_owner_id {
+            filters.add("acting_as_owner_id = ?", [owner_id.to_string()]);
+        }
+        if let Some(state) = &filter.state {
+            filters.add("state = ?", [state.clone()]);
+        }
+        if let Some(app_id) = &filter.app_id {
+            filters.add("app_id = ?", [app_id.clone()]);
+        }
+        let sort = filter.sort;
+        let page = page.clone();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Page<Session>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let sort_column = match sort {
+                SessionSort::CreatedAt => "created_at",
+                SessionSort::ExpiresAt => "expires_at",
+            };
+            let (rows, total) = paging::load_page::<DbSession>(
+                &mut conn,
+                "sessions",
+                "id, user_id, acting_as_owner_id, active_role, app_id, display_number, state, created_at, expires_at, terminated_at, video, tenant_id, interaction",
+                &filters,
+                sort_column,
+                &page,
+            )?;
+            Page::from_rows(rows, page.limit, total, |row| match sort {
+                SessionSort::CreatedAt => Cursor::new(&row.created_at, &row.id),
+                SessionSort::ExpiresAt => Cursor::new(&row.expires_at, &row.id),
+            })
+            .try_map(db_to_session)
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
 }
@@ -0,0 +1,117 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::SqliteConnection;
+use crate::application::ports::session_snapshot_repository::SessionSnapshotRepository;
+use crate::domain::entities::session_snapshot::SessionSnapshot;
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::driven::persistence::db_types::DbSessionSnapshot;
+
+pub struct SqliteSessionSnapshotRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+}
+
+impl SqliteSessionSnapshotRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>) -> Self {
+        Self { pool }
+    }
+}
+
+fn db_to_snapshot(row: DbSessionSnapshot) -> Result<SessionSnapshot, String> {
+    Ok(SessionSnapshot {
+        session_id: uuid::Uuid::parse_str(&row.session_id).map_err(|e| format!("Invalid session id: {e}"))?,
+        user_id: uuid::Uuid::parse_str(&row.user_id)
+            .map(UserId::from_uuid)
+            .map_err(|e| format!("Invalid user_id: {e}"))?,
+        app_id: row.app_id,
+        state: row.state,
+        suspended_at: row.suspended_at.parse::<DateTime<Utc>>().unwrap_or_else(|_| Utc::now()),
+    })
+}
+
+#[async_trait]
+impl SessionSnapshotRepository for SqliteSessionSnapshotRepository {
+    async fn save(&self, snapshot: &SessionSnapshot) -> Result<(), String> {
+        let session_id = snapshot.session_id.to_string();
+        let user_id = snapshot.user_id.to_string();
+        let app_id = snapshot.app_id.clone();
+        let state = snapshot.state.clone();
+        let suspended_at = snapshot.suspended_at.to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query(
+                "INSERT INTO session_snapshots (session_id, user_id, app_id, state, suspended_at) VALUES (?1, ?2, ?3, ?4, ?5)"
+            )
+            .bind::<diesel::sql_types::Text, _>(&session_id)
+            .bind::<diesel::sql_types::Text, _>(&user_id)
+            .bind::<diesel::sql_types::Text, _>(&app_id)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Binary>, _>(&state)
+            .bind::<diesel::sql_types::Text, _>(&suspended_at)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to save session snapshot: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find(&self, session_id: &uuid::Uuid) -> Result<Option<SessionSnapshot>, String> {
+        let id_str = session_id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Option<SessionSnapshot>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbSessionSnapshot> = diesel::sql_query(
+                "SELECT session_id, user_id, app_id, state, suspended_at FROM session_snapshots WHERE session_id = ?1"
+            )
+            .bind::<diesel::sql_types::Text, _>(&id_str)
+            .load(&mut conn)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+            rows.into_iter().next().map(db_to_snapshot).transpose()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn delete(&self, session_id: &uuid::Uuid) -> Result<(), String> {
+        let id_str = session_id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query("DELETE FROM session_snapshots WHERE session_id = ?1")
+                .bind::<diesel::sql_types::Text, _>(&id_str)
+                .execute(&mut conn)
+                .map_err(|e| format!("Failed to delete session snapshot: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find_suspended_before(&self, cutoff: DateTime<Utc>) -> Result<Vec<uuid::Uuid>, String> {
+        let cutoff = cutoff.to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<uuid::Uuid>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbSessionSnapshot> = diesel::sql_query(
+                "SELECT session_id, user_id, app_id, NULL AS state, suspended_at FROM session_snapshots WHERE suspended_at < ?1"
+            )
+            .bind::<diesel::sql_types::Text, _>(&cutoff)
+            .load(&mut conn)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+            rows.into_iter()
+                .map(|row| uuid::Uuid::parse_str(&row.session_id).map_err(|e| format!("Invalid session id: {e}")))
+                .collect()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+}
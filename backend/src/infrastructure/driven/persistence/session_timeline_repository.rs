@@ -0,0 +1,70 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::SqliteConnection;
+use crate::application::ports::session_timeline_repository::SessionTimelineRepository;
+use crate::domain::entities::session_timeline::SessionTimeline;
+use crate::infrastructure::driven::persistence::db_types::DbSessionTimeline;
+
+pub struct SqliteSessionTimelineRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+}
+
+impl SqliteSessionTimelineRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>) -> Self {
+        Self { pool }
+    }
+}
+
+fn db_to_timeline(row: DbSessionTimeline) -> Result<SessionTimeline, String> {
+    Ok(SessionTimeline {
+        session_id: uuid::Uuid::parse_str(&row.session_id).map_err(|e| format!("Invalid session id: {e}"))?,
+        events: serde_json::from_str(&row.events).map_err(|e| format!("Invalid timeline events: {e}"))?,
+    })
+}
+
+#[async_trait]
+impl SessionTimelineRepository for SqliteSessionTimelineRepository {
+    async fn save(&self, timeline: &SessionTimeline) -> Result<(), String> {
+        let session_id = timeline.session_id.to_string();
+        let events = serde_json::to_string(&timeline.events).map_err(|e| e.to_string())?;
+        let recorded_at = Utc::now().to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query(
+                "INSERT INTO session_timelines (session_id, events, recorded_at) VALUES (?1, ?2, ?3) \
+                 ON CONFLICT(session_id) DO UPDATE SET events = excluded.events, recorded_at = excluded.recorded_at"
+            )
+            .bind::<diesel::sql_types::Text, _>(&session_id)
+            .bind::<diesel::sql_types::Text, _>(&events)
+            .bind::<diesel::sql_types::Text, _>(&recorded_at)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to save session timeline: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find(&self, session_id: &uuid::Uuid) -> Result<Option<SessionTimeline>, String> {
+        let id_str = session_id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Option<SessionTimeline>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbSessionTimeline> =
+                diesel::sql_query("SELECT session_id, events FROM session_timelines WHERE session_id = ?1")
+                    .bind::<diesel::sql_types::Text, _>(&id_str)
+                    .load(&mut conn)
+                    .map_err(|e| format!("Database error: {e}"))?;
+
+            rows.into_iter().next().map(db_to_timeline).transpose()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+}
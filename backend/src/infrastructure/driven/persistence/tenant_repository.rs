@@ -0,0 +1,108 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::SqliteConnection;
+use crate::application::ports::tenant_repository::TenantRepository;
+use crate::domain::entities::tenant::Tenant;
+use crate::infrastructure::driven::persistence::db_types::DbTenant;
+
+pub struct SqliteTenantRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+}
+
+impl SqliteTenantRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>) -> Self {
+        Self { pool }
+    }
+
+    async fn load(&self, condition: &'static str, value: Option<String>) -> Result<Vec<Tenant>, String> {
+        let pool = self.pool.clone();
+        tokio::task::spawn_blocking(move || -> Result<Vec<Tenant>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let query = diesel::sql_query(format!("{SELECT_TENANT} {condition} ORDER BY created_at, id"));
+            let rows: Vec<DbTenant> = match value {
+                Some(value) => query.bind::<diesel::sql_types::Text, _>(value).load(&mut conn),
+                None => query.load(&mut conn),
+            }
+            .map_err(|e| format!("Database error: {e}"))?;
+            rows.into_iter().map(db_to_tenant).collect()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+}
+
+const SELECT_TENANT: &str = "SELECT id, name, setup_token_hash, created_at FROM tenants";
+
+fn db_to_tenant(row: DbTenant) -> Result<Tenant, String> {
+    Ok(Tenant {
+        id: uuid::Uuid::parse_str(&row.id).map_err(|e| format!("Invalid tenant id: {e}"))?,
+        name: row.name,
+        setup_token_hash: row.setup_token_hash,
+        created_at: row
+            .created_at
+            .parse::<chrono::DateTime<chrono::Utc>>()
+            .unwrap_or_else(|_| chrono::Utc::now()),
+    })
+}
+
+#[async_trait]
+impl TenantRepository for SqliteTenantRepository {
+    async fn save(&self, tenant: &Tenant) -> Result<(), String> {
+        let id = tenant.id.to_string();
+        let name = tenant.name.clone();
+        let setup_token_hash = tenant.setup_token_hash.clone();
+        let created_at = tenant.created_at.to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query(
+                "INSERT INTO tenants (id, name, setup_token_hash, created_at) VALUES (?1, ?2, ?3, ?4)"
+            )
+            .bind::<diesel::sql_types::Text, _>(&id)
+            .bind::<diesel::sql_types::Text, _>(&name)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&setup_token_hash)
+            .bind::<diesel::sql_types::Text, _>(&created_at)
+            .execute(&mut conn)
+            .map_err(|e| match e {
+                diesel::result::Error::DatabaseError(diesel::result::DatabaseErrorKind::UniqueViolation, _) => {
+                    format!("A tenant named {name} already exists")
+                }
+                e => format!("Failed to save tenant: {e}"),
+            })?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find(&self, id: &uuid::Uuid) -> Result<Option<Tenant>, String> {
+        Ok(self.load("WHERE id = ?1", Some(id.to_string())).await?.into_iter().next())
+    }
+
+    async fn find_by_setup_token_hash(&self, hash: &str) -> Result<Option<Tenant>, String> {
+        Ok(self.load("WHERE setup_token_hash = ?1", Some(hash.to_string())).await?.into_iter().next())
+    }
+
+    async fn list(&self) -> Result<Vec<Tenant>, String> {
+        self.load("", None).await
+    }
+
+    async fn complete_setup(&self, id: &uuid::Uuid) -> Result<(), String> {
+        let id = id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query("UPDATE tenants SET setup_token_hash = NULL WHERE id = ?1")
+                .bind::<diesel::sql_types::Text, _>(&id)
+                .execute(&mut conn)
+                .map_err(|e| format!("Failed to complete tenant setup: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+}
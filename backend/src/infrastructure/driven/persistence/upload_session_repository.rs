@@ -0,0 +1,169 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::SqliteConnection;
+use crate::application::ports::upload_session_repository::UploadSessionRepository;
+use crate::domain::entities::upload_session::{UploadSession, UploadStatus};
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::driven::persistence::db_types::DbUploadSession;
+
+pub struct SqliteUploadSessionRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+}
+
+impl SqliteUploadSessionRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>) -> Self {
+        Self { pool }
+    }
+}
+
+const SELECT_UPLOAD: &str =
+    "SELECT id, owner_id, created_by, path, size, received, status, error, created_at, updated_at, expires_at FROM upload_sessions";
+
+fn parse_user_id(s: &str, field: &str) -> Result<UserId, String> {
+    uuid::Uuid::parse_str(s)
+        .map(UserId::from_uuid)
+        .map_err(|e| format!("Invalid {field}: {e}"))
+}
+
+fn db_to_upload(row: DbUploadSession) -> Result<UploadSession, String> {
+    let id = uuid::Uuid::parse_str(&row.id).map_err(|e| format!("Invalid upload id: {e}"))?;
+    let parse_time = |s: &str| s.parse::<DateTime<Utc>>().unwrap_or_else(|_| Utc::now());
+
+    Ok(UploadSession {
+        id,
+        owner_id: parse_user_id(&row.owner_id, "owner_id")?,
+        created_by: parse_user_id(&row.created_by, "created_by")?,
+        path: row.path,
+        size: row.size.max(0) as u64,
+        offset: row.received.max(0) as u64,
+        status: UploadStatus::from_db_str(&row.status)?,
+        error: row.error,
+        created_at: parse_time(&row.created_at),
+        updated_at: parse_time(&row.updated_at),
+        expires_at: parse_time(&row.expires_at),
+    })
+}
+
+#[async_trait]
+impl UploadSessionRepository for SqliteUploadSessionRepository {
+    async fn save(&self, upload: &UploadSession) -> Result<(), String> {
+        let id = upload.id.to_string();
+        let owner_id = upload.owner_id.to_string();
+        let created_by = upload.created_by.to_string();
+        let path = upload.path.clone();
+        let size = upload.size as i64;
+        let received = upload.offset as i64;
+        let status = upload.status.as_db_str();
+        let error = upload.error.clone();
+        let created_at = upload.created_at.to_rfc3339();
+        let updated_at = upload.updated_at.to_rfc3339();
+        let expires_at = upload.expires_at.to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query(
+                "INSERT INTO upload_sessions (id, owner_id, created_by, path, size, received, status, error, created_at, updated_at, expires_at) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)"
+            )
+            .bind::<diesel::sql_types::Text, _>(&id)
+            .bind::<diesel::sql_types::Text, _>(&owner_id)
+            .bind::<diesel::sql_types::Text, _>(&created_by)
+            .bind::<diesel::sql_types::Text, _>(&path)
+            .bind::<diesel::sql_types::BigInt, _>(size)
+            .bind::<diesel::sql_types::BigInt, _>(received)
+            .bind::<diesel::sql_types::Text, _>(status)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&error)
+            .bind::<diesel::sql_types::Text, _>(&created_at)
+            .bind::<diesel::sql_types::Text, _>(&updated_at)
+            .bind::<diesel::sql_types::Text, _>(&expires_at)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to save upload: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find_by_id(&self, id: &uuid::Uuid) -> Result<Option<UploadSession>, String> {
+        let id_str = id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Option<UploadSession>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbUploadSession> = diesel::sql_query(format!("{SELECT_UPLOAD} WHERE id = ?1"))
+                .bind::<diesel::sql_types::Text, _>(&id_str)
+                .load(&mut conn)
+                .map_err(|e| format!("Database error: {e}"))?;
+
+            rows.into_iter().next().map(db_to_upload).transpose()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn update_offset(&self, id: &uuid::Uuid, offset: u64, expires_at: DateTime<Utc>) -> Result<(), String> {
+        let id_str = id.to_string();
+        let received = offset as i64;
+        let updated_at = Utc::now().to_rfc3339();
+        let expires_at = expires_at.to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query("UPDATE upload_sessions SET received = ?1, updated_at = ?2, expires_at = ?3 WHERE id = ?4")
+                .bind::<diesel::sql_types::BigInt, _>(received)
+                .bind::<diesel::sql_types::Text, _>(&updated_at)
+                .bind::<diesel::sql_types::Text, _>(&expires_at)
+                .bind::<diesel::sql_types::Text, _>(&id_str)
+                .execute(&mut conn)
+                .map_err(|e| format!("Failed to update upload: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn finish(&self, id: &uuid::Uuid, status: UploadStatus, error: Option<&str>) -> Result<(), String> {
+        let id_str = id.to_string();
+        let status = status.as_db_str();
+        let error = error.map(str::to_string);
+        let updated_at = Utc::now().to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query("UPDATE upload_sessions SET status = ?1, error = ?2, updated_at = ?3 WHERE id = ?4")
+                .bind::<diesel::sql_types::Text, _>(status)
+                .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&error)
+                .bind::<diesel::sql_types::Text, _>(&updated_at)
+                .bind::<diesel::sql_types::Text, _>(&id_str)
+                .execute(&mut conn)
+                .map_err(|e| format!("Failed to finish upload: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find_expired(&self, now: DateTime<Utc>) -> Result<Vec<UploadSession>, String> {
+        let now = now.to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<UploadSession>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbUploadSession> =
+                diesel::sql_query(format!("{SELECT_UPLOAD} WHERE status = 'active' AND expires_at < ?1"))
+                    .bind::<diesel::sql_types::Text, _>(&now)
+                    .load(&mut conn)
+                    .map_err(|e| format!("Database error: {e}"))?;
+
+            rows.into_iter().map(db_to_upload).collect()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+}
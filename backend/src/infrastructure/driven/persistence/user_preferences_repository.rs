@@ -0,0 +1,113 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::SqliteConnection;
+use shared::{Locale, Theme};
+use crate::application::ports::user_preferences_repository::UserPreferencesRepository;
+use crate::domain::entities::user_preferences::UserPreferences;
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::driven::persistence::db_types::DbUserPreferences;
+
+pub struct SqliteUserPreferencesRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+}
+
+impl SqliteUserPreferencesRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>) -> Self {
+        Self { pool }
+    }
+}
+
+fn db_to_user_preferences(row: DbUserPreferences) -> Result<UserPreferences, String> {
+    let user_uuid = uuid::Uuid::parse_str(&row.user_id).map_err(|e| format!("Invalid user_id: {e}"))?;
+    let updated_at = row
+        .updated_at
+        .parse::<chrono::DateTime<chrono::Utc>>()
+        .unwrap_or_else(|_| chrono::Utc::now());
+
+    Ok(UserPreferences {
+        user_id: UserId::from_uuid(user_uuid),
+        theme: Theme::parse(&row.theme).unwrap_or_default(),
+        default_width: u16::try_from(row.default_width).map_err(|e| format!("Invalid default_width: {e}"))?,
+        default_height: u16::try_from(row.default_height).map_err(|e| format!("Invalid default_height: {e}"))?,
+        default_framerate: u8::try_from(row.default_framerate).map_err(|e| format!("Invalid default_framerate: {e}"))?,
+        keyboard_layout: row.keyboard_layout,
+        timezone: row.timezone,
+        notify_email: row.notify_email,
+        notify_in_app: row.notify_in_app,
+        updated_at,
+    })
+}
+
+#[async_trait]
+impl UserPreferencesRepository for SqliteUserPreferencesRepository {
+    async fn save(&self, preferences: &UserPreferences, locale: Locale) -> Result<(), String> {
+        let user_id = preferences.user_id.to_string();
+        let theme = preferences.theme.as_str().to_string();
+        let default_width = preferences.default_width as i32;
+        let default_height = preferences.default_height as i32;
+        let default_framerate = preferences.default_framerate as i32;
+        let keyboard_layout = preferences.keyboard_layout.clone();
+        let timezone = preferences.timezone.clone();
+        let notify_email = preferences.notify_email;
+        let notify_in_app = preferences.notify_in_app;
+        let updated_at = preferences.updated_at.to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                diesel::sql_query("UPDATE users SET locale = ?1 WHERE id = ?2")
+                    .bind::<diesel::sql_types::Text, _>(locale.as_str())
+                    .bind::<diesel::sql_types::Text, _>(&user_id)
+                    .execute(conn)?;
+                diesel::sql_query(
+                    "INSERT INTO user_preferences (user_id, theme, default_width, default_height, default_framerate, \
+                     keyboard_layout, notify_email, notify_in_app, updated_at, timezone) \
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10) \
+                     ON CONFLICT(user_id) DO UPDATE SET theme=excluded.theme, default_width=excluded.default_width, \
+                     default_height=excluded.default_height, default_framerate=excluded.default_framerate, \
+                     keyboard_layout=excluded.keyboard_layout, notify_email=excluded.notify_email, \
+                     notify_in_app=excluded.notify_in_app, updated_at=excluded.updated_at, timezone=excluded.timezone"
+                )
+                .bind::<diesel::sql_types::Text, _>(&user_id)
+                .bind::<diesel::sql_types::Text, _>(&theme)
+                .bind::<diesel::sql_types::Integer, _>(default_width)
+                .bind::<diesel::sql_types::Integer, _>(default_height)
+                .bind::<diesel::sql_types::Integer, _>(default_framerate)
+                .bind::<diesel::sql_types::Text, _>(&keyboard_layout)
+                .bind::<diesel::sql_types::Bool, _>(notify_email)
+                .bind::<diesel::sql_types::Bool, _>(notify_in_app)
+                .bind::<diesel::sql_types::Text, _>(&updated_at)
+                .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&timezone)
+                .execute(conn)?;
+                Ok(())
+            })
+            .map_err(|e| format!("Failed to save user preferences: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find(&self, user_id: &UserId) -> Result<Option<UserPreferences>, String> {
+        let user_id_str = user_id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Option<UserPreferences>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbUserPreferences> = diesel::sql_query(
+                "SELECT user_id, theme, default_width, default_height, default_framerate, keyboard_layout, \
+                 notify_email, notify_in_app, updated_at, timezone FROM user_preferences WHERE user_id = ?1"
+            )
+            .bind::<diesel::sql_types::Text, _>(&user_id_str)
+            .load(&mut conn)
+            .map_err(|e| format!("Database error: {e}"))?;
+
+            rows.into_iter().next().map(db_to_user_preferences).transpose()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+}
@@ -5,6 +5,7 @@ use diesel::SqliteConnection;
 use async_trait::async_trait;
 use crate::application::ports::user_repository::UserRepository;
 use crate::domain::User;
+use shared::Locale;
 use crate::infrastructure::driven::persistence::schema::users;
 use crate::infrastructure::driven::persistence::db_types::{DbUser, NewDbUser};
 
@@ -18,6 +19,37 @@ impl SqliteUserRepository {
     }
 }
 
+fn db_to_user(db_user: DbUser) -> Result<User, String> {
+    let id = uuid::Uuid::parse_str(&db_user.id)
+        .map_err(|e| format!("Invalid UUID in DB: {}", e))?;
+    let roles_strs: Vec<String> = serde_json::from_str(&db_user.roles)
+        .unwrap_or_default();
+    let roles = roles_strs
+        .iter()
+        .filter_map(|r| match r.as_str() {
+            "super_admin" => Some(crate::domain::UserRole::SuperAdmin),
+            "owner" => Some(crate::domain::UserRole::Owner),
+            "client" => Some(crate::domain::UserRole::Client),
+            _ => None,
+        })
+        .collect();
+    let status = match db_user.status.as_str() {
+        "suspended" => crate::domain::UserStatus::Suspended,
+        "pending_deletion" => crate::domain::UserStatus::PendingDeletion,
+        "deleted" => crate::domain::UserStatus::Deleted,
+        _ => crate::domain::UserStatus::Active,
+    };
+    Ok(User::from_persistence(
+        crate::domain::UserId::from_uuid(id),
+        crate::domain::Email::new(db_user.email).map_err(|e| e.to_string())?,
+        crate::domain::DisplayName::new(db_user.display_name).map_err(|e| e.to_string())?,
+        roles,
+        status,
+        Locale::from_tag(&db_user.locale).unwrap_or_default(),
+        uuid::Uuid::parse_str(&db_user.tenant_id).map_err(|e| format!("Invalid tenant id in DB: {}", e))?,
+    ))
+}
+
 #[async_trait]
 impl UserRepository for SqliteUserRepository {
     async fn count_super_admins(&self) -> Result<u64, String> {
@@ -46,36 +78,24 @@ impl UserRepository for SqliteUserRepository {
                 .optional()
                 .map_err(|e| e.to_string())?;
 
-            if let Some(db_user) = result {
-                let id = uuid::Uuid::parse_str(&db_user.id)
-                    .map_err(|e| format!("Invalid UUID in DB: {}", e))?;
-                let roles_strs: Vec<String> = serde_json::from_str(&db_user.roles)
-                    .unwrap_or_default();
-                let roles = roles_strs
-                    .iter()
-                    .filter_map(|r| match r.as_str() {
-                        "super_admin" => Some(crate::domain::UserRole::SuperAdmin),
-                        "owner" => Some(crate::domain::UserRole::Owner),
-                        "client" => Some(crate::domain::UserRole::Client),
-                        _ => None,
-                    })
-                    .collect();
-                let status = match db_user.status.as_str() {
-                    "suspended" => crate::domain::UserStatus::Suspended,
-                    "deleted" => crate::domain::UserStatus::Deleted,
-                    _ => crate::domain::UserStatus::Active,
-                };
-                let user = crate::domain::User::from_persistence(
-                    crate::domain::UserId::from_uuid(id),
-                    crate::domain::Email::new(db_user.email).map_err(|e| e.to_string())?,
-                    crate::domain::DisplayName::new(db_user.display_name).map_err(|e| e.to_string())?,
-                    roles,
-                    status,
-                );
-                Ok(Some(user))
-            } else {
-                Ok(None)
-            }
+            result.map(db_to_user).transpose()
+        })
+        .await
+        .map_err(|e| e.to_string())?
+    }
+
+    async fn find_by_id(&self, id: &crate::domain::UserId) -> Result<Option<User>, String> {
+        let id_str = id.to_string();
+        let pool = self.pool.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let result = users::table
+                .filter(users::id.eq(&id_str))
+                .first::<DbUser>(&mut conn)
+                .optional()
+                .map_err(|e| e.to_string())?;
+
+            result.map(db_to_user).transpose()
         })
         .await
         .map_err(|e| e.to_string())?
@@ -89,11 +109,13 @@ impl UserRepository for SqliteUserRepository {
             &user.roles().iter().map(|r| r.as_db_str()).collect::<Vec<_>>()
         ).map_err(|e| e.to_string())?;
         let status = user.status().as_db_str().to_string();
+        let locale = user.locale().as_str().to_string();
+        let tenant_id = user.tenant_id().to_string();
         let pool = self.pool.clone();
 
         tokio::task::spawn_blocking(move || {
             let mut conn = pool.get().map_err(|e| e.to_string())?;
-            let new_user = NewDbUser { id, email, display_name, roles, status };
+            let new_user = NewDbUser { id, email, display_name, roles, status, locale, tenant_id };
             diesel::insert_into(users::table)
                 .values(&new_user)
                 .execute(&mut conn)
@@ -103,4 +125,52 @@ impl UserRepository for SqliteUserRepository {
         .await
         .map_err(|e| e.to_string())?
     }
+
+    async fn list_by_tenant(&self, tenant_id: &uuid::Uuid) -> Result<Vec<User>, String> {
+        let tenant_id = tenant_id.to_string();
+        let pool = self.pool.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows = users::table
+                .filter(users::tenant_id.eq(&tenant_id))
+                .order(users::email.asc())
+                .load::<DbUser>(&mut conn)
+                .map_err(|e| e.to_string())?;
+            rows.into_iter().map(db_to_user).collect()
+        })
+        .await
+        .map_err(|e| e.to_string())?
+    }
+
+    async fn update_status(&self, id: &crate::domain::UserId, status: crate::domain::UserStatus) -> Result<(), String> {
+        let id_str = id.to_string();
+        let pool = self.pool.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::update(users::table.filter(users::id.eq(&id_str)))
+                .set(users::status.eq(status.as_db_str()))
+                .execute(&mut conn)
+                .map_err(|e| e.to_string())?;
+            Ok(())
+        })
+        .await
+        .map_err(|e| e.to_string())?
+    }
+
+    async fn update_roles(&self, id: &crate::domain::UserId, roles: &[crate::domain::UserRole]) -> Result<(), String> {
+        let id_str = id.to_string();
+        let roles = serde_json::to_string(&roles.iter().map(|r| r.as_db_str()).collect::<Vec<_>>())
+            .map_err(|e| e.to_string())?;
+        let pool = self.pool.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::update(users::table.filter(users::id.eq(&id_str)))
+                .set(users::roles.eq(roles))
+                .execute(&mut conn)
+                .map_err(|e| e.to_string())?;
+            Ok(())
+        })
+        .await
+        .map_err(|e| e.to_string())?
+    }
 }
@@ -0,0 +1,199 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::SqliteConnection;
+use crate::application::ports::vault_import_repository::VaultImportRepository;
+use crate::domain::entities::file_job::JobStatus;
+use crate::domain::entities::vault_import::{ConflictPolicy, ImportMode, ImportProgress, VaultImport};
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::driven::persistence::db_types::DbVaultImport;
+
+pub struct SqliteVaultImportRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+}
+
+impl SqliteVaultImportRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>) -> Self {
+        Self { pool }
+    }
+}
+
+const SELECT_IMPORT: &str = "SELECT id, owner_id, requested_by, source, destination, mode, on_conflict, status, \
+     files_total, files_done, bytes_done, skipped, error, cancel_requested, created_at, updated_at FROM vault_imports";
+
+fn parse_user_id(s: &str, field: &str) -> Result<UserId, String> {
+    uuid::Uuid::parse_str(s)
+        .map(UserId::from_uuid)
+        .map_err(|e| format!("Invalid {field}: {e}"))
+}
+
+fn to_db_count(n: u64) -> i64 {
+    n.min(i64::MAX as u64) as i64
+}
+
+fn db_to_vault_import(row: DbVaultImport) -> Result<VaultImport, String> {
+    let parse_time = |s: &str| {
+        s.parse::<chrono::DateTime<chrono::Utc>>()
+            .unwrap_or_else(|_| chrono::Utc::now())
+    };
+
+    Ok(VaultImport {
+        id: uuid::Uuid::parse_str(&row.id).map_err(|e| format!("Invalid import id: {e}"))?,
+        owner_id: parse_user_id(&row.owner_id, "owner_id")?,
+        requested_by: parse_user_id(&row.requested_by, "requested_by")?,
+        source: row.source,
+        destination: row.destination,
+        mode: ImportMode::from_db_str(&row.mode)?,
+        on_conflict: ConflictPolicy::from_db_str(&row.on_conflict)?,
+        status: JobStatus::from_db_str(&row.status)?,
+        progress: ImportProgress {
+            files_total: row.files_total.map(|n| n.max(0) as u64),
+            files_done: row.files_done.max(0) as u64,
+            bytes_done: row.bytes_done.max(0) as u64,
+            skipped: row.skipped.max(0) as u64,
+        },
+        error: row.error,
+        cancel_requested: row.cancel_requested,
+        created_at: parse_time(&row.created_at),
+        updated_at: parse_time(&row.updated_at),
+    })
+}
+
+#[async_trait]
+impl VaultImportRepository for SqliteVaultImportRepository {
+    async fn save(&self, import: &VaultImport) -> Result<(), String> {
+        let id = import.id.to_string();
+        let owner_id = import.owner_id.to_string();
+        let requested_by = import.requested_by.to_string();
+        let source = import.source.clone();
+        let destination = import.destination.clone();
+        let mode = import.mode.as_db_str();
+        let on_conflict = import.on_conflict.as_db_str();
+        let status = import.status.as_db_str();
+        let progress = import.progress;
+        let error = import.error.clone();
+        let cancel_requested = import.cancel_requested;
+        let created_at = import.created_at.to_rfc3339();
+        let updated_at = import.updated_at.to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query(
+                "INSERT INTO vault_imports (id, owner_id, requested_by, source, destination, mode, on_conflict, status, \
+                 files_total, files_done, bytes_done, skipped, error, cancel_requested, created_at, updated_at) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)"
+            )
+            .bind::<diesel::sql_types::Text, _>(&id)
+            .bind::<diesel::sql_types::Text, _>(&owner_id)
+            .bind::<diesel::sql_types::Text, _>(&requested_by)
+            .bind::<diesel::sql_types::Text, _>(&source)
+            .bind::<diesel::sql_types::Text, _>(&destination)
+            .bind::<diesel::sql_types::Text, _>(mode)
+            .bind::<diesel::sql_types::Text, _>(on_conflict)
+            .bind::<diesel::sql_types::Text, _>(status)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::BigInt>, _>(progress.files_total.map(to_db_count))
+            .bind::<diesel::sql_types::BigInt, _>(to_db_count(progress.files_done))
+            .bind::<diesel::sql_types::BigInt, _>(to_db_count(progress.bytes_done))
+            .bind::<diesel::sql_types::BigInt, _>(to_db_count(progress.skipped))
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&error)
+            .bind::<diesel::sql_types::Bool, _>(cancel_requested)
+            .bind::<diesel::sql_types::Text, _>(&created_at)
+            .bind::<diesel::sql_types::Text, _>(&updated_at)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to save vault import: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find_by_id(&self, id: &uuid::Uuid) -> Result<Option<VaultImport>, String> {
+        let id_str = id.to_string();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Option<VaultImport>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbVaultImport> = diesel::sql_query(format!("{SELECT_IMPORT} WHERE id = ?1"))
+                .bind::<diesel::sql_types::Text, _>(&id_str)
+                .load(&mut conn)
+                .map_err(|e| format!("Database error: {e}"))?;
+
+            rows.into_iter().next().map(db_to_vault_import).transpose()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn find_by_status(&self, status: JobStatus) -> Result<Vec<VaultImport>, String> {
+        let status = status.as_db_str();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<VaultImport>, String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            let rows: Vec<DbVaultImport> = diesel::sql_query(format!("{SELECT_IMPORT} WHERE status = ?1 ORDER BY created_at"))
+                .bind::<diesel::sql_types::Text, _>(status)
+                .load(&mut conn)
+                .map_err(|e| format!("Database error: {e}"))?;
+
+            rows.into_iter().map(db_to_vault_import).collect()
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn update_progress(
+        &self,
+        id: &uuid::Uuid,
+        status: JobStatus,
+        progress: &ImportProgress,
+        error: Option<&str>,
+    ) -> Result<(), String> {
+        let id_str = id.to_string();
+        let status = status.as_db_str();
+        let progress = *progress;
+        let error = error.map(str::to_string);
+        let updated_at = chrono::Utc::now().to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query(
+                "UPDATE vault_imports SET status = ?1, files_total = ?2, files_done = ?3, bytes_done = ?4, skipped = ?5, \
+                 error = ?6, updated_at = ?7 WHERE id = ?8"
+            )
+            .bind::<diesel::sql_types::Text, _>(status)
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::BigInt>, _>(progress.files_total.map(to_db_count))
+            .bind::<diesel::sql_types::BigInt, _>(to_db_count(progress.files_done))
+            .bind::<diesel::sql_types::BigInt, _>(to_db_count(progress.bytes_done))
+            .bind::<diesel::sql_types::BigInt, _>(to_db_count(progress.skipped))
+            .bind::<diesel::sql_types::Nullable<diesel::sql_types::Text>, _>(&error)
+            .bind::<diesel::sql_types::Text, _>(&updated_at)
+            .bind::<diesel::sql_types::Text, _>(&id_str)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to update vault import: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+
+    async fn request_cancel(&self, id: &uuid::Uuid) -> Result<(), String> {
+        let id_str = id.to_string();
+        let updated_at = chrono::Utc::now().to_rfc3339();
+        let pool = self.pool.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let mut conn = pool.get().map_err(|e| e.to_string())?;
+            diesel::sql_query("UPDATE vault_imports SET cancel_requested = 1, updated_at = ?1 WHERE id = ?2")
+                .bind::<diesel::sql_types::Text, _>(&updated_at)
+                .bind::<diesel::sql_types::Text, _>(&id_str)
+                .execute(&mut conn)
+                .map_err(|e| format!("Failed to cancel vault import: {e}"))?;
+            Ok(())
+        })
+        .await
+        .map_err(|e: tokio::task::JoinError| e.to_string())?
+    }
+}
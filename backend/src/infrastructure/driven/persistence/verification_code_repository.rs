@@ -0,0 +1,54 @@
+use async_trait::async_trait;
+use redis::AsyncCommands;
+use crate::application::ports::VerificationCodeRepository;
+
+pub struct RedisVerificationCodeRepository {
+    client: redis::Client,
+}
+
+impl RedisVerificationCodeRepository {
+    pub fn new(client: redis::Client) -> Self {
+        Self { client }
+    }
+}
+
+fn code_key(key: &str) -> String {
+    format!("verify:code:{}", key)
+}
+
+#[async_trait]
+impl VerificationCodeRepository for RedisVerificationCodeRepository {
+    async fn save(&self, key: &str, code: &str, ttl_seconds: u64) -> Result<(), String> {
+        let mut conn = self.client.get_multiplexed_async_connection()
+            .await
+            .map_err(|e| format!("Redis connection error: {}", e))?;
+
+        conn.set_ex::<_, _, ()>(code_key(key), code, ttl_seconds)
+            .await
+            .map_err(|e| format!("Failed to store verification code: {}", e))?;
+
+        Ok(())
+    }
+
+    async fn find(&self, key: &str) -> Result<Option<String>, String> {
+        let mut conn = self.client.get_multiplexed_async_connection()
+            .await
+            .map_err(|e| format!("Redis connection error: {}", e))?;
+
+        conn.get(code_key(key))
+            .await
+            .map_err(|e| format!("Failed to read verification code: {}", e))
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), String> {
+        let mut conn = self.client.get_multiplexed_async_connection()
+            .await
+            .map_err(|e| format!("Redis connection error: {}", e))?;
+
+        conn.del::<_, ()>(code_key(key))
+            .await
+            .map_err(|e| format!("Failed to delete verification code: {}", e))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,240 @@
+//! Environment variables and arguments an app declares in its manifest, on top of what the
+//! sandbox sets. Values may use placeholders for the session, such as `{session_id}`, and
+//! variables may take a named secret, read at launch from `APP_SECRET_<APP>__<NAME>` through
+//! the backend's secrets providers, so apps can only reach the secrets meant for them.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use crate::infrastructure::driven::secrets::SecretsProvider;
+use shared::Locale;
+
+/// Variables the sandbox sets itself, which an app's environment cannot replace
+pub const RESERVED_ENV: [&str; 4] = ["DISPLAY", "IPC_SOCKET_PATH", "ROOT_PATH", "ALLOWED_PATHS"];
+
+/// Placeholders a value or argument may use, filled from [`TemplateVars`]
+const PLACEHOLDERS: [&str; 5] = ["session_id", "user_id", "app_dir", "root_path", "allowed_paths"];
+
+/// Whether `name` is set by the sandbox, and so off limits to manifests
+pub fn is_reserved(name: &str) -> bool {
+    RESERVED_ENV.contains(&name) || name.starts_with("SANDBOX_")
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum EnvValue {
+    /// Text with placeholders
+    Text(String),
+    /// A secret of the app, by name
+    Secret { secret: String },
+}
+
+/// The top-level `env` and `args` of an app's manifest
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct AppEnv {
+    #[serde(default)]
+    pub env: BTreeMap<String, EnvValue>,
+    /// Appended to the app's command line
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+/// What placeholders stand for in one session
+pub struct TemplateVars<'a> {
+    pub session_id: &'a str,
+    pub user_id: &'a str,
+    pub app_dir: &'a str,
+    pub root_path: &'a str,
+    pub allowed_paths: &'a [String],
+}
+
+impl TemplateVars<'_> {
+    fn get(&self, name: &str) -> Option<String> {
+        match name {
+            "session_id" => Some(self.session_id.to_string()),
+            "user_id" => Some(self.user_id.to_string()),
+            "app_dir" => Some(self.app_dir.to_string()),
+            "root_path" => Some(self.root_path.to_string()),
+            "allowed_paths" => Some(self.allowed_paths.join(":")),
+            _ => None,
+        }
+    }
+}
+
+/// Environment and arguments ready to hand to the app's process
+#[derive(Debug, Default)]
+pub struct ResolvedEnv {
+    pub env: Vec<(String, String)>,
+    pub args: Vec<String>,
+}
+
+impl ResolvedEnv {
+    /// Set `name` unless the manifest already did, for values of the user the app may pin.
+    pub fn set_default(&mut self, name: &str, value: String) {
+        if !self.env.iter().any(|(existing, _)| existing == name) {
+            self.env.push((name.to_string(), value));
+        }
+    }
+}
+
+/// `LANG` for a user's locale; the sandbox's own `/usr` provides the locale data.
+pub fn posix_locale(locale: Locale) -> String {
+    match locale {
+        Locale::En => "en_US.UTF-8".to_string(),
+        Locale::Fr => "fr_FR.UTF-8".to_string(),
+    }
+}
+
+/// Replace each `{name}` in `template` with `lookup(name)`; `{{` is a literal brace.
+fn render(template: &str, lookup: impl Fn(&str) -> Option<String>) -> Result<String> {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        if let Some(tail) = after.strip_prefix('{') {
+            out.push('{');
+            rest = tail;
+            continue;
+        }
+        let end = after.find('}').with_context(|| format!("Unclosed placeholder in {:?}", template))?;
+        let name = &after[..end];
+        out.push_str(&lookup(name).with_context(|| format!("Unknown placeholder {{{}}} in {:?}", name, template))?);
+        rest = &after[end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// The name a secret of `app_name` is looked up under, e.g. `APP_SECRET_MAP_VIEWER__API_KEY`
+fn secret_key(app_name: &str, secret: &str) -> String {
+    format!("APP_SECRET_{}__{}", app_name.replace('-', "_"), secret).to_ascii_uppercase()
+}
+
+impl AppEnv {
+    /// Reject names an app must not set and templates the launch could not fill.
+    pub fn validate(&self) -> Result<()> {
+        for (name, value) in &self.env {
+            let mut chars = name.chars();
+            let valid = chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+            if !valid {
+                anyhow::bail!("Invalid environment variable name {:?}", name);
+            }
+            if is_reserved(name) {
+                anyhow::bail!("App environment cannot set {}", name);
+            }
+            // These change which code every program in the sandbox runs
+            if name.starts_with("LD_") || name == "GCONV_PATH" {
+                anyhow::bail!("App environment cannot set {}", name);
+            }
+            match value {
+                EnvValue::Text(template) => {
+                    render(template, |p| PLACEHOLDERS.contains(&p).then(String::new))?;
+                }
+                EnvValue::Secret { secret } => {
+                    if secret.is_empty() || !secret.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                        anyhow::bail!("Invalid secret name {:?} for {}", secret, name);
+                    }
+                }
+            }
+        }
+        for arg in &self.args {
+            render(arg, |p| PLACEHOLDERS.contains(&p).then(String::new))?;
+        }
+        Ok(())
+    }
+
+    /// Fill placeholders and read secrets for one session of `app_name`. A missing secret
+    /// fails the launch rather than starting the app half configured.
+    pub fn resolve(&self, app_name: &str, vars: &TemplateVars, secrets: &dyn SecretsProvider) -> Result<ResolvedEnv> {
+        let mut env = Vec::with_capacity(self.env.len());
+        for (name, value) in &self.env {
+            let value = match value {
+                EnvValue::Text(template) => render(template, |p| vars.get(p))?,
+                EnvValue::Secret { secret } => {
+                    let key = secret_key(app_name, secret);
+                    secrets.get(&key).with_context(|| format!("Secret {} for {} is not set", key, name))?
+                }
+            };
+            if value.contains('\0') {
+                anyhow::bail!("Value of {} contains a NUL byte", name);
+            }
+            env.push((name.clone(), value));
+        }
+        let args = self
+            .args
+            .iter()
+            .map(|arg| render(arg, |p| vars.get(p)))
+            .collect::<Result<Vec<_>>>()?;
+        if args.iter().any(|arg| arg.contains('\0')) {
+            anyhow::bail!("An argument contains a NUL byte");
+        }
+        Ok(ResolvedEnv { env, args })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    struct Fixed(HashMap<&'static str, &'static str>);
+
+    impl SecretsProvider for Fixed {
+        fn get(&self, name: &str) -> Option<String> {
+            self.0.get(name).map(|s| s.to_string())
+        }
+    }
+
+    fn app_env(json: serde_json::Value) -> AppEnv {
+        serde_json::from_value(json).unwrap()
+    }
+
+    fn vars() -> TemplateVars<'static> {
+        TemplateVars {
+            session_id: "s1",
+            user_id: "u1",
+            app_dir: "/app/.app/map_viewer",
+            root_path: "/vault/u1",
+            allowed_paths: &[],
+        }
+    }
+
+    #[test]
+    fn test_resolve_fills_placeholders_and_secrets() {
+        let spec = app_env(serde_json::json!({
+            "env": {
+                "CACHE_DIR": "{root_path}/.cache/{user_id}",
+                "MAPS_TOKEN": { "secret": "api_key" }
+            },
+            "args": ["--session={session_id}", "{{literal}"]
+        }));
+        assert!(spec.validate().is_ok());
+        let secrets = Fixed(HashMap::from([("APP_SECRET_MAP_VIEWER__API_KEY", "t0ken")]));
+        let resolved = spec.resolve("map-viewer", &vars(), &secrets).unwrap();
+        assert_eq!(
+            resolved.env,
+            vec![
+                ("CACHE_DIR".to_string(), "/vault/u1/.cache/u1".to_string()),
+                ("MAPS_TOKEN".to_string(), "t0ken".to_string()),
+            ]
+        );
+        assert_eq!(resolved.args, ["--session=s1", "{literal}"]);
+
+        // Another app's secret, or one not configured, is not there to be read
+        assert!(spec.resolve("other-app", &vars(), &secrets).is_err());
+    }
+
+    #[test]
+    fn test_validate_rejects_unsafe_names_and_unknown_placeholders() {
+        assert!(app_env(serde_json::json!({ "env": { "DISPLAY": ":0" } })).validate().is_err());
+        assert!(app_env(serde_json::json!({ "env": { "SANDBOX_WIDTH": "1" } })).validate().is_err());
+        assert!(app_env(serde_json::json!({ "env": { "LD_PRELOAD": "/tmp/x.so" } })).validate().is_err());
+        assert!(app_env(serde_json::json!({ "env": { "A=B": "1" } })).validate().is_err());
+        assert!(app_env(serde_json::json!({ "env": { "HOME": "{jwt_secret}" } })).validate().is_err());
+        assert!(app_env(serde_json::json!({ "env": { "TOKEN": { "secret": "../jwt" } } })).validate().is_err());
+        assert!(app_env(serde_json::json!({ "args": ["{session_id"] })).validate().is_err());
+        assert!(app_env(serde_json::json!({ "args": ["--user", "{user_id}"] })).validate().is_ok());
+    }
+}
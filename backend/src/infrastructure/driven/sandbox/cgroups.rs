@@ -1,15 +1,16 @@
 use std::path::PathBuf;
 use tracing::{info, warn};
+use crate::domain::value_objects::ResourceClass;
 
-const CGROUP_BASE: &str = "/sys/fs/cgroup/sandbox";
+pub const CGROUP_BASE: &str = "/sys/fs/cgroup/sandbox";
 
 fn cgroup_path(session_id: &str) -> PathBuf {
     PathBuf::from(CGROUP_BASE).join(session_id)
 }
 
-/// Create a cgroup v2 for the given session and configure resource limits.
+/// Create a cgroup v2 for the given session and configure the limits of the app's class.
 /// Must be called in the PARENT process after spawning the child.
-pub fn setup_cgroup(session_id: &str, pid: u32) -> std::io::Result<()> {
+pub fn setup_cgroup(session_id: &str, pid: u32, class: ResourceClass) -> std::io::Result<()> {
     let dir = cgroup_path(session_id);
 
     // Ensure parent cgroup exists
@@ -22,19 +23,20 @@ pub fn setup_cgroup(session_id: &str, pid: u32) -> std::io::Result<()> {
     std::fs::write(dir.join("cgroup.procs"), pid.to_string())
         .unwrap_or_else(|e| warn!("cgroup: failed to write cgroup.procs: {}", e));
 
-    // CPU: 50% of one core (500ms per 1000ms period)
-    std::fs::write(dir.join("cpu.max"), "500000 1000000")
+    let resources = class.resources();
+
+    // CPU: the class's share of a core, per 1000ms period
+    std::fs::write(dir.join("cpu.max"), format!("{} 1000000", resources.cpu_millis as u64 * 1000))
         .unwrap_or_else(|e| warn!("cgroup: failed to set cpu.max: {}", e));
 
-    // Memory: 512 MiB
-    std::fs::write(dir.join("memory.max"), (512 * 1024 * 1024).to_string())
+    std::fs::write(dir.join("memory.max"), (resources.memory_mb as u64 * 1024 * 1024).to_string())
         .unwrap_or_else(|e| warn!("cgroup: failed to set memory.max: {}", e));
 
-    // PID count: max 100 threads/processes
-    std::fs::write(dir.join("pids.max"), "100")
+    // PID count: threads and processes together
+    std::fs::write(dir.join("pids.max"), class.max_pids().to_string())
         .unwrap_or_else(|e| warn!("cgroup: failed to set pids.max: {}", e));
 
-    info!("cgroup v2 configured for session {} (pid {})", session_id, pid);
+    info!("cgroup v2 configured for session {} (pid {}, {:?})", session_id, pid, class);
     Ok(())
 }
 
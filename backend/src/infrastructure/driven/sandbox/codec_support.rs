@@ -0,0 +1,110 @@
+use gstreamer as gst;
+use serde::Serialize;
+use tracing::{info, warn};
+
+use crate::domain::aggregates::application_session::VideoCodec;
+
+/// Elements every built-in capture pipeline uses, whatever the codec
+const CAPTURE_ELEMENTS: [&str; 8] = [
+    "ximagesrc",
+    "videoconvert",
+    "videoscale",
+    "videorate",
+    "capsfilter",
+    "queue",
+    "tee",
+    "appsink",
+];
+
+/// The encoder the built-in pipeline would use for `codec`
+fn encoder_element(codec: VideoCodec) -> &'static str {
+    match codec {
+        VideoCodec::H264 => "x264enc",
+        VideoCodec::VP8 => "vp8enc",
+        VideoCodec::VP9 => "vp9enc",
+        VideoCodec::AV1 => "av1enc",
+    }
+}
+
+/// Whether one codec can be encoded here, as reported by `/health`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CodecStatus {
+    pub codec: VideoCodec,
+    pub available: bool,
+    /// GStreamer elements the built-in pipeline needs but no installed plugin provides
+    pub missing_elements: Vec<String>,
+}
+
+/// Which configured codecs the built-in capture pipeline can encode with the installed
+/// GStreamer plugins. Probed once at startup, so a missing plugin disables its codec up front
+/// instead of failing deep inside a session start.
+#[derive(Debug, Clone, Default)]
+pub struct CodecSupport {
+    statuses: Vec<CodecStatus>,
+}
+
+impl CodecSupport {
+    /// Look up the elements of each codec in the GStreamer registry. Requires `gst::init`.
+    pub fn probe(codecs: &[VideoCodec]) -> Self {
+        let support = Self::probe_with(codecs, |name| gst::ElementFactory::find(name).is_some());
+        for status in &support.statuses {
+            if status.available {
+                info!("Codec {:?} available", status.codec);
+            } else {
+                warn!(
+                    "Codec {:?} disabled: missing GStreamer elements {}",
+                    status.codec,
+                    status.missing_elements.join(", ")
+                );
+            }
+        }
+        support
+    }
+
+    fn probe_with(codecs: &[VideoCodec], has_element: impl Fn(&str) -> bool) -> Self {
+        let statuses = codecs
+            .iter()
+            .map(|&codec| {
+                let missing_elements: Vec<String> = CAPTURE_ELEMENTS
+                    .iter()
+                    .copied()
+                    .chain([encoder_element(codec)])
+                    .filter(|name| !has_element(name))
+                    .map(str::to_string)
+                    .collect();
+                CodecStatus { codec, available: missing_elements.is_empty(), missing_elements }
+            })
+            .collect();
+        Self { statuses }
+    }
+
+    /// Codecs that were not probed count as unavailable.
+    pub fn is_available(&self, codec: VideoCodec) -> bool {
+        self.statuses.iter().any(|status| status.codec == codec && status.available)
+    }
+
+    pub fn statuses(&self) -> &[CodecStatus] {
+        &self.statuses
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_encoder_disables_only_its_codec() {
+        let support = CodecSupport::probe_with(&[VideoCodec::VP8, VideoCodec::H264], |name| name != "x264enc");
+        assert!(support.is_available(VideoCodec::VP8));
+        assert!(!support.is_available(VideoCodec::H264));
+        assert_eq!(support.statuses()[1].missing_elements, vec!["x264enc".to_string()]);
+        assert!(!support.is_available(VideoCodec::VP9));
+    }
+
+    #[test]
+    fn test_missing_capture_element_disables_every_codec() {
+        let support = CodecSupport::probe_with(&[VideoCodec::VP8, VideoCodec::VP9], |name| name != "ximagesrc");
+        assert!(support.statuses().iter().all(|status| !status.available));
+        assert_eq!(support.statuses()[0].missing_elements, vec!["ximagesrc".to_string()]);
+    }
+}
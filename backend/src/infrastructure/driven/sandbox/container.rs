@@ -0,0 +1,234 @@
+//! Container execution mode, for third-party apps that are not built against the SDK. The app
+//! runs from an OCI image under `CONTAINER_RUNTIME` (podman by default; any runtime with a
+//! docker-compatible CLI works). The session's Xvfb stays on the host and only its socket is
+//! mounted into the container, so capture and input work exactly as for native apps.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::process::Stdio;
+use tokio::process::{Child, Command};
+use tracing::{info, warn};
+use crate::domain::value_objects::ResourceClass;
+
+/// The `container` section of an app's manifest, read when `runtime` is `"container"`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ContainerApp {
+    pub image: String,
+    /// Overrides the image's entrypoint arguments
+    #[serde(default)]
+    pub command: Vec<String>,
+}
+
+/// What a container needs to know about the session it runs in
+pub struct ContainerLaunch<'a> {
+    pub session_id: &'a str,
+    pub display_number: u16,
+    pub width: u16,
+    pub height: u16,
+    pub root_path: &'a str,
+    pub allowed_paths: &'a [String],
+    /// Paths under legal hold within the mounted ones, mounted again read-only
+    pub held_paths: &'a [String],
+    pub fonts_dir: Option<&'a str>,
+    pub class: ResourceClass,
+    /// Variables from the app's manifest, passed through the runtime's environment so
+    /// secrets stay off its command line
+    pub env: &'a [(String, String)],
+    /// Appended to the image's command
+    pub args: &'a [String],
+}
+
+fn runtime() -> String {
+    std::env::var("CONTAINER_RUNTIME").unwrap_or_else(|_| "podman".to_string())
+}
+
+fn container_name(session_id: &str) -> String {
+    format!("sandbox-{}", session_id)
+}
+
+/// Thousandths of a core as the decimal `--cpus` expects
+fn cpus(millis: u32) -> String {
+    format!("{}.{:03}", millis / 1000, millis % 1000)
+}
+
+/// Arguments of `<runtime> run`. The limits that native apps get from cgroups, namespaces and
+/// Landlock become the container's limits: no network, the class's CPU, memory and process
+/// count, a read-only image, and only the session's vault paths mounted.
+fn run_args(app: &ContainerApp, launch: &ContainerLaunch) -> Vec<String> {
+    let resources = launch.class.resources();
+    let x_socket = format!("/tmp/.X11-unix/X{}", launch.display_number);
+    let mut args: Vec<String> = vec![
+        "run".into(),
+        "--rm".into(),
+        "--name".into(),
+        container_name(launch.session_id),
+        "--network".into(),
+        "none".into(),
+        "--cpus".into(),
+        cpus(resources.cpu_millis),
+        "--memory".into(),
+        format!("{}m", resources.memory_mb),
+        "--pids-limit".into(),
+        launch.class.max_pids().to_string(),
+        "--read-only".into(),
+        "--tmpfs".into(),
+        "/tmp".into(),
+        "--cap-drop".into(),
+        "all".into(),
+        "--security-opt".into(),
+        "no-new-privileges".into(),
+        "-v".into(),
+        format!("{}:{}", x_socket, x_socket),
+        "-e".into(),
+        format!("DISPLAY=:{}", launch.display_number),
+        "-e".into(),
+        format!("SANDBOX_SESSION_ID={}", launch.session_id),
+        "-e".into(),
+        format!("SANDBOX_WIDTH={}", launch.width),
+        "-e".into(),
+        format!("SANDBOX_HEIGHT={}", launch.height),
+    ];
+
+    // Paths keep their host location, so ROOT_PATH and ALLOWED_PATHS mean the same inside
+    let vault_paths: Vec<&str> = if launch.allowed_paths.is_empty() {
+        Some(launch.root_path).filter(|path| !path.is_empty()).into_iter().collect()
+    } else {
+        launch.allowed_paths.iter().map(String::as_str).collect()
+    };
+    for path in &vault_paths {
+        args.extend(["-v".into(), format!("{}:{}", path, path)]);
+    }
+    // Later mounts shadow the read-write ones above them
+    for path in launch.held_paths {
+        args.extend(["-v".into(), format!("{}:{}:ro", path, path)]);
+    }
+    if !launch.root_path.is_empty() {
+        args.extend(["-e".into(), format!("ROOT_PATH={}", launch.root_path)]);
+    }
+    if !launch.allowed_paths.is_empty() {
+        args.extend(["-e".into(), format!("ALLOWED_PATHS={}", launch.allowed_paths.join(":"))]);
+    }
+    if let Some(dir) = launch.fonts_dir {
+        args.extend(["-v".into(), format!("{}:{}:ro", dir, dir), "-e".into(), format!("SANDBOX_FONTS_DIR={}", dir)]);
+    }
+    // `-e NAME` without a value copies it from the runtime's own environment
+    for (name, _) in launch.env {
+        args.extend(["-e".into(), name.clone()]);
+    }
+
+    args.push(app.image.clone());
+    args.extend(app.command.iter().cloned());
+    args.extend(launch.args.iter().cloned());
+    args
+}
+
+/// Start the app's container. The returned child is the runtime's client, which lives as long
+/// as the container and carries its output.
+pub fn spawn(app: &ContainerApp, launch: &ContainerLaunch) -> Result<Child> {
+    let runtime = runtime();
+    info!("Starting container {} from {} for session {}", container_name(launch.session_id), app.image, launch.session_id);
+    unsafe {
+        Command::new(&runtime)
+            .args(run_args(app, launch))
+            .envs(launch.env.iter().map(|(name, value)| (name, value)))
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .pre_exec(|| {
+                libc::setsid();
+                Ok(())
+            })
+            .spawn()
+            .with_context(|| format!("Failed to run {} for image {}", runtime, app.image))
+    }
+}
+
+/// Stop and delete the session's container; killing the runtime's client may leave it running.
+pub async fn remove(session_id: &str) {
+    let name = container_name(session_id);
+    let result = Command::new(runtime())
+        .args(["rm", "-f", "--time", "2", &name])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .await;
+    match result {
+        Ok(status) if status.success() => info!("Removed container {}", name),
+        Ok(status) => warn!("Removing container {} exited with {}", name, status),
+        Err(e) => warn!("Failed to remove container {}: {}", name, e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn launch<'a>(root_path: &'a str, allowed_paths: &'a [String]) -> ContainerLaunch<'a> {
+        ContainerLaunch {
+            session_id: "abc",
+            display_number: 101,
+            width: 1280,
+            height: 720,
+            root_path,
+            allowed_paths,
+            held_paths: &[],
+            fonts_dir: None,
+            class: ResourceClass::Medium,
+            env: &[],
+            args: &[],
+        }
+    }
+
+    fn has_pair(args: &[String], flag: &str, value: &str) -> bool {
+        args.windows(2).any(|pair| pair[0] == flag && pair[1] == value)
+    }
+
+    #[test]
+    fn test_run_args_map_class_and_display() {
+        let app = ContainerApp { image: "docker.io/library/gimp:2.10".to_string(), command: vec!["gimp".to_string()] };
+        let args = run_args(&app, &launch("/vault/u1", &[]));
+        assert!(has_pair(&args, "--name", "sandbox-abc"));
+        assert!(has_pair(&args, "--network", "none"));
+        assert!(has_pair(&args, "--cpus", "1.000"));
+        assert!(has_pair(&args, "--memory", "1024m"));
+        assert!(has_pair(&args, "--pids-limit", "200"));
+        assert!(has_pair(&args, "-v", "/tmp/.X11-unix/X101:/tmp/.X11-unix/X101"));
+        assert!(has_pair(&args, "-e", "DISPLAY=:101"));
+        assert!(has_pair(&args, "-v", "/vault/u1:/vault/u1"));
+        // The image and its command come last
+        assert_eq!(&args[args.len() - 2..], ["docker.io/library/gimp:2.10", "gimp"]);
+    }
+
+    #[test]
+    fn test_clients_only_get_their_allowed_paths() {
+        let app = ContainerApp { image: "app".to_string(), command: vec![] };
+        let allowed = vec!["/vault/o1/docs".to_string(), "/vault/o1/photos".to_string()];
+        let args = run_args(&app, &launch("/vault/o1", &allowed));
+        assert!(!has_pair(&args, "-v", "/vault/o1:/vault/o1"));
+        assert!(has_pair(&args, "-v", "/vault/o1/docs:/vault/o1/docs"));
+        assert!(has_pair(&args, "-v", "/vault/o1/photos:/vault/o1/photos"));
+        assert!(has_pair(&args, "-e", "ALLOWED_PATHS=/vault/o1/docs:/vault/o1/photos"));
+        assert_eq!(cpus(500), "0.500");
+    }
+
+    #[test]
+    fn test_held_paths_are_mounted_read_only() {
+        let app = ContainerApp { image: "app".to_string(), command: vec![] };
+        let held = vec!["/vault/u1/taxes".to_string()];
+        let args = run_args(&app, &ContainerLaunch { held_paths: &held, ..launch("/vault/u1", &[]) });
+        let rw = args.iter().position(|arg| arg == "/vault/u1:/vault/u1").unwrap();
+        let ro = args.iter().position(|arg| arg == "/vault/u1/taxes:/vault/u1/taxes:ro").unwrap();
+        assert!(rw < ro);
+    }
+
+    #[test]
+    fn test_manifest_env_values_stay_off_the_command_line() {
+        let app = ContainerApp { image: "app".to_string(), command: vec!["serve".to_string()] };
+        let env = vec![("API_TOKEN".to_string(), "t0ken".to_string())];
+        let extra_args = vec!["--session=abc".to_string()];
+        let args = run_args(&app, &ContainerLaunch { env: &env, args: &extra_args, ..launch("/vault/u1", &[]) });
+        assert!(has_pair(&args, "-e", "API_TOKEN"));
+        assert!(!args.iter().any(|arg| arg.contains("t0ken")));
+        assert_eq!(&args[args.len() - 3..], ["app", "serve", "--session=abc"]);
+    }
+}
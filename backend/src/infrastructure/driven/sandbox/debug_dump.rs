@@ -0,0 +1,281 @@
+//! Per-session stream dumps for diagnosing video problems: the encoded stream as an IVF file
+//! and periodic RGBA snapshots of the display, all drawn from one byte budget. Comparing the
+//! two tells encoder faults (snapshots fine, IVF broken) from browser ones (both fine).
+//! Dumps of watermarked sessions also index each recorded frame by its watermark stamp.
+
+use std::fs;
+use std::io::{self, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use chrono::SecondsFormat;
+use super::frame_clock::FrameStamp;
+
+/// File name of the encoded stream inside a dump directory
+pub const STREAM_FILE: &str = "stream.ivf";
+
+/// File name of the frame index of a watermarked session's dump
+pub const FRAMES_FILE: &str = "frames.csv";
+
+const IVF_FILE_HEADER_LEN: u16 = 32;
+pub const IVF_FRAME_HEADER_LEN: u64 = 12;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DumpLimits {
+    /// Total bytes across the stream and all snapshots
+    pub max_bytes: u64,
+    pub snapshot_interval: Duration,
+}
+
+impl Default for DumpLimits {
+    fn default() -> Self {
+        Self {
+            max_bytes: 256 * 1024 * 1024,
+            snapshot_interval: Duration::from_secs(5),
+        }
+    }
+}
+
+/// Bytes still available to a dump, shared by its writers.
+#[derive(Debug, Clone)]
+pub struct DumpBudget {
+    used: Arc<AtomicU64>,
+    max: u64,
+}
+
+impl DumpBudget {
+    pub fn new(max: u64) -> Self {
+        Self { used: Arc::new(AtomicU64::new(0)), max }
+    }
+
+    /// Reserve `len` bytes; false once they no longer fit.
+    pub fn take(&self, len: u64) -> bool {
+        self.used
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |used| {
+                used.checked_add(len).filter(|total| *total <= self.max)
+            })
+            .is_ok()
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        self.used.load(Ordering::SeqCst) >= self.max
+    }
+}
+
+/// Minimal IVF writer for VP8: a 32-byte file header written with the first frame, then a
+/// 12-byte header before each frame. Timestamps use a millisecond time base.
+pub struct IvfWriter<W: Write + Seek> {
+    out: W,
+    frames: u32,
+}
+
+impl<W: Write + Seek> IvfWriter<W> {
+    pub fn new(out: W) -> Self {
+        Self { out, frames: 0 }
+    }
+
+    pub fn write_frame(&mut self, width: u16, height: u16, timestamp_ms: u64, data: &[u8]) -> io::Result<()> {
+        if self.frames == 0 {
+            let mut header = Vec::with_capacity(IVF_FILE_HEADER_LEN as usize);
+            header.extend_from_slice(b"DKIF");
+            header.extend_from_slice(&0u16.to_le_bytes());
+            header.extend_from_slice(&IVF_FILE_HEADER_LEN.to_le_bytes());
+            header.extend_from_slice(b"VP80");
+            header.extend_from_slice(&width.to_le_bytes());
+            header.extend_from_slice(&height.to_le_bytes());
+            header.extend_from_slice(&1000u32.to_le_bytes());
+            header.extend_from_slice(&1u32.to_le_bytes());
+            header.extend_from_slice(&0u32.to_le_bytes());
+            header.extend_from_slice(&0u32.to_le_bytes());
+            self.out.write_all(&header)?;
+        }
+        self.out.write_all(&(data.len() as u32).to_le_bytes())?;
+        self.out.write_all(&timestamp_ms.to_le_bytes())?;
+        self.out.write_all(data)?;
+        self.frames += 1;
+        Ok(())
+    }
+
+    /// Frames written so far, i.e. the position in the stream of the next one
+    pub fn frames(&self) -> u32 {
+        self.frames
+    }
+
+    /// Record the frame count in the file header. Players cope without it, so this is
+    /// best-effort when the writer is dropped.
+    pub fn finish(&mut self) -> io::Result<()> {
+        if self.frames == 0 {
+            return self.out.flush();
+        }
+        let end = self.out.stream_position()?;
+        self.out.seek(SeekFrom::Start(24))?;
+        self.out.write_all(&self.frames.to_le_bytes())?;
+        self.out.seek(SeekFrom::Start(end))?;
+        self.out.flush()
+    }
+}
+
+impl<W: Write + Seek> Drop for IvfWriter<W> {
+    fn drop(&mut self) {
+        let _ = self.finish();
+    }
+}
+
+/// The IVF writer a dump's GStreamer branch feeds
+pub type IvfFile = IvfWriter<io::BufWriter<fs::File>>;
+
+/// CSV of the recorded frames' watermark stamps: position in the IVF stream, its timestamp,
+/// and the session frame index and capture time drawn on it.
+pub struct FrameIndex<W: Write> {
+    out: W,
+}
+
+impl<W: Write> FrameIndex<W> {
+    pub fn new(mut out: W) -> io::Result<Self> {
+        out.write_all(b"stream_frame,timestamp_ms,frame_index,captured_at\n")?;
+        Ok(Self { out })
+    }
+
+    pub fn write_frame(&mut self, stream_frame: u32, timestamp_ms: u64, stamp: &FrameStamp) -> io::Result<()> {
+        writeln!(
+            self.out,
+            "{},{},{},{}",
+            stream_frame,
+            timestamp_ms,
+            stamp.index,
+            stamp.at.to_rfc3339_opts(SecondsFormat::Millis, true)
+        )
+    }
+}
+
+impl<W: Write> Drop for FrameIndex<W> {
+    fn drop(&mut self) {
+        let _ = self.out.flush();
+    }
+}
+
+pub type FrameIndexFile = FrameIndex<io::BufWriter<fs::File>>;
+
+/// Empty `dir` and open the stream file in it. A new dump replaces the previous one.
+pub fn prepare(dir: &Path) -> io::Result<IvfFile> {
+    if dir.exists() {
+        fs::remove_dir_all(dir)?;
+    }
+    fs::create_dir_all(dir)?;
+    Ok(IvfWriter::new(io::BufWriter::new(fs::File::create(dir.join(STREAM_FILE))?)))
+}
+
+/// Open the frame index in a `dir` made by [`prepare`].
+pub fn prepare_frame_index(dir: &Path) -> io::Result<FrameIndexFile> {
+    FrameIndex::new(io::BufWriter::new(fs::File::create(dir.join(FRAMES_FILE))?))
+}
+
+/// Write one display snapshot; the size is in the file name since raw RGBA has no header.
+pub fn write_snapshot(dir: &Path, index: u32, width: u16, height: u16, rgba: &[u8]) -> io::Result<PathBuf> {
+    let path = dir.join(format!("snapshot-{:04}-{}x{}.rgba", index, width, height));
+    fs::write(&path, rgba)?;
+    Ok(path)
+}
+
+/// Delete the dumps under `root` (one directory per session) last written before `before`,
+/// except those of `running` sessions. Returns how many were deleted.
+pub fn expire(root: &Path, before: SystemTime, running: &[String]) -> io::Result<usize> {
+    let entries = match fs::read_dir(root) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e),
+    };
+    let mut deleted = 0;
+    for entry in entries {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if !entry.file_type()?.is_dir() || running.contains(&name) {
+            continue;
+        }
+        if last_written(&entry.path())? < before {
+            fs::remove_dir_all(entry.path())?;
+            deleted += 1;
+        }
+    }
+    Ok(deleted)
+}
+
+/// Latest modification time of `dir` and the files directly in it
+fn last_written(dir: &Path) -> io::Result<SystemTime> {
+    let mut latest = fs::metadata(dir)?.modified()?;
+    for entry in fs::read_dir(dir)? {
+        latest = latest.max(entry?.metadata()?.modified()?);
+    }
+    Ok(latest)
+}
+
+/// Convert X11 32-bit ZPixmap pixels (BGRx, little-endian) to opaque RGBA.
+pub fn bgrx_to_rgba(bgrx: &[u8]) -> Vec<u8> {
+    bgrx.chunks_exact(4)
+        .flat_map(|px| [px[2], px[1], px[0], 0xff])
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ivf_layout_and_frame_count() {
+        let mut buf = io::Cursor::new(Vec::new());
+        {
+            let mut writer = IvfWriter::new(&mut buf);
+            writer.write_frame(640, 360, 0, &[1, 2, 3]).unwrap();
+            writer.write_frame(640, 360, 33, &[4]).unwrap();
+        }
+        let bytes = buf.into_inner();
+        assert_eq!(&bytes[0..4], b"DKIF");
+        assert_eq!(&bytes[8..12], b"VP80");
+        assert_eq!(u16::from_le_bytes([bytes[12], bytes[13]]), 640);
+        assert_eq!(u32::from_le_bytes(bytes[24..28].try_into().unwrap()), 2);
+        assert_eq!(bytes.len(), 32 + 12 + 3 + 12 + 1);
+        assert_eq!(u64::from_le_bytes(bytes[51..59].try_into().unwrap()), 33);
+    }
+
+    #[test]
+    fn test_frame_index() {
+        use chrono::TimeZone;
+        let mut buf = Vec::new();
+        {
+            let mut index = FrameIndex::new(&mut buf).unwrap();
+            let at = chrono::Utc.with_ymd_and_hms(2026, 10, 17, 12, 0, 0).unwrap();
+            index.write_frame(0, 40, &FrameStamp { index: 1200, at }).unwrap();
+        }
+        let text = String::from_utf8(buf).unwrap();
+        assert_eq!(text.lines().nth(1), Some("0,40,1200,2026-10-17T12:00:00.000Z"));
+    }
+
+    #[test]
+    fn test_budget_stops_at_max() {
+        let budget = DumpBudget::new(100);
+        assert!(budget.take(60));
+        assert!(!budget.take(50));
+        assert!(budget.clone().take(40));
+        assert!(budget.is_exhausted());
+    }
+
+    #[test]
+    fn test_expire_keeps_recent_and_running_dumps() {
+        let root = std::env::temp_dir().join(format!("debug-dump-expire-{}", std::process::id()));
+        for session in ["old", "running"] {
+            prepare(&root.join(session)).unwrap();
+        }
+        let running = vec!["running".to_string()];
+        assert_eq!(expire(&root, SystemTime::now() - Duration::from_secs(3600), &running).unwrap(), 0);
+        assert_eq!(expire(&root, SystemTime::now() + Duration::from_secs(60), &running).unwrap(), 1);
+        assert!(!root.join("old").exists());
+        assert!(root.join("running").join(STREAM_FILE).exists());
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_bgrx_to_rgba() {
+        assert_eq!(bgrx_to_rgba(&[1, 2, 3, 0, 4, 5, 6, 0]), vec![3, 2, 1, 255, 6, 5, 4, 255]);
+    }
+}
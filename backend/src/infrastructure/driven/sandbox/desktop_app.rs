@@ -0,0 +1,87 @@
+//! Existing desktop applications, such as an extracted AppImage or a distribution package,
+//! registered with a `launch` section in their manifest instead of an SDK binary. They run
+//! in the same sandbox as native apps; with `maximize`, the display's window manager fills the
+//! screen with each of their windows so the stream shows the app rather than a corner of it.
+
+use anyhow::Result;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+use super::app_env;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct LaunchSpec {
+    /// Program and arguments. A program starting with `./` is in the app's directory; a bare
+    /// name is looked up in `PATH`.
+    pub command: Vec<String>,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    /// Resize every top-level window to fill the display
+    #[serde(default)]
+    pub maximize: bool,
+    /// Extra directories the app may read, e.g. where its libraries are installed
+    #[serde(default)]
+    pub read_only_paths: Vec<String>,
+}
+
+impl LaunchSpec {
+    pub fn validate(&self) -> Result<()> {
+        if self.command.first().map_or(true, |program| program.is_empty()) {
+            anyhow::bail!("Launch command is empty");
+        }
+        if let Some(name) = self.env.keys().find(|name| app_env::is_reserved(name)) {
+            anyhow::bail!("Launch environment cannot set {}", name);
+        }
+        if let Some(path) = self.read_only_paths.iter().find(|path| !Path::new(path).is_absolute()) {
+            anyhow::bail!("Read-only path {} is not absolute", path);
+        }
+        Ok(())
+    }
+
+    /// The program to execute, resolved against the app's directory
+    pub fn program(&self, app_dir: &str) -> String {
+        let program = &self.command[0];
+        match program.strip_prefix("./") {
+            Some(relative) => format!("{}/{}", app_dir.trim_end_matches('/'), relative),
+            None => program.clone(),
+        }
+    }
+
+    pub fn args(&self) -> &[String] {
+        &self.command[1..]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(json: serde_json::Value) -> LaunchSpec {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_program_is_resolved_against_app_dir() {
+        let appimage = spec(serde_json::json!({
+            "command": ["./squashfs-root/AppRun", "--writer", "--norestore"],
+            "env": { "SAL_USE_VCLPLUGIN": "gen" },
+            "maximize": true
+        }));
+        assert!(appimage.validate().is_ok());
+        assert_eq!(appimage.program("/app/.app/libreoffice/"), "/app/.app/libreoffice/squashfs-root/AppRun");
+        assert_eq!(appimage.args(), ["--writer", "--norestore"]);
+
+        let packaged = spec(serde_json::json!({ "command": ["gimp"] }));
+        assert_eq!(packaged.program("/app/.app/gimp"), "gimp");
+        assert!(!packaged.maximize);
+    }
+
+    #[test]
+    fn test_validate_rejects_sandbox_overrides() {
+        assert!(spec(serde_json::json!({ "command": [] })).validate().is_err());
+        assert!(spec(serde_json::json!({ "command": ["app"], "env": { "DISPLAY": ":0" } })).validate().is_err());
+        assert!(spec(serde_json::json!({ "command": ["app"], "env": { "SANDBOX_WIDTH": "1" } })).validate().is_err());
+        assert!(spec(serde_json::json!({ "command": ["app"], "read_only_paths": ["opt/app"] })).validate().is_err());
+        assert!(spec(serde_json::json!({ "command": ["app"], "read_only_paths": ["/opt/app"] })).validate().is_ok());
+    }
+}
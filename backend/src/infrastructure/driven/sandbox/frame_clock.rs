@@ -0,0 +1,99 @@
+//! One clock per watermarked session for correlating what auditors see: each captured frame
+//! gets the next index and the wall-clock time it was drawn, burned into the watermark, written
+//! next to debug dump recordings and logged with the session's input events.
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use std::collections::VecDeque;
+use std::sync::{Mutex, MutexGuard};
+
+/// Stamps kept for looking up encoded frames by timestamp; the encoder is never further behind
+const RECENT_STAMPS: usize = 256;
+
+/// When a frame was captured, in the session's frame count and in wall-clock time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameStamp {
+    /// Counts up from 0 over the whole session, across pipeline rebuilds
+    pub index: u64,
+    pub at: DateTime<Utc>,
+}
+
+impl FrameStamp {
+    /// `#index 2026-10-17T12:00:00.000Z`, as drawn under the watermark text
+    pub fn label(&self) -> String {
+        format!("#{} {}", self.index, self.at.to_rfc3339_opts(SecondsFormat::Millis, true))
+    }
+}
+
+#[derive(Debug, Default)]
+struct Stamps {
+    latest: Option<FrameStamp>,
+    /// Pipeline timestamp (ns) of each recent frame with its stamp, oldest first
+    recent: VecDeque<(u64, FrameStamp)>,
+}
+
+#[derive(Debug, Default)]
+pub struct FrameClock {
+    stamps: Mutex<Stamps>,
+}
+
+impl FrameClock {
+    /// Stamp the frame with pipeline timestamp `pts_ns` as captured now.
+    pub fn stamp(&self, pts_ns: Option<u64>) -> FrameStamp {
+        self.stamp_at(pts_ns, Utc::now())
+    }
+
+    fn stamp_at(&self, pts_ns: Option<u64>, at: DateTime<Utc>) -> FrameStamp {
+        let mut stamps = self.lock();
+        let index = stamps.latest.map_or(0, |latest| latest.index + 1);
+        let stamp = FrameStamp { index, at };
+        stamps.latest = Some(stamp);
+        if let Some(pts) = pts_ns {
+            if stamps.recent.len() == RECENT_STAMPS {
+                stamps.recent.pop_front();
+            }
+            stamps.recent.push_back((pts, stamp));
+        }
+        stamp
+    }
+
+    /// Stamp of the recent frame with pipeline timestamp `pts_ns`, e.g. once it is encoded.
+    pub fn lookup(&self, pts_ns: u64) -> Option<FrameStamp> {
+        let stamps = self.lock();
+        stamps.recent.iter().rev().find(|(pts, _)| *pts == pts_ns).map(|(_, stamp)| *stamp)
+    }
+
+    /// The last frame stamped, i.e. what the user saw when acting; `None` before any frame.
+    pub fn latest(&self) -> Option<FrameStamp> {
+        self.lock().latest
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Stamps> {
+        self.stamps.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_frame_clock() {
+        let clock = FrameClock::default();
+        assert_eq!(clock.latest(), None);
+        let at = Utc.with_ymd_and_hms(2026, 10, 17, 12, 0, 0).unwrap();
+        let first = clock.stamp_at(Some(1_000), at);
+        let second = clock.stamp_at(Some(2_000), at + chrono::Duration::milliseconds(33));
+        assert_eq!((first.index, second.index), (0, 1));
+        assert_eq!(clock.lookup(1_000), Some(first));
+        assert_eq!(clock.lookup(3_000), None);
+        assert_eq!(clock.latest(), Some(second));
+        assert_eq!(second.label(), "#1 2026-10-17T12:00:00.033Z");
+
+        for n in 0..RECENT_STAMPS as u64 {
+            clock.stamp_at(Some(10_000 + n), at);
+        }
+        assert_eq!(clock.lookup(1_000), None);
+        assert_eq!(clock.latest().map(|s| s.index), Some(RECENT_STAMPS as u64 + 1));
+    }
+}
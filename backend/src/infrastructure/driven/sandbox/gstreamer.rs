@@ -1,9 +1,50 @@
 use anyhow::{Context, Result};
+use bytes::Bytes;
 use gstreamer as gst;
 use gstreamer::prelude::*;
 use gstreamer_app::AppSink;
+use std::sync::mpsc::TrySendError;
 use std::sync::Arc;
-use tracing::{error, info};
+use tracing::{debug, error, info};
+
+use super::debug_dump::{DumpBudget, FrameIndexFile, IvfFile, IVF_FRAME_HEADER_LEN};
+use super::frame_clock::FrameClock;
+use super::pipeline_template::PipelineTemplate;
+use super::screen_activity::{ActivityChange, ScreenActivity, FULL_REDRAW};
+use crate::domain::aggregates::application_session::StreamQuality;
+
+/// Whether the X cursor should be drawn into captured frames (`BAKED_CURSOR=true`).
+/// Disabled by default: the browser composites the cursor from metadata instead.
+pub fn baked_cursor_enabled() -> bool {
+    std::env::var("BAKED_CURSOR")
+        .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+        .unwrap_or(false)
+}
+
+/// Whether the encoder follows screen activity (`STATIC_SCREEN_ENCODING=true`): keyframes
+/// become rare while nothing changes, and a large redraw gets a keyframe of its own.
+pub fn static_screen_encoding_enabled() -> bool {
+    std::env::var("STATIC_SCREEN_ENCODING")
+        .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+        .unwrap_or(false)
+}
+
+/// Text burned into every captured frame, with the frame's index and capture time from the
+/// session's clock under it
+#[derive(Debug, Clone)]
+pub struct Watermark {
+    pub text: String,
+    pub clock: Arc<FrameClock>,
+}
+
+/// Per-session choices for a capture pipeline
+#[derive(Debug, Clone, Default)]
+pub struct CaptureOptions {
+    /// Burned into every captured frame, for view-only sessions
+    pub watermark: Option<Watermark>,
+    /// Encoding chain from configuration, replacing the built-in one
+    pub template: Option<PipelineTemplate>,
+}
 
 pub struct GStreamerManager {}
 
@@ -14,93 +55,94 @@ impl GStreamerManager {
     }
 
     /// Start a pipeline that captures from an Xvfb display via ximagesrc and outputs VP8 via
-    /// appsink. Returns a std::sync::mpsc::Receiver<Vec<u8>> for VP8 encoded frames.
+    /// appsink. Returns a std::sync::mpsc::Receiver<Bytes> for VP8 encoded frames.
     pub fn start_ximagesrc_pipeline(
         &self,
         session_id: &str,
         display_str: &str,
-        framerate: u8,
-    ) -> Result<(gst::Pipeline, std::sync::mpsc::Receiver<Vec<u8>>)> {
+        width: u16,
+        height: u16,
+        quality: &StreamQuality,
+        options: &CaptureOptions,
+    ) -> Result<(gst::Pipeline, std::sync::mpsc::Receiver<Bytes>)> {
+        let (pipeline, rx) = self.prepare_ximagesrc_pipeline(session_id, display_str, width, height, quality, options)?;
+        self.start_prepared(session_id, &pipeline)?;
+        Ok((pipeline, rx))
+    }
+
+    /// Build the capture pipeline and bring it to PAUSED, so the display connection and
+    /// elements are ready but no frames flow until [`Self::start_prepared`].
+    pub fn prepare_ximagesrc_pipeline(
+        &self,
+        session_id: &str,
+        display_str: &str,
+        width: u16,
+        height: u16,
+        quality: &StreamQuality,
+        options: &CaptureOptions,
+    ) -> Result<(gst::Pipeline, std::sync::mpsc::Receiver<Bytes>)> {
         info!(
-            "Starting GStreamer ximagesrc pipeline for session {:?} on display {:?} @{:?}fps",
-            session_id, display_str, framerate
+            "Preparing GStreamer ximagesrc pipeline for session {:?} on display {:?} with {:?}",
+            session_id, display_str, quality
         );
 
+        // The pointer is rendered client-side from cursor metadata; only bake it into
+        // frames when explicitly requested (e.g. for session recordings).
         let ximagesrc = gst::ElementFactory::make("ximagesrc")
+            .name("source")
             .property_from_str("display-name", display_str)
             .property("use-damage", false)
+            .property("show-pointer", baked_cursor_enabled())
             .build()
             .context("Failed to create ximagesrc")?;
 
-        let videoconvert = gst::ElementFactory::make("videoconvert")
-            .build()
-            .context("Failed to create videoconvert")?;
-
-        let capsfilter = gst::ElementFactory::make("capsfilter")
-            .property(
-                "caps",
-                &gst::Caps::builder("video/x-raw")
-                    .field("format", "I420")
-                    .field("framerate", gst::Fraction::new(framerate as i32, 1))
-                    .build(),
-            )
+        // Encoded frames fan out here: the session's own stream plus any owner watch branches
+        let tee = gst::ElementFactory::make("tee")
+            .name("tee")
             .build()
-            .context("Failed to create capsfilter")?;
+            .context("Failed to create tee")?;
 
-        let vp8enc = gst::ElementFactory::make("vp8enc")
-            .property("deadline", 1i64)
-            .property("cpu-used", 8i32)
-            .property("target-bitrate", 1_000_000i32)
+        let queue = gst::ElementFactory::make("queue")
             .build()
-            .context("Failed to create vp8enc")?;
-
-        let appsink = gst::ElementFactory::make("appsink")
-            .name("sink")
-            .property("sync", false)
-            .property("emit-signals", true)
-            .property_from_str("max-buffers", "100")
-            .property("drop", true)
-            .build()
-            .context("Failed to create appsink")?;
+            .context("Failed to create queue")?;
 
-        let pipeline = gst::Pipeline::default();
-        pipeline.add_many([&ximagesrc, &videoconvert, &capsfilter, &vp8enc, &appsink])?;
-        ximagesrc.link(&videoconvert).context("Failed to link ximagesrc -> videoconvert")?;
-        videoconvert.link(&capsfilter).context("Failed to link videoconvert -> capsfilter")?;
-        capsfilter.link(&vp8enc).context("Failed to link capsfilter -> vp8enc")?;
-        vp8enc.link(&appsink).context("Failed to link vp8enc -> appsink")?;
+        let appsink = make_appsink("sink")?;
 
-        let appsink_el = appsink
-            .downcast::<AppSink>()
-            .map_err(|_| anyhow::anyhow!("Failed to downcast to AppSink"))?;
+        let pipeline = gst::Pipeline::default();
+        pipeline.add_many([&ximagesrc, &tee, &queue, &appsink])?;
+        let watermark = options.watermark.as_ref();
+        let encoder = match &options.template {
+            Some(template) => {
+                info!("[session {}] Using configured pipeline template", session_id);
+                let description = template.render(width, height, quality);
+                link_template_chain(&pipeline, &ximagesrc, &description, watermark)?
+            }
+            None => link_builtin_chain(&pipeline, &ximagesrc, width, height, quality, watermark)?,
+        };
+        encoder.link(&tee).context("Failed to link encoder -> tee")?;
+        if static_screen_encoding_enabled() {
+            watch_screen_activity(&pipeline, session_id)?;
+        }
+        tee.link(&queue).context("Failed to link tee -> queue")?;
+        queue.link(&appsink).context("Failed to link queue -> appsink")?;
 
-        let (tx, rx) = std::sync::mpsc::channel::<Vec<u8>>();
-        let tx = Arc::new(tx);
+        let rx = frame_receiver(appsink)?;
 
-        let tx_cb = Arc::clone(&tx);
-        appsink_el.set_callbacks(
-            gstreamer_app::AppSinkCallbacks::builder()
-                .new_sample(move |sink| {
-                    let sample = match sink.pull_sample() {
-                        Ok(s) => s,
-                        Err(_) => return Err(gst::FlowError::Eos),
-                    };
-                    if let Some(buffer) = sample.buffer() {
-                        if let Ok(map) = buffer.map_readable() {
-                            let _ = tx_cb.send(map.as_slice().to_vec());
-                        }
-                    }
-                    Ok(gst::FlowSuccess::Ok)
-                })
-                .build(),
-        );
+        pipeline.set_state(gst::State::Paused)?;
+        Ok((pipeline, rx))
+    }
 
+    /// Set a prepared pipeline playing and watch its bus for errors.
+    pub fn start_prepared(&self, session_id: &str, pipeline: &gst::Pipeline) -> Result<()> {
+        info!("Starting GStreamer pipeline for session {:?}", session_id);
         pipeline.set_state(gst::State::Playing)?;
 
         // Monitor bus for errors in a background thread
         let pipeline_clone = pipeline.clone();
         let session_id_owned = session_id.to_string();
+        let span = tracing::Span::current();
         std::thread::spawn(move || {
+            let _entered = span.entered();
             let bus = pipeline_clone.bus().unwrap();
             for msg in bus.iter_timed(gst::ClockTime::NONE) {
                 use gst::MessageView;
@@ -125,6 +167,514 @@ impl GStreamerManager {
             }
         });
 
-        Ok((pipeline, rx))
+        Ok(())
+    }
+
+    /// Add a branch to a running pipeline's tee that delivers the same VP8 frames to a watcher.
+    /// A keyframe is requested so the new viewer can start decoding right away.
+    pub fn add_watch_branch(
+        &self,
+        pipeline: &gst::Pipeline,
+        branch: &str,
+    ) -> Result<std::sync::mpsc::Receiver<Bytes>> {
+        let appsink = add_branch_sink(pipeline, branch)?;
+        let rx = frame_receiver(appsink)?;
+        link_branch_to_tee(pipeline, branch)?;
+        Ok(rx)
+    }
+
+    /// Add a branch to a running pipeline's tee that records the VP8 frames into `writer`,
+    /// starting at the next keyframe. Recording stops once `budget` is spent. With a frame
+    /// index, each recorded frame the watermark stamped is listed in it with its stamp.
+    pub fn add_dump_branch(
+        &self,
+        pipeline: &gst::Pipeline,
+        branch: &str,
+        mut writer: IvfFile,
+        mut frame_index: Option<(FrameIndexFile, Arc<FrameClock>)>,
+        budget: DumpBudget,
+    ) -> Result<()> {
+        let appsink = add_branch_sink(pipeline, branch)?
+            .downcast::<AppSink>()
+            .map_err(|_| anyhow::anyhow!("Failed to downcast to AppSink"))?;
+        let branch_owned = branch.to_string();
+        let mut started = false;
+        let mut stopped = false;
+        appsink.set_callbacks(
+            gstreamer_app::AppSinkCallbacks::builder()
+                .new_sample(move |sink| {
+                    let sample = sink.pull_sample().map_err(|_| gst::FlowError::Eos)?;
+                    let (Some(buffer), Some(caps)) = (sample.buffer(), sample.caps()) else {
+                        return Ok(gst::FlowSuccess::Ok);
+                    };
+                    // A dump must start on a keyframe to be decodable on its own
+                    started |= !buffer.flags().contains(gst::BufferFlags::DELTA_UNIT);
+                    if !started || stopped {
+                        return Ok(gst::FlowSuccess::Ok);
+                    }
+                    let size = caps
+                        .structure(0)
+                        .and_then(|s| Some((s.get::<i32>("width").ok()?, s.get::<i32>("height").ok()?)));
+                    let (Some((width, height)), Ok(map)) = (size, buffer.map_readable()) else {
+                        return Ok(gst::FlowSuccess::Ok);
+                    };
+                    if !budget.take(IVF_FRAME_HEADER_LEN + map.len() as u64) {
+                        info!("[{}] Size cap reached, no longer recording frames", branch_owned);
+                        stopped = true;
+                        return Ok(gst::FlowSuccess::Ok);
+                    }
+                    let timestamp_ms = buffer.pts().map(|pts| pts.mseconds()).unwrap_or(0);
+                    let stream_frame = writer.frames();
+                    if let Err(e) = writer.write_frame(width as u16, height as u16, timestamp_ms, &map) {
+                        error!("[{}] Failed to record frame: {}", branch_owned, e);
+                        stopped = true;
+                        return Ok(gst::FlowSuccess::Ok);
+                    }
+                    if let Some((index, clock)) = &mut frame_index {
+                        let stamp = buffer.pts().and_then(|pts| clock.lookup(pts.nseconds()));
+                        let written = stamp.map_or(Ok(()), |stamp| index.write_frame(stream_frame, timestamp_ms, &stamp));
+                        if let Err(e) = written {
+                            error!("[{}] Failed to index frame, no longer indexing: {}", branch_owned, e);
+                            frame_index = None;
+                        }
+                    }
+                    Ok(gst::FlowSuccess::Ok)
+                })
+                .build(),
+        );
+        link_branch_to_tee(pipeline, branch)
+    }
+
+    /// Detach a watch or dump branch. Unlinking happens once the tee pad is idle so the
+    /// session's own stream is never interrupted.
+    pub fn remove_branch(&self, pipeline: &gst::Pipeline, branch: &str) -> Result<()> {
+        let (Some(tee), Some(queue), Some(appsink)) = (
+            pipeline.by_name("tee"),
+            pipeline.by_name(&format!("{}-queue", branch)),
+            pipeline.by_name(&format!("{}-sink", branch)),
+        ) else {
+            return Ok(());
+        };
+        let queue_pad = queue
+            .static_pad("sink")
+            .ok_or_else(|| anyhow::anyhow!("branch queue has no sink pad"))?;
+        let Some(tee_pad) = queue_pad.peer() else {
+            return Ok(());
+        };
+
+        let pipeline = pipeline.clone();
+        tee_pad.add_probe(gst::PadProbeType::IDLE, move |tee_pad, _| {
+            let _ = tee_pad.unlink(&queue_pad);
+            tee.release_request_pad(tee_pad);
+            let (queue, appsink) = (queue.clone(), appsink.clone());
+            pipeline.call_async(move |pipeline| {
+                let _ = queue.set_state(gst::State::Null);
+                let _ = appsink.set_state(gst::State::Null);
+                let _ = pipeline.remove_many([&queue, &appsink]);
+            });
+            gst::PadProbeReturn::Remove
+        });
+        Ok(())
+    }
+
+    /// Reconfigure a running capture pipeline: output size, framerate and encoder bitrate.
+    pub fn apply_quality(
+        &self,
+        pipeline: &gst::Pipeline,
+        width: u16,
+        height: u16,
+        quality: &StreamQuality,
+    ) -> Result<()> {
+        let capsfilter = pipeline
+            .by_name("caps")
+            .ok_or_else(|| anyhow::anyhow!("capsfilter not found in pipeline"))?;
+        let encoder = pipeline
+            .by_name("encoder")
+            .ok_or_else(|| anyhow::anyhow!("encoder not found in pipeline"))?;
+
+        let caps = capsfilter.property::<gst::Caps>("caps");
+        capsfilter.set_property("caps", with_quality(caps, width, height, quality));
+        // vpxenc takes bits per second; encoders from templates usually take kbit/s in `bitrate`
+        if encoder.find_property("target-bitrate").is_some() {
+            encoder.set_property_from_str("target-bitrate", &quality.max_bitrate.to_string());
+        } else if encoder.find_property("bitrate").is_some() {
+            encoder.set_property_from_str("bitrate", &(quality.max_bitrate / 1000).to_string());
+        }
+        Ok(())
     }
+
+    /// Follow a display that changed size. ximagesrc reads the screen size when it starts, so
+    /// it is restarted to capture the new size; the encoded size follows through the caps
+    /// filter of the built-in chain, while a template keeps the size it was rendered with.
+    pub fn resize_capture(
+        &self,
+        pipeline: &gst::Pipeline,
+        width: u16,
+        height: u16,
+        quality: &StreamQuality,
+    ) -> Result<()> {
+        let source = pipeline
+            .by_name("source")
+            .ok_or_else(|| anyhow::anyhow!("source not found in pipeline"))?;
+        source.set_state(gst::State::Null)?;
+        if pipeline.by_name("caps").is_some() {
+            self.apply_quality(pipeline, width, height, quality)?;
+        }
+        source
+            .sync_state_with_parent()
+            .context("Failed to restart capture after resize")?;
+        // Frames of the old size can't be decoded against the new one
+        self.request_keyframe(pipeline)
+    }
+
+    /// Switch a running pipeline in or out of low-latency mode. In low-latency mode raw frames
+    /// the encoder cannot keep up with are discarded rather than queued, and the encoder trades
+    /// quality for speed.
+    /// Pipeline templates without an `encode-queue`, or with an encoder other than vpxenc,
+    /// only get the parts they support.
+    pub fn set_low_latency(&self, pipeline: &gst::Pipeline, enabled: bool) -> Result<()> {
+        let encoder = pipeline
+            .by_name("encoder")
+            .ok_or_else(|| anyhow::anyhow!("encoder not found in pipeline"))?;
+
+        if let Some(encode_queue) = pipeline.by_name("encode-queue") {
+            if enabled {
+                encode_queue.set_property_from_str("leaky", "downstream");
+                encode_queue.set_property("max-size-buffers", 1u32);
+            } else {
+                encode_queue.set_property_from_str("leaky", "no");
+                encode_queue.set_property("max-size-buffers", NORMAL_QUEUE_BUFFERS);
+            }
+        }
+        if encoder.find_property("cpu-used").is_some() {
+            encoder.set_property("cpu-used", if enabled { LOW_LATENCY_CPU_USED } else { NORMAL_CPU_USED });
+        }
+        Ok(())
+    }
+
+    /// Ask the encoder for a keyframe, so a consumer that just joined can start decoding.
+    pub fn request_keyframe(&self, pipeline: &gst::Pipeline) -> Result<()> {
+        let encoder = pipeline
+            .by_name("encoder")
+            .ok_or_else(|| anyhow::anyhow!("encoder not found in pipeline"))?;
+        if let Some(pad) = encoder.static_pad("src") {
+            pad.send_event(force_keyframe());
+        }
+        Ok(())
+    }
+}
+
+const NORMAL_CPU_USED: i32 = 8;
+const LOW_LATENCY_CPU_USED: i32 = 16;
+/// GStreamer's own default for `queue`
+const NORMAL_QUEUE_BUFFERS: u32 = 200;
+
+/// vpxenc's own default keyframe interval, in frames
+const NORMAL_KEYFRAME_DIST: i32 = 128;
+/// Keyframe interval while the screen is static; consumers that join still get one on request
+const STATIC_KEYFRAME_DIST: i32 = 3000;
+/// Unchanged frames before the screen counts as static
+const STATIC_AFTER_FRAMES: u32 = 30;
+
+/// Encoded frames that may wait for the consumer. A longer backlog means the consumer is falling
+/// behind, so frames are dropped up to the next keyframe instead of buffering without bound.
+const FRAME_BACKLOG: usize = 8;
+
+fn force_keyframe() -> gst::Event {
+    gst::event::CustomUpstream::new(
+        gst::Structure::builder("GstForceKeyUnit")
+            .field("all-headers", true)
+            .build(),
+    )
+}
+
+/// The default encoding chain: scale and rate-limit raw frames, then encode them with vp8enc.
+/// Returns the chain's last element.
+fn link_builtin_chain(
+    pipeline: &gst::Pipeline,
+    source: &gst::Element,
+    width: u16,
+    height: u16,
+    quality: &StreamQuality,
+    watermark: Option<&Watermark>,
+) -> Result<gst::Element> {
+    let videoconvert = gst::ElementFactory::make("videoconvert")
+        .build()
+        .context("Failed to create videoconvert")?;
+
+    let videoscale = gst::ElementFactory::make("videoscale")
+        .build()
+        .context("Failed to create videoscale")?;
+
+    let videorate = gst::ElementFactory::make("videorate")
+        .build()
+        .context("Failed to create videorate")?;
+
+    let capsfilter = gst::ElementFactory::make("capsfilter")
+        .name("caps")
+        .property("caps", quality_caps(width, height, quality))
+        .build()
+        .context("Failed to create capsfilter")?;
+
+    // Holds raw frames while the encoder is busy; in low-latency mode it keeps only the newest
+    let encode_queue = gst::ElementFactory::make("queue")
+        .name("encode-queue")
+        .build()
+        .context("Failed to create encode queue")?;
+
+    // lag-in-frames 0: every frame is encoded as soon as it arrives, never held for lookahead
+    let vp8enc = gst::ElementFactory::make("vp8enc")
+        .name("encoder")
+        .property("deadline", 1i64)
+        .property("cpu-used", NORMAL_CPU_USED)
+        .property("lag-in-frames", 0i32)
+        .property("target-bitrate", quality.max_bitrate as i32)
+        .build()
+        .context("Failed to create vp8enc")?;
+
+    pipeline.add_many([&videoconvert, &videoscale, &videorate, &capsfilter, &encode_queue, &vp8enc])?;
+    source.link(&videoconvert).context("Failed to link ximagesrc -> videoconvert")?;
+    videoconvert.link(&videoscale).context("Failed to link videoconvert -> videoscale")?;
+    videoscale.link(&videorate).context("Failed to link videoscale -> videorate")?;
+    videorate.link(&capsfilter).context("Failed to link videorate -> capsfilter")?;
+    match watermark {
+        Some(watermark) => {
+            let overlay = make_watermark(watermark)?;
+            pipeline.add(&overlay)?;
+            capsfilter.link(&overlay).context("Failed to link capsfilter -> watermark")?;
+            overlay.link(&encode_queue).context("Failed to link watermark -> encode queue")?;
+        }
+        None => capsfilter.link(&encode_queue).context("Failed to link capsfilter -> encode queue")?,
+    }
+    encode_queue.link(&vp8enc).context("Failed to link encode queue -> vp8enc")?;
+    Ok(vp8enc)
+}
+
+/// An encoding chain from a rendered [`PipelineTemplate`]. The watermark, when present, is drawn
+/// on the captured frames before the template sees them.
+fn link_template_chain(
+    pipeline: &gst::Pipeline,
+    source: &gst::Element,
+    description: &str,
+    watermark: Option<&Watermark>,
+) -> Result<gst::Element> {
+    let chain = gst::parse::bin_from_description(description, true)
+        .context("Failed to build pipeline template")?
+        .upcast::<gst::Element>();
+    pipeline.add(&chain)?;
+    match watermark {
+        Some(watermark) => {
+            let videoconvert = gst::ElementFactory::make("videoconvert")
+                .build()
+                .context("Failed to create videoconvert")?;
+            let overlay = make_watermark(watermark)?;
+            pipeline.add_many([&videoconvert, &overlay])?;
+            source.link(&videoconvert).context("Failed to link ximagesrc -> videoconvert")?;
+            videoconvert.link(&overlay).context("Failed to link videoconvert -> watermark")?;
+            overlay.link(&chain).context("Failed to link watermark -> pipeline template")?;
+        }
+        None => source.link(&chain).context("Failed to link ximagesrc -> pipeline template")?,
+    }
+    Ok(chain)
+}
+
+/// Diff the raw frames entering the encoder. While the screen is static the keyframe interval
+/// is stretched, since unchanged frames cost next to nothing as deltas; when it comes back,
+/// the normal interval returns and a redraw of most of the screen is sent as a keyframe.
+/// Encoders without `keyframe-max-dist` only get the keyframes.
+fn watch_screen_activity(pipeline: &gst::Pipeline, session_id: &str) -> Result<()> {
+    let encoder = pipeline
+        .by_name("encoder")
+        .ok_or_else(|| anyhow::anyhow!("encoder not found in pipeline"))?;
+    let sink_pad = encoder
+        .static_pad("sink")
+        .ok_or_else(|| anyhow::anyhow!("encoder has no sink pad"))?;
+    let has_keyframe_dist = encoder.find_property("keyframe-max-dist").is_some();
+    if has_keyframe_dist {
+        encoder.set_property("keyframe-max-dist", NORMAL_KEYFRAME_DIST);
+    }
+
+    // The probe lives on the encoder's own pad, so it must not keep the encoder alive
+    let encoder = encoder.downgrade();
+    let session_id = session_id.to_string();
+    let mut activity = ScreenActivity::new(STATIC_AFTER_FRAMES);
+    sink_pad.add_probe(gst::PadProbeType::BUFFER, move |_, info| {
+        let (Some(buffer), Some(encoder)) = (info.buffer(), encoder.upgrade()) else {
+            return gst::PadProbeReturn::Ok;
+        };
+        let Ok(map) = buffer.map_readable() else {
+            return gst::PadProbeReturn::Ok;
+        };
+        match activity.observe(&map) {
+            Some(ActivityChange::BecameStatic) => {
+                debug!("[session {}] Screen static, stretching keyframe interval", session_id);
+                if has_keyframe_dist {
+                    encoder.set_property("keyframe-max-dist", STATIC_KEYFRAME_DIST);
+                }
+            }
+            Some(ActivityChange::Resumed { damaged }) => {
+                debug!("[session {}] Screen active again, {:.0}% redrawn", session_id, damaged * 100.0);
+                if has_keyframe_dist {
+                    encoder.set_property("keyframe-max-dist", NORMAL_KEYFRAME_DIST);
+                }
+                if damaged >= FULL_REDRAW {
+                    if let Some(src) = encoder.static_pad("src") {
+                        src.send_event(force_keyframe());
+                    }
+                }
+            }
+            None => {}
+        }
+        gst::PadProbeReturn::Ok
+    });
+    Ok(())
+}
+
+/// A leaky queue feeding an appsink, named `{branch}-queue` and `{branch}-sink`, added to a
+/// running pipeline but not yet linked to the tee.
+fn add_branch_sink(pipeline: &gst::Pipeline, branch: &str) -> Result<gst::Element> {
+    let queue = gst::ElementFactory::make("queue")
+        .name(format!("{}-queue", branch))
+        .property_from_str("leaky", "downstream")
+        .build()
+        .context("Failed to create branch queue")?;
+    let appsink = make_appsink(&format!("{}-sink", branch))?;
+
+    pipeline.add_many([&queue, &appsink])?;
+    queue.link(&appsink).context("Failed to link branch queue -> appsink")?;
+    queue.sync_state_with_parent()?;
+    appsink.sync_state_with_parent()?;
+    Ok(appsink)
+}
+
+/// Start feeding a branch from the tee, with a keyframe so it can decode right away.
+fn link_branch_to_tee(pipeline: &gst::Pipeline, branch: &str) -> Result<()> {
+    let tee = pipeline
+        .by_name("tee")
+        .ok_or_else(|| anyhow::anyhow!("tee not found in pipeline"))?;
+    let encoder = pipeline
+        .by_name("encoder")
+        .ok_or_else(|| anyhow::anyhow!("encoder not found in pipeline"))?;
+    let queue = pipeline
+        .by_name(&format!("{}-queue", branch))
+        .ok_or_else(|| anyhow::anyhow!("branch queue not found in pipeline"))?;
+
+    let tee_pad = tee
+        .request_pad_simple("src_%u")
+        .ok_or_else(|| anyhow::anyhow!("Failed to request tee pad"))?;
+    let queue_pad = queue
+        .static_pad("sink")
+        .ok_or_else(|| anyhow::anyhow!("branch queue has no sink pad"))?;
+    tee_pad.link(&queue_pad).context("Failed to link tee -> branch queue")?;
+
+    if let Some(pad) = encoder.static_pad("src") {
+        pad.send_event(force_keyframe());
+    }
+    Ok(())
+}
+
+fn make_appsink(name: &str) -> Result<gst::Element> {
+    gst::ElementFactory::make("appsink")
+        .name(name)
+        .property("sync", false)
+        .property("emit-signals", true)
+        .property_from_str("max-buffers", "100")
+        .property("drop", true)
+        .build()
+        .context("Failed to create appsink")
+}
+
+/// Forward each encoded buffer reaching `appsink` to the returned channel. Frames are not
+/// copied: each [`Bytes`] keeps its GStreamer buffer mapped until the last reader drops it.
+/// When the consumer lags [`FRAME_BACKLOG`] frames behind, delta frames are dropped and a
+/// keyframe is requested, so the stream resumes cleanly from a fresh picture.
+fn frame_receiver(appsink: gst::Element) -> Result<std::sync::mpsc::Receiver<Bytes>> {
+    let appsink_el = appsink
+        .downcast::<AppSink>()
+        .map_err(|_| anyhow::anyhow!("Failed to downcast to AppSink"))?;
+
+    let (tx, rx) = std::sync::mpsc::sync_channel::<Bytes>(FRAME_BACKLOG);
+    let mut dropping = false;
+
+    appsink_el.set_callbacks(
+        gstreamer_app::AppSinkCallbacks::builder()
+            .new_sample(move |sink| {
+                let sample = match sink.pull_sample() {
+                    Ok(s) => s,
+                    Err(_) => return Err(gst::FlowError::Eos),
+                };
+                let Some(buffer) = sample.buffer_owned() else {
+                    return Ok(gst::FlowSuccess::Ok);
+                };
+                let keyframe = !buffer.flags().contains(gst::BufferFlags::DELTA_UNIT);
+                // A delta frame is useless to the decoder once its predecessor was dropped
+                if dropping && !keyframe {
+                    return Ok(gst::FlowSuccess::Ok);
+                }
+                if let Ok(mapped) = buffer.into_mapped_buffer_readable() {
+                    match tx.try_send(Bytes::from_owner(mapped)) {
+                        Ok(()) => dropping = false,
+                        Err(TrySendError::Full(_)) => {
+                            if !dropping || keyframe {
+                                sink.send_event(force_keyframe());
+                            }
+                            dropping = true;
+                        }
+                        Err(TrySendError::Disconnected(_)) => {}
+                    }
+                }
+                Ok(gst::FlowSuccess::Ok)
+            })
+            .build(),
+    );
+
+    Ok(rx)
+}
+
+/// Semi-transparent text across the middle of the frame, where a capture cannot crop it out.
+/// Each frame is stamped as it reaches the overlay, and its stamp drawn under the text.
+fn make_watermark(watermark: &Watermark) -> Result<gst::Element> {
+    let overlay = gst::ElementFactory::make("textoverlay")
+        .name("watermark")
+        .property("text", &watermark.text)
+        .property_from_str("valignment", "center")
+        .property_from_str("halignment", "center")
+        .property("font-desc", "Sans 18")
+        .property("shaded-background", false)
+        .property("color", 0x60ff_ffffu32)
+        .property("outline-color", 0x6000_0000u32)
+        .build()
+        .context("Failed to create textoverlay")?;
+    let sink_pad = overlay.static_pad("video_sink").context("textoverlay has no video sink pad")?;
+    let (text, clock, weak_overlay) = (watermark.text.clone(), Arc::clone(&watermark.clock), overlay.downgrade());
+    sink_pad.add_probe(gst::PadProbeType::BUFFER, move |_, info| {
+        let (Some(buffer), Some(overlay)) = (info.buffer(), weak_overlay.upgrade()) else {
+            return gst::PadProbeReturn::Ok;
+        };
+        let stamp = clock.stamp(buffer.pts().map(|pts| pts.nseconds()));
+        overlay.set_property("text", format!("{}\n{}", text, stamp.label()));
+        gst::PadProbeReturn::Ok
+    });
+    Ok(overlay)
+}
+
+/// `caps` resized and re-timed for `quality`; other fields, such as the format, are kept.
+fn with_quality(mut caps: gst::Caps, width: u16, height: u16, quality: &StreamQuality) -> gst::Caps {
+    let (out_width, out_height) = quality.scaled_size(width, height);
+    for structure in caps.make_mut().iter_mut() {
+        structure.set("width", out_width as i32);
+        structure.set("height", out_height as i32);
+        structure.set("framerate", gst::Fraction::new(quality.framerate as i32, 1));
+    }
+    caps
+}
+
+fn quality_caps(width: u16, height: u16, quality: &StreamQuality) -> gst::Caps {
+    let (out_width, out_height) = quality.scaled_size(width, height);
+    gst::Caps::builder("video/x-raw")
+        .field("format", "I420")
+        .field("width", out_width as i32)
+        .field("height", out_height as i32)
+        .field("framerate", gst::Fraction::new(quality.framerate as i32, 1))
+        .build()
 }
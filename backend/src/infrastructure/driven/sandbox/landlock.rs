@@ -1,3 +1,4 @@
+use std::path::{Path, PathBuf};
 use landlock::{
     Access, AccessFs, PathBeneath, PathFd, Ruleset, RulesetAttr, RulesetCreatedAttr, ABI,
 };
@@ -6,9 +7,16 @@ use landlock::{
 ///
 /// - `root_path`: owner's storage root (read/write/delete access)
 /// - `allowed_paths`: client-specific allowed paths (overrides root_path when non-empty)
+/// - `read_only_paths`: extra read-only paths (e.g. a fonts directory outside `/usr`)
+/// - `held_paths`: paths under legal hold inside the data paths, left read-only (see [`data_rules`])
 ///
 /// Also grants read-only access to system paths required for the app to run.
-pub fn apply_landlock(root_path: &str, allowed_paths: &[String]) -> std::io::Result<()> {
+pub fn apply_landlock(
+    root_path: &str,
+    allowed_paths: &[String],
+    read_only_paths: &[String],
+    held_paths: &[String],
+) -> std::io::Result<()> {
     if root_path.is_empty() && allowed_paths.is_empty() {
         return Ok(());
     }
@@ -28,7 +36,7 @@ pub fn apply_landlock(root_path: &str, allowed_paths: &[String]) -> std::io::Res
 
     // System paths: read/execute only
     let system_dirs = ["/usr", "/lib", "/lib64", "/lib32", "/etc/fonts", "/proc/self", "/dev"];
-    for dir in &system_dirs {
+    for dir in system_dirs.iter().copied().chain(read_only_paths.iter().map(|s| s.as_str())) {
         if std::path::Path::new(dir).exists() {
             if let Ok(fd) = PathFd::new(dir) {
                 ruleset = ruleset
@@ -48,20 +56,19 @@ pub fn apply_landlock(root_path: &str, allowed_paths: &[String]) -> std::io::Res
         }
     }
 
-    // User data paths: full access
-    let data_paths: Vec<&str> = if !allowed_paths.is_empty() {
-        allowed_paths.iter().map(|s| s.as_str()).collect()
+    // User data paths: full access, except what is under legal hold
+    let data_paths: Vec<String> = if !allowed_paths.is_empty() {
+        allowed_paths.to_vec()
     } else {
-        vec![root_path]
+        vec![root_path.to_string()]
     };
 
-    for path in data_paths {
-        if std::path::Path::new(path).exists() {
-            if let Ok(fd) = PathFd::new(path) {
-                ruleset = ruleset
-                    .add_rule(PathBeneath::new(fd, access_all))
-                    .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, format!("Landlock add data rule: {e}")))?;
-            }
+    for (path, writable) in data_rules(&data_paths, held_paths)? {
+        if let Ok(fd) = PathFd::new(&path) {
+            let access = if writable { access_all } else { access_read };
+            ruleset = ruleset
+                .add_rule(PathBeneath::new(fd, access))
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, format!("Landlock add data rule: {e}")))?;
         }
     }
 
@@ -71,3 +78,102 @@ pub fn apply_landlock(root_path: &str, allowed_paths: &[String]) -> std::io::Res
 
     Ok(())
 }
+
+/// The rules giving access to `data_paths` while leaving `held_paths` read-only, as pairs of
+/// path and whether it is writable. Landlock rules only ever add access, so a held path
+/// cannot be narrowed inside a writable folder: the folders leading to it become read-only
+/// themselves, and each of their other entries gets its own writable rule. Entries created
+/// beside a held path after launch cannot be made; the rest of the vault is unaffected.
+pub fn data_rules(data_paths: &[String], held_paths: &[String]) -> std::io::Result<Vec<(PathBuf, bool)>> {
+    let held: Vec<&Path> = held_paths.iter().map(Path::new).collect();
+    let mut rules = Vec::new();
+    for path in data_paths {
+        let path = Path::new(path);
+        if path.exists() {
+            carve(path, &held, &mut rules)?;
+        }
+    }
+    Ok(rules)
+}
+
+fn carve(path: &Path, held: &[&Path], rules: &mut Vec<(PathBuf, bool)>) -> std::io::Result<()> {
+    if held.iter().any(|hold| path.starts_with(hold)) {
+        rules.push((path.to_path_buf(), false));
+    } else if held.iter().any(|hold| hold.starts_with(path)) && path.is_dir() {
+        rules.push((path.to_path_buf(), false));
+        for entry in std::fs::read_dir(path)? {
+            carve(&entry?.path(), held, rules)?;
+        }
+    } else {
+        rules.push((path.to_path_buf(), true));
+    }
+    Ok(())
+}
+
+/// Restrict the backend process itself (and everything it spawns) to the paths it needs.
+///
+/// - `read_write_paths`: storage, sockets, scratch space (full access)
+/// - `read_only_paths`: binaries, libraries, configuration (read/execute)
+///
+/// Paths that do not exist are skipped. Returns whether the kernel enforced the ruleset.
+pub fn apply_backend_landlock(read_write_paths: &[String], read_only_paths: &[String]) -> std::io::Result<bool> {
+    let abi = ABI::V3;
+    let access_all = AccessFs::from_all(abi);
+    let access_read = AccessFs::ReadFile | AccessFs::ReadDir | AccessFs::Execute;
+
+    let mut ruleset = Ruleset::default()
+        .handle_access(access_all)
+        .map_err(|e| std::io::Error::other(format!("Landlock handle_access: {e}")))?
+        .create()
+        .map_err(|e| std::io::Error::other(format!("Landlock create: {e}")))?;
+
+    let rules = read_only_paths
+        .iter()
+        .map(|p| (p, access_read))
+        .chain(read_write_paths.iter().map(|p| (p, access_all)));
+    for (path, access) in rules {
+        if !std::path::Path::new(path).exists() {
+            continue;
+        }
+        if let Ok(fd) = PathFd::new(path) {
+            ruleset = ruleset
+                .add_rule(PathBeneath::new(fd, access))
+                .map_err(|e| std::io::Error::other(format!("Landlock add rule for {path}: {e}")))?;
+        }
+    }
+
+    let status = ruleset
+        .restrict_self()
+        .map_err(|e| std::io::Error::other(format!("Landlock restrict_self: {e}")))?;
+
+    Ok(status.ruleset != landlock::RulesetStatus::NotEnforced)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_data_rules_leave_held_paths_read_only() {
+        let root = std::env::temp_dir().join(format!("landlock-rules-{}", std::process::id()));
+        for dir in ["docs/taxes", "docs/letters", "photos"] {
+            std::fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        std::fs::write(root.join("docs/notes.txt"), b"").unwrap();
+        let data = vec![root.display().to_string()];
+
+        let mut rules = data_rules(&data, &[root.join("docs/taxes").display().to_string()]).unwrap();
+        rules.sort();
+        let expected = [("", false), ("docs", false), ("docs/letters", true), ("docs/notes.txt", true), ("docs/taxes", false), ("photos", true)];
+        let expected: Vec<(PathBuf, bool)> = expected
+            .iter()
+            .map(|(path, writable)| (if path.is_empty() { root.clone() } else { root.join(path) }, *writable))
+            .collect();
+        assert_eq!(rules, expected);
+
+        // Without holds, or with one elsewhere, the data path keeps a single writable rule
+        assert_eq!(data_rules(&data, &[]).unwrap(), [(root.clone(), true)]);
+        assert_eq!(data_rules(&data, &["/elsewhere/taxes".to_string()]).unwrap(), [(root.clone(), true)]);
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
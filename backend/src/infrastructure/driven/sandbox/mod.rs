@@ -4,6 +4,25 @@ pub use xvfb::XvfbManager;
 pub mod gstreamer;
 pub use gstreamer::GStreamerManager;
 
+pub mod session_pool;
+pub use session_pool::SessionPool;
+
+pub mod pipeline_template;
+pub use pipeline_template::PipelineTemplates;
+
+pub mod codec_support;
+pub use codec_support::CodecSupport;
+
+pub mod debug_dump;
+pub mod screen_activity;
+pub mod frame_clock;
+
 pub mod landlock;
 pub mod seccomp;
 pub mod cgroups;
+pub mod container;
+pub mod app_env;
+pub mod shortcuts;
+pub mod desktop_app;
+pub mod randr;
+pub mod window_manager;
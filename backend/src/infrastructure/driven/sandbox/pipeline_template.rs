@@ -0,0 +1,191 @@
+use anyhow::{Context, Result};
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+use tracing::warn;
+
+use crate::domain::aggregates::application_session::{StreamQuality, VideoCodec};
+use crate::infrastructure::driven::config::Config;
+
+/// Codec of the WebRTC video track; templates for other codecs cannot be streamed yet
+pub const STREAM_CODEC: VideoCodec = VideoCodec::VP8;
+
+const PLACEHOLDERS: [&str; 5] = ["width", "height", "fps", "bitrate", "bitrate_kbps"];
+
+/// An encoding chain in `gst-launch` syntax that turns raw captured frames into encoded ones.
+/// It must contain a capsfilter named `caps` and an encoder named `encoder`, which live quality
+/// changes reconfigure; a queue named `encode-queue` enables frame dropping in low-latency mode.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PipelineTemplate {
+    pub codec: VideoCodec,
+    pub pipeline: String,
+}
+
+impl PipelineTemplate {
+    /// Fill in `{width}`, `{height}`, `{fps}`, `{bitrate}` (bps) and `{bitrate_kbps}`.
+    pub fn render(&self, width: u16, height: u16, quality: &StreamQuality) -> String {
+        let (width, height) = quality.scaled_size(width, height);
+        self.pipeline
+            .replace("{width}", &width.to_string())
+            .replace("{height}", &height.to_string())
+            .replace("{fps}", &quality.framerate.to_string())
+            .replace("{bitrate_kbps}", &(quality.max_bitrate / 1000).to_string())
+            .replace("{bitrate}", &quality.max_bitrate.to_string())
+    }
+
+    fn check_placeholders(&self) -> Result<()> {
+        let mut rest = self.pipeline.as_str();
+        while let Some(start) = rest.find('{') {
+            let end = rest[start..]
+                .find('}')
+                .ok_or_else(|| anyhow::anyhow!("Unclosed placeholder"))?;
+            let name = &rest[start + 1..start + end];
+            if !PLACEHOLDERS.contains(&name) {
+                anyhow::bail!("Unknown placeholder {{{}}}, expected one of {:?}", name, PLACEHOLDERS);
+            }
+            rest = &rest[start + end + 1..];
+        }
+        Ok(())
+    }
+
+    /// Build the rendered chain once to catch syntax errors, missing plugins and missing names.
+    fn check_elements(&self) -> Result<()> {
+        let description = self.render(1280, 720, &StreamQuality::default());
+        let bin = gst::parse::bin_from_description(&description, true).context("Cannot build pipeline")?;
+        for (name, what) in [("caps", "capsfilter"), ("encoder", "encoder")] {
+            if bin.by_name(name).is_none() {
+                anyhow::bail!("No {} named '{}'", what, name);
+            }
+        }
+        if bin.static_pad("sink").is_none() || bin.static_pad("src").is_none() {
+            anyhow::bail!("The chain must have exactly one unlinked input and output");
+        }
+        Ok(())
+    }
+}
+
+/// Encoding pipelines configured in the JSON file named by `PIPELINE_TEMPLATES`.
+/// Apps pick one with `pipeline_template` in their manifest; otherwise the codec's default
+/// template is used, and without one the built-in pipeline.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PipelineTemplates {
+    #[serde(default)]
+    pub templates: HashMap<String, PipelineTemplate>,
+    /// Template used for each codec when the app does not choose one
+    #[serde(default)]
+    pub defaults: HashMap<VideoCodec, String>,
+}
+
+impl PipelineTemplates {
+    /// Load and validate the templates in the file named by `PIPELINE_TEMPLATES`; no file
+    /// configured means built-in only.
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let Some(path) = config.get("PIPELINE_TEMPLATES") else {
+            return Ok(Self::default());
+        };
+        let json = std::fs::read_to_string(&path).with_context(|| format!("Cannot read {}", path))?;
+        let templates: Self = serde_json::from_str(&json).with_context(|| format!("Invalid JSON in {}", path))?;
+        templates.validate()?;
+        Ok(templates)
+    }
+
+    fn validate(&self) -> Result<()> {
+        self.check_references()?;
+        for (name, template) in &self.templates {
+            template
+                .check_elements()
+                .with_context(|| format!("Pipeline template '{}'", name))?;
+        }
+        Ok(())
+    }
+
+    /// Everything that can be checked without GStreamer
+    fn check_references(&self) -> Result<()> {
+        for (name, template) in &self.templates {
+            if template.codec != STREAM_CODEC {
+                anyhow::bail!(
+                    "Pipeline template '{}': codec {:?} is not supported, streams use {:?}",
+                    name,
+                    template.codec,
+                    STREAM_CODEC
+                );
+            }
+            template
+                .check_placeholders()
+                .with_context(|| format!("Pipeline template '{}'", name))?;
+        }
+        for (codec, name) in &self.defaults {
+            match self.templates.get(name) {
+                Some(template) if template.codec == *codec => {}
+                Some(_) => anyhow::bail!("Default template '{}' does not produce {:?}", name, codec),
+                None => anyhow::bail!("Default template '{}' for {:?} is not defined", name, codec),
+            }
+        }
+        Ok(())
+    }
+
+    /// The template for an app, given the name from its manifest. Unknown names or a codec
+    /// mismatch fall back to the codec default; `None` means the built-in pipeline.
+    pub fn select(&self, requested: Option<&str>, codec: VideoCodec) -> Option<&PipelineTemplate> {
+        if let Some(name) = requested {
+            match self.templates.get(name) {
+                Some(template) if template.codec == codec => return Some(template),
+                Some(_) => warn!("Pipeline template '{}' does not produce {:?}, using the default", name, codec),
+                None => warn!("Pipeline template '{}' is not configured, using the default", name),
+            }
+        }
+        self.defaults.get(&codec).and_then(|name| self.templates.get(name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(pipeline: &str) -> PipelineTemplate {
+        PipelineTemplate { codec: VideoCodec::VP8, pipeline: pipeline.to_string() }
+    }
+
+    fn templates(json: &str) -> PipelineTemplates {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_render_fills_placeholders() {
+        let quality = StreamQuality { framerate: 24, max_bitrate: 800_000, resolution_scale: 0.5 };
+        let rendered = template("w={width},h={height},f={fps}/1 ! enc bitrate={bitrate_kbps} target={bitrate}")
+            .render(1280, 720, &quality);
+        assert_eq!(rendered, "w=640,h=360,f=24/1 ! enc bitrate=800 target=800000");
+    }
+
+    #[test]
+    fn test_unknown_placeholders_are_rejected() {
+        assert!(template("caps=video/x-raw,width={width}").check_placeholders().is_ok());
+        assert!(template("caps=video/x-raw,width={widht}").check_placeholders().is_err());
+        assert!(template("caps=video/x-raw,width={width").check_placeholders().is_err());
+    }
+
+    #[test]
+    fn test_defaults_must_reference_known_templates() {
+        let valid = templates(r#"{"templates":{"sharp":{"codec":"VP8","pipeline":"x"}},"defaults":{"VP8":"sharp"}}"#);
+        assert!(valid.check_references().is_ok());
+
+        let missing = templates(r#"{"templates":{},"defaults":{"VP8":"sharp"}}"#);
+        assert!(missing.check_references().is_err());
+
+        let unsupported = templates(r#"{"templates":{"h264":{"codec":"H264","pipeline":"x"}}}"#);
+        assert!(unsupported.check_references().is_err());
+    }
+
+    #[test]
+    fn test_select_falls_back_to_codec_default() {
+        let configured = templates(
+            r#"{"templates":{"sharp":{"codec":"VP8","pipeline":"a"},"fast":{"codec":"VP8","pipeline":"b"}},"defaults":{"VP8":"fast"}}"#,
+        );
+        assert_eq!(configured.select(Some("sharp"), VideoCodec::VP8).unwrap().pipeline, "a");
+        assert_eq!(configured.select(Some("missing"), VideoCodec::VP8).unwrap().pipeline, "b");
+        assert_eq!(configured.select(None, VideoCodec::VP8).unwrap().pipeline, "b");
+        assert!(PipelineTemplates::default().select(Some("sharp"), VideoCodec::VP8).is_none());
+    }
+}
@@ -0,0 +1,111 @@
+//! Screen resizing through the RANDR extension. Xvfb can only shrink below the framebuffer it
+//! was started with, so displays start at `XVFB_MAX_RESOLUTION` (or the requested size if
+//! larger) and are set to the size the session wants right away; later resizes stay within
+//! that framebuffer without restarting Xvfb.
+
+use anyhow::{Context, Result};
+use x11rb::connection::Connection;
+use x11rb::protocol::randr::{ConnectionExt as RandrExt, ModeFlag, ModeInfo, Rotation};
+use x11rb::rust_connection::RustConnection;
+
+const DEFAULT_MAX_RESOLUTION: (u16, u16) = (2560, 1440);
+
+/// Screen density reported alongside the size, so apps that scale by DPI see the usual 96
+const DPI: f64 = 96.0;
+
+/// The framebuffer to start Xvfb with: large enough for `width`x`height` and for later
+/// resizes up to `XVFB_MAX_RESOLUTION`.
+pub fn framebuffer_size(width: u16, height: u16) -> (u16, u16) {
+    let (max_width, max_height) = std::env::var("XVFB_MAX_RESOLUTION")
+        .ok()
+        .and_then(|size| {
+            let (w, h) = size.trim().split_once('x')?;
+            Some((w.parse().ok()?, h.parse().ok()?))
+        })
+        .unwrap_or(DEFAULT_MAX_RESOLUTION);
+    (width.max(max_width), height.max(max_height))
+}
+
+fn millimeters(pixels: u16) -> u32 {
+    (pixels as f64 * 25.4 / DPI).round() as u32
+}
+
+/// A mode for a virtual screen; Xvfb ignores the timings, which only need to be consistent.
+fn mode_info(width: u16, height: u16, name_len: u16) -> ModeInfo {
+    let (htotal, vtotal) = (width + 160, height + 30);
+    ModeInfo {
+        id: 0,
+        width,
+        height,
+        dot_clock: htotal as u32 * vtotal as u32 * 60,
+        hsync_start: width + 48,
+        hsync_end: width + 80,
+        htotal,
+        hskew: 0,
+        vsync_start: height + 3,
+        vsync_end: height + 8,
+        vtotal,
+        name_len,
+        mode_flags: ModeFlag::HSYNC_POSITIVE | ModeFlag::VSYNC_NEGATIVE,
+    }
+}
+
+/// Make the screen `width`x`height`. The CRTC is switched off while the screen changes size,
+/// since the server refuses a screen smaller than the active mode.
+pub fn set_screen_size(conn: &RustConnection, width: u16, height: u16) -> Result<()> {
+    let root = conn.setup().roots[0].root;
+    let range = conn.randr_get_screen_size_range(root)?.reply().context("RANDR not available")?;
+    if width < range.min_width || height < range.min_height || width > range.max_width || height > range.max_height {
+        anyhow::bail!(
+            "{}x{} is outside the display's range {}x{} to {}x{}",
+            width, height, range.min_width, range.min_height, range.max_width, range.max_height
+        );
+    }
+
+    let resources = conn.randr_get_screen_resources(root)?.reply()?;
+    let output = *resources.outputs.first().context("Display has no RANDR output")?;
+    let crtc = *resources.crtcs.first().context("Display has no CRTC")?;
+    let mode = match resources.modes.iter().find(|mode| mode.width == width && mode.height == height) {
+        Some(mode) => mode.id,
+        None => {
+            let name = format!("{}x{}", width, height);
+            let mode = conn
+                .randr_create_mode(root, mode_info(width, height, name.len() as u16), name.as_bytes())?
+                .reply()
+                .context("Cannot create display mode")?
+                .mode;
+            conn.randr_add_output_mode(output, mode)?;
+            mode
+        }
+    };
+
+    conn.randr_set_crtc_config(crtc, x11rb::CURRENT_TIME, resources.config_timestamp, 0, 0, x11rb::NONE, Rotation::ROTATE0, &[])?
+        .reply()
+        .context("Cannot switch the display off")?;
+    conn.randr_set_screen_size(root, width, height, millimeters(width), millimeters(height))?
+        .check()
+        .context("Cannot resize the screen")?;
+    conn.randr_set_crtc_config(crtc, x11rb::CURRENT_TIME, resources.config_timestamp, 0, 0, mode, Rotation::ROTATE0, &[output])?
+        .reply()
+        .context("Cannot set the display mode")?;
+    conn.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reported_size_is_96_dpi() {
+        assert_eq!(millimeters(1920), 508);
+        assert_eq!(millimeters(96), 25);
+    }
+
+    #[test]
+    fn test_mode_timings_are_ordered() {
+        let mode = mode_info(1280, 720, 8);
+        assert!(mode.width < mode.hsync_start && mode.hsync_start < mode.hsync_end && mode.hsync_end < mode.htotal);
+        assert!(mode.height < mode.vsync_start && mode.vsync_start < mode.vsync_end && mode.vsync_end < mode.vtotal);
+    }
+}
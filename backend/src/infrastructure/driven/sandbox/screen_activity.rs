@@ -0,0 +1,122 @@
+//! Frame-diff tracking for the capture pipeline. Mostly-static UIs spend most of their time
+//! sending unchanged frames, so the encoder is told when the screen goes quiet (longer GOPs)
+//! and when a large part of it is redrawn at once (a fresh keyframe).
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hasher;
+
+/// Horizontal bands a frame is split into. Raw frames start with the luma plane, so bands
+/// follow screen rows closely enough to measure how much of the picture changed.
+const BANDS: usize = 32;
+
+/// Part of the screen that must change at once for the redraw to get its own keyframe
+pub const FULL_REDRAW: f32 = 0.5;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ActivityChange {
+    /// Nothing changed for the configured number of frames
+    BecameStatic,
+    /// The screen changed again after being static; `damaged` is the changed fraction (0..=1)
+    Resumed { damaged: f32 },
+}
+
+/// Tracks which parts of successive raw frames changed.
+#[derive(Debug)]
+pub struct ScreenActivity {
+    bands: Vec<u64>,
+    unchanged_frames: u32,
+    static_after: u32,
+    is_static: bool,
+}
+
+impl ScreenActivity {
+    /// The screen counts as static after `static_after` frames without any change.
+    pub fn new(static_after: u32) -> Self {
+        Self { bands: Vec::new(), unchanged_frames: 0, static_after: static_after.max(1), is_static: false }
+    }
+
+    pub fn is_static(&self) -> bool {
+        self.is_static
+    }
+
+    /// Compare `frame` with the previous one; returns a change when the screen goes static or
+    /// comes back to life.
+    pub fn observe(&mut self, frame: &[u8]) -> Option<ActivityChange> {
+        let bands = band_digests(frame);
+        let changed = if self.bands.len() == bands.len() {
+            self.bands.iter().zip(&bands).filter(|(old, new)| old != new).count()
+        } else {
+            BANDS
+        };
+        self.bands = bands;
+
+        if changed == 0 {
+            self.unchanged_frames = self.unchanged_frames.saturating_add(1);
+            if !self.is_static && self.unchanged_frames >= self.static_after {
+                self.is_static = true;
+                return Some(ActivityChange::BecameStatic);
+            }
+            return None;
+        }
+
+        self.unchanged_frames = 0;
+        if std::mem::take(&mut self.is_static) {
+            return Some(ActivityChange::Resumed { damaged: changed as f32 / BANDS as f32 });
+        }
+        None
+    }
+}
+
+fn band_digests(frame: &[u8]) -> Vec<u64> {
+    let band_len = frame.len().div_ceil(BANDS).max(1);
+    frame
+        .chunks(band_len)
+        .map(|band| {
+            let mut hasher = DefaultHasher::new();
+            hasher.write(band);
+            hasher.finish()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_static_after_unchanged_frames() {
+        let mut activity = ScreenActivity::new(3);
+        let frame = vec![7u8; 3200];
+        assert_eq!(activity.observe(&frame), None);
+        assert_eq!(activity.observe(&frame), None);
+        assert_eq!(activity.observe(&frame), None);
+        assert_eq!(activity.observe(&frame), Some(ActivityChange::BecameStatic));
+        assert!(activity.is_static());
+        assert_eq!(activity.observe(&frame), None);
+    }
+
+    #[test]
+    fn test_resume_reports_damaged_fraction() {
+        let mut activity = ScreenActivity::new(1);
+        let mut frame = vec![0u8; 3200];
+        activity.observe(&frame);
+        assert_eq!(activity.observe(&frame), Some(ActivityChange::BecameStatic));
+
+        // One byte touches a single band
+        frame[5] = 1;
+        assert_eq!(activity.observe(&frame), Some(ActivityChange::Resumed { damaged: 1.0 / 32.0 }));
+        assert!(!activity.is_static());
+
+        // Further changes while active are not reported
+        frame[3199] = 1;
+        assert_eq!(activity.observe(&frame), None);
+    }
+
+    #[test]
+    fn test_size_change_counts_as_full_redraw() {
+        let mut activity = ScreenActivity::new(1);
+        activity.observe(&[0u8; 3200]);
+        activity.observe(&[0u8; 3200]);
+        assert_eq!(activity.observe(&[0u8; 6400]), Some(ActivityChange::Resumed { damaged: 1.0 }));
+    }
+}
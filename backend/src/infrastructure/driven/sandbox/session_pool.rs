@@ -0,0 +1,119 @@
+//! Pre-warmed displays, so launching a popular app skips starting Xvfb and building the capture
+//! pipeline. `SESSION_POOL` lists the apps to keep displays for, as `app=count` or
+//! `app@WIDTHxHEIGHT=count` separated by commas; entries without a size use
+//! `SESSION_POOL_RESOLUTION`. A launch only takes a pooled display of exactly its size, since
+//! Xvfb cannot resize its screen.
+
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+
+use super::gstreamer::GStreamerManager;
+use super::xvfb::XvfbManager;
+
+const DEFAULT_RESOLUTION: (u16, u16) = (1920, 1080);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoolTarget {
+    pub app_name: String,
+    pub width: u16,
+    pub height: u16,
+    /// Displays kept waiting for this app at this size
+    pub count: usize,
+}
+
+fn parse_size(size: &str) -> Option<(u16, u16)> {
+    let (width, height) = size.trim().split_once('x')?;
+    Some((width.parse().ok()?, height.parse().ok()?))
+}
+
+/// Targets from a `SESSION_POOL` value; malformed entries are skipped with a warning.
+fn parse_targets(spec: &str, default_size: (u16, u16)) -> Vec<PoolTarget> {
+    spec.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .filter_map(|entry| {
+            let target = (|| {
+                let (app, count) = entry.split_once('=')?;
+                let (app_name, (width, height)) = match app.split_once('@') {
+                    Some((name, size)) => (name, parse_size(size)?),
+                    None => (app, default_size),
+                };
+                let app_name = app_name.trim();
+                let count = count.trim().parse().ok()?;
+                (!app_name.is_empty()).then(|| PoolTarget { app_name: app_name.to_string(), width, height, count })
+            })();
+            if target.is_none() {
+                warn!("Ignoring SESSION_POOL entry {:?}", entry);
+            }
+            target
+        })
+        .collect()
+}
+
+pub struct SessionPool {
+    targets: Vec<PoolTarget>,
+    xvfb_manager: Arc<XvfbManager>,
+    gstreamer: GStreamerManager,
+    // One refill at a time, so concurrent launches do not both start the missing displays
+    refilling: Mutex<()>,
+}
+
+impl SessionPool {
+    pub fn from_env(xvfb_manager: Arc<XvfbManager>) -> anyhow::Result<Self> {
+        let default_size = std::env::var("SESSION_POOL_RESOLUTION")
+            .ok()
+            .and_then(|size| parse_size(&size))
+            .unwrap_or(DEFAULT_RESOLUTION);
+        let targets = std::env::var("SESSION_POOL")
+            .map(|spec| parse_targets(&spec, default_size))
+            .unwrap_or_default();
+        Ok(Self { targets, xvfb_manager, gstreamer: GStreamerManager::new()?, refilling: Mutex::new(()) })
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.targets.iter().any(|target| target.count > 0)
+    }
+
+    /// Start displays until every target has its count. A display that fails to start ends
+    /// the refill of its target until the next call.
+    pub async fn refill(&self) {
+        let _guard = self.refilling.lock().await;
+        for target in &self.targets {
+            let waiting = self.xvfb_manager.warm_count(&target.app_name, target.width, target.height).await;
+            let mut started = 0;
+            for _ in waiting..target.count {
+                let result = self
+                    .xvfb_manager
+                    .warm_up(&target.app_name, target.width, target.height, &self.gstreamer)
+                    .await;
+                if let Err(e) = result {
+                    warn!("Failed to warm a display for {}: {:#}", target.app_name, e);
+                    break;
+                }
+                started += 1;
+            }
+            if started > 0 {
+                info!("Warmed {} displays for {} ({} now waiting)", started, target.app_name, waiting + started);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_targets() {
+        let targets = parse_targets("file-explorer=2, editor@2560x1440=1,,broken,bad@axb=1", (1920, 1080));
+        assert_eq!(
+            targets,
+            vec![
+                PoolTarget { app_name: "file-explorer".to_string(), width: 1920, height: 1080, count: 2 },
+                PoolTarget { app_name: "editor".to_string(), width: 2560, height: 1440, count: 1 },
+            ]
+        );
+        assert!(parse_targets("", (1920, 1080)).is_empty());
+    }
+}
@@ -0,0 +1,146 @@
+//! Keyboard shortcuts the browser captures for the app instead of acting on them itself. The
+//! client is told at session start which combinations to hold back from the browser and
+//! forward; every other combination with Ctrl, Alt or Meta, and every function key, stays with
+//! the browser and is dropped here too, so a page that ignores the policy gains nothing.
+//!
+//! Combinations are written `Ctrl+Alt+Shift+Meta+<key>`, with the modifiers in that order and
+//! the key as a capital letter, a digit or a `KeyboardEvent.key` name such as `F5` or `Tab`.
+
+use anyhow::{bail, Result};
+use serde::Deserialize;
+
+/// Combinations an app may capture. Browser and system combinations that must keep working
+/// while streaming, such as leaving fullscreen, switching tabs or locking the screen, are left
+/// out on purpose.
+pub const ALLOWED_KEYS: [&str; 27] = [
+    "Ctrl+A", "Ctrl+B", "Ctrl+C", "Ctrl+D", "Ctrl+F", "Ctrl+G", "Ctrl+H", "Ctrl+I", "Ctrl+K",
+    "Ctrl+N", "Ctrl+O", "Ctrl+P", "Ctrl+R", "Ctrl+S", "Ctrl+T", "Ctrl+U", "Ctrl+V", "Ctrl+W",
+    "Ctrl+X", "Ctrl+Y", "Ctrl+Z", "Ctrl+Shift+S", "Ctrl+Shift+Z", "F1", "F2", "F3", "F5",
+];
+
+/// Captured for apps whose manifest does not say otherwise: editing, saving and finding, which
+/// an app is expected to handle. Closing and opening tabs stay with the browser.
+const DEFAULT_KEYS: [&str; 9] = [
+    "Ctrl+A", "Ctrl+C", "Ctrl+V", "Ctrl+X", "Ctrl+Z", "Ctrl+Y", "Ctrl+S", "Ctrl+F", "Ctrl+Shift+Z",
+];
+
+/// The `shortcuts` section of an app's manifest, changing the default set
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ShortcutOverrides {
+    /// Captured on top of the default, from [`ALLOWED_KEYS`]
+    #[serde(default)]
+    pub capture: Vec<String>,
+    /// Left to the browser although in the default
+    #[serde(default)]
+    pub release: Vec<String>,
+}
+
+/// Combinations the client captures for one session
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShortcutPolicy {
+    capture: Vec<String>,
+}
+
+impl Default for ShortcutPolicy {
+    fn default() -> Self {
+        Self { capture: DEFAULT_KEYS.iter().map(|key| key.to_string()).collect() }
+    }
+}
+
+impl ShortcutPolicy {
+    /// The default set changed by an app's overrides, each of which must be allowed.
+    pub fn with_overrides(overrides: &ShortcutOverrides) -> Result<Self> {
+        let mut policy = Self::default();
+        for combo in overrides.capture.iter().chain(&overrides.release) {
+            if !ALLOWED_KEYS.contains(&combo.as_str()) {
+                bail!("Shortcut {:?} is not one an app may capture", combo);
+            }
+        }
+        policy.capture.retain(|combo| !overrides.release.contains(combo));
+        for combo in &overrides.capture {
+            if !policy.capture.contains(combo) {
+                policy.capture.push(combo.clone());
+            }
+        }
+        Ok(policy)
+    }
+
+    pub fn captured(&self) -> &[String] {
+        &self.capture
+    }
+}
+
+/// Modifiers held on one client socket, to tell typing from shortcuts
+#[derive(Debug, Default)]
+pub struct ShortcutGuard {
+    ctrl: bool,
+    alt: bool,
+    shift: bool,
+    meta: bool,
+}
+
+impl ShortcutGuard {
+    /// Note a key going down; false for a shortcut outside `policy`, which is not forwarded.
+    pub fn press(&mut self, key: &str, policy: &ShortcutPolicy) -> bool {
+        if self.set_modifier(key, true) {
+            return true;
+        }
+        let function_key = key.len() > 1 && key.starts_with('F') && key[1..].parse::<u8>().is_ok();
+        if !(self.ctrl || self.alt || self.meta || function_key) {
+            return true;
+        }
+        policy.capture.contains(&self.combo(key))
+    }
+
+    /// Note a key going up; releases are always forwarded.
+    pub fn release(&mut self, key: &str) {
+        self.set_modifier(key, false);
+    }
+
+    fn set_modifier(&mut self, key: &str, held: bool) -> bool {
+        let modifier = match key {
+            "Control" => &mut self.ctrl,
+            "Alt" => &mut self.alt,
+            "Shift" => &mut self.shift,
+            "Meta" => &mut self.meta,
+            _ => return false,
+        };
+        *modifier = held;
+        true
+    }
+
+    fn combo(&self, key: &str) -> String {
+        let modifiers = [(self.ctrl, "Ctrl+"), (self.alt, "Alt+"), (self.shift, "Shift+"), (self.meta, "Meta+")];
+        let mut combo: String = modifiers.iter().filter(|(held, _)| *held).map(|(_, name)| *name).collect();
+        if key.chars().count() == 1 {
+            combo.extend(key.chars().flat_map(char::to_uppercase));
+        } else {
+            combo.push_str(key);
+        }
+        combo
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_captures_only_allowed_combos() {
+        let overrides = ShortcutOverrides { capture: vec!["Ctrl+W".to_string()], release: vec!["Ctrl+F".to_string()] };
+        let policy = ShortcutPolicy::with_overrides(&overrides).unwrap();
+        let mut guard = ShortcutGuard::default();
+        assert!(guard.press("a", &policy));
+        assert!(guard.press("Control", &policy));
+        assert!(guard.press("w", &policy));
+        assert!(!guard.press("f", &policy));
+        assert!(!guard.press("l", &policy));
+        guard.release("Control");
+        assert!(guard.press("f", &policy));
+        assert!(!guard.press("F11", &policy));
+
+        let escape = ShortcutOverrides { capture: vec!["Ctrl+Tab".to_string()], release: Vec::new() };
+        assert!(ShortcutPolicy::with_overrides(&escape).is_err());
+    }
+}
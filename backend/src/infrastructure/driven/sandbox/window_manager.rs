@@ -0,0 +1,366 @@
+//! A minimal window manager for session displays. Bare Xvfb has none, so windows open wherever
+//! the app asks, possibly off-screen, and nothing moves the keyboard focus to a new dialog.
+//! This one maximizes the app's main window, centers dialogs, keeps other windows on the
+//! screen, focuses windows as they appear or are clicked, and lets the client list and focus
+//! the windows of multi-window apps.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use tracing::{debug, info, warn};
+use x11rb::connection::Connection;
+use x11rb::protocol::xproto::{
+    Allow, AtomEnum, ButtonIndex, ChangeWindowAttributesAux, ConfigWindow, ConfigureNotifyEvent,
+    ConfigureWindowAux, ConnectionExt, EventMask, GrabMode, InputFocus, ModMask, StackMode, Window,
+    CONFIGURE_NOTIFY_EVENT,
+};
+use x11rb::protocol::Event;
+use x11rb::rust_connection::RustConnection;
+
+/// A top-level window as the client sees it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WindowInfo {
+    pub id: u32,
+    pub title: String,
+    /// A dialog belonging to another window
+    pub transient: bool,
+    pub focused: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Rect {
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+}
+
+/// How a window is laid out on the screen, decided when it is first mapped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Placement {
+    /// Covers the whole screen
+    Fill,
+    /// Centered, shrunk to fit
+    Center,
+    /// Where the app put it, shrunk and moved just enough to be fully visible
+    Keep,
+}
+
+/// Where a window asking for `requested` goes on a `width`×`height` screen.
+fn place(requested: Rect, placement: Placement, (width, height): (u16, u16)) -> Rect {
+    let (screen_w, screen_h) = (width as u32, height as u32);
+    if placement == Placement::Fill {
+        return Rect { x: 0, y: 0, width: screen_w, height: screen_h };
+    }
+    let w = requested.width.clamp(1, screen_w);
+    let h = requested.height.clamp(1, screen_h);
+    let (x, y) = match placement {
+        Placement::Center => (((screen_w - w) / 2) as i32, ((screen_h - h) / 2) as i32),
+        _ => (
+            requested.x.clamp(0, (screen_w - w) as i32),
+            requested.y.clamp(0, (screen_h - h) as i32),
+        ),
+    };
+    Rect { x, y, width: w, height: h }
+}
+
+struct Managed {
+    window: Window,
+    placement: Placement,
+    transient: bool,
+}
+
+#[derive(Default)]
+struct WmState {
+    /// Mapped top-level windows, bottom of the stack first
+    windows: Vec<Managed>,
+    focused: Option<Window>,
+    /// Fill every top-level window, for desktop apps that expect a maximized layout
+    maximize_all: bool,
+    /// Size of the display, which changes when the session is resized
+    screen: (u16, u16),
+}
+
+impl WmState {
+    fn get(&self, window: Window) -> Option<&Managed> {
+        self.windows.iter().find(|managed| managed.window == window)
+    }
+
+    /// Move `window` to the top of the stack and mark it focused
+    fn raise(&mut self, window: Window) {
+        if let Some(index) = self.windows.iter().position(|managed| managed.window == window) {
+            let managed = self.windows.remove(index);
+            self.windows.push(managed);
+            self.focused = Some(window);
+        }
+    }
+}
+
+pub struct WindowManager {
+    conn: Arc<RustConnection>,
+    root: Window,
+    net_wm_name: u32,
+    state: Arc<Mutex<WmState>>,
+}
+
+impl WindowManager {
+    /// Take over window management of `display`. Runs on its own X connection and thread,
+    /// which end with the display.
+    pub fn start(display: &str, width: u16, height: u16) -> Result<Arc<Self>> {
+        let (conn, screen) = RustConnection::connect(Some(display)).context("Cannot connect to the display")?;
+        let root = conn.setup().roots[screen].root;
+        let mask = EventMask::SUBSTRUCTURE_REDIRECT | EventMask::SUBSTRUCTURE_NOTIFY;
+        conn.change_window_attributes(root, &ChangeWindowAttributesAux::new().event_mask(mask))?
+            .check()
+            .context("Another window manager runs on the display")?;
+        let net_wm_name = conn.intern_atom(false, b"_NET_WM_NAME")?.reply()?.atom;
+        conn.flush()?;
+
+        let wm = Arc::new(Self {
+            conn: Arc::new(conn),
+            root,
+            net_wm_name,
+            state: Arc::new(Mutex::new(WmState { screen: (width, height), ..WmState::default() })),
+        });
+        let events = Arc::clone(&wm);
+        let display = display.to_string();
+        let span = tracing::Span::current();
+        std::thread::spawn(move || {
+            let _entered = span.entered();
+            loop {
+                match events.conn.wait_for_event() {
+                    Ok(event) => {
+                        if let Err(e) = events.handle(event) {
+                            debug!("Window manager on {}: {}", display, e);
+                        }
+                    }
+                    Err(_) => break,
+                }
+            }
+            info!("Window manager on {} stopped", display);
+        });
+        Ok(wm)
+    }
+
+    /// Fill every top-level window instead of only the main one.
+    pub fn set_maximize_all(&self, maximize_all: bool) {
+        self.lock().maximize_all = maximize_all;
+    }
+
+    /// Follow the display to a new size, placing every window again so main windows keep
+    /// filling it and dialogs stay on it. Blocks on X round trips.
+    pub fn resize(&self, width: u16, height: u16) -> Result<()> {
+        let windows: Vec<(Window, Placement)> = {
+            let mut state = self.lock();
+            state.screen = (width, height);
+            state.windows.iter().map(|managed| (managed.window, managed.placement)).collect()
+        };
+        for (window, placement) in windows {
+            self.configure(window, place(self.geometry(window)?, placement, (width, height)))?;
+        }
+        self.conn.flush()?;
+        Ok(())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, WmState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// The managed windows, topmost first. Blocks on X round trips.
+    pub fn list(&self) -> Vec<WindowInfo> {
+        let windows: Vec<(Window, bool, bool)> = {
+            let state = self.lock();
+            state
+                .windows
+                .iter()
+                .rev()
+                .map(|managed| (managed.window, managed.transient, state.focused == Some(managed.window)))
+                .collect()
+        };
+        windows
+            .into_iter()
+            .map(|(id, transient, focused)| WindowInfo { id, title: self.title(id), transient, focused })
+            .collect()
+    }
+
+    /// Raise `window` and give it the keyboard. Only managed windows can be focused.
+    pub fn focus(&self, window: Window) -> Result<()> {
+        if self.lock().get(window).is_none() {
+            anyhow::bail!("Window {} is not a window of the session", window);
+        }
+        self.raise_and_focus(window)
+    }
+
+    fn raise_and_focus(&self, window: Window) -> Result<()> {
+        self.conn.configure_window(window, &ConfigureWindowAux::new().stack_mode(StackMode::ABOVE))?;
+        self.conn.set_input_focus(InputFocus::PARENT, window, x11rb::CURRENT_TIME)?;
+        self.conn.flush()?;
+        self.lock().raise(window);
+        Ok(())
+    }
+
+    fn title(&self, window: Window) -> String {
+        [self.net_wm_name, AtomEnum::WM_NAME.into()]
+            .into_iter()
+            .find_map(|atom| {
+                let reply = self.conn.get_property(false, window, atom, AtomEnum::ANY, 0, 1024).ok()?.reply().ok()?;
+                (!reply.value.is_empty()).then(|| String::from_utf8_lossy(&reply.value).into_owned())
+            })
+            .unwrap_or_default()
+    }
+
+    fn is_transient(&self, window: Window) -> bool {
+        self.conn
+            .get_property(false, window, AtomEnum::WM_TRANSIENT_FOR, AtomEnum::WINDOW, 0, 1)
+            .ok()
+            .and_then(|cookie| cookie.reply().ok())
+            .is_some_and(|reply| reply.value_len > 0)
+    }
+
+    fn geometry(&self, window: Window) -> Result<Rect> {
+        let geometry = self.conn.get_geometry(window)?.reply()?;
+        Ok(Rect {
+            x: geometry.x as i32,
+            y: geometry.y as i32,
+            width: geometry.width as u32,
+            height: geometry.height as u32,
+        })
+    }
+
+    fn configure(&self, window: Window, rect: Rect) -> Result<()> {
+        let aux = ConfigureWindowAux::new().x(rect.x).y(rect.y).width(rect.width).height(rect.height).border_width(0);
+        self.conn.configure_window(window, &aux)?;
+        // The app may have asked for another geometry; tell it what it got (ICCCM 4.1.5)
+        let notify = ConfigureNotifyEvent {
+            response_type: CONFIGURE_NOTIFY_EVENT,
+            sequence: 0,
+            event: window,
+            window,
+            above_sibling: x11rb::NONE,
+            x: rect.x as i16,
+            y: rect.y as i16,
+            width: rect.width as u16,
+            height: rect.height as u16,
+            border_width: 0,
+            override_redirect: false,
+        };
+        self.conn.send_event(false, window, EventMask::STRUCTURE_NOTIFY, notify)?;
+        Ok(())
+    }
+
+    fn handle(&self, event: Event) -> Result<()> {
+        match event {
+            Event::MapRequest(e) => self.manage(e.window),
+            Event::ConfigureRequest(e) => {
+                let mut rect = self.geometry(e.window)?;
+                if e.value_mask.contains(ConfigWindow::X) {
+                    rect.x = e.x as i32;
+                }
+                if e.value_mask.contains(ConfigWindow::Y) {
+                    rect.y = e.y as i32;
+                }
+                if e.value_mask.contains(ConfigWindow::WIDTH) {
+                    rect.width = e.width as u32;
+                }
+                if e.value_mask.contains(ConfigWindow::HEIGHT) {
+                    rect.height = e.height as u32;
+                }
+                let (placement, screen) = {
+                    let state = self.lock();
+                    (state.get(e.window).map_or(Placement::Keep, |managed| managed.placement), state.screen)
+                };
+                self.configure(e.window, place(rect, placement, screen))?;
+                self.conn.flush()?;
+                Ok(())
+            }
+            // Click to focus: the grab holds the click until the window is raised
+            Event::ButtonPress(e) => {
+                if self.lock().get(e.event).is_some() {
+                    self.raise_and_focus(e.event)?;
+                }
+                self.conn.allow_events(Allow::REPLAY_POINTER, x11rb::CURRENT_TIME)?;
+                self.conn.flush()?;
+                Ok(())
+            }
+            Event::UnmapNotify(e) if e.event == self.root => self.forget(e.window),
+            Event::DestroyNotify(e) if e.event == self.root => self.forget(e.window),
+            _ => Ok(()),
+        }
+    }
+
+    fn manage(&self, window: Window) -> Result<()> {
+        let transient = self.is_transient(window);
+        let (placement, screen) = {
+            let state = self.lock();
+            let has_main = state.windows.iter().any(|managed| managed.placement == Placement::Fill);
+            let placement = match (transient, state.maximize_all || !has_main) {
+                (true, _) => Placement::Center,
+                (false, true) => Placement::Fill,
+                (false, false) => Placement::Keep,
+            };
+            (placement, state.screen)
+        };
+        self.configure(window, place(self.geometry(window)?, placement, screen))?;
+        self.conn.grab_button(
+            false,
+            window,
+            EventMask::BUTTON_PRESS,
+            GrabMode::SYNC,
+            GrabMode::ASYNC,
+            x11rb::NONE,
+            x11rb::NONE,
+            ButtonIndex::ANY,
+            ModMask::ANY,
+        )?;
+        self.conn.map_window(window)?;
+        self.lock().windows.push(Managed { window, placement, transient });
+        debug!("Managing window {} ({:?})", window, placement);
+        self.raise_and_focus(window)
+    }
+
+    /// Stop managing a window that went away, handing the focus to the next one down.
+    fn forget(&self, window: Window) -> Result<()> {
+        let next = {
+            let mut state = self.lock();
+            let before = state.windows.len();
+            state.windows.retain(|managed| managed.window != window);
+            if state.windows.len() == before || state.focused != Some(window) {
+                return Ok(());
+            }
+            state.focused = None;
+            state.windows.last().map(|managed| managed.window)
+        };
+        if let Some(next) = next {
+            if let Err(e) = self.raise_and_focus(next) {
+                warn!("Cannot focus window {}: {}", next, e);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: i32, y: i32, width: u32, height: u32) -> Rect {
+        Rect { x, y, width, height }
+    }
+
+    #[test]
+    fn test_main_windows_fill_and_dialogs_center() {
+        let screen = (1280, 720);
+        assert_eq!(place(rect(10, 10, 800, 600), Placement::Fill, screen), rect(0, 0, 1280, 720));
+        assert_eq!(place(rect(5000, 5000, 400, 200), Placement::Center, screen), rect(440, 260, 400, 200));
+        // A dialog larger than the screen is shrunk to it
+        assert_eq!(place(rect(0, 0, 2000, 300), Placement::Center, screen), rect(0, 210, 1280, 300));
+    }
+
+    #[test]
+    fn test_kept_windows_are_pulled_on_screen() {
+        let screen = (1280, 720);
+        assert_eq!(place(rect(100, 50, 400, 300), Placement::Keep, screen), rect(100, 50, 400, 300));
+        assert_eq!(place(rect(1200, -40, 400, 300), Placement::Keep, screen), rect(880, 0, 400, 300));
+        assert_eq!(place(rect(-10, 600, 0, 900), Placement::Keep, screen), rect(0, 0, 1, 720));
+    }
+}
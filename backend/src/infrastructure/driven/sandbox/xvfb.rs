@@ -8,38 +8,243 @@ use std::sync::Arc;
 use std::sync::atomic::AtomicU16;
 use tokio::process::{Child, Command};
 use tokio::sync::RwLock;
-use tracing::{error, info, warn, debug};
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, warn, debug, Instrument};
 use x11rb::connection::Connection;
+use x11rb::protocol::xfixes::{ConnectionExt as XFixesExt, CursorNotifyMask};
 use x11rb::protocol::xtest::ConnectionExt as XTestExt;
 use x11rb::rust_connection::RustConnection;
 
-use super::gstreamer::GStreamerManager;
+use super::app_env::{posix_locale, AppEnv, TemplateVars};
+use super::shortcuts::{ShortcutOverrides, ShortcutPolicy};
+use super::container::{self, ContainerApp, ContainerLaunch};
+use super::debug_dump::{self, DumpBudget, DumpLimits};
+use super::desktop_app::LaunchSpec;
+use super::frame_clock::FrameClock;
+use super::gstreamer::{CaptureOptions, GStreamerManager, Watermark};
+use super::pipeline_template::{PipelineTemplate, PipelineTemplates, STREAM_CODEC};
+use super::randr;
+use super::window_manager::{WindowInfo, WindowManager};
+use crate::domain::aggregates::application_session::{AppVideoLimits, StreamQuality};
+use crate::domain::value_objects::{ResourceClass, Resources};
+use crate::infrastructure::driven::secrets;
+use shared::Locale;
+
+/// Who an app is launched for and which part of the vault it sees
+#[derive(Debug, Clone, Copy)]
+pub struct AppLaunch<'a> {
+    pub session_id: &'a str,
+    pub app_name: &'a str,
+    pub user_id: &'a str,
+    pub width: u16,
+    pub height: u16,
+    pub root_path: &'a str,
+    pub allowed_paths: &'a [String],
+    /// Paths under legal hold within what the app sees, which it may read but not change
+    pub held_paths: &'a [String],
+    /// Given to the app as `SANDBOX_IPC_TOKEN`, to prove its session when it connects
+    pub ipc_token: &'a str,
+    /// Given to the app as `LANG` and `TZ`, besides its `Init`
+    pub locale: Locale,
+    pub timezone: &'a str,
+}
+
+/// The vault paths a running app was launched with; its sandbox keeps them until it exits
+#[derive(Debug, Clone, Default)]
+pub struct LaunchedSandbox {
+    /// Its allowed folders, or the vault's root
+    pub data_paths: Vec<String>,
+    /// Paths under legal hold within them, left read-only
+    pub held_paths: Vec<String>,
+}
 
 pub struct XvfbManager {
     displays: Arc<RwLock<HashMap<String, XvfbSession>>>,
     apps_root: String,
     next_display: Arc<AtomicU16>,
+    // Replaced on config reload; sessions keep the template they started with
+    pipeline_templates: std::sync::RwLock<Arc<PipelineTemplates>>,
+    // Displays started ahead of demand, waiting for a launch of their app at their size
+    warm: Arc<RwLock<Vec<WarmDisplay>>>,
+}
+
+struct WarmDisplay {
+    app_name: String,
+    session: XvfbSession,
 }
 
 struct XvfbSession {
     display_str: String,
+    width: u16,
+    height: u16,
     process: Option<Child>,
     app_process: Option<Child>,
     x11_conn: Option<Arc<RustConnection>>,
     keysym_map: Arc<HashMap<u32, (u8, bool)>>,
     shift_keycode: u8,
     gst_pipeline: Option<gst::Pipeline>,
+    // Burned into every captured frame, for view-only sessions; its clock stamps the frames
+    watermark: Option<Watermark>,
+    // Encoding chain chosen by the app's manifest or the configured default
+    pipeline_template: Option<PipelineTemplate>,
+    // Stops the snapshot task of a running debug dump
+    debug_dump: Option<CancellationToken>,
+    // Set once the app is launched; what the session counts against the host's capacity
+    resource_class: Option<ResourceClass>,
+    // The app runs in a container, which outlives its runtime client unless removed
+    container: bool,
+    // The app reports when it is ready instead of being ready with the first frame
+    ready_signal: bool,
+    // Weight of the session's stream when streams share the encoding budget
+    stream_priority: u8,
+    // Combinations the client captures for the app rather than leaving to the browser
+    shortcuts: ShortcutPolicy,
+    // Capture pipeline built while the display waited in the pool, started by start_capture
+    prepared_capture: Option<(gst::Pipeline, std::sync::mpsc::Receiver<bytes::Bytes>)>,
+    // Places and focuses the app's windows; None if it could not take over the display
+    window_manager: Option<Arc<WindowManager>>,
+    // Set once the app is launched; what its sandbox lets it change
+    sandbox: Option<LaunchedSandbox>,
 }
 
+const DEBUG_DUMP_BRANCH: &str = "debug-dump";
+
 impl XvfbManager {
-    pub fn new(apps_root: String) -> Self {
+    pub fn new(apps_root: String, pipeline_templates: Arc<PipelineTemplates>) -> Self {
         Self {
             displays: Arc::new(RwLock::new(HashMap::new())),
             apps_root,
             next_display: Arc::new(AtomicU16::new(0)),
+            pipeline_templates: std::sync::RwLock::new(pipeline_templates),
+            warm: Arc::new(RwLock::new(Vec::new())),
         }
     }
 
+    fn pipeline_templates(&self) -> Arc<PipelineTemplates> {
+        self.pipeline_templates.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Templates for the sessions launched from now on.
+    pub fn set_pipeline_templates(&self, templates: PipelineTemplates) {
+        *self.pipeline_templates.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(templates);
+    }
+
+    fn manifest(&self, binary_name: &str) -> Option<serde_json::Value> {
+        let path = format!("{}/{}/manifest.json", self.apps_root, binary_name);
+        serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok()
+    }
+
+    /// The `pipeline_template` an app asks for in its manifest, if any.
+    fn manifest_pipeline_template(&self, binary_name: &str) -> Option<String> {
+        self.manifest(binary_name)?.get("pipeline_template")?.as_str().map(str::to_string)
+    }
+
+    /// The image to run for apps with `"runtime": "container"`; `None` for native apps.
+    fn manifest_container(&self, binary_name: &str) -> Result<Option<ContainerApp>> {
+        let Some(manifest) = self.manifest(binary_name) else {
+            return Ok(None);
+        };
+        if manifest.get("runtime").and_then(|runtime| runtime.as_str()) != Some("container") {
+            return Ok(None);
+        }
+        let section = manifest.get("container").cloned().context("Container app without a container section")?;
+        Ok(Some(serde_json::from_value(section).context("Invalid container section")?))
+    }
+
+    /// The command line of a desktop app registered with a `launch` section, if any.
+    fn manifest_launch(&self, binary_name: &str) -> Result<Option<LaunchSpec>> {
+        let Some(section) = self.manifest(binary_name).and_then(|manifest| manifest.get("launch").cloned()) else {
+            return Ok(None);
+        };
+        let spec: LaunchSpec = serde_json::from_value(section).context("Invalid launch section")?;
+        spec.validate()?;
+        Ok(Some(spec))
+    }
+
+    /// The `env` and `args` an app adds to its launch, checked but not yet filled in.
+    fn manifest_app_env(&self, binary_name: &str) -> Result<AppEnv> {
+        let Some(manifest) = self.manifest(binary_name) else {
+            return Ok(AppEnv::default());
+        };
+        let app_env: AppEnv = serde_json::from_value(manifest).context("Invalid env or args in manifest")?;
+        app_env.validate()?;
+        Ok(app_env)
+    }
+
+    /// Whether the app streams through a configured pipeline template rather than the
+    /// built-in pipeline.
+    pub fn uses_pipeline_template(&self, app_name: &str) -> bool {
+        let binary_name = app_name.replace('-', "_");
+        self.pipeline_templates()
+            .select(self.manifest_pipeline_template(&binary_name).as_deref(), STREAM_CODEC)
+            .is_some()
+    }
+
+    /// The sizes, framerates and codecs an app's manifest allows under `video`; the server
+    /// limits alone when it has no such section.
+    pub fn video_limits(&self, app_name: &str) -> Result<AppVideoLimits> {
+        let binary_name = app_name.replace('-', "_");
+        let Some(section) = self.manifest(&binary_name).and_then(|manifest| manifest.get("video").cloned()) else {
+            return Ok(AppVideoLimits::default());
+        };
+        serde_json::from_value(section).context("Invalid video section")
+    }
+
+    /// The `resource_class` an app declares in its manifest; small when missing or unknown.
+    pub fn resource_class(&self, app_name: &str) -> ResourceClass {
+        let binary_name = app_name.replace('-', "_");
+        self.manifest(&binary_name)
+            .and_then(|manifest| manifest.get("resource_class").cloned())
+            .and_then(|class| serde_json::from_value(class).ok())
+            .unwrap_or_default()
+    }
+
+    /// The shortcuts an app captures, from the default changed by its manifest's `shortcuts`
+    /// section.
+    pub fn shortcut_policy_of(&self, app_name: &str) -> Result<ShortcutPolicy> {
+        let binary_name = app_name.replace('-', "_");
+        let Some(section) = self.manifest(&binary_name).and_then(|manifest| manifest.get("shortcuts").cloned()) else {
+            return Ok(ShortcutPolicy::default());
+        };
+        let overrides: ShortcutOverrides = serde_json::from_value(section).context("Invalid shortcuts section")?;
+        ShortcutPolicy::with_overrides(&overrides)
+    }
+
+    /// The shortcuts the client of a session captures, the default until its app is launched.
+    pub async fn shortcut_policy(&self, session_id: &str) -> ShortcutPolicy {
+        self.displays.read().await.get(session_id).map(|s| s.shortcuts.clone()).unwrap_or_default()
+    }
+
+    /// Whether the session's app declared `"ready_signal": true` in its manifest.
+    pub async fn awaits_ready_signal(&self, session_id: &str) -> bool {
+        self.displays.read().await.get(session_id).is_some_and(|s| s.ready_signal)
+    }
+
+    /// The session's display size and the `stream_priority` its app declared, 1 to 10.
+    pub async fn stream_profile(&self, session_id: &str) -> Option<(u16, u16, u8)> {
+        self.displays
+            .read()
+            .await
+            .get(session_id)
+            .map(|s| (s.width, s.height, s.stream_priority))
+    }
+
+    /// What the launched sessions on this host reserve, and how many of them are heavy.
+    pub async fn reserved(&self) -> (Resources, u32) {
+        let displays = self.displays.read().await;
+        displays
+            .values()
+            .filter_map(|session| session.resource_class)
+            .fold((Resources::default(), 0), |(reserved, heavy), class| {
+                (reserved.plus(class.resources()), heavy + class.is_heavy() as u32)
+            })
+    }
+
+    /// How many sessions have a display on this host.
+    pub async fn session_count(&self) -> usize {
+        self.displays.read().await.len()
+    }
+
     fn alloc_display(&self) -> u16 {
         // Always return 100 for now; can be improved if multi-display needed
         100 + self.next_display.fetch_add(1, std::sync::atomic::Ordering::SeqCst)
@@ -47,10 +252,69 @@ impl XvfbManager {
 
 
     pub async fn start_xvfb(&self, session_id: &str, width: u16, height: u16) -> Result<(u16, String)> {
+        let (display_number, session) = self.spawn_display(session_id, width, height).await?;
+        let display_str = session.display_str.clone();
+
+        let mut displays = self.displays.write().await;
+        displays.insert(session_id.to_string(), session);
+
+        Ok((display_number, display_str))
+    }
+
+    /// Start `app_name`'s display at `width`x`height` ahead of any launch, with its capture
+    /// pipeline built and paused. The app itself cannot start early: its sandbox depends on
+    /// who launches it.
+    pub async fn warm_up(&self, app_name: &str, width: u16, height: u16, gstreamer: &GStreamerManager) -> Result<()> {
+        let label = format!("pool-{}", app_name);
+        let (_, mut session) = self.spawn_display(&label, width, height).await?;
+        let binary_name = app_name.replace('-', "_");
+        let options = CaptureOptions {
+            watermark: None,
+            template: self
+                .pipeline_templates()
+                .select(self.manifest_pipeline_template(&binary_name).as_deref(), STREAM_CODEC)
+                .cloned(),
+        };
+        match gstreamer.prepare_ximagesrc_pipeline(&label, &session.display_str, width, height, &StreamQuality::default(), &options) {
+            Ok(capture) => session.prepared_capture = Some(capture),
+            Err(e) => warn!("Capture for a pooled {} display not prepared (non-fatal): {:#}", app_name, e),
+        }
+        info!("Pooled display {} ready for {} at {}x{}", session.display_str, app_name, width, height);
+        self.warm.write().await.push(WarmDisplay { app_name: app_name.to_string(), session });
+        Ok(())
+    }
+
+    /// Displays waiting in the pool for `app_name` at `width`x`height`.
+    pub async fn warm_count(&self, app_name: &str, width: u16, height: u16) -> usize {
+        let warm = self.warm.read().await;
+        warm.iter()
+            .filter(|w| w.app_name == app_name && w.session.width == width && w.session.height == height)
+            .count()
+    }
+
+    /// Hand a pooled display of `app_name` at `width`x`height` to the session, in place of
+    /// [`Self::start_xvfb`]. `None` when the pool has none.
+    pub async fn bind_warm(&self, session_id: &str, app_name: &str, width: u16, height: u16) -> Option<(u16, String)> {
+        let session = {
+            let mut warm = self.warm.write().await;
+            let index = warm
+                .iter()
+                .position(|w| w.app_name == app_name && w.session.width == width && w.session.height == height)?;
+            warm.swap_remove(index).session
+        };
+        let display_str = session.display_str.clone();
+        let display_number = display_str.trim_start_matches(':').parse().ok()?;
+        info!("Session {} takes pooled display {}", session_id, display_str);
+        self.displays.write().await.insert(session_id.to_string(), session);
+        Some((display_number, display_str))
+    }
+
+    async fn spawn_display(&self, session_id: &str, width: u16, height: u16) -> Result<(u16, XvfbSession)> {
         let display_number = self.alloc_display();
         let display_str = format!(":{}", display_number);
-        let resolution = format!("{}x{}x24", width, height);
-
+        // Room to grow with later resizes; the screen is set to the requested size below
+        let (fb_width, fb_height) = randr::framebuffer_size(width, height);
+        let resolution = format!("{}x{}x24", fb_width, fb_height);
 
         debug!("About to spawn Xvfb process for session {} on {} ({}x{})", session_id, display_str, width, height);
         let xvfb_child = unsafe {
@@ -106,17 +370,28 @@ impl XvfbManager {
             Err(e) => warn!("Failed to run xsetroot -cursor_name blank for session {}: {}", session_id, e),
         }
 
+        let window_manager = match WindowManager::start(&display_str, width, height) {
+            Ok(wm) => Some(wm),
+            Err(e) => {
+                warn!("No window manager for session {} (non-fatal): {:#}", session_id, e);
+                None
+            }
+        };
+
         // Connect to Xvfb via x11rb and build keysym→keycode map
         let session_id_owned = session_id.to_string();
         let display_str_clone = display_str.clone();
 
-        let (conn, keysym_map, shift_keycode) =
+        let connected =
             tokio::task::spawn_blocking(move || -> Result<(Arc<RustConnection>, Arc<HashMap<u32, (u8, bool)>>, u8)> {
                 debug!("In spawn_blocking: connecting to Xvfb display {} for session {}", display_str_clone, session_id_owned);
                 let (conn, _screen_num) = RustConnection::connect(Some(&display_str_clone))
                     .context("Failed to connect to Xvfb display")?;
 
                 debug!("Connected to Xvfb display {} for session {}", display_str_clone, session_id_owned);
+                if (fb_width, fb_height) != (width, height) {
+                    randr::set_screen_size(&conn, width, height).context("Failed to set the display size")?;
+                }
 
                 let setup = conn.setup();
                 let min_kc = setup.min_keycode;
@@ -150,7 +425,15 @@ impl XvfbManager {
                 Ok((Arc::new(conn), Arc::new(keysym_map), shift_keycode))
             })
             .await
-            .context("spawn_blocking panicked")??;
+            .context("spawn_blocking panicked")?;
+        let (conn, keysym_map, shift_keycode) = match connected {
+            Ok(connected) => connected,
+            Err(e) => {
+                let mut xvfb_child = xvfb_child;
+                kill_child(&mut xvfb_child, "Xvfb").await;
+                return Err(e);
+            }
+        };
 
         debug!(
             "x11rb connected to {} for session {} (shift_keycode={})",
@@ -159,38 +442,69 @@ impl XvfbManager {
 
         let session = XvfbSession {
             display_str: display_str.clone(),
+            width,
+            height,
             process: Some(xvfb_child),
             app_process: None,
             x11_conn: Some(conn),
             keysym_map,
             shift_keycode,
             gst_pipeline: None,
+            watermark: None,
+            pipeline_template: None,
+            debug_dump: None,
+            resource_class: None,
+            container: false,
+            ready_signal: false,
+            stream_priority: 1,
+            shortcuts: ShortcutPolicy::default(),
+            prepared_capture: None,
+            window_manager,
+            sandbox: None,
         };
 
-        let mut displays = self.displays.write().await;
-        displays.insert(session_id.to_string(), session);
-
-        Ok((display_number, display_str))
+        Ok((display_number, session))
     }
 
-    pub async fn launch_app(
-        &self,
-        session_id: &str,
-        app_name: &str,
-        width: u16,
-        height: u16,
-        root_path: &str,
-        allowed_paths: &[String],
-    ) -> Result<()> {
+    pub async fn launch_app(&self, launch: AppLaunch<'_>) -> Result<()> {
+        let AppLaunch { session_id, app_name, user_id, width, height, root_path, allowed_paths, held_paths, ipc_token, locale, timezone } = launch;
         let binary_name = app_name.replace('-', "_");
         let binary_path = format!("{}/{}/{}", self.apps_root, binary_name, binary_name);
+        let resource_class = self.resource_class(app_name);
+        let container_app = self.manifest_container(&binary_name)?;
+        let launch_spec = self.manifest_launch(&binary_name)?;
+        let ready_signal = self
+            .manifest(&binary_name)
+            .and_then(|manifest| manifest.get("ready_signal")?.as_bool())
+            .unwrap_or(false);
+        let stream_priority = self
+            .manifest(&binary_name)
+            .and_then(|manifest| manifest.get("stream_priority")?.as_u64())
+            .map_or(1, |priority| priority.clamp(1, 10) as u8);
+        let shortcuts = self.shortcut_policy_of(app_name)?;
+        let app_dir = format!("{}/{}", self.apps_root, binary_name);
+        let (program, mut args) = match &launch_spec {
+            Some(spec) => (spec.program(&app_dir), spec.args().to_vec()),
+            None => (binary_path, Vec::new()),
+        };
+        // The manifest's own variables and arguments, filled in for this session
+        let vars = TemplateVars { session_id, user_id, app_dir: &app_dir, root_path, allowed_paths };
+        let mut extra = self
+            .manifest_app_env(&binary_name)?
+            .resolve(app_name, &vars, &secrets::default_provider())?;
+        // Timestamps and formats follow the user, unless the app pins its own
+        extra.set_default("LANG", posix_locale(locale));
+        extra.set_default("TZ", timezone.to_string());
+        if container_app.is_none() {
+            args.extend(extra.args.iter().cloned());
+        }
 
         debug!("launch_app: about to read display_str for session {}", session_id);
-        let display_str = {
+        let (display_str, window_manager) = {
             let displays = self.displays.read().await;
             displays
                 .get(session_id)
-                .map(|s| s.display_str.clone())
+                .map(|s| (s.display_str.clone(), s.window_manager.clone()))
                 .ok_or_else(|| anyhow::anyhow!("Session not found: {}", session_id))?
         };
         debug!("launch_app: got display_str, about to spawn app for session {}", session_id);
@@ -198,6 +512,15 @@ impl XvfbManager {
         let ipc_socket_path = std::env::var("IPC_SOCKET_PATH")
             .unwrap_or_else(|_| "/tmp/sandbox-ipc.sock".to_string());
 
+        // Optional directory of fallback fonts (CJK, emoji) the app loads at startup
+        let fonts_dir = std::env::var("SANDBOX_FONTS_DIR").ok();
+        let mut read_only_paths: Vec<String> = fonts_dir.iter().cloned().collect();
+        // Desktop apps load their own files and libraries from where they are installed
+        if let Some(spec) = &launch_spec {
+            read_only_paths.push(app_dir.clone());
+            read_only_paths.extend(spec.read_only_paths.iter().cloned());
+        }
+
         let root_path = root_path.to_string();
         let allowed_paths_owned: Vec<String> = allowed_paths.to_vec();
         let allowed_paths_str = allowed_paths_owned.join(":");
@@ -207,64 +530,109 @@ impl XvfbManager {
 
         let root_path_for_closure = root_path.clone();
         let allowed_paths_for_closure = allowed_paths_owned.clone();
+        let held_paths_for_closure = held_paths.to_vec();
 
 
-        let mut child = unsafe {
-            let mut cmd = Command::new(&binary_path);
-            cmd.env("DISPLAY", &display_str)
-                .env("IPC_SOCKET_PATH", &ipc_socket_path)
-                .env("SANDBOX_WIDTH", width.to_string())
-                .env("SANDBOX_HEIGHT", height.to_string());
-            if !root_path.is_empty() {
-                cmd.env("ROOT_PATH", &root_path);
-            }
-            if !allowed_paths_str.is_empty() {
-                cmd.env("ALLOWED_PATHS", &allowed_paths_str);
-            }
-            cmd.stdout(Stdio::piped())
-                .stderr(Stdio::piped())
-                .pre_exec(move || {
-                    // 1. New session
-                    libc::setsid();
+        // Legacy GUI apps expect each of their windows to be maximized, not only the first
+        let maximize_all = launch_spec.as_ref().is_some_and(|spec| spec.maximize);
+        if let Some(wm) = window_manager.as_ref().filter(|_| maximize_all) {
+            wm.set_maximize_all(true);
+        }
 
-                    // 2. Network namespace: no external network access
-                    if libc::unshare(libc::CLONE_NEWNET) != 0 {
-                        // non-fatal: log via errno but continue
-                        let _ = std::io::Error::last_os_error();
-                    }
+        let mut child = if let Some(app) = &container_app {
+            let display_number = display_str.trim_start_matches(':').parse().context("Invalid display")?;
+            container::spawn(
+                app,
+                &ContainerLaunch {
+                    session_id,
+                    display_number,
+                    width,
+                    height,
+                    root_path: &root_path,
+                    allowed_paths: &allowed_paths_owned,
+                    held_paths,
+                    fonts_dir: fonts_dir.as_deref(),
+                    class: resource_class,
+                    env: &extra.env,
+                    args: &extra.args,
+                },
+            )?
+        } else {
+            unsafe {
+                let mut cmd = Command::new(&program);
+                cmd.args(&args);
+                // Set first, so the sandbox's own variables win
+                if let Some(spec) = &launch_spec {
+                    cmd.envs(&spec.env);
+                }
+                cmd.envs(extra.env.iter().map(|(name, value)| (name, value)));
+                cmd.env("DISPLAY", &display_str)
+                    .env("IPC_SOCKET_PATH", &ipc_socket_path)
+                    .env("SANDBOX_SESSION_ID", session_id)
+                    .env("SANDBOX_IPC_TOKEN", ipc_token)
+                    .env("SANDBOX_WIDTH", width.to_string())
+                    .env("SANDBOX_HEIGHT", height.to_string());
+                if !root_path.is_empty() {
+                    cmd.env("ROOT_PATH", &root_path);
+                }
+                if !allowed_paths_str.is_empty() {
+                    cmd.env("ALLOWED_PATHS", &allowed_paths_str);
+                }
+                if let Some(dir) = &fonts_dir {
+                    cmd.env("SANDBOX_FONTS_DIR", dir);
+                }
+                cmd.stdout(Stdio::piped())
+                    .stderr(Stdio::piped())
+                    .pre_exec(move || {
+                        // 1. New session
+                        libc::setsid();
 
-                    // 3. Mount namespace: isolated mount view
-                    libc::unshare(libc::CLONE_NEWNS);
+                        // 2. Network namespace: no external network access
+                        if libc::unshare(libc::CLONE_NEWNET) != 0 {
+                            // non-fatal: log via errno but continue
+                            let _ = std::io::Error::last_os_error();
+                        }
 
-                    // 4. Landlock filesystem restrictions
-                    if let Err(e) = super::landlock::apply_landlock(
-                        &root_path_for_closure,
-                        &allowed_paths_for_closure,
-                    ) {
-                        // non-fatal: warn but continue (kernel may not support Landlock)
-                        let _ = e;
-                    }
+                        // 3. Mount namespace: isolated mount view
+                        libc::unshare(libc::CLONE_NEWNS);
 
-                    // 5. seccomp syscall denylist
-                    if !seccomp_prog.is_empty() {
-                        let _ = super::seccomp::apply_seccomp_filter(&seccomp_prog);
-                    }
+                        // 4. Landlock filesystem restrictions
+                        if let Err(e) = super::landlock::apply_landlock(
+                            &root_path_for_closure,
+                            &allowed_paths_for_closure,
+                            &read_only_paths,
+                            &held_paths_for_closure,
+                        ) {
+                            // An app that could not be kept off held files does not start
+                            if !held_paths_for_closure.is_empty() {
+                                return Err(e);
+                            }
+                            // non-fatal: warn but continue (kernel may not support Landlock)
+                            let _ = e;
+                        }
 
-                    Ok(())
-                });
+                        // 5. seccomp syscall denylist
+                        if !seccomp_prog.is_empty() {
+                            let _ = super::seccomp::apply_seccomp_filter(&seccomp_prog);
+                        }
 
-            match cmd.spawn() {
-                Ok(child) => child,
-                Err(e) => {
-                    error!("Failed to spawn {}: {}", binary_path, e);
-                    return Err(anyhow::anyhow!("Failed to spawn {}: {}", binary_path, e));
+                        Ok(())
+                    });
+
+                match cmd.spawn() {
+                    Ok(child) => child,
+                    Err(e) => {
+                        error!("Failed to spawn {}: {}", program, e);
+                        return Err(anyhow::anyhow!("Failed to spawn {}: {}", program, e));
+                    }
                 }
             }
         };
 
-        // 6. cgroups v2: resource limits (parent side — needs child PID)
-        if let Some(pid) = child.id() {
-            if let Err(e) = super::cgroups::setup_cgroup(session_id, pid) {
+        // 6. cgroups v2: resource limits (parent side — needs child PID); the container
+        // runtime applies them itself
+        if let (Some(pid), None) = (child.id(), &container_app) {
+            if let Err(e) = super::cgroups::setup_cgroup(session_id, pid, resource_class) {
                 warn!("cgroup setup failed for session {} (non-fatal): {}", session_id, e);
             }
         }
@@ -274,42 +642,63 @@ impl XvfbManager {
             use tokio::io::AsyncBufReadExt;
             let reader = tokio::io::BufReader::new(stdout);
             let app = app_name.to_string();
-            tokio::spawn(async move {
-                let mut lines = reader.lines();
-                loop {
-                    match lines.next_line().await {
-                        Ok(Some(line)) => {
-                            info!("App stdout [{}]: {}", app, line);
-                        },
-                        Ok(None) => break,
-                        Err(e) => { error!("App stdout [{}] read error: {}", app, e); break; }
+            tokio::spawn(
+                async move {
+                    let mut lines = reader.lines();
+                    loop {
+                        match lines.next_line().await {
+                            Ok(Some(line)) => {
+                                info!("App stdout [{}]: {}", app, line);
+                            },
+                            Ok(None) => break,
+                            Err(e) => { error!("App stdout [{}] read error: {}", app, e); break; }
+                        }
                     }
                 }
-            });
+                .in_current_span(),
+            );
         }
 
         if let Some(stderr) = child.stderr.take() {
             use tokio::io::AsyncBufReadExt;
             let reader = tokio::io::BufReader::new(stderr);
             let app = app_name.to_string();
-            tokio::spawn(async move {
-                let mut lines = reader.lines();
-                loop {
-                    match lines.next_line().await {
-                        Ok(Some(line)) => {
-                            error!("App stderr [{}]: {}", app, line);
-                        },
-                        Ok(None) => break,
-                        Err(e) => { error!("App stderr [{}] read error: {}", app, e); break; }
+            tokio::spawn(
+                async move {
+                    let mut lines = reader.lines();
+                    loop {
+                        match lines.next_line().await {
+                            Ok(Some(line)) => {
+                                error!("App stderr [{}]: {}", app, line);
+                            },
+                            Ok(None) => break,
+                            Err(e) => { error!("App stderr [{}] read error: {}", app, e); break; }
+                        }
                     }
                 }
-            });
+                .in_current_span(),
+            );
         }
 
+        let pipeline_template = self
+            .pipeline_templates()
+            .select(self.manifest_pipeline_template(&binary_name).as_deref(), STREAM_CODEC)
+            .cloned();
+
         debug!("launch_app: about to write app_process for session {}", session_id);
         let mut displays = self.displays.write().await;
         if let Some(session) = displays.get_mut(session_id) {
             session.app_process = Some(child);
+            session.pipeline_template = pipeline_template;
+            session.resource_class = Some(resource_class);
+            session.container = container_app.is_some();
+            session.ready_signal = ready_signal;
+            session.stream_priority = stream_priority;
+            session.shortcuts = shortcuts;
+            session.sandbox = Some(LaunchedSandbox {
+                data_paths: if allowed_paths_owned.is_empty() { vec![root_path.clone()] } else { allowed_paths_owned },
+                held_paths: held_paths.to_vec(),
+            });
         } else {
             warn!("Session not found when storing app_process for {}", session_id);
         }
@@ -317,22 +706,68 @@ impl XvfbManager {
         Ok(())
     }
 
+    /// Burn `text` into the frames of the session's capture, each frame with its index and
+    /// capture time. Must be set before [`Self::start_capture`].
+    pub async fn set_watermark(&self, session_id: &str, text: String) {
+        if let Some(session) = self.displays.write().await.get_mut(session_id) {
+            session.watermark = Some(Watermark { text, clock: Arc::new(FrameClock::default()) });
+        }
+    }
+
+    /// Clock stamping the frames of a watermarked session; `None` for other sessions.
+    pub async fn frame_clock(&self, session_id: &str) -> Option<Arc<FrameClock>> {
+        let displays = self.displays.read().await;
+        displays.get(session_id)?.watermark.as_ref().map(|watermark| Arc::clone(&watermark.clock))
+    }
+
     pub async fn start_capture(
         &self,
         session_id: &str,
-        framerate: u8,
+        quality: &StreamQuality,
         gstreamer: &GStreamerManager,
-    ) -> Result<std::sync::mpsc::Receiver<Vec<u8>>> {
-        let display_str = {
-            let displays = self.displays.read().await;
-            displays
-                .get(session_id)
-                .map(|s| s.display_str.clone())
-                .ok_or_else(|| anyhow::anyhow!("Session not found: {}", session_id))?
+    ) -> Result<std::sync::mpsc::Receiver<bytes::Bytes>> {
+        let (display_str, width, height, options, prepared) = {
+            let mut displays = self.displays.write().await;
+            let s = displays
+                .get_mut(session_id)
+                .ok_or_else(|| anyhow::anyhow!("Session not found: {}", session_id))?;
+            let options = CaptureOptions {
+                watermark: s.watermark.clone(),
+                template: s.pipeline_template.clone(),
+            };
+            (s.display_str.clone(), s.width, s.height, options, s.prepared_capture.take())
         };
 
-        let (pipeline, rx) =
-            gstreamer.start_ximagesrc_pipeline(session_id, &display_str, framerate)?;
+        // A pooled display's pipeline was built without a watermark and at default quality
+        let prepared = match prepared {
+            Some((pipeline, _)) if options.watermark.is_some() => {
+                let _ = pipeline.set_state(gst::State::Null);
+                None
+            }
+            Some((pipeline, rx)) => match gstreamer.apply_quality(&pipeline, width, height, quality) {
+                Ok(()) => Some((pipeline, rx)),
+                Err(e) => {
+                    warn!("Prepared capture of session {} unusable, rebuilding: {}", session_id, e);
+                    let _ = pipeline.set_state(gst::State::Null);
+                    None
+                }
+            },
+            None => None,
+        };
+        let (pipeline, rx) = match prepared {
+            Some((pipeline, rx)) => {
+                gstreamer.start_prepared(session_id, &pipeline)?;
+                (pipeline, rx)
+            }
+            None => gstreamer.start_ximagesrc_pipeline(
+                session_id,
+                &display_str,
+                width,
+                height,
+                quality,
+                &options,
+            )?,
+        };
 
         let mut displays = self.displays.write().await;
         if let Some(session) = displays.get_mut(session_id) {
@@ -342,6 +777,327 @@ impl XvfbManager {
         Ok(rx)
     }
 
+    /// Apply a new stream quality to the session's running capture pipeline.
+    pub async fn set_quality(
+        &self,
+        session_id: &str,
+        quality: &StreamQuality,
+        gstreamer: &GStreamerManager,
+    ) -> Result<()> {
+        let displays = self.displays.read().await;
+        let session = displays
+            .get(session_id)
+            .ok_or_else(|| anyhow::anyhow!("Session not found: {}", session_id))?;
+        let pipeline = session
+            .gst_pipeline
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Capture not started for session {}", session_id))?;
+        gstreamer.apply_quality(pipeline, session.width, session.height, quality)
+    }
+
+    /// Change the resolution of a running session's display, then have the window manager and
+    /// the capture pipeline follow it, without restarting Xvfb or the app.
+    pub async fn resize(
+        &self,
+        session_id: &str,
+        width: u16,
+        height: u16,
+        quality: &StreamQuality,
+        gstreamer: &GStreamerManager,
+    ) -> Result<()> {
+        let mut displays = self.displays.write().await;
+        let session = displays
+            .get_mut(session_id)
+            .ok_or_else(|| anyhow::anyhow!("Session not found: {}", session_id))?;
+        if (session.width, session.height) == (width, height) {
+            return Ok(());
+        }
+        let conn = session
+            .x11_conn
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("No X connection for session {}", session_id))?;
+        let wm = session.window_manager.clone();
+        tokio::task::spawn_blocking(move || -> Result<()> {
+            randr::set_screen_size(&conn, width, height)?;
+            if let Some(wm) = wm {
+                wm.resize(width, height)?;
+            }
+            Ok(())
+        })
+        .await
+        .context("Display resize panicked")??;
+        session.width = width;
+        session.height = height;
+        info!("Resized display of session {} to {}x{}", session_id, width, height);
+
+        match session.gst_pipeline.as_ref() {
+            Some(pipeline) => gstreamer.resize_capture(pipeline, width, height, quality),
+            None => Ok(()),
+        }
+    }
+
+    /// Switch the session's running capture pipeline in or out of low-latency mode.
+    pub async fn set_low_latency(
+        &self,
+        session_id: &str,
+        enabled: bool,
+        gstreamer: &GStreamerManager,
+    ) -> Result<()> {
+        let displays = self.displays.read().await;
+        let pipeline = displays
+            .get(session_id)
+            .and_then(|s| s.gst_pipeline.as_ref())
+            .ok_or_else(|| anyhow::anyhow!("Capture not started for session {}", session_id))?;
+        gstreamer.set_low_latency(pipeline, enabled)
+    }
+
+    pub async fn request_keyframe(&self, session_id: &str, gstreamer: &GStreamerManager) -> Result<()> {
+        let displays = self.displays.read().await;
+        let pipeline = displays
+            .get(session_id)
+            .and_then(|s| s.gst_pipeline.as_ref())
+            .ok_or_else(|| anyhow::anyhow!("Capture not started for session {}", session_id))?;
+        gstreamer.request_keyframe(pipeline)
+    }
+
+    /// Attach a read-only watcher to the session's running capture; it receives the same VP8 frames.
+    pub async fn start_watch(
+        &self,
+        session_id: &str,
+        watch_id: &str,
+        gstreamer: &GStreamerManager,
+    ) -> Result<std::sync::mpsc::Receiver<bytes::Bytes>> {
+        let displays = self.displays.read().await;
+        let pipeline = displays
+            .get(session_id)
+            .and_then(|s| s.gst_pipeline.as_ref())
+            .ok_or_else(|| anyhow::anyhow!("Session {} is not streaming", session_id))?;
+        gstreamer.add_watch_branch(pipeline, &format!("watch-{}", watch_id))
+    }
+
+    pub async fn stop_watch(&self, session_id: &str, watch_id: &str, gstreamer: &GStreamerManager) -> Result<()> {
+        let displays = self.displays.read().await;
+        match displays.get(session_id).and_then(|s| s.gst_pipeline.as_ref()) {
+            Some(pipeline) => gstreamer.remove_branch(pipeline, &format!("watch-{}", watch_id)),
+            // The session ended and took its pipeline with it
+            None => Ok(()),
+        }
+    }
+
+    /// Record the session's encoded stream and periodic display snapshots into `dir`, replacing
+    /// any earlier dump there. Runs until [`Self::stop_debug_dump`], the size cap, or the session ends.
+    pub async fn start_debug_dump(
+        &self,
+        session_id: &str,
+        dir: &std::path::Path,
+        limits: DumpLimits,
+        gstreamer: &GStreamerManager,
+    ) -> Result<()> {
+        let mut displays = self.displays.write().await;
+        let session = displays
+            .get_mut(session_id)
+            .ok_or_else(|| anyhow::anyhow!("Session not found: {}", session_id))?;
+        if session.debug_dump.is_some() {
+            anyhow::bail!("A debug dump is already running for session {}", session_id);
+        }
+        let pipeline = session
+            .gst_pipeline
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Session {} is not streaming", session_id))?;
+        let conn = session
+            .x11_conn
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("Session {} has no display connection", session_id))?;
+
+        let writer = debug_dump::prepare(dir).with_context(|| format!("Cannot create {}", dir.display()))?;
+        // Frames of a watermarked session can be matched with their stamp and logged input
+        let frame_index = match &session.watermark {
+            Some(watermark) => Some((
+                debug_dump::prepare_frame_index(dir).with_context(|| format!("Cannot create {}", dir.display()))?,
+                Arc::clone(&watermark.clock),
+            )),
+            None => None,
+        };
+        let budget = DumpBudget::new(limits.max_bytes);
+        gstreamer.add_dump_branch(pipeline, DEBUG_DUMP_BRANCH, writer, frame_index, budget.clone())?;
+
+        let cancel = CancellationToken::new();
+        session.debug_dump = Some(cancel.clone());
+        let (width, height) = (session.width, session.height);
+        info!("Debug dump started for session {} in {}", session_id, dir.display());
+        let (dir, session_id_owned) = (dir.to_path_buf(), session_id.to_string());
+        tokio::spawn(
+            async move {
+                let mut ticker = tokio::time::interval(limits.snapshot_interval);
+                let mut index = 0;
+                loop {
+                    tokio::select! {
+                        _ = cancel.cancelled() => break,
+                        _ = ticker.tick() => {}
+                    }
+                    let conn = Arc::clone(&conn);
+                    let image = tokio::task::spawn_blocking(move || -> Result<Vec<u8>> {
+                        let root = conn.setup().roots[0].root;
+                        let reply = conn
+                            .get_image(x11rb::protocol::xproto::ImageFormat::Z_PIXMAP, root, 0, 0, width, height, !0)?
+                            .reply()?;
+                        Ok(debug_dump::bgrx_to_rgba(&reply.data))
+                    })
+                    .await;
+                    let rgba = match image {
+                        Ok(Ok(rgba)) => rgba,
+                        Ok(Err(e)) => {
+                            warn!("debug dump [{}]: snapshot failed: {}", session_id_owned, e);
+                            continue;
+                        }
+                        Err(_) => break,
+                    };
+                    if !budget.take(rgba.len() as u64) {
+                        info!("debug dump [{}]: size cap reached, no more snapshots", session_id_owned);
+                        break;
+                    }
+                    if let Err(e) = debug_dump::write_snapshot(&dir, index, width, height, &rgba) {
+                        warn!("debug dump [{}]: cannot write snapshot: {}", session_id_owned, e);
+                        break;
+                    }
+                    index += 1;
+                }
+            }
+            .in_current_span(),
+        );
+        Ok(())
+    }
+
+    pub async fn is_debug_dumping(&self, session_id: &str) -> bool {
+        self.displays
+            .read()
+            .await
+            .get(session_id)
+            .is_some_and(|s| s.debug_dump.is_some())
+    }
+
+    /// The sandboxes of the apps running here, keyed by session.
+    pub async fn launched_sandboxes(&self) -> Vec<(String, LaunchedSandbox)> {
+        self.displays
+            .read()
+            .await
+            .iter()
+            .filter_map(|(id, s)| Some((id.clone(), s.sandbox.clone()?)))
+            .collect()
+    }
+
+    /// Sessions with a debug dump running
+    pub async fn debug_dumping_sessions(&self) -> Vec<String> {
+        self.displays
+            .read()
+            .await
+            .iter()
+            .filter(|(_, s)| s.debug_dump.is_some())
+            .map(|(id, _)| id.clone())
+            .collect()
+    }
+
+    /// Stop a running debug dump; the files stay on disk.
+    pub async fn stop_debug_dump(&self, session_id: &str, gstreamer: &GStreamerManager) -> Result<()> {
+        let mut displays = self.displays.write().await;
+        let Some(session) = displays.get_mut(session_id) else {
+            return Ok(());
+        };
+        if let Some(cancel) = session.debug_dump.take() {
+            cancel.cancel();
+        }
+        match &session.gst_pipeline {
+            Some(pipeline) => gstreamer.remove_branch(pipeline, DEBUG_DUMP_BRANCH),
+            None => Ok(()),
+        }
+    }
+
+    /// Watch cursor changes on the session display via XFixes and report them as
+    /// [`CursorUpdate`]s, so the client can draw the pointer itself.
+    /// The watcher thread ends when the X connection is closed.
+    pub async fn start_cursor_watch(
+        &self,
+        session_id: &str,
+    ) -> Result<tokio::sync::mpsc::UnboundedReceiver<CursorUpdate>> {
+        let conn = {
+            let displays = self.displays.read().await;
+            displays
+                .get(session_id)
+                .and_then(|s| s.x11_conn.clone())
+                .ok_or_else(|| anyhow::anyhow!("Session not found: {}", session_id))?
+        };
+
+        let root = conn.setup().roots[0].root;
+        conn.xfixes_query_version(5, 0)?
+            .reply()
+            .context("XFixes extension not available")?;
+        conn.xfixes_select_cursor_input(root, CursorNotifyMask::DISPLAY_CURSOR)?;
+        conn.flush()?;
+
+        // Unbounded so the X thread never blocks; the receiver dropping ends the thread at
+        // its next update
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel::<CursorUpdate>();
+        let session_id_owned = session_id.to_string();
+        let span = tracing::Span::current();
+        std::thread::spawn(move || {
+            let _entered = span.entered();
+            loop {
+                match conn.wait_for_event() {
+                    Ok(x11rb::protocol::Event::XfixesCursorNotify(_)) => {
+                        let reply = match conn.xfixes_get_cursor_image_and_name() {
+                            Ok(cookie) => cookie.reply(),
+                            Err(e) => Err(e.into()),
+                        };
+                        match reply {
+                            Ok(cursor) => {
+                                let name = String::from_utf8_lossy(&cursor.name);
+                                let update = CursorUpdate {
+                                    x: cursor.x,
+                                    y: cursor.y,
+                                    icon: x_cursor_to_css(&name).to_string(),
+                                };
+                                if tx.send(update).is_err() {
+                                    break;
+                                }
+                            }
+                            Err(e) => warn!("cursor watch [{}]: failed to query cursor: {}", session_id_owned, e),
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        debug!("cursor watch [{}] ended: {}", session_id_owned, e);
+                        break;
+                    }
+                }
+            }
+        });
+
+        Ok(rx)
+    }
+
+    async fn window_manager(&self, session_id: &str) -> Result<Arc<WindowManager>> {
+        self.displays
+            .read()
+            .await
+            .get(session_id)
+            .with_context(|| format!("Session not found: {}", session_id))?
+            .window_manager
+            .clone()
+            .context("The session's display has no window manager")
+    }
+
+    /// The app's top-level windows, topmost first.
+    pub async fn list_windows(&self, session_id: &str) -> Result<Vec<WindowInfo>> {
+        let wm = self.window_manager(session_id).await?;
+        tokio::task::spawn_blocking(move || wm.list()).await.context("Window listing panicked")
+    }
+
+    /// Raise one of the app's windows and give it the keyboard.
+    pub async fn focus_window(&self, session_id: &str, window: u32) -> Result<()> {
+        let wm = self.window_manager(session_id).await?;
+        tokio::task::spawn_blocking(move || wm.focus(window)).await.context("Window focus panicked")?
+    }
+
     pub async fn handle_mouse_move(&self, session_id: &str, x: i32, y: i32) {
         let conn = {
             let displays = self.displays.read().await;
@@ -420,7 +1176,14 @@ impl XvfbManager {
 
         let mut displays = self.displays.write().await;
         if let Some(mut session) = displays.remove(session_id) {
+            if let Some(cancel) = session.debug_dump.take() {
+                cancel.cancel();
+            }
+
             // Stop GStreamer pipeline
+            if let Some((pipeline, _)) = session.prepared_capture.take() {
+                let _ = pipeline.set_state(gst::State::Null);
+            }
             if let Some(pipeline) = session.gst_pipeline.take() {
                 info!("Stopping GStreamer pipeline for session {}", session_id);
                 let _ = pipeline.set_state(gst::State::Null);
@@ -430,6 +1193,10 @@ impl XvfbManager {
             info!("Dropping x11 connection for session {}", session_id);
             drop(session.x11_conn.take());
 
+            if session.container {
+                container::remove(session_id).await;
+            }
+
             // Kill app process
             if let Some(mut child) = session.app_process.take() {
                 info!("Killing app process for session {}", session_id);
@@ -449,6 +1216,39 @@ impl XvfbManager {
     }
 }
 
+/// Cursor state reported to the client for client-side pointer rendering.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CursorUpdate {
+    pub x: i16,
+    pub y: i16,
+    /// CSS cursor name (e.g. `default`, `text`, `pointer`).
+    pub icon: String,
+}
+
+/// Map X cursor names (CSS-style or legacy Xcursor names) to CSS cursor values.
+fn x_cursor_to_css(name: &str) -> &'static str {
+    match name {
+        "" | "blank" | "none" => "none",
+        "text" | "xterm" | "ibeam" => "text",
+        "pointer" | "hand" | "hand1" | "hand2" => "pointer",
+        "wait" | "watch" => "wait",
+        "progress" | "left_ptr_watch" => "progress",
+        "crosshair" | "cross" | "tcross" => "crosshair",
+        "move" | "fleur" | "all-scroll" => "move",
+        "grab" | "openhand" => "grab",
+        "grabbing" | "closedhand" => "grabbing",
+        "not-allowed" | "crossed_circle" | "forbidden" => "not-allowed",
+        "help" | "question_arrow" => "help",
+        "col-resize" | "ew-resize" | "sb_h_double_arrow" | "h_double_arrow" => "ew-resize",
+        "row-resize" | "ns-resize" | "sb_v_double_arrow" | "v_double_arrow" => "ns-resize",
+        "nwse-resize" | "size_fdiag" | "bottom_right_corner" | "top_left_corner" => "nwse-resize",
+        "nesw-resize" | "size_bdiag" | "bottom_left_corner" | "top_right_corner" => "nesw-resize",
+        "zoom-in" => "zoom-in",
+        "zoom-out" => "zoom-out",
+        _ => "default",
+    }
+}
+
 /// Map browser key names to X11 keysyms.
 fn browser_key_to_keysym(key: &str) -> Option<u32> {
     match key {
@@ -457,6 +1257,15 @@ fn browser_key_to_keysym(key: &str) -> Option<u32> {
         "Tab" => return Some(0xFF09),
         "Escape" => return Some(0xFF1B),
         "Delete" => return Some(0xFFFF),
+        "Home" => return Some(0xFF50),
+        "End" => return Some(0xFF57),
+        "PageUp" => return Some(0xFF55),
+        "PageDown" => return Some(0xFF56),
+        // Modifiers, so the shortcuts the client captures reach the app as combinations
+        "Shift" => return Some(0xFFE1),
+        "Control" => return Some(0xFFE3),
+        "Alt" => return Some(0xFFE9),
+        "Meta" => return Some(0xFFEB),
         "ArrowLeft" => return Some(0xFF51),
         "ArrowUp" => return Some(0xFF52),
         "ArrowRight" => return Some(0xFF53),
@@ -464,6 +1273,10 @@ fn browser_key_to_keysym(key: &str) -> Option<u32> {
         " " => return Some(0x0020),
         _ => {}
     }
+    // F1 to F12 follow one another from XK_F1
+    if let Some(n) = key.strip_prefix('F').and_then(|n| n.parse::<u32>().ok()).filter(|n| (1..=12).contains(n)) {
+        return Some(0xFFBE + n - 1);
+    }
     // Single printable ASCII char: keysym == Unicode codepoint
     let c = key.chars().next()?;
     if c.is_ascii() && !c.is_control() {
@@ -0,0 +1,216 @@
+// Secrets loading - environment variables and mounted secret files (docker/k8s)
+
+use jsonwebtoken::{decode, decode_header, encode, DecodingKey, EncodingKey, Header, TokenData, Validation};
+use serde::{de::DeserializeOwned, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+const DEV_JWT_SECRET: &str = "dev_secret_key_change_in_production";
+const DEV_TURN_CREDENTIAL: &str = "dev_turn_secret";
+const MIN_SECRET_LEN: usize = 32;
+
+pub trait SecretsProvider: Send + Sync {
+    fn get(&self, name: &str) -> Option<String>;
+}
+
+/// `NAME`, or the contents of the file named by `NAME_FILE`.
+pub struct EnvSecrets;
+
+impl SecretsProvider for EnvSecrets {
+    fn get(&self, name: &str) -> Option<String> {
+        if let Ok(path) = std::env::var(format!("{name}_FILE")) {
+            return read_secret_file(&PathBuf::from(path));
+        }
+        std::env::var(name).ok().filter(|v| !v.is_empty())
+    }
+}
+
+/// One file per secret in a mounted directory, e.g. `/run/secrets/jwt_secret`.
+pub struct FileSecrets {
+    dir: PathBuf,
+}
+
+impl FileSecrets {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+}
+
+impl SecretsProvider for FileSecrets {
+    fn get(&self, name: &str) -> Option<String> {
+        read_secret_file(&self.dir.join(name.to_ascii_lowercase()))
+    }
+}
+
+/// Tries each provider in order.
+pub struct ChainedSecrets(Vec<Box<dyn SecretsProvider>>);
+
+impl SecretsProvider for ChainedSecrets {
+    fn get(&self, name: &str) -> Option<String> {
+        self.0.iter().find_map(|p| p.get(name))
+    }
+}
+
+/// Environment first, then the `SECRETS_DIR` mount (default `/run/secrets`).
+pub fn default_provider() -> ChainedSecrets {
+    let dir = std::env::var("SECRETS_DIR").unwrap_or_else(|_| "/run/secrets".to_string());
+    ChainedSecrets(vec![Box::new(EnvSecrets), Box::new(FileSecrets::new(dir))])
+}
+
+fn read_secret_file(path: &PathBuf) -> Option<String> {
+    std::fs::read_to_string(path)
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// Production mode (`APP_ENV=production`) refuses to start with missing or weak secrets.
+pub fn is_production() -> bool {
+    std::env::var("APP_ENV")
+        .map(|v| v.eq_ignore_ascii_case("production") || v.eq_ignore_ascii_case("prod"))
+        .unwrap_or(false)
+}
+
+pub struct Secrets {
+    pub jwt_keys: JwtKeys,
+    pub turn_credential: String,
+}
+
+/// Load all backend secrets. In production every secret must be set and strong;
+/// otherwise dev defaults are used for anything missing.
+pub fn load(provider: &dyn SecretsProvider, production: bool) -> Result<Secrets, String> {
+    let require = |name: &str, dev_default: &str| -> Result<String, String> {
+        match provider.get(name) {
+            Some(value) if production => check_strength(name, &value, dev_default).map(|_| value),
+            Some(value) => Ok(value),
+            None if production => Err(format!("{name} must be set in production")),
+            None => Ok(dev_default.to_string()),
+        }
+    };
+
+    let jwt_secret = require("JWT_SECRET", DEV_JWT_SECRET)?;
+    let turn_credential = require("TURN_CREDENTIAL", DEV_TURN_CREDENTIAL)?;
+
+    let current_kid = provider.get("JWT_KEY_ID").unwrap_or_else(|| "default".to_string());
+    let mut jwt_keys = JwtKeys::new(current_kid, jwt_secret);
+    // Retired keys still accepted for verification: "kid1:secret1,kid2:secret2"
+    if let Some(previous) = provider.get("JWT_PREVIOUS_SECRETS") {
+        for entry in previous.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let (kid, secret) = entry
+                .split_once(':')
+                .ok_or_else(|| "JWT_PREVIOUS_SECRETS entries must be kid:secret".to_string())?;
+            // A weak retired key would still let anyone forge tokens it verifies
+            if production {
+                check_strength(&format!("JWT_PREVIOUS_SECRETS key {kid}"), secret, DEV_JWT_SECRET)?;
+            }
+            jwt_keys.add_verification_key(kid.to_string(), secret.to_string());
+        }
+    }
+
+    Ok(Secrets { jwt_keys, turn_credential })
+}
+
+fn check_strength(name: &str, value: &str, dev_default: &str) -> Result<(), String> {
+    if value == dev_default {
+        return Err(format!("{name} is set to the development default"));
+    }
+    if value.len() < MIN_SECRET_LEN {
+        return Err(format!("{name} must be at least {MIN_SECRET_LEN} characters"));
+    }
+    Ok(())
+}
+
+/// HMAC signing keys for session tokens. Tokens carry the key id (`kid`) in their
+/// header, so the signing key can be rotated while older tokens stay valid.
+pub struct JwtKeys {
+    current_kid: String,
+    keys: HashMap<String, String>,
+}
+
+impl JwtKeys {
+    pub fn new(current_kid: String, secret: String) -> Self {
+        let mut keys = HashMap::new();
+        keys.insert(current_kid.clone(), secret);
+        Self { current_kid, keys }
+    }
+
+    pub fn add_verification_key(&mut self, kid: String, secret: String) {
+        self.keys.entry(kid).or_insert(secret);
+    }
+
+    pub fn encode<T: Serialize>(&self, claims: &T) -> Result<String, jsonwebtoken::errors::Error> {
+        let header = Header {
+            kid: Some(self.current_kid.clone()),
+            ..Header::default()
+        };
+        encode(&header, claims, &EncodingKey::from_secret(self.keys[&self.current_kid].as_bytes()))
+    }
+
+    /// Key for `purpose` derived from the signing key, for secrets that must stay the same
+    /// across restarts but need no setting of their own.
+    pub fn derive(&self, purpose: &str) -> Vec<u8> {
+        Sha256::digest(format!("{purpose}:{}", self.keys[&self.current_kid]).as_bytes()).to_vec()
+    }
+
+    /// Verify with the key named by the token's `kid`; tokens without one were issued
+    /// before rotation support and are checked against the current key.
+    pub fn decode<T: DeserializeOwned>(&self, token: &str) -> Result<TokenData<T>, jsonwebtoken::errors::Error> {
+        let kid = decode_header(token)?.kid.unwrap_or_else(|| self.current_kid.clone());
+        let secret = self
+            .keys
+            .get(&kid)
+            .ok_or(jsonwebtoken::errors::ErrorKind::InvalidToken)?;
+        decode(token, &DecodingKey::from_secret(secret.as_bytes()), &Validation::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize, serde::Deserialize)]
+    struct Claims {
+        sub: String,
+        exp: usize,
+    }
+
+    fn claims() -> Claims {
+        Claims { sub: "user".to_string(), exp: (chrono::Utc::now().timestamp() + 60) as usize }
+    }
+
+    #[test]
+    fn test_rotated_key_still_verifies_old_tokens() {
+        let old = JwtKeys::new("k1".to_string(), "old-secret".to_string());
+        let token = old.encode(&claims()).unwrap();
+
+        let mut rotated = JwtKeys::new("k2".to_string(), "new-secret".to_string());
+        assert!(rotated.decode::<Claims>(&token).is_err());
+        rotated.add_verification_key("k1".to_string(), "old-secret".to_string());
+        assert_eq!(rotated.decode::<Claims>(&token).unwrap().claims.sub, "user");
+    }
+
+    #[test]
+    fn test_production_rejects_weak_secrets() {
+        struct Fixed(HashMap<&'static str, &'static str>);
+        impl SecretsProvider for Fixed {
+            fn get(&self, name: &str) -> Option<String> {
+                self.0.get(name).map(|s| s.to_string())
+            }
+        }
+
+        let weak = Fixed(HashMap::from([("JWT_SECRET", "short"), ("TURN_CREDENTIAL", DEV_TURN_CREDENTIAL)]));
+        assert!(load(&weak, true).is_err());
+        assert!(load(&weak, false).is_ok());
+        assert!(load(&Fixed(HashMap::new()), true).is_err());
+
+        let strong = "a-secret-of-at-least-thirty-two-characters";
+        let weak_previous = Fixed(HashMap::from([
+            ("JWT_SECRET", strong),
+            ("TURN_CREDENTIAL", strong),
+            ("JWT_PREVIOUS_SECRETS", "old:short"),
+        ]));
+        assert!(load(&weak_previous, true).is_err());
+        assert!(load(&weak_previous, false).is_ok());
+    }
+}
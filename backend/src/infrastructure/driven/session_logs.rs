@@ -0,0 +1,308 @@
+//! Per-session logging context. Work done for a session runs in a `session` span carrying its
+//! `session_id`, `user_id` and `app_id`, so interleaved logs from GStreamer, WebRTC, IPC and
+//! input can be told apart; [`SessionLogLayer`] also keeps each session's recent lines in
+//! memory for admins to fetch.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Write as _;
+use std::sync::{Arc, Mutex, MutexGuard};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Span, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+use crate::domain::value_objects::UserId;
+
+/// Name of the span that marks work done for a session
+const SPAN_NAME: &str = "session";
+
+/// A new `session` span. Fields not known yet can be recorded on it later.
+pub fn session_span(session_id: Option<&str>, user_id: Option<&UserId>, app_id: Option<&str>) -> Span {
+    let span = tracing::info_span!(
+        SPAN_NAME,
+        session_id = tracing::field::Empty,
+        user_id = tracing::field::Empty,
+        app_id = tracing::field::Empty,
+    );
+    if let Some(session_id) = session_id {
+        span.record("session_id", session_id);
+    }
+    if let Some(user_id) = user_id {
+        span.record("user_id", user_id.to_string().as_str());
+    }
+    if let Some(app_id) = app_id {
+        span.record("app_id", app_id);
+    }
+    span
+}
+
+/// How much is kept in memory (`SESSION_LOG_LINES`, `SESSION_LOG_SESSIONS`)
+#[derive(Debug, Clone, Copy)]
+pub struct SessionLogLimits {
+    /// Most recent lines kept per session
+    pub lines_per_session: usize,
+    /// Sessions whose lines are kept, ended ones included; the oldest is forgotten first
+    pub sessions: usize,
+}
+
+impl Default for SessionLogLimits {
+    fn default() -> Self {
+        Self { lines_per_session: 500, sessions: 200 }
+    }
+}
+
+impl SessionLogLimits {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let var = |name: &str| std::env::var(name).ok().and_then(|s| s.parse::<usize>().ok()).filter(|n| *n > 0);
+        Self {
+            lines_per_session: var("SESSION_LOG_LINES").unwrap_or(defaults.lines_per_session),
+            sessions: var("SESSION_LOG_SESSIONS").unwrap_or(defaults.sessions),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LogLine {
+    pub at: DateTime<Utc>,
+    pub level: String,
+    pub target: String,
+    /// The message followed by the event's other fields as `name=value`
+    pub message: String,
+}
+
+#[derive(Default)]
+struct SessionLog {
+    /// Span of the session with all its fields, once the launch opened it
+    span: Option<Span>,
+    lines: VecDeque<LogLine>,
+}
+
+#[derive(Default)]
+struct Buffers {
+    sessions: HashMap<String, SessionLog>,
+    /// Session ids, oldest first, for forgetting sessions beyond the limit
+    order: VecDeque<String>,
+}
+
+/// Recent log lines of each session, in memory only
+pub struct SessionLogs {
+    limits: SessionLogLimits,
+    buffers: Mutex<Buffers>,
+}
+
+impl SessionLogs {
+    pub fn new(limits: SessionLogLimits) -> Self {
+        Self { limits, buffers: Mutex::new(Buffers::default()) }
+    }
+
+    /// Span for a session being launched; later [`SessionLogs::span`] calls return it.
+    pub fn open(&self, session_id: &str, user_id: &UserId, app_id: &str) -> Span {
+        let span = session_span(Some(session_id), Some(user_id), Some(app_id));
+        let evicted = {
+            let mut buffers = self.lock();
+            let evicted = self.make_room(&mut buffers, session_id);
+            buffers.sessions.entry(session_id.to_string()).or_default().span = Some(span.clone());
+            evicted
+        };
+        // Spans are closed outside the lock, in case a layer logs when they close
+        drop(evicted);
+        span
+    }
+
+    /// The session's span, or one carrying only its id if it was not launched here.
+    pub fn span(&self, session_id: &str) -> Span {
+        let span = self.lock().sessions.get(session_id).and_then(|log| log.span.clone());
+        span.unwrap_or_else(|| session_span(Some(session_id), None, None))
+    }
+
+    /// The session's kept lines, oldest first; `None` if none were logged or they were forgotten.
+    pub fn lines(&self, session_id: &str) -> Option<Vec<LogLine>> {
+        let buffers = self.lock();
+        let log = buffers.sessions.get(session_id)?;
+        Some(log.lines.iter().cloned().collect())
+    }
+
+    fn push(&self, session_id: &str, line: LogLine) {
+        let evicted = {
+            let mut buffers = self.lock();
+            let evicted = self.make_room(&mut buffers, session_id);
+            let lines = &mut buffers.sessions.entry(session_id.to_string()).or_default().lines;
+            if lines.len() >= self.limits.lines_per_session {
+                lines.pop_front();
+            }
+            lines.push_back(line);
+            evicted
+        };
+        drop(evicted);
+    }
+
+    /// Forget the oldest sessions if `session_id` is new and the limit is reached.
+    fn make_room(&self, buffers: &mut Buffers, session_id: &str) -> Vec<SessionLog> {
+        if buffers.sessions.contains_key(session_id) {
+            return Vec::new();
+        }
+        let mut evicted = Vec::new();
+        while buffers.order.len() >= self.limits.sessions {
+            let Some(oldest) = buffers.order.pop_front() else { break };
+            evicted.extend(buffers.sessions.remove(&oldest));
+        }
+        buffers.order.push_back(session_id.to_string());
+        evicted
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Buffers> {
+        self.buffers.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Session id recorded on a `session` span
+struct SessionField(String);
+
+#[derive(Default)]
+struct SessionIdVisitor(Option<String>);
+
+impl Visit for SessionIdVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "session_id" {
+            self.0 = Some(value.to_string());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "session_id" {
+            self.0 = Some(format!("{:?}", value));
+        }
+    }
+}
+
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = value.to_string();
+        } else {
+            let _ = write!(self.fields, " {}={}", field.name(), value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            self.message = format!("{:?}", value);
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+/// Copies events logged inside a `session` span into that session's [`SessionLogs`] buffer.
+pub struct SessionLogLayer {
+    logs: Arc<SessionLogs>,
+}
+
+impl SessionLogLayer {
+    pub fn new(logs: Arc<SessionLogs>) -> Self {
+        Self { logs }
+    }
+}
+
+impl<S> Layer<S> for SessionLogLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        if attrs.metadata().name() != SPAN_NAME {
+            return;
+        }
+        let mut visitor = SessionIdVisitor::default();
+        attrs.record(&mut visitor);
+        if let (Some(session_id), Some(span)) = (visitor.0, ctx.span(id)) {
+            span.extensions_mut().insert(SessionField(session_id));
+        }
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        if span.metadata().name() != SPAN_NAME {
+            return;
+        }
+        let mut visitor = SessionIdVisitor::default();
+        values.record(&mut visitor);
+        if let Some(session_id) = visitor.0 {
+            span.extensions_mut().replace(SessionField(session_id));
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let Some(scope) = ctx.event_scope(event) else { return };
+        let Some(session_id) = scope
+            .into_iter()
+            .find_map(|span| span.extensions().get::<SessionField>().map(|field| field.0.clone()))
+        else {
+            return;
+        };
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        let metadata = event.metadata();
+        self.logs.push(
+            &session_id,
+            LogLine {
+                at: Utc::now(),
+                level: metadata.level().to_string(),
+                target: metadata.target().to_string(),
+                message: visitor.message + &visitor.fields,
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::prelude::*;
+
+    #[test]
+    fn test_session_lines_are_kept_per_session() {
+        let logs = Arc::new(SessionLogs::new(SessionLogLimits { lines_per_session: 2, sessions: 2 }));
+        let subscriber = tracing_subscriber::registry().with(SessionLogLayer::new(Arc::clone(&logs)));
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!("outside any session");
+            logs.open("a", &UserId::new(), "file-explorer").in_scope(|| tracing::info!("launched"));
+            // Recorded once the app says which session it belongs to
+            let late = session_span(None, None, None);
+            late.in_scope(|| tracing::info!("before hello"));
+            late.record("session_id", "b");
+            late.in_scope(|| tracing::info!("after hello"));
+            tracing::info_span!(SPAN_NAME, session_id = "c").in_scope(|| tracing::info!("evicts a"));
+        });
+
+        assert!(logs.lines("a").is_none());
+        let b = logs.lines("b").unwrap();
+        assert_eq!(b.iter().map(|l| l.message.as_str()).collect::<Vec<_>>(), vec!["after hello"]);
+        assert_eq!(logs.lines("c").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_session_lines_are_capped() {
+        let logs = Arc::new(SessionLogs::new(SessionLogLimits { lines_per_session: 2, sessions: 2 }));
+        let subscriber = tracing_subscriber::registry().with(SessionLogLayer::new(Arc::clone(&logs)));
+        tracing::subscriber::with_default(subscriber, || {
+            logs.open("a", &UserId::new(), "file-explorer").in_scope(|| {
+                tracing::info!("first");
+                tracing::warn!(bytes = 3, "second");
+                tracing::info!("third");
+            });
+        });
+
+        let a = logs.lines("a").unwrap();
+        assert_eq!(a.iter().map(|l| l.message.as_str()).collect::<Vec<_>>(), vec!["second bytes=3", "third"]);
+        assert_eq!(a[0].level, "WARN");
+    }
+}
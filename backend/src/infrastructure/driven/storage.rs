@@ -1,11 +1,881 @@
+use std::collections::HashMap;
 use std::env;
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::io::{self, Write};
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use shared::archive::{self, ArchiveFormat, ExtractLimits};
+use shared::listing::ListingCursor;
+use tokio::sync::mpsc;
 use uuid::Uuid;
+use crate::application::ports::pagination::Cursor;
+use crate::application::ports::{ByteStream, FileStat, ImportEntry, ImportOutcome, Page, PageRequest, VaultEntry, VaultStorage};
+use crate::domain::entities::file_job::FileOperation;
+use crate::domain::entities::tenant::DEFAULT_TENANT;
+use crate::domain::entities::vault_import::{numbered_name, ConflictPolicy, ImportMode};
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::driven::config::Config;
+use crate::infrastructure::driven::folder_sizes::FolderSizeCache;
+use crate::infrastructure::driven::media;
 
-pub fn create_owner_storage(user_id: &str) -> std::io::Result<PathBuf> {
+/// Where an owner's vault lives: `{storage_root}/{owner_id}` in the default tenant, and
+/// `{storage_root}/tenants/{tenant_id}/{owner_id}` in the others.
+pub fn vault_dir(storage_root: &Path, tenant_id: Uuid, owner_id: &str) -> PathBuf {
+    if tenant_id == DEFAULT_TENANT {
+        storage_root.join(owner_id)
+    } else {
+        storage_root.join("tenants").join(tenant_id.to_string()).join(owner_id)
+    }
+}
+
+pub fn create_owner_storage(user_id: &str, tenant_id: Uuid) -> std::io::Result<PathBuf> {
     let storage_root = env::var("STORAGE_PATH").unwrap();
-    let user_dir = Path::new(&storage_root).join(user_id);
+    let user_dir = vault_dir(Path::new(&storage_root), tenant_id, user_id);
     fs::create_dir_all(&user_dir)?;
     Ok(user_dir)
 }
+
+/// Applies bulk job operations under each owner's [`vault_dir`]. Folders on the way to a path
+/// are resolved and must stay inside the vault; links at the paths themselves are never
+/// followed, so copies and archives cannot pull in files from outside the vault.
+pub struct LocalVaultStorage {
+    root: PathBuf,
+    /// Replaced on config reload
+    limits: RwLock<StorageLimits>,
+    /// Owners outside the default tenant
+    tenants: RwLock<HashMap<UserId, Uuid>>,
+    /// Shared with the IPC server, which answers apps' folder size queries from it
+    folder_sizes: Arc<FolderSizeCache>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct StorageLimits {
+    extract: ExtractLimits,
+    /// Bytes one vault may hold; extraction stops before going over it
+    quota_bytes: Option<u64>,
+}
+
+impl StorageLimits {
+    /// Extraction limits come from `ARCHIVE_MAX_ENTRIES` and `ARCHIVE_MAX_BYTES`, the vault quota
+    /// from `VAULT_QUOTA_BYTES`; unset settings keep the defaults and leave vaults unbounded.
+    fn from_config(config: &Config) -> Self {
+        let defaults = ExtractLimits::default();
+        let extract = ExtractLimits {
+            max_entries: config.parse::<u64>("ARCHIVE_MAX_ENTRIES").unwrap_or(defaults.max_entries as u64) as usize,
+            max_total_bytes: config.parse("ARCHIVE_MAX_BYTES").unwrap_or(defaults.max_total_bytes),
+        };
+        Self { extract, quota_bytes: config.parse("VAULT_QUOTA_BYTES") }
+    }
+}
+
+impl LocalVaultStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            limits: RwLock::new(StorageLimits::default()),
+            tenants: RwLock::default(),
+            folder_sizes: Arc::default(),
+        }
+    }
+
+    pub fn from_config(root: impl Into<PathBuf>, config: &Config) -> Self {
+        Self {
+            root: root.into(),
+            limits: RwLock::new(StorageLimits::from_config(config)),
+            tenants: RwLock::default(),
+            folder_sizes: Arc::new(FolderSizeCache::from_config(config)),
+        }
+    }
+
+    pub fn folder_sizes(&self) -> Arc<FolderSizeCache> {
+        self.folder_sizes.clone()
+    }
+
+    /// Apply reloaded limits and quota to the operations that start from now on.
+    pub fn reconfigure(&self, config: &Config) {
+        *self.limits.write().unwrap_or_else(|e| e.into_inner()) = StorageLimits::from_config(config);
+    }
+
+    fn limits(&self) -> StorageLimits {
+        *self.limits.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn vault(&self, owner_id: &UserId) -> PathBuf {
+        let tenant_id = self.tenants.read().unwrap_or_else(|e| e.into_inner()).get(owner_id).copied();
+        vault_dir(&self.root, tenant_id.unwrap_or(DEFAULT_TENANT), &owner_id.to_string())
+    }
+}
+
+impl LocalVaultStorage {
+    /// Uploads are staged beside the vaults, never inside one, until they are complete.
+    fn staged_upload(&self, upload_id: &Uuid) -> PathBuf {
+        self.root.join(".uploads").join(format!("{upload_id}.part"))
+    }
+
+    /// Data exports are assembled in `.exports/{id}/` and packed into `.exports/{id}.zip`.
+    fn export_staging(&self, export_id: &Uuid) -> PathBuf {
+        self.root.join(".exports").join(export_id.to_string())
+    }
+
+    fn export_archive(&self, export_id: &Uuid) -> PathBuf {
+        self.root.join(".exports").join(format!("{export_id}.zip"))
+    }
+}
+
+#[async_trait::async_trait]
+impl VaultStorage for LocalVaultStorage {
+    async fn apply(&self, owner_id: &UserId, operation: &FileOperation, resumed: bool) -> Result<(), String> {
+        let vault = self.vault(owner_id);
+        let operation = operation.clone();
+        let StorageLimits { extract: limits, quota_bytes: quota } = self.limits();
+        let touched: Vec<PathBuf> = operation.paths().iter().map(|path| vault.join(path.trim_matches('/'))).collect();
+        let result = tokio::task::spawn_blocking(move || apply_operation(&vault, &operation, limits, quota, resumed))
+            .await
+            .map_err(|e| e.to_string());
+        // A failed operation may have changed part of what it touches
+        for path in &touched {
+            self.folder_sizes.invalidate(path);
+        }
+        result?
+    }
+
+    fn place_in_tenant(&self, owner_id: &UserId, tenant_id: Uuid) {
+        let mut tenants = self.tenants.write().unwrap_or_else(|e| e.into_inner());
+        if tenant_id == DEFAULT_TENANT {
+            tenants.remove(owner_id);
+        } else {
+            tenants.insert(owner_id.clone(), tenant_id);
+        }
+    }
+
+    async fn delete_vault(&self, owner_id: &UserId) -> Result<(), String> {
+        let vault = self.vault(owner_id);
+        let result = match tokio::fs::remove_dir_all(&vault).await {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e.to_string()),
+            _ => Ok(()),
+        };
+        self.folder_sizes.invalidate(&vault);
+        result
+    }
+
+    async fn archive(&self, owner_id: &UserId, paths: &[String], format: ArchiveFormat) -> Result<ByteStream, String> {
+        let vault = self.vault(owner_id);
+        let mut sources = Vec::with_capacity(paths.len());
+        for path in paths {
+            let source = vault_path(&vault, path)?;
+            if fs::symlink_metadata(&source).is_err() {
+                return Err(format!("Path not found: {path}"));
+            }
+            sources.push(source);
+        }
+        Ok(archive_stream(format, sources))
+    }
+
+    async fn stat(&self, owner_id: &UserId, path: &str) -> Result<FileStat, String> {
+        let target = vault_path(&self.vault(owner_id), path)?;
+        match tokio::fs::symlink_metadata(&target).await {
+            Ok(meta) if meta.is_file() => Ok(FileStat { size: meta.len(), etag: shared::transfer::etag(&meta) }),
+            _ => Err(format!("File not found: {path}")),
+        }
+    }
+
+    async fn list(&self, owner_id: &UserId, path: &str) -> Result<Vec<VaultEntry>, String> {
+        let vault = self.vault(owner_id);
+        let relative = path.trim_matches('/').to_string();
+        let dir = if relative.is_empty() { vault.clone() } else { vault_path(&vault, &relative)? };
+        let listed = tokio::task::spawn_blocking(move || shared::listing::list_directory(&dir))
+            .await
+            .map_err(|e| e.to_string())?
+            .map_err(|e| match e.kind() {
+                io::ErrorKind::NotFound => format!("Folder not found: {path}"),
+                _ => format!("{path}: {e}"),
+            })?;
+        Ok(listed.into_iter().map(|entry| vault_entry(&relative, entry)).collect())
+    }
+
+    async fn list_page(&self, owner_id: &UserId, path: &str, page: &PageRequest) -> Result<Page<VaultEntry>, String> {
+        let vault = self.vault(owner_id);
+        let relative = path.trim_matches('/').to_string();
+        let dir = if relative.is_empty() { vault.clone() } else { vault_path(&vault, &relative)? };
+        // The cursor is the last entry's name, and whether it is a directory as tiebreaker
+        let after = page.after.as_ref().map(|cursor| ListingCursor { is_dir: cursor.id == "d", name: cursor.key.clone() });
+        let limit = page.limit as usize;
+        let (entries, next, total) = tokio::task::spawn_blocking(move || shared::listing::list_directory_page(&dir, after.as_ref(), limit))
+            .await
+            .map_err(|e| e.to_string())?
+            .map_err(|e| match e.kind() {
+                io::ErrorKind::NotFound => format!("Folder not found: {path}"),
+                _ => format!("{path}: {e}"),
+            })?;
+        Ok(Page {
+            items: entries.into_iter().map(|entry| vault_entry(&relative, entry)).collect(),
+            next_cursor: next.map(|next| Cursor::new(next.name, if next.is_dir { "d" } else { "f" }).encode()),
+            total_estimate: total as u64,
+        })
+    }
+
+    async fn folder_size(&self, owner_id: &UserId, path: &str) -> Result<u64, String> {
+        let vault = self.vault(owner_id);
+        let dir = if path.trim_matches('/').is_empty() { vault } else { vault_path(&vault, path)? };
+        match tokio::fs::symlink_metadata(&dir).await {
+            Ok(meta) if meta.is_dir() => {}
+            _ => return Err(format!("Folder not found: {path}")),
+        }
+        self.folder_sizes.size_of(&dir).await.map_err(|e| format!("{path}: {e}"))
+    }
+
+    async fn read_range(&self, owner_id: &UserId, path: &str, offset: u64, len: u64) -> Result<ByteStream, String> {
+        use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+        self.stat(owner_id, path).await?;
+        let target = vault_path(&self.vault(owner_id), path)?;
+        let mut file = tokio::fs::File::open(&target).await.map_err(|e| format!("{path}: {e}"))?;
+        file.seek(io::SeekFrom::Start(offset)).await.map_err(|e| format!("{path}: {e}"))?;
+        Ok(read_stream(file.take(len)))
+    }
+
+    async fn check_free(&self, owner_id: &UserId, path: &str) -> Result<(), String> {
+        let target = vault_path(&self.vault(owner_id), path)?;
+        match tokio::fs::symlink_metadata(&target).await {
+            Ok(_) => Err(format!("Destination already exists: {}", path.trim_matches('/'))),
+            Err(_) => Ok(()),
+        }
+    }
+
+    async fn check_quota(&self, owner_id: &UserId, incoming: u64) -> Result<(), String> {
+        let Some(quota) = self.limits().quota_bytes else {
+            return Ok(());
+        };
+        let vault = self.vault(owner_id);
+        let used = tokio::task::spawn_blocking(move || match disk_usage(&vault) {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(0),
+            other => other,
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())?;
+        if used.saturating_add(incoming) > quota {
+            return Err(format!("Vault quota exceeded: {used} of {quota} bytes used"));
+        }
+        Ok(())
+    }
+
+    async fn append_upload(&self, upload_id: &Uuid, offset: u64, max_len: u64, mut chunk: ByteStream) -> Result<u64, String> {
+        use futures_util::StreamExt;
+        use tokio::io::{AsyncSeekExt, AsyncWriteExt};
+
+        let staged = self.staged_upload(upload_id);
+        if let Some(parent) = staged.parent() {
+            tokio::fs::create_dir_all(parent).await.map_err(|e| e.to_string())?;
+        }
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(false)
+            .open(&staged)
+            .await
+            .map_err(|e| e.to_string())?;
+        // Bytes past the recorded offset belong to a request that failed before it was recorded
+        file.set_len(offset).await.map_err(|e| e.to_string())?;
+        file.seek(io::SeekFrom::Start(offset)).await.map_err(|e| e.to_string())?;
+
+        let mut written = 0u64;
+        while let Some(data) = chunk.next().await {
+            // The client went away mid-chunk: keep what arrived so it can resume from there
+            let Ok(data) = data else { break };
+            if written + data.len() as u64 > max_len {
+                return Err("Chunk goes past the declared upload size".to_string());
+            }
+            file.write_all(&data).await.map_err(|e| e.to_string())?;
+            written += data.len() as u64;
+        }
+        file.flush().await.map_err(|e| e.to_string())?;
+        file.sync_data().await.map_err(|e| e.to_string())?;
+        Ok(offset + written)
+    }
+
+    async fn commit_upload(&self, upload_id: &Uuid, owner_id: &UserId, path: &str) -> Result<(), String> {
+        let staged = self.staged_upload(upload_id);
+        let target = vault_path(&self.vault(owner_id), path)?;
+        let placed = target.clone();
+        let path = path.to_string();
+        let result = tokio::task::spawn_blocking(move || {
+            ensure_free(&target, &path)?;
+            fs::rename(&staged, &target).map_err(|e| format!("{path}: {e}"))
+        })
+        .await
+        .map_err(|e| e.to_string())?;
+        self.folder_sizes.invalidate(&placed);
+        result
+    }
+
+    async fn discard_upload(&self, upload_id: &Uuid) -> Result<(), String> {
+        match tokio::fs::remove_file(self.staged_upload(upload_id)).await {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e.to_string()),
+            _ => Ok(()),
+        }
+    }
+
+    async fn stage_export_document(&self, export_id: &Uuid, name: &str, contents: Vec<u8>) -> Result<(), String> {
+        let target = vault_path(&self.export_staging(export_id), name)?;
+        if let Some(parent) = target.parent() {
+            tokio::fs::create_dir_all(parent).await.map_err(|e| e.to_string())?;
+        }
+        tokio::fs::write(&target, contents).await.map_err(|e| format!("{name}: {e}"))
+    }
+
+    async fn stage_export_files(&self, export_id: &Uuid, owner_id: &UserId, paths: Option<&[String]>) -> Result<(), String> {
+        let vault = self.vault(owner_id);
+        let target = self.export_staging(export_id).join("files").join(owner_id.to_string());
+        let paths = paths.map(<[String]>::to_vec);
+        tokio::task::spawn_blocking(move || -> Result<(), String> {
+            let sources = match paths {
+                None => vec![(vault.clone(), target)],
+                Some(paths) => {
+                    let mut sources = Vec::with_capacity(paths.len());
+                    for path in &paths {
+                        // A grant on the vault root shares the whole vault
+                        if path.trim_matches('/').is_empty() {
+                            sources.push((vault.clone(), target.clone()));
+                            continue;
+                        }
+                        let source = vault_path(&vault, path)?;
+                        let relative = source.strip_prefix(&vault).map_err(|e| e.to_string())?;
+                        sources.push((source.clone(), target.join(relative)));
+                    }
+                    sources
+                }
+            };
+            for (source, target) in sources {
+                if fs::symlink_metadata(&source).is_err() {
+                    continue;
+                }
+                if let Some(parent) = target.parent() {
+                    fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+                }
+                copy_recursive(&source, &target).map_err(|e| format!("{}: {e}", source.display()))?;
+            }
+            Ok(())
+        })
+        .await
+        .map_err(|e| e.to_string())?
+    }
+
+    async fn strip_export_gps(&self, export_id: &Uuid, owner_id: &UserId) -> Result<u64, String> {
+        let staged = self.export_staging(export_id).join("files").join(owner_id.to_string());
+        tokio::task::spawn_blocking(move || -> Result<u64, String> {
+            let mut stripped = 0;
+            if fs::symlink_metadata(&staged).is_ok() {
+                strip_gps_recursive(&staged, &mut stripped).map_err(|e| e.to_string())?;
+            }
+            Ok(stripped)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+    }
+
+    async fn finish_export(&self, export_id: &Uuid) -> Result<u64, String> {
+        let staging = self.export_staging(export_id);
+        let archive_path = self.export_archive(export_id);
+        tokio::task::spawn_blocking(move || pack_export(&staging, &archive_path, None))
+            .await
+            .map_err(|e| e.to_string())?
+    }
+
+    async fn seal_export(&self, export_id: &Uuid, password: &str) -> Result<u64, String> {
+        let staging = self.export_staging(export_id);
+        let archive_path = self.export_archive(export_id);
+        let password = password.to_string();
+        tokio::task::spawn_blocking(move || pack_export(&staging, &archive_path, Some(&password)))
+            .await
+            .map_err(|e| e.to_string())?
+    }
+
+    async fn read_export(&self, export_id: &Uuid) -> Result<ByteStream, String> {
+        let file = tokio::fs::File::open(self.export_archive(export_id))
+            .await
+            .map_err(|_| "Export archive not found".to_string())?;
+        Ok(read_stream(file))
+    }
+
+    async fn discard_export(&self, export_id: &Uuid) -> Result<(), String> {
+        match tokio::fs::remove_dir_all(self.export_staging(export_id)).await {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e.to_string()),
+            _ => {}
+        }
+        match tokio::fs::remove_file(self.export_archive(export_id)).await {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e.to_string()),
+            _ => Ok(()),
+        }
+    }
+
+    async fn scan_import(&self, source: &str, roots: &[String]) -> Result<Vec<ImportEntry>, String> {
+        let storage_root = self.root.clone();
+        let source = source.to_string();
+        let roots = roots.to_vec();
+        tokio::task::spawn_blocking(move || {
+            // Resolved first, so a link inside an import root cannot point the scan elsewhere
+            let resolved = fs::canonicalize(&source).map_err(|e| format!("{source}: {e}"))?;
+            let allowed = roots
+                .iter()
+                .filter_map(|root| fs::canonicalize(root).ok())
+                .any(|root| resolved.starts_with(root));
+            if !allowed {
+                return Err(format!("{source} is not inside IMPORT_ROOTS"));
+            }
+            if fs::canonicalize(&storage_root).is_ok_and(|root| resolved.starts_with(&root) || root.starts_with(&resolved)) {
+                return Err(format!("{source} overlaps the vault storage"));
+            }
+            if !resolved.is_dir() {
+                return Err(format!("{source} is not a directory"));
+            }
+            let mut entries = Vec::new();
+            scan_tree(&resolved, "", &mut entries).map_err(|e| format!("{source}: {e}"))?;
+            entries.sort_by(|a, b| a.path.cmp(&b.path));
+            Ok(entries)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+    }
+
+    async fn import_file(
+        &self,
+        owner_id: &UserId,
+        entry: &ImportEntry,
+        path: &str,
+        mode: ImportMode,
+        on_conflict: ConflictPolicy,
+    ) -> Result<ImportOutcome, String> {
+        let mut target = vault_path(&self.vault(owner_id), path)?;
+        // A rename keeps the file in the same folder, whose size this covers
+        let changed = target.clone();
+        let source = PathBuf::from(&entry.source);
+        let mut path = path.trim_matches('/').to_string();
+        let result = tokio::task::spawn_blocking(move || {
+            if fs::symlink_metadata(&target).is_ok() {
+                match on_conflict {
+                    ConflictPolicy::Skip => return Ok(ImportOutcome::Skipped),
+                    // Replaced atomically below; a directory in the way fails the file
+                    ConflictPolicy::Overwrite => {}
+                    ConflictPolicy::Rename => (target, path) = free_name(&target, &path),
+                }
+            }
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent).map_err(|e| format!("{path}: {e}"))?;
+            }
+            let placed = match mode {
+                ImportMode::Copy => copy_into_place(&source, &target),
+                ImportMode::Hardlink => match link_into_place(&source, &target) {
+                    Err(e) if crosses_devices(&e) => {
+                        return Err(format!("{}: not on the vault's filesystem, import it by copy or move", source.display()));
+                    }
+                    other => other,
+                },
+                ImportMode::Move => match fs::rename(&source, &target) {
+                    Err(e) if crosses_devices(&e) => {
+                        copy_into_place(&source, &target).and_then(|()| fs::remove_file(&source))
+                    }
+                    other => other,
+                },
+            };
+            placed.map_err(|e| format!("{}: {e}", source.display()))?;
+            Ok(ImportOutcome::Imported(path))
+        })
+        .await
+        .map_err(|e| e.to_string())?;
+        self.folder_sizes.invalidate(&changed);
+        result
+    }
+}
+
+/// Stream a file's bytes in 64 KiB chunks.
+fn read_stream<R: tokio::io::AsyncRead + Unpin + Send + 'static>(reader: R) -> ByteStream {
+    use tokio::io::AsyncReadExt;
+
+    Box::pin(futures_util::stream::unfold(reader, |mut reader| async move {
+        let mut buf = vec![0; 64 * 1024];
+        match reader.read(&mut buf).await {
+            Ok(0) => None,
+            Ok(n) => {
+                buf.truncate(n);
+                Some((Ok(buf), reader))
+            }
+            Err(e) => Some((Err(e.to_string()), reader)),
+        }
+    }))
+}
+
+/// Stream an archive of `sources` while a blocking task builds it.
+pub fn archive_stream(format: ArchiveFormat, sources: Vec<PathBuf>) -> ByteStream {
+    let (tx, rx) = mpsc::channel::<Result<Vec<u8>, String>>(16);
+    tokio::task::spawn_blocking(move || {
+        let writer = io::BufWriter::with_capacity(64 * 1024, ChannelWriter(tx.clone()));
+        if let Err(e) = archive::create(format, writer, &sources).and_then(|mut w| w.flush()) {
+            // Fails silently when the client is the one who went away
+            let _ = tx.blocking_send(Err(e.to_string()));
+        }
+    });
+    Box::pin(futures_util::stream::unfold(rx, |mut rx| async move {
+        rx.recv().await.map(|chunk| (chunk, rx))
+    }))
+}
+
+/// Hands archive bytes to the response as they are produced. Once the receiver is dropped,
+/// writes fail and archive creation stops.
+struct ChannelWriter(mpsc::Sender<Result<Vec<u8>, String>>);
+
+impl Write for ChannelWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0
+            .blocking_send(Ok(buf.to_vec()))
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "Download cancelled"))?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Resolve a vault-relative path, refusing anything but plain components and the root itself.
+/// Folders on the way are resolved and must stay inside the vault, so a linked folder cannot
+/// lead out of it; the last component is never followed.
+fn vault_path(vault: &Path, relative: &str) -> Result<PathBuf, String> {
+    let mut parts = Vec::new();
+    for component in Path::new(relative.trim_matches('/')).components() {
+        match component {
+            Component::Normal(part) => parts.push(part),
+            Component::CurDir => {}
+            _ => return Err(format!("Invalid path: {relative}")),
+        }
+    }
+    let Some((name, folders)) = parts.split_last() else {
+        return Err("Operations cannot target the vault root".to_string());
+    };
+    let root = match fs::canonicalize(vault) {
+        Ok(root) => root,
+        // A vault not created yet holds no links
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(parts.iter().fold(vault.to_path_buf(), |path, part| path.join(part))),
+        Err(e) => return Err(format!("{relative}: {e}")),
+    };
+    let mut parent = root.clone();
+    for (index, folder) in folders.iter().enumerate() {
+        let next = parent.join(folder);
+        match fs::canonicalize(&next) {
+            Ok(resolved) if resolved.starts_with(&root) => parent = resolved,
+            Ok(_) => return Err(format!("Path leaves the vault: {relative}")),
+            // Missing folders are created as plain ones; a dangling link would be followed
+            Err(e) if e.kind() == io::ErrorKind::NotFound && fs::symlink_metadata(&next).is_err() => {
+                parent = folders[index..].iter().fold(parent, |path, part| path.join(part));
+                break;
+            }
+            Err(e) => return Err(format!("{relative}: {e}")),
+        }
+    }
+    // Keep the vault's own spelling, which the folder size cache is keyed by
+    let inside = parent.strip_prefix(&root).map_err(|_| format!("Path leaves the vault: {relative}"))?;
+    Ok(vault.join(inside).join(name))
+}
+
+/// A listed entry of the folder at `relative`, with its vault path
+fn vault_entry(relative: &str, entry: shared::listing::FileEntry) -> VaultEntry {
+    VaultEntry {
+        path: if relative.is_empty() { entry.name.clone() } else { format!("{relative}/{}", entry.name) },
+        size: (!entry.is_dir).then_some(entry.size),
+        modified: entry.modified.map(chrono::DateTime::<chrono::Utc>::from),
+        name: entry.name,
+        is_dir: entry.is_dir,
+        mime_type: entry.mime_type,
+    }
+}
+
+fn ensure_free(path: &Path, relative: &str) -> Result<(), String> {
+    if fs::symlink_metadata(path).is_ok() {
+        return Err(format!("Destination already exists: {relative}"));
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("{relative}: {e}"))?;
+    }
+    Ok(())
+}
+
+/// Build a new entry at `target` under a staging name beside it, then rename it into place,
+/// so an interrupted step leaves either nothing or the finished entry there. When `resumed`,
+/// the step may have run before a restart: a finished entry is kept as its result, and a
+/// half-built one is cleared first.
+fn place_new(target: &Path, relative: &str, resumed: bool, build: impl FnOnce(&Path) -> io::Result<()>) -> Result<(), String> {
+    let name = target.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    let staged = target.with_file_name(format!(".{name}.partial"));
+    if resumed {
+        remove_entry(&staged);
+        if fs::symlink_metadata(target).is_ok() {
+            return Ok(());
+        }
+    }
+    ensure_free(target, relative)?;
+    let result = build(&staged).and_then(|()| fs::rename(&staged, target));
+    if result.is_err() {
+        remove_entry(&staged);
+    }
+    result.map_err(|e| format!("{relative}: {e}"))
+}
+
+fn remove_entry(path: &Path) {
+    let _ = match fs::symlink_metadata(path) {
+        Ok(meta) if meta.is_dir() => fs::remove_dir_all(path),
+        Ok(_) => fs::remove_file(path),
+        Err(_) => Ok(()),
+    };
+}
+
+fn apply_operation(vault: &Path, operation: &FileOperation, limits: ExtractLimits, quota: Option<u64>, resumed: bool) -> Result<(), String> {
+    match operation {
+        FileOperation::Move { from, to } => {
+            let (source, target) = (vault_path(vault, from)?, vault_path(vault, to)?);
+            // The rename went through before the restart
+            if resumed && fs::symlink_metadata(&source).is_err() && fs::symlink_metadata(&target).is_ok() {
+                return Ok(());
+            }
+            ensure_free(&target, to)?;
+            fs::rename(&source, &target).map_err(|e| format!("{from}: {e}"))
+        }
+        FileOperation::Copy { from, to } => {
+            let (source, target) = (vault_path(vault, from)?, vault_path(vault, to)?);
+            if target.starts_with(&source) {
+                return Err(format!("Cannot copy {from} into itself"));
+            }
+            place_new(&target, to, resumed, |staged| copy_recursive(&source, staged))
+        }
+        FileOperation::Delete { path } => {
+            let target = vault_path(vault, path)?;
+            let result = match fs::symlink_metadata(&target) {
+                Ok(meta) if meta.is_dir() => fs::remove_dir_all(&target),
+                Ok(_) => fs::remove_file(&target),
+                Err(e) => Err(e),
+            };
+            match result {
+                // Already gone, e.g. when a resumed job repeats its last step
+                Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+                other => other.map_err(|e| format!("{path}: {e}")),
+            }
+        }
+        FileOperation::Zip { paths, destination } => {
+            let sources = paths.iter().map(|p| vault_path(vault, p)).collect::<Result<Vec<_>, _>>()?;
+            let target = vault_path(vault, destination)?;
+            place_new(&target, destination, resumed, |staged| {
+                fs::File::create(staged)
+                    .map(io::BufWriter::new)
+                    .and_then(|file| archive::create(ArchiveFormat::Zip, file, &sources))
+                    .and_then(|mut file| file.flush())
+            })
+        }
+        FileOperation::Extract { archive: source, destination } => {
+            let (source_path, target) = (vault_path(vault, source)?, vault_path(vault, destination)?);
+            place_new(&target, destination, resumed, |staged| {
+                let limits = match quota {
+                    Some(quota) => {
+                        let used = disk_usage(vault)?;
+                        if used >= quota {
+                            return Err(io::Error::other("Vault quota exceeded"));
+                        }
+                        ExtractLimits { max_total_bytes: limits.max_total_bytes.min(quota - used), ..limits }
+                    }
+                    None => limits,
+                };
+                archive::extract(&source_path, staged, limits).map(|_| ())
+            })
+        }
+    }
+}
+
+pub(crate) fn disk_usage(path: &Path) -> io::Result<u64> {
+    let meta = fs::symlink_metadata(path)?;
+    if !meta.is_dir() {
+        return Ok(meta.len());
+    }
+    let mut total = 0;
+    for entry in fs::read_dir(path)? {
+        total += disk_usage(&entry?.path())?;
+    }
+    Ok(total)
+}
+
+/// Collect the regular files under `dir`. Entries whose names are not UTF-8 cannot have a
+/// vault path and are left out along with links.
+fn scan_tree(dir: &Path, relative: &str, entries: &mut Vec<ImportEntry>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let Ok(name) = entry.file_name().into_string() else {
+            continue;
+        };
+        let path = if relative.is_empty() { name } else { format!("{relative}/{name}") };
+        let meta = entry.metadata()?;
+        if meta.is_dir() {
+            scan_tree(&entry.path(), &path, entries)?;
+        } else if meta.is_file() {
+            entries.push(ImportEntry { source: entry.path().display().to_string(), path, size: meta.len() });
+        }
+    }
+    Ok(())
+}
+
+/// The first of `name (1).ext`, `name (2).ext`, ... that is free beside `target`.
+fn free_name(target: &Path, relative: &str) -> (PathBuf, String) {
+    let name = target.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    let (dir, _) = relative.rsplit_once('/').unwrap_or(("", ""));
+    (1..)
+        .map(|n| numbered_name(&name, n))
+        .map(|candidate| {
+            let relative = if dir.is_empty() { candidate.clone() } else { format!("{dir}/{candidate}") };
+            (target.with_file_name(candidate), relative)
+        })
+        .find(|(path, _)| fs::symlink_metadata(path).is_err())
+        .expect("some numbered name is free")
+}
+
+fn crosses_devices(e: &io::Error) -> bool {
+    e.raw_os_error() == Some(libc::EXDEV)
+}
+
+/// Stage `source` beside `target` with `place`, then rename it over `target`, so a failed or
+/// interrupted import never leaves a partial file at the vault path.
+fn stage_into_place(source: &Path, target: &Path, place: impl FnOnce(&Path, &Path) -> io::Result<()>) -> io::Result<()> {
+    let staged = target.with_file_name(format!(".{}.import", Uuid::new_v4()));
+    let result = place(source, &staged).and_then(|()| fs::rename(&staged, target));
+    if result.is_err() {
+        let _ = fs::remove_file(&staged);
+    }
+    result
+}
+
+fn copy_into_place(source: &Path, target: &Path) -> io::Result<()> {
+    stage_into_place(source, target, |source, staged| {
+        let expected = fs::metadata(source)?.len();
+        let copied = fs::copy(source, staged)?;
+        if copied != expected {
+            return Err(io::Error::other(format!("copied {copied} of {expected} bytes; the file changed during import")));
+        }
+        fs::File::open(staged)?.sync_all()
+    })
+}
+
+fn link_into_place(source: &Path, target: &Path) -> io::Result<()> {
+    stage_into_place(source, target, |source, staged| fs::hard_link(source, staged))
+}
+
+/// Blank the location of every photo under `path`, rewriting only the start of the file
+/// where EXIF sits.
+fn strip_gps_recursive(path: &Path, stripped: &mut u64) -> io::Result<()> {
+    use std::io::{Read, Seek};
+    let meta = fs::symlink_metadata(path)?;
+    if meta.is_dir() {
+        for entry in fs::read_dir(path)? {
+            strip_gps_recursive(&entry?.path(), stripped)?;
+        }
+        return Ok(());
+    }
+    if !meta.is_file() || !media::is_photo(&path.to_string_lossy()) {
+        return Ok(());
+    }
+    let mut file = fs::OpenOptions::new().read(true).write(true).open(path)?;
+    let mut head = Vec::new();
+    (&mut file).take(media::EXIF_HEAD_LEN).read_to_end(&mut head)?;
+    if media::blank_gps(&mut head) {
+        file.seek(io::SeekFrom::Start(0))?;
+        file.write_all(&head)?;
+        *stripped += 1;
+    }
+    Ok(())
+}
+
+/// Zip the contents of an export's staging area into `archive_path`, encrypted when a
+/// password is given, and drop the staging area. Returns the archive size.
+fn pack_export(staging: &Path, archive_path: &Path, password: Option<&str>) -> Result<u64, String> {
+    // Entries are named after the staged documents and folders, not the staging area
+    let mut sources = fs::read_dir(staging)
+        .and_then(|entries| entries.map(|entry| entry.map(|e| e.path())).collect::<io::Result<Vec<_>>>())
+        .map_err(|e| e.to_string())?;
+    sources.sort();
+    let partial = archive_path.with_extension("zip.part");
+    let result = fs::File::create(&partial)
+        .map(io::BufWriter::new)
+        .and_then(|file| match password {
+            Some(password) => archive::create_encrypted_zip(file, &sources, password),
+            None => archive::create(ArchiveFormat::Zip, file, &sources),
+        })
+        .and_then(|mut file| file.flush())
+        .and_then(|_| fs::rename(&partial, archive_path));
+    if let Err(e) = result {
+        let _ = fs::remove_file(&partial);
+        return Err(e.to_string());
+    }
+    fs::remove_dir_all(staging).map_err(|e| e.to_string())?;
+    fs::metadata(archive_path).map(|meta| meta.len()).map_err(|e| e.to_string())
+}
+
+fn copy_recursive(source: &Path, target: &Path) -> io::Result<()> {
+    let meta = fs::symlink_metadata(source)?;
+    if meta.file_type().is_symlink() {
+        return Ok(());
+    }
+    if meta.is_dir() {
+        fs::create_dir_all(target)?;
+        for entry in fs::read_dir(source)? {
+            let entry = entry?;
+            copy_recursive(&entry.path(), &target.join(entry.file_name()))?;
+        }
+        return Ok(());
+    }
+    fs::copy(source, target).map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn copy(from: &str, to: &str) -> FileOperation {
+        FileOperation::Copy { from: from.to_string(), to: to.to_string() }
+    }
+
+    #[tokio::test]
+    async fn test_linked_folders_cannot_lead_out_of_the_vault() {
+        let root = std::env::temp_dir().join(format!("vault-storage-test-{}", std::process::id()));
+        let owner_id = UserId::new();
+        let vault = vault_dir(&root, DEFAULT_TENANT, &owner_id.to_string());
+        let outside = root.join("outside");
+        fs::create_dir_all(vault.join("photos")).unwrap();
+        fs::create_dir_all(&outside).unwrap();
+        fs::write(vault.join("photos/a.jpg"), b"photo").unwrap();
+        fs::write(outside.join("secret.txt"), b"secret").unwrap();
+        std::os::unix::fs::symlink(&outside, vault.join("escape")).unwrap();
+        std::os::unix::fs::symlink(vault.join("photos"), vault.join("alias")).unwrap();
+
+        let storage = LocalVaultStorage::new(&root);
+        assert!(storage.apply(&owner_id, &copy("escape/secret.txt", "stolen.txt"), false).await.is_err());
+        assert!(storage.apply(&owner_id, &copy("photos/a.jpg", "escape/planted.jpg"), false).await.is_err());
+        assert!(storage.stat(&owner_id, "escape/secret.txt").await.is_err());
+        assert!(!outside.join("planted.jpg").exists());
+        // A link to a folder inside the vault resolves there
+        storage.apply(&owner_id, &copy("alias/a.jpg", "b.jpg"), false).await.unwrap();
+        assert_eq!(fs::read(vault.join("b.jpg")).unwrap(), b"photo");
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_resumed_copy_keeps_its_finished_destination() {
+        let root = std::env::temp_dir().join(format!("vault-resume-test-{}", std::process::id()));
+        let owner_id = UserId::new();
+        let vault = vault_dir(&root, DEFAULT_TENANT, &owner_id.to_string());
+        fs::create_dir_all(&vault).unwrap();
+        fs::write(vault.join("a.txt"), b"a").unwrap();
+
+        let storage = LocalVaultStorage::new(&root);
+        storage.apply(&owner_id, &copy("a.txt", "b.txt"), false).await.unwrap();
+        assert!(storage.apply(&owner_id, &copy("a.txt", "b.txt"), false).await.is_err());
+        storage.apply(&owner_id, &copy("a.txt", "b.txt"), true).await.unwrap();
+        assert!(!vault.join(".b.txt.partial").exists());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
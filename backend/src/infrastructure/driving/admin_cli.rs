@@ -0,0 +1,396 @@
+// `admin` subcommands - manage an instance over SSH, against the same database, Redis and
+// secrets as the server
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Arc;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::SqliteConnection;
+use serde_json::json;
+use crate::application::ports::pagination::{Cursor, PageRequest, SortDirection, MAX_PAGE_SIZE};
+use crate::application::ports::session_repository::SessionFilter;
+use crate::application::ports::user_repository::UserRepository;
+use crate::application::ports::{AuditRepository, ChallengeRepository, FilePermissionRepository, SessionRepository};
+use crate::domain::entities::audit_event::AuditEvent;
+use crate::domain::entities::file_permission::FilePermission;
+use crate::domain::entities::invitation::AccessLevel;
+use crate::domain::entities::owner_delegation::normalize_path;
+use crate::domain::value_objects::user_role::UserRole;
+use crate::domain::value_objects::Interaction;
+use crate::domain::{DisplayName, Email, User, UserId};
+use crate::infrastructure::driven::persistence::{
+    RedisChallengeRepository, SqliteAuditRepository, SqliteFilePermissionRepository, SqliteSessionRepository,
+    SqliteUserRepository,
+};
+use crate::infrastructure::driven::secrets::{self, SecretsProvider};
+
+/// How long the link printed by `create-super-admin` stays valid
+const SETUP_LINK_TTL_SECS: u64 = 24 * 3600;
+
+const USAGE: &str = "\
+Usage: sandbox-server admin <command>
+
+  create-super-admin --email EMAIL --name NAME
+      Print a one-time link that registers the first super-admin's passkey
+  sessions list [--user EMAIL]
+      List running and suspended sessions
+  sessions terminate SESSION_ID
+      End a session; the server stops it within a minute
+  permissions grant --owner EMAIL --client EMAIL --path PATH
+                    [--access read,write,delete] [--days N] [--view-only] [--no-input]
+      Share a path of the owner's vault with an existing client; --no-input
+      lets them watch the apps they launch on it without controlling them
+  backup --output FILE
+      Write a consistent copy of the database, safe while the server runs
+  rotate-secret jwt|turn [--write]
+      Generate a new secret and print the settings to deploy; --write stores
+      them in SECRETS_DIR
+";
+
+/// Everything the commands work with, opened from the server's environment
+struct Admin {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+    users: SqliteUserRepository,
+    sessions: SqliteSessionRepository,
+    permissions: SqliteFilePermissionRepository,
+    audit: SqliteAuditRepository,
+    challenges: RedisChallengeRepository,
+}
+
+impl Admin {
+    /// The server creates and migrates the database, so it must have started once.
+    fn open() -> Result<Self, String> {
+        let storage_path = std::env::var("STORAGE_PATH").map_err(|_| "STORAGE_PATH is not set".to_string())?;
+        let db_path = format!("{}/internal/db/sandbox.db", storage_path);
+        if !Path::new(&db_path).exists() {
+            return Err(format!("{db_path} does not exist; start the server once first"));
+        }
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(ConnectionManager::<SqliteConnection>::new(&db_path))
+            .map_err(|e| format!("Cannot open {db_path}: {e}"))?;
+        let pool = Arc::new(pool);
+
+        let redis_url = std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://localhost:6379".to_string());
+        let redis_client = redis::Client::open(redis_url).map_err(|e| format!("Invalid REDIS_URL: {e}"))?;
+
+        Ok(Self {
+            users: SqliteUserRepository::new(pool.clone()),
+            sessions: SqliteSessionRepository::new(pool.clone()),
+            permissions: SqliteFilePermissionRepository::new(pool.clone()),
+            audit: SqliteAuditRepository::new(pool.clone()),
+            challenges: RedisChallengeRepository::new(redis_client),
+            pool,
+        })
+    }
+
+    async fn user_by_email(&self, email: &str) -> Result<User, String> {
+        let email = Email::new(email.to_string())?;
+        self.users
+            .find_by_email(&email)
+            .await?
+            .ok_or_else(|| format!("No user with email {}", email.as_str()))
+    }
+
+    /// CLI actions have no user behind them; the audit trail names the system account instead.
+    async fn record(&self, mut event: AuditEvent) -> Result<(), String> {
+        let operator = std::env::var("SUDO_USER").or_else(|_| std::env::var("USER")).unwrap_or_default();
+        event.payload["via"] = json!("admin-cli");
+        event.payload["operator"] = json!(operator);
+        self.audit.record(&event).await
+    }
+}
+
+/// Run `sandbox-server admin <args>`. Returns the process exit code.
+pub async fn run(args: &[String]) -> i32 {
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let result = match args.as_slice() {
+        ["rotate-secret", rest @ ..] => rotate_secret(rest),
+        [command, ..] if ["create-super-admin", "sessions", "permissions", "backup"].contains(command) => {
+            match Admin::open() {
+                Ok(admin) => dispatch(&admin, &args).await,
+                Err(e) => Err(e),
+            }
+        }
+        _ => {
+            eprint!("{USAGE}");
+            return 2;
+        }
+    };
+    match result {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            1
+        }
+    }
+}
+
+async fn dispatch(admin: &Admin, args: &[&str]) -> Result<(), String> {
+    match args {
+        ["create-super-admin", rest @ ..] => create_super_admin(admin, rest).await,
+        ["sessions", "list", rest @ ..] => list_sessions(admin, rest).await,
+        ["sessions", "terminate", id] => terminate_session(admin, id).await,
+        ["permissions", "grant", rest @ ..] => grant_permission(admin, rest).await,
+        ["backup", rest @ ..] => backup(admin, rest).await,
+        _ => Err("Unknown command; run `sandbox-server admin` for usage".to_string()),
+    }
+}
+
+/// Value of `--name value` in `args`
+fn option<'a>(args: &[&'a str], name: &str) -> Option<&'a str> {
+    args.windows(2).find(|pair| pair[0] == name).map(|pair| pair[1])
+}
+
+fn required<'a>(args: &[&'a str], name: &str) -> Result<&'a str, String> {
+    option(args, name).ok_or_else(|| format!("{name} is required"))
+}
+
+/// 244 random bits from the system RNG, hex-encoded
+fn random_secret() -> String {
+    format!("{}{}", uuid::Uuid::new_v4().simple(), uuid::Uuid::new_v4().simple())
+}
+
+/// Passkeys can only be registered from a browser, so the first super-admin still signs up
+/// through the setup page; the link locks that page to whoever holds it.
+async fn create_super_admin(admin: &Admin, args: &[&str]) -> Result<(), String> {
+    let email = Email::new(required(args, "--email")?.to_string())?;
+    let name = DisplayName::new(required(args, "--name")?.to_string())?;
+    if admin.users.count_super_admins().await? > 0 {
+        return Err("A super-admin already exists; further admins are managed from the web UI".to_string());
+    }
+
+    let token = random_secret();
+    admin.challenges.save_setup_token(&token, SETUP_LINK_TTL_SECS).await?;
+    let origin = std::env::var("WEBAUTHN_ORIGIN").unwrap_or_else(|_| "http://localhost:5173".to_string());
+    let mut link = url::Url::parse(&origin).map_err(|e| format!("Invalid WEBAUTHN_ORIGIN: {e}"))?;
+    link.query_pairs_mut()
+        .append_pair("setup_token", &token)
+        .append_pair("email", email.as_str())
+        .append_pair("name", name.as_str());
+
+    admin.record(AuditEvent::new("setup_link_issued", json!({ "email": email.as_str() }))).await?;
+    println!("Open this link within 24 hours to register the super-admin's passkey:\n\n{link}\n");
+    println!("Until it is used, the setup page refuses anyone without it.");
+    Ok(())
+}
+
+async fn list_sessions(admin: &Admin, args: &[&str]) -> Result<(), String> {
+    let user_id = match option(args, "--user") {
+        Some(email) => Some(admin.user_by_email(email).await?.id().clone()),
+        None => None,
+    };
+    let filter = SessionFilter { user_id, ..Default::default() };
+    let mut page = PageRequest { limit: MAX_PAGE_SIZE, after: None, direction: SortDirection::Asc };
+    let mut emails: HashMap<UserId, String> = HashMap::new();
+
+    println!("{:<36}  {:<12}  {:<20}  {:<25}  {}", "SESSION", "STATE", "APP", "STARTED", "USER");
+    loop {
+        let batch = admin.sessions.list(&filter, &page).await?;
+        for session in batch.items.iter().filter(|s| s.terminated_at.is_none() && s.state != "terminated") {
+            if !emails.contains_key(&session.user_id) {
+                let email = match admin.users.find_by_id(&session.user_id).await? {
+                    Some(user) => user.email().as_str().to_string(),
+                    None => session.user_id.to_string(),
+                };
+                emails.insert(session.user_id.clone(), email);
+            }
+            println!(
+                "{:<36}  {:<12}  {:<20}  {:<25}  {}",
+                session.id,
+                session.state,
+                session.app_id,
+                session.created_at.format("%Y-%m-%d %H:%M:%S UTC"),
+                emails[&session.user_id],
+            );
+        }
+        match batch.next_cursor {
+            Some(cursor) => page.after = Some(Cursor::decode(&cursor)?),
+            None => return Ok(()),
+        }
+    }
+}
+
+/// The session's processes belong to the server that runs it, so the session is marked expired
+/// and that server's expiry sweep ends it. Suspended sessions have no processes and end at once.
+async fn terminate_session(admin: &Admin, id: &str) -> Result<(), String> {
+    let id = uuid::Uuid::parse_str(id).map_err(|e| format!("Invalid session id: {e}"))?;
+    let session = admin
+        .sessions
+        .find_by_id(&id)
+        .await?
+        .filter(|s| s.terminated_at.is_none() && s.state != "terminated")
+        .ok_or_else(|| "Session not found or already ended".to_string())?;
+
+    if session.state == "suspended" {
+        admin.sessions.terminate(&id).await?;
+        println!("Session {id} ended");
+    } else {
+        admin.sessions.expire(&id, chrono::Utc::now()).await?;
+        println!("Session {id} will be ended by its server within a minute");
+    }
+    let mut event = AuditEvent::new("session_terminated_by_admin", json!({ "session_id": id, "app_id": session.app_id }));
+    event.user_id = Some(session.user_id.clone());
+    event.owner_id = session.acting_as_owner_id.clone();
+    admin.record(event).await
+}
+
+async fn grant_permission(admin: &Admin, args: &[&str]) -> Result<(), String> {
+    let owner = admin.user_by_email(required(args, "--owner")?).await?;
+    if !owner.has_role(UserRole::Owner) {
+        return Err(format!("{} is not an owner", owner.email().as_str()));
+    }
+    // Clients get an account by accepting an invitation; the CLI only shares with existing ones
+    let client = admin.user_by_email(required(args, "--client")?).await?;
+    if client.id() == owner.id() {
+        return Err("An owner cannot share with themselves".to_string());
+    }
+    let path = normalize_path(required(args, "--path")?)?;
+    let access = option(args, "--access")
+        .unwrap_or("read")
+        .split(',')
+        .map(|level| match level.trim() {
+            "read" => Ok(AccessLevel::Read),
+            "write" => Ok(AccessLevel::Write),
+            "delete" => Ok(AccessLevel::Delete),
+            other => Err(format!("Unknown access level '{other}'; use read, write or delete")),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let now = chrono::Utc::now();
+    let expires_at = option(args, "--days")
+        .map(|days| {
+            days.parse::<i64>()
+                .ok()
+                .filter(|d| *d > 0)
+                .map(|d| now + chrono::Duration::days(d))
+                .ok_or_else(|| format!("Invalid --days '{days}'"))
+        })
+        .transpose()?;
+
+    let permission = FilePermission {
+        id: uuid::Uuid::new_v4(),
+        owner_id: owner.id().clone(),
+        client_id: client.id().clone(),
+        path,
+        access,
+        granted_at: now,
+        expires_at,
+        revoked_at: None,
+        view_only: args.contains(&"--view-only"),
+        interaction: if args.contains(&"--no-input") { Interaction::ViewOnly } else { Interaction::Interactive },
+        group_id: None,
+    };
+    admin.permissions.save(&permission).await?;
+
+    let mut event = AuditEvent::new(
+        "permission_granted",
+        json!({
+            "permission_id": permission.id,
+            "path": permission.path,
+            "access": permission.access,
+            "expires_at": permission.expires_at,
+            "view_only": permission.view_only,
+            "interaction": permission.interaction,
+        }),
+    );
+    event.user_id = Some(permission.client_id.clone());
+    event.owner_id = Some(permission.owner_id.clone());
+    admin.record(event).await?;
+    println!("Granted {} on '{}' ({})", client.email().as_str(), permission.path, permission.id);
+    Ok(())
+}
+
+/// `VACUUM INTO` copies the database from a single read transaction, so the copy is consistent
+/// while the server keeps writing. Vault files are backed up separately (see DEPLOYMENT.md).
+async fn backup(admin: &Admin, args: &[&str]) -> Result<(), String> {
+    let output = required(args, "--output")?.to_string();
+    if Path::new(&output).exists() {
+        return Err(format!("{output} already exists"));
+    }
+    let pool = admin.pool.clone();
+    let target = output.clone();
+    tokio::task::spawn_blocking(move || -> Result<(), String> {
+        let mut conn = pool.get().map_err(|e| e.to_string())?;
+        diesel::sql_query("VACUUM INTO ?1")
+            .bind::<diesel::sql_types::Text, _>(&target)
+            .execute(&mut conn)
+            .map_err(|e| format!("Backup failed: {e}"))?;
+        Ok(())
+    })
+    .await
+    .map_err(|e: tokio::task::JoinError| e.to_string())??;
+
+    let size = std::fs::metadata(&output).map(|m| m.len()).unwrap_or(0);
+    println!("Database backed up to {output} ({size} bytes)");
+    Ok(())
+}
+
+/// Secrets are only read at startup: the printed settings must be deployed, then the server
+/// restarted. Tokens signed with the retired JWT key stay valid until they expire.
+fn rotate_secret(args: &[&str]) -> Result<(), String> {
+    let provider = secrets::default_provider();
+    let write = args.contains(&"--write");
+    let settings: Vec<(&str, String)> = match args.first() {
+        Some(&"jwt") => {
+            let mut settings = vec![
+                ("JWT_SECRET", random_secret()),
+                ("JWT_KEY_ID", format!("k{}", chrono::Utc::now().format("%Y%m%d%H%M%S"))),
+            ];
+            // Tokens last a day, so only the key being retired needs to keep verifying
+            if let Some(current) = provider.get("JWT_SECRET") {
+                let kid = provider.get("JWT_KEY_ID").unwrap_or_else(|| "default".to_string());
+                settings.push(("JWT_PREVIOUS_SECRETS", format!("{kid}:{current}")));
+            }
+            settings
+        }
+        Some(&"turn") => vec![("TURN_CREDENTIAL", random_secret())],
+        _ => return Err("rotate-secret takes jwt or turn".to_string()),
+    };
+
+    if !write {
+        println!("Deploy these settings, then restart the server:\n");
+        for (name, value) in &settings {
+            println!("{name}={value}");
+        }
+        return Ok(());
+    }
+
+    let dir = std::env::var("SECRETS_DIR").unwrap_or_else(|_| "/run/secrets".to_string());
+    for (name, value) in &settings {
+        // The environment is read before SECRETS_DIR, so a file would be ignored
+        if std::env::var(name).is_ok_and(|v| !v.is_empty()) || std::env::var(format!("{name}_FILE")).is_ok() {
+            return Err(format!("{name} is set in the environment; update it there instead of using --write"));
+        }
+        let path = Path::new(&dir).join(name.to_ascii_lowercase());
+        write_secret_file(&path, value).map_err(|e| format!("Cannot write {}: {e}", path.display()))?;
+        println!("Wrote {}", path.display());
+    }
+    println!("Restart the server to use the new secrets.");
+    Ok(())
+}
+
+fn write_secret_file(path: &Path, value: &str) -> std::io::Result<()> {
+    use std::os::unix::fs::OpenOptionsExt;
+    let mut file = std::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)?;
+    file.write_all(value.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_option_reads_the_value_after_its_flag() {
+        let args = ["--owner", "a@example.com", "--view-only", "--path", "docs"];
+        assert_eq!(option(&args, "--owner"), Some("a@example.com"));
+        assert_eq!(option(&args, "--path"), Some("docs"));
+        assert_eq!(option(&args, "--days"), None);
+        assert!(required(&args, "--client").is_err());
+    }
+}
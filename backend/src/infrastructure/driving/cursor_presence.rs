@@ -0,0 +1,82 @@
+//! Pointer positions of everyone on a session, the client and the owners watching it, so each
+//! browser can draw the others' cursors over the video with their names.
+
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+
+use super::signaling_sender::SignalingSender;
+use super::webrtc::SignalingMessage;
+
+struct Participant {
+    name: String,
+    /// Whether their input drives the app; watchers only point
+    controlling: bool,
+    sender: SignalingSender,
+    /// Last reported position, as fractions of the video's width and height
+    position: Option<(f32, f32)>,
+}
+
+impl Participant {
+    fn cursor(&self, peer_id: &str) -> Option<SignalingMessage> {
+        self.position.map(|(x, y)| SignalingMessage::PeerCursor {
+            peer_id: peer_id.to_string(),
+            name: self.name.clone(),
+            controlling: self.controlling,
+            x,
+            y,
+        })
+    }
+}
+
+/// Participants of each session, keyed by a peer id unique to their signaling socket
+#[derive(Default)]
+pub struct CursorPresence {
+    sessions: RwLock<HashMap<String, HashMap<String, Participant>>>,
+}
+
+impl CursorPresence {
+    /// Add a participant, who is shown the cursors already on the session.
+    pub async fn join(&self, session_id: &str, peer_id: &str, name: String, controlling: bool, sender: SignalingSender) {
+        let mut sessions = self.sessions.write().await;
+        let participants = sessions.entry(session_id.to_string()).or_default();
+        for (other_id, other) in participants.iter() {
+            if let Some(cursor) = other.cursor(other_id) {
+                sender.send(&cursor);
+            }
+        }
+        participants.insert(peer_id.to_string(), Participant { name, controlling, sender, position: None });
+    }
+
+    /// Record where a participant points, `None` once the pointer left the video, and tell the
+    /// others.
+    pub async fn moved(&self, session_id: &str, peer_id: &str, position: Option<(f32, f32)>) {
+        let mut sessions = self.sessions.write().await;
+        let Some(participants) = sessions.get_mut(session_id) else { return };
+        let Some(participant) = participants.get_mut(peer_id) else { return };
+        participant.position = position;
+        let update = participant
+            .cursor(peer_id)
+            .unwrap_or_else(|| SignalingMessage::PeerCursorGone { peer_id: peer_id.to_string() });
+        broadcast(participants, peer_id, &update);
+    }
+
+    /// Remove a participant whose socket closed; the others drop their cursor.
+    pub async fn leave(&self, session_id: &str, peer_id: &str) {
+        let mut sessions = self.sessions.write().await;
+        let Some(participants) = sessions.get_mut(session_id) else { return };
+        if participants.remove(peer_id).is_some_and(|p| p.position.is_some()) {
+            broadcast(participants, peer_id, &SignalingMessage::PeerCursorGone { peer_id: peer_id.to_string() });
+        }
+        if participants.is_empty() {
+            sessions.remove(session_id);
+        }
+    }
+}
+
+fn broadcast(participants: &HashMap<String, Participant>, from: &str, msg: &SignalingMessage) {
+    for (peer_id, participant) in participants {
+        if peer_id != from {
+            participant.sender.send(msg);
+        }
+    }
+}
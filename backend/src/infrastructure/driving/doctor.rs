@@ -0,0 +1,280 @@
+// `--doctor` - check that this host can run the server, without starting it
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use diesel::{Connection, SqliteConnection};
+use diesel_migrations::{EmbeddedMigrations, MigrationHarness};
+use serde::Serialize;
+use crate::infrastructure::driven::config::{Config, LiveConfig};
+use crate::infrastructure::driven::ice_servers::IceServers;
+use crate::infrastructure::driven::sandbox::cgroups::CGROUP_BASE;
+use crate::infrastructure::driven::sandbox::pipeline_template::STREAM_CODEC;
+use crate::infrastructure::driven::sandbox::{CodecSupport, PipelineTemplates};
+use crate::infrastructure::driven::secrets;
+
+const REDIS_TIMEOUT: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Ok,
+    /// The server starts, but a feature is degraded
+    Warn,
+    /// The server will not start, or cannot run sessions
+    Fail,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Check {
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+impl Check {
+    fn new(name: impl Into<String>, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self { name: name.into(), status, detail: detail.into() }
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct Report {
+    pub checks: Vec<Check>,
+}
+
+impl Report {
+    fn count(&self, status: CheckStatus) -> usize {
+        self.checks.iter().filter(|check| check.status == status).count()
+    }
+
+    /// Warnings do not fail the report.
+    pub fn passed(&self) -> bool {
+        self.count(CheckStatus::Fail) == 0
+    }
+
+    fn render(&self) -> String {
+        let width = self.checks.iter().map(|check| check.name.len()).max().unwrap_or(0);
+        let mut out = String::from("Sandbox Server doctor\n\n");
+        for check in &self.checks {
+            let status = match check.status {
+                CheckStatus::Ok => " ok ",
+                CheckStatus::Warn => "warn",
+                CheckStatus::Fail => "FAIL",
+            };
+            out.push_str(&format!("[{status}] {:width$}  {}\n", check.name, check.detail));
+        }
+        out.push_str(&format!(
+            "\n{} ok, {} warnings, {} failures\n",
+            self.count(CheckStatus::Ok),
+            self.count(CheckStatus::Warn),
+            self.count(CheckStatus::Fail),
+        ));
+        out
+    }
+}
+
+/// Run every check and print the report, as JSON when `json` is set. Reads the same
+/// environment and `CONFIG_FILE` as the server, and changes nothing but a probe file in the
+/// storage directory. Returns whether the host passed.
+pub async fn run(migrations: EmbeddedMigrations, json: bool) -> bool {
+    let mut report = Report::default();
+    let config = match LiveConfig::from_env() {
+        Ok(config) => config.current(),
+        Err(e) => {
+            report.checks.push(Check::new("Configuration", CheckStatus::Fail, e));
+            Default::default()
+        }
+    };
+
+    report.checks.extend(streaming(&config));
+    for binary in ["Xvfb", "xsetroot"] {
+        report.checks.push(binary_on_path(binary));
+    }
+    report.checks.push(landlock());
+    report.checks.push(cgroups());
+    match std::env::var("STORAGE_PATH") {
+        Ok(storage_path) => {
+            report.checks.push(storage(Path::new(&storage_path)));
+            report.checks.push(database(&storage_path, migrations));
+        }
+        Err(_) => report.checks.push(Check::new("Storage", CheckStatus::Fail, "STORAGE_PATH is not set")),
+    }
+    report.checks.push(redis().await);
+    report.checks.extend(ice_servers().await);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report).unwrap_or_default());
+    } else {
+        print!("{}", report.render());
+    }
+    report.passed()
+}
+
+/// GStreamer itself, the elements of the streamed codec, and the `PIPELINE_TEMPLATES` file
+fn streaming(config: &Config) -> Vec<Check> {
+    if let Err(e) = gstreamer::init() {
+        return vec![Check::new("GStreamer", CheckStatus::Fail, format!("Cannot initialize: {e}"))];
+    }
+    let mut checks = vec![Check::new("GStreamer", CheckStatus::Ok, gstreamer::version_string().to_string())];
+    for status in CodecSupport::probe(&[STREAM_CODEC]).statuses() {
+        let name = format!("Codec {:?}", status.codec);
+        checks.push(if status.available {
+            Check::new(name, CheckStatus::Ok, "All pipeline elements installed")
+        } else {
+            let detail = format!("Missing GStreamer elements: {}", status.missing_elements.join(", "));
+            Check::new(name, CheckStatus::Fail, detail)
+        });
+    }
+    if let Some(path) = config.get("PIPELINE_TEMPLATES") {
+        checks.push(match PipelineTemplates::from_config(config) {
+            Ok(_) => Check::new("Pipeline templates", CheckStatus::Ok, path),
+            Err(e) => Check::new("Pipeline templates", CheckStatus::Fail, format!("{e:#}")),
+        });
+    }
+    checks
+}
+
+fn find_on_path(binary: &str) -> Option<PathBuf> {
+    use std::os::unix::fs::PermissionsExt;
+    let path = std::env::var_os("PATH")?;
+    std::env::split_paths(&path)
+        .map(|dir| dir.join(binary))
+        .find(|candidate| candidate.metadata().is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0))
+}
+
+fn binary_on_path(binary: &str) -> Check {
+    match find_on_path(binary) {
+        Some(path) => Check::new(binary, CheckStatus::Ok, path.display().to_string()),
+        None => Check::new(binary, CheckStatus::Fail, "Not found on PATH"),
+    }
+}
+
+/// Apps run without their filesystem restriction when the kernel lacks Landlock.
+fn landlock() -> Check {
+    match std::fs::read_to_string("/sys/kernel/security/lsm") {
+        Ok(modules) if modules.trim().split(',').any(|m| m == "landlock") => {
+            Check::new("Landlock", CheckStatus::Ok, "Enabled in the kernel")
+        }
+        Ok(modules) => Check::new(
+            "Landlock",
+            CheckStatus::Warn,
+            format!("Not among the active security modules ({}); apps will not be confined", modules.trim()),
+        ),
+        Err(e) => Check::new("Landlock", CheckStatus::Warn, format!("Cannot read /sys/kernel/security/lsm: {e}")),
+    }
+}
+
+/// Apps run without resource limits when the sandbox cgroup cannot be created.
+fn cgroups() -> Check {
+    let Ok(controllers) = std::fs::read_to_string("/sys/fs/cgroup/cgroup.controllers") else {
+        return Check::new("cgroups", CheckStatus::Warn, "cgroup v2 is not mounted on /sys/fs/cgroup");
+    };
+    let missing: Vec<&str> = ["cpu", "memory", "pids"]
+        .into_iter()
+        .filter(|controller| !controllers.split_whitespace().any(|c| c == *controller))
+        .collect();
+    if !missing.is_empty() {
+        return Check::new("cgroups", CheckStatus::Warn, format!("Controllers not available: {}", missing.join(", ")));
+    }
+    let base = Path::new(CGROUP_BASE);
+    let writable_dir = if base.exists() { base } else { Path::new("/sys/fs/cgroup") };
+    match nix::unistd::access(writable_dir, nix::unistd::AccessFlags::W_OK) {
+        Ok(()) => Check::new("cgroups", CheckStatus::Ok, format!("cgroup v2, {} writable", writable_dir.display())),
+        Err(e) => Check::new("cgroups", CheckStatus::Warn, format!("Cannot write to {}: {e}", writable_dir.display())),
+    }
+}
+
+fn storage(storage_path: &Path) -> Check {
+    if !storage_path.is_dir() {
+        let detail = format!("{} does not exist or is not a directory", storage_path.display());
+        return Check::new("Storage", CheckStatus::Fail, detail);
+    }
+    let probe = storage_path.join(format!(".doctor-{}", uuid::Uuid::new_v4()));
+    match std::fs::write(&probe, b"") {
+        Ok(()) => {
+            let _ = std::fs::remove_file(&probe);
+            Check::new("Storage", CheckStatus::Ok, format!("{} is writable", storage_path.display()))
+        }
+        Err(e) => Check::new("Storage", CheckStatus::Fail, format!("Cannot write to {}: {e}", storage_path.display())),
+    }
+}
+
+/// Pending migrations are only reported; the server applies them when it starts.
+fn database(storage_path: &str, migrations: EmbeddedMigrations) -> Check {
+    let db_path = format!("{}/internal/db/sandbox.db", storage_path);
+    if !Path::new(&db_path).exists() {
+        return Check::new("Database", CheckStatus::Ok, format!("{db_path} is created at the first start"));
+    }
+    let mut conn = match SqliteConnection::establish(&db_path) {
+        Ok(conn) => conn,
+        Err(e) => return Check::new("Database", CheckStatus::Fail, format!("Cannot open {db_path}: {e}")),
+    };
+    match conn.pending_migrations(migrations) {
+        Ok(pending) if pending.is_empty() => Check::new("Database", CheckStatus::Ok, "Migrations up to date"),
+        Ok(pending) => {
+            let names: Vec<String> = pending.iter().map(|m| m.name().to_string()).collect();
+            let detail = format!("{} migrations applied at the next start: {}", pending.len(), names.join(", "));
+            Check::new("Database", CheckStatus::Warn, detail)
+        }
+        Err(e) => Check::new("Database", CheckStatus::Fail, format!("Cannot read migrations: {e}")),
+    }
+}
+
+async fn redis() -> Check {
+    // The URL is left out of the report: it may carry a password
+    let url = std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://localhost:6379".to_string());
+    let client = match redis::Client::open(url) {
+        Ok(client) => client,
+        Err(e) => return Check::new("Redis", CheckStatus::Fail, format!("Invalid REDIS_URL: {e}")),
+    };
+    let ping = async {
+        let mut conn = client.get_multiplexed_async_connection().await?;
+        redis::cmd("PING").query_async::<String>(&mut conn).await
+    };
+    match tokio::time::timeout(REDIS_TIMEOUT, ping).await {
+        Ok(Ok(_)) => Check::new("Redis", CheckStatus::Ok, "Answered PING"),
+        Ok(Err(e)) => Check::new("Redis", CheckStatus::Fail, e.to_string()),
+        Err(_) => Check::new("Redis", CheckStatus::Fail, "Timed out"),
+    }
+}
+
+/// Each STUN/TURN URL, probed as the server does. Unreachable ones are skipped for sessions,
+/// so they only fail the report when none is left.
+async fn ice_servers() -> Vec<Check> {
+    let secrets = match secrets::load(&secrets::default_provider(), secrets::is_production()) {
+        Ok(secrets) => secrets,
+        Err(e) => return vec![Check::new("Secrets", CheckStatus::Fail, e)],
+    };
+    let servers = match IceServers::from_env(secrets.turn_credential) {
+        Ok(servers) => servers,
+        Err(e) => return vec![Check::new("ICE servers", CheckStatus::Fail, format!("{e:#}"))],
+    };
+    servers.check_all().await;
+    let health = servers.health();
+    let unreachable = if health.iter().any(|h| h.healthy) { CheckStatus::Warn } else { CheckStatus::Fail };
+    health
+        .into_iter()
+        .map(|h| match h.error {
+            None => Check::new(format!("ICE {}", h.url), CheckStatus::Ok, "Reachable"),
+            Some(e) => Check::new(format!("ICE {}", h.url), unreachable, e),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_failures_fail_the_report() {
+        let mut report = Report {
+            checks: vec![
+                Check::new("Redis", CheckStatus::Ok, "Answered PING"),
+                Check::new("Landlock", CheckStatus::Warn, "Not enabled"),
+            ],
+        };
+        assert!(report.passed());
+        report.checks.push(Check::new("Xvfb", CheckStatus::Fail, "Not found on PATH"));
+        assert!(!report.passed());
+        assert!(report.render().ends_with("1 ok, 1 warnings, 1 failures\n"));
+    }
+}
@@ -0,0 +1,129 @@
+//! Video delivery for networks where WebRTC cannot connect at all: the session's encoded VP8
+//! frames are sent as binary messages on the signaling WebSocket and decoded in the browser
+//! with WebCodecs. Each message is one frame: a flags byte (bit 0 set on keyframes), the
+//! capture time in microseconds as a big-endian u64, then the VP8 data.
+
+use bytes::{BufMut, Bytes, BytesMut};
+use std::sync::{Mutex, MutexGuard};
+use tokio::sync::mpsc::{self, error::TrySendError};
+
+/// WebCodecs codec string of the frames
+pub const CODEC: &str = "vp8";
+
+const HEADER_LEN: usize = 9;
+const FLAG_KEYFRAME: u8 = 1;
+
+/// Frames that may wait for the socket. A longer backlog means the link is too slow, so the
+/// stream skips ahead to the next keyframe instead of falling further behind.
+const FRAME_BACKLOG: usize = 8;
+
+/// The VP8 frame tag's lowest bit is clear on keyframes (RFC 6386, section 9.1)
+pub fn is_vp8_keyframe(frame: &[u8]) -> bool {
+    frame.first().is_some_and(|tag| tag & 1 == 0)
+}
+
+fn encode_frame(keyframe: bool, timestamp_us: u64, data: &[u8]) -> Bytes {
+    let mut message = BytesMut::with_capacity(HEADER_LEN + data.len());
+    message.put_u8(if keyframe { FLAG_KEYFRAME } else { 0 });
+    message.put_u64(timestamp_us);
+    message.put_slice(data);
+    message.freeze()
+}
+
+struct Sink {
+    frames: mpsc::Sender<Bytes>,
+    awaiting_keyframe: bool,
+}
+
+/// Where a client session's frames go: its WebRTC track, or the fallback socket once
+/// [`FallbackTap::open`] is called. Frames return to the track when the fallback's receiver
+/// is dropped.
+#[derive(Default)]
+pub struct FallbackTap {
+    sink: Mutex<Option<Sink>>,
+}
+
+impl FallbackTap {
+    /// Divert frames from now on, replacing any earlier fallback. The receiver gets them
+    /// framed for the socket, starting at the next keyframe.
+    pub fn open(&self) -> mpsc::Receiver<Bytes> {
+        let (frames, rx) = mpsc::channel(FRAME_BACKLOG);
+        *self.lock() = Some(Sink { frames, awaiting_keyframe: true });
+        rx
+    }
+
+    /// Hand a frame to the fallback. False when no fallback is open, so the frame belongs on
+    /// the WebRTC track.
+    pub fn offer(&self, frame: &[u8], timestamp_us: u64) -> bool {
+        let mut sink = self.lock();
+        let Some(current) = sink.as_mut() else {
+            return false;
+        };
+        let keyframe = is_vp8_keyframe(frame);
+        // A delta frame is useless to the decoder once its predecessor was dropped
+        if current.awaiting_keyframe && !keyframe {
+            return true;
+        }
+        match current.frames.try_send(encode_frame(keyframe, timestamp_us, frame)) {
+            Ok(()) => current.awaiting_keyframe = false,
+            Err(TrySendError::Full(_)) => current.awaiting_keyframe = true,
+            Err(TrySendError::Closed(_)) => {
+                *sink = None;
+                return false;
+            }
+        }
+        true
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Option<Sink>> {
+        self.sink.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEYFRAME: [u8; 3] = [0x10, 0x02, 0x00];
+    const DELTA: [u8; 3] = [0x11, 0x02, 0x00];
+
+    #[test]
+    fn test_frame_layout() {
+        assert!(is_vp8_keyframe(&KEYFRAME));
+        assert!(!is_vp8_keyframe(&DELTA));
+        assert!(!is_vp8_keyframe(&[]));
+
+        let message = encode_frame(true, 33_000, &KEYFRAME);
+        assert_eq!(message[0], FLAG_KEYFRAME);
+        assert_eq!(u64::from_be_bytes(message[1..9].try_into().unwrap()), 33_000);
+        assert_eq!(&message[HEADER_LEN..], &KEYFRAME);
+    }
+
+    #[test]
+    fn test_fallback_starts_on_keyframe_and_skips_after_backlog() {
+        let tap = FallbackTap::default();
+        assert!(!tap.offer(&KEYFRAME, 0));
+
+        let mut rx = tap.open();
+        assert!(tap.offer(&DELTA, 0));
+        assert!(rx.try_recv().is_err());
+
+        for _ in 0..FRAME_BACKLOG + 1 {
+            assert!(tap.offer(&KEYFRAME, 0));
+        }
+        // The backlog is full, so deltas wait for the next keyframe even once it drains
+        while rx.try_recv().is_ok() {}
+        assert!(tap.offer(&DELTA, 0));
+        assert!(rx.try_recv().is_err());
+        assert!(tap.offer(&KEYFRAME, 0));
+        assert_eq!(rx.try_recv().unwrap()[0], FLAG_KEYFRAME);
+    }
+
+    #[test]
+    fn test_frames_return_to_track_when_fallback_ends() {
+        let tap = FallbackTap::default();
+        drop(tap.open());
+        assert!(!tap.offer(&KEYFRAME, 0));
+        assert!(!tap.offer(&DELTA, 0));
+    }
+}
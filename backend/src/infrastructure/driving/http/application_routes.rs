@@ -4,7 +4,9 @@ use axum::response::IntoResponse;
 use serde::{Deserialize, Serialize};
 use crate::infrastructure::AppState;
 use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
-use crate::application::client::commands::launch_application;
+use crate::application::apps::availability;
+use crate::application::client::commands::launch_application::{self, LaunchOrigin, LaunchParameters};
+use crate::domain::aggregates::application_session::VideoCodec;
 
 #[derive(Serialize)]
 pub struct ApplicationMetadata {
@@ -13,30 +15,55 @@ pub struct ApplicationMetadata {
     pub description: String,
 }
 
-/// Returns a list of available applications
-pub async fn list_applications() -> Json<Vec<ApplicationMetadata>> {
-    let apps = vec![
+/// Every installed application, whoever may use it
+pub fn catalog() -> Vec<ApplicationMetadata> {
+    vec![
         ApplicationMetadata {
             app_id: "file_explorer".to_string(),
             name: "File Explorer".to_string(),
             description: "Browse and manage files in your sandboxed environment.".to_string(),
         },
-    ];
-    Json(apps)
+    ]
+}
+
+/// The applications the caller may launch, as set by the owner of the vault they work in
+pub async fn list_applications(State(state): State<AppState>, user: AuthenticatedUser) -> impl IntoResponse {
+    let app_user = match availability::resolve(&*state.file_permission_repo, &user.id, &user.roles).await {
+        Ok(Some(app_user)) => app_user,
+        Ok(None) => return (StatusCode::OK, Json(Vec::<ApplicationMetadata>::new())).into_response(),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    };
+    match availability::filter(&*state.app_setting_repo, &app_user, catalog(), |app| app.app_id.as_str()).await {
+        Ok(apps) => (StatusCode::OK, Json(apps)).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
 }
 
 #[derive(Deserialize)]
 pub struct LaunchApplicationRequest {
     pub app_id: String,
-    #[serde(default = "default_width")]
-    pub width: u16,
-    #[serde(default = "default_height")]
-    pub height: u16,
+    /// Falls back to the user's default resolution preference
+    #[serde(default)]
+    pub width: Option<u16>,
+    #[serde(default)]
+    pub height: Option<u16>,
+    /// Browser `devicePixelRatio`; width/height are in CSS pixels
+    #[serde(default)]
+    pub scale_factor: Option<f32>,
+    /// Falls back to the user's saved quality for the app, within the app's limits
+    #[serde(default)]
+    pub framerate: Option<u8>,
+    /// Defaults to the codec streamed over WebRTC
+    #[serde(default)]
+    pub codec: Option<VideoCodec>,
+    /// Instance the scheduler placed this launch on, when it was redirected here
+    #[serde(default)]
+    pub placed_on: Option<String>,
+    /// Browser's IANA time zone, for users who did not choose one
+    #[serde(default)]
+    pub timezone: Option<String>,
 }
 
-fn default_width() -> u16 { 1280 }
-fn default_height() -> u16 { 720 }
-
 #[derive(Serialize)]
 pub struct LaunchApplicationResponse {
     pub session_id: String,
@@ -48,7 +75,16 @@ pub async fn launch_application(
     user: AuthenticatedUser,
     Json(payload): Json<LaunchApplicationRequest>,
 ) -> impl IntoResponse {
-    match launch_application::execute(&state, &user, &payload.app_id, payload.width, payload.height).await {
+    let origin = LaunchOrigin { placed_on: payload.placed_on.as_deref(), ..Default::default() };
+    let params = LaunchParameters {
+        width: payload.width,
+        height: payload.height,
+        scale_factor: payload.scale_factor,
+        framerate: payload.framerate,
+        codec: payload.codec,
+        timezone: payload.timezone,
+    };
+    match launch_application::execute(&state, &user, &payload.app_id, params, origin).await {
         Ok(result) => (
             StatusCode::OK,
             Json(LaunchApplicationResponse {
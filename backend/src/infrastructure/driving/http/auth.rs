@@ -1,18 +1,29 @@
 use axum::{
     routing::{post, get},
     Router,
-    response::Json,
-    extract::State,
-    http::StatusCode,
+    response::{IntoResponse, Json, Redirect, Response},
+    extract::{ConnectInfo, Query, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
 };
+use std::net::SocketAddr;
 use serde::{Deserialize, Serialize};
 use crate::application::super_admin::commands as super_admin_commands;
+use crate::application::tenants;
+use crate::domain::entities::tenant::DEFAULT_TENANT;
+use uuid::Uuid;
 use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::{client_ip, user_agent};
 
 #[derive(Deserialize)]
 pub struct InitiateRegistrationRequest {
     pub email: String,
     pub display_name: String,
+    /// From the link printed by `sandbox-server admin create-super-admin`
+    #[serde(default)]
+    pub setup_token: Option<String>,
+    /// From the link a platform admin got when creating a tenant; registers its first admin
+    #[serde(default)]
+    pub tenant_token: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -27,6 +38,11 @@ pub struct CompleteRegistrationRequest {
     pub credential: webauthn_rs::prelude::RegisterPublicKeyCredential,
     pub email: String,
     pub display_name: String,
+    #[serde(default)]
+    pub setup_token: Option<String>,
+    /// From the link a platform admin got when creating a tenant; registers its first admin
+    #[serde(default)]
+    pub tenant_token: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -47,6 +63,12 @@ pub struct CompleteLoginRequest {
     pub email: String,
 }
 
+#[derive(Deserialize)]
+pub struct CompleteDiscoverableLoginRequest {
+    pub challenge_id: String,
+    pub credential: webauthn_rs::prelude::PublicKeyCredential,
+}
+
 #[derive(Serialize)]
 pub struct LoginResponse {
     pub token: String,
@@ -61,6 +83,31 @@ pub struct UserInfo {
     pub roles: Vec<String>,
 }
 
+#[derive(Serialize)]
+pub struct OidcStatusResponse {
+    pub enabled: bool,
+}
+
+/// What the provider redirects back with: a code on success, an error otherwise
+#[derive(Deserialize)]
+pub struct OidcCallbackQuery {
+    pub code: Option<String>,
+    pub state: Option<String>,
+    pub error: Option<String>,
+    pub error_description: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct OidcRedeemRequest {
+    pub ticket: String,
+}
+
+#[derive(Deserialize)]
+pub struct OidcPasskeySetupRequest {
+    pub challenge_id: String,
+    pub credential: webauthn_rs::prelude::RegisterPublicKeyCredential,
+}
+
 #[derive(Serialize)]
 pub struct SetupStatusResponse {
     pub initialized: bool,
@@ -73,17 +120,21 @@ pub fn setup_routes() -> Router<AppState> {
         .route("/api/setup/complete-registration", post(complete_registration))
         .route("/api/auth/initiate-login", post(initiate_login))
         .route("/api/auth/complete-login", post(complete_login))
+        .route("/api/auth/login/options", post(login_options))
+        .route("/api/auth/login/complete", post(complete_discoverable_login))
+        .route("/api/auth/oidc", get(oidc_status))
+        .route("/api/auth/oidc/login", get(oidc_login))
+        .route("/api/auth/oidc/callback", get(oidc_callback))
+        .route("/api/auth/oidc/redeem", post(oidc_redeem))
+        .route("/api/auth/oidc/passkey", post(oidc_confirm_passkey))
+        .route("/api/auth/oidc/passkey/setup", post(oidc_set_up_passkey))
 }
 
 async fn initiate_registration(
     State(state): State<AppState>,
     Json(payload): Json<InitiateRegistrationRequest>,
 ) -> Result<Json<InitiateRegistrationResponse>, (StatusCode, String)> {
-    // Lock setup endpoint if already initialized
-    let count = state.user_repo.count_super_admins().await.unwrap_or(0);
-    if count > 0 {
-        return Err((StatusCode::FORBIDDEN, "Setup is locked: SuperAdmin already exists".to_string()));
-    }
+    setup_tenant(&state, payload.setup_token.as_deref(), payload.tenant_token.as_deref()).await?;
     let result = super_admin_commands::initiate_webauthn_registration::execute(
         &state,
         &payload.email,
@@ -99,26 +150,73 @@ async fn complete_registration(
     State(state): State<AppState>,
     Json(payload): Json<CompleteRegistrationRequest>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
-    // Lock setup endpoint if already initialized
-    let count = state.user_repo.count_super_admins().await.unwrap_or(0);
-    if count > 0 {
-        return Err((StatusCode::FORBIDDEN, "Setup is locked: SuperAdmin already exists".to_string()));
-    }
-    super_admin_commands::complete_webauthn_registration::execute(
+    let tenant_id = setup_tenant(&state, payload.setup_token.as_deref(), payload.tenant_token.as_deref()).await?;
+    let user = super_admin_commands::complete_webauthn_registration::execute(
         &state,
         &payload.challenge_id,
         payload.credential,
         &payload.email,
         &payload.display_name,
+        tenant_id,
     ).await?;
+    if tenant_id != DEFAULT_TENANT {
+        tenants::complete_setup(&state, &tenant_id, user.id())
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    } else if let Err(e) = state.challenge_repo.delete_setup_token().await {
+        tracing::warn!("Failed to delete the setup token: {}", e);
+    }
     Ok(Json(serde_json::json!({ "success": true })))
 }
 
+/// The tenant whose first admin is registering: the one a tenant token was issued for, else
+/// the default tenant while the instance has no super admin yet.
+async fn setup_tenant(
+    state: &AppState,
+    setup_token: Option<&str>,
+    tenant_token: Option<&str>,
+) -> Result<Uuid, (StatusCode, String)> {
+    if let Some(token) = tenant_token {
+        return tenants::awaiting_setup(state, token)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+            .map(|tenant| tenant.id)
+            .ok_or_else(|| (StatusCode::FORBIDDEN, "This tenant setup link is invalid or was already used".to_string()));
+    }
+    // Lock setup endpoint if already initialized
+    let count = state.user_repo.count_super_admins().await.unwrap_or(0);
+    if count > 0 {
+        return Err((StatusCode::FORBIDDEN, "Setup is locked: SuperAdmin already exists".to_string()));
+    }
+    check_setup_token(state, setup_token).await?;
+    Ok(DEFAULT_TENANT)
+}
+
+/// Once `sandbox-server admin create-super-admin` has issued a setup link, setup is only open
+/// to whoever holds it.
+async fn check_setup_token(state: &AppState, provided: Option<&str>) -> Result<(), (StatusCode, String)> {
+    let expected = state
+        .challenge_repo
+        .setup_token()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    match expected {
+        Some(expected) if provided != Some(expected.as_str()) => Err((
+            StatusCode::FORBIDDEN,
+            "Setup requires the link printed by `sandbox-server admin create-super-admin`".to_string(),
+        )),
+        _ => Ok(()),
+    }
+}
+
 async fn initiate_login(
     State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Json(payload): Json<InitiateLoginRequest>,
 ) -> Result<Json<InitiateLoginResponse>, (StatusCode, String)> {
-    let result = super_admin_commands::initiate_webauthn_login::execute(&state, &payload.email).await?;
+    let ip = client_ip(&headers, peer).to_string();
+    let result = super_admin_commands::initiate_webauthn_login::execute(&state, &payload.email, &ip).await?;
     
     Ok(Json(InitiateLoginResponse {
         options: result.options,
@@ -128,13 +226,18 @@ async fn initiate_login(
 
 async fn complete_login(
     State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Json(payload): Json<CompleteLoginRequest>,
 ) -> Result<Json<LoginResponse>, (StatusCode, String)> {
+    let ip = client_ip(&headers, peer).to_string();
     let result = super_admin_commands::complete_webauthn_login::execute(
         &state,
         &payload.challenge_id,
         payload.credential,
         &payload.email,
+        &ip,
+        user_agent(&headers),
     ).await?;
     
     Ok(Json(LoginResponse {
@@ -147,6 +250,210 @@ async fn complete_login(
         },
     }))
 }
+
+/// Options for passkey autofill: the login page passes them to `navigator.credentials.get`
+/// with `mediation: "conditional"` before the user has typed anything.
+async fn login_options(
+    State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+) -> Result<Json<InitiateLoginResponse>, (StatusCode, String)> {
+    let ip = client_ip(&headers, peer).to_string();
+    let result = super_admin_commands::initiate_discoverable_login::execute(&state, &ip).await?;
+
+    Ok(Json(InitiateLoginResponse {
+        options: result.options,
+        challenge_id: result.challenge_id,
+    }))
+}
+
+async fn complete_discoverable_login(
+    State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Json(payload): Json<CompleteDiscoverableLoginRequest>,
+) -> Result<Json<LoginResponse>, (StatusCode, String)> {
+    let ip = client_ip(&headers, peer).to_string();
+    let result = super_admin_commands::complete_discoverable_login::execute(
+        &state,
+        &payload.challenge_id,
+        payload.credential,
+        &ip,
+        user_agent(&headers),
+    ).await?;
+
+    Ok(Json(LoginResponse {
+        token: result.token,
+        user: UserInfo {
+            id: result.user_id,
+            email: result.email,
+            display_name: result.display_name,
+            roles: result.roles,
+        },
+    }))
+}
+
+impl From<super_admin_commands::complete_webauthn_login::LoginCompleteResult> for LoginResponse {
+    fn from(result: super_admin_commands::complete_webauthn_login::LoginCompleteResult) -> Self {
+        LoginResponse {
+            token: result.token,
+            user: UserInfo {
+                id: result.user_id,
+                email: result.email,
+                display_name: result.display_name,
+                roles: result.roles,
+            },
+        }
+    }
+}
+
+/// Whether the login page offers a provider login
+async fn oidc_status(State(state): State<AppState>) -> Json<OidcStatusResponse> {
+    Json(OidcStatusResponse { enabled: state.identity_provider.is_some() })
+}
+
+/// Cookie binding a provider login to the browser that started it
+const OIDC_LOGIN_COOKIE: &str = "oidc_login";
+
+/// `Set-Cookie` for [`OIDC_LOGIN_COOKIE`]; an empty value with no lifetime clears it.
+fn oidc_login_cookie(value: &str, max_age_secs: u64) -> Option<HeaderValue> {
+    // Lax, since the provider's redirect back is a cross-site top-level navigation
+    let secure = std::env::var("WEBAUTHN_ORIGIN").is_ok_and(|origin| origin.starts_with("https://"));
+    let cookie = format!(
+        "{OIDC_LOGIN_COOKIE}={value}; Path=/api/auth/oidc; Max-Age={max_age_secs}; HttpOnly; SameSite=Lax{}",
+        if secure { "; Secure" } else { "" }
+    );
+    HeaderValue::from_str(&cookie).ok()
+}
+
+fn request_cookie<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .find_map(|pair| pair.trim().strip_prefix(name)?.strip_prefix('='))
+}
+
+/// Send the browser to the provider
+async fn oidc_login(
+    State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+) -> Response {
+    let ip = client_ip(&headers, peer).to_string();
+    match super_admin_commands::oidc_login::start(&state, &ip).await {
+        Ok(login) => {
+            let mut response = Redirect::to(&login.url).into_response();
+            if let Some(cookie) = oidc_login_cookie(&login.browser, 600) {
+                response.headers_mut().insert(header::SET_COOKIE, cookie);
+            }
+            response
+        }
+        Err(e) => login_page_redirect("oidc_error", &e.1),
+    }
+}
+
+/// The provider's redirect back. The browser lands on the login page either way, with a ticket
+/// to redeem or the reason the login failed.
+async fn oidc_callback(
+    State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Query(query): Query<OidcCallbackQuery>,
+) -> Response {
+    let ip = client_ip(&headers, peer).to_string();
+    let (Some(code), Some(oidc_state)) = (query.code, query.state) else {
+        let reason = query.error_description.or(query.error).unwrap_or_else(|| "The provider sent no code".to_string());
+        return login_page_redirect("oidc_error", &reason);
+    };
+    let browser = request_cookie(&headers, OIDC_LOGIN_COOKIE);
+    let mut response = match super_admin_commands::oidc_login::callback(&state, &oidc_state, &code, browser, &ip).await {
+        Ok(ticket) => login_page_redirect("oidc_ticket", &ticket),
+        Err((status, e)) => {
+            tracing::warn!("OpenID Connect login from {} failed ({}): {}", ip, status, e);
+            login_page_redirect("oidc_error", &e)
+        }
+    };
+    if let Some(cookie) = oidc_login_cookie("", 0) {
+        response.headers_mut().insert(header::SET_COOKIE, cookie);
+    }
+    response
+}
+
+fn login_page_redirect(param: &str, value: &str) -> Response {
+    let origin = std::env::var("WEBAUTHN_ORIGIN").unwrap_or_else(|_| "http://localhost:5173".to_string());
+    match url::Url::parse(&origin).and_then(|origin| origin.join("/login")) {
+        Ok(mut url) => {
+            url.query_pairs_mut().append_pair(param, value);
+            Redirect::to(url.as_str()).into_response()
+        }
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, format!("Invalid WEBAUTHN_ORIGIN: {e}")).into_response(),
+    }
+}
+
+/// A session for the ticket, or the passkey step `OIDC_PASSKEY=second_factor` adds
+async fn oidc_redeem(
+    State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Json(payload): Json<OidcRedeemRequest>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    use super_admin_commands::oidc_login::RedeemResult;
+
+    let ip = client_ip(&headers, peer).to_string();
+    let result = super_admin_commands::oidc_login::redeem(&state, &payload.ticket, &ip, user_agent(&headers)).await?;
+    let body = match result {
+        RedeemResult::LoggedIn(result) => serde_json::to_value(LoginResponse::from(result))
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?,
+        RedeemResult::PasskeyRequired(challenge) => serde_json::json!({
+            "passkey_required": true,
+            "options": challenge.options,
+            "challenge_id": challenge.challenge_id,
+        }),
+        RedeemResult::PasskeySetupRequired { options, challenge_id } => serde_json::json!({
+            "passkey_setup_required": true,
+            "options": options,
+            "challenge_id": challenge_id,
+        }),
+    };
+    Ok(Json(body))
+}
+
+async fn oidc_confirm_passkey(
+    State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Json(payload): Json<CompleteDiscoverableLoginRequest>,
+) -> Result<Json<LoginResponse>, (StatusCode, String)> {
+    let ip = client_ip(&headers, peer).to_string();
+    let result = super_admin_commands::oidc_login::confirm_passkey(
+        &state,
+        &payload.challenge_id,
+        payload.credential,
+        &ip,
+        user_agent(&headers),
+    ).await?;
+    Ok(Json(result.into()))
+}
+
+async fn oidc_set_up_passkey(
+    State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Json(payload): Json<OidcPasskeySetupRequest>,
+) -> Result<Json<LoginResponse>, (StatusCode, String)> {
+    let ip = client_ip(&headers, peer).to_string();
+    let result = super_admin_commands::oidc_login::set_up_passkey(
+        &state,
+        &payload.challenge_id,
+        payload.credential,
+        &ip,
+        user_agent(&headers),
+    ).await?;
+    Ok(Json(result.into()))
+}
+
 async fn check_setup_status(
     State(state): State<AppState>,
 ) -> Result<Json<SetupStatusResponse>, (StatusCode, String)> {
@@ -0,0 +1,121 @@
+use axum::{
+    body::{Body, Bytes},
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use futures_util::StreamExt;
+use uuid::Uuid;
+use crate::application::folder_exports;
+use crate::domain::entities::folder_export::FolderExport;
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+
+#[derive(serde::Deserialize)]
+pub struct FolderExportRequest {
+    /// Whose vault the folder is in
+    pub owner_id: Uuid,
+    pub path: String,
+    pub reason: String,
+}
+
+fn is_client(user: &AuthenticatedUser) -> bool {
+    user.roles.contains(&crate::domain::value_objects::user_role::UserRole::Client)
+}
+
+fn error_status(e: &str) -> StatusCode {
+    if e.contains("not found") {
+        StatusCode::NOT_FOUND
+    } else if e.contains("not shared") {
+        StatusCode::FORBIDDEN
+    } else if e.contains("not ready") {
+        StatusCode::CONFLICT
+    } else {
+        StatusCode::INTERNAL_SERVER_ERROR
+    }
+}
+
+/// Ask the owner for an offline copy of a shared folder. Once approved and built, the
+/// archive is under `/api/my-folder-exports/{id}/download` and its password under `.../key`.
+pub async fn request_folder_export(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(req): Json<FolderExportRequest>,
+) -> impl IntoResponse {
+    if !is_client(&user) {
+        return (StatusCode::FORBIDDEN, "Not a client").into_response();
+    }
+    let export = match FolderExport::request(UserId::from_uuid(req.owner_id), user.id.clone(), &req.path, &req.reason) {
+        Ok(export) => export,
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+    match folder_exports::request(
+        &*state.folder_export_repo,
+        &*state.file_permission_repo,
+        &*state.notification_repo,
+        &*state.audit_repo,
+        export,
+    )
+    .await
+    {
+        Ok(export) => (StatusCode::CREATED, Json(export)).into_response(),
+        Err(e) => (error_status(&e), e).into_response(),
+    }
+}
+
+/// The caller's requests, newest first.
+pub async fn list_my_folder_exports(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> impl IntoResponse {
+    if !is_client(&user) {
+        return (StatusCode::FORBIDDEN, "Not a client").into_response();
+    }
+    match state.folder_export_repo.find_by_client(&user.id).await {
+        Ok(exports) => Json(exports).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
+
+/// Stream the encrypted zip of a ready export.
+pub async fn download_folder_export(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    match folder_exports::download(&*state.folder_export_repo, &*state.vault_storage, &*state.audit_repo, &user.id, &id).await {
+        Ok((export, stream)) => {
+            let name = export.path.rsplit('/').next().filter(|name| !name.is_empty()).unwrap_or("vault");
+            let body = Body::from_stream(stream.map(|chunk| chunk.map(Bytes::from).map_err(std::io::Error::other)));
+            (
+                StatusCode::OK,
+                [
+                    (header::CONTENT_TYPE, "application/zip".to_string()),
+                    (header::CONTENT_DISPOSITION, format!("attachment; filename=\"{}.zip\"", name.replace('"', ""))),
+                ],
+                body,
+            )
+                .into_response()
+        }
+        Err(e) => (error_status(&e), e).into_response(),
+    }
+}
+
+/// The password of a ready export's archive, handed out until the export expires.
+pub async fn get_folder_export_key(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    match folder_exports::key(&*state.folder_export_repo, &*state.audit_repo, &user.id, &id).await {
+        Ok((password, expires_at)) => (
+            StatusCode::OK,
+            [(header::CACHE_CONTROL, "no-store")],
+            Json(serde_json::json!({ "password": password, "expires_at": expires_at })),
+        )
+            .into_response(),
+        Err(e) => (error_status(&e), e).into_response(),
+    }
+}
@@ -0,0 +1,66 @@
+use axum::{extract::{Path, State}, http::StatusCode, response::IntoResponse, Json};
+use uuid::Uuid;
+use crate::application::impersonation;
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+
+#[derive(serde::Deserialize)]
+pub struct ImpersonationConsentRequest {
+    /// Owner who may act as the caller
+    pub owner_id: Uuid,
+    /// How long the consent lasts, at most a week
+    pub hours: i64,
+}
+
+fn is_client(user: &AuthenticatedUser) -> bool {
+    user.roles.contains(&crate::domain::value_objects::user_role::UserRole::Client)
+}
+
+/// Let one of the owners sharing files with the caller open apps as them, read-only, for a
+/// while. Every session they open is audited and announced to the caller.
+pub async fn grant_impersonation_consent(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(req): Json<ImpersonationConsentRequest>,
+) -> impl IntoResponse {
+    if !is_client(&user) {
+        return (StatusCode::FORBIDDEN, "Not a client").into_response();
+    }
+    match impersonation::grant(&state, &user.id, &UserId::from_uuid(req.owner_id), req.hours).await {
+        Ok(consent) => (StatusCode::CREATED, Json(consent)).into_response(),
+        Err(e) if e.contains("no files") => (StatusCode::FORBIDDEN, e).into_response(),
+        Err(e) if e.contains("must last") || e.contains("themselves") => (StatusCode::BAD_REQUEST, e).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
+
+/// The caller's consents, newest first.
+pub async fn list_my_impersonation_consents(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> impl IntoResponse {
+    if !is_client(&user) {
+        return (StatusCode::FORBIDDEN, "Not a client").into_response();
+    }
+    match state.impersonation_consent_repo.find_by_client(&user.id).await {
+        Ok(consents) => Json(consents).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
+
+/// Withdraw the consent given to an owner; sessions they run as the caller end at once.
+pub async fn withdraw_impersonation_consent(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(owner_id): Path<Uuid>,
+) -> impl IntoResponse {
+    if !is_client(&user) {
+        return (StatusCode::FORBIDDEN, "Not a client").into_response();
+    }
+    match impersonation::withdraw(&state, &user.id, &UserId::from_uuid(owner_id)).await {
+        Ok(consent) => Json(consent).into_response(),
+        Err(e) if e.contains("No consent") => (StatusCode::NOT_FOUND, e).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
@@ -1 +1,3 @@
 pub mod my_permissions;
+pub mod folder_exports;
+pub mod impersonation;
@@ -1,17 +1,42 @@
-use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use axum::{extract::{State, Query}, http::StatusCode, response::IntoResponse, Json};
 use crate::infrastructure::AppState;
 use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
 use crate::application::client::commands::list_my_permissions;
+use crate::application::ports::file_permission_repository::{PermissionFilter, PermissionSort, PermissionStatus};
+use crate::application::ports::pagination::{PageRequest, SortDirection};
+
+#[derive(serde::Deserialize)]
+pub struct ListMyPermissionsQuery {
+    #[serde(default)]
+    pub status: PermissionStatus,
+    pub path_prefix: Option<String>,
+    #[serde(default)]
+    pub sort: PermissionSort,
+    pub order: Option<SortDirection>,
+    pub limit: Option<u32>,
+    pub cursor: Option<String>,
+}
 
 pub async fn list_my_permissions(
     State(state): State<AppState>,
     user: AuthenticatedUser,
+    Query(query): Query<ListMyPermissionsQuery>,
 ) -> impl IntoResponse {
     if !user.roles.contains(&crate::domain::value_objects::user_role::UserRole::Client) {
         return (StatusCode::FORBIDDEN, "Not a client").into_response();
     }
-    match list_my_permissions::execute(&*state.file_permission_repo, &user.id).await {
-        Ok(perms) => (StatusCode::OK, Json(perms)).into_response(),
+    let page = match PageRequest::new(query.limit, query.cursor.as_deref(), query.order) {
+        Ok(page) => page,
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+    let filter = PermissionFilter {
+        status: query.status,
+        path_prefix: query.path_prefix,
+        sort: query.sort,
+        ..Default::default()
+    };
+    match list_my_permissions::execute(&*state.file_permission_repo, &user.id, filter, &page).await {
+        Ok(page) => (StatusCode::OK, Json(page)).into_response(),
         Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
     }
 }
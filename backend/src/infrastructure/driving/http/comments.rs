@@ -0,0 +1,85 @@
+use axum::{extract::{Path, Query, State}, http::StatusCode, response::IntoResponse, Json};
+use uuid::Uuid;
+use crate::application::comments;
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+
+#[derive(serde::Deserialize)]
+pub struct CommentQuery {
+    /// Vault the file is in; defaults to the caller's own
+    pub owner_id: Option<Uuid>,
+    /// The file, or for counts the folder
+    #[serde(default)]
+    pub path: String,
+}
+
+#[derive(serde::Deserialize)]
+pub struct CommentRequest {
+    pub owner_id: Option<Uuid>,
+    pub path: String,
+    /// Markdown
+    pub body: String,
+}
+
+fn vault_of(user: &AuthenticatedUser, owner_id: Option<Uuid>) -> UserId {
+    owner_id.map(UserId::from_uuid).unwrap_or_else(|| user.id.clone())
+}
+
+fn error_status(e: &str) -> StatusCode {
+    if e.contains("not found") {
+        StatusCode::NOT_FOUND
+    } else if e.starts_with("Only the author") {
+        StatusCode::FORBIDDEN
+    } else {
+        StatusCode::BAD_REQUEST
+    }
+}
+
+/// The comments on a file the caller may read, oldest first, with their Markdown rendered.
+pub async fn list_comments(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Query(query): Query<CommentQuery>,
+) -> impl IntoResponse {
+    match comments::list(&state, &user.id, &vault_of(&user, query.owner_id), &query.path).await {
+        Ok(thread) => Json(thread).into_response(),
+        Err(e) => (error_status(&e), e).into_response(),
+    }
+}
+
+/// Comment on a file the caller may read. The other side of the share is notified.
+pub async fn add_comment(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(req): Json<CommentRequest>,
+) -> impl IntoResponse {
+    match comments::add(&state, &user.id, &vault_of(&user, req.owner_id), &req.path, &req.body).await {
+        Ok(comment) => (StatusCode::CREATED, Json(comment)).into_response(),
+        Err(e) => (error_status(&e), e).into_response(),
+    }
+}
+
+pub async fn delete_comment(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    match comments::delete(&state, &user.id, &id).await {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => (error_status(&e), e).into_response(),
+    }
+}
+
+/// How many comments each readable file directly in a folder has, for clients' listings;
+/// files without comments are left out. Owners get the same from `GET /api/files`.
+pub async fn count_comments(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Query(query): Query<CommentQuery>,
+) -> impl IntoResponse {
+    match comments::readable_counts(&state, &user.id, &vault_of(&user, query.owner_id), &query.path).await {
+        Ok(counts) => Json(counts).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
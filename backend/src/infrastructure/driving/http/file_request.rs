@@ -0,0 +1,92 @@
+use std::net::SocketAddr;
+use axum::{
+    body::Body,
+    extract::{ConnectInfo, Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use chrono::{DateTime, Utc};
+use futures_util::StreamExt;
+use crate::application::file_requests::{self, token_guard};
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::client_ip;
+
+#[derive(serde::Deserialize)]
+pub struct SendFileQuery {
+    /// The file's name; any folders in it are dropped
+    pub name: String,
+    /// Who is sending, shown to the owner as given
+    pub from: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+pub struct FileRequestView {
+    pub title: String,
+    pub max_file_bytes: u64,
+    pub allowed_extensions: Vec<String>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// What a file request accepts. No account is needed: the link token is the access.
+pub async fn view_file_request(
+    State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Path(token): Path<String>,
+) -> impl IntoResponse {
+    let ip = client_ip(&headers, peer).to_string();
+    match token_guard::open(&state, &token, &ip).await {
+        Ok(request) => Json(FileRequestView {
+            title: request.title,
+            max_file_bytes: request.max_file_bytes,
+            allowed_extensions: request.allowed_extensions,
+            expires_at: request.expires_at,
+        })
+        .into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Send one file as the raw request body, with its `Content-Length`. It waits in the owner's
+/// inbox until they approve it; the sender cannot see or fetch it again.
+pub async fn send_file(
+    State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Path(token): Path<String>,
+    Query(query): Query<SendFileQuery>,
+    body: Body,
+) -> impl IntoResponse {
+    let ip = client_ip(&headers, peer).to_string();
+    let request = match token_guard::open(&state, &token, &ip).await {
+        Ok(request) => request,
+        Err(e) => return e.into_response(),
+    };
+    let Some(size) = headers
+        .get(header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok())
+    else {
+        return (StatusCode::LENGTH_REQUIRED, "Content-Length is required").into_response();
+    };
+    let stream = body
+        .into_data_stream()
+        .map(|data| data.map(|bytes| bytes.to_vec()).map_err(|e| e.to_string()))
+        .boxed();
+    match file_requests::receive(&state, &request, &query.name, size, query.from.as_deref(), &ip, stream).await {
+        Ok(upload) => (
+            StatusCode::CREATED,
+            Json(serde_json::json!({ "file_name": upload.file_name, "size": upload.size, "received_at": upload.received_at })),
+        )
+            .into_response(),
+        Err(e) if e.starts_with("File is too large") => (StatusCode::PAYLOAD_TOO_LARGE, e).into_response(),
+        Err(e) if e.starts_with("File type not accepted") => (StatusCode::UNSUPPORTED_MEDIA_TYPE, e).into_response(),
+        Err(e) if e.starts_with("Too many files") => (StatusCode::TOO_MANY_REQUESTS, e).into_response(),
+        Err(e) if e.starts_with("The owner has no room") => (StatusCode::PAYLOAD_TOO_LARGE, e).into_response(),
+        Err(e) if e.starts_with("Invalid file name") || e.starts_with("File is empty") || e.starts_with("Upload was interrupted") => {
+            (StatusCode::BAD_REQUEST, e).into_response()
+        }
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
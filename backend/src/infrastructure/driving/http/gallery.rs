@@ -0,0 +1,122 @@
+use std::net::SocketAddr;
+use axum::{
+    body::{Body, Bytes},
+    extract::{ConnectInfo, Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use chrono::{DateTime, Utc};
+use futures_util::StreamExt;
+use crate::application::galleries::{self, token_guard, GalleryPhoto};
+use crate::application::ports::{Page, PageRequest};
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::client_ip;
+
+#[derive(serde::Deserialize)]
+pub struct GalleryQuery {
+    /// View key from `/unlock`, for a gallery with a password
+    pub key: Option<String>,
+    pub limit: Option<u32>,
+    pub cursor: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+pub struct UnlockRequest {
+    pub password: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct GalleryView {
+    pub title: String,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub photos: Page<GalleryPhoto>,
+}
+
+/// A page of a public gallery's photos. No account is needed: the link token is the access.
+pub async fn view_gallery(
+    State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Path(token): Path<String>,
+    Query(query): Query<GalleryQuery>,
+) -> impl IntoResponse {
+    let ip = client_ip(&headers, peer).to_string();
+    let share = match token_guard::open(&state, &token, query.key.as_deref(), &ip).await {
+        Ok(share) => share,
+        Err(e) => return e.into_response(),
+    };
+    let page = match PageRequest::new(query.limit, query.cursor.as_deref(), None) {
+        Ok(page) => page,
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+    match galleries::photos(&*state.gallery_share_repo, &*state.vault_storage, &share, &page).await {
+        Ok(photos) => Json(GalleryView { title: share.title, expires_at: share.expires_at, photos }).into_response(),
+        Err(e) if e.contains("not found") => (StatusCode::NOT_FOUND, "Gallery folder no longer exists").into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
+
+/// Trade a gallery's password for the view key to pass as `?key=` on its other endpoints.
+pub async fn unlock_gallery(
+    State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Path(token): Path<String>,
+    Json(req): Json<UnlockRequest>,
+) -> impl IntoResponse {
+    let ip = client_ip(&headers, peer).to_string();
+    match token_guard::unlock(&state, &token, &req.password, &ip).await {
+        Ok(key) => Json(serde_json::json!({ "key": key })).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// One photo at full size, its location blanked.
+pub async fn get_photo(
+    State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Path((token, name)): Path<(String, String)>,
+    Query(query): Query<GalleryQuery>,
+) -> impl IntoResponse {
+    let ip = client_ip(&headers, peer).to_string();
+    serve_photo(&state, &token, &name, query.key.as_deref(), &ip, false).await
+}
+
+/// The preview the camera embedded in a photo, or the photo itself when it has none.
+pub async fn get_thumbnail(
+    State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Path((token, name)): Path<(String, String)>,
+    Query(query): Query<GalleryQuery>,
+) -> impl IntoResponse {
+    let ip = client_ip(&headers, peer).to_string();
+    serve_photo(&state, &token, &name, query.key.as_deref(), &ip, true).await
+}
+
+async fn serve_photo(state: &AppState, token: &str, name: &str, key: Option<&str>, ip: &str, thumbnail: bool) -> axum::response::Response {
+    let share = match token_guard::open(state, token, key, ip).await {
+        Ok(share) => share,
+        Err(e) => return e.into_response(),
+    };
+    match galleries::photo(&*state.vault_storage, &share, name, thumbnail).await {
+        Ok((media_type, len, stream)) => {
+            let body = Body::from_stream(stream.map(|chunk| chunk.map(Bytes::from).map_err(std::io::Error::other)));
+            (
+                StatusCode::OK,
+                [
+                    (header::CONTENT_TYPE, media_type.to_string()),
+                    (header::CONTENT_LENGTH, len.to_string()),
+                    (header::CACHE_CONTROL, "private, max-age=3600".to_string()),
+                    (header::X_CONTENT_TYPE_OPTIONS, "nosniff".to_string()),
+                ],
+                body,
+            )
+                .into_response()
+        }
+        Err(e) if e.contains("not found") => (StatusCode::NOT_FOUND, "Photo not found").into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
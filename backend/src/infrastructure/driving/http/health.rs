@@ -0,0 +1,34 @@
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use crate::infrastructure::AppState;
+
+/// Liveness plus the last ICE server probes and the codecs found at startup. Unreachable
+/// STUN/TURN servers mark the service degraded rather than down, since sessions fall back to
+/// the remaining ones; so does a disabled codec, since apps with a pipeline template still run.
+/// An instance in maintenance reports it, so load balancers and upgrade scripts can wait on it.
+pub async fn health(State(state): State<AppState>) -> impl IntoResponse {
+    let ice_servers = state.ice_servers.health();
+    let codecs = state.codec_support.statuses();
+    let healthy = ice_servers.iter().all(|server| server.healthy) && codecs.iter().all(|codec| codec.available);
+    let maintenance = state.maintenance.current();
+    let status = match (&maintenance, healthy) {
+        (Some(_), _) => "maintenance",
+        (None, true) => "ok",
+        (None, false) => "degraded",
+    };
+    let maintenance = maintenance.map(|window| {
+        serde_json::json!({
+            "started_at": window.started_at,
+            "message": window.message,
+            "block_logins": window.block_logins,
+        })
+    });
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "status": status,
+            "maintenance": maintenance,
+            "ice_servers": ice_servers,
+            "codecs": codecs,
+        })),
+    )
+}
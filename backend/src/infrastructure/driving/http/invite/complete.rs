@@ -1,7 +1,9 @@
-use axum::{extract::{State, Path}, http::StatusCode, response::IntoResponse, Json};
+use std::net::SocketAddr;
+use axum::{extract::{ConnectInfo, State, Path}, http::{HeaderMap, StatusCode}, response::IntoResponse, Json};
 use serde::Deserialize;
 use webauthn_rs::prelude::RegisterPublicKeyCredential;
 use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::{client_ip, user_agent};
 use crate::application::invite::complete_webauthn_registration;
 
 #[derive(Deserialize)]
@@ -12,10 +14,13 @@ pub struct CompleteInviteRequest {
 
 pub async fn complete_webauthn_registration(
     State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Path(token): Path<String>,
     Json(req): Json<CompleteInviteRequest>,
 ) -> impl IntoResponse {
-    match complete_webauthn_registration::execute(&state, &token, &req.challenge_id, req.credential).await {
+    let ip = client_ip(&headers, peer).to_string();
+    match complete_webauthn_registration::execute(&state, &token, &ip, user_agent(&headers), &req.challenge_id, req.credential).await {
         Ok(result) => (
             StatusCode::OK,
             Json(serde_json::json!({
@@ -26,6 +31,6 @@ pub async fn complete_webauthn_registration(
             })),
         )
             .into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+        Err(e) => e.into_response(),
     }
 }
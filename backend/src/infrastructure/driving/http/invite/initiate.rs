@@ -1,12 +1,25 @@
-use axum::{extract::{State, Path}, http::StatusCode, response::IntoResponse, Json};
+use std::net::SocketAddr;
+use axum::{extract::{ConnectInfo, State, Path}, http::{HeaderMap, StatusCode}, response::IntoResponse, Json};
 use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::client_ip;
 use crate::application::invite::initiate_webauthn_registration;
 
+#[derive(serde::Deserialize)]
+pub struct InitiateInviteRequest {
+    /// Code mailed to the invitee, for invitations that require email verification
+    pub code: Option<String>,
+}
+
 pub async fn initiate_webauthn_registration(
     State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Path(token): Path<String>,
+    body: Option<Json<InitiateInviteRequest>>,
 ) -> impl IntoResponse {
-    match initiate_webauthn_registration::execute(&state, &token).await {
+    let ip = client_ip(&headers, peer).to_string();
+    let code = body.and_then(|Json(b)| b.code);
+    match initiate_webauthn_registration::execute(&state, &token, &ip, code.as_deref()).await {
         Ok(result) => (
             StatusCode::OK,
             Json(serde_json::json!({
@@ -15,6 +28,6 @@ pub async fn initiate_webauthn_registration(
             })),
         )
             .into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+        Err(e) => e.into_response(),
     }
 }
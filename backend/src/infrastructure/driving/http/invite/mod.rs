@@ -1,3 +1,4 @@
 pub mod view;
 pub mod initiate;
 pub mod complete;
+pub mod verify_email;
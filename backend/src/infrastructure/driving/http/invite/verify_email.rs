@@ -0,0 +1,25 @@
+use std::net::SocketAddr;
+use axum::{extract::{ConnectInfo, State, Path}, http::{header, HeaderMap, StatusCode}, response::IntoResponse};
+use shared::i18n::Locale;
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::client_ip;
+use crate::application::invite::email_verification;
+
+/// Mail a verification code to the invitee; the email is written in the requester's language.
+pub async fn send_verification_code(
+    State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Path(token): Path<String>,
+) -> impl IntoResponse {
+    let ip = client_ip(&headers, peer).to_string();
+    let locale = headers
+        .get(header::ACCEPT_LANGUAGE)
+        .and_then(|v| v.to_str().ok())
+        .map(Locale::negotiate)
+        .unwrap_or_default();
+    match email_verification::send_code(&state, &token, &ip, locale).await {
+        Ok(()) => (StatusCode::OK, "Verification code sent").into_response(),
+        Err(e) => e.into_response(),
+    }
+}
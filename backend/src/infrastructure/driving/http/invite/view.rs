@@ -1,14 +1,18 @@
-use axum::{extract::{State, Path}, http::StatusCode, response::IntoResponse, Json};
+use std::net::SocketAddr;
+use axum::{extract::{ConnectInfo, State, Path}, http::{HeaderMap, StatusCode}, response::IntoResponse, Json};
 use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::client_ip;
 use crate::application::invite::view_invitation;
 
 pub async fn view_invitation(
     State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Path(token): Path<String>,
 ) -> impl IntoResponse {
-    match view_invitation::execute(&*state.invitation_repo, &token).await {
+    let ip = client_ip(&headers, peer).to_string();
+    match view_invitation::execute(&state, &token, &ip).await {
         Ok(v) => (StatusCode::OK, Json(v)).into_response(),
-        Err(e) if e.contains("not found") => StatusCode::NOT_FOUND.into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+        Err(e) => e.into_response(),
     }
 }
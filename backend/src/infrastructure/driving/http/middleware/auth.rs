@@ -1,14 +1,34 @@
-use axum::{extract::FromRequestParts, http::{request::Parts, StatusCode}};
-use jsonwebtoken::{decode, DecodingKey, Validation};
+use axum::{extract::FromRequestParts, http::{request::Parts, Method, StatusCode}};
+use crate::domain::entities::access_token::{self, TokenScope};
+use crate::domain::services::secrets::hash_secret;
+use crate::domain::entities::tenant::DEFAULT_TENANT;
 use crate::domain::value_objects::UserId;
 use crate::domain::value_objects::user_role::UserRole;
 use crate::infrastructure::AppState;
+use shared::i18n::{tr, Locale};
 
 #[derive(Debug, Clone)]
 pub struct AuthenticatedUser {
     pub id: UserId,
     pub email: String,
     pub roles: Vec<UserRole>,
+    /// Auth session the token was issued for; absent on tokens issued before sessions existed
+    /// and on personal access tokens
+    pub session_id: Option<uuid::Uuid>,
+    pub tenant_id: uuid::Uuid,
+}
+
+impl AuthenticatedUser {
+    /// Super admins of the default tenant run the instance itself.
+    pub fn is_platform_admin(&self) -> bool {
+        self.roles.contains(&UserRole::SuperAdmin) && self.tenant_id == DEFAULT_TENANT
+    }
+
+    /// Whether the user may administer what belongs to `tenant_id`: they are a super admin of
+    /// that tenant, or of the platform.
+    pub fn administers(&self, tenant_id: uuid::Uuid) -> bool {
+        self.roles.contains(&UserRole::SuperAdmin) && (self.tenant_id == tenant_id || self.tenant_id == DEFAULT_TENANT)
+    }
 }
 
 #[derive(Debug, serde::Deserialize)]
@@ -17,40 +37,126 @@ struct Claims {
     email: String,
     roles: Vec<String>,
     exp: usize,
+    #[serde(default)]
+    sid: Option<String>,
+    /// Absent on tokens issued before tenants existed, which all belong to the default tenant
+    #[serde(default)]
+    tid: Option<uuid::Uuid>,
 }
 
 impl FromRequestParts<AppState> for AuthenticatedUser {
     type Rejection = (StatusCode, String);
 
-    fn from_request_parts(
-        parts: &mut Parts,
-        state: &AppState,
-    ) -> impl std::future::Future<Output = Result<Self, Self::Rejection>> + Send {
-        let result = extract(parts, state);
-        std::future::ready(result)
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Self::Rejection> {
+        let locale = request_locale(parts);
+        let auth_header = parts
+            .headers
+            .get("Authorization")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|s| s.strip_prefix("Bearer "))
+            .ok_or_else(|| (StatusCode::UNAUTHORIZED, tr(locale, "errors.missing_token").to_string()))?;
+
+        if auth_header.starts_with(access_token::TOKEN_PREFIX) {
+            return authenticate_access_token(state, auth_header, &parts.method, parts.uri.path(), locale).await;
+        }
+        authenticate_token(state, auth_header, locale)
     }
 }
 
-fn extract(parts: &mut Parts, state: &AppState) -> Result<AuthenticatedUser, (StatusCode, String)> {
-    let auth_header = parts
-        .headers
-        .get("Authorization")
-        .and_then(|v| v.to_str().ok())
-        .and_then(|s| s.strip_prefix("Bearer "))
-        .ok_or((StatusCode::UNAUTHORIZED, "Missing or invalid Authorization header".to_string()))?;
+/// The scope a personal access token needs for a route. Routes not listed here refuse tokens,
+/// so a leaked token can never manage the account that created it.
+fn required_scope(method: &Method, path: &str) -> Option<TokenScope> {
+    // `prefix` followed by a single id segment, e.g. `/api/files/jobs/{id}`
+    let with_id = |prefix: &str| path.strip_prefix(prefix).is_some_and(|id| !id.is_empty() && !id.contains('/'));
+    match (method.as_str(), path) {
+        (
+            "GET",
+            "/api/files" | "/api/files/download" | "/api/files/metadata" | "/api/files/media" | "/api/files/media/facets"
+            | "/api/files/duplicates" | "/api/files/usage-breakdown" | "/api/files/processing/dead-letters",
+        )
+        | ("POST", "/api/files/archive") => Some(TokenScope::FilesRead),
+        ("GET", _) if with_id("/api/files/uploads/") || with_id("/api/files/jobs/") => Some(TokenScope::FilesRead),
+        ("POST", "/api/files/uploads" | "/api/files/jobs" | "/api/files/duplicates/cleanup") => Some(TokenScope::FilesWrite),
+        ("PATCH" | "DELETE", _) if with_id("/api/files/uploads/") => Some(TokenScope::FilesWrite),
+        ("DELETE", _) if with_id("/api/files/jobs/") => Some(TokenScope::FilesWrite),
+        ("POST", p) if p.strip_suffix("/retry").is_some_and(|p| {
+            p.strip_prefix("/api/files/processing/").is_some_and(|id| !id.is_empty() && !id.contains('/'))
+        }) => Some(TokenScope::FilesWrite),
+        ("GET", "/api/vault/bandwidth" | "/api/admin/render-stats" | "/api/admin/scheduler") => Some(TokenScope::MetricsRead),
+        _ => None,
+    }
+}
 
-    let token_data = decode::<Claims>(
-        auth_header,
-        &DecodingKey::from_secret(state.jwt_secret.as_bytes()),
-        &Validation::default(),
-    )
-    .map_err(|_| (StatusCode::UNAUTHORIZED, "Invalid token".to_string()))?;
+/// Resolve a personal access token to its user, as they are now: a token does not outlive
+/// the account, and a role the user lost is lost to their tokens too.
+async fn authenticate_access_token(
+    state: &AppState,
+    secret: &str,
+    method: &Method,
+    path: &str,
+    locale: Locale,
+) -> Result<AuthenticatedUser, (StatusCode, String)> {
+    let invalid = || (StatusCode::UNAUTHORIZED, tr(locale, "errors.invalid_token").to_string());
+    let token = state
+        .access_token_repo
+        .find_by_hash(&hash_secret(secret))
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+        .filter(|token| token.revoked_at.is_none())
+        .ok_or_else(invalid)?;
+    let now = chrono::Utc::now();
+    if token.is_expired(now) {
+        return Err((StatusCode::UNAUTHORIZED, tr(locale, "errors.token_expired").to_string()));
+    }
+    if !required_scope(method, path).is_some_and(|scope| token.has_scope(scope)) {
+        return Err((StatusCode::FORBIDDEN, tr(locale, "errors.token_scope").to_string()));
+    }
+    let user = state
+        .user_repo
+        .find_by_id(&token.user_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+        .filter(|user| !user.status().is_deleting())
+        .ok_or_else(invalid)?;
+    if token.use_worth_recording(now) {
+        if let Err(e) = state.access_token_repo.record_use(&token.id, now).await {
+            tracing::warn!("Failed to record use of access token {}: {}", token.id, e);
+        }
+    }
+
+    Ok(AuthenticatedUser {
+        id: user.id().clone(),
+        email: user.email().as_str().to_string(),
+        roles: user.roles().clone(),
+        session_id: None,
+        tenant_id: user.tenant_id(),
+    })
+}
+
+/// Validate a bearer token passed outside the `Authorization` header (e.g. a WebSocket query).
+pub fn authenticate_token(state: &AppState, token: &str, locale: Locale) -> Result<AuthenticatedUser, (StatusCode, String)> {
+    let token_data = state
+        .jwt_keys
+        .decode::<Claims>(token)
+        .map_err(|_| (StatusCode::UNAUTHORIZED, tr(locale, "errors.invalid_token").to_string()))?;
 
     let claims = token_data.claims;
     let id = UserId::from_uuid(
         uuid::Uuid::parse_str(&claims.sub)
-            .map_err(|_| (StatusCode::UNAUTHORIZED, "Invalid user id in token".to_string()))?,
+            .map_err(|_| (StatusCode::UNAUTHORIZED, tr(locale, "errors.invalid_token_user").to_string()))?,
     );
+    let session_id = claims
+        .sid
+        .as_deref()
+        .map(uuid::Uuid::parse_str)
+        .transpose()
+        .map_err(|_| (StatusCode::UNAUTHORIZED, tr(locale, "errors.invalid_token").to_string()))?;
+    if let Some(session_id) = session_id {
+        if state.auth_sessions.is_revoked(&session_id) {
+            return Err((StatusCode::UNAUTHORIZED, tr(locale, "errors.session_revoked").to_string()));
+        }
+        state.auth_sessions.seen(session_id);
+    }
     let roles = claims
         .roles
         .iter()
@@ -66,5 +172,38 @@ fn extract(parts: &mut Parts, state: &AppState) -> Result<AuthenticatedUser, (St
         id,
         email: claims.email,
         roles,
+        session_id,
+        tenant_id: claims.tid.unwrap_or(DEFAULT_TENANT),
     })
 }
+
+/// Locale for error messages, negotiated from the `Accept-Language` header.
+fn request_locale(parts: &Parts) -> Locale {
+    parts
+        .headers
+        .get(axum::http::header::ACCEPT_LANGUAGE)
+        .and_then(|v| v.to_str().ok())
+        .map(Locale::negotiate)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_required_scope() {
+        let scope = |method: Method, path: &str| required_scope(&method, path);
+        assert_eq!(scope(Method::GET, "/api/files"), Some(TokenScope::FilesRead));
+        assert_eq!(scope(Method::GET, "/api/files/media/facets"), Some(TokenScope::FilesRead));
+        assert_eq!(scope(Method::GET, "/api/files/usage-breakdown"), Some(TokenScope::FilesRead));
+        assert_eq!(scope(Method::GET, "/api/files/jobs/42"), Some(TokenScope::FilesRead));
+        assert_eq!(scope(Method::PATCH, "/api/files/uploads/42"), Some(TokenScope::FilesWrite));
+        assert_eq!(scope(Method::POST, "/api/files/processing/42/retry"), Some(TokenScope::FilesWrite));
+        assert_eq!(scope(Method::POST, "/api/files/duplicates/cleanup"), Some(TokenScope::FilesWrite));
+        // Routes nobody listed refuse tokens rather than falling under a files scope
+        assert_eq!(scope(Method::POST, "/api/files/unknown"), None);
+        assert_eq!(scope(Method::GET, "/api/files/jobs/42/extra"), None);
+        assert_eq!(scope(Method::POST, "/api/access-tokens"), None);
+    }
+}
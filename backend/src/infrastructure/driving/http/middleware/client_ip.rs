@@ -0,0 +1,98 @@
+use std::net::{IpAddr, SocketAddr};
+use std::sync::OnceLock;
+use axum::http::HeaderMap;
+use crate::domain::value_objects::IpRange;
+
+/// Proxies whose `X-Forwarded-For` is believed, from `TRUSTED_PROXIES` (addresses or CIDR
+/// blocks, comma-separated), read once at startup. Entries that do not parse are skipped.
+fn trusted_proxies() -> &'static [IpRange] {
+    static TRUSTED: OnceLock<Vec<IpRange>> = OnceLock::new();
+    TRUSTED.get_or_init(|| {
+        let value = std::env::var("TRUSTED_PROXIES").unwrap_or_default();
+        value
+            .split(',')
+            .filter(|entry| !entry.trim().is_empty())
+            .filter_map(|entry| match IpRange::parse(entry) {
+                Ok(range) => Some(range),
+                Err(e) => {
+                    tracing::warn!("Ignoring TRUSTED_PROXIES entry: {}", e);
+                    None
+                }
+            })
+            .collect()
+    })
+}
+
+/// Address of the calling client. A peer that is one of the `TRUSTED_PROXIES` speaks for
+/// someone else, named in `X-Forwarded-For`: the rightmost entry that is not itself a trusted
+/// proxy wins, as anything left of it is client-supplied. Any other peer's header is ignored,
+/// since a client can send whatever it likes there.
+pub fn client_ip(headers: &HeaderMap, peer: SocketAddr) -> IpAddr {
+    forwarded_ip(headers, peer, trusted_proxies())
+}
+
+fn forwarded_ip(headers: &HeaderMap, peer: SocketAddr, trusted: &[IpRange]) -> IpAddr {
+    let is_trusted = |ip: IpAddr| trusted.iter().any(|range| range.contains(ip));
+    if !is_trusted(peer.ip()) {
+        return peer.ip();
+    }
+    let Some(forwarded) = headers.get("X-Forwarded-For").and_then(|v| v.to_str().ok()) else {
+        return peer.ip();
+    };
+    let mut client = peer.ip();
+    for entry in forwarded.rsplit(',') {
+        match entry.trim().parse::<IpAddr>() {
+            Ok(ip) => {
+                client = ip;
+                if !is_trusted(ip) {
+                    break;
+                }
+            }
+            // What a trusted proxy passed on unparsed cannot be told from a forgery
+            Err(_) => break,
+        }
+    }
+    client
+}
+
+/// Longest `User-Agent` kept for an auth session
+const MAX_USER_AGENT_LEN: usize = 512;
+
+/// The caller's `User-Agent`, cut to a length worth storing.
+pub fn user_agent(headers: &HeaderMap) -> Option<&str> {
+    let agent = headers.get(axum::http::header::USER_AGENT)?.to_str().ok()?.trim();
+    if agent.is_empty() {
+        return None;
+    }
+    // Header values that pass `to_str` are ASCII, so any byte index is a char boundary
+    Some(&agent[..agent.len().min(MAX_USER_AGENT_LEN)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uses_proxy_appended_address() {
+        let trusted = [IpRange::parse("10.0.0.0/24").unwrap()];
+        let proxy: SocketAddr = "10.0.0.2:40000".parse().unwrap();
+        let mut headers = HeaderMap::new();
+        assert_eq!(forwarded_ip(&headers, proxy, &trusted), proxy.ip());
+
+        headers.insert("X-Forwarded-For", "1.1.1.1, 203.0.113.7".parse().unwrap());
+        assert_eq!(forwarded_ip(&headers, proxy, &trusted).to_string(), "203.0.113.7");
+
+        // A chain of trusted proxies is walked back to the first outside address
+        headers.insert("X-Forwarded-For", "1.1.1.1, 203.0.113.7, 10.0.0.9".parse().unwrap());
+        assert_eq!(forwarded_ip(&headers, proxy, &trusted).to_string(), "203.0.113.7");
+
+        headers.insert("X-Forwarded-For", "not-an-ip".parse().unwrap());
+        assert_eq!(forwarded_ip(&headers, proxy, &trusted), proxy.ip());
+
+        // Anyone else's header is their own claim
+        let direct: SocketAddr = "198.51.100.4:50000".parse().unwrap();
+        headers.insert("X-Forwarded-For", "203.0.113.7".parse().unwrap());
+        assert_eq!(forwarded_ip(&headers, direct, &trusted), direct.ip());
+        assert_eq!(forwarded_ip(&headers, proxy, &[]), proxy.ip());
+    }
+}
@@ -1,2 +1,4 @@
 pub mod auth;
+pub mod client_ip;
 pub use auth::AuthenticatedUser;
+pub use client_ip::{client_ip, user_agent};
@@ -1,8 +1,15 @@
 pub mod check_setup_status;
+pub mod health;
 pub mod auth;
 pub mod files;
+pub mod comments;
 pub mod application_routes;
 pub mod middleware;
 pub mod owner;
 pub mod client;
 pub mod invite;
+pub mod gallery;
+pub mod file_request;
+pub mod profile;
+pub mod super_admin;
+pub mod provisioning;
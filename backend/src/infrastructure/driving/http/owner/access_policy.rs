@@ -0,0 +1,69 @@
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+use crate::application::owner::commands::{get_access_policy, update_access_policy};
+use crate::domain::entities::access_policy::AccessPolicy;
+use crate::domain::value_objects::IpRange;
+
+#[derive(Serialize, Deserialize)]
+pub struct AccessPolicyDto {
+    pub allowed_cidrs: Vec<String>,
+    pub denied_cidrs: Vec<String>,
+    pub allowed_countries: Vec<String>,
+    pub denied_countries: Vec<String>,
+}
+
+impl AccessPolicyDto {
+    fn from_domain(p: AccessPolicy) -> Self {
+        Self {
+            allowed_cidrs: p.allowed_ranges.iter().map(|r| r.to_string()).collect(),
+            denied_cidrs: p.denied_ranges.iter().map(|r| r.to_string()).collect(),
+            allowed_countries: p.allowed_countries,
+            denied_countries: p.denied_countries,
+        }
+    }
+}
+
+fn parse_ranges(ranges: &[String]) -> Result<Vec<IpRange>, String> {
+    ranges.iter().map(|r| IpRange::parse(r)).collect()
+}
+
+pub async fn get_access_policy(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> impl IntoResponse {
+    if !user.roles.contains(&crate::domain::value_objects::user_role::UserRole::Owner) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    match get_access_policy::execute(&*state.access_policy_repo, &user.id).await {
+        Ok(policy) => (StatusCode::OK, Json(AccessPolicyDto::from_domain(policy))).into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+    }
+}
+
+pub async fn update_access_policy(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(payload): Json<AccessPolicyDto>,
+) -> impl IntoResponse {
+    if !user.roles.contains(&crate::domain::value_objects::user_role::UserRole::Owner) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    let (allowed_ranges, denied_ranges) = match (parse_ranges(&payload.allowed_cidrs), parse_ranges(&payload.denied_cidrs)) {
+        (Ok(allowed), Ok(denied)) => (allowed, denied),
+        (Err(e), _) | (_, Err(e)) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+    let policy = AccessPolicy {
+        owner_id: user.id.clone(),
+        allowed_ranges,
+        denied_ranges,
+        allowed_countries: payload.allowed_countries.iter().map(|c| c.trim().to_ascii_uppercase()).collect(),
+        denied_countries: payload.denied_countries.iter().map(|c| c.trim().to_ascii_uppercase()).collect(),
+        updated_at: chrono::Utc::now(),
+    };
+    match update_access_policy::execute(&*state.access_policy_repo, &*state.audit_repo, policy).await {
+        Ok(saved) => (StatusCode::OK, Json(AccessPolicyDto::from_domain(saved))).into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+    }
+}
@@ -0,0 +1,26 @@
+use axum::{extract::{State, Path}, http::StatusCode, response::IntoResponse};
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+use crate::application::owner::commands::unlock_account;
+use crate::domain::value_objects::UserId;
+use uuid::Uuid;
+
+pub async fn unlock_account(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(client_id): Path<Uuid>,
+) -> impl IntoResponse {
+    if !user.roles.contains(&crate::domain::value_objects::user_role::UserRole::Owner) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    match unlock_account::execute(
+        &*state.login_attempt_repo,
+        &*state.file_permission_repo,
+        &*state.audit_repo,
+        &user.id,
+        &UserId::from_uuid(client_id),
+    ).await {
+        Ok(_) => (StatusCode::OK, "Account unlocked").into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+    }
+}
@@ -0,0 +1,62 @@
+use axum::{extract::{Path, State}, http::StatusCode, response::IntoResponse, Json};
+use serde::Deserialize;
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::application_routes::catalog;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+use crate::application::owner::commands::{list_app_settings, update_app_setting};
+use crate::domain::entities::app_setting::AppSetting;
+use crate::domain::value_objects::{UserId, UserRole};
+use uuid::Uuid;
+
+#[derive(Deserialize)]
+pub struct AppSettingRequest {
+    pub enabled: bool,
+    /// `Owner` and/or `Client`; with no users either, everyone may use the app
+    #[serde(default)]
+    pub allowed_roles: Vec<UserRole>,
+    #[serde(default)]
+    pub allowed_users: Vec<Uuid>,
+}
+
+/// How each installed app is offered in the caller's vault.
+pub async fn list_app_settings(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> impl IntoResponse {
+    if !user.roles.contains(&UserRole::Owner) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    let apps = catalog();
+    let app_ids: Vec<&str> = apps.iter().map(|app| app.app_id.as_str()).collect();
+    match list_app_settings::execute(&*state.app_setting_repo, &user.id, &app_ids).await {
+        Ok(settings) => (StatusCode::OK, Json(settings)).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
+
+/// Enable or disable an app in the caller's vault, and choose who sees it.
+pub async fn update_app_setting(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(app_id): Path<String>,
+    Json(req): Json<AppSettingRequest>,
+) -> impl IntoResponse {
+    if !user.roles.contains(&UserRole::Owner) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    let mut setting = match AppSetting::new(user.id.clone(), &app_id) {
+        Ok(setting) => setting,
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+    if !catalog().iter().any(|app| app.app_id == setting.app_id) {
+        return (StatusCode::NOT_FOUND, format!("App not found: {app_id}")).into_response();
+    }
+    setting.enabled = req.enabled;
+    setting.allowed_roles = req.allowed_roles;
+    setting.allowed_users = req.allowed_users.into_iter().map(UserId::from_uuid).collect();
+    match update_app_setting::execute(&*state.app_setting_repo, &*state.audit_repo, setting).await {
+        Ok(saved) => (StatusCode::OK, Json(saved)).into_response(),
+        Err(e) if e.contains("Allowed") || e.contains("allowed") => (StatusCode::BAD_REQUEST, e).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
@@ -0,0 +1,88 @@
+use axum::{
+    body::{Body, Bytes},
+    extract::State,
+    http::{header, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use futures_util::StreamExt;
+use shared::ArchiveFormat;
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+use crate::application::owner::commands::download_archive;
+use crate::application::owner::scope;
+use crate::domain::value_objects::UserId;
+use uuid::Uuid;
+
+#[derive(serde::Deserialize)]
+pub struct ArchiveRequest {
+    pub paths: Vec<String>,
+    /// Defaults to zip
+    pub format: Option<ArchiveFormat>,
+    /// Vault to read from, for co-owners; defaults to the caller's own
+    pub owner_id: Option<Uuid>,
+    /// Download file name, without extension
+    pub name: Option<String>,
+}
+
+fn is_owner(user: &AuthenticatedUser) -> bool {
+    user.roles.contains(&crate::domain::value_objects::user_role::UserRole::Owner)
+}
+
+/// Keep the name safe to place inside a quoted Content-Disposition header.
+pub(super) fn download_name(name: Option<&str>, fallback: &str) -> String {
+    let cleaned: String = name
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ' '))
+        .collect();
+    let cleaned = cleaned.trim().trim_matches('.');
+    if cleaned.is_empty() { fallback.to_string() } else { cleaned.to_string() }
+}
+
+/// Build a zip or tar of the given paths and stream it while it is produced.
+/// Extraction is a bulk job: submit an `extract` operation to `POST /api/files/jobs`.
+pub async fn download_archive(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(req): Json<ArchiveRequest>,
+) -> impl IntoResponse {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    let owner_id = req.owner_id.map(UserId::from_uuid).unwrap_or_else(|| user.id.clone());
+    let scope = match scope::resolve(&*state.delegation_repo, &user.id, &owner_id).await {
+        Ok(scope) => scope,
+        Err(e) => return (StatusCode::FORBIDDEN, e).into_response(),
+    };
+    let format = req.format.unwrap_or(ArchiveFormat::Zip);
+    let filename = format!("{}.{}", download_name(req.name.as_deref(), "archive"), format.extension());
+    match download_archive::execute(&*state.vault_storage, &*state.audit_repo, &user.id, &owner_id, req.paths, format, &scope).await {
+        Ok(stream) => {
+            let body = Body::from_stream(stream.map(|chunk| chunk.map(Bytes::from).map_err(std::io::Error::other)));
+            (
+                StatusCode::OK,
+                [
+                    (header::CONTENT_TYPE, format.content_type().to_string()),
+                    (header::CONTENT_DISPOSITION, format!("attachment; filename=\"{filename}\"")),
+                ],
+                body,
+            )
+                .into_response()
+        }
+        Err(e) if e.contains("not found") => (StatusCode::NOT_FOUND, e).into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_download_name_strips_header_breaking_characters() {
+        assert_eq!(download_name(Some("photos \"2024\"\r\n"), "archive"), "photos 2024");
+        assert_eq!(download_name(Some("../.."), "archive"), "archive");
+        assert_eq!(download_name(None, "archive"), "archive");
+    }
+}
@@ -0,0 +1,45 @@
+use axum::{extract::{State, Query}, http::StatusCode, response::IntoResponse, Json};
+use chrono::{DateTime, Utc};
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+use crate::application::owner::commands::list_audit_events;
+use crate::application::ports::audit_repository::AuditFilter;
+use crate::application::ports::pagination::{PageRequest, SortDirection};
+use crate::domain::value_objects::UserId;
+use uuid::Uuid;
+
+#[derive(serde::Deserialize)]
+pub struct ListAuditQuery {
+    pub user_id: Option<Uuid>,
+    pub event_type: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    pub order: Option<SortDirection>,
+    pub limit: Option<u32>,
+    pub cursor: Option<String>,
+}
+
+pub async fn list_audit_events(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Query(query): Query<ListAuditQuery>,
+) -> impl IntoResponse {
+    if !user.roles.contains(&crate::domain::value_objects::user_role::UserRole::Owner) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    let page = match PageRequest::new(query.limit, query.cursor.as_deref(), query.order) {
+        Ok(page) => page,
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+    let filter = AuditFilter {
+        owner_id: Some(user.id.clone()),
+        user_id: query.user_id.map(UserId::from_uuid),
+        event_type: query.event_type,
+        since: query.since,
+        until: query.until,
+    };
+    match list_audit_events::execute(&*state.audit_repo, &filter, &page).await {
+        Ok(page) => (StatusCode::OK, Json(page)).into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+    }
+}
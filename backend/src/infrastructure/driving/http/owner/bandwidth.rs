@@ -0,0 +1,78 @@
+use axum::{extract::{State, Path, Query}, http::StatusCode, response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+use crate::application::owner::commands::{list_bandwidth_usage, set_bandwidth_caps};
+use crate::application::ports::BandwidthUsage;
+use crate::domain::value_objects::{BandwidthCaps, UserId};
+use uuid::Uuid;
+
+fn is_owner(user: &AuthenticatedUser) -> bool {
+    user.roles.contains(&crate::domain::value_objects::user_role::UserRole::Owner)
+}
+
+#[derive(Deserialize)]
+pub struct BandwidthUsageQuery {
+    /// `YYYY-MM`, the current month by default
+    pub month: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct BandwidthOverview {
+    pub month: String,
+    pub clients: Vec<BandwidthUsage>,
+}
+
+/// Bytes each client streamed from the caller's vault this month, with their caps.
+pub async fn list_bandwidth_usage(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Query(query): Query<BandwidthUsageQuery>,
+) -> impl IntoResponse {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    match list_bandwidth_usage::execute(&*state.bandwidth_repo, &user.id, query.month.as_deref()).await {
+        Ok((month, clients)) => (StatusCode::OK, Json(BandwidthOverview { month, clients })).into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+    }
+}
+
+pub async fn set_bandwidth_caps(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(client_id): Path<Uuid>,
+    Json(caps): Json<BandwidthCaps>,
+) -> impl IntoResponse {
+    update_caps(state, user, client_id, Some(caps)).await
+}
+
+pub async fn clear_bandwidth_caps(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(client_id): Path<Uuid>,
+) -> impl IntoResponse {
+    update_caps(state, user, client_id, None).await
+}
+
+async fn update_caps(
+    state: AppState,
+    user: AuthenticatedUser,
+    client_id: Uuid,
+    caps: Option<BandwidthCaps>,
+) -> axum::response::Response {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    match set_bandwidth_caps::execute(
+        &*state.bandwidth_repo,
+        &*state.file_permission_repo,
+        &*state.audit_repo,
+        &user.id,
+        &UserId::from_uuid(client_id),
+        caps,
+    ).await {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+    }
+}
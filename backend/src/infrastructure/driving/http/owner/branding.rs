@@ -0,0 +1,54 @@
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use serde::Deserialize;
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+use crate::application::owner::commands::update_branding;
+use crate::domain::entities::owner_branding::OwnerBranding;
+use crate::domain::value_objects::user_role::UserRole;
+
+#[derive(Deserialize)]
+pub struct BrandingRequest {
+    pub display_name: Option<String>,
+    /// `data:image/png;base64,...`, JPEG or WebP
+    pub logo: Option<String>,
+    /// `#rrggbb`
+    pub accent_color: Option<String>,
+}
+
+/// The caller's branding, `null` while they use the defaults.
+pub async fn get_branding(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> impl IntoResponse {
+    if !user.roles.contains(&UserRole::Owner) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    match state.branding_repo.find(&user.id).await {
+        Ok(branding) => (StatusCode::OK, Json(branding)).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
+
+/// Replace the caller's branding, shown on their invitation pages.
+pub async fn update_branding(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(req): Json<BrandingRequest>,
+) -> impl IntoResponse {
+    if !user.roles.contains(&UserRole::Owner) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    let branding = match OwnerBranding::new(
+        user.id.clone(),
+        req.display_name.as_deref(),
+        req.logo.as_deref(),
+        req.accent_color.as_deref(),
+    ) {
+        Ok(branding) => branding,
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+    match update_branding::execute(&*state.branding_repo, &*state.audit_repo, branding).await {
+        Ok(saved) => (StatusCode::OK, Json(saved)).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
@@ -0,0 +1,27 @@
+use axum::{extract::{Path, State}, http::StatusCode, response::IntoResponse, Json};
+use uuid::Uuid;
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+use crate::application::account_deletion;
+use crate::domain::value_objects::UserId;
+
+/// Delete the account of a client only the caller shares with. Same grace period as a
+/// self-service deletion.
+pub async fn delete_client_account(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(client_id): Path<Uuid>,
+) -> impl IntoResponse {
+    if !user.roles.contains(&crate::domain::value_objects::user_role::UserRole::Owner) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    match account_deletion::request(&state, &UserId::from_uuid(client_id), &user.id).await {
+        Ok(deletion) => (StatusCode::ACCEPTED, Json(deletion)).into_response(),
+        Err(e) if e.contains("not found") => (StatusCode::NOT_FOUND, e).into_response(),
+        Err(e) if e.contains("already being deleted") || e.contains("other owners") => {
+            (StatusCode::CONFLICT, e).into_response()
+        }
+        Err(e) if e.contains("Only client accounts") => (StatusCode::FORBIDDEN, e).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
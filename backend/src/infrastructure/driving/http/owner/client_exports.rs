@@ -0,0 +1,33 @@
+use axum::{extract::{Path, State}, http::StatusCode, response::IntoResponse, Json};
+use uuid::Uuid;
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+use crate::application::owner::commands::export_client_data;
+use crate::domain::value_objects::UserId;
+
+/// Queue an archive of a client's footprint in the caller's vault. Progress and the download
+/// are under `/api/my-data/exports/{id}`, like the caller's own exports.
+pub async fn export_client_data(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(client_id): Path<Uuid>,
+) -> impl IntoResponse {
+    if !user.roles.contains(&crate::domain::value_objects::user_role::UserRole::Owner) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    let client_id = UserId::from_uuid(client_id);
+    match export_client_data::execute(
+        &*state.data_export_repo,
+        &*state.file_permission_repo,
+        &*state.audit_repo,
+        &user.id,
+        &client_id,
+    )
+    .await
+    {
+        Ok(export) => (StatusCode::ACCEPTED, Json(export)).into_response(),
+        Err(e) if e.contains("not found") => (StatusCode::NOT_FOUND, e).into_response(),
+        Err(e) if e.contains("in progress") => (StatusCode::CONFLICT, e).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
@@ -0,0 +1,14 @@
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+
+/// The subtrees of other owners' vaults the caller administers.
+pub async fn my_delegations(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> impl IntoResponse {
+    match state.delegation_repo.find_active_for_delegate(&user.id).await {
+        Ok(delegations) => (StatusCode::OK, Json(delegations)).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
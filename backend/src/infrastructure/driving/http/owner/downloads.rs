@@ -0,0 +1,184 @@
+use axum::{
+    body::{Body, Bytes},
+    extract::{Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::IntoResponse,
+};
+use futures_util::StreamExt;
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+use crate::application::owner::commands::download_file;
+use crate::application::owner::scope;
+use crate::domain::value_objects::UserId;
+use uuid::Uuid;
+
+#[derive(serde::Deserialize)]
+pub struct DownloadQuery {
+    pub path: String,
+    /// Vault to read from, for co-owners; defaults to the caller's own
+    pub owner_id: Option<Uuid>,
+}
+
+fn is_owner(user: &AuthenticatedUser) -> bool {
+    user.roles.contains(&crate::domain::value_objects::user_role::UserRole::Owner)
+}
+
+/// What a `Range` header asks for, resolved against the file size.
+#[derive(Debug, PartialEq, Eq)]
+enum ByteRange {
+    Full,
+    /// Inclusive bounds, as in `Content-Range`
+    Partial { start: u64, end: u64 },
+    Unsatisfiable,
+}
+
+/// Only single `bytes=` ranges are honoured; anything else, including malformed or multiple
+/// ranges, gets the whole file, which RFC 9110 allows.
+fn parse_range(header: Option<&str>, size: u64) -> ByteRange {
+    let Some(spec) = header.and_then(|h| h.trim().strip_prefix("bytes=")) else {
+        return ByteRange::Full;
+    };
+    let Some((first, last)) = spec.trim().split_once('-') else {
+        return ByteRange::Full;
+    };
+    if spec.contains(',') {
+        return ByteRange::Full;
+    }
+    let (first, last) = (first.trim(), last.trim());
+    let (start, end) = if first.is_empty() {
+        // Suffix range: the last `n` bytes
+        match last.parse::<u64>() {
+            Ok(0) => return ByteRange::Unsatisfiable,
+            Ok(n) => (size.saturating_sub(n), size.saturating_sub(1)),
+            Err(_) => return ByteRange::Full,
+        }
+    } else {
+        let Ok(start) = first.parse::<u64>() else {
+            return ByteRange::Full;
+        };
+        let end = match last {
+            "" => size.saturating_sub(1),
+            last => match last.parse::<u64>() {
+                Ok(end) if end >= start => end.min(size.saturating_sub(1)),
+                _ => return ByteRange::Full,
+            },
+        };
+        (start, end)
+    };
+    if size == 0 || start >= size {
+        return ByteRange::Unsatisfiable;
+    }
+    ByteRange::Partial { start, end }
+}
+
+fn header_str<'a>(headers: &'a HeaderMap, name: header::HeaderName) -> Option<&'a str> {
+    headers.get(name).and_then(|v| v.to_str().ok())
+}
+
+/// `If-None-Match` lists entity tags; `*` matches any current file.
+fn matches_etag(list: &str, etag: &str) -> bool {
+    list.split(',').map(str::trim).any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
+}
+
+/// Download one vault file. Supports `Range` (a single byte range), `If-Range`, and
+/// `If-None-Match` against the file's `ETag`, so interrupted downloads resume where they stopped.
+pub async fn download_file(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Query(query): Query<DownloadQuery>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    let owner_id = query.owner_id.map(UserId::from_uuid).unwrap_or_else(|| user.id.clone());
+    let scope = match scope::resolve(&*state.delegation_repo, &user.id, &owner_id).await {
+        Ok(scope) => scope,
+        Err(e) => return (StatusCode::FORBIDDEN, e).into_response(),
+    };
+    let stat = match download_file::stat(&*state.vault_storage, &owner_id, &query.path, &scope).await {
+        Ok(stat) => stat,
+        Err(e) if e.contains("not found") => return (StatusCode::NOT_FOUND, e).into_response(),
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+
+    if header_str(&headers, header::IF_NONE_MATCH).is_some_and(|list| matches_etag(list, &stat.etag)) {
+        return (StatusCode::NOT_MODIFIED, [(header::ETAG, stat.etag)]).into_response();
+    }
+    // A range is only valid against the version the client already holds part of
+    let range_header = match header_str(&headers, header::IF_RANGE) {
+        Some(tag) if tag.trim() != stat.etag => None,
+        _ => header_str(&headers, header::RANGE),
+    };
+    let (status, start, end) = match parse_range(range_header, stat.size) {
+        ByteRange::Full => (StatusCode::OK, 0, stat.size),
+        ByteRange::Partial { start, end } => (StatusCode::PARTIAL_CONTENT, start, end + 1),
+        ByteRange::Unsatisfiable => {
+            return (
+                StatusCode::RANGE_NOT_SATISFIABLE,
+                [(header::CONTENT_RANGE, format!("bytes */{}", stat.size))],
+            )
+                .into_response();
+        }
+    };
+
+    let filename = super::archives::download_name(query.path.rsplit('/').next(), "download");
+    match download_file::execute(&*state.vault_storage, &*state.audit_repo, &user.id, &owner_id, &query.path, start..end, &scope).await {
+        Ok(stream) => {
+            let body = Body::from_stream(stream.map(|chunk| chunk.map(Bytes::from).map_err(std::io::Error::other)));
+            let mut response = (
+                status,
+                [
+                    (header::CONTENT_TYPE, "application/octet-stream".to_string()),
+                    (header::CONTENT_LENGTH, (end - start).to_string()),
+                    (header::ACCEPT_RANGES, "bytes".to_string()),
+                    (header::ETAG, stat.etag),
+                    (header::CONTENT_DISPOSITION, format!("attachment; filename=\"{filename}\"")),
+                ],
+                body,
+            )
+                .into_response();
+            if status == StatusCode::PARTIAL_CONTENT {
+                if let Ok(value) = format!("bytes {}-{}/{}", start, end - 1, stat.size).parse() {
+                    response.headers_mut().insert(header::CONTENT_RANGE, value);
+                }
+            }
+            response
+        }
+        Err(e) if e.contains("not found") => (StatusCode::NOT_FOUND, e).into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_range_resolves_single_ranges() {
+        assert_eq!(parse_range(Some("bytes=0-99"), 1000), ByteRange::Partial { start: 0, end: 99 });
+        assert_eq!(parse_range(Some("bytes=500-"), 1000), ByteRange::Partial { start: 500, end: 999 });
+        assert_eq!(parse_range(Some("bytes=-100"), 1000), ByteRange::Partial { start: 900, end: 999 });
+        assert_eq!(parse_range(Some("bytes=900-5000"), 1000), ByteRange::Partial { start: 900, end: 999 });
+        assert_eq!(parse_range(Some("bytes=-5000"), 1000), ByteRange::Partial { start: 0, end: 999 });
+    }
+
+    #[test]
+    fn test_parse_range_falls_back_or_refuses() {
+        assert_eq!(parse_range(None, 1000), ByteRange::Full);
+        assert_eq!(parse_range(Some("items=0-1"), 1000), ByteRange::Full);
+        assert_eq!(parse_range(Some("bytes=0-1,5-6"), 1000), ByteRange::Full);
+        assert_eq!(parse_range(Some("bytes=9-3"), 1000), ByteRange::Full);
+        assert_eq!(parse_range(Some("bytes=1000-"), 1000), ByteRange::Unsatisfiable);
+        assert_eq!(parse_range(Some("bytes=-0"), 1000), ByteRange::Unsatisfiable);
+        assert_eq!(parse_range(Some("bytes=0-"), 0), ByteRange::Unsatisfiable);
+    }
+
+    #[test]
+    fn test_matches_etag() {
+        assert!(matches_etag("\"a\", \"b-1\"", "\"b-1\""));
+        assert!(matches_etag("W/\"b-1\"", "\"b-1\""));
+        assert!(matches_etag("*", "\"b-1\""));
+        assert!(!matches_etag("\"c\"", "\"b-1\""));
+    }
+}
@@ -0,0 +1,68 @@
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use crate::application::files::duplicates;
+use crate::application::owner::commands::submit_file_job;
+use crate::application::owner::scope::OwnerScope;
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+
+#[derive(serde::Deserialize)]
+pub struct CleanupRequest {
+    /// Hashes of the groups to clean up; all of them when left out
+    pub groups: Option<Vec<String>>,
+    /// Copies to keep instead of the suggested ones
+    #[serde(default)]
+    pub keep: Vec<String>,
+}
+
+fn is_owner(user: &AuthenticatedUser) -> bool {
+    user.roles.contains(&crate::domain::value_objects::user_role::UserRole::Owner)
+}
+
+/// Identical files in the caller's vault, with the copy a cleanup would keep and the space it
+/// would free.
+pub async fn get_duplicates(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> impl IntoResponse {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    match duplicates::report(&*state.file_hash_repo, &user.id).await {
+        Ok(report) => Json(report).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
+
+/// Queue a file job deleting every copy but one; poll it at `GET /api/files/jobs/{id}`.
+pub async fn clean_up_duplicates(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(req): Json<CleanupRequest>,
+) -> impl IntoResponse {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    let groups = match state.file_hash_repo.find_duplicates(&user.id).await {
+        Ok(groups) => groups,
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    };
+    let operations = duplicates::cleanup_operations(&groups, req.groups.as_deref(), &req.keep);
+    if operations.is_empty() {
+        return (StatusCode::NOT_FOUND, "No duplicates to clean up").into_response();
+    }
+    let result = submit_file_job::execute(
+        &*state.file_job_repo,
+        &*state.legal_hold_repo,
+        &*state.audit_repo,
+        &user.id,
+        user.id.clone(),
+        operations,
+        &OwnerScope::Full,
+    )
+    .await;
+    match result {
+        Ok(job) => (StatusCode::ACCEPTED, Json(job)).into_response(),
+        Err(e) if e.contains("legal hold") => (StatusCode::LOCKED, e).into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+    }
+}
@@ -0,0 +1,83 @@
+use axum::{extract::{State, Path}, http::StatusCode, response::IntoResponse, Json};
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+use crate::application::owner::commands::{cancel_file_job, get_file_job, submit_file_job};
+use crate::application::owner::scope;
+use crate::domain::entities::file_job::FileOperation;
+use crate::domain::value_objects::UserId;
+use uuid::Uuid;
+
+#[derive(serde::Deserialize)]
+pub struct FileJobRequest {
+    pub operations: Vec<FileOperation>,
+    /// Vault to operate on, for co-owners; defaults to the caller's own
+    pub owner_id: Option<Uuid>,
+}
+
+fn is_owner(user: &AuthenticatedUser) -> bool {
+    user.roles.contains(&crate::domain::value_objects::user_role::UserRole::Owner)
+}
+
+fn error_status(e: &str) -> StatusCode {
+    if e.contains("not found") { StatusCode::NOT_FOUND } else { StatusCode::BAD_REQUEST }
+}
+
+/// Queue the operations; poll `GET /api/files/jobs/{id}` for progress.
+pub async fn submit_file_job(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(req): Json<FileJobRequest>,
+) -> impl IntoResponse {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    let owner_id = req.owner_id.map(UserId::from_uuid).unwrap_or_else(|| user.id.clone());
+    let scope = match scope::resolve(&*state.delegation_repo, &user.id, &owner_id).await {
+        Ok(scope) => scope,
+        Err(e) => return (StatusCode::FORBIDDEN, e).into_response(),
+    };
+    let result = submit_file_job::execute(
+        &*state.file_job_repo,
+        &*state.legal_hold_repo,
+        &*state.audit_repo,
+        &user.id,
+        owner_id,
+        req.operations,
+        &scope,
+    )
+    .await;
+    match result {
+        Ok(job) => (StatusCode::ACCEPTED, Json(job)).into_response(),
+        Err(e) if e.contains("legal hold") => (StatusCode::LOCKED, e).into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+    }
+}
+
+pub async fn get_file_job(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(job_id): Path<Uuid>,
+) -> impl IntoResponse {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    match get_file_job::execute(&*state.file_job_repo, &user.id, &job_id).await {
+        Ok(job) => (StatusCode::OK, Json(job)).into_response(),
+        Err(e) => (error_status(&e), e).into_response(),
+    }
+}
+
+pub async fn cancel_file_job(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(job_id): Path<Uuid>,
+) -> impl IntoResponse {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    match cancel_file_job::execute(&*state.file_job_repo, &user.id, &job_id).await {
+        Ok(job) => (StatusCode::OK, Json(job)).into_response(),
+        Err(e) if e.contains("finished") => (StatusCode::CONFLICT, e).into_response(),
+        Err(e) => (error_status(&e), e).into_response(),
+    }
+}
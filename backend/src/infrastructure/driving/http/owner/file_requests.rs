@@ -0,0 +1,146 @@
+use axum::{extract::{Path, Query, State}, http::StatusCode, response::IntoResponse, Json};
+use uuid::Uuid;
+use crate::application::file_requests;
+use crate::domain::entities::file_request::FileRequest;
+use crate::domain::entities::inbox_upload::InboxStatus;
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+
+#[derive(serde::Deserialize)]
+pub struct CreateFileRequestRequest {
+    /// Where approved files go, vault-relative
+    pub folder: String,
+    #[serde(default)]
+    pub title: String,
+    /// Largest file accepted; a default applies when left out
+    pub max_file_bytes: Option<u64>,
+    /// Extensions accepted, e.g. `["pdf", "jpg"]`; empty for any
+    #[serde(default)]
+    pub allowed_extensions: Vec<String>,
+    pub expires_in_days: i64,
+}
+
+#[derive(serde::Deserialize)]
+pub struct InboxQuery {
+    pub status: Option<InboxStatus>,
+}
+
+#[derive(serde::Deserialize)]
+pub struct ApproveRequest {
+    /// Name to file it under instead of the one it was sent with
+    pub name: Option<String>,
+}
+
+fn is_owner(user: &AuthenticatedUser) -> bool {
+    user.roles.contains(&crate::domain::value_objects::user_role::UserRole::Owner)
+}
+
+fn error_status(e: &str) -> StatusCode {
+    if e.contains("not found") {
+        StatusCode::NOT_FOUND
+    } else if e.contains("already") {
+        StatusCode::CONFLICT
+    } else if e.contains("quota") {
+        StatusCode::PAYLOAD_TOO_LARGE
+    } else {
+        StatusCode::BAD_REQUEST
+    }
+}
+
+/// The caller's file requests, newest first.
+pub async fn list_file_requests(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> impl IntoResponse {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    match state.file_request_repo.find_by_owner(&user.id).await {
+        Ok(requests) => Json(requests).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
+
+/// Open an upload-only link to a folder. The link token is in the response only; senders use
+/// `/api/file-request/{token}`.
+pub async fn create_file_request(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(req): Json<CreateFileRequestRequest>,
+) -> impl IntoResponse {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    let created = FileRequest::create(user.id.clone(), &req.folder, &req.title, req.max_file_bytes, &req.allowed_extensions, req.expires_in_days);
+    let (request, secret) = match created {
+        Ok(created) => created,
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+    match file_requests::create(&*state.file_request_repo, &*state.vault_storage, &*state.audit_repo, request).await {
+        Ok(request) => (StatusCode::CREATED, Json(serde_json::json!({ "file_request": request, "token": secret }))).into_response(),
+        Err(e) if e.contains("not found") => (StatusCode::NOT_FOUND, e).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
+
+pub async fn revoke_file_request(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    match file_requests::revoke(&*state.file_request_repo, &*state.audit_repo, &user.id, &id).await {
+        Ok(request) => Json(request).into_response(),
+        Err(e) => (error_status(&e), e).into_response(),
+    }
+}
+
+/// Files sent through the caller's requests, newest first; `?status=pending` for those still
+/// waiting for a decision.
+pub async fn list_inbox(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Query(query): Query<InboxQuery>,
+) -> impl IntoResponse {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    match state.file_request_repo.find_uploads(&user.id, query.status).await {
+        Ok(uploads) => Json(uploads).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
+
+/// Move a pending file into its request's folder.
+pub async fn approve_inbox_upload(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(id): Path<Uuid>,
+    body: Option<Json<ApproveRequest>>,
+) -> impl IntoResponse {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    let name = body.and_then(|Json(b)| b.name);
+    match file_requests::approve(&state, &user.id, &id, name.as_deref()).await {
+        Ok(upload) => Json(upload).into_response(),
+        Err(e) => (error_status(&e), e).into_response(),
+    }
+}
+
+/// Delete a pending file unseen.
+pub async fn reject_inbox_upload(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    match file_requests::reject(&state, &user.id, &id).await {
+        Ok(upload) => Json(upload).into_response(),
+        Err(e) => (error_status(&e), e).into_response(),
+    }
+}
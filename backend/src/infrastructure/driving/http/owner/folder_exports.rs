@@ -0,0 +1,51 @@
+use axum::{extract::{Path, State}, http::StatusCode, response::IntoResponse, Json};
+use uuid::Uuid;
+use crate::application::folder_exports;
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+
+fn is_owner(user: &AuthenticatedUser) -> bool {
+    user.roles.contains(&crate::domain::value_objects::user_role::UserRole::Owner)
+}
+
+/// Clients' requests to take folders of the caller's vault offline, newest first.
+pub async fn list_folder_exports(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> impl IntoResponse {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    match state.folder_export_repo.find_by_owner(&user.id).await {
+        Ok(exports) => Json(exports).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
+
+pub async fn approve_folder_export(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    decide(state, user, id, true).await
+}
+
+pub async fn deny_folder_export(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    decide(state, user, id, false).await
+}
+
+async fn decide(state: AppState, user: AuthenticatedUser, id: Uuid, approve: bool) -> axum::response::Response {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    match folder_exports::decide(&*state.folder_export_repo, &*state.notification_repo, &*state.audit_repo, &user.id, &id, approve).await {
+        Ok(export) => Json(export).into_response(),
+        Err(e) if e.contains("not found") => (StatusCode::NOT_FOUND, e).into_response(),
+        Err(e) if e.contains("already decided") => (StatusCode::CONFLICT, e).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
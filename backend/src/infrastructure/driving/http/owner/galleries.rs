@@ -0,0 +1,79 @@
+use axum::{extract::{Path, State}, http::StatusCode, response::IntoResponse, Json};
+use uuid::Uuid;
+use crate::application::galleries;
+use crate::domain::entities::gallery_share::GalleryShare;
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+
+#[derive(serde::Deserialize)]
+pub struct CreateGalleryRequest {
+    /// Folder to publish, vault-relative
+    pub path: String,
+    #[serde(default)]
+    pub title: String,
+    pub password: Option<String>,
+    /// Left out for a gallery that stays open until revoked
+    pub expires_in_days: Option<i64>,
+}
+
+fn is_owner(user: &AuthenticatedUser) -> bool {
+    user.roles.contains(&crate::domain::value_objects::user_role::UserRole::Owner)
+}
+
+/// The caller's galleries, newest first, with how often each was viewed.
+pub async fn list_galleries(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> impl IntoResponse {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    match state.gallery_share_repo.find_by_owner(&user.id).await {
+        Ok(galleries) => Json(galleries).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
+
+/// Publish a folder. The link token is in the response only; visitors open
+/// `/api/gallery/{token}`.
+pub async fn create_gallery(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(req): Json<CreateGalleryRequest>,
+) -> impl IntoResponse {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    // Hashing the password takes a while, so it stays off the async workers
+    let owner_id = user.id.clone();
+    let created = tokio::task::spawn_blocking(move || {
+        GalleryShare::create(owner_id, &req.path, &req.title, req.password.as_deref(), req.expires_in_days)
+    })
+    .await;
+    let (share, secret) = match created {
+        Ok(Ok(created)) => created,
+        Ok(Err(e)) => return (StatusCode::BAD_REQUEST, e).into_response(),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    };
+    match galleries::create(&*state.gallery_share_repo, &*state.vault_storage, &*state.audit_repo, share).await {
+        Ok(share) => (StatusCode::CREATED, Json(serde_json::json!({ "gallery": share, "token": secret }))).into_response(),
+        Err(e) if e.contains("not found") => (StatusCode::NOT_FOUND, e).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
+
+pub async fn revoke_gallery(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    match galleries::revoke(&*state.gallery_share_repo, &*state.audit_repo, &user.id, &id).await {
+        Ok(share) => Json(share).into_response(),
+        Err(e) if e.contains("not found") => (StatusCode::NOT_FOUND, e).into_response(),
+        Err(e) if e.contains("already revoked") => (StatusCode::CONFLICT, e).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
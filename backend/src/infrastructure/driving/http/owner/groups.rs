@@ -0,0 +1,149 @@
+use axum::{extract::{State, Path}, http::StatusCode, response::IntoResponse, Json};
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+use crate::application::owner::commands::{
+    add_group_member, create_group, delete_group, list_groups, remove_group_member, update_group,
+};
+use crate::application::sessions::scope;
+use crate::domain::entities::invitation::GrantedPath;
+use crate::domain::value_objects::UserId;
+use uuid::Uuid;
+
+#[derive(serde::Deserialize)]
+pub struct GroupRequest {
+    pub name: String,
+    #[serde(default)]
+    pub grants: Vec<GrantedPath>,
+}
+
+#[derive(serde::Deserialize)]
+pub struct AddMemberRequest {
+    pub client_id: Uuid,
+}
+
+fn is_owner(user: &AuthenticatedUser) -> bool {
+    user.roles.contains(&crate::domain::value_objects::user_role::UserRole::Owner)
+}
+
+fn error_status(e: &str) -> StatusCode {
+    if e.contains("not found") { StatusCode::NOT_FOUND } else { StatusCode::BAD_REQUEST }
+}
+
+pub async fn list_groups(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> impl IntoResponse {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    match list_groups::execute(&*state.client_group_repo, &user.id).await {
+        Ok(groups) => (StatusCode::OK, Json(groups)).into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+    }
+}
+
+pub async fn create_group(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(req): Json<GroupRequest>,
+) -> impl IntoResponse {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    match create_group::execute(&*state.client_group_repo, &*state.audit_repo, &user.id, req.name, req.grants).await {
+        Ok(group) => (StatusCode::CREATED, Json(group)).into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+    }
+}
+
+pub async fn update_group(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(group_id): Path<Uuid>,
+    Json(req): Json<GroupRequest>,
+) -> impl IntoResponse {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    match update_group::execute(
+        &*state.client_group_repo,
+        &*state.file_permission_repo,
+        &*state.audit_repo,
+        &user.id,
+        &group_id,
+        req.name,
+        req.grants,
+    ).await {
+        Ok(group) => {
+            scope::refresh_after_change(&state, None).await;
+            (StatusCode::OK, Json(group)).into_response()
+        }
+        Err(e) => (error_status(&e), e).into_response(),
+    }
+}
+
+pub async fn delete_group(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(group_id): Path<Uuid>,
+) -> impl IntoResponse {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    match delete_group::execute(&*state.client_group_repo, &*state.file_permission_repo, &*state.audit_repo, &user.id, &group_id).await {
+        Ok(_) => {
+            scope::refresh_after_change(&state, None).await;
+            (StatusCode::OK, "Group deleted").into_response()
+        }
+        Err(e) => (error_status(&e), e).into_response(),
+    }
+}
+
+pub async fn add_group_member(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(group_id): Path<Uuid>,
+    Json(req): Json<AddMemberRequest>,
+) -> impl IntoResponse {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    match add_group_member::execute(
+        &*state.client_group_repo,
+        &*state.file_permission_repo,
+        &*state.audit_repo,
+        &user.id,
+        &group_id,
+        &UserId::from_uuid(req.client_id),
+    ).await {
+        Ok(group) => {
+            scope::refresh_after_change(&state, Some(&UserId::from_uuid(req.client_id))).await;
+            (StatusCode::OK, Json(group)).into_response()
+        }
+        Err(e) => (error_status(&e), e).into_response(),
+    }
+}
+
+pub async fn remove_group_member(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path((group_id, client_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    match remove_group_member::execute(
+        &*state.client_group_repo,
+        &*state.file_permission_repo,
+        &*state.audit_repo,
+        &user.id,
+        &group_id,
+        &UserId::from_uuid(client_id),
+    ).await {
+        Ok(group) => {
+            scope::refresh_after_change(&state, Some(&UserId::from_uuid(client_id))).await;
+            (StatusCode::OK, Json(group)).into_response()
+        }
+        Err(e) => (error_status(&e), e).into_response(),
+    }
+}
@@ -0,0 +1,56 @@
+use axum::{extract::{Path, State}, http::StatusCode, response::IntoResponse, Json};
+use uuid::Uuid;
+use crate::application::client::commands::launch_application::LaunchParameters;
+use crate::application::impersonation;
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::application_routes::{LaunchApplicationRequest, LaunchApplicationResponse};
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+
+fn is_owner(user: &AuthenticatedUser) -> bool {
+    user.roles.contains(&crate::domain::value_objects::user_role::UserRole::Owner)
+}
+
+/// Open an app as a client who agreed to it, to see their folders and apps as they do. The
+/// session is read-only and ends with the consent, at the latest after half an hour.
+pub async fn impersonate_client(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(client_id): Path<Uuid>,
+    Json(payload): Json<LaunchApplicationRequest>,
+) -> impl IntoResponse {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    let params = LaunchParameters {
+        width: payload.width,
+        height: payload.height,
+        scale_factor: payload.scale_factor,
+        framerate: payload.framerate,
+        codec: payload.codec,
+        timezone: payload.timezone,
+    };
+    let client_id = UserId::from_uuid(client_id);
+    match impersonation::start(&state, &user, &client_id, &payload.app_id, params, payload.placed_on.as_deref()).await {
+        Ok(result) => Json(LaunchApplicationResponse {
+            session_id: result.session_id,
+            websocket_url: result.websocket_url,
+        })
+        .into_response(),
+        Err((status, msg)) => (status, msg).into_response(),
+    }
+}
+
+/// Consents the caller's clients gave them, newest first, withdrawn and expired ones included.
+pub async fn list_impersonation_consents(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> impl IntoResponse {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    match state.impersonation_consent_repo.find_by_owner(&user.id).await {
+        Ok(consents) => Json(consents).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
@@ -1,6 +1,12 @@
-use axum::{extract::{State, Json}, http::StatusCode, response::IntoResponse};
+use axum::{extract::{State, Json, Query}, http::StatusCode, response::IntoResponse};
 use crate::infrastructure::AppState;
 use crate::application::owner::commands::create_invitation::{self, CreateInvitationCommand};
+use crate::application::owner::commands::list_invitations;
+use crate::application::ports::invitation_repository::{InvitationFilter, InvitationSort};
+use crate::application::ports::pagination::{PageRequest, SortDirection};
+use crate::domain::entities::invitation::InvitationStatus;
+use crate::application::owner::scope;
+use crate::domain::value_objects::UserId;
 use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
 
 #[derive(serde::Deserialize)]
@@ -8,6 +14,45 @@ pub struct InvitationRequest {
     pub invitee_email: String,
     pub granted_paths: Vec<crate::domain::entities::invitation::GrantedPath>,
     pub expires_in_hours: Option<i64>,
+    #[serde(default)]
+    pub require_email_verification: bool,
+    /// Vault to invite into, for co-owners; defaults to the caller's own
+    pub owner_id: Option<uuid::Uuid>,
+}
+
+#[derive(serde::Deserialize)]
+pub struct ListInvitationsQuery {
+    pub status: Option<InvitationStatus>,
+    pub email: Option<String>,
+    #[serde(default)]
+    pub sort: InvitationSort,
+    pub order: Option<SortDirection>,
+    pub limit: Option<u32>,
+    pub cursor: Option<String>,
+}
+
+pub async fn list_invitations(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Query(query): Query<ListInvitationsQuery>,
+) -> impl IntoResponse {
+    if !user.roles.contains(&crate::domain::value_objects::user_role::UserRole::Owner) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    let page = match PageRequest::new(query.limit, query.cursor.as_deref(), query.order) {
+        Ok(page) => page,
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+    let filter = InvitationFilter {
+        owner_id: user.id.clone(),
+        status: query.status,
+        email: query.email,
+        sort: query.sort,
+    };
+    match list_invitations::execute(&*state.invitation_repo, &filter, &page).await {
+        Ok(page) => (StatusCode::OK, Json(page)).into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+    }
 }
 
 pub async fn create_invitation(
@@ -18,14 +63,24 @@ pub async fn create_invitation(
     if !user.roles.contains(&crate::domain::value_objects::user_role::UserRole::Owner) {
         return (StatusCode::FORBIDDEN, "Not an owner").into_response();
     }
+    // The invitee could never receive a code
+    if req.require_email_verification && state.email_sender.is_none() {
+        return (StatusCode::BAD_REQUEST, "Email verification requires email delivery to be configured").into_response();
+    }
+    let owner_id = req.owner_id.map(UserId::from_uuid).unwrap_or_else(|| user.id.clone());
+    let scope = match scope::resolve(&*state.delegation_repo, &user.id, &owner_id).await {
+        Ok(scope) => scope,
+        Err(e) => return (StatusCode::FORBIDDEN, e).into_response(),
+    };
     let cmd = CreateInvitationCommand {
-        owner_id: user.id.clone(),
+        owner_id,
         invitee_email: req.invitee_email,
         granted_paths: req.granted_paths,
         expires_in_hours: req.expires_in_hours,
+        require_email_verification: req.require_email_verification,
     };
     let base_url = std::env::var("BASE_URL").unwrap_or_else(|_| "http://localhost:5173".to_string());
-    match create_invitation::execute(&*state.invitation_repo, cmd, &base_url).await {
+    match create_invitation::execute(&*state.invitation_repo, cmd, &scope, &base_url).await {
         Ok(res) => (StatusCode::OK, Json(serde_json::json!({
             "invitation_id": res.invitation_id,
             "token": res.token,
@@ -0,0 +1,96 @@
+use axum::{extract::{Path, Query, State}, http::StatusCode, response::IntoResponse, Json};
+use chrono::{DateTime, Utc};
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+use crate::application::owner::commands::{list_legal_holds, place_legal_hold, release_legal_hold};
+use crate::application::owner::commands::place_legal_hold::PlaceLegalHoldCommand;
+use crate::application::owner::scope;
+use crate::application::sessions::scope as session_scope;
+use crate::domain::value_objects::UserId;
+use uuid::Uuid;
+
+#[derive(serde::Deserialize)]
+pub struct LegalHoldQuery {
+    /// Vault to look at, for co-owners; defaults to the caller's own
+    pub owner_id: Option<Uuid>,
+}
+
+#[derive(serde::Deserialize)]
+pub struct PlaceLegalHoldRequest {
+    pub path: String,
+    pub reason: String,
+    /// Kept until released when absent
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Vault to protect, for co-owners; defaults to the caller's own
+    pub owner_id: Option<Uuid>,
+}
+
+pub async fn list_legal_holds(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Query(query): Query<LegalHoldQuery>,
+) -> impl IntoResponse {
+    if !user.roles.contains(&crate::domain::value_objects::user_role::UserRole::Owner) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    let owner_id = query.owner_id.map(UserId::from_uuid).unwrap_or_else(|| user.id.clone());
+    let scope = match scope::resolve(&*state.delegation_repo, &user.id, &owner_id).await {
+        Ok(scope) => scope,
+        Err(e) => return (StatusCode::FORBIDDEN, e).into_response(),
+    };
+    match list_legal_holds::execute(&*state.legal_hold_repo, &owner_id, &scope).await {
+        Ok(holds) => (StatusCode::OK, Json(holds)).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
+
+/// Protect a file or folder from deletion and moves until the hold expires or is released.
+pub async fn place_legal_hold(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(req): Json<PlaceLegalHoldRequest>,
+) -> impl IntoResponse {
+    if !user.roles.contains(&crate::domain::value_objects::user_role::UserRole::Owner) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    let owner_id = req.owner_id.map(UserId::from_uuid).unwrap_or_else(|| user.id.clone());
+    let scope = match scope::resolve(&*state.delegation_repo, &user.id, &owner_id).await {
+        Ok(scope) => scope,
+        Err(e) => return (StatusCode::FORBIDDEN, e).into_response(),
+    };
+    let cmd = PlaceLegalHoldCommand { owner_id, path: req.path, reason: req.reason, expires_at: req.expires_at };
+    match place_legal_hold::execute(&*state.legal_hold_repo, &*state.audit_repo, &user.id, cmd, &scope).await {
+        Ok(hold) => {
+            // Apps already running may still change what is now held
+            session_scope::refresh_after_change(&state, None).await;
+            (StatusCode::CREATED, Json(hold)).into_response()
+        }
+        Err(e) if e.contains("delegated") => (StatusCode::FORBIDDEN, e).into_response(),
+        Err(e) if e.contains("Hold") || e.contains("Invalid path") => (StatusCode::BAD_REQUEST, e).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
+
+pub async fn release_legal_hold(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(hold_id): Path<Uuid>,
+) -> impl IntoResponse {
+    if !user.roles.contains(&crate::domain::value_objects::user_role::UserRole::Owner) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    let result = release_legal_hold::execute(
+        &*state.legal_hold_repo,
+        &*state.delegation_repo,
+        &*state.audit_repo,
+        &user.id,
+        &hold_id,
+    )
+    .await;
+    match result {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) if e.contains("not found") => (StatusCode::NOT_FOUND, e).into_response(),
+        Err(e) if e.contains("already released") => (StatusCode::CONFLICT, e).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
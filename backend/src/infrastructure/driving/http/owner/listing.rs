@@ -0,0 +1,115 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use std::collections::HashMap;
+use crate::application::comments;
+use crate::application::owner::commands::list_files;
+use crate::application::owner::scope;
+use crate::application::ports::{PageRequest, VaultEntry};
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+use uuid::Uuid;
+
+#[derive(serde::Deserialize)]
+pub struct ListQuery {
+    /// Folder to list; the vault root when left out
+    #[serde(default)]
+    pub path: String,
+    /// Measure each subfolder, which can be slow the first time on a large vault
+    #[serde(default)]
+    pub include_sizes: bool,
+    /// Vault to list, for co-owners; defaults to the caller's own
+    pub owner_id: Option<Uuid>,
+    /// Page size; with it, or with `cursor`, one page comes back in the paged envelope
+    pub limit: Option<u32>,
+    pub cursor: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+pub struct EntryDto {
+    pub name: String,
+    pub path: String,
+    pub is_dir: bool,
+    /// Null for folders unless sizes were asked for and could be measured
+    pub size: Option<u64>,
+    pub modified: Option<chrono::DateTime<chrono::Utc>>,
+    pub mime_type: &'static str,
+    /// Comments on the file; always 0 for folders
+    pub comment_count: u64,
+}
+
+impl EntryDto {
+    fn new(entry: VaultEntry, comment_counts: &HashMap<String, u64>) -> Self {
+        Self {
+            comment_count: comment_counts.get(&entry.path).copied().unwrap_or(0),
+            name: entry.name,
+            path: entry.path,
+            is_dir: entry.is_dir,
+            size: entry.size,
+            modified: entry.modified,
+            mime_type: entry.mime_type,
+        }
+    }
+}
+
+fn is_owner(user: &AuthenticatedUser) -> bool {
+    user.roles.contains(&crate::domain::value_objects::user_role::UserRole::Owner)
+}
+
+/// List a vault folder, directories first, with how many comments each file has.
+/// `?include_sizes=true` fills in folder sizes. `?limit=` pages through large folders: the
+/// response is then `{items, next_cursor, total_estimate}`, continued with `?cursor=`.
+pub async fn list_files(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Query(query): Query<ListQuery>,
+) -> impl IntoResponse {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    let owner_id = query.owner_id.map(UserId::from_uuid).unwrap_or_else(|| user.id.clone());
+    let scope = match scope::resolve(&*state.delegation_repo, &user.id, &owner_id).await {
+        Ok(scope) => scope,
+        Err(e) => return (StatusCode::FORBIDDEN, e).into_response(),
+    };
+    if query.limit.is_some() || query.cursor.is_some() {
+        let request = match PageRequest::new(query.limit, query.cursor.as_deref(), None) {
+            Ok(request) => request,
+            Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+        };
+        return match list_files::page(&*state.vault_storage, &owner_id, &query.path, query.include_sizes, &scope, &request).await {
+            Ok(page) => {
+                let counts = comment_counts(&state, &owner_id, &query.path).await;
+                Json(page.map(|entry| EntryDto::new(entry, &counts))).into_response()
+            }
+            Err(e) => list_error(e),
+        };
+    }
+    match list_files::execute(&*state.vault_storage, &owner_id, &query.path, query.include_sizes, &scope).await {
+        Ok(entries) => {
+            let counts = comment_counts(&state, &owner_id, &query.path).await;
+            Json(entries.into_iter().map(|entry| EntryDto::new(entry, &counts)).collect::<Vec<_>>()).into_response()
+        }
+        Err(e) => list_error(e),
+    }
+}
+
+/// Comments per file in the folder; a listing without them beats no listing.
+async fn comment_counts(state: &AppState, owner_id: &UserId, path: &str) -> HashMap<String, u64> {
+    comments::counts(&*state.file_comment_repo, owner_id, path).await.unwrap_or_else(|e| {
+        tracing::warn!("Failed to count comments in {}: {}", path, e);
+        HashMap::new()
+    })
+}
+
+fn list_error(e: String) -> axum::response::Response {
+    if e.contains("not found") {
+        (StatusCode::NOT_FOUND, e).into_response()
+    } else {
+        (StatusCode::BAD_REQUEST, e).into_response()
+    }
+}
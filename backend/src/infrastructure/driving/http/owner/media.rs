@@ -0,0 +1,67 @@
+use axum::{extract::{Query, State}, http::StatusCode, response::IntoResponse, Json};
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+
+/// Most results a search returns
+const SEARCH_LIMIT: u32 = 500;
+
+#[derive(serde::Deserialize)]
+pub struct MetadataQuery {
+    pub path: String,
+}
+
+#[derive(serde::Deserialize)]
+pub struct MediaSearchQuery {
+    pub year: Option<i32>,
+    pub camera: Option<String>,
+}
+
+fn is_owner(user: &AuthenticatedUser) -> bool {
+    user.roles.contains(&crate::domain::value_objects::user_role::UserRole::Owner)
+}
+
+/// What was read from one of the caller's photos or videos once it was uploaded or imported.
+pub async fn get_metadata(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Query(query): Query<MetadataQuery>,
+) -> impl IntoResponse {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    match state.media_metadata_repo.find(&user.id, &query.path).await {
+        Ok(Some(metadata)) => Json(metadata).into_response(),
+        Ok(None) => (StatusCode::NOT_FOUND, "No metadata for this file").into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
+
+/// The caller's media taken in a year and/or with a camera, most recent first.
+pub async fn search_media(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Query(query): Query<MediaSearchQuery>,
+) -> impl IntoResponse {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    let camera = query.camera.as_deref().map(str::trim).filter(|c| !c.is_empty());
+    match state.media_metadata_repo.search(&user.id, query.year, camera, SEARCH_LIMIT).await {
+        Ok(media) => Json(media).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
+
+/// Years and cameras the caller's media spreads over, with how many files each.
+pub async fn get_facets(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> impl IntoResponse {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    match state.media_metadata_repo.facets(&user.id).await {
+        Ok(facets) => Json(facets).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
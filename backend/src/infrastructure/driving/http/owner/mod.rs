@@ -1,2 +1,29 @@
 pub mod invitations;
 pub mod permissions;
+pub mod accounts;
+pub mod access_policy;
+pub mod groups;
+pub mod delegations;
+pub mod audit;
+pub mod sessions;
+pub mod file_jobs;
+pub mod archives;
+pub mod downloads;
+pub mod uploads;
+pub mod bandwidth;
+pub mod client_exports;
+pub mod client_accounts;
+pub mod provisioning_tokens;
+pub mod legal_holds;
+pub mod app_settings;
+pub mod branding;
+pub mod processing;
+pub mod media;
+pub mod organization_rules;
+pub mod duplicates;
+pub mod folder_exports;
+pub mod galleries;
+pub mod file_requests;
+pub mod listing;
+pub mod usage;
+pub mod impersonation;
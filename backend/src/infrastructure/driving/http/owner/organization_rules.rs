@@ -0,0 +1,152 @@
+use axum::{extract::{Path, Query, State}, http::StatusCode, response::IntoResponse, Json};
+use crate::application::organization;
+use crate::application::ports::audit_repository::AuditFilter;
+use crate::application::ports::pagination::{PageRequest, SortDirection};
+use crate::domain::entities::organization_rule::OrganizationRule;
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+use uuid::Uuid;
+
+#[derive(serde::Deserialize)]
+pub struct RuleRequest {
+    pub name: String,
+    pub source: String,
+    pub destination: String,
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+#[derive(serde::Deserialize)]
+pub struct ActivityQuery {
+    pub order: Option<SortDirection>,
+    pub limit: Option<u32>,
+    pub cursor: Option<String>,
+}
+
+fn is_owner(user: &AuthenticatedUser) -> bool {
+    user.roles.contains(&crate::domain::value_objects::user_role::UserRole::Owner)
+}
+
+fn error_status(e: &str) -> StatusCode {
+    if e.contains("not found") { StatusCode::NOT_FOUND } else { StatusCode::BAD_REQUEST }
+}
+
+impl RuleRequest {
+    fn into_rule(self, user: &AuthenticatedUser) -> Result<OrganizationRule, String> {
+        let mut rule = OrganizationRule::new(user.id.clone(), &self.name, &self.source, &self.destination)?;
+        rule.enabled = self.enabled;
+        Ok(rule)
+    }
+}
+
+/// The caller's rules, in the order they are tried.
+pub async fn list_rules(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> impl IntoResponse {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    match state.organization_rule_repo.find_by_owner(&user.id).await {
+        Ok(rules) => Json(rules).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
+
+pub async fn create_rule(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(req): Json<RuleRequest>,
+) -> impl IntoResponse {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    let rule = match req.into_rule(&user) {
+        Ok(rule) => rule,
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+    match organization::create(&*state.organization_rule_repo, &*state.audit_repo, rule).await {
+        Ok(rule) => (StatusCode::CREATED, Json(rule)).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
+
+pub async fn update_rule(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(rule_id): Path<Uuid>,
+    Json(req): Json<RuleRequest>,
+) -> impl IntoResponse {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    let changes = match req.into_rule(&user) {
+        Ok(rule) => rule,
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+    match organization::update(&*state.organization_rule_repo, &*state.audit_repo, &rule_id, changes).await {
+        Ok(rule) => Json(rule).into_response(),
+        Err(e) => (error_status(&e), e).into_response(),
+    }
+}
+
+pub async fn delete_rule(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(rule_id): Path<Uuid>,
+) -> impl IntoResponse {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    match organization::delete(&*state.organization_rule_repo, &*state.audit_repo, &user.id, &rule_id).await {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => (error_status(&e), e).into_response(),
+    }
+}
+
+/// Dry run of a rule, saved or not: where the media already in its source folder would go.
+pub async fn preview_rule(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(req): Json<RuleRequest>,
+) -> impl IntoResponse {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    let rule = match req.into_rule(&user) {
+        Ok(rule) => rule,
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+    match organization::preview(&*state.media_metadata_repo, &rule).await {
+        Ok(moves) => Json(moves).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
+
+/// Files the caller's rules moved, most recent first unless `order=asc`.
+pub async fn list_activity(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Query(query): Query<ActivityQuery>,
+) -> impl IntoResponse {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    let page = match PageRequest::new(query.limit, query.cursor.as_deref(), query.order) {
+        Ok(page) => page,
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+    let filter = AuditFilter {
+        owner_id: Some(user.id.clone()),
+        event_type: Some(organization::RULE_APPLIED.to_string()),
+        ..Default::default()
+    };
+    match state.audit_repo.list(&filter, &page).await {
+        Ok(page) => Json(page).into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+    }
+}
@@ -1,12 +1,44 @@
 use axum::{extract::{State, Query, Path}, http::StatusCode, response::IntoResponse, Json};
 use crate::infrastructure::AppState;
 use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
-use crate::application::owner::commands::{list_permissions, revoke_permission};
+use crate::application::owner::commands::{get_permission_history, list_permissions, renew_permission, revoke_permission};
+use crate::application::owner::scope;
+use crate::application::sessions::scope as session_scope;
+use crate::application::ports::file_permission_repository::{PermissionFilter, PermissionSort, PermissionStatus};
+use crate::application::ports::pagination::{PageRequest, SortDirection};
+use crate::domain::value_objects::UserId;
 use uuid::Uuid;
 
 #[derive(serde::Deserialize)]
 pub struct ListPermissionsQuery {
-    pub client_id: Option<String>,
+    pub client_id: Option<Uuid>,
+    /// Vault to list, for co-owners; defaults to the caller's own
+    pub owner_id: Option<Uuid>,
+    #[serde(default)]
+    pub status: PermissionStatus,
+    pub path_prefix: Option<String>,
+    #[serde(default)]
+    pub sort: PermissionSort,
+    pub order: Option<SortDirection>,
+    pub limit: Option<u32>,
+    pub cursor: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+pub struct PermissionHistoryQuery {
+    /// Folder to look at; the whole vault when empty
+    #[serde(default)]
+    pub path: String,
+    /// Defaults to now
+    pub at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Vault to look at, for co-owners; defaults to the caller's own
+    pub owner_id: Option<Uuid>,
+}
+
+#[derive(serde::Deserialize)]
+pub struct RenewPermissionRequest {
+    /// Defaults to the permission's original grant length
+    pub extend_hours: Option<i64>,
 }
 
 pub async fn list_permissions(
@@ -17,13 +49,24 @@ pub async fn list_permissions(
     if !user.roles.contains(&crate::domain::value_objects::user_role::UserRole::Owner) {
         return (StatusCode::FORBIDDEN, "Not an owner").into_response();
     }
-    let client_id = query
-        .client_id
-        .as_ref()
-        .and_then(|s| uuid::Uuid::parse_str(s).ok())
-        .map(crate::domain::value_objects::UserId::from_uuid);
-    match list_permissions::execute(&*state.file_permission_repo, &user.id, client_id.as_ref()).await {
-        Ok(perms) => (StatusCode::OK, Json(perms)).into_response(),
+    let page = match PageRequest::new(query.limit, query.cursor.as_deref(), query.order) {
+        Ok(page) => page,
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+    let owner_id = query.owner_id.map(UserId::from_uuid).unwrap_or_else(|| user.id.clone());
+    let scope = match scope::resolve(&*state.delegation_repo, &user.id, &owner_id).await {
+        Ok(scope) => scope,
+        Err(e) => return (StatusCode::FORBIDDEN, e).into_response(),
+    };
+    let filter = PermissionFilter {
+        client_id: query.client_id.map(UserId::from_uuid),
+        status: query.status,
+        path_prefix: query.path_prefix,
+        sort: query.sort,
+        ..Default::default()
+    };
+    match list_permissions::execute(&*state.file_permission_repo, &owner_id, filter, &page, &scope).await {
+        Ok(page) => (StatusCode::OK, Json(page)).into_response(),
         Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
     }
 }
@@ -36,8 +79,63 @@ pub async fn revoke_permission(
     if !user.roles.contains(&crate::domain::value_objects::user_role::UserRole::Owner) {
         return (StatusCode::FORBIDDEN, "Not an owner").into_response();
     }
-    match revoke_permission::execute(&*state.file_permission_repo, &permission_id).await {
-        Ok(_) => (StatusCode::OK, "Permission revoked").into_response(),
+    match revoke_permission::execute(&*state.file_permission_repo, &*state.delegation_repo, &user.id, &permission_id).await {
+        Ok(permission) => {
+            session_scope::refresh_after_change(&state, Some(&permission.client_id)).await;
+            (StatusCode::OK, "Permission revoked").into_response()
+        }
+        Err(e) if e.contains("not found") => (StatusCode::NOT_FOUND, e).into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+    }
+}
+
+/// One-click renewal: an empty body extends by the original grant length.
+pub async fn renew_permission(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(permission_id): Path<Uuid>,
+    body: Option<Json<RenewPermissionRequest>>,
+) -> impl IntoResponse {
+    if !user.roles.contains(&crate::domain::value_objects::user_role::UserRole::Owner) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    let extend_hours = body.and_then(|Json(b)| b.extend_hours);
+    match renew_permission::execute(
+        &*state.file_permission_repo,
+        &*state.delegation_repo,
+        &*state.notification_repo,
+        &*state.audit_repo,
+        &user.id,
+        &permission_id,
+        extend_hours,
+    ).await {
+        Ok(permission) => {
+            session_scope::refresh_after_change(&state, Some(&permission.client_id)).await;
+            (StatusCode::OK, Json(permission)).into_response()
+        }
         Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
     }
 }
+
+/// Who could access a folder at a point in time, replayed from the permission history.
+pub async fn permission_history(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Query(query): Query<PermissionHistoryQuery>,
+) -> impl IntoResponse {
+    if !user.roles.contains(&crate::domain::value_objects::user_role::UserRole::Owner) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    let owner_id = query.owner_id.map(UserId::from_uuid).unwrap_or_else(|| user.id.clone());
+    let scope = match scope::resolve(&*state.delegation_repo, &user.id, &owner_id).await {
+        Ok(scope) => scope,
+        Err(e) => return (StatusCode::FORBIDDEN, e).into_response(),
+    };
+    let at = query.at.unwrap_or_else(chrono::Utc::now);
+    match get_permission_history::execute(&*state.file_permission_repo, &owner_id, &query.path, at, &scope).await {
+        Ok(history) => (StatusCode::OK, Json(history)).into_response(),
+        Err(e) if e.contains("authority") => (StatusCode::FORBIDDEN, e).into_response(),
+        Err(e) if e.contains("Invalid path") => (StatusCode::BAD_REQUEST, e).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
@@ -0,0 +1,39 @@
+use axum::{extract::{State, Path}, http::StatusCode, response::IntoResponse, Json};
+use crate::application::processing;
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+use uuid::Uuid;
+
+fn is_owner(user: &AuthenticatedUser) -> bool {
+    user.roles.contains(&crate::domain::value_objects::user_role::UserRole::Owner)
+}
+
+/// Files a processing stage kept failing on, with the last error.
+pub async fn list_dead_letters(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> impl IntoResponse {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    match state.processing_repo.list_dead_letters(&user.id).await {
+        Ok(tasks) => Json(tasks).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
+
+/// Run a dead-lettered file through the stages it has left again.
+pub async fn retry_dead_letter(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(task_id): Path<Uuid>,
+) -> impl IntoResponse {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    match processing::retry(&*state.processing_repo, &user.id, &task_id).await {
+        Ok(task) => (StatusCode::ACCEPTED, Json(task)).into_response(),
+        Err(e) if e.contains("not found") => (StatusCode::NOT_FOUND, e).into_response(),
+        Err(e) => (StatusCode::CONFLICT, e).into_response(),
+    }
+}
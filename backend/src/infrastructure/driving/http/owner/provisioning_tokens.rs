@@ -0,0 +1,60 @@
+use axum::{extract::{Path, State}, http::StatusCode, response::IntoResponse, Json};
+use uuid::Uuid;
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+use crate::application::provisioning;
+use crate::domain::value_objects::user_role::UserRole;
+
+#[derive(serde::Deserialize)]
+pub struct CreateTokenRequest {
+    pub name: String,
+}
+
+pub async fn list_tokens(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> impl IntoResponse {
+    if !user.roles.contains(&UserRole::Owner) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    match state.provisioning_repo.list_tokens(&user.id).await {
+        Ok(tokens) => (StatusCode::OK, Json(tokens)).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
+
+/// The token is in the response only; store it in the script's secrets.
+pub async fn create_token(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(req): Json<CreateTokenRequest>,
+) -> impl IntoResponse {
+    if !user.roles.contains(&UserRole::Owner) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    match provisioning::issue_token(&state, &user.id, &req.name).await {
+        Ok((token, secret)) => (StatusCode::CREATED, Json(serde_json::json!({
+            "id": token.id,
+            "name": token.name,
+            "token": secret,
+            "created_at": token.created_at,
+        }))).into_response(),
+        Err(e) if e.contains("Token name") => (StatusCode::BAD_REQUEST, e).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
+
+pub async fn revoke_token(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(token_id): Path<Uuid>,
+) -> impl IntoResponse {
+    if !user.roles.contains(&UserRole::Owner) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    match provisioning::revoke_token(&state, &user.id, &token_id).await {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) if e.contains("not found") => (StatusCode::NOT_FOUND, e).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
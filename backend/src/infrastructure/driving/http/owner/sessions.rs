@@ -0,0 +1,46 @@
+use axum::{extract::{State, Query}, http::StatusCode, response::IntoResponse, Json};
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+use crate::application::owner::commands::list_vault_sessions;
+use crate::application::ports::pagination::{PageRequest, SortDirection};
+use crate::application::ports::session_repository::{SessionFilter, SessionSort};
+use crate::domain::value_objects::UserId;
+use uuid::Uuid;
+
+#[derive(serde::Deserialize)]
+pub struct ListVaultSessionsQuery {
+    pub client_id: Option<Uuid>,
+    pub state: Option<String>,
+    pub app_id: Option<String>,
+    #[serde(default)]
+    pub sort: SessionSort,
+    pub order: Option<SortDirection>,
+    pub limit: Option<u32>,
+    pub cursor: Option<String>,
+}
+
+/// Sessions clients ran inside the caller's vault.
+pub async fn list_vault_sessions(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Query(query): Query<ListVaultSessionsQuery>,
+) -> impl IntoResponse {
+    if !user.roles.contains(&crate::domain::value_objects::user_role::UserRole::Owner) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    let page = match PageRequest::new(query.limit, query.cursor.as_deref(), query.order) {
+        Ok(page) => page,
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+    let filter = SessionFilter {
+        user_id: query.client_id.map(UserId::from_uuid),
+        state: query.state,
+        app_id: query.app_id,
+        sort: query.sort,
+        ..Default::default()
+    };
+    match list_vault_sessions::execute(&*state.session_repo, &user.id, filter, &page).await {
+        Ok(page) => (StatusCode::OK, Json(page)).into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+    }
+}
@@ -0,0 +1,160 @@
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::{HeaderMap, HeaderName, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use futures_util::StreamExt;
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+use crate::application::owner::commands::{append_upload, cancel_upload, complete_upload, create_upload, get_upload};
+use crate::application::owner::scope;
+use crate::domain::entities::upload_session::UploadSession;
+use crate::domain::value_objects::UserId;
+use uuid::Uuid;
+
+// Header names follow the tus 1.0 protocol, so existing resumable-upload clients can talk to it
+const UPLOAD_OFFSET: HeaderName = HeaderName::from_static("upload-offset");
+const UPLOAD_LENGTH: HeaderName = HeaderName::from_static("upload-length");
+const TUS_RESUMABLE: HeaderName = HeaderName::from_static("tus-resumable");
+
+#[derive(serde::Deserialize)]
+pub struct CreateUploadRequest {
+    /// Destination, relative to the vault
+    pub path: String,
+    pub size: u64,
+    /// Vault to upload to, for co-owners; defaults to the caller's own
+    pub owner_id: Option<Uuid>,
+}
+
+fn is_owner(user: &AuthenticatedUser) -> bool {
+    user.roles.contains(&crate::domain::value_objects::user_role::UserRole::Owner)
+}
+
+fn error_status(e: &str) -> StatusCode {
+    if e.contains("not found") {
+        StatusCode::NOT_FOUND
+    } else if e.contains("Offset mismatch")
+        || e.contains("no longer active")
+        || e.contains("already finished")
+        || e.contains("already exists")
+        || e.contains("being received")
+    {
+        StatusCode::CONFLICT
+    } else if e.contains("quota") {
+        StatusCode::PAYLOAD_TOO_LARGE
+    } else {
+        StatusCode::BAD_REQUEST
+    }
+}
+
+/// Upload state as headers, so a client can resume without parsing the body.
+fn with_upload_headers(mut response: Response, upload: &UploadSession) -> Response {
+    let headers = response.headers_mut();
+    headers.insert(UPLOAD_OFFSET, HeaderValue::from(upload.offset));
+    headers.insert(UPLOAD_LENGTH, HeaderValue::from(upload.size));
+    headers.insert(TUS_RESUMABLE, HeaderValue::from_static("1.0.0"));
+    response
+}
+
+/// Open an upload; send the bytes with `PATCH /api/files/uploads/{id}`.
+pub async fn create_upload(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(req): Json<CreateUploadRequest>,
+) -> impl IntoResponse {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    let owner_id = req.owner_id.map(UserId::from_uuid).unwrap_or_else(|| user.id.clone());
+    let scope = match scope::resolve(&*state.delegation_repo, &user.id, &owner_id).await {
+        Ok(scope) => scope,
+        Err(e) => return (StatusCode::FORBIDDEN, e).into_response(),
+    };
+    let upload = match create_upload::execute(&*state.upload_session_repo, &*state.vault_storage, &user.id, owner_id, req.path, req.size, &scope).await {
+        Ok(upload) => upload,
+        Err(e) => return (error_status(&e), e).into_response(),
+    };
+    // Nothing to wait for: an empty file completes at once
+    let upload = if upload.is_complete() {
+        match complete_upload::execute(&*state.upload_session_repo, &*state.vault_storage, &state.upload_hooks, &*state.processing_repo, &state.processors, &*state.audit_repo, upload).await {
+            Ok(upload) => upload,
+            Err(e) => return (error_status(&e), e).into_response(),
+        }
+    } else {
+        upload
+    };
+    let location = format!("/api/files/uploads/{}", upload.id);
+    let response = (StatusCode::CREATED, [(axum::http::header::LOCATION, location)], Json(&upload)).into_response();
+    with_upload_headers(response, &upload)
+}
+
+/// Current offset of an upload; `HEAD` gives the same headers without the body.
+pub async fn get_upload(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(upload_id): Path<Uuid>,
+) -> impl IntoResponse {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    match get_upload::execute(&*state.upload_session_repo, &user.id, &upload_id).await {
+        Ok(upload) => with_upload_headers((StatusCode::OK, Json(&upload)).into_response(), &upload),
+        Err(e) => (error_status(&e), e).into_response(),
+    }
+}
+
+/// Append the request body at `Upload-Offset`. The body is streamed to disk, and the last
+/// chunk moves the file into the vault.
+pub async fn append_upload(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(upload_id): Path<Uuid>,
+    headers: HeaderMap,
+    body: Body,
+) -> impl IntoResponse {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    let Some(offset) = headers
+        .get(UPLOAD_OFFSET)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok())
+    else {
+        return (StatusCode::BAD_REQUEST, "Missing or invalid Upload-Offset header").into_response();
+    };
+    let chunk = body
+        .into_data_stream()
+        .map(|data| data.map(|bytes| bytes.to_vec()).map_err(|e| e.to_string()))
+        .boxed();
+    let upload = match append_upload::execute(&*state.upload_session_repo, &*state.vault_storage, &state.appending_uploads, &user.id, &upload_id, offset, chunk).await {
+        Ok(upload) => upload,
+        Err(e) => return (error_status(&e), e).into_response(),
+    };
+    let upload = if upload.is_complete() {
+        match complete_upload::execute(&*state.upload_session_repo, &*state.vault_storage, &state.upload_hooks, &*state.processing_repo, &state.processors, &*state.audit_repo, upload).await {
+            Ok(upload) => upload,
+            // Rejected by a completion hook; the upload cannot be retried
+            Err(e) if e.contains("quota") => return (StatusCode::PAYLOAD_TOO_LARGE, e).into_response(),
+            Err(e) => return (StatusCode::UNPROCESSABLE_ENTITY, e).into_response(),
+        }
+    } else {
+        upload
+    };
+    with_upload_headers(StatusCode::NO_CONTENT.into_response(), &upload)
+}
+
+pub async fn cancel_upload(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(upload_id): Path<Uuid>,
+) -> impl IntoResponse {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    match cancel_upload::execute(&*state.upload_session_repo, &*state.vault_storage, &user.id, &upload_id).await {
+        Ok(upload) => (StatusCode::OK, Json(upload)).into_response(),
+        Err(e) => (error_status(&e), e).into_response(),
+    }
+}
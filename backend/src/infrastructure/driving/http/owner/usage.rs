@@ -0,0 +1,42 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use crate::application::files::usage;
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+
+#[derive(serde::Deserialize)]
+pub struct BreakdownQuery {
+    /// Folder to break down; the whole vault when left out
+    #[serde(default)]
+    pub path: String,
+    /// Levels of folders and files below it, at most [`usage::MAX_DEPTH`]
+    #[serde(default = "default_depth")]
+    pub depth: u32,
+}
+
+fn default_depth() -> u32 {
+    3
+}
+
+fn is_owner(user: &AuthenticatedUser) -> bool {
+    user.roles.contains(&crate::domain::value_objects::user_role::UserRole::Owner)
+}
+
+/// Space used in the caller's vault by folder and by file type, for a treemap.
+pub async fn get_usage_breakdown(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Query(query): Query<BreakdownQuery>,
+) -> impl IntoResponse {
+    if !is_owner(&user) {
+        return (StatusCode::FORBIDDEN, "Not an owner").into_response();
+    }
+    match usage::breakdown(&*state.file_hash_repo, &user.id, &query.path, query.depth).await {
+        Ok(breakdown) => Json(breakdown).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
@@ -0,0 +1,63 @@
+use axum::{extract::{Path, State}, http::StatusCode, response::IntoResponse, Json};
+use uuid::Uuid;
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+use crate::application::profile::commands::{create_my_access_token, list_my_access_tokens, revoke_my_access_token};
+use crate::domain::entities::access_token::{TokenScope, DEFAULT_LIFETIME_DAYS};
+
+#[derive(serde::Deserialize)]
+pub struct CreateAccessTokenRequest {
+    pub name: String,
+    pub scopes: Vec<TokenScope>,
+    pub expires_in_days: Option<i64>,
+}
+
+/// The caller's personal access tokens, newest first, including expired and revoked ones.
+pub async fn list_access_tokens(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> impl IntoResponse {
+    match list_my_access_tokens::execute(&*state.access_token_repo, &user.id).await {
+        Ok(tokens) => (StatusCode::OK, Json(tokens)).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
+
+/// Create a token; the response is the only time its secret is shown.
+pub async fn create_access_token(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(req): Json<CreateAccessTokenRequest>,
+) -> impl IntoResponse {
+    let result = create_my_access_token::execute(
+        &*state.access_token_repo,
+        &*state.audit_repo,
+        &user.id,
+        &user.roles,
+        &req.name,
+        req.scopes,
+        req.expires_in_days.unwrap_or(DEFAULT_LIFETIME_DAYS),
+    )
+    .await;
+    match result {
+        Ok((token, secret)) => (StatusCode::CREATED, Json(serde_json::json!({
+            "token": token,
+            "secret": secret,
+        }))).into_response(),
+        Err(e) if e.contains("Token") || e.contains("scope") || e.contains("Scope") => (StatusCode::BAD_REQUEST, e).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
+
+/// Revoke one of the caller's tokens.
+pub async fn revoke_access_token(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(token_id): Path<Uuid>,
+) -> impl IntoResponse {
+    match revoke_my_access_token::execute(&*state.access_token_repo, &*state.audit_repo, &user.id, &token_id).await {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) if e.contains("not found") => (StatusCode::NOT_FOUND, e).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
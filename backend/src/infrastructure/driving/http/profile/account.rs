@@ -0,0 +1,28 @@
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+use crate::application::account_deletion;
+
+#[derive(serde::Deserialize)]
+pub struct DeleteAccountRequest {
+    /// Must repeat the account's email, so a stray request cannot delete it
+    pub confirm_email: String,
+}
+
+/// Delete the caller's account. It stops working at once and is purged after the grace
+/// period, until which a super-admin can restore it.
+pub async fn delete_my_account(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(req): Json<DeleteAccountRequest>,
+) -> impl IntoResponse {
+    if !req.confirm_email.trim().eq_ignore_ascii_case(&user.email) {
+        return (StatusCode::BAD_REQUEST, "Confirmation email does not match").into_response();
+    }
+    match account_deletion::request(&state, &user.id, &user.id).await {
+        Ok(deletion) => (StatusCode::ACCEPTED, Json(deletion)).into_response(),
+        Err(e) if e.contains("already being deleted") => (StatusCode::CONFLICT, e).into_response(),
+        Err(e) if e.contains("last super-admin") => (StatusCode::FORBIDDEN, e).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
@@ -0,0 +1,63 @@
+use axum::{extract::{Path, State}, http::StatusCode, response::IntoResponse, Json};
+use serde::Serialize;
+use uuid::Uuid;
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+use crate::application::profile::commands::{list_my_auth_sessions, revoke_my_auth_session};
+use crate::domain::entities::auth_session::AuthSession;
+
+#[derive(Serialize)]
+pub struct AuthSessionDto {
+    pub id: Uuid,
+    pub user_agent: Option<String>,
+    pub ip: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub last_seen_at: chrono::DateTime<chrono::Utc>,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+    /// Whether this is the session making the request
+    pub current: bool,
+}
+
+impl AuthSessionDto {
+    fn from_domain(session: AuthSession, current: Option<Uuid>) -> Self {
+        Self {
+            current: current == Some(session.id),
+            id: session.id,
+            user_agent: session.user_agent,
+            ip: session.ip,
+            created_at: session.created_at,
+            last_seen_at: session.last_seen_at,
+            expires_at: session.expires_at,
+        }
+    }
+}
+
+/// Devices the caller is logged in on, most recently active first.
+pub async fn list_auth_sessions(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> impl IntoResponse {
+    match list_my_auth_sessions::execute(&state.auth_sessions, &user.id).await {
+        Ok(sessions) => {
+            let dtos: Vec<AuthSessionDto> = sessions
+                .into_iter()
+                .map(|session| AuthSessionDto::from_domain(session, user.session_id))
+                .collect();
+            (StatusCode::OK, Json(dtos)).into_response()
+        }
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
+
+/// Sign out one of the caller's devices, possibly the current one.
+pub async fn revoke_auth_session(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(session_id): Path<Uuid>,
+) -> impl IntoResponse {
+    match revoke_my_auth_session::execute(&state.auth_sessions, &user.id, &session_id).await {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) if e.contains("not found") => (StatusCode::NOT_FOUND, e).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
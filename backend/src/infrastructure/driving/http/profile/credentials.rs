@@ -0,0 +1,48 @@
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use serde::Serialize;
+use webauthn_rs::prelude::CredentialID;
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+use crate::application::profile::commands::list_my_credentials;
+use crate::domain::Credential;
+use crate::domain::entities::credential::CredentialMetadata;
+
+#[derive(Serialize)]
+pub struct CredentialDto {
+    /// Base64url credential id, as the browser reports it
+    pub id: CredentialID,
+    #[serde(flatten)]
+    pub metadata: CredentialMetadata,
+    pub sign_count: u32,
+    pub sign_count_anomalies: u32,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub last_used_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl CredentialDto {
+    fn from_domain(credential: &Credential) -> Self {
+        let usage = credential.usage();
+        Self {
+            id: credential.passkey().cred_id().clone(),
+            metadata: credential.metadata().clone(),
+            sign_count: usage.sign_count,
+            sign_count_anomalies: usage.sign_count_anomalies,
+            created_at: usage.created_at,
+            last_used_at: usage.last_used_at,
+        }
+    }
+}
+
+/// The caller's registered passkeys, oldest first.
+pub async fn list_my_credentials(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> impl IntoResponse {
+    match list_my_credentials::execute(&*state.credential_repo, &user.id).await {
+        Ok(credentials) => {
+            let dtos: Vec<CredentialDto> = credentials.iter().map(CredentialDto::from_domain).collect();
+            (StatusCode::OK, Json(dtos)).into_response()
+        }
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
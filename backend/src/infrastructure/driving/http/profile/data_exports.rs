@@ -0,0 +1,70 @@
+use axum::{
+    body::{Body, Bytes},
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use futures_util::StreamExt;
+use uuid::Uuid;
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+use crate::application::data_exports;
+use crate::application::profile::commands::request_my_data_export;
+
+fn error_status(e: &str) -> StatusCode {
+    if e.contains("not found") {
+        StatusCode::NOT_FOUND
+    } else if e.contains("in progress") || e.contains("not ready") {
+        StatusCode::CONFLICT
+    } else {
+        StatusCode::INTERNAL_SERVER_ERROR
+    }
+}
+
+/// Queue an archive of the caller's data; poll `GET /api/my-data/exports/{id}` for progress.
+pub async fn request_data_export(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> impl IntoResponse {
+    match request_my_data_export::execute(&*state.data_export_repo, &*state.audit_repo, &user.id).await {
+        Ok(export) => (StatusCode::ACCEPTED, Json(export)).into_response(),
+        Err(e) => (error_status(&e), e).into_response(),
+    }
+}
+
+/// Progress of an export the caller requested, their own or a client's.
+pub async fn get_data_export(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(export_id): Path<Uuid>,
+) -> impl IntoResponse {
+    match data_exports::find(&*state.data_export_repo, &user.id, &export_id).await {
+        Ok(export) => (StatusCode::OK, Json(export)).into_response(),
+        Err(e) => (error_status(&e), e).into_response(),
+    }
+}
+
+/// Stream the zip of a completed export.
+pub async fn download_data_export(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(export_id): Path<Uuid>,
+) -> impl IntoResponse {
+    match data_exports::download(&*state.data_export_repo, &*state.vault_storage, &user.id, &export_id).await {
+        Ok((export, stream)) => {
+            let filename = format!("data-export-{}.zip", export.created_at.format("%Y-%m-%d"));
+            let body = Body::from_stream(stream.map(|chunk| chunk.map(Bytes::from).map_err(std::io::Error::other)));
+            (
+                StatusCode::OK,
+                [
+                    (header::CONTENT_TYPE, "application/zip".to_string()),
+                    (header::CONTENT_DISPOSITION, format!("attachment; filename=\"{filename}\"")),
+                ],
+                body,
+            )
+                .into_response()
+        }
+        Err(e) => (error_status(&e), e).into_response(),
+    }
+}
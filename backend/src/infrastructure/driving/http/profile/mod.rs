@@ -0,0 +1,7 @@
+pub mod auth_sessions;
+pub mod credentials;
+pub mod preferences;
+pub mod sessions;
+pub mod data_exports;
+pub mod account;
+pub mod access_tokens;
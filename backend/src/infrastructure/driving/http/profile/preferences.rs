@@ -0,0 +1,70 @@
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+use shared::{Locale, Theme};
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+use crate::application::profile::commands::{get_my_preferences, update_my_preferences};
+use crate::domain::entities::user_preferences::UserPreferences;
+
+#[derive(Serialize, Deserialize)]
+pub struct PreferencesDto {
+    pub locale: Locale,
+    pub theme: Theme,
+    pub default_width: u16,
+    pub default_height: u16,
+    pub default_framerate: u8,
+    pub keyboard_layout: String,
+    #[serde(default)]
+    pub timezone: Option<String>,
+    pub notify_email: bool,
+    pub notify_in_app: bool,
+}
+
+impl PreferencesDto {
+    fn from_domain(locale: Locale, p: UserPreferences) -> Self {
+        Self {
+            locale,
+            theme: p.theme,
+            default_width: p.default_width,
+            default_height: p.default_height,
+            default_framerate: p.default_framerate,
+            keyboard_layout: p.keyboard_layout,
+            timezone: p.timezone,
+            notify_email: p.notify_email,
+            notify_in_app: p.notify_in_app,
+        }
+    }
+}
+
+pub async fn get_preferences(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> impl IntoResponse {
+    match get_my_preferences::execute(&*state.user_repo, &*state.user_preferences_repo, &user.id).await {
+        Ok((locale, prefs)) => (StatusCode::OK, Json(PreferencesDto::from_domain(locale, prefs))).into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+    }
+}
+
+pub async fn update_preferences(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(payload): Json<PreferencesDto>,
+) -> impl IntoResponse {
+    let prefs = UserPreferences {
+        user_id: user.id.clone(),
+        theme: payload.theme,
+        default_width: payload.default_width,
+        default_height: payload.default_height,
+        default_framerate: payload.default_framerate,
+        keyboard_layout: payload.keyboard_layout,
+        timezone: payload.timezone,
+        notify_email: payload.notify_email,
+        notify_in_app: payload.notify_in_app,
+        updated_at: chrono::Utc::now(),
+    };
+    match update_my_preferences::execute(&*state.user_preferences_repo, payload.locale, prefs).await {
+        Ok(saved) => (StatusCode::OK, Json(PreferencesDto::from_domain(payload.locale, saved))).into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+    }
+}
@@ -0,0 +1,131 @@
+use axum::{extract::{State, Path, Query}, http::StatusCode, response::IntoResponse, Json};
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+use crate::application::profile::commands::{get_session_signaling, get_session_timeline, list_my_sessions};
+use crate::application::sessions::{resume, suspend};
+use crate::application::ports::pagination::{PageRequest, SortDirection};
+use crate::application::ports::session_repository::{SessionFilter, SessionSort};
+use uuid::Uuid;
+
+#[derive(serde::Deserialize)]
+pub struct ListMySessionsQuery {
+    pub state: Option<String>,
+    pub app_id: Option<String>,
+    #[serde(default)]
+    pub sort: SessionSort,
+    pub order: Option<SortDirection>,
+    pub limit: Option<u32>,
+    pub cursor: Option<String>,
+}
+
+pub async fn list_my_sessions(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Query(query): Query<ListMySessionsQuery>,
+) -> impl IntoResponse {
+    let page = match PageRequest::new(query.limit, query.cursor.as_deref(), query.order) {
+        Ok(page) => page,
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+    let filter = SessionFilter {
+        state: query.state,
+        app_id: query.app_id,
+        sort: query.sort,
+        ..Default::default()
+    };
+    match list_my_sessions::execute(&*state.session_repo, &user.id, filter, &page).await {
+        Ok(page) => (StatusCode::OK, Json(page)).into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+    }
+}
+
+/// What happened to a session from launch to cleanup, for diagnosing sessions without video.
+pub async fn get_session_timeline(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(session_id): Path<Uuid>,
+) -> impl IntoResponse {
+    match get_session_timeline::execute(&*state.session_repo, &state.session_timelines, &user.id, |tenant_id| user.administers(tenant_id), &session_id).await {
+        Ok(timeline) => (StatusCode::OK, Json(timeline)).into_response(),
+        Err(e) if e.contains("not found") => (StatusCode::NOT_FOUND, e).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
+
+#[derive(serde::Serialize)]
+pub struct SessionSignaling {
+    pub websocket_url: String,
+}
+
+/// The signaling socket of a running session, on whichever instance runs it.
+pub async fn get_session_signaling(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(session_id): Path<Uuid>,
+) -> impl IntoResponse {
+    match get_session_signaling::execute(&*state.session_repo, &state.session_affinity, &user.id, |tenant_id| user.administers(tenant_id), &session_id).await {
+        Ok(websocket_url) => (StatusCode::OK, Json(SessionSignaling { websocket_url })).into_response(),
+        Err(e) if e.contains("not found") => (StatusCode::NOT_FOUND, e).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
+
+/// Pause a running session, keeping the state its app saves for a later resume.
+pub async fn suspend_session(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(session_id): Path<Uuid>,
+) -> impl IntoResponse {
+    match suspend::execute(&state, &user, &session_id).await {
+        Ok(result) => (StatusCode::OK, Json(result)).into_response(),
+        Err((status, msg)) => (status, msg).into_response(),
+    }
+}
+
+#[derive(serde::Deserialize, Default)]
+pub struct ResumeSessionRequest {
+    /// Size of the resuming device, as for a launch; defaults to the user's preference
+    #[serde(default)]
+    pub width: Option<u16>,
+    #[serde(default)]
+    pub height: Option<u16>,
+    #[serde(default)]
+    pub scale_factor: Option<f32>,
+    /// Instance the scheduler placed this resume on, when it was redirected here
+    #[serde(default)]
+    pub placed_on: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+pub struct ResumedSession {
+    pub session_id: String,
+    pub websocket_url: String,
+}
+
+/// Relaunch a suspended session's app with its saved state, as a new session.
+pub async fn resume_session(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(session_id): Path<Uuid>,
+    body: Option<Json<ResumeSessionRequest>>,
+) -> impl IntoResponse {
+    let payload = body.map(|Json(b)| b).unwrap_or_default();
+    let result = resume::execute(
+        &state,
+        &user,
+        &session_id,
+        payload.width,
+        payload.height,
+        payload.scale_factor,
+        payload.placed_on.as_deref(),
+    )
+    .await;
+    match result {
+        Ok(launched) => (
+            StatusCode::OK,
+            Json(ResumedSession { session_id: launched.session_id, websocket_url: launched.websocket_url }),
+        )
+            .into_response(),
+        Err((status, msg)) => (status, msg).into_response(),
+    }
+}
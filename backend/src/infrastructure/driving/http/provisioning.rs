@@ -0,0 +1,128 @@
+use axum::{
+    body::Bytes,
+    extract::{FromRequestParts, State},
+    http::{request::Parts, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use crate::application::ports::{IdempotencyClaim, StoredResponse};
+use crate::application::provisioning::{self, ProvisioningRequest};
+use crate::domain::entities::provisioning_token::TOKEN_PREFIX;
+use crate::domain::services::secrets::hash_secret;
+use crate::domain::value_objects::user_role::UserRole;
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::AppState;
+
+/// The owner a provisioning token acts for, from `Authorization: Bearer pvp_…`
+#[derive(Debug, Clone)]
+pub struct ProvisioningCaller {
+    pub owner_id: UserId,
+    pub token_id: uuid::Uuid,
+}
+
+impl FromRequestParts<AppState> for ProvisioningCaller {
+    type Rejection = (StatusCode, String);
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Self::Rejection> {
+        let secret = parts
+            .headers
+            .get("Authorization")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|s| s.strip_prefix("Bearer "))
+            .filter(|s| s.starts_with(TOKEN_PREFIX))
+            .ok_or((StatusCode::UNAUTHORIZED, "Missing provisioning token".to_string()))?;
+        let token = state
+            .provisioning_repo
+            .find_token_by_hash(&hash_secret(secret))
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?
+            .filter(|token| token.is_active())
+            .ok_or((StatusCode::UNAUTHORIZED, "Invalid or revoked provisioning token".to_string()))?;
+        // A token stops working with its owner's role or account
+        let owner = state
+            .user_repo
+            .find_by_id(&token.owner_id)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+        if !owner.is_some_and(|o| o.has_role(UserRole::Owner) && !o.status().is_deleting()) {
+            return Err((StatusCode::FORBIDDEN, "The token's owner can no longer provision clients".to_string()));
+        }
+        if let Err(e) = state.provisioning_repo.touch_token(&token.id).await {
+            tracing::warn!("Failed to record use of provisioning token {}: {}", token.id, e);
+        }
+        Ok(ProvisioningCaller { owner_id: token.owner_id, token_id: token.id })
+    }
+}
+
+fn json_response(status: StatusCode, body: String) -> Response {
+    (status, [(axum::http::header::CONTENT_TYPE, "application/json")], body).into_response()
+}
+
+/// Create, update or deactivate clients in bulk. With an `Idempotency-Key` header, a retry of
+/// the same request within a day gets the first response back instead of being applied again,
+/// and one sent while the first is still being applied is refused.
+pub async fn provision_users(
+    State(state): State<AppState>,
+    caller: ProvisioningCaller,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let request: ProvisioningRequest = match serde_json::from_slice(&body) {
+        Ok(request) => request,
+        Err(e) => return (StatusCode::BAD_REQUEST, format!("Invalid request: {e}")).into_response(),
+    };
+    // Dry runs change nothing, so there is nothing to replay
+    let key = headers
+        .get("Idempotency-Key")
+        .and_then(|v| v.to_str().ok())
+        .filter(|key| !key.is_empty() && !request.dry_run);
+    if key.is_some_and(|key| key.len() > 255) {
+        return (StatusCode::BAD_REQUEST, "Idempotency-Key is longer than 255 characters").into_response();
+    }
+    let request_hash = provisioning::request_hash(&body);
+
+    if let Some(key) = key {
+        match state.provisioning_repo.claim_key(&caller.owner_id, key, &request_hash, provisioning::idempotency_window()).await {
+            Ok(IdempotencyClaim::Claimed) => {}
+            Ok(IdempotencyClaim::InProgress { request_hash: held }) if held != request_hash => {
+                return (StatusCode::UNPROCESSABLE_ENTITY, "Idempotency-Key was already used for a different request").into_response();
+            }
+            Ok(IdempotencyClaim::InProgress { .. }) => {
+                return (StatusCode::CONFLICT, "A request with this Idempotency-Key is still being applied").into_response();
+            }
+            Ok(IdempotencyClaim::Done(stored)) if stored.request_hash != request_hash => {
+                return (StatusCode::UNPROCESSABLE_ENTITY, "Idempotency-Key was already used for a different request").into_response();
+            }
+            Ok(IdempotencyClaim::Done(stored)) => {
+                let status = StatusCode::from_u16(stored.status).unwrap_or(StatusCode::OK);
+                let mut response = json_response(status, stored.body);
+                response.headers_mut().insert("Idempotent-Replayed", HeaderValue::from_static("true"));
+                return response;
+            }
+            Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+        }
+    }
+
+    let base_url = std::env::var("BASE_URL").unwrap_or_else(|_| "http://localhost:5173".to_string());
+    let report = provisioning::apply(&state, &caller.owner_id, &caller.token_id, request, &base_url)
+        .await
+        .and_then(|report| serde_json::to_string(&report).map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string())));
+    let body = match report {
+        Ok(body) => body,
+        Err(e) => {
+            // Nothing to replay, so a retry with the key is applied afresh
+            if let Some(key) = key {
+                if let Err(e) = state.provisioning_repo.release_key(&caller.owner_id, key).await {
+                    tracing::warn!("Failed to release Idempotency-Key: {}", e);
+                }
+            }
+            return e.into_response();
+        }
+    };
+    if let Some(key) = key {
+        let stored = StoredResponse { request_hash, status: StatusCode::OK.as_u16(), body: body.clone() };
+        if let Err(e) = state.provisioning_repo.save_response(&caller.owner_id, key, &stored).await {
+            tracing::warn!("Failed to store provisioning response for replay: {}", e);
+        }
+    }
+    json_response(StatusCode::OK, body)
+}
@@ -0,0 +1,26 @@
+use axum::{extract::{Path, State}, http::StatusCode, response::IntoResponse};
+use uuid::Uuid;
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+use crate::application::account_deletion;
+use crate::infrastructure::driving::http::super_admin::tenants::administers_user;
+use crate::domain::value_objects::UserId;
+
+/// Restore an account whose deletion is still in its grace period.
+pub async fn cancel_account_deletion(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(user_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let user_id = UserId::from_uuid(user_id);
+    match administers_user(&state, &user, &user_id).await {
+        Ok(true) => {}
+        Ok(false) => return (StatusCode::FORBIDDEN, "Not an admin of this user's tenant").into_response(),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+    match account_deletion::cancel(&state, &user_id, &user.id).await {
+        Ok(()) => (StatusCode::OK, "Account deletion cancelled").into_response(),
+        Err(e) if e.contains("not found") => (StatusCode::NOT_FOUND, e).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
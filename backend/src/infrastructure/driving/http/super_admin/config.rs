@@ -0,0 +1,26 @@
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+use crate::domain::entities::audit_event::AuditEvent;
+
+/// Read the settings again, as on SIGHUP, and report which changes took effect and which
+/// wait for a restart. Running sessions are left alone.
+pub async fn reload_config(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> impl IntoResponse {
+    if !user.is_platform_admin() {
+        return (StatusCode::FORBIDDEN, "Not a super admin").into_response();
+    }
+    let changes = match state.config.reload() {
+        Ok(changes) => changes,
+        Err(e) => return (StatusCode::UNPROCESSABLE_ENTITY, e).into_response(),
+    };
+
+    let mut event = AuditEvent::new("config_reloaded", serde_json::json!(changes));
+    event.user_id = Some(user.id.clone());
+    if let Err(e) = state.audit_repo.record(&event).await {
+        tracing::warn!("Failed to audit config reload: {}", e);
+    }
+    (StatusCode::OK, Json(changes)).into_response()
+}
@@ -0,0 +1,31 @@
+use axum::{extract::{Query, State}, http::StatusCode, response::IntoResponse, Json};
+use crate::application::ports::pagination::{PageRequest, SortDirection};
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+
+#[derive(serde::Deserialize)]
+pub struct ListCrashReportsQuery {
+    pub app_id: Option<String>,
+    pub order: Option<SortDirection>,
+    pub limit: Option<u32>,
+    pub cursor: Option<String>,
+}
+
+/// Panics SDK apps reported before exiting, newest first unless `order=asc`.
+pub async fn list_crash_reports(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Query(query): Query<ListCrashReportsQuery>,
+) -> impl IntoResponse {
+    if !user.is_platform_admin() {
+        return (StatusCode::FORBIDDEN, "Not a super admin").into_response();
+    }
+    let page = match PageRequest::new(query.limit, query.cursor.as_deref(), query.order) {
+        Ok(page) => page,
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+    match state.app_crash_repo.list(query.app_id.as_deref(), &page).await {
+        Ok(page) => (StatusCode::OK, Json(page)).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
@@ -0,0 +1,172 @@
+use axum::{
+    body::{Body, Bytes},
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use futures_util::StreamExt;
+use shared::ArchiveFormat;
+use std::time::Duration;
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+use crate::infrastructure::driven::sandbox::debug_dump::DumpLimits;
+use crate::infrastructure::driven::sandbox::GStreamerManager;
+use crate::infrastructure::driven::storage::archive_stream;
+use crate::application::super_admin::commands::debug_session_dump;
+use crate::infrastructure::driving::http::super_admin::tenants::administers_session;
+use uuid::Uuid;
+
+#[derive(serde::Deserialize, Default)]
+pub struct DebugDumpRequest {
+    /// Bytes for the stream and snapshots together, capped by `DEBUG_DUMP_MAX_BYTES`
+    pub max_bytes: Option<u64>,
+    pub snapshot_interval_secs: Option<u64>,
+}
+
+async fn forbidden(state: &AppState, user: &AuthenticatedUser, session_id: &Uuid) -> Option<axum::response::Response> {
+    match administers_session(state, user, session_id).await {
+        Ok(true) => None,
+        Ok(false) => Some((StatusCode::FORBIDDEN, "Not an admin of this session's tenant").into_response()),
+        Err(e) => Some((StatusCode::INTERNAL_SERVER_ERROR, e).into_response()),
+    }
+}
+
+fn dump_limits(req: &DebugDumpRequest, ceiling: u64) -> DumpLimits {
+    let defaults = DumpLimits::default();
+    DumpLimits {
+        max_bytes: req.max_bytes.unwrap_or(ceiling).min(ceiling),
+        snapshot_interval: req
+            .snapshot_interval_secs
+            .map(|secs| Duration::from_secs(secs.max(1)))
+            .unwrap_or(defaults.snapshot_interval),
+    }
+}
+
+/// Where every session's dump lives, one directory each
+pub fn dumps_root(storage_path: &str) -> std::path::PathBuf {
+    std::path::Path::new(storage_path).join("internal").join("debug-dumps")
+}
+
+fn dump_dir(state: &AppState, session_id: &Uuid) -> std::path::PathBuf {
+    dumps_root(&state.storage_path).join(session_id.to_string())
+}
+
+/// Record a session's encoded stream (IVF) and periodic RGBA display snapshots, to tell
+/// encoder problems from browser ones. A new dump replaces the session's previous one.
+pub async fn start_debug_dump(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(session_id): Path<Uuid>,
+    Json(req): Json<DebugDumpRequest>,
+) -> impl IntoResponse {
+    if let Some(response) = forbidden(&state, &user, &session_id).await {
+        return response;
+    }
+    let started = debug_session_dump::start(
+        &*state.session_repo,
+        &*state.audit_repo,
+        &*state.notification_repo,
+        &user.id,
+        &session_id,
+    )
+    .await;
+    if let Err(e) = started {
+        return (StatusCode::NOT_FOUND, e).into_response();
+    }
+    let gstreamer = match GStreamerManager::new() {
+        Ok(gstreamer) => gstreamer,
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    };
+    let dir = dump_dir(&state, &session_id);
+    let ceiling = state.config.current().parse("DEBUG_DUMP_MAX_BYTES").unwrap_or(DumpLimits::default().max_bytes);
+    match state
+        .xvfb_manager
+        .start_debug_dump(&session_id.to_string(), &dir, dump_limits(&req, ceiling), &gstreamer)
+        .await
+    {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => (StatusCode::CONFLICT, e.to_string()).into_response(),
+    }
+}
+
+pub async fn stop_debug_dump(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(session_id): Path<Uuid>,
+) -> impl IntoResponse {
+    if let Some(response) = forbidden(&state, &user, &session_id).await {
+        return response;
+    }
+    let stopped = debug_session_dump::stop(
+        &*state.session_repo,
+        &*state.audit_repo,
+        &*state.notification_repo,
+        &user.id,
+        &session_id,
+    )
+    .await;
+    if let Err(e) = stopped {
+        return (StatusCode::NOT_FOUND, e).into_response();
+    }
+    let gstreamer = match GStreamerManager::new() {
+        Ok(gstreamer) => gstreamer,
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    };
+    match state.xvfb_manager.stop_debug_dump(&session_id.to_string(), &gstreamer).await {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+/// The session's last dump as a tar: `stream.ivf` plus `snapshot-NNNN-WxH.rgba` files.
+pub async fn download_debug_dump(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(session_id): Path<Uuid>,
+) -> impl IntoResponse {
+    if let Some(response) = forbidden(&state, &user, &session_id).await {
+        return response;
+    }
+    // The stream file is still growing while the dump runs
+    if state.xvfb_manager.is_debug_dumping(&session_id.to_string()).await {
+        return (StatusCode::CONFLICT, "Stop the debug dump before downloading it").into_response();
+    }
+    let dir = dump_dir(&state, &session_id);
+    if !dir.is_dir() {
+        return (StatusCode::NOT_FOUND, "No debug dump for this session").into_response();
+    }
+    if let Err(e) = debug_session_dump::download(&*state.session_repo, &*state.audit_repo, &user.id, &session_id).await {
+        return (StatusCode::NOT_FOUND, e).into_response();
+    }
+    let stream = archive_stream(ArchiveFormat::Tar, vec![dir]);
+    let body = Body::from_stream(stream.map(|chunk| chunk.map(Bytes::from).map_err(std::io::Error::other)));
+    (
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, ArchiveFormat::Tar.content_type().to_string()),
+            (header::CONTENT_DISPOSITION, format!("attachment; filename=\"debug-dump-{session_id}.tar\"")),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dump_limits_stay_under_the_ceiling() {
+        let defaults = DumpLimits::default();
+        let ceiling = defaults.max_bytes;
+        let huge = DebugDumpRequest { max_bytes: Some(u64::MAX), snapshot_interval_secs: Some(0) };
+        assert_eq!(dump_limits(&huge, ceiling).max_bytes, ceiling);
+        assert_eq!(dump_limits(&huge, ceiling).snapshot_interval, Duration::from_secs(1));
+        assert_eq!(dump_limits(&huge, 4096).max_bytes, 4096);
+
+        let small = DebugDumpRequest { max_bytes: Some(1024), snapshot_interval_secs: None };
+        assert_eq!(dump_limits(&small, ceiling).max_bytes, 1024);
+        assert_eq!(dump_limits(&DebugDumpRequest::default(), ceiling), defaults);
+    }
+}
@@ -0,0 +1,103 @@
+use axum::{extract::{State, Path, Query}, http::StatusCode, response::IntoResponse, Json};
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+use crate::application::super_admin::commands::{delegate_subtree, revoke_delegation};
+use crate::infrastructure::driving::http::super_admin::tenants::administers_user;
+use crate::domain::value_objects::UserId;
+use uuid::Uuid;
+
+#[derive(serde::Deserialize)]
+pub struct DelegationRequest {
+    pub owner_id: Uuid,
+    pub delegate_id: Uuid,
+    /// Vault-relative subtree the delegate administers
+    pub path: String,
+}
+
+#[derive(serde::Deserialize)]
+pub struct ListDelegationsQuery {
+    pub owner_id: Uuid,
+}
+
+/// Whether `user` administers the tenant of every one of `users`.
+async fn administers_all(state: &AppState, user: &AuthenticatedUser, users: &[UserId]) -> Result<bool, String> {
+    for id in users {
+        if !administers_user(state, user, id).await? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+pub async fn create_delegation(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(req): Json<DelegationRequest>,
+) -> impl IntoResponse {
+    let (owner_id, delegate_id) = (UserId::from_uuid(req.owner_id), UserId::from_uuid(req.delegate_id));
+    match administers_all(&state, &user, &[owner_id.clone(), delegate_id.clone()]).await {
+        Ok(true) => {}
+        Ok(false) => return (StatusCode::FORBIDDEN, "Not an admin of these users' tenant").into_response(),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+    match delegate_subtree::execute(
+        &*state.user_repo,
+        &*state.delegation_repo,
+        &*state.audit_repo,
+        &user.id,
+        owner_id,
+        delegate_id,
+        &req.path,
+    ).await {
+        Ok(delegation) => (StatusCode::CREATED, Json(delegation)).into_response(),
+        Err(e) if e.contains("not found") => (StatusCode::NOT_FOUND, e).into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+    }
+}
+
+pub async fn list_delegations(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Query(query): Query<ListDelegationsQuery>,
+) -> impl IntoResponse {
+    let owner_id = UserId::from_uuid(query.owner_id);
+    match administers_all(&state, &user, std::slice::from_ref(&owner_id)).await {
+        Ok(true) => {}
+        Ok(false) => return (StatusCode::FORBIDDEN, "Not an admin of this owner's tenant").into_response(),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+    match state.delegation_repo.find_by_owner(&owner_id).await {
+        Ok(delegations) => (StatusCode::OK, Json(delegations)).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
+
+pub async fn revoke_delegation(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(delegation_id): Path<Uuid>,
+) -> impl IntoResponse {
+    let (owner_id, delegate_id) = match state.delegation_repo.find_by_id(&delegation_id).await {
+        Ok(Some(delegation)) => (delegation.owner_id, delegation.delegate_id),
+        Ok(None) => return (StatusCode::NOT_FOUND, "Delegation not found").into_response(),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    };
+    match administers_all(&state, &user, &[owner_id]).await {
+        Ok(true) => {}
+        Ok(false) => return (StatusCode::FORBIDDEN, "Not an admin of this owner's tenant").into_response(),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+    match revoke_delegation::execute(&*state.user_repo, &*state.delegation_repo, &*state.audit_repo, &user.id, &delegation_id).await {
+        Ok(role_removed) => {
+            // Tokens carry the roles they were issued with; log the former co-owner out
+            if role_removed {
+                if let Err(e) = state.auth_sessions.revoke_all(&delegate_id).await {
+                    tracing::warn!("Failed to end sessions of former co-owner {}: {}", delegate_id, e);
+                }
+            }
+            (StatusCode::OK, "Delegation revoked").into_response()
+        }
+        Err(e) if e.contains("not found") => (StatusCode::NOT_FOUND, e).into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+    }
+}
@@ -0,0 +1,78 @@
+use axum::{extract::{State, Path}, http::StatusCode, response::IntoResponse, Json};
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+use crate::application::imports;
+use crate::domain::entities::vault_import::{ConflictPolicy, ImportMode, VaultImport};
+use crate::domain::value_objects::user_role::UserRole;
+use crate::domain::value_objects::UserId;
+use uuid::Uuid;
+
+#[derive(serde::Deserialize)]
+pub struct ImportRequest {
+    pub owner_id: Uuid,
+    /// Absolute host directory, under one of `IMPORT_ROOTS`
+    pub source: String,
+    /// Vault folder the tree is imported into
+    pub destination: String,
+    pub mode: ImportMode,
+    pub on_conflict: ConflictPolicy,
+}
+
+/// Queue the import; poll `GET /api/admin/imports/{id}` for progress.
+pub async fn start_import(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(req): Json<ImportRequest>,
+) -> impl IntoResponse {
+    if !user.is_platform_admin() {
+        return (StatusCode::FORBIDDEN, "Not a super admin").into_response();
+    }
+    let owner_id = UserId::from_uuid(req.owner_id);
+    match state.user_repo.find_by_id(&owner_id).await {
+        Ok(Some(owner)) if owner.has_role(UserRole::Owner) => {}
+        Ok(Some(_)) => return (StatusCode::BAD_REQUEST, "Imports go into an owner's vault").into_response(),
+        Ok(None) => return (StatusCode::NOT_FOUND, "Owner not found").into_response(),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+    let import = match VaultImport::new(owner_id, user.id.clone(), &req.source, &req.destination, req.mode, req.on_conflict) {
+        Ok(import) => import,
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+    let roots = imports::import_roots(state.config.current().get("IMPORT_ROOTS"));
+    match imports::queue(&*state.vault_import_repo, &*state.audit_repo, import, &roots).await {
+        Ok(import) => (StatusCode::ACCEPTED, Json(import)).into_response(),
+        Err(e) if e.contains("IMPORT_ROOTS") => (StatusCode::BAD_REQUEST, e).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
+
+pub async fn get_import(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(import_id): Path<Uuid>,
+) -> impl IntoResponse {
+    if !user.is_platform_admin() {
+        return (StatusCode::FORBIDDEN, "Not a super admin").into_response();
+    }
+    match state.vault_import_repo.find_by_id(&import_id).await {
+        Ok(Some(import)) => (StatusCode::OK, Json(import)).into_response(),
+        Ok(None) => (StatusCode::NOT_FOUND, "Import not found").into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
+
+pub async fn cancel_import(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(import_id): Path<Uuid>,
+) -> impl IntoResponse {
+    if !user.is_platform_admin() {
+        return (StatusCode::FORBIDDEN, "Not a super admin").into_response();
+    }
+    match imports::cancel(&*state.vault_import_repo, &import_id).await {
+        Ok(import) => (StatusCode::OK, Json(import)).into_response(),
+        Err(e) if e.contains("not found") => (StatusCode::NOT_FOUND, e).into_response(),
+        Err(e) if e.contains("finished") => (StatusCode::CONFLICT, e).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
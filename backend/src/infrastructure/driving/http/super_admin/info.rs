@@ -0,0 +1,24 @@
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use crate::infrastructure::AppState;
+use crate::infrastructure::driven::build_info;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+
+/// What this instance runs: version, commit, optional features and the settings in effect,
+/// secrets redacted. Settings read only at startup show their current value, which a reload
+/// may have changed without applying it.
+pub async fn get_info(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> impl IntoResponse {
+    if !user.is_platform_admin() {
+        return (StatusCode::FORBIDDEN, "Not a super admin").into_response();
+    }
+    Json(serde_json::json!({
+        "version": build_info::VERSION,
+        "build_hash": build_info::GIT_HASH,
+        "features": build_info::enabled_features(),
+        "instance_id": state.session_affinity.instance().id,
+        "config": state.config.current().redacted(),
+    }))
+    .into_response()
+}
@@ -0,0 +1,37 @@
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+use crate::application::maintenance;
+
+#[derive(serde::Deserialize)]
+pub struct MaintenanceRequest {
+    pub enabled: bool,
+    /// Shown to connected users with the warning
+    #[serde(default)]
+    pub message: Option<String>,
+    /// Refuse logins of everyone but super-admins
+    #[serde(default)]
+    pub block_logins: bool,
+}
+
+/// Put this instance in maintenance or take it out. New launches go to other hosts, or are
+/// refused; running sessions carry on. The state shows in `/health`.
+pub async fn set_maintenance(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(req): Json<MaintenanceRequest>,
+) -> impl IntoResponse {
+    if !user.is_platform_admin() {
+        return (StatusCode::FORBIDDEN, "Not a super admin").into_response();
+    }
+    let result = if req.enabled {
+        let message = req.message.map(|m| m.trim().to_string()).filter(|m| !m.is_empty());
+        maintenance::start(&state, &user.id, message, req.block_logins).await.map(|_| ())
+    } else {
+        maintenance::end(&state, &user.id).await.map(|_| ())
+    };
+    match result {
+        Ok(()) => (StatusCode::OK, Json(state.maintenance.current())).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
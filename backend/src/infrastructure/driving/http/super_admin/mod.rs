@@ -0,0 +1,13 @@
+pub mod delegations;
+pub mod debug_dumps;
+pub mod scheduler;
+pub mod render_stats;
+pub mod crash_reports;
+pub mod account_deletions;
+pub mod maintenance;
+pub mod config;
+pub mod imports;
+pub mod session_logs;
+pub mod info;
+pub mod storage;
+pub mod tenants;
@@ -0,0 +1,15 @@
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+
+/// How long each running app's frames take to draw, keyed by session, as last reported by the
+/// apps themselves. Apps that do not report are absent.
+pub async fn get_render_stats(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> impl IntoResponse {
+    if !user.is_platform_admin() {
+        return (StatusCode::FORBIDDEN, "Not a super admin").into_response();
+    }
+    Json(state.ipc_server.render_stats().await).into_response()
+}
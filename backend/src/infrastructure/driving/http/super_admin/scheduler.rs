@@ -0,0 +1,31 @@
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use serde::Serialize;
+use crate::domain::entities::placement::{HostStatus, OvercommitPolicy, PlacementDecision};
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+
+const RECENT_DECISIONS: usize = 50;
+
+#[derive(Serialize)]
+pub struct SchedulerView {
+    pub policy: OvercommitPolicy,
+    pub hosts: Vec<HostStatus>,
+    /// Most recent first
+    pub decisions: Vec<PlacementDecision>,
+}
+
+/// Hosts with their reservations and recent placements, to see why a session went where it did.
+pub async fn get_scheduler(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> impl IntoResponse {
+    if !user.is_platform_admin() {
+        return (StatusCode::FORBIDDEN, "Not a super admin").into_response();
+    }
+    let local = state.host_metrics.status(&state.xvfb_manager, state.maintenance.is_active()).await;
+    let hosts = state.scheduler.hosts(&local).await;
+    match state.scheduler.recent_decisions(RECENT_DECISIONS).await {
+        Ok(decisions) => Json(SchedulerView { policy: *state.scheduler.policy(), hosts, decisions }).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
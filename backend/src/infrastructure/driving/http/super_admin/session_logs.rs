@@ -0,0 +1,27 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+use crate::infrastructure::driving::http::super_admin::tenants::administers_session;
+
+/// Recent log lines of one session on this instance, oldest first. Only what was logged at
+/// `SESSION_LOG_LEVEL` or above is kept, and nothing survives a restart.
+pub async fn get_session_logs(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Path(session_id): Path<uuid::Uuid>,
+) -> impl IntoResponse {
+    match administers_session(&state, &user, &session_id).await {
+        Ok(true) => {}
+        Ok(false) => return (StatusCode::FORBIDDEN, "Not an admin of this session's tenant").into_response(),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+    match state.session_logs.lines(&session_id.to_string()) {
+        Some(lines) => Json(lines).into_response(),
+        None => (StatusCode::NOT_FOUND, "No logs kept for this session").into_response(),
+    }
+}
@@ -0,0 +1,16 @@
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use crate::application::storage_scheduler::DeferPolicy;
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+
+/// When the storage disk last read or wrote, and the background jobs waiting for it to wake.
+pub async fn get_storage_activity(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> impl IntoResponse {
+    if !user.is_platform_admin() {
+        return (StatusCode::FORBIDDEN, "Not a super admin").into_response();
+    }
+    let policy = DeferPolicy::from_config(&state.config.current());
+    Json(state.storage_scheduler.activity(&policy)).into_response()
+}
@@ -0,0 +1,111 @@
+use axum::{extract::{Query, State}, http::StatusCode, response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::application::tenants;
+use crate::domain::entities::tenant::Tenant;
+use crate::domain::value_objects::UserId;
+use crate::infrastructure::AppState;
+use crate::infrastructure::driving::http::middleware::auth::AuthenticatedUser;
+
+#[derive(Deserialize)]
+pub struct CreateTenantRequest {
+    pub name: String,
+}
+
+#[derive(Serialize)]
+pub struct CreatedTenant {
+    pub tenant: Tenant,
+    /// Opens the setup page for the tenant's first admin; shown only once
+    pub setup_url: String,
+}
+
+#[derive(Deserialize)]
+pub struct ListUsersQuery {
+    /// Platform admins may list another tenant's users
+    pub tenant_id: Option<Uuid>,
+}
+
+#[derive(Serialize)]
+pub struct TenantUser {
+    pub id: String,
+    pub email: String,
+    pub display_name: String,
+    pub roles: Vec<&'static str>,
+    pub status: &'static str,
+}
+
+/// Whether `admin` may administer `user_id`: the user is in a tenant they administer.
+pub(crate) async fn administers_user(state: &AppState, admin: &AuthenticatedUser, user_id: &UserId) -> Result<bool, String> {
+    let user = state.user_repo.find_by_id(user_id).await?;
+    Ok(user.is_some_and(|user| admin.administers(user.tenant_id())))
+}
+
+/// Whether `admin` may administer a session: it was run in a tenant they administer.
+pub(crate) async fn administers_session(state: &AppState, admin: &AuthenticatedUser, session_id: &Uuid) -> Result<bool, String> {
+    let session = state.session_repo.find_by_id(session_id).await?;
+    Ok(session.is_some_and(|session| admin.administers(session.tenant_id)))
+}
+
+/// Create a tenant. Its first admin registers through the returned link.
+pub async fn create_tenant(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Json(req): Json<CreateTenantRequest>,
+) -> impl IntoResponse {
+    if !user.is_platform_admin() {
+        return (StatusCode::FORBIDDEN, "Not a platform admin").into_response();
+    }
+    let (tenant, token) = match tenants::create(&state, &req.name, &user.id).await {
+        Ok(created) => created,
+        Err(e) if e.contains("already exists") => return (StatusCode::CONFLICT, e).into_response(),
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+    let origin = std::env::var("WEBAUTHN_ORIGIN").unwrap_or_else(|_| "http://localhost:5173".to_string());
+    let mut setup_url = match url::Url::parse(&origin) {
+        Ok(url) => url,
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, format!("Invalid WEBAUTHN_ORIGIN: {e}")).into_response(),
+    };
+    setup_url.query_pairs_mut().append_pair("tenant_token", &token);
+    (StatusCode::CREATED, Json(CreatedTenant { tenant, setup_url: setup_url.to_string() })).into_response()
+}
+
+pub async fn list_tenants(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+) -> impl IntoResponse {
+    if !user.is_platform_admin() {
+        return (StatusCode::FORBIDDEN, "Not a platform admin").into_response();
+    }
+    match state.tenant_repo.list().await {
+        Ok(tenants) => Json(tenants).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
+
+/// Users of the caller's tenant, or of the one a platform admin asks for.
+pub async fn list_users(
+    State(state): State<AppState>,
+    user: AuthenticatedUser,
+    Query(query): Query<ListUsersQuery>,
+) -> impl IntoResponse {
+    let tenant_id = query.tenant_id.unwrap_or(user.tenant_id);
+    if !user.administers(tenant_id) {
+        return (StatusCode::FORBIDDEN, "Not an admin of this tenant").into_response();
+    }
+    match state.user_repo.list_by_tenant(&tenant_id).await {
+        Ok(users) => {
+            let users: Vec<TenantUser> = users
+                .iter()
+                .map(|u| TenantUser {
+                    id: u.id().to_string(),
+                    email: u.email().as_str().to_string(),
+                    display_name: u.display_name().as_str().to_string(),
+                    roles: u.roles().iter().map(|r| r.as_db_str()).collect(),
+                    status: u.status().as_db_str(),
+                })
+                .collect();
+            Json(users).into_response()
+        }
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
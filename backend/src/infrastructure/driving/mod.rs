@@ -1,4 +1,11 @@
+pub mod admin_cli;
+pub mod cursor_presence;
+pub mod doctor;
+pub mod fallback_stream;
 pub mod http;
+pub mod reconnect_tokens;
+pub mod signaling_guard;
+pub mod signaling_sender;
 pub mod webrtc;
 
 pub use webrtc::WebRTCAdapter;
@@ -0,0 +1,84 @@
+//! Tokens letting a refreshed browser tab take back its still-running session. A client
+//! session holds at most one, issued once ICE connects. It stays valid while the session's
+//! signaling socket is up and for the reconnect grace after the socket drops.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+struct Grant {
+    session_id: String,
+    /// Set once the session's signaling socket dropped
+    expires_at: Option<Instant>,
+}
+
+#[derive(Default)]
+pub struct ReconnectTokens {
+    grants: Mutex<HashMap<String, Grant>>,
+}
+
+impl ReconnectTokens {
+    /// A new token for the session, replacing the one it held.
+    pub fn issue(&self, session_id: &str) -> String {
+        let token = format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+        let mut grants = self.lock();
+        grants.retain(|_, grant| grant.session_id != session_id);
+        grants.insert(token.clone(), Grant { session_id: session_id.to_string(), expires_at: None });
+        token
+    }
+
+    /// Whether the session's client was given a token to come back with.
+    pub fn has_token(&self, session_id: &str) -> bool {
+        self.lock().values().any(|grant| grant.session_id == session_id)
+    }
+
+    /// Let the session's token expire `grace` after `now`, once its signaling socket dropped.
+    pub fn expire_after(&self, session_id: &str, grace: Duration, now: Instant) {
+        for grant in self.lock().values_mut().filter(|grant| grant.session_id == session_id) {
+            grant.expires_at = Some(now + grace);
+        }
+    }
+
+    /// The session a token was issued for, taking the token, since each one binds a single
+    /// socket. `None` if unknown or expired.
+    pub fn redeem(&self, token: &str, now: Instant) -> Option<String> {
+        let grant = self.lock().remove(token)?;
+        grant.expires_at.map_or(true, |at| now < at).then_some(grant.session_id)
+    }
+
+    /// Drop the session's token once the session ends.
+    pub fn revoke(&self, session_id: &str) {
+        self.lock().retain(|_, grant| grant.session_id != session_id);
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, Grant>> {
+        self.grants.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reconnect_tokens() {
+        let tokens = ReconnectTokens::default();
+        let now = Instant::now();
+        let first = tokens.issue("s1");
+        let second = tokens.issue("s1");
+        assert_eq!(tokens.redeem(&first, now), None);
+        assert_eq!(tokens.redeem(&second, now).as_deref(), Some("s1"));
+        assert_eq!(tokens.redeem(&second, now), None);
+
+        let token = tokens.issue("s1");
+        tokens.expire_after("s1", Duration::from_secs(30), now);
+        assert!(tokens.has_token("s1"));
+        assert_eq!(tokens.redeem(&token, now + Duration::from_secs(31)), None);
+
+        let token = tokens.issue("s2");
+        tokens.revoke("s2");
+        assert!(!tokens.has_token("s2"));
+        assert_eq!(tokens.redeem(&token, now), None);
+    }
+}
@@ -0,0 +1,103 @@
+//! Flood protection for signaling sockets: a ceiling on how fast a client may send, and a
+//! deadline on every send to it (enforced by [`super::signaling_sender`]) so a client that
+//! stopped reading cannot stall the session.
+
+use std::time::{Duration, Instant};
+
+/// Limits applied to each signaling connection (`SIGNALING_MAX_MESSAGES_PER_SEC`,
+/// `SIGNALING_MESSAGE_BURST`, `SIGNALING_SEND_TIMEOUT_SECS`)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FloodLimits {
+    /// Sustained rate; pointer moves at a high refresh rate stay well below the default
+    pub messages_per_sec: u32,
+    /// Messages that may arrive at once after a quiet spell
+    pub burst: u32,
+    /// How long a send may wait for the client before it counts as a slow consumer
+    pub send_timeout: Duration,
+}
+
+impl Default for FloodLimits {
+    fn default() -> Self {
+        Self { messages_per_sec: 200, burst: 400, send_timeout: Duration::from_secs(10) }
+    }
+}
+
+impl FloodLimits {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let var = |name: &str| std::env::var(name).ok().and_then(|s| s.parse::<u32>().ok()).filter(|n| *n > 0);
+        Self {
+            messages_per_sec: var("SIGNALING_MAX_MESSAGES_PER_SEC").unwrap_or(defaults.messages_per_sec),
+            burst: var("SIGNALING_MESSAGE_BURST").unwrap_or(defaults.burst),
+            send_timeout: var("SIGNALING_SEND_TIMEOUT_SECS")
+                .map(|secs| Duration::from_secs(secs.into()))
+                .unwrap_or(defaults.send_timeout),
+        }
+    }
+}
+
+/// Token bucket over the messages a client sends
+pub struct MessageRate {
+    per_sec: f64,
+    burst: f64,
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl MessageRate {
+    pub fn new(limits: &FloodLimits, now: Instant) -> Self {
+        let burst = f64::from(limits.burst.max(1));
+        Self { per_sec: f64::from(limits.messages_per_sec), burst, tokens: burst, refilled_at: now }
+    }
+
+    /// Count a message received at `now`; false once the client is over its ceiling.
+    pub fn admit(&mut self, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.refilled_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.per_sec).min(self.burst);
+        self.refilled_at = now;
+        if self.tokens < 1.0 {
+            return false;
+        }
+        self.tokens -= 1.0;
+        true
+    }
+}
+
+/// Why a signaling connection was cut off
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Violation {
+    Flood,
+    SlowConsumer,
+    OversizedMessage,
+}
+
+impl Violation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Violation::Flood => "message_flood",
+            Violation::SlowConsumer => "slow_consumer",
+            Violation::OversizedMessage => "oversized_message",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_rate() {
+        let limits = FloodLimits { messages_per_sec: 10, burst: 5, ..FloodLimits::default() };
+        let start = Instant::now();
+        let mut rate = MessageRate::new(&limits, start);
+        assert!((0..5).all(|_| rate.admit(start)));
+        assert!(!rate.admit(start));
+        // A tenth of a second refills one message
+        assert!(rate.admit(start + Duration::from_millis(100)));
+        assert!(!rate.admit(start + Duration::from_millis(100)));
+        // A long pause refills only up to the burst
+        let later = start + Duration::from_secs(60);
+        assert!((0..5).all(|_| rate.admit(later)));
+        assert!(!rate.admit(later));
+    }
+}
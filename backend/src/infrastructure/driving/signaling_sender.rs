@@ -0,0 +1,185 @@
+//! Outbound half of a signaling socket. Messages are queued for a writer task that owns the
+//! socket, so ICE callbacks and handlers never wait on a client that stopped reading.
+
+use axum::extract::ws::{Message, WebSocket};
+use bytes::Bytes;
+use futures_util::{stream::SplitSink, SinkExt};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use tokio::sync::mpsc::{self, error::TryRecvError, error::TrySendError};
+use tokio::sync::Notify;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, Instrument};
+
+use super::webrtc::SignalingMessage;
+
+/// Signaling messages that may wait for the socket. A client this far behind is not reading,
+/// and the session cannot continue without them, so it is cut off as a slow consumer.
+const RELIABLE_BACKLOG: usize = 256;
+
+/// Stats and status updates that may wait; older ones are dropped first
+const LOSSY_BACKLOG: usize = 16;
+
+/// Fallback video frames that may wait; a full queue pushes back on the frame source, which
+/// skips ahead to the next keyframe
+const FRAME_BACKLOG: usize = 2;
+
+/// Updates dropped oldest first when the client falls behind
+#[derive(Default)]
+struct LossyQueue {
+    messages: Mutex<VecDeque<Message>>,
+    queued: Notify,
+}
+
+impl LossyQueue {
+    fn push(&self, message: Message) {
+        let mut messages = self.lock();
+        if messages.len() == LOSSY_BACKLOG {
+            messages.pop_front();
+        }
+        messages.push_back(message);
+        drop(messages);
+        self.queued.notify_one();
+    }
+
+    fn pop(&self) -> Option<Message> {
+        self.lock().pop_front()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, VecDeque<Message>> {
+        self.messages.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+struct Outbound {
+    reliable: mpsc::Sender<Message>,
+    lossy: Arc<LossyQueue>,
+    frames: mpsc::Sender<Bytes>,
+    stalled: CancellationToken,
+}
+
+/// Handle on a client's signaling socket, cheap to clone. Sending never blocks; the socket is
+/// written by a task that gives up on the client after the send timeout.
+#[derive(Clone)]
+pub struct SignalingSender {
+    inner: Arc<Outbound>,
+}
+
+impl SignalingSender {
+    /// Take over `sink`, writing to it until every handle is dropped or the client stalls.
+    pub fn spawn(sink: SplitSink<WebSocket, Message>, send_timeout: Duration) -> Self {
+        let (reliable, reliable_rx) = mpsc::channel(RELIABLE_BACKLOG);
+        let (frames, frames_rx) = mpsc::channel(FRAME_BACKLOG);
+        let lossy = Arc::new(LossyQueue::default());
+        let stalled = CancellationToken::new();
+        let writer = write(sink, reliable_rx, Arc::clone(&lossy), frames_rx, stalled.clone(), send_timeout);
+        tokio::spawn(writer.in_current_span());
+        Self { inner: Arc::new(Outbound { reliable, lossy, frames, stalled }) }
+    }
+
+    /// Queue `msg` for the client. Status updates a later one supersedes go to the lossy queue;
+    /// everything else is kept, and a client that lets it fill up is marked stalled.
+    /// False when the message was not queued.
+    pub fn send(&self, msg: &SignalingMessage) -> bool {
+        let Ok(json) = serde_json::to_string(msg) else { return false };
+        let message = Message::Text(json.into());
+        if msg.is_droppable() {
+            self.inner.lossy.push(message);
+            return true;
+        }
+        match self.inner.reliable.try_send(message) {
+            Ok(()) => true,
+            Err(TrySendError::Full(_)) => {
+                self.inner.stalled.cancel();
+                false
+            }
+            Err(TrySendError::Closed(_)) => false,
+        }
+    }
+
+    /// Queue a fallback video frame, waiting while the frame queue is full. False once the
+    /// socket is gone.
+    pub async fn send_frame(&self, frame: Bytes) -> bool {
+        self.inner.frames.send(frame).await.is_ok()
+    }
+
+    /// Send a close frame after what is already queued, then stop writing.
+    pub fn close(&self) {
+        let _ = self.inner.reliable.try_send(Message::Close(None));
+    }
+
+    /// Resolves once the client stopped taking messages.
+    pub async fn stalled(&self) {
+        self.inner.stalled.cancelled().await
+    }
+
+    /// Whether both handles write to the same socket.
+    pub fn same_socket(&self, other: &SignalingSender) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+/// Write queued messages to the socket: signaling first, then status updates, then frames.
+async fn write(
+    mut sink: SplitSink<WebSocket, Message>,
+    mut reliable: mpsc::Receiver<Message>,
+    lossy: Arc<LossyQueue>,
+    mut frames: mpsc::Receiver<Bytes>,
+    stalled: CancellationToken,
+    send_timeout: Duration,
+) {
+    loop {
+        let message = match reliable.try_recv() {
+            Ok(message) => message,
+            Err(TryRecvError::Disconnected) => break,
+            Err(TryRecvError::Empty) => match lossy.pop() {
+                Some(message) => message,
+                None => tokio::select! {
+                    biased;
+                    message = reliable.recv() => match message {
+                        Some(message) => message,
+                        None => break,
+                    },
+                    _ = lossy.queued.notified() => continue,
+                    Some(frame) = frames.recv() => Message::Binary(frame),
+                },
+            },
+        };
+        let closing = matches!(message, Message::Close(_));
+        match tokio::time::timeout(send_timeout, sink.send(message)).await {
+            Ok(Ok(())) if !closing => {}
+            Ok(Ok(())) => break,
+            Ok(Err(e)) => {
+                debug!("Signaling socket closed: {}", e);
+                break;
+            }
+            Err(_) => {
+                stalled.cancel();
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lossy_queue_drops_oldest() {
+        let queue = LossyQueue::default();
+        for n in 0..LOSSY_BACKLOG + 3 {
+            queue.push(Message::Text(n.to_string().into()));
+        }
+        let kept: Vec<_> = std::iter::from_fn(|| queue.pop())
+            .map(|message| match message {
+                Message::Text(text) => text.to_string(),
+                _ => unreachable!(),
+            })
+            .collect();
+        assert_eq!(kept.len(), LOSSY_BACKLOG);
+        assert_eq!(kept.first().map(String::as_str), Some("3"));
+        assert_eq!(kept.last(), Some(&(LOSSY_BACKLOG + 2).to_string()));
+    }
+}
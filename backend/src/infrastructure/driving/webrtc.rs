@@ -1,6 +1,26 @@
-use crate::domain::aggregates::application_session::VideoConfig;
+use crate::domain::aggregates::application_session::{
+    LatencyMonitor, LatencyPolicy, QualityLimits, StreamQuality,
+};
 use crate::infrastructure::driven::sandbox::XvfbManager;
 use crate::infrastructure::driven::sandbox::GStreamerManager;
+use crate::infrastructure::driven::sandbox::window_manager::WindowInfo;
+use crate::infrastructure::driven::sandbox::shortcuts::ShortcutGuard;
+use crate::infrastructure::driven::ice_servers::IceServers;
+use crate::infrastructure::driven::config::Config;
+use crate::infrastructure::driving::fallback_stream::{self, FallbackTap};
+use crate::infrastructure::driving::reconnect_tokens::ReconnectTokens;
+use crate::infrastructure::driving::signaling_guard::{FloodLimits, MessageRate, Violation};
+use crate::infrastructure::driving::signaling_sender::SignalingSender;
+use crate::infrastructure::driving::cursor_presence::CursorPresence;
+use crate::domain::entities::audit_event::AuditEvent;
+use crate::application::client::commands::set_stream_quality;
+use crate::application::owner::commands::watch_session;
+use crate::application::sessions::affinity::SessionLocation;
+use crate::application::sessions::bandwidth::{BandwidthAccounting, BandwidthScope};
+use crate::application::sessions::stream_budget::StreamBudgetController;
+use crate::domain::entities::stream_budget::{StreamDemand, StreamShare};
+use crate::application::sessions::timeline::{SessionTimelines, TimelineRecorder};
+use crate::domain::entities::session_timeline::{TimelineEvent, TimelineStage};
 use anyhow::Result;
 use axum::extract::{
     ws::{Message, WebSocket},
@@ -8,24 +28,34 @@ use axum::extract::{
 };
 use futures_util::{
     stream::{SplitSink, SplitStream},
-    SinkExt, StreamExt,
+    StreamExt,
 };
 use serde::{Deserialize, Serialize};
+use shared::wire::{self, check_text, DecodeError, Validate};
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{watch, RwLock};
 use tokio_util::sync::CancellationToken;
-use tracing::{debug, error, info, warn};
+use tracing::{debug, error, info, warn, Instrument};
 use uuid::Uuid;
 use webrtc::{
-    api::{media_engine::MediaEngine, APIBuilder},
-    ice_transport::ice_server::RTCIceServer,
+    api::{interceptor_registry::configure_rtcp_reports, media_engine::MediaEngine, APIBuilder},
+    data_channel::{data_channel_init::RTCDataChannelInit, RTCDataChannel},
+    ice_transport::{
+        ice_candidate::RTCIceCandidate, ice_candidate_pair::RTCIceCandidatePair,
+        ice_connection_state::RTCIceConnectionState,
+    },
+    interceptor::registry::Registry,
     peer_connection::{
         configuration::RTCConfiguration,
         peer_connection_state::RTCPeerConnectionState,
+        offer_answer_options::RTCOfferOptions,
+        policy::ice_transport_policy::RTCIceTransportPolicy,
         sdp::session_description::RTCSessionDescription, RTCPeerConnection,
     },
     rtp_transceiver::rtp_codec::RTCRtpCodecCapability,
+    stats::StatsReportType,
     track::track_local::{track_local_static_sample::TrackLocalStaticSample, TrackLocal},
 };
 
@@ -34,6 +64,8 @@ use webrtc::{
 #[serde(tag = "type", rename_all = "kebab-case")]
 pub enum SignalingMessage {
     RequestOffer,
+    /// Renegotiate ICE on the existing peer after a network change; answered with an offer
+    RestartIce,
     Offer { sdp: String },
     Answer { sdp: String },
     IceCandidate {
@@ -50,34 +82,393 @@ pub enum SignalingMessage {
     KeyDown { key: String, code: String },
     KeyUp { key: String, code: String },
     Resize { width: u32, height: u32 },
+    SetQuality {
+        framerate: u8,
+        max_bitrate: u32,
+        resolution_scale: f32,
+    },
+    QualityChanged {
+        framerate: u8,
+        max_bitrate: u32,
+        resolution_scale: f32,
+    },
+    /// Widget events from the app, announced by the browser's screen reader
+    Accessibility { events: Vec<shared::AccessibilityEvent> },
+    /// Ask the app to continue a chunked download the browser did not fully receive
+    ResumeDownload { path: String, offset: u64, etag: String },
+    /// Owners currently watching the session, shown to the client as an on-screen indicator
+    WatchStatus { watchers: usize },
+    /// Where this participant points, as fractions of the video's width and height. Sent by
+    /// watchers too: it only moves their cursor on the others' screens, never the app's.
+    PointerPosition { x: f32, y: f32 },
+    /// The pointer left the video
+    PointerLeft,
+    /// Another participant's pointer, drawn over the video with their name
+    PeerCursor { peer_id: String, name: String, controlling: bool, x: f32, y: f32 },
+    /// Another participant's pointer left the video or they left the session
+    PeerCursorGone { peer_id: String },
+    /// The session was shared without input: pointer, keyboard and window focus messages are
+    /// dropped, so the client stops capturing them. Sent when the socket opens.
+    InputDisabled,
+    /// Key combinations the client holds back from the browser and forwards, as
+    /// `Ctrl+Alt+Shift+Meta+<key>`. Other combinations with Ctrl, Alt or Meta and function keys
+    /// are left to the browser and dropped if sent. Sent when the socket opens.
+    ShortcutPolicy { capture: Vec<String> },
+    /// The stream switched in or out of the low-latency profile because of the measured RTT
+    LatencyMode { low_latency: bool, rtt_ms: u32 },
+    /// ICE selected a candidate pair for the media, sent again whenever it switches
+    IceDiagnostics { local: CandidateInfo, remote: CandidateInfo, relay_forced: bool },
+    /// Send the video over this socket because the peer connection cannot get through
+    StartFallbackStream,
+    /// Binary frames follow on this socket, to be decoded with WebCodecs as `codec`
+    FallbackStream { codec: String },
+    /// A step of the session's launch, replayed from the start when the socket opens
+    LaunchProgress {
+        stage: TimelineStage,
+        detail: Option<String>,
+        elapsed_ms: u64,
+        /// `None` for a failed launch
+        percent: Option<u8>,
+    },
+    /// The app is up, so the client can stop showing its loading state. Sent once, and again
+    /// to each later socket of the session.
+    SessionReady { source: ReadySource, ready_after_ms: u64 },
+    /// The session was suspended; the stream ends and the client stops reconnecting
+    SessionSuspended,
+    /// Kept by the client to take the session back after a tab refresh with
+    /// `/ws?reconnect=<token>`, until `grace_secs` after its socket drops. Sent each time ICE
+    /// connects, replacing the previous token.
+    ReconnectToken { token: String, grace_secs: u64 },
+    /// The instance entered or left maintenance; running sessions carry on until it ends
+    Maintenance { active: bool, message: Option<String> },
+    /// Ask for the app's windows, answered with `Windows`
+    ListWindows,
+    /// Raise a window and give it the keyboard, answered with `Windows`
+    FocusWindow { id: u32 },
+    /// The app's top-level windows, topmost first
+    Windows { windows: Vec<WindowInfo> },
     Error { message: String },
 }
 
+impl SignalingMessage {
+    /// Stats and status updates that a later one supersedes, so they may be dropped for a
+    /// client that falls behind. Everything else must arrive for the session to work.
+    pub fn is_droppable(&self) -> bool {
+        matches!(
+            self,
+            SignalingMessage::IceDiagnostics { .. }
+                | SignalingMessage::LatencyMode { .. }
+                | SignalingMessage::QualityChanged { .. }
+                | SignalingMessage::WatchStatus { .. }
+                | SignalingMessage::PeerCursor { .. }
+        )
+    }
+
+    /// Messages that drive the app, dropped for sessions without input
+    pub fn is_input(&self) -> bool {
+        matches!(
+            self,
+            SignalingMessage::MouseMove { .. }
+                | SignalingMessage::MouseDown { .. }
+                | SignalingMessage::MouseUp { .. }
+                | SignalingMessage::MouseScroll { .. }
+                | SignalingMessage::KeyDown { .. }
+                | SignalingMessage::KeyUp { .. }
+                | SignalingMessage::FocusWindow { .. }
+        )
+    }
+}
+
+/// Largest signaling message accepted from a browser; SDP offers and answers stay well below
+const MAX_SIGNALING_MESSAGE_BYTES: usize = 64 * 1024;
+
+impl Validate for SignalingMessage {
+    fn validate(&self) -> Result<(), String> {
+        match self {
+            SignalingMessage::KeyDown { key, code } | SignalingMessage::KeyUp { key, code } => {
+                check_text("key", key, 32)?;
+                check_text("code", code, 32)
+            }
+            SignalingMessage::IceCandidate { candidate, sdp_mid, .. } => {
+                check_text("candidate", candidate, 1024)?;
+                sdp_mid.as_deref().map_or(Ok(()), |mid| check_text("sdpMid", mid, 64))
+            }
+            SignalingMessage::MouseScroll { delta_y } if !delta_y.is_finite() => {
+                Err("delta_y is not a finite number".to_string())
+            }
+            SignalingMessage::SetQuality { resolution_scale, .. } if !resolution_scale.is_finite() => {
+                Err("resolution_scale is not a finite number".to_string())
+            }
+            SignalingMessage::PointerPosition { x, y } if ![x, y].iter().all(|v| (0.0..=1.0).contains(*v)) => {
+                Err("pointer position is outside the video".to_string())
+            }
+            SignalingMessage::Accessibility { events } if events.len() > 256 => {
+                Err(format!("{} accessibility events exceed the limit of 256", events.len()))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Decode a message from a signaling socket, whose peer is not trusted.
+fn decode_signaling(text: &str) -> Result<SignalingMessage, DecodeError> {
+    wire::decode(text, MAX_SIGNALING_MESSAGE_BYTES)
+}
+
+/// What decided that a session is ready
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReadySource {
+    /// The app sent `AppMessage::Ready`
+    App,
+    /// The encoder produced the first frame of an app that does not report readiness
+    FirstFrame,
+}
+
+/// An owner watching a client session, and the switch that ends the watch.
+#[derive(Clone)]
+pub struct ActiveWatch {
+    pub owner_id: crate::domain::UserId,
+    pub session: crate::domain::Session,
+    cancel: CancellationToken,
+}
+
 /// WebRTC session manager
 pub struct WebRTCAdapter {
     peers: Arc<RwLock<HashMap<String, Arc<RTCPeerConnection>>>>,
     tracks: Arc<RwLock<HashMap<String, Arc<TrackLocalStaticSample>>>>,
     cancel_tokens: Arc<RwLock<HashMap<String, CancellationToken>>>,
+    framerates: Arc<RwLock<HashMap<String, Arc<AtomicU8>>>>,
+    /// Video bytes sent to each client session since its usage was last recorded
+    sent_bytes: Arc<RwLock<HashMap<String, Arc<AtomicU64>>>>,
+    /// Signaling socket of each client session, used to tell it about watchers
+    client_senders: Arc<RwLock<HashMap<String, SignalingSender>>>,
+    watcher_counts: Arc<RwLock<HashMap<String, usize>>>,
+    /// Owners watching sessions, keyed by `watch_key(..)`, so a watch ends with the owner's grant
+    watches: Arc<RwLock<HashMap<String, ActiveWatch>>>,
+    /// Reliable channel carrying the app's download chunks to the client
+    transfer_channels: Arc<RwLock<HashMap<String, Arc<RTCDataChannel>>>>,
+    /// Unreliable channel carrying pointer updates to the client
+    cursor_channels: Arc<RwLock<HashMap<String, Arc<RTCDataChannel>>>>,
+    reconnect_tokens: Arc<ReconnectTokens>,
+    /// Switch sending a client session's frames to its signaling socket instead of the track
+    fallback_taps: Arc<RwLock<HashMap<String, Arc<FallbackTap>>>>,
+    /// `SessionReady` of each ready session, replayed to sockets that connect later
+    ready: Arc<RwLock<HashMap<String, SignalingMessage>>>,
+    /// Pointers of the client and watchers of each session, shown to one another
+    cursors: Arc<CursorPresence>,
     xvfb_manager: Arc<XvfbManager>,
+    timelines: Arc<SessionTimelines>,
+    ice_servers: Arc<IceServers>,
+}
+
+/// Server-side bounds for client quality requests (`STREAM_MAX_FRAMERATE`, `STREAM_MAX_BITRATE`).
+pub fn quality_limits(config: &Config) -> QualityLimits {
+    let defaults = QualityLimits::default();
+    QualityLimits {
+        max_framerate: config.parse("STREAM_MAX_FRAMERATE").unwrap_or(defaults.max_framerate),
+        max_bitrate: config.parse("STREAM_MAX_BITRATE").unwrap_or(defaults.max_bitrate),
+        ..defaults
+    }
+}
+
+/// How often the peer's round-trip time is sampled for the low-latency heuristic
+const RTT_SAMPLE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// How often a session's sent bytes are recorded and its bandwidth caps checked again
+const USAGE_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// RTT thresholds for automatic low-latency mode (`LOW_LATENCY_ENTER_RTT_MS`, `LOW_LATENCY_EXIT_RTT_MS`).
+/// `LOW_LATENCY_AUTO=false` keeps every stream on the user's quality.
+/// One end of the ICE candidate pair carrying the media
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CandidateInfo {
+    /// host, srflx, prflx or relay
+    pub candidate_type: String,
+    pub protocol: String,
+    pub address: String,
+    pub port: u16,
+}
+
+impl From<&RTCIceCandidate> for CandidateInfo {
+    fn from(candidate: &RTCIceCandidate) -> Self {
+        Self {
+            candidate_type: candidate.typ.to_string(),
+            protocol: candidate.protocol.to_string(),
+            address: candidate.address.clone(),
+            port: candidate.port,
+        }
+    }
+}
+
+impl std::fmt::Display for CandidateInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {}:{}", self.candidate_type, self.protocol, self.address, self.port)
+    }
+}
+
+/// Gather only TURN relay candidates, for networks where direct media never gets through
+fn force_relay() -> bool {
+    std::env::var("ICE_FORCE_RELAY")
+        .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+        .unwrap_or(false)
+}
+
+/// How long a session whose signaling socket dropped waits for the client to reconnect
+fn reconnect_grace() -> std::time::Duration {
+    let secs = std::env::var("SESSION_RECONNECT_GRACE_SECS")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(30);
+    std::time::Duration::from_secs(secs)
+}
+
+fn latency_policy() -> Option<LatencyPolicy> {
+    let disabled = std::env::var("LOW_LATENCY_AUTO")
+        .map(|v| v == "0" || v.eq_ignore_ascii_case("false"))
+        .unwrap_or(false);
+    if disabled {
+        return None;
+    }
+    let defaults = LatencyPolicy::default();
+    Some(LatencyPolicy {
+        enter_rtt_ms: std::env::var("LOW_LATENCY_ENTER_RTT_MS")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(defaults.enter_rtt_ms),
+        exit_rtt_ms: std::env::var("LOW_LATENCY_EXIT_RTT_MS")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(defaults.exit_rtt_ms),
+        ..defaults
+    })
+}
+
+/// Stream settings of one client socket
+struct StreamState {
+    /// Quality the user asked for; the low-latency profile is derived from it
+    preferred: StreamQuality,
+    monitor: LatencyMonitor,
+    limits: QualityLimits,
+    /// Bitrate the owner's bandwidth caps allow the session, if any
+    bitrate_ceiling: Option<u32>,
+    /// Part of the host's encoding budget left to this stream by the others
+    share: StreamShare,
+}
+
+impl StreamState {
+    fn new(preferred: StreamQuality, limits: QualityLimits) -> Self {
+        Self {
+            preferred,
+            monitor: LatencyMonitor::default(),
+            limits,
+            bitrate_ceiling: None,
+            share: StreamShare::default(),
+        }
+    }
+
+    /// Quality the stream would run with if it had the host to itself
+    fn wanted(&self) -> StreamQuality {
+        let quality = if self.monitor.is_low_latency() {
+            self.preferred.low_latency(&self.limits)
+        } else {
+            self.preferred
+        };
+        match self.bitrate_ceiling {
+            Some(ceiling) => quality.within_bitrate(ceiling, &self.limits),
+            None => quality,
+        }
+    }
+
+    /// Quality the pipeline currently runs with
+    fn applied(&self) -> StreamQuality {
+        let wanted = self.wanted();
+        let quality = match self.share.max_bitrate {
+            Some(max_bitrate) => wanted.within_bitrate(max_bitrate, &self.limits),
+            None => wanted,
+        };
+        match self.share.max_framerate {
+            Some(max_framerate) => StreamQuality {
+                framerate: quality.framerate.min(max_framerate).max(self.limits.min_framerate),
+                ..quality
+            },
+            None => quality,
+        }
+    }
+}
+
+/// Tells a session's client, once, that the session is ready. Usable from the frame writer
+/// thread and the app message forwarder alike.
+#[derive(Clone)]
+struct ReadyAnnouncer {
+    session_id: String,
+    ready: Arc<RwLock<HashMap<String, SignalingMessage>>>,
+    senders: Arc<RwLock<HashMap<String, SignalingSender>>>,
+    timelines: Arc<SessionTimelines>,
+}
+
+impl ReadyAnnouncer {
+    async fn announce(&self, source: ReadySource) {
+        let msg = {
+            let mut ready = self.ready.write().await;
+            if ready.contains_key(&self.session_id) {
+                return;
+            }
+            let detail = match source {
+                ReadySource::App => "app",
+                ReadySource::FirstFrame => "first frame",
+            };
+            self.timelines.record(&self.session_id, TimelineStage::SessionReady, Some(detail.to_string()));
+            let ready_after_ms = self.timelines.elapsed_at(&self.session_id, TimelineStage::SessionReady).unwrap_or(0);
+            let msg = SignalingMessage::SessionReady { source, ready_after_ms };
+            ready.insert(self.session_id.clone(), msg.clone());
+            msg
+        };
+        info!("Session {} ready ({:?})", self.session_id, source);
+        if let Some(sender) = self.senders.read().await.get(&self.session_id) {
+            sender.send(&msg);
+        }
+    }
 }
 
 impl WebRTCAdapter {
-    pub fn new(xvfb_manager: Arc<XvfbManager>) -> Self {
+    pub fn new(xvfb_manager: Arc<XvfbManager>, timelines: Arc<SessionTimelines>, ice_servers: Arc<IceServers>) -> Self {
         Self {
             peers: Arc::new(RwLock::new(HashMap::new())),
             tracks: Arc::new(RwLock::new(HashMap::new())),
             cancel_tokens: Arc::new(RwLock::new(HashMap::new())),
+            framerates: Arc::new(RwLock::new(HashMap::new())),
+            sent_bytes: Arc::new(RwLock::new(HashMap::new())),
+            client_senders: Arc::new(RwLock::new(HashMap::new())),
+            watcher_counts: Arc::new(RwLock::new(HashMap::new())),
+            watches: Arc::new(RwLock::new(HashMap::new())),
+            transfer_channels: Arc::new(RwLock::new(HashMap::new())),
+            cursor_channels: Arc::new(RwLock::new(HashMap::new())),
+            reconnect_tokens: Arc::new(ReconnectTokens::default()),
+            fallback_taps: Arc::new(RwLock::new(HashMap::new())),
+            ready: Arc::new(RwLock::new(HashMap::new())),
+            cursors: Arc::new(CursorPresence::default()),
             xvfb_manager,
+            timelines,
+            ice_servers,
         }
     }
 
-    async fn create_peer_connection(
+    fn ready_announcer(&self, session_id: &str) -> ReadyAnnouncer {
+        ReadyAnnouncer {
+            session_id: session_id.to_string(),
+            ready: Arc::clone(&self.ready),
+            senders: Arc::clone(&self.client_senders),
+            timelines: Arc::clone(&self.timelines),
+        }
+    }
+
+    /// Peer connection sending `video_track`, whose ICE candidates are relayed over `ws_sender`.
+    async fn new_peer(
         &self,
-        session_id: &str,
-        ws_sender: Arc<tokio::sync::Mutex<SplitSink<WebSocket, Message>>>,
-        gstreamer: Arc<GStreamerManager>,
-        config: &VideoConfig,
-    ) -> Result<(Arc<RTCPeerConnection>, Arc<TrackLocalStaticSample>)> {
+        ws_sender: SignalingSender,
+        video_track: Arc<TrackLocalStaticSample>,
+    ) -> Result<Arc<RTCPeerConnection>> {
         let mut media_engine = MediaEngine::default();
 
         media_engine.register_codec(
@@ -95,153 +486,208 @@ impl WebRTCAdapter {
             webrtc::rtp_transceiver::rtp_codec::RTPCodecType::Video,
         )?;
 
-        let api = APIBuilder::new().with_media_engine(media_engine).build();
-
-        let turn_server =
-            std::env::var("TURN_SERVER").unwrap_or_else(|_| "turn:localhost:3478".to_string());
-        let turn_username =
-            std::env::var("TURN_USERNAME").unwrap_or_else(|_| "sandbox".to_string());
-        let turn_credential =
-            std::env::var("TURN_CREDENTIAL").unwrap_or_else(|_| "dev_turn_secret".to_string());
+        // Sender reports let the browser's receiver reports carry the round-trip time
+        let registry = configure_rtcp_reports(Registry::new());
+        let api = APIBuilder::new()
+            .with_media_engine(media_engine)
+            .with_interceptor_registry(registry)
+            .build();
 
         let rtc_config = RTCConfiguration {
-            ice_servers: vec![
-                RTCIceServer {
-                    urls: vec!["stun:stun.l.google.com:19302".to_owned()],
-                    ..Default::default()
-                },
-                RTCIceServer {
-                    urls: vec![turn_server],
-                    username: turn_username,
-                    credential: turn_credential,
-                },
-            ],
+            ice_servers: self.ice_servers.for_session(),
+            ice_transport_policy: if force_relay() { RTCIceTransportPolicy::Relay } else { RTCIceTransportPolicy::All },
             ..Default::default()
         };
 
         let peer_connection = Arc::new(api.new_peer_connection(rtc_config).await?);
-
-        let video_track = Arc::new(TrackLocalStaticSample::new(
-            RTCRtpCodecCapability {
-                mime_type: "video/VP8".to_owned(),
-                clock_rate: 90000,
-                channels: 0,
-                sdp_fmtp_line: "".to_owned(),
-                rtcp_feedback: vec![],
-            },
-            "video".to_owned(),
-            "webrtc-rs".to_owned(),
-        ));
-
         peer_connection
-            .add_track(Arc::clone(&video_track) as Arc<dyn TrackLocal + Send + Sync>)
+            .add_track(video_track as Arc<dyn TrackLocal + Send + Sync>)
             .await?;
 
-        let width = config.width;
-        let height = config.height;
-        let framerate = config.framerate;
+        relay_ice_candidates(&peer_connection, ws_sender);
+
+        Ok(peer_connection)
+    }
+
+    async fn create_peer_connection(
+        &self,
+        session_id: &str,
+        ws_sender: SignalingSender,
+        gstreamer: Arc<GStreamerManager>,
+        quality: &StreamQuality,
+    ) -> Result<(Arc<RTCPeerConnection>, Arc<TrackLocalStaticSample>)> {
+        let video_track = vp8_track();
+        let peer_connection = self.new_peer(ws_sender, Arc::clone(&video_track)).await?;
 
         // Start capture (Xvfb and app are launched by the HTTP launch endpoint before WS connects)
-        let vp8_rx = self.xvfb_manager.start_capture(session_id, framerate, &gstreamer).await?;
+        let vp8_rx = self.xvfb_manager.start_capture(session_id, quality, &gstreamer).await?;
+
+        // Shared with set_quality so sample durations follow live framerate changes
+        let framerate = Arc::new(AtomicU8::new(quality.framerate));
+        self.framerates
+            .write()
+            .await
+            .insert(session_id.to_string(), Arc::clone(&framerate));
 
         // Set up cancel token for this session
         let cancel_token = CancellationToken::new();
-        let token_clone = cancel_token.clone();
 
         // Spawn task to read VP8 frames from GStreamer → WebRTC track
-        let video_track_for_thread = Arc::clone(&video_track);
-        tokio::task::spawn_blocking(move || {
-            while let Ok(frame_data) = vp8_rx.recv() {
-                if token_clone.is_cancelled() {
-                    break;
-                }
-                let result = tokio::runtime::Handle::current().block_on(
-                    video_track_for_thread.write_sample(&webrtc::media::Sample {
-                        data: frame_data.into(),
-                        duration: std::time::Duration::from_millis(1000 / framerate.max(1) as u64),
-                        ..Default::default()
-                    })
-                );
-                if let Err(e) = result {
-                    warn!("Failed to send VP8 sample: {}", e);
+        let timeline = self.timelines.recorder(session_id);
+        let fallback = Arc::new(FallbackTap::default());
+        self.fallback_taps
+            .write()
+            .await
+            .insert(session_id.to_string(), Arc::clone(&fallback));
+        // Apps that report readiness themselves are not ready with the first frame
+        let ready = if self.xvfb_manager.awaits_ready_signal(session_id).await {
+            None
+        } else {
+            Some(self.ready_announcer(session_id))
+        };
+        // Kept across offers, so a renegotiated stream adds to the same count
+        let sent_bytes = Arc::clone(self.sent_bytes.write().await.entry(session_id.to_string()).or_default());
+        let client = ClientStream { timeline: timeline.clone(), fallback, ready, sent_bytes };
+        spawn_sample_writer(vp8_rx, Arc::clone(&video_track), framerate, cancel_token.clone(), Some(client));
+
+        // Cursor metadata: the pointer is drawn by the browser unless baked into frames. Updates
+        // go to the channel of the session's current peer, which a refreshed tab replaces.
+        if !crate::infrastructure::driven::sandbox::gstreamer::baked_cursor_enabled() {
+            match self.xvfb_manager.start_cursor_watch(session_id).await {
+                Ok(mut cursor_rx) => {
+                    let channels = Arc::clone(&self.cursor_channels);
+                    let key = session_id.to_string();
+                    let token_clone = cancel_token.clone();
+                    tokio::spawn(
+                        async move {
+                            // A still pointer sends nothing, so cancellation is awaited alongside
+                            loop {
+                                let update = tokio::select! {
+                                    _ = token_clone.cancelled() => break,
+                                    update = cursor_rx.recv() => match update {
+                                        Some(update) => update,
+                                        None => break,
+                                    },
+                                };
+                                let Ok(json) = serde_json::to_string(&update) else { continue };
+                                let Some(channel) = channels.read().await.get(&key).cloned() else {
+                                    continue;
+                                };
+                                // The channel may not be open yet; dropped updates are superseded anyway
+                                let _ = channel.send_text(json).await;
+                            }
+                        }
+                        .instrument(tracing::Span::current()),
+                    );
                 }
+                Err(e) => warn!("Cursor metadata unavailable for session {}: {}", session_id, e),
             }
-        });
+        }
+        self.open_data_channels(session_id, &peer_connection).await?;
 
         // Store cancel token
         let mut tokens = self.cancel_tokens.write().await;
         tokens.insert(session_id.to_string(), cancel_token.clone());
         drop(tokens);
 
-        // ICE candidate handler
-        let ws_sender_clone = Arc::clone(&ws_sender);
-        peer_connection.on_ice_candidate(Box::new(
-            move |candidate: Option<webrtc::ice_transport::ice_candidate::RTCIceCandidate>| {
-                let sender = Arc::clone(&ws_sender_clone);
-                Box::pin(async move {
-                    if let Some(candidate) = candidate {
-                        match candidate.to_json() {
-                            Ok(json_candidate) => {
-                                let msg = SignalingMessage::IceCandidate {
-                                    candidate: json_candidate.candidate,
-                                    sdp_mid: json_candidate.sdp_mid,
-                                    sdp_mline_index: json_candidate
-                                        .sdp_mline_index
-                                };
-                                if let Ok(json) = serde_json::to_string(&msg) {
-                                    let mut sender_lock = sender.lock().await;
-                                    let _ = sender_lock.send(Message::Text(json.into())).await;
-                                }
-                            }
-                            Err(e) => {
-                                warn!("Failed to serialize ICE candidate: {}", e);
-                            }
-                        }
-                    }
-                })
-            },
-        ));
-
-        // Connection state handler
-        let session_id_clone = session_id.to_string();
-        let cancel_token_clone = cancel_token.clone();
-        peer_connection.on_peer_connection_state_change(Box::new(
-            move |state: RTCPeerConnectionState| {
-                let session = session_id_clone.clone();
-                let token = cancel_token_clone.clone();
-                Box::pin(async move {
-                    info!("Peer connection state changed: {}", state);
-                    match state {
-                        RTCPeerConnectionState::Failed
-                        | RTCPeerConnectionState::Disconnected
-                        | RTCPeerConnectionState::Closed => {
-                            warn!(
-                                "Connection {} failed/disconnected/closed, stopping streams",
-                                session
-                            );
-                            token.cancel();
-                        }
-                        _ => {}
-                    }
-                })
-            },
-        ));
+        // A dropped connection can be revived with an ICE restart, so the streaming tasks
+        // run until the session is cleaned up rather than stopping on disconnect
+        record_peer_progress(&peer_connection, timeline.clone(), self.reconnect_offer(session_id));
+        self.report_candidate_pairs(session_id, &peer_connection, timeline);
 
         Ok((peer_connection, video_track))
     }
 
+    /// Open the cursor and transfer channels on the session's peer, in place of those of a
+    /// peer it replaces.
+    async fn open_data_channels(&self, session_id: &str, peer_connection: &RTCPeerConnection) -> Result<()> {
+        if !crate::infrastructure::driven::sandbox::gstreamer::baked_cursor_enabled() {
+            let cursor_channel = peer_connection
+                .create_data_channel(
+                    "cursor",
+                    Some(RTCDataChannelInit {
+                        ordered: Some(false),
+                        max_retransmits: Some(0),
+                        ..Default::default()
+                    }),
+                )
+                .await?;
+            self.cursor_channels
+                .write()
+                .await
+                .insert(session_id.to_string(), cursor_channel);
+        }
+
+        // Download chunks must all arrive, in order, for the client to resume from its byte count
+        let transfer_channel = peer_connection
+            .create_data_channel(
+                "transfer",
+                Some(RTCDataChannelInit {
+                    ordered: Some(true),
+                    ..Default::default()
+                }),
+            )
+            .await?;
+        self.transfer_channels
+            .write()
+            .await
+            .insert(session_id.to_string(), transfer_channel);
+        Ok(())
+    }
+
+    /// Peer for a client that reconnected from a refreshed tab, whose browser peer is gone.
+    /// It sends the running capture's track, so the app, pipeline, watchers and dumps carry on.
+    async fn rebind_peer(
+        &self,
+        session_id: &str,
+        ws_sender: SignalingSender,
+        video_track: Arc<TrackLocalStaticSample>,
+    ) -> Result<Arc<RTCPeerConnection>> {
+        let peer_connection = self.new_peer(ws_sender, Arc::clone(&video_track)).await?;
+        self.open_data_channels(session_id, &peer_connection).await?;
+        let timeline = self.timelines.recorder(session_id);
+        record_peer_progress(&peer_connection, timeline.clone(), self.reconnect_offer(session_id));
+        self.report_candidate_pairs(session_id, &peer_connection, timeline);
+
+        if let Some(previous) = self.peers.write().await.remove(session_id) {
+            if let Err(e) = previous.close().await {
+                debug!("Closing the previous peer of session {} failed: {}", session_id, e);
+            }
+        }
+        info!("Session {} continues on a new peer connection", session_id);
+        Ok(peer_connection)
+    }
+
+    /// Hands the client a reconnect token each time its peer's ICE connects.
+    fn reconnect_offer(&self, session_id: &str) -> ReconnectOffer {
+        ReconnectOffer {
+            session_id: session_id.to_string(),
+            tokens: Arc::clone(&self.reconnect_tokens),
+            senders: Arc::clone(&self.client_senders),
+        }
+    }
+
     async fn handle_request_offer(
         &self,
         session_id: &str,
-        ws_sender: Arc<tokio::sync::Mutex<SplitSink<WebSocket, Message>>>,
+        ws_sender: SignalingSender,
         gstreamer: Arc<GStreamerManager>,
-        config: &VideoConfig,
+        quality: &StreamQuality,
     ) -> Result<String> {
         info!("Creating WebRTC offer for session: {}", session_id);
 
-        let (peer_connection, video_track) = self
-            .create_peer_connection(session_id, ws_sender, gstreamer, config)
-            .await?;
+        // A session already streaming lost its browser peer to a tab refresh
+        let running = self.tracks.read().await.get(session_id).cloned();
+        let (peer_connection, video_track) = match running {
+            Some(video_track) => {
+                let peer_connection = self.rebind_peer(session_id, ws_sender, Arc::clone(&video_track)).await?;
+                (peer_connection, video_track)
+            }
+            None => {
+                self.create_peer_connection(session_id, ws_sender, gstreamer, quality)
+                    .await?
+            }
+        };
 
         let offer = peer_connection.create_offer(None).await?;
         let offer_sdp = offer.sdp.clone();
@@ -256,6 +702,114 @@ impl WebRTCAdapter {
         Ok(offer_sdp)
     }
 
+    /// New offer for the session's peer with fresh ICE credentials. The track, data channels
+    /// and capture pipeline stay as they are, so the stream resumes once ICE reconnects.
+    async fn handle_restart_ice(
+        &self,
+        session_id: &str,
+        ws_sender: SignalingSender,
+    ) -> Result<String> {
+        let peer_connection = self
+            .peers
+            .read()
+            .await
+            .get(session_id)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Peer connection not found"))?;
+        info!("Restarting ICE for session: {}", session_id);
+
+        // The client may be on a new signaling socket after the network change
+        relay_ice_candidates(&peer_connection, ws_sender);
+        let offer = peer_connection
+            .create_offer(Some(RTCOfferOptions { ice_restart: true, ..Default::default() }))
+            .await?;
+        let offer_sdp = offer.sdp.clone();
+        peer_connection.set_local_description(offer).await?;
+        self.timelines.record(session_id, TimelineStage::IceRestarted, None);
+        Ok(offer_sdp)
+    }
+
+    /// Move the session's video onto `ws_sender` for a client whose peer connection cannot
+    /// connect. The capture keeps running; frames go back to the track if the socket drops.
+    async fn start_fallback(
+        &self,
+        session_id: &str,
+        ws_sender: SignalingSender,
+        gstreamer: &GStreamerManager,
+    ) -> Result<()> {
+        let tap = self
+            .fallback_taps
+            .read()
+            .await
+            .get(session_id)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Stream not started"))?;
+        info!("Streaming session {} over its signaling socket", session_id);
+
+        // Announced before the first frame so the client has its decoder ready
+        let msg = SignalingMessage::FallbackStream { codec: fallback_stream::CODEC.to_string() };
+        if !ws_sender.send(&msg) {
+            anyhow::bail!("Signaling socket is gone");
+        }
+        let mut frames = tap.open();
+        if let Err(e) = self.xvfb_manager.request_keyframe(session_id, gstreamer).await {
+            warn!("Fallback stream of session {} waits for the next keyframe: {}", session_id, e);
+        }
+        self.timelines.record(session_id, TimelineStage::FallbackStarted, None);
+
+        tokio::spawn(
+            async move {
+                while let Some(frame) = frames.recv().await {
+                    if !ws_sender.send_frame(frame).await {
+                        break;
+                    }
+                }
+            }
+            .in_current_span(),
+        );
+        Ok(())
+    }
+
+    /// Tell the client, and the session timeline, which candidate pair carries the media each
+    /// time ICE selects one. Sent on the session's current signaling socket.
+    fn report_candidate_pairs(&self, session_id: &str, peer_connection: &RTCPeerConnection, timeline: TimelineRecorder) {
+        let senders = Arc::clone(&self.client_senders);
+        let session_id = session_id.to_string();
+        let relay_forced = force_relay();
+        // ICE callbacks run on the WebRTC stack's tasks, outside the session's span
+        let span = tracing::Span::current();
+        peer_connection.sctp().transport().ice_transport().on_selected_candidate_pair_change(Box::new(
+            move |pair: RTCIceCandidatePair| {
+                let _entered = span.enter();
+                let (local, remote) = (CandidateInfo::from(&pair.local), CandidateInfo::from(&pair.remote));
+                info!("Media path for session {}: {} <-> {}", session_id, local, remote);
+                timeline.record(TimelineStage::CandidatePairSelected, Some(format!("{} <-> {}", local, remote)));
+                let senders = Arc::clone(&senders);
+                let session_id = session_id.clone();
+                Box::pin(async move {
+                    let msg = SignalingMessage::IceDiagnostics { local, remote, relay_forced };
+                    if let Some(sender) = senders.read().await.get(&session_id) {
+                        sender.send(&msg);
+                    }
+                })
+            },
+        ));
+    }
+
+    /// Whether `sender` is still the session's signaling socket, i.e. the client has not
+    /// reconnected on another one.
+    async fn owns_signaling(
+        &self,
+        session_id: &str,
+        sender: &SignalingSender,
+    ) -> bool {
+        self.client_senders
+            .read()
+            .await
+            .get(session_id)
+            .is_some_and(|current| current.same_socket(sender))
+    }
+
     async fn handle_answer(&self, session_id: &str, sdp: String) -> Result<()> {
         info!("Received answer from client for session: {}", session_id);
 
@@ -301,6 +855,231 @@ impl WebRTCAdapter {
         Ok(())
     }
 
+    /// Offer a read-only stream of `session_id` to a watcher, fed from a new branch of the
+    /// session's capture tee. Answers and ICE candidates are keyed by `watch_key(..)`.
+    async fn handle_watch_request_offer(
+        &self,
+        session_id: &str,
+        watch_id: &str,
+        ws_sender: SignalingSender,
+        gstreamer: Arc<GStreamerManager>,
+    ) -> Result<String> {
+        info!("Creating watch offer {} for session: {}", watch_id, session_id);
+        let key = watch_key(session_id, watch_id);
+        let framerate = self
+            .framerates
+            .read()
+            .await
+            .get(session_id)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Session {} is not streaming", session_id))?;
+
+        let video_track = vp8_track();
+        let peer_connection = self.new_peer(ws_sender, Arc::clone(&video_track)).await?;
+        let vp8_rx = self.xvfb_manager.start_watch(session_id, watch_id, &gstreamer).await?;
+
+        let cancel_token = CancellationToken::new();
+        spawn_sample_writer(vp8_rx, Arc::clone(&video_track), framerate, cancel_token.clone(), None);
+        self.cancel_tokens.write().await.insert(key.clone(), cancel_token.clone());
+        cancel_on_disconnect(&peer_connection, &key, cancel_token);
+
+        let offer = peer_connection.create_offer(None).await?;
+        let offer_sdp = offer.sdp.clone();
+        peer_connection.set_local_description(offer).await?;
+
+        self.peers.write().await.insert(key.clone(), peer_connection);
+        self.tracks.write().await.insert(key, video_track);
+        Ok(offer_sdp)
+    }
+
+    /// Tear down a watcher's stream without touching the watched session.
+    async fn cleanup_watch(&self, session_id: &str, watch_id: &str, gstreamer: &GStreamerManager) -> Result<()> {
+        let key = watch_key(session_id, watch_id);
+        if let Some(token) = self.cancel_tokens.write().await.remove(&key) {
+            token.cancel();
+        }
+        self.xvfb_manager.stop_watch(session_id, watch_id, gstreamer).await?;
+        self.tracks.write().await.remove(&key);
+        if let Some(pc) = self.peers.write().await.remove(&key) {
+            pc.close().await?;
+        }
+        Ok(())
+    }
+
+    /// Track watchers of a session and tell its client how many are attached.
+    async fn update_watchers(&self, session_id: &str, joined: bool) {
+        let watchers = {
+            let mut counts = self.watcher_counts.write().await;
+            let count = counts.entry(session_id.to_string()).or_insert(0);
+            *count = if joined { *count + 1 } else { count.saturating_sub(1) };
+            let watchers = *count;
+            if watchers == 0 {
+                counts.remove(session_id);
+            }
+            watchers
+        };
+
+        if let Some(sender) = self.client_senders.read().await.get(session_id) {
+            sender.send(&SignalingMessage::WatchStatus { watchers });
+        }
+    }
+
+    /// The watches running on this instance, keyed by `watch_key(..)`.
+    pub async fn active_watches(&self) -> Vec<(String, ActiveWatch)> {
+        self.watches.read().await.iter().map(|(key, watch)| (key.clone(), watch.clone())).collect()
+    }
+
+    /// Disconnect the watcher behind `key`; its socket cleans up as if it had left.
+    pub async fn end_watch(&self, key: &str) {
+        if let Some(watch) = self.watches.read().await.get(key) {
+            watch.cancel.cancel();
+        }
+    }
+
+    /// Apply a new quality to the running stream, if one has been started.
+    async fn apply_quality(
+        &self,
+        session_id: &str,
+        quality: &StreamQuality,
+        gstreamer: &GStreamerManager,
+    ) -> Result<()> {
+        let framerates = self.framerates.read().await;
+        let Some(framerate) = framerates.get(session_id) else {
+            // No stream yet: the quality is used when the offer is requested
+            return Ok(());
+        };
+        self.xvfb_manager.set_quality(session_id, quality, gstreamer).await?;
+        framerate.store(quality.framerate, Ordering::Relaxed);
+        info!("Applied {:?} to session: {}", quality, session_id);
+        Ok(())
+    }
+
+    /// Latest round-trip time reported by the client for the session's video stream.
+    async fn round_trip_ms(&self, session_id: &str) -> Option<u32> {
+        let peer = self.peers.read().await.get(session_id).cloned()?;
+        let stats = peer.get_stats().await;
+        stats.reports.values().find_map(|report| match report {
+            // webrtc-rs reports this RTT in milliseconds
+            StatsReportType::RemoteInboundRTP(inbound) if inbound.kind == "video" => {
+                inbound.round_trip_time.map(|rtt| rtt.round() as u32)
+            }
+            _ => None,
+        })
+    }
+
+    /// Sample the session's RTT and switch the stream in or out of the low-latency profile
+    /// when the monitor says so. Returns the message telling the client about the switch.
+    async fn check_latency(
+        &self,
+        session_id: &str,
+        stream: &mut StreamState,
+        policy: &LatencyPolicy,
+        gstreamer: &GStreamerManager,
+    ) -> Result<Option<SignalingMessage>> {
+        let Some(rtt_ms) = self.round_trip_ms(session_id).await else {
+            return Ok(None);
+        };
+        let Some(low_latency) = stream.monitor.observe(rtt_ms, policy) else {
+            return Ok(None);
+        };
+        info!(
+            "Session {} {} low-latency mode (RTT {} ms)",
+            session_id,
+            if low_latency { "entering" } else { "leaving" },
+            rtt_ms
+        );
+        self.apply_quality(session_id, &stream.applied(), gstreamer).await?;
+        self.xvfb_manager.set_low_latency(session_id, low_latency, gstreamer).await?;
+        Ok(Some(SignalingMessage::LatencyMode { low_latency, rtt_ms }))
+    }
+
+    /// Forget the previous peer's latency mode, taking the pipeline out of low-latency mode with
+    /// it, so the monitor and the pipeline agree again.
+    async fn reset_latency(&self, session_id: &str, stream: &mut StreamState, gstreamer: &GStreamerManager) -> Result<()> {
+        let was_low_latency = stream.monitor.is_low_latency();
+        stream.monitor = LatencyMonitor::default();
+        if was_low_latency {
+            self.xvfb_manager.set_low_latency(session_id, false, gstreamer).await?;
+        }
+        Ok(())
+    }
+
+    /// Add the bytes sent to the session's client since the last call to the user's usage.
+    async fn record_usage(&self, session_id: &str, scope: &BandwidthScope, accounting: &BandwidthAccounting) -> Result<()> {
+        let Some(sent_bytes) = self.sent_bytes.read().await.get(session_id).cloned() else {
+            return Ok(());
+        };
+        let bytes = sent_bytes.swap(0, Ordering::Relaxed);
+        if let Err(e) = accounting.record(scope, bytes).await {
+            // Counted again with the next check
+            sent_bytes.fetch_add(bytes, Ordering::Relaxed);
+            anyhow::bail!(e);
+        }
+        Ok(())
+    }
+
+    /// Record the session's usage, then follow the bitrate its caps now allow, which drops
+    /// once the monthly cap is reached and recovers when the owner raises it.
+    async fn check_bandwidth(
+        &self,
+        session_id: &str,
+        stream: &mut StreamState,
+        scope: &BandwidthScope,
+        accounting: &BandwidthAccounting,
+        gstreamer: &GStreamerManager,
+    ) -> Result<Option<SignalingMessage>> {
+        self.record_usage(session_id, scope, accounting).await?;
+        let ceiling = accounting.ceiling(scope).await.map_err(anyhow::Error::msg)?;
+        if ceiling == stream.bitrate_ceiling {
+            return Ok(None);
+        }
+        info!("Session {} bitrate ceiling {:?} -> {:?}", session_id, stream.bitrate_ceiling, ceiling);
+        stream.bitrate_ceiling = ceiling;
+        let applied = stream.applied();
+        self.apply_quality(session_id, &applied, gstreamer).await?;
+        Ok(Some(SignalingMessage::QualityChanged {
+            framerate: applied.framerate,
+            max_bitrate: applied.max_bitrate,
+            resolution_scale: applied.resolution_scale,
+        }))
+    }
+
+    /// What the session's stream asks of the host's shared encoding budget; `None` once the
+    /// session's display is gone.
+    async fn stream_demand(&self, session_id: &str, stream: &StreamState) -> Option<StreamDemand> {
+        let (width, height, priority) = self.xvfb_manager.stream_profile(session_id).await?;
+        let wanted = stream.wanted();
+        let (encoded_width, encoded_height) = wanted.scaled_size(width, height);
+        Some(StreamDemand {
+            pixels: encoded_width as u64 * encoded_height as u64,
+            priority,
+            bitrate: wanted.max_bitrate,
+            framerate: wanted.framerate,
+        })
+    }
+
+    /// Tell the budget what the stream asks for now, after its quality or size changed.
+    async fn update_demand(&self, session_id: &str, stream: &StreamState, budget: &StreamBudgetController) {
+        if let Some(demand) = self.stream_demand(session_id, stream).await {
+            budget.update(session_id, demand);
+        }
+    }
+
+    /// Tell the session's client it was suspended, then stop streaming it.
+    pub async fn suspend(&self, session_id: &str) -> Result<()> {
+        if let Some(sender) = self.client_senders.read().await.get(session_id) {
+            sender.send(&SignalingMessage::SessionSuspended);
+        }
+        self.cleanup(session_id).await
+    }
+
+    /// Send `msg` to every session's signaling socket on this instance.
+    pub async fn broadcast(&self, msg: &SignalingMessage) {
+        for sender in self.client_senders.read().await.values() {
+            sender.send(msg);
+        }
+    }
+
     pub async fn cleanup(&self, session_id: &str) -> Result<()> {
         info!(
             "Cleaning up WebRTC resources for session: {}",
@@ -314,6 +1093,13 @@ impl WebRTCAdapter {
             info!("Cancelled streams for session: {}", session_id);
         }
         drop(tokens);
+        self.framerates.write().await.remove(session_id);
+        self.sent_bytes.write().await.remove(session_id);
+        self.transfer_channels.write().await.remove(session_id);
+        self.cursor_channels.write().await.remove(session_id);
+        self.reconnect_tokens.revoke(session_id);
+        self.fallback_taps.write().await.remove(session_id);
+        self.ready.write().await.remove(session_id);
 
         // Cleanup Xvfb session (stops pipeline, xdotool, app, Xvfb)
         let _ = self.xvfb_manager.cleanup_session(session_id).await;
@@ -331,58 +1117,734 @@ impl WebRTCAdapter {
     }
 }
 
-/// WebSocket handler for signaling
+/// Send the peer's local ICE candidates to the browser over `ws_sender`, then an empty
+/// candidate once gathering is complete.
+fn relay_ice_candidates(
+    peer_connection: &RTCPeerConnection,
+    ws_sender: SignalingSender,
+) {
+    let span = tracing::Span::current();
+    peer_connection.on_ice_candidate(Box::new(
+        move |candidate: Option<webrtc::ice_transport::ice_candidate::RTCIceCandidate>| {
+            let sender = ws_sender.clone();
+            Box::pin(
+                async move {
+                    let msg = match candidate.map(|c| c.to_json()) {
+                        Some(Ok(json_candidate)) => SignalingMessage::IceCandidate {
+                            candidate: json_candidate.candidate,
+                            sdp_mid: json_candidate.sdp_mid,
+                            sdp_mline_index: json_candidate.sdp_mline_index,
+                        },
+                        Some(Err(e)) => {
+                            warn!("Failed to serialize ICE candidate: {}", e);
+                            return;
+                        }
+                        None => SignalingMessage::IceCandidate {
+                            candidate: String::new(),
+                            sdp_mid: None,
+                            sdp_mline_index: None,
+                        },
+                    };
+                    sender.send(&msg);
+                }
+                .instrument(span.clone()),
+            )
+        },
+    ));
+}
+
+/// What the stream of the session's own client does besides feeding the track, which an
+/// owner's watch stream does not
+struct ClientStream {
+    /// Gets the first frame and first sent packet
+    timeline: TimelineRecorder,
+    /// Takes the frames instead of the track while open
+    fallback: Arc<FallbackTap>,
+    /// Announced with the first frame
+    ready: Option<ReadyAnnouncer>,
+    /// Counts the bytes sent, for the user's bandwidth usage
+    sent_bytes: Arc<AtomicU64>,
+}
+
+/// Feed encoded frames from a capture branch into a WebRTC track until cancelled.
+fn spawn_sample_writer(
+    vp8_rx: std::sync::mpsc::Receiver<bytes::Bytes>,
+    video_track: Arc<TrackLocalStaticSample>,
+    framerate: Arc<AtomicU8>,
+    cancel_token: CancellationToken,
+    mut client: Option<ClientStream>,
+) {
+    let span = tracing::Span::current();
+    tokio::task::spawn_blocking(move || {
+        let _entered = span.entered();
+        let started = std::time::Instant::now();
+        let (mut framed, mut sent) = (false, false);
+        while let Ok(frame_data) = vp8_rx.recv() {
+            if cancel_token.is_cancelled() {
+                break;
+            }
+            if !framed {
+                framed = true;
+                if let Some(client) = &mut client {
+                    client.timeline.record(TimelineStage::FirstFrame, Some(format!("{} bytes", frame_data.len())));
+                    if let Some(ready) = client.ready.take() {
+                        tokio::runtime::Handle::current().spawn(async move { ready.announce(ReadySource::FirstFrame).await });
+                    }
+                }
+            }
+            if let Some(client) = &client {
+                client.sent_bytes.fetch_add(frame_data.len() as u64, Ordering::Relaxed);
+            }
+            let timestamp_us = started.elapsed().as_micros() as u64;
+            if client.as_ref().is_some_and(|client| client.fallback.offer(&frame_data, timestamp_us)) {
+                continue;
+            }
+            let result = tokio::runtime::Handle::current().block_on(
+                video_track.write_sample(&webrtc::media::Sample {
+                    data: frame_data,
+                    duration: std::time::Duration::from_millis(
+                        1000 / framerate.load(Ordering::Relaxed).max(1) as u64,
+                    ),
+                    ..Default::default()
+                })
+            );
+            match result {
+                Ok(()) if !sent => {
+                    sent = true;
+                    if let Some(client) = &client {
+                        client.timeline.record(TimelineStage::FirstRtpSent, None);
+                    }
+                }
+                Ok(()) => {}
+                Err(e) => warn!("Failed to send VP8 sample: {}", e),
+            }
+        }
+    });
+}
+
+/// The stream's next share of the host's budget. Never resolves once the stream left the
+/// budget, or was replaced in it by the session's next signaling connection.
+async fn next_share(share: &mut Option<watch::Receiver<StreamShare>>) -> StreamShare {
+    if let Some(receiver) = share {
+        if receiver.changed().await.is_ok() {
+            return *receiver.borrow_and_update();
+        }
+    }
+    *share = None;
+    std::future::pending().await
+}
+
+/// Send one timeline event to the client if it is a launch step. False once the client needs
+/// no more progress.
+fn forward_launch_step(sender: &SignalingSender, event: TimelineEvent) -> bool {
+    let failed = event.stage == TimelineStage::LaunchFailed;
+    let percent = event.stage.launch_percent();
+    if percent.is_none() && !failed {
+        return event.stage != TimelineStage::CleanedUp;
+    }
+    let done = failed || event.stage == TimelineStage::SessionReady;
+    let msg = SignalingMessage::LaunchProgress {
+        stage: event.stage,
+        detail: event.detail,
+        elapsed_ms: event.elapsed_ms,
+        percent,
+    };
+    sender.send(&msg) && !done
+}
+
+/// Send the client the session's launch steps so far, then each new one, until the session
+/// is ready or the launch failed.
+fn spawn_launch_progress(
+    timelines: &SessionTimelines,
+    session_id: &str,
+    sender: SignalingSender,
+) -> Option<tokio::task::JoinHandle<()>> {
+    let id = Uuid::parse_str(session_id).ok()?;
+    let (past, mut live) = timelines.follow(&id);
+    Some(tokio::spawn(
+        async move {
+            for event in past {
+                if !forward_launch_step(&sender, event) {
+                    return;
+                }
+            }
+            loop {
+                match live.recv().await {
+                    Ok((event_session, event)) if event_session == id => {
+                        if !forward_launch_step(&sender, event) {
+                            return;
+                        }
+                    }
+                    Ok(_) | Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => {}
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => return,
+                }
+            }
+        }
+        .in_current_span(),
+    ))
+}
+
+/// Stop a peer's streaming tasks when its connection drops.
+fn cancel_on_disconnect(peer_connection: &RTCPeerConnection, key: &str, cancel_token: CancellationToken) {
+    let key = key.to_string();
+    let span = tracing::Span::current();
+    peer_connection.on_peer_connection_state_change(Box::new(
+        move |state: RTCPeerConnectionState| {
+            let session = key.clone();
+            let token = cancel_token.clone();
+            Box::pin(
+                async move {
+                    info!("Peer connection state changed: {}", state);
+                    match state {
+                        RTCPeerConnectionState::Failed
+                        | RTCPeerConnectionState::Disconnected
+                        | RTCPeerConnectionState::Closed => {
+                            warn!(
+                                "Connection {} failed/disconnected/closed, stopping streams",
+                                session
+                            );
+                            token.cancel();
+                        }
+                        _ => {}
+                    }
+                }
+                .instrument(span.clone()),
+            )
+        },
+    ));
+}
+
+/// A reconnect token for the session's client, sent on its current signaling socket
+struct ReconnectOffer {
+    session_id: String,
+    tokens: Arc<ReconnectTokens>,
+    senders: Arc<RwLock<HashMap<String, SignalingSender>>>,
+}
+
+impl ReconnectOffer {
+    async fn send(&self) {
+        let Some(sender) = self.senders.read().await.get(&self.session_id).cloned() else { return };
+        let token = self.tokens.issue(&self.session_id);
+        sender.send(&SignalingMessage::ReconnectToken { token, grace_secs: reconnect_grace().as_secs() });
+    }
+}
+
+/// Record a client peer's connection and ICE progress. A session stuck before ICE completes
+/// has a network problem (firewall, TURN) rather than a capture one. Each time ICE connects,
+/// the client gets a token to come back with after a tab refresh.
+fn record_peer_progress(peer_connection: &RTCPeerConnection, timeline: TimelineRecorder, reconnect: ReconnectOffer) {
+    let peer_timeline = timeline.clone();
+    let span = tracing::Span::current();
+    peer_connection.on_peer_connection_state_change(Box::new(move |state: RTCPeerConnectionState| {
+        let _entered = span.enter();
+        info!("Peer connection state changed: {}", state);
+        match state {
+            RTCPeerConnectionState::Connected => peer_timeline.record(TimelineStage::PeerConnected, None),
+            RTCPeerConnectionState::Failed | RTCPeerConnectionState::Disconnected => {
+                peer_timeline.record(TimelineStage::Disconnected, Some(format!("peer {}", state)));
+            }
+            _ => {}
+        }
+        Box::pin(async {})
+    }));
+    let reconnect = Arc::new(reconnect);
+    peer_connection.on_ice_connection_state_change(Box::new(move |state: RTCIceConnectionState| {
+        if matches!(state, RTCIceConnectionState::Connected | RTCIceConnectionState::Completed) {
+            timeline.record(TimelineStage::IceCompleted, Some(state.to_string()));
+        }
+        let reconnect = (state == RTCIceConnectionState::Connected).then(|| Arc::clone(&reconnect));
+        Box::pin(async move {
+            if let Some(reconnect) = reconnect {
+                reconnect.send().await;
+            }
+        })
+    }));
+}
+
+/// A VP8 track fed from a capture pipeline, which one peer or, after a tab refresh, the next
+/// one sends.
+fn vp8_track() -> Arc<TrackLocalStaticSample> {
+    Arc::new(TrackLocalStaticSample::new(
+        RTCRtpCodecCapability {
+            mime_type: "video/VP8".to_owned(),
+            clock_rate: 90000,
+            channels: 0,
+            sdp_fmtp_line: "".to_owned(),
+            rtcp_feedback: vec![],
+        },
+        "video".to_owned(),
+        "webrtc-rs".to_owned(),
+    ))
+}
+
+fn watch_key(session_id: &str, watch_id: &str) -> String {
+    format!("{}/watch/{}", session_id, watch_id)
+}
+
+/// WebSocket handler for signaling.
+/// `?session=<id>&watch=true&token=<jwt>` attaches an owner read-only to a client session.
+/// `?reconnect=<token>` takes back the session a [`SignalingMessage::ReconnectToken`] was
+/// issued for, e.g. after a tab refresh.
 pub async fn ws_handler(
     ws: WebSocketUpgrade,
     axum::extract::Query(params): axum::extract::Query<std::collections::HashMap<String, String>>,
     State(adapter): State<Arc<WebRTCAdapter>>,
     axum::Extension(app_state): axum::Extension<crate::infrastructure::AppState>,
-) -> impl axum::response::IntoResponse {
+    axum::extract::ConnectInfo(peer): axum::extract::ConnectInfo<std::net::SocketAddr>,
+    headers: axum::http::HeaderMap,
+) -> axum::response::Response {
+    use axum::response::IntoResponse;
+
+    let ip = crate::infrastructure::driving::http::middleware::client_ip(&headers, peer);
+
+    if let Some(session_id) = params.get("session") {
+        if let Err(rejection) = ensure_local(&app_state, session_id).await {
+            return rejection.into_response();
+        }
+    }
+    if params.get("watch").is_some_and(|v| v == "1" || v == "true") {
+        return match authorize_watch(&params, &app_state).await {
+            Ok((owner_id, session)) => {
+                if let Err(rejection) = admit(&app_state, &session.id.to_string(), ip).await {
+                    return rejection.into_response();
+                }
+                let span = app_state.session_logs.span(&session.id.to_string());
+                ws.max_message_size(MAX_SIGNALING_MESSAGE_BYTES)
+                    .on_upgrade(move |socket| {
+                        handle_watch_socket(socket, adapter, owner_id, session, app_state).instrument(span)
+                    })
+                    .into_response()
+            }
+            Err(rejection) => rejection.into_response(),
+        };
+    }
+
+    let reconnected = match params.get("reconnect") {
+        Some(token) => match adapter.reconnect_tokens.redeem(token, std::time::Instant::now()) {
+            Some(session_id) if params.get("session").map_or(true, |s| *s == session_id) => Some(session_id),
+            _ => {
+                return (axum::http::StatusCode::UNAUTHORIZED, "Reconnect token expired or unknown").into_response();
+            }
+        },
+        None => None,
+    };
+    let session_id = reconnected
+        .or_else(|| params.get("session").cloned())
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+    if let Err(rejection) = admit(&app_state, &session_id, ip).await {
+        return rejection.into_response();
+    }
+    // Signaling, ICE callbacks and the pipeline log under the session's span
+    let span = app_state.session_logs.span(&session_id);
+    ws.max_message_size(MAX_SIGNALING_MESSAGE_BYTES)
+        .on_upgrade(move |socket| handle_socket(socket, adapter, session_id, app_state).instrument(span))
+        .into_response()
+}
+
+/// Refuse the connection if the access policy of a vault the session's user reaches does not
+/// admit `ip`. A session id that names no session reaches no vault.
+async fn admit(
+    app_state: &crate::infrastructure::AppState,
+    session_id: &str,
+    ip: std::net::IpAddr,
+) -> std::result::Result<(), (axum::http::StatusCode, String)> {
+    let internal = |e: String| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e);
+    let Ok(id) = Uuid::parse_str(session_id) else {
+        return Ok(());
+    };
+    let Some(session) = app_state.session_repo.find_by_id(&id).await.map_err(internal)? else {
+        return Ok(());
+    };
+    let Some(user) = app_state.user_repo.find_by_id(&session.user_id).await.map_err(internal)? else {
+        return Ok(());
+    };
+    crate::application::access::check_access::ensure_admitted(app_state, &user, ip, "/ws").await
+}
+
+/// Handshake check for watch mode: an authenticated owner who granted the session's user access.
+/// Refuse signaling for a session another instance runs, since only that instance holds its
+/// peer and pipeline. The client finds it with `GET /api/sessions/{id}/signaling`.
+async fn ensure_local(
+    app_state: &crate::infrastructure::AppState,
+    session_id: &str,
+) -> std::result::Result<(), (axum::http::StatusCode, String)> {
+    match app_state.session_affinity.locate(session_id).await {
+        Ok(SessionLocation::Elsewhere(owner)) => Err((
+            axum::http::StatusCode::MISDIRECTED_REQUEST,
+            format!("Session runs on {}", owner.websocket_base_url),
+        )),
+        Ok(_) => Ok(()),
+        // Without Redis there is no way to tell; a single instance still works
+        Err(e) => {
+            warn!("Cannot locate session {}: {}", session_id, e);
+            Ok(())
+        }
+    }
+}
+
+async fn authorize_watch(
+    params: &HashMap<String, String>,
+    app_state: &crate::infrastructure::AppState,
+) -> std::result::Result<(crate::domain::UserId, crate::domain::Session), (axum::http::StatusCode, String)> {
+    use axum::http::StatusCode;
+
+    let token = params
+        .get("token")
+        .ok_or_else(|| (StatusCode::UNAUTHORIZED, "Missing token".to_string()))?;
+    let user = crate::infrastructure::driving::http::middleware::auth::authenticate_token(
+        app_state,
+        token,
+        shared::Locale::default(),
+    )?;
+    if !user.roles.contains(&crate::domain::UserRole::Owner) {
+        return Err((StatusCode::FORBIDDEN, "Not an owner".to_string()));
+    }
     let session_id = params
         .get("session")
-        .cloned()
-        .unwrap_or_else(|| Uuid::new_v4().to_string());
-    ws.on_upgrade(move |socket| handle_socket(socket, adapter, session_id, app_state))
+        .and_then(|s| Uuid::parse_str(s).ok())
+        .ok_or_else(|| (StatusCode::BAD_REQUEST, "Invalid session id".to_string()))?;
+
+    let session = watch_session::execute(
+        app_state.session_repo.as_ref(),
+        app_state.file_permission_repo.as_ref(),
+        app_state.audit_repo.as_ref(),
+        &user.id,
+        &session_id,
+    )
+    .await
+    .map_err(|e| (StatusCode::FORBIDDEN, e))?;
+    Ok((user.id, session))
+}
+
+/// Read-only signaling for a watcher: offer/answer/ICE only, input messages are ignored.
+async fn handle_watch_socket(
+    socket: WebSocket,
+    adapter: Arc<WebRTCAdapter>,
+    owner_id: crate::domain::UserId,
+    session: crate::domain::Session,
+    app_state: crate::infrastructure::AppState,
+) {
+    let (sender, mut receiver): (SplitSink<WebSocket, Message>, SplitStream<WebSocket>) =
+        socket.split();
+    let sender = SignalingSender::spawn(sender, FloodLimits::from_env().send_timeout);
+    let gstreamer = Arc::new(
+        crate::infrastructure::driven::sandbox::GStreamerManager::new()
+            .expect("Failed to init GStreamer"),
+    );
+    let session_id = session.id.to_string();
+    let watch_id = Uuid::new_v4().to_string();
+    let key = watch_key(&session_id, &watch_id);
+    info!("Owner {} watching session {}", owner_id, session_id);
+    let cancel = CancellationToken::new();
+    let watch = ActiveWatch { owner_id: owner_id.clone(), session: session.clone(), cancel: cancel.clone() };
+    adapter.watches.write().await.insert(key.clone(), watch);
+    adapter.update_watchers(&session_id, true).await;
+    let name = participant_name(&app_state, &owner_id).await;
+    adapter.cursors.join(&session_id, &watch_id, name, false, sender.clone()).await;
+
+    loop {
+        let msg = tokio::select! {
+            _ = cancel.cancelled() => {
+                info!("Ending watch {}: the owner no longer shares with the session's user", key);
+                sender.send(&SignalingMessage::Error { message: "Access to this session was revoked".to_string() });
+                break;
+            }
+            msg = receiver.next() => match msg {
+                Some(Ok(msg)) => msg,
+                _ => break,
+            },
+        };
+        let text = match msg {
+            Message::Text(text) => text,
+            Message::Close(_) => break,
+            _ => continue,
+        };
+        let result = match decode_signaling(&text) {
+            Ok(SignalingMessage::RequestOffer) => adapter
+                .handle_watch_request_offer(&session_id, &watch_id, sender.clone(), Arc::clone(&gstreamer))
+                .await
+                .map(|sdp| Some(SignalingMessage::Offer { sdp })),
+            Ok(SignalingMessage::Answer { sdp }) => adapter.handle_answer(&key, sdp).await.map(|_| None),
+            Ok(SignalingMessage::PointerPosition { x, y }) => {
+                adapter.cursors.moved(&session_id, &watch_id, Some((x, y))).await;
+                Ok(None)
+            }
+            Ok(SignalingMessage::PointerLeft) => {
+                adapter.cursors.moved(&session_id, &watch_id, None).await;
+                Ok(None)
+            }
+            Ok(SignalingMessage::IceCandidate { candidate, sdp_mid, sdp_mline_index }) => adapter
+                .handle_ice_candidate(&key, candidate, sdp_mid, sdp_mline_index)
+                .await
+                .map(|_| None),
+            Ok(other) => {
+                debug!("Ignoring {:?} from read-only watcher of session {}", other, session_id);
+                Ok(None)
+            }
+            Err(e) => {
+                warn!("Failed to parse signaling message: {}", e);
+                Ok(None)
+            }
+        };
+        let reply = result.unwrap_or_else(|e| {
+            error!("Error handling watch signaling message: {}", e);
+            Some(SignalingMessage::Error { message: e.to_string() })
+        });
+        if let Some(reply) = reply {
+            sender.send(&reply);
+        }
+    }
+
+    info!("Owner {} stopped watching session {}", owner_id, session_id);
+    adapter.watches.write().await.remove(&key);
+    adapter.cursors.leave(&session_id, &watch_id).await;
+    if let Err(e) = adapter.cleanup_watch(&session_id, &watch_id, &gstreamer).await {
+        warn!("Failed to clean up watch {}: {}", key, e);
+    }
+    adapter.update_watchers(&session_id, false).await;
+    if let Err(e) = watch_session::finish(app_state.audit_repo.as_ref(), &owner_id, &session).await {
+        warn!("Failed to audit end of watch {}: {}", key, e);
+    }
 }
 
 async fn handle_socket(socket: WebSocket, adapter: Arc<WebRTCAdapter>, session_id: String, app_state: crate::infrastructure::AppState) {
     let (sender, mut receiver): (SplitSink<WebSocket, Message>, SplitStream<WebSocket>) =
         socket.split();
-    let sender = Arc::new(tokio::sync::Mutex::new(sender));
+    let limits = FloodLimits::from_env();
+    let sender = SignalingSender::spawn(sender, limits.send_timeout);
+    adapter
+        .client_senders
+        .write()
+        .await
+        .insert(session_id.clone(), sender.clone());
 
     let gstreamer = Arc::new(
         crate::infrastructure::driven::sandbox::GStreamerManager::new()
             .expect("Failed to init GStreamer"),
     );
-    let config = VideoConfig::default();
+    // Start from the user's saved quality for this app, else their default framerate. A saved
+    // quality outside the current limits (set before they were lowered) is ignored.
+    let stream_limits = quality_limits(&app_state.config.current());
+    let mut quality = StreamQuality::default();
+    let session = match Uuid::parse_str(&session_id) {
+        Ok(id) => app_state.session_repo.find_by_id(&id).await.ok().flatten(),
+        Err(_) => None,
+    };
+    if let Some(session) = &session {
+        let saved = match app_state.quality_preference_repo.find(&session.user_id, &session.app_id).await {
+            Ok(Some(pref)) if pref.quality.validate(&stream_limits).is_ok() => Some(pref.quality),
+            _ => None,
+        };
+        if let Some(saved) = saved {
+            quality = saved;
+        } else if let Ok(Some(prefs)) = app_state.user_preferences_repo.find(&session.user_id).await {
+            quality.framerate = prefs.default_framerate;
+        }
+        // The framerate the launch settled on, within the app's limits
+        if let Some(video) = &session.video {
+            quality.framerate = video.framerate;
+        }
+    }
+
+    let mut rate = MessageRate::new(&limits, std::time::Instant::now());
+
+    let progress_forwarder = spawn_launch_progress(&app_state.session_timelines, &session_id, sender.clone());
+
+    // Forward accessibility events and download chunks from the app to the browser
+    let mut app_rx = app_state.ipc_server.subscribe(&session_id).await;
+    // A reconnecting client learns the session is ready again; an app that became ready
+    // before any client connected is announced now
+    let ready = adapter.ready_announcer(&session_id);
+    let replay = adapter.ready.read().await.get(&session_id).cloned();
+    match replay {
+        Some(msg) => {
+            sender.send(&msg);
+        }
+        None if app_state.ipc_server.is_ready(&session_id).await => ready.announce(ReadySource::App).await,
+        None => {}
+    }
+    if let Some(window) = app_state.maintenance.current() {
+        let msg = SignalingMessage::Maintenance { active: true, message: window.message };
+        sender.send(&msg);
+    }
+    // A reconnecting client may have missed owners starting or stopping to watch meanwhile
+    let watchers = adapter.watcher_counts.read().await.get(&session_id).copied().unwrap_or(0);
+    sender.send(&SignalingMessage::WatchStatus { watchers });
+    let input_allowed = session.as_ref().map_or(true, |s| s.interaction.allows_input());
+    if !input_allowed {
+        sender.send(&SignalingMessage::InputDisabled);
+    }
+    let shortcuts = adapter.xvfb_manager.shortcut_policy(&session_id).await;
+    let mut held_keys = ShortcutGuard::default();
+    if input_allowed {
+        sender.send(&SignalingMessage::ShortcutPolicy { capture: shortcuts.captured().to_vec() });
+    }
+    // Each socket is its own participant, so a reconnecting tab does not inherit a stale cursor
+    let peer_id = Uuid::new_v4().to_string();
+    if let Some(session) = &session {
+        let name = participant_name(&app_state, &session.user_id).await;
+        adapter.cursors.join(&session_id, &peer_id, name, input_allowed, sender.clone()).await;
+    }
+    let sender_for_app = sender.clone();
+    let adapter_for_app = Arc::clone(&adapter);
+    let session_for_app = session_id.clone();
+    let app_forwarder = tokio::spawn(
+        async move {
+            while let Some(msg) = app_rx.recv().await {
+                match msg {
+                    shared::AppMessage::Accessibility { events } => {
+                        let msg = SignalingMessage::Accessibility { events };
+                        sender_for_app.send(&msg);
+                    }
+                    shared::AppMessage::Ready => ready.announce(ReadySource::App).await,
+                    chunk @ shared::AppMessage::DownloadChunk { .. } => {
+                        let channel = adapter_for_app.transfer_channels.read().await.get(&session_for_app).cloned();
+                        let Some(channel) = channel else { continue };
+                        // A chunk lost here leaves a gap; the client resumes from its byte count
+                        if let Ok(json) = serde_json::to_string(&chunk) {
+                            if let Err(e) = channel.send_text(json).await {
+                                debug!("Download chunk not delivered for session {}: {}", session_for_app, e);
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        .in_current_span(),
+    );
 
     info!(
         "WebSocket connection established for session: {}",
         session_id
     );
 
+    // Limits are read once per socket, so a reload applies from the next connection
+    let mut stream = StreamState::new(quality, stream_limits);
+    let policy = latency_policy();
+    let mut rtt_check = tokio::time::interval(RTT_SAMPLE_INTERVAL);
+    // Caps of the session's vault owner apply from the first offer
+    let bandwidth_scope = session.as_ref().map(BandwidthScope::for_session);
+    if let Some(scope) = &bandwidth_scope {
+        match app_state.bandwidth.ceiling(scope).await {
+            Ok(ceiling) => stream.bitrate_ceiling = ceiling,
+            Err(e) => warn!("Failed to read bandwidth caps for session {}: {}", session_id, e),
+        }
+    }
+    let mut usage_check = tokio::time::interval(USAGE_CHECK_INTERVAL);
+    let mut share = match adapter.stream_demand(&session_id, &stream).await {
+        Some(demand) => {
+            let mut receiver = app_state.stream_budget.join(&session_id, demand);
+            stream.share = *receiver.borrow_and_update();
+            Some(receiver)
+        }
+        None => None,
+    };
+    // Only a close frame means the client left; a dropped socket may be a network change
+    let mut closed_by_client = false;
+    let mut violation = None;
+
     loop {
-        match receiver.next().await {
+        let next = tokio::select! {
+            next = receiver.next() => next,
+            _ = sender.stalled() => {
+                violation = Some(Violation::SlowConsumer);
+                break;
+            }
+            _ = rtt_check.tick(), if policy.is_some() => {
+                let Some(policy) = &policy else { continue };
+                match adapter.check_latency(&session_id, &mut stream, policy, &gstreamer).await {
+                    Ok(Some(msg)) => {
+                        adapter.update_demand(&session_id, &stream, &app_state.stream_budget).await;
+                        let applied = stream.applied();
+                        for msg in [
+                            msg,
+                            SignalingMessage::QualityChanged {
+                                framerate: applied.framerate,
+                                max_bitrate: applied.max_bitrate,
+                                resolution_scale: applied.resolution_scale,
+                            },
+                        ] {
+                            sender.send(&msg);
+                        }
+                    }
+                    Ok(None) => {}
+                    Err(e) => warn!("Failed to switch latency mode for session {}: {}", session_id, e),
+                }
+                continue;
+            }
+            _ = usage_check.tick(), if bandwidth_scope.is_some() => {
+                let Some(scope) = &bandwidth_scope else { continue };
+                match adapter.check_bandwidth(&session_id, &mut stream, scope, &app_state.bandwidth, &gstreamer).await {
+                    Ok(Some(msg)) => {
+                        adapter.update_demand(&session_id, &stream, &app_state.stream_budget).await;
+                        sender.send(&msg);
+                    }
+                    Ok(None) => {}
+                    Err(e) => warn!("Failed to check bandwidth of session {}: {}", session_id, e),
+                }
+                continue;
+            }
+            new_share = next_share(&mut share) => {
+                stream.share = new_share;
+                let applied = stream.applied();
+                match adapter.apply_quality(&session_id, &applied, &gstreamer).await {
+                    Ok(()) => {
+                        let msg = SignalingMessage::QualityChanged {
+                            framerate: applied.framerate,
+                            max_bitrate: applied.max_bitrate,
+                            resolution_scale: applied.resolution_scale,
+                        };
+                        sender.send(&msg);
+                    }
+                    Err(e) => warn!("Failed to apply stream share to session {}: {}", session_id, e),
+                }
+                continue;
+            }
+        };
+        match next {
+            Some(Ok(_)) if !rate.admit(std::time::Instant::now()) => {
+                violation = Some(Violation::Flood);
+                break;
+            }
             Some(Ok(msg)) => match msg {
                 Message::Text(text) => {
                     debug!("Received message: {}", text);
-                    match serde_json::from_str::<SignalingMessage>(&text) {
+                    match decode_signaling(&text) {
+                        Ok(SignalingMessage::PointerPosition { x, y }) => {
+                            adapter.cursors.moved(&session_id, &peer_id, Some((x, y))).await;
+                        }
+                        Ok(SignalingMessage::PointerLeft) => {
+                            adapter.cursors.moved(&session_id, &peer_id, None).await;
+                        }
+                        Ok(SignalingMessage::KeyDown { key, .. }) if !held_keys.press(&key, &shortcuts) => {
+                            debug!("Dropped shortcut outside the policy of session {}", session_id);
+                        }
                         Ok(message) => {
+                            if let SignalingMessage::KeyUp { key, .. } = &message {
+                                held_keys.release(key);
+                            }
+                            let changes_demand = matches!(
+                                message,
+                                SignalingMessage::RequestOffer | SignalingMessage::SetQuality { .. } | SignalingMessage::Resize { .. }
+                            );
                             let response = handle_signaling_message(
                                 message,
                                 &session_id,
                                 &adapter,
-                                Arc::clone(&sender),
+                                sender.clone(),
                                 Arc::clone(&gstreamer),
-                                config.clone(),
+                                &mut stream,
+                                input_allowed,
+                                &app_state,
                             )
                             .await;
+                            if changes_demand {
+                                adapter.update_demand(&session_id, &stream, &app_state.stream_budget).await;
+                            }
                             match response {
                                 Ok(Some(msg)) => {
-                                    if let Ok(json) = serde_json::to_string(&msg) {
-                                        let mut sender_lock = sender.lock().await;
-                                        let _ = sender_lock.send(Message::Text(json.into())).await;
-                                    }
+                                    sender.send(&msg);
                                 }
                                 Ok(None) => {}
                                 Err(e) => {
@@ -390,10 +1852,7 @@ async fn handle_socket(socket: WebSocket, adapter: Arc<WebRTCAdapter>, session_i
                                     let error_msg = SignalingMessage::Error {
                                         message: e.to_string(),
                                     };
-                                    if let Ok(json) = serde_json::to_string(&error_msg) {
-                                        let mut sender_lock = sender.lock().await;
-                                        let _ = sender_lock.send(Message::Text(json.into())).await;
-                                    }
+                                    sender.send(&error_msg);
                                 }
                             }
                         }
@@ -402,52 +1861,174 @@ async fn handle_socket(socket: WebSocket, adapter: Arc<WebRTCAdapter>, session_i
                         }
                     }
                 }
-                Message::Close(_) => {
+                Message::Close(frame) => {
                     info!("WebSocket closed for session: {}", session_id);
-                    let _ = adapter.cleanup(&session_id).await;
+                    app_state
+                        .session_timelines
+                        .record(&session_id, TimelineStage::Disconnected, Some("WebSocket closed".to_string()));
+                    // A page going away may be a refresh whose tab comes back with its token
+                    let going_away = frame.is_some_and(|f| f.code == axum::extract::ws::close_code::AWAY);
+                    closed_by_client = !(going_away && adapter.reconnect_tokens.has_token(&session_id));
                     break;
                 }
                 _ => {}
             },
+            // Larger than `MAX_SIGNALING_MESSAGE_BYTES`: the socket refuses to buffer it
+            Some(Err(e)) if e.to_string().contains("too long") => {
+                violation = Some(Violation::OversizedMessage);
+                break;
+            }
             Some(Err(e)) => {
                 error!("WebSocket error: {}", e);
+                app_state
+                    .session_timelines
+                    .record(&session_id, TimelineStage::Disconnected, Some(format!("WebSocket error: {}", e)));
                 break;
             }
             None => {
                 info!("WebSocket stream ended for session: {}", session_id);
+                app_state
+                    .session_timelines
+                    .record(&session_id, TimelineStage::Disconnected, Some("WebSocket ended".to_string()));
                 break;
             }
         }
     }
 
+    if let Some(violation) = violation {
+        warn!("Disconnecting signaling of session {}: {}", session_id, violation.as_str());
+        app_state.session_timelines.record(
+            &session_id,
+            TimelineStage::Disconnected,
+            Some(format!("Disconnected for {}", violation.as_str().replace('_', " "))),
+        );
+        let mut event = AuditEvent::new(
+            "signaling_disconnected",
+            serde_json::json!({ "reason": violation.as_str(), "limits": {
+                "messages_per_sec": limits.messages_per_sec,
+                "burst": limits.burst,
+                "send_timeout_secs": limits.send_timeout.as_secs(),
+                "max_message_bytes": MAX_SIGNALING_MESSAGE_BYTES,
+            } }),
+        );
+        event.session_id = Some(session_id.clone());
+        event.user_id = session.as_ref().map(|s| s.user_id.clone());
+        if let Err(e) = app_state.audit_repo.record(&event).await {
+            warn!("Failed to audit disconnect of session {}: {}", session_id, e);
+        }
+        sender.close();
+    }
+
+    adapter.cursors.leave(&session_id, &peer_id).await;
+    app_forwarder.abort();
+    if let Some(forwarder) = progress_forwarder {
+        forwarder.abort();
+    }
+    if !closed_by_client {
+        // Keep the app and pipeline for a client that reconnects and restarts ICE
+        let grace = reconnect_grace();
+        adapter.reconnect_tokens.expire_after(&session_id, grace, std::time::Instant::now());
+        info!("Signaling for session {} dropped, waiting {:?} for a reconnect", session_id, grace);
+        tokio::time::sleep(grace).await;
+    }
+    if !adapter.owns_signaling(&session_id, &sender).await {
+        info!("Session {} continues on a new signaling connection", session_id);
+        return;
+    }
+
     info!(
         "[CLEANUP] WebSocket handler ending, cleaning up session: {}",
         session_id
     );
+    adapter.client_senders.write().await.remove(&session_id);
+    app_state.ipc_server.unsubscribe(&session_id).await;
+    app_state.stream_budget.leave(&session_id);
+    if let Some(scope) = &bandwidth_scope {
+        if let Err(e) = adapter.record_usage(&session_id, scope, &app_state.bandwidth).await {
+            warn!("Failed to record bandwidth of session {}: {}", session_id, e);
+        }
+    }
     let cleanup_result = adapter.cleanup(&session_id).await;
     info!("[CLEANUP] WebSocket handler cleanup result for session {}: {:?}", session_id, cleanup_result);
 
+    // A suspended session was already wound down and must stay resumable
+    let session_uuid = uuid::Uuid::parse_str(&session_id).ok();
+    if let Some(id) = &session_uuid {
+        if let Ok(Some(stored)) = app_state.session_repo.find_by_id(id).await {
+            if stored.state == "suspended" {
+                info!("Session {} was suspended, keeping it", session_id);
+                app_state.session_timelines.discard(&session_id);
+                return;
+            }
+        }
+    }
+
     // Mark session as terminated in DB (best-effort)
-    if let Ok(session_uuid) = uuid::Uuid::parse_str(&session_id) {
-        let _ = app_state.session_repo.terminate(&session_uuid).await;
+    if let Some(id) = &session_uuid {
+        let _ = app_state.session_repo.terminate(id).await;
+    }
+    if let Err(e) = app_state.session_timelines.finish(&session_id).await {
+        warn!("Failed to save timeline of session {}: {}", session_id, e);
+    }
+    if let Err(e) = app_state.session_affinity.release(&session_id).await {
+        warn!("Failed to release session {}: {}", session_id, e);
+    }
+}
+
+/// Name shown on a participant's cursor
+async fn participant_name(app_state: &crate::infrastructure::AppState, user_id: &crate::domain::UserId) -> String {
+    match app_state.user_repo.find_by_id(user_id).await {
+        Ok(Some(user)) => user.display_name().as_str().to_string(),
+        _ => "Someone".to_string(),
     }
 }
 
+/// Log a watermarked session's clicks and key presses with the frame on screen when they
+/// arrived, the same index and clock as the watermark and the dump's frame index, so auditors
+/// can tell who did what when. Pointer moves are too frequent to log.
+async fn log_input(adapter: &WebRTCAdapter, session_id: &str, input: &str, detail: &str) {
+    let Some(clock) = adapter.xvfb_manager.frame_clock(session_id).await else { return };
+    let frame = clock.latest().map(|stamp| stamp.label()).unwrap_or_default();
+    info!(input, detail, %frame, at = %chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true), "Input");
+}
+
+/// What the input log keeps of a key: named keys such as `Enter` or `Control`, never the
+/// characters typed.
+fn audited_key(key: &str) -> &str {
+    if key.chars().count() > 1 { key } else { "character" }
+}
+
 async fn handle_signaling_message(
     message: SignalingMessage,
     session_id: &str,
     adapter: &Arc<WebRTCAdapter>,
-    ws_sender: Arc<tokio::sync::Mutex<SplitSink<WebSocket, Message>>>,
+    ws_sender: SignalingSender,
     gstreamer: Arc<GStreamerManager>,
-    config: VideoConfig,
+    stream: &mut StreamState,
+    input_allowed: bool,
+    app_state: &crate::infrastructure::AppState,
 ) -> Result<Option<SignalingMessage>> {
+    if !input_allowed && message.is_input() {
+        debug!("Dropped input for view-only session {}", session_id);
+        return Ok(None);
+    }
     match message {
         SignalingMessage::RequestOffer => {
+            // A new peer starts on the user's quality until its own RTT is measured
+            adapter.reset_latency(session_id, stream, &gstreamer).await?;
             let sdp = adapter
-                .handle_request_offer(session_id, ws_sender, gstreamer, &config)
+                .handle_request_offer(session_id, ws_sender, gstreamer, &stream.applied())
                 .await?;
             Ok(Some(SignalingMessage::Offer { sdp }))
         }
+        SignalingMessage::RestartIce => {
+            let sdp = adapter.handle_restart_ice(session_id, ws_sender).await?;
+            Ok(Some(SignalingMessage::Offer { sdp }))
+        }
+        SignalingMessage::StartFallbackStream => {
+            adapter.start_fallback(session_id, ws_sender, &gstreamer).await?;
+            Ok(None)
+        }
         SignalingMessage::Answer { sdp } => {
             adapter.handle_answer(session_id, sdp).await?;
             Ok(None)
@@ -464,16 +2045,21 @@ async fn handle_signaling_message(
         }
         SignalingMessage::MouseMove { x, y } => {
             debug!("Received MouseMove: x={}, y={}", x, y);
+            // Client coordinates are in encoded-stream pixels, which may be downscaled
+            let scale = stream.applied().resolution_scale.max(f32::EPSILON);
+            let (x, y) = ((x as f32 / scale).round() as i32, (y as f32 / scale).round() as i32);
             adapter.xvfb_manager.handle_mouse_move(session_id, x, y).await;
             Ok(None)
         }
         SignalingMessage::MouseDown { button } => {
             debug!("Received MouseDown: button={}", button);
+            log_input(adapter, session_id, "mouse_down", &button.to_string()).await;
             adapter.xvfb_manager.handle_mouse_button(session_id, button, true).await;
             Ok(None)
         }
         SignalingMessage::MouseUp { button } => {
             debug!("Received MouseUp: button={}", button);
+            log_input(adapter, session_id, "mouse_up", &button.to_string()).await;
             adapter.xvfb_manager.handle_mouse_button(session_id, button, false).await;
             Ok(None)
         }
@@ -483,18 +2069,122 @@ async fn handle_signaling_message(
         }
         SignalingMessage::KeyDown { key, .. } => {
             debug!("Received KeyDown: key={}", key);
+            log_input(adapter, session_id, "key_down", audited_key(&key)).await;
             adapter.xvfb_manager.handle_keyboard(session_id, &key, true).await;
             Ok(None)
         }
         SignalingMessage::KeyUp { key, .. } => {
             debug!("Received KeyUp: key={}", key);
+            log_input(adapter, session_id, "key_up", audited_key(&key)).await;
             adapter.xvfb_manager.handle_keyboard(session_id, &key, false).await;
             Ok(None)
         }
         SignalingMessage::Resize { width, height } => {
             debug!("Received Resize: width={}, height={}", width, height);
+            let (Ok(width), Ok(height)) = (u16::try_from(width), u16::try_from(height)) else {
+                anyhow::bail!("Invalid display size {}x{}", width, height);
+            };
+            if width == 0 || height == 0 {
+                anyhow::bail!("Invalid display size {}x{}", width, height);
+            }
+            adapter
+                .xvfb_manager
+                .resize(session_id, width, height, &stream.applied(), &gstreamer)
+                .await?;
+            Ok(None)
+        }
+        SignalingMessage::SetQuality {
+            framerate,
+            max_bitrate,
+            resolution_scale,
+        } => {
+            debug!(
+                "Received SetQuality: framerate={}, max_bitrate={}, resolution_scale={}",
+                framerate, max_bitrate, resolution_scale
+            );
+            let requested = StreamQuality {
+                framerate,
+                max_bitrate,
+                resolution_scale,
+            };
+            let session = app_state
+                .session_repo
+                .find_by_id(&Uuid::parse_str(session_id)?)
+                .await
+                .map_err(|e| anyhow::anyhow!(e))?
+                .ok_or_else(|| anyhow::anyhow!("Session not found"))?;
+            let applied = set_stream_quality::execute(
+                app_state.quality_preference_repo.as_ref(),
+                &session.user_id,
+                &session.app_id,
+                requested,
+                &stream.limits,
+            )
+            .await
+            .map_err(|e| anyhow::anyhow!(e))?;
+
+            // While in low-latency mode the new quality is only a ceiling for the profile
+            stream.preferred = applied;
+            let applied = stream.applied();
+            adapter.apply_quality(session_id, &applied, &gstreamer).await?;
+            Ok(Some(SignalingMessage::QualityChanged {
+                framerate: applied.framerate,
+                max_bitrate: applied.max_bitrate,
+                resolution_scale: applied.resolution_scale,
+            }))
+        }
+        SignalingMessage::ListWindows => {
+            let windows = adapter.xvfb_manager.list_windows(session_id).await?;
+            Ok(Some(SignalingMessage::Windows { windows }))
+        }
+        SignalingMessage::FocusWindow { id } => {
+            debug!("Received FocusWindow: id={}", id);
+            adapter.xvfb_manager.focus_window(session_id, id).await?;
+            let windows = adapter.xvfb_manager.list_windows(session_id).await?;
+            Ok(Some(SignalingMessage::Windows { windows }))
+        }
+        SignalingMessage::ResumeDownload { path, offset, etag } => {
+            debug!("Received ResumeDownload: path={}, offset={}", path, offset);
+            app_state
+                .ipc_server
+                .send(session_id, shared::PlatformMessage::ResumeDownload { path, offset, etag })
+                .await?;
             Ok(None)
         }
         _ => Ok(None),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_signaling_rejects_hostile_input() {
+        assert!(matches!(decode_signaling(r#"{"type":"request-offer"}"#), Ok(SignalingMessage::RequestOffer)));
+        assert!(matches!(
+            decode_signaling(&format!(r#"{{"type":"answer","sdp":"{}"}}"#, "a".repeat(MAX_SIGNALING_MESSAGE_BYTES))),
+            Err(DecodeError::TooLarge { .. })
+        ));
+        let deep = format!(r#"{{"type":"accessibility","events":{}{}}}"#, "[".repeat(100), "]".repeat(100));
+        assert!(matches!(decode_signaling(&deep), Err(DecodeError::TooDeep { .. })));
+        let key = r#"{"type":"key-down","key":"a\u0000b","code":"KeyA"}"#;
+        assert!(matches!(decode_signaling(key), Err(DecodeError::Invalid(_))));
+        let scroll = r#"{"type":"mouse-scroll","delta_y":1e999}"#;
+        assert!(decode_signaling(scroll).is_err());
+        let pointer = r#"{"type":"pointer-position","x":1.5,"y":0.2}"#;
+        assert!(matches!(decode_signaling(pointer), Err(DecodeError::Invalid(_))));
+        for input in ["", "[", r#"{"type":"resize","width":-1,"height":2}"#, r#"{"type":"mouse-down","button":999}"#] {
+            assert!(matches!(decode_signaling(input), Err(DecodeError::Malformed(_))));
+        }
+    }
+
+    #[test]
+    fn test_input_messages() {
+        assert!(SignalingMessage::KeyDown { key: "a".to_string(), code: "KeyA".to_string() }.is_input());
+        assert!(SignalingMessage::FocusWindow { id: 1 }.is_input());
+        assert!(!SignalingMessage::Resize { width: 800, height: 600 }.is_input());
+        assert!(!SignalingMessage::RequestOffer.is_input());
+        assert!(!SignalingMessage::PointerPosition { x: 0.5, y: 0.5 }.is_input());
+    }
+}
@@ -2,7 +2,8 @@
 // Implements interfaces defined in application layer
 
 use std::sync::Arc;
-use crate::application::ports::{CredentialRepository, ChallengeRepository, InvitationRepository, FilePermissionRepository, SessionRepository};
+use crate::application::ports::{CredentialRepository, ChallengeRepository, InvitationRepository, FilePermissionRepository, SessionRepository, QualityPreferenceRepository, UserPreferencesRepository, AuditRepository, NotificationRepository, LoginAttemptRepository, AccessPolicyRepository, GeoIpResolver, EmailSender, VerificationCodeRepository, ClientGroupRepository, DelegationRepository, FileJobRepository, VaultStorage, UploadSessionRepository, UploadHook, SessionSnapshotRepository, BandwidthRepository, AppCrashRepository, DataExportRepository, AccountDeletionRepository, VaultImportRepository, IdentityProvider, ExternalIdentityRepository, ProvisioningRepository, AccessTokenRepository, LegalHoldRepository, AppSettingRepository, TenantRepository, BrandingRepository, FileProcessor, ProcessingRepository, MediaMetadataRepository, OrganizationRuleRepository, FileHashRepository, FolderExportRepository, GalleryShareRepository, FileCommentRepository, FileRequestRepository, ImpersonationConsentRepository};
+use crate::domain::value_objects::lockout_policy::LockoutPolicy;
 
 pub mod driven;    // Output adapters (repositories, external services)
 pub mod driving;   // Input adapters (HTTP, CLI, etc.)
@@ -10,13 +11,105 @@ pub mod driving;   // Input adapters (HTTP, CLI, etc.)
 #[derive(Clone)]
 pub struct AppState {
     pub webauthn: Arc<webauthn_rs::prelude::Webauthn>,
-    pub jwt_secret: String,
+    pub jwt_keys: Arc<crate::infrastructure::driven::secrets::JwtKeys>,
     pub user_repo: Arc<dyn crate::application::ports::user_repository::UserRepository>,
     pub credential_repo: Arc<dyn CredentialRepository>,
     pub challenge_repo: Arc<dyn ChallengeRepository>,
     pub invitation_repo: Arc<dyn InvitationRepository>,
     pub file_permission_repo: Arc<dyn FilePermissionRepository>,
     pub session_repo: Arc<dyn SessionRepository>,
+    pub quality_preference_repo: Arc<dyn QualityPreferenceRepository>,
+    pub user_preferences_repo: Arc<dyn UserPreferencesRepository>,
+    pub audit_repo: Arc<dyn AuditRepository>,
+    pub notification_repo: Arc<dyn NotificationRepository>,
+    pub login_attempt_repo: Arc<dyn LoginAttemptRepository>,
+    pub verification_code_repo: Arc<dyn VerificationCodeRepository>,
+    pub lockout_policy: LockoutPolicy,
+    pub access_policy_repo: Arc<dyn AccessPolicyRepository>,
+    pub client_group_repo: Arc<dyn ClientGroupRepository>,
+    pub delegation_repo: Arc<dyn DelegationRepository>,
+    pub file_job_repo: Arc<dyn FileJobRepository>,
+    pub vault_storage: Arc<dyn VaultStorage>,
+    pub upload_session_repo: Arc<dyn UploadSessionRepository>,
+    /// Uploads whose next chunk is being written
+    pub appending_uploads: crate::application::owner::commands::append_upload::AppendingUploads,
+    /// Checks every finished upload passes before it enters a vault
+    pub upload_hooks: Arc<Vec<Arc<dyn UploadHook>>>,
+    /// Stages run on every file once it is in a vault, in this order
+    pub processors: Arc<Vec<Arc<dyn FileProcessor>>>,
+    pub processing_repo: Arc<dyn ProcessingRepository>,
+    /// Date, camera and location read from photos and videos
+    pub media_metadata_repo: Arc<dyn MediaMetadataRepository>,
+    pub organization_rule_repo: Arc<dyn OrganizationRuleRepository>,
+    /// Content hashes of vault files, to find duplicates by
+    pub file_hash_repo: Arc<dyn FileHashRepository>,
+    /// Clients' requests to take shared folders offline
+    pub folder_export_repo: Arc<dyn FolderExportRepository>,
+    /// Folders of photos owners publish to anyone holding the link
+    pub gallery_share_repo: Arc<dyn GalleryShareRepository>,
+    /// Notes owners and clients leave on shared files
+    pub file_comment_repo: Arc<dyn FileCommentRepository>,
+    /// Upload-only links and the inbox of files sent through them
+    pub file_request_repo: Arc<dyn FileRequestRepository>,
+    /// Clients' consents to owners opening sessions as them
+    pub impersonation_consent_repo: Arc<dyn ImpersonationConsentRepository>,
+    pub geoip: Option<Arc<dyn GeoIpResolver>>,
+    pub email_sender: Option<Arc<dyn EmailSender>>,
     pub xvfb_manager: Arc<crate::infrastructure::driven::sandbox::xvfb::XvfbManager>,
+    pub session_pool: Arc<crate::infrastructure::driven::sandbox::SessionPool>,
+    pub ipc_server: Arc<crate::infrastructure::driven::ipc::IpcSocketServer>,
+    pub webrtc: Arc<crate::infrastructure::driving::webrtc::WebRTCAdapter>,
+    pub session_timelines: Arc<crate::application::sessions::timeline::SessionTimelines>,
+    /// Saved state of suspended sessions
+    pub session_snapshot_repo: Arc<dyn SessionSnapshotRepository>,
+    /// What SDK apps saved per user, restored on their next launch
+    pub app_states: Arc<crate::application::sessions::app_state::AppStateStore>,
+    /// Owners' bandwidth caps for their clients, and what the clients used
+    pub bandwidth_repo: Arc<dyn BandwidthRepository>,
+    pub bandwidth: Arc<crate::application::sessions::bandwidth::BandwidthAccounting>,
+    /// Panics reported by SDK apps
+    pub app_crash_repo: Arc<dyn AppCrashRepository>,
+    /// Devices users are logged in on, checked when validating tokens
+    pub auth_sessions: Arc<crate::application::auth_sessions::AuthSessions>,
+    /// Archives of users' data requested through `/api/my-data/export`
+    pub data_export_repo: Arc<dyn DataExportRepository>,
+    /// Accounts pending deletion, purged once `account_deletion_grace` has passed
+    pub account_deletion_repo: Arc<dyn AccountDeletionRepository>,
+    pub account_deletion_grace: chrono::Duration,
+    /// Host directories being adopted into owners' vaults
+    pub vault_import_repo: Arc<dyn VaultImportRepository>,
+    /// External OpenID Connect provider, when `OIDC_ISSUER` is set
+    pub identity_provider: Option<Arc<dyn IdentityProvider>>,
+    /// Provider accounts linked to local users
+    pub external_identity_repo: Arc<dyn ExternalIdentityRepository>,
+    pub oidc_policy: crate::domain::entities::external_identity::OidcPolicy,
+    /// Owners' provisioning tokens, and the responses kept for idempotent retries
+    pub provisioning_repo: Arc<dyn ProvisioningRepository>,
+    /// Users' personal access tokens for scripts and backup tools
+    pub access_token_repo: Arc<dyn AccessTokenRepository>,
+    /// Vault paths protected from deletion and change
+    pub legal_hold_repo: Arc<dyn LegalHoldRepository>,
+    /// Owners' choices of which installed apps their vault offers, and to whom
+    pub app_setting_repo: Arc<dyn AppSettingRepository>,
+    /// Isolated groups of users sharing the instance
+    pub tenant_repo: Arc<dyn TenantRepository>,
+    /// Owners' names, logos and colors shown to the people they invite
+    pub branding_repo: Arc<dyn BrandingRepository>,
+    /// Whether this instance is draining for an upgrade
+    pub maintenance: Arc<crate::application::maintenance::Maintenance>,
+    /// When the storage disk was last active, and the disk work held back until it is
+    pub storage_scheduler: Arc<crate::application::storage_scheduler::StorageScheduler>,
+    /// Non-secret settings, reloaded without a restart
+    pub config: Arc<crate::infrastructure::driven::config::LiveConfig>,
+    /// The host's encoding budget, shared by the sessions streaming at once
+    pub stream_budget: Arc<crate::application::sessions::stream_budget::StreamBudgetController>,
+    pub session_affinity: Arc<crate::application::sessions::affinity::SessionAffinity>,
+    pub scheduler: Arc<crate::application::sessions::scheduler::Scheduler>,
+    pub host_metrics: Arc<crate::infrastructure::driven::host_metrics::HostMetrics>,
+    pub ice_servers: Arc<crate::infrastructure::driven::ice_servers::IceServers>,
+    /// Codecs the installed GStreamer plugins can encode, probed at startup
+    pub codec_support: Arc<crate::infrastructure::driven::sandbox::CodecSupport>,
+    /// Recent log lines of each session, for admins debugging one
+    pub session_logs: Arc<crate::infrastructure::driven::session_logs::SessionLogs>,
     pub storage_path: String,
 }
@@ -15,10 +15,22 @@ mod infrastructure;
 
 use infrastructure::driving::{WebRTCAdapter};
 use infrastructure::driven::{XvfbManager, IpcSocketServer};
-use infrastructure::driven::persistence::{SqliteCredentialRepository, RedisChallengeRepository, SqliteUserRepository, SqliteInvitationRepository, SqliteFilePermissionRepository, SqliteSessionRepository};
+use infrastructure::driven::build_info;
+use infrastructure::driven::config::LiveConfig;
+use infrastructure::driven::session_logs::{SessionLogLayer, SessionLogLimits, SessionLogs};
+use tracing_subscriber::filter::{EnvFilter, LevelFilter};
+use tracing_subscriber::prelude::*;
+use infrastructure::driven::persistence::{SqliteCredentialRepository, RedisChallengeRepository, SqliteUserRepository, SqliteInvitationRepository, SqliteFilePermissionRepository, SqliteSessionRepository, SqliteQualityPreferenceRepository, SqliteUserPreferencesRepository, SqliteAuditRepository, SqliteNotificationRepository, RedisLoginAttemptRepository, SqliteAccessPolicyRepository, RedisVerificationCodeRepository, SqliteClientGroupRepository, SqliteDelegationRepository, SqliteFileJobRepository, SqliteUploadSessionRepository, SqliteSessionTimelineRepository, SqliteSessionSnapshotRepository, SqliteAppStateRepository, SqliteBandwidthRepository, SqliteAppCrashRepository, SqliteAuthSessionRepository, SqliteDataExportRepository, SqliteAccountDeletionRepository, SqliteVaultImportRepository, SqliteExternalIdentityRepository, SqliteProvisioningRepository, SqliteAccessTokenRepository, SqliteLegalHoldRepository, SqliteAppSettingRepository, SqliteTenantRepository, SqliteBrandingRepository, SqliteProcessingRepository, SqliteMediaMetadataRepository, SqliteOrganizationRuleRepository, SqliteFileHashRepository, SqliteFolderExportRepository, SqliteGalleryShareRepository, SqliteFileCommentRepository, SqliteFileRequestRepository, SqliteImpersonationConsentRepository, RedisSessionOwnershipRepository, RedisSchedulerRepository};
 use axum::routing::post;
 use infrastructure::driving::http::auth;
-use application::ports::{CredentialRepository, ChallengeRepository, InvitationRepository, FilePermissionRepository, SessionRepository};
+use application::ports::{CredentialRepository, ChallengeRepository, InvitationRepository, FilePermissionRepository, SessionRepository, QualityPreferenceRepository, UserPreferencesRepository, AuditRepository, NotificationRepository, LoginAttemptRepository, AccessPolicyRepository, VerificationCodeRepository, ClientGroupRepository, DelegationRepository, FileJobRepository, VaultStorage, UploadSessionRepository, UploadHook, SessionSnapshotRepository, BandwidthRepository, AppCrashRepository, DataExportRepository, AccountDeletionRepository, VaultImportRepository, ExternalIdentityRepository, ProvisioningRepository, AccessTokenRepository, LegalHoldRepository, AppSettingRepository, TenantRepository, BrandingRepository, FileProcessor, ProcessingRepository, MediaMetadataRepository, OrganizationRuleRepository, FileHashRepository, FolderExportRepository, GalleryShareRepository, FileCommentRepository, FileRequestRepository, ImpersonationConsentRepository};
+use application::sessions::affinity::SessionAffinity;
+use application::sessions::scheduler::Scheduler;
+use application::sessions::timeline::SessionTimelines;
+use application::sessions::app_state::AppStateStore;
+use application::auth_sessions::AuthSessions;
+use application::sessions::bandwidth::BandwidthAccounting;
+use application::sessions::stream_budget::StreamBudgetController;
 
 use diesel::r2d2::{self, ConnectionManager};
 use diesel::SqliteConnection;
@@ -28,6 +40,19 @@ const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // `--doctor [--json]`: check this host and exit without starting the server
+    if std::env::args().any(|arg| arg == "--doctor") {
+        let json = std::env::args().any(|arg| arg == "--json");
+        let passed = infrastructure::driving::doctor::run(MIGRATIONS, json).await;
+        std::process::exit(if passed { 0 } else { 1 });
+    }
+
+    // `admin <command>`: manage the instance from a shell, see `sandbox-server admin`
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).is_some_and(|arg| arg == "admin") {
+        std::process::exit(infrastructure::driving::admin_cli::run(&args[2..]).await);
+    }
+
     tracing::info!("[DEBUG] Backend main() started");
     // Set panic hook
     std::panic::set_hook(Box::new(|panic_info| {
@@ -44,15 +69,33 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         tracing::error!("[SHUTDOWN] Failed to set Ctrl-C handler: {}", e);
     }
 
-    // Minimal logging: info and above
-    tracing_subscriber::fmt::init();
+    // Minimal logging: info and above. Lines logged inside a session's span are also kept in
+    // memory for `/api/admin/sessions/{id}/logs`, at SESSION_LOG_LEVEL.
+    let session_logs = Arc::new(SessionLogs::new(SessionLogLimits::from_env()));
+    let session_log_level = std::env::var("SESSION_LOG_LEVEL")
+        .ok()
+        .and_then(|level| level.parse::<LevelFilter>().ok())
+        .unwrap_or(LevelFilter::INFO);
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::fmt::layer().with_filter(EnvFilter::from_default_env()))
+        .with(SessionLogLayer::new(session_logs.clone()).with_filter(session_log_level))
+        .init();
 
-    println!("Sandbox Server starting...");
+    info!(
+        version = build_info::VERSION,
+        build_hash = build_info::GIT_HASH.unwrap_or("unknown"),
+        features = ?build_info::enabled_features(),
+        "Sandbox Server starting"
+    );
 
     // Check prerequisites
-    println!("Checking prerequisites...");
     check_prerequisites()?;
 
+    // Settings from the environment and CONFIG_FILE; the non-secret ones can be reloaded
+    let config = Arc::new(
+        LiveConfig::from_env().map_err(|e| anyhow::anyhow!("Invalid configuration: {}", e))?,
+    );
+
     // Initialize WebAuthn
     let rp_id = std::env::var("WEBAUTHN_RP_ID").unwrap_or_else(|_| "localhost".to_string());
     let rp_origin = std::env::var("WEBAUTHN_ORIGIN").unwrap_or_else(|_| "http://localhost:5173".to_string());
@@ -84,13 +127,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .map_err(|e| anyhow::anyhow!("Failed to create database pool: {}", e))?;
 
     // Run embedded migrations
-    println!("Running database migrations...");
+    info!(db_path = %db_path, "Running database migrations");
     let mut conn = pool.get()
         .map_err(|e| anyhow::anyhow!("Failed to get DB connection: {}", e))?;
     conn.run_pending_migrations(MIGRATIONS)
         .map_err(|e| anyhow::anyhow!("Migration failed: {}", e))?;
     drop(conn);
-    println!("Database migrations completed");
+    info!("Database migrations completed");
 
     let pool = Arc::new(pool);
 
@@ -103,45 +146,296 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         as Arc<dyn InvitationRepository>;
     let file_permission_repo = Arc::new(SqliteFilePermissionRepository::new(pool.clone()))
         as Arc<dyn FilePermissionRepository>;
-    let session_repo = Arc::new(SqliteSessionRepository::new(pool))
+    let session_repo = Arc::new(SqliteSessionRepository::new(pool.clone()))
         as Arc<dyn SessionRepository>;
+    let quality_preference_repo = Arc::new(SqliteQualityPreferenceRepository::new(pool.clone()))
+        as Arc<dyn QualityPreferenceRepository>;
+    let user_preferences_repo = Arc::new(SqliteUserPreferencesRepository::new(pool.clone()))
+        as Arc<dyn UserPreferencesRepository>;
+    let audit_repo = Arc::new(SqliteAuditRepository::new(pool.clone()))
+        as Arc<dyn AuditRepository>;
+    let notification_repo = Arc::new(SqliteNotificationRepository::new(pool.clone()))
+        as Arc<dyn NotificationRepository>;
+    let access_policy_repo = Arc::new(SqliteAccessPolicyRepository::new(pool.clone()))
+        as Arc<dyn AccessPolicyRepository>;
+    let client_group_repo = Arc::new(SqliteClientGroupRepository::new(pool.clone()))
+        as Arc<dyn ClientGroupRepository>;
+    let delegation_repo = Arc::new(SqliteDelegationRepository::new(pool.clone()))
+        as Arc<dyn DelegationRepository>;
+    let file_job_repo = Arc::new(SqliteFileJobRepository::new(pool.clone()))
+        as Arc<dyn FileJobRepository>;
+    let session_timelines = Arc::new(SessionTimelines::new(Arc::new(SqliteSessionTimelineRepository::new(pool.clone()))));
+    let session_snapshot_repo = Arc::new(SqliteSessionSnapshotRepository::new(pool.clone()))
+        as Arc<dyn SessionSnapshotRepository>;
+    let app_states = Arc::new(AppStateStore::from_env(Arc::new(SqliteAppStateRepository::new(pool.clone()))));
+    let bandwidth_repo = Arc::new(SqliteBandwidthRepository::new(pool.clone()))
+        as Arc<dyn BandwidthRepository>;
+    let bandwidth = Arc::new(BandwidthAccounting::new(bandwidth_repo.clone()));
+    let app_crash_repo = Arc::new(SqliteAppCrashRepository::new(pool.clone()))
+        as Arc<dyn AppCrashRepository>;
+    let access_token_repo = Arc::new(SqliteAccessTokenRepository::new(pool.clone()))
+        as Arc<dyn AccessTokenRepository>;
+    let auth_sessions = Arc::new(AuthSessions::new(
+        Arc::new(SqliteAuthSessionRepository::new(pool.clone())),
+        access_token_repo.clone(),
+    ));
+    auth_sessions.refresh()
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to load revoked auth sessions: {}", e))?;
+    let data_export_repo = Arc::new(SqliteDataExportRepository::new(pool.clone()))
+        as Arc<dyn DataExportRepository>;
+    let account_deletion_repo = Arc::new(SqliteAccountDeletionRepository::new(pool.clone()))
+        as Arc<dyn AccountDeletionRepository>;
+    let vault_import_repo = Arc::new(SqliteVaultImportRepository::new(pool.clone()))
+        as Arc<dyn VaultImportRepository>;
+    let external_identity_repo = Arc::new(SqliteExternalIdentityRepository::new(pool.clone()))
+        as Arc<dyn ExternalIdentityRepository>;
+    let provisioning_repo = Arc::new(SqliteProvisioningRepository::new(pool.clone()))
+        as Arc<dyn ProvisioningRepository>;
+    let legal_hold_repo = Arc::new(SqliteLegalHoldRepository::new(pool.clone()))
+        as Arc<dyn LegalHoldRepository>;
+    let app_setting_repo = Arc::new(SqliteAppSettingRepository::new(pool.clone()))
+        as Arc<dyn AppSettingRepository>;
+    let tenant_repo = Arc::new(SqliteTenantRepository::new(pool.clone()))
+        as Arc<dyn TenantRepository>;
+    let branding_repo = Arc::new(SqliteBrandingRepository::new(pool.clone()))
+        as Arc<dyn BrandingRepository>;
+    let oidc_policy = domain::entities::external_identity::OidcPolicy::from_env()
+        .map_err(|e| anyhow::anyhow!("Invalid OpenID Connect settings: {}", e))?;
+    let account_deletion_grace = std::env::var("ACCOUNT_DELETION_GRACE_DAYS")
+        .ok()
+        .and_then(|s| s.parse::<i64>().ok())
+        .map(chrono::Duration::days)
+        .unwrap_or_else(|| chrono::Duration::days(domain::entities::account_deletion::DEFAULT_GRACE_DAYS));
+    let processing_repo = Arc::new(SqliteProcessingRepository::new(pool.clone()))
+        as Arc<dyn ProcessingRepository>;
+    let media_metadata_repo = Arc::new(SqliteMediaMetadataRepository::new(pool.clone()))
+        as Arc<dyn MediaMetadataRepository>;
+    let organization_rule_repo = Arc::new(SqliteOrganizationRuleRepository::new(pool.clone()))
+        as Arc<dyn OrganizationRuleRepository>;
+    let file_hash_repo = Arc::new(SqliteFileHashRepository::new(pool.clone()))
+        as Arc<dyn FileHashRepository>;
+    let folder_export_repo = Arc::new(SqliteFolderExportRepository::new(pool.clone()))
+        as Arc<dyn FolderExportRepository>;
+    let gallery_share_repo = Arc::new(SqliteGalleryShareRepository::new(pool.clone()))
+        as Arc<dyn GalleryShareRepository>;
+    let file_comment_repo = Arc::new(SqliteFileCommentRepository::new(pool.clone()))
+        as Arc<dyn FileCommentRepository>;
+    let file_request_repo = Arc::new(SqliteFileRequestRepository::new(pool.clone()))
+        as Arc<dyn FileRequestRepository>;
+    let impersonation_consent_repo = Arc::new(SqliteImpersonationConsentRepository::new(pool.clone()))
+        as Arc<dyn ImpersonationConsentRepository>;
+    let upload_session_repo = Arc::new(SqliteUploadSessionRepository::new(pool))
+        as Arc<dyn UploadSessionRepository>;
+    let local_storage = Arc::new(infrastructure::driven::storage::LocalVaultStorage::from_config(&storage_path, &config.current()));
+    let vault_storage = local_storage.clone() as Arc<dyn VaultStorage>;
+    // Virus scanning and versioning register here as they are added
+    let upload_hooks: Vec<Arc<dyn UploadHook>> =
+        vec![Arc::new(application::files::upload_hooks::QuotaHook::new(vault_storage.clone()))];
+    // Scanning, thumbnails, indexing and metadata register here, in the order they run
+    let processors: Vec<Arc<dyn FileProcessor>> = vec![
+        Arc::new(application::files::duplicates::HashStage::new(vault_storage.clone(), file_hash_repo.clone())),
+        Arc::new(infrastructure::driven::media::MetadataExtractor::new(vault_storage.clone(), media_metadata_repo.clone())),
+        // Moves the file, so it stays last
+        Arc::new(application::organization::OrganizeStage::new(
+            organization_rule_repo.clone(),
+            media_metadata_repo.clone(),
+            file_hash_repo.clone(),
+            vault_storage.clone(),
+            audit_repo.clone(),
+        )),
+    ];
 
     // Initialize Redis challenge repository
     let redis_url = std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://localhost:6379".to_string());
     let redis_client = redis::Client::open(redis_url)
         .map_err(|e| anyhow::anyhow!("Failed to create Redis client: {}", e))?;
-    let challenge_repo = Arc::new(RedisChallengeRepository::new(redis_client)) as Arc<dyn ChallengeRepository>;
+    let challenge_repo = Arc::new(RedisChallengeRepository::new(redis_client.clone())) as Arc<dyn ChallengeRepository>;
+    let login_attempt_repo = Arc::new(RedisLoginAttemptRepository::new(redis_client.clone())) as Arc<dyn LoginAttemptRepository>;
+    let verification_code_repo = Arc::new(RedisVerificationCodeRepository::new(redis_client.clone())) as Arc<dyn VerificationCodeRepository>;
 
-    let jwt_secret = std::env::var("JWT_SECRET").unwrap_or_else(|_| "dev_secret_key_change_in_production".to_string());
+    // Each replica claims the sessions it runs, so signaling can be routed back to it
+    let instance = application::ports::InstanceInfo {
+        id: std::env::var("INSTANCE_ID").unwrap_or_else(|_| uuid::Uuid::new_v4().to_string()),
+        websocket_base_url: std::env::var("WEBSOCKET_BASE_URL").unwrap_or_else(|_| "ws://localhost:8080".to_string()),
+    };
+    let claim_ttl = std::env::var("SESSION_CLAIM_TTL_SECS")
+        .ok()
+        .and_then(|s| s.parse::<u64>().ok())
+        .unwrap_or(30);
+    let host_metrics = Arc::new(infrastructure::driven::host_metrics::HostMetrics::from_env(
+        instance.id.clone(),
+        &std::env::var("API_BASE_URL").unwrap_or_else(|_| "http://localhost:8080".to_string()),
+    ));
+    let session_affinity = Arc::new(SessionAffinity::new(
+        instance,
+        Arc::new(RedisSessionOwnershipRepository::new(redis_client.clone())),
+        claim_ttl,
+    ));
+
+    // New sessions go to the host with room for their app's resource class
+    let env_ratio = |name: &str, default: f32| {
+        std::env::var(name).ok().and_then(|s| s.parse::<f32>().ok()).filter(|r| *r > 0.0).unwrap_or(default)
+    };
+    let overcommit = domain::entities::placement::OvercommitPolicy {
+        cpu_ratio: env_ratio("SCHEDULER_CPU_OVERCOMMIT", 2.0),
+        memory_ratio: env_ratio("SCHEDULER_MEMORY_OVERCOMMIT", 1.0),
+    };
+    let scheduler = Arc::new(Scheduler::new(Arc::new(RedisSchedulerRepository::new(redis_client)), overcommit, claim_ttl));
+
+    // Secrets from env / mounted files; production refuses to start without strong ones
+    let secrets = infrastructure::driven::secrets::load(
+        &infrastructure::driven::secrets::default_provider(),
+        infrastructure::driven::secrets::is_production(),
+    )
+    .map_err(|e| anyhow::anyhow!("Invalid secrets configuration: {}", e))?;
 
     // Initialize Xvfb manager
     let apps_root = std::env::var("APPS_ROOT").unwrap_or_else(|_| "/app/.app".to_string());
-    let xvfb_manager = Arc::new(XvfbManager::new(apps_root));
+    // Checked now so a broken template fails at startup rather than when a session streams
+    let pipeline_templates = infrastructure::driven::sandbox::PipelineTemplates::from_config(&config.current())
+        .map_err(|e| anyhow::anyhow!("Invalid pipeline templates: {:#}", e))?;
+    let xvfb_manager = Arc::new(XvfbManager::new(apps_root.clone(), Arc::new(pipeline_templates)));
+    // A missing encoder plugin disables its codec here, rather than failing each session start
+    let codec_support = Arc::new(infrastructure::driven::sandbox::CodecSupport::probe(&[
+        infrastructure::driven::sandbox::pipeline_template::STREAM_CODEC,
+    ]));
+    // Displays kept warm for popular apps (SESSION_POOL), filled in the background below
+    let session_pool = Arc::new(
+        infrastructure::driven::sandbox::SessionPool::from_env(xvfb_manager.clone())
+            .map_err(|e| anyhow::anyhow!("Failed to set up the session pool: {:#}", e))?,
+    );
+
+    // STUN/TURN servers offered to peers; probed in the background below
+    let ice_servers = Arc::new(
+        infrastructure::driven::ice_servers::IceServers::from_env(secrets.turn_credential)
+            .map_err(|e| anyhow::anyhow!("Invalid ICE servers: {:#}", e))?,
+    );
 
     // Initialize WebRTC adapter with XvfbManager
-    let webrtc_adapter = Arc::new(WebRTCAdapter::new(xvfb_manager.clone()));
+    let webrtc_adapter = Arc::new(WebRTCAdapter::new(xvfb_manager.clone(), session_timelines.clone(), ice_servers.clone()));
+
+    // Create IPC socket server for app communication (started below)
+    let ipc_socket_path = std::env::var("IPC_SOCKET_PATH")
+        .unwrap_or_else(|_| "/tmp/sandbox-ipc.sock".to_string());
+
+    // Restrict the backend's own filesystem access (BACKEND_LANDLOCK=false to disable)
+    let import_roots = application::imports::import_roots(config.current().get("IMPORT_ROOTS"));
+    restrict_backend_filesystem(&storage_path, &apps_root, &ipc_socket_path, &import_roots);
+    let ipc_server = Arc::new(IpcSocketServer::new(
+        ipc_socket_path.clone().into(),
+        app_states.clone(),
+        app_crash_repo.clone(),
+        local_storage.folder_sizes(),
+    ));
 
     // Create auth app state
+    let stream_budget = {
+        let current = config.current();
+        Arc::new(StreamBudgetController::new(StreamBudgetController::configured_budget(|name| current.parse(name))))
+    };
+    // Subsystems that take reloaded settings (POST /api/admin/config/reload or SIGHUP)
+    {
+        let storage = local_storage.clone();
+        config.on_reload("vault storage", move |config| {
+            storage.reconfigure(config);
+            Ok(())
+        });
+        let budget = stream_budget.clone();
+        config.on_reload("stream budget", move |config| {
+            budget.set_budget(StreamBudgetController::configured_budget(|name| config.parse(name)));
+            Ok(())
+        });
+        let xvfb_manager = xvfb_manager.clone();
+        config.on_reload("pipeline templates", move |config| {
+            let templates = infrastructure::driven::sandbox::PipelineTemplates::from_config(config)
+                .map_err(|e| format!("{:#}", e))?;
+            xvfb_manager.set_pipeline_templates(templates);
+            Ok(())
+        });
+    }
+
     let app_state = AppState {
         webauthn,
-        jwt_secret,
+        jwt_keys: Arc::new(secrets.jwt_keys),
         user_repo,
         credential_repo,
         challenge_repo,
         invitation_repo,
         file_permission_repo,
         session_repo,
+        quality_preference_repo,
+        user_preferences_repo,
+        audit_repo,
+        notification_repo,
+        login_attempt_repo,
+        verification_code_repo,
+        lockout_policy: Default::default(),
+        access_policy_repo,
+        client_group_repo,
+        delegation_repo,
+        file_job_repo,
+        vault_storage,
+        upload_session_repo,
+        appending_uploads: Default::default(),
+        upload_hooks: Arc::new(upload_hooks),
+        processors: Arc::new(processors),
+        processing_repo,
+        media_metadata_repo,
+        organization_rule_repo,
+        file_hash_repo,
+        folder_export_repo,
+        gallery_share_repo,
+        file_comment_repo,
+        file_request_repo,
+        impersonation_consent_repo,
+        geoip: infrastructure::driven::geoip::from_env(),
+        email_sender: infrastructure::driven::email::from_env(),
         xvfb_manager: xvfb_manager.clone(),
+        session_pool: session_pool.clone(),
+        ipc_server: ipc_server.clone(),
+        webrtc: webrtc_adapter.clone(),
+        session_timelines,
+        session_snapshot_repo,
+        app_states,
+        bandwidth_repo,
+        bandwidth,
+        app_crash_repo,
+        auth_sessions: auth_sessions.clone(),
+        data_export_repo,
+        account_deletion_repo,
+        account_deletion_grace,
+        vault_import_repo,
+        identity_provider: infrastructure::driven::oidc::from_env(),
+        external_identity_repo,
+        oidc_policy,
+        provisioning_repo,
+        access_token_repo,
+        legal_hold_repo,
+        app_setting_repo,
+        tenant_repo,
+        branding_repo,
+        maintenance: Arc::new(application::maintenance::Maintenance::default()),
+        storage_scheduler: Arc::new(application::storage_scheduler::StorageScheduler::default()),
+        config: config.clone(),
+        stream_budget: stream_budget.clone(),
+        session_affinity: session_affinity.clone(),
+        scheduler,
+        host_metrics,
+        ice_servers: ice_servers.clone(),
+        codec_support,
+        session_logs,
         storage_path: storage_path.clone(),
     };
 
+    // Vaults outside the default tenant live under their tenant's storage root
+    application::tenants::place_vaults(&app_state)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to locate tenants' vaults: {}", e))?;
+
     // Create API state
     // ApiState and video session handlers removed
 
-    // Start IPC socket server for app communication
-    let ipc_socket_path = std::env::var("IPC_SOCKET_PATH")
-        .unwrap_or_else(|_| "/tmp/sandbox-ipc.sock".to_string());
-    let ipc_server = Arc::new(IpcSocketServer::new(ipc_socket_path.clone().into()));
     let ipc_server_clone = ipc_server.clone();
 
     tokio::spawn(async move {
@@ -149,7 +443,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             tracing::error!("IPC server error: {}", e);
         }
     });
-    println!("IPC socket server started at {}", ipc_socket_path);
+    info!(path = %ipc_socket_path, "IPC socket server started");
 
     // Auth routes with AppState
     let auth_routes = auth::setup_routes()
@@ -167,17 +461,144 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .route("/api/applications/launch", post(infrastructure::driving::http::application_routes::launch_application))
         .with_state(app_state.clone());
 
-    use infrastructure::driving::http::{owner, client, invite};
+    let system_routes = Router::new()
+        .route("/health", get(infrastructure::driving::http::health::health))
+        .with_state(app_state.clone());
+
+    use infrastructure::driving::http::{owner, client, invite, gallery, file_request, comments, profile, super_admin};
     // Owner routes (require Owner role — enforced in handlers)
     let owner_routes = Router::new()
-        .route("/api/invitations", post(owner::invitations::create_invitation))
+        .route("/api/invitations", get(owner::invitations::list_invitations).post(owner::invitations::create_invitation))
         .route("/api/permissions", get(owner::permissions::list_permissions))
+        .route("/api/permissions/history", get(owner::permissions::permission_history))
+        .route("/api/legal-holds", get(owner::legal_holds::list_legal_holds).post(owner::legal_holds::place_legal_hold))
+        .route("/api/legal-holds/{id}", axum::routing::delete(owner::legal_holds::release_legal_hold))
         .route("/api/permissions/{id}", axum::routing::delete(owner::permissions::revoke_permission))
+        .route("/api/permissions/{id}/renew", post(owner::permissions::renew_permission))
+        .route("/api/users/{id}/unlock", post(owner::accounts::unlock_account))
+        .route("/api/access-policy", get(owner::access_policy::get_access_policy).put(owner::access_policy::update_access_policy))
+        .route("/api/branding", get(owner::branding::get_branding).put(owner::branding::update_branding))
+        .route("/api/applications/settings", get(owner::app_settings::list_app_settings))
+        .route("/api/applications/{app_id}/settings", axum::routing::put(owner::app_settings::update_app_setting))
+        .route("/api/groups", get(owner::groups::list_groups).post(owner::groups::create_group))
+        .route("/api/groups/{id}", axum::routing::put(owner::groups::update_group).delete(owner::groups::delete_group))
+        .route("/api/groups/{id}/members", post(owner::groups::add_group_member))
+        .route("/api/groups/{id}/members/{client_id}", axum::routing::delete(owner::groups::remove_group_member))
+        .route("/api/delegations", get(owner::delegations::my_delegations))
+        .route("/api/audit", get(owner::audit::list_audit_events))
+        .route("/api/vault/sessions", get(owner::sessions::list_vault_sessions))
+        .route("/api/vault/bandwidth", get(owner::bandwidth::list_bandwidth_usage))
+        .route(
+            "/api/users/{id}/bandwidth",
+            axum::routing::put(owner::bandwidth::set_bandwidth_caps).delete(owner::bandwidth::clear_bandwidth_caps),
+        )
+        .route("/api/files", get(owner::listing::list_files))
+        .route("/api/files/download", get(owner::downloads::download_file))
+        .route("/api/files/uploads", post(owner::uploads::create_upload))
+        .route(
+            "/api/files/uploads/{id}",
+            get(owner::uploads::get_upload).patch(owner::uploads::append_upload).delete(owner::uploads::cancel_upload),
+        )
+        .route("/api/files/archive", post(owner::archives::download_archive))
+        .route("/api/files/jobs", post(owner::file_jobs::submit_file_job))
+        .route("/api/files/jobs/{id}", get(owner::file_jobs::get_file_job).delete(owner::file_jobs::cancel_file_job))
+        .route("/api/files/processing/dead-letters", get(owner::processing::list_dead_letters))
+        .route("/api/files/processing/{id}/retry", post(owner::processing::retry_dead_letter))
+        .route("/api/files/metadata", get(owner::media::get_metadata))
+        .route("/api/files/media", get(owner::media::search_media))
+        .route("/api/files/media/facets", get(owner::media::get_facets))
+        .route("/api/files/duplicates", get(owner::duplicates::get_duplicates))
+        .route("/api/files/usage-breakdown", get(owner::usage::get_usage_breakdown))
+        .route("/api/files/duplicates/cleanup", post(owner::duplicates::clean_up_duplicates))
+        .route("/api/organization-rules", get(owner::organization_rules::list_rules).post(owner::organization_rules::create_rule))
+        .route("/api/organization-rules/preview", post(owner::organization_rules::preview_rule))
+        .route("/api/organization-rules/activity", get(owner::organization_rules::list_activity))
+        .route("/api/organization-rules/{id}", axum::routing::put(owner::organization_rules::update_rule).delete(owner::organization_rules::delete_rule))
+        .route("/api/clients/{id}/export", post(owner::client_exports::export_client_data))
+        .route("/api/clients/{id}/impersonate", post(owner::impersonation::impersonate_client))
+        .route("/api/impersonation-consents", get(owner::impersonation::list_impersonation_consents))
+        .route("/api/folder-exports", get(owner::folder_exports::list_folder_exports))
+        .route("/api/folder-exports/{id}/approve", post(owner::folder_exports::approve_folder_export))
+        .route("/api/folder-exports/{id}/deny", post(owner::folder_exports::deny_folder_export))
+        .route("/api/galleries", get(owner::galleries::list_galleries).post(owner::galleries::create_gallery))
+        .route("/api/galleries/{id}", axum::routing::delete(owner::galleries::revoke_gallery))
+        .route("/api/file-requests", get(owner::file_requests::list_file_requests).post(owner::file_requests::create_file_request))
+        .route("/api/file-requests/{id}", axum::routing::delete(owner::file_requests::revoke_file_request))
+        .route("/api/inbox", get(owner::file_requests::list_inbox))
+        .route("/api/inbox/{id}/approve", post(owner::file_requests::approve_inbox_upload))
+        .route("/api/inbox/{id}/reject", post(owner::file_requests::reject_inbox_upload))
+        .route("/api/clients/{id}", axum::routing::delete(owner::client_accounts::delete_client_account))
+        .route(
+            "/api/provisioning/tokens",
+            get(owner::provisioning_tokens::list_tokens).post(owner::provisioning_tokens::create_token),
+        )
+        .route("/api/provisioning/tokens/{id}", axum::routing::delete(owner::provisioning_tokens::revoke_token))
+        // Authenticated with a provisioning token instead of a login
+        .route("/api/provisioning/users", post(infrastructure::driving::http::provisioning::provision_users))
+        .with_state(app_state.clone());
+
+    // Super admin routes (require SuperAdmin role — enforced in handlers)
+    let super_admin_routes = Router::new()
+        .route("/api/admin/delegations", get(super_admin::delegations::list_delegations).post(super_admin::delegations::create_delegation))
+        .route("/api/admin/delegations/{id}", axum::routing::delete(super_admin::delegations::revoke_delegation))
+        .route(
+            "/api/admin/sessions/{id}/debug-dump",
+            get(super_admin::debug_dumps::download_debug_dump)
+                .post(super_admin::debug_dumps::start_debug_dump)
+                .delete(super_admin::debug_dumps::stop_debug_dump),
+        )
+        .route("/api/admin/sessions/{id}/logs", get(super_admin::session_logs::get_session_logs))
+        .route("/api/admin/scheduler", get(super_admin::scheduler::get_scheduler))
+        .route("/api/admin/render-stats", get(super_admin::render_stats::get_render_stats))
+        .route("/api/admin/crash-reports", get(super_admin::crash_reports::list_crash_reports))
+        .route("/api/admin/account-deletions/{id}", axum::routing::delete(super_admin::account_deletions::cancel_account_deletion))
+        .route("/api/admin/maintenance", post(super_admin::maintenance::set_maintenance))
+        .route("/api/admin/config/reload", post(super_admin::config::reload_config))
+        .route("/api/admin/info", get(super_admin::info::get_info))
+        .route("/api/admin/storage", get(super_admin::storage::get_storage_activity))
+        .route("/api/admin/imports", post(super_admin::imports::start_import))
+        .route("/api/admin/imports/{id}", get(super_admin::imports::get_import).delete(super_admin::imports::cancel_import))
+        .route("/api/admin/tenants", get(super_admin::tenants::list_tenants).post(super_admin::tenants::create_tenant))
+        .route("/api/admin/users", get(super_admin::tenants::list_users))
         .with_state(app_state.clone());
 
     // Client routes (require Client role — enforced in handlers)
     let client_routes = Router::new()
         .route("/api/my-permissions", get(client::my_permissions::list_my_permissions))
+        .route(
+            "/api/my-folder-exports",
+            get(client::folder_exports::list_my_folder_exports).post(client::folder_exports::request_folder_export),
+        )
+        .route("/api/my-folder-exports/{id}/download", get(client::folder_exports::download_folder_export))
+        .route("/api/my-folder-exports/{id}/key", get(client::folder_exports::get_folder_export_key))
+        .route(
+            "/api/my-impersonation-consents",
+            get(client::impersonation::list_my_impersonation_consents).post(client::impersonation::grant_impersonation_consent),
+        )
+        .route("/api/my-impersonation-consents/{owner_id}", axum::routing::delete(client::impersonation::withdraw_impersonation_consent))
+        .with_state(app_state.clone());
+
+    // Profile routes (any authenticated user)
+    let profile_routes = Router::new()
+        .route("/api/me/preferences", get(profile::preferences::get_preferences).put(profile::preferences::update_preferences))
+        .route("/api/me/credentials", get(profile::credentials::list_my_credentials))
+        .route("/api/auth/sessions", get(profile::auth_sessions::list_auth_sessions))
+        .route("/api/auth/sessions/{id}", axum::routing::delete(profile::auth_sessions::revoke_auth_session))
+        .route("/api/me/sessions", get(profile::sessions::list_my_sessions))
+        .route("/api/me", axum::routing::delete(profile::account::delete_my_account))
+        .route("/api/me/tokens", get(profile::access_tokens::list_access_tokens).post(profile::access_tokens::create_access_token))
+        .route("/api/me/tokens/{id}", axum::routing::delete(profile::access_tokens::revoke_access_token))
+        .route("/api/my-data/export", post(profile::data_exports::request_data_export))
+        .route("/api/my-data/exports/{id}", get(profile::data_exports::get_data_export))
+        .route("/api/my-data/exports/{id}/download", get(profile::data_exports::download_data_export))
+        .route("/api/sessions/{id}/timeline", get(profile::sessions::get_session_timeline))
+        .route("/api/sessions/{id}/signaling", get(profile::sessions::get_session_signaling))
+        .route("/api/sessions/{id}/suspend", post(profile::sessions::suspend_session))
+        .route("/api/sessions/{id}/resume", post(profile::sessions::resume_session))
+        // Owners and clients alike, on the files they may read
+        .route("/api/comments", get(comments::list_comments).post(comments::add_comment))
+        .route("/api/comments/counts", get(comments::count_comments))
+        .route("/api/comments/{id}", axum::routing::delete(comments::delete_comment))
         .with_state(app_state.clone());
 
     // Invite routes (public)
@@ -185,6 +606,21 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .route("/api/invitations/{token}", get(invite::view::view_invitation))
         .route("/api/invitations/{token}/accept/initiate", post(invite::initiate::initiate_webauthn_registration))
         .route("/api/invitations/{token}/accept/complete", post(invite::complete::complete_webauthn_registration))
+        .route("/api/invitations/{token}/verify-email", post(invite::verify_email::send_verification_code))
+        .with_state(app_state.clone());
+
+    // Public gallery routes: the link token stands in for an account
+    let gallery_routes = Router::new()
+        .route("/api/gallery/{token}", get(gallery::view_gallery))
+        .route("/api/gallery/{token}/unlock", post(gallery::unlock_gallery))
+        .route("/api/gallery/{token}/photos/{name}", get(gallery::get_photo))
+        .route("/api/gallery/{token}/photos/{name}/thumbnail", get(gallery::get_thumbnail))
+        .with_state(app_state.clone());
+
+    // Public file request routes: upload-only, the link token stands in for an account
+    let file_request_routes = Router::new()
+        .route("/api/file-request/{token}", get(file_request::view_file_request))
+        .route("/api/file-request/{token}/files", post(file_request::send_file))
         .with_state(app_state.clone());
 
     // Custom middleware: 503 if not initialized and not /api/setup/* or /health
@@ -214,9 +650,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .merge(auth_routes)
         .merge(ws_routes)
         .merge(app_routes)
+        .merge(system_routes)
         .merge(owner_routes)
+        .merge(super_admin_routes)
         .merge(client_routes)
+        .merge(profile_routes)
         .merge(invite_routes)
+        .merge(gallery_routes)
+        .merge(file_request_routes)
         .layer(
             CorsLayer::new()
                 .allow_origin(Any)
@@ -238,6 +679,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                             let sid = session.id.to_string();
                             let _ = state_for_expiry.xvfb_manager.cleanup_session(&sid).await;
                             let _ = state_for_expiry.session_repo.terminate(&session.id).await;
+                            if let Err(e) = state_for_expiry.session_timelines.finish(&sid).await {
+                                tracing::warn!("Failed to save timeline of session {}: {}", sid, e);
+                            }
+                            let _ = state_for_expiry.session_affinity.release(&sid).await;
                             tracing::info!("Expired session cleaned up: {}", sid);
                         }
                     }
@@ -247,28 +692,439 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         });
     }
 
+    // Background task: bring running client sessions in line with permissions that lapsed or
+    // were granted since their launch
+    {
+        let state_for_scopes = app_state.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(60));
+            loop {
+                interval.tick().await;
+                match application::sessions::scope::refresh(&state_for_scopes, None).await {
+                    Ok(0) => {}
+                    Ok(count) => tracing::info!("Updated the folders of {} running sessions", count),
+                    Err(e) => tracing::warn!("Failed to update the folders of running sessions: {}", e),
+                }
+            }
+        });
+    }
+
+    // Reload settings on SIGHUP, as `systemctl reload` sends
+    {
+        let config = config.clone();
+        tokio::spawn(async move {
+            let mut hangups = match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup()) {
+                Ok(hangups) => hangups,
+                Err(e) => {
+                    tracing::warn!("Config reload on SIGHUP unavailable: {}", e);
+                    return;
+                }
+            };
+            while hangups.recv().await.is_some() {
+                match config.reload() {
+                    Ok(changes) => tracing::info!(
+                        "Config reloaded: applied {:?}, needs a restart {:?}",
+                        changes.applied,
+                        changes.requires_restart
+                    ),
+                    Err(e) => tracing::warn!("Config reload failed: {}", e),
+                }
+            }
+        });
+    }
+
+    // Background task: pick up devices signed out through other instances
+    {
+        let auth_sessions = auth_sessions.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(30));
+            loop {
+                interval.tick().await;
+                if let Err(e) = auth_sessions.refresh().await {
+                    tracing::warn!("Failed to refresh revoked auth sessions: {}", e);
+                }
+            }
+        });
+    }
+
+    // Background task: warn owners and clients about permissions nearing expiry
+    {
+        let state_for_notice = app_state.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(300));
+            loop {
+                interval.tick().await;
+                let notice_hours = state_for_notice.config.current().parse::<i64>("PERMISSION_EXPIRY_NOTICE_HOURS").unwrap_or(72);
+                let result = application::permissions::notify_expiring::execute(
+                    &*state_for_notice.file_permission_repo,
+                    &*state_for_notice.user_repo,
+                    &*state_for_notice.notification_repo,
+                    state_for_notice.email_sender.as_deref(),
+                    chrono::Duration::hours(notice_hours),
+                ).await;
+                match result {
+                    Ok(0) => {}
+                    Ok(count) => tracing::info!("Sent expiry notices for {} permissions", count),
+                    Err(e) => tracing::warn!("Failed to send permission expiry notices: {}", e),
+                }
+            }
+        });
+    }
+
+    // Background task: run bulk file jobs, resuming any a restart interrupted. Each owner's
+    // jobs run in their own task, so one vault's large job does not hold up the others.
+    {
+        let state_for_jobs = app_state.clone();
+        tokio::spawn(async move {
+            let runner = application::files::run_jobs::JobRunner::default();
+            match application::files::run_jobs::requeue_interrupted(&*state_for_jobs.file_job_repo, &runner).await {
+                Ok(0) => {}
+                Ok(count) => tracing::info!("Resuming {} interrupted file jobs", count),
+                Err(e) => tracing::warn!("Failed to requeue interrupted file jobs: {}", e),
+            }
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(2));
+            loop {
+                interval.tick().await;
+                let owners = match application::files::run_jobs::queued_owners(&*state_for_jobs.file_job_repo).await {
+                    Ok(owners) => owners,
+                    Err(e) => {
+                        tracing::warn!("Failed to read the file job queue: {}", e);
+                        continue;
+                    }
+                };
+                for owner_id in owners {
+                    let Some(claim) = runner.claim(&owner_id) else {
+                        continue;
+                    };
+                    let state = state_for_jobs.clone();
+                    tokio::spawn(async move {
+                        let result = application::files::run_jobs::run_pending(
+                            &*state.file_job_repo,
+                            &*state.vault_storage,
+                            &*state.legal_hold_repo,
+                            &*state.media_metadata_repo,
+                            &*state.file_hash_repo,
+                            &*state.audit_repo,
+                            &claim,
+                        ).await;
+                        if let Err(e) = result {
+                            tracing::warn!("Failed to run file jobs of {}: {}", owner_id, e);
+                        }
+                    });
+                }
+            }
+        });
+    }
+
+    // Background task: run the processing stages on files that landed in a vault
+    {
+        let state_for_processing = app_state.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(5));
+            loop {
+                interval.tick().await;
+                if state_for_processing.processors.is_empty() {
+                    continue;
+                }
+                let policy = application::processing::retry_policy(&state_for_processing.config.current());
+                let result = application::processing::run_due(
+                    &*state_for_processing.processing_repo,
+                    &state_for_processing.processors,
+                    &*state_for_processing.audit_repo,
+                    &policy,
+                    chrono::Utc::now(),
+                ).await;
+                if let Err(e) = result {
+                    tracing::warn!("Failed to run file processing: {}", e);
+                }
+            }
+        });
+    }
+
+    // Background task: import host directories into owners' vaults, resuming any a restart
+    // interrupted
+    {
+        let state_for_imports = app_state.clone();
+        tokio::spawn(async move {
+            match application::imports::requeue_interrupted(&*state_for_imports.vault_import_repo).await {
+                Ok(0) => {}
+                Ok(count) => tracing::info!("Resuming {} interrupted vault imports", count),
+                Err(e) => tracing::warn!("Failed to requeue interrupted vault imports: {}", e),
+            }
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(5));
+            loop {
+                interval.tick().await;
+                let roots = application::imports::import_roots(state_for_imports.config.current().get("IMPORT_ROOTS"));
+                let result = application::imports::run_pending(
+                    &*state_for_imports.vault_import_repo,
+                    &*state_for_imports.vault_storage,
+                    &*state_for_imports.processing_repo,
+                    &state_for_imports.processors,
+                    &*state_for_imports.audit_repo,
+                    &roots,
+                ).await;
+                if let Err(e) = result {
+                    tracing::warn!("Failed to run vault imports: {}", e);
+                }
+            }
+        });
+    }
+
+    // Background task: assemble requested data and folder exports and drop those past their
+    // retention
+    {
+        let state_for_exports = app_state.clone();
+        tokio::spawn(async move {
+            match application::data_exports::run::requeue_interrupted(
+                &*state_for_exports.data_export_repo,
+                &*state_for_exports.vault_storage,
+            ).await {
+                Ok(0) => {}
+                Ok(count) => tracing::info!("Restarting {} interrupted data exports", count),
+                Err(e) => tracing::warn!("Failed to requeue interrupted data exports: {}", e),
+            }
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(5));
+            loop {
+                interval.tick().await;
+                if let Err(e) = application::data_exports::run::run_pending(&state_for_exports).await {
+                    tracing::warn!("Failed to run data exports: {}", e);
+                }
+                if let Err(e) = application::folder_exports::run_pending(&state_for_exports).await {
+                    tracing::warn!("Failed to build folder exports: {}", e);
+                }
+                if !may_run(&state_for_exports, application::storage_scheduler::EXPIRE_DATA_EXPORTS).await {
+                    continue;
+                }
+                let retention_hours = state_for_exports.config.current().parse::<i64>("DATA_EXPORT_RETENTION_HOURS").unwrap_or(168);
+                let before = chrono::Utc::now() - chrono::Duration::hours(retention_hours);
+                let result = application::data_exports::run::expire(
+                    &*state_for_exports.data_export_repo,
+                    &*state_for_exports.vault_storage,
+                    before,
+                ).await;
+                match result {
+                    Ok(0) => {}
+                    Ok(count) => tracing::info!("Deleted {} expired data exports", count),
+                    Err(e) => tracing::warn!("Failed to delete expired data exports: {}", e),
+                }
+                let result = application::folder_exports::expire(
+                    &*state_for_exports.folder_export_repo,
+                    &*state_for_exports.vault_storage,
+                    &*state_for_exports.audit_repo,
+                    chrono::Utc::now(),
+                ).await;
+                match result {
+                    Ok(0) => {}
+                    Ok(count) => tracing::info!("Expired {} folder exports", count),
+                    Err(e) => tracing::warn!("Failed to expire folder exports: {}", e),
+                }
+            }
+        });
+    }
+
+    // Background task: note when the storage disk reads or writes, so deferred work can run
+    // while it is awake anyway
+    {
+        let state_for_disk = app_state.clone();
+        let mut disk_activity = infrastructure::driven::disk_activity::DiskActivity::for_path(&storage_path);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(5));
+            loop {
+                interval.tick().await;
+                if disk_activity.poll() {
+                    state_for_disk.storage_scheduler.record_disk_activity(chrono::Utc::now());
+                }
+            }
+        });
+    }
+
+    // Background task: purge accounts whose deletion grace period is over
+    {
+        let state_for_deletions = app_state.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(3600));
+            loop {
+                interval.tick().await;
+                // Held back work waits for the disk to wake rather than for the next hour
+                while !may_run(&state_for_deletions, application::storage_scheduler::PURGE_DELETED_ACCOUNTS).await {
+                    tokio::time::sleep(tokio::time::Duration::from_secs(30)).await;
+                }
+                match application::account_deletion::purge_due(&state_for_deletions, chrono::Utc::now()).await {
+                    Ok(0) => {}
+                    Ok(count) => tracing::info!("Purged {} deleted accounts", count),
+                    Err(e) => tracing::warn!("Failed to purge deleted accounts: {}", e),
+                }
+            }
+        });
+    }
+
+    // Background task: keep the session pool full; launches also refill it as they take displays
+    if session_pool.is_enabled() {
+        let session_pool = session_pool.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(10));
+            loop {
+                interval.tick().await;
+                session_pool.refill().await;
+            }
+        });
+    }
+
+    // Background task: probe STUN/TURN servers so sessions skip unreachable ones
+    {
+        let ice_servers = ice_servers.clone();
+        let interval_secs = std::env::var("ICE_HEALTH_CHECK_SECS")
+            .ok()
+            .and_then(|s| s.parse::<u64>().ok())
+            .unwrap_or(60);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(interval_secs.max(1)));
+            loop {
+                interval.tick().await;
+                ice_servers.check_all().await;
+            }
+        });
+    }
+
+    // Background task: keep this instance's session claims and host status alive, and end
+    // sessions whose instance stopped. A claim lost to another instance leaves local
+    // resources to free.
+    {
+        let state_for_claims = app_state.clone();
+        let webrtc_adapter = webrtc_adapter.clone();
+        tokio::spawn(async move {
+            let affinity = state_for_claims.session_affinity.clone();
+            let mut interval = tokio::time::interval(affinity.renew_interval());
+            loop {
+                interval.tick().await;
+                match affinity.renew().await {
+                    Ok(lost) => {
+                        for sid in lost {
+                            tracing::warn!("Session {} was taken over by another instance, stopping it here", sid);
+                            let _ = webrtc_adapter.cleanup(&sid).await;
+                        }
+                    }
+                    Err(e) => tracing::warn!("Failed to renew session claims: {}", e),
+                }
+                let status = state_for_claims.host_metrics.status(&state_for_claims.xvfb_manager, state_for_claims.maintenance.is_active()).await;
+                if let Err(e) = state_for_claims.scheduler.publish(&status).await {
+                    tracing::warn!("Failed to publish host status: {}", e);
+                }
+                let result = application::sessions::end_orphaned::execute(
+                    &*state_for_claims.session_repo,
+                    &affinity,
+                    &state_for_claims.session_timelines,
+                ).await;
+                match result {
+                    Ok(0) => {}
+                    Ok(count) => tracing::info!("Ended {} sessions of stopped instances", count),
+                    Err(e) => tracing::warn!("Failed to end orphaned sessions: {}", e),
+                }
+            }
+        });
+    }
+
+    // Background task: end sessions left suspended past their retention
+    {
+        let state_for_suspended = app_state.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(3600));
+            loop {
+                interval.tick().await;
+                let retention_hours = state_for_suspended.config.current().parse::<i64>("SUSPENDED_SESSION_RETENTION_HOURS").unwrap_or(168);
+                let result = application::sessions::expire_suspended::execute(
+                    &*state_for_suspended.session_snapshot_repo,
+                    &*state_for_suspended.session_repo,
+                    chrono::Duration::hours(retention_hours),
+                ).await;
+                match result {
+                    Ok(0) => {}
+                    Ok(count) => tracing::info!("Ended {} sessions suspended past their retention", count),
+                    Err(e) => tracing::warn!("Failed to expire suspended sessions: {}", e),
+                }
+            }
+        });
+    }
+
+    // Background task: delete admin debug dumps past their retention
+    {
+        let state_for_dumps = app_state.clone();
+        tokio::spawn(async move {
+            let root = infrastructure::driving::http::super_admin::debug_dumps::dumps_root(&state_for_dumps.storage_path);
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(3600));
+            loop {
+                interval.tick().await;
+                let retention_hours = state_for_dumps.config.current().parse::<u64>("DEBUG_DUMP_RETENTION_HOURS").unwrap_or(72);
+                let before = std::time::SystemTime::now()
+                    .checked_sub(std::time::Duration::from_secs(retention_hours.saturating_mul(3600)))
+                    .unwrap_or(std::time::UNIX_EPOCH);
+                let running = state_for_dumps.xvfb_manager.debug_dumping_sessions().await;
+                let root = root.clone();
+                let result = tokio::task::spawn_blocking(move || {
+                    infrastructure::driven::sandbox::debug_dump::expire(&root, before, &running)
+                })
+                .await;
+                match result {
+                    Ok(Ok(0)) => {}
+                    Ok(Ok(count)) => tracing::info!("Deleted {} debug dumps past their retention", count),
+                    Ok(Err(e)) => tracing::warn!("Failed to delete expired debug dumps: {}", e),
+                    Err(e) => tracing::warn!("Debug dump cleanup task failed: {}", e),
+                }
+            }
+        });
+    }
+
+    // Background task: discard resumable uploads left idle past their expiry
+    {
+        let state_for_uploads = app_state.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(3600));
+            loop {
+                interval.tick().await;
+                // Held back work waits for the disk to wake rather than for the next hour
+                while !may_run(&state_for_uploads, application::storage_scheduler::EXPIRE_UPLOADS).await {
+                    tokio::time::sleep(tokio::time::Duration::from_secs(30)).await;
+                }
+                let result = application::files::expire_uploads::execute(
+                    &*state_for_uploads.upload_session_repo,
+                    &*state_for_uploads.vault_storage,
+                ).await;
+                match result {
+                    Ok(0) => {}
+                    Ok(count) => tracing::info!("Expired {} idle uploads", count),
+                    Err(e) => tracing::warn!("Failed to expire idle uploads: {}", e),
+                }
+            }
+        });
+    }
+
+    // Background task: free the places and bytes of file request uploads that never finished
+    {
+        let state_for_inbox = app_state.clone();
+        tokio::spawn(async move {
+            let max_age = chrono::Duration::hours(domain::entities::file_request::RECEIVING_EXPIRY_HOURS);
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(3600));
+            loop {
+                interval.tick().await;
+                match application::file_requests::expire_receiving(&state_for_inbox, max_age).await {
+                    Ok(0) => {}
+                    Ok(count) => tracing::info!("Dropped {} unfinished file request uploads", count),
+                    Err(e) => tracing::warn!("Failed to drop unfinished file request uploads: {}", e),
+                }
+            }
+        });
+    }
+
     // Start server
     let addr = SocketAddr::from(([0, 0, 0, 0], 8080));
-    println!("Server listening on http://{}", addr);
-    println!("\nAvailable Endpoints:");
-    println!("   AUTH:");
-    println!("   - POST http://localhost:8080/api/auth/register");
-    println!("   - POST http://localhost:8080/api/auth/login");
-    println!("   APPLICATION PLATFORM:");
-    println!("   - GET  http://localhost:8080/api/applications");
-    println!("   - POST http://localhost:8080/api/applications/launch");
-    println!("   WEBSOCKET:");
-    println!("   - WS   ws://localhost:8080/ws (for application signaling, not video)");
-    println!("   SYSTEM:");
-    println!("   - GET  http://localhost:8080/health");
-    println!();
-
+    info!(%addr, instance_id = %app_state.session_affinity.instance().id, "Server listening");
 
     let listener = tokio::net::TcpListener::bind(addr).await?;
     info!("[DEBUG] TcpListener bound on {}", addr);
     info!("[DEBUG] About to call axum::serve");
     tracing::warn!("[AXUM] >>> axum::serve about to start");
-    let serve_result = axum::serve(listener, app).await;
+    // Peer address feeds per-IP login throttling
+    let serve_result = axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>()).await;
     tracing::warn!("[AXUM] <<< axum::serve returned: {:?}", serve_result);
     if let Err(ref e) = serve_result {
         tracing::error!("[SHUTDOWN] axum::serve returned error: {:?}", e);
@@ -281,13 +1137,69 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// Whether a deferrable background job may touch the storage disk now, per `DEFER_BACKGROUND_JOBS`.
+async fn may_run(state: &infrastructure::AppState, job: &'static str) -> bool {
+    let policy = application::storage_scheduler::DeferPolicy::from_config(&state.config.current());
+    let sessions_running = state.xvfb_manager.session_count().await > 0;
+    let may_run = state.storage_scheduler.may_run(job, &policy, sessions_running, chrono::Utc::now());
+    if !may_run {
+        tracing::debug!("Deferring {} until the storage disk is awake", job);
+    }
+    may_run
+}
+
+fn restrict_backend_filesystem(storage_path: &str, apps_root: &str, ipc_socket_path: &str, import_roots: &[String]) {
+    let enabled = std::env::var("BACKEND_LANDLOCK")
+        .map(|v| v != "0" && !v.eq_ignore_ascii_case("false"))
+        .unwrap_or(true);
+    if !enabled {
+        tracing::warn!("Backend Landlock self-restriction disabled (BACKEND_LANDLOCK=false)");
+        return;
+    }
+
+    let mut read_write = vec![
+        storage_path.to_string(),
+        // X11 sockets/locks, xkbcomp output, default IPC socket
+        "/tmp".to_string(),
+        "/dev".to_string(),
+        "/sys/fs/cgroup/sandbox".to_string(),
+    ];
+    if let Some(dir) = std::path::Path::new(ipc_socket_path).parent() {
+        read_write.push(dir.display().to_string());
+    }
+    // Host directories imported into vaults; writable so moves can remove their sources
+    read_write.extend(import_roots.iter().cloned());
+    // GStreamer plugin registry cache
+    if let Ok(home) = std::env::var("HOME") {
+        read_write.push(format!("{}/.cache", home));
+    }
+
+    let mut read_only: Vec<String> = ["/usr", "/lib", "/lib64", "/lib32", "/bin", "/sbin", "/etc", "/proc", "/sys"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    read_only.push(apps_root.to_string());
+    if let Ok(fonts_dir) = std::env::var("SANDBOX_FONTS_DIR") {
+        read_only.push(fonts_dir);
+    }
+    if let Some(geoip_dir) = std::env::var("GEOIP_DB_PATH").ok().and_then(|p| std::path::Path::new(&p).parent().map(|d| d.display().to_string())) {
+        read_only.push(geoip_dir);
+    }
+
+    match infrastructure::driven::sandbox::landlock::apply_backend_landlock(&read_write, &read_only) {
+        Ok(true) => info!("Backend filesystem access restricted with Landlock"),
+        Ok(false) => tracing::warn!("Landlock not supported by this kernel; backend runs unrestricted"),
+        Err(e) => tracing::warn!("Failed to apply backend Landlock restrictions: {}", e),
+    }
+}
+
 fn check_prerequisites() -> Result<(), Box<dyn std::error::Error>> {
     // Check GStreamer
     if gstreamer::init().is_err() {
-        eprintln!("GStreamer not available. Install GStreamer runtime and plugins.");
+        tracing::error!("GStreamer not available. Install GStreamer runtime and plugins.");
         return Err("GStreamer not available".into());
     }
-    println!("GStreamer found");
+    info!(version = %gstreamer::version_string(), "GStreamer found");
 
     Ok(())
 }
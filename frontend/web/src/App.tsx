@@ -45,7 +45,9 @@ function App() {
     try {
       const response = await fetch('http://localhost:8080/api/setup/status');
       const data = await response.json();
-      setNeedsSetup(data.initialized === false);
+      // A new tenant's first admin signs up through the setup page too, with their tenant's link
+      const tenantSetup = new URLSearchParams(window.location.search).has('tenant_token');
+      setNeedsSetup(data.initialized === false || tenantSetup);
     } catch (error) {
       console.error('Failed to check setup status:', error);
     } finally {
@@ -1,5 +1,7 @@
 import React, { useEffect, useRef, useState } from 'react'
-import { Box, Typography, CircularProgress, Alert } from '@mui/material'
+import { Box, Typography, CircularProgress, LinearProgress, Alert, Chip } from '@mui/material'
+import VisibilityIcon from '@mui/icons-material/Visibility'
+import SpeedIcon from '@mui/icons-material/Speed'
 
 export interface SignalingMessage {
   type: string
@@ -7,28 +9,225 @@ export interface SignalingMessage {
   candidate?: string
   sdpMid?: string | null
   sdpMLineIndex?: number | null
+  events?: AccessibilityEvent[]
+  watchers?: number
+  low_latency?: boolean
+  rtt_ms?: number
+  local?: CandidateInfo
+  remote?: CandidateInfo
+  relay_forced?: boolean
+  codec?: string
+  source?: 'app' | 'first_frame'
+  ready_after_ms?: number
+  stage?: string
+  detail?: string | null
+  elapsed_ms?: number
+  percent?: number | null
+  active?: boolean
+  message?: string | null
+  token?: string
+  grace_secs?: number
+  peer_id?: string
+  name?: string
+  controlling?: boolean
+  x?: number
+  y?: number
+  capture?: string[]
+}
+
+// One end of the ICE candidate pair the server reports as carrying the media
+export interface CandidateInfo {
+  candidate_type: 'host' | 'srflx' | 'prflx' | 'relay'
+  protocol: string
+  address: string
+  port: number
+}
+
+export interface AccessibilityEvent {
+  kind: 'focus' | 'click' | 'value-changed'
+  role: string
+  label?: string | null
+  value?: string | null
+}
+
+// A file the app sends in chunks over the 'transfer' data channel
+interface TransferInfo {
+  path: string
+  filename: string
+  size: number
+  etag: string
+}
+
+interface DownloadChunk {
+  type: 'download-chunk'
+  transfer: TransferInfo
+  offset: number
+  data: string
+}
+
+interface PendingTransfer {
+  info: TransferInfo
+  parts: Uint8Array[]
+  received: number
+  resumeRequested: boolean
+}
+
+// Incomplete downloads by app path; kept outside the component so they survive a reconnect
+const pendingTransfers = new Map<string, PendingTransfer>()
+
+const saveFile = (parts: Uint8Array[], filename: string) => {
+  const url = URL.createObjectURL(new Blob(parts))
+  const link = document.createElement('a')
+  link.href = url
+  link.download = filename
+  link.click()
+  URL.revokeObjectURL(url)
+}
+
+const requestResume = (websocket: WebSocket | null, pending: PendingTransfer) => {
+  if (!websocket || websocket.readyState !== WebSocket.OPEN) return
+  pending.resumeRequested = true
+  websocket.send(JSON.stringify({
+    type: 'resume-download',
+    path: pending.info.path,
+    offset: pending.received,
+    etag: pending.info.etag
+  }))
+}
+
+// Append a chunk if it continues the transfer; a gap means chunks were lost, so ask the app
+// to resume from the last byte received
+const handleDownloadChunk = (chunk: DownloadChunk, websocket: WebSocket | null) => {
+  const { transfer, offset } = chunk
+  let pending = pendingTransfers.get(transfer.path)
+  if (offset === 0 && (!pending || pending.received > 0 || pending.info.etag !== transfer.etag)) {
+    pending = { info: transfer, parts: [], received: 0, resumeRequested: false }
+    pendingTransfers.set(transfer.path, pending)
+  }
+  if (!pending || pending.info.etag !== transfer.etag) return
+  if (offset !== pending.received) {
+    if (offset > pending.received && !pending.resumeRequested) requestResume(websocket, pending)
+    return
+  }
+  const bytes = Uint8Array.from(atob(chunk.data), (c) => c.charCodeAt(0))
+  pending.parts.push(bytes)
+  pending.received += bytes.length
+  pending.resumeRequested = false
+  if (pending.received >= transfer.size) {
+    pendingTransfers.delete(transfer.path)
+    saveFile(pending.parts, transfer.filename)
+  }
+}
+
+// A dropped signaling socket is retried this often; the server keeps the session for
+// SESSION_RECONNECT_GRACE_SECS (30 s by default)
+const RECONNECT_DELAY_MS = 2000
+const MAX_RECONNECT_ATTEMPTS = 15
+// Reconnect token of each session, kept per tab so a refresh takes the running session back
+const reconnectTokenKey = (websocketUrl: string): string | null => {
+  const sessionId = new URL(websocketUrl).searchParams.get('session')
+  return sessionId ? `reconnect-token:${sessionId}` : null
+}
+// An ICE disconnect that lasts this long is treated as a network change
+const ICE_RESTART_DELAY_MS = 3000
+// A peer still not connected after this long is given up on, and the video is streamed over
+// the signaling socket instead, for networks that block WebRTC entirely
+const FALLBACK_DELAY_MS = 15000
+// Fallback frames start with a flags byte (bit 0: keyframe) and a big-endian u64 timestamp in µs
+const FALLBACK_HEADER_LEN = 9
+
+// Key combination as the server's shortcut policy writes it, such as `Ctrl+Shift+S` or `F5`;
+// null for plain typing, which is always forwarded
+const shortcutOf = (e: KeyboardEvent): string | null => {
+  const functionKey = /^F\d{1,2}$/.test(e.key)
+  if (!e.ctrlKey && !e.altKey && !e.metaKey && !functionKey) return null
+  const key = e.code.startsWith('Key') ? e.code.slice(3) : e.code.startsWith('Digit') ? e.code.slice(5) : e.key
+  return [e.ctrlKey && 'Ctrl', e.altKey && 'Alt', e.shiftKey && 'Shift', e.metaKey && 'Meta', key]
+    .filter(Boolean)
+    .join('+')
+}
+
+// Text read by screen readers for a widget event from the streamed app
+const describeAccessibilityEvent = (event: AccessibilityEvent): string =>
+  [event.label, event.role, event.value].filter(Boolean).join(', ')
+
+// Overlay text for each launch step the server reports
+const LAUNCH_STAGE_LABELS: Record<string, string> = {
+  launched: 'Preparing the session...',
+  xvfb_started: 'Starting the display...',
+  app_spawned: 'Starting the app...',
+  first_frame: 'Rendering the first frame...',
+  peer_connected: 'Connecting the video...',
+  fallback_started: 'Connecting the video...',
+  ice_completed: 'Connecting the video...',
+  first_rtp_sent: 'Receiving the video...',
+  session_ready: 'Ready'
+}
+
+// Pointer of another participant of the session, as fractions of the video
+interface PeerCursor {
+  name: string
+  controlling: boolean
+  x: number
+  y: number
+}
+
+interface LaunchProgress {
+  percent: number
+  label: string
 }
 
 interface VideoPlayerProps {
   websocketUrl: string
+  // Watch mode: the stream is shown but no input is sent
+  readOnly?: boolean
   onConnectionStateChange?: (state: RTCPeerConnectionState) => void
   onError?: (error: string) => void
 }
 
 export const VideoPlayer: React.FC<VideoPlayerProps> = ({
   websocketUrl,
+  readOnly = false,
   onConnectionStateChange,
   onError
 }) => {
   const videoRef = useRef<HTMLVideoElement>(null)
+  const canvasRef = useRef<HTMLCanvasElement>(null)
   const containerRef = useRef<HTMLDivElement>(null)
   const pcRef = useRef<RTCPeerConnection | null>(null)
   const wsRef = useRef<WebSocket | null>(null)
   const mountedRef = useRef(true)
   const connectionInitializedRef = useRef(false)
   const resizeTimeoutRef = useRef<NodeJS.Timeout | null>(null)
+  const reconnectTimeoutRef = useRef<NodeJS.Timeout | null>(null)
+  // Shortcuts the app takes from the browser, negotiated when the socket opens
+  const capturedShortcutsRef = useRef<Set<string>>(new Set())
   const [connectionState, setConnectionState] = useState<string>('new')
   const [error, setError] = useState<string | null>(null)
+  // Pointer icon reported by the server; the cursor is composited here, not in the video
+  const [remoteCursor, setRemoteCursor] = useState<string>('default')
+  const [announcement, setAnnouncement] = useState<string>('')
+  // Owners currently watching this session, reported by the server for transparency
+  const [watchers, setWatchers] = useState<number>(0)
+  // Set while the server streams the reduced low-latency profile because of a slow link
+  const [lowLatency, setLowLatency] = useState<boolean>(false)
+  // Set while the media goes through the TURN server rather than directly
+  const [relayed, setRelayed] = useState<boolean>(false)
+  // Set once the video arrives over the signaling socket and is drawn to the canvas
+  const [fallback, setFallback] = useState<boolean>(false)
+  // Set once the server reports the app is up; until then the video may still be black
+  const [ready, setReady] = useState<boolean>(false)
+  const mountedAtRef = useRef<number>(performance.now())
+  // Furthest launch step reported by the server, shown under the spinner until ready
+  const [launchProgress, setLaunchProgress] = useState<LaunchProgress | null>(null)
+  // Bumped for each signaling socket that opens, so input handlers move to the new socket
+  const [signalingEpoch, setSignalingEpoch] = useState<number>(0)
+  // Set while the server is in maintenance; the session keeps running
+  const [maintenance, setMaintenance] = useState<string | null>(null)
+  // Set when the session was shared without input; the server drops it anyway
+  const [inputDisabled, setInputDisabled] = useState<boolean>(false)
+  // Pointers of the client and owners watching, drawn over the video with their names
+  const [peerCursors, setPeerCursors] = useState<Record<string, PeerCursor>>({})
 
   useEffect(() => {
     mountedRef.current = true
@@ -39,12 +238,91 @@ export const VideoPlayer: React.FC<VideoPlayerProps> = ({
     }
     connectionInitializedRef.current = true
 
+    const sendSignal = (message: SignalingMessage) => {
+      const websocket = wsRef.current
+      if (websocket?.readyState === WebSocket.OPEN) {
+        websocket.send(JSON.stringify(message))
+      }
+    }
+
+    // After a network change the server renegotiates ICE on the same peer, so the app and its
+    // stream carry on; one restart at a time until the connection recovers
+    let iceRestartPending = false
+    // Once streaming over the socket, the peer connection is no longer needed
+    let fallbackActive = false
+    // Set once the server suspends the session; its socket is then not reopened
+    let suspended = false
+    const tokenKey = readOnly ? null : reconnectTokenKey(websocketUrl)
+    const restartIce = () => {
+      if (readOnly || suspended || fallbackActive || iceRestartPending || !pcRef.current || pcRef.current.connectionState === 'new') return
+      iceRestartPending = true
+      console.log('Requesting ICE restart')
+      sendSignal({ type: 'restart-ice' })
+    }
+
+    // Encoded frames from the socket are decoded with WebCodecs and drawn to the canvas
+    let fallbackCodec: string | null = null
+    let decoder: VideoDecoder | null = null
+    let fallbackTimeout: NodeJS.Timeout | null = null
+    const startFallback = () => {
+      if (readOnly || fallbackActive || !('VideoDecoder' in window)) return
+      fallbackActive = true
+      console.log('Peer connection did not recover, requesting fallback stream')
+      sendSignal({ type: 'start-fallback-stream' })
+    }
+    const armFallback = () => {
+      if (!fallbackTimeout && !suspended) {
+        fallbackTimeout = setTimeout(startFallback, FALLBACK_DELAY_MS)
+      }
+    }
+    const disarmFallback = () => {
+      if (fallbackTimeout) {
+        clearTimeout(fallbackTimeout)
+        fallbackTimeout = null
+      }
+    }
+    const closeDecoder = () => {
+      if (decoder && decoder.state !== 'closed') {
+        decoder.close()
+      }
+      decoder = null
+    }
+    const createDecoder = (codec: string) => {
+      const next = new VideoDecoder({
+        output: (frame) => {
+          const canvas = canvasRef.current
+          if (canvas) {
+            if (canvas.width !== frame.displayWidth || canvas.height !== frame.displayHeight) {
+              canvas.width = frame.displayWidth
+              canvas.height = frame.displayHeight
+            }
+            canvas.getContext('2d')?.drawImage(frame, 0, 0)
+          }
+          frame.close()
+        },
+        error: (err) => console.error('Fallback decoder error:', err)
+      })
+      next.configure({ codec })
+      return next
+    }
+    const handleFallbackFrame = (data: ArrayBuffer) => {
+      if (!fallbackCodec || data.byteLength <= FALLBACK_HEADER_LEN) return
+      const view = new DataView(data)
+      const keyframe = (view.getUint8(0) & 1) === 1
+      // A decoder closed by an error can only be replaced from a keyframe
+      if (!decoder || decoder.state === 'closed') {
+        if (!keyframe) return
+        decoder = createDecoder(fallbackCodec)
+      }
+      decoder.decode(new EncodedVideoChunk({
+        type: keyframe ? 'key' : 'delta',
+        timestamp: Number(view.getBigUint64(1)),
+        data: new Uint8Array(data, FALLBACK_HEADER_LEN)
+      }))
+    }
+
     const setupConnection = async () => {
       try {
-        // Create WebSocket connection
-        const websocket = new WebSocket(websocketUrl)
-        wsRef.current = websocket
-
         // Create RTCPeerConnection
         const peerConnection = new RTCPeerConnection({
           iceServers: [
@@ -65,6 +343,12 @@ export const VideoPlayer: React.FC<VideoPlayerProps> = ({
             readyState: event.track.readyState
           })
           console.log('Stream details:', event.streams[0])
+
+          // Render frames as soon as they decode instead of smoothing playback with a jitter buffer
+          const receiver = event.receiver as RTCRtpReceiver & { jitterBufferTarget?: number | null }
+          if ('jitterBufferTarget' in receiver) {
+            receiver.jitterBufferTarget = 0
+          }
           
           if (videoRef.current && mountedRef.current) {
             videoRef.current.srcObject = event.streams[0]
@@ -86,13 +370,63 @@ export const VideoPlayer: React.FC<VideoPlayerProps> = ({
           }
         }
 
+        // Cursor metadata ({ x, y, icon }) sent by the server over a data channel
+        peerConnection.ondatachannel = (event) => {
+          if (event.channel.label === 'transfer') {
+            // Downloads interrupted by a previous connection continue where they stopped
+            event.channel.onopen = () => {
+              pendingTransfers.forEach((pending) => requestResume(wsRef.current, pending))
+            }
+            event.channel.onmessage = (msg) => {
+              try {
+                const chunk = JSON.parse(msg.data)
+                if (chunk.type === 'download-chunk') {
+                  handleDownloadChunk(chunk, wsRef.current)
+                }
+              } catch (err) {
+                console.warn('Invalid download chunk:', err)
+              }
+            }
+            return
+          }
+          if (event.channel.label !== 'cursor') return
+          event.channel.onmessage = (msg) => {
+            try {
+              const cursor = JSON.parse(msg.data)
+              if (mountedRef.current && typeof cursor.icon === 'string') {
+                setRemoteCursor(cursor.icon)
+              }
+            } catch (err) {
+              console.warn('Invalid cursor metadata:', err)
+            }
+          }
+        }
+
         // Handle ICE candidates
+        // An empty candidate tells the server gathering is complete
         peerConnection.onicecandidate = (event) => {
-          if (event.candidate && websocket.readyState === WebSocket.OPEN) {
-            websocket.send(JSON.stringify({
-              type: 'ice-candidate',
-              candidate: event.candidate.candidate
-            }))
+          sendSignal({
+            type: 'ice-candidate',
+            candidate: event.candidate?.candidate ?? '',
+            sdpMid: event.candidate?.sdpMid ?? null,
+            sdpMLineIndex: event.candidate?.sdpMLineIndex ?? null
+          })
+        }
+
+        // Renegotiate when the network path breaks instead of giving up on the session
+        let iceDisconnectTimeout: NodeJS.Timeout | null = null
+        peerConnection.oniceconnectionstatechange = () => {
+          const state = peerConnection.iceConnectionState
+          if (iceDisconnectTimeout) {
+            clearTimeout(iceDisconnectTimeout)
+            iceDisconnectTimeout = null
+          }
+          if (state === 'connected' || state === 'completed') {
+            iceRestartPending = false
+          } else if (state === 'failed') {
+            restartIce()
+          } else if (state === 'disconnected') {
+            iceDisconnectTimeout = setTimeout(restartIce, ICE_RESTART_DELAY_MS)
           }
         }
 
@@ -104,15 +438,19 @@ export const VideoPlayer: React.FC<VideoPlayerProps> = ({
             setConnectionState(state)
             onConnectionStateChange?.(state)
             
-            if (state === 'failed') {
-              setError('WebRTC connection failed')
-              onError?.('WebRTC connection failed')
+            if (state === 'connected') {
+              setError(null)
             }
           }
+          if (state === 'connected') {
+            disarmFallback()
+          } else if (state === 'disconnected' || state === 'failed') {
+            armFallback()
+          }
         }
 
         // Handle signaling messages
-        websocket.onmessage = async (event) => {
+        const handleSignalingMessage = async (websocket: WebSocket, event: MessageEvent) => {
           try {
             const message: SignalingMessage = JSON.parse(event.data)
             console.log('Received signaling message:', message.type)
@@ -135,8 +473,8 @@ export const VideoPlayer: React.FC<VideoPlayerProps> = ({
                 break
 
               case 'ice-candidate':
-                // Add server's ICE candidate
-                if (message.candidate) {
+                // Add server's ICE candidate; an empty one ends its gathering
+                if (message.candidate !== undefined) {
                   await peerConnection.addIceCandidate(
                     new RTCIceCandidate({
                       candidate: message.candidate,
@@ -147,8 +485,132 @@ export const VideoPlayer: React.FC<VideoPlayerProps> = ({
                 }
                 break
 
+              case 'accessibility':
+                if (message.events?.length && mountedRef.current) {
+                  setAnnouncement(message.events.map(describeAccessibilityEvent).join('. '))
+                }
+                break
+
+              case 'watch-status':
+                if (mountedRef.current) {
+                  setWatchers(message.watchers ?? 0)
+                }
+                break
+
+              case 'latency-mode':
+                console.log('Low-latency mode', message.low_latency ? 'on' : 'off', `(RTT ${message.rtt_ms} ms)`)
+                if (mountedRef.current) {
+                  setLowLatency(message.low_latency ?? false)
+                }
+                break
+
+              case 'ice-diagnostics':
+                if (message.local && message.remote) {
+                  const describe = (c: CandidateInfo) => `${c.candidate_type} ${c.protocol} ${c.address}:${c.port}`
+                  console.log('Media path:', describe(message.local), '<->', describe(message.remote),
+                    message.relay_forced ? '(relay forced)' : '')
+                  if (mountedRef.current) {
+                    setRelayed(message.local.candidate_type === 'relay' || message.remote.candidate_type === 'relay')
+                  }
+                }
+                break
+
+              case 'fallback-stream':
+                console.log('Streaming over the signaling socket as', message.codec)
+                fallbackActive = true
+                fallbackCodec = message.codec ?? 'vp8'
+                closeDecoder()
+                if (mountedRef.current) {
+                  setFallback(true)
+                  setError(null)
+                }
+                break
+
+              case 'session-ready':
+                console.log('Session ready', {
+                  source: message.source,
+                  serverMs: message.ready_after_ms,
+                  clientMs: Math.round(performance.now() - mountedAtRef.current)
+                })
+                if (mountedRef.current) {
+                  setReady(true)
+                }
+                break
+
+              case 'launch-progress':
+                if (message.stage === 'launch_failed') {
+                  const failure = `Launch failed${message.detail ? `: ${message.detail}` : ''}`
+                  if (mountedRef.current) {
+                    setError(failure)
+                    onError?.(failure)
+                  }
+                  break
+                }
+                if (mountedRef.current && message.stage && message.percent != null) {
+                  const step = { percent: message.percent, label: LAUNCH_STAGE_LABELS[message.stage] ?? message.stage }
+                  // Replayed and live steps can interleave; the bar never moves back
+                  setLaunchProgress((current) => (current && current.percent >= step.percent ? current : step))
+                }
+                break
+
+              case 'maintenance':
+                if (mountedRef.current) {
+                  setMaintenance(message.active ? (message.message ?? 'The server is about to go down for maintenance') : null)
+                }
+                break
+
+              case 'peer-cursor':
+                if (message.peer_id && mountedRef.current) {
+                  const cursor: PeerCursor = {
+                    name: message.name ?? '',
+                    controlling: message.controlling ?? false,
+                    x: message.x ?? 0,
+                    y: message.y ?? 0
+                  }
+                  setPeerCursors(cursors => ({ ...cursors, [message.peer_id!]: cursor }))
+                }
+                break
+
+              case 'peer-cursor-gone':
+                if (message.peer_id && mountedRef.current) {
+                  setPeerCursors(({ [message.peer_id!]: _gone, ...rest }) => rest)
+                }
+                break
+
+              case 'shortcut-policy':
+                capturedShortcutsRef.current = new Set(message.capture ?? [])
+                break
+
+              case 'input-disabled':
+                if (mountedRef.current) {
+                  setInputDisabled(true)
+                }
+                break
+
+              case 'reconnect-token':
+                if (tokenKey && message.token) {
+                  sessionStorage.setItem(tokenKey, message.token)
+                }
+                break
+
+              case 'session-suspended':
+                console.log('Session suspended')
+                suspended = true
+                if (tokenKey) {
+                  sessionStorage.removeItem(tokenKey)
+                }
+                disarmFallback()
+                if (mountedRef.current) {
+                  setConnectionState('disconnected')
+                  setError('Session suspended')
+                  onError?.('Session suspended')
+                }
+                websocket.close()
+                break
+
               case 'error':
                 console.error('Signaling error:', message)
+                iceRestartPending = false
                 if (mountedRef.current) {
                   setError('Signaling error')
                   onError?.('Signaling error')
@@ -164,26 +626,69 @@ export const VideoPlayer: React.FC<VideoPlayerProps> = ({
           }
         }
 
-        // Request offer from server
-        websocket.onopen = () => {
-          console.log('WebSocket connected, requesting offer...')
-          websocket.send(JSON.stringify({ type: 'request-offer' }))
-        }
+        // A signaling socket that drops (e.g. Wi-Fi to LTE) is reopened and the peer's ICE
+        // restarted; the first socket asks for the initial offer
+        const connectSignaling = (attempt: number) => {
+          // Each token binds one socket; a failed or later attempt uses the session id alone
+          const token = attempt === 0 && tokenKey ? sessionStorage.getItem(tokenKey) : null
+          if (token && tokenKey) {
+            sessionStorage.removeItem(tokenKey)
+          }
+          const url = token ? `${websocketUrl}&reconnect=${encodeURIComponent(token)}` : websocketUrl
+          const websocket = new WebSocket(url)
+          wsRef.current = websocket
+          websocket.binaryType = 'arraybuffer'
+          let opened = false
+          websocket.onmessage = (event) => {
+            if (event.data instanceof ArrayBuffer) {
+              handleFallbackFrame(event.data)
+            } else {
+              handleSignalingMessage(websocket, event)
+            }
+          }
 
-        websocket.onerror = (err) => {
-          console.error('WebSocket error:', err)
-          if (mountedRef.current) {
-            setError('WebSocket connection error')
-            onError?.('WebSocket connection error')
+          websocket.onopen = () => {
+            opened = true
+            if (mountedRef.current) {
+              setSignalingEpoch((epoch) => epoch + 1)
+            }
+            if (attempt === 0 && peerConnection.connectionState === 'new') {
+              console.log('WebSocket connected, requesting offer...')
+              websocket.send(JSON.stringify({ type: 'request-offer' }))
+              armFallback()
+            } else if (fallbackActive) {
+              console.log('WebSocket reconnected, resuming fallback stream')
+              websocket.send(JSON.stringify({ type: 'start-fallback-stream' }))
+            } else {
+              console.log('WebSocket reconnected')
+              iceRestartPending = false
+              restartIce()
+            }
           }
-        }
 
-        websocket.onclose = () => {
-          console.log('WebSocket closed')
-          if (mountedRef.current) {
-            setConnectionState('disconnected')
+          websocket.onerror = (err) => {
+            console.error('WebSocket error:', err)
+          }
+
+          websocket.onclose = () => {
+            console.log('WebSocket closed')
+            if (!mountedRef.current) return
+            // A watch ends with its socket; the owner starts a new one
+            if (readOnly || suspended) {
+              setConnectionState('disconnected')
+              return
+            }
+            const nextAttempt = opened ? 1 : attempt + 1
+            if (nextAttempt > MAX_RECONNECT_ATTEMPTS) {
+              setConnectionState('disconnected')
+              setError('Connection lost')
+              onError?.('Connection lost')
+              return
+            }
+            reconnectTimeoutRef.current = setTimeout(() => connectSignaling(nextAttempt), RECONNECT_DELAY_MS)
           }
         }
+        connectSignaling(0)
 
       } catch (err) {
         console.error('Error setting up connection:', err)
@@ -195,11 +700,18 @@ export const VideoPlayer: React.FC<VideoPlayerProps> = ({
     }
 
     setupConnection()
+    window.addEventListener('online', restartIce)
 
     // Cleanup
     return () => {
       mountedRef.current = false
       connectionInitializedRef.current = false
+      window.removeEventListener('online', restartIce)
+      disarmFallback()
+      closeDecoder()
+      if (reconnectTimeoutRef.current) {
+        clearTimeout(reconnectTimeoutRef.current)
+      }
       if (pcRef.current) {
         pcRef.current.close()
       }
@@ -251,14 +763,44 @@ export const VideoPlayer: React.FC<VideoPlayerProps> = ({
         clearTimeout(resizeTimeoutRef.current)
       }
     }
-  }, [connectionState])
+  }, [connectionState, signalingEpoch])
 
-  // Handle input events
+  // Share where the pointer is, also when watching or without input, so the others see it
   useEffect(() => {
     const container = containerRef.current
     const ws = wsRef.current
     if (!container || !ws || ws.readyState !== WebSocket.OPEN) return
 
+    let lastMove = 0
+    const handlePointerMove = (e: MouseEvent) => {
+      const now = Date.now()
+      if (now - lastMove < 50 || ws.readyState !== WebSocket.OPEN) return
+      lastMove = now
+      const rect = container.getBoundingClientRect()
+      const x = Math.min(Math.max((e.clientX - rect.left) / rect.width, 0), 1)
+      const y = Math.min(Math.max((e.clientY - rect.top) / rect.height, 0), 1)
+      ws.send(JSON.stringify({ type: 'pointer-position', x, y }))
+    }
+    const handlePointerLeave = () => {
+      if (ws.readyState === WebSocket.OPEN) {
+        ws.send(JSON.stringify({ type: 'pointer-left' }))
+      }
+    }
+
+    container.addEventListener('mousemove', handlePointerMove)
+    container.addEventListener('mouseleave', handlePointerLeave)
+    return () => {
+      container.removeEventListener('mousemove', handlePointerMove)
+      container.removeEventListener('mouseleave', handlePointerLeave)
+    }
+  }, [connectionState, signalingEpoch])
+
+  // Handle input events
+  useEffect(() => {
+    const container = containerRef.current
+    const ws = wsRef.current
+    if (readOnly || inputDisabled || !container || !ws || ws.readyState !== WebSocket.OPEN) return
+
     const sendInput = (event: any) => {
       if (ws.readyState === WebSocket.OPEN) {
         ws.send(JSON.stringify(event))
@@ -272,9 +814,12 @@ export const VideoPlayer: React.FC<VideoPlayerProps> = ({
       if (now - lastMouseMove < 33) return // ~30fps
       lastMouseMove = now
       
+      // Coordinates in stream pixels; the server maps them back to the display
       const rect = container.getBoundingClientRect()
-      const x = Math.round((e.clientX - rect.left) / rect.width * 1920)
-      const y = Math.round((e.clientY - rect.top) / rect.height * 1080)
+      const streamWidth = videoRef.current?.videoWidth || canvasRef.current?.width || 1920
+      const streamHeight = videoRef.current?.videoHeight || canvasRef.current?.height || 1080
+      const x = Math.round((e.clientX - rect.left) / rect.width * streamWidth)
+      const y = Math.round((e.clientY - rect.top) / rect.height * streamHeight)
       sendInput({ type: 'mouse-move', x, y })
     }
 
@@ -291,6 +836,9 @@ export const VideoPlayer: React.FC<VideoPlayerProps> = ({
     }
 
     const handleKeyDown = (e: KeyboardEvent) => {
+      // Shortcuts outside the policy stay with the browser; the server would drop them
+      const shortcut = shortcutOf(e)
+      if (shortcut && !capturedShortcutsRef.current.has(shortcut)) return
       e.preventDefault()
       sendInput({ type: 'key-down', key: e.key, code: e.code })
     }
@@ -324,7 +872,7 @@ export const VideoPlayer: React.FC<VideoPlayerProps> = ({
         sendInput({ type: 'mouse-scroll', delta_y: e.deltaY })
       })
     }
-  }, [connectionState])
+  }, [connectionState, signalingEpoch, readOnly, inputDisabled])
 
   return (
     <Box ref={containerRef} sx={{ 
@@ -332,8 +880,99 @@ export const VideoPlayer: React.FC<VideoPlayerProps> = ({
       width: '100%', 
       height: '100%',
       outline: 'none',
-      bgcolor: '#000'
+      bgcolor: '#000',
+      cursor: remoteCursor
     }}>
+      <Box
+        role="status"
+        aria-live="polite"
+        sx={{ position: 'absolute', width: 1, height: 1, overflow: 'hidden', clip: 'rect(0 0 0 0)' }}
+      >
+        {announcement}
+      </Box>
+
+      {(watchers > 0 || readOnly) && (
+        <Chip
+          icon={<VisibilityIcon />}
+          color="warning"
+          label={readOnly ? 'Watching (read-only)' : `Being watched by ${watchers} owner${watchers > 1 ? 's' : ''}`}
+          sx={{ position: 'absolute', top: 8, right: 8, zIndex: 10 }}
+        />
+      )}
+
+      {Object.entries(peerCursors).map(([peerId, cursor]) => (
+        <Box
+          key={peerId}
+          sx={{
+            position: 'absolute',
+            left: `${cursor.x * 100}%`,
+            top: `${cursor.y * 100}%`,
+            zIndex: 9,
+            pointerEvents: 'none',
+            transition: 'left 50ms linear, top 50ms linear'
+          }}
+        >
+          <Box
+            sx={{
+              width: 0,
+              height: 0,
+              borderLeft: '6px solid transparent',
+              borderRight: '6px solid transparent',
+              borderBottom: '14px solid',
+              borderBottomColor: cursor.controlling ? 'primary.main' : 'secondary.main',
+              transform: 'rotate(-30deg)',
+              transformOrigin: 'top center'
+            }}
+          />
+          <Chip
+            size="small"
+            color={cursor.controlling ? 'primary' : 'secondary'}
+            label={cursor.controlling ? cursor.name : `${cursor.name} (pointing)`}
+            sx={{ ml: 1, mt: 0.25 }}
+          />
+        </Box>
+      ))}
+
+      {lowLatency && (
+        <Chip
+          icon={<SpeedIcon />}
+          size="small"
+          label="Low-latency mode"
+          sx={{ position: 'absolute', top: 8, left: 8, zIndex: 10 }}
+        />
+      )}
+
+      {fallback && (
+        <Chip
+          size="small"
+          label="Streaming without WebRTC"
+          sx={{ position: 'absolute', bottom: 8, right: 8, zIndex: 10 }}
+        />
+      )}
+
+      {inputDisabled && !readOnly && (
+        <Chip
+          size="small"
+          icon={<VisibilityIcon />}
+          label="View only: input is disabled"
+          sx={{ position: 'absolute', top: 8, left: '50%', transform: 'translateX(-50%)', zIndex: 10 }}
+        />
+      )}
+
+      {relayed && (
+        <Chip
+          size="small"
+          label="Relayed via TURN"
+          sx={{ position: 'absolute', bottom: 8, left: 8, zIndex: 10 }}
+        />
+      )}
+
+      {maintenance && (
+        <Alert severity="warning" sx={{ mb: 2 }}>
+          {maintenance}
+        </Alert>
+      )}
+
       {error && (
         <Alert severity="error" sx={{ mb: 2 }}>
           {error}
@@ -360,11 +999,21 @@ export const VideoPlayer: React.FC<VideoPlayerProps> = ({
           height: '100%',
           backgroundColor: '#000',
           objectFit: 'fill',
-          display: 'block'
+          display: fallback ? 'none' : 'block'
+        }}
+      />
+
+      <canvas
+        ref={canvasRef}
+        style={{
+          width: '100%',
+          height: '100%',
+          backgroundColor: '#000',
+          display: fallback ? 'block' : 'none'
         }}
       />
       
-      {connectionState !== 'connected' && !error && (
+      {((connectionState !== 'connected' && !fallback) || !ready) && !error && (
         <Box
           sx={{
             position: 'absolute',
@@ -382,11 +1031,25 @@ export const VideoPlayer: React.FC<VideoPlayerProps> = ({
         >
           <CircularProgress sx={{ mb: 2 }} />
           <Typography sx={{ color: 'white' }}>
-            {connectionState === 'new' && 'Initializing...'}
-            {connectionState === 'connecting' && 'Connecting...'}
-            {connectionState === 'failed' && 'Connection failed'}
-            {connectionState === 'disconnected' && 'Disconnected'}
+            {launchProgress && connectionState !== 'failed' && connectionState !== 'disconnected'
+              ? launchProgress.label
+              : connectionState === 'connected' || fallback ? 'Starting the app...' : (
+                <>
+                  {connectionState === 'new' && 'Initializing...'}
+                  {connectionState === 'connecting' && 'Connecting...'}
+                  {connectionState === 'failed' && 'Connection failed'}
+                  {connectionState === 'disconnected' && 'Disconnected'}
+                </>
+              )}
           </Typography>
+          {launchProgress && (
+            <LinearProgress
+              variant="determinate"
+              value={launchProgress.percent}
+              aria-label="Launch progress"
+              sx={{ width: 240, mt: 2 }}
+            />
+          )}
         </Box>
       )}
     </Box>
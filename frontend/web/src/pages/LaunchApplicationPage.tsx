@@ -59,23 +59,35 @@ export function LaunchApplicationPage() {
     setError(null);
 
     try {
-      const response = await authFetch('http://localhost:8080/api/applications/launch', {
-        method: 'POST',
-        headers: {
-          'Content-Type': 'application/json',
-        },
-        body: JSON.stringify({
-          app_id: appId,
-          user_id: user?.id || 'test-user',
-          user_role: userRole,
-          allowed_paths: allowedPaths,
-          video_width: videoWidth,
-          video_height: videoHeight,
-          video_framerate: videoFramerate,
-          enable_watermarking: enableWatermarking,
-          timeout_minutes: timeoutMinutes,
-        }),
-      });
+      const launch = (url: string, placedOn?: string) =>
+        authFetch(url, {
+          method: 'POST',
+          headers: {
+            'Content-Type': 'application/json',
+          },
+          body: JSON.stringify({
+            app_id: appId,
+            user_id: user?.id || 'test-user',
+            user_role: userRole,
+            allowed_paths: allowedPaths,
+            video_width: videoWidth,
+            video_height: videoHeight,
+            video_framerate: videoFramerate,
+            scale_factor: window.devicePixelRatio || 1,
+            // Used for the app's clock unless a time zone is set in the preferences
+            timezone: Intl.DateTimeFormat().resolvedOptions().timeZone,
+            enable_watermarking: enableWatermarking,
+            timeout_minutes: timeoutMinutes,
+            placed_on: placedOn,
+          }),
+        });
+
+      let response = await launch('http://localhost:8080/api/applications/launch');
+      // 421: the scheduler placed the session on another host, which launches it
+      if (response.status === 421) {
+        const target = await response.json();
+        response = await launch(target.launch_url, target.instance_id);
+      }
 
       if (!response.ok) {
         const errorData = await response.text();
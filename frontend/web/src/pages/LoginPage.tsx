@@ -1,5 +1,5 @@
-import { useState } from 'react'
-import { useNavigate } from 'react-router-dom'
+import { useEffect, useRef, useState } from 'react'
+import { useNavigate, useSearchParams } from 'react-router-dom'
 import { useTranslation } from 'react-i18next'
 import { Formik, Form } from 'formik'
 import * as Yup from 'yup'
@@ -11,8 +11,10 @@ import {
   Button,
   Alert,
   Box,
+  Divider,
 } from '@mui/material'
 import SecurityIcon from '@mui/icons-material/Security'
+import LoginIcon from '@mui/icons-material/Login'
 import { useAuthStore } from '../store/authStore'
 
 // Helper functions for WebAuthn data conversion
@@ -46,12 +48,197 @@ function convertCredentialRequestOptions(options: any): PublicKeyCredentialReque
   };
 }
 
+function convertCredentialCreationOptions(options: any): PublicKeyCredentialCreationOptions {
+  return {
+    ...options,
+    challenge: typeof options.challenge === 'string'
+      ? base64urlToArrayBuffer(options.challenge)
+      : arrayToArrayBuffer(options.challenge),
+    user: {
+      ...options.user,
+      id: typeof options.user.id === 'string'
+        ? base64urlToArrayBuffer(options.user.id)
+        : arrayToArrayBuffer(options.user.id),
+    },
+    excludeCredentials: options.excludeCredentials?.map((cred: any) => ({
+      ...cred,
+      id: typeof cred.id === 'string'
+        ? base64urlToArrayBuffer(cred.id)
+        : arrayToArrayBuffer(cred.id),
+    })) || [],
+  };
+}
+
+function serializeAttestation(credential: PublicKeyCredential) {
+  const response = credential.response as AuthenticatorAttestationResponse
+  return {
+    id: credential.id,
+    rawId: Array.from(new Uint8Array(credential.rawId)),
+    response: {
+      attestationObject: Array.from(new Uint8Array(response.attestationObject)),
+      clientDataJSON: Array.from(new Uint8Array(response.clientDataJSON)),
+    },
+    type: credential.type,
+  }
+}
+
+async function postJson(url: string, body: unknown) {
+  const res = await fetch(url, {
+    method: 'POST',
+    headers: { 'Content-Type': 'application/json' },
+    body: JSON.stringify(body),
+  })
+  if (!res.ok) {
+    const errData = await res.text()
+    throw new Error(errData || 'Login failed')
+  }
+  return res.json()
+}
+
+function serializeAssertion(credential: PublicKeyCredential) {
+  const response = credential.response as AuthenticatorAssertionResponse
+  return {
+    id: credential.id,
+    rawId: Array.from(new Uint8Array(credential.rawId)),
+    response: {
+      authenticatorData: Array.from(new Uint8Array(response.authenticatorData)),
+      clientDataJSON: Array.from(new Uint8Array(response.clientDataJSON)),
+      signature: Array.from(new Uint8Array(response.signature)),
+      userHandle: response.userHandle
+        ? Array.from(new Uint8Array(response.userHandle))
+        : null,
+    },
+    type: credential.type,
+  }
+}
+
 export function LoginPage() {
   const { t } = useTranslation()
   const [loading, setLoading] = useState(false)
   const [error, setError] = useState('')
   const navigate = useNavigate()
+  const [searchParams, setSearchParams] = useSearchParams()
+  const [oidcEnabled, setOidcEnabled] = useState(false)
   const login = useAuthStore((state) => state.login)
+  // Pending passkey autofill request, aborted when the user logs in with their email instead
+  const conditionalRequest = useRef<AbortController | null>(null)
+
+  useEffect(() => {
+    fetch('http://localhost:8080/api/auth/oidc')
+      .then((res) => (res.ok ? res.json() : { enabled: false }))
+      .then(({ enabled }) => setOidcEnabled(enabled))
+      .catch(() => setOidcEnabled(false))
+  }, [])
+
+  // Back from the identity provider with a ticket to redeem, or the reason it failed
+  useEffect(() => {
+    const ticket = searchParams.get('oidc_ticket')
+    const oidcError = searchParams.get('oidc_error')
+    if (!ticket && !oidcError) {
+      return
+    }
+    setSearchParams({}, { replace: true })
+    if (oidcError) {
+      setError(t('auth.login.providerError', { reason: oidcError }))
+      return
+    }
+
+    const redeem = async () => {
+      setLoading(true)
+      setError('')
+      conditionalRequest.current?.abort()
+      const result = await postJson('http://localhost:8080/api/auth/oidc/redeem', { ticket })
+      let token: string = result.token
+      if (result.passkey_required) {
+        // OIDC_PASSKEY=second_factor: confirm with one of the user's passkeys
+        const credential = await navigator.credentials.get({
+          publicKey: convertCredentialRequestOptions(result.options.publicKey),
+        }) as PublicKeyCredential
+        const completed = await postJson('http://localhost:8080/api/auth/oidc/passkey', {
+          challenge_id: result.challenge_id,
+          credential: serializeAssertion(credential),
+        })
+        token = completed.token
+      } else if (result.passkey_setup_required) {
+        // ...or register their first one
+        const credential = await navigator.credentials.create({
+          publicKey: convertCredentialCreationOptions(result.options.publicKey),
+        }) as PublicKeyCredential
+        const completed = await postJson('http://localhost:8080/api/auth/oidc/passkey/setup', {
+          challenge_id: result.challenge_id,
+          credential: serializeAttestation(credential),
+        })
+        token = completed.token
+      }
+      await login(token)
+      navigate('/')
+    }
+
+    redeem()
+      .catch((err) => {
+        console.error('Provider login error:', err)
+        setError(err instanceof Error ? err.message : 'Login failed')
+      })
+      .finally(() => setLoading(false))
+  }, [searchParams, setSearchParams, login, navigate, t])
+
+  useEffect(() => {
+    const controller = new AbortController()
+    conditionalRequest.current = controller
+
+    const startAutofill = async () => {
+      if (!window.PublicKeyCredential?.isConditionalMediationAvailable
+        || !(await PublicKeyCredential.isConditionalMediationAvailable())) {
+        return
+      }
+      const optionsRes = await fetch('http://localhost:8080/api/auth/login/options', { method: 'POST' })
+      if (!optionsRes.ok) {
+        return
+      }
+      const { options, challenge_id } = await optionsRes.json()
+
+      // Resolves only once the user picks a passkey from the email field's autofill
+      const credential = await navigator.credentials.get({
+        mediation: 'conditional',
+        publicKey: convertCredentialRequestOptions(options.publicKey),
+        signal: controller.signal,
+      }) as PublicKeyCredential | null
+      if (!credential) {
+        return
+      }
+
+      setLoading(true)
+      setError('')
+      const completeRes = await fetch('http://localhost:8080/api/auth/login/complete', {
+        method: 'POST',
+        headers: { 'Content-Type': 'application/json' },
+        body: JSON.stringify({ challenge_id, credential: serializeAssertion(credential) }),
+      })
+      if (!completeRes.ok) {
+        const errData = await completeRes.text()
+        throw new Error(errData || 'Failed to complete login')
+      }
+      const { token } = await completeRes.json()
+      await login(token)
+      navigate('/')
+    }
+
+    startAutofill()
+      .catch((err) => {
+        if (err instanceof DOMException && err.name === 'AbortError') {
+          return
+        }
+        console.error('Passkey autofill error:', err)
+        setError(err instanceof Error ? err.message : 'Login failed')
+      })
+      .finally(() => {
+        if (!controller.signal.aborted) {
+          setLoading(false)
+        }
+      })
+
+    return () => controller.abort()
+  }, [login, navigate])
 
   const validationSchema = Yup.object({
     email: Yup.string().email('Invalid email').required('Email is required'),
@@ -60,6 +247,8 @@ export function LoginPage() {
   const handleLogin = async (values: { email: string }) => {
     setLoading(true)
     setError('')
+    // Browsers allow a single pending credential request
+    conditionalRequest.current?.abort()
 
     try {
       // Step 1: Initiate login
@@ -92,25 +281,7 @@ export function LoginPage() {
         headers: { 'Content-Type': 'application/json' },
         body: JSON.stringify({
           challenge_id,
-          credential: {
-            id: credential.id,
-            rawId: Array.from(new Uint8Array(credential.rawId)),
-            response: {
-              authenticatorData: Array.from(
-                new Uint8Array((credential.response as AuthenticatorAssertionResponse).authenticatorData)
-              ),
-              clientDataJSON: Array.from(
-                new Uint8Array(credential.response.clientDataJSON)
-              ),
-              signature: Array.from(
-                new Uint8Array((credential.response as AuthenticatorAssertionResponse).signature)
-              ),
-              userHandle: (credential.response as AuthenticatorAssertionResponse).userHandle
-                ? Array.from(new Uint8Array((credential.response as AuthenticatorAssertionResponse).userHandle!))
-                : null,
-            },
-            type: credential.type,
-          },
+          credential: serializeAssertion(credential),
           email: values.email,
         }),
       });
@@ -179,6 +350,7 @@ export function LoginPage() {
                   helperText={touched.email && errors.email}
                   margin="normal"
                   type="email"
+                  autoComplete="username webauthn"
                 />
 
                 <Button
@@ -195,6 +367,22 @@ export function LoginPage() {
               </Form>
             )}
           </Formik>
+
+          {oidcEnabled && (
+            <>
+              <Divider sx={{ my: 3 }}>{t('auth.login.or')}</Divider>
+              <Button
+                fullWidth
+                variant="outlined"
+                size="large"
+                disabled={loading}
+                startIcon={<LoginIcon />}
+                href="http://localhost:8080/api/auth/oidc/login"
+              >
+                {t('auth.login.providerButton')}
+              </Button>
+            </>
+          )}
         </Paper>
       </Box>
     </Container>
@@ -61,6 +61,11 @@ function SetupPage({ onSetupComplete }: SetupPageProps) {
   const { t } = useTranslation();
   const [loading, setLoading] = useState(false);
   const [error, setError] = useState('');
+  // Set when opening the link printed by `sandbox-server admin create-super-admin`
+  const params = new URLSearchParams(window.location.search);
+  const setupToken = params.get('setup_token') ?? undefined;
+  // Set when opening the link a platform admin got when creating a tenant
+  const tenantToken = params.get('tenant_token') ?? undefined;
 
   const validationSchema = Yup.object({
     email: Yup.string().email('Invalid email').required('Email is required'),
@@ -76,7 +81,12 @@ function SetupPage({ onSetupComplete }: SetupPageProps) {
       const initiateRes = await fetch('http://localhost:8080/api/setup/initiate-registration', {
         method: 'POST',
         headers: { 'Content-Type': 'application/json' },
-        body: JSON.stringify({ email: values.email, display_name: values.displayName }),
+        body: JSON.stringify({
+          email: values.email,
+          display_name: values.displayName,
+          setup_token: setupToken,
+          tenant_token: tenantToken,
+        }),
       });
 
       if (!initiateRes.ok) {
@@ -117,6 +127,8 @@ function SetupPage({ onSetupComplete }: SetupPageProps) {
           },
           email: values.email,
           display_name: values.displayName,
+          setup_token: setupToken,
+          tenant_token: tenantToken,
         }),
       });
 
@@ -163,7 +175,7 @@ function SetupPage({ onSetupComplete }: SetupPageProps) {
           )}
 
           <Formik
-            initialValues={{ email: '', displayName: '' }}
+            initialValues={{ email: params.get('email') ?? '', displayName: params.get('name') ?? '' }}
             validationSchema={validationSchema}
             onSubmit={handleSubmit}
           >
@@ -16,28 +16,40 @@ import StopCircleIcon from '@mui/icons-material/StopCircle'
 import { VideoPlayer } from '../components/VideoPlayer'
 import { WebRTCService } from '../services/webrtc'
 import { useAuthStore } from '../store/authStore'
+import { authFetch } from '../services/authFetch'
 
 export const VideoSessionPage: React.FC = () => {
   const [searchParams] = useSearchParams()
   const launchedSessionId = searchParams.get('sessionId')
+  // Owners open a client's session read-only with ?sessionId=...&watch=1
+  const watchMode = searchParams.get('watch') === '1'
   
   const [sessionId, setSessionId] = useState<string | null>(launchedSessionId)
   const [websocketUrl, setWebsocketUrl] = useState<string | null>(null)
   const [loading, setLoading] = useState(false)
   const [error, setError] = useState<string | null>(null)
   const [connectionState, setConnectionState] = useState<string>('disconnected')
-  const { user } = useAuthStore()
+  const { user, token } = useAuthStore()
 
   const webrtcService = new WebRTCService()
 
   // If we have a session ID from the launch page, set up the WebSocket URL
   useEffect(() => {
     if (launchedSessionId && !websocketUrl) {
-      // For launched applications, we use the WebSocket URL directly
-      // The WebRTC offer is already handled by the backend
-      setWebsocketUrl(`ws://localhost:8080/ws?session=${launchedSessionId}`)
+      // Signaling must reach the backend instance that runs the session
+      const watchParams = watchMode && token ? `&watch=true&token=${encodeURIComponent(token)}` : ''
+      authFetch(`http://localhost:8080/api/sessions/${launchedSessionId}/signaling`)
+        .then(async (response) => {
+          if (!response.ok) throw new Error(await response.text())
+          const { websocket_url } = await response.json()
+          setWebsocketUrl(`${websocket_url}${watchParams}`)
+        })
+        .catch((err) => {
+          console.error('Failed to locate session:', err)
+          setError(err instanceof Error ? err.message : 'Session not found')
+        })
     }
-  }, [launchedSessionId, websocketUrl])
+  }, [launchedSessionId, websocketUrl, watchMode, token])
 
   const handleStartSession = async () => {
     if (!user?.id) {
@@ -121,6 +133,7 @@ export const VideoSessionPage: React.FC = () => {
         {websocketUrl ? (
           <VideoPlayer
             websocketUrl={websocketUrl}
+            readOnly={watchMode}
             onConnectionStateChange={(state) => setConnectionState(state)}
             onError={(err) => setError(err)}
           />
@@ -0,0 +1,17 @@
+#![no_main]
+//! What the backend does with every line an app writes to the IPC socket.
+
+use libfuzzer_sys::fuzz_target;
+use shared::wire::{self, MAX_IPC_MESSAGE_BYTES};
+use shared::AppMessage;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(line) = std::str::from_utf8(data) else {
+        return;
+    };
+    if let Ok(message) = wire::decode::<AppMessage>(line, MAX_IPC_MESSAGE_BYTES) {
+        // Whatever was accepted must survive being relayed
+        let json = serde_json::to_string(&message).expect("accepted message serializes");
+        wire::decode::<AppMessage>(&json, MAX_IPC_MESSAGE_BYTES).expect("relayed message decodes");
+    }
+});
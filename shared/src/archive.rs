@@ -0,0 +1,336 @@
+//! Archive creation and safe extraction, shared by the platform's file API and the apps.
+
+use std::fs;
+use std::io::{self, Read, Seek, Write};
+use std::path::{Component, Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ArchiveFormat {
+    Zip,
+    Tar,
+}
+
+impl ArchiveFormat {
+    /// Detect the format from a file name's extension.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "zip" => Some(ArchiveFormat::Zip),
+            "tar" => Some(ArchiveFormat::Tar),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ArchiveFormat::Zip => "zip",
+            ArchiveFormat::Tar => "tar",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ArchiveFormat::Zip => "application/zip",
+            ArchiveFormat::Tar => "application/x-tar",
+        }
+    }
+}
+
+/// Bounds on what extracting one untrusted archive may write.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExtractLimits {
+    pub max_entries: usize,
+    /// Uncompressed bytes, counted as they are written rather than trusted from headers
+    pub max_total_bytes: u64,
+}
+
+impl Default for ExtractLimits {
+    fn default() -> Self {
+        Self {
+            max_entries: 10_000,
+            max_total_bytes: 4 * 1024 * 1024 * 1024,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExtractSummary {
+    pub entries: usize,
+    pub bytes: u64,
+}
+
+/// Write `sources` into an archive on `writer`, handing the writer back once the archive is
+/// complete. The writer only needs [`Write`], so the archive can be streamed while it is built.
+/// Entries are named relative to each source's parent, and symbolic links are skipped.
+pub fn create<W: Write>(format: ArchiveFormat, writer: W, sources: &[PathBuf]) -> io::Result<W> {
+    let writer = match format {
+        ArchiveFormat::Zip => {
+            let mut zip = zip::ZipWriter::new_stream(writer);
+            let options = zip::write::SimpleFileOptions::default()
+                .compression_method(zip::CompressionMethod::Deflated)
+                .large_file(true);
+            for source in sources {
+                walk(source, &mut |path, name, is_dir| {
+                    if is_dir {
+                        zip.add_directory(format!("{name}/"), options).map_err(io::Error::other)
+                    } else {
+                        zip.start_file(name, options).map_err(io::Error::other)?;
+                        io::copy(&mut fs::File::open(path)?, &mut zip).map(|_| ())
+                    }
+                })?;
+            }
+            zip.finish().map_err(io::Error::other)?.into_inner()
+        }
+        ArchiveFormat::Tar => {
+            let mut tar = tar::Builder::new(writer);
+            tar.follow_symlinks(false);
+            for source in sources {
+                walk(source, &mut |path, name, is_dir| {
+                    if is_dir {
+                        tar.append_dir(name, path)
+                    } else {
+                        tar.append_path_with_name(path, name)
+                    }
+                })?;
+            }
+            tar.into_inner()?
+        }
+    };
+    Ok(writer)
+}
+
+/// Like [`create`] with [`ArchiveFormat::Zip`], but every file entry is AES-256 encrypted with
+/// `password`. Folder entries stay readable so the tree can be browsed before unlocking.
+pub fn create_encrypted_zip<W: Write + Seek>(writer: W, sources: &[PathBuf], password: &str) -> io::Result<W> {
+    let mut zip = zip::ZipWriter::new(writer);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated)
+        .large_file(true);
+    let encrypted = options.with_aes_encryption(zip::AesMode::Aes256, password);
+    for source in sources {
+        walk(source, &mut |path, name, is_dir| {
+            if is_dir {
+                zip.add_directory(format!("{name}/"), options).map_err(io::Error::other)
+            } else {
+                zip.start_file(name, encrypted).map_err(io::Error::other)?;
+                io::copy(&mut fs::File::open(path)?, &mut zip).map(|_| ())
+            }
+        })?;
+    }
+    zip.finish().map_err(io::Error::other)
+}
+
+fn walk(source: &Path, visit: &mut impl FnMut(&Path, &str, bool) -> io::Result<()>) -> io::Result<()> {
+    let base = source.parent().unwrap_or(Path::new(""));
+    walk_from(base, source, visit)
+}
+
+fn walk_from(base: &Path, path: &Path, visit: &mut impl FnMut(&Path, &str, bool) -> io::Result<()>) -> io::Result<()> {
+    let meta = fs::symlink_metadata(path)?;
+    if meta.file_type().is_symlink() {
+        return Ok(());
+    }
+    let name = path
+        .strip_prefix(base)
+        .map_err(io::Error::other)?
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+    if !meta.is_dir() {
+        return visit(path, &name, false);
+    }
+    visit(path, &name, true)?;
+    let mut children = fs::read_dir(path)?.map(|e| e.map(|e| e.path())).collect::<io::Result<Vec<_>>>()?;
+    children.sort();
+    for child in children {
+        walk_from(base, &child, visit)?;
+    }
+    Ok(())
+}
+
+/// Unpack `archive` into `destination`, which must not exist yet. Nothing is ever written
+/// outside it: entries with absolute paths or `..` fail the extraction, links and special
+/// files are skipped, and `limits` are enforced. On failure the destination is removed.
+pub fn extract(archive: &Path, destination: &Path, limits: ExtractLimits) -> io::Result<ExtractSummary> {
+    let format = ArchiveFormat::from_path(archive)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Unsupported archive format"))?;
+    if fs::symlink_metadata(destination).is_ok() {
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, "Destination already exists"));
+    }
+    fs::create_dir_all(destination)?;
+    let result = match format {
+        ArchiveFormat::Zip => extract_zip(archive, destination, limits),
+        ArchiveFormat::Tar => extract_tar(archive, destination, limits),
+    };
+    if result.is_err() {
+        let _ = fs::remove_dir_all(destination);
+    }
+    result
+}
+
+fn extract_zip(archive: &Path, destination: &Path, limits: ExtractLimits) -> io::Result<ExtractSummary> {
+    let mut zip = zip::ZipArchive::new(fs::File::open(archive)?).map_err(io::Error::other)?;
+    if zip.len() > limits.max_entries {
+        return Err(limit_exceeded("too many entries"));
+    }
+    let mut summary = ExtractSummary::default();
+    for index in 0..zip.len() {
+        let mut entry = zip.by_index(index).map_err(io::Error::other)?;
+        let target = safe_join(destination, entry.name())?;
+        summary.entries += 1;
+        if entry.is_symlink() {
+            continue;
+        }
+        if entry.is_dir() {
+            fs::create_dir_all(&target)?;
+        } else {
+            write_limited(&mut entry, &target, &mut summary, limits)?;
+        }
+    }
+    Ok(summary)
+}
+
+fn extract_tar(archive: &Path, destination: &Path, limits: ExtractLimits) -> io::Result<ExtractSummary> {
+    let mut tar = tar::Archive::new(fs::File::open(archive)?);
+    let mut summary = ExtractSummary::default();
+    for entry in tar.entries()? {
+        let mut entry = entry?;
+        summary.entries += 1;
+        if summary.entries > limits.max_entries {
+            return Err(limit_exceeded("too many entries"));
+        }
+        let name = entry.path()?.to_string_lossy().into_owned();
+        let target = safe_join(destination, &name)?;
+        match entry.header().entry_type() {
+            tar::EntryType::Directory => fs::create_dir_all(&target)?,
+            tar::EntryType::Regular | tar::EntryType::Continuous => {
+                write_limited(&mut entry, &target, &mut summary, limits)?
+            }
+            _ => {}
+        }
+    }
+    Ok(summary)
+}
+
+/// Resolve an entry name below `destination`, rejecting names that could escape it.
+fn safe_join(destination: &Path, name: &str) -> io::Result<PathBuf> {
+    let mut path = destination.to_path_buf();
+    for component in Path::new(name).components() {
+        match component {
+            Component::Normal(part) => path.push(part),
+            Component::CurDir => {}
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("Unsafe path in archive: {name}"),
+                ))
+            }
+        }
+    }
+    Ok(path)
+}
+
+fn write_limited(reader: &mut impl Read, target: &Path, summary: &mut ExtractSummary, limits: ExtractLimits) -> io::Result<()> {
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = fs::OpenOptions::new().write(true).create_new(true).open(target)?;
+    let remaining = limits.max_total_bytes.saturating_sub(summary.bytes);
+    let written = io::copy(&mut reader.take(remaining.saturating_add(1)), &mut file)?;
+    if written > remaining {
+        return Err(limit_exceeded("archive is too large"));
+    }
+    summary.bytes += written;
+    Ok(())
+}
+
+fn limit_exceeded(what: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("Extraction limit reached: {what}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("shared-archive-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn sample_tree(root: &Path) -> PathBuf {
+        let photos = root.join("photos");
+        fs::create_dir_all(photos.join("2024")).unwrap();
+        fs::write(photos.join("readme.txt"), b"hello").unwrap();
+        fs::write(photos.join("2024").join("beach.jpg"), vec![7u8; 4096]).unwrap();
+        photos
+    }
+
+    #[test]
+    fn test_round_trips_through_both_formats() {
+        for format in [ArchiveFormat::Zip, ArchiveFormat::Tar] {
+            let root = scratch_dir(format.extension());
+            let photos = sample_tree(&root);
+            let archive = root.join(format!("photos.{}", format.extension()));
+            create(format, fs::File::create(&archive).unwrap(), &[photos]).unwrap();
+
+            let out = root.join("out");
+            let summary = extract(&archive, &out, ExtractLimits::default()).unwrap();
+            assert_eq!(summary.bytes, 5 + 4096);
+            assert_eq!(fs::read(out.join("photos/readme.txt")).unwrap(), b"hello");
+            assert_eq!(fs::read(out.join("photos/2024/beach.jpg")).unwrap().len(), 4096);
+            let _ = fs::remove_dir_all(&root);
+        }
+    }
+
+    #[test]
+    fn test_limits_abort_and_clean_up() {
+        let root = scratch_dir("limits");
+        let photos = sample_tree(&root);
+        let archive = root.join("photos.zip");
+        create(ArchiveFormat::Zip, fs::File::create(&archive).unwrap(), &[photos]).unwrap();
+
+        let out = root.join("out");
+        let small = ExtractLimits { max_entries: 100, max_total_bytes: 1024 };
+        assert!(extract(&archive, &out, small).is_err());
+        assert!(!out.exists());
+
+        let few = ExtractLimits { max_entries: 2, max_total_bytes: u64::MAX };
+        assert!(extract(&archive, &out, few).is_err());
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_encrypted_zip_needs_the_password() {
+        let root = scratch_dir("encrypted");
+        let photos = sample_tree(&root);
+        let archive = root.join("photos.zip");
+        create_encrypted_zip(fs::File::create(&archive).unwrap(), &[photos], "s3cret").unwrap();
+
+        let mut zip = zip::ZipArchive::new(fs::File::open(&archive).unwrap()).unwrap();
+        let index = zip.index_for_name("photos/readme.txt").unwrap();
+        assert!(zip.by_index_decrypt(index, b"wrong").is_err());
+        let mut contents = Vec::new();
+        zip.by_index_decrypt(index, b"s3cret").unwrap().read_to_end(&mut contents).unwrap();
+        assert_eq!(contents, b"hello");
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_safe_join_rejects_escaping_names() {
+        let dest = Path::new("/vault/out");
+        assert_eq!(safe_join(dest, "a/./b.txt").unwrap(), dest.join("a/b.txt"));
+        assert!(safe_join(dest, "../evil.sh").is_err());
+        assert!(safe_join(dest, "a/../../evil.sh").is_err());
+        assert!(safe_join(dest, "/etc/passwd").is_err());
+    }
+
+    #[test]
+    fn test_format_from_extension() {
+        assert_eq!(ArchiveFormat::from_path(Path::new("a/b.ZIP")), Some(ArchiveFormat::Zip));
+        assert_eq!(ArchiveFormat::from_path(Path::new("b.tar")), Some(ArchiveFormat::Tar));
+        assert_eq!(ArchiveFormat::from_path(Path::new("b.txt")), None);
+    }
+}
@@ -0,0 +1,151 @@
+//! Blocking IPC client used by sandboxed apps to talk to the platform.
+
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::os::unix::net::UnixStream;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use crate::i18n::Locale;
+use crate::protocol::{default_keyboard_layout, default_scale_factor, default_timezone, AppMessage, PlatformMessage, Theme};
+use crate::wire::{self, MAX_IPC_MESSAGE_BYTES};
+
+/// How long an app waits for the platform's `Init` reply before using defaults
+const INIT_TIMEOUT: Duration = Duration::from_secs(2);
+
+pub struct IpcClient {
+    writer: UnixStream,
+    reader: BufReader<UnixStream>,
+}
+
+impl IpcClient {
+    pub fn connect(socket_path: &str) -> Result<Self> {
+        let writer = UnixStream::connect(socket_path)
+            .with_context(|| format!("Failed to connect to IPC socket {}", socket_path))?;
+        let reader = BufReader::new(writer.try_clone()?);
+        Ok(Self { writer, reader })
+    }
+
+    /// Connect using `IPC_SOCKET_PATH`, `SANDBOX_SESSION_ID` and `SANDBOX_IPC_TOKEN`, send
+    /// `Hello`, and wait for the platform's `Init` reply.
+    pub fn connect_from_env() -> Result<(Self, SessionInit)> {
+        let socket_path = std::env::var("IPC_SOCKET_PATH").context("IPC_SOCKET_PATH not set")?;
+        let session_id = std::env::var("SANDBOX_SESSION_ID").context("SANDBOX_SESSION_ID not set")?;
+        let token = std::env::var("SANDBOX_IPC_TOKEN").context("SANDBOX_IPC_TOKEN not set")?;
+
+        let mut client = Self::connect(&socket_path)?;
+        client.send(&AppMessage::Hello { session_id, token })?;
+
+        client.writer.set_read_timeout(Some(INIT_TIMEOUT))?;
+        let init = match client.recv()? {
+            PlatformMessage::Init {
+                locale,
+                theme,
+                keyboard_layout,
+                timezone,
+                scale_factor,
+                view_only,
+                saved_state,
+                allowed_paths,
+            } => SessionInit {
+                locale,
+                theme,
+                keyboard_layout,
+                timezone,
+                scale_factor,
+                view_only,
+                saved_state,
+                allowed_paths,
+            },
+            other => anyhow::bail!("Expected init message, got {:?}", other),
+        };
+        client.writer.set_read_timeout(None)?;
+
+        Ok((client, init))
+    }
+
+    /// A second handle on the connection, for writers outside the UI loop such as the panic hook.
+    pub(crate) fn try_clone_stream(&self) -> Result<UnixStream> {
+        Ok(self.writer.try_clone()?)
+    }
+
+    pub fn send(&mut self, msg: &AppMessage) -> Result<()> {
+        let json = serde_json::to_string(msg)?;
+        self.writer.write_all(format!("{}\n", json).as_bytes())?;
+        Ok(())
+    }
+
+    /// Read platform messages on a background thread, so a UI loop can poll them from the
+    /// returned channel. Do not call [`IpcClient::recv`] afterwards.
+    pub fn listen(&mut self) -> Result<mpsc::Receiver<PlatformMessage>> {
+        let mut reader = std::mem::replace(&mut self.reader, BufReader::new(self.writer.try_clone()?));
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let mut line = String::new();
+            loop {
+                line.clear();
+                match read_message(&mut reader, &mut line) {
+                    Ok(0) | Err(_) => break,
+                    Ok(_) => match wire::decode(&line, MAX_IPC_MESSAGE_BYTES) {
+                        Ok(msg) => {
+                            if tx.send(msg).is_err() {
+                                break;
+                            }
+                        }
+                        Err(e) => eprintln!("Ignoring unparsable platform message: {}", e),
+                    },
+                }
+            }
+        });
+        Ok(rx)
+    }
+
+    pub fn recv(&mut self) -> Result<PlatformMessage> {
+        let mut line = String::new();
+        if read_message(&mut self.reader, &mut line)? == 0 {
+            anyhow::bail!("IPC connection closed");
+        }
+        wire::decode(&line, MAX_IPC_MESSAGE_BYTES).context("Failed to parse platform message")
+    }
+}
+
+/// Read one newline-terminated message, refusing to buffer more than the IPC limit.
+pub(crate) fn read_message<R: BufRead>(reader: &mut R, line: &mut String) -> io::Result<usize> {
+    let read = reader.by_ref().take(MAX_IPC_MESSAGE_BYTES as u64 + 1).read_line(line)?;
+    if read > MAX_IPC_MESSAGE_BYTES {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "IPC message exceeds the size limit"));
+    }
+    Ok(read)
+}
+
+/// Session context delivered by the platform's `Init` message
+#[derive(Debug, Clone)]
+pub struct SessionInit {
+    pub locale: Locale,
+    pub theme: Theme,
+    pub keyboard_layout: String,
+    /// IANA time zone, `UTC` unless the user or their browser gave one
+    pub timezone: String,
+    pub scale_factor: f32,
+    pub view_only: bool,
+    /// Values the app saved for this user in earlier sessions
+    pub saved_state: BTreeMap<String, serde_json::Value>,
+    /// Folders a client may browse; empty when the whole vault is open
+    pub allowed_paths: Vec<String>,
+}
+
+impl Default for SessionInit {
+    fn default() -> Self {
+        Self {
+            locale: Locale::default(),
+            theme: Theme::default(),
+            keyboard_layout: default_keyboard_layout(),
+            timezone: default_timezone(),
+            scale_factor: default_scale_factor(),
+            view_only: false,
+            saved_state: BTreeMap::new(),
+            allowed_paths: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,130 @@
+//! Crash reports for sandboxed apps. A panicking app otherwise leaves nothing behind but a dead
+//! process, so [`install`] sets a panic hook that sends the platform what the app was doing:
+//! the panic message and backtrace, the last input it handled, and how many frames it drew.
+
+use std::collections::VecDeque;
+use std::io::Write;
+use std::os::unix::net::UnixStream;
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+
+use crate::client::IpcClient;
+use crate::protocol::AppMessage;
+
+/// Input events kept for a crash report
+pub const INPUT_HISTORY: usize = 20;
+
+/// What an app was doing when it panicked, sent as [`AppMessage::Crash`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CrashReport {
+    pub message: String,
+    /// `file:line:column` of the panic, when known
+    #[serde(default)]
+    pub location: Option<String>,
+    #[serde(default)]
+    pub backtrace: String,
+    /// Descriptions of the last [`INPUT_HISTORY`] input events, oldest first
+    #[serde(default)]
+    pub recent_input: Vec<String>,
+    /// Frames drawn since the app started
+    #[serde(default)]
+    pub frames: u64,
+}
+
+#[derive(Debug, Default)]
+struct Activity {
+    recent_input: VecDeque<String>,
+    frames: u64,
+}
+
+/// Input and frames seen so far, shared between the UI loop and the panic hook.
+#[derive(Debug, Clone, Default)]
+pub struct CrashRecorder {
+    activity: Arc<Mutex<Activity>>,
+}
+
+impl CrashRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Remember an input event. Describe it without typed text, which may be private.
+    pub fn input(&self, description: impl Into<String>) {
+        let mut activity = self.activity.lock().unwrap_or_else(|e| e.into_inner());
+        if activity.recent_input.len() == INPUT_HISTORY {
+            activity.recent_input.pop_front();
+        }
+        activity.recent_input.push_back(description.into());
+    }
+
+    pub fn frame(&self) {
+        self.activity.lock().unwrap_or_else(|e| e.into_inner()).frames += 1;
+    }
+
+    /// A report for a panic with `message` at `location`.
+    pub fn report(&self, message: String, location: Option<String>, backtrace: String) -> CrashReport {
+        // The panic may have happened while the UI loop held the lock
+        let activity = self.activity.lock().unwrap_or_else(|e| e.into_inner());
+        CrashReport {
+            message,
+            location,
+            backtrace,
+            recent_input: activity.recent_input.iter().cloned().collect(),
+            frames: activity.frames,
+        }
+    }
+}
+
+/// Send a [`CrashReport`] over `ipc`'s connection when the app panics, then run the previous
+/// hook (which prints the panic as usual). Without a platform connection only the previous
+/// hook runs.
+pub fn install(recorder: CrashRecorder, ipc: Option<&IpcClient>) {
+    let socket: Option<UnixStream> = ipc.and_then(|ipc| ipc.try_clone_stream().ok());
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if let Some(mut socket) = socket.as_ref() {
+            let message = info.payload_as_str().unwrap_or("Box<dyn Any>").to_string();
+            let location = info.location().map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()));
+            let backtrace = std::backtrace::Backtrace::force_capture().to_string();
+            let report = recorder.report(message, location, backtrace);
+            if let Ok(json) = serde_json::to_string(&AppMessage::Crash { report }) {
+                let _ = socket.write_all(format!("{}\n", json).as_bytes());
+                let _ = socket.flush();
+            }
+        }
+        previous(info);
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recorder_keeps_recent_input() {
+        let recorder = CrashRecorder::new();
+        for i in 0..INPUT_HISTORY + 5 {
+            recorder.input(format!("key {}", i));
+        }
+        recorder.frame();
+        recorder.clone().frame();
+
+        let report = recorder.report("boom".to_string(), None, String::new());
+        assert_eq!(report.recent_input.len(), INPUT_HISTORY);
+        assert_eq!(report.recent_input[0], "key 5");
+        assert_eq!(report.recent_input.last().unwrap(), &format!("key {}", INPUT_HISTORY + 4));
+        assert_eq!(report.frames, 2);
+    }
+
+    #[test]
+    fn test_report_round_trips_as_app_message() {
+        let report = CrashRecorder::new().report("boom".to_string(), Some("src/app.rs:1:1".to_string()), "bt".to_string());
+        let json = serde_json::to_string(&AppMessage::Crash { report: report.clone() }).unwrap();
+        assert!(json.contains(r#""type":"crash""#));
+        match serde_json::from_str::<AppMessage>(&json).unwrap() {
+            AppMessage::Crash { report: parsed } => assert_eq!(parsed, report),
+            other => panic!("unexpected message {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,127 @@
+//! Frame pacing for apps drawn by Xvfb's software rasterizer, where every pass costs CPU
+//! whether or not anything on screen changed. Apps run a pass only when input or an animation
+//! asks for one, poll background work at a low idle framerate, and report how long their
+//! passes take so the platform can see which sessions are expensive to draw.
+
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+/// Passes per second while only background work (IPC, file operations) needs polling
+pub const IDLE_FPS: u32 = 2;
+
+/// How often [`FrameScheduler::report`] hands out stats
+pub const REPORT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Render times over one reporting interval, sent as [`crate::AppMessage::RenderStats`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct RenderStats {
+    /// Passes run in the interval
+    pub frames: u32,
+    /// Of those, the passes run only to poll background work
+    pub idle_frames: u32,
+    /// Mean CPU time of a pass, in milliseconds
+    pub avg_ms: f32,
+    /// Slowest pass, in milliseconds
+    pub max_ms: f32,
+    /// Length of the interval, in seconds
+    pub interval_secs: f32,
+}
+
+/// Decides when the next pass runs and accumulates [`RenderStats`].
+pub struct FrameScheduler {
+    idle_interval: Duration,
+    since: Instant,
+    frames: u32,
+    idle_frames: u32,
+    total: Duration,
+    max: Duration,
+}
+
+impl Default for FrameScheduler {
+    fn default() -> Self {
+        Self::new(IDLE_FPS)
+    }
+}
+
+impl FrameScheduler {
+    pub fn new(idle_fps: u32) -> Self {
+        Self {
+            idle_interval: Duration::from_secs(1) / idle_fps.max(1),
+            since: Instant::now(),
+            frames: 0,
+            idle_frames: 0,
+            total: Duration::ZERO,
+            max: Duration::ZERO,
+        }
+    }
+
+    /// Record a pass that took `cpu` (when the toolkit measures it). `active` when input arrived
+    /// or an animation asked for another pass, which the toolkit schedules itself; `polling`
+    /// when background work needs checking. Returns how long to wait before the next pass if
+    /// nothing else wakes the app, or `None` to sleep until input.
+    pub fn frame(&mut self, cpu: Option<Duration>, active: bool, polling: bool) -> Option<Duration> {
+        self.frames += 1;
+        if !active {
+            self.idle_frames += 1;
+        }
+        if let Some(cpu) = cpu {
+            self.total += cpu;
+            self.max = self.max.max(cpu);
+        }
+        polling.then_some(self.idle_interval)
+    }
+
+    /// Stats since the last report, once [`REPORT_INTERVAL`] has passed.
+    pub fn report(&mut self, now: Instant) -> Option<RenderStats> {
+        let interval = now.saturating_duration_since(self.since);
+        if interval < REPORT_INTERVAL {
+            return None;
+        }
+        let stats = RenderStats {
+            frames: self.frames,
+            idle_frames: self.idle_frames,
+            avg_ms: if self.frames == 0 { 0.0 } else { self.total.as_secs_f32() * 1000.0 / self.frames as f32 },
+            max_ms: self.max.as_secs_f32() * 1000.0,
+            interval_secs: interval.as_secs_f32(),
+        };
+        self.since = now;
+        self.frames = 0;
+        self.idle_frames = 0;
+        self.total = Duration::ZERO;
+        self.max = Duration::ZERO;
+        Some(stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_polling_schedules_a_pass() {
+        let mut frames = FrameScheduler::new(4);
+        assert_eq!(frames.frame(None, true, false), None);
+        assert_eq!(frames.frame(None, false, false), None);
+        assert_eq!(frames.frame(None, false, true), Some(Duration::from_millis(250)));
+    }
+
+    #[test]
+    fn test_report_averages_and_resets() {
+        let mut frames = FrameScheduler::default();
+        let start = frames.since;
+        frames.frame(Some(Duration::from_millis(10)), true, false);
+        frames.frame(Some(Duration::from_millis(30)), false, true);
+        assert_eq!(frames.report(start + Duration::from_secs(1)), None);
+
+        let stats = frames.report(start + REPORT_INTERVAL).unwrap();
+        assert_eq!((stats.frames, stats.idle_frames), (2, 1));
+        assert!((stats.avg_ms - 20.0).abs() < 0.01);
+        assert!((stats.max_ms - 30.0).abs() < 0.01);
+
+        frames.frame(None, false, false);
+        let stats = frames.report(start + REPORT_INTERVAL * 2).unwrap();
+        assert_eq!((stats.frames, stats.max_ms), (1, 0.0));
+        assert_eq!(frames.idle_interval, Duration::from_millis(500));
+    }
+}
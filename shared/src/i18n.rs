@@ -0,0 +1,196 @@
+//! Key-based string catalogs shared by the platform and SDK apps.
+//!
+//! Keys follow the dotted layout of the web frontend catalogs (`errors.invalid_token`).
+//! Lookups fall back to English, then to the key itself, so a missing translation never
+//! breaks a response.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En,
+    Fr,
+}
+
+impl Locale {
+    /// Parse a BCP 47 tag such as `fr`, `fr-FR` or `en_US`; unknown languages yield `None`.
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let lang = tag
+            .trim()
+            .split(['-', '_'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match lang.as_str() {
+            "en" => Some(Locale::En),
+            "fr" => Some(Locale::Fr),
+            _ => None,
+        }
+    }
+
+    /// Pick the best supported locale from an `Accept-Language` header value.
+    pub fn negotiate(accept_language: &str) -> Self {
+        let mut candidates: Vec<(f32, &str)> = accept_language
+            .split(',')
+            .filter_map(|part| {
+                let mut fields = part.split(';');
+                let tag = fields.next()?.trim();
+                let q = fields
+                    .find_map(|f| f.trim().strip_prefix("q="))
+                    .and_then(|q| q.parse::<f32>().ok())
+                    .unwrap_or(1.0);
+                Some((q, tag))
+            })
+            .collect();
+        candidates.sort_by(|a, b| b.0.total_cmp(&a.0));
+        candidates
+            .into_iter()
+            .find_map(|(_, tag)| Locale::from_tag(tag))
+            .unwrap_or_default()
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Fr => "fr",
+        }
+    }
+
+    fn catalog(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Locale::En => EN,
+            Locale::Fr => FR,
+        }
+    }
+}
+
+/// Look up `key` in the catalog for `locale`.
+pub fn tr(locale: Locale, key: &str) -> &str {
+    lookup(locale, key)
+        .or_else(|| lookup(Locale::En, key))
+        .unwrap_or(key)
+}
+
+fn lookup(locale: Locale, key: &str) -> Option<&'static str> {
+    locale
+        .catalog()
+        .iter()
+        .find(|(k, _)| *k == key)
+        .map(|(_, v)| *v)
+}
+
+const EN: &[(&str, &str)] = &[
+    ("errors.missing_token", "Missing or invalid Authorization header"),
+    ("errors.invalid_token", "Invalid token"),
+    ("errors.invalid_token_user", "Invalid user id in token"),
+    ("errors.session_revoked", "This device was signed out"),
+    ("errors.token_expired", "This access token has expired"),
+    ("errors.token_scope", "This access token does not allow this request"),
+    ("errors.no_active_permissions", "No active permissions for this client"),
+    ("errors.codec_unavailable", "Codec {codec} is unavailable on this server"),
+    ("errors.maintenance", "The server is under maintenance, please try again later"),
+    ("errors.app_unavailable", "This application is not available to you"),
+    ("explorer.title", "File Explorer"),
+    ("explorer.search", "Search:"),
+    ("explorer.path", "Path:"),
+    ("explorer.up", "↑ Up"),
+    ("explorer.access_denied", "Access denied"),
+    ("explorer.read_error", "Error reading"),
+    ("explorer.selected", "Selected"),
+    ("explorer.directory", "directory"),
+    ("explorer.file", "file"),
+    ("explorer.bytes", "bytes"),
+    ("explorer.modified", "modified"),
+    ("explorer.details", "Details"),
+    ("explorer.column_name", "Name"),
+    ("explorer.column_size", "Size"),
+    ("explorer.column_modified", "Modified"),
+    ("explorer.column_type", "Type"),
+    ("explorer.column_owner", "Owner"),
+    ("explorer.owner_session", "You"),
+    ("explorer.download", "Download"),
+    ("explorer.download_failed", "Download failed"),
+    ("explorer.compress", "Compress to zip"),
+    ("explorer.extract", "Extract here"),
+    ("explorer.archive_working", "Working on archive…"),
+    ("explorer.listing", "Listing folder…"),
+    ("explorer.updated", "Updated"),
+    ("explorer.archive_failed", "Archive operation failed"),
+    ("explorer.view_only", "View only: downloads and uploads are disabled"),
+    ("email.invitation_code.subject", "Your invitation verification code"),
+    ("email.invitation_code.body", "Enter this code to accept your invitation: {code}\nIt expires in 10 minutes. If you did not request it, ignore this email."),
+    ("email.permission_expiring.subject", "Shared access is about to expire"),
+    ("email.permission_expiring.body", "Access to \"{path}\" expires on {expires_at}. Ask the person who shared it to renew it."),
+    ("email.client_permission_expiring.subject", "A client's access is about to expire"),
+    ("email.client_permission_expiring.body", "The access of {client} to \"{path}\" expires on {expires_at}. Renew it from the permissions page to keep it."),
+];
+
+const FR: &[(&str, &str)] = &[
+    ("errors.missing_token", "En-tête Authorization manquant ou invalide"),
+    ("errors.invalid_token", "Jeton invalide"),
+    ("errors.invalid_token_user", "Identifiant utilisateur invalide dans le jeton"),
+    ("errors.session_revoked", "Cet appareil a été déconnecté"),
+    ("errors.token_expired", "Ce jeton d'accès a expiré"),
+    ("errors.token_scope", "Ce jeton d'accès ne permet pas cette requête"),
+    ("errors.no_active_permissions", "Aucune permission active pour ce client"),
+    ("errors.codec_unavailable", "Le codec {codec} n'est pas disponible sur ce serveur"),
+    ("errors.maintenance", "Le serveur est en maintenance, veuillez réessayer plus tard"),
+    ("errors.app_unavailable", "Cette application ne vous est pas proposée"),
+    ("explorer.title", "Explorateur de fichiers"),
+    ("explorer.search", "Rechercher :"),
+    ("explorer.path", "Chemin :"),
+    ("explorer.up", "↑ Parent"),
+    ("explorer.access_denied", "Accès refusé"),
+    ("explorer.read_error", "Erreur de lecture"),
+    ("explorer.selected", "Sélection"),
+    ("explorer.directory", "dossier"),
+    ("explorer.file", "fichier"),
+    ("explorer.bytes", "octets"),
+    ("explorer.modified", "modifié le"),
+    ("explorer.details", "Détails"),
+    ("explorer.column_name", "Nom"),
+    ("explorer.column_size", "Taille"),
+    ("explorer.column_modified", "Modifié"),
+    ("explorer.column_type", "Type"),
+    ("explorer.column_owner", "Propriétaire"),
+    ("explorer.owner_session", "Vous"),
+    ("explorer.download", "Télécharger"),
+    ("explorer.download_failed", "Échec du téléchargement"),
+    ("explorer.compress", "Compresser en zip"),
+    ("explorer.extract", "Extraire ici"),
+    ("explorer.archive_working", "Archive en cours…"),
+    ("explorer.listing", "Lecture du dossier…"),
+    ("explorer.updated", "Mis à jour"),
+    ("explorer.archive_failed", "Échec de l'opération sur l'archive"),
+    ("explorer.view_only", "Consultation seule : téléchargements et envois désactivés"),
+    ("email.invitation_code.subject", "Votre code de vérification d'invitation"),
+    ("email.invitation_code.body", "Saisissez ce code pour accepter votre invitation : {code}\nIl expire dans 10 minutes. Si vous ne l'avez pas demandé, ignorez cet e-mail."),
+    ("email.permission_expiring.subject", "Un accès partagé expire bientôt"),
+    ("email.permission_expiring.body", "L'accès à « {path} » expire le {expires_at}. Demandez à la personne qui l'a partagé de le renouveler."),
+    ("email.client_permission_expiring.subject", "L'accès d'un client expire bientôt"),
+    ("email.client_permission_expiring.body", "L'accès de {client} à « {path} » expire le {expires_at}. Renouvelez-le depuis la page des permissions pour le conserver."),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_negotiate_prefers_highest_quality_supported_tag() {
+        assert_eq!(Locale::negotiate("de-DE,fr;q=0.8,en;q=0.5"), Locale::Fr);
+        assert_eq!(Locale::negotiate("en-GB;q=0.3,fr-CA;q=0.9"), Locale::Fr);
+        assert_eq!(Locale::negotiate("de"), Locale::En);
+        assert_eq!(Locale::negotiate(""), Locale::En);
+    }
+
+    #[test]
+    fn test_catalogs_have_the_same_keys() {
+        for (key, _) in EN {
+            assert!(lookup(Locale::Fr, key).is_some(), "missing fr translation for {key}");
+        }
+        assert_eq!(EN.len(), FR.len());
+        assert_eq!(tr(Locale::Fr, "unknown.key"), "unknown.key");
+    }
+}
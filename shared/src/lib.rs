@@ -1,3 +1,20 @@
+pub mod archive;
+pub mod client;
+pub mod crash;
+pub mod frame;
+pub mod i18n;
+pub mod listing;
+pub mod platform_file;
 pub mod protocol;
+pub mod transfer;
+pub mod watch;
+pub mod wire;
 
-pub use protocol::{AppMessage, LogLevel, PlatformMessage};
+pub use archive::ArchiveFormat;
+pub use client::{IpcClient, SessionInit};
+pub use crash::{CrashRecorder, CrashReport};
+pub use frame::{FrameScheduler, RenderStats};
+pub use i18n::Locale;
+pub use platform_file::PlatformFile;
+pub use protocol::{AccessibilityEvent, AccessibilityEventKind, AppMessage, ListedEntry, LogLevel, PlatformMessage, Theme, TransferInfo};
+pub use watch::DirectoryWatcher;
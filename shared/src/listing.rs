@@ -0,0 +1,246 @@
+//! Directory listings with the details file browsers show in columns, and their sort orders.
+
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::ffi::OsString;
+use std::fs;
+use std::io;
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// One entry of a listed directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileEntry {
+    pub name: String,
+    pub path: PathBuf,
+    pub is_dir: bool,
+    pub size: u64,
+    pub modified: Option<SystemTime>,
+    /// Guessed from the extension; `inode/directory` for directories
+    pub mime_type: &'static str,
+    pub owner: FileOwner,
+}
+
+/// Who owns an entry on disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FileOwner {
+    /// The user the app runs as, which owns everything written through the vault
+    Session,
+    /// Any other account, by uid, such as files restored from elsewhere
+    Other(u32),
+}
+
+/// Column a listing is sorted by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortKey {
+    #[default]
+    Name,
+    Size,
+    Modified,
+    Type,
+    Owner,
+}
+
+/// Most entries one page of a listing may hold
+pub const MAX_PAGE_ENTRIES: usize = 1000;
+
+/// Where the next page of a listing starts: just past the entry with this name and kind.
+/// Pages follow [`list_directory`]'s order, so a cursor stays valid while entries come and go.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ListingCursor {
+    pub is_dir: bool,
+    pub name: String,
+}
+
+/// One entry's place in name order: directories first, then case-insensitively by name, the
+/// exact name breaking ties.
+fn name_order(is_dir: bool, name: &str) -> (bool, String, &str) {
+    (!is_dir, name.to_lowercase(), name)
+}
+
+/// A name read from a directory, before its details are
+struct Listed {
+    is_dir: bool,
+    name: String,
+    file_name: OsString,
+}
+
+/// A folder's entries in name order, directories first, read a page at a time. Opening reads
+/// the names alone, which stays quick for tens of thousands of entries; each page then reads
+/// the details of just its own entries.
+pub struct DirectoryPages {
+    dir: PathBuf,
+    names: Vec<Listed>,
+    next: usize,
+    session_uid: Option<u32>,
+}
+
+impl DirectoryPages {
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let mut names: Vec<Listed> = fs::read_dir(path)?
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let file_type = entry.file_type().ok()?;
+                let file_name = entry.file_name();
+                Some(Listed { is_dir: file_type.is_dir(), name: file_name.to_string_lossy().into_owned(), file_name })
+            })
+            .collect();
+        names.sort_by_cached_key(|listed| (!listed.is_dir, listed.name.to_lowercase(), listed.name.clone()));
+        Ok(Self {
+            dir: path.to_path_buf(),
+            names,
+            next: 0,
+            // Listings run inside the sandbox, where /proc/self is the one account file readable
+            session_uid: fs::metadata("/proc/self").map(|m| m.uid()).ok(),
+        })
+    }
+
+    /// Entries in the folder when it was opened
+    pub fn total(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Continue just past `cursor`, wherever the entry it names went.
+    pub fn seek(&mut self, cursor: &ListingCursor) {
+        let after = name_order(cursor.is_dir, &cursor.name);
+        self.next = self.names.partition_point(|listed| name_order(listed.is_dir, &listed.name) <= after);
+    }
+
+    /// The next `limit` entries with their details, skipping those that vanished since the
+    /// folder was opened. Empty once every entry was read.
+    pub fn next_page(&mut self, limit: usize) -> Vec<FileEntry> {
+        let end = self.names.len().min(self.next.saturating_add(limit));
+        let page = self.names[self.next..end].iter().filter_map(|listed| self.describe(listed)).collect();
+        self.next = end;
+        page
+    }
+
+    /// Where the page after the last one read starts, `None` once every entry was read.
+    pub fn cursor(&self) -> Option<ListingCursor> {
+        let last = self.names.get(self.next.checked_sub(1)?)?;
+        (self.next < self.names.len()).then(|| ListingCursor { is_dir: last.is_dir, name: last.name.clone() })
+    }
+
+    fn describe(&self, listed: &Listed) -> Option<FileEntry> {
+        let path = self.dir.join(&listed.file_name);
+        let metadata = fs::symlink_metadata(&path).ok()?;
+        let is_dir = metadata.is_dir();
+        Some(FileEntry {
+            name: listed.name.clone(),
+            mime_type: if is_dir { "inode/directory" } else { mime_type(&path) },
+            path,
+            is_dir,
+            size: if is_dir { 0 } else { metadata.len() },
+            modified: metadata.modified().ok(),
+            owner: if Some(metadata.uid()) == self.session_uid { FileOwner::Session } else { FileOwner::Other(metadata.uid()) },
+        })
+    }
+}
+
+/// List `path`, skipping entries that vanish while being read, sorted by name.
+pub fn list_directory(path: &Path) -> io::Result<Vec<FileEntry>> {
+    let mut pages = DirectoryPages::open(path)?;
+    Ok(pages.next_page(usize::MAX))
+}
+
+/// One page of at most `limit` entries of `path` in [`list_directory`]'s order, starting past
+/// `after`, with the cursor of the next page and the folder's entry count.
+pub fn list_directory_page(
+    path: &Path,
+    after: Option<&ListingCursor>,
+    limit: usize,
+) -> io::Result<(Vec<FileEntry>, Option<ListingCursor>, usize)> {
+    let mut pages = DirectoryPages::open(path)?;
+    if let Some(after) = after {
+        pages.seek(after);
+    }
+    let entries = pages.next_page(limit.clamp(1, MAX_PAGE_ENTRIES));
+    Ok((entries, pages.cursor(), pages.total()))
+}
+
+/// Sort with directories first whatever the key, then by `key`, then by name.
+pub fn sort(entries: &mut [FileEntry], key: SortKey, ascending: bool) {
+    entries.sort_by(|a, b| {
+        let by_key = match key {
+            SortKey::Name => Ordering::Equal,
+            SortKey::Size => a.size.cmp(&b.size),
+            SortKey::Modified => a.modified.cmp(&b.modified),
+            SortKey::Type => a.mime_type.cmp(b.mime_type),
+            SortKey::Owner => a.owner.cmp(&b.owner),
+        };
+        let order = by_key.then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+        b.is_dir.cmp(&a.is_dir).then(if ascending { order } else { order.reverse() })
+    });
+}
+
+/// Media type for a file name's extension; `application/octet-stream` when unknown.
+pub fn mime_type(path: &Path) -> &'static str {
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or_default().to_ascii_lowercase();
+    match extension.as_str() {
+        "txt" | "log" => "text/plain",
+        "md" => "text/markdown",
+        "csv" => "text/csv",
+        "html" | "htm" => "text/html",
+        "json" => "application/json",
+        "pdf" => "application/pdf",
+        "zip" => "application/zip",
+        "tar" => "application/x-tar",
+        "gz" => "application/gzip",
+        "jpg" | "jpeg" => "image/jpeg",
+        "png" => "image/png",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "heic" => "image/heic",
+        "svg" => "image/svg+xml",
+        "mp3" => "audio/mpeg",
+        "flac" => "audio/flac",
+        "wav" => "audio/wav",
+        "ogg" => "audio/ogg",
+        "mp4" => "video/mp4",
+        "mov" => "video/quicktime",
+        "mkv" => "video/x-matroska",
+        "webm" => "video/webm",
+        "doc" => "application/msword",
+        "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        "xlsx" => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        "odt" => "application/vnd.oasis.opendocument.text",
+        _ => "application/octet-stream",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lists_details_and_sorts_directories_first() {
+        let dir = std::env::temp_dir().join(format!("listing-test-{}", std::process::id()));
+        fs::create_dir_all(dir.join("Photos")).unwrap();
+        fs::write(dir.join("b.txt"), b"hello").unwrap();
+        fs::write(dir.join("A.JPG"), b"jpeg bytes").unwrap();
+
+        let mut entries = list_directory(&dir).unwrap();
+        let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["Photos", "A.JPG", "b.txt"]);
+        assert_eq!(entries[1].mime_type, "image/jpeg");
+        assert_eq!(entries[0].mime_type, "inode/directory");
+        assert!(entries.iter().all(|e| e.modified.is_some()));
+
+        sort(&mut entries, SortKey::Size, true);
+        let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["Photos", "b.txt", "A.JPG"]);
+        sort(&mut entries, SortKey::Name, false);
+        assert_eq!(entries[1].name, "b.txt");
+
+        let (first, next, total) = list_directory_page(&dir, None, 2).unwrap();
+        assert_eq!((first.len(), total), (2, 3));
+        assert_eq!(next, Some(ListingCursor { is_dir: false, name: "A.JPG".to_string() }));
+        fs::write(dir.join("a0.txt"), b"added meanwhile").unwrap();
+        let (rest, next, _) = list_directory_page(&dir, next.as_ref(), 2).unwrap();
+        let names: Vec<&str> = rest.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["a0.txt", "b.txt"]);
+        assert_eq!(next, None);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
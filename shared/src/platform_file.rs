@@ -0,0 +1,206 @@
+//! Random access to vault files from inside the sandbox. A [`PlatformFile`] reads through the
+//! platform in ranges, over a connection of its own, so a media player can seek in a large file
+//! without loading it. The platform checks every range against the session's permissions.
+
+use std::io::{self, BufReader, Read, Seek, SeekFrom, Write};
+use std::os::unix::net::UnixStream;
+
+use anyhow::Context;
+
+use crate::client::read_message;
+use crate::protocol::{AppMessage, PlatformMessage};
+use crate::wire::{self, MAX_IPC_MESSAGE_BYTES};
+
+/// Largest range one [`AppMessage::ReadFile`] may ask for; stays well under the IPC message
+/// limit once base64-encoded.
+pub const MAX_READ_BYTES: u64 = 1024 * 1024;
+
+/// Bytes fetched at least per request, so small sequential reads are mostly served from what
+/// the previous request brought back.
+pub const READ_AHEAD_BYTES: u64 = 256 * 1024;
+
+/// A vault file opened through the platform, with [`Read`] and [`Seek`] like a local file.
+/// Reads block until the platform answers, so keep them off the UI loop.
+pub struct PlatformFile {
+    writer: UnixStream,
+    reader: BufReader<UnixStream>,
+    path: String,
+    size: u64,
+    position: u64,
+    next_request: u64,
+    /// Bytes read ahead, starting at `cache_offset`
+    cache: Vec<u8>,
+    cache_offset: u64,
+}
+
+impl PlatformFile {
+    /// Open `path`, relative to `ROOT_PATH`, using `IPC_SOCKET_PATH`, `SANDBOX_SESSION_ID` and
+    /// `SANDBOX_IPC_TOKEN`.
+    pub fn open(path: &str) -> anyhow::Result<Self> {
+        let socket_path = std::env::var("IPC_SOCKET_PATH").context("IPC_SOCKET_PATH not set")?;
+        let session_id = std::env::var("SANDBOX_SESSION_ID").context("SANDBOX_SESSION_ID not set")?;
+        let token = std::env::var("SANDBOX_IPC_TOKEN").context("SANDBOX_IPC_TOKEN not set")?;
+        Self::open_with(&socket_path, &session_id, &token, path)
+    }
+
+    pub fn open_with(socket_path: &str, session_id: &str, token: &str, path: &str) -> anyhow::Result<Self> {
+        let mut stream = UnixStream::connect(socket_path)
+            .with_context(|| format!("Failed to connect to IPC socket {}", socket_path))?;
+        send(&mut stream, &AppMessage::OpenReader { session_id: session_id.to_string(), token: token.to_string() })?;
+        Ok(Self::start(stream, path)?)
+    }
+
+    /// Ask for the size first, which also fails early when the file may not be read.
+    fn start(stream: UnixStream, path: &str) -> io::Result<Self> {
+        let reader = BufReader::new(stream.try_clone()?);
+        let mut file = Self {
+            writer: stream,
+            reader,
+            path: path.to_string(),
+            size: 0,
+            position: 0,
+            next_request: 0,
+            cache: Vec::new(),
+            cache_offset: 0,
+        };
+        file.fetch(0, 0)?;
+        Ok(file)
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Size of the file as of the last answer from the platform
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    fn fetch(&mut self, offset: u64, length: u64) -> io::Result<Vec<u8>> {
+        self.next_request += 1;
+        let request_id = self.next_request;
+        let read = AppMessage::ReadFile { request_id, path: self.path.clone(), offset, length };
+        send(&mut self.writer, &read)?;
+
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if read_message(&mut self.reader, &mut line)? == 0 {
+                return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "IPC connection closed"));
+            }
+            let message = wire::decode::<PlatformMessage>(&line, MAX_IPC_MESSAGE_BYTES)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            match message {
+                // Answers to earlier requests that were given up on are skipped
+                PlatformMessage::FileData { request_id: id, size, data, .. } if id == request_id => {
+                    self.size = size;
+                    return Ok(data);
+                }
+                PlatformMessage::FileReadFailed { request_id: id, reason, code } if id == request_id => {
+                    let kind = if code.is_some() { io::ErrorKind::PermissionDenied } else { io::ErrorKind::Other };
+                    return Err(io::Error::new(kind, reason));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn is_cached(&self, position: u64) -> bool {
+        position >= self.cache_offset && position < self.cache_offset + self.cache.len() as u64
+    }
+}
+
+impl Read for PlatformFile {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() || self.position >= self.size {
+            return Ok(0);
+        }
+        if !self.is_cached(self.position) {
+            let length = (buf.len() as u64).clamp(READ_AHEAD_BYTES, MAX_READ_BYTES);
+            self.cache = self.fetch(self.position, length)?;
+            self.cache_offset = self.position;
+            // The file shrank since its size was last reported
+            if self.cache.is_empty() {
+                return Ok(0);
+            }
+        }
+        let start = (self.position - self.cache_offset) as usize;
+        let count = buf.len().min(self.cache.len() - start);
+        buf[..count].copy_from_slice(&self.cache[start..start + count]);
+        self.position += count as u64;
+        Ok(count)
+    }
+}
+
+impl Seek for PlatformFile {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let target = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(delta) => self.size.checked_add_signed(delta),
+            SeekFrom::Current(delta) => self.position.checked_add_signed(delta),
+        };
+        self.position = target
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Seek to a negative or overflowing position"))?;
+        Ok(self.position)
+    }
+}
+
+fn send(stream: &mut UnixStream, message: &AppMessage) -> io::Result<()> {
+    let json = serde_json::to_string(message)?;
+    stream.write_all(format!("{}\n", json).as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::BufRead;
+
+    /// Answer reads from `contents` like the platform would, counting the requests.
+    fn serve(stream: UnixStream, contents: Vec<u8>) -> std::thread::JoinHandle<usize> {
+        std::thread::spawn(move || {
+            let mut writer = stream.try_clone().unwrap();
+            let mut requests = 0;
+            for line in BufReader::new(stream).lines() {
+                let Ok(AppMessage::ReadFile { request_id, offset, length, .. }) = serde_json::from_str(&line.unwrap()) else {
+                    continue;
+                };
+                requests += 1;
+                let start = (offset as usize).min(contents.len());
+                let end = (start + length as usize).min(contents.len());
+                let reply = PlatformMessage::FileData {
+                    request_id,
+                    offset,
+                    size: contents.len() as u64,
+                    data: contents[start..end].to_vec(),
+                };
+                writer.write_all(format!("{}\n", serde_json::to_string(&reply).unwrap()).as_bytes()).unwrap();
+            }
+            requests
+        })
+    }
+
+    #[test]
+    fn test_reads_ahead_and_seeks() {
+        let contents: Vec<u8> = (0..600 * 1024).map(|i| (i % 251) as u8).collect();
+        let (app, platform) = UnixStream::pair().unwrap();
+        let server = serve(platform, contents.clone());
+
+        let mut file = PlatformFile::start(app, "videos/clip.mp4").unwrap();
+        assert_eq!(file.size(), contents.len() as u64);
+        let mut head = [0u8; 4096];
+        for _ in 0..10 {
+            file.read_exact(&mut head).unwrap();
+        }
+        assert_eq!(&head[..], &contents[9 * 4096..10 * 4096]);
+
+        file.seek(SeekFrom::End(-100)).unwrap();
+        let mut tail = Vec::new();
+        file.read_to_end(&mut tail).unwrap();
+        assert_eq!(tail, &contents[contents.len() - 100..]);
+        assert!(file.seek(SeekFrom::Current(-(contents.len() as i64) - 1)).is_err());
+        drop(file);
+
+        // The size, one read-ahead for the ten small reads, one for the tail
+        assert_eq!(server.join().unwrap(), 3);
+    }
+}
@@ -1,9 +1,44 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::crash::CrashReport;
+use crate::frame::RenderStats;
+use crate::i18n::Locale;
+use crate::listing::{ListingCursor, MAX_PAGE_ENTRIES};
+use crate::platform_file::MAX_READ_BYTES;
+use crate::transfer::CHUNK_SIZE;
+use crate::wire::{check_text, Validate};
 
 /// Messages sent from platform to app
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "kebab-case")]
 pub enum PlatformMessage {
+    /// Session context, sent in reply to [`AppMessage::Hello`]
+    Init {
+        /// User's locale (see [`crate::i18n::Locale`])
+        locale: Locale,
+        #[serde(default)]
+        theme: Theme,
+        /// XKB layout name, e.g. `us` or `fr`
+        #[serde(default = "default_keyboard_layout")]
+        keyboard_layout: String,
+        /// IANA time zone of the user, e.g. `Europe/Paris`; native apps also get it as `TZ`
+        #[serde(default = "default_timezone")]
+        timezone: String,
+        /// Browser device pixel ratio; the display is sized in device pixels
+        #[serde(default = "default_scale_factor")]
+        scale_factor: f32,
+        /// The session may only view files: apps must not offer download or upload
+        #[serde(default)]
+        view_only: bool,
+        /// What the app saved with [`AppMessage::SaveState`] for this user, to restore from
+        #[serde(default)]
+        saved_state: BTreeMap<String, serde_json::Value>,
+        /// Folders a client's session may browse, as paths inside the sandbox; empty for
+        /// owners, who browse the whole vault. Native apps also get them as `ALLOWED_PATHS`.
+        #[serde(default)]
+        allowed_paths: Vec<String>,
+    },
     /// Upload a file to the app
     UploadFile {
         filename: String,
@@ -12,6 +47,13 @@ pub enum PlatformMessage {
     },
     /// Request app to send file data for download
     RequestDownload,
+    /// Continue a chunked download from `offset`, the first byte the browser is missing.
+    /// The app restarts from zero if the file no longer matches `etag`.
+    ResumeDownload {
+        path: String,
+        offset: u64,
+        etag: String,
+    },
     /// Delete selected file/directory
     Delete,
     /// Custom command with arbitrary data
@@ -20,12 +62,81 @@ pub enum PlatformMessage {
         #[serde(default)]
         params: serde_json::Value,
     },
+    /// The user suspends the session: reply with [`AppMessage::SuspendState`] holding what
+    /// the app needs to continue later. The app is stopped afterwards.
+    Suspend,
+    /// The session continues a suspended one; sent right after `Init` with the state the app
+    /// saved in [`AppMessage::SuspendState`]
+    Resume {
+        #[serde(with = "base64_serde")]
+        state: Vec<u8>,
+    },
+    /// Reply to [`AppMessage::LoadState`]; `value` is null when nothing is saved under `key`
+    StateLoaded {
+        key: String,
+        value: serde_json::Value,
+    },
+    /// An [`AppMessage::SaveState`] was not stored, e.g. because it exceeds the size limits
+    StateRejected {
+        key: String,
+        reason: String,
+    },
+    /// Reply to [`AppMessage::ReadFile`]: the bytes from `offset`, fewer than asked for at the
+    /// end of the file, and the file's current size
+    FileData {
+        request_id: u64,
+        offset: u64,
+        size: u64,
+        #[serde(with = "base64_serde")]
+        data: Vec<u8>,
+    },
+    /// An [`AppMessage::ReadFile`] was refused or failed. `code` is the permission denial's code
+    /// when the session may not read the file.
+    FileReadFailed {
+        request_id: u64,
+        reason: String,
+        #[serde(default)]
+        code: Option<String>,
+    },
+    /// Reply to [`AppMessage::FolderSize`]: bytes under the folder, `None` when it may not be
+    /// read or could not be measured
+    FolderSize {
+        request_id: u64,
+        path: String,
+        size: Option<u64>,
+    },
+    /// Reply to [`AppMessage::ListFolder`]: the page's entries, the cursor of the next page,
+    /// `None` after the last one, and how many entries the folder holds
+    FolderPage {
+        request_id: u64,
+        path: String,
+        entries: Vec<ListedEntry>,
+        next: Option<ListingCursor>,
+        total: u64,
+    },
+    /// An [`AppMessage::ListFolder`] was refused or failed
+    FolderListFailed {
+        request_id: u64,
+        reason: String,
+    },
+    /// The folders a client's session may browse changed mid-session, e.g. a permission was
+    /// revoked. Replaces the `allowed_paths` of `Init`; the app leaves a folder no longer
+    /// among them.
+    ScopeChanged { allowed_paths: Vec<String> },
 }
 
 /// Messages sent from app to platform
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "kebab-case")]
 pub enum AppMessage {
+    /// First message on a new connection, identifying the app's session
+    Hello {
+        /// Value of the `SANDBOX_SESSION_ID` environment variable
+        session_id: String,
+        /// Value of the `SANDBOX_IPC_TOKEN` environment variable, proving the connection comes
+        /// from the app launched for that session
+        token: String,
+    },
     /// App state update
     State {
         /// Current path/location in the app
@@ -44,6 +155,13 @@ pub enum AppMessage {
         #[serde(with = "base64_serde")]
         data: Vec<u8>,
     },
+    /// Part of a file, starting at `offset`; chunks of one transfer arrive in order
+    DownloadChunk {
+        transfer: TransferInfo,
+        offset: u64,
+        #[serde(with = "base64_serde")]
+        data: Vec<u8>,
+    },
     /// Operation completed successfully
     Success {
         operation: String,
@@ -61,6 +179,221 @@ pub enum AppMessage {
         level: LogLevel,
         message: String,
     },
+    /// Semantic UI events for screen readers in the browser
+    Accessibility { events: Vec<AccessibilityEvent> },
+    /// The app drew its first usable screen. Only awaited for apps whose manifest sets
+    /// `ready_signal`; the others are ready with their first video frame.
+    Ready,
+    /// Reply to [`PlatformMessage::Suspend`]: opaque state handed back in
+    /// [`PlatformMessage::Resume`] when the session resumes
+    SuspendState {
+        #[serde(with = "base64_serde")]
+        state: Vec<u8>,
+    },
+    /// Remember `value` under `key` for this user and app, across sessions. A null value
+    /// forgets the key. Saved entries come back in the next `Init`.
+    SaveState {
+        key: String,
+        value: serde_json::Value,
+    },
+    /// Ask for the value saved under `key`, answered with [`PlatformMessage::StateLoaded`]
+    LoadState { key: String },
+    /// How long the app's recent frames took to draw, every [`crate::frame::REPORT_INTERVAL`]
+    RenderStats { stats: RenderStats },
+    /// The app panicked; sent by the hook from [`crate::crash::install`] just before it exits
+    Crash { report: CrashReport },
+    /// First message on an extra connection that only carries [`AppMessage::ReadFile`]s, so
+    /// reads can block a decoder thread without going through the UI loop
+    OpenReader { session_id: String, token: String },
+    /// Read up to `length` bytes of the vault file at `path`, relative to `ROOT_PATH`, from
+    /// `offset`. A zero `length` only asks for the size. Answered with
+    /// [`PlatformMessage::FileData`] or [`PlatformMessage::FileReadFailed`].
+    ReadFile {
+        request_id: u64,
+        path: String,
+        offset: u64,
+        length: u64,
+    },
+    /// Ask for the total size of the vault folder at `path`, relative to `ROOT_PATH`. Measuring
+    /// a large folder takes a while, so other messages keep flowing until the platform answers
+    /// with [`PlatformMessage::FolderSize`].
+    FolderSize { request_id: u64, path: String },
+    /// List the vault folder at `path`, relative to `ROOT_PATH`, a page of at most `limit`
+    /// entries at a time in name order, directories first. `after` is the `next` cursor of the
+    /// previous [`PlatformMessage::FolderPage`], left out for the first page.
+    ListFolder {
+        request_id: u64,
+        path: String,
+        #[serde(default)]
+        after: Option<ListingCursor>,
+        limit: u32,
+    },
+}
+
+fn check_paths(allowed_paths: &[String]) -> Result<(), String> {
+    allowed_paths.iter().try_for_each(|path| check_text("allowed_paths", path, 4096))
+}
+
+impl Validate for PlatformMessage {
+    fn validate(&self) -> Result<(), String> {
+        match self {
+            PlatformMessage::Init { keyboard_layout, timezone, scale_factor, allowed_paths, .. } => {
+                check_text("keyboard_layout", keyboard_layout, 64)?;
+                check_text("timezone", timezone, 64)?;
+                if !scale_factor.is_finite() || *scale_factor <= 0.0 || *scale_factor > 16.0 {
+                    return Err(format!("scale_factor {} is out of range", scale_factor));
+                }
+                check_paths(allowed_paths)
+            }
+            PlatformMessage::ResumeDownload { etag, .. } => check_text("etag", etag, 128),
+            PlatformMessage::FileData { data, .. } if data.len() as u64 > MAX_READ_BYTES => {
+                Err(format!("File data of {} bytes exceeds {}", data.len(), MAX_READ_BYTES))
+            }
+            PlatformMessage::Command { command, .. } => check_text("command", command, 256),
+            PlatformMessage::FolderPage { entries, .. } if entries.len() > MAX_PAGE_ENTRIES => {
+                Err(format!("{} entries exceed the page limit of {}", entries.len(), MAX_PAGE_ENTRIES))
+            }
+            PlatformMessage::ScopeChanged { allowed_paths } => check_paths(allowed_paths),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Validate for AppMessage {
+    fn validate(&self) -> Result<(), String> {
+        match self {
+            AppMessage::Hello { session_id, token } | AppMessage::OpenReader { session_id, token } => {
+                check_text("session_id", session_id, 128)?;
+                check_text("token", token, 128)?;
+                if session_id.is_empty() {
+                    return Err("session_id is empty".to_string());
+                }
+                Ok(())
+            }
+            AppMessage::State { actions, .. } if actions.len() > 256 => {
+                Err(format!("{} actions exceed the limit of 256", actions.len()))
+            }
+            AppMessage::DownloadChunk { transfer, offset, data } => {
+                check_text("etag", &transfer.etag, 128)?;
+                if data.len() as u64 > CHUNK_SIZE {
+                    return Err(format!("Chunk of {} bytes exceeds {}", data.len(), CHUNK_SIZE));
+                }
+                match offset.checked_add(data.len() as u64) {
+                    Some(end) if end <= transfer.size => Ok(()),
+                    _ => Err(format!("Chunk at {} runs past the {} byte file", offset, transfer.size)),
+                }
+            }
+            AppMessage::FolderSize { path, .. } => check_text("path", path, 4096),
+            AppMessage::ListFolder { path, after, limit, .. } => {
+                check_text("path", path, 4096)?;
+                // Names on disk may hold any character but the separator, so only the length is checked
+                if after.as_ref().is_some_and(|after| after.name.len() > 4096) {
+                    return Err("after exceeds 4096 bytes".to_string());
+                }
+                if *limit == 0 || *limit as usize > MAX_PAGE_ENTRIES {
+                    return Err(format!("Page of {} entries is not within 1 to {}", limit, MAX_PAGE_ENTRIES));
+                }
+                Ok(())
+            }
+            AppMessage::ReadFile { path, length, .. } => {
+                check_text("path", path, 4096)?;
+                if *length > MAX_READ_BYTES {
+                    return Err(format!("Read of {} bytes exceeds {}", length, MAX_READ_BYTES));
+                }
+                Ok(())
+            }
+            AppMessage::Accessibility { events } if events.len() > 256 => {
+                Err(format!("{} accessibility events exceed the limit of 256", events.len()))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A file sent in [`AppMessage::DownloadChunk`]s
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransferInfo {
+    /// Path relative to the app's root, echoed back in [`PlatformMessage::ResumeDownload`]
+    pub path: String,
+    pub filename: String,
+    pub size: u64,
+    /// Version of the file the chunks were read from (see [`crate::transfer::etag`])
+    pub etag: String,
+}
+
+/// One entry of a [`PlatformMessage::FolderPage`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ListedEntry {
+    pub name: String,
+    pub is_dir: bool,
+    /// Zero for folders
+    pub size: u64,
+    /// Seconds since the Unix epoch
+    #[serde(default)]
+    pub modified: Option<i64>,
+    pub mime_type: String,
+}
+
+/// A widget interaction worth announcing (mirrors egui's output events)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AccessibilityEvent {
+    pub kind: AccessibilityEventKind,
+    /// Widget role, e.g. `button`, `text-edit`, `selectable-label`
+    pub role: String,
+    #[serde(default)]
+    pub label: Option<String>,
+    /// Current text or selection state, if the widget has one
+    #[serde(default)]
+    pub value: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum AccessibilityEventKind {
+    Focus,
+    Click,
+    ValueChanged,
+}
+
+/// UI theme requested by the user
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+impl Theme {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Theme::System => "system",
+            Theme::Light => "light",
+            Theme::Dark => "dark",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "system" => Some(Theme::System),
+            "light" => Some(Theme::Light),
+            "dark" => Some(Theme::Dark),
+            _ => None,
+        }
+    }
+}
+
+pub fn default_keyboard_layout() -> String {
+    "us".to_string()
+}
+
+pub fn default_timezone() -> String {
+    "UTC".to_string()
+}
+
+pub fn default_scale_factor() -> f32 {
+    1.0
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
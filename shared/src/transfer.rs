@@ -0,0 +1,131 @@
+//! Resumable file transfers. Files leave an app in chunks tagged with the file's version, so an
+//! interrupted transfer can continue from the last byte the browser received.
+
+use std::fs;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+use crate::protocol::{AppMessage, TransferInfo};
+
+/// Raw bytes per chunk; stays under 64 KiB per data-channel message once base64-encoded.
+pub const CHUNK_SIZE: u64 = 32 * 1024;
+
+/// Strong validator for a file's content, derived from its size and modification time.
+/// Doubles as the HTTP `ETag` of vault downloads.
+pub fn etag(meta: &fs::Metadata) -> String {
+    let modified = meta
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    format!("\"{:x}-{:x}\"", meta.len(), modified)
+}
+
+/// A file read as [`AppMessage::DownloadChunk`] messages.
+pub struct Chunks {
+    file: fs::File,
+    info: TransferInfo,
+    offset: u64,
+    done: bool,
+}
+
+impl Chunks {
+    /// Start sending `path`, a file under `root`, at `offset`. The transfer names it relative
+    /// to `root`, so the browser never learns where the vault is mounted. If `etag` no longer
+    /// matches, the file changed since the transfer began and it restarts from zero under the
+    /// new tag.
+    pub fn open(root: &Path, path: &Path, offset: u64, etag: Option<&str>) -> io::Result<Self> {
+        let relative = path
+            .strip_prefix(root)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "File outside the root"))?;
+        let mut file = fs::File::open(path)?;
+        let meta = file.metadata()?;
+        if !meta.is_file() {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "Not a regular file"));
+        }
+        let current = self::etag(&meta);
+        let offset = if etag == Some(current.as_str()) { offset.min(meta.len()) } else { 0 };
+        file.seek(SeekFrom::Start(offset))?;
+        let info = TransferInfo {
+            path: relative.to_string_lossy().into_owned(),
+            filename: path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default(),
+            size: meta.len(),
+            etag: current,
+        };
+        Ok(Self { file, info, offset, done: false })
+    }
+
+    pub fn info(&self) -> &TransferInfo {
+        &self.info
+    }
+}
+
+impl Iterator for Chunks {
+    type Item = io::Result<AppMessage>;
+
+    /// An empty file still yields one empty chunk, so the receiver learns it is complete.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let want = CHUNK_SIZE.min(self.info.size - self.offset);
+        let mut data = Vec::with_capacity(want as usize);
+        if let Err(e) = (&mut self.file).take(want).read_to_end(&mut data) {
+            self.done = true;
+            return Some(Err(e));
+        }
+        if (data.len() as u64) < want {
+            self.done = true;
+            return Some(Err(io::Error::new(io::ErrorKind::UnexpectedEof, "File shrank during transfer")));
+        }
+        let offset = self.offset;
+        self.offset += want;
+        self.done = self.offset >= self.info.size;
+        Some(Ok(AppMessage::DownloadChunk { transfer: self.info.clone(), offset, data }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_file(name: &str, len: usize) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("shared-transfer-{}-{}", name, std::process::id()));
+        fs::write(&path, vec![3u8; len]).unwrap();
+        path
+    }
+
+    fn offsets(chunks: Chunks) -> Vec<(u64, usize)> {
+        chunks
+            .map(|c| match c.unwrap() {
+                AppMessage::DownloadChunk { offset, data, .. } => (offset, data.len()),
+                other => panic!("unexpected {:?}", other),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_resumes_only_the_same_file_version() {
+        let path = scratch_file("resume", CHUNK_SIZE as usize + 10);
+        let root = std::env::temp_dir();
+        let opened = Chunks::open(&root, &path, 0, None).unwrap();
+        assert_eq!(opened.info().path, path.file_name().unwrap().to_string_lossy());
+        let etag = opened.info().etag.clone();
+
+        let resumed = Chunks::open(&root, &path, CHUNK_SIZE, Some(&etag)).unwrap();
+        assert_eq!(offsets(resumed), vec![(CHUNK_SIZE, 10)]);
+
+        let restarted = Chunks::open(&root, &path, CHUNK_SIZE, Some("\"stale\"")).unwrap();
+        assert_eq!(offsets(restarted), vec![(0, CHUNK_SIZE as usize), (CHUNK_SIZE, 10)]);
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_empty_file_sends_one_chunk() {
+        let path = scratch_file("empty", 0);
+        assert_eq!(offsets(Chunks::open(&std::env::temp_dir(), &path, 0, None).unwrap()), vec![(0, 0)]);
+        let _ = fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,110 @@
+//! Change notifications for the directory an app is showing, from inotify. Apps poll
+//! [`DirectoryWatcher::changed`] with the rest of their background work, so nothing blocks
+//! and no thread is needed.
+
+use std::ffi::CString;
+use std::io;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
+
+/// Changes to a directory's entries that alter its listing, and the directory itself going away
+const EVENTS: u32 = libc::IN_CREATE
+    | libc::IN_DELETE
+    | libc::IN_MOVED_FROM
+    | libc::IN_MOVED_TO
+    | libc::IN_CLOSE_WRITE
+    | libc::IN_DELETE_SELF
+    | libc::IN_MOVE_SELF
+    | libc::IN_ONLYDIR;
+
+/// Fixed part of each event read; the entry's name, padded, follows it
+const EVENT_HEADER: usize = std::mem::size_of::<libc::inotify_event>();
+
+/// Watches one directory at a time for entries being added, removed, renamed or written.
+pub struct DirectoryWatcher {
+    fd: OwnedFd,
+    watch: Option<i32>,
+}
+
+impl DirectoryWatcher {
+    pub fn new() -> io::Result<Self> {
+        let fd = unsafe { libc::inotify_init1(libc::IN_NONBLOCK | libc::IN_CLOEXEC) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Self { fd: unsafe { OwnedFd::from_raw_fd(fd) }, watch: None })
+    }
+
+    /// Watch `dir` instead of the directory watched so far.
+    pub fn watch(&mut self, dir: &Path) -> io::Result<()> {
+        if let Some(wd) = self.watch.take() {
+            // Fails only if the directory is already gone, which removed the watch anyway
+            unsafe { libc::inotify_rm_watch(self.fd.as_raw_fd(), wd) };
+        }
+        let path = CString::new(dir.as_os_str().as_bytes()).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        let wd = unsafe { libc::inotify_add_watch(self.fd.as_raw_fd(), path.as_ptr(), EVENTS) };
+        if wd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        self.watch = Some(wd);
+        Ok(())
+    }
+
+    /// Whether the watched directory changed since the last call. Events left over from
+    /// directories watched before are dropped. A queue overflow counts as a change, as
+    /// anything may have happened.
+    pub fn changed(&mut self) -> bool {
+        let mut buf = [0u8; 4096];
+        let mut changed = false;
+        loop {
+            let read = unsafe { libc::read(self.fd.as_raw_fd(), buf.as_mut_ptr().cast(), buf.len()) };
+            // Nothing more queued (EAGAIN), or the descriptor failed: either way, stop here
+            if read <= 0 {
+                return changed;
+            }
+            let read = read as usize;
+            let mut offset = 0;
+            while offset + EVENT_HEADER <= read {
+                let field = |at: usize| <[u8; 4]>::try_from(&buf[offset + at..offset + at + 4]).unwrap_or_default();
+                let wd = i32::from_ne_bytes(field(0));
+                let mask = u32::from_ne_bytes(field(4));
+                let name_len = u32::from_ne_bytes(field(12)) as usize;
+                if self.watch == Some(wd) || mask & libc::IN_Q_OVERFLOW != 0 {
+                    changed = true;
+                }
+                offset += EVENT_HEADER + name_len;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_reports_changes_to_the_watched_directory_only() {
+        let root = std::env::temp_dir().join(format!("watch-test-{}", std::process::id()));
+        let (first, second) = (root.join("first"), root.join("second"));
+        fs::create_dir_all(&first).unwrap();
+        fs::create_dir_all(&second).unwrap();
+
+        let mut watcher = DirectoryWatcher::new().unwrap();
+        watcher.watch(&first).unwrap();
+        assert!(!watcher.changed());
+        fs::write(first.join("upload.bin"), b"bytes").unwrap();
+        assert!(watcher.changed());
+        assert!(!watcher.changed());
+
+        watcher.watch(&second).unwrap();
+        fs::remove_file(first.join("upload.bin")).unwrap();
+        assert!(!watcher.changed());
+        fs::rename(root.join("first"), second.join("moved")).unwrap();
+        assert!(watcher.changed());
+
+        fs::remove_dir_all(&root).unwrap();
+        assert!(watcher.watch(&first).is_err());
+    }
+}
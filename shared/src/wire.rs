@@ -0,0 +1,129 @@
+//! Guarded decoding of JSON messages from peers that may be hostile: apps on the IPC socket,
+//! browsers on the signaling socket. Size and nesting are checked before anything is parsed,
+//! and the decoded message must pass its own [`Validate`] checks.
+
+use serde::de::DeserializeOwned;
+use std::fmt;
+
+/// Largest IPC line, in bytes. Downloads travel in [`crate::transfer::CHUNK_SIZE`] chunks, so
+/// only a whole-file `DownloadData` or a saved state comes close.
+pub const MAX_IPC_MESSAGE_BYTES: usize = 16 * 1024 * 1024;
+
+/// Deepest nesting of arrays and objects accepted in a message. The protocols themselves stay
+/// under 5; the rest is headroom for app-defined values such as saved state.
+pub const MAX_NESTING: usize = 32;
+
+/// Checks on a decoded message that its types alone cannot express.
+pub trait Validate {
+    fn validate(&self) -> Result<(), String>;
+}
+
+#[derive(Debug)]
+pub enum DecodeError {
+    TooLarge { bytes: usize, limit: usize },
+    TooDeep { limit: usize },
+    Malformed(serde_json::Error),
+    Invalid(String),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::TooLarge { bytes, limit } => write!(f, "Message of {} bytes exceeds the {} byte limit", bytes, limit),
+            DecodeError::TooDeep { limit } => write!(f, "Message nests deeper than {} levels", limit),
+            DecodeError::Malformed(e) => write!(f, "Malformed message: {}", e),
+            DecodeError::Invalid(reason) => write!(f, "Invalid message: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Decode one message of at most `max_bytes`, rejecting it before parsing if it is too large
+/// or nests too deeply. Never panics, whatever `input` holds.
+pub fn decode<T: DeserializeOwned + Validate>(input: &str, max_bytes: usize) -> Result<T, DecodeError> {
+    if input.len() > max_bytes {
+        return Err(DecodeError::TooLarge { bytes: input.len(), limit: max_bytes });
+    }
+    if nesting_depth(input) > MAX_NESTING {
+        return Err(DecodeError::TooDeep { limit: MAX_NESTING });
+    }
+    let message: T = serde_json::from_str(input).map_err(DecodeError::Malformed)?;
+    message.validate().map_err(DecodeError::Invalid)?;
+    Ok(message)
+}
+
+/// Deepest nesting of arrays and objects in `input`, ignoring brackets inside strings.
+/// Works on malformed JSON too, so it can run before the parser.
+pub fn nesting_depth(input: &str) -> usize {
+    let (mut depth, mut deepest) = (0usize, 0usize);
+    let (mut in_string, mut escaped) = (false, false);
+    for byte in input.bytes() {
+        if in_string {
+            match byte {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match byte {
+            b'"' => in_string = true,
+            b'[' | b'{' => {
+                depth += 1;
+                deepest = deepest.max(depth);
+            }
+            b']' | b'}' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    deepest
+}
+
+/// `value` is at most `max` bytes and free of control characters, for identifiers that end
+/// up in logs and map keys.
+pub fn check_text(field: &str, value: &str, max: usize) -> Result<(), String> {
+    if value.len() > max {
+        return Err(format!("{} exceeds {} bytes", field, max));
+    }
+    if value.chars().any(char::is_control) {
+        return Err(format!("{} contains control characters", field));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::{AppMessage, PlatformMessage};
+
+    #[test]
+    fn test_nesting_depth() {
+        assert_eq!(nesting_depth(r#"{"type":"ready"}"#), 1);
+        assert_eq!(nesting_depth(r#"{"a":[{"b":"[[[{{"}]}"#), 3);
+        assert_eq!(nesting_depth(r#"{"a":"\"[[["}"#), 1);
+        assert_eq!(nesting_depth("]]]}}"), 0);
+        assert_eq!(nesting_depth(&"[".repeat(10_000)), 10_000);
+    }
+
+    #[test]
+    fn test_decode_rejects_hostile_input() {
+        let deep = format!(r#"{{"type":"save-state","key":"k","value":{}0{}}}"#, "[".repeat(200), "]".repeat(200));
+        assert!(matches!(decode::<AppMessage>(&deep, MAX_IPC_MESSAGE_BYTES), Err(DecodeError::TooDeep { .. })));
+        assert!(matches!(
+            decode::<AppMessage>(r#"{"type":"ready"}"#, 8),
+            Err(DecodeError::TooLarge { .. })
+        ));
+        for input in ["", "null", "{", r#"{"type":"nope"}"#, r#"{"type":"hello"}"#, "\u{0}\u{ffff}"] {
+            assert!(matches!(decode::<AppMessage>(input, MAX_IPC_MESSAGE_BYTES), Err(DecodeError::Malformed(_))));
+        }
+        let hello = format!(r#"{{"type":"hello","session_id":"{}","token":"t0ken"}}"#, "a".repeat(500));
+        assert!(matches!(decode::<AppMessage>(&hello, MAX_IPC_MESSAGE_BYTES), Err(DecodeError::Invalid(_))));
+
+        let init = r#"{"type":"init","locale":"en","scale_factor":-1}"#;
+        assert!(matches!(decode::<PlatformMessage>(init, MAX_IPC_MESSAGE_BYTES), Err(DecodeError::Invalid(_))));
+        let init = r#"{"type":"init","locale":"en","scale_factor":2}"#;
+        assert!(decode::<PlatformMessage>(init, MAX_IPC_MESSAGE_BYTES).is_ok());
+    }
+}